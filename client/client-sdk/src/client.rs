@@ -0,0 +1,249 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::retry::RetryPolicy;
+use anyhow::{bail, ensure, format_err, Result};
+use libra_crypto::HashValue;
+use libra_json_rpc_client::{
+    views::{
+        AccountStateWithProofView, AccountView, BlockMetadata, EventView, StateProofView,
+        TransactionView,
+    },
+    JsonRpcBatch, JsonRpcClient, JsonRpcResponse, ResponseAsView,
+};
+use libra_types::{
+    account_address::AccountAddress,
+    account_state::AccountState,
+    account_state_blob::{AccountStateBlob, AccountStateWithProof},
+    epoch_change::EpochChangeProof,
+    event::EventKey,
+    ledger_info::LedgerInfoWithSignatures,
+    proof::{AccountStateProof, TransactionInfoWithProof},
+    transaction::SignedTransaction,
+    trusted_state::{TrustedState, TrustedStateChange},
+    waypoint::Waypoint,
+};
+use reqwest::Url;
+use std::convert::TryFrom;
+
+/// A client of the JSON-RPC API of a full node.
+///
+/// The client starts trusting the ledger at a waypoint, and only moves its trusted state forward
+/// after verifying the epoch changes and the ledger info signatures returned by the node.
+pub struct Client {
+    client: JsonRpcClient,
+    trusted_state: TrustedState,
+    retry_policy: RetryPolicy,
+}
+
+impl Client {
+    pub fn new(url: Url, waypoint: Waypoint) -> Result<Self> {
+        Ok(Self {
+            client: JsonRpcClient::new(url)?,
+            trusted_state: TrustedState::from(waypoint),
+            retry_policy: RetryPolicy::default(),
+        })
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// The latest state of the ledger verified by this client.
+    pub fn trusted_state(&self) -> &TrustedState {
+        &self.trusted_state
+    }
+
+    /// Submits a signed transaction to the mempool of the node.
+    pub fn submit(&self, transaction: SignedTransaction) -> Result<()> {
+        let mut batch = JsonRpcBatch::default();
+        batch.add_submit_request(transaction)?;
+        match self.execute_single(batch)? {
+            JsonRpcResponse::SubmissionResponse => Ok(()),
+            response => bail!(
+                "did not receive expected submission response: {:?}",
+                response
+            ),
+        }
+    }
+
+    /// Fetches the latest ledger info from the node and ratchets the trusted state to it.
+    pub fn sync_trusted_state(&mut self) -> Result<LedgerInfoWithSignatures> {
+        let mut batch = JsonRpcBatch::default();
+        batch.add_get_state_proof_request(self.trusted_state.latest_version());
+        let state_proof = StateProofView::from_response(self.execute_single(batch)?)?;
+        self.verify_state_proof(state_proof)
+    }
+
+    /// Fetches the latest state of `address`, verified against the ledger info fetched in the same
+    /// batch. The trusted state is ratcheted to this ledger info.
+    pub fn get_account_state(&mut self, address: AccountAddress) -> Result<AccountStateWithProof> {
+        let mut batch = JsonRpcBatch::default();
+        batch.add_get_state_proof_request(self.trusted_state.latest_version());
+        batch.add_get_account_state_with_proof_request(address, None, None);
+        let mut responses = self.execute(batch)?.into_iter();
+
+        let state_proof = StateProofView::from_response(next_response(&mut responses)?)?;
+        let account_state_with_proof = account_state_with_proof_from_view(
+            AccountStateWithProofView::from_response(next_response(&mut responses)?)?,
+        )?;
+
+        let li = self.verify_state_proof(state_proof)?;
+        let version = li.ledger_info().version();
+        account_state_with_proof.verify(li.ledger_info(), version, address)?;
+        Ok(account_state_with_proof)
+    }
+
+    /// Fetches the nonce to use in the next admin transaction sent by `address`, based on its
+    /// verified `SlidingNonce` resource.
+    pub fn get_next_sliding_nonce(&mut self, address: AccountAddress) -> Result<u64> {
+        let blob = self
+            .get_account_state(address)?
+            .blob
+            .ok_or_else(|| format_err!("account {} does not exist", address))?;
+        let sliding_nonce = AccountState::try_from(&blob)?
+            .get_sliding_nonce_resource()?
+            .ok_or_else(|| format_err!("account {} has no sliding nonce", address))?;
+        Ok(sliding_nonce.next_nonce())
+    }
+
+    /// Fetches the account view of `address`. The response is not verified.
+    pub fn get_account(&self, address: AccountAddress) -> Result<Option<AccountView>> {
+        let mut batch = JsonRpcBatch::default();
+        batch.add_get_account_state_request(address);
+        AccountView::optional_from_response(self.execute_single(batch)?)
+    }
+
+    /// Fetches the committed transaction with hash `hash`. The response is not verified.
+    pub fn get_transaction_by_hash(
+        &self,
+        hash: HashValue,
+        include_events: bool,
+    ) -> Result<Option<TransactionView>> {
+        let mut batch = JsonRpcBatch::default();
+        batch.add_get_transaction_by_hash_request(hash.to_hex(), include_events);
+        TransactionView::optional_from_response(self.execute_single(batch)?)
+    }
+
+    /// Fetches up to `limit` events of the stream `event_key`, starting at sequence number `start`.
+    /// The response is not verified.
+    pub fn get_events(
+        &self,
+        event_key: &EventKey,
+        start: u64,
+        limit: u64,
+    ) -> Result<Vec<EventView>> {
+        let mut batch = JsonRpcBatch::default();
+        batch.add_get_events_request(format!("{:x}", event_key), start, limit);
+        EventView::vec_from_response(self.execute_single(batch)?)
+    }
+
+    /// Fetches the metadata of the latest block. The response is not verified.
+    pub fn get_metadata(&self) -> Result<BlockMetadata> {
+        let mut batch = JsonRpcBatch::default();
+        batch.add_get_metadata_request(None);
+        BlockMetadata::from_response(self.execute_single(batch)?)
+    }
+
+    fn verify_state_proof(
+        &mut self,
+        state_proof: StateProofView,
+    ) -> Result<LedgerInfoWithSignatures> {
+        let li: LedgerInfoWithSignatures =
+            lcs::from_bytes(&state_proof.ledger_info_with_signatures.into_bytes()?)?;
+        let epoch_change_proof: EpochChangeProof =
+            lcs::from_bytes(&state_proof.epoch_change_proof.into_bytes()?)?;
+
+        ensure!(
+            li.ledger_info().version() >= self.trusted_state.latest_version(),
+            "Got stale ledger_info with version {}, known version: {}",
+            li.ledger_info().version(),
+            self.trusted_state.latest_version(),
+        );
+
+        match self
+            .trusted_state
+            .verify_and_ratchet(&li, &epoch_change_proof)?
+        {
+            TrustedStateChange::Epoch { new_state, .. }
+            | TrustedStateChange::Version { new_state } => self.trusted_state = new_state,
+            TrustedStateChange::NoChange => (),
+        }
+        Ok(li)
+    }
+
+    fn execute(&self, batch: JsonRpcBatch) -> Result<Vec<Result<JsonRpcResponse>>> {
+        self.retry_policy
+            .retry(|| self.client.execute(batch.clone()))
+    }
+
+    fn execute_single(&self, batch: JsonRpcBatch) -> Result<JsonRpcResponse> {
+        next_response(&mut self.execute(batch)?.into_iter())
+    }
+}
+
+fn next_response(
+    responses: &mut impl Iterator<Item = Result<JsonRpcResponse>>,
+) -> Result<JsonRpcResponse> {
+    responses
+        .next()
+        .ok_or_else(|| format_err!("[JSON RPC client] response missing in batch"))?
+}
+
+fn account_state_with_proof_from_view(
+    view: AccountStateWithProofView,
+) -> Result<AccountStateWithProof> {
+    let blob = match view.blob {
+        Some(blob) => Some(lcs::from_bytes::<AccountStateBlob>(&blob.into_bytes()?)?),
+        None => None,
+    };
+    let proof = AccountStateProof::new(
+        TransactionInfoWithProof::new(
+            lcs::from_bytes(
+                &view
+                    .proof
+                    .ledger_info_to_transaction_info_proof
+                    .into_bytes()?,
+            )?,
+            lcs::from_bytes(&view.proof.transaction_info.into_bytes()?)?,
+        ),
+        lcs::from_bytes(&view.proof.transaction_info_to_account_proof.into_bytes()?)?,
+    );
+    Ok(AccountStateWithProof::new(view.version, blob, proof))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libra_types::{
+        proof::{SparseMerkleProof, TransactionAccumulatorProof},
+        transaction::TransactionInfo,
+        vm_status::StatusCode,
+    };
+
+    #[test]
+    fn test_account_state_with_proof_from_view() {
+        let proof = AccountStateProof::new(
+            TransactionInfoWithProof::new(
+                TransactionAccumulatorProof::new(vec![HashValue::random()]),
+                TransactionInfo::new(
+                    HashValue::random(),
+                    HashValue::random(),
+                    HashValue::random(),
+                    7,
+                    StatusCode::EXECUTED,
+                ),
+            ),
+            SparseMerkleProof::new(None, vec![HashValue::random()]),
+        );
+        let account_state_with_proof =
+            AccountStateWithProof::new(42, Some(AccountStateBlob::from(vec![1, 2, 3])), proof);
+
+        let view = AccountStateWithProofView::try_from(account_state_with_proof.clone()).unwrap();
+        assert_eq!(
+            account_state_with_proof_from_view(view).unwrap(),
+            account_state_with_proof
+        );
+    }
+}
@@ -0,0 +1,40 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! Client SDK for integrating with Libra through the JSON-RPC API of a full node.
+//!
+//! The [`Client`] wraps the JSON-RPC API with typed requests and responses. It keeps a
+//! [`TrustedState`], starting from a waypoint, which it ratchets forward with the state proofs
+//! returned by the node, and verifies the account states it returns against it. Requests failing
+//! to reach the node are retried following a [`RetryPolicy`]. Payments to the subaddresses of
+//! custodial accounts are requested with a [`PaymentUri`].
+//!
+//! ```no_run
+//! # use libra_client_sdk::{sign_transaction, Client};
+//! # fn example(
+//! #     url: reqwest::Url,
+//! #     waypoint: libra_types::waypoint::Waypoint,
+//! #     raw_txn: libra_types::transaction::RawTransaction,
+//! #     private_key: &libra_crypto::ed25519::Ed25519PrivateKey,
+//! # ) -> anyhow::Result<()> {
+//! let client = Client::new(url, waypoint)?;
+//! let signed_txn = sign_transaction(raw_txn, private_key)?;
+//! client.submit(signed_txn)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`TrustedState`]: libra_types::trusted_state::TrustedState
+
+mod client;
+mod payment;
+mod retry;
+mod signing;
+
+pub use client::Client;
+pub use libra_json_rpc_client::{errors, views};
+pub use payment::{peer_to_peer_script, PaymentUri, PAYMENT_URI_SCHEME};
+pub use retry::RetryPolicy;
+pub use signing::sign_transaction;
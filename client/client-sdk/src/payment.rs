@@ -0,0 +1,244 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Payments to subaddresses, for custodial wallets to route the payments they receive to their
+//! users.
+//!
+//! A payee requests a payment with a [`PaymentUri`], e.g.
+//! `libra://tlb1p7ujcndcl7nudzwt8fglhx6wxn08kgs5tm6mz4usugm707?c=LBR&am=1000000`, made of the
+//! account identifier of the payee, which carries its subaddress, then optionally the currency
+//! `c` and the amount `am`, in micro units, of the payment.
+
+use anyhow::{bail, ensure, format_err, Error, Result};
+use libra_types::{
+    account_config::{from_currency_code_string, type_tag_for_currency_code},
+    account_identifier::{AccountIdentifier, Subaddress},
+    transaction::{
+        metadata::{GeneralMetadata, GeneralMetadataV0, Metadata},
+        Script,
+    },
+};
+use std::{fmt, str::FromStr};
+use transaction_builder::encode_peer_to_peer_with_metadata_script;
+
+/// The scheme of payment URIs.
+pub const PAYMENT_URI_SCHEME: &str = "libra";
+
+const CURRENCY_PARAM: &str = "c";
+const AMOUNT_PARAM: &str = "am";
+
+/// A request for a payment to an account, and to the user of its subaddress if any.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentUri {
+    payee: AccountIdentifier,
+    currency: Option<String>,
+    amount: Option<u64>,
+}
+
+impl PaymentUri {
+    pub fn new(payee: AccountIdentifier, currency: Option<String>, amount: Option<u64>) -> Self {
+        Self {
+            payee,
+            currency,
+            amount,
+        }
+    }
+
+    pub fn payee(&self) -> &AccountIdentifier {
+        &self.payee
+    }
+
+    pub fn currency(&self) -> Option<&str> {
+        self.currency.as_deref()
+    }
+
+    pub fn amount(&self) -> Option<u64> {
+        self.amount
+    }
+
+    /// The script of the requested payment, which must have a currency and an amount, on behalf
+    /// of the user of `from_subaddress` if any. See [`peer_to_peer_script`].
+    pub fn payment_script(&self, from_subaddress: Option<Subaddress>) -> Result<Script> {
+        let currency = self
+            .currency()
+            .ok_or_else(|| format_err!("Payment URI has no currency"))?;
+        let amount = self
+            .amount
+            .ok_or_else(|| format_err!("Payment URI has no amount"))?;
+        peer_to_peer_script(&self.payee, from_subaddress, currency, amount)
+    }
+}
+
+impl fmt::Display for PaymentUri {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}://{}", PAYMENT_URI_SCHEME, self.payee)?;
+        let mut separator = '?';
+        if let Some(currency) = &self.currency {
+            write!(f, "{}{}={}", separator, CURRENCY_PARAM, currency)?;
+            separator = '&';
+        }
+        if let Some(amount) = self.amount {
+            write!(f, "{}{}={}", separator, AMOUNT_PARAM, amount)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PaymentUri {
+    type Err = Error;
+
+    /// Rejects the parameters it does not know, rather than a payment missing part of its request.
+    fn from_str(s: &str) -> Result<Self> {
+        let scheme_end = s
+            .find("://")
+            .ok_or_else(|| format_err!("Payment URI {:?} has no scheme", s))?;
+        ensure!(
+            s[..scheme_end].eq_ignore_ascii_case(PAYMENT_URI_SCHEME),
+            "Payment URI {:?} does not have the {:?} scheme",
+            s,
+            PAYMENT_URI_SCHEME
+        );
+        let rest = &s[scheme_end + 3..];
+        let (payee, query) = match rest.find('?') {
+            Some(query_start) => (&rest[..query_start], Some(&rest[query_start + 1..])),
+            None => (rest, None),
+        };
+
+        let mut uri = Self::new(AccountIdentifier::from_str(payee)?, None, None);
+        for param in query.into_iter().flat_map(|query| query.split('&')) {
+            let (name, value) = match param.find('=') {
+                Some(value_start) => (&param[..value_start], &param[value_start + 1..]),
+                None => bail!("Payment URI parameter {:?} has no value", param),
+            };
+            match name {
+                CURRENCY_PARAM if uri.currency.is_none() => {
+                    from_currency_code_string(value)?;
+                    uri.currency = Some(value.to_string());
+                }
+                AMOUNT_PARAM if uri.amount.is_none() => {
+                    uri.amount = Some(value.parse().map_err(|error| {
+                        format_err!("Invalid payment URI amount {:?}: {}", value, error)
+                    })?);
+                }
+                CURRENCY_PARAM | AMOUNT_PARAM => {
+                    bail!("Payment URI parameter {:?} is repeated", name)
+                }
+                _ => bail!("Unknown payment URI parameter {:?}", name),
+            }
+        }
+        Ok(uri)
+    }
+}
+
+/// The script of a payment of `amount` of `currency` to `payee`. The subaddresses of the payee,
+/// if any, and of the payer, `from_subaddress`, are recorded in the general metadata of the
+/// payment, for the custodians of the accounts to route it. The metadata is left empty if there
+/// are no subaddresses. The payment is not dual attested, so it may not exceed the limit of
+/// payments between VASPs.
+pub fn peer_to_peer_script(
+    payee: &AccountIdentifier,
+    from_subaddress: Option<Subaddress>,
+    currency: &str,
+    amount: u64,
+) -> Result<Script> {
+    let metadata = if payee.subaddress().is_none() && from_subaddress.is_none() {
+        vec![]
+    } else {
+        lcs::to_bytes(&Metadata::GeneralMetadata(
+            GeneralMetadata::GeneralMetadataVersion0(GeneralMetadataV0::new(
+                payee.subaddress(),
+                from_subaddress,
+                None,
+            )),
+        ))?
+    };
+    Ok(encode_peer_to_peer_with_metadata_script(
+        type_tag_for_currency_code(from_currency_code_string(currency)?),
+        payee.address(),
+        amount,
+        metadata,
+        vec![],
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libra_types::{
+        account_address::AccountAddress, account_identifier::AddressNetwork,
+        transaction::TransactionArgument,
+    };
+
+    fn payee() -> AccountIdentifier {
+        AccountIdentifier::with_subaddress(
+            AddressNetwork::Testnet,
+            AccountAddress::from_str("f72589b71ff4f8d139674a3f7369c69b").unwrap(),
+            Subaddress::from_str("cf64428bdeb62af2").unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_payment_uri() {
+        let uri = PaymentUri::new(payee(), Some("LBR".to_string()), Some(1_000_000));
+        let encoded = "libra://tlb1p7ujcndcl7nudzwt8fglhx6wxn08kgs5tm6mz4usugm707?c=LBR&am=1000000";
+        assert_eq!(uri.to_string(), encoded);
+        assert_eq!(PaymentUri::from_str(encoded).unwrap(), uri);
+        assert_eq!(
+            PaymentUri::from_str(
+                "libra://tlb1p7ujcndcl7nudzwt8fglhx6wxn08kgs5tm6mz4usugm707?am=1000000&c=LBR"
+            )
+            .unwrap(),
+            uri
+        );
+
+        let uri = PaymentUri::new(payee(), None, None);
+        assert_eq!(PaymentUri::from_str(&uri.to_string()).unwrap(), uri);
+        assert!(uri.payment_script(None).is_err());
+
+        for invalid in &[
+            "http://tlb1p7ujcndcl7nudzwt8fglhx6wxn08kgs5tm6mz4usugm707",
+            "tlb1p7ujcndcl7nudzwt8fglhx6wxn08kgs5tm6mz4usugm707",
+            "libra://tlb1p7ujcndcl7nudzwt8fglhx6wxn08kgs5tm6mz4usugm708",
+            "libra://tlb1p7ujcndcl7nudzwt8fglhx6wxn08kgs5tm6mz4usugm707?am=-1",
+            "libra://tlb1p7ujcndcl7nudzwt8fglhx6wxn08kgs5tm6mz4usugm707?am=1&am=2",
+            "libra://tlb1p7ujcndcl7nudzwt8fglhx6wxn08kgs5tm6mz4usugm707?c=LBR&memo=hi",
+            "libra://tlb1p7ujcndcl7nudzwt8fglhx6wxn08kgs5tm6mz4usugm707?c",
+        ] {
+            assert!(PaymentUri::from_str(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_peer_to_peer_script_metadata() {
+        let from_subaddress = Subaddress::random();
+        let script = PaymentUri::new(payee(), Some("LBR".to_string()), Some(1_000_000))
+            .payment_script(Some(from_subaddress))
+            .unwrap();
+        assert_eq!(
+            script.args()[0],
+            TransactionArgument::Address(payee().address())
+        );
+        assert_eq!(script.args()[1], TransactionArgument::U64(1_000_000));
+        let metadata = match &script.args()[2] {
+            TransactionArgument::U8Vector(metadata) => {
+                lcs::from_bytes::<Metadata>(metadata).unwrap()
+            }
+            argument => panic!("Unexpected metadata argument {:?}", argument),
+        };
+        let metadata = match metadata {
+            Metadata::GeneralMetadata(GeneralMetadata::GeneralMetadataVersion0(metadata)) => {
+                metadata
+            }
+            metadata => panic!("Unexpected metadata {:?}", metadata),
+        };
+        assert_eq!(
+            metadata.receiver_subaddress().unwrap(),
+            payee().subaddress()
+        );
+        assert_eq!(metadata.sender_subaddress().unwrap(), Some(from_subaddress));
+
+        let payee = AccountIdentifier::new(AddressNetwork::Testnet, payee().address());
+        let script = peer_to_peer_script(&payee, None, "LBR", 1).unwrap();
+        assert_eq!(script.args()[2], TransactionArgument::U8Vector(vec![]));
+    }
+}
@@ -0,0 +1,113 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use std::{thread, time::Duration};
+
+/// How requests that fail to reach the node are retried, with an exponential backoff between the
+/// attempts. Errors returned by the node itself, e.g. a rejected transaction, are not retried.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RetryPolicy {
+    /// The number of attempts, including the first one. 1 disables retries.
+    pub max_attempts: u32,
+    /// The delay before the first retry, doubled for each following retry.
+    pub initial_delay: Duration,
+    /// The maximum delay between two attempts.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy making a single attempt.
+    pub fn no_retry() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// The delay before the retry number `retry`, counting from 0.
+    pub fn delay(&self, retry: u32) -> Duration {
+        self.initial_delay
+            .checked_mul(2u32.saturating_pow(retry))
+            .map_or(self.max_delay, |delay| std::cmp::min(delay, self.max_delay))
+    }
+
+    /// Runs `f` until it succeeds or `max_attempts` is reached, returning the last error.
+    pub fn retry<T>(&self, mut f: impl FnMut() -> Result<T>) -> Result<T> {
+        let mut retry = 0;
+        loop {
+            match f() {
+                Ok(result) => return Ok(result),
+                Err(e) if retry + 1 >= self.max_attempts => return Err(e),
+                Err(_) => {
+                    thread::sleep(self.delay(retry));
+                    retry += 1;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::bail;
+
+    #[test]
+    fn test_delay() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            initial_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(50),
+        };
+        assert_eq!(policy.delay(0), Duration::from_millis(10));
+        assert_eq!(policy.delay(1), Duration::from_millis(20));
+        assert_eq!(policy.delay(2), Duration::from_millis(40));
+        assert_eq!(policy.delay(3), Duration::from_millis(50));
+        assert_eq!(policy.delay(100), Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_retry() {
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(1),
+        };
+
+        let mut attempts = 0;
+        let result = policy.retry(|| {
+            attempts += 1;
+            if attempts < 3 {
+                bail!("attempt {} failed", attempts)
+            }
+            Ok(attempts)
+        });
+        assert_eq!(result.unwrap(), 3);
+
+        let mut attempts = 0;
+        let result: Result<()> = policy.retry(|| {
+            attempts += 1;
+            bail!("attempt {} failed", attempts)
+        });
+        assert_eq!(result.unwrap_err().to_string(), "attempt 3 failed");
+
+        let mut attempts = 0;
+        let result: Result<()> = RetryPolicy::no_retry().retry(|| {
+            attempts += 1;
+            bail!("attempt {} failed", attempts)
+        });
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
+}
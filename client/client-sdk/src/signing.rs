@@ -0,0 +1,16 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use libra_crypto::{ed25519::Ed25519PrivateKey, PrivateKey};
+use libra_types::transaction::{RawTransaction, SignedTransaction};
+
+/// Signs the LCS serialized `raw_txn` with `private_key`, ready to be submitted.
+pub fn sign_transaction(
+    raw_txn: RawTransaction,
+    private_key: &Ed25519PrivateKey,
+) -> Result<SignedTransaction> {
+    Ok(raw_txn
+        .sign(private_key, private_key.public_key())?
+        .into_inner())
+}
@@ -0,0 +1,17 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! The faucet funds accounts on test networks. It holds the keys of the libra root and testnet
+//! designated dealer accounts in secure storage and exposes an HTTP endpoint accepting
+//! `POST /?auth_key=<hex>&amount=<base units>&currency_code=<code>`, which creates the account if
+//! needed and mints to it. The response is the sequence number of the designated dealer account
+//! once the mint is committed, so clients can wait for it.
+//!
+//! Requests are rate limited per IP address and per account, and are batched: all the requests
+//! received within a short window are submitted together with consecutive sequence numbers.
+
+pub mod minter;
+pub mod rate_limiter;
+pub mod server;
@@ -0,0 +1,55 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Usage: ./libra-faucet faucet.config
+
+#![forbid(unsafe_code)]
+
+use libra_config::config::FaucetConfig;
+use libra_faucet::{minter::Minter, server};
+use libra_logger::info;
+use libra_secure_json_rpc::JsonRpcClient;
+use libra_secure_storage::Storage;
+use std::{convert::TryInto, env, process};
+use tokio::runtime::Runtime;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    if args.len() != 2 {
+        eprintln!("Error! Incorrect number of parameters, expected a path to a config file.");
+        process::exit(1);
+    }
+
+    let faucet_config = FaucetConfig::load(&args[1]).unwrap_or_else(|e| {
+        eprintln!(
+            "Error! Unable to load provided faucet config: {}, error: {}",
+            args[1], e
+        );
+        process::exit(1);
+    });
+
+    libra_logger::Logger::new()
+        .channel_size(faucet_config.logger.chan_size)
+        .format(faucet_config.logger.format)
+        .is_async(faucet_config.logger.is_async)
+        .level(faucet_config.logger.level)
+        .init();
+
+    let storage: Storage = (&faucet_config.secure_backend)
+        .try_into()
+        .expect("Unable to initialize storage");
+    info!(
+        "Creating a faucet that talks to the JSON RPC endpoint at: {:?}.",
+        faucet_config.json_rpc_endpoint
+    );
+    let minter = Minter::new(
+        storage,
+        JsonRpcClient::new(faucet_config.json_rpc_endpoint.clone()),
+        faucet_config.chain_id,
+        faucet_config.txn_expiration_secs,
+    );
+
+    let mut runtime = Runtime::new().expect("Unable to create the runtime");
+    runtime.block_on(server::serve(&faucet_config, minter));
+}
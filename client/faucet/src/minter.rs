@@ -0,0 +1,218 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, format_err, Result};
+use libra_secure_json_rpc::{Error as JsonRpcError, JsonRpcClient};
+use libra_secure_storage::CryptoStorage;
+use libra_types::{
+    account_address::AccountAddress,
+    account_config::{
+        libra_root_address, testnet_dd_account_address, type_tag_for_currency_code, LBR_NAME,
+    },
+    chain_id::ChainId,
+    transaction::{
+        authenticator::AuthenticationKey, helpers::create_unsigned_txn, Script, SignedTransaction,
+        TransactionPayload,
+    },
+};
+use move_core_types::identifier::Identifier;
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+/// The name of the libra root account key in secure storage, used to create accounts.
+pub const LIBRA_ROOT_KEY: &str = "libra_root";
+/// The name of the testnet designated dealer account key in secure storage, used to mint.
+pub const TESTNET_DD_KEY: &str = "testnet_dd";
+
+const MAX_GAS_AMOUNT: u64 = 1_000_000;
+const GAS_UNIT_PRICE: u64 = 0;
+const POLL_INTERVAL_MS: u64 = 500;
+
+/// A request to fund an account, creating it if it doesn't exist yet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MintRequest {
+    pub auth_key: AuthenticationKey,
+    pub amount: u64,
+    pub currency_code: Identifier,
+}
+
+/// Submits mint transactions signed with the keys held in secure storage.
+pub struct Minter<S> {
+    storage: S,
+    client: JsonRpcClient,
+    chain_id: ChainId,
+    txn_expiration_secs: u64,
+}
+
+impl<S: CryptoStorage> Minter<S> {
+    pub fn new(
+        storage: S,
+        client: JsonRpcClient,
+        chain_id: ChainId,
+        txn_expiration_secs: u64,
+    ) -> Self {
+        Self {
+            storage,
+            client,
+            chain_id,
+            txn_expiration_secs,
+        }
+    }
+
+    /// Mints for a batch of requests. The accounts that don't exist yet are created by the libra
+    /// root account first, then all the mints are submitted by the testnet designated dealer
+    /// account with consecutive sequence numbers. Returns, for each request, the sequence number
+    /// of the designated dealer account once its mint is committed.
+    pub fn mint_batch(&self, requests: &[MintRequest]) -> Vec<Result<u64>> {
+        let mut statuses = Vec::with_capacity(requests.len());
+        let mut new_accounts = vec![];
+        for (index, request) in requests.iter().enumerate() {
+            match self
+                .client
+                .get_account_state(request.auth_key.derived_address(), None)
+            {
+                Ok(_) => statuses.push(Ok(())),
+                Err(JsonRpcError::MissingData(_)) => {
+                    new_accounts.push(index);
+                    statuses.push(Ok(()));
+                }
+                Err(e) => statuses.push(Err(e.into())),
+            }
+        }
+
+        let scripts = new_accounts
+            .iter()
+            .map(|index| {
+                let request = &requests[*index];
+                transaction_builder::encode_create_testing_account_script(
+                    type_tag_for_currency_code(request.currency_code.clone()),
+                    request.auth_key.derived_address(),
+                    request.auth_key.prefix().to_vec(),
+                    false, /* add all currencies */
+                )
+            })
+            .collect();
+        let creations = self.submit(libra_root_address(), LIBRA_ROOT_KEY, scripts);
+        // The mints can't be executed until the accounts exist
+        let committed = match creations.iter().filter_map(|c| c.as_ref().ok()).last() {
+            Some(sequence_number) => self.wait_for(libra_root_address(), *sequence_number),
+            None => Ok(()),
+        };
+        for (index, creation) in new_accounts.into_iter().zip(creations) {
+            statuses[index] = match (creation, &committed) {
+                (Err(e), _) => Err(e),
+                (Ok(_), Err(e)) => Err(format_err!("Account creation not committed: {}", e)),
+                (Ok(_), Ok(())) => Ok(()),
+            };
+        }
+
+        let minting: Vec<_> = (0..requests.len())
+            .filter(|index| statuses[*index].is_ok())
+            .collect();
+        let scripts = minting
+            .iter()
+            .map(|index| {
+                let request = &requests[*index];
+                transaction_builder::encode_testnet_mint_script(
+                    type_tag_for_currency_code(request.currency_code.clone()),
+                    request.auth_key.derived_address(),
+                    request.amount,
+                )
+            })
+            .collect();
+        let mints = self.submit(testnet_dd_account_address(), TESTNET_DD_KEY, scripts);
+
+        let mut results: Vec<_> = statuses.into_iter().map(|s| s.map(|_| 0)).collect();
+        for (index, mint) in minting.into_iter().zip(mints) {
+            results[index] = mint;
+        }
+        results
+    }
+
+    /// Signs and submits the scripts from the given account with consecutive sequence numbers,
+    /// returning the sequence number of the account after each transaction. Once a submission
+    /// fails the following transactions would be stuck behind the gap, so they are not submitted.
+    fn submit(&self, sender: AccountAddress, key: &str, scripts: Vec<Script>) -> Vec<Result<u64>> {
+        let mut sequence_number = match self.sequence_number(sender) {
+            Ok(sequence_number) => sequence_number,
+            Err(e) => {
+                let error = format!("Unable to get sequence number of {}: {}", sender, e);
+                return scripts
+                    .iter()
+                    .map(|_| Err(format_err!("{}", error)))
+                    .collect();
+            }
+        };
+        let mut failure: Option<String> = None;
+        scripts
+            .into_iter()
+            .map(|script| {
+                if let Some(failure) = &failure {
+                    bail!("Not submitted after an earlier failure: {}", failure);
+                }
+                match self.submit_script(sender, key, sequence_number, script) {
+                    Ok(()) => {
+                        sequence_number += 1;
+                        Ok(sequence_number)
+                    }
+                    Err(e) => {
+                        failure = Some(e.to_string());
+                        Err(e)
+                    }
+                }
+            })
+            .collect()
+    }
+
+    fn submit_script(
+        &self,
+        sender: AccountAddress,
+        key: &str,
+        sequence_number: u64,
+        script: Script,
+    ) -> Result<()> {
+        let raw_txn = create_unsigned_txn(
+            TransactionPayload::Script(script),
+            sender,
+            sequence_number,
+            MAX_GAS_AMOUNT,
+            GAS_UNIT_PRICE,
+            LBR_NAME.to_owned(),
+            self.txn_expiration_secs as i64,
+            self.chain_id,
+        );
+        let public_key = self.storage.get_public_key(key)?.public_key;
+        let signature = self.storage.sign(key, &raw_txn)?;
+        self.client
+            .submit_transaction(SignedTransaction::new(raw_txn, public_key, signature))?;
+        Ok(())
+    }
+
+    fn sequence_number(&self, account: AccountAddress) -> Result<u64> {
+        let account_resource = self
+            .client
+            .get_account_state(account, None)?
+            .get_account_resource()?
+            .ok_or_else(|| format_err!("No account resource for {}", account))?;
+        Ok(account_resource.sequence_number())
+    }
+
+    /// Waits until the sequence number of the account reaches the given one, or the transactions
+    /// have expired.
+    fn wait_for(&self, account: AccountAddress, sequence_number: u64) -> Result<()> {
+        let deadline = Instant::now() + Duration::from_secs(self.txn_expiration_secs);
+        while self.sequence_number(account)? < sequence_number {
+            if Instant::now() > deadline {
+                bail!(
+                    "Sequence number of {} didn't reach {} in time",
+                    account,
+                    sequence_number
+                );
+            }
+            thread::sleep(Duration::from_millis(POLL_INTERVAL_MS));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
+/// Limits the number of requests accepted per key (e.g., an IP address or an account) within a
+/// fixed window of time. The window of a key starts with the first request made for it.
+pub struct RateLimiter<K> {
+    limit: u32,
+    window: Duration,
+    requests: HashMap<K, (Instant, u32)>,
+}
+
+impl<K: Eq + Hash> RateLimiter<K> {
+    pub fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            requests: HashMap::new(),
+        }
+    }
+
+    /// Records a request for the given key at the given time and returns whether it is within the
+    /// limit. Rejected requests do not count towards the limit.
+    pub fn check(&mut self, key: K, now: Instant) -> bool {
+        let window = self.window;
+        // Forget the keys whose window has passed, so the limiter doesn't grow unbounded
+        self.requests
+            .retain(|_, (start, _)| now.saturating_duration_since(*start) < window);
+
+        let (_, count) = self.requests.entry(key).or_insert((now, 0));
+        if *count >= self.limit {
+            return false;
+        }
+        *count += 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter() {
+        let start = Instant::now();
+        let window = Duration::from_secs(10);
+        let mut limiter = RateLimiter::new(2, window);
+
+        assert!(limiter.check("a", start));
+        assert!(limiter.check("a", start + Duration::from_secs(1)));
+        assert!(!limiter.check("a", start + Duration::from_secs(2)));
+        assert!(limiter.check("b", start + Duration::from_secs(2)));
+
+        // The window of "a" has passed, while the window of "b" has not
+        assert!(limiter.check("a", start + window));
+        assert!(limiter.check("b", start + window));
+        assert!(!limiter.check("b", start + window));
+    }
+}
@@ -0,0 +1,207 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    minter::{MintRequest, Minter},
+    rate_limiter::RateLimiter,
+};
+use anyhow::{ensure, format_err, Result};
+use libra_config::config::FaucetConfig;
+use libra_logger::prelude::*;
+use libra_secure_storage::CryptoStorage;
+use libra_types::{
+    account_config::from_currency_code_string, transaction::authenticator::AuthenticationKey,
+};
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    net::{IpAddr, SocketAddr},
+    str::FromStr,
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+use tokio::sync::oneshot;
+use warp::{
+    http::StatusCode,
+    reply::{self, WithStatus},
+    Filter,
+};
+
+/// A mint request waiting to be batched, along with the channel to answer it on.
+struct PendingMint {
+    request: MintRequest,
+    response: oneshot::Sender<Result<u64>>,
+}
+
+/// The state shared by the HTTP handlers.
+struct Faucet {
+    max_amount: u64,
+    ip_limiter: Mutex<RateLimiter<IpAddr>>,
+    account_limiter: Mutex<RateLimiter<AuthenticationKey>>,
+    pending: Mutex<mpsc::Sender<PendingMint>>,
+}
+
+/// Serves the faucet endpoint until the server fails. Mint requests are handed over to a thread
+/// that batches them and submits them with the minter.
+pub async fn serve<S: CryptoStorage + 'static>(config: &FaucetConfig, minter: Minter<S>) {
+    let (sender, receiver) = mpsc::channel();
+    let max_batch_size = config.max_batch_size;
+    let batch_window = Duration::from_millis(config.batch_window_ms);
+    thread::spawn(move || run_batches(minter, receiver, max_batch_size, batch_window));
+
+    let window = Duration::from_secs(config.rate_limit_window_secs);
+    let faucet = Arc::new(Faucet {
+        max_amount: config.max_amount,
+        ip_limiter: Mutex::new(RateLimiter::new(config.requests_per_ip, window)),
+        account_limiter: Mutex::new(RateLimiter::new(config.requests_per_account, window)),
+        pending: Mutex::new(sender),
+    });
+
+    let handler = warp::any()
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(warp::addr::remote())
+        .and(warp::any().map(move || Arc::clone(&faucet)))
+        .and_then(handle_mint);
+
+    info!("Faucet listening on {}", config.address);
+    warp::serve(handler).run(config.address).await
+}
+
+async fn handle_mint(
+    params: HashMap<String, String>,
+    remote: Option<SocketAddr>,
+    faucet: Arc<Faucet>,
+) -> Result<WithStatus<String>, Infallible> {
+    let request = match parse_request(&params, faucet.max_amount) {
+        Ok(request) => request,
+        Err(e) => return Ok(reply::with_status(e.to_string(), StatusCode::BAD_REQUEST)),
+    };
+
+    let now = Instant::now();
+    if let Some(remote) = remote {
+        if !faucet.ip_limiter.lock().unwrap().check(remote.ip(), now) {
+            return Ok(rate_limited(format!("address {}", remote.ip())));
+        }
+    }
+    if !faucet
+        .account_limiter
+        .lock()
+        .unwrap()
+        .check(request.auth_key, now)
+    {
+        return Ok(rate_limited(format!("account {}", request.auth_key)));
+    }
+
+    let (response, receiver) = oneshot::channel();
+    let pending = PendingMint { request, response };
+    if faucet.pending.lock().unwrap().send(pending).is_err() {
+        return Ok(internal_error("Faucet has stopped minting".to_string()));
+    }
+    Ok(match receiver.await {
+        Ok(Ok(sequence_number)) => reply::with_status(sequence_number.to_string(), StatusCode::OK),
+        Ok(Err(e)) => internal_error(format!("Mint failed: {}", e)),
+        Err(_) => internal_error("Faucet has stopped minting".to_string()),
+    })
+}
+
+fn parse_request(params: &HashMap<String, String>, max_amount: u64) -> Result<MintRequest> {
+    let param = |name| {
+        params
+            .get(name)
+            .ok_or_else(|| format_err!("Missing parameter {}", name))
+    };
+    let auth_key = AuthenticationKey::from_str(param("auth_key")?)
+        .map_err(|_| format_err!("Malformed auth_key"))?;
+    let amount = param("amount")?
+        .parse::<u64>()
+        .map_err(|_| format_err!("Bad amount"))?;
+    ensure!(amount > 0, "Bad amount");
+    ensure!(
+        amount <= max_amount,
+        "Exceeded max amount of {}",
+        max_amount
+    );
+    let currency_code = from_currency_code_string(param("currency_code")?)
+        .map_err(|_| format_err!("Bad currency_code"))?;
+    Ok(MintRequest {
+        auth_key,
+        amount,
+        currency_code,
+    })
+}
+
+fn rate_limited(limited: String) -> WithStatus<String> {
+    reply::with_status(
+        format!("Too many requests for {}", limited),
+        StatusCode::TOO_MANY_REQUESTS,
+    )
+}
+
+fn internal_error(message: String) -> WithStatus<String> {
+    error!("{}", message);
+    reply::with_status(message, StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// Collects the pending requests into batches: a batch is submitted once it is full, or once the
+/// batch window has passed since its first request was received.
+fn run_batches<S: CryptoStorage>(
+    minter: Minter<S>,
+    receiver: mpsc::Receiver<PendingMint>,
+    max_batch_size: usize,
+    batch_window: Duration,
+) {
+    while let Ok(first) = receiver.recv() {
+        let deadline = Instant::now() + batch_window;
+        let mut batch = vec![first];
+        while batch.len() < max_batch_size {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            match receiver.recv_timeout(timeout) {
+                Ok(pending) => batch.push(pending),
+                Err(_) => break,
+            }
+        }
+
+        info!("Minting a batch of {} requests", batch.len());
+        let requests: Vec<_> = batch.iter().map(|p| p.request.clone()).collect();
+        let results = minter.mint_batch(&requests);
+        for (pending, result) in batch.into_iter().zip(results) {
+            // The client may have gone away, in which case there's no one to answer
+            let _ = pending.response.send(result);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(auth_key: &str, amount: &str, currency_code: &str) -> HashMap<String, String> {
+        vec![
+            ("auth_key", auth_key),
+            ("amount", amount),
+            ("currency_code", currency_code),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+    }
+
+    #[test]
+    fn test_parse_request() {
+        let auth_key = AuthenticationKey::random();
+        let request = parse_request(&params(&auth_key.to_string(), "100", "LBR"), 100).unwrap();
+        assert_eq!(request.auth_key, auth_key);
+        assert_eq!(request.amount, 100);
+        assert_eq!(request.currency_code.as_str(), "LBR");
+
+        parse_request(&params("abc", "100", "LBR"), 100).unwrap_err();
+        parse_request(&params(&auth_key.to_string(), "101", "LBR"), 100).unwrap_err();
+        parse_request(&params(&auth_key.to_string(), "0", "LBR"), 100).unwrap_err();
+        parse_request(&params(&auth_key.to_string(), "1.5", "LBR"), 100).unwrap_err();
+        parse_request(&params(&auth_key.to_string(), "100", "L-B"), 100).unwrap_err();
+        parse_request(&HashMap::new(), 100).unwrap_err();
+    }
+}
@@ -40,6 +40,19 @@ impl JsonRpcBatch {
         Ok(())
     }
 
+    pub fn add_simulate_transaction_request(
+        &mut self,
+        transaction: SignedTransaction,
+        skip_signature_check: bool,
+    ) -> Result<()> {
+        let txn_payload = hex::encode(lcs::to_bytes(&transaction)?);
+        self.add_request(
+            "simulate_transaction".to_string(),
+            vec![Value::String(txn_payload), json!(skip_signature_check)],
+        );
+        Ok(())
+    }
+
     pub fn add_get_account_state_request(&mut self, address: AccountAddress) {
         self.add_request(
             "get_account_state".to_string(),
@@ -47,14 +60,29 @@ impl JsonRpcBatch {
         );
     }
 
+    pub fn add_get_account_resources_request(&mut self, address: AccountAddress) {
+        self.add_request(
+            "get_account_resources".to_string(),
+            vec![Value::String(address.to_string())],
+        );
+    }
+
     pub fn add_get_metadata_request(&mut self, version: Option<u64>) {
         self.add_request("get_metadata".to_string(), vec![json!(version)]);
     }
 
+    pub fn add_get_epoch_participation_request(&mut self, epoch: u64) {
+        self.add_request("get_epoch_participation".to_string(), vec![json!(epoch)]);
+    }
+
     pub fn add_get_currencies_info(&mut self) {
         self.add_request("get_currencies".to_string(), vec![]);
     }
 
+    pub fn add_get_gas_estimate_request(&mut self) {
+        self.add_request("get_gas_estimate".to_string(), vec![]);
+    }
+
     pub fn add_get_transactions_request(
         &mut self,
         start_version: u64,
@@ -83,6 +111,13 @@ impl JsonRpcBatch {
         );
     }
 
+    pub fn add_get_transaction_by_hash_request(&mut self, hash: String, include_events: bool) {
+        self.add_request(
+            "get_transaction_by_hash".to_string(),
+            vec![json!(hash), json!(include_events)],
+        );
+    }
+
     pub fn add_get_events_request(&mut self, event_key: String, start: u64, limit: u64) {
         self.add_request(
             "get_events".to_string(),
@@ -90,10 +125,33 @@ impl JsonRpcBatch {
         );
     }
 
+    pub fn add_get_events_with_proofs_request(
+        &mut self,
+        event_key: String,
+        start: u64,
+        limit: u64,
+    ) {
+        self.add_request(
+            "get_events_with_proofs".to_string(),
+            vec![json!(event_key), json!(start), json!(limit)],
+        );
+    }
+
     pub fn add_get_state_proof_request(&mut self, known_version: u64) {
         self.add_request("get_state_proof".to_string(), vec![json!(known_version)]);
     }
 
+    pub fn add_get_accumulator_consistency_proof_request(
+        &mut self,
+        client_known_version: u64,
+        target_version: Option<u64>,
+    ) {
+        self.add_request(
+            "get_accumulator_consistency_proof".to_string(),
+            vec![json!(client_known_version), json!(target_version)],
+        );
+    }
+
     pub fn add_get_account_state_with_proof_request(
         &mut self,
         account: AccountAddress,
@@ -2,8 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::views::{
-    AccountStateWithProofView, AccountView, BlockMetadata, CurrencyInfoView, EventView,
-    StateProofView, TransactionView,
+    AccountStateWithProofView, AccountView, AccumulatorConsistencyProofView, BlockMetadata,
+    CurrencyInfoView, EpochParticipationView, EventView, EventsWithProofsView, GasEstimateView,
+    SimulatedTransactionView, StateProofView, TransactionView,
 };
 use anyhow::{ensure, format_err, Error, Result};
 
@@ -14,15 +15,22 @@ use std::convert::TryFrom;
 #[derive(Clone, PartialEq, Debug)]
 pub enum JsonRpcResponse {
     SubmissionResponse,
+    SimulationResponse(SimulatedTransactionView),
     AccountResponse(Option<AccountView>),
     StateProofResponse(StateProofView),
+    AccumulatorConsistencyProofResponse(AccumulatorConsistencyProofView),
     AccountTransactionResponse(Option<TransactionView>),
     TransactionsResponse(Vec<TransactionView>),
     EventsResponse(Vec<EventView>),
+    EventsWithProofsResponse(EventsWithProofsView),
     BlockMetadataResponse(BlockMetadata),
+    EpochParticipationResponse(Option<EpochParticipationView>),
     CurrenciesResponse(Vec<CurrencyInfoView>),
+    GasEstimateResponse(GasEstimateView),
     AccountStateWithProofResponse(AccountStateWithProofView),
     NetworkStatusResponse(Number),
+    /// The annotated resources of an account, in the JSON format of the resource viewer.
+    AccountResourcesResponse(Option<Value>),
     UnknownResponse(Value),
 }
 
@@ -39,6 +47,10 @@ impl TryFrom<(String, Value)> for JsonRpcResponse {
                 );
                 Ok(JsonRpcResponse::SubmissionResponse)
             }
+            "simulate_transaction" => {
+                let simulation: SimulatedTransactionView = serde_json::from_value(value)?;
+                Ok(JsonRpcResponse::SimulationResponse(simulation))
+            }
             "get_account_state" => {
                 let account = match value {
                     Value::Null => None,
@@ -53,14 +65,32 @@ impl TryFrom<(String, Value)> for JsonRpcResponse {
                 let events: Vec<EventView> = serde_json::from_value(value)?;
                 Ok(JsonRpcResponse::EventsResponse(events))
             }
+            "get_events_with_proofs" => {
+                let events: EventsWithProofsView = serde_json::from_value(value)?;
+                Ok(JsonRpcResponse::EventsWithProofsResponse(events))
+            }
             "get_metadata" => {
                 let metadata: BlockMetadata = serde_json::from_value(value)?;
                 Ok(JsonRpcResponse::BlockMetadataResponse(metadata))
             }
+            "get_epoch_participation" => {
+                let participation = match value {
+                    Value::Null => None,
+                    _ => {
+                        let participation: EpochParticipationView = serde_json::from_value(value)?;
+                        Some(participation)
+                    }
+                };
+                Ok(JsonRpcResponse::EpochParticipationResponse(participation))
+            }
             "get_currencies" => {
                 let info: Vec<CurrencyInfoView> = serde_json::from_value(value)?;
                 Ok(JsonRpcResponse::CurrenciesResponse(info))
             }
+            "get_gas_estimate" => {
+                let estimate: GasEstimateView = serde_json::from_value(value)?;
+                Ok(JsonRpcResponse::GasEstimateResponse(estimate))
+            }
             "get_account_state_with_proof" => {
                 let account_with_proof: AccountStateWithProofView = serde_json::from_value(value)?;
                 Ok(JsonRpcResponse::AccountStateWithProofResponse(
@@ -71,7 +101,11 @@ impl TryFrom<(String, Value)> for JsonRpcResponse {
                 let state_proof: StateProofView = serde_json::from_value(value)?;
                 Ok(JsonRpcResponse::StateProofResponse(state_proof))
             }
-            "get_account_transaction" => {
+            "get_accumulator_consistency_proof" => {
+                let proof: AccumulatorConsistencyProofView = serde_json::from_value(value)?;
+                Ok(JsonRpcResponse::AccumulatorConsistencyProofResponse(proof))
+            }
+            "get_account_transaction" | "get_transaction_by_hash" => {
                 let txn = match value {
                     Value::Null => None,
                     _ => {
@@ -91,6 +125,13 @@ impl TryFrom<(String, Value)> for JsonRpcResponse {
                     connected_peers_count,
                 ))
             }
+            "get_account_resources" => {
+                let resources = match value {
+                    Value::Null => None,
+                    _ => Some(value),
+                };
+                Ok(JsonRpcResponse::AccountResourcesResponse(resources))
+            }
             _ => Ok(JsonRpcResponse::UnknownResponse(value)),
         }
     }
@@ -146,6 +187,16 @@ impl ResponseAsView for BlockMetadata {
     }
 }
 
+impl ResponseAsView for EpochParticipationView {
+    fn optional_from_response(response: JsonRpcResponse) -> Result<Option<Self>> {
+        if let JsonRpcResponse::EpochParticipationResponse(view) = response {
+            Ok(view)
+        } else {
+            Self::unexpected_response_error::<Option<Self>>(response)
+        }
+    }
+}
+
 impl ResponseAsView for CurrencyInfoView {
     fn vec_from_response(response: JsonRpcResponse) -> Result<Vec<Self>> {
         if let JsonRpcResponse::CurrenciesResponse(info) = response {
@@ -174,6 +225,26 @@ impl ResponseAsView for TransactionView {
     }
 }
 
+impl ResponseAsView for EventsWithProofsView {
+    fn from_response(response: JsonRpcResponse) -> Result<Self> {
+        if let JsonRpcResponse::EventsWithProofsResponse(view) = response {
+            Ok(view)
+        } else {
+            Self::unexpected_response_error::<Self>(response)
+        }
+    }
+}
+
+impl ResponseAsView for GasEstimateView {
+    fn from_response(response: JsonRpcResponse) -> Result<Self> {
+        if let JsonRpcResponse::GasEstimateResponse(view) = response {
+            Ok(view)
+        } else {
+            Self::unexpected_response_error::<Self>(response)
+        }
+    }
+}
+
 impl ResponseAsView for StateProofView {
     fn from_response(response: JsonRpcResponse) -> Result<Self> {
         if let JsonRpcResponse::StateProofResponse(view) = response {
@@ -184,6 +255,16 @@ impl ResponseAsView for StateProofView {
     }
 }
 
+impl ResponseAsView for AccumulatorConsistencyProofView {
+    fn from_response(response: JsonRpcResponse) -> Result<Self> {
+        if let JsonRpcResponse::AccumulatorConsistencyProofResponse(view) = response {
+            Ok(view)
+        } else {
+            Self::unexpected_response_error::<Self>(response)
+        }
+    }
+}
+
 impl ResponseAsView for AccountStateWithProofView {
     fn from_response(response: JsonRpcResponse) -> Result<Self> {
         if let JsonRpcResponse::AccountStateWithProofResponse(resp) = response {
@@ -193,3 +274,13 @@ impl ResponseAsView for AccountStateWithProofView {
         }
     }
 }
+
+impl ResponseAsView for SimulatedTransactionView {
+    fn from_response(response: JsonRpcResponse) -> Result<Self> {
+        if let JsonRpcResponse::SimulationResponse(view) = response {
+            Ok(view)
+        } else {
+            Self::unexpected_response_error::<Self>(response)
+        }
+    }
+}
@@ -3,6 +3,7 @@
 
 use anyhow::Result;
 use libra_logger::json_log::JsonLogEntry;
+use node_debug_service::PeerAccounts;
 use reqwest::blocking;
 use std::collections::HashMap;
 
@@ -50,6 +51,57 @@ impl NodeDebugClient {
 
         Ok(response.json()?)
     }
+
+    /// Returns an error with the per component detail if the node reports itself unhealthy.
+    pub fn check_health(&mut self) -> Result<()> {
+        let response = self.client.get(&format!("{}/health", self.addr)).send()?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(anyhow::format_err!(
+                "Node is unhealthy: {}",
+                response.text()?
+            ))
+        }
+    }
+
+    /// Returns the on-chain accounts of the validator network and full node network PeerIds known
+    /// to the node.
+    pub fn get_peer_accounts(&mut self) -> Result<Vec<PeerAccounts>> {
+        let response = self.client.get(&format!("{}/peers", self.addr)).send()?;
+        if response.status().is_success() {
+            Ok(response.json()?)
+        } else {
+            let error: String = response.json()?;
+            Err(anyhow::format_err!("Peer accounts unavailable: {}", error))
+        }
+    }
+
+    /// Asks the node to reload its config file, and returns the changes it applied.
+    pub fn reload_config(&mut self) -> Result<Vec<String>> {
+        let response = self.client.post(&format!("{}/reload", self.addr)).send()?;
+        if response.status().is_success() {
+            Ok(response.json()?)
+        } else {
+            let issues: Vec<String> = response.json()?;
+            Err(anyhow::format_err!(
+                "Config reload rejected: {}",
+                issues.join(", ")
+            ))
+        }
+    }
+
+    /// Asks the node to drain: it stops taking transactions, waits for the consensus rounds in
+    /// flight, flushes its storage and exits.
+    pub fn drain(&mut self) -> Result<()> {
+        let response = self.client.post(&format!("{}/drain", self.addr)).send()?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            let error: String = response.json()?;
+            Err(anyhow::format_err!("Drain rejected: {}", error))
+        }
+    }
 }
 
 /// Implement default utility client for AsyncNodeDebugInterface
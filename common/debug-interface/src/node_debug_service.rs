@@ -4,9 +4,42 @@
 //! Debug interface to access information in a specific node.
 
 use libra_logger::json_log;
-use std::net::SocketAddr;
+use libra_metrics::health;
+use serde::{Deserialize, Serialize};
+use std::{net::SocketAddr, sync::Arc};
 use tokio::runtime::{Builder, Runtime};
-use warp::Filter;
+use warp::{http::StatusCode, Filter};
+
+/// Reloads the node config, and returns the changes applied or why the config was rejected.
+pub type ReloadHandler = Arc<dyn Fn() -> Result<Vec<String>, Vec<String>> + Send + Sync>;
+
+/// Starts draining the node before it exits, or returns why it can not.
+pub type DrainHandler = Arc<dyn Fn() -> Result<(), String> + Send + Sync>;
+
+/// Returns the on-chain accounts of the peers the node knows of, or why they can not be read.
+pub type PeersHandler = Arc<dyn Fn() -> Result<Vec<PeerAccounts>, String> + Send + Sync>;
+
+/// The on-chain accounts a network PeerId belongs to, so PeerIds in logs can be attributed to
+/// operators.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PeerAccounts {
+    pub peer_id: String,
+    /// The network the PeerId is used on, "validator" or "full_node"
+    pub network: String,
+    /// The validator account
+    pub owner: String,
+    /// The account allowed to operate the validator, if delegated
+    pub operator: Option<String>,
+}
+
+/// Operations on the node exposed by the debug interface, the endpoints of the missing ones
+/// answer 404.
+#[derive(Clone, Default)]
+pub struct ManagementHandlers {
+    pub reload: Option<ReloadHandler>,
+    pub drain: Option<DrainHandler>,
+    pub peers: Option<PeersHandler>,
+}
 
 #[derive(Debug)]
 pub struct NodeDebugService {
@@ -14,7 +47,7 @@ pub struct NodeDebugService {
 }
 
 impl NodeDebugService {
-    pub fn new(address: SocketAddr) -> Self {
+    pub fn new(address: SocketAddr, handlers: ManagementHandlers) -> Self {
         let runtime = Builder::new()
             .thread_name("nodedebug-")
             .threaded_scheduler()
@@ -29,7 +62,62 @@ impl NodeDebugService {
         // GET /evnets
         let events = warp::path("events").map(|| warp::reply::json(&json_log::pop_last_entries()));
 
-        let routes = warp::get().and(metrics.or(events));
+        // GET /health
+        let health = warp::path("health").map(|| {
+            let report = health::health_report();
+            let status = if report.healthy {
+                StatusCode::OK
+            } else {
+                StatusCode::SERVICE_UNAVAILABLE
+            };
+            warp::reply::with_status(warp::reply::json(&report.to_json()), status)
+        });
+
+        // GET /peers
+        let peers_handler = handlers.peers;
+        let peers =
+            warp::path("peers").map(move || match peers_handler.as_ref().map(|peers| peers()) {
+                Some(Ok(peers)) => {
+                    warp::reply::with_status(warp::reply::json(&peers), StatusCode::OK)
+                }
+                Some(Err(e)) => warp::reply::with_status(
+                    warp::reply::json(&e),
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                ),
+                None => warp::reply::with_status(
+                    warp::reply::json(&"peer accounts are not available"),
+                    StatusCode::NOT_FOUND,
+                ),
+            });
+
+        // POST /reload
+        let reload_handler = handlers.reload;
+        let reload = warp::path("reload").map(move || {
+            let (reply, status) = match reload_handler.as_ref().map(|reload| reload()) {
+                Some(Ok(changes)) => (changes, StatusCode::OK),
+                Some(Err(issues)) => (issues, StatusCode::BAD_REQUEST),
+                None => (
+                    vec!["config reload is not enabled".to_string()],
+                    StatusCode::NOT_FOUND,
+                ),
+            };
+            warp::reply::with_status(warp::reply::json(&reply), status)
+        });
+
+        // POST /drain
+        let drain_handler = handlers.drain;
+        let drain = warp::path("drain").map(move || {
+            let (reply, status) = match drain_handler.as_ref().map(|drain| drain()) {
+                Some(Ok(())) => ("draining".to_string(), StatusCode::ACCEPTED),
+                Some(Err(e)) => (e, StatusCode::CONFLICT),
+                None => ("drain is not enabled".to_string(), StatusCode::NOT_FOUND),
+            };
+            warp::reply::with_status(warp::reply::json(&reply), status)
+        });
+
+        let routes = warp::get()
+            .and(metrics.or(events).or(health).or(peers))
+            .or(warp::post().and(reload.or(drain)));
 
         let server = runtime.enter(move || warp::serve(routes).bind(address));
         runtime.handle().spawn(server);
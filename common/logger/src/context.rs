@@ -0,0 +1,68 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Context fields attached to every log line emitted on the current thread while they are in
+//! scope, such as the hash of the transaction or the id of the block being processed. Both text
+//! logs and structured logs carry them, so the lifecycle of one transaction or block can be
+//! followed across mempool, consensus, execution and storage by grepping for its id.
+//!
+//! ```pseudo
+//! with_context(BLOCK_ID, block.id(), || {
+//!     // Logged as "... Executing block block_id=<id>"
+//!     info!("Executing block");
+//! });
+//! ```
+
+use std::{cell::RefCell, fmt::Display};
+
+/// Hash of the transaction being processed.
+pub const TXN_HASH: &str = "txn_hash";
+/// Id of the block being processed.
+pub const BLOCK_ID: &str = "block_id";
+
+thread_local! {
+    static CONTEXT: RefCell<Vec<(&'static str, String)>> = RefCell::new(vec![]);
+}
+
+/// Runs `f` with `key` set to `value` in the logging context of the current thread. Scopes nest,
+/// the fields of the enclosing scopes are kept. The context does not follow work handed off to
+/// other threads or tasks, which have to set it again.
+pub fn with_context<V: Display, T>(key: &'static str, value: V, f: impl FnOnce() -> T) -> T {
+    CONTEXT.with(|context| context.borrow_mut().push((key, value.to_string())));
+    // Pops the field even if `f` panics
+    let _guard = ContextGuard;
+    f()
+}
+
+/// Returns the fields currently in scope, outermost first.
+pub fn context_fields() -> Vec<(&'static str, String)> {
+    CONTEXT.with(|context| context.borrow().clone())
+}
+
+struct ContextGuard;
+
+impl Drop for ContextGuard {
+    fn drop(&mut self) {
+        CONTEXT.with(|context| context.borrow_mut().pop());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nested_context() {
+        assert!(context_fields().is_empty());
+        with_context(BLOCK_ID, "b", || {
+            with_context(TXN_HASH, 1, || {
+                assert_eq!(
+                    context_fields(),
+                    vec![(BLOCK_ID, "b".to_string()), (TXN_HASH, "1".to_string())]
+                );
+            });
+            assert_eq!(context_fields(), vec![(BLOCK_ID, "b".to_string())]);
+        });
+        assert!(context_fields().is_empty());
+    }
+}
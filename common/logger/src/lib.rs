@@ -6,6 +6,8 @@
 //! # Text logging
 //!
 //! Text logging is configured via RUST_LOG macro and have exactly same facade as rust log crate
+//! Lines are written to stderr either as plain text or, with `LogFormat::Json`, as one json
+//! object per line.
 //!
 //! # Structured logging
 //!
@@ -104,6 +106,12 @@
 //! // }
 //! ```
 //!
+//! ## Context fields
+//!
+//! Fields such as a transaction hash or a block id can be attached to every log line emitted
+//! within a scope with `context::with_context`. They are appended to text logs and added to the
+//! data of structured logs.
+//!
 //! Structured log sink
 //! Application must define implementation of StructLogSink interface in order to direct structured logs emitted by send_struct_log and other macros. This sink can be only initialized once, by calling set_struct_logger function.
 //!
@@ -115,6 +123,7 @@
 
 pub use log;
 
+pub mod context;
 pub mod prelude {
     pub use crate::{
         crit, debug, error, event, info,
@@ -134,7 +143,7 @@ pub use struct_log::{
 
 mod text_log;
 pub use log::Level;
-pub use text_log::{Logger, CHANNEL_SIZE, DEFAULT_TARGET};
+pub use text_log::{set_level, LogFormat, Logger, CHANNEL_SIZE, DEFAULT_TARGET};
 pub mod counters;
 
 /// Define crit macro that specify libra as the target
@@ -1,6 +1,7 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::context::context_fields;
 use chrono::Utc;
 use once_cell::sync::Lazy;
 use serde::Serialize;
@@ -163,7 +164,10 @@ impl StructuredLogEntry {
 
     // Use send_struct_log! macro instead of this method to populate extra meta information such as git rev and module name
     #[doc(hidden)]
-    pub fn send(self) {
+    pub fn send(mut self) {
+        for (key, value) in context_fields() {
+            self.data.entry(key).or_insert(Value::String(value));
+        }
         struct_logger().send(self);
     }
 }
@@ -1,9 +1,12 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::context::context_fields;
 use chrono::Utc;
 use env_logger::filter;
-use log::{self, Level, Log, Metadata, Record};
+use log::{self, Level, LevelFilter, Log, Metadata, Record};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 
 use std::{
     env, fmt,
@@ -16,6 +19,22 @@ pub const CHANNEL_SIZE: usize = 256;
 pub const DEFAULT_TARGET: &str = "libra";
 const RUST_LOG: &str = "RUST_LOG";
 
+/// How log lines are written.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// LOG_LEVEL TIMESTAMP FILE:LINE MESSAGE KEY=VALUE..
+    Text,
+    /// One json object per line, with the context fields as keys
+    Json,
+}
+
+impl Default for LogFormat {
+    fn default() -> Self {
+        LogFormat::Text
+    }
+}
+
 /// Logging framework for Libra that encapsulates a minimal dependency logger with support for
 /// environmental variable (RUST_LOG) and asynchronous logging.
 /// Note: only a single logger can be instantiated at a time. Repeated instantiates of the loggers
@@ -25,6 +44,8 @@ pub struct Logger {
     channel_size: usize,
     /// Only instantiate a logger if the environment is properly set
     environment_only: bool,
+    /// How log lines are written.
+    format: LogFormat,
     /// Use a dedicated thread for logging.
     is_async: bool,
     /// The default logging level.
@@ -38,6 +59,7 @@ impl Logger {
         Self {
             channel_size: CHANNEL_SIZE,
             environment_only: false,
+            format: LogFormat::Text,
             is_async: false,
             level: Level::Info,
             override_rust_log: false,
@@ -54,6 +76,11 @@ impl Logger {
         self
     }
 
+    pub fn format(&mut self, format: LogFormat) -> &mut Self {
+        self.format = format;
+        self
+    }
+
     pub fn is_async(&mut self, is_async: bool) -> &mut Self {
         self.is_async = is_async;
         self
@@ -89,17 +116,27 @@ impl Logger {
         }
 
         if use_level {
-            filter_builder.filter(None, self.level.to_level_filter());
+            // The level is enforced by the global max level instead, so that `set_level` can
+            // raise it at runtime
+            filter_builder.filter(None, LevelFilter::Trace);
         }
 
         let filter = filter_builder.build();
         // Even if there is an existing logger, update the logging level
-        log::set_max_level(filter.filter());
+        if use_level {
+            log::set_max_level(self.level.to_level_filter());
+        } else {
+            log::set_max_level(filter.filter());
+        }
 
         if self.is_async {
             let (sender, receiver) = mpsc::sync_channel(self.channel_size);
 
-            let client = AsyncLogClient { filter, sender };
+            let client = AsyncLogClient {
+                filter,
+                format: self.format,
+                sender,
+            };
             if let Err(e) = log::set_boxed_logger(Box::new(client)) {
                 eprintln!("Unable to set logger: {}", e);
                 return;
@@ -109,7 +146,11 @@ impl Logger {
 
             thread::spawn(move || service.log_handler());
         } else {
-            let logger = SyncLogger { filter, writer };
+            let logger = SyncLogger {
+                filter,
+                format: self.format,
+                writer,
+            };
             if let Err(e) = log::set_boxed_logger(Box::new(logger)) {
                 eprintln!("Unable to set logger: {}", e);
                 return;
@@ -118,6 +159,12 @@ impl Logger {
     }
 }
 
+/// Changes the logging level of the running logger. When the filter comes from RUST_LOG, the
+/// level can only be lowered below it.
+pub fn set_level(level: Level) {
+    log::set_max_level(level.to_level_filter());
+}
+
 impl Default for Logger {
     fn default() -> Self {
         Self::new()
@@ -127,6 +174,7 @@ impl Default for Logger {
 /// Provies the log::Log for Libra's synchronous logger
 struct SyncLogger<W> {
     filter: filter::Filter,
+    format: LogFormat,
     writer: W,
 }
 
@@ -147,7 +195,7 @@ impl<W: Writer> Log for SyncLogger<W> {
             return;
         }
 
-        match format(record) {
+        match format(record, self.format) {
             Ok(formatted) => self.writer.write(formatted),
             Err(e) => self
                 .writer
@@ -195,6 +243,7 @@ impl<W: Writer> AsyncLogService<W> {
 /// Provides the log::Log interface for Libra's asynchronous logger
 struct AsyncLogClient {
     filter: filter::Filter,
+    format: LogFormat,
     sender: SyncSender<LogOp>,
 }
 
@@ -216,7 +265,8 @@ impl Log for AsyncLogClient {
             return;
         }
 
-        let formatted = format(record)
+        // Formatted on the calling thread, which holds the context fields
+        let formatted = format(record, self.format)
             .unwrap_or_else(|e| format!("Unable to format log {:?} due to {}", record, e));
         if let Err(e) = self.sender.try_send(LogOp::Log(formatted)) {
             match e {
@@ -232,11 +282,18 @@ impl Log for AsyncLogClient {
     fn flush(&self) {}
 }
 
+fn format(record: &Record, format: LogFormat) -> Result<String, fmt::Error> {
+    match format {
+        LogFormat::Text => format_text(record),
+        LogFormat::Json => format_json(record),
+    }
+}
+
 /// Converts a record into a string representation:
-/// UNIX_TIMESTAMP LOG_LEVEL FILE:LINE MESSAGE
+/// LOG_LEVEL UNIX_TIMESTAMP FILE:LINE MESSAGE KEY=VALUE..
 /// Example:
-/// 2020-03-07 05:03:03 INFO common/libra-logger/src/lib.rs:261 Hello
-fn format(record: &Record) -> Result<String, fmt::Error> {
+/// INFO 2020-03-07 05:03:03 common/libra-logger/src/lib.rs:261 Hello block_id=2a4e
+fn format_text(record: &Record) -> Result<String, fmt::Error> {
     let mut buffer = String::new();
 
     write!(buffer, "{} ", record.metadata().level())?;
@@ -252,9 +309,30 @@ fn format(record: &Record) -> Result<String, fmt::Error> {
 
     write!(buffer, "{}", record.args())?;
 
+    for (key, value) in context_fields() {
+        write!(buffer, " {}={}", key, value)?;
+    }
+
     Ok(buffer)
 }
 
+/// Converts a record into a json object on a single line:
+/// {"level":"INFO","timestamp":"2020-03-07 05:03:03","location":"common/libra-logger/src/lib.rs:261","message":"Hello","block_id":"2a4e"}
+fn format_json(record: &Record) -> Result<String, fmt::Error> {
+    let mut object = json!({
+        "level": record.metadata().level().to_string(),
+        "timestamp": Utc::now().format("%F %T").to_string(),
+        "message": record.args().to_string(),
+    });
+    if let (Some(file), Some(line)) = (record.file(), record.line()) {
+        object["location"] = Value::String(format!("{}:{}", file, line));
+    }
+    for (key, value) in context_fields() {
+        object[key] = Value::String(value);
+    }
+    serde_json::to_string(&object).map_err(|_| fmt::Error)
+}
+
 /// An trait encapsulating the operations required for writing logs.
 trait Writer: Send + Sync {
     /// Write the log.
@@ -302,5 +380,30 @@ mod tests {
         let string = logs.write().unwrap().remove(0);
         assert!(string.contains("INFO"));
         assert!(string.ends_with("Hello"));
+
+        context::with_context(context::BLOCK_ID, "2a4e", || info!("Executing"));
+        let string = logs.write().unwrap().remove(0);
+        assert!(string.ends_with("Executing block_id=2a4e"));
+    }
+
+    #[test]
+    fn verify_json_format() {
+        let line = context::with_context(context::TXN_HASH, "c0ffee", || {
+            let mut builder = Record::builder();
+            builder
+                .level(Level::Info)
+                .file(Some("lib.rs"))
+                .line(Some(1));
+            format(
+                &builder.args(format_args!("Hello")).build(),
+                LogFormat::Json,
+            )
+            .unwrap()
+        });
+        let object: Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(object["level"], "INFO");
+        assert_eq!(object["location"], "lib.rs:1");
+        assert_eq!(object["message"], "Hello");
+        assert_eq!(object["txn_hash"], "c0ffee");
     }
 }
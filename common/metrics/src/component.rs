@@ -0,0 +1,66 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Metric names follow `libra_<component>_<name>` (or `<component>_<name>` for `OpMetrics`), the
+//! component is derived from that prefix so metrics from all crates can be grouped the same way.
+
+use prometheus::proto::{LabelPair, MetricFamily};
+
+/// The label carrying the component of a metric, e.g. `component="consensus"`.
+pub const COMPONENT_LABEL: &str = "component";
+/// The component of the metrics whose name matches no known prefix.
+pub const UNKNOWN_COMPONENT: &str = "other";
+
+/// Name prefixes, after `libra_` is stripped, and the component they belong to.
+const COMPONENT_PREFIXES: &[(&str, &str)] = &[
+    ("consensus", "consensus"),
+    ("safety_rules", "consensus"),
+    ("shared_mempool", "mempool"),
+    ("mempool", "mempool"),
+    ("network", "network"),
+    ("pending_network", "network"),
+    ("state_sync", "state_sync"),
+    ("storage", "storage"),
+    ("schemadb", "storage"),
+    ("executor", "execution"),
+    ("vm", "vm"),
+    ("move", "vm"),
+    ("key_manager", "key_manager"),
+    ("client_service", "json_rpc"),
+    ("struct_log", "logger"),
+    ("trace", "trace"),
+];
+
+/// Returns the component a metric belongs to, based on its name.
+pub fn metric_component(name: &str) -> &'static str {
+    let name = name.trim_start_matches("libra_");
+    COMPONENT_PREFIXES
+        .iter()
+        .find(|(prefix, _)| {
+            name.starts_with(prefix)
+                && (name.len() == prefix.len() || name[prefix.len()..].starts_with('_'))
+        })
+        .map_or(UNKNOWN_COMPONENT, |(_, component)| component)
+}
+
+/// Adds the component label to every metric of the families that do not set it themselves.
+pub fn add_component_labels(metric_families: &mut [MetricFamily]) {
+    for metric_family in metric_families {
+        let component = metric_component(metric_family.get_name());
+        for metric in metric_family.mut_metric().iter_mut() {
+            if metric
+                .get_label()
+                .iter()
+                .any(|label| label.get_name() == COMPONENT_LABEL)
+            {
+                continue;
+            }
+            let mut label = LabelPair::new();
+            label.set_name(COMPONENT_LABEL.to_string());
+            label.set_value(component.to_string());
+            let mut labels = metric.take_label();
+            labels.push(label);
+            metric.set_label(labels.into());
+        }
+    }
+}
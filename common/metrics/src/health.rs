@@ -0,0 +1,179 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Registry where the components of the node report their liveness, aggregated by the `/health`
+//! endpoint of the debug interface.
+//!
+//! A component either sends heartbeats from its event loop, and is unhealthy once they stop for
+//! longer than its max interval, or registers a check that is run on every health report. Either
+//! kind can also report a failure explicitly, until it is cleared.
+
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+use std::{
+    collections::BTreeMap,
+    sync::RwLock,
+    time::{Duration, Instant},
+};
+
+pub type HealthCheck = Box<dyn Fn() -> Result<(), String> + Send + Sync>;
+
+enum Liveness {
+    Heartbeat {
+        last_heartbeat: Instant,
+        max_interval: Duration,
+    },
+    Check(HealthCheck),
+}
+
+struct ComponentState {
+    liveness: Liveness,
+    failure: Option<String>,
+}
+
+static HEALTH_REGISTRY: Lazy<RwLock<BTreeMap<&'static str, ComponentState>>> =
+    Lazy::new(|| RwLock::new(BTreeMap::new()));
+
+/// Handle to report the health of a registered component.
+#[derive(Clone, Debug)]
+pub struct HealthReporter {
+    component: &'static str,
+}
+
+impl HealthReporter {
+    /// Records that the component is alive, only meaningful for heartbeat components.
+    pub fn heartbeat(&self) {
+        self.update(|state| {
+            if let Liveness::Heartbeat { last_heartbeat, .. } = &mut state.liveness {
+                *last_heartbeat = Instant::now();
+            }
+        });
+    }
+
+    /// Marks the component unhealthy until `clear_failure` is called.
+    pub fn report_failure(&self, reason: impl Into<String>) {
+        let reason = reason.into();
+        self.update(|state| state.failure = Some(reason));
+    }
+
+    pub fn clear_failure(&self) {
+        self.update(|state| state.failure = None);
+    }
+
+    fn update(&self, f: impl FnOnce(&mut ComponentState)) {
+        if let Some(state) = HEALTH_REGISTRY
+            .write()
+            .expect("Health registry lock should not be poisoned")
+            .get_mut(self.component)
+        {
+            f(state);
+        }
+    }
+}
+
+/// Registers a component which is healthy as long as it sends a heartbeat at least every
+/// `max_interval`. Registering a component again replaces it.
+pub fn register_heartbeat(component: &'static str, max_interval: Duration) -> HealthReporter {
+    register(
+        component,
+        Liveness::Heartbeat {
+            last_heartbeat: Instant::now(),
+            max_interval,
+        },
+    )
+}
+
+/// Registers a component whose health is given by `check`, run on every report. The check should
+/// be cheap, as the report holds the registry lock while running it.
+pub fn register_check(component: &'static str, check: HealthCheck) -> HealthReporter {
+    register(component, Liveness::Check(check))
+}
+
+fn register(component: &'static str, liveness: Liveness) -> HealthReporter {
+    HEALTH_REGISTRY
+        .write()
+        .expect("Health registry lock should not be poisoned")
+        .insert(
+            component,
+            ComponentState {
+                liveness,
+                failure: None,
+            },
+        );
+    HealthReporter { component }
+}
+
+/// The health of one component, with the reason it is unhealthy.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ComponentHealth {
+    pub component: &'static str,
+    pub healthy: bool,
+    pub detail: String,
+}
+
+/// The health of all the registered components, the node is healthy if all of them are.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HealthReport {
+    pub healthy: bool,
+    pub components: Vec<ComponentHealth>,
+}
+
+impl HealthReport {
+    pub fn to_json(&self) -> Value {
+        let components: BTreeMap<_, _> = self
+            .components
+            .iter()
+            .map(|c| {
+                (
+                    c.component,
+                    json!({"healthy": c.healthy, "detail": c.detail}),
+                )
+            })
+            .collect();
+        json!({
+            "healthy": self.healthy,
+            "components": components,
+        })
+    }
+}
+
+/// Evaluates the health of every registered component.
+pub fn health_report() -> HealthReport {
+    let registry = HEALTH_REGISTRY
+        .read()
+        .expect("Health registry lock should not be poisoned");
+    let components: Vec<_> = registry
+        .iter()
+        .map(|(component, state)| {
+            let liveness = match &state.liveness {
+                Liveness::Heartbeat {
+                    last_heartbeat,
+                    max_interval,
+                } => {
+                    let elapsed = last_heartbeat.elapsed();
+                    let detail = format!("last heartbeat {} ms ago", elapsed.as_millis());
+                    if elapsed <= *max_interval {
+                        Ok(detail)
+                    } else {
+                        Err(detail)
+                    }
+                }
+                Liveness::Check(check) => check().map(|()| "ok".to_string()),
+            };
+            let (healthy, detail) = match (&state.failure, liveness) {
+                (Some(failure), _) => (false, failure.clone()),
+                (None, Ok(detail)) => (true, detail),
+                (None, Err(detail)) => (false, detail),
+            };
+            ComponentHealth {
+                component: *component,
+                healthy,
+                detail,
+            }
+        })
+        .collect();
+    HealthReport {
+        healthy: components.iter().all(|c| c.healthy),
+        components,
+    }
+}
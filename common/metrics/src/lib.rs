@@ -4,11 +4,15 @@
 #![forbid(unsafe_code)]
 #![recursion_limit = "128"]
 
+mod component;
+pub use component::{add_component_labels, metric_component, COMPONENT_LABEL};
 pub mod counters;
+pub mod health;
 mod json_encoder;
 mod json_metrics;
 pub mod metric_server;
 mod public_metrics;
+pub mod push_gateway;
 
 mod op_counters;
 pub use op_counters::{DurationHistogram, OpMetrics};
@@ -26,8 +30,9 @@ pub use prometheus::{
 use anyhow::Result;
 use libra_logger::prelude::*;
 use prometheus::{proto::MetricType, Encoder, TextEncoder};
+use serde_json::{json, Value};
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap},
     fs::{create_dir_all, File, OpenOptions},
     io::Write,
     path::Path,
@@ -100,6 +105,28 @@ pub fn get_all_metrics() -> HashMap<String, String> {
     all_metrics
 }
 
+/// Describes every registered metric: its name, help string, type, component and label names,
+/// so that dashboards can be generated from a running node.
+pub fn describe_all_metrics() -> Vec<Value> {
+    prometheus::gather()
+        .iter()
+        .map(|metric_family| {
+            let labels: BTreeSet<_> = metric_family
+                .get_metric()
+                .iter()
+                .flat_map(|m| m.get_label().iter().map(|l| l.get_name()))
+                .collect();
+            json!({
+                "name": metric_family.get_name(),
+                "help": metric_family.get_help(),
+                "type": format!("{:?}", metric_family.get_field_type()).to_lowercase(),
+                "component": metric_component(metric_family.get_name()),
+                "labels": labels,
+            })
+        })
+        .collect()
+}
+
 // Launches a background thread which will periodically collect metrics
 // every interval and write them to the provided file
 pub fn dump_all_metrics_to_file_periodically<P: AsRef<Path>>(
@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    json_encoder::JsonEncoder, json_metrics::get_json_metrics, public_metrics::PUBLIC_METRICS,
+    describe_all_metrics, json_encoder::JsonEncoder, json_metrics::get_json_metrics,
+    public_metrics::PUBLIC_METRICS,
 };
 use futures::future;
 use hyper::{
@@ -73,6 +74,11 @@ async fn serve_metrics(req: Request<Body>) -> Result<Response<Body>, hyper::Erro
             let encoded_metrics = serde_json::to_string(&json_metrics).unwrap();
             *resp.body_mut() = Body::from(encoded_metrics);
         }
+        // lists the name and help string of every metric, to generate dashboards
+        (&Method::GET, "/metric_names") => {
+            let encoded_metrics = serde_json::to_string(&describe_all_metrics()).unwrap();
+            *resp.body_mut() = Body::from(encoded_metrics);
+        }
         (&Method::GET, "/counters") => {
             // Json encoded libra_metrics;
             let encoder = JsonEncoder;
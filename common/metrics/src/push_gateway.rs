@@ -0,0 +1,60 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Periodically pushes all the metrics to a Prometheus push gateway, for nodes that can not be
+//! scraped, e.g. behind a NAT or short lived test nodes.
+
+use crate::component::add_component_labels;
+use anyhow::{bail, Result};
+use hyper::{header::CONTENT_TYPE, Body, Client, Method, Request};
+use libra_logger::prelude::*;
+use prometheus::{Encoder, TextEncoder};
+use std::{thread, time::Duration};
+use tokio::{runtime, time};
+
+/// Starts a thread pushing the metrics every `interval` to the push gateway at `url`, grouped
+/// under `job` and `instance`. Each push replaces the metrics previously pushed for the group.
+pub fn start_push_gateway(url: String, job: String, instance: String, interval: Duration) {
+    let push_url = format!(
+        "{}/metrics/job/{}/instance/{}",
+        url.trim_end_matches('/'),
+        job,
+        instance
+    );
+    thread::spawn(move || {
+        let mut rt = runtime::Builder::new()
+            .basic_scheduler()
+            .enable_io()
+            .enable_time()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let mut interval = time::interval(interval);
+            loop {
+                interval.tick().await;
+                if let Err(e) = push_metrics(&push_url).await {
+                    warn!("Failed to push metrics to {}: {}", push_url, e);
+                }
+            }
+        });
+    });
+}
+
+async fn push_metrics(push_url: &str) -> Result<()> {
+    let mut metric_families = prometheus::gather();
+    add_component_labels(&mut metric_families);
+    let encoder = TextEncoder::new();
+    let mut buffer = vec![];
+    encoder.encode(&metric_families, &mut buffer)?;
+
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(push_url)
+        .header(CONTENT_TYPE, encoder.format_type())
+        .body(Body::from(buffer))?;
+    let response = Client::new().request(request).await?;
+    if !response.status().is_success() {
+        bail!("unexpected status {}", response.status());
+    }
+    Ok(())
+}
@@ -4,7 +4,9 @@
 use super::super::*;
 use assert_approx_eq::assert_approx_eq;
 use once_cell::sync::Lazy;
-use prometheus::{core::Collector, proto::MetricFamily, Counter, IntCounter, Opts, Registry};
+use prometheus::{
+    core::Collector, proto::MetricFamily, Counter, IntCounter, IntCounterVec, Opts, Registry,
+};
 use rusty_fork::rusty_fork_test;
 
 pub static INT_COUNTER: Lazy<IntCounter> =
@@ -66,3 +68,86 @@ fn get_all_metrics_test() {
     }
 }
 }
+
+#[test]
+fn metric_component_test() {
+    assert_eq!(metric_component("libra_consensus_epoch"), "consensus");
+    assert_eq!(metric_component("libra_shared_mempool_events"), "mempool");
+    assert_eq!(metric_component("storage_duration"), "storage");
+    assert_eq!(metric_component("libra_vm_num_txns"), "vm");
+    // A prefix only matches a whole word
+    assert_eq!(metric_component("libra_vmx"), "other");
+}
+
+#[test]
+fn add_component_labels_test() {
+    let counter = IntCounterVec::new(Opts::new("libra_mempool_txns", "txns"), &["op"]).unwrap();
+    counter.with_label_values(&["add"]).inc();
+    let component_counter = IntCounterVec::new(
+        Opts::new("libra_consensus_txns", "txns"),
+        &[COMPONENT_LABEL],
+    )
+    .unwrap();
+    component_counter.with_label_values(&["safety_rules"]).inc();
+
+    let r = Registry::new();
+    r.register(Box::new(counter)).unwrap();
+    r.register(Box::new(component_counter)).unwrap();
+    let mut metric_families = r.gather();
+    add_component_labels(&mut metric_families);
+
+    let labels: Vec<Vec<_>> = metric_families
+        .iter()
+        .map(|mf| {
+            mf.get_metric()[0]
+                .get_label()
+                .iter()
+                .map(|l| (l.get_name(), l.get_value()))
+                .collect()
+        })
+        .collect();
+    assert_eq!(
+        labels,
+        vec![
+            vec![(COMPONENT_LABEL, "safety_rules")],
+            vec![("op", "add"), (COMPONENT_LABEL, "mempool")],
+        ]
+    );
+}
+
+rusty_fork_test! {
+#[test]
+fn describe_all_metrics_test() {
+    INT_COUNTER.inc();
+
+    let descriptions = describe_all_metrics();
+    assert_eq!(descriptions.len(), 1);
+    assert_eq!(descriptions[0]["name"], "INT_COUNTER");
+    assert_eq!(descriptions[0]["help"], "An integer counter");
+    assert_eq!(descriptions[0]["type"], "counter");
+    assert_eq!(descriptions[0]["component"], "other");
+}
+}
+
+rusty_fork_test! {
+#[test]
+fn health_report_test() {
+    let heartbeat = health::register_heartbeat("heartbeat", std::time::Duration::from_secs(60));
+    health::register_heartbeat("stale", std::time::Duration::from_secs(0));
+    health::register_check("check", Box::new(|| Err("unreachable".to_string())));
+    std::thread::sleep(std::time::Duration::from_millis(1));
+
+    let report = health::health_report();
+    assert!(!report.healthy);
+    let healthy: Vec<_> = report.components.iter().map(|c| (c.component, c.healthy)).collect();
+    assert_eq!(healthy, vec![("check", false), ("heartbeat", true), ("stale", false)]);
+    assert_eq!(report.components[0].detail, "unreachable");
+
+    heartbeat.report_failure("stuck");
+    let report = health::health_report();
+    assert_eq!(report.components[1].detail, "stuck");
+    assert_eq!(report.to_json()["components"]["heartbeat"]["healthy"], false);
+    heartbeat.clear_failure();
+    assert!(health::health_report().components[1].healthy);
+}
+}
@@ -12,7 +12,8 @@ use channel::{
 };
 use libra_types::{
     event::EventKey,
-    on_chain_config::{ConfigID, OnChainConfigPayload},
+    event_filter::EventFilter,
+    on_chain_config::{ConfigID, OnChainConfigPayload, ON_CHAIN_CONFIG_REGISTRY},
 };
 use std::{collections::HashSet, num::NonZeroUsize};
 
@@ -50,16 +51,22 @@ impl<T: Clone, U> SubscriptionService<T, U> {
 /// when `subscription_service` crate is a dependency of `types`, the build-dev fails
 pub type ReconfigSubscription = SubscriptionService<SubscriptionBundle, OnChainConfigPayload>;
 
+/// The notifications received through a `ReconfigSubscription`.
+pub type ReconfigEvents = Receiver<(), OnChainConfigPayload>;
+
 #[derive(Clone)]
 pub struct SubscriptionBundle {
     pub configs: HashSet<ConfigID>,
-    pub events: HashSet<EventKey>,
+    pub events: EventFilter,
 }
 
 impl SubscriptionBundle {
     pub fn new(configs: Vec<ConfigID>, events: Vec<EventKey>) -> Self {
+        Self::with_event_filter(configs, EventFilter::any_key(events))
+    }
+
+    pub fn with_event_filter(configs: Vec<ConfigID>, events: EventFilter) -> Self {
         let configs = configs.into_iter().collect::<HashSet<_>>();
-        let events = events.into_iter().collect::<HashSet<_>>();
 
         Self { configs, events }
     }
@@ -73,4 +80,21 @@ impl ReconfigSubscription {
         let bundle = SubscriptionBundle::new(configs, events);
         Self::subscribe(bundle)
     }
+
+    /// Subscribes to changes of every config of `ON_CHAIN_CONFIG_REGISTRY`, including the ones
+    /// registered later on, and to the committed events with one of the `events` keys.
+    pub fn subscribe_to_registry(
+        events: Vec<EventKey>,
+    ) -> (Self, Receiver<(), OnChainConfigPayload>) {
+        Self::subscribe_all(ON_CHAIN_CONFIG_REGISTRY.to_vec(), events)
+    }
+
+    /// Subscribes to changes of `configs` and to the committed events matched by `events`.
+    pub fn subscribe_with_event_filter(
+        configs: Vec<ConfigID>,
+        events: EventFilter,
+    ) -> (Self, Receiver<(), OnChainConfigPayload>) {
+        let bundle = SubscriptionBundle::with_event_filter(configs, events);
+        Self::subscribe(bundle)
+    }
 }
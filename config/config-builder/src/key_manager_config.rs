@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Result;
-use libra_config::config::{KeyManagerConfig as KMConfig, SecureBackend, Token, VaultConfig};
+use libra_config::config::{
+    KeyManagerConfig as KMConfig, RetryPolicy, SecureBackend, Token, VaultConfig,
+};
 
 pub struct KeyManagerConfig {
     pub rotation_period_secs: Option<u64>,
@@ -47,6 +49,9 @@ impl KeyManagerConfig {
             namespace: self.vault_namespace.clone(),
             server: self.vault_host.clone(),
             token: Token::FromConfig(self.vault_token.clone()),
+            enterprise_namespace: None,
+            renew_ttl_secs: None,
+            retry_policy: RetryPolicy::default(),
         });
 
         if let Some(rotation_period_secs) = &self.rotation_period_secs {
@@ -6,8 +6,8 @@ use anyhow::{ensure, format_err, Result};
 use executor::db_bootstrapper;
 use libra_config::{
     config::{
-        DiscoveryMethod, NodeConfig, OnDiskStorageConfig, RemoteService, SafetyRulesService,
-        SecureBackend, Token, VaultConfig, WaypointConfig,
+        DiscoveryMethod, NodeConfig, OnDiskStorageConfig, RemoteService, RetryPolicy,
+        SafetyRulesService, SecureBackend, Token, VaultConfig, WaypointConfig,
     },
     generator,
 };
@@ -154,6 +154,7 @@ impl ValidatorConfig {
                 .test
                 .as_ref()
                 .and_then(|config| config.publishing_option.clone()),
+            self.template.base.chain_id,
         );
 
         let (waypoint, maybe_waypoint) = if self.build_waypoint {
@@ -217,6 +218,9 @@ impl ValidatorConfig {
                             .ok_or_else(|| Error::MissingSafetyRulesToken)?
                             .clone(),
                     ),
+                    enterprise_namespace: None,
+                    renew_ttl_secs: None,
+                    retry_policy: RetryPolicy::default(),
                 }),
                 _ => return Err(Error::InvalidSafetyRulesBackend(backend.to_string()).into()),
             };
@@ -3,7 +3,10 @@
 
 #![forbid(unsafe_code)]
 
-use libra_crypto::{ed25519::Ed25519PrivateKey, Uniform};
+use libra_crypto::{
+    ed25519::Ed25519PrivateKey, multi_ed25519::MultiEd25519PrivateKey, traits::CryptoMaterialError,
+    Uniform,
+};
 use rand::{rngs::OsRng, Rng, SeedableRng};
 use std::{
     fs::{self, File},
@@ -17,6 +20,16 @@ pub fn generate_key() -> Ed25519PrivateKey {
     Ed25519PrivateKey::generate(&mut rng)
 }
 
+/// Generates a K-of-N MultiEd25519 key, see `MultiEd25519PrivateKey::generate_threshold_key`.
+pub fn generate_multi_key(
+    threshold: u8,
+    num_of_keys: usize,
+) -> Result<MultiEd25519PrivateKey, CryptoMaterialError> {
+    let mut seed_rng = OsRng;
+    let mut rng = rand::rngs::StdRng::from_seed(seed_rng.gen());
+    MultiEd25519PrivateKey::generate_threshold_key(&mut rng, threshold, num_of_keys)
+}
+
 pub fn generate_and_save_key<P: AsRef<Path>>(output_file: P) -> Ed25519PrivateKey {
     let key = generate_key();
     save_key(key, output_file)
@@ -23,5 +23,6 @@ pub const VALIDATOR_NETWORK_KEY: &str = "validator_network";
 pub const EPOCH: &str = "epoch";
 pub const LAST_VOTED_ROUND: &str = "last_voted_round";
 pub const PREFERRED_ROUND: &str = "preferred_round";
+pub const TRUSTED_STATE: &str = "trusted_state";
 pub const WAYPOINT: &str = "waypoint";
 pub const LAST_VOTE: &str = "last_vote";
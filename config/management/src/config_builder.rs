@@ -159,7 +159,10 @@ impl<T: AsRef<Path>> ValidatorBuilder<T> {
 
         let genesis_path = TempPath::new();
         genesis_path.create_as_file().unwrap();
-        let genesis = self.storage_helper.genesis(genesis_path.path()).unwrap();
+        let genesis = self
+            .storage_helper
+            .genesis(self.template.base.chain_id, genesis_path.path())
+            .unwrap();
 
         let _ = self
             .storage_helper
@@ -209,7 +212,7 @@ impl<T: AsRef<Path>> BuildSwarm for ValidatorBuilder<T> {
         // Create genesis and waypoint
         let _ = self
             .storage_helper
-            .create_waypoint(constants::COMMON_NS)
+            .create_waypoint(self.template.base.chain_id, constants::COMMON_NS)
             .unwrap();
         for (i, config) in configs.iter_mut().enumerate() {
             self.finish_validator_config(i, config);
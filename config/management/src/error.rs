@@ -34,6 +34,8 @@ pub enum Error {
     UnableToReadFile(String, String),
     #[error("Unable to parse file, {0}, error {1}")]
     UnableToParseFile(String, String),
+    #[error("Unable to write file, {0}, error {1}")]
+    UnableToWriteFile(String, String),
     #[error("Unexpected command, expected {0}, found {1}")]
     UnexpectedCommand(CommandName, CommandName),
     #[error("Unexpected error: {0}")]
@@ -3,7 +3,7 @@
 
 use crate::{
     constants, error::Error, layout::Layout, secure_backend::StorageLocation::RemoteStorage,
-    SingleBackend,
+    waypoint::compute_waypoint, SingleBackend,
 };
 use libra_crypto::ed25519::Ed25519PublicKey;
 use libra_global_constants::{ASSOCIATION_KEY, OPERATOR_KEY, OWNER_KEY};
@@ -11,11 +11,17 @@ use libra_secure_storage::KVStorage;
 use libra_types::{
     account_address,
     account_address::AccountAddress,
+    chain_id::ChainId,
     transaction::{Transaction, TransactionPayload},
+    waypoint::Waypoint,
+};
+use std::{
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
 };
-use std::{fs::File, io::Write, path::PathBuf};
 use structopt::StructOpt;
-use vm_genesis::{OperatorAssignment, OperatorRegistration};
+use vm_genesis::{GenesisBuilder, OperatorAssignment, OperatorRegistration};
 
 /// Note, it is implicitly expected that the storage supports
 /// a namespace but one has not been set.
@@ -25,6 +31,8 @@ pub struct Genesis {
     pub backend: SingleBackend,
     #[structopt(long)]
     pub path: Option<PathBuf>,
+    #[structopt(long)]
+    pub chain_id: ChainId,
 }
 
 impl Genesis {
@@ -39,18 +47,11 @@ impl Genesis {
             &operator_assignments,
             &operator_registrations,
             Some(libra_types::on_chain_config::VMPublishingOption::open()),
+            self.chain_id,
         );
 
         if let Some(path) = self.path {
-            let mut file = File::create(path).map_err(|e| {
-                Error::UnexpectedError(format!("Unable to create genesis file: {}", e.to_string()))
-            })?;
-            let bytes = lcs::to_bytes(&genesis).map_err(|e| {
-                Error::UnexpectedError(format!("Unable to serialize genesis: {}", e.to_string()))
-            })?;
-            file.write_all(&bytes).map_err(|e| {
-                Error::UnexpectedError(format!("Unable to write genesis file: {}", e.to_string()))
-            })?;
+            save_genesis(&genesis, &path)?;
         }
 
         Ok(genesis)
@@ -181,3 +182,29 @@ impl Genesis {
         Ok(registrations)
     }
 }
+
+/// Builds the genesis of a custom chain along with its waypoint, and optionally writes the genesis
+/// blob to `path`.
+pub fn build_genesis_with_waypoint(
+    builder: &GenesisBuilder,
+    path: Option<&Path>,
+) -> Result<(Transaction, Waypoint), Error> {
+    let genesis = builder.build();
+    let waypoint = compute_waypoint(&genesis)?;
+    if let Some(path) = path {
+        save_genesis(&genesis, path)?;
+    }
+    Ok((genesis, waypoint))
+}
+
+fn save_genesis(genesis: &Transaction, path: &Path) -> Result<(), Error> {
+    let mut file = File::create(path).map_err(|e| {
+        Error::UnexpectedError(format!("Unable to create genesis file: {}", e.to_string()))
+    })?;
+    let bytes = lcs::to_bytes(genesis).map_err(|e| {
+        Error::UnexpectedError(format!("Unable to serialize genesis: {}", e.to_string()))
+    })?;
+    file.write_all(&bytes).map_err(|e| {
+        Error::UnexpectedError(format!("Unable to write genesis file: {}", e.to_string()))
+    })
+}
@@ -8,6 +8,8 @@ mod genesis;
 mod json_rpc;
 mod key;
 mod layout;
+mod multi_key;
+mod rotate_operator;
 mod secure_backend;
 mod validator_config;
 mod validator_operator;
@@ -20,6 +22,8 @@ pub mod config_builder;
 #[cfg(any(test, feature = "testing"))]
 mod storage_helper;
 
+pub use crate::{genesis::build_genesis_with_waypoint, waypoint::compute_waypoint};
+
 use crate::{error::Error, layout::SetLayout, secure_backend::SecureBackend};
 use libra_crypto::ed25519::Ed25519PublicKey;
 use libra_types::{transaction::Transaction, waypoint::Waypoint};
@@ -45,6 +49,8 @@ pub enum Command {
     AssociationKey(crate::key::AssociationKey),
     #[structopt(about = "Create a waypoint and optionally place it in a store")]
     CreateWaypoint(crate::waypoint::CreateWaypoint),
+    #[structopt(about = "Generates a K-of-N MultiEd25519 key and writes each share to a file")]
+    GenerateMultiKey(crate::multi_key::GenerateMultiKey),
     #[structopt(about = "Retrieves data from a store to produce genesis")]
     Genesis(crate::genesis::Genesis),
     #[structopt(about = "Insert a waypoint")]
@@ -55,6 +61,10 @@ pub enum Command {
     OwnerKey(crate::key::OwnerKey),
     #[structopt(about = "Read account state from JSON-RPC endpoint")]
     ReadAccountState(crate::json_rpc::ReadAccountState),
+    #[structopt(about = "Rebuilds a MultiEd25519 public key and its authentication key")]
+    ReconstructMultiKey(crate::multi_key::ReconstructMultiKey),
+    #[structopt(about = "Assigns a new operator to an owner on-chain, signing with the owner key")]
+    RotateOperator(crate::rotate_operator::RotateOperator),
     #[structopt(about = "Submit a transaction to the blockchain")]
     SubmitTransaction(crate::json_rpc::SubmitTransaction),
     #[structopt(about = "Submits a Layout doc to a shared storage")]
@@ -71,11 +81,14 @@ pub enum Command {
 pub enum CommandName {
     AssociationKey,
     CreateWaypoint,
+    GenerateMultiKey,
     Genesis,
     InsertWaypoint,
     OperatorKey,
     OwnerKey,
     ReadAccountState,
+    ReconstructMultiKey,
+    RotateOperator,
     SetLayout,
     SetOperator,
     SubmitTransaction,
@@ -88,11 +101,14 @@ impl From<&Command> for CommandName {
         match command {
             Command::AssociationKey(_) => CommandName::AssociationKey,
             Command::CreateWaypoint(_) => CommandName::CreateWaypoint,
+            Command::GenerateMultiKey(_) => CommandName::GenerateMultiKey,
             Command::Genesis(_) => CommandName::Genesis,
             Command::InsertWaypoint(_) => CommandName::InsertWaypoint,
             Command::OperatorKey(_) => CommandName::OperatorKey,
             Command::OwnerKey(_) => CommandName::OwnerKey,
             Command::ReadAccountState(_) => CommandName::ReadAccountState,
+            Command::ReconstructMultiKey(_) => CommandName::ReconstructMultiKey,
+            Command::RotateOperator(_) => CommandName::RotateOperator,
             Command::SetLayout(_) => CommandName::SetLayout,
             Command::SetOperator(_) => CommandName::SetOperator,
             Command::SubmitTransaction(_) => CommandName::SubmitTransaction,
@@ -107,11 +123,14 @@ impl std::fmt::Display for CommandName {
         let name = match self {
             CommandName::AssociationKey => "association-key",
             CommandName::CreateWaypoint => "create-waypoint",
+            CommandName::GenerateMultiKey => "generate-multi-key",
             CommandName::Genesis => "genesis",
             CommandName::InsertWaypoint => "insert-waypoint",
             CommandName::OperatorKey => "operator-key",
             CommandName::OwnerKey => "owner-key",
             CommandName::ReadAccountState => "read-account-state",
+            CommandName::ReconstructMultiKey => "reconstruct-multi-key",
+            CommandName::RotateOperator => "rotate-operator",
             CommandName::SetLayout => "set-layout",
             CommandName::SetOperator => "set-operator",
             CommandName::SubmitTransaction => "submit-transaction",
@@ -127,11 +146,14 @@ impl Command {
         match &self {
             Command::AssociationKey(_) => self.association_key().unwrap().to_string(),
             Command::CreateWaypoint(_) => self.create_waypoint().unwrap().to_string(),
+            Command::GenerateMultiKey(_) => self.generate_multi_key().unwrap().to_string(),
             Command::Genesis(_) => format!("{:?}", self.genesis().unwrap()),
             Command::InsertWaypoint(_) => self.insert_waypoint().unwrap().to_string(),
             Command::OperatorKey(_) => self.operator_key().unwrap().to_string(),
             Command::OwnerKey(_) => self.owner_key().unwrap().to_string(),
             Command::ReadAccountState(_) => format!("{:?}", self.read_account_state().unwrap()),
+            Command::ReconstructMultiKey(_) => self.reconstruct_multi_key().unwrap().to_string(),
+            Command::RotateOperator(_) => format!("{:?}", self.rotate_operator().unwrap()),
             Command::SetLayout(_) => self.set_layout().unwrap().to_string(),
             Command::SetOperator(_) => format!("{:?}", self.set_operator().unwrap()),
             Command::SubmitTransaction(_) => self
@@ -158,6 +180,13 @@ impl Command {
         }
     }
 
+    pub fn generate_multi_key(self) -> Result<crate::multi_key::MultiKey, Error> {
+        match self {
+            Command::GenerateMultiKey(generate_multi_key) => generate_multi_key.execute(),
+            _ => Err(self.unexpected_command(CommandName::GenerateMultiKey)),
+        }
+    }
+
     pub fn genesis(self) -> Result<Transaction, Error> {
         match self {
             Command::Genesis(genesis) => genesis.execute(),
@@ -193,6 +222,20 @@ impl Command {
         }
     }
 
+    pub fn reconstruct_multi_key(self) -> Result<crate::multi_key::MultiKey, Error> {
+        match self {
+            Command::ReconstructMultiKey(reconstruct_multi_key) => reconstruct_multi_key.execute(),
+            _ => Err(self.unexpected_command(CommandName::ReconstructMultiKey)),
+        }
+    }
+
+    pub fn rotate_operator(self) -> Result<Transaction, Error> {
+        match self {
+            Command::RotateOperator(rotate_operator) => rotate_operator.execute(),
+            _ => Err(self.unexpected_command(CommandName::RotateOperator)),
+        }
+    }
+
     pub fn set_layout(self) -> Result<crate::layout::Layout, Error> {
         match self {
             Command::SetLayout(set_layout) => set_layout.execute(),
@@ -245,6 +288,8 @@ pub struct SecureBackends {
     ///         an optional namespace: "namespace=NAMESPACE"
     ///     InMemory: "backend=memory"
     ///     OnDisk: "backend=disk;path=LOCAL_PATH"
+    ///     PKCS#11: "backend=pkcs11;library=PATH_TO_MODULE;token_label=LABEL;pin=PATH_TO_PIN"
+    ///         an optional namespace: "namespace=NAMESPACE"
     #[structopt(long, verbatim_doc_comment)]
     local: SecureBackend,
     /// The remote secure backend, this is where data is stored. See
@@ -265,6 +310,8 @@ pub struct SingleBackend {
     ///         an optional namespace: "namespace=NAMESPACE"
     ///     InMemory: "backend=memory"
     ///     OnDisk: "backend=disk;path=LOCAL_PATH"
+    ///     PKCS#11: "backend=pkcs11;library=PATH_TO_MODULE;token_label=LABEL;pin=PATH_TO_PIN"
+    ///         an optional namespace: "namespace=NAMESPACE"
     #[structopt(long, verbatim_doc_comment)]
     pub backend: SecureBackend,
 }
@@ -282,7 +329,7 @@ pub mod tests {
     use libra_global_constants::{OPERATOR_KEY, OWNER_KEY};
     use libra_secure_storage::{CryptoStorage, KVStorage, Value};
     use libra_types::{
-        account_address,
+        account_address, account_config,
         chain_id::ChainId,
         transaction::{TransactionArgument, TransactionPayload},
     };
@@ -389,7 +436,9 @@ pub mod tests {
         // Step 7) Produce genesis
         let genesis_path = libra_temppath::TempPath::new();
         genesis_path.create_as_file().unwrap();
-        helper.genesis(genesis_path.path()).unwrap();
+        helper
+            .genesis(ChainId::test(), genesis_path.path())
+            .unwrap();
         let mut file = File::open(genesis_path.path()).unwrap();
         let mut contents = Vec::new();
         assert!(contents.is_empty());
@@ -554,6 +603,28 @@ pub mod tests {
         assert_eq!(output, 3);
     }
 
+    #[test]
+    fn test_build_genesis_with_waypoint() {
+        let key = Ed25519PrivateKey::generate_for_testing().public_key();
+        let builder = vm_genesis::GenesisBuilder::new(key.clone(), ChainId::test())
+            .with_account(vm_genesis::GenesisAccount::new(key).with_balance(
+                account_config::from_currency_code_string("Coin1").unwrap(),
+                100,
+            ))
+            .without_testnet_minting();
+
+        let path = libra_temppath::TempPath::new();
+        let (genesis, waypoint) = build_genesis_with_waypoint(&builder, Some(path.path())).unwrap();
+        assert_eq!(waypoint, compute_waypoint(&genesis).unwrap());
+
+        let mut bytes = Vec::new();
+        File::open(path.path())
+            .unwrap()
+            .read_to_end(&mut bytes)
+            .unwrap();
+        assert_eq!(genesis, lcs::from_bytes(&bytes).unwrap());
+    }
+
     #[test]
     fn test_owner_key() {
         test_key(libra_global_constants::OWNER_KEY, StorageHelper::owner_key);
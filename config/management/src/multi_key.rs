@@ -0,0 +1,170 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::Error;
+use libra_crypto::{
+    ed25519::Ed25519PublicKey,
+    multi_ed25519::{MultiEd25519KeyShare, MultiEd25519PublicKey},
+    ValidCryptoMaterialStringExt,
+};
+use libra_types::transaction::authenticator::AuthenticationKey;
+use std::{
+    fmt,
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+};
+use structopt::StructOpt;
+
+/// The public key of a K-of-N MultiEd25519 key along with the authentication key it produces.
+#[derive(Debug, PartialEq)]
+pub struct MultiKey {
+    pub public_key: MultiEd25519PublicKey,
+    pub authentication_key: AuthenticationKey,
+}
+
+impl MultiKey {
+    fn new(public_key: MultiEd25519PublicKey) -> Self {
+        let authentication_key = AuthenticationKey::multi_ed25519(&public_key);
+        Self {
+            public_key,
+            authentication_key,
+        }
+    }
+}
+
+impl fmt::Display for MultiKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Threshold: {}", self.public_key.threshold())?;
+        for (index, public_key) in self.public_key.public_keys().iter().enumerate() {
+            writeln!(f, "Share {} public key: {}", index, public_key)?;
+        }
+        writeln!(f, "Public key: {}", self.public_key)?;
+        writeln!(f, "Authentication key: {}", self.authentication_key)?;
+        write!(
+            f,
+            "Account address: {}",
+            self.authentication_key.derived_address()
+        )
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub struct GenerateMultiKey {
+    /// The number of shares required to sign
+    #[structopt(long)]
+    threshold: u8,
+    /// The total number of shares
+    #[structopt(long)]
+    shares: usize,
+    /// The directory where each share is written, LCS encoded, as share_<index>. Existing shares
+    /// are never overwritten.
+    #[structopt(long)]
+    output_dir: PathBuf,
+}
+
+impl GenerateMultiKey {
+    pub fn execute(self) -> Result<MultiKey, Error> {
+        let private_key =
+            generate_key::generate_multi_key(self.threshold, self.shares).map_err(|e| {
+                Error::CommandArgumentError(format!(
+                    "Unable to generate a {}-of-{} key: {}",
+                    self.threshold, self.shares, e
+                ))
+            })?;
+        let multi_key = MultiKey::new(MultiEd25519PublicKey::from(&private_key));
+
+        for share in private_key.into_shares() {
+            write_share(&self.output_dir, &share)?;
+        }
+        Ok(multi_key)
+    }
+}
+
+fn write_share(output_dir: &Path, share: &MultiEd25519KeyShare) -> Result<(), Error> {
+    let path = output_dir.join(format!("share_{}", share.index()));
+    let path_str = path.to_str().unwrap_or_default().to_string();
+    let encoded = lcs::to_bytes(share).map_err(|e| Error::UnexpectedError(e.to_string()))?;
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(&encoded))
+        .map_err(|e| Error::UnableToWriteFile(path_str, e.to_string()))
+}
+
+#[derive(Debug, StructOpt)]
+pub struct ReconstructMultiKey {
+    /// The number of shares required to sign
+    #[structopt(long)]
+    threshold: u8,
+    /// The hex encoded public keys of all the shares, ordered by share index
+    #[structopt(long, use_delimiter = true)]
+    public_keys: Vec<String>,
+}
+
+impl ReconstructMultiKey {
+    pub fn execute(self) -> Result<MultiKey, Error> {
+        let public_keys = self
+            .public_keys
+            .iter()
+            .map(|public_key| {
+                Ed25519PublicKey::from_encoded_string(public_key).map_err(|e| {
+                    Error::CommandArgumentError(format!("Invalid public key {}: {}", public_key, e))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let public_key = MultiEd25519PublicKey::from_shares(
+            public_keys
+                .into_iter()
+                .enumerate()
+                .map(|(index, public_key)| (index as u8, public_key))
+                .collect(),
+            self.threshold,
+        )
+        .map_err(|e| Error::CommandArgumentError(e.to_string()))?;
+        Ok(MultiKey::new(public_key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libra_temppath::TempPath;
+    use std::fs;
+
+    #[test]
+    fn test_generate_and_reconstruct() {
+        let output_dir = TempPath::new();
+        output_dir.create_as_dir().unwrap();
+        let generate = GenerateMultiKey {
+            threshold: 2,
+            shares: 3,
+            output_dir: output_dir.path().to_path_buf(),
+        };
+        let multi_key = generate.execute().unwrap();
+
+        let shares: Vec<MultiEd25519KeyShare> = (0..3)
+            .map(|index| {
+                let path = output_dir.path().join(format!("share_{}", index));
+                lcs::from_bytes(&fs::read(path).unwrap()).unwrap()
+            })
+            .collect();
+        let reconstruct = ReconstructMultiKey {
+            threshold: 2,
+            public_keys: shares
+                .iter()
+                .map(|share| share.public_key().to_encoded_string().unwrap())
+                .collect(),
+        };
+        assert_eq!(reconstruct.execute().unwrap(), multi_key);
+
+        // Shares are never overwritten
+        let generate = GenerateMultiKey {
+            threshold: 2,
+            shares: 3,
+            output_dir: output_dir.path().to_path_buf(),
+        };
+        assert!(generate.execute().is_err());
+    }
+}
@@ -0,0 +1,144 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    constants, error::Error, secure_backend::StorageLocation::LocalStorage, SingleBackend,
+};
+use libra_crypto::ed25519::Ed25519PublicKey;
+use libra_global_constants::OWNER_KEY;
+use libra_secure_json_rpc::JsonRpcClient;
+use libra_secure_storage::{CryptoStorage, Storage};
+use libra_secure_time::{RealTimeService, TimeService};
+use libra_types::{
+    account_address::{self, AccountAddress},
+    account_state::AccountState,
+    chain_id::ChainId,
+    transaction::{RawTransaction, SignedTransaction, Transaction},
+};
+use std::{thread, time::Duration};
+use structopt::StructOpt;
+
+/// Assigns a new operator to an owner on-chain: the set-validator-operator transaction is signed
+/// with the owner key from the local storage, submitted, and then checked against the on-chain
+/// state of the owner account.
+#[derive(Debug, StructOpt)]
+pub struct RotateOperator {
+    /// The account of the new validator operator
+    #[structopt(long)]
+    operator_account: AccountAddress,
+    /// JSON-RPC endpoint of a node of the chain
+    #[structopt(long)]
+    host: String,
+    #[structopt(long)]
+    chain_id: ChainId,
+    /// How long to wait for the transaction to be committed
+    #[structopt(long, default_value = "30")]
+    timeout_secs: u64,
+    #[structopt(flatten)]
+    backend: SingleBackend,
+}
+
+impl RotateOperator {
+    pub fn execute(self) -> Result<Transaction, Error> {
+        let mut local_storage = self.backend.backend.clone().create_storage(LocalStorage)?;
+        let owner_key = local_storage
+            .get_public_key(OWNER_KEY)
+            .map_err(|e| Error::LocalStorageReadError(OWNER_KEY, e.to_string()))?
+            .public_key;
+        let owner_account = account_address::from_public_key(&owner_key);
+
+        let client = JsonRpcClient::new(self.host.clone());
+        let sequence_number = account_sequence_number(&account_state(&client, owner_account)?)?;
+
+        let script =
+            transaction_builder::encode_set_validator_operator_script(self.operator_account);
+        let expiration_time = RealTimeService::new().now() + constants::TXN_EXPIRATION_SECS;
+        let raw_transaction = RawTransaction::new_script(
+            owner_account,
+            sequence_number,
+            script,
+            constants::MAX_GAS_AMOUNT,
+            constants::GAS_UNIT_PRICE,
+            constants::GAS_CURRENCY_CODE.to_owned(),
+            Duration::from_secs(expiration_time),
+            self.chain_id,
+        );
+        let signed_txn = sign(&mut local_storage, raw_transaction, owner_key)?;
+
+        client.submit_transaction(signed_txn.clone()).map_err(|e| {
+            Error::UnexpectedError(format!("Unable to submit transaction: {}", e.to_string()))
+        })?;
+        self.wait_for_operator(&client, owner_account, sequence_number)?;
+
+        Ok(Transaction::UserTransaction(signed_txn))
+    }
+
+    /// Waits for the transaction to be executed, then checks that the owner delegates to the new
+    /// operator.
+    fn wait_for_operator(
+        &self,
+        client: &JsonRpcClient,
+        owner_account: AccountAddress,
+        sequence_number: u64,
+    ) -> Result<(), Error> {
+        for _ in 0..self.timeout_secs {
+            let state = account_state(client, owner_account)?;
+            if account_sequence_number(&state)? <= sequence_number {
+                thread::sleep(Duration::from_secs(1));
+                continue;
+            }
+            let operator_account = state
+                .get_validator_config_resource()
+                .map_err(|e| Error::UnexpectedError(e.to_string()))?
+                .and_then(|resource| resource.delegated_account);
+            return if operator_account == Some(self.operator_account) {
+                Ok(())
+            } else {
+                Err(Error::UnexpectedError(format!(
+                    "Owner {} delegates to {:?} instead of {} after the transaction",
+                    owner_account, operator_account, self.operator_account
+                )))
+            };
+        }
+        Err(Error::UnexpectedError(format!(
+            "Transaction {} of {} not executed after {} seconds",
+            sequence_number, owner_account, self.timeout_secs
+        )))
+    }
+}
+
+pub(crate) fn account_state(
+    client: &JsonRpcClient,
+    account: AccountAddress,
+) -> Result<AccountState, Error> {
+    client.get_account_state(account, None).map_err(|e| {
+        Error::UnexpectedError(format!(
+            "Unable to read the state of {}: {}",
+            account,
+            e.to_string()
+        ))
+    })
+}
+
+fn account_sequence_number(state: &AccountState) -> Result<u64, Error> {
+    state
+        .get_account_resource()
+        .map_err(|e| Error::UnexpectedError(e.to_string()))?
+        .map(|resource| resource.sequence_number())
+        .ok_or_else(|| Error::UnexpectedError("Account resource not found".into()))
+}
+
+fn sign(
+    storage: &mut Storage,
+    raw_transaction: RawTransaction,
+    owner_key: Ed25519PublicKey,
+) -> Result<SignedTransaction, Error> {
+    let signature = storage.sign(OWNER_KEY, &raw_transaction).map_err(|e| {
+        Error::LocalStorageSigningError("set-validator-operator", OWNER_KEY, e.to_string())
+    })?;
+    Ok(SignedTransaction::new(
+        raw_transaction,
+        owner_key,
+        signature,
+    ))
+}
@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::error::Error;
-use libra_config::config::{self, GitHubConfig, OnDiskStorageConfig, Token, VaultConfig};
+use libra_config::config::{
+    self, GitHubConfig, OnDiskStorageConfig, Pkcs11Config, RetryPolicy, Token, VaultConfig,
+};
 use libra_secure_storage::{KVStorage, Storage};
 use std::{
     collections::HashMap,
@@ -14,6 +16,7 @@ use std::{
 pub const DISK: &str = "disk";
 pub const GITHUB: &str = "github";
 pub const MEMORY: &str = "memory";
+pub const PKCS11: &str = "pkcs11";
 pub const VAULT: &str = "vault";
 
 #[derive(Copy, Clone, Debug)]
@@ -124,6 +127,26 @@ impl TryInto<config::SecureBackend> for SecureBackend {
                 })
             }
             MEMORY => config::SecureBackend::InMemoryStorage,
+            PKCS11 => {
+                let library = self
+                    .parameters
+                    .remove("library")
+                    .ok_or_else(|| Error::BackendParsingError("missing library".into()))?;
+                let token_label = self
+                    .parameters
+                    .remove("token_label")
+                    .ok_or_else(|| Error::BackendParsingError("missing token label".into()))?;
+                let pin = self
+                    .parameters
+                    .remove("pin")
+                    .ok_or_else(|| Error::BackendParsingError("missing pin".into()))?;
+                config::SecureBackend::Pkcs11(Pkcs11Config {
+                    library: PathBuf::from(library),
+                    token_label,
+                    pin: Token::FromDisk(PathBuf::from(pin)),
+                    namespace: self.parameters.remove("namespace"),
+                })
+            }
             VAULT => {
                 let certificate = self.parameters.remove("ca_certificate").map(PathBuf::from);
                 let server = self
@@ -134,11 +157,23 @@ impl TryInto<config::SecureBackend> for SecureBackend {
                     .parameters
                     .remove("token")
                     .ok_or_else(|| Error::BackendParsingError("missing token".into()))?;
+                let renew_ttl_secs = self
+                    .parameters
+                    .remove("renew_ttl_secs")
+                    .map(|ttl| {
+                        ttl.parse::<u32>().map_err(|e| {
+                            Error::BackendParsingError(format!("invalid renew_ttl_secs: {}", e))
+                        })
+                    })
+                    .transpose()?;
                 config::SecureBackend::Vault(VaultConfig {
                     namespace: self.parameters.remove("namespace"),
                     server,
                     ca_certificate: certificate,
                     token: Token::FromDisk(PathBuf::from(token)),
+                    enterprise_namespace: self.parameters.remove("enterprise_namespace"),
+                    renew_ttl_secs,
+                    retry_policy: RetryPolicy::default(),
                 })
             }
             _ => panic!("Invalid backend: {}", self.backend),
@@ -236,6 +271,31 @@ mod tests {
         assert!(storage(vault).is_err());
     }
 
+    #[test]
+    fn test_pkcs11() {
+        // Opening the storage requires an HSM, so only the configuration is parsed here.
+        let pkcs11 = concat!(
+            "backend=pkcs11;library=/usr/lib/softhsm/libsofthsm2.so;",
+            "token_label=libra;pin=/pin;namespace=test"
+        );
+        let management_backend: SecureBackend = pkcs11.try_into().unwrap();
+        let config: config::SecureBackend = management_backend.try_into().unwrap();
+        assert_eq!(
+            config,
+            config::SecureBackend::Pkcs11(Pkcs11Config {
+                library: PathBuf::from("/usr/lib/softhsm/libsofthsm2.so"),
+                token_label: "libra".into(),
+                pin: Token::FromDisk(PathBuf::from("/pin")),
+                namespace: Some("test".into()),
+            })
+        );
+
+        let pkcs11 = "backend=pkcs11;library=/usr/lib/softhsm/libsofthsm2.so";
+        let management_backend: SecureBackend = pkcs11.try_into().unwrap();
+        let config: Result<config::SecureBackend, Error> = management_backend.try_into();
+        assert!(config.is_err());
+    }
+
     fn storage(s: &str) -> Result<Storage, Error> {
         let management_backend: SecureBackend = s.try_into()?;
         management_backend.try_into()
@@ -53,10 +53,14 @@ impl StorageHelper {
         storage.create_key(VALIDATOR_NETWORK_KEY).unwrap();
 
         // Initialize all other data in storage
-        storage.set(EPOCH, Value::U64(0)).unwrap();
-        storage.set(LAST_VOTED_ROUND, Value::U64(0)).unwrap();
-        storage.set(PREFERRED_ROUND, Value::U64(0)).unwrap();
-        storage.set(WAYPOINT, Value::String("".into())).unwrap();
+        storage
+            .batch_set(vec![
+                (EPOCH.into(), Value::U64(0)),
+                (LAST_VOTED_ROUND.into(), Value::U64(0)),
+                (PREFERRED_ROUND.into(), Value::U64(0)),
+                (WAYPOINT.into(), Value::String("".into())),
+            ])
+            .unwrap();
     }
 
     pub fn association_key(
@@ -85,11 +89,12 @@ impl StorageHelper {
         command.association_key()
     }
 
-    pub fn create_waypoint(&self, remote_ns: &str) -> Result<Waypoint, Error> {
+    pub fn create_waypoint(&self, chain_id: ChainId, remote_ns: &str) -> Result<Waypoint, Error> {
         let args = format!(
             "
                 management
                 create-waypoint
+                --chain-id {chain_id}
                 --local backend={backend};\
                     path={path}
                 --remote backend={backend};\
@@ -98,6 +103,7 @@ impl StorageHelper {
             ",
             backend = crate::secure_backend::DISK,
             path = self.path_string(),
+            chain_id = chain_id.id(),
             remote_ns = remote_ns,
         );
 
@@ -105,15 +111,17 @@ impl StorageHelper {
         command.create_waypoint()
     }
 
-    pub fn genesis(&self, genesis_path: &Path) -> Result<Transaction, Error> {
+    pub fn genesis(&self, chain_id: ChainId, genesis_path: &Path) -> Result<Transaction, Error> {
         let args = format!(
             "
                 management
                 genesis
+                --chain-id {chain_id}
                 --backend backend={backend};\
                     path={path}
                 --path {genesis_path}
             ",
+            chain_id = chain_id.id(),
             backend = crate::secure_backend::DISK,
             path = self.path_string(),
             genesis_path = genesis_path.to_str().expect("Unable to parse genesis_path"),
@@ -56,22 +56,25 @@ impl ValidatorConfig {
         let validator_config_tx =
             self.create_validator_config_transaction(validator_config_script)?;
 
-        // Write validator config to local storage to save for verification later on
+        // Write validator config to local storage to save for verification later on, along with
+        // the owner account for deployment later on. Both are written together so that local
+        // storage never holds a validator config without its owner account.
         let mut local_storage = self.backends.local.create_storage(LocalStorage)?;
         local_storage
-            .set(
-                constants::VALIDATOR_CONFIG,
-                Value::Transaction(validator_config_tx.clone()),
-            )
+            .batch_set(vec![
+                (
+                    constants::VALIDATOR_CONFIG.into(),
+                    Value::Transaction(validator_config_tx.clone()),
+                ),
+                (
+                    OWNER_ACCOUNT.into(),
+                    Value::String(owner_account.to_string()),
+                ),
+            ])
             .map_err(|e| {
                 Error::LocalStorageWriteError(constants::VALIDATOR_CONFIG, e.to_string())
             })?;
 
-        // Save the owner account in local storage for deployment later on
-        local_storage
-            .set(OWNER_ACCOUNT, Value::String(owner_account.to_string()))
-            .map_err(|e| Error::LocalStorageWriteError(OWNER_ACCOUNT, e.to_string()))?;
-
         // Upload the validator config to shared storage
         match self.backends.remote {
             None => return Err(Error::RemoteStorageMissing),
@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    constants::VALIDATOR_CONFIG, error::Error, secure_backend::StorageLocation::LocalStorage,
-    SingleBackend,
+    constants::VALIDATOR_CONFIG, error::Error, rotate_operator::account_state,
+    secure_backend::StorageLocation::LocalStorage, SingleBackend,
 };
 use executor::db_bootstrapper;
 use libra_crypto::{ed25519::Ed25519PublicKey, x25519};
@@ -11,6 +11,7 @@ use libra_global_constants::{
     CONSENSUS_KEY, EPOCH, FULLNODE_NETWORK_KEY, LAST_VOTED_ROUND, OPERATOR_ACCOUNT, OPERATOR_KEY,
     OWNER_ACCOUNT, OWNER_KEY, PREFERRED_ROUND, VALIDATOR_NETWORK_KEY, WAYPOINT,
 };
+use libra_secure_json_rpc::JsonRpcClient;
 use libra_secure_storage::{CryptoStorage, KVStorage, Storage};
 use libra_temppath::TempPath;
 use libra_types::{
@@ -46,6 +47,11 @@ pub struct Verify {
     /// the provided genesis after execution has begun.
     #[structopt(long, verbatim_doc_comment)]
     genesis_path: Option<PathBuf>,
+    /// If specified, compares the internal state to the validator
+    /// config and operator currently on-chain, read from this
+    /// JSON-RPC endpoint.
+    #[structopt(long, verbatim_doc_comment)]
+    json_server: Option<String>,
 }
 
 impl Verify {
@@ -81,6 +87,10 @@ impl Verify {
             compare_genesis(&local_storage, &mut buffer, genesis_path)?;
         }
 
+        if let Some(json_server) = self.json_server {
+            compare_on_chain(&local_storage, &mut buffer, json_server)?;
+        }
+
         Ok(buffer)
     }
 }
@@ -164,7 +174,42 @@ fn compare_genesis(
     // Fetch on-chain validator config and compare on-chain keys to local keys
     let validator_account = validator_account(storage)?;
     let validator_config = validator_config(validator_account, db_rw.reader.clone())?;
+    compare_validator_config(storage, buffer, validator_config)
+}
+
+/// Compares the validator config and operator currently on-chain to the local ones
+fn compare_on_chain(storage: &Storage, buffer: &mut String, host: String) -> Result<(), Error> {
+    let validator_account = validator_account(storage)?;
+    let client = JsonRpcClient::new(host);
+    let resource = account_state(&client, validator_account)?
+        .get_validator_config_resource()
+        .map_err(|e| Error::UnexpectedError(format!("ValidatorConfig issue {}", e.to_string())))?
+        .ok_or_else(|| Error::UnexpectedError("ValidatorConfig does not exist".into()))?;
+
+    let actual_operator_account = storage
+        .get(OPERATOR_ACCOUNT)
+        .and_then(|v| v.value.string())
+        .map_err(|e| Error::LocalStorageReadError(OPERATOR_ACCOUNT, e.to_string()))?;
+    let actual_operator_account = AccountAddress::from_str(&actual_operator_account)
+        .map_err(|e| Error::BackendParsingError(e.to_string()))?;
+    write_assert(
+        buffer,
+        OPERATOR_ACCOUNT,
+        resource.delegated_account == Some(actual_operator_account),
+    );
+
+    let validator_config = resource
+        .validator_config
+        .ok_or_else(|| Error::UnexpectedError("ValidatorConfig is not set".into()))?;
+    compare_validator_config(storage, buffer, validator_config)
+}
 
+/// Compares the keys of a validator config to the local ones
+fn compare_validator_config(
+    storage: &Storage,
+    buffer: &mut String,
+    validator_config: ValidatorConfig,
+) -> Result<(), Error> {
     let actual_consensus_key = ed25519_from_storage(CONSENSUS_KEY, storage)
         .map_err(|e| Error::LocalStorageReadError(CONSENSUS_KEY, e))?;
     let expected_consensus_key = validator_config.consensus_public_key;
@@ -13,7 +13,7 @@ use executor::db_bootstrapper;
 use libra_global_constants::WAYPOINT;
 use libra_secure_storage::{KVStorage, Storage, Value};
 use libra_temppath::TempPath;
-use libra_types::waypoint::Waypoint;
+use libra_types::{chain_id::ChainId, transaction::Transaction, waypoint::Waypoint};
 use libra_vm::LibraVM;
 use libradb::LibraDB;
 use std::{convert::TryInto, str::FromStr};
@@ -26,6 +26,8 @@ use structopt::StructOpt;
 pub struct CreateWaypoint {
     #[structopt(flatten)]
     secure_backends: SecureBackends,
+    #[structopt(long)]
+    chain_id: ChainId,
 }
 
 impl CreateWaypoint {
@@ -34,18 +36,11 @@ impl CreateWaypoint {
         let genesis_helper = crate::genesis::Genesis {
             backend: SingleBackend { backend },
             path: None,
+            chain_id: self.chain_id,
         };
 
         let genesis = genesis_helper.execute()?;
-
-        let path = TempPath::new();
-        let libradb =
-            LibraDB::open(&path, false, None).map_err(|e| Error::UnexpectedError(e.to_string()))?;
-        let db_rw = DbReaderWriter::new(libradb);
-
-        let waypoint = db_bootstrapper::bootstrap_db_if_empty::<LibraVM>(&db_rw, &genesis)
-            .map_err(|e| Error::UnexpectedError(e.to_string()))?
-            .ok_or_else(|| Error::UnexpectedError("Unable to generate a waypoint".to_string()))?;
+        let waypoint = compute_waypoint(&genesis)?;
 
         if let Some(remote) = self.secure_backends.remote {
             let mut remote_storage: Storage = remote.try_into()?;
@@ -59,6 +54,18 @@ impl CreateWaypoint {
     }
 }
 
+/// Computes the waypoint of a genesis transaction by applying it to an empty, temporary database.
+pub fn compute_waypoint(genesis: &Transaction) -> Result<Waypoint, Error> {
+    let path = TempPath::new();
+    let libradb =
+        LibraDB::open(&path, false, None).map_err(|e| Error::UnexpectedError(e.to_string()))?;
+    let db_rw = DbReaderWriter::new(libradb);
+
+    db_bootstrapper::bootstrap_db_if_empty::<LibraVM>(&db_rw, genesis)
+        .map_err(|e| Error::UnexpectedError(e.to_string()))?
+        .ok_or_else(|| Error::UnexpectedError("Unable to generate a waypoint".to_string()))
+}
+
 #[derive(Debug, StructOpt)]
 pub struct InsertWaypoint {
     #[structopt(flatten)]
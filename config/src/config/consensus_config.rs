@@ -15,6 +15,13 @@ pub struct ConsensusConfig {
     pub round_initial_timeout_ms: u64,
     pub proposer_type: ConsensusProposerType,
     pub safety_rules: SafetyRulesConfig,
+    // Offset applied to the local clock of consensus, only meant to test clock skew between
+    // validators.
+    pub clock_skew_ms: i64,
+    // Broadcast proposals with the summaries of their transactions instead of the full payload.
+    // The receivers find the transactions in their mempool and fetch the others from the
+    // proposer.
+    pub compact_proposals: bool,
 }
 
 impl Default for ConsensusConfig {
@@ -29,6 +36,8 @@ impl Default for ConsensusConfig {
                 inactive_weights: 1,
             }),
             safety_rules: SafetyRulesConfig::default(),
+            clock_skew_ms: 0,
+            compact_proposals: false,
         }
     }
 }
@@ -22,6 +22,18 @@ pub struct ExecutionConfig {
     pub genesis_file_location: PathBuf,
     pub service: ExecutionCorrectnessService,
     pub backend: SecureBackend,
+    /// Number of state trees, one per transaction, kept in memory for each executed block that is
+    /// not yet committed. The state tree resulting from the block is always kept.
+    pub max_retained_state_generations: usize,
+    /// Number of threads verifying the signatures of the user transactions of blocks and of the
+    /// transactions submitted to mempool, or one per CPU if 0.
+    pub signature_verification_threads: usize,
+    /// Maximum total size, in bytes, of the modules and scripts the Move VM keeps loaded. All its
+    /// caches are flushed once this is exceeded.
+    pub vm_max_loaded_binary_size: usize,
+    /// Maximum number of struct instantiations the Move VM keeps cached. All its caches are
+    /// flushed once this is exceeded.
+    pub vm_max_type_cache_entries: usize,
 }
 
 impl std::fmt::Debug for ExecutionConfig {
@@ -39,9 +51,19 @@ impl std::fmt::Debug for ExecutionConfig {
         )?;
         write!(
             f,
-            ", sign_vote_proposal: {:?}, service: {:?}, backend: {:?} }}",
+            ", sign_vote_proposal: {:?}, service: {:?}, backend: {:?}",
             self.sign_vote_proposal, self.service, self.backend
         )?;
+        write!(
+            f,
+            ", max_retained_state_generations: {:?}, signature_verification_threads: {:?}",
+            self.max_retained_state_generations, self.signature_verification_threads
+        )?;
+        write!(
+            f,
+            ", vm_max_loaded_binary_size: {:?}, vm_max_type_cache_entries: {:?} }}",
+            self.vm_max_loaded_binary_size, self.vm_max_type_cache_entries
+        )?;
         self.service.fmt(f)
     }
 }
@@ -54,6 +76,10 @@ impl Default for ExecutionConfig {
             service: ExecutionCorrectnessService::Thread,
             backend: SecureBackend::InMemoryStorage,
             sign_vote_proposal: true,
+            max_retained_state_generations: 0,
+            signature_verification_threads: 0,
+            vm_max_loaded_binary_size: 64 * 1024 * 1024,
+            vm_max_type_cache_entries: 100_000,
         }
     }
 }
@@ -0,0 +1,86 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::{Error, LoggerConfig, PersistableConfig, SecureBackend};
+use libra_types::chain_id::{self, ChainId};
+use serde::{Deserialize, Serialize};
+use std::{
+    net::SocketAddr,
+    path::{Path, PathBuf},
+};
+
+const DEFAULT_ADDRESS: &str = "0.0.0.0:8000";
+const DEFAULT_JSON_RPC_ENDPOINT: &str = "http://127.0.0.1:8080";
+
+const DEFAULT_MAX_AMOUNT: u64 = 10_000_000_000_000_000_000; // 10 trillion libras
+const DEFAULT_MAX_BATCH_SIZE: usize = 50;
+const DEFAULT_BATCH_WINDOW_MS: u64 = 500;
+const DEFAULT_TXN_EXPIRATION_SECS: u64 = 60;
+
+// Rate limiting related defaults
+const DEFAULT_RATE_LIMIT_WINDOW_SECS: u64 = 3600; // 1 hour
+const DEFAULT_REQUESTS_PER_IP: u32 = 100;
+const DEFAULT_REQUESTS_PER_ACCOUNT: u32 = 10;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FaucetConfig {
+    pub logger: LoggerConfig,
+    // The address the HTTP endpoint listens on
+    pub address: SocketAddr,
+    pub json_rpc_endpoint: String,
+    // Holds the keys of the libra root and testnet designated dealer accounts
+    pub secure_backend: SecureBackend,
+    #[serde(deserialize_with = "chain_id::deserialize_config_chain_id")]
+    pub chain_id: ChainId,
+    // The largest amount, in base units, that can be minted by a single request
+    pub max_amount: u64,
+    // The most mint requests submitted together
+    pub max_batch_size: usize,
+    // How long to wait for more requests before submitting a batch
+    pub batch_window_ms: u64,
+    pub txn_expiration_secs: u64,
+    pub rate_limit_window_secs: u64,
+    // The most requests accepted from a single IP address per rate limit window
+    pub requests_per_ip: u32,
+    // The most requests accepted for a single account per rate limit window
+    pub requests_per_account: u32,
+}
+
+impl Default for FaucetConfig {
+    fn default() -> FaucetConfig {
+        FaucetConfig {
+            logger: LoggerConfig::default(),
+            address: DEFAULT_ADDRESS.parse().unwrap(),
+            json_rpc_endpoint: DEFAULT_JSON_RPC_ENDPOINT.into(),
+            secure_backend: SecureBackend::InMemoryStorage,
+            chain_id: ChainId::test(),
+            max_amount: DEFAULT_MAX_AMOUNT,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            batch_window_ms: DEFAULT_BATCH_WINDOW_MS,
+            txn_expiration_secs: DEFAULT_TXN_EXPIRATION_SECS,
+            rate_limit_window_secs: DEFAULT_RATE_LIMIT_WINDOW_SECS,
+            requests_per_ip: DEFAULT_REQUESTS_PER_IP,
+            requests_per_account: DEFAULT_REQUESTS_PER_ACCOUNT,
+        }
+    }
+}
+
+impl FaucetConfig {
+    /// Reads the faucet config file from the given input_path. Paths used in the config are
+    /// either absolute or relative to the config location
+    pub fn load<P: AsRef<Path>>(input_path: P) -> Result<Self, Error> {
+        Self::load_config(&input_path)
+    }
+
+    /// Saves the faucet config file to the given output_path.
+    pub fn save<P: AsRef<Path>>(&mut self, output_path: P) -> Result<(), Error> {
+        self.save_config(&output_path)
+    }
+
+    pub fn set_data_dir(&mut self, data_dir: PathBuf) {
+        if let SecureBackend::OnDiskStorage(backend) = &mut self.secure_backend {
+            backend.set_data_dir(data_dir);
+        }
+    }
+}
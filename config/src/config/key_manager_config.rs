@@ -24,6 +24,10 @@ pub struct KeyManagerConfig {
     pub txn_expiration_secs: u64,
     #[serde(deserialize_with = "chain_id::deserialize_config_chain_id")]
     pub chain_id: ChainId,
+    // The frequency by which to rotate the validator network key, which is not rotated if unset
+    pub network_rotation_period_secs: Option<u64>,
+    // Rehearse a single iteration without modifying secure storage or submitting transactions
+    pub dry_run: bool,
 }
 
 impl Default for KeyManagerConfig {
@@ -36,6 +40,8 @@ impl Default for KeyManagerConfig {
             sleep_period_secs: DEFAULT_SLEEP_PERIOD_SECS,
             txn_expiration_secs: DEFAULT_TXN_EXPIRATION_SECS,
             chain_id: ChainId::test(),
+            network_rotation_period_secs: None,
+            dry_run: false,
         }
     }
 }
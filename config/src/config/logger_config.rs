@@ -1,7 +1,7 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use libra_logger::CHANNEL_SIZE;
+use libra_logger::{LogFormat, CHANNEL_SIZE};
 use log::Level;
 use serde::{Deserialize, Serialize};
 
@@ -10,6 +10,8 @@ use serde::{Deserialize, Serialize};
 pub struct LoggerConfig {
     // channel size for the asychronous channel for node logging.
     pub chan_size: usize,
+    // Write log lines as text or as json objects
+    pub format: LogFormat,
     // Use async logging
     pub is_async: bool,
     // The default logging level for slog.
@@ -20,6 +22,7 @@ impl Default for LoggerConfig {
     fn default() -> LoggerConfig {
         LoggerConfig {
             chan_size: CHANNEL_SIZE,
+            format: LogFormat::Text,
             is_async: true,
             level: Level::Info,
         }
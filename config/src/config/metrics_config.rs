@@ -10,6 +10,8 @@ pub struct MetricsConfig {
     pub collection_interval_ms: u64,
     pub dir: PathBuf,
     pub enabled: bool,
+    // Push the metrics to a Prometheus push gateway, in addition to serving them
+    pub push_gateway: Option<PushGatewayConfig>,
     #[serde(skip)]
     data_dir: PathBuf,
 }
@@ -21,10 +23,21 @@ impl Default for MetricsConfig {
             data_dir: PathBuf::from("/opt/libra/data"),
             enabled: false,
             dir: PathBuf::from("metrics"),
+            push_gateway: None,
         }
     }
 }
 
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PushGatewayConfig {
+    // e.g. http://pushgateway:9091
+    pub url: String,
+    // The job the metrics are grouped under, the node's peer id is used as the instance
+    pub job: String,
+    pub push_interval_ms: u64,
+}
+
 impl MetricsConfig {
     pub fn dir(&self) -> PathBuf {
         if self.dir.is_relative() {
@@ -21,6 +21,8 @@ mod error;
 pub use error::*;
 mod execution_config;
 pub use execution_config::*;
+mod faucet_config;
+pub use faucet_config::*;
 mod key_manager_config;
 pub use key_manager_config::*;
 mod logger_config;
@@ -31,6 +33,8 @@ mod mempool_config;
 pub use mempool_config::*;
 mod network_config;
 pub use network_config::*;
+mod reload;
+pub use reload::*;
 mod rpc_config;
 pub use rpc_config::*;
 mod secure_backend_config;
@@ -43,6 +47,8 @@ mod safety_rules_config;
 pub use safety_rules_config::*;
 mod upstream_config;
 pub use upstream_config::*;
+mod validation;
+pub use validation::*;
 mod test_config;
 use crate::network_id::NetworkId;
 use libra_secure_storage::{KVStorage, Storage};
@@ -55,7 +55,12 @@ pub struct NetworkConfig {
     // mutual_authentication network. This config field is intended as a fallback
     // in case some peers don't have well defined addresses.
     pub seed_pubkeys: SeedPublicKeys,
+    // Peers this node never connects to. In a mutual_authentication network, connections from
+    // them are rejected as well. This is used to partition test networks.
+    pub denied_peers: HashSet<PeerId>,
     pub max_frame_size: usize,
+    // Periodic rekeying of established Noise sessions.
+    pub noise_rekey: NoiseRekeyConfig,
 }
 
 impl Default for NetworkConfig {
@@ -75,7 +80,9 @@ impl NetworkConfig {
             network_id,
             seed_pubkeys: HashMap::default(),
             seed_addrs: HashMap::default(),
+            denied_peers: HashSet::default(),
             max_frame_size: 8 * 1024 * 1024, // TODO use constant
+            noise_rekey: NoiseRekeyConfig::default(),
         };
         config.prepare_identity();
         config
@@ -95,7 +102,9 @@ impl NetworkConfig {
             network_id: self.network_id.clone(),
             seed_pubkeys: self.seed_pubkeys.clone(),
             seed_addrs: self.seed_addrs.clone(),
+            denied_peers: self.denied_peers.clone(),
             max_frame_size: self.max_frame_size,
+            noise_rekey: self.noise_rekey,
         }
     }
 
@@ -103,13 +112,7 @@ impl NetworkConfig {
         let key = match &mut self.identity {
             Identity::FromConfig(config) => config.keypair.take_private(),
             Identity::FromStorage(config) => {
-                let storage: Storage = (&config.backend).into();
-                let key = storage
-                    .export_private_key(&config.key_name)
-                    .expect("Unable to read key");
-                let key = x25519::PrivateKey::from_ed25519_private_bytes(&key.to_bytes())
-                    .expect("Unable to convert key");
-                Some(key)
+                Some(config.identity_key().expect("Unable to load identity key"))
             }
             Identity::None => None,
         };
@@ -204,12 +207,36 @@ impl NetworkConfig {
                         addr,
                     ),
                 )?;
+                if let Some(pubkey) = addr.find_noise_proto() {
+                    verify_identity_pubkey(peer_id, &pubkey)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn verify_seed_pubkeys(&self) -> Result<(), Error> {
+        for (peer_id, pubkeys) in self.seed_pubkeys.iter() {
+            for pubkey in pubkeys {
+                verify_identity_pubkey(peer_id, pubkey)?;
             }
         }
         Ok(())
     }
 }
 
+/// Rejects network identity public keys that are unsafe to perform a handshake with.
+fn verify_identity_pubkey(peer_id: &PeerId, pubkey: &x25519::PublicKey) -> Result<(), Error> {
+    pubkey.validate().map_err(|err| {
+        Error::InvariantViolation(format!(
+            "Invalid seed peer public key: peer_id: {}, pubkey: {}, error: {:?}",
+            peer_id.short_str(),
+            pubkey,
+            err,
+        ))
+    })
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum DiscoveryMethod {
@@ -244,6 +271,29 @@ pub struct GossipConfig {
     pub discovery_interval_ms: u64,
 }
 
+/// Noise sessions are rekeyed in each direction once either limit is reached. A node only rekeys
+/// its outbound direction after the remote peer has announced that it understands rekeying, so
+/// this can be enabled while peers without rekeying support remain on the network.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NoiseRekeyConfig {
+    pub enabled: bool,
+    // The number of plaintext bytes encrypted with a single key before rekeying.
+    pub max_bytes: u64,
+    // The time a single key is used for before rekeying.
+    pub interval_ms: u64,
+}
+
+impl Default for NoiseRekeyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_bytes: 1024 * 1024 * 1024, // 1 GiB
+            interval_ms: 60 * 60 * 1000,   // 1 hour
+        }
+    }
+}
+
 #[cfg_attr(any(test, feature = "fuzzing"), derive(Clone, PartialEq))]
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case", tag = "type")]
@@ -287,11 +337,56 @@ pub struct IdentityFromConfig {
 }
 
 /// This represents an identity in a secure-storage as defined in NodeConfig::secure.
-#[cfg_attr(any(test, feature = "fuzzing"), derive(Clone, PartialEq))]
-#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(PartialEq))]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct IdentityFromStorage {
     pub backend: SecureBackend,
     pub key_name: String,
     pub peer_id_name: String,
 }
+
+impl IdentityFromStorage {
+    /// Reads the latest version of the identity key from storage. The key may have been rotated
+    /// since it was last read.
+    pub fn identity_key(&self) -> Result<x25519::PrivateKey, Error> {
+        let storage: Storage = (&self.backend).into();
+        let key = storage
+            .export_private_key(&self.key_name)
+            .map_err(|e| Error::InvariantViolation(format!("Unable to read key: {}", e)))?;
+        x25519::PrivateKey::from_ed25519_private_bytes(&key.to_bytes())
+            .map_err(|e| Error::InvariantViolation(format!("Unable to convert key: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_verify_seed_pubkeys() {
+        let mut config = NetworkConfig::default();
+        let peer_id = PeerId::random();
+        let mut rng = StdRng::from_seed([0u8; 32]);
+        let valid_pubkey = x25519::PrivateKey::generate(&mut rng).public_key();
+        // u = 0 is a point of small order
+        let small_order_pubkey = x25519::PublicKey::from([0u8; x25519::PUBLIC_KEY_SIZE]);
+
+        config.seed_pubkeys = vec![(peer_id, vec![valid_pubkey].into_iter().collect())]
+            .into_iter()
+            .collect();
+        config.verify_seed_pubkeys().unwrap();
+
+        config.seed_pubkeys = vec![(peer_id, vec![small_order_pubkey].into_iter().collect())]
+            .into_iter()
+            .collect();
+        assert!(config.verify_seed_pubkeys().is_err());
+
+        let addr = "/ip4/127.0.0.1/tcp/6180"
+            .parse::<NetworkAddress>()
+            .unwrap()
+            .append_prod_protos(small_order_pubkey, HANDSHAKE_VERSION);
+        config.seed_addrs = vec![(peer_id, vec![addr])].into_iter().collect();
+        assert!(config.verify_seed_addrs().is_err());
+    }
+}
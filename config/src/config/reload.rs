@@ -0,0 +1,163 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::{ConfigIssue, NodeConfig};
+use serde_yaml::Value;
+use std::fmt;
+
+/// The fields of a `NodeConfig` that a running node can apply without restarting.
+pub const RELOADABLE_FIELDS: &[&str] =
+    &["logger.level", "storage.prune_window", "upstream.networks"];
+
+/// A change to a reloadable field, with the old and new values rendered for audit logs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigChange {
+    pub field: &'static str,
+    pub old: String,
+    pub new: String,
+}
+
+impl fmt::Display for ConfigChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {} -> {}", self.field, self.old, self.new)
+    }
+}
+
+impl NodeConfig {
+    /// Compares this config with `new` and returns the changes to apply. Both configs are
+    /// expected to be read with `load_config`, as `load` fills in derived fields. If any field
+    /// outside of `RELOADABLE_FIELDS` changed, the node has to be restarted and nothing should be
+    /// applied, so only the issues are returned.
+    pub fn reloadable_changes(
+        &self,
+        new: &NodeConfig,
+    ) -> Result<Vec<ConfigChange>, Vec<ConfigIssue>> {
+        let mut issues = vec![];
+        if self.storage.prune_window.is_some() != new.storage.prune_window.is_some() {
+            issues.push(ConfigIssue::new(
+                "storage.prune_window",
+                "the pruner can only be enabled or disabled by restarting the node",
+            ));
+        }
+
+        let mut old_value = to_value(self)?;
+        let mut new_value = to_value(new)?;
+        for field in RELOADABLE_FIELDS {
+            remove_field(&mut old_value, field);
+            remove_field(&mut new_value, field);
+        }
+        diff_values("", &old_value, &new_value, &mut issues);
+        if !issues.is_empty() {
+            return Err(issues);
+        }
+
+        Ok(self
+            .reloadable_values()
+            .into_iter()
+            .zip(new.reloadable_values())
+            .filter(|((_, old), (_, new))| old != new)
+            .map(|((field, old), (_, new))| ConfigChange { field, old, new })
+            .collect())
+    }
+
+    fn reloadable_values(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("logger.level", self.logger.level.to_string()),
+            (
+                "storage.prune_window",
+                format!("{:?}", self.storage.prune_window),
+            ),
+            ("upstream.networks", format!("{:?}", self.upstream.networks)),
+        ]
+    }
+}
+
+fn to_value(config: &NodeConfig) -> Result<Value, Vec<ConfigIssue>> {
+    serde_yaml::to_value(config).map_err(|e| {
+        vec![ConfigIssue::new(
+            "node_config",
+            format!("unable to serialize the config: {}", e),
+        )]
+    })
+}
+
+fn remove_field(value: &mut Value, field: &str) {
+    let mut keys: Vec<_> = field.split('.').collect();
+    let last = keys.pop().expect("Field names are not empty");
+    let parent = keys
+        .into_iter()
+        .try_fold(value, |value, key| value.get_mut(key));
+    if let Some(Value::Mapping(mapping)) = parent {
+        mapping.remove(&Value::String(last.into()));
+    }
+}
+
+/// Reports every leaf that differs between the two values, so that all the fields requiring a
+/// restart are listed at once.
+fn diff_values(path: &str, old: &Value, new: &Value, issues: &mut Vec<ConfigIssue>) {
+    match (old, new) {
+        (Value::Mapping(old), Value::Mapping(new)) => {
+            let keys = old.iter().map(|(key, _)| key).chain(
+                new.iter()
+                    .map(|(key, _)| key)
+                    .filter(|key| !old.contains_key(key)),
+            );
+            for key in keys {
+                let name = match key.as_str() {
+                    Some(name) => name.to_string(),
+                    None => format!("{:?}", key),
+                };
+                let path = if path.is_empty() {
+                    name
+                } else {
+                    format!("{}.{}", path, name)
+                };
+                diff_values(
+                    &path,
+                    old.get(key).unwrap_or(&Value::Null),
+                    new.get(key).unwrap_or(&Value::Null),
+                    issues,
+                );
+            }
+        }
+        (old, new) if old != new => issues.push(ConfigIssue::new(
+            path,
+            "can not be changed without restarting the node",
+        )),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use libra_logger::Level;
+
+    #[test]
+    fn test_reloadable_change() {
+        let old = NodeConfig::default();
+        let mut new = old.clone();
+        new.logger.level = Level::Debug;
+        new.storage.prune_window = Some(1_000);
+
+        let changes = old.reloadable_changes(&new).unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].field, "logger.level");
+        assert_eq!(changes[0].new, "DEBUG");
+        assert_eq!(changes[1].field, "storage.prune_window");
+        assert_eq!(old.reloadable_changes(&old.clone()).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_restart_required() {
+        let old = NodeConfig::default();
+        let mut new = old.clone();
+        new.logger.level = Level::Debug;
+        new.rpc.address.set_port(1);
+        new.storage.prune_window = None;
+
+        let issues = old.reloadable_changes(&new).unwrap_err();
+        let fields: Vec<_> = issues.iter().map(|issue| issue.field.as_str()).collect();
+        assert_eq!(fields, vec!["storage.prune_window", "rpc.address"]);
+    }
+}
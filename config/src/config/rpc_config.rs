@@ -9,6 +9,10 @@ use std::net::SocketAddr;
 #[serde(default, deny_unknown_fields)]
 pub struct RpcConfig {
     pub address: SocketAddr,
+    /// Whether transaction views include the write sets of the transactions, annotated. Write
+    /// sets are not stored, so the transactions are re-executed to produce them: this is meant
+    /// for test networks only.
+    pub include_write_sets: bool,
 }
 
 pub const DEFAULT_JSON_RPC_PORT: u16 = 8080;
@@ -19,6 +23,7 @@ impl Default for RpcConfig {
             address: format!("0.0.0.0:{}", DEFAULT_JSON_RPC_PORT)
                 .parse()
                 .unwrap(),
+            include_write_sets: false,
         }
     }
 }
@@ -2,8 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::config::Error;
+pub use libra_secure_storage::RetryPolicy;
 use libra_secure_storage::{
-    GitHubStorage, InMemoryStorage, NamespacedStorage, OnDiskStorage, Storage, VaultStorage,
+    GitHubStorage, InMemoryStorage, NamespacedStorage, OnDiskStorage, Pkcs11Storage, Storage,
+    VaultStorage,
 };
 use serde::{Deserialize, Serialize};
 use std::{fs::File, io::Read, path::PathBuf};
@@ -15,6 +17,7 @@ pub enum SecureBackend {
     InMemoryStorage,
     Vault(VaultConfig),
     OnDiskStorage(OnDiskStorageConfig),
+    Pkcs11(Pkcs11Config),
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -32,6 +35,21 @@ pub struct GitHubConfig {
     pub namespace: Option<String>,
 }
 
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Pkcs11Config {
+    /// The PKCS#11 module provided by the HSM vendor, this is expected to be a full path.
+    pub library: PathBuf,
+    /// The label of the token holding the keys
+    pub token_label: String,
+    /// The user PIN for logging into the token
+    pub pin: Token,
+    /// A namespace is an optional prefix of the label of the objects stored on the token. For
+    /// example, a key, S, without a namespace would be labeled S, with a namespace, N, it would be
+    /// labeled N/S.
+    pub namespace: Option<String>,
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct VaultConfig {
@@ -45,6 +63,15 @@ pub struct VaultConfig {
     pub server: String,
     /// The authorization token for accessing secrets
     pub token: Token,
+    /// The Vault Enterprise namespace in which all requests are made. Unlike namespace above,
+    /// this is a namespace of the Vault server itself, with its own secrets, policies and tokens.
+    pub enterprise_namespace: Option<String>,
+    /// If specified, the token is periodically renewed to this TTL, so that it does not expire
+    /// as long as the process is running and the token's max TTL has not been reached.
+    pub renew_ttl_secs: Option<u32>,
+    /// How requests failing with transient errors are retried
+    #[serde(default)]
+    pub retry_policy: RetryPolicy,
 }
 
 impl VaultConfig {
@@ -157,6 +184,15 @@ impl From<&SecureBackend> for Storage {
                     Storage::from(storage)
                 }
             }
+            SecureBackend::Pkcs11(config) => Storage::from(
+                Pkcs11Storage::new(
+                    &config.library,
+                    &config.token_label,
+                    &config.pin.read_token().expect("Unable to read PIN"),
+                    config.namespace.clone(),
+                )
+                .expect("Unable to open PKCS#11 session"),
+            ),
             SecureBackend::Vault(config) => Storage::from(VaultStorage::new(
                 config.server.clone(),
                 config.token.read_token().expect("Unable to read token"),
@@ -165,6 +201,9 @@ impl From<&SecureBackend> for Storage {
                     .ca_certificate
                     .as_ref()
                     .map(|_| config.ca_certificate().unwrap()),
+                config.enterprise_namespace.clone(),
+                config.renew_ttl_secs,
+                config.retry_policy.clone(),
             )),
         }
     }
@@ -187,6 +226,9 @@ mod tests {
                 server: "127.0.0.1:8200".to_string(),
                 ca_certificate: None,
                 token: Token::FromConfig("test".to_string()),
+                enterprise_namespace: None,
+                renew_ttl_secs: None,
+                retry_policy: RetryPolicy::default(),
             },
         };
 
@@ -211,6 +253,9 @@ vault:
                 server: "127.0.0.1:8200".to_string(),
                 ca_certificate: None,
                 token: Token::FromDisk(PathBuf::from("/token")),
+                enterprise_namespace: None,
+                renew_ttl_secs: None,
+                retry_policy: RetryPolicy::default(),
             },
         };
 
@@ -227,6 +272,63 @@ vault:
         serde_yaml::to_string(&from_disk).unwrap();
     }
 
+    #[test]
+    fn test_pkcs11_backend_parsing() {
+        let backend = SecureBackend::Pkcs11(Pkcs11Config {
+            library: PathBuf::from("/usr/lib/softhsm/libsofthsm2.so"),
+            token_label: "libra".to_string(),
+            pin: Token::FromDisk(PathBuf::from("/pin")),
+            namespace: Some("safety_rules".to_string()),
+        });
+
+        let text = r#"
+type: pkcs11
+library: "/usr/lib/softhsm/libsofthsm2.so"
+token_label: "libra"
+pin:
+    from_disk: "/pin"
+namespace: "safety_rules"
+        "#;
+
+        let de_backend: SecureBackend = serde_yaml::from_str(text).unwrap();
+        assert_eq!(de_backend, backend);
+        serde_yaml::to_string(&backend).unwrap();
+    }
+
+    #[test]
+    fn test_vault_renewal_and_retry_parsing() {
+        let backend = SecureBackend::Vault(VaultConfig {
+            namespace: Some("safety_rules".to_string()),
+            server: "127.0.0.1:8200".to_string(),
+            ca_certificate: None,
+            token: Token::FromDisk(PathBuf::from("/token")),
+            enterprise_namespace: Some("libra".to_string()),
+            renew_ttl_secs: Some(3600),
+            retry_policy: RetryPolicy {
+                max_retries: 5,
+                initial_delay_ms: 100,
+                max_delay_ms: 10_000,
+            },
+        });
+
+        let text = r#"
+type: vault
+namespace: "safety_rules"
+server: "127.0.0.1:8200"
+token:
+    from_disk: "/token"
+enterprise_namespace: "libra"
+renew_ttl_secs: 3600
+retry_policy:
+    max_retries: 5
+    max_delay_ms: 10000
+        "#;
+
+        let de_backend: SecureBackend = serde_yaml::from_str(text).unwrap();
+        assert_eq!(de_backend, backend);
+        serde_yaml::to_string(&backend).unwrap();
+    }
+
     #[test]
     fn test_token_reading() {
         let temppath = libra_temppath::TempPath::new();
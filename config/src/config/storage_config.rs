@@ -3,18 +3,25 @@
 
 use crate::utils;
 use serde::{Deserialize, Serialize};
-use std::{net::SocketAddr, path::PathBuf};
+use std::{collections::BTreeMap, net::SocketAddr, path::PathBuf};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct StorageConfig {
+    /// The number of transaction accumulator nodes cached in memory to serve proofs, 0 disables
+    /// the cache.
+    pub accumulator_cache_size: usize,
     pub address: SocketAddr,
     pub backup_service_port: u16,
     pub dir: PathBuf,
     pub grpc_max_receive_len: Option<i32>,
+    /// Index the committed events by type, for analytics over all the events of a type. Only the
+    /// events committed while enabled are indexed.
+    pub index_events_by_type: bool,
     /// None disables pruning. The windows is in number of versions, consider system tps
     /// (transaction per second) when calculating proper window.
     pub prune_window: Option<u64>,
+    pub rocksdb_config: RocksdbConfig,
     #[serde(skip)]
     data_dir: PathBuf,
 }
@@ -22,13 +29,16 @@ pub struct StorageConfig {
 impl Default for StorageConfig {
     fn default() -> StorageConfig {
         StorageConfig {
+            accumulator_cache_size: 100_000,
             address: "127.0.0.1:6666".parse().unwrap(),
             backup_service_port: 7777,
             dir: PathBuf::from("libradb/db"),
             grpc_max_receive_len: Some(100_000_000),
+            index_events_by_type: false,
             // At 100 tps on avg, we keep 4~5 days of history.
             // n.b. Validators have more aggressive override in the config builder.
             prune_window: Some(40_000_000),
+            rocksdb_config: RocksdbConfig::default(),
             data_dir: PathBuf::from("/opt/libra/data/common"),
         }
     }
@@ -52,3 +62,45 @@ impl StorageConfig {
         self.backup_service_port = utils::get_available_port();
     }
 }
+
+/// Tuning of the RocksDB instance backing LibraDB.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RocksdbConfig {
+    /// The maximum number of concurrent flushes and compactions.
+    pub max_background_jobs: i32,
+    /// The size of the write-ahead log in bytes above which the column families backing the
+    /// oldest log are flushed.
+    pub max_total_wal_size: u64,
+    /// Per column family tuning, by column family name.
+    pub column_families: BTreeMap<String, ColumnFamilyConfig>,
+}
+
+impl Default for RocksdbConfig {
+    fn default() -> Self {
+        Self {
+            max_background_jobs: 2,
+            max_total_wal_size: 1 << 30,
+            column_families: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ColumnFamilyConfig {
+    /// The size of the memtable of the column family in bytes, None keeps the RocksDB default.
+    pub write_buffer_size: Option<usize>,
+    /// Sync the write-ahead log to disk before acknowledging a write to the column family. A
+    /// write touching several column families is synced if any of them requires it.
+    pub sync_writes: bool,
+}
+
+impl Default for ColumnFamilyConfig {
+    fn default() -> Self {
+        Self {
+            write_buffer_size: None,
+            sync_writes: true,
+        }
+    }
+}
@@ -0,0 +1,304 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::{Identity, NetworkConfig, NodeConfig, RoleType, SecureBackend, WaypointConfig};
+use libra_network_address::parse_ip_tcp;
+use libra_secure_storage::{KVStorage, Storage};
+use std::{collections::HashMap, fmt};
+
+/// An inconsistency found in a `NodeConfig`: the field at fault and how to fix it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigIssue {
+    pub field: String,
+    pub message: String,
+}
+
+impl ConfigIssue {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+impl NodeConfig {
+    /// Cross-checks the fields of the config, and returns all the issues found rather than the
+    /// first one. Secure backends are contacted to check that they are reachable.
+    pub fn validate(&self) -> Vec<ConfigIssue> {
+        let mut issues = vec![];
+        self.validate_networks(&mut issues);
+        self.validate_waypoint(&mut issues);
+        self.validate_backends(&mut issues);
+        self.validate_ports(&mut issues);
+        self.validate_push_gateway(&mut issues);
+        issues
+    }
+
+    fn networks(&self) -> impl Iterator<Item = (String, &NetworkConfig)> {
+        self.validator_network
+            .iter()
+            .map(|network| ("validator_network".to_string(), network))
+            .chain(
+                self.full_node_networks
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, network)| (format!("full_node_networks[{}]", idx), network)),
+            )
+    }
+
+    fn validate_networks(&self, issues: &mut Vec<ConfigIssue>) {
+        match (self.base.role, &self.validator_network) {
+            (RoleType::Validator, None) => issues.push(ConfigIssue::new(
+                "validator_network",
+                "a validator needs a validator network, add one or set base.role to full_node",
+            )),
+            (RoleType::FullNode, Some(_)) => issues.push(ConfigIssue::new(
+                "validator_network",
+                "a full node can not join the validator network, remove it or set base.role to \
+                 validator",
+            )),
+            (RoleType::FullNode, None) if self.full_node_networks.is_empty() => {
+                issues.push(ConfigIssue::new(
+                    "full_node_networks",
+                    "a full node needs at least one full node network to sync from",
+                ))
+            }
+            _ => (),
+        }
+
+        let mut network_ids = HashMap::new();
+        for (idx, network) in self.full_node_networks.iter().enumerate() {
+            let field = format!("full_node_networks[{}]", idx);
+            if let Some(previous) = network_ids.insert(&network.network_id, field.clone()) {
+                issues.push(ConfigIssue::new(
+                    format!("{}.network_id", field),
+                    format!(
+                        "{:?} is already used by {}, each network needs its own id",
+                        network.network_id, previous
+                    ),
+                ));
+            }
+        }
+    }
+
+    fn validate_waypoint(&self, issues: &mut Vec<ConfigIssue>) {
+        if self.base.waypoint == WaypointConfig::None {
+            issues.push(ConfigIssue::new(
+                "base.waypoint",
+                "no waypoint is set, use from_config or from_storage",
+            ));
+        }
+    }
+
+    fn validate_push_gateway(&self, issues: &mut Vec<ConfigIssue>) {
+        if let Some(push_gateway) = &self.metrics.push_gateway {
+            if !push_gateway.url.starts_with("http://") && !push_gateway.url.starts_with("https://")
+            {
+                issues.push(ConfigIssue::new(
+                    "metrics.push_gateway.url",
+                    format!("{} is not an http url", push_gateway.url),
+                ));
+            }
+            if push_gateway.push_interval_ms == 0 {
+                issues.push(ConfigIssue::new(
+                    "metrics.push_gateway.push_interval_ms",
+                    "must be positive",
+                ));
+            }
+        }
+    }
+
+    fn validate_backends(&self, issues: &mut Vec<ConfigIssue>) {
+        let mut backends = vec![];
+        if self.base.role.is_validator() {
+            backends.push((
+                "consensus.safety_rules.backend".to_string(),
+                &self.consensus.safety_rules.backend,
+            ));
+            backends.push(("execution.backend".to_string(), &self.execution.backend));
+        }
+        if let WaypointConfig::FromStorage(backend) = &self.base.waypoint {
+            backends.push(("base.waypoint".to_string(), backend));
+        }
+        for (field, network) in self.networks() {
+            if let Identity::FromStorage(identity) = &network.identity {
+                backends.push((format!("{}.identity.backend", field), &identity.backend));
+            }
+        }
+
+        for (field, backend) in backends {
+            if let Err(message) = check_backend(backend) {
+                issues.push(ConfigIssue::new(field, message));
+            }
+        }
+    }
+
+    fn validate_ports(&self, issues: &mut Vec<ConfigIssue>) {
+        let mut ports = vec![
+            ("rpc.address".to_string(), self.rpc.address.port()),
+            ("storage.address".to_string(), self.storage.address.port()),
+            (
+                "storage.backup_service_port".to_string(),
+                self.storage.backup_service_port,
+            ),
+            (
+                "debug_interface.admission_control_node_debug_port".to_string(),
+                self.debug_interface.admission_control_node_debug_port,
+            ),
+            (
+                "debug_interface.metrics_server_port".to_string(),
+                self.debug_interface.metrics_server_port,
+            ),
+            (
+                "debug_interface.public_metrics_server_port".to_string(),
+                self.debug_interface.public_metrics_server_port,
+            ),
+        ];
+        for (field, network) in self.networks() {
+            if let Some(((_, port), _)) = parse_ip_tcp(network.listen_address.as_slice()) {
+                ports.push((format!("{}.listen_address", field), port));
+            }
+        }
+
+        let mut used = HashMap::new();
+        // Port 0 lets the OS pick a free port
+        for (field, port) in ports.into_iter().filter(|(_, port)| *port != 0) {
+            if let Some(previous) = used.insert(port, field.clone()) {
+                issues.push(ConfigIssue::new(
+                    field,
+                    format!("port {} is already used by {}", port, previous),
+                ));
+            }
+        }
+    }
+}
+
+/// Checks that the credentials of a backend can be read and that it answers requests.
+fn check_backend(backend: &SecureBackend) -> Result<(), String> {
+    match backend {
+        SecureBackend::InMemoryStorage => return Ok(()),
+        SecureBackend::OnDiskStorage(config) => {
+            let path = config.path();
+            return match path.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() && !dir.exists() => Err(format!(
+                    "directory {} of the on disk storage does not exist",
+                    dir.display()
+                )),
+                _ => Ok(()),
+            };
+        }
+        SecureBackend::Pkcs11(config) => {
+            config
+                .pin
+                .read_token()
+                .map_err(|e| format!("unable to read the PIN: {}", e))?;
+            return if config.library.exists() {
+                Ok(())
+            } else {
+                Err(format!(
+                    "PKCS#11 library {} does not exist",
+                    config.library.display()
+                ))
+            };
+        }
+        SecureBackend::GitHub(config) => {
+            config
+                .token
+                .read_token()
+                .map_err(|e| format!("unable to read the token: {}", e))?;
+        }
+        SecureBackend::Vault(config) => {
+            config
+                .token
+                .read_token()
+                .map_err(|e| format!("unable to read the token: {}", e))?;
+            if config.ca_certificate.is_some() {
+                config
+                    .ca_certificate()
+                    .map_err(|e| format!("unable to read the CA certificate: {}", e))?;
+            }
+        }
+    }
+
+    let storage: Storage = backend.into();
+    storage
+        .available()
+        .map_err(|e| format!("secure storage is not reachable: {}", e))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{config::PushGatewayConfig, network_id::NetworkId};
+    use libra_types::waypoint::Waypoint;
+
+    fn validator_config() -> NodeConfig {
+        let mut config = NodeConfig::default();
+        config.base.waypoint = WaypointConfig::FromConfig(Waypoint::default());
+        config.validator_network = Some(NetworkConfig::network_with_id(NetworkId::Validator));
+        config
+    }
+
+    #[test]
+    fn test_valid_config() {
+        assert_eq!(validator_config().validate(), vec![]);
+    }
+
+    #[test]
+    fn test_role_and_networks() {
+        let mut config = validator_config();
+        config.validator_network = None;
+        let issues = config.validate();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].field, "validator_network");
+
+        let mut config = validator_config();
+        config.base.role = RoleType::FullNode;
+        assert_eq!(config.validate()[0].field, "validator_network");
+    }
+
+    #[test]
+    fn test_missing_waypoint() {
+        let mut config = validator_config();
+        config.base.waypoint = WaypointConfig::None;
+        assert_eq!(config.validate()[0].field, "base.waypoint");
+    }
+
+    #[test]
+    fn test_port_collision() {
+        let mut config = validator_config();
+        config.debug_interface.metrics_server_port = config.rpc.address.port();
+        let issues = config.validate();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].field, "debug_interface.metrics_server_port");
+    }
+
+    #[test]
+    fn test_push_gateway() {
+        let mut config = validator_config();
+        config.metrics.push_gateway = Some(PushGatewayConfig {
+            url: "pushgateway:9091".into(),
+            job: "libra".into(),
+            push_interval_ms: 0,
+        });
+        let fields: Vec<_> = config
+            .validate()
+            .into_iter()
+            .map(|issue| issue.field)
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                "metrics.push_gateway.url",
+                "metrics.push_gateway.push_interval_ms"
+            ]
+        );
+    }
+}
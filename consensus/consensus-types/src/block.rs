@@ -192,6 +192,20 @@ impl Block {
         }
     }
 
+    /// Rebuilds a proposal from its `block_data` and the `signature` of its author, e.g. once the
+    /// payload of a compact proposal is known. The signature is checked along with the rest of the
+    /// proposal by `validate_signature`.
+    pub fn new_proposal_from_block_data_and_signature(
+        block_data: BlockData,
+        signature: Ed25519Signature,
+    ) -> Self {
+        Block {
+            id: block_data.hash(),
+            block_data,
+            signature: Some(signature),
+        }
+    }
+
     /// Verifies that the proposal and the QC are correctly signed.
     /// If this is the genesis block, we skip these checks.
     pub fn validate_signature(&self, validator: &ValidatorVerifier) -> anyhow::Result<()> {
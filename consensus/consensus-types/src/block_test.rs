@@ -6,7 +6,10 @@ use crate::{
         block_test_utils::{certificate_for_genesis, *},
         Block,
     },
+    compact_proposal_msg::CompactProposalMsg,
+    proposal_msg::ProposalMsg,
     quorum_cert::QuorumCert,
+    sync_info::SyncInfo,
 };
 use libra_crypto::hash::HashValue;
 use libra_types::{validator_signer::ValidatorSigner, validator_verifier::ValidatorVerifier};
@@ -134,3 +137,39 @@ fn test_same_qc_different_authors() {
     assert!(block_round_1.id() != block_round_1_altered.id());
     assert_eq!(block_round_1.id(), block_round_1_same.id());
 }
+
+#[test]
+fn test_compact_proposal() {
+    let signer = ValidatorSigner::random(None);
+    let genesis_qc = certificate_for_genesis();
+    let payload = random_payload(3);
+    let block = Block::new_proposal(
+        payload.clone(),
+        1,
+        get_current_timestamp().as_micros() as u64,
+        genesis_qc.clone(),
+        &signer,
+    );
+    let proposal = ProposalMsg::new(block, SyncInfo::new(genesis_qc.clone(), genesis_qc, None));
+
+    let compact_proposal = CompactProposalMsg::new(&proposal).unwrap();
+    assert_eq!(compact_proposal.txn_summaries().len(), payload.len());
+    assert_eq!(compact_proposal.epoch(), proposal.epoch());
+    assert_eq!(
+        compact_proposal
+            .clone()
+            .into_proposal_msg(payload.clone())
+            .unwrap(),
+        proposal
+    );
+
+    let mut reordered_payload = payload.clone();
+    reordered_payload.swap(0, 1);
+    assert!(compact_proposal
+        .clone()
+        .into_proposal_msg(reordered_payload)
+        .is_err());
+    assert!(compact_proposal
+        .into_proposal_msg(payload[..2].to_vec())
+        .is_err());
+}
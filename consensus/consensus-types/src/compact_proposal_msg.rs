@@ -0,0 +1,140 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    block::Block,
+    block_data::{BlockData, BlockType},
+    common::{Author, Payload, Round},
+    proposal_msg::ProposalMsg,
+    quorum_cert::QuorumCert,
+    sync_info::SyncInfo,
+};
+use anyhow::{ensure, format_err, Result};
+use libra_crypto::{ed25519::Ed25519Signature, hash::CryptoHash, HashValue};
+use libra_types::{
+    account_address::AccountAddress,
+    transaction::{SignedTransaction, Transaction},
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Identifies a transaction of a compact proposal. The sender and the sequence number let the
+/// receiver find the transaction in its mempool, the hash ensures it is the proposed one.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TransactionSummary {
+    pub sender: AccountAddress,
+    pub sequence_number: u64,
+    pub hash: HashValue,
+}
+
+impl TransactionSummary {
+    pub fn new(txn: &SignedTransaction) -> Self {
+        Self {
+            sender: txn.sender(),
+            sequence_number: txn.sequence_number(),
+            hash: transaction_hash(txn),
+        }
+    }
+}
+
+/// The hash of a transaction once committed, which is also the one a compact proposal carries.
+pub fn transaction_hash(txn: &SignedTransaction) -> HashValue {
+    Transaction::UserTransaction(txn.clone()).hash()
+}
+
+/// A `ProposalMsg` whose payload is replaced by the summaries of its transactions. Most of the
+/// transactions of a proposal are already in the mempool of the receivers, which only need to
+/// fetch the others, e.g. from the proposer, to rebuild the proposal before verifying it.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CompactProposalMsg {
+    round: Round,
+    timestamp_usecs: u64,
+    quorum_cert: QuorumCert,
+    author: Author,
+    signature: Ed25519Signature,
+    txn_summaries: Vec<TransactionSummary>,
+    sync_info: SyncInfo,
+}
+
+impl CompactProposalMsg {
+    /// Builds the compact form of `proposal`, which must be a signed proposal block.
+    pub fn new(proposal: &ProposalMsg) -> Result<Self> {
+        let block = proposal.proposal();
+        let (payload, author) = match block.block_data().block_type() {
+            BlockType::Proposal { payload, author } => (payload, *author),
+            _ => return Err(format_err!("{} is not a proposal block", block)),
+        };
+        let signature = block
+            .signature()
+            .cloned()
+            .ok_or_else(|| format_err!("Missing signature in proposal {}", block))?;
+        Ok(Self {
+            round: block.round(),
+            timestamp_usecs: block.timestamp_usecs(),
+            quorum_cert: block.quorum_cert().clone(),
+            author,
+            signature,
+            txn_summaries: payload.iter().map(TransactionSummary::new).collect(),
+            sync_info: proposal.sync_info().clone(),
+        })
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.quorum_cert.certified_block().epoch()
+    }
+
+    pub fn round(&self) -> Round {
+        self.round
+    }
+
+    pub fn author(&self) -> Author {
+        self.author
+    }
+
+    pub fn txn_summaries(&self) -> &[TransactionSummary] {
+        &self.txn_summaries
+    }
+
+    /// Rebuilds the `ProposalMsg` from the transactions of its payload, in the order of the
+    /// summaries. The result still needs to be verified as any other `ProposalMsg`.
+    pub fn into_proposal_msg(self, payload: Payload) -> Result<ProposalMsg> {
+        ensure!(
+            payload.len() == self.txn_summaries.len(),
+            "Compact proposal has {} transactions, {} provided",
+            self.txn_summaries.len(),
+            payload.len()
+        );
+        for (txn, summary) in payload.iter().zip(self.txn_summaries.iter()) {
+            ensure!(
+                transaction_hash(txn) == summary.hash,
+                "Transaction {}:{} does not match hash {} of compact proposal",
+                summary.sender,
+                summary.sequence_number,
+                summary.hash
+            );
+        }
+        let block_data = BlockData::new_proposal(
+            payload,
+            self.author,
+            self.round,
+            self.timestamp_usecs,
+            self.quorum_cert,
+        );
+        Ok(ProposalMsg::new(
+            Block::new_proposal_from_block_data_and_signature(block_data, self.signature),
+            self.sync_info,
+        ))
+    }
+}
+
+impl fmt::Display for CompactProposalMsg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[compact proposal of round {} from {} with {} txns]",
+            self.round,
+            self.author.short_str(),
+            self.txn_summaries.len()
+        )
+    }
+}
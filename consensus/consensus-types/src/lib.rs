@@ -7,6 +7,7 @@ pub mod block;
 pub mod block_data;
 pub mod block_retrieval;
 pub mod common;
+pub mod compact_proposal_msg;
 pub mod epoch_retrieval;
 pub mod executed_block;
 pub mod proposal_msg;
@@ -14,6 +15,7 @@ pub mod quorum_cert;
 pub mod sync_info;
 pub mod timeout;
 pub mod timeout_certificate;
+pub mod transaction_retrieval;
 pub mod vote;
 pub mod vote_data;
 pub mod vote_msg;
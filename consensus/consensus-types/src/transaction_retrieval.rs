@@ -0,0 +1,89 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::{Payload, Round},
+    compact_proposal_msg::transaction_hash,
+};
+use anyhow::ensure;
+use libra_crypto::hash::HashValue;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// RPC to get the transactions with the given hashes from the proposal of the given round, which
+/// the receiver of a compact proposal could not find in its mempool.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TransactionRetrievalRequest {
+    epoch: u64,
+    round: Round,
+    hashes: Vec<HashValue>,
+}
+
+impl TransactionRetrievalRequest {
+    pub fn new(epoch: u64, round: Round, hashes: Vec<HashValue>) -> Self {
+        Self {
+            epoch,
+            round,
+            hashes,
+        }
+    }
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+    pub fn round(&self) -> Round {
+        self.round
+    }
+    pub fn hashes(&self) -> &[HashValue] {
+        &self.hashes
+    }
+}
+
+impl fmt::Display for TransactionRetrievalRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[TransactionRetrievalRequest of {} txns from round {} of epoch {}]",
+            self.hashes.len(),
+            self.round,
+            self.epoch
+        )
+    }
+}
+
+/// Carries the requested transactions in the order of the request, or none if the proposal is
+/// unknown.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TransactionRetrievalResponse {
+    txns: Payload,
+}
+
+impl TransactionRetrievalResponse {
+    pub fn new(txns: Payload) -> Self {
+        Self { txns }
+    }
+
+    pub fn txns(&self) -> &Payload {
+        &self.txns
+    }
+
+    pub fn take_txns(self) -> Payload {
+        self.txns
+    }
+
+    pub fn verify(&self, hashes: &[HashValue]) -> anyhow::Result<()> {
+        ensure!(
+            self.txns.len() == hashes.len(),
+            "Requested {} txns, got {}",
+            hashes.len(),
+            self.txns.len()
+        );
+        for (txn, hash) in self.txns.iter().zip(hashes) {
+            ensure!(
+                transaction_hash(txn) == *hash,
+                "Retrieved txn does not match hash {}",
+                hash
+            );
+        }
+        Ok(())
+    }
+}
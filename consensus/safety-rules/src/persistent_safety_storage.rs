@@ -69,15 +69,17 @@ impl PersistentSafetyStorage {
     ) -> Result<()> {
         internal_store.import_private_key(CONSENSUS_KEY, consensus_private_key)?;
         internal_store.import_private_key(EXECUTION_KEY, execution_private_key)?;
-        internal_store.set(EPOCH, Value::U64(1))?;
-        internal_store.set(LAST_VOTED_ROUND, Value::U64(0))?;
-        internal_store.set(OWNER_ACCOUNT, Value::String(author.to_string()))?;
-        internal_store.set(PREFERRED_ROUND, Value::U64(0))?;
-        internal_store.set(WAYPOINT, Value::String(waypoint.to_string()))?;
-        internal_store.set(
-            LAST_VOTE,
-            Value::Bytes(lcs::to_bytes::<Option<Vote>>(&None)?),
-        )?;
+        internal_store.batch_set(vec![
+            (EPOCH.into(), Value::U64(1)),
+            (LAST_VOTED_ROUND.into(), Value::U64(0)),
+            (OWNER_ACCOUNT.into(), Value::String(author.to_string())),
+            (PREFERRED_ROUND.into(), Value::U64(0)),
+            (WAYPOINT.into(), Value::String(waypoint.to_string())),
+            (
+                LAST_VOTE.into(),
+                Value::Bytes(lcs::to_bytes::<Option<Vote>>(&None)?),
+            ),
+        ])?;
         Ok(())
     }
 
@@ -206,6 +206,50 @@ impl SafetyRules {
         Ok(())
     }
 
+    /// Picks the consensus key this validator has in `epoch_state` from secure storage. A key
+    /// rotated on-chain may not be in storage yet when its epoch starts, in which case the signer
+    /// is left unset rather than failing: every signing request then fails as not initialized,
+    /// which makes the caller initialize again and look the key up anew, so that the validator
+    /// resumes signing as soon as the key shows up without a restart.
+    fn reconcile_validator_signer(&mut self, epoch_state: &EpochState) -> Result<(), Error> {
+        let author = self.persistent_storage.author()?;
+        let expected_key = match epoch_state.verifier.get_public_key(&author) {
+            Some(expected_key) => expected_key,
+            None => {
+                debug!("The validator is not in set!");
+                self.validator_signer = None;
+                return Ok(());
+            }
+        };
+
+        let curr_key = self.signer().ok().map(|s| s.public_key());
+        if curr_key == Some(expected_key.clone()) {
+            debug!("Validator key matches the key in validator set.");
+            return Ok(());
+        }
+
+        match self
+            .persistent_storage
+            .consensus_key_for_version(expected_key.clone())
+        {
+            Ok(consensus_key) => {
+                debug!(
+                    "Reconciled pub key for signer {} [{:#?} -> {}]",
+                    author, curr_key, expected_key
+                );
+                self.validator_signer = Some(ValidatorSigner::new(author, consensus_key));
+            }
+            Err(e) => {
+                warn!(
+                    "Validator key {} of epoch {} not found in secure storage, waiting for it: {}",
+                    expected_key, epoch_state.epoch, e
+                );
+                self.validator_signer = None;
+            }
+        }
+        Ok(())
+    }
+
     // Internal functions mapped to the public interface to enable exhaustive logging and metrics
 
     fn guarded_consensus_state(&mut self) -> Result<ConsensusState, Error> {
@@ -231,31 +275,7 @@ impl SafetyRules {
             .cloned()
             .ok_or(Error::InvalidLedgerInfo)?;
 
-        let author = self.persistent_storage.author()?;
-        if let Some(expected_key) = epoch_state.verifier.get_public_key(&author) {
-            let curr_key = self.signer().ok().map(|s| s.public_key());
-            if curr_key != Some(expected_key.clone()) {
-                let consensus_key = self
-                    .persistent_storage
-                    .consensus_key_for_version(expected_key.clone())
-                    .ok()
-                    .ok_or_else(|| {
-                        debug!("Validator key not found!");
-                        self.validator_signer = None;
-                        Error::InternalError("Validator key not found".into())
-                    })?;
-                debug!(
-                    "Reconciled pub key for signer {} [{:#?} -> {}]",
-                    author, curr_key, expected_key
-                );
-                self.validator_signer = Some(ValidatorSigner::new(author, consensus_key));
-            } else {
-                debug!("Validator key matches the key in validator set.");
-            }
-        } else {
-            debug!("The validator is not in set!");
-            self.validator_signer = None;
-        }
+        self.reconcile_validator_signer(&epoch_state)?;
 
         let current_epoch = self.persistent_storage.epoch()?;
 
@@ -1,7 +1,7 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{test_utils, Error, SafetyRules, TSafetyRules};
+use crate::{test_utils, Error, PersistentSafetyStorage, SafetyRules, TSafetyRules};
 use consensus_types::{
     block::block_test_utils::random_payload, common::Round, quorum_cert::QuorumCert,
     timeout::Timeout, vote_proposal::MaybeSignedVoteProposal,
@@ -11,7 +11,8 @@ use libra_crypto::{
     hash::{CryptoHash, HashValue},
 };
 use libra_global_constants::CONSENSUS_KEY;
-use libra_secure_storage::CryptoStorage;
+use libra_secure_storage::{CryptoStorage, OnDiskStorage, Storage};
+use libra_temppath::TempPath;
 use libra_types::{
     epoch_state::EpochState, validator_signer::ValidatorSigner,
     validator_verifier::ValidatorVerifier,
@@ -65,6 +66,7 @@ pub fn run_test_suite(safety_rules: &Callback) {
     test_reconcile_key(safety_rules);
     test_validator_not_in_set(safety_rules);
     test_key_not_in_store(safety_rules);
+    test_key_added_to_store_after_epoch_change(safety_rules);
 }
 
 fn test_bad_execution_output(safety_rules: &Callback) {
@@ -760,9 +762,83 @@ fn test_key_not_in_store(safety_rules: &Callback) {
     proof
         .ledger_info_with_sigs
         .push(a2.block().quorum_cert().ledger_info().clone());
-    let err = safety_rules.initialize(&proof).unwrap_err();
-    assert_eq!(err, Error::InternalError("Validator key not found".into()));
+    safety_rules.initialize(&proof).unwrap();
 
+    // The validator cannot sign until the key shows up in persistent storage
     let state = safety_rules.consensus_state().unwrap();
     assert_eq!(state.in_validator_set(), false);
 }
+
+// Tests for picking up a rotated validator key once it shows up in persistent storage.
+fn test_key_added_to_store_after_epoch_change(_safety_rules: &Callback) {
+    let signer = ValidatorSigner::from_int(0);
+    let storage_path = TempPath::new();
+    storage_path.create_as_file().unwrap();
+    let storage = PersistentSafetyStorage::initialize(
+        Storage::from(OnDiskStorage::new(storage_path.path().to_path_buf())),
+        signer.author(),
+        signer.private_key().clone(),
+        Ed25519PrivateKey::generate_for_testing(),
+        test_utils::validator_signers_to_waypoint(&[&signer]),
+    );
+    let mut safety_rules = Box::new(SafetyRules::new(storage, false));
+
+    let (mut proof, genesis_qc) = test_utils::make_genesis(&signer);
+    let round = genesis_qc.certified_block().round();
+
+    safety_rules.initialize(&proof).unwrap();
+
+    let a1 = test_utils::make_proposal_with_qc(round + 1, genesis_qc, &signer, None);
+    safety_rules.construct_and_sign_vote(&a1).unwrap();
+
+    // Update to an epoch with a new key which is not in persistent storage yet
+    let new_signer =
+        ValidatorSigner::new(signer.author(), Ed25519PrivateKey::generate_for_testing());
+    let mut next_epoch_state = EpochState::empty();
+    next_epoch_state.epoch = 2;
+    next_epoch_state.verifier =
+        ValidatorVerifier::new_single(signer.author(), new_signer.public_key());
+    let a2 = test_utils::make_proposal_with_parent_and_overrides(
+        vec![],
+        round + 2,
+        &a1,
+        Some(&a1),
+        &signer,
+        Some(1),
+        Some(next_epoch_state),
+        None,
+    );
+    safety_rules.construct_and_sign_vote(&a2).unwrap();
+    proof
+        .ledger_info_with_sigs
+        .push(a2.block().quorum_cert().ledger_info().clone());
+    safety_rules.initialize(&proof).unwrap();
+
+    let a3 = test_utils::make_proposal_with_parent_and_overrides(
+        vec![],
+        round + 3,
+        &a2,
+        Some(&a2),
+        &new_signer,
+        Some(2),
+        None,
+        None,
+    );
+    let err = safety_rules.construct_and_sign_vote(&a3).unwrap_err();
+    assert_eq!(err, Error::NotInitialized("validator_signer".into()));
+
+    // Once the key is added to persistent storage, initializing again picks it up
+    let mut other_storage = PersistentSafetyStorage::new(Storage::from(OnDiskStorage::new(
+        storage_path.path().to_path_buf(),
+    )));
+    other_storage
+        .internal_store()
+        .import_private_key(CONSENSUS_KEY, new_signer.private_key().clone())
+        .unwrap();
+    safety_rules.initialize(&proof).unwrap();
+    assert_eq!(
+        safety_rules.consensus_state().unwrap().in_validator_set(),
+        true
+    );
+    safety_rules.construct_and_sign_vote(&a3).unwrap();
+}
@@ -3,7 +3,7 @@
 
 use crate::{tests::suite, PersistentSafetyStorage, SafetyRulesManager};
 use libra_crypto::{ed25519::Ed25519PrivateKey, Uniform};
-use libra_secure_storage::{KVStorage, Storage, VaultStorage};
+use libra_secure_storage::{KVStorage, RetryPolicy, Storage, VaultStorage};
 use libra_types::validator_signer::ValidatorSigner;
 
 /// A test for verifying VaultStorage properly supports the SafetyRule backend.  This test
@@ -21,7 +21,15 @@ fn safety_rules(verify_vote_proposal_signature: bool) -> suite::Callback {
         let signer = ValidatorSigner::from_int(0);
         let host = "http://localhost:8200".to_string();
         let token = "root_token".to_string();
-        let mut storage = Storage::from(VaultStorage::new(host, token, None, None));
+        let mut storage = Storage::from(VaultStorage::new(
+            host,
+            token,
+            None,
+            None,
+            None,
+            None,
+            RetryPolicy::default(),
+        ));
         storage.reset_and_clear().unwrap();
 
         let waypoint = crate::test_utils::validator_signers_to_waypoint(&[&signer]);
@@ -154,6 +154,7 @@ impl BlockStore {
 
         let result = StateComputeResult::new(
             root_metadata.accu_hash,
+            root_metadata.state_root,
             root_metadata.frozen_root_hashes,
             root_metadata.num_leaves, /* num_leaves */
             vec![],                   /* parent_root_hashes */
@@ -486,6 +487,7 @@ impl BlockStore {
         let compute_result = executed_block.compute_result();
         let result = StateComputeResult::new(
             compute_result.root_hash(),
+            compute_result.state_root(),
             compute_result.frozen_subtree_roots().clone(),
             compute_result.num_leaves(),
             compute_result.parent_frozen_subtree_roots().clone(),
@@ -9,7 +9,9 @@ use crate::{
     util::mock_time_service::SimulatedTimeService,
 };
 use consensus_types::{block::Block, quorum_cert::QuorumCert};
-use execution_correctness::{ExecutionCorrectness, ExecutionCorrectnessManager};
+use execution_correctness::{
+    extract_execution_pubkey, ExecutionCorrectness, ExecutionCorrectnessManager,
+};
 use executor_test_helpers::start_storage_service;
 use executor_types::ExecutedTrees;
 use futures::channel::mpsc;
@@ -55,6 +57,7 @@ fn get_initial_data_and_qc(db: &dyn DbReader) -> (RecoveryData, QuorumCert) {
             RootMetadata::new(
                 root_executed_trees.txn_accumulator().num_leaves(),
                 root_executed_trees.state_id(),
+                root_executed_trees.state_root(),
                 frozen_root_hashes,
             ),
             vec![],
@@ -74,6 +77,7 @@ fn build_inserter(
 
     let state_computer = Arc::new(ExecutionProxy::new(
         lec_client,
+        extract_execution_pubkey(config).unwrap(),
         Arc::new(StateSyncClient::new(coordinator_sender)),
     ));
 
@@ -4,7 +4,7 @@
 use crate::{
     counters,
     epoch_manager::EpochManager,
-    network::NetworkTask,
+    network::{NetworkTask, ProposedPayloads},
     network_interface::{ConsensusNetworkEvents, ConsensusNetworkSender},
     persistent_liveness_storage::StorageWriteProxy,
     state_computer::ExecutionProxy,
@@ -12,7 +12,7 @@ use crate::{
     util::time_service::ClockTimeService,
 };
 use channel::libra_channel;
-use execution_correctness::ExecutionCorrectnessManager;
+use execution_correctness::{extract_execution_pubkey, ExecutionCorrectnessManager};
 use futures::channel::mpsc;
 use libra_config::config::NodeConfig;
 use libra_logger::prelude::*;
@@ -42,16 +42,23 @@ pub fn start_consensus(
     let storage = Arc::new(StorageWriteProxy::new(node_config, libra_db));
     let txn_manager = Arc::new(MempoolProxy::new(consensus_to_mempool_sender));
     let execution_correctness_manager = ExecutionCorrectnessManager::new(node_config);
+    let execution_public_key =
+        extract_execution_pubkey(node_config).expect("Unable to load the execution public key");
     let state_computer = Arc::new(ExecutionProxy::new(
         execution_correctness_manager.client(),
+        execution_public_key,
         state_sync_client,
     ));
-    let time_service = Arc::new(ClockTimeService::new(runtime.handle().clone()));
+    let time_service = Arc::new(
+        ClockTimeService::new(runtime.handle().clone())
+            .with_clock_skew(node_config.consensus.clock_skew_ms),
+    );
 
     let (timeout_sender, timeout_receiver) = channel::new(1_024, &counters::PENDING_ROUND_TIMEOUTS);
     let (self_sender, self_receiver) = channel::new(1_024, &counters::PENDING_SELF_MESSAGES);
 
-    let epoch_mgr = EpochManager::new(
+    let compact_proposals = node_config.consensus.compact_proposals;
+    let mut epoch_mgr = EpochManager::new(
         node_config,
         time_service,
         self_sender,
@@ -62,7 +69,12 @@ pub fn start_consensus(
         storage,
     );
 
-    let (network_task, network_receiver) = NetworkTask::new(network_events, self_receiver);
+    let (mut network_task, network_receiver) = NetworkTask::new(network_events, self_receiver);
+    if compact_proposals {
+        let proposed_payloads = ProposedPayloads::default();
+        epoch_mgr = epoch_mgr.with_compact_proposals(proposed_payloads.clone());
+        network_task = network_task.with_proposed_payloads(proposed_payloads);
+    }
 
     runtime.spawn(network_task.start());
     runtime.spawn(epoch_mgr.start(timeout_receiver, network_receiver, reconfig_events));
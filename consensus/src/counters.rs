@@ -179,6 +179,17 @@ pub static NUM_BLOCKS_IN_TREE: Lazy<IntGauge> = Lazy::new(|| {
 //     .unwrap()
 // });
 
+/// Count of the transactions of the received compact proposals since last restart.
+/// source is mempool or proposer
+pub static COMPACT_PROPOSAL_TXNS_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "libra_consensus_compact_proposal_txns_count",
+        "Count of the transactions of the received compact proposals since last restart. source is mempool or proposer",
+        &["source"]
+    )
+    .unwrap()
+});
+
 /// Histogram for the number of txns per (committed) blocks.
 pub static NUM_TXNS_PER_BLOCK: Lazy<Histogram> = Lazy::new(|| {
     register_histogram!(
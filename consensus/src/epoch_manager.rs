@@ -13,7 +13,7 @@ use crate::{
         round_state::{ExponentialTimeInterval, RoundState},
     },
     metrics_safety_rules::MetricsSafetyRules,
-    network::{IncomingBlockRetrievalRequest, NetworkReceivers, NetworkSender},
+    network::{IncomingBlockRetrievalRequest, NetworkReceivers, NetworkSender, ProposedPayloads},
     network_interface::{ConsensusMsg, ConsensusNetworkSender},
     persistent_liveness_storage::{LedgerRecoveryData, PersistentLivenessStorage, RecoveryData},
     round_manager::{RecoveryManager, RoundManager, UnverifiedEvent, VerifiedEvent},
@@ -24,12 +24,15 @@ use anyhow::{anyhow, bail, ensure, Context};
 use channel::libra_channel;
 use consensus_types::{
     common::{Author, Round},
+    compact_proposal_msg::CompactProposalMsg,
     epoch_retrieval::EpochRetrievalRequest,
+    proposal_msg::ProposalMsg,
+    transaction_retrieval::TransactionRetrievalRequest,
 };
-use futures::{select, StreamExt};
+use futures::{select, SinkExt, StreamExt};
 use libra_config::config::{ConsensusConfig, ConsensusProposerType, NodeConfig};
 use libra_logger::prelude::*;
-use libra_metrics::monitor;
+use libra_metrics::{health, monitor};
 use libra_types::{
     account_address::AccountAddress,
     epoch_change::EpochChangeProof,
@@ -39,6 +42,10 @@ use libra_types::{
 use network::protocols::network::Event;
 use safety_rules::SafetyRulesManager;
 use std::{cmp::Ordering, sync::Arc, time::Duration};
+use tokio::time::interval;
+
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(1);
+const TXN_RETRIEVAL_TIMEOUT: Duration = Duration::from_millis(500);
 
 /// RecoveryManager is used to process events in order to sync up with peer if we can't recover from local consensusdb
 /// RoundManager is used for normal event handling.
@@ -78,6 +85,8 @@ pub struct EpochManager {
     storage: Arc<dyn PersistentLivenessStorage>,
     safety_rules_manager: SafetyRulesManager,
     processor: Option<RoundProcessor>,
+    // Set if our proposals are broadcast in their compact form.
+    proposed_payloads: Option<ProposedPayloads>,
 }
 
 impl EpochManager {
@@ -107,9 +116,17 @@ impl EpochManager {
             storage,
             safety_rules_manager,
             processor: None,
+            proposed_payloads: None,
         }
     }
 
+    /// Broadcasts the proposals of this node as `CompactProposalMsg`s, keeping their payload in
+    /// `proposed_payloads` for the `NetworkTask` to serve.
+    pub fn with_compact_proposals(mut self, proposed_payloads: ProposedPayloads) -> Self {
+        self.proposed_payloads = Some(proposed_payloads);
+        self
+    }
+
     fn epoch_state(&self) -> &EpochState {
         match self
             .processor
@@ -236,6 +253,131 @@ impl EpochManager {
         }
     }
 
+    /// Checks that the compact proposal comes from its author, a validator of the current epoch,
+    /// for a round that is not stale, then rebuilds it in a separate task so the fetch of its
+    /// missing transactions does not block the event loop. The rebuilt `ProposalMsg` is sent back
+    /// to the loop through `self_sender`.
+    fn process_compact_proposal(
+        &mut self,
+        compact_proposal: CompactProposalMsg,
+        peer_id: AccountAddress,
+    ) -> anyhow::Result<()> {
+        let author = compact_proposal.author();
+        ensure!(
+            author == peer_id,
+            "[EpochManager] {} sent by {} instead of its author",
+            compact_proposal,
+            peer_id
+        );
+        ensure!(
+            self.epoch_state()
+                .verifier
+                .get_public_key(&author)
+                .is_some(),
+            "[EpochManager] {} from a non-validator",
+            compact_proposal
+        );
+        if let RoundProcessor::Normal(p) = self.processor_mut() {
+            let current_round = p.round_state().current_round();
+            ensure!(
+                compact_proposal.round() >= current_round,
+                "[EpochManager] Stale {}, current round {}",
+                compact_proposal,
+                current_round
+            );
+        }
+
+        let txn_manager = self.txn_manager.clone();
+        let network_sender = self.network_sender.clone();
+        let mut self_sender = self.self_sender.clone();
+        tokio::spawn(async move {
+            let proposal = match Self::rebuild_proposal(
+                txn_manager,
+                network_sender,
+                compact_proposal,
+                peer_id,
+            )
+            .await
+            {
+                Ok(proposal) => proposal,
+                Err(e) => {
+                    counters::ERROR_COUNT.inc();
+                    error!("{:?}", e);
+                    return;
+                }
+            };
+            let msg = Event::Message((peer_id, ConsensusMsg::ProposalMsg(Box::new(proposal))));
+            if let Err(e) = self_sender.send(Ok(msg)).await {
+                warn!(
+                    "[EpochManager] Failed to send a rebuilt proposal to self: {:?}",
+                    e
+                );
+            }
+        });
+        Ok(())
+    }
+
+    /// Rebuilds the proposal from the transactions in mempool, fetching the missing ones from the
+    /// peer that sent the compact proposal.
+    async fn rebuild_proposal(
+        txn_manager: Arc<dyn TxnManager>,
+        mut network_sender: ConsensusNetworkSender,
+        compact_proposal: CompactProposalMsg,
+        peer_id: AccountAddress,
+    ) -> anyhow::Result<ProposalMsg> {
+        let mut txns = txn_manager
+            .get_txns(compact_proposal.txn_summaries())
+            .await
+            .unwrap_or_else(|e| {
+                warn!("[EpochManager] Failed to get txns from mempool: {:?}", e);
+                vec![None; compact_proposal.txn_summaries().len()]
+            });
+        let missing_hashes: Vec<_> = compact_proposal
+            .txn_summaries()
+            .iter()
+            .zip(txns.iter())
+            .filter(|(_, txn)| txn.is_none())
+            .map(|(summary, _)| summary.hash)
+            .collect();
+        counters::COMPACT_PROPOSAL_TXNS_COUNT
+            .with_label_values(&["mempool"])
+            .inc_by((txns.len() - missing_hashes.len()) as i64);
+
+        if !missing_hashes.is_empty() {
+            counters::COMPACT_PROPOSAL_TXNS_COUNT
+                .with_label_values(&["proposer"])
+                .inc_by(missing_hashes.len() as i64);
+            let request = TransactionRetrievalRequest::new(
+                compact_proposal.epoch(),
+                compact_proposal.round(),
+                missing_hashes.clone(),
+            );
+            let msg = ConsensusMsg::TransactionRetrievalRequest(Box::new(request));
+            let response = match monitor!(
+                "txn_retrieval",
+                network_sender
+                    .send_rpc(peer_id, msg, TXN_RETRIEVAL_TIMEOUT)
+                    .await?
+            ) {
+                ConsensusMsg::TransactionRetrievalResponse(response) => *response,
+                _ => bail!("[EpochManager] Invalid response to transaction retrieval"),
+            };
+            response
+                .verify(&missing_hashes)
+                .context("[EpochManager] Invalid transaction retrieval response")?;
+            let mut retrieved_txns = response.take_txns().into_iter();
+            for txn in txns.iter_mut().filter(|txn| txn.is_none()) {
+                *txn = retrieved_txns.next();
+            }
+        }
+
+        let payload = txns
+            .into_iter()
+            .collect::<Option<_>>()
+            .ok_or_else(|| anyhow!("[EpochManager] Missing txns of compact proposal"))?;
+        compact_proposal.into_proposal_msg(payload)
+    }
+
     async fn start_new_epoch(&mut self, proof: EpochChangeProof) -> anyhow::Result<()> {
         let ledger_info = proof
             .verify(self.epoch_state())
@@ -302,12 +444,15 @@ impl EpochManager {
 
         info!("Create ProposerElection");
         let proposer_election = self.create_proposer_election(&epoch_state);
-        let network_sender = NetworkSender::new(
+        let mut network_sender = NetworkSender::new(
             self.author,
             self.network_sender.clone(),
             self.self_sender.clone(),
             epoch_state.verifier.clone(),
         );
+        if let Some(proposed_payloads) = &self.proposed_payloads {
+            network_sender = network_sender.with_compact_proposals(proposed_payloads.clone());
+        }
 
         let mut processor = RoundManager::new(
             epoch_state,
@@ -413,6 +558,14 @@ impl EpochManager {
                     self.process_different_epoch(event.epoch(), peer_id).await?;
                 }
             }
+            ConsensusMsg::CompactProposalMsg(compact_proposal) => {
+                let msg_epoch = compact_proposal.epoch();
+                if msg_epoch == self.epoch() {
+                    self.process_compact_proposal(*compact_proposal, peer_id)?;
+                } else {
+                    self.process_different_epoch(msg_epoch, peer_id).await?;
+                }
+            }
             ConsensusMsg::EpochChangeProof(proof) => {
                 let msg_epoch = proof.epoch()?;
                 if msg_epoch == self.epoch() {
@@ -500,10 +653,18 @@ impl EpochManager {
         if let Some(payload) = reconfig_events.next().await {
             self.start_processor(payload).await;
         }
+        // The loop wakes up for the heartbeats even without events, so they only stop if an event
+        // handler is stuck
+        let health = health::register_heartbeat("consensus", HEARTBEAT_INTERVAL * 10);
+        let mut heartbeats = interval(HEARTBEAT_INTERVAL).fuse();
         loop {
             if let Err(e) = monitor!(
                 "main_loop",
                 select! {
+                    _ = heartbeats.select_next_some() => {
+                        health.heartbeat();
+                        Ok(())
+                    }
                     payload = reconfig_events.select_next_some() => {
                         monitor!("reconfig", self.start_processor(payload).await);
                         Ok(())
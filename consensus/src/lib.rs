@@ -24,6 +24,8 @@ mod network_tests;
 mod pending_votes;
 mod persistent_liveness_storage;
 mod round_manager;
+#[cfg(test)]
+mod simulation_test;
 mod state_computer;
 mod state_replication;
 #[cfg(any(test, feature = "fuzzing"))]
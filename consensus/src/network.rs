@@ -10,9 +10,11 @@ use bytes::Bytes;
 use channel::{self, libra_channel, message_queues::QueueStyle};
 use consensus_types::{
     block_retrieval::{BlockRetrievalRequest, BlockRetrievalResponse},
-    common::Author,
+    common::{Author, Payload, Round},
+    compact_proposal_msg::{transaction_hash, CompactProposalMsg},
     proposal_msg::ProposalMsg,
     sync_info::SyncInfo,
+    transaction_retrieval::{TransactionRetrievalRequest, TransactionRetrievalResponse},
     vote_msg::VoteMsg,
 };
 use futures::{channel::oneshot, stream::select, SinkExt, Stream, StreamExt, TryStreamExt};
@@ -24,11 +26,16 @@ use libra_types::{
 };
 use network::protocols::{network::Event, rpc::error::RpcError};
 use std::{
+    collections::{HashMap, VecDeque},
     mem::{discriminant, Discriminant},
     num::NonZeroUsize,
+    sync::{Arc, Mutex},
     time::Duration,
 };
 
+/// Number of recent proposals whose payload is kept to serve transaction retrievals.
+const MAX_PROPOSED_PAYLOADS: usize = 10;
+
 /// The block retrieval request is used internally for implementing RPC: the callback is executed
 /// for carrying the response
 #[derive(Debug)]
@@ -37,6 +44,47 @@ pub struct IncomingBlockRetrievalRequest {
     pub response_sender: oneshot::Sender<Result<Bytes, RpcError>>,
 }
 
+/// The payloads of the recent proposals of this node, shared between the `NetworkSender` that
+/// broadcasts the compact proposals and the `NetworkTask` that serves the transactions the
+/// receivers miss.
+#[derive(Clone, Default)]
+pub struct ProposedPayloads {
+    payloads: Arc<Mutex<VecDeque<(u64, Round, Payload)>>>,
+}
+
+impl ProposedPayloads {
+    fn insert(&self, epoch: u64, round: Round, payload: Payload) {
+        let mut payloads = self.payloads.lock().unwrap();
+        if payloads.len() == MAX_PROPOSED_PAYLOADS {
+            payloads.pop_front();
+        }
+        payloads.push_back((epoch, round, payload));
+    }
+
+    /// Returns the requested transactions in the order of the request, or none if the proposal
+    /// is unknown or doesn't hold all of them.
+    fn get(&self, request: &TransactionRetrievalRequest) -> Payload {
+        let payloads = self.payloads.lock().unwrap();
+        let payload = match payloads
+            .iter()
+            .find(|(epoch, round, _)| *epoch == request.epoch() && *round == request.round())
+        {
+            Some((_, _, payload)) => payload,
+            None => return vec![],
+        };
+        let txns_by_hash: HashMap<_, _> = payload
+            .iter()
+            .map(|txn| (transaction_hash(txn), txn))
+            .collect();
+        request
+            .hashes()
+            .iter()
+            .map(|hash| txns_by_hash.get(hash).map(|txn| (*txn).clone()))
+            .collect::<Option<_>>()
+            .unwrap_or_default()
+    }
+}
+
 /// Just a convenience struct to keep all the network proxy receiving queues in one place.
 /// Will be returned by the NetworkTask upon startup.
 pub struct NetworkReceivers {
@@ -58,6 +106,8 @@ pub struct NetworkSender {
     // Note that we do not support self rpc requests as it might cause infinite recursive calls.
     self_sender: channel::Sender<anyhow::Result<Event<ConsensusMsg>>>,
     validators: ValidatorVerifier,
+    // Set if the proposals are sent to the other validators in their compact form.
+    proposed_payloads: Option<ProposedPayloads>,
 }
 
 impl NetworkSender {
@@ -72,9 +122,17 @@ impl NetworkSender {
             network_sender,
             self_sender,
             validators,
+            proposed_payloads: None,
         }
     }
 
+    /// Sends the proposals to the other validators as `CompactProposalMsg`s, keeping their
+    /// payload in `proposed_payloads` to serve the transactions the receivers miss.
+    pub fn with_compact_proposals(mut self, proposed_payloads: ProposedPayloads) -> Self {
+        self.proposed_payloads = Some(proposed_payloads);
+        self
+    }
+
     /// Tries to retrieve num of blocks backwards starting from id from the given peer: the function
     /// returns a future that is fulfilled with BlockRetrievalResponse.
     pub async fn request_block(
@@ -118,18 +176,49 @@ impl NetworkSender {
     /// out. It does not give indication about when the message is delivered to the recipients,
     /// as well as there is no indication about the network failures.
     pub async fn broadcast_proposal(&mut self, proposal: ProposalMsg) {
+        let compact_msg = match (&self.proposed_payloads, proposal.proposal().payload()) {
+            (Some(proposed_payloads), Some(payload)) => match CompactProposalMsg::new(&proposal) {
+                Ok(compact_proposal) => {
+                    proposed_payloads.insert(
+                        proposal.epoch(),
+                        proposal.proposal().round(),
+                        payload.clone(),
+                    );
+                    Some(ConsensusMsg::CompactProposalMsg(Box::new(compact_proposal)))
+                }
+                Err(e) => {
+                    error!("Failed to compact proposal {}: {:?}", proposal, e);
+                    None
+                }
+            },
+            _ => None,
+        };
         let msg = ConsensusMsg::ProposalMsg(Box::new(proposal));
         // counters::UNWRAPPED_PROPOSAL_SIZE_BYTES.observe(msg.message.len() as f64);
-        self.broadcast(msg).await
+        match compact_msg {
+            // We always deliver the full proposal to ourself.
+            Some(compact_msg) => {
+                self.send_to_self(msg).await;
+                self.send_to_others(compact_msg);
+            }
+            None => self.broadcast(msg).await,
+        }
     }
 
     async fn broadcast(&mut self, msg: ConsensusMsg) {
         // Directly send the message to ourself without going through network.
-        let self_msg = Event::Message((self.author, msg.clone()));
+        self.send_to_self(msg.clone()).await;
+        self.send_to_others(msg);
+    }
+
+    async fn send_to_self(&mut self, msg: ConsensusMsg) {
+        let self_msg = Event::Message((self.author, msg));
         if let Err(err) = self.self_sender.send(Ok(self_msg)).await {
             error!("Error broadcasting to self: {:?}", err);
         }
+    }
 
+    fn send_to_others(&mut self, msg: ConsensusMsg) {
         // Get the list of validators excluding our own account address. Note the
         // ordering is not important in this case.
         let self_author = self.author;
@@ -213,6 +302,7 @@ pub struct NetworkTask {
     >,
     block_retrieval_tx: libra_channel::Sender<AccountAddress, IncomingBlockRetrievalRequest>,
     all_events: Box<dyn Stream<Item = anyhow::Result<Event<ConsensusMsg>>> + Send + Unpin>,
+    proposed_payloads: Option<ProposedPayloads>,
 }
 
 impl NetworkTask {
@@ -238,6 +328,7 @@ impl NetworkTask {
                 consensus_messages_tx,
                 block_retrieval_tx,
                 all_events,
+                proposed_payloads: None,
             },
             NetworkReceivers {
                 consensus_messages,
@@ -246,6 +337,13 @@ impl NetworkTask {
         )
     }
 
+    /// Serves the transactions of the compact proposals broadcast by the `NetworkSender` sharing
+    /// `proposed_payloads`.
+    pub fn with_proposed_payloads(mut self, proposed_payloads: ProposedPayloads) -> Self {
+        self.proposed_payloads = Some(proposed_payloads);
+        self
+    }
+
     pub async fn start(mut self) {
         while let Some(Ok(message)) = self.all_events.next().await {
             match message {
@@ -271,6 +369,24 @@ impl NetworkTask {
                             warn!("libra channel closed: {:?}", e);
                         }
                     }
+                    ConsensusMsg::TransactionRetrievalRequest(request) => {
+                        debug!("Received transaction retrieval request {}", request);
+                        let txns = self
+                            .proposed_payloads
+                            .as_ref()
+                            .map_or_else(Vec::new, |payloads| payloads.get(&request));
+                        let response = ConsensusMsg::TransactionRetrievalResponse(Box::new(
+                            TransactionRetrievalResponse::new(txns),
+                        ));
+                        let result = lcs::to_bytes(&response).and_then(|bytes| {
+                            callback
+                                .send(Ok(bytes.into()))
+                                .map_err(|e| lcs::Error::Custom(format!("{:?}", e)))
+                        });
+                        if let Err(e) = result {
+                            warn!("Failed to serve transaction retrieval: {:?}", e);
+                        }
+                    }
                     _ => {
                         warn!("Unexpected msg from {}: {:?}", peer_id, msg);
                         continue;
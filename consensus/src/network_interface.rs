@@ -7,9 +7,11 @@ use crate::counters;
 use channel::message_queues::QueueStyle;
 use consensus_types::{
     block_retrieval::{BlockRetrievalRequest, BlockRetrievalResponse},
+    compact_proposal_msg::CompactProposalMsg,
     epoch_retrieval::EpochRetrievalRequest,
     proposal_msg::ProposalMsg,
     sync_info::SyncInfo,
+    transaction_retrieval::{TransactionRetrievalRequest, TransactionRetrievalResponse},
     vote_msg::VoteMsg,
 };
 use libra_metrics::IntCounterVec;
@@ -47,6 +49,13 @@ pub enum ConsensusMsg {
     /// VoteMsg is the struct that is ultimately sent by the voter in response for receiving a
     /// proposal.
     VoteMsg(Box<VoteMsg>),
+    /// ProposalMsg whose payload only carries the summaries of the transactions, which the
+    /// receivers look up in their mempool.
+    CompactProposalMsg(Box<CompactProposalMsg>),
+    /// RPC to get the transactions of a compact proposal missing from the mempool of the receiver.
+    TransactionRetrievalRequest(Box<TransactionRetrievalRequest>),
+    /// Carries the returned transactions.
+    TransactionRetrievalResponse(Box<TransactionRetrievalResponse>),
 }
 
 /// The interface from Network to Consensus layer.
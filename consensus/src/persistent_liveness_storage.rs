@@ -126,15 +126,22 @@ impl LedgerRecoveryData {
 
 pub struct RootMetadata {
     pub accu_hash: HashValue,
+    pub state_root: HashValue,
     pub frozen_root_hashes: Vec<HashValue>,
     pub num_leaves: Version,
 }
 
 impl RootMetadata {
-    pub fn new(num_leaves: u64, accu_hash: HashValue, frozen_root_hashes: Vec<HashValue>) -> Self {
+    pub fn new(
+        num_leaves: u64,
+        accu_hash: HashValue,
+        state_root: HashValue,
+        frozen_root_hashes: Vec<HashValue>,
+    ) -> Self {
         Self {
             num_leaves,
             accu_hash,
+            state_root,
             frozen_root_hashes,
         }
     }
@@ -145,7 +152,12 @@ impl RootMetadata {
 
     #[cfg(any(test, feature = "fuzzing"))]
     pub fn new_empty() -> Self {
-        Self::new(0, *libra_crypto::hash::ACCUMULATOR_PLACEHOLDER_HASH, vec![])
+        Self::new(
+            0,
+            *libra_crypto::hash::ACCUMULATOR_PLACEHOLDER_HASH,
+            *libra_crypto::hash::SPARSE_MERKLE_PLACEHOLDER_HASH,
+            vec![],
+        )
     }
 }
 
@@ -365,6 +377,7 @@ impl PersistentLivenessStorage for StorageWriteProxy {
             RootMetadata::new(
                 root_executed_trees.txn_accumulator().num_leaves(),
                 root_executed_trees.state_id(),
+                root_executed_trees.state_root(),
                 frozen_root_hashes,
             ),
             quorum_certs,
@@ -0,0 +1,602 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Deterministic simulation of several consensus instances.
+//!
+//! All the nodes run on a single thread and share a simulated clock. Messages between nodes and
+//! round timeouts are events of a single queue ordered by delivery time, and the latency and drops
+//! of every message are drawn from a seeded RNG: a run is entirely determined by its seed, so a
+//! liveness or safety failure can be replayed from the seed it was found with.
+
+use crate::{
+    block_storage::{BlockReader, BlockStore},
+    liveness::{
+        proposal_generator::ProposalGenerator,
+        rotating_proposer_election::RotatingProposer,
+        round_state::{ExponentialTimeInterval, RoundState},
+    },
+    metrics_safety_rules::MetricsSafetyRules,
+    network::NetworkSender,
+    network_interface::{ConsensusMsg, ConsensusNetworkSender},
+    round_manager::{RoundManager, UnverifiedEvent, VerifiedEvent},
+    test_utils::{MockStateComputer, MockStorage, MockTransactionManager},
+    util::time_service::{ScheduledTask, TimeService},
+};
+use channel::{self, libra_channel, message_queues::QueueStyle};
+use consensus_types::{
+    block_retrieval::{BlockRetrievalResponse, BlockRetrievalStatus},
+    common::{Author, Payload, Round},
+};
+use futures::{
+    channel::mpsc,
+    executor::block_on,
+    future::{select, FutureExt},
+    pin_mut, StreamExt,
+};
+use libra_crypto::{ed25519::Ed25519PrivateKey, HashValue, Uniform};
+use libra_logger::prelude::*;
+use libra_secure_storage::Storage;
+use libra_types::{
+    block_info::BlockInfo,
+    epoch_state::EpochState,
+    ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    validator_verifier::random_validator_verifier,
+    waypoint::Waypoint,
+    PeerId,
+};
+use network::{
+    peer_manager::{ConnectionRequestSender, PeerManagerRequest, PeerManagerRequestSender},
+    protocols::network::{Event, NewNetworkSender},
+    ProtocolId,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use safety_rules::{PersistentSafetyStorage, SafetyRulesManager};
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// How messages travel between nodes. Block retrieval requests are answered instantly, but can be
+/// dropped like any other message.
+#[derive(Clone, Copy, Debug)]
+pub struct NetworkModel {
+    pub min_latency: Duration,
+    pub max_latency: Duration,
+    pub drop_probability: f64,
+}
+
+impl Default for NetworkModel {
+    fn default() -> Self {
+        Self {
+            min_latency: Duration::from_millis(1),
+            max_latency: Duration::from_millis(100),
+            drop_probability: 0.0,
+        }
+    }
+}
+
+enum Action {
+    Deliver {
+        from: Author,
+        to: usize,
+        msg: ConsensusMsg,
+    },
+    Timer {
+        node: usize,
+        task: Box<dyn ScheduledTask>,
+    },
+}
+
+/// An action of the queue, ordered by time and then by insertion order.
+struct Scheduled {
+    time: Duration,
+    seq: u64,
+    action: Action,
+}
+
+impl PartialEq for Scheduled {
+    fn eq(&self, other: &Self) -> bool {
+        (self.time, self.seq) == (other.time, other.seq)
+    }
+}
+
+impl Eq for Scheduled {}
+
+impl PartialOrd for Scheduled {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Scheduled {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // BinaryHeap is a max-heap, the earliest action must be the greatest
+        (other.time, other.seq).cmp(&(self.time, self.seq))
+    }
+}
+
+#[derive(Default)]
+struct EventQueue {
+    now: Duration,
+    seq: u64,
+    queue: BinaryHeap<Scheduled>,
+}
+
+impl EventQueue {
+    fn schedule(&mut self, time: Duration, action: Action) {
+        self.seq += 1;
+        self.queue.push(Scheduled {
+            time,
+            seq: self.seq,
+            action,
+        });
+    }
+}
+
+/// The time service of a simulated node: timeouts are scheduled on the simulation queue.
+struct SimulatedClock {
+    node: usize,
+    events: Arc<Mutex<EventQueue>>,
+}
+
+impl TimeService for SimulatedClock {
+    fn run_after(&self, timeout: Duration, task: Box<dyn ScheduledTask>) {
+        let mut events = self.events.lock().unwrap();
+        let time = events.now + timeout;
+        events.schedule(
+            time,
+            Action::Timer {
+                node: self.node,
+                task,
+            },
+        );
+    }
+
+    fn get_current_timestamp(&self) -> Duration {
+        self.events.lock().unwrap().now
+    }
+
+    fn sleep(&self, t: Duration) {
+        self.events.lock().unwrap().now += t;
+    }
+}
+
+struct SimulatedNode {
+    round_manager: RoundManager,
+    network_reqs_rx: libra_channel::Receiver<(PeerId, ProtocolId), PeerManagerRequest>,
+    self_receiver: channel::Receiver<anyhow::Result<Event<ConsensusMsg>>>,
+    timeout_receiver: channel::Receiver<Round>,
+    commit_receiver: mpsc::UnboundedReceiver<LedgerInfoWithSignatures>,
+    _state_sync_receiver: mpsc::UnboundedReceiver<Payload>,
+    committed: Vec<BlockInfo>,
+}
+
+enum Input {
+    Start,
+    Message(Author, VerifiedEvent),
+    Timeout(Round),
+}
+
+pub struct Simulation {
+    nodes: Vec<SimulatedNode>,
+    authors: Vec<Author>,
+    block_stores: Vec<Arc<BlockStore>>,
+    events: Arc<Mutex<EventQueue>>,
+    model: NetworkModel,
+    rng: StdRng,
+}
+
+impl Simulation {
+    pub fn new(num_nodes: usize, model: NetworkModel, seed: u64) -> Self {
+        let (signers, validators) = random_validator_verifier(num_nodes, None, false);
+        let authors: Vec<_> = signers.iter().map(|signer| signer.author()).collect();
+        let validator_set = (&validators).into();
+        let waypoint =
+            Waypoint::new_epoch_boundary(&LedgerInfo::mock_genesis(Some(validator_set))).unwrap();
+        // Blocks need increasing timestamps, the clock starts after the one of genesis
+        let events = Arc::new(Mutex::new(EventQueue {
+            now: Duration::from_secs(1),
+            ..EventQueue::default()
+        }));
+
+        let mut nodes = vec![];
+        let mut block_stores = vec![];
+        for (id, signer) in signers.into_iter().enumerate() {
+            let (initial_data, storage) = MockStorage::start_for_testing((&validators).into());
+            let epoch_state = EpochState {
+                epoch: 1,
+                verifier: storage.get_validator_set().into(),
+            };
+            let author = signer.author();
+            let safety_storage = PersistentSafetyStorage::initialize(
+                Storage::from(libra_secure_storage::InMemoryStorage::new()),
+                author,
+                signer.private_key().clone(),
+                Ed25519PrivateKey::generate_for_testing(),
+                waypoint,
+            );
+            let safety_rules_manager = SafetyRulesManager::new_local(safety_storage, false);
+            let mut safety_rules =
+                MetricsSafetyRules::new(safety_rules_manager.client(), storage.clone());
+            safety_rules.perform_initialize().unwrap();
+
+            let (network_reqs_tx, network_reqs_rx) =
+                libra_channel::new(QueueStyle::FIFO, NonZeroUsize::new(1024).unwrap(), None);
+            let (connection_reqs_tx, _) =
+                libra_channel::new(QueueStyle::FIFO, NonZeroUsize::new(8).unwrap(), None);
+            let network_sender = ConsensusNetworkSender::new(
+                PeerManagerRequestSender::new(network_reqs_tx),
+                ConnectionRequestSender::new(connection_reqs_tx),
+            );
+            let (self_sender, self_receiver) = channel::new_test(1024);
+            let network = NetworkSender::new(
+                author,
+                network_sender,
+                self_sender,
+                epoch_state.verifier.clone(),
+            );
+
+            let (commit_cb_sender, commit_receiver) = mpsc::unbounded();
+            let (state_sync_client, state_sync_receiver) = mpsc::unbounded();
+            let state_computer = Arc::new(MockStateComputer::new(
+                state_sync_client,
+                commit_cb_sender,
+                Arc::clone(&storage),
+            ));
+            let time_service = Arc::new(SimulatedClock {
+                node: id,
+                events: events.clone(),
+            });
+            let block_store = Arc::new(BlockStore::new(
+                storage.clone(),
+                initial_data,
+                state_computer,
+                10, // max pruned blocks in mem
+                time_service.clone(),
+            ));
+            // Empty blocks, as the mock transaction manager generates random payloads
+            let proposal_generator = ProposalGenerator::new(
+                author,
+                block_store.clone(),
+                Arc::new(MockTransactionManager::new(None)),
+                time_service.clone(),
+                0,
+            );
+            let (timeout_sender, timeout_receiver) = channel::new_test(1024);
+            let round_state = RoundState::new(
+                Box::new(ExponentialTimeInterval::new(
+                    Duration::from_millis(1000),
+                    1.2,
+                    6,
+                )),
+                time_service,
+                timeout_sender,
+            );
+            let round_manager = RoundManager::new(
+                epoch_state,
+                block_store.clone(),
+                round_state,
+                Box::new(RotatingProposer::new(authors.clone(), 1)),
+                proposal_generator,
+                safety_rules,
+                network,
+                Arc::new(MockTransactionManager::new(None)),
+                storage,
+            );
+
+            block_stores.push(block_store);
+            nodes.push(SimulatedNode {
+                round_manager,
+                network_reqs_rx,
+                self_receiver,
+                timeout_receiver,
+                commit_receiver,
+                _state_sync_receiver: state_sync_receiver,
+                committed: vec![],
+            });
+        }
+
+        let mut simulation = Self {
+            nodes,
+            authors,
+            block_stores,
+            events,
+            model,
+            rng: StdRng::seed_from_u64(seed),
+        };
+        for node in 0..num_nodes {
+            simulation.step(node, Input::Start);
+        }
+        simulation
+    }
+
+    pub fn now(&self) -> Duration {
+        self.events.lock().unwrap().now
+    }
+
+    /// Processes all the events scheduled before `deadline`, in order.
+    pub fn run_until(&mut self, deadline: Duration) {
+        loop {
+            let next = {
+                let mut events = self.events.lock().unwrap();
+                match events.queue.peek() {
+                    Some(next) if next.time <= deadline => {
+                        let next = events.queue.pop().unwrap();
+                        events.now = events.now.max(next.time);
+                        next
+                    }
+                    _ => {
+                        events.now = events.now.max(deadline);
+                        return;
+                    }
+                }
+            };
+            match next.action {
+                Action::Deliver { from, to, msg } => self.deliver(from, to, msg),
+                Action::Timer { node, mut task } => {
+                    block_on(task.run());
+                    while let Some(Some(round)) =
+                        self.nodes[node].timeout_receiver.next().now_or_never()
+                    {
+                        self.step(node, Input::Timeout(round));
+                    }
+                }
+            }
+        }
+    }
+
+    /// The blocks committed by each node, in commit order.
+    pub fn committed(&self) -> Vec<&[BlockInfo]> {
+        self.nodes
+            .iter()
+            .map(|node| node.committed.as_slice())
+            .collect()
+    }
+
+    /// Safety: two nodes never commit different blocks at the same round.
+    pub fn check_safety(&self) {
+        let mut committed_at: HashMap<Round, HashValue> = HashMap::new();
+        for (node, committed) in self.committed().into_iter().enumerate() {
+            for block in committed {
+                let id = *committed_at
+                    .entry(block.round())
+                    .or_insert_with(|| block.id());
+                assert_eq!(
+                    id,
+                    block.id(),
+                    "Node {} committed a conflicting block at round {}",
+                    node,
+                    block.round()
+                );
+            }
+        }
+    }
+
+    /// The highest round committed by each node.
+    pub fn committed_rounds(&self) -> Vec<Round> {
+        self.nodes
+            .iter()
+            .map(|node| node.committed.last().map_or(0, |block| block.round()))
+            .collect()
+    }
+
+    fn deliver(&mut self, from: Author, to: usize, msg: ConsensusMsg) {
+        let event = match msg {
+            ConsensusMsg::ProposalMsg(_) | ConsensusMsg::VoteMsg(_) | ConsensusMsg::SyncInfo(_) => {
+                UnverifiedEvent::from(msg)
+            }
+            msg => {
+                debug!("[Simulation] Ignoring message {:?}", msg);
+                return;
+            }
+        };
+        match event.verify(&self.nodes[to].round_manager.epoch_state().verifier) {
+            Ok(event) => self.step(to, Input::Message(from, event)),
+            Err(e) => error!("[Simulation] Invalid message from {}: {:?}", from, e),
+        }
+    }
+
+    /// Runs an input through the round manager of a node, then schedules everything it sent.
+    fn step(&mut self, idx: usize, input: Input) {
+        let author = self.authors[idx];
+        let mut outbox = vec![];
+        {
+            let node = &mut self.nodes[idx];
+            let process = process_input(&mut node.round_manager, input);
+            let serve = serve_network(
+                &mut node.network_reqs_rx,
+                &mut outbox,
+                &self.authors,
+                &self.block_stores,
+                &self.model,
+                &mut self.rng,
+            );
+            pin_mut!(process);
+            pin_mut!(serve);
+            block_on(select(process, serve));
+        }
+        let node = &mut self.nodes[idx];
+        while let Some(Some(request)) = node.network_reqs_rx.next().now_or_never() {
+            handle_request(
+                request,
+                &mut outbox,
+                &self.authors,
+                &self.block_stores,
+                &self.model,
+                &mut self.rng,
+            );
+        }
+        while let Some(Some(Ok(event))) = node.self_receiver.next().now_or_never() {
+            if let Event::Message((_, msg)) = event {
+                outbox.push((idx, msg, Duration::from_secs(0)));
+            }
+        }
+        while let Some(Some(ledger_info)) = node.commit_receiver.next().now_or_never() {
+            node.committed
+                .push(ledger_info.ledger_info().commit_info().clone());
+        }
+
+        let mut events = self.events.lock().unwrap();
+        let now = events.now;
+        for (to, msg, latency) in outbox {
+            events.schedule(
+                now + latency,
+                Action::Deliver {
+                    from: author,
+                    to,
+                    msg,
+                },
+            );
+        }
+    }
+}
+
+async fn process_input(round_manager: &mut RoundManager, input: Input) {
+    let result = match input {
+        Input::Start => {
+            round_manager.start(None).await;
+            Ok(())
+        }
+        Input::Message(_, VerifiedEvent::ProposalMsg(proposal)) => {
+            round_manager.process_proposal_msg(*proposal).await
+        }
+        Input::Message(_, VerifiedEvent::VoteMsg(vote)) => {
+            round_manager.process_vote_msg(*vote).await
+        }
+        Input::Message(from_peer, VerifiedEvent::SyncInfo(sync_info)) => {
+            round_manager
+                .process_sync_info_msg(*sync_info, from_peer)
+                .await
+        }
+        Input::Timeout(round) => round_manager.process_local_timeout(round).await,
+    };
+    if let Err(e) = result {
+        debug!("[Simulation] Error processing input: {:?}", e);
+    }
+}
+
+/// Handles the requests a node sends while it processes an input. Block retrievals have to be
+/// answered concurrently, as the node waits for them.
+async fn serve_network(
+    network_reqs_rx: &mut libra_channel::Receiver<(PeerId, ProtocolId), PeerManagerRequest>,
+    outbox: &mut Vec<(usize, ConsensusMsg, Duration)>,
+    authors: &[Author],
+    block_stores: &[Arc<BlockStore>],
+    model: &NetworkModel,
+    rng: &mut StdRng,
+) {
+    while let Some(request) = network_reqs_rx.next().await {
+        handle_request(request, outbox, authors, block_stores, model, rng);
+    }
+}
+
+fn handle_request(
+    request: PeerManagerRequest,
+    outbox: &mut Vec<(usize, ConsensusMsg, Duration)>,
+    authors: &[Author],
+    block_stores: &[Arc<BlockStore>],
+    model: &NetworkModel,
+    rng: &mut StdRng,
+) {
+    let (peer_id, data) = match &request {
+        PeerManagerRequest::SendMessage(peer_id, msg) => (*peer_id, &msg.mdata),
+        PeerManagerRequest::SendRpc(peer_id, rpc) => (*peer_id, &rpc.data),
+    };
+    let to = match authors.iter().position(|author| *author == peer_id) {
+        Some(to) => to,
+        None => return,
+    };
+    let msg: ConsensusMsg = match lcs::from_bytes(data) {
+        Ok(msg) => msg,
+        Err(e) => {
+            error!("[Simulation] Undecodable message to {}: {:?}", peer_id, e);
+            return;
+        }
+    };
+    // Dropping an rpc drops its response channel, which fails the request right away
+    if rng.gen_bool(model.drop_probability) {
+        return;
+    }
+    match (request, msg) {
+        (PeerManagerRequest::SendMessage(..), msg) => {
+            let latency = rng.gen_range(model.min_latency, model.max_latency);
+            outbox.push((to, msg, latency));
+        }
+        (PeerManagerRequest::SendRpc(_, rpc), ConsensusMsg::BlockRetrievalRequest(request)) => {
+            let mut blocks = vec![];
+            let mut status = BlockRetrievalStatus::Succeeded;
+            let mut id = request.block_id();
+            while (blocks.len() as u64) < request.num_blocks() {
+                if let Some(executed_block) = block_stores[to].get_block(id) {
+                    id = executed_block.parent_id();
+                    blocks.push(executed_block.block().clone());
+                } else {
+                    status = BlockRetrievalStatus::NotEnoughBlocks;
+                    break;
+                }
+            }
+            if blocks.is_empty() {
+                status = BlockRetrievalStatus::IdNotFound;
+            }
+            let response = ConsensusMsg::BlockRetrievalResponse(Box::new(
+                BlockRetrievalResponse::new(status, blocks),
+            ));
+            let bytes = lcs::to_bytes(&response).expect("Failed to serialize response");
+            let _ = rpc.res_tx.send(Ok(bytes.into()));
+        }
+        (_, msg) => debug!("[Simulation] Ignoring rpc {:?}", msg),
+    }
+}
+
+fn run_simulation(model: NetworkModel, seed: u64, duration: Duration) -> Simulation {
+    let mut simulation = Simulation::new(4, model, seed);
+    let deadline = simulation.now() + duration;
+    simulation.run_until(deadline);
+    simulation.check_safety();
+    simulation
+}
+
+#[test]
+fn simulation_commits_with_latency() {
+    let simulation = run_simulation(NetworkModel::default(), 0, Duration::from_secs(30));
+    for round in simulation.committed_rounds() {
+        assert!(
+            round > 10,
+            "Committed rounds: {:?}",
+            simulation.committed_rounds()
+        );
+    }
+}
+
+#[test]
+fn simulation_recovers_from_drops() {
+    let model = NetworkModel {
+        drop_probability: 0.2,
+        ..NetworkModel::default()
+    };
+    for seed in 0..5 {
+        let simulation = run_simulation(model, seed, Duration::from_secs(120));
+        let rounds = simulation.committed_rounds();
+        assert!(
+            rounds.iter().filter(|round| **round > 0).count() >= 3,
+            "Seed {} committed rounds: {:?}",
+            seed,
+            rounds
+        );
+    }
+}
+
+#[test]
+fn simulation_is_deterministic() {
+    let model = NetworkModel {
+        drop_probability: 0.1,
+        ..NetworkModel::default()
+    };
+    let first = run_simulation(model, 42, Duration::from_secs(30));
+    let second = run_simulation(model, 42, Duration::from_secs(30));
+    assert_eq!(first.committed(), second.committed());
+    assert_eq!(first.now(), second.now());
+}
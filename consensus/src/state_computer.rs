@@ -6,8 +6,11 @@ use anyhow::Result;
 use consensus_types::block::Block;
 use execution_correctness::ExecutionCorrectness;
 use executor_types::{Error, StateComputeResult};
-use libra_crypto::HashValue;
-use libra_logger::prelude::*;
+use libra_crypto::{ed25519::Ed25519PublicKey, HashValue};
+use libra_logger::{
+    context::{with_context, BLOCK_ID},
+    prelude::*,
+};
 use libra_metrics::monitor;
 use libra_types::ledger_info::LedgerInfoWithSignatures;
 use state_synchronizer::StateSyncClient;
@@ -20,16 +23,20 @@ use std::{
 /// implements StateComputer traits.
 pub struct ExecutionProxy {
     execution_correctness_client: Mutex<Box<dyn ExecutionCorrectness + Send + Sync>>,
+    /// If set, every execution result must carry an attestation signed by this key.
+    execution_public_key: Option<Ed25519PublicKey>,
     synchronizer: Arc<StateSyncClient>,
 }
 
 impl ExecutionProxy {
     pub fn new(
         execution_correctness_client: Box<dyn ExecutionCorrectness + Send + Sync>,
+        execution_public_key: Option<Ed25519PublicKey>,
         synchronizer: Arc<StateSyncClient>,
     ) -> Self {
         Self {
             execution_correctness_client: Mutex::new(execution_correctness_client),
+            execution_public_key,
             synchronizer,
         }
     }
@@ -44,20 +51,27 @@ impl StateComputer for ExecutionProxy {
         // The parent block id.
         parent_block_id: HashValue,
     ) -> Result<StateComputeResult, Error> {
-        debug!(
-            "Executing block {:x}. Parent: {:x}.",
-            block.id(),
-            block.parent_id(),
-        );
+        // Tags the executor and storage logs on this thread with the block
+        with_context(BLOCK_ID, block.id().to_hex(), || {
+            debug!(
+                "Executing block {:x}. Parent: {:x}.",
+                block.id(),
+                block.parent_id(),
+            );
 
-        // TODO: figure out error handling for the prologue txn
-        monitor!(
-            "execute_block",
-            self.execution_correctness_client
-                .lock()
-                .unwrap()
-                .execute_block(block.clone(), parent_block_id)
-        )
+            // TODO: figure out error handling for the prologue txn
+            let result = monitor!(
+                "execute_block",
+                self.execution_correctness_client
+                    .lock()
+                    .unwrap()
+                    .execute_block(block.clone(), parent_block_id)
+            )?;
+            if let Some(public_key) = self.execution_public_key.as_ref() {
+                result.verify_attestation(block.id(), public_key)?;
+            }
+            Ok(result)
+        })
     }
 
     /// Send a successful commit. A future is fulfilled when the state is finalized.
@@ -66,13 +80,16 @@ impl StateComputer for ExecutionProxy {
         block_ids: Vec<HashValue>,
         finality_proof: LedgerInfoWithSignatures,
     ) -> Result<()> {
-        let (committed_txns, reconfig_events) = monitor!(
-            "commit_block",
-            self.execution_correctness_client
-                .lock()
-                .unwrap()
-                .commit_blocks(block_ids, finality_proof)?
-        );
+        let block_id = finality_proof.ledger_info().consensus_block_id();
+        let (committed_txns, reconfig_events) = with_context(BLOCK_ID, block_id.to_hex(), || {
+            monitor!(
+                "commit_block",
+                self.execution_correctness_client
+                    .lock()
+                    .unwrap()
+                    .commit_blocks(block_ids, finality_proof)
+            )
+        })?;
         if let Err(e) = monitor!(
             "notify_state_sync",
             self.synchronizer
@@ -2,10 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Result;
-use consensus_types::{block::Block, common::Payload};
+use consensus_types::{block::Block, common::Payload, compact_proposal_msg::TransactionSummary};
 use executor_types::{Error, StateComputeResult};
 use libra_crypto::HashValue;
-use libra_types::ledger_info::LedgerInfoWithSignatures;
+use libra_types::{ledger_info::LedgerInfoWithSignatures, transaction::SignedTransaction};
 
 /// Retrieves and updates the status of transactions on demand (e.g., via talking with Mempool)
 #[async_trait::async_trait]
@@ -15,6 +15,13 @@ pub trait TxnManager: Send + Sync {
     /// branch of blocks consensus is trying to extend.
     async fn pull_txns(&self, max_size: u64, exclude: Vec<&Payload>) -> Result<Payload>;
 
+    /// Looks up the transactions of a compact proposal, in the order of the summaries.
+    /// A transaction that is unknown, or whose hash doesn't match its summary, is `None`.
+    async fn get_txns(
+        &self,
+        summaries: &[TransactionSummary],
+    ) -> Result<Vec<Option<SignedTransaction>>>;
+
     /// Notifies TxnManager about the executed result of the block,
     /// which includes the specifics of what transactions succeeded and failed.
     async fn notify(&self, block: &Block, compute_result: &StateComputeResult) -> Result<()>;
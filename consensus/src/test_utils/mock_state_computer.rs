@@ -6,7 +6,10 @@ use anyhow::{format_err, Result};
 use consensus_types::{block::Block, common::Payload};
 use executor_types::{Error, StateComputeResult};
 use futures::channel::mpsc;
-use libra_crypto::{hash::ACCUMULATOR_PLACEHOLDER_HASH, HashValue};
+use libra_crypto::{
+    hash::{ACCUMULATOR_PLACEHOLDER_HASH, SPARSE_MERKLE_PLACEHOLDER_HASH},
+    HashValue,
+};
 use libra_logger::prelude::*;
 use libra_types::ledger_info::LedgerInfoWithSignatures;
 use std::{
@@ -50,6 +53,7 @@ impl StateComputer for MockStateComputer {
             .insert(block.id(), block.payload().unwrap_or(&vec![]).clone());
         let result = StateComputeResult::new(
             *ACCUMULATOR_PLACEHOLDER_HASH,
+            *SPARSE_MERKLE_PLACEHOLDER_HASH,
             vec![],
             0,
             vec![],
@@ -117,6 +121,7 @@ impl StateComputer for EmptyStateComputer {
     ) -> Result<StateComputeResult, Error> {
         Ok(StateComputeResult::new(
             *ACCUMULATOR_PLACEHOLDER_HASH,
+            *SPARSE_MERKLE_PLACEHOLDER_HASH,
             vec![],
             0,
             vec![],
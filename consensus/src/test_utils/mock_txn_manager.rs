@@ -6,12 +6,13 @@ use anyhow::Result;
 use consensus_types::{
     block::{block_test_utils::random_payload, Block},
     common::Payload,
+    compact_proposal_msg::TransactionSummary,
 };
 use executor_types::StateComputeResult;
 use futures::channel::mpsc;
 use libra_mempool::ConsensusRequest;
 use libra_types::{
-    transaction::TransactionStatus,
+    transaction::{SignedTransaction, TransactionStatus},
     vm_status::{StatusCode, VMStatus},
 };
 use rand::Rng;
@@ -55,10 +56,19 @@ impl TxnManager for MockTransactionManager {
         Ok(random_payload(max_size as usize))
     }
 
+    /// The mocked mempool knows none of the proposed transactions
+    async fn get_txns(
+        &self,
+        summaries: &[TransactionSummary],
+    ) -> Result<Vec<Option<SignedTransaction>>> {
+        Ok(vec![None; summaries.len()])
+    }
+
     async fn notify(&self, block: &Block, compute_results: &StateComputeResult) -> Result<()> {
         if self.mempool_proxy.is_some() {
             let mock_compute_result = StateComputeResult::new(
                 compute_results.root_hash(),
+                compute_results.state_root(),
                 compute_results.frozen_subtree_roots().clone(),
                 compute_results.num_leaves(),
                 compute_results.parent_frozen_subtree_roots().clone(),
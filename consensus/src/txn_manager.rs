@@ -3,7 +3,11 @@
 
 use crate::state_replication::TxnManager;
 use anyhow::{format_err, Result};
-use consensus_types::{block::Block, common::Payload};
+use consensus_types::{
+    block::Block,
+    common::Payload,
+    compact_proposal_msg::{transaction_hash, TransactionSummary},
+};
 use executor_types::StateComputeResult;
 use futures::channel::{mpsc, oneshot};
 use itertools::Itertools;
@@ -12,7 +16,7 @@ use libra_mempool::{
 };
 use libra_metrics::monitor;
 use libra_trace::prelude::*;
-use libra_types::transaction::TransactionStatus;
+use libra_types::transaction::{SignedTransaction, TransactionStatus};
 use std::time::Duration;
 use tokio::time::timeout;
 
@@ -63,6 +67,45 @@ impl TxnManager for MempoolProxy {
         }
     }
 
+    async fn get_txns(
+        &self,
+        summaries: &[TransactionSummary],
+    ) -> Result<Vec<Option<SignedTransaction>>> {
+        let txn_pointers = summaries
+            .iter()
+            .map(|summary| (summary.sender, summary.sequence_number))
+            .collect();
+        let (callback, callback_rcv) = oneshot::channel();
+        let req = ConsensusRequest::GetTransactionsRequest(txn_pointers, callback);
+        // send to shared mempool
+        self.consensus_to_mempool_sender.clone().try_send(req)?;
+        // wait for response
+        let txns = match monitor!(
+            "get_txns",
+            timeout(Duration::from_secs(1), callback_rcv).await
+        ) {
+            Err(_) => {
+                return Err(format_err!(
+                    "[consensus] did not receive GetTransactionsResponse on time"
+                ))
+            }
+            Ok(resp) => match resp?? {
+                ConsensusResponse::GetTransactionsResponse(txns) => txns,
+                _ => {
+                    return Err(format_err!(
+                        "[consensus] did not receive expected GetTransactionsResponse"
+                    ))
+                }
+            },
+        };
+        // mempool may hold a different transaction with the same sequence number
+        Ok(txns
+            .into_iter()
+            .zip(summaries)
+            .map(|(txn, summary)| txn.filter(|txn| transaction_hash(txn) == summary.hash))
+            .collect())
+    }
+
     // Consensus notifies mempool of executed transactions
     async fn notify(&self, block: &Block, compute_results: &StateComputeResult) -> Result<()> {
         let mut rejected_txns = vec![];
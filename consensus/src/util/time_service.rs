@@ -99,13 +99,23 @@ where
 /// TimeService implementation that uses actual clock to schedule tasks
 pub struct ClockTimeService {
     executor: Handle,
+    clock_skew_ms: i64,
 }
 
 impl ClockTimeService {
     /// Creates new TimeService that runs tasks based on actual clock
     /// It needs executor to schedule internal tasks that facilitates it's work
     pub fn new(executor: Handle) -> ClockTimeService {
-        ClockTimeService { executor }
+        ClockTimeService {
+            executor,
+            clock_skew_ms: 0,
+        }
+    }
+
+    /// Shifts the reported timestamps by `clock_skew_ms`, to simulate a node whose clock is off
+    pub fn with_clock_skew(mut self, clock_skew_ms: i64) -> Self {
+        self.clock_skew_ms = clock_skew_ms;
+        self
     }
 }
 
@@ -119,7 +129,13 @@ impl TimeService for ClockTimeService {
     }
 
     fn get_current_timestamp(&self) -> Duration {
-        duration_since_epoch()
+        let now = duration_since_epoch();
+        let skew = Duration::from_millis(self.clock_skew_ms.abs() as u64);
+        if self.clock_skew_ms >= 0 {
+            now + skew
+        } else {
+            now - skew
+        }
     }
 
     fn sleep(&self, t: Duration) {
@@ -0,0 +1,477 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module provides an API for BLS signatures over the BLS12-381 curve, following the
+//! proof-of-possession scheme of the
+//! [BLS signatures draft](https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04) with
+//! public keys in G1 and signatures in G2.
+//!
+//! Signatures on the same message can be aggregated into a single signature and verified at once
+//! against the public keys of their signers. Since aggregation is vulnerable to rogue-key attacks,
+//! a public key must only be aggregated once its proof of possession has been verified.
+//!
+//! This scheme is experimental and only built with the `bls12381` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use libra_crypto_derive::{CryptoHasher, LCSCryptoHash};
+//! use libra_crypto::{
+//!     bls12381::*,
+//!     traits::{Signature, SigningKey, Uniform},
+//! };
+//! use rand::{rngs::StdRng, SeedableRng};
+//! use serde::{Serialize, Deserialize};
+//!
+//! #[derive(Serialize, Deserialize, CryptoHasher, LCSCryptoHash)]
+//! pub struct TestCryptoDocTest(String);
+//! let message = TestCryptoDocTest("Test message".to_string());
+//!
+//! let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+//! let private_keys: Vec<_> = (0..3).map(|_| BLS12381PrivateKey::generate(&mut rng)).collect();
+//! let public_keys: Vec<BLS12381PublicKey> = private_keys.iter().map(|key| key.into()).collect();
+//! let signatures: Vec<_> = private_keys.iter().map(|key| key.sign(&message)).collect();
+//!
+//! let aggregate = BLS12381Signature::aggregate(&signatures.iter().collect::<Vec<_>>()).unwrap();
+//! assert!(aggregate
+//!     .verify_aggregate(&message, &public_keys.iter().collect::<Vec<_>>())
+//!     .is_ok());
+//! ```
+//! **Note**: The above example generates a private key using a private function intended only for
+//! testing purposes. Production code should find an alternate means for secure key generation.
+
+use crate::{
+    hash::{CryptoHash, CryptoHasher},
+    traits::*,
+};
+use anyhow::{anyhow, Result};
+use blst::{min_pk as blst_core, BLST_ERROR};
+use core::convert::TryFrom;
+use libra_crypto_derive::{DeserializeKey, SerializeKey, SilentDebug, SilentDisplay};
+use serde::Serialize;
+use std::fmt;
+
+/// The length of the BLS12381PrivateKey
+pub const BLS12381_PRIVATE_KEY_LENGTH: usize = 32;
+/// The length of the BLS12381PublicKey, a compressed G1 point
+pub const BLS12381_PUBLIC_KEY_LENGTH: usize = 48;
+/// The length of the BLS12381Signature, a compressed G2 point
+pub const BLS12381_SIGNATURE_LENGTH: usize = 96;
+
+/// The domain separation tag used to hash messages to G2.
+const SIGNATURE_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+/// The domain separation tag used to hash public keys to G2 for proofs of possession.
+const PROOF_OF_POSSESSION_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// A BLS12-381 private key
+#[derive(DeserializeKey, SerializeKey, SilentDebug, SilentDisplay)]
+pub struct BLS12381PrivateKey(blst_core::SecretKey);
+
+#[cfg(feature = "assert-private-keys-not-cloneable")]
+static_assertions::assert_not_impl_any!(BLS12381PrivateKey: Clone);
+
+#[cfg(any(test, feature = "cloneable-private-keys"))]
+impl Clone for BLS12381PrivateKey {
+    fn clone(&self) -> Self {
+        let serialized: &[u8] = &(self.to_bytes());
+        BLS12381PrivateKey::try_from(serialized).unwrap()
+    }
+}
+
+/// A BLS12-381 public key
+#[derive(DeserializeKey, Clone, SerializeKey)]
+pub struct BLS12381PublicKey(blst_core::PublicKey);
+
+/// A BLS12-381 signature, either of a single signer or aggregated from several signers
+#[derive(DeserializeKey, Clone, SerializeKey)]
+pub struct BLS12381Signature(blst_core::Signature);
+
+/// Converts the status returned by blst into a `Result`.
+fn blst_result(status: BLST_ERROR) -> Result<()> {
+    match status {
+        BLST_ERROR::BLST_SUCCESS => Ok(()),
+        error => Err(anyhow!("BLS12381 error: {:?}", error)),
+    }
+}
+
+/// Serializes `message` for signing, prefixed by the seed of its hasher.
+fn signing_message<T: CryptoHash + Serialize>(message: &T) -> Result<Vec<u8>> {
+    let mut bytes = <T::Hasher as CryptoHasher>::seed().to_vec();
+    lcs::serialize_into(&mut bytes, &message)
+        .map_err(|_| CryptoMaterialError::SerializationError)?;
+    Ok(bytes)
+}
+
+impl BLS12381PrivateKey {
+    /// The length of the BLS12381PrivateKey
+    pub const LENGTH: usize = BLS12381_PRIVATE_KEY_LENGTH;
+
+    /// Serialize a BLS12381PrivateKey.
+    pub fn to_bytes(&self) -> [u8; BLS12381_PRIVATE_KEY_LENGTH] {
+        self.0.to_bytes()
+    }
+
+    /// Private function aimed at minimizing code duplication between sign
+    /// methods of the SigningKey implementation. This should remain private.
+    fn sign_arbitrary_message(&self, message: &[u8]) -> BLS12381Signature {
+        BLS12381Signature(self.0.sign(message, SIGNATURE_DST, &[]))
+    }
+
+    /// Proves possession of this key by signing the corresponding public key. The proof must be
+    /// checked with `BLS12381PublicKey::verify_proof_of_possession` before the public key is
+    /// aggregated.
+    pub fn proof_of_possession(&self) -> BLS12381Signature {
+        let public_key: BLS12381PublicKey = self.into();
+        BLS12381Signature(
+            self.0
+                .sign(&public_key.to_bytes(), PROOF_OF_POSSESSION_DST, &[]),
+        )
+    }
+}
+
+impl BLS12381PublicKey {
+    /// Serialize a BLS12381PublicKey.
+    pub fn to_bytes(&self) -> [u8; BLS12381_PUBLIC_KEY_LENGTH] {
+        self.0.to_bytes()
+    }
+
+    /// Verifies that `proof` proves the possession of the private key of this public key.
+    pub fn verify_proof_of_possession(&self, proof: &BLS12381Signature) -> Result<()> {
+        blst_result(proof.0.verify(
+            true,
+            &self.to_bytes(),
+            PROOF_OF_POSSESSION_DST,
+            &[],
+            &self.0,
+            false,
+        ))
+    }
+
+    /// Aggregates `public_keys` into a single public key. The proof of possession of each of
+    /// `public_keys` must have been verified beforehand.
+    pub fn aggregate(public_keys: &[&BLS12381PublicKey]) -> Result<BLS12381PublicKey> {
+        let public_keys: Vec<_> = public_keys.iter().map(|key| &key.0).collect();
+        blst_core::AggregatePublicKey::aggregate(&public_keys, false)
+            .map(|aggregate| BLS12381PublicKey(aggregate.to_public_key()))
+            .map_err(|e| anyhow!("BLS12381 error: {:?}", e))
+    }
+}
+
+impl BLS12381Signature {
+    /// The length of the BLS12381Signature
+    pub const LENGTH: usize = BLS12381_SIGNATURE_LENGTH;
+
+    /// Serialize a BLS12381Signature.
+    pub fn to_bytes(&self) -> [u8; BLS12381_SIGNATURE_LENGTH] {
+        self.0.to_bytes()
+    }
+
+    /// Aggregates `signatures` into a single signature, which can be verified against the
+    /// public keys of all the signers with `verify_aggregate`.
+    pub fn aggregate(signatures: &[&BLS12381Signature]) -> Result<BLS12381Signature> {
+        let signatures: Vec<_> = signatures.iter().map(|signature| &signature.0).collect();
+        blst_core::AggregateSignature::aggregate(&signatures, true)
+            .map(|aggregate| BLS12381Signature(aggregate.to_signature()))
+            .map_err(|e| anyhow!("BLS12381 error: {:?}", e))
+    }
+
+    /// Verifies that `self` aggregates signatures of `message` by all of `public_keys`. The
+    /// proof of possession of each of `public_keys` must have been verified beforehand.
+    pub fn verify_aggregate<T: CryptoHash + Serialize>(
+        &self,
+        message: &T,
+        public_keys: &[&BLS12381PublicKey],
+    ) -> Result<()> {
+        let bytes = signing_message(message)?;
+        self.verify_aggregate_arbitrary_msg(&bytes, public_keys)
+    }
+
+    /// Verifies that `self` aggregates signatures of an arbitrary `message` by all of
+    /// `public_keys`.
+    pub fn verify_aggregate_arbitrary_msg(
+        &self,
+        message: &[u8],
+        public_keys: &[&BLS12381PublicKey],
+    ) -> Result<()> {
+        let public_keys: Vec<_> = public_keys.iter().map(|key| &key.0).collect();
+        blst_result(
+            self.0
+                .fast_aggregate_verify(true, message, SIGNATURE_DST, &public_keys),
+        )
+    }
+}
+
+///////////////////////
+// PrivateKey Traits //
+///////////////////////
+
+impl PrivateKey for BLS12381PrivateKey {
+    type PublicKeyMaterial = BLS12381PublicKey;
+}
+
+impl SigningKey for BLS12381PrivateKey {
+    type VerifyingKeyMaterial = BLS12381PublicKey;
+    type SignatureMaterial = BLS12381Signature;
+
+    fn sign<T: CryptoHash + Serialize>(&self, message: &T) -> BLS12381Signature {
+        let bytes =
+            signing_message(message).expect("Serialization of signable material should not fail.");
+        BLS12381PrivateKey::sign_arbitrary_message(&self, bytes.as_ref())
+    }
+
+    #[cfg(any(test, feature = "fuzzing"))]
+    fn sign_arbitrary_message(&self, message: &[u8]) -> BLS12381Signature {
+        BLS12381PrivateKey::sign_arbitrary_message(self, message)
+    }
+}
+
+impl Uniform for BLS12381PrivateKey {
+    fn generate<R>(rng: &mut R) -> Self
+    where
+        R: ::rand::RngCore + ::rand::CryptoRng,
+    {
+        let mut ikm = [0u8; 32];
+        rng.fill_bytes(&mut ikm);
+        BLS12381PrivateKey(
+            blst_core::SecretKey::key_gen(&ikm, &[])
+                .expect("Key generation from 32 bytes of keying material should not fail."),
+        )
+    }
+}
+
+impl PartialEq<Self> for BLS12381PrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_bytes() == other.to_bytes()
+    }
+}
+
+impl Eq for BLS12381PrivateKey {}
+
+impl TryFrom<&[u8]> for BLS12381PrivateKey {
+    type Error = CryptoMaterialError;
+
+    /// Deserialize a BLS12381PrivateKey. This method will also check that the key is a valid
+    /// non-zero scalar.
+    fn try_from(bytes: &[u8]) -> std::result::Result<BLS12381PrivateKey, CryptoMaterialError> {
+        if bytes.len() != BLS12381_PRIVATE_KEY_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        blst_core::SecretKey::from_bytes(bytes)
+            .map(BLS12381PrivateKey)
+            .map_err(|_| CryptoMaterialError::DeserializationError)
+    }
+}
+
+impl Length for BLS12381PrivateKey {
+    fn length(&self) -> usize {
+        Self::LENGTH
+    }
+}
+
+impl ValidCryptoMaterial for BLS12381PrivateKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+//////////////////////
+// PublicKey Traits //
+//////////////////////
+
+// Implementing From<&PrivateKey<...>> allows to derive a public key in a more elegant fashion
+impl From<&BLS12381PrivateKey> for BLS12381PublicKey {
+    fn from(private_key: &BLS12381PrivateKey) -> Self {
+        BLS12381PublicKey(private_key.0.sk_to_pk())
+    }
+}
+
+// We deduce PublicKey from this
+impl PublicKey for BLS12381PublicKey {
+    type PrivateKeyMaterial = BLS12381PrivateKey;
+}
+
+impl std::hash::Hash for BLS12381PublicKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let encoded_pubkey = self.to_bytes();
+        state.write(&encoded_pubkey);
+    }
+}
+
+// Those are required by the implementation of hash above
+impl PartialEq for BLS12381PublicKey {
+    fn eq(&self, other: &BLS12381PublicKey) -> bool {
+        self.to_bytes()[..] == other.to_bytes()[..]
+    }
+}
+
+impl Eq for BLS12381PublicKey {}
+
+// We deduce VerifyingKey from pointing to the signature material
+// we get the ability to do `pubkey.validate(msg, signature)`
+impl VerifyingKey for BLS12381PublicKey {
+    type SigningKeyMaterial = BLS12381PrivateKey;
+    type SignatureMaterial = BLS12381Signature;
+}
+
+impl fmt::Display for BLS12381PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(&self.to_bytes()[..]))
+    }
+}
+
+impl fmt::Debug for BLS12381PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BLS12381PublicKey({})", self)
+    }
+}
+
+impl TryFrom<&[u8]> for BLS12381PublicKey {
+    type Error = CryptoMaterialError;
+
+    /// Deserialize a BLS12381PublicKey. This method will also check for key validity: the point
+    /// must be in the prime-order subgroup of G1 and must not be the identity.
+    fn try_from(bytes: &[u8]) -> std::result::Result<BLS12381PublicKey, CryptoMaterialError> {
+        if bytes.len() != BLS12381_PUBLIC_KEY_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        blst_core::PublicKey::key_validate(bytes)
+            .map(BLS12381PublicKey)
+            .map_err(|_| CryptoMaterialError::ValidationError)
+    }
+}
+
+impl Length for BLS12381PublicKey {
+    fn length(&self) -> usize {
+        BLS12381_PUBLIC_KEY_LENGTH
+    }
+}
+
+impl ValidCryptoMaterial for BLS12381PublicKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+//////////////////////
+// Signature Traits //
+//////////////////////
+
+impl Signature for BLS12381Signature {
+    type VerifyingKeyMaterial = BLS12381PublicKey;
+    type SigningKeyMaterial = BLS12381PrivateKey;
+
+    /// Verifies that the provided signature is valid for the provided message.
+    fn verify<T: CryptoHash + Serialize>(
+        &self,
+        message: &T,
+        public_key: &BLS12381PublicKey,
+    ) -> Result<()> {
+        let bytes = signing_message(message)?;
+        Self::verify_arbitrary_msg(self, &bytes, public_key)
+    }
+
+    /// Checks that `self` is valid for an arbitrary &[u8] `message` using `public_key`. The
+    /// signature is checked to be in the prime-order subgroup of G2.
+    fn verify_arbitrary_msg(&self, message: &[u8], public_key: &BLS12381PublicKey) -> Result<()> {
+        blst_result(
+            self.0
+                .verify(true, message, SIGNATURE_DST, &[], &public_key.0, false),
+        )
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+
+    /// Batch verification of signatures of the same message, by verifying their aggregate
+    /// against the keys of their signers with a single pairing check. The proof of possession of
+    /// each key must have been verified beforehand.
+    fn batch_verify<T: CryptoHash + Serialize>(
+        message: &T,
+        keys_and_signatures: Vec<(Self::VerifyingKeyMaterial, Self)>,
+    ) -> Result<()> {
+        let (public_keys, signatures): (Vec<_>, Vec<_>) = keys_and_signatures
+            .iter()
+            .map(|(key, signature)| (key, signature))
+            .unzip();
+        BLS12381Signature::aggregate(&signatures)?.verify_aggregate(message, &public_keys)
+    }
+}
+
+impl Length for BLS12381Signature {
+    fn length(&self) -> usize {
+        BLS12381_SIGNATURE_LENGTH
+    }
+}
+
+impl ValidCryptoMaterial for BLS12381Signature {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+impl std::hash::Hash for BLS12381Signature {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let encoded_signature = self.to_bytes();
+        state.write(&encoded_signature);
+    }
+}
+
+impl TryFrom<&[u8]> for BLS12381Signature {
+    type Error = CryptoMaterialError;
+
+    /// Deserialize a BLS12381Signature. Subgroup membership is checked on verification.
+    fn try_from(bytes: &[u8]) -> std::result::Result<BLS12381Signature, CryptoMaterialError> {
+        if bytes.len() != BLS12381_SIGNATURE_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        blst_core::Signature::from_bytes(bytes)
+            .map(BLS12381Signature)
+            .map_err(|_| CryptoMaterialError::DeserializationError)
+    }
+}
+
+// Those are required by the implementation of hash above
+impl PartialEq for BLS12381Signature {
+    fn eq(&self, other: &BLS12381Signature) -> bool {
+        self.to_bytes()[..] == other.to_bytes()[..]
+    }
+}
+
+impl Eq for BLS12381Signature {}
+
+impl fmt::Display for BLS12381Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(&self.to_bytes()[..]))
+    }
+}
+
+impl fmt::Debug for BLS12381Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BLS12381Signature({})", self)
+    }
+}
+
+#[cfg(any(test, feature = "fuzzing"))]
+use crate::test_utils::{self, KeyPair};
+
+/// Produces a uniformly random BLS12-381 keypair from a seed
+#[cfg(any(test, feature = "fuzzing"))]
+pub fn keypair_strategy() -> impl Strategy<Value = KeyPair<BLS12381PrivateKey, BLS12381PublicKey>> {
+    test_utils::uniform_keypair_strategy::<BLS12381PrivateKey, BLS12381PublicKey>()
+}
+
+#[cfg(any(test, feature = "fuzzing"))]
+use proptest::prelude::*;
+
+#[cfg(any(test, feature = "fuzzing"))]
+impl proptest::arbitrary::Arbitrary for BLS12381PublicKey {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        crate::test_utils::uniform_keypair_strategy::<BLS12381PrivateKey, BLS12381PublicKey>()
+            .prop_map(|v| v.public_key)
+            .boxed()
+    }
+}
@@ -209,6 +209,75 @@ impl Ed25519Signature {
         }
         Ok(())
     }
+
+    /// Verifies a batch of signatures, where `signatures[i]` is expected to be a signature of
+    /// `messages[i]` under `public_keys[i]`. This is considerably cheaper than verifying each
+    /// signature on its own, but only reports whether the whole batch is valid.
+    ///
+    /// On top of the malleability check, the public keys and the R components of the signatures
+    /// are required to be in the prime order subgroup, i.e. neither of small order nor carrying a
+    /// torsion component. For such points the verification equation does not depend on the
+    /// cofactor, so a valid batch implies that every signature passes
+    /// [`Signature::verify_arbitrary_msg`], except with negligible probability.
+    ///
+    /// The converse does not hold: [`Signature::verify_arbitrary_msg`] only rejects points of small
+    /// order, and may accept a signature that fails here. Callers that need its exact result, e.g.
+    /// because validators must agree on it, have to verify the signatures one by one on failure.
+    pub fn batch_verify_arbitrary_msgs(
+        messages: &[&[u8]],
+        public_keys: &[&Ed25519PublicKey],
+        signatures: &[&Ed25519Signature],
+    ) -> Result<()> {
+        if messages.len() != signatures.len() || public_keys.len() != signatures.len() {
+            return Err(anyhow!(
+                "Batch verification received {} messages, {} public keys and {} signatures",
+                messages.len(),
+                public_keys.len(),
+                signatures.len()
+            ));
+        }
+        for (public_key, signature) in public_keys.iter().zip(signatures.iter()) {
+            let bytes = signature.to_bytes();
+            Ed25519Signature::check_malleability(&bytes)?;
+            let mut r_bytes = [0u8; 32];
+            r_bytes.copy_from_slice(&bytes[..32]);
+            if !is_in_prime_order_subgroup(r_bytes) {
+                return Err(anyhow!(
+                    "Signature R component is invalid or not in the prime order subgroup"
+                ));
+            }
+            if !is_in_prime_order_subgroup(public_key.to_bytes()) {
+                return Err(anyhow!("Public key is not in the prime order subgroup"));
+            }
+        }
+
+        #[cfg(all(feature = "batch", not(feature = "vanilla")))]
+        {
+            let dalek_public_keys: Vec<_> = public_keys.iter().map(|key| key.0).collect();
+            let dalek_signatures: Vec<_> = signatures.iter().map(|signature| signature.0).collect();
+            ed25519_dalek::verify_batch(messages, &dalek_signatures[..], &dalek_public_keys[..])
+                .map_err(|e| anyhow!("{}", e))
+        }
+        #[cfg(not(all(feature = "batch", not(feature = "vanilla"))))]
+        {
+            messages
+                .iter()
+                .zip(public_keys.iter())
+                .zip(signatures.iter())
+                .try_for_each(|((message, public_key), signature)| {
+                    signature.verify_arbitrary_msg(message, public_key)
+                })
+        }
+    }
+}
+
+/// Returns whether `bytes` encode a point of the prime order subgroup of the curve, other than the
+/// identity.
+fn is_in_prime_order_subgroup(bytes: [u8; 32]) -> bool {
+    match curve25519_dalek::edwards::CompressedEdwardsY(bytes).decompress() {
+        Some(point) => !point.is_small_order() && point.is_torsion_free(),
+        None => false,
+    }
 }
 
 ///////////////////////
@@ -429,31 +498,36 @@ impl Signature for Ed25519Signature {
 
     /// Batch signature verification as described in the original EdDSA article
     /// by Bernstein et al. "High-speed high-security signatures". Current implementation works for
-    /// signatures on the same message and it checks for malleability.
+    /// signatures on the same message and it checks for malleability. If the batch fails, the
+    /// signatures are verified one by one, so that the result is the same as verifying each of
+    /// them with [`Signature::verify`].
     #[cfg(all(feature = "batch", not(feature = "vanilla")))] // see https://github.com/dalek-cryptography/ed25519-dalek/issues/126
     fn batch_verify<T: CryptoHash + Serialize>(
         message: &T,
         keys_and_signatures: Vec<(Self::VerifyingKeyMaterial, Self)>,
     ) -> Result<()> {
-        for (_, sig) in keys_and_signatures.iter() {
-            Ed25519Signature::check_malleability(&sig.to_bytes())?
-        }
         let mut message_bytes = <T::Hasher as CryptoHasher>::seed().to_vec();
         lcs::serialize_into(&mut message_bytes, &message)
             .map_err(|_| CryptoMaterialError::SerializationError)?;
 
-        let batch_argument = keys_and_signatures
+        let (public_keys, signatures): (Vec<_>, Vec<_>) = keys_and_signatures
             .iter()
-            .map(|(key, signature)| (key.0, signature.0));
-        let (dalek_public_keys, dalek_signatures): (Vec<_>, Vec<_>) = batch_argument.unzip();
-        let message_ref = &(&message_bytes)[..];
+            .map(|(key, signature)| (key, signature))
+            .unzip();
         // The original batching algorithm works for different messages and it expects as many
         // messages as the number of signatures. In our case, we just populate the same
-        // message to meet dalek's api requirements.
-        let messages = vec![message_ref; dalek_signatures.len()];
-        ed25519_dalek::verify_batch(&messages[..], &dalek_signatures[..], &dalek_public_keys[..])
-            .map_err(|e| anyhow!("{}", e))?;
-        Ok(())
+        // message to meet the api requirements.
+        let messages = vec![&message_bytes[..]; signatures.len()];
+        if Ed25519Signature::batch_verify_arbitrary_msgs(&messages, &public_keys, &signatures)
+            .is_ok()
+        {
+            return Ok(());
+        }
+        // The batch is stricter than the individual verification, see
+        // `batch_verify_arbitrary_msgs`.
+        keys_and_signatures
+            .iter()
+            .try_for_each(|(key, signature)| signature.verify_arbitrary_msg(&message_bytes, key))
     }
 }
 
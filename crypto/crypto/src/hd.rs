@@ -0,0 +1,174 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Hierarchical deterministic (HD) derivation of Ed25519 private keys from a single seed.
+//!
+//! Ed25519 does not support BIP32-style public key derivation, so every key along a derivation
+//! path is a private key derived with [HKDF RFC 5869](https://tools.ietf.org/html/rfc5869) over
+//! SHA3-256, in the spirit of hardened BIP32 derivation:
+//!
+//! * the root main key is `HKDF-Extract(MAIN_KEY_SALT, seed)`;
+//! * the private key of child `i` is `HKDF-Expand(main, DERIVED_KEY_INFO_PREFIX || LE64(i), 32)`;
+//! * the main key of child `i`, used to derive its own children, is
+//! `HKDF-Expand(main, DERIVED_MAIN_KEY_INFO_PREFIX || LE64(i), 32)`.
+//!
+//! Paths are written as `m/<i_0>/<i_1>/.../<i_n>`, where the private key of the path is the
+//! private key of child `i_n` of the node at `m/<i_0>/.../<i_(n-1)>`. Single level paths `m/<i>`
+//! yield exactly the account keys of the CLI's LibraWallet for child number `i`, so a wallet
+//! built on this module can recover the accounts of a CLI wallet from the same seed.
+//!
+//! The seed itself is the output of a one-way function over a mnemonic phrase (see the
+//! LibraWallet's `Seed`), and is not computed by this module.
+
+use crate::{
+    compat::Sha3_256,
+    ed25519::{Ed25519PrivateKey, ED25519_PRIVATE_KEY_LENGTH},
+    hkdf::{Hkdf, HkdfError},
+};
+use libra_crypto_derive::SilentDebug;
+use std::{convert::TryFrom, fmt, str::FromStr};
+use thiserror::Error;
+
+/// The HKDF salt used to extract the root main key from a seed.
+pub const MAIN_KEY_SALT: &[u8] = b"LIBRA WALLET: main key salt$";
+/// The HKDF info prefix used to expand the private key of a child.
+pub const DERIVED_KEY_INFO_PREFIX: &[u8] = b"LIBRA WALLET: derived key$";
+/// The HKDF info prefix used to expand the main key of a child.
+pub const DERIVED_MAIN_KEY_INFO_PREFIX: &[u8] = b"LIBRA WALLET: derived main key$";
+
+/// The length in bytes of a main key.
+pub const MAIN_KEY_LENGTH: usize = 32;
+
+/// The prefix of every textual derivation path.
+const PATH_ROOT: &str = "m";
+
+/// An error encountered while parsing or constructing a derivation path.
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum DerivationPathError {
+    /// The path does not address any child of the root.
+    #[error("A derivation path must contain at least one child number")]
+    EmptyPath,
+    /// The path is not of the form `m/<u64>/.../<u64>`.
+    #[error("Invalid derivation path: {0}")]
+    InvalidPath(String),
+}
+
+/// A non-empty sequence of child numbers, leading from the root to a derived key.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DerivationPath(Vec<u64>);
+
+impl DerivationPath {
+    /// Creates a derivation path from its child numbers, ordered from the root.
+    pub fn new(child_numbers: Vec<u64>) -> Result<Self, DerivationPathError> {
+        if child_numbers.is_empty() {
+            return Err(DerivationPathError::EmptyPath);
+        }
+        Ok(Self(child_numbers))
+    }
+
+    /// The path `m/<index>` of the account keys used by the CLI's LibraWallet.
+    pub fn account(index: u64) -> Self {
+        Self(vec![index])
+    }
+
+    /// Returns the child numbers of this path, ordered from the root.
+    pub fn child_numbers(&self) -> &[u64] {
+        &self.0
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = DerivationPathError;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let mut components = path.split('/');
+        if components.next() != Some(PATH_ROOT) {
+            return Err(DerivationPathError::InvalidPath(path.into()));
+        }
+        let child_numbers = components
+            .map(|component| {
+                component
+                    .parse::<u64>()
+                    .map_err(|_| DerivationPathError::InvalidPath(path.into()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::new(child_numbers)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", PATH_ROOT)?;
+        for child_number in &self.0 {
+            write!(f, "/{}", child_number)?;
+        }
+        Ok(())
+    }
+}
+
+/// A node of the derivation tree, holding the main key from which its children are derived.
+#[derive(SilentDebug)]
+pub struct HDKeyFactory {
+    main: [u8; MAIN_KEY_LENGTH],
+}
+
+impl HDKeyFactory {
+    /// Creates the root of the derivation tree from a high-entropy seed.
+    pub fn from_seed(seed: &[u8]) -> Result<Self, HkdfError> {
+        let prk = Hkdf::<Sha3_256>::extract(Some(MAIN_KEY_SALT), seed)?;
+        let mut main = [0u8; MAIN_KEY_LENGTH];
+        main.copy_from_slice(&prk[..MAIN_KEY_LENGTH]);
+        Ok(Self { main })
+    }
+
+    /// Restores a node of the derivation tree from its main key.
+    pub fn from_main(main: [u8; MAIN_KEY_LENGTH]) -> Self {
+        Self { main }
+    }
+
+    /// Returns the main key of this node.
+    pub fn main(&self) -> &[u8] {
+        &self.main[..]
+    }
+
+    /// Derives the private key of the child `child_number` of this node.
+    pub fn private_child(&self, child_number: u64) -> Ed25519PrivateKey {
+        let bytes = self.expand(DERIVED_KEY_INFO_PREFIX, child_number);
+        Ed25519PrivateKey::try_from(&bytes[..ED25519_PRIVATE_KEY_LENGTH])
+            .expect("Unable to convert into private key")
+    }
+
+    /// Derives the node of the child `child_number`, from which its own children are derived.
+    pub fn child_factory(&self, child_number: u64) -> Self {
+        let bytes = self.expand(DERIVED_MAIN_KEY_INFO_PREFIX, child_number);
+        let mut main = [0u8; MAIN_KEY_LENGTH];
+        main.copy_from_slice(&bytes[..MAIN_KEY_LENGTH]);
+        Self { main }
+    }
+
+    /// Derives the private key at `path`, relative to this node.
+    pub fn derive_private_key(&self, path: &DerivationPath) -> Ed25519PrivateKey {
+        let (last, parents) = path
+            .child_numbers()
+            .split_last()
+            .expect("Derivation paths are never empty");
+        match parents.first() {
+            None => self.private_child(*last),
+            Some(first) => parents[1..]
+                .iter()
+                .fold(self.child_factory(*first), |factory, child_number| {
+                    factory.child_factory(*child_number)
+                })
+                .private_child(*last),
+        }
+    }
+
+    fn expand(&self, info_prefix: &[u8], child_number: u64) -> Vec<u8> {
+        let mut info = info_prefix.to_vec();
+        info.extend_from_slice(&child_number.to_le_bytes());
+        // The main key is as long as the SHA3-256 output and 32 bytes are far below the HKDF
+        // output limit, so expansion cannot fail.
+        Hkdf::<Sha3_256>::expand(&self.main, Some(&info), MAIN_KEY_LENGTH)
+            .expect("HKDF expansion of a main key cannot fail")
+    }
+}
@@ -5,10 +5,13 @@
 #![deny(missing_docs)]
 
 //! A library supplying various cryptographic primitives
+#[cfg(feature = "bls12381")]
+pub mod bls12381;
 pub mod compat;
 pub mod ed25519;
 pub mod error;
 pub mod hash;
+pub mod hd;
 pub mod hkdf;
 pub mod multi_ed25519;
 pub mod noise;
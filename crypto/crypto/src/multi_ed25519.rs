@@ -20,7 +20,7 @@ use anyhow::{anyhow, Result};
 use core::convert::TryFrom;
 use libra_crypto_derive::{DeserializeKey, SerializeKey, SilentDebug, SilentDisplay};
 use rand::Rng;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::{convert::TryInto, fmt};
 
 const MAX_NUM_OF_KEYS: usize = 32;
@@ -73,12 +73,85 @@ impl MultiEd25519PrivateKey {
         }
     }
 
+    /// Generates a K-of-N key made of `num_of_keys` fresh private keys, any `threshold` of which
+    /// are required to sign.
+    pub fn generate_threshold_key<R>(
+        rng: &mut R,
+        threshold: u8,
+        num_of_keys: usize,
+    ) -> std::result::Result<Self, CryptoMaterialError>
+    where
+        R: ::rand::RngCore + ::rand::CryptoRng,
+    {
+        let private_keys = (0..num_of_keys)
+            .map(|_| Ed25519PrivateKey::generate(&mut *rng))
+            .collect();
+        MultiEd25519PrivateKey::new(private_keys, threshold)
+    }
+
+    /// Splits this key into its individual private keys, so that each of them can be handed to a
+    /// different custodian. The public key can later be rebuilt from the public keys of the shares
+    /// with `MultiEd25519PublicKey::from_shares`.
+    pub fn into_shares(self) -> Vec<MultiEd25519KeyShare> {
+        let threshold = self.threshold;
+        let num_of_keys = self.private_keys.len() as u8;
+        self.private_keys
+            .into_iter()
+            .enumerate()
+            .map(|(index, private_key)| MultiEd25519KeyShare {
+                index: index as u8,
+                threshold,
+                num_of_keys,
+                private_key,
+            })
+            .collect()
+    }
+
     /// Serialize a MultiEd25519PrivateKey.
     pub fn to_bytes(&self) -> Vec<u8> {
         to_bytes(&self.private_keys, self.threshold)
     }
 }
 
+/// One of the private keys of a K-of-N MultiEd25519PrivateKey, held by a single custodian. A share
+/// knows its position in the key set, which is required to combine its signatures with those of
+/// the other shares into a MultiEd25519Signature.
+#[derive(Deserialize, Eq, PartialEq, Serialize, SilentDebug)]
+pub struct MultiEd25519KeyShare {
+    index: u8,
+    threshold: u8,
+    num_of_keys: u8,
+    private_key: Ed25519PrivateKey,
+}
+
+impl MultiEd25519KeyShare {
+    /// Getter index
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Getter threshold
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// Getter num_of_keys
+    pub fn num_of_keys(&self) -> u8 {
+        self.num_of_keys
+    }
+
+    /// The public key of this share.
+    pub fn public_key(&self) -> Ed25519PublicKey {
+        self.private_key.public_key()
+    }
+
+    /// Signs `message`, returning the signature along with the index of this share as expected by
+    /// `MultiEd25519Signature::new`.
+    pub fn sign<T: CryptoHash + Serialize>(&self, message: &T) -> (Ed25519Signature, u8) {
+        (self.private_key.sign(message), self.index)
+    }
+}
+
 impl MultiEd25519PublicKey {
     /// Construct a new MultiEd25519PublicKey.
     /// --- Rules ---
@@ -102,6 +175,30 @@ impl MultiEd25519PublicKey {
         }
     }
 
+    /// Rebuilds the public key of a K-of-N key from the public keys of all of its shares, given
+    /// along with their indices in any order.
+    pub fn from_shares(
+        shares: Vec<(u8, Ed25519PublicKey)>,
+        threshold: u8,
+    ) -> std::result::Result<Self, CryptoMaterialError> {
+        let mut shares = shares;
+        shares.sort_by_key(|(index, _)| *index);
+        if shares
+            .iter()
+            .enumerate()
+            .any(|(position, (index, _))| position != *index as usize)
+        {
+            return Err(CryptoMaterialError::ValidationError);
+        }
+        MultiEd25519PublicKey::new(
+            shares
+                .into_iter()
+                .map(|(_, public_key)| public_key)
+                .collect(),
+            threshold,
+        )
+    }
+
     /// Getter public_keys
     pub fn public_keys(&self) -> &Vec<Ed25519PublicKey> {
         &self.public_keys
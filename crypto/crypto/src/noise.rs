@@ -692,6 +692,40 @@ impl NoiseSession {
         // return a subslice of the buffer representing the decrypted plaintext
         Ok(buffer)
     }
+
+    /// replaces the key used to encrypt messages to the other peer, as specified by the REKEY()
+    /// function of the Noise specification. The nonce is left untouched.
+    pub fn rekey_write(&mut self) -> Result<(), NoiseError> {
+        if !self.valid {
+            return Err(NoiseError::SessionClosed);
+        }
+        self.write_key = rekey(&self.write_key)?;
+        Ok(())
+    }
+
+    /// replaces the key used to decrypt messages from the other peer, as specified by the REKEY()
+    /// function of the Noise specification. The nonce is left untouched.
+    pub fn rekey_read(&mut self) -> Result<(), NoiseError> {
+        if !self.valid {
+            return Err(NoiseError::SessionClosed);
+        }
+        self.read_key = rekey(&self.read_key)?;
+        Ok(())
+    }
+}
+
+/// REKEY(k): returns the first 32 bytes of ENCRYPT(k, maxnonce, zerolen, zeros),
+/// where maxnonce is 2^64 - 1 and zeros is a sequence of 32 bytes filled with zeros.
+fn rekey(key: &[u8]) -> Result<Vec<u8>, NoiseError> {
+    let aead = Aes256Gcm::new(GenericArray::from_slice(key));
+    let mut nonce = [0u8; 4].to_vec();
+    nonce.extend_from_slice(&u64::max_value().to_be_bytes());
+    let nonce = GenericArray::from_slice(&nonce);
+
+    let mut new_key = [0u8; 32];
+    aead.encrypt_in_place_detached(nonce, b"", &mut new_key)
+        .map_err(|_| NoiseError::Encrypt)?;
+    Ok(new_key.to_vec())
 }
 
 impl std::fmt::Debug for NoiseSession {
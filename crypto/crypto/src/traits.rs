@@ -297,4 +297,11 @@ pub(crate) mod private {
     impl Sealed for crate::multi_ed25519::MultiEd25519PrivateKey {}
     impl Sealed for crate::multi_ed25519::MultiEd25519PublicKey {}
     impl Sealed for crate::multi_ed25519::MultiEd25519Signature {}
+
+    #[cfg(feature = "bls12381")]
+    impl Sealed for crate::bls12381::BLS12381PrivateKey {}
+    #[cfg(feature = "bls12381")]
+    impl Sealed for crate::bls12381::BLS12381PublicKey {}
+    #[cfg(feature = "bls12381")]
+    impl Sealed for crate::bls12381::BLS12381Signature {}
 }
@@ -0,0 +1,131 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    bls12381::{
+        BLS12381PrivateKey, BLS12381PublicKey, BLS12381Signature, BLS12381_PRIVATE_KEY_LENGTH,
+        BLS12381_PUBLIC_KEY_LENGTH, BLS12381_SIGNATURE_LENGTH,
+    },
+    test_utils::{random_serializable_struct, uniform_keypair_strategy, KeyPair},
+    traits::*,
+    CryptoMaterialError::WrongLengthError,
+};
+
+use core::convert::TryFrom;
+use proptest::{collection::vec, prelude::*};
+
+fn keypairs_strategy() -> impl Strategy<Value = Vec<KeyPair<BLS12381PrivateKey, BLS12381PublicKey>>>
+{
+    vec(
+        uniform_keypair_strategy::<BLS12381PrivateKey, BLS12381PublicKey>(),
+        1..5,
+    )
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(20))]
+
+    #[test]
+    fn test_sign_and_verify(
+        message in random_serializable_struct(),
+        keypair in uniform_keypair_strategy::<BLS12381PrivateKey, BLS12381PublicKey>(),
+        other_keypair in uniform_keypair_strategy::<BLS12381PrivateKey, BLS12381PublicKey>(),
+    ) {
+        let signature = keypair.private_key.sign(&message);
+        prop_assert!(signature.verify(&message, &keypair.public_key).is_ok());
+        prop_assert!(keypair.public_key.verify_struct_signature(&message, &signature).is_ok());
+        prop_assert!(signature.verify(&message, &other_keypair.public_key).is_err());
+    }
+
+    #[test]
+    fn test_keys_and_signature_serialization(
+        message in random_serializable_struct(),
+        keypair in uniform_keypair_strategy::<BLS12381PrivateKey, BLS12381PublicKey>(),
+    ) {
+        let private_key_bytes = keypair.private_key.to_bytes();
+        prop_assert_eq!(private_key_bytes.len(), BLS12381_PRIVATE_KEY_LENGTH);
+        let private_key = BLS12381PrivateKey::try_from(&private_key_bytes[..]).unwrap();
+        prop_assert_eq!(&private_key, &keypair.private_key);
+
+        let public_key_bytes = keypair.public_key.to_bytes();
+        prop_assert_eq!(public_key_bytes.len(), BLS12381_PUBLIC_KEY_LENGTH);
+        let public_key = BLS12381PublicKey::try_from(&public_key_bytes[..]).unwrap();
+        prop_assert_eq!(&public_key, &keypair.public_key);
+
+        let signature = keypair.private_key.sign(&message);
+        let signature_bytes = signature.to_bytes();
+        prop_assert_eq!(signature_bytes.len(), BLS12381_SIGNATURE_LENGTH);
+        let deserialized = BLS12381Signature::try_from(&signature_bytes[..]).unwrap();
+        prop_assert_eq!(&deserialized, &signature);
+
+        let serialized = lcs::to_bytes(&public_key).unwrap();
+        prop_assert_eq!(public_key, lcs::from_bytes::<BLS12381PublicKey>(&serialized).unwrap());
+        let serialized = serde_json::to_string(&signature).unwrap();
+        prop_assert_eq!(signature, serde_json::from_str::<BLS12381Signature>(&serialized).unwrap());
+    }
+
+    #[test]
+    fn test_aggregate_verify(
+        message in random_serializable_struct(),
+        other_message in random_serializable_struct(),
+        keypairs in keypairs_strategy(),
+    ) {
+        let public_keys: Vec<_> = keypairs.iter().map(|keypair| &keypair.public_key).collect();
+        let signatures: Vec<_> = keypairs
+            .iter()
+            .map(|keypair| keypair.private_key.sign(&message))
+            .collect();
+        let aggregate = BLS12381Signature::aggregate(&signatures.iter().collect::<Vec<_>>())
+            .unwrap();
+        prop_assert!(aggregate.verify_aggregate(&message, &public_keys).is_ok());
+        prop_assert!(aggregate
+            .verify(&message, &BLS12381PublicKey::aggregate(&public_keys).unwrap())
+            .is_ok());
+
+        // The aggregate does not verify against a different message or a subset of the signers.
+        if message.0 != other_message.0 {
+            prop_assert!(aggregate.verify_aggregate(&other_message, &public_keys).is_err());
+        }
+        if public_keys.len() > 1 {
+            prop_assert!(aggregate.verify_aggregate(&message, &public_keys[1..]).is_err());
+        }
+
+        let keys_and_signatures: Vec<_> = keypairs
+            .iter()
+            .map(|keypair| keypair.public_key.clone())
+            .zip(signatures.into_iter())
+            .collect();
+        prop_assert!(BLS12381Signature::batch_verify(&message, keys_and_signatures).is_ok());
+    }
+
+    #[test]
+    fn test_proof_of_possession(
+        keypair in uniform_keypair_strategy::<BLS12381PrivateKey, BLS12381PublicKey>(),
+        other_keypair in uniform_keypair_strategy::<BLS12381PrivateKey, BLS12381PublicKey>(),
+    ) {
+        let proof = keypair.private_key.proof_of_possession();
+        prop_assert!(keypair.public_key.verify_proof_of_possession(&proof).is_ok());
+        prop_assert!(other_keypair.public_key.verify_proof_of_possession(&proof).is_err());
+
+        // A proof of possession is not a signature of the public key under the signature domain.
+        prop_assert!(proof
+            .verify_arbitrary_msg(&keypair.public_key.to_bytes(), &keypair.public_key)
+            .is_err());
+    }
+}
+
+#[test]
+fn test_wrong_lengths() {
+    assert_eq!(
+        BLS12381PrivateKey::try_from(&[0u8; BLS12381_PRIVATE_KEY_LENGTH - 1][..]),
+        Err(WrongLengthError)
+    );
+    assert_eq!(
+        BLS12381PublicKey::try_from(&[0u8; BLS12381_PUBLIC_KEY_LENGTH + 1][..]),
+        Err(WrongLengthError)
+    );
+    assert_eq!(
+        BLS12381Signature::try_from(&[0u8; BLS12381_SIGNATURE_LENGTH - 1][..]),
+        Err(WrongLengthError)
+    );
+}
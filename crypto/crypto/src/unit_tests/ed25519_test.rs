@@ -11,6 +11,7 @@ use crate::{
         Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature, ED25519_PRIVATE_KEY_LENGTH,
         ED25519_PUBLIC_KEY_LENGTH, ED25519_SIGNATURE_LENGTH,
     },
+    hash::{CryptoHash, CryptoHasher},
     test_utils::{random_serializable_struct, uniform_keypair_strategy},
     traits::*,
     x25519,
@@ -150,6 +151,43 @@ proptest! {
         prop_assert!(Ed25519Signature::batch_verify(&message, signatures).is_err());
     }
 
+    #[test]
+    fn test_batch_verify_arbitrary_msgs(
+        messages in vec(vec(any::<u8>(), 0..64), 10),
+        keypairs in proptest::array::uniform10(uniform_keypair_strategy::<Ed25519PrivateKey, Ed25519PublicKey>())
+    ) {
+        let messages: Vec<&[u8]> = messages.iter().map(|message| &message[..]).collect();
+        let public_keys: Vec<_> = keypairs.iter().map(|keypair| &keypair.public_key).collect();
+        let signatures: Vec<_> = keypairs
+            .iter()
+            .zip(messages.iter())
+            .map(|(keypair, message)| keypair.private_key.sign_arbitrary_message(message))
+            .collect();
+        let mut signature_refs: Vec<_> = signatures.iter().collect();
+        prop_assert!(Ed25519Signature::batch_verify_arbitrary_msgs(
+            &messages,
+            &public_keys,
+            &signature_refs
+        ).is_ok());
+
+        // Mismatched lengths are rejected.
+        prop_assert!(Ed25519Signature::batch_verify_arbitrary_msgs(
+            &messages[1..],
+            &public_keys,
+            &signature_refs
+        ).is_err());
+
+        // A signature over the wrong message invalidates the whole batch.
+        if messages[0] != messages[1] {
+            signature_refs.swap(0, 1);
+            prop_assert!(Ed25519Signature::batch_verify_arbitrary_msgs(
+                &messages,
+                &public_keys,
+                &signature_refs
+            ).is_err());
+        }
+    }
+
     #[test]
     fn test_keys_custom_serialisation(
         keypair in uniform_keypair_strategy::<Ed25519PrivateKey, Ed25519PublicKey>()
@@ -281,6 +319,61 @@ fn test_publickey_smallorder() {
     }
 }
 
+// Test a signature under a public key with a torsion component. The strict verification of a
+// single signature only rejects keys of small order, and accepts it. Batch verification rejects
+// it, as it requires keys in the prime order subgroup, and the batch verification of the
+// signature trait then falls back to verifying the signatures one by one.
+#[test]
+fn test_batch_verify_mixed_order_public_key() {
+    use curve25519_dalek::{
+        constants::ED25519_BASEPOINT_POINT, edwards::CompressedEdwardsY, scalar::Scalar,
+        traits::IsIdentity,
+    };
+    use sha2::Digest;
+
+    let message = CryptoHashable(0);
+    let mut message_bytes =
+        <<CryptoHashable as CryptoHash>::Hasher as CryptoHasher>::seed().to_vec();
+    lcs::serialize_into(&mut message_bytes, &message).unwrap();
+
+    let torsion = CompressedEdwardsY(EIGHT_TORSION[1]).decompress().unwrap();
+    let secret = Scalar::from_bytes_mod_order([7; 32]);
+    let public_point = secret * ED25519_BASEPOINT_POINT + torsion;
+    let public_key = Ed25519PublicKey::try_from(&public_point.compress().to_bytes()[..]).unwrap();
+
+    // Look for a nonce point R = [r]B + [k]T such that the torsion components of the verification
+    // equation [s]B = R + [h]A cancel out, i.e. [k]T + [h]T is the identity.
+    let signature = (1u8..=255)
+        .flat_map(|i| (0u8..8).map(move |k| (i, k)))
+        .find_map(|(i, k)| {
+            let nonce = Scalar::from_bytes_mod_order([i; 32]);
+            let nonce_point = nonce * ED25519_BASEPOINT_POINT + Scalar::from(k) * torsion;
+            let mut hasher = sha2::Sha512::new();
+            hasher.update(nonce_point.compress().as_bytes());
+            hasher.update(public_point.compress().as_bytes());
+            hasher.update(&message_bytes);
+            let mut hash = [0u8; 64];
+            hash.copy_from_slice(&hasher.finalize());
+            let h = Scalar::from_bytes_mod_order_wide(&hash);
+            if !(Scalar::from(k) * torsion + h * torsion).is_identity() {
+                return None;
+            }
+            let s = nonce + h * secret;
+            let bytes = [nonce_point.compress().to_bytes(), s.to_bytes()].concat();
+            Some(Ed25519Signature::try_from(&bytes[..]).unwrap())
+        })
+        .unwrap();
+
+    assert!(signature.verify(&message, &public_key).is_ok());
+    assert!(Ed25519Signature::batch_verify_arbitrary_msgs(
+        &[&message_bytes[..]],
+        &[&public_key],
+        &[&signature]
+    )
+    .is_err());
+    assert!(Ed25519Signature::batch_verify(&message, vec![(public_key, signature)]).is_ok());
+}
+
 // The 8-torsion subgroup E[8].
 //
 // In the case of Curve25519, it is cyclic; the i-th element of
@@ -0,0 +1,124 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    hd::{DerivationPath, DerivationPathError, HDKeyFactory, MAIN_KEY_LENGTH},
+    traits::*,
+};
+use proptest::prelude::*;
+
+// The seed of the LibraWallet for the mnemonic "legal winner thank year wave sausage worth useful
+// legal winner thank year wave sausage worth useful legal will" and the salt "LIBRA".
+const SEED: &str = "8d8d9b85e36b2b9486becd31288e9dc2501cf77f95deb7d141eeb49d77f8a80f";
+
+// (path, expected private key)
+const TEST_VECTORS: &[(&str, &str)] = &[
+    (
+        "m/0",
+        "e6400d102987959a5165867583fdb1b4ae0ef6679a655d5a671483edee10f3f2",
+    ),
+    (
+        "m/1",
+        "ea0d01cd35dada45246208dfea0be4a32a9fc63dffcacbf277b321dd2c9a4828",
+    ),
+    (
+        "m/0/0",
+        "0243123ed38f9b1aa4aed87c69f8ab47a1250db6279d5efabf79e6a4a39a9bff",
+    ),
+    (
+        "m/0/1",
+        "ba62dc98e44bca70e381d8b33139cb80b3946da2cac3219c2c8156f5e77aa288",
+    ),
+    (
+        "m/1/5/7",
+        "ff365e0310fb21ea17663adf3220a30965212c03acead174e54df7f104737f96",
+    ),
+];
+
+fn factory() -> HDKeyFactory {
+    HDKeyFactory::from_seed(&hex::decode(SEED).unwrap()).unwrap()
+}
+
+#[test]
+fn test_hd_test_vectors() {
+    let factory = factory();
+    assert_eq!(
+        "f3573b6dfee9718f6344971a7eb6c3521448b12fb893b77bdb7e8f99e3f7013f",
+        hex::encode(factory.main())
+    );
+    assert_eq!(
+        "c16681bd291bee1c060a7060ee38022effb6f6c322aa9a3ef3866a3b8c9967a4",
+        hex::encode(factory.child_factory(0).main())
+    );
+    let mut main = [0u8; MAIN_KEY_LENGTH];
+    main.copy_from_slice(factory.child_factory(0).main());
+    assert_eq!(
+        "0243123ed38f9b1aa4aed87c69f8ab47a1250db6279d5efabf79e6a4a39a9bff",
+        hex::encode(HDKeyFactory::from_main(main).private_child(0).to_bytes())
+    );
+
+    for (path, expected) in TEST_VECTORS {
+        let path: DerivationPath = path.parse().unwrap();
+        let private_key = factory.derive_private_key(&path);
+        assert_eq!(*expected, hex::encode(private_key.to_bytes()));
+    }
+}
+
+#[test]
+fn test_account_paths_match_children() {
+    let factory = factory();
+    for index in 0..4 {
+        let path = DerivationPath::account(index);
+        assert_eq!(path.to_string(), format!("m/{}", index));
+        assert_eq!(
+            factory.derive_private_key(&path),
+            factory.private_child(index)
+        );
+    }
+}
+
+#[test]
+fn test_invalid_paths() {
+    assert_eq!(
+        "m".parse::<DerivationPath>(),
+        Err(DerivationPathError::EmptyPath)
+    );
+    assert_eq!(
+        DerivationPath::new(vec![]),
+        Err(DerivationPathError::EmptyPath)
+    );
+    for path in &[
+        "",
+        "m/",
+        "n/0",
+        "0/1",
+        "m/0/",
+        "m/-1",
+        "m/0'",
+        "m/18446744073709551616",
+    ] {
+        assert_eq!(
+            path.parse::<DerivationPath>(),
+            Err(DerivationPathError::InvalidPath(path.to_string()))
+        );
+    }
+}
+
+proptest! {
+    #[test]
+    fn test_path_roundtrip(child_numbers in proptest::collection::vec(any::<u64>(), 1..8)) {
+        let path = DerivationPath::new(child_numbers).unwrap();
+        prop_assert_eq!(path.to_string().parse::<DerivationPath>().unwrap(), path);
+    }
+
+    #[test]
+    fn test_distinct_paths_derive_distinct_keys(seed in any::<[u8; 32]>(), a in any::<u64>(), b in any::<u64>()) {
+        let factory = HDKeyFactory::from_seed(&seed).unwrap();
+        let parent = DerivationPath::account(a);
+        let child = DerivationPath::new(vec![a, b]).unwrap();
+        prop_assert_ne!(
+            factory.derive_private_key(&parent).to_bytes(),
+            factory.derive_private_key(&child).to_bytes()
+        );
+    }
+}
@@ -1,11 +1,15 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "bls12381")]
+mod bls12381_test;
 mod compat_test;
 mod cross_test;
 mod cryptohasher;
 mod ed25519_test;
 mod hash_test;
+mod hd_test;
 mod hkdf_test;
 mod multi_ed25519_test;
 mod noise_test;
+mod x25519_test;
@@ -452,3 +452,49 @@ fn test_multi_ed25519_signature_verification() {
         .verify(message(), &multi_public_key_2of3)
         .is_err());
 }
+
+// Test splitting a K-of-N key into shares, signing with K of them and rebuilding the public key.
+#[test]
+fn test_multi_ed25519_key_shares() {
+    let mut rng = StdRng::from_seed(TEST_SEED);
+    let private_key = MultiEd25519PrivateKey::generate_threshold_key(&mut rng, 2, 3).unwrap();
+    let public_key = MultiEd25519PublicKey::from(&private_key);
+
+    let shares = private_key.into_shares();
+    assert_eq!(shares.len(), 3);
+    for (index, share) in shares.iter().enumerate() {
+        assert_eq!(share.index() as usize, index);
+        assert_eq!(share.threshold(), 2);
+        assert_eq!(share.num_of_keys(), 3);
+        assert_eq!(share.public_key(), public_key.public_keys()[index]);
+        let serialized = lcs::to_bytes(share).unwrap();
+        assert_eq!(share, &lcs::from_bytes(&serialized).unwrap());
+    }
+
+    // Any 2 of the shares can sign on behalf of the key.
+    let signature =
+        MultiEd25519Signature::new(vec![shares[2].sign(message()), shares[0].sign(message())])
+            .unwrap();
+    assert!(signature.verify(message(), &public_key).is_ok());
+    let signature = MultiEd25519Signature::new(vec![shares[1].sign(message())]).unwrap();
+    assert!(signature.verify(message(), &public_key).is_err());
+
+    // The public key can be rebuilt from the public keys of the shares, in any order.
+    let share_public_keys: Vec<_> = shares
+        .iter()
+        .rev()
+        .map(|share| (share.index(), share.public_key()))
+        .collect();
+    assert_eq!(
+        MultiEd25519PublicKey::from_shares(share_public_keys.clone(), 2).unwrap(),
+        public_key
+    );
+    assert_eq!(
+        MultiEd25519PublicKey::from_shares(share_public_keys[..2].to_vec(), 2),
+        Err(ValidationError)
+    );
+    assert_eq!(
+        MultiEd25519PrivateKey::generate_threshold_key(&mut rng, 4, 3).err(),
+        Some(ValidationError)
+    );
+}
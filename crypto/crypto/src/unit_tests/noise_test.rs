@@ -108,6 +108,66 @@ fn simple_handshake() {
     }
 }
 
+#[test]
+fn rekey() {
+    // setup peers
+    let mut rng = ::rand::rngs::StdRng::from_seed(TEST_SEED);
+    let initiator_private = x25519::PrivateKey::generate(&mut rng);
+    let responder_private = x25519::PrivateKey::generate(&mut rng);
+    let responder_public = responder_private.public_key();
+    let initiator = NoiseConfig::new(initiator_private);
+    let responder = NoiseConfig::new(responder_private);
+
+    // handshake
+    let mut first_message = vec![0u8; handshake_init_msg_len(0)];
+    let initiator_state = initiator
+        .initiate_connection(&mut rng, b"", responder_public, None, &mut first_message)
+        .unwrap();
+    let mut second_message = vec![0u8; handshake_resp_msg_len(0)];
+    let (_, mut responder_session) = responder
+        .respond_to_client_and_finalize(&mut rng, b"", &first_message, None, &mut second_message)
+        .unwrap();
+    let (_, mut initiator_session) = initiator
+        .finalize_connection(initiator_state, &second_message)
+        .unwrap();
+
+    // both peers rekey the initiator -> responder direction
+    initiator_session.rekey_write().unwrap();
+    responder_session.rekey_read().unwrap();
+    let message_sent = b"after rekey".to_vec();
+    let mut message = message_sent.clone();
+    let auth_tag = initiator_session
+        .write_message_in_place(&mut message)
+        .unwrap();
+    message.extend_from_slice(&auth_tag);
+    let received_message = responder_session
+        .read_message_in_place(&mut message)
+        .unwrap();
+    assert_eq!(received_message, message_sent.as_slice());
+
+    // the responder -> initiator direction is left untouched
+    let mut message = message_sent.clone();
+    let auth_tag = responder_session
+        .write_message_in_place(&mut message)
+        .unwrap();
+    message.extend_from_slice(&auth_tag);
+    let received_message = initiator_session
+        .read_message_in_place(&mut message)
+        .unwrap();
+    assert_eq!(received_message, message_sent.as_slice());
+
+    // a peer that does not rekey can no longer decrypt
+    initiator_session.rekey_write().unwrap();
+    let mut message = message_sent.clone();
+    let auth_tag = initiator_session
+        .write_message_in_place(&mut message)
+        .unwrap();
+    message.extend_from_slice(&auth_tag);
+    assert!(responder_session
+        .read_message_in_place(&mut message)
+        .is_err());
+}
+
 #[test]
 fn test_vectors() {
     // structures needed to deserialize test vectors
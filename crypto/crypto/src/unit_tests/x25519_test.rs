@@ -0,0 +1,100 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{test_utils::uniform_keypair_strategy, x25519, CryptoMaterialError::*};
+use proptest::prelude::*;
+
+/// Helper to build a public key from its hex encoding
+fn public_key(hex_encoding: &str) -> x25519::PublicKey {
+    let mut bytes = [0u8; x25519::PUBLIC_KEY_SIZE];
+    bytes.copy_from_slice(&hex::decode(hex_encoding).unwrap());
+    x25519::PublicKey::from(bytes)
+}
+
+#[test]
+fn test_validate_small_order_points() {
+    // u = 0 (order 2)
+    assert_eq!(
+        public_key("0000000000000000000000000000000000000000000000000000000000000000").validate(),
+        Err(SmallSubgroupError)
+    );
+    // u = 1 (order 4)
+    assert_eq!(
+        public_key("0100000000000000000000000000000000000000000000000000000000000000").validate(),
+        Err(SmallSubgroupError)
+    );
+    // points of order 8
+    assert_eq!(
+        public_key("e0eb7a7c3b41b8ae1656e3faf19fc46ada098deb9c32b1fd866205165f49b800").validate(),
+        Err(SmallSubgroupError)
+    );
+    assert_eq!(
+        public_key("5f9c95bca3508c24b1d0b1559c83ef5b04445cc4581c8e86d8224eddd09f1157").validate(),
+        Err(SmallSubgroupError)
+    );
+    // u = p - 1
+    assert!(
+        public_key("ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f")
+            .validate()
+            .is_err()
+    );
+}
+
+#[test]
+fn test_validate_non_canonical_points() {
+    // u = p and u = p + 1, non-canonical encodings of 0 and 1
+    assert_eq!(
+        public_key("edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f").validate(),
+        Err(CanonicalRepresentationError)
+    );
+    assert_eq!(
+        public_key("eeffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f").validate(),
+        Err(CanonicalRepresentationError)
+    );
+    // the base point u = 9, with the unused top bit set
+    assert_eq!(
+        public_key("0900000000000000000000000000000000000000000000000000000000000080").validate(),
+        Err(CanonicalRepresentationError)
+    );
+}
+
+#[test]
+fn test_validate_twist_points() {
+    // u = 2 lies on the twist of Curve25519
+    assert_eq!(
+        public_key("0200000000000000000000000000000000000000000000000000000000000000").validate(),
+        Err(PointNotOnCurveError)
+    );
+}
+
+#[test]
+fn test_validate_base_point() {
+    assert!(
+        public_key("0900000000000000000000000000000000000000000000000000000000000000")
+            .validate()
+            .is_ok()
+    );
+}
+
+proptest! {
+    #[test]
+    fn test_validate_generated_keys(
+        keypair in uniform_keypair_strategy::<x25519::PrivateKey, x25519::PublicKey>()
+    ) {
+        prop_assert!(keypair.public_key.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_arbitrary_bytes(
+        bytes in any::<[u8; x25519::PUBLIC_KEY_SIZE]>(),
+        keypair in uniform_keypair_strategy::<x25519::PrivateKey, x25519::PublicKey>()
+    ) {
+        let public_key = x25519::PublicKey::from(bytes);
+        if public_key.validate().is_ok() {
+            // valid keys are canonical and never lead to a trivial shared secret
+            prop_assert_eq!(bytes[x25519::PUBLIC_KEY_SIZE - 1] & 0x80, 0);
+            let shared_secret = keypair.private_key.diffie_hellman(&public_key);
+            prop_assert_ne!(shared_secret, [0u8; x25519::SHARED_SECRET_SIZE]);
+        }
+    }
+}
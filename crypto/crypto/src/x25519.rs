@@ -153,6 +153,28 @@ impl PublicKey {
 
         Ok(x25519::PublicKey::from(ed_point.to_montgomery().to_bytes()))
     }
+
+    /// Validate a public key before using it as a network identity.
+    ///
+    /// A Diffie-Hellman exchange with a point of small order (including the identity) results in
+    /// a predictable shared secret, and non-canonical encodings allow several byte
+    /// representations of the same key. This rejects:
+    /// - encodings that are not the canonical encoding of their u-coordinate,
+    /// - u-coordinates that lie on the twist rather than on Curve25519,
+    /// - points of small order.
+    pub fn validate(&self) -> Result<(), CryptoMaterialError> {
+        let montgomery_point = curve25519_dalek::montgomery::MontgomeryPoint(self.0);
+        let edwards_point = montgomery_point
+            .to_edwards(0)
+            .ok_or(CryptoMaterialError::PointNotOnCurveError)?;
+        if edwards_point.to_montgomery() != montgomery_point {
+            return Err(CryptoMaterialError::CanonicalRepresentationError);
+        }
+        if edwards_point.is_small_order() {
+            return Err(CryptoMaterialError::SmallSubgroupError);
+        }
+        Ok(())
+    }
 }
 
 //
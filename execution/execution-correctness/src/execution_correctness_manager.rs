@@ -10,12 +10,13 @@ use crate::{
     spawned_process::SpawnedProcess,
     thread::ThreadService,
 };
+use anyhow::{Context, Result};
 use executor::Executor;
 use libra_config::{
     config::{ExecutionCorrectnessService, NodeConfig},
     keys::KeyPair,
 };
-use libra_crypto::ed25519::Ed25519PrivateKey;
+use libra_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey};
 use libra_global_constants::EXECUTION_KEY;
 use libra_secure_storage::{CryptoStorage, Storage};
 use libra_vm::LibraVM;
@@ -58,6 +59,27 @@ pub fn extract_execution_prikey(config: &mut NodeConfig) -> Option<Ed25519Privat
     }
 }
 
+/// Returns the public execution key that consumers of execution results should use to verify
+/// result attestations, or `None` if execution is not configured to sign its results. Fails if
+/// signing is configured but the key cannot be read from the execution backend.
+pub fn extract_execution_pubkey(config: &NodeConfig) -> Result<Option<Ed25519PublicKey>> {
+    if !config.execution.sign_vote_proposal {
+        return Ok(None);
+    }
+    if let Some(keypair) = config
+        .test
+        .as_ref()
+        .and_then(|test_config| test_config.execution_keypair.as_ref())
+    {
+        return Ok(Some(keypair.public_key()));
+    }
+    let storage = Storage::from(&config.execution.backend);
+    let response = storage
+        .get_public_key(EXECUTION_KEY)
+        .with_context(|| format!("Unable to read {} from secure storage", EXECUTION_KEY))?;
+    Ok(Some(response.public_key))
+}
+
 enum ExecutionCorrectnessWrapper {
     Local(Arc<Mutex<LocalService>>),
     Process(ProcessService),
@@ -84,16 +106,23 @@ impl ExecutionCorrectnessManager {
 
         let execution_prikey = extract_execution_prikey(config);
         let storage_address = config.storage.address;
+        let max_retained_state_generations = config.execution.max_retained_state_generations;
         match &config.execution.service {
-            ExecutionCorrectnessService::Local => {
-                Self::new_local(storage_address, execution_prikey)
-            }
-            ExecutionCorrectnessService::Serializer => {
-                Self::new_serializer(storage_address, execution_prikey)
-            }
-            ExecutionCorrectnessService::Thread => {
-                Self::new_thread(storage_address, execution_prikey)
-            }
+            ExecutionCorrectnessService::Local => Self::new_local(
+                storage_address,
+                execution_prikey,
+                max_retained_state_generations,
+            ),
+            ExecutionCorrectnessService::Serializer => Self::new_serializer(
+                storage_address,
+                execution_prikey,
+                max_retained_state_generations,
+            ),
+            ExecutionCorrectnessService::Thread => Self::new_thread(
+                storage_address,
+                execution_prikey,
+                max_retained_state_generations,
+            ),
             _ => unreachable!(
                 "Unimplemented ExecutionCorrectnessService: {:?}",
                 config.execution.service
@@ -104,10 +133,12 @@ impl ExecutionCorrectnessManager {
     pub fn new_local(
         storage_address: SocketAddr,
         execution_prikey: Option<Ed25519PrivateKey>,
+        max_retained_state_generations: usize,
     ) -> Self {
-        let block_executor = Box::new(Executor::<LibraVM>::new(
+        let mut block_executor = Box::new(Executor::<LibraVM>::new(
             StorageClient::new(&storage_address).into(),
         ));
+        block_executor.set_max_retained_state_generations(max_retained_state_generations);
         Self {
             internal_execution_correctness: ExecutionCorrectnessWrapper::Local(Arc::new(
                 Mutex::new(LocalService::new(block_executor, execution_prikey)),
@@ -125,10 +156,12 @@ impl ExecutionCorrectnessManager {
     pub fn new_serializer(
         storage_address: SocketAddr,
         execution_prikey: Option<Ed25519PrivateKey>,
+        max_retained_state_generations: usize,
     ) -> Self {
-        let block_executor = Box::new(Executor::<LibraVM>::new(
+        let mut block_executor = Box::new(Executor::<LibraVM>::new(
             StorageClient::new(&storage_address).into(),
         ));
+        block_executor.set_max_retained_state_generations(max_retained_state_generations);
         let serializer_service = SerializerService::new(block_executor, execution_prikey);
         Self {
             internal_execution_correctness: ExecutionCorrectnessWrapper::Serializer(Arc::new(
@@ -147,8 +180,13 @@ impl ExecutionCorrectnessManager {
     pub fn new_thread(
         storage_address: SocketAddr,
         execution_prikey: Option<Ed25519PrivateKey>,
+        max_retained_state_generations: usize,
     ) -> Self {
-        let thread = ThreadService::new(storage_address, execution_prikey);
+        let thread = ThreadService::new(
+            storage_address,
+            execution_prikey,
+            max_retained_state_generations,
+        );
         Self {
             internal_execution_correctness: ExecutionCorrectnessWrapper::Thread(thread),
         }
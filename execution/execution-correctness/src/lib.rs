@@ -3,8 +3,9 @@
 
 #![forbid(unsafe_code)]
 
-use consensus_types::block::Block;
-use libra_crypto::HashValue;
+use consensus_types::{block::Block, vote_proposal::VoteProposal};
+use executor_types::StateComputeResult;
+use libra_crypto::{ed25519::Ed25519PrivateKey, traits::SigningKey, HashValue};
 use libra_types::transaction::Transaction;
 
 mod execution_correctness;
@@ -18,7 +19,8 @@ mod thread;
 
 pub use crate::{
     execution_correctness::ExecutionCorrectness,
-    execution_correctness_manager::ExecutionCorrectnessManager, process::Process,
+    execution_correctness_manager::{extract_execution_pubkey, ExecutionCorrectnessManager},
+    process::Process,
 };
 
 #[cfg(test)]
@@ -40,3 +42,19 @@ fn id_and_transactions_from_block(block: &Block) -> (HashValue, Vec<Transaction>
     );
     (id, transactions)
 }
+
+/// Signs both the vote proposal and the execution attestation of the result of executing `block`.
+fn sign_execution_result(
+    prikey: &Ed25519PrivateKey,
+    block: Block,
+    result: &mut StateComputeResult,
+) {
+    let attestation = result.attestation(block.id());
+    result.set_attestation_signature(prikey.sign(&attestation));
+    let vote_proposal = VoteProposal::new(
+        result.extension_proof(),
+        block,
+        result.epoch_state().clone(),
+    );
+    result.set_signature(prikey.sign(&vote_proposal));
+}
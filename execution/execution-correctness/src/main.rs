@@ -24,6 +24,7 @@ fn main() {
 
     libra_logger::Logger::new()
         .channel_size(config.logger.chan_size)
+        .format(config.logger.format)
         .is_async(config.logger.is_async)
         .level(config.logger.level)
         .init();
@@ -29,7 +29,21 @@ impl Process {
             }
             _ => panic!("Unexpected ExecutionCorrectness service: {:?}", service),
         };
-        remote_service::execute(self.config.storage.address, server_addr, self.prikey);
+        libra_vm::signature_verification::set_num_threads(
+            self.config.execution.signature_verification_threads,
+        )
+        .expect("Starting signature verification pool should work.");
+        libra_vm::set_loader_cache_budget(
+            self.config.execution.vm_max_loaded_binary_size,
+            self.config.execution.vm_max_type_cache_entries,
+        )
+        .expect("Setting the VM loader cache budget should work.");
+        remote_service::execute(
+            self.config.storage.address,
+            server_addr,
+            self.prikey,
+            self.config.execution.max_retained_state_generations,
+        );
     }
 }
 
@@ -27,10 +27,12 @@ pub fn execute(
     storage_addr: SocketAddr,
     listen_addr: SocketAddr,
     prikey: Option<Ed25519PrivateKey>,
+    max_retained_state_generations: usize,
 ) {
-    let block_executor = Box::new(Executor::<LibraVM>::new(
+    let mut block_executor = Box::new(Executor::<LibraVM>::new(
         StorageClient::new(&storage_addr).into(),
     ));
+    block_executor.set_max_retained_state_generations(max_retained_state_generations);
     let mut serializer_service = SerializerService::new(block_executor, prikey);
     let mut network_server = NetworkServer::new(listen_addr);
 
@@ -1,10 +1,13 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{execution_correctness::ExecutionCorrectness, id_and_transactions_from_block};
-use consensus_types::{block::Block, vote_proposal::VoteProposal};
+use crate::{
+    execution_correctness::ExecutionCorrectness, id_and_transactions_from_block,
+    sign_execution_result,
+};
+use consensus_types::block::Block;
 use executor_types::{BlockExecutor, Error, StateComputeResult};
-use libra_crypto::{ed25519::Ed25519PrivateKey, traits::SigningKey, HashValue};
+use libra_crypto::{ed25519::Ed25519PrivateKey, HashValue};
 use libra_types::{
     contract_event::ContractEvent, ledger_info::LedgerInfoWithSignatures, transaction::Transaction,
 };
@@ -46,13 +49,11 @@ impl SerializerService {
                     )
                     .map(|mut result| {
                         if let Some(prikey) = self.prikey.as_ref() {
-                            let vote_proposal = VoteProposal::new(
-                                result.extension_proof(),
+                            sign_execution_result(
+                                prikey,
                                 block_with_parent_id.0.clone(),
-                                result.epoch_state().clone(),
+                                &mut result,
                             );
-                            let signature = prikey.sign(&vote_proposal);
-                            result.set_signature(signature);
                         }
                         result
                     }),
@@ -27,7 +27,10 @@ fn execution_correctness(
     } else {
         (None, None)
     };
-    let execution_correctness_manager =
-        ExecutionCorrectnessManager::new_serializer(config.storage.address, prikey);
+    let execution_correctness_manager = ExecutionCorrectnessManager::new_serializer(
+        config.storage.address,
+        prikey,
+        config.execution.max_retained_state_generations,
+    );
     (execution_correctness_manager.client(), pubkey)
 }
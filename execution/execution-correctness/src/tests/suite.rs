@@ -4,7 +4,7 @@
 use crate::execution_correctness::ExecutionCorrectness;
 use consensus_types::{block::Block, vote_proposal::VoteProposal};
 use executor_test_helpers::{extract_signer, gen_ledger_info_with_sigs};
-use libra_crypto::{ed25519::*, traits::Signature};
+use libra_crypto::{ed25519::*, traits::Signature, HashValue};
 
 pub fn run_test_suite(executor_pair: (Box<dyn ExecutionCorrectness>, Option<Ed25519PublicKey>)) {
     let (mut config, _genesis_key) = config_builder::test_config();
@@ -25,8 +25,16 @@ pub fn run_test_suite(executor_pair: (Box<dyn ExecutionCorrectness>, Option<Ed25
             block,
             result.epoch_state().clone(),
         );
-        sig.verify(&vote_proposal, &execution_pubkey.unwrap())
+        sig.verify(&vote_proposal, execution_pubkey.as_ref().unwrap())
             .unwrap();
+        result
+            .verify_attestation(block_id, execution_pubkey.as_ref().unwrap())
+            .unwrap();
+        assert!(result
+            .verify_attestation(HashValue::zero(), execution_pubkey.as_ref().unwrap())
+            .is_err());
+    } else {
+        assert!(result.attestation_signature().is_none());
     }
 
     let ledger_info_with_sigs = gen_ledger_info_with_sigs(1, result, block_id, vec![&signer]);
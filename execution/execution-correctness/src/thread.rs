@@ -23,13 +23,23 @@ pub struct ThreadService {
 }
 
 impl ThreadService {
-    pub fn new(storage_addr: SocketAddr, prikey: Option<Ed25519PrivateKey>) -> Self {
+    pub fn new(
+        storage_addr: SocketAddr,
+        prikey: Option<Ed25519PrivateKey>,
+        max_retained_state_generations: usize,
+    ) -> Self {
         let listen_port = utils::get_available_port();
         let listen_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), listen_port);
         let server_addr = listen_addr;
 
-        let child =
-            thread::spawn(move || remote_service::execute(storage_addr, listen_addr, prikey));
+        let child = thread::spawn(move || {
+            remote_service::execute(
+                storage_addr,
+                listen_addr,
+                prikey,
+                max_retained_state_generations,
+            )
+        });
 
         Self {
             _child: child,
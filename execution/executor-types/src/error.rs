@@ -11,6 +11,12 @@ pub enum Error {
     #[error("Cannot find speculation result for block id {0}")]
     BlockNotFound(HashValue),
 
+    #[error("Missing execution attestation for block id {0}")]
+    AttestationNotFound(HashValue),
+
+    #[error("Invalid execution attestation for block id {0}")]
+    InvalidAttestation(HashValue),
+
     #[error("Internal error: {:?}", error)]
     InternalError { error: String },
 
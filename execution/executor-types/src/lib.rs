@@ -8,10 +8,12 @@ pub use error::Error;
 
 use anyhow::Result;
 use libra_crypto::{
-    ed25519::Ed25519Signature,
+    ed25519::{Ed25519PublicKey, Ed25519Signature},
     hash::{TransactionAccumulatorHasher, SPARSE_MERKLE_PLACEHOLDER_HASH},
+    traits::Signature,
     HashValue,
 };
+use libra_crypto_derive::{CryptoHasher, LCSCryptoHash};
 use libra_types::{
     contract_event::ContractEvent,
     epoch_state::EpochState,
@@ -85,6 +87,8 @@ pub trait BlockExecutor: Send {
 pub struct StateComputeResult {
     /// transaction accumulator root hash is identified as `state_id` in Consensus.
     root_hash: HashValue,
+    /// The root hash of the sparse merkle tree of the account state after the execution.
+    state_root: HashValue,
     /// Represents the roots of all the full subtrees from left to right in this accumulator
     /// after the execution. For details, please see [`InMemoryAccumulator`](accumulator::InMemoryAccumulator).
     frozen_subtree_roots: Vec<HashValue>,
@@ -111,11 +115,16 @@ pub struct StateComputeResult {
 
     /// The signature of the VoteProposal corresponding to this block.
     signature: Option<Ed25519Signature>,
+
+    /// The signature of the `ExecutionAttestation` corresponding to this block, produced by the
+    /// execution key so that consumers can check the result did not change in transit.
+    attestation_signature: Option<Ed25519Signature>,
 }
 
 impl StateComputeResult {
     pub fn new(
         root_hash: HashValue,
+        state_root: HashValue,
         frozen_subtree_roots: Vec<HashValue>,
         num_leaves: u64,
         parent_frozen_subtree_roots: Vec<HashValue>,
@@ -126,6 +135,7 @@ impl StateComputeResult {
     ) -> Self {
         Self {
             root_hash,
+            state_root,
             frozen_subtree_roots,
             num_leaves,
             parent_frozen_subtree_roots,
@@ -134,6 +144,7 @@ impl StateComputeResult {
             compute_status,
             transaction_info_hashes,
             signature: None,
+            attestation_signature: None,
         }
     }
 }
@@ -147,6 +158,10 @@ impl StateComputeResult {
         self.root_hash
     }
 
+    pub fn state_root(&self) -> HashValue {
+        self.state_root
+    }
+
     pub fn compute_status(&self) -> &Vec<TransactionStatus> {
         &self.compute_status
     }
@@ -194,6 +209,66 @@ impl StateComputeResult {
     pub fn set_signature(&mut self, sig: Ed25519Signature) {
         self.signature = Some(sig);
     }
+
+    /// Builds the attestation that binds this result to the block that produced it.
+    pub fn attestation(&self, block_id: HashValue) -> ExecutionAttestation {
+        ExecutionAttestation::new(block_id, self.state_root, self.version())
+    }
+
+    pub fn attestation_signature(&self) -> &Option<Ed25519Signature> {
+        &self.attestation_signature
+    }
+
+    pub fn set_attestation_signature(&mut self, sig: Ed25519Signature) {
+        self.attestation_signature = Some(sig);
+    }
+
+    /// Verifies that the attestation of this result was signed by the given execution key.
+    pub fn verify_attestation(
+        &self,
+        block_id: HashValue,
+        public_key: &Ed25519PublicKey,
+    ) -> Result<(), Error> {
+        let signature = self
+            .attestation_signature
+            .as_ref()
+            .ok_or_else(|| Error::AttestationNotFound(block_id))?;
+        signature
+            .verify(&self.attestation(block_id), public_key)
+            .map_err(|_| Error::InvalidAttestation(block_id))
+    }
+}
+
+/// The statement signed by the execution key about the outcome of executing a block: executing
+/// `block_id` results in the account state with root hash `state_root` at `version`. It allows a
+/// consumer running on a different host than execution to check the result it received.
+#[derive(Clone, CryptoHasher, Debug, Deserialize, Eq, LCSCryptoHash, PartialEq, Serialize)]
+pub struct ExecutionAttestation {
+    block_id: HashValue,
+    state_root: HashValue,
+    version: Version,
+}
+
+impl ExecutionAttestation {
+    pub fn new(block_id: HashValue, state_root: HashValue, version: Version) -> Self {
+        Self {
+            block_id,
+            state_root,
+            version,
+        }
+    }
+
+    pub fn block_id(&self) -> HashValue {
+        self.block_id
+    }
+
+    pub fn state_root(&self) -> HashValue {
+        self.state_root
+    }
+
+    pub fn version(&self) -> Version {
+        self.version
+    }
 }
 
 /// A wrapper of the in-memory state sparse merkle tree and the transaction accumulator that
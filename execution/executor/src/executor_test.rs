@@ -168,6 +168,34 @@ fn test_executor_one_block() {
     executor.commit_blocks(vec![block_id], ledger_info).unwrap();
 }
 
+#[test]
+fn test_executor_release_state_trees() {
+    let mut executor = TestExecutor::new();
+    executor.set_max_retained_state_generations(3);
+    let parent_block_id = executor.committed_block_id();
+    let block_id = gen_block_id(1);
+
+    let txns = (0..10)
+        .map(|i| encode_mint_transaction(gen_address(i), 100))
+        .collect::<Vec<_>>();
+    let output = executor
+        .execute_block((block_id, txns), parent_block_id)
+        .unwrap();
+
+    let block = executor.cache.get_block(&block_id).unwrap();
+    let block = block.lock().unwrap();
+    let transaction_data = block.output().transaction_data();
+    assert_eq!(transaction_data.len(), 10);
+    for (i, txn_data) in transaction_data.iter().enumerate() {
+        assert_eq!(txn_data.state_tree().is_some(), i >= 7);
+    }
+    assert_eq!(
+        transaction_data.last().unwrap().state_root_hash(),
+        block.output().executed_trees().state_root(),
+    );
+    assert_eq!(output.version(), 10);
+}
+
 #[test]
 fn test_executor_multiple_blocks() {
     let mut executor = TestExecutor::new();
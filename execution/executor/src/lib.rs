@@ -56,10 +56,16 @@ use storage_interface::{state_view::VerifiedStateView, DbReaderWriter, TreeState
 static OP_COUNTERS: Lazy<libra_metrics::OpMetrics> =
     Lazy::new(|| libra_metrics::OpMetrics::new_and_registered("executor"));
 
+/// By default, only the state tree resulting from each block is kept in memory, not the ones after
+/// each of its transactions.
+pub const DEFAULT_MAX_RETAINED_STATE_GENERATIONS: usize = 0;
+
 /// `Executor` implements all functionalities the execution module needs to provide.
 pub struct Executor<V> {
     db: DbReaderWriter,
     cache: SpeculationCache,
+    /// The number of per transaction state trees kept in memory for each executed block.
+    max_retained_state_generations: usize,
     phantom: PhantomData<V>,
 }
 
@@ -82,10 +88,18 @@ where
         Self {
             db,
             cache: SpeculationCache::new_with_startup_info(startup_info),
+            max_retained_state_generations: DEFAULT_MAX_RETAINED_STATE_GENERATIONS,
             phantom: PhantomData,
         }
     }
 
+    /// Changes the number of per transaction state trees kept in memory for each executed block,
+    /// from the next executed block on. They are only needed for debugging, the state tree
+    /// resulting from the block is always kept until the block is committed or discarded.
+    pub fn set_max_retained_state_generations(&mut self, max_retained_state_generations: usize) {
+        self.max_retained_state_generations = max_retained_state_generations;
+    }
+
     fn reset_cache(&mut self) -> Result<(), Error> {
         let startup_info = self
             .db
@@ -100,6 +114,7 @@ where
         Self {
             db,
             cache: SpeculationCache::new_for_db_bootstrapping(tree_state),
+            max_retained_state_generations: DEFAULT_MAX_RETAINED_STATE_GENERATIONS,
             phantom: PhantomData,
         }
     }
@@ -682,7 +697,7 @@ impl<V: VMExecutor> BlockExecutor for Executor<V> {
             }
 
             let (account_to_state, account_to_proof) = state_view.into();
-            let output = Self::process_vm_outputs(
+            let mut output = Self::process_vm_outputs(
                 account_to_state,
                 account_to_proof,
                 &transactions,
@@ -690,6 +705,7 @@ impl<V: VMExecutor> BlockExecutor for Executor<V> {
                 &parent_block_executed_trees,
             )
             .map_err(|err| format_err!("Failed to execute block: {}", err))?;
+            output.release_state_trees(self.max_retained_state_generations);
 
             let parent_accu = parent_block_executed_trees.txn_accumulator();
 
@@ -703,6 +719,10 @@ impl<V: VMExecutor> BlockExecutor for Executor<V> {
         // Add the output to the speculation_output_tree
         self.cache
             .add_block(parent_block_id, (block_id, transactions, output))?;
+        OP_COUNTERS.set(
+            "state_tree_retained_nodes",
+            SparseMerkleTree::num_retained_nodes(),
+        );
 
         Ok(state_compute_result)
     }
@@ -808,8 +828,13 @@ impl<V: VMExecutor> BlockExecutor for Executor<V> {
             for txn_data in block.output().transaction_data() {
                 txn_data.prune_state_tree();
             }
+            block.output().executed_trees().state_tree().prune();
         }
         self.cache.prune(ledger_info_with_sigs.ledger_info())?;
+        OP_COUNTERS.set(
+            "state_tree_retained_nodes",
+            SparseMerkleTree::num_retained_nodes(),
+        );
 
         // Calculate committed transactions and reconfig events now that commit has succeeded
         let mut committed_txns = vec![];
@@ -48,7 +48,7 @@ pub static KEEP_STATUS: Lazy<TransactionStatus> =
 // We use 10 as the assertion error code for insufficient balance within the Libra coin contract.
 // TODO(tmn) provide a real abort location
 pub static DISCARD_STATUS: Lazy<TransactionStatus> =
-    Lazy::new(|| TransactionStatus::Discard(VMStatus::MoveAbort(AbortLocation::Script, 10)));
+    Lazy::new(|| TransactionStatus::Discard(VMStatus::MoveAbort(AbortLocation::Script, 10, None)));
 
 pub struct MockVM;
 
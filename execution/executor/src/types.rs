@@ -34,8 +34,12 @@ pub struct TransactionData {
 
     /// The in-memory Sparse Merkle Tree after the write set is applied. This is `Rc` because the
     /// tree has uncommitted state and sometimes `StateVersionView` needs to have a pointer to the
-    /// tree so VM can read it.
-    state_tree: Arc<SparseMerkleTree>,
+    /// tree so VM can read it. It is `None` once released to save memory, see
+    /// `ProcessedVMOutput::release_state_trees`.
+    state_tree: Option<Arc<SparseMerkleTree>>,
+
+    /// The root hash of `state_tree`, kept after the tree is released.
+    state_root_hash: HashValue,
 
     /// The in-memory Merkle Accumulator that has all events emitted by this transaction.
     event_tree: Arc<InMemoryAccumulator<EventAccumulatorHasher>>,
@@ -61,7 +65,8 @@ impl TransactionData {
             account_blobs,
             events,
             status,
-            state_tree,
+            state_root_hash: state_tree.root_hash(),
+            state_tree: Some(state_tree),
             event_tree,
             gas_used,
             txn_info_hash,
@@ -81,7 +86,11 @@ impl TransactionData {
     }
 
     pub fn state_root_hash(&self) -> HashValue {
-        self.state_tree.root_hash()
+        self.state_root_hash
+    }
+
+    pub fn state_tree(&self) -> Option<&Arc<SparseMerkleTree>> {
+        self.state_tree.as_ref()
     }
 
     pub fn event_root_hash(&self) -> HashValue {
@@ -93,7 +102,14 @@ impl TransactionData {
     }
 
     pub fn prune_state_tree(&self) {
-        self.state_tree.prune()
+        if let Some(state_tree) = &self.state_tree {
+            state_tree.prune()
+        }
+    }
+
+    /// Drops the reference to the state tree, whose nodes not shared with a later tree are freed.
+    pub fn release_state_tree(&mut self) {
+        self.state_tree = None;
     }
 
     pub fn txn_info_hash(&self) -> Option<HashValue> {
@@ -133,6 +149,17 @@ impl ProcessedVMOutput {
         &self.transaction_data
     }
 
+    /// Keeps the state trees of only the last `num_to_retain` transactions. The state tree after
+    /// each transaction is a generation of the speculative state, only the last one is needed to
+    /// execute the following blocks, and it is kept in `executed_trees` regardless. Retaining all of
+    /// them keeps the previous values of every account updated in the block in memory.
+    pub fn release_state_trees(&mut self, num_to_retain: usize) {
+        let num_to_release = self.transaction_data.len().saturating_sub(num_to_retain);
+        for txn_data in &mut self.transaction_data[..num_to_release] {
+            txn_data.release_state_tree();
+        }
+    }
+
     pub fn executed_trees(&self) -> &ExecutedTrees {
         &self.executed_trees
     }
@@ -165,6 +192,7 @@ impl ProcessedVMOutput {
         // next epoch that is part of a block execution.
         StateComputeResult::new(
             self.accu_root(),
+            self.executed_trees().state_root(),
             txn_accu.frozen_subtree_roots().clone(),
             txn_accu.num_leaves(),
             parent_frozen_subtree_roots,
@@ -0,0 +1,412 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! Re-executes a range of committed transactions and compares the outputs against what is
+//! recorded in a LibraDB or in a transaction backup. This is meant to validate VM upgrades before
+//! they are deployed: any change in behavior shows up as a divergence in status, gas, events or
+//! state, the latter two being checked against the event and state root hashes recorded in the
+//! `TransactionInfo`s so that no write or event can go unnoticed.
+
+use anyhow::{ensure, format_err, Result};
+use libra_crypto::{
+    hash::{CryptoHash, EventAccumulatorHasher, SPARSE_MERKLE_PLACEHOLDER_HASH},
+    HashValue,
+};
+use libra_state_view::StateView;
+use libra_types::{
+    access_path::AccessPath,
+    account_address::AccountAddress,
+    account_state::AccountState,
+    account_state_blob::AccountStateBlob,
+    contract_event::ContractEvent,
+    proof::{accumulator::InMemoryAccumulator, SparseMerkleProof},
+    transaction::{Transaction, TransactionInfo, TransactionOutput, Version},
+    vm_status::StatusCode,
+    write_set::WriteOp,
+};
+use libra_vm::VMExecutor;
+use scratchpad::{ProofRead, SparseMerkleTree};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    fmt,
+};
+use storage_interface::DbReader;
+
+#[cfg(test)]
+mod replay_test;
+
+/// The number of transactions fetched from the DB at a time.
+const BATCH_SIZE: u64 = 1000;
+
+/// A state view that reads the account states from the DB as of `base_version` and then reflects
+/// the write sets of all the transactions replayed so far, so that every replayed transaction
+/// observes the state produced by the replay rather than the state recorded in the DB. It also
+/// maintains the state tree of the replay, whose root hash is compared against the recorded one.
+struct ReplayStateView<'a> {
+    reader: &'a dyn DbReader,
+    base_version: Option<Version>,
+    account_states: RefCell<HashMap<AccountAddress, AccountState>>,
+    account_proofs: RefCell<HashMap<HashValue, SparseMerkleProof>>,
+    state_tree: SparseMerkleTree,
+}
+
+impl<'a> ReplayStateView<'a> {
+    fn new(
+        reader: &'a dyn DbReader,
+        base_version: Option<Version>,
+        base_state_root: HashValue,
+    ) -> Self {
+        Self {
+            reader,
+            base_version,
+            account_states: RefCell::new(HashMap::new()),
+            account_proofs: RefCell::new(HashMap::new()),
+            state_tree: SparseMerkleTree::new(base_state_root),
+        }
+    }
+
+    /// Fetches the state of `address` as of `base_version`, along with the proof the state tree
+    /// needs to update it, unless it is already known.
+    fn load_account(&self, address: AccountAddress) -> Result<()> {
+        if self.account_states.borrow().contains_key(&address) {
+            return Ok(());
+        }
+        let account_state = match self.base_version {
+            Some(version) => {
+                let (blob, proof) = self
+                    .reader
+                    .get_account_state_with_proof_by_version(address, version)?;
+                self.account_proofs
+                    .borrow_mut()
+                    .insert(address.hash(), proof);
+                match blob {
+                    Some(blob) => AccountState::try_from(&blob)?,
+                    None => AccountState::default(),
+                }
+            }
+            None => AccountState::default(),
+        };
+        self.account_states
+            .borrow_mut()
+            .insert(address, account_state);
+        Ok(())
+    }
+
+    /// Applies the write set of `output` and returns the resulting state root hash.
+    fn apply(&mut self, output: &TransactionOutput) -> Result<HashValue> {
+        let mut addresses = HashSet::new();
+        for (access_path, write_op) in output.write_set() {
+            self.load_account(access_path.address)?;
+            let account_state = self
+                .account_states
+                .get_mut()
+                .get_mut(&access_path.address)
+                .expect("Account state should have been loaded.");
+            match write_op {
+                WriteOp::Value(blob) => {
+                    account_state.insert(access_path.path.clone(), blob.clone())
+                }
+                WriteOp::Deletion => account_state.remove(&access_path.path),
+            };
+            addresses.insert(access_path.address);
+        }
+
+        let account_states = self.account_states.get_mut();
+        let updates = addresses
+            .into_iter()
+            .map(|address| {
+                Ok((
+                    address.hash(),
+                    AccountStateBlob::try_from(&account_states[&address])?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.state_tree = self
+            .state_tree
+            .update(updates, &ProofReader(self.account_proofs.get_mut()))
+            .map_err(|e| format_err!("Failed to update the state tree: {:?}", e))?;
+        Ok(self.state_tree.root_hash())
+    }
+}
+
+impl<'a> StateView for ReplayStateView<'a> {
+    fn get(&self, access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
+        self.load_account(access_path.address)?;
+        Ok(self.account_states.borrow()[&access_path.address]
+            .get(&access_path.path)
+            .cloned())
+    }
+
+    fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
+        access_paths.iter().map(|path| self.get(path)).collect()
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.base_version.is_none()
+    }
+}
+
+struct ProofReader<'a>(&'a HashMap<HashValue, SparseMerkleProof>);
+
+impl<'a> ProofRead for ProofReader<'a> {
+    fn get_proof(&self, key: HashValue) -> Option<&SparseMerkleProof> {
+        self.0.get(&key)
+    }
+}
+
+fn get_at_version(
+    reader: &dyn DbReader,
+    access_path: &AccessPath,
+    version: Version,
+) -> Result<Option<Vec<u8>>> {
+    let (blob, _proof) =
+        reader.get_account_state_with_proof_by_version(access_path.address, version)?;
+    match blob {
+        Some(blob) => Ok(AccountState::try_from(&blob)?
+            .get(&access_path.path)
+            .cloned()),
+        None => Ok(None),
+    }
+}
+
+/// Describes how a replayed transaction differs from the recorded one.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Divergence {
+    Status {
+        expected: StatusCode,
+        actual: StatusCode,
+    },
+    GasUsed {
+        expected: u64,
+        actual: u64,
+    },
+    Events {
+        expected: Vec<ContractEvent>,
+        actual: Vec<ContractEvent>,
+    },
+    EventRoot {
+        expected: HashValue,
+        actual: HashValue,
+    },
+    WriteSet {
+        access_path: AccessPath,
+        expected: Option<Vec<u8>>,
+        actual: Option<Vec<u8>>,
+    },
+    StateRoot {
+        expected: HashValue,
+        actual: HashValue,
+    },
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Divergence::Status { expected, actual } => {
+                write!(f, "status: expected {:?}, got {:?}", expected, actual)
+            }
+            Divergence::GasUsed { expected, actual } => {
+                write!(f, "gas used: expected {}, got {}", expected, actual)
+            }
+            Divergence::Events { expected, actual } => {
+                write!(f, "events: expected {:?}, got {:?}", expected, actual)
+            }
+            Divergence::EventRoot { expected, actual } => {
+                write!(f, "event root hash: expected {}, got {}", expected, actual)
+            }
+            Divergence::WriteSet {
+                access_path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "write set at {}: expected {:?}, got {:?}",
+                access_path, expected, actual
+            ),
+            Divergence::StateRoot { expected, actual } => {
+                write!(f, "state root hash: expected {}, got {}", expected, actual)
+            }
+        }
+    }
+}
+
+/// Re-executes all the transactions in `[start_version, end_version]` using the VM `V` and
+/// returns the first version at which the outcome differs from the one recorded in `reader`.
+pub fn replay<V: VMExecutor>(
+    reader: &dyn DbReader,
+    start_version: Version,
+    end_version: Version,
+) -> Result<Option<(Version, Divergence)>> {
+    let ledger_version = reader.get_latest_version()?;
+    ensure!(
+        start_version <= end_version && end_version <= ledger_version,
+        "Invalid version range [{}, {}], latest version is {}.",
+        start_version,
+        end_version,
+        ledger_version,
+    );
+
+    let base_state_root = match start_version.checked_sub(1) {
+        Some(base_version) => reader
+            .get_transactions(base_version, 1, ledger_version, false)?
+            .proof
+            .transaction_infos()
+            .first()
+            .ok_or_else(|| format_err!("Transaction info missing at version {}.", base_version))?
+            .state_root_hash(),
+        None => *SPARSE_MERKLE_PLACEHOLDER_HASH,
+    };
+    let mut state_view =
+        ReplayStateView::new(reader, start_version.checked_sub(1), base_state_root);
+    let mut version = start_version;
+    while version <= end_version {
+        let limit = std::cmp::min(BATCH_SIZE, end_version - version + 1);
+        let txn_list = reader.get_transactions(version, limit, ledger_version, true)?;
+        let events = txn_list
+            .events
+            .ok_or_else(|| format_err!("Events missing at version {}.", version))?;
+        let txn_infos = txn_list.proof.transaction_infos();
+        for ((txn, expected_events), txn_info) in txn_list
+            .transactions
+            .into_iter()
+            .zip(events.into_iter())
+            .zip(txn_infos.iter())
+        {
+            if let Some(divergence) = replay_one::<V>(
+                &mut state_view,
+                version,
+                txn,
+                txn_info,
+                Some(expected_events),
+                Some(reader),
+            )? {
+                return Ok(Some((version, divergence)));
+            }
+            version += 1;
+        }
+    }
+    Ok(None)
+}
+
+/// Re-executes `transactions`, the first of which is at `start_version`, using the VM `V` and
+/// returns the first version at which the outcome differs from the recorded `TransactionInfo`.
+/// This is meant for transactions read from a backup: `reader` only needs to hold the state as of
+/// `start_version - 1`, e.g. restored from a state snapshot, whose root hash is
+/// `base_state_root`.
+pub fn replay_transactions<V: VMExecutor>(
+    reader: &dyn DbReader,
+    start_version: Version,
+    base_state_root: HashValue,
+    transactions: impl IntoIterator<Item = (Transaction, TransactionInfo)>,
+) -> Result<Option<(Version, Divergence)>> {
+    let mut state_view =
+        ReplayStateView::new(reader, start_version.checked_sub(1), base_state_root);
+    let mut version = start_version;
+    for (txn, txn_info) in transactions {
+        if let Some(divergence) =
+            replay_one::<V>(&mut state_view, version, txn, &txn_info, None, None)?
+        {
+            return Ok(Some((version, divergence)));
+        }
+        version += 1;
+    }
+    Ok(None)
+}
+
+/// Re-executes `txn` and compares its output against `txn_info`, as well as against the events and
+/// the states at `version` recorded in the DB if they are available, which pinpoint the
+/// divergence better than the root hashes do.
+fn replay_one<V: VMExecutor>(
+    state_view: &mut ReplayStateView,
+    version: Version,
+    txn: Transaction,
+    txn_info: &TransactionInfo,
+    expected_events: Option<Vec<ContractEvent>>,
+    recorded_states: Option<&dyn DbReader>,
+) -> Result<Option<Divergence>> {
+    let output = V::execute_block(vec![txn], state_view)
+        .map_err(|e| format_err!("VM failed to execute version {}: {:?}", version, e))?
+        .pop()
+        .ok_or_else(|| format_err!("VM returned no output for version {}.", version))?;
+
+    if let Some(divergence) = compare_output(&output, txn_info, expected_events) {
+        return Ok(Some(divergence));
+    }
+    if let Some(reader) = recorded_states {
+        if let Some(divergence) = compare_write_set(reader, version, &output)? {
+            return Ok(Some(divergence));
+        }
+    }
+    let state_root = state_view.apply(&output)?;
+    if state_root != txn_info.state_root_hash() {
+        return Ok(Some(Divergence::StateRoot {
+            expected: txn_info.state_root_hash(),
+            actual: state_root,
+        }));
+    }
+    Ok(None)
+}
+
+fn compare_output(
+    output: &TransactionOutput,
+    txn_info: &TransactionInfo,
+    expected_events: Option<Vec<ContractEvent>>,
+) -> Option<Divergence> {
+    let status = output.status().vm_status().status_code();
+    if status != txn_info.major_status() {
+        return Some(Divergence::Status {
+            expected: txn_info.major_status(),
+            actual: status,
+        });
+    }
+    if output.gas_used() != txn_info.gas_used() {
+        return Some(Divergence::GasUsed {
+            expected: txn_info.gas_used(),
+            actual: output.gas_used(),
+        });
+    }
+    if let Some(expected_events) = expected_events {
+        if output.events() != expected_events.as_slice() {
+            return Some(Divergence::Events {
+                expected: expected_events,
+                actual: output.events().to_vec(),
+            });
+        }
+    }
+    let event_hashes: Vec<_> = output.events().iter().map(CryptoHash::hash).collect();
+    let event_root =
+        InMemoryAccumulator::<EventAccumulatorHasher>::from_leaves(&event_hashes).root_hash();
+    if event_root != txn_info.event_root_hash() {
+        return Some(Divergence::EventRoot {
+            expected: txn_info.event_root_hash(),
+            actual: event_root,
+        });
+    }
+    None
+}
+
+fn compare_write_set(
+    reader: &dyn DbReader,
+    version: Version,
+    output: &TransactionOutput,
+) -> Result<Option<Divergence>> {
+    for (access_path, write_op) in output.write_set() {
+        let actual = match write_op {
+            WriteOp::Value(blob) => Some(blob.clone()),
+            WriteOp::Deletion => None,
+        };
+        let expected = get_at_version(reader, access_path, version)?;
+        if expected != actual {
+            return Ok(Some(Divergence::WriteSet {
+                access_path: access_path.clone(),
+                expected,
+                actual,
+            }));
+        }
+    }
+    Ok(None)
+}
@@ -0,0 +1,136 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, ensure, format_err, Context, Result};
+use backup_cli::{
+    backup_types::transaction::restore::TransactionBackupReader,
+    storage::{FileHandle, FileHandleRef, StorageOpt},
+};
+use libra_crypto::hash::SPARSE_MERKLE_PLACEHOLDER_HASH;
+use libra_types::transaction::{Transaction, TransactionInfo, Version};
+use libra_vm::LibraVM;
+use libradb::LibraDB;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+#[structopt(
+    name = "transaction-replay",
+    about = "Re-execute committed transactions and report the first divergence from the DB."
+)]
+struct Opt {
+    /// The DB holding the transactions to replay. When replaying a transaction backup, it only
+    /// needs to hold the state as of the version before `start_version`, e.g. restored from a
+    /// state snapshot with db-restore.
+    #[structopt(parse(from_os_str))]
+    db_dir: PathBuf,
+
+    #[structopt(short, long)]
+    start_version: Version,
+
+    /// Defaults to the latest version in the DB, or in the transaction backup.
+    #[structopt(short, long)]
+    end_version: Option<Version>,
+
+    /// Replay the transactions of this transaction backup instead of the ones in the DB. Unless
+    /// `start_version` is 0, the backup must also cover the version before it, whose state root
+    /// the replay starts from.
+    #[structopt(long = "transaction-manifest")]
+    transaction_manifest: Option<FileHandle>,
+
+    #[structopt(subcommand)]
+    storage: Option<StorageOpt>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let opt = Opt::from_args();
+
+    let db = LibraDB::open(
+        &opt.db_dir,
+        true, /* readonly */
+        None, /* pruner */
+    )
+    .with_context(|| format_err!("Failed to open DB."))?;
+
+    let (end_version, result) = match (opt.transaction_manifest, opt.storage) {
+        (Some(manifest_handle), Some(storage)) => {
+            let (first_version, txns, txn_infos) =
+                read_transaction_backup(storage, &manifest_handle).await?;
+            ensure!(
+                !txns.is_empty(),
+                "The transaction backup holds no transactions."
+            );
+            let last_version = first_version + txns.len() as Version - 1;
+            let end_version = opt.end_version.unwrap_or(last_version);
+            ensure!(
+                first_version <= opt.start_version.saturating_sub(1)
+                    && opt.start_version <= end_version
+                    && end_version <= last_version,
+                "Invalid version range [{}, {}], the backup covers [{}, {}].",
+                opt.start_version,
+                end_version,
+                first_version,
+                last_version,
+            );
+            let base_state_root = match opt.start_version.checked_sub(1) {
+                Some(base_version) => {
+                    txn_infos[(base_version - first_version) as usize].state_root_hash()
+                }
+                None => *SPARSE_MERKLE_PLACEHOLDER_HASH,
+            };
+            let transactions = txns
+                .into_iter()
+                .zip(txn_infos)
+                .skip((opt.start_version - first_version) as usize)
+                .take((end_version - opt.start_version + 1) as usize);
+            let result = transaction_replay::replay_transactions::<LibraVM>(
+                &db,
+                opt.start_version,
+                base_state_root,
+                transactions,
+            )?;
+            (end_version, result)
+        }
+        (Some(_), None) => bail!("A backup storage is needed to replay a transaction backup."),
+        (None, _) => {
+            let end_version = match opt.end_version {
+                Some(version) => version,
+                None => storage_interface::DbReader::get_latest_version(&db)?,
+            };
+            let result =
+                transaction_replay::replay::<LibraVM>(&db, opt.start_version, end_version)?;
+            (end_version, result)
+        }
+    };
+
+    match result {
+        Some((version, divergence)) => {
+            println!("Divergence at version {}: {}", version, divergence);
+            std::process::exit(1);
+        }
+        None => println!(
+            "Successfully replayed versions {} to {} without divergence.",
+            opt.start_version, end_version
+        ),
+    }
+    Ok(())
+}
+
+/// Reads and verifies all the transactions of the backup at `manifest_handle`, and returns them
+/// along with the version of the first one.
+async fn read_transaction_backup(
+    storage: StorageOpt,
+    manifest_handle: &FileHandleRef,
+) -> Result<(Version, Vec<Transaction>, Vec<TransactionInfo>)> {
+    let reader = TransactionBackupReader::new(storage.init_storage().await?);
+    let manifest = reader.read_manifest(manifest_handle).await?;
+    let mut txns = Vec::new();
+    let mut txn_infos = Vec::new();
+    for chunk in &manifest.chunks {
+        let (txn_list_with_proof, _ledger_info) = reader.read_chunk(chunk).await?;
+        txn_infos.extend_from_slice(txn_list_with_proof.proof.transaction_infos());
+        txns.extend(txn_list_with_proof.transactions);
+    }
+    Ok((manifest.first_version, txns, txn_infos))
+}
@@ -0,0 +1,125 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{replay, replay_transactions, Divergence};
+use executor_test_helpers::start_storage_service;
+use libra_crypto::hash::SPARSE_MERKLE_PLACEHOLDER_HASH;
+use libra_state_view::StateView;
+use libra_types::{
+    transaction::{Transaction, TransactionOutput},
+    vm_status::VMStatus,
+    write_set::WriteSetMut,
+};
+use libra_vm::{LibraVM, VMExecutor};
+
+/// Executes like `LibraVM`, but drops the last write of every transaction. As the write is
+/// missing rather than different, only the state root reveals it.
+struct DroppingWriteVM;
+
+impl VMExecutor for DroppingWriteVM {
+    fn execute_block(
+        transactions: Vec<Transaction>,
+        state_view: &dyn StateView,
+    ) -> Result<Vec<TransactionOutput>, VMStatus> {
+        Ok(LibraVM::execute_block(transactions, state_view)?
+            .into_iter()
+            .map(|output| {
+                let mut writes: Vec<_> = output.write_set().iter().cloned().collect();
+                writes.pop();
+                TransactionOutput::new(
+                    WriteSetMut::new(writes).freeze().unwrap(),
+                    output.events().to_vec(),
+                    output.gas_used(),
+                    output.status().clone(),
+                )
+            })
+            .collect())
+    }
+}
+
+/// Executes like `LibraVM`, but drops the last event of every transaction.
+struct DroppingEventVM;
+
+impl VMExecutor for DroppingEventVM {
+    fn execute_block(
+        transactions: Vec<Transaction>,
+        state_view: &dyn StateView,
+    ) -> Result<Vec<TransactionOutput>, VMStatus> {
+        Ok(LibraVM::execute_block(transactions, state_view)?
+            .into_iter()
+            .map(|output| {
+                let mut events = output.events().to_vec();
+                events.pop();
+                TransactionOutput::new(
+                    output.write_set().clone(),
+                    events,
+                    output.gas_used(),
+                    output.status().clone(),
+                )
+            })
+            .collect())
+    }
+}
+
+#[test]
+fn test_replay_genesis() {
+    let (_config, _handle, db) = start_storage_service();
+    assert_eq!(replay::<LibraVM>(&*db, 0, 0).unwrap(), None);
+}
+
+#[test]
+fn test_replay_invalid_range() {
+    let (_config, _handle, db) = start_storage_service();
+    assert!(replay::<LibraVM>(&*db, 1, 0).is_err());
+    assert!(replay::<LibraVM>(&*db, 0, 100).is_err());
+}
+
+#[test]
+fn test_replay_reports_missing_write() {
+    let (_config, _handle, db) = start_storage_service();
+    match replay::<DroppingWriteVM>(&*db, 0, 0).unwrap() {
+        Some((0, Divergence::StateRoot { .. })) => (),
+        result => panic!("Expected a state root divergence, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_replay_reports_missing_event() {
+    let (_config, _handle, db) = start_storage_service();
+    match replay::<DroppingEventVM>(&*db, 0, 0).unwrap() {
+        Some((0, Divergence::Events { expected, actual })) => {
+            assert_eq!(expected.len(), actual.len() + 1)
+        }
+        result => panic!("Expected an events divergence, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_replay_transactions_without_events() {
+    let (_config, _handle, db) = start_storage_service();
+    let txn_list = db.get_transactions(0, 1, 0, false).unwrap();
+    let transactions = || {
+        txn_list
+            .transactions
+            .clone()
+            .into_iter()
+            .zip(txn_list.proof.transaction_infos().to_vec())
+    };
+
+    assert_eq!(
+        replay_transactions::<LibraVM>(&*db, 0, *SPARSE_MERKLE_PLACEHOLDER_HASH, transactions())
+            .unwrap(),
+        None
+    );
+    match replay_transactions::<DroppingEventVM>(
+        &*db,
+        0,
+        *SPARSE_MERKLE_PLACEHOLDER_HASH,
+        transactions(),
+    )
+    .unwrap()
+    {
+        Some((0, Divergence::EventRoot { .. })) => (),
+        result => panic!("Expected an event root divergence, got {:?}", result),
+    }
+}
@@ -5,46 +5,73 @@
 use crate::{
     errors::JsonRpcError,
     views::{
-        AccountStateWithProofView, AccountView, BlockMetadata, CurrencyInfoView, EventView,
-        StateProofView, TransactionView,
+        AccountStateWithProofView, AccountView, AccumulatorConsistencyProofView, BlockMetadata,
+        CurrencyInfoView, EpochParticipationView, EventView, EventsWithProofsView, GasEstimateView,
+        SimulatedTransactionView, StateProofView, TransactionView, WriteSetChangeView,
     },
 };
 use anyhow::{ensure, format_err, Error, Result};
 use core::future::Future;
 use futures::{channel::oneshot, SinkExt};
 use libra_config::config::RoleType;
-use libra_crypto::hash::CryptoHash;
-use libra_mempool::MempoolClientSender;
+use libra_crypto::{
+    hash::{CryptoHash, EventAccumulatorHasher},
+    HashValue,
+};
+use libra_mempool::{counters as mempool_counters, AdmissionControl, MempoolClientSender};
 use libra_trace::prelude::*;
 use libra_types::{
     account_address::AccountAddress,
     account_config::{from_currency_code_string, CurrencyInfoResource},
+    account_identifier::parse_account_address,
     account_state::AccountState,
     event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
     mempool_status::MempoolStatusCode,
     move_resource::MoveStorage,
     on_chain_config::{OnChainConfig, RegisteredCurrencies},
-    transaction::SignedTransaction,
+    proof::accumulator::InMemoryAccumulator,
+    transaction::{SignedTransaction, Transaction, TransactionInfo, TransactionWithProof, Version},
+    vm_status::ErrorReason,
+    write_set::WriteOp,
 };
+use libra_vm::{LibraVM, VMExecutor};
 use network::counters;
+use resource_viewer::{AnnotatedAccountStateBlob, MoveValueAnnotator};
 use serde_json::Value;
-use std::{collections::HashMap, convert::TryFrom, ops::Deref, pin::Pin, str::FromStr, sync::Arc};
-use storage_interface::DbReader;
+use std::{
+    collections::{btree_map::Entry, BTreeMap, HashMap},
+    convert::TryFrom,
+    ops::Deref,
+    pin::Pin,
+    sync::Arc,
+};
+use storage_interface::{state_view::DbStateView, DbReader};
 
 #[derive(Clone)]
 pub(crate) struct JsonRpcService {
     db: Arc<dyn DbReader>,
     mempool_sender: MempoolClientSender,
+    /// Rejects the submissions that obviously can't be executed before they reach mempool
+    admission_control: Option<AdmissionControl>,
     role: RoleType,
+    include_write_sets: bool,
 }
 
 impl JsonRpcService {
-    pub fn new(db: Arc<dyn DbReader>, mempool_sender: MempoolClientSender, role: RoleType) -> Self {
+    pub fn new(
+        db: Arc<dyn DbReader>,
+        mempool_sender: MempoolClientSender,
+        admission_control: Option<AdmissionControl>,
+        role: RoleType,
+        include_write_sets: bool,
+    ) -> Self {
         Self {
             db,
             mempool_sender,
+            admission_control,
             role,
+            include_write_sets,
         }
     }
 
@@ -81,6 +108,12 @@ async fn submit(mut service: JsonRpcService, request: JsonRpcRequest) -> Result<
     let transaction: SignedTransaction = lcs::from_bytes(&hex::decode(txn_payload)?)?;
     trace_code_block!("json-rpc::submit", {"txn", transaction.sender(), transaction.sequence_number()});
 
+    if let Some(admission_control) = &service.admission_control {
+        if let Err(vm_status) = admission_control.check(&transaction) {
+            return Err(Error::new(JsonRpcError::vm_status(vm_status)));
+        }
+    }
+
     let (req_sender, callback) = oneshot::channel();
     service
         .mempool_sender
@@ -97,13 +130,32 @@ async fn submit(mut service: JsonRpcService, request: JsonRpcRequest) -> Result<
     }
 }
 
+/// Executes a transaction against the state at the latest known version without committing it,
+/// returning its would-be output. The signature is only checked if `skip_signature_check` is not
+/// set, so that wallets can preflight transactions before signing them.
+async fn simulate_transaction(
+    service: JsonRpcService,
+    request: JsonRpcRequest,
+) -> Result<SimulatedTransactionView> {
+    let txn_payload: String = serde_json::from_value(request.get_param(0))?;
+    let transaction: SignedTransaction = lcs::from_bytes(&hex::decode(txn_payload)?)?;
+    let skip_signature_check =
+        serde_json::from_value::<bool>(request.get_param(1)).unwrap_or(false);
+    trace_code_block!("json-rpc::simulate_transaction", {"txn", transaction.sender(), transaction.sequence_number()});
+
+    let state_view = DbStateView::new(service.db.clone(), request.version());
+    let output =
+        LibraVM::simulate_signed_transaction(transaction, skip_signature_check, &state_view);
+    Ok(SimulatedTransactionView::new(request.version(), output))
+}
+
 /// Returns account state (AccountView) by given address
 async fn get_account_state(
     service: JsonRpcService,
     request: JsonRpcRequest,
 ) -> Result<Option<AccountView>> {
     let address: String = serde_json::from_value(request.get_param(0))?;
-    let account_address = AccountAddress::from_str(&address)?;
+    let account_address = parse_account_address(&address)?;
     let response = service
         .db
         .get_account_state_with_proof_by_version(account_address, request.version())?
@@ -113,23 +165,34 @@ async fn get_account_state(
         .into_iter()
         .map(|info| from_currency_code_string(&info.code))
         .collect::<Result<_, _>>()?;
-    if let Some(blob) = response {
-        let account_state = AccountState::try_from(&blob)?;
-        if let Some(account) = account_state.get_account_resource()? {
-            let balances = account_state.get_balance_resources(&currencies)?;
-            if let Some(account_role) = account_state.get_account_role()? {
-                if let Some(freezing_bit) = account_state.get_freezing_bit()? {
-                    return Ok(Some(AccountView::new(
-                        &account,
-                        balances,
-                        account_role,
-                        freezing_bit,
-                    )));
-                }
-            }
+    match response {
+        Some(blob) => {
+            AccountView::try_from_account_state(&AccountState::try_from(&blob)?, &currencies)
         }
+        None => Ok(None),
     }
-    Ok(None)
+}
+
+/// Returns all resources published under the given address, annotated with their types and field
+/// names, in the JSON format of `resource_viewer` (see `resource_viewer::JSON_SCHEMA`)
+async fn get_account_resources(
+    service: JsonRpcService,
+    request: JsonRpcRequest,
+) -> Result<Option<AnnotatedAccountStateBlob>> {
+    let address: String = serde_json::from_value(request.get_param(0))?;
+    let account_address = parse_account_address(&address)?;
+    let blob = match service
+        .db
+        .get_account_state_with_proof_by_version(account_address, request.version())?
+        .0
+    {
+        Some(blob) => blob,
+        None => return Ok(None),
+    };
+    let account_state = AccountState::try_from(&blob)?;
+    let state_view = DbStateView::new(service.db.clone(), request.version());
+    let annotator = MoveValueAnnotator::new(&state_view);
+    Ok(Some(annotator.view_account_state(&account_state)?))
 }
 
 /// Returns the blockchain metadata for a specified version. If no version is specified, default to
@@ -148,6 +211,61 @@ async fn get_metadata(service: JsonRpcService, request: JsonRpcRequest) -> Resul
     }
 }
 
+/// Returns the participation of validators in consensus during the given epoch, or null if no
+/// block has been committed in it
+async fn get_epoch_participation(
+    service: JsonRpcService,
+    request: JsonRpcRequest,
+) -> Result<Option<EpochParticipationView>> {
+    let epoch: u64 = serde_json::from_value(request.get_param(0))?;
+    ensure!(
+        epoch <= request.ledger_info.ledger_info().epoch(),
+        "epoch {} is in the future",
+        epoch
+    );
+
+    Ok(service
+        .db
+        .get_epoch_participation(epoch)?
+        .map(|participation| (epoch, participation).into()))
+}
+
+/// Number of the latest transactions the gas estimate is computed from.
+const GAS_ESTIMATE_NUM_TXNS: u64 = 1000;
+
+/// Returns a gas unit price estimate from the gas unit prices of the user transactions among the
+/// latest committed ones and the number of transactions waiting in mempool
+async fn get_gas_estimate(
+    service: JsonRpcService,
+    request: JsonRpcRequest,
+) -> Result<GasEstimateView> {
+    let ledger_version = request.version();
+    let start_version = (ledger_version + 1).saturating_sub(GAS_ESTIMATE_NUM_TXNS);
+    let txns = service.db.get_transactions(
+        start_version,
+        ledger_version - start_version + 1,
+        ledger_version,
+        false,
+    )?;
+
+    let mut gas_unit_prices = vec![];
+    let mut num_blocks = 0;
+    for txn in txns.transactions {
+        match txn {
+            Transaction::UserTransaction(txn) => gas_unit_prices.push(txn.gas_unit_price()),
+            Transaction::BlockMetadata(_) => num_blocks += 1,
+            _ => (),
+        }
+    }
+    let num_mempool_transactions = mempool_counters::CORE_MEMPOOL_READY_TXNS.get() as u64;
+
+    Ok(GasEstimateView::new(
+        gas_unit_prices,
+        num_blocks,
+        num_mempool_transactions,
+    ))
+}
+
 /// Returns transactions by range
 async fn get_transactions(
     service: JsonRpcService,
@@ -193,6 +311,11 @@ async fn get_transactions(
         } else {
             vec![]
         };
+        let write_set = if service.include_write_sets {
+            write_set_view(&service.db, start_version + v as u64, &tx, info)?
+        } else {
+            None
+        };
 
         result.push(TransactionView {
             version: start_version + v as u64,
@@ -200,7 +323,9 @@ async fn get_transactions(
             transaction: tx.into(),
             events,
             vm_status: info.major_status(),
+            error_reason: ErrorReason::from_status_code(info.major_status()),
             gas_used: info.gas_used(),
+            write_set,
         });
     }
     Ok(result)
@@ -215,39 +340,154 @@ async fn get_account_transaction(
     let sequence: u64 = serde_json::from_value(request.get_param(1))?;
     let include_events: bool = serde_json::from_value(request.get_param(2))?;
 
-    let account = AccountAddress::try_from(p_account)?;
+    let account = parse_account_address(&p_account)?;
 
     let tx = service
         .db
         .get_txn_by_account(account, sequence, request.version(), include_events)?;
 
-    if let Some(tx) = tx {
-        if include_events {
-            ensure!(
-                tx.events.is_some(),
-                "Storage layer didn't return events when requested!"
-            );
-        }
-        let tx_version = tx.version;
-
-        let events = tx
-            .events
-            .unwrap_or_default()
-            .into_iter()
-            .map(|x| ((tx_version, x).into()))
-            .collect();
-
-        Ok(Some(TransactionView {
-            version: tx_version,
-            hash: tx.transaction.hash().to_hex(),
-            transaction: tx.transaction.into(),
-            events,
-            vm_status: tx.proof.transaction_info().major_status(),
-            gas_used: tx.proof.transaction_info().gas_used(),
-        }))
+    tx.map(|tx| transaction_view(&service, tx, include_events))
+        .transpose()
+}
+
+/// Returns transaction by its hash
+async fn get_transaction_by_hash(
+    service: JsonRpcService,
+    request: JsonRpcRequest,
+) -> Result<Option<TransactionView>> {
+    let hash: String = serde_json::from_value(request.get_param(0))?;
+    let include_events: bool = serde_json::from_value(request.get_param(1))?;
+
+    let hash = HashValue::from_hex(&hash)?;
+
+    let tx = service
+        .db
+        .get_transaction_by_hash(hash, request.version(), include_events)?;
+
+    tx.map(|tx| transaction_view(&service, tx, include_events))
+        .transpose()
+}
+
+fn transaction_view(
+    service: &JsonRpcService,
+    tx: TransactionWithProof,
+    include_events: bool,
+) -> Result<TransactionView> {
+    if include_events {
+        ensure!(
+            tx.events.is_some(),
+            "Storage layer didn't return events when requested!"
+        );
+    }
+    let tx_version = tx.version;
+
+    let events = tx
+        .events
+        .unwrap_or_default()
+        .into_iter()
+        .map(|x| ((tx_version, x).into()))
+        .collect();
+    let write_set = if service.include_write_sets {
+        write_set_view(
+            &service.db,
+            tx_version,
+            &tx.transaction,
+            tx.proof.transaction_info(),
+        )?
     } else {
-        Ok(None)
+        None
+    };
+
+    Ok(TransactionView {
+        version: tx_version,
+        hash: tx.transaction.hash().to_hex(),
+        transaction: tx.transaction.into(),
+        events,
+        vm_status: tx.proof.transaction_info().major_status(),
+        error_reason: ErrorReason::from_status_code(tx.proof.transaction_info().major_status()),
+        gas_used: tx.proof.transaction_info().gas_used(),
+        write_set,
+    })
+}
+
+/// Re-executes the transaction committed at `version` against the state it was committed on top
+/// of and returns its write set, annotated. Write sets are not stored, so the re-execution is
+/// checked against what was committed: its status, gas used and events against `info`, and the
+/// account states it produces against the committed ones. Returns `None` for the genesis
+/// transaction, which has no state to be executed against.
+fn write_set_view(
+    db: &Arc<dyn DbReader>,
+    version: Version,
+    tx: &Transaction,
+    info: &TransactionInfo,
+) -> Result<Option<Vec<WriteSetChangeView>>> {
+    if version == 0 {
+        return Ok(None);
+    }
+    let state_view = DbStateView::new(db.clone(), version - 1);
+    let output = LibraVM::execute_block(vec![tx.clone()], &state_view)
+        .map_err(|status| {
+            format_err!("Failed to re-execute transaction {}: {:?}", version, status)
+        })?
+        .pop()
+        .ok_or_else(|| format_err!("No output for transaction {}", version))?;
+
+    let event_hashes: Vec<_> = output.events().iter().map(CryptoHash::hash).collect();
+    ensure!(
+        !output.status().is_discarded()
+            && output.status().vm_status().status_code() == info.major_status()
+            && output.gas_used() == info.gas_used()
+            && InMemoryAccumulator::<EventAccumulatorHasher>::from_leaves(&event_hashes)
+                .root_hash()
+                == info.event_root_hash(),
+        "Re-executed transaction {} does not match its TransactionInfo",
+        version
+    );
+
+    let account_state = |address: AccountAddress, version: Version| -> Result<AccountState> {
+        match db
+            .get_account_state_with_proof_by_version(address, version)?
+            .0
+        {
+            Some(blob) => AccountState::try_from(&blob),
+            None => Ok(AccountState::default()),
+        }
+    };
+    let mut account_states = BTreeMap::new();
+    for (access_path, write_op) in output.write_set() {
+        let state = match account_states.entry(access_path.address) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(account_state(access_path.address, version - 1)?),
+        };
+        match write_op {
+            WriteOp::Value(value) => state.insert(access_path.path.clone(), value.clone()),
+            WriteOp::Deletion => state.remove(&access_path.path),
+        };
     }
+    for (address, state) in account_states {
+        ensure!(
+            state == account_state(address, version)?,
+            "Re-executed transaction {} does not match the committed state of account {}",
+            version,
+            address
+        );
+    }
+
+    let annotator = MoveValueAnnotator::new(&state_view);
+    let write_set = annotator
+        .view_write_set(output.write_set())?
+        .into_iter()
+        .map(|entry| -> Result<WriteSetChangeView> {
+            Ok(WriteSetChangeView {
+                address: entry.access_path.address.as_ref().into(),
+                path: entry.access_path.path.as_slice().into(),
+                deletion: entry.deletion,
+                before: entry.before.map(serde_json::to_value).transpose()?,
+                after: entry.after.map(serde_json::to_value).transpose()?,
+            })
+        })
+        .collect::<Result<_>>()?;
+    Ok(Some(write_set))
 }
 
 /// Returns events by given access path
@@ -268,6 +508,24 @@ async fn get_events(service: JsonRpcService, request: JsonRpcRequest) -> Result<
     Ok(events)
 }
 
+/// Returns events by given access path, with the proofs of their inclusion in the ledger anchored
+/// by the ledger info of the response
+async fn get_events_with_proofs(
+    service: JsonRpcService,
+    request: JsonRpcRequest,
+) -> Result<EventsWithProofsView> {
+    let raw_event_key: String = serde_json::from_value(request.get_param(0))?;
+    let start: u64 = serde_json::from_value(request.get_param(1))?;
+    let limit: u64 = serde_json::from_value(request.get_param(2))?;
+
+    let event_key = EventKey::try_from(&hex::decode(raw_event_key)?[..])?;
+    let events_with_proof =
+        service
+            .db
+            .get_events_with_proofs(&event_key, start, true, limit, request.version())?;
+    EventsWithProofsView::try_from((request.ledger_info, events_with_proof))
+}
+
 /// Returns meta information about supported currencies
 async fn currencies_info(
     service: JsonRpcService,
@@ -309,6 +567,22 @@ async fn get_state_proof(
     StateProofView::try_from((request.ledger_info, proofs.0, proofs.1))
 }
 
+/// Returns proof that the ledger at the target version extends the ledger at the version known to
+/// client. If the target version is not specified, the latest known version will be used.
+async fn get_accumulator_consistency_proof(
+    service: JsonRpcService,
+    request: JsonRpcRequest,
+) -> Result<AccumulatorConsistencyProofView> {
+    let client_known_version: u64 = serde_json::from_value(request.get_param(0))?;
+    let target_version =
+        serde_json::from_value::<u64>(request.get_param(1)).unwrap_or_else(|_| request.version());
+
+    let proof = service
+        .db
+        .get_accumulator_consistency_proof(client_known_version, target_version)?;
+    AccumulatorConsistencyProofView::try_from((client_known_version, target_version, proof))
+}
+
 /// Returns the account state to the client, alongside a proof relative to the version and
 /// ledger_version specified by the client. If version or ledger_version are not specified,
 /// the latest known versions will be used.
@@ -317,7 +591,7 @@ async fn get_account_state_with_proof(
     request: JsonRpcRequest,
 ) -> Result<AccountStateWithProofView> {
     let address: String = serde_json::from_value(request.get_param(0))?;
-    let account_address = AccountAddress::from_str(&address)?;
+    let account_address = parse_account_address(&address)?;
 
     // If versions are specified by the request parameters, use them, otherwise use the defaults
     let version =
@@ -347,8 +621,16 @@ async fn get_network_status(service: JsonRpcService, _request: JsonRpcRequest) -
 pub(crate) fn build_registry() -> RpcRegistry {
     let mut registry = RpcRegistry::new();
     register_rpc_method!(registry, "submit", submit, 1);
+    register_rpc_method!(registry, "simulate_transaction", simulate_transaction, 2);
     register_rpc_method!(registry, "get_metadata", get_metadata, 1);
+    register_rpc_method!(
+        registry,
+        "get_epoch_participation",
+        get_epoch_participation,
+        1
+    );
     register_rpc_method!(registry, "get_account_state", get_account_state, 1);
+    register_rpc_method!(registry, "get_account_resources", get_account_resources, 1);
     register_rpc_method!(registry, "get_transactions", get_transactions, 3);
     register_rpc_method!(
         registry,
@@ -356,10 +638,29 @@ pub(crate) fn build_registry() -> RpcRegistry {
         get_account_transaction,
         3
     );
+    register_rpc_method!(
+        registry,
+        "get_transaction_by_hash",
+        get_transaction_by_hash,
+        2
+    );
     register_rpc_method!(registry, "get_events", get_events, 3);
+    register_rpc_method!(
+        registry,
+        "get_events_with_proofs",
+        get_events_with_proofs,
+        3
+    );
     register_rpc_method!(registry, "get_currencies", currencies_info, 0);
+    register_rpc_method!(registry, "get_gas_estimate", get_gas_estimate, 0);
 
     register_rpc_method!(registry, "get_state_proof", get_state_proof, 1);
+    register_rpc_method!(
+        registry,
+        "get_accumulator_consistency_proof",
+        get_accumulator_consistency_proof,
+        2
+    );
     register_rpc_method!(
         registry,
         "get_account_state_with_proof",
@@ -11,7 +11,7 @@ use libra_config::config::{NodeConfig, RoleType};
 use libra_json_rpc_types::views::{
     JSONRPC_LIBRA_LEDGER_TIMESTAMPUSECS, JSONRPC_LIBRA_LEDGER_VERSION,
 };
-use libra_mempool::MempoolClientSender;
+use libra_mempool::{AdmissionControl, MempoolClientSender};
 use libra_types::ledger_info::LedgerInfoWithSignatures;
 use serde_json::{map::Map, Value};
 use std::{net::SocketAddr, sync::Arc};
@@ -36,7 +36,9 @@ pub fn bootstrap(
     address: SocketAddr,
     libra_db: Arc<dyn DbReader>,
     mp_sender: MempoolClientSender,
+    admission_control: Option<AdmissionControl>,
     role: RoleType,
+    include_write_sets: bool,
 ) -> Runtime {
     let runtime = Builder::new()
         .thread_name("rpc-")
@@ -46,7 +48,13 @@ pub fn bootstrap(
         .expect("[rpc] failed to create runtime");
 
     let registry = Arc::new(build_registry());
-    let service = JsonRpcService::new(libra_db, mp_sender, role);
+    let service = JsonRpcService::new(
+        libra_db,
+        mp_sender,
+        admission_control,
+        role,
+        include_write_sets,
+    );
 
     let handler = warp::any()
         .and(warp::path::end())
@@ -69,13 +77,22 @@ pub fn bootstrap(
     runtime
 }
 
-/// Creates JSON RPC endpoint by given node config
+/// Creates JSON RPC endpoint by given node config, checking submissions with the admission control
+/// shared with mempool
 pub fn bootstrap_from_config(
     config: &NodeConfig,
     libra_db: Arc<dyn DbReader>,
     mp_sender: MempoolClientSender,
+    admission_control: AdmissionControl,
 ) -> Runtime {
-    bootstrap(config.rpc.address, libra_db, mp_sender, config.base.role)
+    bootstrap(
+        config.rpc.address,
+        libra_db,
+        mp_sender,
+        Some(admission_control),
+        config.base.role,
+        config.rpc.include_write_sets,
+    )
 }
 
 /// JSON RPC entry point
@@ -5,35 +5,46 @@ use crate::{
     errors::{JsonRpcError, ServerCode},
     tests::utils::{test_bootstrap, MockLibraDB},
 };
+use anyhow::Result;
 use futures::{channel::mpsc::channel, StreamExt};
-use libra_config::utils;
+use libra_config::{config::RoleType, utils};
 use libra_crypto::{ed25519::Ed25519PrivateKey, hash::CryptoHash, HashValue, PrivateKey, Uniform};
 use libra_json_rpc_client::{
     views::{
-        AccountStateWithProofView, BlockMetadata, BytesView, EventView, StateProofView,
-        TransactionDataView, TransactionView,
+        AccountStateWithProofView, AccumulatorConsistencyProofView, BlockMetadata, BytesView,
+        EpochParticipationView, EventView, EventsWithProofsView, GasEstimateView,
+        SimulatedTransactionView, StateProofView, TransactionDataView, TransactionView,
     },
     JsonRpcAsyncClient, JsonRpcBatch, JsonRpcResponse, ResponseAsView,
 };
 use libra_json_rpc_types::views::{
     JSONRPC_LIBRA_LEDGER_TIMESTAMPUSECS, JSONRPC_LIBRA_LEDGER_VERSION,
 };
+use libra_mempool::AdmissionControl;
 use libra_proptest_helpers::ValueGenerator;
 use libra_types::{
     account_address::AccountAddress,
-    account_config::AccountResource,
+    account_config::{lbr_type_tag, AccountResource, BalanceResource, LBR_NAME},
+    account_identifier::{AccountIdentifier, AddressNetwork},
+    account_state::AccountState,
     account_state_blob::{AccountStateBlob, AccountStateWithProof},
-    contract_event::ContractEvent,
-    event::EventKey,
+    contract_event::{ContractEvent, EventWithProof},
+    event::{EventHandle, EventKey},
     ledger_info::LedgerInfoWithSignatures,
     mempool_status::{MempoolStatus, MempoolStatusCode},
-    proof::{SparseMerkleProof, TransactionAccumulatorProof, TransactionInfoWithProof},
-    test_helpers::transaction_test_helpers::get_test_signed_txn,
+    proof::{
+        AccumulatorConsistencyProof, SparseMerkleProof, TransactionAccumulatorProof,
+        TransactionInfoWithProof,
+    },
+    test_helpers::transaction_test_helpers::{get_test_signed_transaction, get_test_signed_txn},
     transaction::{Transaction, TransactionInfo, TransactionPayload},
-    vm_status::{StatusCode, VMStatus},
+    vm_status::{AbortFrame, AbortLocation, ErrorReason, StatusCode, VMStatus},
 };
 use libradb::test_helper::arb_blocks_to_commit;
-use move_core_types::language_storage::TypeTag;
+use move_core_types::{
+    identifier::Identifier,
+    language_storage::{ModuleId, TypeTag},
+};
 use proptest::prelude::*;
 use std::{
     collections::{BTreeMap, HashMap},
@@ -238,6 +249,146 @@ fn test_transaction_submission() {
     }
 }
 
+#[test]
+fn test_transaction_submission_admission_control() {
+    let (mp_sender, mut mp_events) = channel(1);
+    let mut mock_db = mock_db();
+    let sender = AccountAddress::new([7; AccountAddress::LENGTH]);
+    let account_resource = AccountResource::new(
+        5,
+        vec![],
+        None,
+        None,
+        EventHandle::random_handle(0),
+        EventHandle::random_handle(0),
+    );
+    let mut account_state = AccountState::default();
+    account_state.insert(
+        AccountResource::resource_path(),
+        lcs::to_bytes(&account_resource).unwrap(),
+    );
+    account_state.insert(
+        BalanceResource::access_path_for(lbr_type_tag()),
+        lcs::to_bytes(&BalanceResource::new(1000)).unwrap(),
+    );
+    mock_db
+        .all_accounts
+        .insert(sender, AccountStateBlob::try_from(&account_state).unwrap());
+    let db: Arc<dyn DbReader> = Arc::new(mock_db);
+
+    let port = utils::get_available_port();
+    let address = format!("0.0.0.0:{}", port);
+    let mut runtime = crate::bootstrap(
+        address.parse().unwrap(),
+        Arc::clone(&db),
+        mp_sender,
+        Some(AdmissionControl::new(db)),
+        RoleType::Validator,
+        false,
+    );
+    let client = JsonRpcAsyncClient::new(
+        reqwest::Url::from_str(format!("http://{}:{}", "127.0.0.1", port).as_str())
+            .expect("invalid url"),
+    );
+
+    // future that mocks shared mempool, which only sees the admitted transactions
+    runtime.spawn(async move {
+        while let Some((_txn, cb)) = mp_events.next().await {
+            cb.send(Ok((MempoolStatus::new(MempoolStatusCode::Accepted), None)))
+                .unwrap();
+        }
+    });
+
+    let mut txn_submission = move |sequence_number, gas_unit_price| {
+        let privkey = Ed25519PrivateKey::generate_for_testing();
+        let txn = get_test_signed_transaction(
+            sender,
+            sequence_number,
+            &privkey,
+            privkey.public_key(),
+            None,
+            u64::max_value(),
+            gas_unit_price,
+            LBR_NAME.to_owned(),
+            Some(1_000_000),
+        );
+        let mut batch = JsonRpcBatch::default();
+        batch.add_submit_request(txn).unwrap();
+        runtime.block_on(client.execute(batch)).unwrap()
+    };
+    let rejection_status = |response: &Result<JsonRpcResponse>| {
+        let error = response
+            .as_ref()
+            .expect_err("expected error")
+            .downcast_ref::<JsonRpcError>()
+            .expect("unexpected error format");
+        assert_eq!(error.code, ServerCode::VmValidationError as i16);
+        let vm_status: VMStatus =
+            serde_json::from_value(error.data.as_ref().unwrap().clone()).unwrap();
+        vm_status.status_code()
+    };
+
+    // check admitted submission
+    assert!(txn_submission(5, 0)[0].as_ref().unwrap() == &JsonRpcResponse::SubmissionResponse);
+
+    // check submission reusing a committed sequence number
+    assert_eq!(
+        rejection_status(&txn_submission(0, 0)[0]),
+        StatusCode::SEQUENCE_NUMBER_TOO_OLD
+    );
+
+    // check submission whose maximum gas can't be paid for
+    assert_eq!(
+        rejection_status(&txn_submission(5, 1)[0]),
+        StatusCode::INSUFFICIENT_BALANCE_FOR_TRANSACTION_FEE
+    );
+}
+
+#[test]
+fn test_simulate_transaction_with_invalid_signature() {
+    let (_mock_db, client, mut runtime) = create_database_client_and_runtime(1);
+
+    let privkey = Ed25519PrivateKey::generate_for_testing();
+    let other_key = Ed25519PrivateKey::generate_for_testing();
+    let sender = AccountAddress::new([9; AccountAddress::LENGTH]);
+    let txn = get_test_signed_txn(sender, 0, &privkey, other_key.public_key(), None);
+
+    let mut batch = JsonRpcBatch::default();
+    batch.add_simulate_transaction_request(txn, false).unwrap();
+    let simulation = SimulatedTransactionView::from_response(execute_batch_and_get_first_response(
+        &client,
+        &mut runtime,
+        batch,
+    ))
+    .unwrap();
+
+    assert_eq!(simulation.vm_status, StatusCode::INVALID_SIGNATURE);
+    assert_eq!(simulation.error_reason, Some(ErrorReason::InvalidSignature));
+    assert!(simulation.discarded);
+    assert_eq!(simulation.gas_used, 0);
+    assert!(simulation.write_set.is_empty());
+    assert!(simulation.events.is_empty());
+}
+
+#[test]
+fn test_vm_status_error_abort_info() {
+    let status = VMStatus::MoveAbort(
+        AbortLocation::Module(ModuleId::new(
+            AccountAddress::new([1; AccountAddress::LENGTH]),
+            Identifier::new("M").unwrap(),
+        )),
+        7,
+        Some(AbortFrame {
+            function: Identifier::new("f").unwrap(),
+            code_offset: 3,
+        }),
+    );
+    let error = JsonRpcError::vm_status(status.clone());
+    assert_eq!(error.code, ServerCode::VmExecutionError as i16);
+    assert_eq!(error.get_vm_status(), Some(status));
+    assert_eq!(error.get_error_reason(), Some(ErrorReason::Aborted));
+}
+
 // TODO: Once account configs are published in the mock DB this test can be turned back on
 //#[test]
 //fn test_get_account_state() {
@@ -337,6 +488,43 @@ fn test_get_metadata() {
     assert_eq!(result_view.timestamp, mock_db.timestamps[1]);
 }
 
+#[test]
+fn test_get_epoch_participation() {
+    let (mock_db, client, mut runtime) = create_database_client_and_runtime(1);
+
+    let mut batch = JsonRpcBatch::default();
+    batch.add_get_epoch_participation_request(0);
+    batch.add_get_epoch_participation_request(1);
+    let mut responses = runtime.block_on(client.execute(batch)).unwrap();
+
+    let participation =
+        EpochParticipationView::optional_from_response(responses.remove(0).unwrap())
+            .unwrap()
+            .expect("epoch participation does not exist");
+    let blocks: Vec<_> = mock_db
+        .all_txns
+        .iter()
+        .filter_map(|(txn, _)| match txn {
+            Transaction::BlockMetadata(block) => Some(block),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(participation.epoch, 0);
+    assert_eq!(participation.num_blocks, blocks.len() as u64);
+    let num_proposals: u64 = participation
+        .validators
+        .iter()
+        .map(|validator| validator.num_proposals)
+        .sum();
+    assert_eq!(
+        num_proposals + participation.num_nil_blocks,
+        participation.num_blocks
+    );
+
+    // The mock DB is in epoch 0.
+    assert!(responses.remove(0).is_err());
+}
+
 #[test]
 fn test_get_events() {
     let (mock_db, client, mut runtime) = create_database_client_and_runtime(1);
@@ -367,6 +555,90 @@ fn test_get_events() {
     );
 }
 
+#[test]
+fn test_get_events_with_proofs() {
+    let (mock_db, client, mut runtime) = create_database_client_and_runtime(1);
+
+    let (first_event_version, first_event) = mock_db.events[0].clone();
+    let event_key = hex::encode(first_event.key().as_bytes());
+
+    let mut batch = JsonRpcBatch::default();
+    batch.add_get_events_with_proofs_request(
+        event_key,
+        first_event.sequence_number(),
+        first_event.sequence_number() + 10,
+    );
+    let result = execute_batch_and_get_first_response(&client, &mut runtime, batch);
+    let view = EventsWithProofsView::from_response(result).unwrap();
+
+    let ledger_info: LedgerInfoWithSignatures =
+        lcs::from_bytes(&view.ledger_info_with_signatures.into_bytes().unwrap()).unwrap();
+    assert_eq!(ledger_info.ledger_info().version(), mock_db.version);
+
+    let fetched_event = &view.events[0];
+    assert_eq!(
+        fetched_event.event.sequence_number,
+        first_event.sequence_number()
+    );
+    assert_eq!(fetched_event.event.transaction_version, first_event_version);
+    let event_with_proof: EventWithProof =
+        lcs::from_bytes(&fetched_event.event_with_proof.clone().into_bytes().unwrap()).unwrap();
+    assert_eq!(event_with_proof.transaction_version, first_event_version);
+    assert_eq!(event_with_proof.event_index, fetched_event.event_index);
+    assert_eq!(event_with_proof.event, first_event);
+}
+
+#[test]
+fn test_get_gas_estimate() {
+    let (mock_db, client, mut runtime) = create_database_client_and_runtime(1);
+
+    let mut batch = JsonRpcBatch::default();
+    batch.add_get_gas_estimate_request();
+    let result = execute_batch_and_get_first_response(&client, &mut runtime, batch);
+    let estimate = GasEstimateView::from_response(result).unwrap();
+
+    let mut gas_unit_prices: Vec<_> = mock_db
+        .all_txns
+        .iter()
+        .filter_map(|(txn, _)| txn.as_signed_user_txn().ok())
+        .map(|txn| txn.gas_unit_price())
+        .collect();
+    gas_unit_prices.sort();
+    assert_eq!(
+        estimate.num_sampled_transactions,
+        gas_unit_prices.len() as u64
+    );
+    if !gas_unit_prices.is_empty() {
+        assert_eq!(
+            estimate.median_gas_unit_price,
+            gas_unit_prices[(gas_unit_prices.len() - 1) / 2]
+        );
+    }
+    assert!(estimate.gas_unit_price >= estimate.median_gas_unit_price);
+}
+
+#[test]
+fn test_gas_estimate_with_backlog() {
+    let gas_unit_prices: Vec<u64> = (1..=100).rev().collect();
+
+    let estimate = GasEstimateView::new(gas_unit_prices.clone(), 10, 5);
+    assert_eq!(estimate.median_gas_unit_price, 50);
+    assert_eq!(estimate.backlog_blocks, 0);
+    assert_eq!(estimate.gas_unit_price, 50);
+
+    // Mempool holds 2 blocks of 10 transactions.
+    let estimate = GasEstimateView::new(gas_unit_prices.clone(), 10, 25);
+    assert_eq!(estimate.backlog_blocks, 2);
+    assert_eq!(estimate.gas_unit_price, 70);
+
+    let estimate = GasEstimateView::new(gas_unit_prices, 10, 1000);
+    assert_eq!(estimate.gas_unit_price, 100);
+
+    let estimate = GasEstimateView::new(vec![], 0, 1000);
+    assert_eq!(estimate.num_sampled_transactions, 0);
+    assert_eq!(estimate.gas_unit_price, 0);
+}
+
 #[test]
 fn test_get_transactions() {
     let (mock_db, client, mut runtime) = create_database_client_and_runtime(1);
@@ -401,6 +673,7 @@ fn test_get_transactions() {
 
             assert_eq!(expected_events.len(), view.events.len());
             assert_eq!(status, &view.vm_status);
+            assert_eq!(view.error_reason, ErrorReason::from_status_code(*status));
 
             for (i, event_view) in view.events.iter().enumerate() {
                 let expected_event = expected_events.get(i).expect("Expected event didn't find");
@@ -445,6 +718,38 @@ fn test_get_transactions() {
     }
 }
 
+#[test]
+fn test_get_transaction_by_hash() {
+    let (mock_db, client, mut runtime) = create_database_client_and_runtime(1);
+
+    for (version, (txn, status)) in mock_db.all_txns.iter().enumerate().take(10) {
+        let mut batch = JsonRpcBatch::default();
+        batch.add_get_transaction_by_hash_request(txn.hash().to_hex(), true);
+
+        let result = execute_batch_and_get_first_response(&client, &mut runtime, batch);
+        let tx_view = TransactionView::optional_from_response(result)
+            .unwrap()
+            .expect("Transaction didn't exists!");
+
+        assert_eq!(tx_view.version, version as u64);
+        assert_eq!(tx_view.hash, txn.hash().to_hex());
+        assert_eq!(&tx_view.vm_status, status);
+        let num_events = mock_db
+            .events
+            .iter()
+            .filter(|(ev, _)| *ev == version as u64)
+            .count();
+        assert_eq!(tx_view.events.len(), num_events);
+    }
+
+    let mut batch = JsonRpcBatch::default();
+    batch.add_get_transaction_by_hash_request(HashValue::zero().to_hex(), false);
+    let result = execute_batch_and_get_first_response(&client, &mut runtime, batch);
+    assert!(TransactionView::optional_from_response(result)
+        .unwrap()
+        .is_none());
+}
+
 #[test]
 fn test_get_account_transaction() {
     let (mock_db, client, mut runtime) = create_database_client_and_runtime(1);
@@ -486,6 +791,10 @@ fn test_get_account_transaction() {
 
             // check VM major status
             assert_eq!(&tx_view.vm_status, expected_status);
+            assert_eq!(
+                tx_view.error_reason,
+                ErrorReason::from_status_code(*expected_status)
+            );
 
             for (i, event_view) in tx_view.events.iter().enumerate() {
                 let expected_event = expected_events.get(i).expect("Expected event didn't find");
@@ -540,6 +849,35 @@ fn test_get_account_state_with_proof_null_versions() {
     assert_eq!(received_proof.version, expected_proof.version);
 }
 
+#[test]
+fn test_get_account_state_with_proof_by_account_identifier() {
+    let address = format!("0.0.0.0:{}", utils::get_available_port());
+    let mock_db = mock_db();
+    let account = get_first_account_from_mock_db(&mock_db);
+    let mp_sender = channel(1024).0;
+    let _runtime = test_bootstrap(address.parse().unwrap(), Arc::new(mock_db), mp_sender);
+    let client = reqwest::blocking::Client::new();
+    let url = format!("http://{}", address);
+    let get_account_state_with_proof = |account: String| {
+        let request = serde_json::json!({"jsonrpc": "2.0", "method": "get_account_state_with_proof", "params": [account, 0, 0], "id": 1});
+        client.post(&url).json(&request).send().unwrap()
+    };
+
+    let identifier = AccountIdentifier::new(AddressNetwork::Testnet, account).to_string();
+    let data: JsonMap = get_account_state_with_proof(identifier.clone())
+        .json()
+        .unwrap();
+    let expected: JsonMap = get_account_state_with_proof(account.to_string())
+        .json()
+        .unwrap();
+    assert!(data.get("error").is_none());
+    assert_eq!(data.get("result"), expected.get("result"));
+
+    // a typo is caught by the checksum
+    let typo = identifier.replacen('q', "p", 1);
+    assert_eq!(fetch_error(get_account_state_with_proof(typo)), -32000);
+}
+
 #[test]
 fn test_get_account_state_with_proof() {
     let (mock_db, client, mut runtime) = create_database_client_and_runtime(1);
@@ -602,6 +940,42 @@ fn test_get_state_proof() {
     assert_eq!(li.ledger_info().version(), version);
 }
 
+#[test]
+fn test_get_accumulator_consistency_proof() {
+    let (mock_db, client, mut runtime) = create_database_client_and_runtime(1024);
+
+    let version = mock_db.version;
+    let mut batch = JsonRpcBatch::default();
+    batch.add_get_accumulator_consistency_proof_request(1, None);
+    batch.add_get_accumulator_consistency_proof_request(1, Some(version - 1));
+    let responses = runtime
+        .block_on(client.execute(batch))
+        .unwrap()
+        .into_iter()
+        .map(|response| AccumulatorConsistencyProofView::from_response(response.unwrap()).unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(responses[0].client_known_version, 1);
+    assert_eq!(responses[0].target_version, version);
+    assert_eq!(responses[1].target_version, version - 1);
+    let proof: AccumulatorConsistencyProof =
+        lcs::from_bytes(&responses[0].ledger_consistency_proof.into_bytes().unwrap()).unwrap();
+    assert_eq!(proof, AccumulatorConsistencyProof::new(vec![]));
+}
+
+#[test]
+fn test_get_account_resources_of_unknown_account() {
+    let (_mock_db, client, mut runtime) = create_database_client_and_runtime(1);
+
+    let mut batch = JsonRpcBatch::default();
+    batch.add_get_account_resources_request(AccountAddress::random());
+
+    match execute_batch_and_get_first_response(&client, &mut runtime, batch) {
+        JsonRpcResponse::AccountResourcesResponse(resources) => assert!(resources.is_none()),
+        response => panic!("did not receive expected json rpc response: {:?}", response),
+    }
+}
+
 #[test]
 fn test_get_network_status() {
     let (_mock_db, client, mut runtime) = create_database_client_and_runtime(1);
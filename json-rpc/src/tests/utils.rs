@@ -3,19 +3,21 @@
 
 use anyhow::{Error, Result};
 use libra_config::config::RoleType;
-use libra_crypto::HashValue;
+use libra_crypto::{hash::CryptoHash, HashValue};
 use libra_mempool::MempoolClientSender;
 use libra_types::{
     account_address::AccountAddress,
     account_state_blob::{AccountStateBlob, AccountStateWithProof},
     block_info::BlockInfo,
-    contract_event::ContractEvent,
+    contract_event::{ContractEvent, EventWithProof},
     epoch_change::EpochChangeProof,
+    epoch_participation::EpochParticipation,
     event::EventKey,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
     proof::{
-        AccumulatorConsistencyProof, AccumulatorRangeProof, SparseMerkleProof,
-        TransactionAccumulatorProof, TransactionInfoWithProof, TransactionListProof,
+        AccumulatorConsistencyProof, AccumulatorRangeProof, EventAccumulatorProof, EventProof,
+        SparseMerkleProof, TransactionAccumulatorProof, TransactionInfoWithProof,
+        TransactionListProof,
     },
     transaction::{
         Transaction, TransactionInfo, TransactionListWithProof, TransactionWithProof, Version,
@@ -33,7 +35,14 @@ pub fn test_bootstrap(
     libra_db: Arc<dyn DbReader>,
     mp_sender: MempoolClientSender,
 ) -> Runtime {
-    crate::bootstrap(address, libra_db, mp_sender, RoleType::Validator)
+    crate::bootstrap(
+        address,
+        libra_db,
+        mp_sender,
+        None,
+        RoleType::Validator,
+        false,
+    )
 }
 
 /// Lightweight mock of LibraDB
@@ -47,6 +56,38 @@ pub(crate) struct MockLibraDB {
     pub timestamps: Vec<u64>,
 }
 
+impl MockLibraDB {
+    fn get_txn_with_proof(&self, version: usize, fetch_events: bool) -> TransactionWithProof {
+        let (transaction, status) = &self.all_txns[version];
+        TransactionWithProof {
+            version: version as u64,
+            transaction: transaction.clone(),
+            events: if fetch_events {
+                Some(
+                    self.events
+                        .iter()
+                        .filter(|(ev, _)| *ev == version as u64)
+                        .map(|(_, e)| e)
+                        .cloned()
+                        .collect(),
+                )
+            } else {
+                None
+            },
+            proof: TransactionInfoWithProof::new(
+                TransactionAccumulatorProof::new(vec![]),
+                TransactionInfo::new(
+                    Default::default(),
+                    Default::default(),
+                    Default::default(),
+                    0,
+                    *status,
+                ),
+            ),
+        }
+    }
+}
+
 impl DbReader for MockLibraDB {
     fn get_latest_account_state(
         &self,
@@ -87,40 +128,27 @@ impl DbReader for MockLibraDB {
         Ok(self
             .all_txns
             .iter()
-            .enumerate()
-            .find(|(_, (x, _))| {
+            .position(|(x, _)| {
                 if let Ok(t) = x.as_signed_user_txn() {
                     t.sender() == address && t.sequence_number() == seq_num
                 } else {
                     false
                 }
             })
-            .map(|(v, (x, status))| TransactionWithProof {
-                version: v as u64,
-                transaction: x.clone(),
-                events: if fetch_events {
-                    Some(
-                        self.events
-                            .iter()
-                            .filter(|(ev, _)| *ev == v as u64)
-                            .map(|(_, e)| e)
-                            .cloned()
-                            .collect(),
-                    )
-                } else {
-                    None
-                },
-                proof: TransactionInfoWithProof::new(
-                    TransactionAccumulatorProof::new(vec![]),
-                    TransactionInfo::new(
-                        Default::default(),
-                        Default::default(),
-                        Default::default(),
-                        0,
-                        *status,
-                    ),
-                ),
-            }))
+            .map(|v| self.get_txn_with_proof(v, fetch_events)))
+    }
+
+    fn get_transaction_by_hash(
+        &self,
+        hash: HashValue,
+        _ledger_version: u64,
+        fetch_events: bool,
+    ) -> Result<Option<TransactionWithProof>, Error> {
+        Ok(self
+            .all_txns
+            .iter()
+            .position(|(x, _)| x.hash() == hash)
+            .map(|v| self.get_txn_with_proof(v, fetch_events)))
     }
 
     fn get_transactions(
@@ -192,6 +220,44 @@ impl DbReader for MockLibraDB {
         Ok(events)
     }
 
+    fn get_events_with_proofs(
+        &self,
+        key: &EventKey,
+        start: u64,
+        ascending: bool,
+        limit: u64,
+        _ledger_version: Version,
+    ) -> Result<Vec<EventWithProof>> {
+        let events = self
+            .get_events(key, start, ascending, limit)?
+            .into_iter()
+            .map(|(version, event)| {
+                let event_index = self
+                    .events
+                    .iter()
+                    .filter(|(v, _)| *v == version)
+                    .position(|(_, e)| *e == event)
+                    .expect("event must exist") as u64;
+                let (_, status) = &self.all_txns[version as usize];
+                let proof = EventProof::new(
+                    TransactionInfoWithProof::new(
+                        TransactionAccumulatorProof::new(vec![]),
+                        TransactionInfo::new(
+                            Default::default(),
+                            Default::default(),
+                            Default::default(),
+                            0,
+                            *status,
+                        ),
+                    ),
+                    EventAccumulatorProof::new(vec![]),
+                );
+                EventWithProof::new(version, event_index, event, proof)
+            })
+            .collect();
+        Ok(events)
+    }
+
     fn get_state_proof(
         &self,
         known_version: u64,
@@ -220,6 +286,14 @@ impl DbReader for MockLibraDB {
         ))
     }
 
+    fn get_accumulator_consistency_proof(
+        &self,
+        _client_known_version: Version,
+        _target_version: Version,
+    ) -> Result<AccumulatorConsistencyProof> {
+        Ok(AccumulatorConsistencyProof::new(vec![]))
+    }
+
     fn get_account_state_with_proof(
         &self,
         _address: AccountAddress,
@@ -245,7 +319,7 @@ impl DbReader for MockLibraDB {
     }
 
     fn get_latest_state_root(&self) -> Result<(u64, HashValue)> {
-        unimplemented!()
+        Ok((self.version, HashValue::zero()))
     }
 
     fn get_latest_tree_state(&self) -> Result<TreeState> {
@@ -267,4 +341,18 @@ impl DbReader for MockLibraDB {
     fn get_block_timestamp(&self, version: u64) -> Result<u64> {
         Ok(self.timestamps[version as usize])
     }
+
+    /// All the transactions of the mock DB are in epoch 0.
+    fn get_epoch_participation(&self, epoch: u64) -> Result<Option<EpochParticipation>> {
+        if epoch != 0 {
+            return Ok(None);
+        }
+        let mut participation = EpochParticipation::new();
+        for (txn, _) in &self.all_txns {
+            if let Transaction::BlockMetadata(block) = txn {
+                participation.record_block(block);
+            }
+        }
+        Ok(Some(participation))
+    }
 }
@@ -4,7 +4,7 @@
 use anyhow::Result;
 use libra_types::{
     mempool_status::{MempoolStatus, MempoolStatusCode},
-    vm_status::{StatusType, VMStatus},
+    vm_status::{ErrorReason, StatusType, VMStatus},
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -29,6 +29,7 @@ pub enum ServerCode {
     MempoolInvalidUpdate = -32010,
     MempoolVmError = -32011,
     MempoolUnknownError = -32012,
+    MempoolDraining = -32013,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -91,6 +92,7 @@ impl JsonRpcError {
             MempoolStatusCode::InvalidUpdate => ServerCode::MempoolInvalidUpdate,
             MempoolStatusCode::VmError => ServerCode::MempoolVmError,
             MempoolStatusCode::UnknownStatus => ServerCode::MempoolUnknownError,
+            MempoolStatusCode::Draining => ServerCode::MempoolDraining,
             MempoolStatusCode::Accepted => {
                 return Err(anyhow::format_err!(
                     "[JSON RPC] cannot create mempool error for mempool accepted status"
@@ -135,4 +137,10 @@ impl JsonRpcError {
         }
         None
     }
+
+    /// Returns the client-facing reason of the VM error carried by this error, if any.
+    pub fn get_error_reason(&self) -> Option<ErrorReason> {
+        self.get_vm_status()
+            .and_then(|vm_status| vm_status.error_reason())
+    }
 }
@@ -6,16 +6,20 @@ use libra_crypto::HashValue;
 use libra_types::{
     account_config::{
         AccountResource, AccountRole, BalanceResource, BurnEvent, CancelBurnEvent,
-        CurrencyInfoResource, FreezingBit, MintEvent, NewBlockEvent, NewEpochEvent, PreburnEvent,
-        ReceivedPaymentEvent, SentPaymentEvent, ToLBRExchangeRateUpdateEvent, UpgradeEvent,
+        CurrencyInfoResource, FreezeAccountEvent, FreezingBit, MintEvent, NewBlockEvent,
+        NewEpochEvent, PreburnEvent, ReceivedPaymentEvent, RecoveryAddressResource,
+        SentPaymentEvent, SlidingNonceResource, ToLBRExchangeRateUpdateEvent, UnfreezeAccountEvent,
+        UpgradeEvent,
     },
+    account_state::AccountState,
     account_state_blob::AccountStateWithProof,
-    contract_event::ContractEvent,
+    contract_event::{ContractEvent, EventWithProof},
     epoch_change::EpochChangeProof,
+    epoch_participation::EpochParticipation,
     ledger_info::LedgerInfoWithSignatures,
     proof::{AccountStateProof, AccumulatorConsistencyProof},
-    transaction::{Transaction, TransactionArgument, TransactionPayload},
-    vm_status::StatusCode,
+    transaction::{Transaction, TransactionArgument, TransactionOutput, TransactionPayload},
+    vm_status::{ErrorReason, StatusCode},
 };
 use move_core_types::{
     identifier::Identifier,
@@ -23,6 +27,7 @@ use move_core_types::{
     move_resource::MoveResource,
 };
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::{collections::BTreeMap, convert::TryFrom};
 use transaction_builder::get_transaction_name;
 
@@ -64,6 +69,7 @@ pub enum AccountRoleView {
         base_url: String,
         expiration_time: u64,
         compliance_key: BytesView,
+        received_mint_events_key: BytesView,
     },
 }
 
@@ -78,6 +84,14 @@ pub struct AccountView {
     pub delegated_withdrawal_capability: bool,
     pub is_frozen: bool,
     pub role: AccountRoleView,
+    /// The accounts whose authentication key this account can rotate, if it published a
+    /// `RecoveryAddress` resource
+    #[serde(default)]
+    pub recoverable_addresses: Vec<BytesView>,
+    /// The nonces recorded by the admin transactions of this account, if it published a
+    /// `SlidingNonce` resource
+    #[serde(default)]
+    pub sliding_nonce: Option<SlidingNonceView>,
 }
 
 impl AccountView {
@@ -86,6 +100,8 @@ impl AccountView {
         balances: BTreeMap<Identifier, BalanceResource>,
         account_role: AccountRole,
         freezing_bit: FreezingBit,
+        recovery_address: Option<RecoveryAddressResource>,
+        sliding_nonce: Option<SlidingNonceResource>,
     ) -> Self {
         Self {
             balances: balances
@@ -102,6 +118,65 @@ impl AccountView {
             delegated_withdrawal_capability: account.has_delegated_withdrawal_capability(),
             is_frozen: freezing_bit.is_frozen(),
             role: AccountRoleView::from(account_role),
+            recoverable_addresses: recovery_address
+                .map(|recovery_address| {
+                    recovery_address
+                        .recoverable_addresses()
+                        .iter()
+                        .map(|address| BytesView::from(address.as_ref()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            sliding_nonce: sliding_nonce.map(SlidingNonceView::from),
+        }
+    }
+
+    /// Builds the view of `account_state` with its balances in `currencies`, or `None` if it is
+    /// missing any of the resources the view is made of.
+    pub fn try_from_account_state(
+        account_state: &AccountState,
+        currencies: &[Identifier],
+    ) -> Result<Option<Self>> {
+        let account = match account_state.get_account_resource()? {
+            Some(account) => account,
+            None => return Ok(None),
+        };
+        let balances = account_state.get_balance_resources(currencies)?;
+        let account_role = match account_state.get_account_role()? {
+            Some(account_role) => account_role,
+            None => return Ok(None),
+        };
+        let freezing_bit = match account_state.get_freezing_bit()? {
+            Some(freezing_bit) => freezing_bit,
+            None => return Ok(None),
+        };
+        let recovery_address = account_state.get_recovery_address_resource()?;
+        let sliding_nonce = account_state.get_sliding_nonce_resource()?;
+        Ok(Some(Self::new(
+            &account,
+            balances,
+            account_role,
+            freezing_bit,
+            recovery_address,
+            sliding_nonce,
+        )))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SlidingNonceView {
+    pub min_nonce: u64,
+    /// Big-endian bytes of the bitmap of the recorded nonces, starting at `min_nonce`
+    pub nonce_mask: BytesView,
+    pub next_nonce: u64,
+}
+
+impl From<SlidingNonceResource> for SlidingNonceView {
+    fn from(sliding_nonce: SlidingNonceResource) -> Self {
+        Self {
+            min_nonce: sliding_nonce.min_nonce(),
+            nonce_mask: BytesView::from(&sliding_nonce.nonce_mask().to_be_bytes()[..]),
+            next_nonce: sliding_nonce.next_nonce(),
         }
     }
 }
@@ -163,6 +238,16 @@ pub enum EventDataView {
         proposer: BytesView,
         proposed_time: u64,
     },
+    #[serde(rename = "freezeaccount")]
+    FreezeAccount {
+        initiator_address: BytesView,
+        frozen_address: BytesView,
+    },
+    #[serde(rename = "unfreezeaccount")]
+    UnfreezeAccount {
+        initiator_address: BytesView,
+        unfrozen_address: BytesView,
+    },
     #[serde(rename = "unknown")]
     Unknown {},
 }
@@ -284,6 +369,24 @@ impl From<(u64, ContractEvent)> for EventView {
             } else {
                 Err(format_err!("Unable to parse UpgradeEvent"))
             }
+        } else if event.type_tag() == &TypeTag::Struct(FreezeAccountEvent::struct_tag()) {
+            if let Ok(freeze_event) = FreezeAccountEvent::try_from(&event) {
+                Ok(EventDataView::FreezeAccount {
+                    initiator_address: BytesView::from(freeze_event.initiator_address().as_ref()),
+                    frozen_address: BytesView::from(freeze_event.frozen_address().as_ref()),
+                })
+            } else {
+                Err(format_err!("Unable to parse FreezeAccountEvent"))
+            }
+        } else if event.type_tag() == &TypeTag::Struct(UnfreezeAccountEvent::struct_tag()) {
+            if let Ok(unfreeze_event) = UnfreezeAccountEvent::try_from(&event) {
+                Ok(EventDataView::UnfreezeAccount {
+                    initiator_address: BytesView::from(unfreeze_event.initiator_address().as_ref()),
+                    unfrozen_address: BytesView::from(unfreeze_event.unfrozen_address().as_ref()),
+                })
+            } else {
+                Err(format_err!("Unable to parse UnfreezeAccountEvent"))
+            }
         } else {
             Err(format_err!("Unknown events"))
         };
@@ -303,6 +406,86 @@ pub struct BlockMetadata {
     pub timestamp: u64,
 }
 
+/// The participation of validators in consensus during an epoch.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct EpochParticipationView {
+    pub epoch: u64,
+    pub num_blocks: u64,
+    pub num_nil_blocks: u64,
+    pub num_skipped_rounds: u64,
+    pub last_round: u64,
+    pub validators: Vec<ValidatorParticipationView>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct ValidatorParticipationView {
+    pub address: BytesView,
+    pub num_proposals: u64,
+    pub num_votes: u64,
+}
+
+impl From<(u64, EpochParticipation)> for EpochParticipationView {
+    fn from((epoch, participation): (u64, EpochParticipation)) -> Self {
+        Self {
+            epoch,
+            num_blocks: participation.num_blocks(),
+            num_nil_blocks: participation.num_nil_blocks(),
+            num_skipped_rounds: participation.num_skipped_rounds(),
+            last_round: participation.last_round(),
+            validators: participation
+                .validators()
+                .iter()
+                .map(|(address, validator)| ValidatorParticipationView {
+                    address: BytesView::from(address.as_ref()),
+                    num_proposals: validator.num_proposals,
+                    num_votes: validator.num_votes,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A gas unit price estimate, from the gas unit prices of the recently committed user transactions
+/// and the backlog of mempool.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct GasEstimateView {
+    /// The number of recently committed user transactions the estimate is based on.
+    pub num_sampled_transactions: u64,
+    pub median_gas_unit_price: u64,
+    /// The number of transactions in mempool ready to be included in a block.
+    pub num_mempool_transactions: u64,
+    /// The number of full blocks mempool holds transactions for, at the recent block size.
+    pub backlog_blocks: u64,
+    /// The suggested gas unit price: the median one when mempool holds less than a block of
+    /// transactions, otherwise 10 more percentiles per block of backlog.
+    pub gas_unit_price: u64,
+}
+
+impl GasEstimateView {
+    pub fn new(
+        mut gas_unit_prices: Vec<u64>,
+        num_blocks: u64,
+        num_mempool_transactions: u64,
+    ) -> Self {
+        gas_unit_prices.sort_unstable();
+        let num_sampled_transactions = gas_unit_prices.len() as u64;
+        let percentile = |p: u64| match num_sampled_transactions {
+            0 => 0,
+            n => gas_unit_prices[((n - 1) * p / 100) as usize],
+        };
+
+        let block_size = std::cmp::max(num_sampled_transactions / std::cmp::max(num_blocks, 1), 1);
+        let backlog_blocks = num_mempool_transactions / block_size;
+        Self {
+            num_sampled_transactions,
+            median_gas_unit_price: percentile(50),
+            num_mempool_transactions,
+            backlog_blocks,
+            gas_unit_price: percentile(std::cmp::min(50 + 10 * backlog_blocks, 100)),
+        }
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub struct BytesView(pub String);
 
@@ -331,7 +514,71 @@ pub struct TransactionView {
     pub hash: String,
     pub events: Vec<EventView>,
     pub vm_status: StatusCode,
+    /// The client-facing reason the transaction failed, or `None` if it executed successfully.
+    pub error_reason: Option<ErrorReason>,
     pub gas_used: u64,
+    /// The write set of the transaction, only included by nodes configured to re-execute
+    /// transactions for their write sets.
+    #[serde(default)]
+    pub write_set: Option<Vec<WriteSetChangeView>>,
+}
+
+/// The would-be output of a transaction executed against the latest state without being committed.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct SimulatedTransactionView {
+    pub vm_status: StatusCode,
+    /// The client-facing reason the transaction would fail, or `None` if it would succeed.
+    pub error_reason: Option<ErrorReason>,
+    pub gas_used: u64,
+    /// Whether the transaction would be discarded rather than committed with a failure.
+    pub discarded: bool,
+    pub write_set: Vec<WriteSetChangeView>,
+    pub events: Vec<EventView>,
+}
+
+impl SimulatedTransactionView {
+    /// Builds the view of the `output` of a transaction simulated on top of `ledger_version`.
+    pub fn new(ledger_version: u64, output: TransactionOutput) -> Self {
+        let status = output.status().vm_status();
+        Self {
+            vm_status: status.status_code(),
+            error_reason: status.error_reason(),
+            gas_used: output.gas_used(),
+            discarded: output.status().is_discarded(),
+            write_set: output
+                .write_set()
+                .iter()
+                .map(|(access_path, write_op)| WriteSetChangeView {
+                    address: BytesView::from(access_path.address.as_ref()),
+                    path: BytesView::from(&access_path.path),
+                    deletion: write_op.is_deletion(),
+                    before: None,
+                    after: None,
+                })
+                .collect(),
+            events: output
+                .events()
+                .iter()
+                .cloned()
+                .map(|event| (ledger_version + 1, event).into())
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct WriteSetChangeView {
+    pub address: BytesView,
+    pub path: BytesView,
+    /// Whether the value at the access path is deleted rather than written.
+    pub deletion: bool,
+    /// The resource at the access path before the change, in the JSON format of
+    /// `resource_viewer`, if it is annotated.
+    #[serde(default)]
+    pub before: Option<Value>,
+    /// The resource at the access path after the change, if it is annotated and not deleted.
+    #[serde(default)]
+    pub after: Option<Value>,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -439,11 +686,17 @@ impl From<AccountRole> for AccountRoleView {
                 compliance_key: BytesView::from(credential.compliance_public_key()),
                 num_children: vasp.num_children(),
             },
-            AccountRole::DesignatedDealer(dd) => AccountRoleView::DesignatedDealer {
-                human_name: dd.human_name().to_string(),
-                base_url: dd.base_url().to_string(),
-                expiration_time: dd.expiration_date(),
-                compliance_key: BytesView::from(dd.compliance_public_key()),
+            AccountRole::DesignatedDealer {
+                dd_credential,
+                designated_dealer,
+            } => AccountRoleView::DesignatedDealer {
+                human_name: dd_credential.human_name().to_string(),
+                base_url: dd_credential.base_url().to_string(),
+                expiration_time: dd_credential.expiration_date(),
+                compliance_key: BytesView::from(dd_credential.compliance_public_key()),
+                received_mint_events_key: BytesView::from(
+                    designated_dealer.mint_event_handle().key().as_bytes(),
+                ),
             },
         }
     }
@@ -575,6 +828,31 @@ impl
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AccumulatorConsistencyProofView {
+    pub client_known_version: u64,
+    pub target_version: u64,
+    pub ledger_consistency_proof: BytesView,
+}
+
+impl TryFrom<(u64, u64, AccumulatorConsistencyProof)> for AccumulatorConsistencyProofView {
+    type Error = Error;
+
+    fn try_from(
+        (client_known_version, target_version, ledger_consistency_proof): (
+            u64,
+            u64,
+            AccumulatorConsistencyProof,
+        ),
+    ) -> Result<AccumulatorConsistencyProofView, Self::Error> {
+        Ok(AccumulatorConsistencyProofView {
+            client_known_version,
+            target_version,
+            ledger_consistency_proof: BytesView::from(&lcs::to_bytes(&ledger_consistency_proof)?),
+        })
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct AccountStateWithProofView {
     pub version: u64,
@@ -629,3 +907,49 @@ impl TryFrom<AccountStateProof> for AccountStateProofView {
         })
     }
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct EventsWithProofsView {
+    /// The ledger info the proofs of the events are relative to.
+    pub ledger_info_with_signatures: BytesView,
+    pub events: Vec<EventWithProofView>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct EventWithProofView {
+    pub event: EventView,
+    pub event_index: u64,
+    /// The LCS serialized `EventWithProof`, verifiable against `ledger_info_with_signatures`.
+    pub event_with_proof: BytesView,
+}
+
+impl TryFrom<(LedgerInfoWithSignatures, Vec<EventWithProof>)> for EventsWithProofsView {
+    type Error = Error;
+
+    fn try_from(
+        (ledger_info_with_signatures, events_with_proof): (
+            LedgerInfoWithSignatures,
+            Vec<EventWithProof>,
+        ),
+    ) -> Result<EventsWithProofsView, Error> {
+        let events = events_with_proof
+            .into_iter()
+            .map(|event_with_proof| {
+                Ok(EventWithProofView {
+                    event_with_proof: BytesView::from(&lcs::to_bytes(&event_with_proof)?),
+                    event: EventView::from((
+                        event_with_proof.transaction_version,
+                        event_with_proof.event,
+                    )),
+                    event_index: event_with_proof.event_index,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(EventsWithProofsView {
+            ledger_info_with_signatures: BytesView::from(&lcs::to_bytes(
+                &ledger_info_with_signatures,
+            )?),
+            events,
+        })
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use bytecode_verifier::CompatibilityChecker;
+use libra_types::vm_status::StatusCode;
+use proptest::prelude::*;
+use vm::file_format::{
+    basic_test_module, Bytecode, CompiledModule, SignatureToken, StructFieldInformation,
+};
+
+proptest! {
+    #[test]
+    fn identical_module_is_compatible(module in CompiledModule::valid_strategy(20)) {
+        prop_assert!(CompatibilityChecker::verify_module_upgrade(&module, &module).is_ok());
+    }
+}
+
+#[test]
+fn changed_function_body_is_compatible() {
+    let old_module = basic_test_module().freeze().unwrap();
+    let mut new_module = basic_test_module();
+    if let Some(code_unit) = &mut new_module.function_defs[0].code {
+        code_unit.code = vec![Bytecode::LdU64(0), Bytecode::Pop, Bytecode::Ret];
+    }
+    let new_module = new_module.freeze().unwrap();
+    assert!(CompatibilityChecker::verify_module_upgrade(&old_module, &new_module).is_ok());
+}
+
+#[test]
+fn made_function_public_is_compatible() {
+    let old_module = basic_test_module().freeze().unwrap();
+    let mut new_module = basic_test_module();
+    new_module.function_defs[0].is_public = true;
+    let new_module = new_module.freeze().unwrap();
+    assert!(CompatibilityChecker::verify_module_upgrade(&old_module, &new_module).is_ok());
+}
+
+#[test]
+fn removed_public_function_is_incompatible() {
+    let mut old_module = basic_test_module();
+    old_module.function_defs[0].is_public = true;
+    let old_module = old_module.freeze().unwrap();
+    let new_module = basic_test_module().freeze().unwrap();
+    assert_eq!(
+        CompatibilityChecker::verify_module_upgrade(&old_module, &new_module)
+            .unwrap_err()
+            .major_status(),
+        StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE
+    );
+}
+
+#[test]
+fn changed_struct_layout_is_incompatible() {
+    let old_module = basic_test_module().freeze().unwrap();
+    let mut new_module = basic_test_module();
+    if let StructFieldInformation::Declared(fields) =
+        &mut new_module.struct_defs[0].field_information
+    {
+        fields[0].signature.0 = SignatureToken::Bool;
+    }
+    let new_module = new_module.freeze().unwrap();
+    assert_eq!(
+        CompatibilityChecker::verify_module_upgrade(&old_module, &new_module)
+            .unwrap_err()
+            .major_status(),
+        StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE
+    );
+}
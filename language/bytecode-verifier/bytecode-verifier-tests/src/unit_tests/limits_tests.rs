@@ -0,0 +1,102 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use bytecode_verifier::{LimitsVerifier, VerifierConfig};
+use libra_types::vm_status::StatusCode;
+use proptest::prelude::*;
+use vm::file_format::{
+    basic_test_module, empty_script, Bytecode, CompiledModule, Signature, SignatureIndex,
+    SignatureToken,
+};
+
+proptest! {
+    #[test]
+    fn valid_generated(module in CompiledModule::valid_strategy(20)) {
+        prop_assert!(LimitsVerifier::verify_module(&VerifierConfig::unbounded(), &module).is_ok());
+    }
+}
+
+#[test]
+fn too_many_basic_blocks() {
+    let mut module = basic_test_module();
+    module.function_defs[0].code.as_mut().unwrap().code = vec![
+        Bytecode::Branch(1),
+        Bytecode::Branch(2),
+        Bytecode::Branch(3),
+        Bytecode::Ret,
+    ];
+    let module = module.freeze().unwrap();
+
+    let config = VerifierConfig {
+        max_basic_blocks: Some(4),
+        ..VerifierConfig::unbounded()
+    };
+    assert!(LimitsVerifier::verify_module(&config, &module).is_ok());
+
+    let config = VerifierConfig {
+        max_basic_blocks: Some(3),
+        ..VerifierConfig::unbounded()
+    };
+    assert_eq!(
+        LimitsVerifier::verify_module(&config, &module)
+            .unwrap_err()
+            .major_status(),
+        StatusCode::TOO_MANY_BASIC_BLOCKS
+    );
+}
+
+#[test]
+fn too_many_locals() {
+    let mut script = empty_script();
+    script
+        .signatures
+        .push(Signature(vec![SignatureToken::U64, SignatureToken::Bool]));
+    script.parameters = SignatureIndex(1);
+    script.code.locals = SignatureIndex(1);
+    let script = script.freeze().unwrap();
+
+    let config = VerifierConfig {
+        max_locals: Some(4),
+        ..VerifierConfig::unbounded()
+    };
+    assert!(LimitsVerifier::verify_script(&config, &script).is_ok());
+
+    let config = VerifierConfig {
+        max_locals: Some(3),
+        ..VerifierConfig::unbounded()
+    };
+    assert_eq!(
+        LimitsVerifier::verify_script(&config, &script)
+            .unwrap_err()
+            .major_status(),
+        StatusCode::TOO_MANY_LOCALS
+    );
+}
+
+#[test]
+fn too_deeply_nested_type() {
+    let mut ty = SignatureToken::U8;
+    for _ in 0..9 {
+        ty = SignatureToken::Vector(Box::new(ty));
+    }
+    let mut module = basic_test_module();
+    module.signatures.push(Signature(vec![ty]));
+    let module = module.freeze().unwrap();
+
+    let config = VerifierConfig {
+        max_type_depth: Some(10),
+        ..VerifierConfig::unbounded()
+    };
+    assert!(LimitsVerifier::verify_module(&config, &module).is_ok());
+
+    let config = VerifierConfig {
+        max_type_depth: Some(9),
+        ..VerifierConfig::unbounded()
+    };
+    assert_eq!(
+        LimitsVerifier::verify_module(&config, &module)
+            .unwrap_err()
+            .major_status(),
+        StatusCode::TOO_DEEPLY_NESTED_TYPE
+    );
+}
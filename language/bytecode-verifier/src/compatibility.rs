@@ -0,0 +1,226 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements a checker that verifies that a new version of a module can replace an
+//! already published one. The new version is compatible with the old one if:
+//! - every struct in the old module is still defined, with the same resource-ness, type
+//!   parameters and fields (names and types, in the same order), so that the layout of data
+//!   already in global storage is unchanged;
+//! - every public function in the old module is still defined as public, with the same type
+//!   parameters, parameters and return types, so that existing callers still link.
+//!
+//! New structs and functions can be freely added, and function bodies can be changed.
+use libra_types::vm_status::StatusCode;
+use move_core_types::{identifier::Identifier, language_storage::ModuleId};
+use std::collections::BTreeMap;
+use vm::{
+    access::ModuleAccess,
+    errors::{Location, PartialVMError, PartialVMResult, VMResult},
+    file_format::{
+        CompiledModule, FunctionHandle, Kind, SignatureIndex, SignatureToken, StructDefinition,
+        StructFieldInformation, StructHandleIndex,
+    },
+};
+
+pub struct CompatibilityChecker<'a> {
+    old_module: &'a CompiledModule,
+    new_module: &'a CompiledModule,
+}
+
+impl<'a> CompatibilityChecker<'a> {
+    pub fn verify_module_upgrade(
+        old_module: &'a CompiledModule,
+        new_module: &'a CompiledModule,
+    ) -> VMResult<()> {
+        Self::verify_module_upgrade_impl(old_module, new_module)
+            .map_err(|e| e.finish(Location::Module(new_module.self_id())))
+    }
+
+    fn verify_module_upgrade_impl(
+        old_module: &'a CompiledModule,
+        new_module: &'a CompiledModule,
+    ) -> PartialVMResult<()> {
+        if old_module.self_id() != new_module.self_id() {
+            return Err(incompatible(format!(
+                "cannot upgrade {:?} to {:?}",
+                old_module.self_id(),
+                new_module.self_id()
+            )));
+        }
+        let checker = Self {
+            old_module,
+            new_module,
+        };
+        checker.verify_structs()?;
+        checker.verify_public_functions()
+    }
+
+    fn verify_structs(&self) -> PartialVMResult<()> {
+        let new_structs: BTreeMap<_, _> = self
+            .new_module
+            .struct_defs()
+            .iter()
+            .map(|def| (struct_name(self.new_module, def), def))
+            .collect();
+        for old_def in self.old_module.struct_defs() {
+            let name = struct_name(self.old_module, old_def);
+            let new_def = new_structs
+                .get(&name)
+                .ok_or_else(|| incompatible(format!("struct {} was removed", name)))?;
+
+            let old_handle = self.old_module.struct_handle_at(old_def.struct_handle);
+            let new_handle = self.new_module.struct_handle_at(new_def.struct_handle);
+            if old_handle.is_nominal_resource != new_handle.is_nominal_resource
+                || old_handle.type_parameters != new_handle.type_parameters
+            {
+                return Err(incompatible(format!(
+                    "declaration of struct {} changed",
+                    name
+                )));
+            }
+            if self.fields(self.old_module, old_def) != self.fields(self.new_module, new_def) {
+                return Err(incompatible(format!("layout of struct {} changed", name)));
+            }
+        }
+        Ok(())
+    }
+
+    fn verify_public_functions(&self) -> PartialVMResult<()> {
+        let new_functions: BTreeMap<_, _> = self
+            .new_module
+            .function_defs()
+            .iter()
+            .filter(|def| def.is_public)
+            .map(|def| {
+                let handle = self.new_module.function_handle_at(def.function);
+                (
+                    self.new_module.identifier_at(handle.name).to_owned(),
+                    handle,
+                )
+            })
+            .collect();
+        for old_def in self
+            .old_module
+            .function_defs()
+            .iter()
+            .filter(|def| def.is_public)
+        {
+            let old_handle = self.old_module.function_handle_at(old_def.function);
+            let name = self.old_module.identifier_at(old_handle.name);
+            let new_handle = new_functions
+                .get(name)
+                .ok_or_else(|| incompatible(format!("public function {} was removed", name)))?;
+            if self.function_signature(self.old_module, old_handle)
+                != self.function_signature(self.new_module, new_handle)
+            {
+                return Err(incompatible(format!(
+                    "signature of public function {} changed",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn fields(&self, module: &CompiledModule, def: &StructDefinition) -> Option<Vec<Field>> {
+        match &def.field_information {
+            StructFieldInformation::Native => None,
+            StructFieldInformation::Declared(fields) => Some(
+                fields
+                    .iter()
+                    .map(|field| {
+                        (
+                            module.identifier_at(field.name).to_owned(),
+                            Type::new(module, &field.signature.0),
+                        )
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    fn function_signature(
+        &self,
+        module: &CompiledModule,
+        handle: &FunctionHandle,
+    ) -> (Vec<Kind>, Vec<Type>, Vec<Type>) {
+        let types = |sig_idx: SignatureIndex| -> Vec<Type> {
+            module
+                .signature_at(sig_idx)
+                .0
+                .iter()
+                .map(|token| Type::new(module, token))
+                .collect()
+        };
+        (
+            handle.type_parameters.clone(),
+            types(handle.parameters),
+            types(handle.return_),
+        )
+    }
+}
+
+type Field = (Identifier, Type);
+
+/// A module independent representation of a `SignatureToken`, with struct handles resolved to
+/// the fully qualified name of the struct they refer to.
+#[derive(Debug, Eq, PartialEq)]
+enum Type {
+    Bool,
+    U8,
+    U64,
+    U128,
+    Address,
+    Signer,
+    Vector(Box<Type>),
+    Struct(ModuleId, Identifier, Vec<Type>),
+    Reference(Box<Type>),
+    MutableReference(Box<Type>),
+    TypeParameter(u16),
+}
+
+impl Type {
+    fn new(module: &CompiledModule, token: &SignatureToken) -> Self {
+        match token {
+            SignatureToken::Bool => Type::Bool,
+            SignatureToken::U8 => Type::U8,
+            SignatureToken::U64 => Type::U64,
+            SignatureToken::U128 => Type::U128,
+            SignatureToken::Address => Type::Address,
+            SignatureToken::Signer => Type::Signer,
+            SignatureToken::Vector(ty) => Type::Vector(Box::new(Type::new(module, ty))),
+            SignatureToken::Struct(idx) => Self::new_struct(module, *idx, &[]),
+            SignatureToken::StructInstantiation(idx, type_args) => {
+                Self::new_struct(module, *idx, type_args)
+            }
+            SignatureToken::Reference(ty) => Type::Reference(Box::new(Type::new(module, ty))),
+            SignatureToken::MutableReference(ty) => {
+                Type::MutableReference(Box::new(Type::new(module, ty)))
+            }
+            SignatureToken::TypeParameter(idx) => Type::TypeParameter(*idx),
+        }
+    }
+
+    fn new_struct(
+        module: &CompiledModule,
+        idx: StructHandleIndex,
+        type_args: &[SignatureToken],
+    ) -> Self {
+        let handle = module.struct_handle_at(idx);
+        let module_id = module.module_id_for_handle(module.module_handle_at(handle.module));
+        Type::Struct(
+            module_id,
+            module.identifier_at(handle.name).to_owned(),
+            type_args.iter().map(|ty| Type::new(module, ty)).collect(),
+        )
+    }
+}
+
+fn struct_name(module: &CompiledModule, def: &StructDefinition) -> Identifier {
+    let handle = module.struct_handle_at(def.struct_handle);
+    module.identifier_at(handle.name).to_owned()
+}
+
+fn incompatible(message: String) -> PartialVMError {
+    PartialVMError::new(StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE).with_message(message)
+}
@@ -8,12 +8,14 @@
 // Bounds checks are implemented in the `vm` crate.
 pub mod check_duplication;
 pub mod code_unit_verifier;
+pub mod compatibility;
 pub mod constants;
 pub mod control_flow;
 pub mod control_flow_graph;
 pub mod dependencies;
 pub mod instantiation_loops;
 pub mod instruction_consistency;
+pub mod limits;
 pub mod resources;
 pub mod signature;
 pub mod struct_defs;
@@ -22,8 +24,10 @@ pub mod verifier;
 
 pub use check_duplication::DuplicationChecker;
 pub use code_unit_verifier::CodeUnitVerifier;
+pub use compatibility::CompatibilityChecker;
 pub use dependencies::DependencyChecker;
 pub use instruction_consistency::InstructionConsistency;
+pub use limits::{LimitsVerifier, VerifierConfig};
 pub use resources::ResourceTransitiveChecker;
 pub use signature::SignatureChecker;
 pub use struct_defs::RecursiveStructDefChecker;
@@ -0,0 +1,179 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements a checker that bounds the complexity of the bytecode handed to the
+//! other verification passes. Several of those passes run an abstract interpreter over the
+//! control flow graph of every function, so a function with very many basic blocks and locals,
+//! or with deeply nested types, can make verification disproportionately expensive.
+use crate::control_flow_graph::{ControlFlowGraph, VMControlFlowGraph};
+use libra_types::vm_status::StatusCode;
+use vm::{
+    access::{ModuleAccess, ScriptAccess},
+    errors::{Location, PartialVMError, PartialVMResult, VMResult},
+    file_format::{
+        CodeUnit, CompiledModule, CompiledScript, FunctionDefinitionIndex, Signature,
+        SignatureToken, StructFieldInformation, TableIndex,
+    },
+    IndexKind,
+};
+
+/// Limits on the complexity of the bytecode accepted by the verifier. `None` means unbounded.
+#[derive(Clone, Copy, Debug)]
+pub struct VerifierConfig {
+    /// Maximum number of basic blocks in the control flow graph of a function.
+    pub max_basic_blocks: Option<usize>,
+    /// Maximum number of locals of a function, parameters included. The binary format already
+    /// bounds this to the range of a `LocalIndex`.
+    pub max_locals: Option<usize>,
+    /// Maximum nesting depth of a type, e.g. `vector<vector<u8>>` has a depth of 3.
+    pub max_type_depth: Option<usize>,
+}
+
+impl VerifierConfig {
+    /// A configuration that does not limit anything.
+    pub fn unbounded() -> Self {
+        Self {
+            max_basic_blocks: None,
+            max_locals: None,
+            max_type_depth: None,
+        }
+    }
+}
+
+impl Default for VerifierConfig {
+    fn default() -> Self {
+        Self {
+            max_basic_blocks: Some(1024),
+            max_locals: None,
+            max_type_depth: Some(64),
+        }
+    }
+}
+
+pub struct LimitsVerifier<'a> {
+    config: &'a VerifierConfig,
+}
+
+impl<'a> LimitsVerifier<'a> {
+    pub fn verify_module(config: &'a VerifierConfig, module: &CompiledModule) -> VMResult<()> {
+        Self { config }
+            .verify_module_impl(module)
+            .map_err(|e| e.finish(Location::Module(module.self_id())))
+    }
+
+    fn verify_module_impl(&self, module: &CompiledModule) -> PartialVMResult<()> {
+        self.verify_type_depths(module.signatures())?;
+        for (idx, constant) in module.constant_pool().iter().enumerate() {
+            self.verify_type_depth(&constant.type_)
+                .map_err(|err| err.at_index(IndexKind::ConstantPool, idx as TableIndex))?;
+        }
+        for (idx, struct_def) in module.struct_defs().iter().enumerate() {
+            if let StructFieldInformation::Declared(fields) = &struct_def.field_information {
+                for field in fields {
+                    self.verify_type_depth(&field.signature.0).map_err(|err| {
+                        err.at_index(IndexKind::StructDefinition, idx as TableIndex)
+                    })?;
+                }
+            }
+        }
+        for (idx, function_def) in module.function_defs().iter().enumerate() {
+            // nothing to verify for native function
+            let code = match &function_def.code {
+                Some(code) => code,
+                None => continue,
+            };
+            let handle = module.function_handle_at(function_def.function);
+            let parameters = module.signature_at(handle.parameters);
+            let locals = module.signature_at(code.locals);
+            self.verify_code_unit(code, parameters, locals)
+                .map_err(|err| err.at_index(IndexKind::FunctionDefinition, idx as TableIndex))?;
+        }
+        Ok(())
+    }
+
+    pub fn verify_script(config: &'a VerifierConfig, script: &CompiledScript) -> VMResult<()> {
+        Self { config }
+            .verify_script_impl(script)
+            .map_err(|e| e.finish(Location::Script))
+    }
+
+    fn verify_script_impl(&self, script: &CompiledScript) -> PartialVMResult<()> {
+        self.verify_type_depths(script.signatures())?;
+        for (idx, constant) in script.constant_pool().iter().enumerate() {
+            self.verify_type_depth(&constant.type_)
+                .map_err(|err| err.at_index(IndexKind::ConstantPool, idx as TableIndex))?;
+        }
+        let parameters = script.signature_at(script.as_inner().parameters);
+        let locals = script.signature_at(script.code().locals);
+        self.verify_code_unit(script.code(), parameters, locals)
+            .map_err(|err| {
+                err.at_index(IndexKind::FunctionDefinition, CompiledScript::MAIN_INDEX.0)
+            })
+    }
+
+    fn verify_code_unit(
+        &self,
+        code: &CodeUnit,
+        parameters: &Signature,
+        locals: &Signature,
+    ) -> PartialVMResult<()> {
+        if let Some(max_locals) = self.config.max_locals {
+            let num_locals = parameters.len() + locals.len();
+            if num_locals > max_locals {
+                return Err(
+                    PartialVMError::new(StatusCode::TOO_MANY_LOCALS).with_message(format!(
+                        "{} locals exceed the limit of {}",
+                        num_locals, max_locals
+                    )),
+                );
+            }
+        }
+        if let Some(max_basic_blocks) = self.config.max_basic_blocks {
+            let num_blocks = VMControlFlowGraph::new(&code.code).num_blocks() as usize;
+            if num_blocks > max_basic_blocks {
+                return Err(
+                    PartialVMError::new(StatusCode::TOO_MANY_BASIC_BLOCKS).with_message(format!(
+                        "{} basic blocks exceed the limit of {}",
+                        num_blocks, max_basic_blocks
+                    )),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn verify_type_depths(&self, signatures: &[Signature]) -> PartialVMResult<()> {
+        for (idx, signature) in signatures.iter().enumerate() {
+            for ty in &signature.0 {
+                self.verify_type_depth(ty)
+                    .map_err(|err| err.at_index(IndexKind::Signature, idx as TableIndex))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn verify_type_depth(&self, ty: &SignatureToken) -> PartialVMResult<()> {
+        if let Some(max_type_depth) = self.config.max_type_depth {
+            let depth = type_depth(ty);
+            if depth > max_type_depth {
+                return Err(
+                    PartialVMError::new(StatusCode::TOO_DEEPLY_NESTED_TYPE).with_message(format!(
+                        "Type of depth {} exceeds the limit of {}",
+                        depth, max_type_depth
+                    )),
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+fn type_depth(ty: &SignatureToken) -> usize {
+    use SignatureToken::*;
+
+    match ty {
+        Bool | U8 | U64 | U128 | Address | Signer | Struct(_) | TypeParameter(_) => 1,
+        Vector(inner) | Reference(inner) | MutableReference(inner) => 1 + type_depth(inner),
+        StructInstantiation(_, ty_args) => 1 + ty_args.iter().map(type_depth).max().unwrap_or(0),
+    }
+}
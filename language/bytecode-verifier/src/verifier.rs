@@ -3,9 +3,14 @@
 
 //! This module contains the public APIs supported by the bytecode verifier.
 use crate::{
-    check_duplication::DuplicationChecker, code_unit_verifier::CodeUnitVerifier, constants,
-    instantiation_loops::InstantiationLoopChecker, instruction_consistency::InstructionConsistency,
-    resources::ResourceTransitiveChecker, signature::SignatureChecker,
+    check_duplication::DuplicationChecker,
+    code_unit_verifier::CodeUnitVerifier,
+    constants,
+    instantiation_loops::InstantiationLoopChecker,
+    instruction_consistency::InstructionConsistency,
+    limits::{LimitsVerifier, VerifierConfig},
+    resources::ResourceTransitiveChecker,
+    signature::SignatureChecker,
     struct_defs::RecursiveStructDefChecker,
 };
 use libra_types::vm_status::StatusCode;
@@ -29,6 +34,7 @@ pub fn verify_module(module: &CompiledModule) -> VMResult<()> {
     ResourceTransitiveChecker::verify_module(&module)?;
     RecursiveStructDefChecker::verify_module(&module)?;
     InstantiationLoopChecker::verify_module(&module)?;
+    LimitsVerifier::verify_module(&VerifierConfig::default(), &module)?;
     CodeUnitVerifier::verify_module(&module)
 }
 
@@ -43,6 +49,7 @@ pub fn verify_script(script: &CompiledScript) -> VMResult<()> {
     SignatureChecker::verify_script(&script)?;
     InstructionConsistency::verify_script(&script)?;
     constants::verify_script(&script)?;
+    LimitsVerifier::verify_script(&VerifierConfig::default(), &script)?;
     CodeUnitVerifier::verify_script(&script)?;
     verify_main_signature(&script)
 }
@@ -53,40 +60,49 @@ pub fn verify_main_signature(script: &CompiledScript) -> VMResult<()> {
 }
 
 fn verify_main_signature_impl(script: &CompiledScript) -> PartialVMResult<()> {
-    fn is_valid_arg_type(idx: usize, arg_type: &SignatureToken) -> bool {
+    fn is_signer_reference(arg_type: &SignatureToken) -> bool {
+        use SignatureToken as S;
+        matches!(arg_type, S::Reference(inner) if matches!(&**inner, S::Signer))
+    }
+
+    fn is_valid_arg_type(arg_type: &SignatureToken) -> bool {
         use SignatureToken as S;
         match arg_type {
             S::Bool | S::U8 | S::U64 | S::U128 | S::Address => true,
             S::Vector(inner) => match &**inner {
                 S::U8 => true,
+                S::Vector(inner) => matches!(&**inner, S::U8),
                 S::Bool
                 | S::U64
                 | S::U128
                 | S::Address
                 | S::Signer
                 | S::Struct(_)
-                | S::Vector(_)
                 | S::StructInstantiation(_, _)
                 | S::Reference(_)
                 | S::MutableReference(_)
                 | S::TypeParameter(_) => false,
             },
 
-            // &signer is a type that can only be populated by the Move VM. And its value is filled
-            // based on the sender of the transaction
-            S::Reference(inner) => idx == 0 && matches!(&**inner, S::Signer),
-
             S::Signer
             | S::Struct(_)
             | S::StructInstantiation(_, _)
+            | S::Reference(_)
             | S::MutableReference(_)
             | S::TypeParameter(_) => false,
         }
     }
 
+    // &signer is a type that can only be populated by the Move VM, with the signers of the
+    // transaction. Any number of them can be taken, but only before all other arguments.
     let arguments = script.signature_at(script.as_inner().parameters);
-    for (idx, arg_type) in arguments.0.iter().enumerate() {
-        if !is_valid_arg_type(idx, arg_type) {
+    let num_signers = arguments
+        .0
+        .iter()
+        .take_while(|arg_type| is_signer_reference(arg_type))
+        .count();
+    for arg_type in &arguments.0[num_signers..] {
+        if !is_valid_arg_type(arg_type) {
             return Err(PartialVMError::new(
                 StatusCode::INVALID_MAIN_FUNCTION_SIGNATURE,
             ));
@@ -22,7 +22,7 @@ use vm::{
 
 pub type SourceName<Location> = (String, Location);
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct StructSourceMap<Location: Clone + Eq> {
     /// The source declaration location of the struct
     pub decl_location: Location,
@@ -35,7 +35,7 @@ pub struct StructSourceMap<Location: Clone + Eq> {
     pub fields: Vec<Location>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct FunctionSourceMap<Location: Clone + Eq> {
     /// The source location for the definition of this entire function. Note that in certain
     /// instances this will have no valid source location e.g. the "main" function for modules that
@@ -60,7 +60,7 @@ pub struct FunctionSourceMap<Location: Clone + Eq> {
     pub code_map: BTreeMap<CodeOffset, Location>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct SourceMap<Location: Clone + Eq> {
     /// The name <address.module_name> for module that this source map is for
     /// None if it is a script
@@ -12,9 +12,14 @@ use codespan_reporting::{
         Config,
     },
 };
+use move_core_types::identifier::IdentStr;
 use move_ir_types::location::Loc;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{collections::HashMap, fs::File, io::Read, path::Path};
+use vm::{
+    access::ModuleAccess,
+    file_format::{CodeOffset, FunctionDefinitionIndex, TableIndex},
+};
 
 pub type Error = (Loc, String);
 pub type Errors = Vec<Error>;
@@ -53,6 +58,35 @@ pub fn create_diagnostic(id: FileId, (loc, msg): Error) -> Diagnostic {
     Diagnostic::new_error("", Label::new(id, loc.span(), msg))
 }
 
+/// Returns the source file name and the (1-based) line of the instruction at `code_offset` in
+/// `function`. This is used to map the frame of a Move abort back to the source code.
+pub fn source_line_of_code_offset(
+    source_mapper: &SourceMapping<Loc>,
+    function: &IdentStr,
+    code_offset: CodeOffset,
+) -> Result<(String, usize)> {
+    let (source_file_name, source_string) = source_mapper
+        .source_code
+        .as_ref()
+        .ok_or_else(|| format_err!("Source code is not available"))?;
+    let module = &source_mapper.bytecode;
+    let fdef_idx = module
+        .function_defs()
+        .iter()
+        .position(|fdef| {
+            module.identifier_at(module.function_handle_at(fdef.function).name) == function
+        })
+        .ok_or_else(|| format_err!("Unable to find function {}", function))?;
+    let loc = source_mapper
+        .source_map
+        .get_code_location(FunctionDefinitionIndex(fdef_idx as TableIndex), code_offset)?;
+    let start = loc.span().start().0 as usize;
+    let prefix = source_string
+        .get(..start)
+        .ok_or_else(|| format_err!("Code location is outside of the source code"))?;
+    Ok((source_file_name.clone(), prefix.matches('\n').count() + 1))
+}
+
 //***************************************************************************
 // Deserialization helper
 //***************************************************************************
@@ -78,6 +78,16 @@ impl Compiler {
         Ok(self.compile_mod(file_name, code)?.0)
     }
 
+    /// Compiles into a `CompiledModule` where the bytecode hasn't been serialized.
+    pub fn into_compiled_module_and_source_map(
+        mut self,
+        file_name: &str,
+        code: &str,
+    ) -> Result<(CompiledModule, SourceMap<Loc>)> {
+        let (compiled_module, source_map, _) = self.compile_mod(file_name, code)?;
+        Ok((compiled_module, source_map))
+    }
+
     /// Compiles the module into a serialized form.
     pub fn into_module_blob(mut self, file_name: &str, code: &str) -> Result<Vec<u8>> {
         let compiled_module = self.compile_mod(file_name, code)?.0;
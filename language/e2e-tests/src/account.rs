@@ -393,24 +393,28 @@ impl Default for Account {
 
 pub struct TransactionBuilder {
     pub sender: Account,
+    pub secondary_signers: Vec<Account>,
     pub sequence_number: Option<u64>,
     pub program: Option<TransactionPayload>,
     pub max_gas_amount: Option<u64>,
     pub gas_unit_price: Option<u64>,
     pub gas_currency_code: Option<String>,
     pub ttl: Option<Duration>,
+    pub chain_id: Option<ChainId>,
 }
 
 impl TransactionBuilder {
     pub fn new(sender: Account) -> Self {
         Self {
             sender,
+            secondary_signers: Vec::new(),
             sequence_number: None,
             program: None,
             max_gas_amount: None,
             gas_unit_price: None,
             gas_currency_code: None,
             ttl: None,
+            chain_id: None,
         }
     }
 
@@ -419,6 +423,13 @@ impl TransactionBuilder {
         self
     }
 
+    /// Makes the transaction multi-agent: `secondary_signers` sign it along with the sender, and
+    /// are passed to the script as signers after the sender, in order.
+    pub fn secondary_signers(mut self, secondary_signers: Vec<Account>) -> Self {
+        self.secondary_signers = secondary_signers;
+        self
+    }
+
     pub fn script(mut self, s: Script) -> Self {
         self.program = Some(TransactionPayload::Script(s));
         self
@@ -454,8 +465,13 @@ impl TransactionBuilder {
         self
     }
 
+    pub fn chain_id(mut self, chain_id: ChainId) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
     pub fn sign(self) -> SignedTransaction {
-        RawTransaction::new(
+        let raw_txn = RawTransaction::new(
             *self.sender.address(),
             self.sequence_number.expect("sequence number not set"),
             self.program.expect("transaction payload not set"),
@@ -466,9 +482,23 @@ impl TransactionBuilder {
                 .unwrap_or_else(|| LBR_NAME.to_owned()),
             self.ttl
                 .unwrap_or_else(|| Duration::from_secs(DEFAULT_EXPIRATION_TIME)),
-            ChainId::test(),
-        )
-        .sign(&self.sender.privkey, self.sender.pubkey)
+            self.chain_id.unwrap_or_else(ChainId::test),
+        );
+        if self.secondary_signers.is_empty() {
+            raw_txn.sign(&self.sender.privkey, self.sender.pubkey)
+        } else {
+            raw_txn.sign_multi_agent(
+                &self.sender.privkey,
+                self.secondary_signers
+                    .iter()
+                    .map(|signer| *signer.address())
+                    .collect(),
+                self.secondary_signers
+                    .iter()
+                    .map(|signer| &signer.privkey)
+                    .collect(),
+            )
+        }
         .unwrap()
         .into_inner()
     }
@@ -269,7 +269,7 @@ pub fn txn_one_account_result(
             sender.balance -= gas_used * gas_price;
             (
                 // TODO(tmn) provide a real abort location
-                TransactionStatus::Keep(VMStatus::MoveAbort(AbortLocation::Script, 6)),
+                TransactionStatus::Keep(VMStatus::MoveAbort(AbortLocation::Script, 6, None)),
                 false,
             )
         }
@@ -281,7 +281,7 @@ pub fn txn_one_account_result(
             sender.balance -= low_gas_used * gas_price;
             (
                 // TODO(tmn) provide a real abort location
-                TransactionStatus::Keep(VMStatus::MoveAbort(AbortLocation::Script, 10)),
+                TransactionStatus::Keep(VMStatus::MoveAbort(AbortLocation::Script, 10, None)),
                 false,
             )
         }
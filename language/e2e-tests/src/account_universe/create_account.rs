@@ -115,7 +115,7 @@ impl AUTransactionGen for CreateExistingAccountGen {
             gas_used = sender.create_existing_account_gas_cost();
             sender.balance -= gas_used * gas_price;
             // TODO(tmn) provide a real abort location
-            TransactionStatus::Keep(VMStatus::MoveAbort(AbortLocation::Script, 777_777))
+            TransactionStatus::Keep(VMStatus::MoveAbort(AbortLocation::Script, 777_777, None))
         } else {
             // Not enough gas to get past the prologue.
             TransactionStatus::Discard(VMStatus::Error(
@@ -80,7 +80,8 @@ impl AUTransactionGen for P2PTransferGen {
                 // epilogue.
                 // TODO: define these values in a central location
                 // TODO(tmn) provide a real abort location
-                status = TransactionStatus::Keep(VMStatus::MoveAbort(AbortLocation::Script, 6));
+                status =
+                    TransactionStatus::Keep(VMStatus::MoveAbort(AbortLocation::Script, 6, None));
             }
             (true, false, _) => {
                 // Enough to pass validation but not to do the transfer. The transaction will be run
@@ -89,7 +90,8 @@ impl AUTransactionGen for P2PTransferGen {
                 gas_used = sender.peer_to_peer_too_low_gas_cost();
                 sender.balance -= gas_used * txn.gas_unit_price();
                 // 10 means the balance was insufficient while trying to transfer.
-                status = TransactionStatus::Keep(VMStatus::MoveAbort(AbortLocation::Script, 5));
+                status =
+                    TransactionStatus::Keep(VMStatus::MoveAbort(AbortLocation::Script, 5, None));
             }
             (false, _, _) => {
                 // Not enough gas to pass validation. Nothing will happen.
@@ -14,6 +14,10 @@ use libra_types::{
 };
 use move_core_types::language_storage::TypeTag;
 use once_cell::sync::Lazy;
+use transaction_builder::{
+    encode_create_child_vasp_account_script, encode_create_parent_vasp_account_script,
+    encode_peer_to_peer_with_metadata_script,
+};
 
 pub static CREATE_ACCOUNT_SCRIPT: Lazy<Vec<u8>> = Lazy::new(|| {
     let code = "
@@ -80,6 +84,80 @@ pub static EMPTY_SCRIPT: Lazy<Vec<u8>> = Lazy::new(|| {
         .expect("Failed to compile")
 });
 
+pub static ADD_TO_SCRIPT_ALLOW_LIST_SCRIPT: Lazy<Vec<u8>> = Lazy::new(|| {
+    let code = "
+    import 0x1.LibraTransactionPublishingOption;
+
+    main(account: &signer, hash: vector<u8>) {
+      LibraTransactionPublishingOption.add_to_script_allow_list(move(account), move(hash));
+      return;
+    }
+";
+
+    let compiler = Compiler {
+        address: account_config::CORE_CODE_ADDRESS,
+        extra_deps: vec![],
+        ..Compiler::default()
+    };
+    compiler
+        .into_script_blob("file_name", code)
+        .expect("Failed to compile")
+});
+
+pub static REMOVE_FROM_SCRIPT_ALLOW_LIST_SCRIPT: Lazy<Vec<u8>> = Lazy::new(|| {
+    let code = "
+    import 0x1.LibraTransactionPublishingOption;
+
+    main(account: &signer, hash: vector<u8>) {
+      LibraTransactionPublishingOption.remove_from_script_allow_list(move(account), move(hash));
+      return;
+    }
+";
+
+    let compiler = Compiler {
+        address: account_config::CORE_CODE_ADDRESS,
+        extra_deps: vec![],
+        ..Compiler::default()
+    };
+    compiler
+        .into_script_blob("file_name", code)
+        .expect("Failed to compile")
+});
+
+/// Returns a transaction to allow the script with hash `hash` to be executed
+pub fn add_to_script_allow_list_txn(
+    sender: &Account,
+    hash: Vec<u8>,
+    seq_num: u64,
+) -> SignedTransaction {
+    sender.create_signed_txn_with_args(
+        ADD_TO_SCRIPT_ALLOW_LIST_SCRIPT.to_vec(),
+        vec![],
+        vec![TransactionArgument::U8Vector(hash)],
+        seq_num,
+        gas_costs::TXN_RESERVED,
+        0,
+        LBR_NAME.to_owned(),
+    )
+}
+
+/// Returns a transaction to remove the script with hash `hash` from the allow list
+pub fn remove_from_script_allow_list_txn(
+    sender: &Account,
+    hash: Vec<u8>,
+    seq_num: u64,
+) -> SignedTransaction {
+    sender.create_signed_txn_with_args(
+        REMOVE_FROM_SCRIPT_ALLOW_LIST_SCRIPT.to_vec(),
+        vec![],
+        vec![TransactionArgument::U8Vector(hash)],
+        seq_num,
+        gas_costs::TXN_RESERVED,
+        0,
+        LBR_NAME.to_owned(),
+    )
+}
+
 /// Returns a transaction to add a new validator
 pub fn add_validator_txn(
     sender: &Account,
@@ -316,3 +394,112 @@ pub fn raw_rotate_key_txn(
         LBR_NAME.to_owned(),
     )
 }
+
+/// Returns a transaction for the treasury compliance account `sender` to freeze `account`.
+pub fn freeze_account_txn(
+    sender: &Account,
+    account: &Account,
+    sliding_nonce: u64,
+    seq_num: u64,
+) -> SignedTransaction {
+    let args = vec![
+        TransactionArgument::U64(sliding_nonce),
+        TransactionArgument::Address(*account.address()),
+    ];
+    sender.create_signed_txn_with_args(
+        StdlibScript::FreezeAccount.compiled_bytes().into_vec(),
+        vec![],
+        args,
+        seq_num,
+        gas_costs::TXN_RESERVED,
+        0,
+        LBR_NAME.to_owned(),
+    )
+}
+
+/// Returns a transaction for the treasury compliance account `sender` to unfreeze `account`.
+pub fn unfreeze_account_txn(
+    sender: &Account,
+    account: &Account,
+    sliding_nonce: u64,
+    seq_num: u64,
+) -> SignedTransaction {
+    let args = vec![
+        TransactionArgument::U64(sliding_nonce),
+        TransactionArgument::Address(*account.address()),
+    ];
+    sender.create_signed_txn_with_args(
+        StdlibScript::UnfreezeAccount.compiled_bytes().into_vec(),
+        vec![],
+        args,
+        seq_num,
+        gas_costs::TXN_RESERVED,
+        0,
+        LBR_NAME.to_owned(),
+    )
+}
+
+/// Returns a transaction for the libra root account `sender` to create the parent VASP account
+/// `new_account`, holding a balance in `coin_type` and using `compliance_public_key`.
+pub fn create_parent_vasp_txn(
+    sender: &Account,
+    new_account: &Account,
+    coin_type: TypeTag,
+    compliance_public_key: Vec<u8>,
+    seq_num: u64,
+) -> SignedTransaction {
+    sender.signed_script_txn(
+        encode_create_parent_vasp_account_script(
+            coin_type,
+            *new_account.address(),
+            new_account.auth_key_prefix(),
+            vec![],
+            vec![],
+            compliance_public_key,
+            false,
+        ),
+        seq_num,
+    )
+}
+
+/// Returns a transaction for the parent VASP `sender` to create the child VASP account `child`,
+/// funded with `initial_balance` of `coin_type` from the parent.
+pub fn create_child_vasp_txn(
+    sender: &Account,
+    child: &Account,
+    coin_type: TypeTag,
+    initial_balance: u64,
+    seq_num: u64,
+) -> SignedTransaction {
+    sender.signed_script_txn(
+        encode_create_child_vasp_account_script(
+            coin_type,
+            *child.address(),
+            child.auth_key_prefix(),
+            false,
+            initial_balance,
+        ),
+        seq_num,
+    )
+}
+
+/// Returns a transaction to transfer `amount` of `currency` from one account to another, without
+/// metadata. Transfers between a parent VASP and its children are not subject to dual attestation.
+pub fn peer_to_peer_with_currency_txn(
+    sender: &Account,
+    receiver: &Account,
+    currency: TypeTag,
+    amount: u64,
+    seq_num: u64,
+) -> SignedTransaction {
+    sender.signed_script_txn(
+        encode_peer_to_peer_with_metadata_script(
+            currency,
+            *receiver.address(),
+            amount,
+            vec![],
+            vec![],
+        ),
+        seq_num,
+    )
+}
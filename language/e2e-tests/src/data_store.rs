@@ -9,6 +9,7 @@ use compiled_stdlib::StdLibOptions;
 use libra_state_view::StateView;
 use libra_types::{
     access_path::AccessPath,
+    account_state::AccountState,
     on_chain_config::ConfigStorage,
     transaction::ChangeSet,
     write_set::{WriteOp, WriteSet},
@@ -74,6 +75,17 @@ impl FakeDataStore {
         self.data.remove(access_path)
     }
 
+    /// Collects all the resources stored under `address` into an [`AccountState`].
+    pub fn account_state(&self, address: &AccountAddress) -> AccountState {
+        let mut account_state = AccountState::default();
+        for (access_path, blob) in &self.data {
+            if &access_path.address == address {
+                account_state.insert(access_path.path.clone(), blob.clone());
+            }
+        }
+        account_state
+    }
+
     /// Adds an [`AccountData`] to this data store.
     pub fn add_account_data(&mut self, account_data: &AccountData) {
         let write_set = account_data.to_writeset();
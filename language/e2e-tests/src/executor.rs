@@ -14,8 +14,10 @@ use libra_state_view::StateView;
 use libra_types::{
     access_path::AccessPath,
     account_config::{AccountResource, BalanceResource, CORE_CODE_ADDRESS},
+    account_state::AccountState,
     block_metadata::{new_block_event_key, BlockMetadata, NewBlockEvent},
-    on_chain_config::{OnChainConfig, ScriptPublishingOption, VMPublishingOption, ValidatorSet},
+    chain_id::ChainId,
+    on_chain_config::{OnChainConfig, VMPublishingOption, ValidatorSet},
     transaction::{
         SignedTransaction, Transaction, TransactionOutput, TransactionStatus, VMValidatorResult,
     },
@@ -23,8 +25,8 @@ use libra_types::{
     write_set::WriteSet,
 };
 use libra_vm::{
-    data_cache::RemoteStorage, txn_effects_to_writeset_and_events, LibraVM, LibraVMValidator,
-    VMExecutor, VMValidator,
+    data_cache::RemoteStorage, effects_audit::EffectsReport, txn_effects_to_writeset_and_events,
+    LibraVM, LibraVMValidator, VMExecutor, VMValidator,
 };
 use move_core_types::{
     account_address::AccountAddress,
@@ -32,11 +34,12 @@ use move_core_types::{
     identifier::Identifier,
     language_storage::{ModuleId, TypeTag},
 };
-use move_vm_runtime::move_vm::MoveVM;
+use move_vm_runtime::{execution_trace::TraceEvent, move_vm::MoveVM};
 use move_vm_types::{
     gas_schedule::{zero_cost_schedule, CostStrategy},
     values::Value,
 };
+use resource_viewer::{AnnotatedWriteSetEntry, MoveValueAnnotator};
 use vm::CompiledModule;
 use vm_genesis::GENESIS_KEYPAIR;
 
@@ -47,6 +50,7 @@ use vm_genesis::GENESIS_KEYPAIR;
 pub struct FakeExecutor {
     data_store: FakeDataStore,
     block_time: u64,
+    gas_free: bool,
 }
 
 impl FakeExecutor {
@@ -55,6 +59,7 @@ impl FakeExecutor {
         let mut executor = FakeExecutor {
             data_store: FakeDataStore::default(),
             block_time: 0,
+            gas_free: false,
         };
         executor.apply_write_set(write_set);
         executor
@@ -82,7 +87,7 @@ impl FakeExecutor {
     /// publishing options given by `publishing_options`. These can only be either `Open` or
     /// `CustomScript`.
     pub fn from_genesis_with_options(publishing_options: VMPublishingOption) -> Self {
-        if let ScriptPublishingOption::Locked(_) = publishing_options.script_option {
+        if !publishing_options.is_open_script() {
             panic!("Whitelisted transactions are not supported as a publishing option")
         }
 
@@ -98,6 +103,7 @@ impl FakeExecutor {
         FakeExecutor {
             data_store: FakeDataStore::default(),
             block_time: 0,
+            gas_free: false,
         }
     }
 
@@ -117,6 +123,7 @@ impl FakeExecutor {
                 &vm_genesis::operator_registrations(&swarm.nodes),
                 &genesis_modules,
                 publishing_options,
+                ChainId::test(),
             )
             .0
         };
@@ -176,6 +183,11 @@ impl FakeExecutor {
             })
     }
 
+    /// Reads all the resources of an account from this executor's data store.
+    pub fn read_account_state(&self, account: &Account) -> AccountState {
+        self.data_store.account_state(account.address())
+    }
+
     /// Executes the given block of transactions.
     ///
     /// Typical tests will call this method and check that the output matches what was expected.
@@ -184,12 +196,11 @@ impl FakeExecutor {
         &self,
         txn_block: Vec<SignedTransaction>,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
-        LibraVM::execute_block(
+        self.execute_transaction_block(
             txn_block
                 .into_iter()
                 .map(Transaction::UserTransaction)
                 .collect(),
-            &self.data_store,
         )
     }
 
@@ -218,7 +229,11 @@ impl FakeExecutor {
         &self,
         txn_block: Vec<Transaction>,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
-        LibraVM::execute_block(txn_block, &self.data_store)
+        if self.gas_free {
+            LibraVM::execute_block_without_gas_metering(txn_block, &self.data_store)
+        } else {
+            LibraVM::execute_block(txn_block, &self.data_store)
+        }
     }
 
     pub fn execute_transaction(&self, txn: SignedTransaction) -> TransactionOutput {
@@ -231,6 +246,41 @@ impl FakeExecutor {
             .expect("A block with one transaction should have one output")
     }
 
+    /// Executes the transaction as a singleton block and returns its output along with every
+    /// instruction and native call executed by the VM.
+    pub fn execute_transaction_with_trace(
+        &self,
+        txn: SignedTransaction,
+    ) -> (TransactionOutput, Vec<TraceEvent>) {
+        let mut outputs = LibraVM::execute_block_with_trace(
+            vec![Transaction::UserTransaction(txn)],
+            &self.data_store,
+        )
+        .expect("The VM should not fail to startup");
+        outputs
+            .pop()
+            .expect("A block with one transaction should have one output")
+    }
+
+    /// Executes the transaction as a singleton block and returns its output along with the entries
+    /// of its write set, annotated with the resources they replace and write.
+    pub fn execute_transaction_with_annotated_write_set(
+        &self,
+        txn: SignedTransaction,
+    ) -> (TransactionOutput, Vec<AnnotatedWriteSetEntry>) {
+        let output = self.execute_transaction(txn);
+        let write_set = MoveValueAnnotator::new(&self.data_store)
+            .view_write_set(output.write_set())
+            .expect("Failed to annotate the write set");
+        (output, write_set)
+    }
+
+    /// Executes the transaction as a singleton block and reports which of its effects persist.
+    pub fn audit_transaction_effects(&self, txn: SignedTransaction) -> EffectsReport {
+        LibraVM::audit_transaction_effects(Transaction::UserTransaction(txn), &self.data_store)
+            .expect("The VM should not fail to startup")
+    }
+
     /// Get the blob for the associated AccessPath
     pub fn read_from_access_path(&self, path: &AccessPath) -> Option<Vec<u8>> {
         StateView::get(&self.data_store, path).unwrap()
@@ -246,7 +296,9 @@ impl FakeExecutor {
         &self.data_store
     }
 
-    pub fn new_block(&mut self) {
+    /// Executes the block prologue of a new block proposed by the first validator and applies its
+    /// write set. Returns the output of the block prologue.
+    pub fn new_block(&mut self) -> TransactionOutput {
         let validator_set = ValidatorSet::fetch_config(&self.data_store)
             .expect("Unable to retrieve the validator set from storage");
         self.block_time += 1;
@@ -267,6 +319,7 @@ impl FakeExecutor {
         assert_eq!(event.key(), &new_block_event_key());
         assert!(lcs::from_bytes::<NewBlockEvent>(event.event_data()).is_ok());
         self.apply_write_set(output.write_set());
+        output
     }
 
     fn module(name: &str) -> ModuleId {
@@ -285,6 +338,13 @@ impl FakeExecutor {
         self.block_time
     }
 
+    /// Sets whether transactions are executed with gas metering disabled, so that tests of the
+    /// logic of scripts don't depend on gas: transactions then use no gas and never run out of it.
+    /// Traced executions are always metered.
+    pub fn set_gas_free(&mut self, gas_free: bool) {
+        self.gas_free = gas_free;
+    }
+
     pub fn exec(
         &mut self,
         module_name: &str,
@@ -7,7 +7,10 @@
 //!
 //! This crate contains helpers for executing tests against the Libra VM.
 
-use libra_types::{transaction::TransactionStatus, vm_status::VMStatus};
+use libra_types::{
+    transaction::TransactionStatus,
+    vm_status::{AbortLocation, VMStatus},
+};
 
 #[cfg(test)]
 mod tests;
@@ -38,6 +41,25 @@ pub fn transaction_status_eq(t1: &TransactionStatus, t2: &TransactionStatus) ->
     }
 }
 
+/// Asserts that `status` is an abort with `code` raised by an `abort` instruction in `function` at
+/// `location`. The bytecode offset of the `abort` is not compared.
+pub fn assert_move_abort(
+    status: &TransactionStatus,
+    location: &AbortLocation,
+    function: &str,
+    code: u64,
+) {
+    let vm_status = status.vm_status();
+    assert_eq!(vm_status.move_abort_code(), Some(code));
+    assert_eq!(vm_status.move_abort_location(), Some(location));
+    assert_eq!(
+        vm_status
+            .move_abort_frame()
+            .map(|frame| frame.function.as_str()),
+        Some(function)
+    );
+}
+
 #[macro_export]
 macro_rules! assert_prologue_parity {
     ($e1:expr, $e2:expr, $e3:expr) => {
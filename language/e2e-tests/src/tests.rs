@@ -9,20 +9,33 @@
 //! separate binary. The linker ends up repeating a lot of work for each binary to not much
 //! benefit.
 
+mod abort_source_line;
+mod account_freezing;
 mod account_universe;
+mod annotated_write_set;
 mod create_account;
+mod currency_registration;
 mod data_store;
+mod delayed_key_rotation;
 mod execution_strategies;
+mod execution_trace;
+mod exchange_rates;
 mod failed_transaction_tests;
+mod failure_effects;
+mod gas_free;
 mod genesis;
 mod mint;
 mod module_publishing;
+mod multi_agent;
 mod on_chain_configs;
 mod peer_to_peer;
+mod recovery_address;
 mod rotate_key;
 mod scripts;
+mod sliding_nonce;
 mod transaction_builder;
 mod transaction_fees;
 mod validator_set_management;
+mod vasp;
 mod verify_txn;
 mod write_set;
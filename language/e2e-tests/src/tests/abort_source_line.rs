@@ -0,0 +1,81 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{account::AccountData, compile::compile_script_with_address, executor::FakeExecutor};
+use bytecode_source_map::{mapping::SourceMapping, utils::source_line_of_code_offset};
+use compiler::Compiler;
+use libra_types::{account_config::LBR_NAME, vm_status::AbortLocation};
+
+#[test]
+fn abort_frame_maps_to_source_line() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let sender = AccountData::new(1_000_000, 10);
+    executor.add_account_data(&sender);
+
+    let module_code = "
+        module M {
+            public check(x: u64) {
+                if (copy(x) > 10) {
+                    abort 77;
+                }
+                return;
+            }
+        }
+    ";
+    let compiler = Compiler {
+        address: *sender.address(),
+        ..Compiler::default()
+    };
+    let (module, source_map) = compiler
+        .into_compiled_module_and_source_map("M.mvir", module_code)
+        .expect("Module compilation failed");
+    executor.add_module(&module.self_id(), &module);
+
+    let program = format!(
+        "
+            import 0x{}.M;
+
+            main() {{
+                M.check(11);
+                return;
+            }}
+        ",
+        sender.address(),
+    );
+    let script = compile_script_with_address(
+        sender.address(),
+        "file_name",
+        &program,
+        vec![module.clone()],
+    );
+    let txn = sender.account().create_signed_txn_impl(
+        *sender.address(),
+        script,
+        10,
+        100_000,
+        0,
+        LBR_NAME.to_owned(),
+    );
+    let output = executor.execute_transaction(txn);
+    let vm_status = output.status().vm_status();
+    assert_eq!(vm_status.move_abort_code(), Some(77));
+    assert_eq!(
+        vm_status.move_abort_location(),
+        Some(&AbortLocation::Module(module.self_id()))
+    );
+    let frame = vm_status
+        .move_abort_frame()
+        .expect("Abort must have a frame");
+
+    let mut source_mapper = SourceMapping::new(source_map, module);
+    source_mapper.source_code = Some(("M.mvir".to_string(), module_code.to_string()));
+    let abort_line = module_code
+        .lines()
+        .position(|line| line.contains("abort 77"))
+        .unwrap()
+        + 1;
+    assert_eq!(
+        source_line_of_code_offset(&source_mapper, &frame.function, frame.code_offset).unwrap(),
+        ("M.mvir".to_string(), abort_line)
+    );
+}
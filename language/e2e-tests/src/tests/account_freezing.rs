@@ -0,0 +1,170 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account::{Account, AccountData},
+    assert_move_abort,
+    common_transactions::{freeze_account_txn, peer_to_peer_txn, unfreeze_account_txn},
+    executor::FakeExecutor,
+    keygen::KeyGen,
+};
+use libra_json_rpc_types::views::{AccountView, BytesView, EventDataView, EventView};
+use libra_types::{
+    account_config::{
+        self, from_currency_code_string, FreezeAccountEvent, UnfreezeAccountEvent,
+        CORE_CODE_ADDRESS, LBR_NAME,
+    },
+    transaction::{TransactionOutput, TransactionStatus},
+    vm_status::{AbortLocation, StatusCode, VMStatus},
+};
+use move_core_types::{identifier::Identifier, language_storage::ModuleId};
+use std::convert::TryFrom;
+use transaction_builder::encode_create_parent_vasp_account_script;
+
+fn create_parent_vasp(executor: &mut FakeExecutor, account: &Account, seq_num: u64) {
+    let libra_root = Account::new_libra_root();
+    let mut keygen = KeyGen::from_seed([9u8; 32]);
+    let (_, cpubkey) = keygen.generate_keypair();
+    let add_all_currencies = true;
+    executor.execute_and_apply(libra_root.signed_script_txn(
+        encode_create_parent_vasp_account_script(
+            account_config::lbr_type_tag(),
+            *account.address(),
+            account.auth_key_prefix(),
+            vec![],
+            vec![],
+            cpubkey.to_bytes().to_vec(),
+            add_all_currencies,
+        ),
+        seq_num,
+    ));
+}
+
+/// The JSON-RPC view of `account` built from the current state of `executor`.
+fn account_view(executor: &FakeExecutor, account: &Account) -> AccountView {
+    AccountView::try_from_account_state(
+        &executor.read_account_state(account),
+        &[from_currency_code_string(LBR_NAME).unwrap()],
+    )
+    .unwrap()
+    .expect("account view must exist")
+}
+
+fn assert_account_freezing_abort(output: &TransactionOutput, function: &str, code: u64) {
+    assert_move_abort(
+        output.status(),
+        &AbortLocation::Module(ModuleId::new(
+            CORE_CODE_ADDRESS,
+            Identifier::new("AccountFreezing").unwrap(),
+        )),
+        function,
+        code,
+    );
+}
+
+#[test]
+fn freeze_and_unfreeze_parent_vasp() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let blessed = Account::new_blessed_tc();
+    let account = Account::new();
+    create_parent_vasp(&mut executor, &account, 1);
+    let sender = AccountData::new(1_000_000, 0);
+    executor.add_account_data(&sender);
+
+    assert!(!account_view(&executor, &account).is_frozen);
+
+    // Freezing the account emits an event recording who froze it.
+    let output = executor.execute_and_apply(freeze_account_txn(&blessed, &account, 0, 0));
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(VMStatus::Executed)
+    );
+    let freeze_events: Vec<_> = output
+        .events()
+        .iter()
+        .filter_map(|event| FreezeAccountEvent::try_from(event).ok())
+        .collect();
+    assert_eq!(freeze_events.len(), 1);
+    assert_eq!(freeze_events[0].initiator_address(), *blessed.address());
+    assert_eq!(freeze_events[0].frozen_address(), *account.address());
+
+    let event = output
+        .events()
+        .iter()
+        .find(|event| FreezeAccountEvent::try_from(*event).is_ok())
+        .unwrap();
+    assert_eq!(
+        EventView::from((0, event.clone())).data,
+        EventDataView::FreezeAccount {
+            initiator_address: BytesView::from(blessed.address().as_ref()),
+            frozen_address: BytesView::from(account.address().as_ref()),
+        }
+    );
+
+    assert!(account_view(&executor, &account).is_frozen);
+
+    // Transactions sent by the frozen account are rejected by the prologue.
+    let output = executor.execute_transaction(peer_to_peer_txn(&account, sender.account(), 0, 10));
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Discard(VMStatus::Error(StatusCode::SENDING_ACCOUNT_FROZEN)),
+    );
+
+    // The frozen account cannot receive payments either.
+    let output = executor.execute_transaction(peer_to_peer_txn(sender.account(), &account, 0, 10));
+    assert_move_abort(
+        output.status(),
+        &AbortLocation::Module(account_config::ACCOUNT_MODULE.clone()),
+        "deposit",
+        16,
+    );
+
+    // Unfreezing the account emits an event and lifts both restrictions.
+    let output = executor.execute_and_apply(unfreeze_account_txn(&blessed, &account, 1, 1));
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(VMStatus::Executed)
+    );
+    let unfreeze_events: Vec<_> = output
+        .events()
+        .iter()
+        .filter_map(|event| UnfreezeAccountEvent::try_from(event).ok())
+        .collect();
+    assert_eq!(unfreeze_events.len(), 1);
+    assert_eq!(unfreeze_events[0].initiator_address(), *blessed.address());
+    assert_eq!(unfreeze_events[0].unfrozen_address(), *account.address());
+
+    assert!(!account_view(&executor, &account).is_frozen);
+
+    let output = executor.execute_and_apply(peer_to_peer_txn(sender.account(), &account, 0, 10));
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(VMStatus::Executed)
+    );
+    let output = executor.execute_and_apply(peer_to_peer_txn(&account, sender.account(), 0, 10));
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(VMStatus::Executed)
+    );
+}
+
+#[test]
+fn freeze_account_permissions() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let blessed = Account::new_blessed_tc();
+    let libra_root = Account::new_libra_root();
+    let account = Account::new();
+    create_parent_vasp(&mut executor, &account, 1);
+
+    // Only the treasury compliance account can freeze and unfreeze accounts.
+    let output = executor.execute_transaction(freeze_account_txn(&libra_root, &account, 0, 2));
+    assert_account_freezing_abort(&output, "freeze_account", 2);
+    let output = executor.execute_transaction(unfreeze_account_txn(&libra_root, &account, 0, 2));
+    assert_account_freezing_abort(&output, "unfreeze_account", 5);
+
+    // The libra root and treasury compliance accounts cannot be frozen.
+    let output = executor.execute_transaction(freeze_account_txn(&blessed, &libra_root, 0, 0));
+    assert_account_freezing_abort(&output, "freeze_account", 3);
+    let output = executor.execute_transaction(freeze_account_txn(&blessed, &blessed, 0, 0));
+    assert_account_freezing_abort(&output, "freeze_account", 4);
+}
@@ -0,0 +1,58 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account::{self, AccountData},
+    common_transactions::peer_to_peer_txn,
+    executor::FakeExecutor,
+};
+use libra_types::{transaction::TransactionStatus, vm_status::VMStatus};
+use resource_viewer::AnnotatedMoveStruct;
+
+/// The coins of an annotated `Balance` resource.
+fn coins(balance: &AnnotatedMoveStruct) -> u64 {
+    let balance = serde_json::to_value(balance).unwrap();
+    balance["fields"][0]["value"]["value"]["fields"][0]["value"]["value"]
+        .as_u64()
+        .expect("Balance resource has coins")
+}
+
+#[test]
+fn annotated_write_set_of_peer_to_peer() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let sender = AccountData::new(1_000_000, 10);
+    let receiver = AccountData::new(100_000, 10);
+    executor.add_account_data(&sender);
+    executor.add_account_data(&receiver);
+
+    let txn = peer_to_peer_txn(sender.account(), receiver.account(), 10, 1_000);
+    let (output, write_set) = executor.execute_transaction_with_annotated_write_set(txn.clone());
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(VMStatus::Executed)
+    );
+    // annotating the write set must not change the outcome of the transaction
+    assert_eq!(output, executor.execute_transaction(txn));
+    assert_eq!(write_set.len(), output.write_set().iter().count());
+
+    // only the accounts of the sender and of the receiver change
+    assert!(write_set.iter().all(|entry| {
+        entry.access_path.address == *sender.address()
+            || entry.access_path.address == *receiver.address()
+    }));
+    for (account, before, after) in &[
+        (&sender, 1_000_000, 1_000_000 - 1_000),
+        (&receiver, 100_000, 100_000 + 1_000),
+    ] {
+        let balance_path = account
+            .account()
+            .make_balance_access_path(account::lbr_currency_code());
+        let entry = write_set
+            .iter()
+            .find(|entry| entry.access_path == balance_path)
+            .expect("Balance is written");
+        assert!(!entry.deletion);
+        assert_eq!(coins(entry.before.as_ref().unwrap()), *before);
+        assert_eq!(coins(entry.after.as_ref().unwrap()), *after);
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{account::Account, executor::FakeExecutor, keygen::KeyGen};
+use compiler::Compiler;
+use libra_types::{
+    account_config::{self, from_currency_code_string, LBR_NAME},
+    on_chain_config::{OnChainConfig, RegisteredCurrencies},
+    transaction::TransactionPayload,
+};
+use transaction_builder::{encode_create_designated_dealer_script, encode_tiered_mint_script};
+use vm::CompiledModule;
+use writeset_transaction_generator::encode_register_currency_change_set;
+
+const COIN3_NAME: &str = "Coin3";
+
+/// A currency defined the same way as `Coin1` and `Coin2`, to be registered after genesis.
+fn coin3_module() -> CompiledModule {
+    let code = "
+    module Coin3 {
+        import 0x1.AccountLimits;
+        import 0x1.FixedPoint32;
+        import 0x1.Libra;
+        import 0x1.TransactionFee;
+
+        struct Coin3 { dummy_field: bool }
+
+        public initialize(lr_account: &signer, tc_account: &signer) {
+            Libra.register_SCS_currency<Self.Coin3>(
+                copy(lr_account),
+                copy(tc_account),
+                FixedPoint32.create_from_rational(1, 2),
+                1000000,
+                100,
+                h\"436f696e33\"
+            );
+            AccountLimits.publish_unrestricted_limits<Self.Coin3>(copy(lr_account));
+            TransactionFee.add_currency<Self.Coin3>(move(lr_account), move(tc_account));
+            return;
+        }
+    }
+    ";
+    Compiler {
+        address: account_config::CORE_CODE_ADDRESS,
+        ..Compiler::default()
+    }
+    .into_compiled_module("file_name", code)
+    .expect("Failed to compile")
+}
+
+#[test]
+fn register_currency_after_genesis() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    // The registration reconfigures the system, which requires a new block time.
+    executor.new_block();
+    let libra_root = Account::new_libra_root();
+    let blessed = Account::new_blessed_tc();
+    let coin3_code = from_currency_code_string(COIN3_NAME).unwrap();
+    let coin3_tag = account_config::type_tag_for_currency_code(coin3_code.clone());
+
+    let module = coin3_module();
+    let change_set =
+        encode_register_currency_change_set(executor.get_state_view(), &module).unwrap();
+    executor.execute_and_apply(libra_root.create_signed_txn_impl(
+        *libra_root.address(),
+        TransactionPayload::WriteSet(change_set),
+        1,
+        100_000,
+        0,
+        LBR_NAME.to_owned(),
+    ));
+    let registered_currencies =
+        RegisteredCurrencies::fetch_config(executor.get_state_view()).unwrap();
+    assert!(registered_currencies.currency_codes().contains(&coin3_code));
+
+    // The currency cannot be registered twice.
+    assert!(encode_register_currency_change_set(executor.get_state_view(), &module).is_err());
+
+    // Designated dealers can be created for the new currency, and mint it.
+    let dd = Account::new();
+    let mut keygen = KeyGen::from_seed([9u8; 32]);
+    let (_, pubkey) = keygen.generate_keypair();
+    executor.execute_and_apply(blessed.signed_script_txn(
+        encode_create_designated_dealer_script(
+            coin3_tag.clone(),
+            0,
+            *dd.address(),
+            dd.auth_key_prefix(),
+            vec![],
+            vec![],
+            pubkey.to_bytes().to_vec(),
+            false, // add_all_currencies
+        ),
+        0,
+    ));
+    executor.execute_and_apply(blessed.signed_script_txn(
+        encode_tiered_mint_script(coin3_tag, 1, *dd.address(), 1_000, 0),
+        1,
+    ));
+    let dd_balance = executor
+        .read_balance_resource(&dd, coin3_code)
+        .expect("receiver balance must exist");
+    assert_eq!(dd_balance.coin(), 1_000);
+}
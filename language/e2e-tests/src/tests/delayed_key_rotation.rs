@@ -0,0 +1,168 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account::AccountData, assert_move_abort, common_transactions::rotate_key_txn,
+    executor::FakeExecutor, keygen::KeyGen,
+};
+use libra_types::{
+    account_config::{self, CORE_CODE_ADDRESS},
+    transaction::{authenticator::AuthenticationKey, TransactionOutput, TransactionStatus},
+    vm_status::{AbortLocation, StatusCode, VMStatus},
+};
+use move_core_types::{identifier::Identifier, language_storage::ModuleId};
+use transaction_builder::{
+    encode_activate_delayed_key_rotation_script, encode_cancel_delayed_key_rotation_script,
+    encode_enable_delayed_key_rotation_script, encode_schedule_delayed_key_rotation_script,
+};
+
+const DELAY_SECONDS: u64 = 10;
+const DELAY_MICROS: u64 = DELAY_SECONDS * 1_000_000;
+
+fn assert_delayed_key_rotation_abort(output: &TransactionOutput, function: &str, code: u64) {
+    assert_move_abort(
+        output.status(),
+        &AbortLocation::Module(ModuleId::new(
+            CORE_CODE_ADDRESS,
+            Identifier::new("DelayedKeyRotation").unwrap(),
+        )),
+        function,
+        code,
+    );
+}
+
+#[test]
+fn rotate_key_after_delay() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    executor.new_block();
+    let mut keygen = KeyGen::from_seed([9u8; 32]);
+    let sender = AccountData::new(1_000_000, 0);
+    let other = AccountData::new(1_000_000, 0);
+    executor.add_account_data(&sender);
+    executor.add_account_data(&other);
+    let mut sender = sender.into_account();
+    let other = other.into_account();
+
+    executor.execute_and_apply(
+        sender.signed_script_txn(encode_enable_delayed_key_rotation_script(DELAY_SECONDS), 0),
+    );
+
+    // The key can no longer be rotated at once.
+    let (_, public_key) = keygen.generate_keypair();
+    let output = executor.execute_transaction(rotate_key_txn(
+        &sender,
+        AuthenticationKey::ed25519(&public_key).to_vec(),
+        1,
+    ));
+    assert_move_abort(
+        output.status(),
+        &AbortLocation::Module(account_config::ACCOUNT_MODULE.clone()),
+        "extract_key_rotation_capability",
+        9,
+    );
+
+    // A rotation scheduled with a compromised key can be canceled with the current key until its
+    // delay has passed.
+    let (_, attacker_public_key) = keygen.generate_keypair();
+    executor.execute_and_apply(sender.signed_script_txn(
+        encode_schedule_delayed_key_rotation_script(
+            AuthenticationKey::ed25519(&attacker_public_key).to_vec(),
+        ),
+        1,
+    ));
+    let output = executor.execute_transaction(other.signed_script_txn(
+        encode_activate_delayed_key_rotation_script(*sender.address()),
+        0,
+    ));
+    assert_delayed_key_rotation_abort(&output, "activate_rotation", 6);
+    executor.execute_and_apply(
+        sender.signed_script_txn(encode_cancel_delayed_key_rotation_script(), 2),
+    );
+    let output = executor.execute_transaction(other.signed_script_txn(
+        encode_activate_delayed_key_rotation_script(*sender.address()),
+        0,
+    ));
+    assert_delayed_key_rotation_abort(&output, "activate_rotation", 5);
+
+    // A rotation can only be activated once its delay has passed.
+    let (new_private_key, new_public_key) = keygen.generate_keypair();
+    let new_key = AuthenticationKey::ed25519(&new_public_key).to_vec();
+    let scheduled_time = executor.get_block_time();
+    executor.execute_and_apply(sender.signed_script_txn(
+        encode_schedule_delayed_key_rotation_script(new_key.clone()),
+        3,
+    ));
+    let output = executor.execute_transaction(sender.signed_script_txn(
+        encode_schedule_delayed_key_rotation_script(new_key.clone()),
+        4,
+    ));
+    assert_delayed_key_rotation_abort(&output, "schedule_rotation", 4);
+
+    executor.set_block_time(scheduled_time + DELAY_MICROS - 2);
+    executor.new_block();
+    let output = executor.execute_transaction(other.signed_script_txn(
+        encode_activate_delayed_key_rotation_script(*sender.address()),
+        0,
+    ));
+    assert_delayed_key_rotation_abort(&output, "activate_rotation", 6);
+
+    executor.new_block();
+    executor.execute_and_apply(other.signed_script_txn(
+        encode_activate_delayed_key_rotation_script(*sender.address()),
+        0,
+    ));
+    assert_eq!(
+        executor
+            .read_account_resource(&sender)
+            .unwrap()
+            .authentication_key(),
+        new_key.as_slice()
+    );
+
+    // The old key can no longer sign, while the new one can schedule the next rotation.
+    let output = executor.execute_transaction(
+        sender.signed_script_txn(encode_cancel_delayed_key_rotation_script(), 4),
+    );
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Discard(VMStatus::Error(StatusCode::INVALID_AUTH_KEY)),
+    );
+    sender.rotate_key(new_private_key, new_public_key);
+    let output = executor.execute_transaction(
+        sender.signed_script_txn(encode_cancel_delayed_key_rotation_script(), 4),
+    );
+    assert_delayed_key_rotation_abort(&output, "cancel_rotation", 5);
+}
+
+#[test]
+fn delayed_key_rotation_errors() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let sender = AccountData::new(1_000_000, 0);
+    executor.add_account_data(&sender);
+    let sender = sender.into_account();
+
+    let output = executor.execute_transaction(
+        sender.signed_script_txn(encode_schedule_delayed_key_rotation_script(vec![0; 32]), 0),
+    );
+    assert_delayed_key_rotation_abort(&output, "schedule_rotation", 1);
+
+    for invalid_delay in &[0, 365 * 24 * 3600 + 1] {
+        let output = executor.execute_transaction(
+            sender.signed_script_txn(encode_enable_delayed_key_rotation_script(*invalid_delay), 0),
+        );
+        assert_delayed_key_rotation_abort(&output, "publish", 2);
+    }
+
+    executor.execute_and_apply(
+        sender.signed_script_txn(encode_enable_delayed_key_rotation_script(DELAY_SECONDS), 0),
+    );
+    let output = executor.execute_transaction(
+        sender.signed_script_txn(encode_enable_delayed_key_rotation_script(DELAY_SECONDS), 1),
+    );
+    assert_delayed_key_rotation_abort(&output, "publish", 0);
+
+    let output = executor.execute_transaction(
+        sender.signed_script_txn(encode_schedule_delayed_key_rotation_script(vec![0; 31]), 1),
+    );
+    assert_delayed_key_rotation_abort(&output, "schedule_rotation", 3);
+}
@@ -0,0 +1,140 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{account::Account, assert_move_abort, executor::FakeExecutor};
+use libra_types::{
+    account_config::{
+        self, from_currency_code_string, CurrencyInfoResource, COIN1_NAME, COIN2_NAME,
+        CORE_CODE_ADDRESS,
+    },
+    transaction::TransactionOutput,
+    vm_status::AbortLocation,
+};
+use move_core_types::{identifier::Identifier, language_storage::ModuleId};
+use transaction_builder::{
+    encode_set_exchange_rate_update_bounds_script, encode_update_exchange_rate_script,
+    encode_update_exchange_rates_script,
+};
+
+fn exchange_rate(executor: &FakeExecutor, currency_name: &str) -> f32 {
+    let path =
+        CurrencyInfoResource::resource_path_for(from_currency_code_string(currency_name).unwrap());
+    let blob = executor
+        .read_from_access_path(&path)
+        .expect("currency info must exist");
+    lcs::from_bytes::<CurrencyInfoResource>(&blob)
+        .expect("Failure decoding currency info resource")
+        .exchange_rate()
+}
+
+fn assert_libra_abort(output: &TransactionOutput, function: &str, code: u64) {
+    assert_move_abort(
+        output.status(),
+        &AbortLocation::Module(ModuleId::new(
+            CORE_CODE_ADDRESS,
+            Identifier::new("Libra").unwrap(),
+        )),
+        function,
+        code,
+    );
+}
+
+#[test]
+fn exchange_rate_update_bounds() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let blessed = Account::new_blessed_tc();
+    assert_eq!(exchange_rate(&executor, COIN1_NAME), 0.5);
+
+    // Updates can change the current rate by at most a tenth of it.
+    executor.execute_and_apply(
+        blessed.signed_script_txn(encode_set_exchange_rate_update_bounds_script(0, 1, 10), 0),
+    );
+
+    // 0.5 -> 0.56 is over the bound.
+    let output = executor.execute_transaction(blessed.signed_script_txn(
+        encode_update_exchange_rate_script(account_config::coin1_tag(), 1, 56, 100),
+        1,
+    ));
+    assert_libra_abort(&output, "assert_exchange_rate_change_in_bounds", 11);
+    assert_eq!(exchange_rate(&executor, COIN1_NAME), 0.5);
+
+    // 0.5 -> 0.45 is over the bound by a rounding error: the raw value of 0.45 is rounded down,
+    // so the decrease is one unit larger than the rounded down bound.
+    let output = executor.execute_transaction(blessed.signed_script_txn(
+        encode_update_exchange_rate_script(account_config::coin1_tag(), 1, 9, 20),
+        1,
+    ));
+    assert_libra_abort(&output, "assert_exchange_rate_change_in_bounds", 11);
+
+    // 0.5 -> 0.55 is exactly on the bound.
+    executor.execute_and_apply(blessed.signed_script_txn(
+        encode_update_exchange_rate_script(account_config::coin1_tag(), 1, 11, 20),
+        1,
+    ));
+    assert_eq!(exchange_rate(&executor, COIN1_NAME), 0.55);
+
+    // Loosening the bounds allows larger changes.
+    executor.execute_and_apply(
+        blessed.signed_script_txn(encode_set_exchange_rate_update_bounds_script(2, 1, 1), 2),
+    );
+    executor.execute_and_apply(blessed.signed_script_txn(
+        encode_update_exchange_rate_script(account_config::coin1_tag(), 3, 1, 1),
+        3,
+    ));
+    assert_eq!(exchange_rate(&executor, COIN1_NAME), 1.0);
+}
+
+#[test]
+fn batched_exchange_rate_update() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let blessed = Account::new_blessed_tc();
+
+    executor.execute_and_apply(blessed.signed_script_txn(
+        encode_update_exchange_rates_script(
+            account_config::coin1_tag(),
+            account_config::coin2_tag(),
+            0,
+            123,
+            100,
+            1,
+            4,
+        ),
+        0,
+    ));
+    assert_eq!(exchange_rate(&executor, COIN1_NAME), 1.23);
+    assert_eq!(exchange_rate(&executor, COIN2_NAME), 0.25);
+
+    executor.execute_and_apply(
+        blessed.signed_script_txn(encode_set_exchange_rate_update_bounds_script(1, 1, 5), 1),
+    );
+
+    // The second update is over the bound, so neither rate changes.
+    let output = executor.execute_transaction(blessed.signed_script_txn(
+        encode_update_exchange_rates_script(
+            account_config::coin1_tag(),
+            account_config::coin2_tag(),
+            2,
+            1,
+            1,
+            1,
+            2,
+        ),
+        2,
+    ));
+    assert_libra_abort(&output, "assert_exchange_rate_change_in_bounds", 11);
+    executor.apply_write_set(output.write_set());
+    assert_eq!(exchange_rate(&executor, COIN1_NAME), 1.23);
+    assert_eq!(exchange_rate(&executor, COIN2_NAME), 0.25);
+}
+
+#[test]
+fn set_exchange_rate_update_bounds_permissions() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let libra_root = Account::new_libra_root();
+
+    // Only the treasury compliance account can bound exchange rate updates.
+    let output = executor.execute_transaction(
+        libra_root.signed_script_txn(encode_set_exchange_rate_update_bounds_script(0, 1, 10), 1),
+    );
+    assert_libra_abort(&output, "set_exchange_rate_update_bounds", 2);
+}
@@ -0,0 +1,33 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{account::AccountData, common_transactions::peer_to_peer_txn, executor::FakeExecutor};
+use libra_types::{transaction::TransactionStatus, vm_status::VMStatus};
+use move_vm_runtime::execution_trace::TraceEvent;
+
+#[test]
+fn trace_peer_to_peer() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let sender = AccountData::new(1_000_000, 10);
+    let receiver = AccountData::new(100_000, 10);
+    executor.add_account_data(&sender);
+    executor.add_account_data(&receiver);
+
+    let txn = peer_to_peer_txn(sender.account(), receiver.account(), 10, 1_000);
+    let (output, trace) = executor.execute_transaction_with_trace(txn.clone());
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(VMStatus::Executed)
+    );
+    // tracing must not change the outcome of the transaction
+    assert_eq!(output, executor.execute_transaction(txn));
+
+    assert!(trace.iter().any(|event| match event {
+        TraceEvent::Instruction { function, .. } => function == "Script::main",
+        _ => false,
+    }));
+    assert!(trace.iter().any(|event| match event {
+        TraceEvent::NativeCall { .. } => true,
+        _ => false,
+    }));
+}
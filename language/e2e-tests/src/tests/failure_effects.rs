@@ -0,0 +1,185 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account::AccountData, common_transactions::peer_to_peer_txn, executor::FakeExecutor, gas_costs,
+};
+use compiled_stdlib::transaction_scripts::StdlibScript;
+use compiler::Compiler;
+use libra_types::{
+    account_config,
+    on_chain_config::VMPublishingOption,
+    transaction::{Script, SignedTransaction, TransactionArgument},
+};
+use libra_vm::effects_audit::{FailureClass, Phase};
+
+fn script_txn(sender: &AccountData, code: &str, max_gas_amount: u64) -> SignedTransaction {
+    let compiler = Compiler {
+        address: *sender.address(),
+        ..Compiler::default()
+    };
+    let blob = compiler
+        .into_script_blob("file_name", code)
+        .expect("script must compile");
+    sender.account().create_signed_txn_with_args(
+        blob,
+        vec![],
+        vec![],
+        sender.sequence_number(),
+        max_gas_amount,
+        1,
+        account_config::LBR_NAME.to_owned(),
+    )
+}
+
+#[test]
+fn effects_of_executed_transaction() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let sender = AccountData::new(1_000_000, 10);
+    let receiver = AccountData::new(100_000, 10);
+    executor.add_account_data(&sender);
+    executor.add_account_data(&receiver);
+
+    let txn = peer_to_peer_txn(sender.account(), receiver.account(), 10, 1_000);
+    let report = executor.audit_transaction_effects(txn);
+    assert_eq!(report.failure_class, FailureClass::None);
+    assert_eq!(
+        report.phases,
+        vec![Phase::Prologue, Phase::Script, Phase::SuccessEpilogue]
+    );
+    // sent and received payment events
+    assert_eq!(report.events.len(), 2);
+    assert!(!report.writes.is_empty());
+}
+
+#[test]
+fn effects_of_multi_agent_transaction() {
+    let mut executor = FakeExecutor::from_genesis_with_options(VMPublishingOption::open());
+    let sender = AccountData::new(1_000_000, 10);
+    let secondary_signer = AccountData::new(1_000_000, 10);
+    executor.add_account_data(&sender);
+    executor.add_account_data(&secondary_signer);
+
+    let blob = Compiler::default()
+        .into_script_blob(
+            "file_name",
+            "main(sender: &signer, secondary_signer: &signer) { return; }",
+        )
+        .expect("script must compile");
+    let txn = sender
+        .account()
+        .transaction()
+        .script(Script::new(blob, vec![], vec![]))
+        .secondary_signers(vec![secondary_signer.account().clone()])
+        .sequence_number(10)
+        .gas_currency_code(account_config::LBR_NAME)
+        .sign();
+    // the multi-agent prologue is reported like the single-agent one
+    let report = executor.audit_transaction_effects(txn);
+    assert_eq!(report.failure_class, FailureClass::None);
+    assert_eq!(
+        report.phases,
+        vec![Phase::Prologue, Phase::Script, Phase::SuccessEpilogue]
+    );
+}
+
+#[test]
+fn effects_of_discarded_transaction() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let sender = AccountData::new(1_000_000, 10);
+    let receiver = AccountData::new(100_000, 10);
+    executor.add_account_data(&sender);
+    executor.add_account_data(&receiver);
+
+    // the sequence number is too old, so the prologue rejects the transaction
+    let txn = peer_to_peer_txn(sender.account(), receiver.account(), 5, 1_000);
+    let report = executor.audit_transaction_effects(txn);
+    assert_eq!(report.failure_class, FailureClass::Discarded);
+    assert_eq!(report.phases, vec![Phase::Prologue]);
+    assert_eq!(report.gas_used, 0);
+    assert!(report.writes.is_empty());
+    assert!(report.events.is_empty());
+}
+
+#[test]
+fn effects_of_aborted_script() {
+    let mut executor = FakeExecutor::from_genesis_with_options(VMPublishingOption::open());
+    let sender = AccountData::new(1_000_000, 10);
+    executor.add_account_data(&sender);
+
+    let code = "
+    main() {
+        abort 77;
+    }
+    ";
+    let txn = script_txn(&sender, code, gas_costs::TXN_RESERVED);
+    let report = executor.audit_transaction_effects(txn);
+    assert_eq!(report.failure_class, FailureClass::Abort);
+    assert_eq!(
+        report.phases,
+        vec![Phase::Prologue, Phase::Script, Phase::FailureEpilogue]
+    );
+    // only the failure epilogue's charge for gas and sequence number bump persist
+    assert!(!report.writes.is_empty());
+    assert!(report.writes.iter().all(|write| !write.deletion));
+    assert!(report.events.is_empty());
+}
+
+#[test]
+fn effects_of_out_of_gas_script() {
+    let mut executor = FakeExecutor::from_genesis_with_options(VMPublishingOption::open());
+    let sender = AccountData::new(1_000_000, 10);
+    executor.add_account_data(&sender);
+
+    let code = "
+    main() {
+        while (true) {}
+        return;
+    }
+    ";
+    // every instruction executed is traced, so the loop must run out of gas early
+    let max_gas_amount = 1_000;
+    let txn = script_txn(&sender, code, max_gas_amount);
+    let report = executor.audit_transaction_effects(txn);
+    assert_eq!(report.failure_class, FailureClass::OutOfGas);
+    assert_eq!(
+        report.phases,
+        vec![Phase::Prologue, Phase::Script, Phase::FailureEpilogue]
+    );
+    // all the gas is charged
+    assert_eq!(report.gas_used, max_gas_amount);
+    assert!(!report.writes.is_empty());
+    assert!(report.events.is_empty());
+}
+
+#[test]
+fn effects_of_failed_success_epilogue() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let sender = AccountData::new(1_000_000, 10);
+    let receiver = AccountData::new(100_000, 10);
+    executor.add_account_data(&sender);
+    executor.add_account_data(&receiver);
+
+    // transferring the whole balance leaves nothing to pay for gas with
+    let txn = sender.account().create_signed_txn_with_args(
+        StdlibScript::PeerToPeerWithMetadata
+            .compiled_bytes()
+            .into_vec(),
+        vec![account_config::lbr_type_tag()],
+        vec![
+            TransactionArgument::Address(*receiver.address()),
+            TransactionArgument::U64(1_000_000),
+            TransactionArgument::U8Vector(vec![]),
+            TransactionArgument::U8Vector(vec![]),
+        ],
+        10,
+        gas_costs::TXN_RESERVED,
+        1,
+        account_config::LBR_NAME.to_owned(),
+    );
+    let report = executor.audit_transaction_effects(txn);
+    assert_eq!(report.failure_class, FailureClass::EpilogueFailure);
+    assert_eq!(report.phases.last(), Some(&Phase::FailureEpilogue));
+    // the payment events of the script are dropped with its writes
+    assert!(report.events.is_empty());
+}
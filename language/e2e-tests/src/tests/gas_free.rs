@@ -0,0 +1,64 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account::{self, AccountData},
+    executor::FakeExecutor,
+};
+use libra_types::{
+    account_config,
+    transaction::{SignedTransaction, TransactionStatus},
+    vm_status::{StatusCode, VMStatus},
+};
+use transaction_builder::encode_peer_to_peer_with_metadata_script;
+
+fn p2p_txn_without_gas(sender: &AccountData, receiver: &AccountData) -> SignedTransaction {
+    sender
+        .account()
+        .transaction()
+        .script(encode_peer_to_peer_with_metadata_script(
+            account_config::lbr_type_tag(),
+            *receiver.address(),
+            1_000,
+            vec![],
+            vec![],
+        ))
+        .sequence_number(10)
+        .max_gas_amount(0)
+        .gas_unit_price(1)
+        .sign()
+}
+
+#[test]
+fn gas_free_peer_to_peer() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let sender = AccountData::new(1_000_000, 10);
+    let receiver = AccountData::new(100_000, 10);
+    executor.add_account_data(&sender);
+    executor.add_account_data(&receiver);
+
+    // transactions are metered by default
+    let output = executor.execute_transaction(p2p_txn_without_gas(&sender, &receiver));
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Discard(VMStatus::Error(
+            StatusCode::MAX_GAS_UNITS_BELOW_MIN_TRANSACTION_GAS_UNITS
+        ))
+    );
+
+    executor.set_gas_free(true);
+    let output = executor.execute_and_apply(p2p_txn_without_gas(&sender, &receiver));
+    assert_eq!(output.gas_used(), 0);
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(VMStatus::Executed)
+    );
+    let sender_balance = executor
+        .read_balance_resource(sender.account(), account::lbr_currency_code())
+        .expect("sender balance must exist");
+    let receiver_balance = executor
+        .read_balance_resource(receiver.account(), account::lbr_currency_code())
+        .expect("receiver balance must exist");
+    assert_eq!(sender_balance.coin(), 1_000_000 - 1_000);
+    assert_eq!(receiver_balance.coin(), 100_000 + 1_000);
+}
@@ -3,16 +3,17 @@
 
 use crate::{
     account::{self, Account},
+    assert_move_abort,
     executor::FakeExecutor,
     gas_costs::TXN_RESERVED,
     keygen::KeyGen,
-    transaction_status_eq,
 };
 use libra_types::{
-    account_config,
-    transaction::TransactionStatus,
-    vm_status::{AbortLocation, StatusCode, VMStatus},
+    account_config::{self, CORE_CODE_ADDRESS},
+    transaction::TransactionOutput,
+    vm_status::{AbortLocation, StatusCode},
 };
+use move_core_types::{identifier::Identifier, language_storage::ModuleId};
 use transaction_builder::*;
 
 #[test]
@@ -88,11 +89,172 @@ fn tiered_mint_designated_dealer() {
         ),
         3,
     ));
-    // TODO(tmn) provide a real abort location
-    assert!(transaction_status_eq(
-        &output.status(),
-        &TransactionStatus::Keep(VMStatus::MoveAbort(AbortLocation::Script, 3)),
+    assert_move_abort(
+        output.status(),
+        &AbortLocation::Module(ModuleId::new(
+            CORE_CODE_ADDRESS,
+            Identifier::new("DesignatedDealer").unwrap(),
+        )),
+        "validate_and_record_mint",
+        3,
+    );
+}
+
+fn assert_designated_dealer_abort(output: &TransactionOutput, function: &str, code: u64) {
+    assert_move_abort(
+        output.status(),
+        &AbortLocation::Module(ModuleId::new(
+            CORE_CODE_ADDRESS,
+            Identifier::new("DesignatedDealer").unwrap(),
+        )),
+        function,
+        code,
+    );
+}
+
+#[test]
+fn designated_dealer_tier_management() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let blessed = Account::new_blessed_tc();
+    let mut keygen = KeyGen::from_seed([9u8; 32]);
+    let (_, pubkey) = keygen.generate_keypair();
+
+    // The DD starts with the default tiers [500_000, 5_000_000, 50_000_000, 500_000_000].
+    let dd = Account::new();
+    executor.execute_and_apply(blessed.signed_script_txn(
+        encode_create_designated_dealer_script(
+            account_config::coin1_tag(),
+            0,
+            *dd.address(),
+            dd.auth_key_prefix(),
+            vec![],
+            vec![],
+            pubkey.to_bytes().to_vec(),
+            false, // add_all_currencies
+        ),
+        0,
+    ));
+
+    // Removing tier 1 moves the tiers above it down: [500_000, 50_000_000, 500_000_000].
+    executor.execute_and_apply(blessed.signed_script_txn(
+        encode_remove_designated_dealer_tier_script(
+            account_config::coin1_tag(),
+            1,
+            *dd.address(),
+            1,
+        ),
+        1,
+    ));
+    let mint_amount = 10_000_000;
+    executor.execute_and_apply(blessed.signed_script_txn(
+        encode_tiered_mint_script(
+            account_config::coin1_tag(),
+            2,
+            *dd.address(),
+            mint_amount,
+            1,
+        ),
+        2,
+    ));
+    let dd_balance = executor
+        .read_balance_resource(&dd, account::coin1_currency_code())
+        .expect("receiver balance must exist");
+    assert_eq!(mint_amount, dd_balance.coin());
+
+    // There is no tier 3 anymore.
+    let output = executor.execute_transaction(blessed.signed_script_txn(
+        encode_tiered_mint_script(account_config::coin1_tag(), 3, *dd.address(), 1_000, 3),
+        3,
+    ));
+    assert_designated_dealer_abort(&output, "validate_and_record_mint", 3);
+
+    // Add a tier back: [500_000, 50_000_000, 500_000_000, 1_000_000_000].
+    executor.execute_and_apply(blessed.signed_script_txn(
+        encode_add_designated_dealer_tier_script(
+            account_config::coin1_tag(),
+            3,
+            *dd.address(),
+            1_000_000_000,
+        ),
+        3,
+    ));
+
+    // A DD has at most 4 tiers.
+    let output = executor.execute_transaction(blessed.signed_script_txn(
+        encode_add_designated_dealer_tier_script(
+            account_config::coin1_tag(),
+            4,
+            *dd.address(),
+            2_000_000_000,
+        ),
+        4,
+    ));
+    assert_designated_dealer_abort(&output, "add_tier", 1);
+
+    // Tiers cannot be reordered by an update.
+    let output = executor.execute_transaction(blessed.signed_script_txn(
+        encode_update_designated_dealer_tier_script(
+            account_config::coin1_tag(),
+            4,
+            *dd.address(),
+            0,
+            60_000_000,
+        ),
+        4,
+    ));
+    assert_designated_dealer_abort(&output, "update_tier", 2);
+
+    // Lowering tier 1 applies to the inflow already minted in the current window.
+    executor.execute_and_apply(blessed.signed_script_txn(
+        encode_update_designated_dealer_tier_script(
+            account_config::coin1_tag(),
+            4,
+            *dd.address(),
+            1,
+            20_000_000,
+        ),
+        4,
+    ));
+    let output = executor.execute_transaction(blessed.signed_script_txn(
+        encode_tiered_mint_script(account_config::coin1_tag(), 5, *dd.address(), 15_000_000, 1),
+        5,
+    ));
+    assert_designated_dealer_abort(&output, "validate_and_record_mint", 6);
+
+    // The last remaining tier cannot be removed.
+    for (i, tier_index) in [3, 2, 1].iter().enumerate() {
+        executor.execute_and_apply(blessed.signed_script_txn(
+            encode_remove_designated_dealer_tier_script(
+                account_config::coin1_tag(),
+                5 + i as u64,
+                *dd.address(),
+                *tier_index,
+            ),
+            5 + i as u64,
+        ));
+    }
+    let output = executor.execute_transaction(blessed.signed_script_txn(
+        encode_remove_designated_dealer_tier_script(
+            account_config::coin1_tag(),
+            8,
+            *dd.address(),
+            0,
+        ),
+        8,
+    ));
+    assert_designated_dealer_abort(&output, "remove_tier", 8);
+
+    // The DD does not have tiers for a currency it was not set up with.
+    let output = executor.execute_transaction(blessed.signed_script_txn(
+        encode_remove_designated_dealer_tier_script(
+            account_config::coin2_tag(),
+            8,
+            *dd.address(),
+            0,
+        ),
+        8,
     ));
+    assert_designated_dealer_abort(&output, "remove_tier", 7);
 }
 
 #[test]
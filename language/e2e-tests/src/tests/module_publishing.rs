@@ -65,9 +65,9 @@ fn bad_module_address() {
     assert!(status.status_code() == StatusCode::MODULE_ADDRESS_DOES_NOT_MATCH_SENDER);
 }
 
-// Publishing a module named M under the same address twice should be rejected
+// Republishing a module named M under the same address without changes is a valid upgrade
 #[test]
-fn duplicate_module() {
+fn republish_unchanged_module() {
     let mut executor = FakeExecutor::from_genesis_with_options(VMPublishingOption::open());
 
     let sequence_number = 2;
@@ -109,11 +109,72 @@ fn duplicate_module() {
         &TransactionStatus::Keep(VMStatus::Executed),
     ));
 
-    // second one should fail because it tries to re-publish a module named M
+    // second one should succeed as well since the module is unchanged
     let output2 = executor.execute_transaction(txn2);
     assert!(transaction_status_eq(
         &output2.status(),
-        &TransactionStatus::Keep(VMStatus::Error(StatusCode::DUPLICATE_MODULE_NAME)),
+        &TransactionStatus::Keep(VMStatus::Executed),
+    ));
+}
+
+// Upgrading a module in a way that changes the layout of a struct should be rejected
+#[test]
+fn incompatible_module_upgrade() {
+    let mut executor = FakeExecutor::from_genesis_with_options(VMPublishingOption::open());
+
+    let sequence_number = 2;
+    let account = AccountData::new(1_000_000, sequence_number);
+    executor.add_account_data(&account);
+
+    let program1 = String::from(
+        "
+        module M {
+            resource T { f: u64 }
+            public foo(): u64 { return 1; }
+        }
+        ",
+    );
+    let program2 = String::from(
+        "
+        module M {
+            resource T { f: bool }
+            public foo(): u64 { return 1; }
+        }
+        ",
+    );
+    let module1 = compile_module_with_address(account.address(), "file_name", &program1);
+    let module2 = compile_module_with_address(account.address(), "file_name", &program2);
+
+    let txn1 = account.account().create_signed_txn_impl(
+        *account.address(),
+        module1,
+        sequence_number,
+        100_000,
+        1,
+        LBR_NAME.to_owned(),
+    );
+    let txn2 = account.account().create_signed_txn_impl(
+        *account.address(),
+        module2,
+        sequence_number + 1,
+        100_000,
+        1,
+        LBR_NAME.to_owned(),
+    );
+
+    let output1 = executor.execute_transaction(txn1);
+    executor.apply_write_set(output1.write_set());
+    assert!(transaction_status_eq(
+        &output1.status(),
+        &TransactionStatus::Keep(VMStatus::Executed),
+    ));
+
+    let output2 = executor.execute_transaction(txn2);
+    assert!(transaction_status_eq(
+        &output2.status(),
+        &TransactionStatus::Keep(VMStatus::Error(
+            StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE
+        )),
     ));
 }
 
@@ -0,0 +1,262 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account::{
+        coin1_currency_code, coin2_currency_code, Account, AccountData, AccountRoleSpecifier,
+    },
+    assert_prologue_parity, assert_status_eq,
+    executor::FakeExecutor,
+    transaction_status_eq,
+};
+use compiler::Compiler;
+use libra_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
+use libra_types::{
+    account_config::{self, COIN1_NAME},
+    on_chain_config::VMPublishingOption,
+    transaction::{Module, Script, SignedTransaction, TransactionArgument, TransactionStatus},
+    vm_status::{StatusCode, VMStatus},
+};
+
+// Each party of the exchange pays the other one in a different currency, so that both payments
+// either happen or not.
+const EXCHANGE_SCRIPT: &str = "
+import 0x1.Coin1;
+import 0x1.Coin2;
+import 0x1.LibraAccount;
+import 0x1.Signer;
+
+main(alice: &signer, bob: &signer, coin1_amount: u64, coin2_amount: u64) {
+    let alice_address: address;
+    let bob_address: address;
+    let alice_cap: LibraAccount.WithdrawCapability;
+    let bob_cap: LibraAccount.WithdrawCapability;
+
+    alice_address = Signer.address_of(copy(alice));
+    bob_address = Signer.address_of(copy(bob));
+
+    alice_cap = LibraAccount.extract_withdraw_capability(move(alice));
+    LibraAccount.pay_from<Coin1.Coin1>(&alice_cap, move(bob_address), move(coin1_amount), h\"\", h\"\");
+    LibraAccount.restore_withdraw_capability(move(alice_cap));
+
+    bob_cap = LibraAccount.extract_withdraw_capability(move(bob));
+    LibraAccount.pay_from<Coin2.Coin2>(&bob_cap, move(alice_address), move(coin2_amount), h\"\", h\"\");
+    LibraAccount.restore_withdraw_capability(move(bob_cap));
+    return;
+}
+";
+
+fn compile_script(code: &str) -> Vec<u8> {
+    let compiler = Compiler {
+        address: account_config::CORE_CODE_ADDRESS,
+        extra_deps: vec![],
+        ..Compiler::default()
+    };
+    compiler
+        .into_script_blob("file_name", code)
+        .expect("Failed to compile")
+}
+
+/// Creates Alice, holding Coin1, and Bob, holding Coin2, each of them able to receive the
+/// currency of the other.
+fn create_parties(executor: &mut FakeExecutor) -> (AccountData, AccountData) {
+    let mut alice = AccountData::with_account(
+        Account::new(),
+        1_000,
+        coin1_currency_code(),
+        0,
+        AccountRoleSpecifier::ParentVASP,
+    );
+    alice.add_balance_currency(coin2_currency_code());
+    let mut bob = AccountData::with_account(
+        Account::new(),
+        2_000,
+        coin2_currency_code(),
+        0,
+        AccountRoleSpecifier::ParentVASP,
+    );
+    bob.add_balance_currency(coin1_currency_code());
+    executor.add_account_data(&alice);
+    executor.add_account_data(&bob);
+    (alice, bob)
+}
+
+fn exchange_txn(
+    sender: &Account,
+    secondary_signers: Vec<Account>,
+    coin1_amount: u64,
+    coin2_amount: u64,
+) -> SignedTransaction {
+    sender
+        .transaction()
+        .script(Script::new(
+            compile_script(EXCHANGE_SCRIPT),
+            vec![],
+            vec![
+                TransactionArgument::U64(coin1_amount),
+                TransactionArgument::U64(coin2_amount),
+            ],
+        ))
+        .secondary_signers(secondary_signers)
+        .sequence_number(0)
+        .gas_currency_code(COIN1_NAME)
+        .sign()
+}
+
+fn balance(executor: &FakeExecutor, account: &AccountData, coin1: bool) -> u64 {
+    let currency_code = if coin1 {
+        coin1_currency_code()
+    } else {
+        coin2_currency_code()
+    };
+    executor
+        .read_balance_resource(account.account(), currency_code)
+        .expect("balance must exist")
+        .coin()
+}
+
+#[test]
+fn multi_agent_exchange() {
+    let mut executor = FakeExecutor::from_genesis_with_options(VMPublishingOption::open());
+    let (alice, bob) = create_parties(&mut executor);
+
+    let txn = exchange_txn(alice.account(), vec![bob.account().clone()], 100, 200);
+    assert_eq!(txn.secondary_signer_addresses(), vec![*bob.address()]);
+    assert_eq!(executor.verify_transaction(txn.clone()).status(), None);
+    let output = executor.execute_and_apply(txn);
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(VMStatus::Executed)
+    );
+
+    assert_eq!(balance(&executor, &alice, true), 900);
+    assert_eq!(balance(&executor, &alice, false), 200);
+    assert_eq!(balance(&executor, &bob, true), 100);
+    assert_eq!(balance(&executor, &bob, false), 1_800);
+
+    // only the sender's sequence number is bumped
+    let sequence_number = |account: &AccountData| {
+        executor
+            .read_account_resource(account.account())
+            .expect("account must exist")
+            .sequence_number()
+    };
+    assert_eq!(sequence_number(&alice), 1);
+    assert_eq!(sequence_number(&bob), 0);
+}
+
+#[test]
+fn multi_agent_exchange_is_atomic() {
+    let mut executor = FakeExecutor::from_genesis_with_options(VMPublishingOption::open());
+    let (alice, bob) = create_parties(&mut executor);
+
+    // Bob cannot pay his part, so Alice does not pay hers either
+    let txn = exchange_txn(alice.account(), vec![bob.account().clone()], 100, 5_000);
+    let output = executor.execute_transaction(txn);
+    assert!(matches!(
+        output.status(),
+        TransactionStatus::Keep(VMStatus::MoveAbort(..))
+    ));
+    executor.apply_write_set(output.write_set());
+
+    assert_eq!(balance(&executor, &alice, true), 1_000);
+    assert_eq!(balance(&executor, &alice, false), 0);
+    assert_eq!(balance(&executor, &bob, true), 0);
+    assert_eq!(balance(&executor, &bob, false), 2_000);
+}
+
+#[test]
+fn multi_agent_requires_all_signers() {
+    let mut executor = FakeExecutor::from_genesis_with_options(VMPublishingOption::open());
+    let (alice, bob) = create_parties(&mut executor);
+
+    // a script taking two signers cannot run with the sender alone
+    let txn = exchange_txn(alice.account(), vec![], 100, 200);
+    let output = executor.execute_transaction(txn);
+    assert_eq!(
+        output.status().vm_status().status_code(),
+        StatusCode::NUMBER_OF_SIGNER_ARGUMENTS_MISMATCH
+    );
+
+    // the signature of a secondary signer over another transaction is rejected
+    let signed_for_other = exchange_txn(alice.account(), vec![bob.account().clone()], 1, 1);
+    let txn = exchange_txn(alice.account(), vec![bob.account().clone()], 100, 200);
+    let forged = SignedTransaction::new_multi_agent(
+        txn.clone().into_raw_transaction(),
+        txn.authenticator().sender().clone(),
+        txn.secondary_signer_addresses(),
+        signed_for_other.authenticator().secondary_signers(),
+    );
+    assert_prologue_parity!(
+        executor.verify_transaction(forged.clone()).status(),
+        executor.execute_transaction(forged).status(),
+        VMStatus::Error(StatusCode::INVALID_SIGNATURE)
+    );
+}
+
+#[test]
+fn multi_agent_checks_secondary_signers() {
+    let mut executor = FakeExecutor::from_genesis_with_options(VMPublishingOption::open());
+    let (alice, bob) = create_parties(&mut executor);
+
+    // the key of a secondary signer must match its account's authentication key
+    let mut impostor = bob.account().clone();
+    let private_key = Ed25519PrivateKey::generate_for_testing();
+    let public_key = private_key.public_key();
+    impostor.rotate_key(private_key, public_key);
+    let txn = exchange_txn(alice.account(), vec![impostor], 100, 200);
+    assert_prologue_parity!(
+        executor.verify_transaction(txn.clone()).status(),
+        executor.execute_transaction(txn).status(),
+        VMStatus::Error(StatusCode::INVALID_AUTH_KEY)
+    );
+
+    // the account of a secondary signer must exist
+    let txn = exchange_txn(alice.account(), vec![Account::new()], 100, 200);
+    assert_prologue_parity!(
+        executor.verify_transaction(txn.clone()).status(),
+        executor.execute_transaction(txn).status(),
+        VMStatus::Error(StatusCode::SECONDARY_SIGNER_ACCOUNT_DOES_NOT_EXIST)
+    );
+
+    // an account cannot sign twice
+    let txn = exchange_txn(alice.account(), vec![alice.account().clone()], 100, 200);
+    assert_prologue_parity!(
+        executor.verify_transaction(txn.clone()).status(),
+        executor.execute_transaction(txn).status(),
+        VMStatus::Error(StatusCode::SIGNERS_CONTAIN_DUPLICATES)
+    );
+}
+
+#[test]
+fn multi_agent_module_publishing_not_allowed() {
+    let mut executor = FakeExecutor::from_genesis_with_options(VMPublishingOption::open());
+    let (alice, bob) = create_parties(&mut executor);
+
+    let module_code = "
+    module M {
+        public f() { return; }
+    }
+    ";
+    let compiler = Compiler {
+        address: *alice.address(),
+        skip_stdlib_deps: true,
+        ..Compiler::default()
+    };
+    let module = compiler
+        .into_module_blob("file_name", module_code)
+        .expect("Module compilation failed");
+    let txn = alice
+        .account()
+        .transaction()
+        .module(Module::new(module))
+        .secondary_signers(vec![bob.account().clone()])
+        .sequence_number(0)
+        .gas_currency_code(COIN1_NAME)
+        .sign();
+    assert_prologue_parity!(
+        executor.verify_transaction(txn.clone()).status(),
+        executor.execute_transaction(txn).status(),
+        VMStatus::Error(StatusCode::SECONDARY_SIGNERS_NOT_ALLOWED)
+    );
+}
@@ -3,19 +3,25 @@
 
 use crate::{
     account::{self, Account, AccountData},
-    common_transactions::peer_to_peer_txn,
+    common_transactions::{
+        add_to_script_allow_list_txn, peer_to_peer_txn, remove_from_script_allow_list_txn,
+        ADD_TO_SCRIPT_ALLOW_LIST_SCRIPT, REMOVE_FROM_SCRIPT_ALLOW_LIST_SCRIPT,
+    },
     executor::FakeExecutor,
     gas_costs::TXN_RESERVED,
     transaction_status_eq,
 };
-use compiled_stdlib::transaction_scripts::StdlibScript;
+use compiled_stdlib::{stdlib_modules, transaction_scripts::StdlibScript, StdLibOptions};
+use compiler::Compiler;
+use libra_crypto::HashValue;
 use libra_types::{
     account_config::LBR_NAME,
-    on_chain_config::LibraVersion,
-    transaction::{TransactionArgument, TransactionStatus},
-    vm_status::VMStatus,
+    on_chain_config::{LibraVersion, OnChainConfig, VMConfig, VMPublishingOption},
+    transaction::{SignedTransaction, TransactionArgument, TransactionStatus},
+    vm_status::{StatusCode, VMStatus},
 };
-use libra_vm::LibraVM;
+use libra_vm::{LibraVM, VERIFIER_LIMITS_MAJOR_VERSION};
+use move_core_types::gas_schedule::{GasAlgebra, GasCost};
 use transaction_builder::encode_update_dual_attestation_limit_script;
 
 #[test]
@@ -48,6 +54,67 @@ fn initial_libra_version() {
     );
 }
 
+#[test]
+fn verifier_limits_enabled_by_version() {
+    let mut executor = FakeExecutor::from_genesis_with_options(VMPublishingOption::open());
+    let sender = AccountData::new(1_000_000, 10);
+    executor.add_account_data(&sender);
+
+    // a local nested deeper than the verifier accepts once the limits are enforced
+    let nested_type = (0..64).fold("u8".to_string(), |ty, _| format!("vector<{}>", ty));
+    let code = format!(
+        "
+    main() {{
+        let v: {};
+        return;
+    }}
+    ",
+        nested_type
+    );
+    let script = Compiler {
+        address: *sender.address(),
+        ..Compiler::default()
+    }
+    .into_script_blob("file_name", &code)
+    .expect("Script compilation failed");
+    let script_txn = |seq_num| {
+        sender.account().create_signed_txn_with_args(
+            script.clone(),
+            vec![],
+            vec![],
+            seq_num,
+            TXN_RESERVED,
+            0,
+            LBR_NAME.to_owned(),
+        )
+    };
+
+    // validators of the genesis version do not enforce the limits
+    let output = executor.execute_and_apply(script_txn(10));
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(VMStatus::Executed)
+    );
+
+    let account = Account::new_genesis_account(libra_types::on_chain_config::config_address());
+    executor.new_block();
+    executor.execute_and_apply(account.create_signed_txn_with_args(
+        StdlibScript::UpdateLibraVersion.compiled_bytes().into_vec(),
+        vec![],
+        vec![TransactionArgument::U64(VERIFIER_LIMITS_MAJOR_VERSION)],
+        1,
+        TXN_RESERVED,
+        0,
+        LBR_NAME.to_owned(),
+    ));
+
+    let output = executor.execute_transaction(script_txn(11));
+    assert_eq!(
+        output.status().vm_status().status_code(),
+        StatusCode::TOO_DEEPLY_NESTED_TYPE
+    );
+}
+
 #[test]
 fn drop_txn_after_reconfiguration() {
     let mut executor = FakeExecutor::from_genesis_file();
@@ -118,3 +185,194 @@ fn updated_limit_allows_txn() {
     assert_eq!(3_999_990, sender_balance.coin());
     assert_eq!(1_000_010, receiver_balance.coin());
 }
+
+fn update_gas_schedule_txn(
+    version: u64,
+    scale: u64,
+    executor: &FakeExecutor,
+    seq_num: u64,
+) -> SignedTransaction {
+    let scale_costs = |costs: &[GasCost]| {
+        let costs = costs
+            .iter()
+            .map(|cost| {
+                GasCost::new(
+                    cost.instruction_gas.get() * scale,
+                    cost.memory_gas.get() * scale,
+                )
+            })
+            .collect::<Vec<_>>();
+        lcs::to_bytes(&costs).unwrap()
+    };
+    let vm = LibraVM::new(executor.get_state_view());
+    let gas_schedule = vm.internals().gas_schedule().unwrap().clone();
+
+    let account = Account::new_genesis_account(libra_types::on_chain_config::config_address());
+    account.create_signed_txn_with_args(
+        StdlibScript::UpdateGasSchedule.compiled_bytes().into_vec(),
+        vec![],
+        vec![
+            TransactionArgument::U64(version),
+            TransactionArgument::U8Vector(scale_costs(&gas_schedule.instruction_table)),
+            TransactionArgument::U8Vector(scale_costs(&gas_schedule.native_table)),
+        ],
+        seq_num,
+        TXN_RESERVED,
+        0,
+        LBR_NAME.to_owned(),
+    )
+}
+
+#[test]
+fn update_gas_schedule() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let sender = AccountData::new(1_000_000, 10);
+    let receiver = AccountData::new(100_000, 10);
+    executor.add_account_data(&sender);
+    executor.add_account_data(&receiver);
+
+    let vm_config = VMConfig::fetch_config(executor.get_state_view()).unwrap();
+    assert_eq!(vm_config.gas_schedule_version, 0);
+    let txn = peer_to_peer_txn(sender.account(), receiver.account(), 10, 1_000);
+    let gas_used_before = executor.execute_transaction(txn.clone()).gas_used();
+
+    executor.new_block();
+    let output = executor.execute_and_apply(update_gas_schedule_txn(1, 2, &executor, 1));
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(VMStatus::Executed)
+    );
+
+    // the next VM picks up the new schedule from storage, nothing has to be restarted
+    let vm = LibraVM::new(executor.get_state_view());
+    assert_eq!(vm.internals().gas_schedule_version().unwrap(), 1);
+    let gas_used_after = executor.execute_transaction(txn).gas_used();
+    assert!(gas_used_after > gas_used_before);
+
+    // an update which does not increase the version is rejected
+    executor.new_block();
+    let output = executor.execute_transaction(update_gas_schedule_txn(1, 3, &executor, 2));
+    assert_eq!(
+        output.status().vm_status().status_code(),
+        StatusCode::ABORTED
+    );
+    assert_eq!(output.status().vm_status().move_abort_code(), Some(0));
+}
+
+fn allow_list_executor() -> FakeExecutor {
+    // the scripts updating the allow list must be allowed themselves
+    let mut allow_list = StdlibScript::whitelist();
+    allow_list.push(*HashValue::sha3_256_of(&ADD_TO_SCRIPT_ALLOW_LIST_SCRIPT).as_ref());
+    allow_list.push(*HashValue::sha3_256_of(&REMOVE_FROM_SCRIPT_ALLOW_LIST_SCRIPT).as_ref());
+    FakeExecutor::custom_genesis(
+        stdlib_modules(StdLibOptions::Compiled).to_vec(),
+        None,
+        VMPublishingOption::locked(allow_list),
+    )
+}
+
+fn custom_script() -> Vec<u8> {
+    Compiler::default()
+        .into_script_blob("file_name", "main() { return; }")
+        .expect("Script compilation failed")
+}
+
+fn custom_script_txn(sender: &AccountData, script: Vec<u8>) -> SignedTransaction {
+    sender.account().create_signed_txn_with_args(
+        script,
+        vec![],
+        vec![],
+        sender.sequence_number(),
+        TXN_RESERVED,
+        0,
+        LBR_NAME.to_owned(),
+    )
+}
+
+#[test]
+fn update_script_allow_list() {
+    let mut executor = allow_list_executor();
+    let sender = AccountData::new(1_000_000, 10);
+    executor.add_account_data(&sender);
+    let libra_root = Account::new_libra_root();
+
+    let script = custom_script();
+    let hash = HashValue::sha3_256_of(&script);
+    let txn = custom_script_txn(&sender, script);
+    assert_eq!(
+        executor.verify_transaction(txn.clone()).status(),
+        Some(VMStatus::Error(StatusCode::UNKNOWN_SCRIPT))
+    );
+
+    // the libra root account allows the script, which the next VM accepts
+    executor.new_block();
+    let output =
+        executor.execute_and_apply(add_to_script_allow_list_txn(&libra_root, hash.to_vec(), 1));
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(VMStatus::Executed)
+    );
+    let publishing_option = VMPublishingOption::fetch_config(executor.get_state_view()).unwrap();
+    assert!(publishing_option.script_allow_list.contains(&hash));
+    assert_eq!(executor.verify_transaction(txn.clone()).status(), None);
+    assert_eq!(
+        executor.execute_transaction(txn.clone()).status(),
+        &TransactionStatus::Keep(VMStatus::Executed)
+    );
+
+    // adding the same script twice is rejected
+    executor.new_block();
+    let output =
+        executor.execute_transaction(add_to_script_allow_list_txn(&libra_root, hash.to_vec(), 2));
+    assert_eq!(output.status().vm_status().move_abort_code(), Some(3));
+
+    // once removed, the script is rejected again
+    let output = executor.execute_and_apply(remove_from_script_allow_list_txn(
+        &libra_root,
+        hash.to_vec(),
+        2,
+    ));
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(VMStatus::Executed)
+    );
+    assert_eq!(
+        executor.verify_transaction(txn.clone()).status(),
+        Some(VMStatus::Error(StatusCode::UNKNOWN_SCRIPT))
+    );
+    assert_eq!(
+        executor.execute_transaction(txn).status(),
+        &TransactionStatus::Discard(VMStatus::Error(StatusCode::UNKNOWN_SCRIPT))
+    );
+}
+
+#[test]
+fn update_script_allow_list_requires_libra_root() {
+    let mut executor = allow_list_executor();
+    let sender = AccountData::new(1_000_000, 10);
+    executor.add_account_data(&sender);
+
+    executor.new_block();
+    let hash = HashValue::sha3_256_of(&[]);
+    let output = executor.execute_transaction(add_to_script_allow_list_txn(
+        sender.account(),
+        hash.to_vec(),
+        10,
+    ));
+    assert_eq!(
+        output.status().vm_status().status_code(),
+        StatusCode::ABORTED
+    );
+    assert_eq!(output.status().vm_status().move_abort_code(), Some(1));
+}
+
+#[test]
+fn update_script_allow_list_rejects_invalid_hash() {
+    let mut executor = allow_list_executor();
+    let libra_root = Account::new_libra_root();
+
+    executor.new_block();
+    let output =
+        executor.execute_transaction(add_to_script_allow_list_txn(&libra_root, vec![0; 4], 1));
+    assert_eq!(output.status().vm_status().move_abort_code(), Some(2));
+}
@@ -3,11 +3,13 @@
 
 use crate::{
     account::{self, Account, AccountData},
+    assert_move_abort,
     common_transactions::peer_to_peer_txn,
     executor::FakeExecutor,
-    gas_costs, transaction_status_eq,
+    gas_costs,
 };
 use compiled_stdlib::transaction_scripts::StdlibScript;
+use libra_json_rpc_types::views::SimulatedTransactionView;
 use libra_types::{
     account_config::{self, ReceivedPaymentEvent, SentPaymentEvent, LBR_NAME},
     on_chain_config::VMPublishingOption,
@@ -15,8 +17,9 @@ use libra_types::{
         Script, SignedTransaction, TransactionArgument, TransactionOutput, TransactionPayload,
         TransactionStatus,
     },
-    vm_status::{AbortLocation, VMStatus},
+    vm_status::{AbortLocation, ErrorReason, StatusCode, VMStatus},
 };
+use move_core_types::vm_status::LIBRA_ACCOUNT_EINSUFFICIENT_BALANCE;
 use std::{convert::TryFrom, time::Instant};
 use vm::file_format::{Bytecode, CompiledScript};
 
@@ -248,12 +251,48 @@ fn zero_amount_peer_to_peer() {
     );
 
     let output = &executor.execute_transaction(txn);
-    // Error code 7 means that the transaction was a zero-amount one.
-    // TODO(tmn) provide a real abort location
-    assert!(transaction_status_eq(
-        &output.status(),
-        &TransactionStatus::Keep(VMStatus::MoveAbort(AbortLocation::Script, 2)),
-    ));
+    // Error code 2 means that the transaction was a zero-amount one.
+    assert_move_abort(
+        output.status(),
+        &AbortLocation::Module(account_config::ACCOUNT_MODULE.clone()),
+        "deposit",
+        2,
+    );
+}
+
+/// Test that a transfer exceeding the balance of the sender is reported to clients as such.
+#[test]
+fn overdraft_peer_to_peer() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let sequence_number = 10;
+    let sender = AccountData::new(1_000_000, sequence_number);
+    let receiver = AccountData::new(100_000, sequence_number);
+    executor.add_account_data(&sender);
+    executor.add_account_data(&receiver);
+
+    let transfer_amount = 2_000_000;
+    let txn = peer_to_peer_txn(
+        sender.account(),
+        receiver.account(),
+        sequence_number,
+        transfer_amount,
+    );
+
+    let output = executor.execute_transaction(txn);
+    assert_move_abort(
+        output.status(),
+        &AbortLocation::Module(account_config::ACCOUNT_MODULE.clone()),
+        "withdraw_from_balance",
+        LIBRA_ACCOUNT_EINSUFFICIENT_BALANCE,
+    );
+
+    let view = SimulatedTransactionView::new(0, output);
+    assert_eq!(view.vm_status, StatusCode::ABORTED);
+    assert_eq!(view.error_reason, Some(ErrorReason::InsufficientBalance));
+    assert_eq!(
+        serde_json::to_value(&view).unwrap()["error_reason"],
+        "insufficient_balance"
+    );
 }
 
 // Holder for transaction data; arguments to transactions.
@@ -0,0 +1,188 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account::Account,
+    assert_move_abort,
+    common_transactions::{create_child_vasp_txn, create_parent_vasp_txn, rotate_key_txn},
+    executor::FakeExecutor,
+    keygen::KeyGen,
+};
+use libra_json_rpc_types::views::{AccountView, BytesView};
+use libra_types::{
+    account_config::{self, CORE_CODE_ADDRESS},
+    transaction::{authenticator::AuthenticationKey, TransactionOutput},
+    vm_status::AbortLocation,
+};
+use move_core_types::{identifier::Identifier, language_storage::ModuleId};
+use transaction_builder::{
+    encode_add_recovery_rotation_capability_script, encode_create_recovery_address_script,
+    encode_rotate_authentication_key_with_recovery_address_script,
+};
+
+fn assert_recovery_address_abort(output: &TransactionOutput, function: &str, code: u64) {
+    assert_move_abort(
+        output.status(),
+        &AbortLocation::Module(ModuleId::new(
+            CORE_CODE_ADDRESS,
+            Identifier::new("RecoveryAddress").unwrap(),
+        )),
+        function,
+        code,
+    );
+}
+
+fn account_view(executor: &FakeExecutor, account: &Account) -> AccountView {
+    AccountView::try_from_account_state(&executor.read_account_state(account), &[])
+        .unwrap()
+        .expect("account view must exist")
+}
+
+#[test]
+fn recover_lost_key_of_child_vasp() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let libra_root = Account::new_libra_root();
+    let mut keygen = KeyGen::from_seed([9u8; 32]);
+    let (_, compliance_public_key) = keygen.generate_keypair();
+
+    let parent = Account::new();
+    let mut child = Account::new();
+    let other_child = Account::new();
+    executor.execute_and_apply(create_parent_vasp_txn(
+        &libra_root,
+        &parent,
+        account_config::coin1_tag(),
+        compliance_public_key.to_bytes().to_vec(),
+        1,
+    ));
+    executor.execute_and_apply(create_child_vasp_txn(
+        &parent,
+        &child,
+        account_config::coin1_tag(),
+        0,
+        0,
+    ));
+    executor.execute_and_apply(create_child_vasp_txn(
+        &parent,
+        &other_child,
+        account_config::coin1_tag(),
+        0,
+        1,
+    ));
+
+    // The parent becomes the recovery account of the child.
+    executor
+        .execute_and_apply(parent.signed_script_txn(encode_create_recovery_address_script(), 2));
+    executor.execute_and_apply(child.signed_script_txn(
+        encode_add_recovery_rotation_capability_script(*parent.address()),
+        0,
+    ));
+
+    let parent_view = account_view(&executor, &parent);
+    assert_eq!(
+        parent_view.recoverable_addresses,
+        vec![
+            BytesView::from(parent.address().as_ref()),
+            BytesView::from(child.address().as_ref()),
+        ]
+    );
+    assert!(parent_view.delegated_key_rotation_capability);
+    assert!(account_view(&executor, &child).delegated_key_rotation_capability);
+    assert!(account_view(&executor, &other_child)
+        .recoverable_addresses
+        .is_empty());
+
+    // The child lost its key: the recovery account rotates it to a new one.
+    let (new_private_key, new_public_key) = keygen.generate_keypair();
+    let new_key = AuthenticationKey::ed25519(&new_public_key).to_vec();
+    executor.execute_and_apply(parent.signed_script_txn(
+        encode_rotate_authentication_key_with_recovery_address_script(
+            *parent.address(),
+            *child.address(),
+            new_key.clone(),
+        ),
+        3,
+    ));
+    assert_eq!(
+        executor
+            .read_account_resource(&child)
+            .unwrap()
+            .authentication_key(),
+        new_key.as_slice()
+    );
+
+    // The child can send transactions with its new key. Since its key rotation capability is
+    // held by the recovery account, it cannot rotate the key by itself.
+    child.rotate_key(new_private_key, new_public_key);
+    let (_, public_key) = keygen.generate_keypair();
+    let output = executor.execute_transaction(rotate_key_txn(
+        &child,
+        AuthenticationKey::ed25519(&public_key).to_vec(),
+        1,
+    ));
+    assert_move_abort(
+        output.status(),
+        &AbortLocation::Module(account_config::ACCOUNT_MODULE.clone()),
+        "extract_key_rotation_capability",
+        9,
+    );
+
+    // Neither the recovery account nor the account itself can rotate the key of an account
+    // that did not register with the recovery account.
+    let output = executor.execute_transaction(other_child.signed_script_txn(
+        encode_rotate_authentication_key_with_recovery_address_script(
+            *parent.address(),
+            *other_child.address(),
+            new_key.clone(),
+        ),
+        0,
+    ));
+    assert_recovery_address_abort(&output, "rotate_authentication_key", 4);
+
+    // Only the recovery account and the account itself can rotate its key.
+    let output = executor.execute_transaction(other_child.signed_script_txn(
+        encode_rotate_authentication_key_with_recovery_address_script(
+            *parent.address(),
+            *child.address(),
+            new_key,
+        ),
+        0,
+    ));
+    assert_recovery_address_abort(&output, "rotate_authentication_key", 2);
+}
+
+#[test]
+fn recovery_address_permissions() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let libra_root = Account::new_libra_root();
+    let dd = Account::new_genesis_account(account_config::testnet_dd_account_address());
+    let mut keygen = KeyGen::from_seed([9u8; 32]);
+    let (_, compliance_public_key) = keygen.generate_keypair();
+
+    // Only VASPs can publish a recovery address.
+    let output = executor
+        .execute_transaction(dd.signed_script_txn(encode_create_recovery_address_script(), 0));
+    assert_recovery_address_abort(&output, "publish", 0);
+
+    let parent = Account::new();
+    let other_vasp = Account::new();
+    for (seq_num, vasp) in [&parent, &other_vasp].iter().enumerate() {
+        executor.execute_and_apply(create_parent_vasp_txn(
+            &libra_root,
+            vasp,
+            account_config::coin1_tag(),
+            compliance_public_key.to_bytes().to_vec(),
+            seq_num as u64 + 1,
+        ));
+    }
+    executor
+        .execute_and_apply(parent.signed_script_txn(encode_create_recovery_address_script(), 0));
+
+    // Accounts of another VASP cannot delegate their key rotation capability to the recovery
+    // account.
+    let output = executor.execute_transaction(other_vasp.signed_script_txn(
+        encode_add_recovery_rotation_capability_script(*parent.address()),
+        0,
+    ));
+    assert_recovery_address_abort(&output, "add_rotation_capability", 3);
+}
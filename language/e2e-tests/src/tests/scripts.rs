@@ -2,9 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{account, account::AccountData, executor::FakeExecutor, gas_costs};
+use compiler::Compiler;
 use libra_types::{
-    account_address::AccountAddress, account_config, on_chain_config::VMPublishingOption,
-    transaction::TransactionStatus, vm_status::StatusCode,
+    account_address::AccountAddress,
+    account_config,
+    on_chain_config::VMPublishingOption,
+    transaction::{TransactionArgument, TransactionStatus},
+    vm_status::{StatusCode, VMStatus},
 };
 use move_core_types::identifier::Identifier;
 use vm::file_format::{
@@ -277,3 +281,93 @@ fn script_bad_sig_function_dep() {
     assert_eq!(balance, updated_sender_balance.coin());
     assert_eq!(11, updated_sender.sequence_number());
 }
+
+#[test]
+fn script_vector_of_vectors_argument() {
+    let mut executor = FakeExecutor::from_genesis_with_options(VMPublishingOption::open());
+    let sender = AccountData::new(1_000_000, 10);
+    executor.add_account_data(&sender);
+
+    let code = "
+    import 0x1.Vector;
+
+    main(v: vector<vector<u8>>) {
+        assert(Vector.length<vector<u8>>(&v) == 2, 42);
+        assert(*Vector.borrow<u8>(Vector.borrow<vector<u8>>(&v, 1), 1) == 3u8, 43);
+        return;
+    }
+    ";
+    let compiler = Compiler {
+        address: *sender.address(),
+        ..Compiler::default()
+    };
+    let blob = compiler
+        .into_script_blob("file_name", code)
+        .expect("script must compile");
+
+    let txn = sender.account().create_signed_txn_with_args(
+        blob.clone(),
+        vec![],
+        vec![TransactionArgument::U8VectorVector(vec![
+            vec![1],
+            vec![2, 3],
+        ])],
+        10,
+        gas_costs::TXN_RESERVED,
+        1,
+        account_config::LBR_NAME.to_owned(),
+    );
+    let output = executor.execute_and_apply(txn);
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(VMStatus::Executed)
+    );
+
+    let txn = sender.account().create_signed_txn_with_args(
+        blob,
+        vec![],
+        vec![TransactionArgument::U8VectorVector(vec![vec![1]])],
+        11,
+        gas_costs::TXN_RESERVED,
+        1,
+        account_config::LBR_NAME.to_owned(),
+    );
+    let output = executor.execute_transaction(txn);
+    assert_eq!(output.status().vm_status().move_abort_code(), Some(42));
+}
+
+#[test]
+fn script_signers_mismatch() {
+    let mut executor = FakeExecutor::from_genesis_with_options(VMPublishingOption::open());
+    let sender = AccountData::new(1_000_000, 10);
+    executor.add_account_data(&sender);
+
+    // a transaction only has one signer, the sender
+    let code = "
+    main(s1: &signer, s2: &signer) {
+        return;
+    }
+    ";
+    let compiler = Compiler {
+        address: *sender.address(),
+        ..Compiler::default()
+    };
+    let blob = compiler
+        .into_script_blob("file_name", code)
+        .expect("script must compile");
+
+    let txn = sender.account().create_signed_txn_with_args(
+        blob,
+        vec![],
+        vec![],
+        10,
+        gas_costs::TXN_RESERVED,
+        1,
+        account_config::LBR_NAME.to_owned(),
+    );
+    let output = executor.execute_transaction(txn);
+    assert_eq!(
+        output.status().vm_status().status_code(),
+        StatusCode::NUMBER_OF_SIGNER_ARGUMENTS_MISMATCH
+    );
+}
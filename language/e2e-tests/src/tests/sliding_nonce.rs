@@ -0,0 +1,79 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{account::Account, assert_move_abort, executor::FakeExecutor};
+use libra_json_rpc_types::views::{AccountView, BytesView};
+use libra_types::{
+    account_config::{self, SlidingNonceResource, CORE_CODE_ADDRESS},
+    transaction::SignedTransaction,
+    vm_status::AbortLocation,
+};
+use move_core_types::{identifier::Identifier, language_storage::ModuleId};
+use transaction_builder::encode_update_exchange_rate_script;
+
+fn sliding_nonce(executor: &FakeExecutor, account: &Account) -> SlidingNonceResource {
+    executor
+        .read_account_state(account)
+        .get_sliding_nonce_resource()
+        .unwrap()
+        .expect("sliding nonce must exist")
+}
+
+fn update_exchange_rate_txn(
+    sender: &Account,
+    sliding_nonce: u64,
+    seq_num: u64,
+) -> SignedTransaction {
+    sender.signed_script_txn(
+        encode_update_exchange_rate_script(account_config::coin1_tag(), sliding_nonce, 1, 2),
+        seq_num,
+    )
+}
+
+#[test]
+fn next_sliding_nonce() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let blessed = Account::new_blessed_tc();
+    assert_eq!(sliding_nonce(&executor, &blessed).next_nonce(), 1);
+
+    executor.execute_and_apply(update_exchange_rate_txn(&blessed, 1, 0));
+    executor.execute_and_apply(update_exchange_rate_txn(&blessed, 3, 1));
+    let nonce = sliding_nonce(&executor, &blessed);
+    assert!(nonce.is_used(1));
+    assert!(!nonce.is_used(2));
+    assert!(nonce.is_used(3));
+    assert_eq!(nonce.next_nonce(), 4);
+
+    let view = AccountView::try_from_account_state(&executor.read_account_state(&blessed), &[])
+        .unwrap()
+        .expect("account view must exist")
+        .sliding_nonce
+        .expect("sliding nonce view must exist");
+    assert_eq!(view.min_nonce, 0);
+    assert_eq!(view.next_nonce, 4);
+    assert_eq!(
+        view.nonce_mask,
+        BytesView::from(&0b1010u128.to_be_bytes()[..])
+    );
+
+    // A recorded nonce is rejected, while the next nonce is accepted.
+    let output = executor.execute_transaction(update_exchange_rate_txn(&blessed, 3, 2));
+    assert_move_abort(
+        output.status(),
+        &AbortLocation::Module(ModuleId::new(
+            CORE_CODE_ADDRESS,
+            Identifier::new("SlidingNonce").unwrap(),
+        )),
+        "record_nonce_or_abort",
+        3,
+    );
+    executor.execute_and_apply(update_exchange_rate_txn(&blessed, 4, 2));
+    assert_eq!(sliding_nonce(&executor, &blessed).next_nonce(), 5);
+
+    // Nonces far enough ahead slide the window past the older ones.
+    executor.execute_and_apply(update_exchange_rate_txn(&blessed, 200, 3));
+    let nonce = sliding_nonce(&executor, &blessed);
+    assert_eq!(nonce.min_nonce(), 73);
+    assert!(nonce.is_used(4));
+    assert_eq!(nonce.next_nonce(), 201);
+}
@@ -1,11 +1,19 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{account::Account, executor::FakeExecutor, gas_costs};
+use crate::{
+    account::{self, Account},
+    common_transactions::{
+        add_validator_txn, create_validator_account_txn, reconfigure_txn, set_validator_config_txn,
+        EMPTY_SCRIPT,
+    },
+    executor::FakeExecutor,
+    gas_costs,
+};
 use compiled_stdlib::transaction_scripts::StdlibScript;
 use libra_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
 use libra_types::{
-    account_config::{self, BurnEvent, COIN1_NAME},
+    account_config::{self, BurnEvent, ReceivedPaymentEvent, COIN1_NAME},
     transaction::{authenticator::AuthenticationKey, TransactionArgument},
     vm_status::StatusCode,
 };
@@ -15,7 +23,8 @@ use move_core_types::{
 };
 use std::convert::TryFrom;
 use transaction_builder::{
-    encode_burn_txn_fees_script, encode_create_testing_account_script, encode_testnet_mint_script,
+    encode_add_currency_to_account_script, encode_burn_txn_fees_script,
+    encode_create_testing_account_script, encode_testnet_mint_script,
 };
 
 #[test]
@@ -90,3 +99,112 @@ fn burn_txn_fees() {
         .iter()
         .for_each(|event| assert_eq!(event.amount(), gas_used));
 }
+
+fn coin1_balance(executor: &FakeExecutor, account: &Account) -> u64 {
+    executor
+        .read_balance_resource(account, account::coin1_currency_code())
+        .expect("account must hold a Coin1 balance")
+        .coin()
+}
+
+#[test]
+fn distribute_txn_fees_at_reconfiguration() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let libra_root = Account::new_libra_root();
+    let dd = Account::new_genesis_account(account_config::testnet_dd_account_address());
+    let validator = Account::new();
+    let sender = Account::new();
+
+    // Add a validator accepting Coin1, and start an epoch it is part of.
+    executor.execute_and_apply(create_validator_account_txn(&libra_root, &validator, 1));
+    executor.new_block();
+    let consensus_pubkey = vec![
+        0xd7, 0x5a, 0x98, 0x01, 0x82, 0xb1, 0x0a, 0xb7, 0xd5, 0x4b, 0xfe, 0xd3, 0xc9, 0x64, 0x07,
+        0x3a, 0x0e, 0xe1, 0x72, 0xf3, 0xda, 0xa6, 0x23, 0x25, 0xaf, 0x02, 0x1a, 0x68, 0xf7, 0x07,
+        0x51, 0x1a,
+    ];
+    executor.execute_and_apply(set_validator_config_txn(
+        &validator,
+        &validator,
+        consensus_pubkey.clone(),
+        vec![254; 32],
+        vec![],
+        vec![253; 32],
+        vec![],
+        0,
+    ));
+    executor.execute_and_apply(validator.signed_script_txn(
+        encode_add_currency_to_account_script(account_config::coin1_tag()),
+        1,
+    ));
+    executor.execute_and_apply(add_validator_txn(&libra_root, &validator, 2));
+    executor.new_block();
+
+    // Collect fees in Coin1 during the epoch.
+    executor.execute_and_apply(libra_root.signed_script_txn(
+        encode_create_testing_account_script(
+            account_config::coin1_tag(),
+            *sender.address(),
+            sender.auth_key_prefix(),
+            false,
+        ),
+        3,
+    ));
+    executor.execute_and_apply(dd.signed_script_txn(
+        encode_testnet_mint_script(account_config::coin1_tag(), *sender.address(), 10_000_000),
+        0,
+    ));
+    let gas_used = executor
+        .execute_and_apply(sender.create_signed_txn_with_args(
+            EMPTY_SCRIPT.to_vec(),
+            vec![account_config::coin1_tag()],
+            vec![],
+            0,
+            gas_costs::TXN_RESERVED,
+            1,
+            COIN1_NAME.to_owned(),
+        ))
+        .gas_used();
+    assert!(gas_used > 0);
+
+    // The fees are not distributed before the end of the epoch.
+    executor.new_block();
+    assert_eq!(coin1_balance(&executor, &validator), 0);
+
+    // The first block of the next epoch pays the fees to the validators of the previous epoch
+    // accepting Coin1. Reconfiguring only starts a new epoch if a validator config changed.
+    executor.execute_and_apply(set_validator_config_txn(
+        &validator,
+        &validator,
+        consensus_pubkey,
+        vec![252; 32],
+        vec![],
+        vec![253; 32],
+        vec![],
+        2,
+    ));
+    executor.execute_and_apply(reconfigure_txn(&libra_root, 4));
+    let output = executor.new_block();
+    assert_eq!(coin1_balance(&executor, &validator), gas_used);
+
+    let received_events: Vec<_> = output
+        .events()
+        .iter()
+        .filter(|event| event.key().get_creator_address() == *validator.address())
+        .filter_map(|event| ReceivedPaymentEvent::try_from_bytes(event.event_data()).ok())
+        .collect();
+    assert_eq!(received_events.len(), 1);
+    assert_eq!(received_events[0].amount(), gas_used);
+    assert_eq!(received_events[0].currency_code().as_str(), COIN1_NAME);
+    assert_eq!(
+        received_events[0].sender(),
+        account_config::libra_root_address()
+    );
+
+    // The fees are only distributed once.
+    let output = executor.new_block();
+    assert!(!output
+        .events()
+        .iter()
+        .any(|event| event.key().get_creator_address() == *validator.address()));
+}
@@ -0,0 +1,140 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account::{self, Account},
+    assert_move_abort,
+    common_transactions::{
+        create_child_vasp_txn, create_parent_vasp_txn, peer_to_peer_with_currency_txn,
+    },
+    executor::FakeExecutor,
+    keygen::KeyGen,
+};
+use libra_types::{
+    account_config::{self, MAX_CHILD_ACCOUNTS},
+    vm_status::AbortLocation,
+};
+use move_core_types::{identifier::Identifier, language_storage::ModuleId};
+use transaction_builder::encode_testnet_mint_script;
+
+/// Creates `parent` as a parent VASP holding `balance` Coin1.
+fn create_funded_parent_vasp(executor: &mut FakeExecutor, parent: &Account, balance: u64) {
+    let libra_root = Account::new_libra_root();
+    let dd = Account::new_genesis_account(account_config::testnet_dd_account_address());
+    let mut keygen = KeyGen::from_seed([9u8; 32]);
+    let (_, compliance_public_key) = keygen.generate_keypair();
+
+    executor.execute_and_apply(create_parent_vasp_txn(
+        &libra_root,
+        parent,
+        account_config::coin1_tag(),
+        compliance_public_key.to_bytes().to_vec(),
+        1,
+    ));
+    executor.execute_and_apply(dd.signed_script_txn(
+        encode_testnet_mint_script(account_config::coin1_tag(), *parent.address(), balance),
+        0,
+    ));
+}
+
+fn coin1_balance(executor: &FakeExecutor, account: &Account) -> u64 {
+    executor
+        .read_balance_resource(account, account::coin1_currency_code())
+        .expect("account must hold Coin1")
+        .coin()
+}
+
+#[test]
+fn move_funds_between_parent_and_child_vasp() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let parent = Account::new();
+    let child = Account::new();
+    create_funded_parent_vasp(&mut executor, &parent, 1_000);
+
+    executor.execute_and_apply(create_child_vasp_txn(
+        &parent,
+        &child,
+        account_config::coin1_tag(),
+        100,
+        0,
+    ));
+    assert_eq!(coin1_balance(&executor, &parent), 900);
+    assert_eq!(coin1_balance(&executor, &child), 100);
+
+    let parent_state = executor.read_account_state(&parent);
+    let child_state = executor.read_account_state(&child);
+    assert_eq!(parent_state.get_num_children().unwrap(), Some(1));
+    assert_eq!(
+        parent_state.get_parent_vasp_address().unwrap(),
+        Some(*parent.address())
+    );
+    assert_eq!(child_state.get_num_children().unwrap(), None);
+    assert_eq!(
+        child_state.get_parent_vasp_address().unwrap(),
+        Some(*parent.address())
+    );
+
+    // parent -> child
+    executor.execute_and_apply(peer_to_peer_with_currency_txn(
+        &parent,
+        &child,
+        account_config::coin1_tag(),
+        300,
+        1,
+    ));
+    assert_eq!(coin1_balance(&executor, &parent), 600);
+    assert_eq!(coin1_balance(&executor, &child), 400);
+
+    // child -> parent
+    executor.execute_and_apply(peer_to_peer_with_currency_txn(
+        &child,
+        &parent,
+        account_config::coin1_tag(),
+        250,
+        0,
+    ));
+    assert_eq!(coin1_balance(&executor, &parent), 850);
+    assert_eq!(coin1_balance(&executor, &child), 150);
+}
+
+#[test]
+fn child_vasp_limit() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let parent = Account::new();
+    create_funded_parent_vasp(&mut executor, &parent, 1_000);
+
+    for seq_num in 0..MAX_CHILD_ACCOUNTS {
+        executor.execute_and_apply(create_child_vasp_txn(
+            &parent,
+            &Account::new(),
+            account_config::coin1_tag(),
+            0,
+            seq_num,
+        ));
+    }
+    assert_eq!(
+        executor
+            .read_account_state(&parent)
+            .get_num_children()
+            .unwrap(),
+        Some(MAX_CHILD_ACCOUNTS)
+    );
+
+    // Creating one more child puts the parent VASP over the limit.
+    let output = executor.execute_transaction(create_child_vasp_txn(
+        &parent,
+        &Account::new(),
+        account_config::coin1_tag(),
+        0,
+        MAX_CHILD_ACCOUNTS,
+    ));
+    assert_move_abort(
+        output.status(),
+        &AbortLocation::Module(ModuleId::new(
+            account_config::CORE_CODE_ADDRESS,
+            Identifier::new("VASP").unwrap(),
+        )),
+        "publish_child_vasp_credential",
+        8,
+    );
+}
@@ -13,6 +13,7 @@ use compiler::Compiler;
 use libra_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
 use libra_types::{
     account_config::{self, lbr_type_tag, LBR_NAME},
+    chain_id::ChainId,
     on_chain_config::VMPublishingOption,
     test_helpers::transaction_test_helpers,
     transaction::{
@@ -341,6 +342,50 @@ fn verify_simple_payment() {
     );
 }
 
+#[test]
+fn verify_chain_id() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let sender = AccountData::new(900_000, 10);
+    let receiver = AccountData::new(100_000, 10);
+    executor.add_account_data(&sender);
+    executor.add_account_data(&receiver);
+    let txn = |chain_id| {
+        sender
+            .account()
+            .transaction()
+            .script(encode_peer_to_peer_with_metadata_script(
+                lbr_type_tag(),
+                *receiver.address(),
+                100,
+                vec![],
+                vec![],
+            ))
+            .sequence_number(10)
+            .chain_id(chain_id)
+            .sign()
+    };
+
+    // a transaction signed for another chain is rejected
+    let other_chain_txn = txn(ChainId::new(ChainId::test().id() + 1));
+    assert_prologue_parity!(
+        executor
+            .verify_transaction(other_chain_txn.clone())
+            .status(),
+        executor.execute_transaction(other_chain_txn).status(),
+        VMStatus::Error(StatusCode::BAD_CHAIN_ID)
+    );
+
+    let testing_txn = txn(ChainId::test());
+    assert_eq!(
+        executor.verify_transaction(testing_txn.clone()).status(),
+        None
+    );
+    assert_eq!(
+        executor.execute_transaction(testing_txn).status(),
+        &TransactionStatus::Keep(VMStatus::Executed)
+    );
+}
+
 #[test]
 pub fn test_whitelist() {
     // create a FakeExecutor with a genesis from file
@@ -4,8 +4,10 @@
 use crate::{
     account::{self, Account, AccountData},
     common_transactions::rotate_key_txn,
+    compile::compile_module_with_address,
     executor::FakeExecutor,
 };
+use compiled_stdlib::StdLibOptions;
 use libra_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
 use libra_types::{
     access_path::AccessPath,
@@ -20,8 +22,9 @@ use libra_types::{
 };
 use move_core_types::{
     identifier::Identifier,
-    language_storage::{ResourceKey, StructTag},
+    language_storage::{ModuleId, ResourceKey, StructTag},
 };
+use transaction_builder::encode_stdlib_upgrade_transaction;
 
 #[test]
 fn invalid_write_set_sender() {
@@ -304,3 +307,59 @@ fn transfer_and_execute_writeset() {
 
     executor.apply_write_set(output.write_set());
 }
+
+#[test]
+fn upgrade_stdlib_with_writeset() {
+    // create a FakeExecutor with a genesis from file
+    let mut executor = FakeExecutor::from_genesis_file();
+    let genesis_account = Account::new_libra_root();
+    executor.new_block();
+
+    // (1) Republishing the stdlib is a compatible upgrade
+    let writeset_txn = genesis_account.create_signed_txn_impl(
+        *genesis_account.address(),
+        TransactionPayload::WriteSet(encode_stdlib_upgrade_transaction(StdLibOptions::Compiled)),
+        1, // sequence number
+        100_000,
+        0, // gas unit price
+        LBR_NAME.to_owned(),
+    );
+    let output = executor.execute_transaction(writeset_txn);
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(VMStatus::Executed)
+    );
+    executor.apply_write_set(output.write_set());
+
+    // (2) Dropping the functions of a published module is not
+    let module = match compile_module_with_address(
+        &CORE_CODE_ADDRESS,
+        "file_name",
+        "module SlidingNonce { }",
+    ) {
+        TransactionPayload::Module(module) => module,
+        _ => unreachable!(),
+    };
+    let module_id = ModuleId::new(CORE_CODE_ADDRESS, Identifier::new("SlidingNonce").unwrap());
+    let write_set = WriteSetMut::new(vec![(
+        AccessPath::code_access_path(&module_id),
+        WriteOp::Value(module.code().to_vec()),
+    )])
+    .freeze()
+    .unwrap();
+    let writeset_txn = genesis_account.create_signed_txn_impl(
+        *genesis_account.address(),
+        TransactionPayload::WriteSet(ChangeSet::new(write_set, vec![])),
+        2, // sequence number
+        100_000,
+        0, // gas unit price
+        LBR_NAME.to_owned(),
+    );
+    let output = executor.execute_transaction(writeset_txn);
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Discard(VMStatus::Error(
+            StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE
+        ))
+    );
+}
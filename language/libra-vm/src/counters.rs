@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use libra_metrics::{
-    register_histogram, register_int_counter, register_int_counter_vec, register_int_gauge,
-    Histogram, IntCounter, IntCounterVec, IntGauge,
+    register_histogram, register_histogram_vec, register_int_counter, register_int_counter_vec,
+    register_int_gauge, Histogram, HistogramVec, IntCounter, IntCounterVec, IntGauge,
 };
 use once_cell::sync::Lazy;
 
@@ -101,3 +101,34 @@ pub static TXN_GAS_USAGE: Lazy<Histogram> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+/// Count the number of user transaction signatures verified, with a "result" label to
+/// distinguish valid and invalid signatures.
+pub static SIGNATURES_VERIFIED: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "libra_vm_signatures_verified",
+        "Number of user transaction signatures verified",
+        &["result"]
+    )
+    .unwrap()
+});
+
+/// Time to verify the signatures of the user transactions of a block, or of a batch of
+/// transactions to validate, with a "caller" label to distinguish execution and validation.
+pub static SIGNATURE_VERIFICATION_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "libra_vm_signature_verification_seconds",
+        "Histogram of the time to verify the signatures of a batch of transactions",
+        &["caller"]
+    )
+    .unwrap()
+});
+
+/// Number of threads of the signature verification pool.
+pub static SIGNATURE_VERIFICATION_THREADS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "libra_vm_signature_verification_threads",
+        "Number of threads verifying the signatures of user transactions"
+    )
+    .unwrap()
+});
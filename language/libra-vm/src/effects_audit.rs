@@ -0,0 +1,179 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reports of the effects a transaction leaves behind, depending on how it fails.
+//!
+//! A failed transaction may still be kept, in which case the writes and events of the failure
+//! epilogue persist while those of the script are dropped. `EffectsReport` records which effects
+//! persisted for a transaction, the phases of execution that ran and the class of failure, so
+//! that tests can pin down these semantics. Phases are recovered from an execution trace, which
+//! makes auditing expensive: this is meant for tests and tooling only.
+
+use crate::{system_module_names::*, LibraVM};
+use libra_state_view::StateView;
+use libra_types::{
+    access_path::AccessPath,
+    account_config,
+    event::EventKey,
+    transaction::{Transaction, TransactionOutput, TransactionStatus},
+    vm_status::{StatusCode, VMStatus},
+    write_set::WriteOp,
+};
+use move_core_types::{identifier::IdentStr, language_storage::TypeTag};
+use move_vm_runtime::execution_trace::TraceEvent;
+use serde::{Deserialize, Serialize};
+
+/// A phase of the execution of a user transaction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub enum Phase {
+    Prologue,
+    /// The `main` of the transaction script.
+    Script,
+    SuccessEpilogue,
+    FailureEpilogue,
+}
+
+/// How a transaction failed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub enum FailureClass {
+    /// The transaction did not fail.
+    None,
+    /// The transaction was discarded, e.g. because the prologue rejected it.
+    Discarded,
+    /// The transaction ran out of gas before the epilogue.
+    OutOfGas,
+    /// The transaction aborted before the epilogue.
+    Abort,
+    /// The transaction failed with another error before the epilogue, e.g. an arithmetic error
+    /// or a script failing verification.
+    Error,
+    /// The transaction executed but its success epilogue failed, e.g. because the sender could
+    /// no longer pay for gas.
+    EpilogueFailure,
+}
+
+/// A write which persisted.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct WriteEffect {
+    pub access_path: AccessPath,
+    pub deletion: bool,
+}
+
+/// An event which persisted.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct EventEffect {
+    pub key: EventKey,
+    pub sequence_number: u64,
+    pub type_tag: TypeTag,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct EffectsReport {
+    pub status: TransactionStatus,
+    pub failure_class: FailureClass,
+    /// The phases which started executing, in order.
+    pub phases: Vec<Phase>,
+    pub gas_used: u64,
+    pub writes: Vec<WriteEffect>,
+    pub events: Vec<EventEffect>,
+}
+
+impl LibraVM {
+    /// Executes `txn` as a singleton block and reports the effects of its output.
+    pub fn audit_transaction_effects(
+        txn: Transaction,
+        state_view: &dyn StateView,
+    ) -> Result<EffectsReport, VMStatus> {
+        let (output, trace) = Self::execute_block_with_trace(vec![txn], state_view)?
+            .pop()
+            .ok_or(VMStatus::Error(StatusCode::UNREACHABLE))?;
+        Ok(EffectsReport::new(&output, &trace))
+    }
+}
+
+impl EffectsReport {
+    pub fn new(output: &TransactionOutput, trace: &[TraceEvent]) -> Self {
+        let phases = phases(trace);
+        let failure_class = match output.status() {
+            TransactionStatus::Discard(_) | TransactionStatus::Retry => FailureClass::Discarded,
+            TransactionStatus::Keep(VMStatus::Executed) => FailureClass::None,
+            TransactionStatus::Keep(_) if phases.contains(&Phase::SuccessEpilogue) => {
+                FailureClass::EpilogueFailure
+            }
+            TransactionStatus::Keep(VMStatus::Error(StatusCode::OUT_OF_GAS)) => {
+                FailureClass::OutOfGas
+            }
+            TransactionStatus::Keep(VMStatus::MoveAbort(..)) => FailureClass::Abort,
+            TransactionStatus::Keep(VMStatus::Error(_)) => FailureClass::Error,
+        };
+        let writes = output
+            .write_set()
+            .iter()
+            .map(|(access_path, op)| WriteEffect {
+                access_path: access_path.clone(),
+                deletion: *op == WriteOp::Deletion,
+            })
+            .collect();
+        let events = output
+            .events()
+            .iter()
+            .map(|event| EventEffect {
+                key: *event.key(),
+                sequence_number: event.sequence_number(),
+                type_tag: event.type_tag().clone(),
+            })
+            .collect();
+        Self {
+            status: output.status().clone(),
+            failure_class,
+            phases,
+            gas_used: output.gas_used(),
+            writes,
+            events,
+        }
+    }
+}
+
+/// Returns the phases entered in `trace`, recognized by the first instruction of their function.
+fn phases(trace: &[TraceEvent]) -> Vec<Phase> {
+    // Functions are named like `Function::pretty_string` in the VM.
+    fn account_function(name: &IdentStr) -> String {
+        format!(
+            "0x{}::{}::{}",
+            account_config::ACCOUNT_MODULE.address(),
+            account_config::ACCOUNT_MODULE.name(),
+            name
+        )
+    }
+    let entry_points = [
+        (account_function(&PROLOGUE_NAME), Phase::Prologue),
+        (
+            account_function(&MULTI_AGENT_PROLOGUE_NAME),
+            Phase::Prologue,
+        ),
+        ("Script::main".to_string(), Phase::Script),
+        (
+            account_function(&SUCCESS_EPILOGUE_NAME),
+            Phase::SuccessEpilogue,
+        ),
+        (
+            account_function(&FAILURE_EPILOGUE_NAME),
+            Phase::FailureEpilogue,
+        ),
+    ];
+    let mut phases = vec![];
+    for event in trace {
+        if let TraceEvent::Instruction {
+            function, pc: 0, ..
+        } = event
+        {
+            if let Some((_, phase)) = entry_points.iter().find(|(name, _)| name == function) {
+                // A phase never calls itself, so a repeated entry is the same phase.
+                if phases.last() != Some(phase) {
+                    phases.push(*phase);
+                }
+            }
+        }
+    }
+    phases
+}
@@ -106,6 +106,7 @@ mod access_path_cache;
 #[macro_use]
 mod counters;
 pub mod data_cache;
+pub mod effects_audit;
 
 #[cfg(feature = "mirai-contracts")]
 pub mod foreign_contracts;
@@ -115,11 +116,15 @@ pub mod transaction_metadata;
 
 pub mod libra_transaction_executor;
 pub mod libra_transaction_validator;
+pub mod signature_verification;
 pub mod system_module_names;
 
 pub use crate::{
-    libra_transaction_executor::LibraVM, libra_transaction_validator::LibraVMValidator,
-    libra_vm::txn_effects_to_writeset_and_events,
+    libra_transaction_executor::LibraVM,
+    libra_transaction_validator::LibraVMValidator,
+    libra_vm::{
+        set_loader_cache_budget, txn_effects_to_writeset_and_events, VERIFIER_LIMITS_MAJOR_VERSION,
+    },
 };
 
 use libra_state_view::StateView;
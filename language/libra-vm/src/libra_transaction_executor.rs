@@ -8,38 +8,45 @@ use crate::{
         charge_global_write_gas_usage, get_transaction_output,
         txn_effects_to_writeset_and_events_cached, LibraVMImpl, LibraVMInternals,
     },
+    signature_verification::verify_signatures,
     system_module_names::*,
     transaction_metadata::TransactionMetadata,
     VMExecutor,
 };
+use bytecode_verifier::CompatibilityChecker;
 use libra_logger::prelude::*;
 use libra_state_view::StateView;
 use libra_trace::prelude::*;
 use libra_types::{
+    access_path::AccessPath,
     account_config,
     block_metadata::BlockMetadata,
     transaction::{
-        ChangeSet, Module, Script, SignatureCheckedTransaction, Transaction, TransactionArgument,
-        TransactionOutput, TransactionPayload, TransactionStatus,
+        ChangeSet, Module, Script, SignatureCheckedTransaction, SignedTransaction, Transaction,
+        TransactionArgument, TransactionOutput, TransactionPayload, TransactionStatus,
     },
     vm_status::{StatusCode, VMStatus},
-    write_set::{WriteSet, WriteSetMut},
+    write_set::{WriteOp, WriteSet, WriteSetMut},
 };
 use move_core_types::{
     gas_schedule::{CostTable, GasAlgebra, GasCarrier, GasUnits},
     identifier::IdentStr,
 };
-use move_vm_runtime::{data_cache::RemoteCache, session::Session};
+use move_vm_runtime::{
+    data_cache::RemoteCache,
+    execution_trace::{ExecutionTrace, TraceEvent},
+    session::Session,
+};
 
 use move_vm_types::{
     gas_schedule::{zero_cost_schedule, CostStrategy},
     values::Value,
 };
-use rayon::prelude::*;
 use std::{
     collections::HashSet,
     convert::{AsMut, AsRef, TryFrom},
 };
+use vm::CompiledModule;
 
 pub struct LibraVM(LibraVMImpl);
 
@@ -52,6 +59,59 @@ impl LibraVM {
         LibraVMInternals::new(&self.0)
     }
 
+    /// Executes a block like `VMExecutor::execute_block`, also returning every instruction and
+    /// native call executed for each transaction. Tracing is expensive: this is meant for tests
+    /// and tooling only.
+    pub fn execute_block_with_trace(
+        transactions: Vec<Transaction>,
+        state_view: &dyn StateView,
+    ) -> Result<Vec<(TransactionOutput, Vec<TraceEvent>)>, VMStatus> {
+        let mut state_view_cache = StateViewCache::new(state_view);
+        let mut vm = LibraVM::new(&state_view_cache);
+        vm.0.enable_tracing(ExecutionTrace::new());
+        let mut traces = vec![];
+        let outputs = vm.execute_block_impl(transactions, &mut state_view_cache, &mut traces)?;
+        Ok(outputs.into_iter().zip(traces).collect())
+    }
+
+    /// Executes a block like `VMExecutor::execute_block`, with gas metering disabled: transactions
+    /// use no gas, so they never run out of it whatever their gas parameters. This is meant for
+    /// tests of the logic of scripts only.
+    pub fn execute_block_without_gas_metering(
+        transactions: Vec<Transaction>,
+        state_view: &dyn StateView,
+    ) -> Result<Vec<TransactionOutput>, VMStatus> {
+        let mut state_view_cache = StateViewCache::new(state_view);
+        let mut vm = LibraVM::new(&state_view_cache);
+        vm.0.disable_gas_metering();
+        vm.execute_block_impl(transactions, &mut state_view_cache, &mut vec![])
+    }
+
+    /// Executes a single user transaction against `state_view` to preview its output, which is
+    /// never committed. If `skip_signature_check` is set, the signature is not verified so that
+    /// unsigned transactions can be simulated; the prologue still checks that the declared public
+    /// key matches the authentication key of the sender.
+    pub fn simulate_signed_transaction(
+        transaction: SignedTransaction,
+        skip_signature_check: bool,
+        state_view: &dyn StateView,
+    ) -> TransactionOutput {
+        let checked_txn = if skip_signature_check {
+            Ok(transaction.into_unchecked_for_simulation())
+        } else {
+            transaction.check_signature()
+        };
+        let state_view_cache = StateViewCache::new(state_view);
+        let mut vm = LibraVM::new(&state_view_cache);
+        match preprocess_user_transaction(checked_txn) {
+            Ok(PreprocessedTransaction::UserTransaction(txn)) => {
+                vm.execute_user_transaction(&state_view_cache, &txn)
+            }
+            Ok(_) => discard_error_output(VMStatus::Error(StatusCode::REJECTED_WRITE_SET)),
+            Err(e) => discard_error_output(e),
+        }
+    }
+
     /// Generates a transaction output for a transaction that encountered errors during the
     /// execution process. This is public for now only for tests.
     pub fn failed_transaction_cleanup(
@@ -145,7 +205,7 @@ impl LibraVM {
                     script.code().to_vec(),
                     script.ty_args().to_vec(),
                     convert_txn_args(script.args()),
-                    txn_data.sender(),
+                    txn_data.senders(),
                     cost_strategy,
                 )
                 .map_err(|e| e.into_vm_status())?;
@@ -186,7 +246,7 @@ impl LibraVM {
         )?;
 
         // Publish the module
-        let module_address = if self.0.on_chain_config()?.publishing_option.is_open_module() {
+        let module_address = if self.0.publishing_option()?.is_open_module() {
             txn_data.sender()
         } else {
             account_config::CORE_CODE_ADDRESS
@@ -293,6 +353,46 @@ impl LibraVM {
         Ok(())
     }
 
+    /// Write set transactions, which upgrade the stdlib, may republish modules. Like modules
+    /// published by transactions, the new versions must not break the layout of existing data nor
+    /// any existing caller.
+    fn check_module_upgrades(
+        &self,
+        remote_cache: &StateViewCache<'_>,
+        write_set: &WriteSet,
+    ) -> Result<(), VMStatus> {
+        for (ap, write_op) in write_set.iter() {
+            if ap.path.first() != Some(&AccessPath::CODE_TAG) {
+                continue;
+            }
+            let old_blob = match remote_cache
+                .get(ap)
+                .map_err(|_| VMStatus::Error(StatusCode::STORAGE_ERROR))?
+            {
+                Some(blob) => blob,
+                None => continue,
+            };
+            let new_blob = match write_op {
+                WriteOp::Value(blob) => blob,
+                WriteOp::Deletion => {
+                    return Err(VMStatus::Error(
+                        StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE,
+                    ))
+                }
+            };
+            let (old_module, new_module) = match (
+                CompiledModule::deserialize(&old_blob),
+                CompiledModule::deserialize(new_blob),
+            ) {
+                (Ok(old_module), Ok(new_module)) => (old_module, new_module),
+                _ => return Err(VMStatus::Error(StatusCode::CODE_DESERIALIZATION_ERROR)),
+            };
+            CompatibilityChecker::verify_module_upgrade(&old_module, &new_module)
+                .map_err(|e| e.into_vm_status())?;
+        }
+        Ok(())
+    }
+
     fn process_waypoint_change_set(
         &mut self,
         remote_cache: &mut StateViewCache<'_>,
@@ -369,6 +469,10 @@ impl LibraVM {
 
         let txn_data = TransactionMetadata::new(&txn);
 
+        if let Err(e) = self.check_module_upgrades(remote_cache, &change_set.write_set()) {
+            return Ok(discard_error_output(e));
+        };
+
         let mut session = self.0.new_session(remote_cache);
 
         if let Err(e) = self.0.run_writeset_prologue(&mut session, &txn_data) {
@@ -463,10 +567,12 @@ impl LibraVM {
         ))
     }
 
+    /// If tracing is enabled, the events recorded for each transaction are pushed to `traces`.
     fn execute_block_impl(
         &mut self,
         transactions: Vec<Transaction>,
         data_cache: &mut StateViewCache,
+        traces: &mut Vec<Vec<TraceEvent>>,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
         let count = transactions.len();
         let mut result = vec![];
@@ -474,13 +580,7 @@ impl LibraVM {
         let mut execute_block_trace_guard = vec![];
         let mut should_restart = false;
 
-        let signature_verified_block: Vec<Result<PreprocessedTransaction, VMStatus>>;
-        {
-            signature_verified_block = transactions
-                .into_par_iter()
-                .map(preprocess_transaction)
-                .collect();
-        }
+        let signature_verified_block = preprocess_transactions(transactions);
 
         for txn in signature_verified_block {
             if should_restart {
@@ -490,6 +590,9 @@ impl LibraVM {
                     0,
                     TransactionStatus::Retry,
                 ));
+                if self.0.trace().is_some() {
+                    traces.push(vec![]);
+                }
                 continue;
             };
             let output = match txn {
@@ -511,6 +614,15 @@ impl LibraVM {
                 }
                 Err(e) => discard_error_output(e),
             };
+            if let Some(trace) = self.0.trace() {
+                traces.push(trace.take_events());
+            }
+            // Modules are cached by the loader for the lifetime of the VM, so transactions after
+            // a module upgrade must be retried against the new code.
+            if !output.status().is_discarded() && is_module_upgrade(data_cache, &output) {
+                should_restart = true;
+            }
+
             if !output.status().is_discarded() {
                 data_cache.push_write_set(output.write_set());
             }
@@ -545,21 +657,57 @@ impl LibraVM {
     }
 }
 
-fn preprocess_transaction(txn: Transaction) -> Result<PreprocessedTransaction, VMStatus> {
-    Ok(match txn {
-        Transaction::BlockMetadata(b) => PreprocessedTransaction::BlockPrologue(b),
-        Transaction::WaypointWriteSet(cs) => PreprocessedTransaction::WaypointWriteSet(cs),
-        Transaction::UserTransaction(txn) => {
-            let checked_txn = txn
-                .check_signature()
-                .map_err(|_| VMStatus::Error(StatusCode::INVALID_SIGNATURE))?;
-            if let TransactionPayload::WriteSet(_) = checked_txn.payload() {
-                PreprocessedTransaction::WriteSet(Box::new(checked_txn))
-            } else {
-                PreprocessedTransaction::UserTransaction(Box::new(checked_txn))
+/// Preprocesses a block, verifying the signatures of its user transactions on the signature
+/// verification pool.
+fn preprocess_transactions(
+    txns: Vec<Transaction>,
+) -> Vec<Result<PreprocessedTransaction, VMStatus>> {
+    let mut user_txns = vec![];
+    let mut preprocessed = Vec::with_capacity(txns.len());
+    for txn in txns {
+        match txn {
+            Transaction::UserTransaction(txn) => {
+                user_txns.push(txn);
+                preprocessed.push(None);
             }
+            txn => preprocessed.push(Some(preprocess_transaction(txn))),
         }
-    })
+    }
+
+    let mut checked_txns = verify_signatures(user_txns, "execution").into_iter();
+    preprocessed
+        .into_iter()
+        .map(|txn| {
+            txn.unwrap_or_else(|| {
+                preprocess_user_transaction(
+                    checked_txns
+                        .next()
+                        .expect("every user transaction has a signature check result"),
+                )
+            })
+        })
+        .collect()
+}
+
+fn preprocess_transaction(txn: Transaction) -> Result<PreprocessedTransaction, VMStatus> {
+    match txn {
+        Transaction::BlockMetadata(b) => Ok(PreprocessedTransaction::BlockPrologue(b)),
+        Transaction::WaypointWriteSet(cs) => Ok(PreprocessedTransaction::WaypointWriteSet(cs)),
+        Transaction::UserTransaction(txn) => preprocess_user_transaction(txn.check_signature()),
+    }
+}
+
+fn preprocess_user_transaction(
+    checked_txn: anyhow::Result<SignatureCheckedTransaction>,
+) -> Result<PreprocessedTransaction, VMStatus> {
+    let checked_txn = checked_txn.map_err(|_| VMStatus::Error(StatusCode::INVALID_SIGNATURE))?;
+    Ok(
+        if let TransactionPayload::WriteSet(_) = checked_txn.payload() {
+            PreprocessedTransaction::WriteSet(Box::new(checked_txn))
+        } else {
+            PreprocessedTransaction::UserTransaction(Box::new(checked_txn))
+        },
+    )
 }
 
 fn is_reconfiguration(vm_output: &TransactionOutput) -> bool {
@@ -570,6 +718,15 @@ fn is_reconfiguration(vm_output: &TransactionOutput) -> bool {
         .any(|event| *event.key() == new_epoch_event_key)
 }
 
+fn is_module_upgrade(data_cache: &StateViewCache, vm_output: &TransactionOutput) -> bool {
+    vm_output.write_set().iter().any(|(access_path, _)| {
+        access_path.path.first() == Some(&AccessPath::CODE_TAG)
+            && data_cache
+                .get(access_path)
+                .map_or(true, |blob| blob.is_some())
+    })
+}
+
 /// Transactions divided by transaction flow.
 /// Transaction flows are different across different types of transactions.
 enum PreprocessedTransaction {
@@ -592,7 +749,7 @@ impl VMExecutor for LibraVM {
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
         let mut state_view_cache = StateViewCache::new(state_view);
         let mut vm = LibraVM::new(&state_view_cache);
-        vm.execute_block_impl(transactions, &mut state_view_cache)
+        vm.execute_block_impl(transactions, &mut state_view_cache, &mut vec![])
     }
 }
 
@@ -616,6 +773,7 @@ fn convert_txn_args(args: &[TransactionArgument]) -> Vec<Value> {
             TransactionArgument::Address(a) => Value::address(*a),
             TransactionArgument::Bool(b) => Value::bool(*b),
             TransactionArgument::U8Vector(v) => Value::vector_u8(v.clone()),
+            TransactionArgument::U8VectorVector(vs) => Value::vector_vector_u8(vs.clone()),
         })
         .collect()
 }
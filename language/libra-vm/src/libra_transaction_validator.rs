@@ -3,13 +3,14 @@
 
 use crate::{
     counters::*, create_access_path, data_cache::StateViewCache, libra_vm::LibraVMImpl,
-    transaction_metadata::TransactionMetadata, VMValidator,
+    signature_verification::batch_verify_signatures, transaction_metadata::TransactionMetadata,
+    VMValidator,
 };
 use libra_state_view::StateView;
 use libra_types::{
     account_address::AccountAddress,
     account_config::{self, RoleId},
-    on_chain_config::{LibraVersion, VMConfig},
+    on_chain_config::{LibraVersion, VMConfig, VMPublishingOption},
     transaction::{
         SignatureCheckedTransaction, SignedTransaction, TransactionPayload, VMValidatorResult,
     },
@@ -35,8 +36,16 @@ impl LibraVMValidator {
         Self(LibraVMImpl::new(state))
     }
 
-    pub fn init_with_config(version: LibraVersion, on_chain_config: VMConfig) -> Self {
-        LibraVMValidator(LibraVMImpl::init_with_config(version, on_chain_config))
+    pub fn init_with_config(
+        version: LibraVersion,
+        on_chain_config: VMConfig,
+        publishing_option: VMPublishingOption,
+    ) -> Self {
+        LibraVMValidator(LibraVMImpl::init_with_config(
+            version,
+            on_chain_config,
+            publishing_option,
+        ))
     }
 
     fn verify_transaction_impl(
@@ -94,23 +103,78 @@ impl VMValidator for LibraVMValidator {
         transaction: SignedTransaction,
         state_view: &dyn StateView,
     ) -> VMValidatorResult {
-        let data_cache = StateViewCache::new(state_view);
-        let _timer = TXN_VALIDATION_SECONDS.start_timer();
+        let txn_sender = transaction.sender();
         let gas_price = transaction.gas_unit_price();
-        let currency_code =
-            match account_config::from_currency_code_string(transaction.gas_currency_code()) {
-                Ok(code) => code,
-                Err(_) => {
-                    return VMValidatorResult::new(
-                        Some(VMStatus::Error(StatusCode::INVALID_GAS_SPECIFIER)),
+        let gas_currency_code = transaction.gas_currency_code().to_string();
+        self.validate_checked_transaction(
+            txn_sender,
+            gas_price,
+            &gas_currency_code,
+            transaction.check_signature(),
+            state_view,
+        )
+    }
+}
+
+impl LibraVMValidator {
+    /// Validates a batch of transactions like `validate_transaction`, returning one result per
+    /// transaction in the same order. Their signatures are verified in parallel on the signature
+    /// verification pool, rather than one after the other.
+    pub fn validate_transactions(
+        &self,
+        transactions: Vec<SignedTransaction>,
+        state_view: &dyn StateView,
+    ) -> Vec<VMValidatorResult> {
+        let txns_data: Vec<_> = transactions
+            .iter()
+            .map(|txn| {
+                (
+                    txn.sender(),
+                    txn.gas_unit_price(),
+                    txn.gas_currency_code().to_string(),
+                )
+            })
+            .collect();
+        txns_data
+            .into_iter()
+            .zip(batch_verify_signatures(transactions, "validation"))
+            .map(
+                |((txn_sender, gas_price, gas_currency_code), checked_txn)| {
+                    self.validate_checked_transaction(
+                        txn_sender,
                         gas_price,
-                        false,
+                        &gas_currency_code,
+                        checked_txn,
+                        state_view,
                     )
-                }
-            };
+                },
+            )
+            .collect()
+    }
 
-        let txn_sender = transaction.sender();
-        let signature_verified_txn = if let Ok(t) = transaction.check_signature() {
+    /// Validates a transaction given the result of the check of its signature.
+    fn validate_checked_transaction(
+        &self,
+        txn_sender: AccountAddress,
+        gas_price: u64,
+        gas_currency_code: &str,
+        signature_verified_txn: anyhow::Result<SignatureCheckedTransaction>,
+        state_view: &dyn StateView,
+    ) -> VMValidatorResult {
+        let data_cache = StateViewCache::new(state_view);
+        let _timer = TXN_VALIDATION_SECONDS.start_timer();
+        let currency_code = match account_config::from_currency_code_string(gas_currency_code) {
+            Ok(code) => code,
+            Err(_) => {
+                return VMValidatorResult::new(
+                    Some(VMStatus::Error(StatusCode::INVALID_GAS_SPECIFIER)),
+                    gas_price,
+                    false,
+                )
+            }
+        };
+
+        let signature_verified_txn = if let Ok(t) = signature_verified_txn {
             t
         } else {
             return VMValidatorResult::new(
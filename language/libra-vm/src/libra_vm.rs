@@ -15,56 +15,103 @@ use libra_types::{
     account_config,
     contract_event::ContractEvent,
     event::EventKey,
-    on_chain_config::{ConfigStorage, LibraVersion, OnChainConfig, VMConfig},
+    on_chain_config::{LibraVersion, OnChainConfig, VMConfig, VMPublishingOption},
     transaction::{ChangeSet, Script, TransactionOutput, TransactionStatus},
     vm_status::{convert_prologue_runtime_error, StatusCode, VMStatus},
     write_set::{WriteOp, WriteSet, WriteSetMut},
 };
 use move_core_types::{
-    gas_schedule::{CostTable, GasAlgebra, GasUnits},
+    gas_schedule::{CostTable, GasAlgebra, GasConstants, GasUnits},
     identifier::IdentStr,
     language_storage::TypeTag,
 };
 
+use anyhow::{format_err, Result};
+use bytecode_verifier::VerifierConfig;
 use move_vm_runtime::{
     data_cache::{RemoteCache, TransactionEffects},
-    move_vm::MoveVM,
+    execution_trace::ExecutionTrace,
+    move_vm::{LoaderConfig, MoveVM},
     session::Session,
 };
 use move_vm_types::{
     gas_schedule::{calculate_intrinsic_gas, zero_cost_schedule, CostStrategy},
     values::Value,
 };
+use once_cell::sync::OnceCell;
 use std::{convert::TryFrom, sync::Arc};
 use vm::errors::Location;
 
+static LOADER_CONFIG: OnceCell<LoaderConfig> = OnceCell::new();
+
+/// Bounds the loader caches of the Move VMs created from now on. Without it, they use the default
+/// `LoaderConfig`, and once a VM was created the bounds can no longer be set.
+pub fn set_loader_cache_budget(
+    max_binary_size: usize,
+    max_type_cache_entries: usize,
+) -> Result<()> {
+    LOADER_CONFIG
+        .set(LoaderConfig {
+            max_binary_size,
+            max_type_cache_entries,
+            ..LoaderConfig::default()
+        })
+        .map_err(|_| format_err!("Loader cache budget is already set."))
+}
+
+/// The first major Libra version at which the complexity limits of the bytecode verifier are
+/// enforced. Before it, modules and scripts are verified without limits, so that all validators
+/// agree on which code is valid until the limits are turned on by an on-chain version bump.
+pub const VERIFIER_LIMITS_MAJOR_VERSION: u64 = 2;
+
+fn new_move_vm(version: Option<&LibraVersion>) -> MoveVM {
+    let mut config = *LOADER_CONFIG.get_or_init(LoaderConfig::default);
+    let enforce_limits = version.map_or(false, |version| {
+        version.major >= VERIFIER_LIMITS_MAJOR_VERSION
+    });
+    if !enforce_limits {
+        config.verifier_config = VerifierConfig::unbounded();
+    }
+    MoveVM::new_with_config(config)
+}
+
 #[derive(Clone)]
 /// A wrapper to make VMRuntime standalone and thread safe.
 pub struct LibraVMImpl {
     move_vm: Arc<MoveVM>,
     on_chain_config: Option<VMConfig>,
+    publishing_option: Option<VMPublishingOption>,
     version: Option<LibraVersion>,
+    trace: Option<ExecutionTrace>,
 }
 
 impl LibraVMImpl {
     #[allow(clippy::new_without_default)]
     pub fn new<S: StateView>(state: &S) -> Self {
-        let inner = MoveVM::new();
-        let mut vm = Self {
+        let data_cache = RemoteStorage::new(state);
+        let version = LibraVersion::fetch_config(&data_cache);
+        let inner = new_move_vm(version.as_ref());
+        Self {
             move_vm: Arc::new(inner),
-            on_chain_config: None,
-            version: None,
-        };
-        vm.load_configs_impl(&RemoteStorage::new(state));
-        vm
+            on_chain_config: VMConfig::fetch_config(&data_cache),
+            publishing_option: VMPublishingOption::fetch_config(&data_cache),
+            version,
+            trace: None,
+        }
     }
 
-    pub fn init_with_config(version: LibraVersion, on_chain_config: VMConfig) -> Self {
-        let inner = MoveVM::new();
+    pub fn init_with_config(
+        version: LibraVersion,
+        on_chain_config: VMConfig,
+        publishing_option: VMPublishingOption,
+    ) -> Self {
+        let inner = new_move_vm(Some(&version));
         Self {
             move_vm: Arc::new(inner),
             on_chain_config: Some(on_chain_config),
+            publishing_option: Some(publishing_option),
             version: Some(version),
+            trace: None,
         }
     }
 
@@ -80,9 +127,11 @@ impl LibraVMImpl {
         })
     }
 
-    fn load_configs_impl<S: ConfigStorage>(&mut self, data_cache: &S) {
-        self.on_chain_config = VMConfig::fetch_config(data_cache);
-        self.version = LibraVersion::fetch_config(data_cache);
+    pub(crate) fn publishing_option(&self) -> Result<&VMPublishingOption, VMStatus> {
+        self.publishing_option.as_ref().ok_or_else(|| {
+            error!("VM Startup Failed. Publishing Option Not Found");
+            VMStatus::Error(StatusCode::VM_STARTUP_FAILURE)
+        })
     }
 
     pub fn get_gas_schedule(&self) -> Result<&CostTable, VMStatus> {
@@ -95,6 +144,19 @@ impl LibraVMImpl {
             })
     }
 
+    /// Disables gas metering for the transactions executed from now on: nothing they do costs
+    /// any gas. The bounds on the gas parameters of transactions are left as configured on chain.
+    pub fn disable_gas_metering(&mut self) {
+        if let Some(config) = &mut self.on_chain_config {
+            config.gas_schedule = free_gas_schedule(&config.gas_schedule.gas_constants);
+        }
+    }
+
+    pub fn get_gas_schedule_version(&self) -> Result<u64, VMStatus> {
+        self.on_chain_config()
+            .map(|config| config.gas_schedule_version)
+    }
+
     pub fn get_libra_version(&self) -> Result<LibraVersion, VMStatus> {
         self.version.clone().ok_or_else(|| {
             error!("VM Startup Failed. Libra Version Not Found");
@@ -176,11 +238,7 @@ impl LibraVMImpl {
     }
 
     pub(crate) fn is_allowed_script(&self, script: &Script) -> Result<(), VMStatus> {
-        if !self
-            .on_chain_config()?
-            .publishing_option
-            .is_allowed_script(&script.code())
-        {
+        if !self.publishing_option()?.is_allowed_script(&script.code()) {
             warn!("[VM] Custom scripts not allowed: {:?}", &script.code());
             Err(VMStatus::Error(StatusCode::UNKNOWN_SCRIPT))
         } else {
@@ -193,9 +251,12 @@ impl LibraVMImpl {
         txn_data: &TransactionMetadata,
         _remote_cache: &StateViewCache,
     ) -> Result<(), VMStatus> {
-        if !&self
-            .on_chain_config()?
-            .publishing_option
+        if txn_data.is_multi_agent() {
+            warn!("[VM] Module publishing transactions cannot have secondary signers");
+            return Err(VMStatus::Error(StatusCode::SECONDARY_SIGNERS_NOT_ALLOWED));
+        }
+        if !self
+            .publishing_option()?
             .is_allowed_module(&txn_data.sender)
         {
             warn!("[VM] Custom modules not allowed");
@@ -206,7 +267,8 @@ impl LibraVMImpl {
     }
 
     /// Run the prologue of a transaction by calling into `PROLOGUE_NAME` function stored
-    /// in the `ACCOUNT_MODULE` on chain.
+    /// in the `ACCOUNT_MODULE` on chain, or `MULTI_AGENT_PROLOGUE_NAME` if the transaction has
+    /// secondary signers.
     pub(crate) fn run_prologue<R: RemoteCache>(
         &self,
         session: &mut Session<R>,
@@ -221,12 +283,27 @@ impl LibraVMImpl {
         let txn_gas_price = txn_data.gas_unit_price().get();
         let txn_max_gas_units = txn_data.max_gas_amount().get();
         let txn_expiration_time = txn_data.expiration_time();
-        let _timer = TXN_PROLOGUE_SECONDS.start_timer();
-        session
-            .execute_function(
-                &account_config::ACCOUNT_MODULE,
-                &PROLOGUE_NAME,
-                vec![gas_currency_ty],
+        let chain_id = txn_data.chain_id();
+        let (prologue_name, args) = if txn_data.is_multi_agent() {
+            (
+                &*MULTI_AGENT_PROLOGUE_NAME,
+                vec![
+                    Value::transaction_argument_signer_reference(txn_data.sender),
+                    Value::u64(txn_sequence_number),
+                    Value::vector_u8(txn_public_key),
+                    Value::vector_address(txn_data.secondary_signers().to_vec()),
+                    Value::vector_vector_u8(
+                        txn_data.secondary_authentication_key_preimages().to_vec(),
+                    ),
+                    Value::u64(txn_gas_price),
+                    Value::u64(txn_max_gas_units),
+                    Value::u64(txn_expiration_time),
+                    Value::u8(chain_id.id()),
+                ],
+            )
+        } else {
+            (
+                &*PROLOGUE_NAME,
                 vec![
                     Value::transaction_argument_signer_reference(txn_data.sender),
                     Value::u64(txn_sequence_number),
@@ -234,7 +311,17 @@ impl LibraVMImpl {
                     Value::u64(txn_gas_price),
                     Value::u64(txn_max_gas_units),
                     Value::u64(txn_expiration_time),
+                    Value::u8(chain_id.id()),
                 ],
+            )
+        };
+        let _timer = TXN_PROLOGUE_SECONDS.start_timer();
+        session
+            .execute_function(
+                &account_config::ACCOUNT_MODULE,
+                prologue_name,
+                vec![gas_currency_ty],
+                args,
                 txn_data.sender,
                 cost_strategy,
             )
@@ -316,6 +403,10 @@ impl LibraVMImpl {
         session: &mut Session<R>,
         txn_data: &TransactionMetadata,
     ) -> Result<(), VMStatus> {
+        if txn_data.is_multi_agent() {
+            warn!("[VM] Write set transactions cannot have secondary signers");
+            return Err(VMStatus::Error(StatusCode::SECONDARY_SIGNERS_NOT_ALLOWED));
+        }
         let txn_sequence_number = txn_data.sequence_number();
         let txn_public_key = txn_data.authentication_key_preimage().to_vec();
         let gas_schedule = zero_cost_schedule();
@@ -330,6 +421,7 @@ impl LibraVMImpl {
                     Value::transaction_argument_signer_reference(txn_data.sender),
                     Value::u64(txn_sequence_number),
                     Value::vector_u8(txn_public_key),
+                    Value::u8(txn_data.chain_id().id()),
                 ],
                 txn_data.sender,
                 &mut cost_strategy,
@@ -367,7 +459,20 @@ impl LibraVMImpl {
     }
 
     pub fn new_session<'r, R: RemoteCache>(&self, r: &'r R) -> Session<'r, '_, R> {
-        self.move_vm.new_session(r)
+        let mut session = self.move_vm.new_session(r);
+        if let Some(trace) = &self.trace {
+            session.enable_tracing(trace.clone());
+        }
+        session
+    }
+
+    /// Records the execution of all the sessions created from now on into `trace`.
+    pub fn enable_tracing(&mut self, trace: ExecutionTrace) {
+        self.trace = Some(trace);
+    }
+
+    pub fn trace(&self) -> Option<&ExecutionTrace> {
+        self.trace.as_ref()
     }
 }
 
@@ -390,6 +495,16 @@ impl<'a> LibraVMInternals<'a> {
         self.0.get_gas_schedule()
     }
 
+    /// Returns the version of the loaded gas schedule, or an error if it hasn't been loaded.
+    pub fn gas_schedule_version(self) -> Result<u64, VMStatus> {
+        self.0.get_gas_schedule_version()
+    }
+
+    /// Returns the publishing option if it has been loaded, or an error if it hasn't.
+    pub fn publishing_option(self) -> Result<&'a VMPublishingOption, VMStatus> {
+        self.0.publishing_option()
+    }
+
     /// Returns the version of Move Runtime.
     pub fn libra_version(self) -> Result<LibraVersion, VMStatus> {
         self.0.get_libra_version()
@@ -463,6 +578,21 @@ pub fn txn_effects_to_writeset_and_events_cached<C: AccessPathCache>(
     Ok((ws, events))
 }
 
+/// A gas schedule in which instructions, natives, storage and the intrinsic cost of transactions
+/// are all free, with the other `gas_constants` unchanged.
+fn free_gas_schedule(gas_constants: &GasConstants) -> CostTable {
+    CostTable {
+        gas_constants: GasConstants {
+            global_memory_per_byte_cost: GasUnits::new(0),
+            global_memory_per_byte_write_cost: GasUnits::new(0),
+            min_transaction_gas_units: GasUnits::new(0),
+            instrinsic_gas_per_byte: GasUnits::new(0),
+            ..gas_constants.clone()
+        },
+        ..zero_cost_schedule()
+    }
+}
+
 pub(crate) fn charge_global_write_gas_usage<R: RemoteCache>(
     cost_strategy: &mut CostStrategy,
     session: &Session<R>,
@@ -0,0 +1,101 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification of the signatures of user transactions on a dedicated rayon thread pool, so that
+//! the signatures of a block of thousands of transactions are not checked on a single core and
+//! don't compete with the other users of the global rayon pool.
+//!
+//! Execution checks every signature on its own with `SignedTransaction::check_signature`.
+//! Validation splits the transactions into chunks of `SIGNATURE_VERIFICATION_BATCH_SIZE`, whose
+//! Ed25519 signatures are batch verified. The batch only filters what enters mempool, since a
+//! transaction accepted by it is checked again on its own when it is executed.
+
+use crate::counters::{
+    SIGNATURES_VERIFIED, SIGNATURE_VERIFICATION_SECONDS, SIGNATURE_VERIFICATION_THREADS,
+};
+use anyhow::{format_err, Result};
+use libra_types::transaction::{SignatureCheckedTransaction, SignedTransaction};
+use once_cell::sync::OnceCell;
+use rayon::{prelude::*, ThreadPool, ThreadPoolBuilder};
+
+/// Number of transactions whose signatures are batch verified together.
+pub const SIGNATURE_VERIFICATION_BATCH_SIZE: usize = 64;
+
+static SIGNATURE_VERIFICATION_POOL: OnceCell<ThreadPool> = OnceCell::new();
+
+/// Starts the signature verification pool with `num_threads` threads, or one per CPU if 0.
+/// Without it, the pool is started with one thread per CPU when the first signature is verified,
+/// after which the number of threads can no longer be set.
+pub fn set_num_threads(num_threads: usize) -> Result<()> {
+    let pool = build_pool(num_threads)?;
+    SIGNATURE_VERIFICATION_POOL
+        .set(pool)
+        .map_err(|_| format_err!("Signature verification pool is already started."))
+}
+
+fn build_pool(num_threads: usize) -> Result<ThreadPool> {
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .thread_name(|index| format!("signature-verification-{}", index))
+        .build()?;
+    SIGNATURE_VERIFICATION_THREADS.set(pool.current_num_threads() as i64);
+    Ok(pool)
+}
+
+fn pool() -> &'static ThreadPool {
+    SIGNATURE_VERIFICATION_POOL
+        .get_or_init(|| build_pool(0).expect("Building signature verification pool should work."))
+}
+
+/// Checks the signature of each of `txns` on its own on the signature verification pool, returning
+/// one result per transaction in the same order. `caller` labels the time taken in the metrics.
+pub fn verify_signatures(
+    txns: Vec<SignedTransaction>,
+    caller: &str,
+) -> Vec<Result<SignatureCheckedTransaction>> {
+    verify_on_pool(txns, caller, |txns| {
+        txns.into_par_iter()
+            .map(SignedTransaction::check_signature)
+            .collect()
+    })
+}
+
+/// Like `verify_signatures`, but batch verifies the Ed25519 signatures of chunks of
+/// `SIGNATURE_VERIFICATION_BATCH_SIZE` transactions. Only meant to reject invalid transactions
+/// early, e.g. before they enter mempool: the transactions it accepts must still go through
+/// `verify_signatures` before they are executed.
+pub fn batch_verify_signatures(
+    txns: Vec<SignedTransaction>,
+    caller: &str,
+) -> Vec<Result<SignatureCheckedTransaction>> {
+    verify_on_pool(txns, caller, |txns| {
+        txns.into_par_iter()
+            .chunks(SIGNATURE_VERIFICATION_BATCH_SIZE)
+            .flat_map(SignedTransaction::check_signatures)
+            .collect()
+    })
+}
+
+fn verify_on_pool(
+    txns: Vec<SignedTransaction>,
+    caller: &str,
+    verify: impl FnOnce(Vec<SignedTransaction>) -> Vec<Result<SignatureCheckedTransaction>> + Send,
+) -> Vec<Result<SignatureCheckedTransaction>> {
+    if txns.is_empty() {
+        return vec![];
+    }
+    let timer = SIGNATURE_VERIFICATION_SECONDS
+        .with_label_values(&[caller])
+        .start_timer();
+    let checked_txns = pool().install(|| verify(txns));
+    timer.observe_duration();
+
+    let num_valid = checked_txns.iter().filter(|txn| txn.is_ok()).count();
+    SIGNATURES_VERIFIED
+        .with_label_values(&["valid"])
+        .inc_by(num_valid as i64);
+    SIGNATURES_VERIFIED
+        .with_label_values(&["invalid"])
+        .inc_by((checked_txns.len() - num_valid) as i64);
+    checked_txns
+}
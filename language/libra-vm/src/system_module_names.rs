@@ -25,6 +25,8 @@ pub static LIBRA_BLOCK_MODULE: Lazy<ModuleId> = Lazy::new(|| {
 
 // Names for special functions and structs
 pub static PROLOGUE_NAME: Lazy<Identifier> = Lazy::new(|| Identifier::new("prologue").unwrap());
+pub static MULTI_AGENT_PROLOGUE_NAME: Lazy<Identifier> =
+    Lazy::new(|| Identifier::new("multi_agent_prologue").unwrap());
 pub static WRITESET_EPILOGUE_NAME: Lazy<Identifier> =
     Lazy::new(|| Identifier::new("epilogue").unwrap());
 pub static SUCCESS_EPILOGUE_NAME: Lazy<Identifier> =
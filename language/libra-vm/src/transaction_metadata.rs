@@ -4,6 +4,7 @@
 use libra_crypto::{ed25519::Ed25519PrivateKey, PrivateKey};
 use libra_types::{
     account_address::AccountAddress,
+    chain_id::ChainId,
     transaction::{authenticator::AuthenticationKeyPreimage, SignedTransaction},
 };
 use move_core_types::gas_schedule::{
@@ -14,11 +15,14 @@ use std::{convert::TryFrom, time::Duration};
 pub struct TransactionMetadata {
     pub sender: AccountAddress,
     pub authentication_key_preimage: Vec<u8>,
+    pub secondary_signers: Vec<AccountAddress>,
+    pub secondary_authentication_key_preimages: Vec<Vec<u8>>,
     pub sequence_number: u64,
     pub max_gas_amount: GasUnits<GasCarrier>,
     pub gas_unit_price: GasPrice<GasCarrier>,
     pub transaction_size: AbstractMemorySize<GasCarrier>,
     pub expiration_time: Duration,
+    pub chain_id: ChainId,
 }
 
 impl TransactionMetadata {
@@ -29,11 +33,19 @@ impl TransactionMetadata {
                 .authenticator()
                 .authentication_key_preimage()
                 .into_vec(),
+            secondary_signers: txn.secondary_signer_addresses(),
+            secondary_authentication_key_preimages: txn
+                .authenticator()
+                .secondary_signers()
+                .iter()
+                .map(|signer| signer.authentication_key_preimage().into_vec())
+                .collect(),
             sequence_number: txn.sequence_number(),
             max_gas_amount: GasUnits::new(txn.max_gas_amount()),
             gas_unit_price: GasPrice::new(txn.gas_unit_price()),
             transaction_size: AbstractMemorySize::new(txn.raw_txn_bytes_len() as u64),
             expiration_time: txn.expiration_time(),
+            chain_id: txn.chain_id(),
         }
     }
 
@@ -53,6 +65,25 @@ impl TransactionMetadata {
         &self.authentication_key_preimage
     }
 
+    pub fn secondary_signers(&self) -> &[AccountAddress] {
+        &self.secondary_signers
+    }
+
+    pub fn secondary_authentication_key_preimages(&self) -> &[Vec<u8>] {
+        &self.secondary_authentication_key_preimages
+    }
+
+    /// The signers passed to the script: the sender followed by the secondary signers.
+    pub fn senders(&self) -> Vec<AccountAddress> {
+        let mut senders = vec![self.sender];
+        senders.extend_from_slice(&self.secondary_signers);
+        senders
+    }
+
+    pub fn is_multi_agent(&self) -> bool {
+        !self.secondary_signers.is_empty()
+    }
+
     pub fn sequence_number(&self) -> u64 {
         self.sequence_number
     }
@@ -64,6 +95,10 @@ impl TransactionMetadata {
     pub fn expiration_time(&self) -> u64 {
         self.expiration_time.as_secs()
     }
+
+    pub fn chain_id(&self) -> ChainId {
+        self.chain_id
+    }
 }
 
 impl Default for TransactionMetadata {
@@ -74,11 +109,14 @@ impl Default for TransactionMetadata {
         TransactionMetadata {
             sender: AccountAddress::ZERO,
             authentication_key_preimage: AuthenticationKeyPreimage::ed25519(&public_key).into_vec(),
+            secondary_signers: vec![],
+            secondary_authentication_key_preimages: vec![],
             sequence_number: 0,
             max_gas_amount: GasUnits::new(100_000_000),
             gas_unit_price: GasPrice::new(0),
             transaction_size: AbstractMemorySize::new(0),
             expiration_time: Duration::new(0, 0),
+            chain_id: ChainId::test(),
         }
     }
 }
@@ -55,6 +55,7 @@ impl Arbitrary for TransactionArgument {
             any::<u64>().prop_map(TransactionArgument::U64),
             any::<AccountAddress>().prop_map(TransactionArgument::Address),
             vec(any::<u8>(), 0..10).prop_map(TransactionArgument::U8Vector),
+            vec(vec(any::<u8>(), 0..10), 0..3).prop_map(TransactionArgument::U8VectorVector),
         ]
         .boxed()
     }
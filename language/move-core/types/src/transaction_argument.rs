@@ -13,6 +13,7 @@ pub enum TransactionArgument {
     Address(AccountAddress),
     U8Vector(#[serde(with = "serde_bytes")] Vec<u8>),
     Bool(bool),
+    U8VectorVector(Vec<Vec<u8>>),
 }
 
 impl fmt::Debug for TransactionArgument {
@@ -26,6 +27,13 @@ impl fmt::Debug for TransactionArgument {
             TransactionArgument::U8Vector(vector) => {
                 write!(f, "{{U8Vector: 0x{}}}", hex::encode(vector))
             }
+            TransactionArgument::U8VectorVector(vectors) => {
+                let vectors = vectors
+                    .iter()
+                    .map(|vector| format!("0x{}", hex::encode(vector)))
+                    .collect::<Vec<_>>();
+                write!(f, "{{U8VectorVector: [{}]}}", vectors.join(", "))
+            }
         }
     }
 }
@@ -4,3 +4,4 @@
 mod address_test;
 mod identifier_test;
 mod language_storage_test;
+mod vm_status_test;
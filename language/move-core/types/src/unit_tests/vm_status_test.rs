@@ -0,0 +1,117 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    identifier::Identifier,
+    language_storage::{ModuleId, CORE_CODE_ADDRESS},
+    vm_status::{
+        AbortLocation, ErrorCategory, ErrorReason, StatusCode, VMStatus, EINSUFFICIENT_BALANCE,
+        LIBRA_ACCOUNT_EINSUFFICIENT_BALANCE, LIBRA_ACCOUNT_ENOT_LIBRA_ROOT,
+    },
+};
+
+#[test]
+fn error_reason_from_status_code() {
+    assert_eq!(ErrorReason::from_status_code(StatusCode::EXECUTED), None);
+
+    let cases = [
+        (
+            StatusCode::SEQUENCE_NUMBER_TOO_OLD,
+            ErrorReason::SequenceNumberTooOld,
+            ErrorCategory::Validation,
+        ),
+        (
+            StatusCode::GAS_UNIT_PRICE_ABOVE_MAX_BOUND,
+            ErrorReason::InvalidGasParameters,
+            ErrorCategory::Validation,
+        ),
+        (
+            StatusCode::REJECTED_WRITE_SET,
+            ErrorReason::InvalidTransaction,
+            ErrorCategory::Validation,
+        ),
+        (
+            StatusCode::TYPE_MISMATCH,
+            ErrorReason::InvalidCode,
+            ErrorCategory::Verification,
+        ),
+        (
+            StatusCode::OUT_OF_GAS,
+            ErrorReason::OutOfGas,
+            ErrorCategory::Execution,
+        ),
+        (
+            StatusCode::ABORTED,
+            ErrorReason::Aborted,
+            ErrorCategory::Abort,
+        ),
+        (
+            StatusCode::UNKNOWN_STATUS,
+            ErrorReason::InternalError,
+            ErrorCategory::Internal,
+        ),
+    ];
+    for (status_code, reason, category) in cases.iter() {
+        assert_eq!(ErrorReason::from_status_code(*status_code), Some(*reason));
+        assert_eq!(reason.category(), *category);
+    }
+}
+
+#[test]
+fn error_reason_from_vm_status() {
+    assert_eq!(VMStatus::Executed.error_reason(), None);
+    assert_eq!(
+        VMStatus::Error(StatusCode::BAD_CHAIN_ID).error_reason(),
+        Some(ErrorReason::WrongChain)
+    );
+    let libra_account = ModuleId::new(CORE_CODE_ADDRESS, Identifier::new("LibraAccount").unwrap());
+    let other_module = ModuleId::new(CORE_CODE_ADDRESS, Identifier::new("Libra").unwrap());
+    assert_eq!(
+        VMStatus::MoveAbort(
+            AbortLocation::Module(libra_account.clone()),
+            LIBRA_ACCOUNT_EINSUFFICIENT_BALANCE,
+            None
+        )
+        .error_reason(),
+        Some(ErrorReason::InsufficientBalance)
+    );
+    assert_eq!(
+        VMStatus::MoveAbort(
+            AbortLocation::Module(libra_account),
+            LIBRA_ACCOUNT_ENOT_LIBRA_ROOT,
+            None
+        )
+        .error_reason(),
+        Some(ErrorReason::InsufficientPrivileges)
+    );
+    // The same codes raised elsewhere carry no meaning.
+    assert_eq!(
+        VMStatus::MoveAbort(
+            AbortLocation::Module(other_module),
+            LIBRA_ACCOUNT_EINSUFFICIENT_BALANCE,
+            None
+        )
+        .error_reason(),
+        Some(ErrorReason::Aborted)
+    );
+    assert_eq!(
+        VMStatus::MoveAbort(AbortLocation::Script, EINSUFFICIENT_BALANCE, None).error_reason(),
+        Some(ErrorReason::Aborted)
+    );
+    assert_eq!(
+        VMStatus::MoveAbort(AbortLocation::Script, 1234, None).error_reason(),
+        Some(ErrorReason::Aborted)
+    );
+}
+
+#[test]
+fn error_reason_json_names() {
+    assert_eq!(
+        serde_json::to_string(&ErrorReason::InsufficientBalanceForTransactionFee).unwrap(),
+        "\"insufficient_balance_for_transaction_fee\""
+    );
+    assert_eq!(
+        serde_json::to_string(&ErrorCategory::Validation).unwrap(),
+        "\"validation\""
+    );
+}
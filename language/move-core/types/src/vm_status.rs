@@ -3,7 +3,10 @@
 
 #![allow(clippy::unit_arg)]
 
-use crate::language_storage::ModuleId;
+use crate::{
+    identifier::Identifier,
+    language_storage::{ModuleId, CORE_CODE_ADDRESS},
+};
 use anyhow::Result;
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest::prelude::*;
@@ -58,8 +61,9 @@ pub enum VMStatus {
     /// The code will neither EXECUTED nor ABORTED
     Error(StatusCode),
 
-    /// Indicates an `abort` from inside Move code. Contains the location of the abort and the code
-    MoveAbort(AbortLocation, /* code */ u64),
+    /// Indicates an `abort` from inside Move code. Contains the location of the abort, the code,
+    /// and the frame of the `abort` instruction when it is known
+    MoveAbort(AbortLocation, /* code */ u64, Option<AbortFrame>),
 }
 
 /// An `AbortLocation` specifies where a Move program `abort` occurred, either in a function in
@@ -74,6 +78,20 @@ pub enum AbortLocation {
     Script,
 }
 
+/// An `AbortFrame` identifies the function and the bytecode offset within that function at which
+/// an `abort` instruction was executed. Together with the `AbortLocation` and a source map for the
+/// module or script it is enough to recover the source line of the abort.
+/// Aborts raised by native functions do not have a frame.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+#[cfg_attr(any(test, feature = "fuzzing"), proptest(no_params))]
+pub struct AbortFrame {
+    /// The name of the function containing the `abort`
+    pub function: Identifier,
+    /// The offset of the `abort` instruction in the code of `function`
+    pub code_offset: u16,
+}
+
 /// A status type is one of 5 different variants, along with a fallback variant in the case that we
 /// don't recognize the status code.
 #[derive(Clone, PartialEq, Eq, Debug, Hash)]
@@ -105,8 +123,11 @@ impl fmt::Display for VMStatus {
         let status_type = self.status_type();
         let mut status = format!("status {:#?} of type {}", self.status_code(), status_type);
 
-        if let VMStatus::MoveAbort(_, code) = self {
-            status = format!("{} with sub status {}", status, code);
+        if let VMStatus::MoveAbort(location, code, frame) = self {
+            status = format!("{} with sub status {} in {:?}", status, code, location);
+            if let Some(frame) = frame {
+                status = format!("{} at {:?}", status, frame);
+            }
         }
 
         write!(f, "{}", status)
@@ -118,10 +139,11 @@ impl fmt::Debug for VMStatus {
         match self {
             VMStatus::Executed => write!(f, "EXECUTED"),
             VMStatus::Error(code) => f.debug_struct("ERROR").field("status_code", code).finish(),
-            VMStatus::MoveAbort(location, code) => f
+            VMStatus::MoveAbort(location, code, frame) => f
                 .debug_struct("ABORTED")
                 .field("code", code)
                 .field("location", location)
+                .field("frame", frame)
                 .finish(),
         }
     }
@@ -136,6 +158,12 @@ impl fmt::Debug for AbortLocation {
     }
 }
 
+impl fmt::Debug for AbortFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}+{}", self.function, self.code_offset)
+    }
+}
+
 impl std::error::Error for VMStatus {}
 
 impl VMStatus {
@@ -143,7 +171,7 @@ impl VMStatus {
     pub fn status_code(&self) -> StatusCode {
         match self {
             Self::Executed => StatusCode::EXECUTED,
-            Self::MoveAbort(_, _) => StatusCode::ABORTED,
+            Self::MoveAbort(_, _, _) => StatusCode::ABORTED,
             Self::Error(code) => {
                 let code = *code;
                 debug_assert!(code != StatusCode::EXECUTED);
@@ -156,7 +184,24 @@ impl VMStatus {
     /// Returns the move abort code if the status is `MoveAbort`, and `None` otherwise
     pub fn move_abort_code(&self) -> Option<u64> {
         match self {
-            Self::MoveAbort(_, code) => Some(*code),
+            Self::MoveAbort(_, code, _) => Some(*code),
+            Self::Error(_) | Self::Executed => None,
+        }
+    }
+
+    /// Returns the location of the abort if the status is `MoveAbort`, and `None` otherwise
+    pub fn move_abort_location(&self) -> Option<&AbortLocation> {
+        match self {
+            Self::MoveAbort(location, _, _) => Some(location),
+            Self::Error(_) | Self::Executed => None,
+        }
+    }
+
+    /// Returns the frame of the abort if the status is `MoveAbort` and the frame is known, and
+    /// `None` otherwise
+    pub fn move_abort_frame(&self) -> Option<&AbortFrame> {
+        match self {
+            Self::MoveAbort(_, _, frame) => frame.as_ref(),
             Self::Error(_) | Self::Executed => None,
         }
     }
@@ -165,8 +210,36 @@ impl VMStatus {
     pub fn status_type(&self) -> StatusType {
         self.status_code().status_type()
     }
+
+    /// Returns the client-facing reason for this `VMStatus`, or `None` if it is `Executed`.
+    /// Abort codes are only meaningful together with the module that raised them, so only aborts
+    /// of `LibraAccount` are refined beyond `Aborted`.
+    pub fn error_reason(&self) -> Option<ErrorReason> {
+        match self {
+            Self::MoveAbort(AbortLocation::Module(module), code, _)
+                if module.address() == &CORE_CODE_ADDRESS
+                    && module.name().as_str() == LIBRA_ACCOUNT_MODULE_NAME =>
+            {
+                Some(match *code {
+                    LIBRA_ACCOUNT_EINSUFFICIENT_BALANCE => ErrorReason::InsufficientBalance,
+                    LIBRA_ACCOUNT_ENOT_LIBRA_ROOT | LIBRA_ACCOUNT_ESENDER_NOT_VM => {
+                        ErrorReason::InsufficientPrivileges
+                    }
+                    _ => ErrorReason::Aborted,
+                })
+            }
+            _ => ErrorReason::from_status_code(self.status_code()),
+        }
+    }
 }
 
+/// Abort codes of the `LibraAccount` module that `VMStatus::error_reason` reports specifically.
+/// They must be kept in sync with the constants of `LibraAccount.move`.
+const LIBRA_ACCOUNT_MODULE_NAME: &str = "LibraAccount";
+pub const LIBRA_ACCOUNT_EINSUFFICIENT_BALANCE: u64 = 5; // withdrawing more than the balance
+pub const LIBRA_ACCOUNT_ENOT_LIBRA_ROOT: u64 = 11; // operation reserved to the Libra root
+pub const LIBRA_ACCOUNT_ESENDER_NOT_VM: u64 = 19; // operation reserved to the VM
+
 /// Error codes that can be emitted by the prologue. These have special significance to the VM when
 /// they are raised during the prologue. However, they can also be raised by user code during
 /// execution of a transaction script. They have no significance to the VM in that case.
@@ -177,6 +250,9 @@ pub const ESEQUENCE_NUMBER_TOO_NEW: u64 = 3; // transaction sequence number is t
 pub const EACCOUNT_DOES_NOT_EXIST: u64 = 4; // transaction sender's account does not exist
 pub const ECANT_PAY_GAS_DEPOSIT: u64 = 5; // insufficient balance to pay for gas deposit
 pub const ETRANSACTION_EXPIRED: u64 = 6; // transaction expiration time exceeds block time.
+pub const ESIGNERS_CONTAIN_DUPLICATES: u64 = 7; // an account signs the transaction twice
+pub const ESECONDARY_ACCOUNT_DOES_NOT_EXIST: u64 = 8; // a secondary signer's account does not exist
+pub const EBAD_CHAIN_ID: u64 = 9; // chain_id in transaction doesn't match the one on-chain
 
 /// Generic error codes. These codes don't have any special meaning for the VM, but they are useful
 /// conventions for debugging
@@ -191,7 +267,7 @@ pub const EASSERT_ERROR: u64 = 42; // catch-all error code for assert failures
 pub fn convert_prologue_runtime_error(status: VMStatus) -> VMStatus {
     match status {
         // TODO look at location? or remove this function entirely
-        VMStatus::MoveAbort(location, code) => {
+        VMStatus::MoveAbort(location, code, frame) => {
             let new_major_status = match code {
                 EACCOUNT_FROZEN => StatusCode::SENDING_ACCOUNT_FROZEN,
                 // Invalid authentication key
@@ -205,7 +281,12 @@ pub fn convert_prologue_runtime_error(status: VMStatus) -> VMStatus {
                 // Can't pay for transaction gas deposit/fee
                 ECANT_PAY_GAS_DEPOSIT => StatusCode::INSUFFICIENT_BALANCE_FOR_TRANSACTION_FEE,
                 ETRANSACTION_EXPIRED => StatusCode::TRANSACTION_EXPIRED,
-                code => return VMStatus::MoveAbort(location, code),
+                ESIGNERS_CONTAIN_DUPLICATES => StatusCode::SIGNERS_CONTAIN_DUPLICATES,
+                ESECONDARY_ACCOUNT_DOES_NOT_EXIST => {
+                    StatusCode::SECONDARY_SIGNER_ACCOUNT_DOES_NOT_EXIST
+                }
+                EBAD_CHAIN_ID => StatusCode::BAD_CHAIN_ID,
+                code => return VMStatus::MoveAbort(location, code, frame),
             };
             VMStatus::Error(new_major_status)
         }
@@ -213,6 +294,146 @@ pub fn convert_prologue_runtime_error(status: VMStatus) -> VMStatus {
     }
 }
 
+/// The client-facing category of a failed transaction. Unlike `StatusType`, this is part of the
+/// stable interface exposed to clients: variants are never renamed or removed.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    /// The transaction was rejected before execution and is not committed.
+    Validation,
+    /// The code published or run by the transaction is malformed.
+    Verification,
+    /// The transaction failed during execution, e.g., it ran out of gas.
+    Execution,
+    /// The transaction aborted in Move code.
+    Abort,
+    /// The VM hit an internal error. This is not the fault of the transaction.
+    Internal,
+}
+
+/// The client-facing reason of a failed transaction, derived from its `StatusCode` and, for
+/// aborts, from the abort code conventions of the standard library. Wallets can map these to
+/// user-facing messages instead of interpreting numeric codes. Variants are never renamed or
+/// removed; new ones may be added.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorReason {
+    /// The signature of the transaction is invalid.
+    InvalidSignature,
+    /// The key that signed the transaction does not match the authentication key of the sender.
+    InvalidAuthenticationKey,
+    /// The sequence number of the transaction has already been used.
+    SequenceNumberTooOld,
+    /// The sequence number of the transaction is ahead of the one of the sender.
+    SequenceNumberTooNew,
+    /// The sender cannot afford the maximum transaction fee.
+    InsufficientBalanceForTransactionFee,
+    /// The expiration time of the transaction has passed.
+    TransactionExpired,
+    /// The sender or a secondary signer of the transaction does not have an account.
+    AccountDoesNotExist,
+    /// The sending account is frozen.
+    AccountFrozen,
+    /// The transaction was signed for another chain.
+    WrongChain,
+    /// The maximum gas amount, gas price or gas currency of the transaction is invalid.
+    InvalidGasParameters,
+    /// The transaction is too large.
+    TransactionTooLarge,
+    /// The script of the transaction is not allowed on this chain.
+    ScriptNotAllowed,
+    /// The sender is not allowed to publish modules.
+    ModulePublishingNotAllowed,
+    /// The transaction was rejected for any other reason before execution.
+    InvalidTransaction,
+    /// The code published or run by the transaction is malformed.
+    InvalidCode,
+    /// The transaction ran out of gas.
+    OutOfGas,
+    /// The transaction overflowed, underflowed or divided by zero.
+    ArithmeticError,
+    /// The transaction failed during execution for any other reason.
+    ExecutionFailure,
+    /// The transaction aborted because an account does not hold enough funds.
+    InsufficientBalance,
+    /// The transaction aborted because an account lacks the privileges to perform an operation.
+    InsufficientPrivileges,
+    /// The transaction aborted in Move code for any other reason.
+    Aborted,
+    /// The VM hit an internal error.
+    InternalError,
+}
+
+impl ErrorReason {
+    /// Returns the reason for a transaction that completed with `status_code`, or `None` if the
+    /// transaction executed successfully. Aborts are reported as `Aborted`, since the status code
+    /// does not carry the abort code; use `VMStatus::error_reason` when the full status is known.
+    pub fn from_status_code(status_code: StatusCode) -> Option<Self> {
+        use StatusCode::*;
+        Some(match status_code {
+            EXECUTED => return None,
+            INVALID_SIGNATURE => ErrorReason::InvalidSignature,
+            INVALID_AUTH_KEY => ErrorReason::InvalidAuthenticationKey,
+            SEQUENCE_NUMBER_TOO_OLD => ErrorReason::SequenceNumberTooOld,
+            SEQUENCE_NUMBER_TOO_NEW => ErrorReason::SequenceNumberTooNew,
+            INSUFFICIENT_BALANCE_FOR_TRANSACTION_FEE => {
+                ErrorReason::InsufficientBalanceForTransactionFee
+            }
+            TRANSACTION_EXPIRED => ErrorReason::TransactionExpired,
+            SENDING_ACCOUNT_DOES_NOT_EXIST | SECONDARY_SIGNER_ACCOUNT_DOES_NOT_EXIST => {
+                ErrorReason::AccountDoesNotExist
+            }
+            SENDING_ACCOUNT_FROZEN => ErrorReason::AccountFrozen,
+            BAD_CHAIN_ID => ErrorReason::WrongChain,
+            MAX_GAS_UNITS_EXCEEDS_MAX_GAS_UNITS_BOUND
+            | MAX_GAS_UNITS_BELOW_MIN_TRANSACTION_GAS_UNITS
+            | GAS_UNIT_PRICE_BELOW_MIN_BOUND
+            | GAS_UNIT_PRICE_ABOVE_MAX_BOUND
+            | INVALID_GAS_SPECIFIER => ErrorReason::InvalidGasParameters,
+            EXCEEDED_MAX_TRANSACTION_SIZE => ErrorReason::TransactionTooLarge,
+            UNKNOWN_SCRIPT => ErrorReason::ScriptNotAllowed,
+            UNKNOWN_MODULE | INVALID_MODULE_PUBLISHER => ErrorReason::ModulePublishingNotAllowed,
+            OUT_OF_GAS => ErrorReason::OutOfGas,
+            ARITHMETIC_ERROR => ErrorReason::ArithmeticError,
+            ABORTED => ErrorReason::Aborted,
+            status_code => match status_code.status_type() {
+                StatusType::Validation => ErrorReason::InvalidTransaction,
+                StatusType::Verification | StatusType::Deserialization => ErrorReason::InvalidCode,
+                StatusType::Execution => ErrorReason::ExecutionFailure,
+                StatusType::InvariantViolation | StatusType::Unknown => ErrorReason::InternalError,
+            },
+        })
+    }
+
+    /// Returns the category this reason belongs to.
+    pub fn category(self) -> ErrorCategory {
+        match self {
+            ErrorReason::InvalidSignature
+            | ErrorReason::InvalidAuthenticationKey
+            | ErrorReason::SequenceNumberTooOld
+            | ErrorReason::SequenceNumberTooNew
+            | ErrorReason::InsufficientBalanceForTransactionFee
+            | ErrorReason::TransactionExpired
+            | ErrorReason::AccountDoesNotExist
+            | ErrorReason::AccountFrozen
+            | ErrorReason::WrongChain
+            | ErrorReason::InvalidGasParameters
+            | ErrorReason::TransactionTooLarge
+            | ErrorReason::ScriptNotAllowed
+            | ErrorReason::ModulePublishingNotAllowed
+            | ErrorReason::InvalidTransaction => ErrorCategory::Validation,
+            ErrorReason::InvalidCode => ErrorCategory::Verification,
+            ErrorReason::OutOfGas
+            | ErrorReason::ArithmeticError
+            | ErrorReason::ExecutionFailure => ErrorCategory::Execution,
+            ErrorReason::InsufficientBalance
+            | ErrorReason::InsufficientPrivileges
+            | ErrorReason::Aborted => ErrorCategory::Abort,
+            ErrorReason::InternalError => ErrorCategory::Internal,
+        }
+    }
+}
+
 macro_rules! derive_status_try_from_repr {
     (
         #[repr($repr_ty:ident)]
@@ -313,6 +534,14 @@ pub enum StatusCode {
     INVALID_MODULE_PUBLISHER = 21,
     // The sending account has no role
     NO_ACCOUNT_ROLE = 22,
+    // An account is among the signers of the transaction more than once
+    SIGNERS_CONTAIN_DUPLICATES = 23,
+    // The account of a secondary signer does not exist
+    SECONDARY_SIGNER_ACCOUNT_DOES_NOT_EXIST = 24,
+    // Only script transactions may have secondary signers
+    SECONDARY_SIGNERS_NOT_ALLOWED = 25,
+    // The chain ID of the transaction does not match the one of the chain
+    BAD_CHAIN_ID = 26,
 
     // When a code module/script is published it is verified. These are the
     // possible errors that can arise from the verification process.
@@ -414,6 +643,14 @@ pub enum StatusCode {
     GENERIC_MEMBER_OPCODE_MISMATCH = 1090,
     FUNCTION_RESOLUTION_FAILURE = 1091,
     INVALID_OPERATION_IN_SCRIPT = 1094,
+    // Reported when a module is republished with changes that break existing data or callers
+    BACKWARD_INCOMPATIBLE_MODULE_UPDATE = 1095,
+    // Reported when a function has more basic blocks than the verifier accepts
+    TOO_MANY_BASIC_BLOCKS = 1096,
+    // Reported when a type is nested deeper than the verifier accepts
+    TOO_DEEPLY_NESTED_TYPE = 1097,
+    // Reported when a script takes `&signer` arguments, but not as many as there are signers
+    NUMBER_OF_SIGNER_ARGUMENTS_MISMATCH = 1098,
 
     // These are errors that the VM might raise if a violation of internal
     // invariants takes place.
@@ -25,7 +25,7 @@ fun main(account: &signer) {
 // check: 4
 
 
-// Validators and ValidatorOperators should not be able to add a balance
+// Validators can add a balance to receive transaction fees, ValidatorOperators cannot
 //! account: vivian, 0, 0, address
 //! account: otto, 0, 0, address
 
@@ -49,10 +49,10 @@ use 0x1::LibraAccount;
 use 0x1::Coin2::Coin2;
 fun main(account: &signer) {
     LibraAccount::add_currency<Coin2>(account);
+    assert(LibraAccount::accepts_currency<Coin2>({{vivian}}), 0);
 }
 }
-// check: ABORTED
-// check: 4
+// check: EXECUTED
 
 // check ValidatorOperator case
 //! new-transaction
@@ -49,6 +49,10 @@ pub struct Options {
         long = cli::SOURCE_MAP,
     )]
     pub emit_source_map: bool,
+
+    /// Skip constant folding and dead code elimination
+    #[structopt(name = "no-optimize", long = cli::NO_OPTIMIZE)]
+    pub no_optimize: bool,
 }
 
 pub fn main() -> anyhow::Result<()> {
@@ -58,7 +62,10 @@ pub fn main() -> anyhow::Result<()> {
         sender,
         out_dir,
         emit_source_map,
+        no_optimize,
     } = Options::from_args();
-    let (files, compiled_units) = move_lang::move_compile(&source_files, &dependencies, sender)?;
+    let flags = Flags { no_optimize };
+    let (files, compiled_units) =
+        move_lang::move_compile_with_flags(&source_files, &dependencies, sender, flags)?;
     move_lang::output_compiled_units(emit_source_map, files, compiled_units, &out_dir)
 }
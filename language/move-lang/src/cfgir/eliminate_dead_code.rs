@@ -0,0 +1,65 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::cfg::BlockCFG;
+use crate::hlir::ast::{BasicBlock, Command, Command_, Exp, ExpListItem, UnannotatedExp_};
+
+/// Removes commands which have no effect: popping a value which has no side effects and cannot
+/// abort, and branching on such a value to the same label either way.
+/// Unreachable blocks are removed when the CFG is recomputed.
+/// returns true if anything changed
+pub fn optimize(cfg: &mut BlockCFG) -> bool {
+    let mut changed = false;
+    for block in cfg.blocks_mut().values_mut() {
+        let old_block = std::mem::replace(block, BasicBlock::new());
+        let old_len = old_block.len();
+        *block = old_block
+            .into_iter()
+            .filter(|cmd| !is_dead_pop(cmd))
+            .map(|mut cmd| {
+                changed = simplify_jump(&mut cmd) || changed;
+                cmd
+            })
+            .collect::<BasicBlock>();
+        changed = changed || old_len != block.len();
+    }
+    if changed {
+        let _dead_blocks = cfg.recompute();
+    }
+    changed
+}
+
+fn is_dead_pop(sp!(_, cmd_): &Command) -> bool {
+    match cmd_ {
+        Command_::IgnoreAndPop { exp, .. } => is_pure(exp),
+        _ => false,
+    }
+}
+
+fn simplify_jump(sp!(_, cmd_): &mut Command) -> bool {
+    use Command_ as C;
+    match cmd_ {
+        C::JumpIf {
+            cond,
+            if_true,
+            if_false,
+        } if if_true == if_false && is_pure(cond) => {
+            *cmd_ = C::Jump(*if_true);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Whether evaluating `e` has no effect besides producing its value. Locals are not considered,
+/// as liveness inserts pops of references held in locals to release them.
+fn is_pure(e: &Exp) -> bool {
+    use UnannotatedExp_ as E;
+    match &e.exp.value {
+        E::Unit { .. } | E::Value(_) | E::Constant(_) => true,
+        E::ExpList(items) => items.iter().all(|item| match item {
+            ExpListItem::Single(e, _) | ExpListItem::Splat(_, e, _) => is_pure(e),
+        }),
+        _ => false,
+    }
+}
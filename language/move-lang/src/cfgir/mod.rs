@@ -6,6 +6,7 @@ pub mod ast;
 mod borrows;
 pub(crate) mod cfg;
 mod constant_fold;
+mod eliminate_dead_code;
 mod eliminate_locals;
 mod inline_blocks;
 mod liveness;
@@ -50,6 +51,7 @@ pub fn optimize(
         let mut changed = false;
         changed |= eliminate_locals::optimize(cfg);
         changed |= constant_fold::optimize(cfg);
+        changed |= eliminate_dead_code::optimize(cfg);
         changed |= simplify_jumps::optimize(cfg);
         changed |= inline_blocks::optimize(cfg);
 
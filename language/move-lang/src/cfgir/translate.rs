@@ -11,7 +11,7 @@ use crate::{
     expansion::ast::{Value, Value_},
     hlir::ast::{self as H, Label},
     parser::ast::{ConstantName, FunctionName, ModuleIdent, StructName, Var},
-    shared::unique_map::UniqueMap,
+    shared::{unique_map::UniqueMap, Flags},
 };
 use libra_types::account_address::AccountAddress as LibraAddress;
 use move_core_types::value::MoveValue;
@@ -27,6 +27,7 @@ use std::{
 
 struct Context {
     errors: Errors,
+    flags: Flags,
     start: Option<Label>,
     loop_begin: Option<Label>,
     loop_end: Option<Label>,
@@ -38,9 +39,10 @@ struct Context {
 }
 
 impl Context {
-    pub fn new(_prog: &H::Program, errors: Errors) -> Self {
+    pub fn new(_prog: &H::Program, flags: Flags, errors: Errors) -> Self {
         Context {
             errors,
+            flags,
             next_label: None,
             loop_begin: None,
             loop_end: None,
@@ -101,8 +103,8 @@ impl Context {
 // Entry
 //**************************************************************************************************
 
-pub fn program(errors: Errors, prog: H::Program) -> (G::Program, Errors) {
-    let mut context = Context::new(&prog, errors);
+pub fn program(errors: Errors, flags: Flags, prog: H::Program) -> (G::Program, Errors) {
+    let mut context = Context::new(&prog, flags, errors);
     let modules = modules(&mut context, prog.modules);
     let scripts = scripts(&mut context, prog.scripts);
 
@@ -333,7 +335,7 @@ fn function_body(
                 &mut cfg,
                 &infinite_loop_starts,
             );
-            if context.errors.is_empty() {
+            if context.errors.is_empty() && !context.flags.no_optimize {
                 cfgir::optimize(signature, &locals, &mut cfg);
             }
 
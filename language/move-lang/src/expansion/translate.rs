@@ -13,6 +13,7 @@ use crate::{
         ModuleIdent_, ModuleName, StructName, Var,
     },
     shared::{unique_map::UniqueMap, *},
+    unit_test::{EXPECTED_FAILURE_ATTRIBUTE, KNOWN_ATTRIBUTES, TEST_ATTRIBUTE},
 };
 use move_ir_types::location::*;
 use std::{
@@ -31,17 +32,19 @@ struct Context {
     address: Option<Address>,
     aliases: AliasMap,
     is_source_module: bool,
+    test_mode: bool,
     in_spec_context: bool,
     exp_specs: BTreeMap<SpecId, E::SpecBlock>,
 }
 impl Context {
-    fn new(module_members: UniqueMap<ModuleIdent, ModuleMembers>) -> Self {
+    fn new(module_members: UniqueMap<ModuleIdent, ModuleMembers>, test_mode: bool) -> Self {
         Self {
             module_members,
             errors: vec![],
             address: None,
             aliases: AliasMap::new(),
             is_source_module: false,
+            test_mode,
             in_spec_context: false,
             exp_specs: BTreeMap::new(),
         }
@@ -114,14 +117,20 @@ impl Context {
 // Entry
 //**************************************************************************************************
 
-pub fn program(prog: P::Program, sender: Option<Address>) -> (E::Program, Errors) {
+/// Functions annotated with `#[test]` are only kept in `test_mode`, their attributes and bodies
+/// being checked regardless.
+pub fn program(
+    prog: P::Program,
+    sender: Option<Address>,
+    test_mode: bool,
+) -> (E::Program, Errors) {
     let module_members = {
         let mut members = UniqueMap::new();
         all_module_members(&mut members, sender, &prog.lib_definitions);
         all_module_members(&mut members, sender, &prog.source_definitions);
         members
     };
-    let mut context = Context::new(module_members);
+    let mut context = Context::new(module_members, test_mode);
     let mut module_map = UniqueMap::new();
     let mut scripts = vec![];
 
@@ -308,6 +317,10 @@ fn script_(context: &mut Context, pscript: P::Script) -> E::Script {
         constant(context, &mut constants, c);
     }
 
+    if let Some(attribute) = pfunction.attributes.first() {
+        let msg = "Invalid attribute. Script functions cannot have attributes";
+        context.error(vec![(attribute.loc, msg)]);
+    }
     let (function_name, function) = function_(context, pfunction);
     if let FunctionVisibility::Public(loc) = &function.visibility {
         let msg = "Extraneous 'public' modifier. Script functions are always public";
@@ -686,7 +699,11 @@ fn function(
     functions: &mut UniqueMap<FunctionName, E::Function>,
     pfunction: P::Function,
 ) {
+    let is_test = pfunction.attributes.iter().any(|a| a.value == TEST_ATTRIBUTE);
     let (fname, fdef) = function_(context, pfunction);
+    if is_test && !context.test_mode {
+        return;
+    }
     if let Err(_old_loc) = functions.add(fname, fdef) {
         assert!(context.has_errors())
     }
@@ -695,6 +712,7 @@ fn function(
 fn function_(context: &mut Context, pfunction: P::Function) -> (FunctionName, E::Function) {
     let P::Function {
         loc,
+        attributes,
         name,
         visibility,
         signature: psignature,
@@ -702,6 +720,7 @@ fn function_(context: &mut Context, pfunction: P::Function) -> (FunctionName, E:
         acquires,
     } = pfunction;
     assert!(context.exp_specs.is_empty());
+    check_attributes(context, &attributes);
     let old_aliases = context.new_alias_scope(AliasMap::new());
     let signature = function_signature(context, psignature);
     let acquires = acquires
@@ -723,6 +742,28 @@ fn function_(context: &mut Context, pfunction: P::Function) -> (FunctionName, E:
     (name, fdef)
 }
 
+fn check_attributes(context: &mut Context, attributes: &[P::Attribute]) {
+    let mut seen = BTreeSet::new();
+    for attribute in attributes {
+        let name = attribute.value.as_str();
+        if !KNOWN_ATTRIBUTES.contains(&name) {
+            let msg = format!("Unknown attribute '{}'", name);
+            context.error(vec![(attribute.loc, msg)]);
+        } else if !seen.insert(name) {
+            let msg = format!("Duplicate attribute '{}'", name);
+            context.error(vec![(attribute.loc, msg)]);
+        } else if name == EXPECTED_FAILURE_ATTRIBUTE
+            && !attributes.iter().any(|a| a.value == TEST_ATTRIBUTE)
+        {
+            let msg = format!(
+                "Invalid attribute. '{}' is only allowed on '{}' functions",
+                EXPECTED_FAILURE_ATTRIBUTE, TEST_ATTRIBUTE
+            );
+            context.error(vec![(attribute.loc, msg)]);
+        }
+    }
+}
+
 fn function_signature(
     context: &mut Context,
     psignature: P::FunctionSignature,
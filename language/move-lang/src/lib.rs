@@ -19,6 +19,7 @@ pub mod shared;
 pub mod test_utils;
 mod to_bytecode;
 pub mod typing;
+pub mod unit_test;
 
 use anyhow::anyhow;
 use codespan::{ByteIndex, Span};
@@ -26,7 +27,7 @@ use compiled_unit::CompiledUnit;
 use errors::*;
 use move_ir_types::location::*;
 use parser::syntax::parse_file_string;
-use shared::Address;
+use shared::{Address, Flags};
 use std::{
     collections::{BTreeMap, HashMap},
     fs::File,
@@ -69,7 +70,7 @@ pub fn move_check_no_report(
 ) -> anyhow::Result<(FilesSourceText, Errors)> {
     let (files, pprog_and_comments_res) = parse_program(targets, deps)?;
     let pprog_res = pprog_and_comments_res.map(|(pprog, _)| pprog);
-    match check_program(pprog_res, sender_opt) {
+    match check_program(pprog_res, sender_opt, Flags::default(), false) {
         Err(errors) => Ok((files, errors)),
         Ok(_) => Ok((files, vec![])),
     }
@@ -84,10 +85,20 @@ pub fn move_compile(
     targets: &[String],
     deps: &[String],
     sender_opt: Option<Address>,
+) -> anyhow::Result<(FilesSourceText, Vec<CompiledUnit>)> {
+    move_compile_with_flags(targets, deps, sender_opt, Flags::default())
+}
+
+/// Move compile, with `flags` controlling the compiled code
+pub fn move_compile_with_flags(
+    targets: &[String],
+    deps: &[String],
+    sender_opt: Option<Address>,
+    flags: Flags,
 ) -> anyhow::Result<(FilesSourceText, Vec<CompiledUnit>)> {
     let (files, pprog_and_comments_res) = parse_program(targets, deps)?;
     let pprog_res = pprog_and_comments_res.map(|(pprog, _)| pprog);
-    match compile_program(pprog_res, sender_opt) {
+    match compile_program(pprog_res, sender_opt, flags) {
         Err(errors) => errors::report_errors(files, errors),
         Ok(compiled_units) => Ok((files, compiled_units)),
     }
@@ -98,10 +109,20 @@ pub fn move_compile_no_report(
     targets: &[String],
     deps: &[String],
     sender_opt: Option<Address>,
+) -> anyhow::Result<(FilesSourceText, Result<Vec<CompiledUnit>, Errors>)> {
+    move_compile_with_flags_no_report(targets, deps, sender_opt, Flags::default())
+}
+
+/// Move compile with `flags`, returning the errors instead of reporting them to stderr
+pub fn move_compile_with_flags_no_report(
+    targets: &[String],
+    deps: &[String],
+    sender_opt: Option<Address>,
+    flags: Flags,
 ) -> anyhow::Result<(FilesSourceText, Result<Vec<CompiledUnit>, Errors>)> {
     let (files, pprog_and_comments_res) = parse_program(targets, deps)?;
     let pprog_res = pprog_and_comments_res.map(|(pprog, _)| pprog);
-    Ok(match compile_program(pprog_res, sender_opt) {
+    Ok(match compile_program(pprog_res, sender_opt, flags) {
         Err(errors) => (files, Err(errors)),
         Ok(units) => (files, Ok(units)),
     })
@@ -120,13 +141,45 @@ pub fn move_compile_to_expansion_no_report(
 )> {
     let (files, pprog_and_comments_res) = parse_program(targets, deps)?;
     let res = pprog_and_comments_res.and_then(|(pprog, comment_map)| {
-        let (eprog, errors) = expansion::translate::program(pprog, sender_opt);
+        let (eprog, errors) = expansion::translate::program(pprog, sender_opt, false);
         check_errors(errors)?;
         Ok((eprog, comment_map))
     });
     Ok((files, res))
 }
 
+/// Given a set of targets and a set of dependencies
+/// - Compiles the targets and the dependencies to Move bytecode, tests included, so that they can
+///   be executed
+/// - Returns the unit tests declared in the targets, see `unit_test`
+/// Returns the errors instead of reporting them to stderr
+pub fn move_compile_unit_tests_no_report(
+    targets: &[String],
+    deps: &[String],
+    sender_opt: Option<Address>,
+) -> anyhow::Result<(
+    FilesSourceText,
+    Result<(Vec<CompiledUnit>, Vec<unit_test::TestFunction>), Errors>,
+)> {
+    let (files, pprog_and_comments_res) = parse_program(targets, deps)?;
+    let res = pprog_and_comments_res.and_then(|(pprog, _)| {
+        let tests = unit_test::test_functions(&pprog, sender_opt);
+        let parser::ast::Program {
+            mut source_definitions,
+            lib_definitions,
+        } = pprog;
+        source_definitions.extend(lib_definitions);
+        let pprog = parser::ast::Program {
+            source_definitions,
+            lib_definitions: vec![],
+        };
+        let cprog = check_program(Ok(pprog), sender_opt, Flags::default(), true)?;
+        let units = to_bytecode::translate::program(cprog)?;
+        Ok((units, tests))
+    });
+    Ok((files, res))
+}
+
 //**************************************************************************************************
 // Utils
 //**************************************************************************************************
@@ -204,13 +257,15 @@ pub fn output_compiled_units(
 fn check_program(
     prog: Result<parser::ast::Program, Errors>,
     sender_opt: Option<Address>,
+    flags: Flags,
+    test_mode: bool,
 ) -> Result<cfgir::ast::Program, Errors> {
-    let (eprog, errors) = expansion::translate::program(prog?, sender_opt);
+    let (eprog, errors) = expansion::translate::program(prog?, sender_opt, test_mode);
     let (nprog, errors) = naming::translate::program(eprog, errors);
     let (tprog, errors) = typing::translate::program(nprog, errors);
     check_errors(errors)?;
     let (hprog, errors) = hlir::translate::program(tprog);
-    let (cprog, errors) = cfgir::translate::program(errors, hprog);
+    let (cprog, errors) = cfgir::translate::program(errors, flags, hprog);
     check_errors(errors)?;
     Ok(cprog)
 }
@@ -218,8 +273,9 @@ fn check_program(
 fn compile_program(
     prog: Result<parser::ast::Program, Errors>,
     sender_opt: Option<Address>,
+    flags: Flags,
 ) -> Result<Vec<CompiledUnit>, Errors> {
-    let cprog = check_program(prog, sender_opt)?;
+    let cprog = check_program(prog, sender_opt, flags, false)?;
     to_bytecode::translate::program(cprog)
 }
 
@@ -137,6 +137,9 @@ pub enum StructFields {
 
 new_name!(FunctionName);
 
+// An attribute on a function, e.g. the `test` in `#[test]`
+pub type Attribute = Name;
+
 #[derive(PartialEq, Debug)]
 pub struct FunctionSignature {
     pub type_parameters: Vec<(Name, Kind)>,
@@ -164,6 +167,7 @@ pub type FunctionBody = Spanned<FunctionBody_>;
 // (public?) native foo<T1(: copyable?), ..., TN(: copyable?)>(x1: t1, ..., xn: tn): t1 * ... * tn;
 pub struct Function {
     pub loc: Loc,
+    pub attributes: Vec<Attribute>,
     pub visibility: FunctionVisibility,
     pub signature: FunctionSignature,
     pub acquires: Vec<ModuleAccess>,
@@ -1027,12 +1031,18 @@ impl AstDebug for Function {
     fn ast_debug(&self, w: &mut AstWriter) {
         let Function {
             loc: _loc,
+            attributes,
             visibility,
             signature,
             acquires,
             name,
             body,
         } = self;
+        if !attributes.is_empty() {
+            w.write("#[");
+            w.comma(attributes, |w, a| w.write(&a.value));
+            w.writeln("]");
+        }
         visibility.ast_debug(w);
         if let FunctionBody_::Native = &body.value {
             w.write("native ");
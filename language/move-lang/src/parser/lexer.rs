@@ -46,6 +46,7 @@ pub enum Tok {
     GreaterEqual,
     GreaterGreater,
     Caret,
+    NumSign,
     Abort,
     Acquires,
     As,
@@ -122,6 +123,7 @@ impl fmt::Display for Tok {
             GreaterEqual => ">=",
             GreaterGreater => ">>",
             Caret => "^",
+            NumSign => "#",
             Abort => "abort",
             Acquires => "acquires",
             As => "as",
@@ -411,6 +413,7 @@ fn find_token(file: &'static str, text: &str, start_offset: usize) -> Result<(To
         '/' => (Tok::Slash, 1),
         ';' => (Tok::Semicolon, 1),
         '^' => (Tok::Caret, 1),
+        '#' => (Tok::NumSign, 1),
         '{' => (Tok::LBrace, 1),
         '}' => (Tok::RBrace, 1),
         _ => {
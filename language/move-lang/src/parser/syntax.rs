@@ -1278,18 +1278,34 @@ fn parse_optional_type_parameters<'input>(
 // Functions
 //**************************************************************************************************
 
+// Parse the attributes of a function declaration:
+//      Attributes = ("#" "[" Comma<Identifier> "]")*
+fn parse_attributes<'input>(tokens: &mut Lexer<'input>) -> Result<Vec<Attribute>, Error> {
+    let mut attributes = vec![];
+    while match_token(tokens, Tok::NumSign)? {
+        attributes.extend(parse_comma_list(
+            tokens,
+            Tok::LBracket,
+            Tok::RBracket,
+            parse_identifier,
+            "an attribute",
+        )?);
+    }
+    Ok(attributes)
+}
+
 // Parse a function declaration:
 //      FunctionDecl =
 //          <NativeFunctionDecl>
 //          | <MoveFunctionDecl>
 //      NativeFunctionDecl =
-//          <DocComments> "native" ( "public" )? "fun"
+//          <DocComments> <Attributes> "native" ( "public" )? "fun"
 //          <FunctionDefName> "(" Comma<Parameter> ")"
 //          (":" <Type>)?
 //          ("acquires" <ModuleAccess> ("," <ModuleAccess>)*)?
 //          ";"
 //      MoveFunctionDecl =
-//          <DocComments> ( "public" )? "fun"
+//          <DocComments> <Attributes> ( "public" )? "fun"
 //          <FunctionDefName> "(" Comma<Parameter> ")"
 //          (":" <Type>)?
 //          ("acquires" <ModuleAccess> ("," <ModuleAccess>)*)?
@@ -1305,6 +1321,7 @@ fn parse_function_decl<'input>(
 ) -> Result<Function, Error> {
     tokens.match_doc_comments();
     let start_loc = tokens.start_loc();
+    let attributes = parse_attributes(tokens)?;
     // Record the source location of the "native" keyword (if there is one).
     let native_opt = if allow_native {
         consume_optional_token_with_loc(tokens, Tok::Native)?
@@ -1392,6 +1409,7 @@ fn parse_function_decl<'input>(
     let loc = make_loc(tokens.file_name(), start_loc, tokens.previous_end_loc());
     Ok(Function {
         loc,
+        attributes,
         visibility,
         signature,
         acquires,
@@ -181,6 +181,18 @@ pub fn shortest_cycle<'a, T: Ord + Hash>(
     path
 }
 
+//**************************************************************************************************
+// Flags
+//**************************************************************************************************
+
+/// Options which change the compiled code, but not which programs are accepted.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Flags {
+    /// Skip the optimizations of function bodies, such as constant folding and dead code
+    /// elimination. Module constants are always folded.
+    pub no_optimize: bool,
+}
+
 //**************************************************************************************************
 // Counter
 //**************************************************************************************************
@@ -101,7 +101,9 @@ fn script(context: &mut Context, nscript: N::Script) -> T::Script {
     }
 }
 
-fn check_primitive_script_arg(context: &mut Context, mloc: Loc, idx: usize, ty: &Type) {
+/// Checks the type of a script parameter. `&signer` parameters are only allowed before all other
+/// parameters; `after_non_signer` is true if a parameter of another type was already declared.
+fn check_primitive_script_arg(context: &mut Context, mloc: Loc, after_non_signer: bool, ty: &Type) {
     let loc = ty.loc;
 
     let signer_ref = sp(loc, Type_::Ref(false, Box::new(Type_::signer(loc))));
@@ -112,6 +114,7 @@ fn check_primitive_script_arg(context: &mut Context, mloc: Loc, idx: usize, ty:
         Type_::bool(loc),
         Type_::address(loc),
         Type_::vector(loc, Type_::u8(loc)),
+        Type_::vector(loc, Type_::vector(loc, Type_::u8(loc))),
         signer_ref.clone(),
     ];
     let ty_is_unacceptable = acceptable_types.iter().all(|acceptable_type| {
@@ -134,13 +137,13 @@ fn check_primitive_script_arg(context: &mut Context, mloc: Loc, idx: usize, ty:
         return;
     }
 
-    if idx != 0 && subtype_no_report(context, ty.clone(), signer_ref.clone()).is_ok() {
+    if after_non_signer && is_signer_ref(context, ty) {
         let mmsg = format!(
             "Invalid parameter for script function '{}'",
             context.current_function.as_ref().unwrap()
         );
         let tmsg = format!(
-            "{} must be the first argument to a script",
+            "{} arguments must come before all other arguments to a script",
             core::error_format(&signer_ref, &Subst::empty()),
         );
         context.error(vec![(mloc, mmsg), (loc, tmsg)]);
@@ -148,6 +151,12 @@ fn check_primitive_script_arg(context: &mut Context, mloc: Loc, idx: usize, ty:
     }
 }
 
+fn is_signer_ref(context: &mut Context, ty: &Type) -> bool {
+    let loc = ty.loc;
+    let signer_ref = sp(loc, Type_::Ref(false, Box::new(Type_::signer(loc))));
+    subtype_no_report(context, ty.clone(), signer_ref).is_ok()
+}
+
 //**************************************************************************************************
 // Functions
 //**************************************************************************************************
@@ -171,8 +180,10 @@ fn function(
 
     function_signature(context, &signature);
     if is_script {
-        for (idx, (_, param_ty)) in signature.parameters.iter().enumerate() {
-            check_primitive_script_arg(context, loc, idx, param_ty);
+        let mut after_non_signer = false;
+        for (_, param_ty) in &signature.parameters {
+            check_primitive_script_arg(context, loc, after_non_signer, param_ty);
+            after_non_signer = after_non_signer || !is_signer_ref(context, param_ty);
         }
         subtype(
             context,
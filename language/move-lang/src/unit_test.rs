@@ -0,0 +1,80 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Discovery of unit tests in Move sources.
+//!
+//! A unit test is a module function annotated with `#[test]`. A test also annotated with
+//! `#[expected_failure]` is expected to abort. Running the tests is left to a separate runner,
+//! this only finds them. Tests are only compiled along with the code they test when compiling
+//! for unit tests, and are left out of the compiled modules otherwise.
+
+use crate::{
+    parser::ast::{Definition, Function, ModuleDefinition, ModuleMember, Program},
+    shared::{Address, Identifier},
+};
+use move_ir_types::location::Loc;
+
+pub const TEST_ATTRIBUTE: &str = "test";
+pub const EXPECTED_FAILURE_ATTRIBUTE: &str = "expected_failure";
+
+/// The attributes accepted on functions.
+pub const KNOWN_ATTRIBUTES: &[&str] = &[TEST_ATTRIBUTE, EXPECTED_FAILURE_ATTRIBUTE];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestFunction {
+    pub loc: Loc,
+    pub address: Address,
+    pub module_name: String,
+    pub function_name: String,
+    pub expected_failure: bool,
+}
+
+impl TestFunction {
+    /// The name of the test, in the form `Module::function`.
+    pub fn name(&self) -> String {
+        format!("{}::{}", self.module_name, self.function_name)
+    }
+}
+
+/// Returns the tests declared in the source definitions of `prog`, in declaration order.
+/// Modules without an address are published under `sender_opt`, and are skipped if there is
+/// none, expansion reporting the missing address.
+pub fn test_functions(prog: &Program, sender_opt: Option<Address>) -> Vec<TestFunction> {
+    let mut tests = vec![];
+    for def in &prog.source_definitions {
+        match def {
+            Definition::Module(module) => {
+                if let Some(address) = sender_opt {
+                    module_tests(&mut tests, address, module)
+                }
+            }
+            Definition::Address(_, address, modules) => {
+                for module in modules {
+                    module_tests(&mut tests, *address, module)
+                }
+            }
+            Definition::Script(_) => (),
+        }
+    }
+    tests
+}
+
+fn module_tests(tests: &mut Vec<TestFunction>, address: Address, module: &ModuleDefinition) {
+    for member in &module.members {
+        if let ModuleMember::Function(function) = member {
+            if has_attribute(function, TEST_ATTRIBUTE) {
+                tests.push(TestFunction {
+                    loc: function.loc,
+                    address,
+                    module_name: module.name.value().to_string(),
+                    function_name: function.name.value().to_string(),
+                    expected_failure: has_attribute(function, EXPECTED_FAILURE_ATTRIBUTE),
+                })
+            }
+        }
+    }
+}
+
+fn has_attribute(function: &Function, attribute: &str) -> bool {
+    function.attributes.iter().any(|a| a.value == attribute)
+}
@@ -0,0 +1,5 @@
+module M {
+    #[test]
+    #[test]
+    fun f() {}
+}
@@ -0,0 +1,4 @@
+module M {
+    #[expected_failure]
+    fun f() {}
+}
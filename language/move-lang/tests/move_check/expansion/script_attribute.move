@@ -0,0 +1,4 @@
+script {
+    #[test]
+    fun main() {}
+}
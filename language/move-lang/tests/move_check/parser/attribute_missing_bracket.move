@@ -0,0 +1,4 @@
+module M {
+    #test
+    fun f() {}
+}
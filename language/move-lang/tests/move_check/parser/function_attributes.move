@@ -0,0 +1,11 @@
+module M {
+    #[test]
+    fun f() {}
+
+    #[test, expected_failure]
+    fun g() { abort 0 }
+
+    #[test]
+    #[expected_failure]
+    public fun h(_s: &signer) { abort 0 }
+}
@@ -0,0 +1,7 @@
+script {
+fun main() {
+    0;
+    1 + 2;
+    if (true) () else abort 42;
+}
+}
@@ -0,0 +1,7 @@
+script {
+fun main() {
+    let x = 1 + 2 * 3;
+    if (x == 7) x = x + 1 else abort 42;
+    assert(x == 8, 43);
+}
+}
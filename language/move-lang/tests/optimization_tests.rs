@@ -0,0 +1,58 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_lang::{
+    compiled_unit::{self, CompiledUnit},
+    errors, move_compile_with_flags_no_report,
+    shared::{Address, Flags},
+    test_utils::*,
+};
+
+const FOLDING: &str = "tests/optimization/folding.move";
+const DEAD_CODE: &str = "tests/optimization/dead_code.move";
+
+/// Compiles `path` with `flags`, checks that the units verify, and returns the code size of each
+/// function.
+fn code_sizes(path: &str, flags: Flags) -> Vec<usize> {
+    let targets = vec![path.to_owned()];
+    let deps = stdlib_files(STD_LIB_DIR);
+    let sender = Some(Address::parse_str(SENDER).unwrap());
+    let (files, units_or_errors) =
+        move_compile_with_flags_no_report(&targets, &deps, sender, flags).unwrap();
+    let units = match units_or_errors {
+        Ok(units) => units,
+        Err(errors) => panic!(
+            "{}",
+            String::from_utf8_lossy(&errors::report_errors_to_buffer(files, errors))
+        ),
+    };
+    let (units, errors) = compiled_unit::verify_units(units);
+    assert!(errors.is_empty(), "compiled units must verify");
+    units
+        .iter()
+        .flat_map(|unit| match unit {
+            CompiledUnit::Module { module, .. } => module
+                .as_inner()
+                .function_defs
+                .iter()
+                .map(|def| def.code.as_ref().map_or(0, |code| code.code.len()))
+                .collect(),
+            CompiledUnit::Script { script, .. } => vec![script.as_inner().code.code.len()],
+        })
+        .collect()
+}
+
+#[test]
+fn optimizations_shrink_code() {
+    let optimized = code_sizes(FOLDING, Flags::default());
+    let unoptimized = code_sizes(FOLDING, Flags { no_optimize: true });
+    assert_eq!(optimized.len(), unoptimized.len());
+    assert!(optimized.iter().sum::<usize>() < unoptimized.iter().sum::<usize>());
+}
+
+#[test]
+fn optimizations_remove_dead_code() {
+    // the popped values and the unreachable branch are removed, leaving only the return
+    assert_eq!(code_sizes(DEAD_CODE, Flags::default()), vec![1]);
+    assert!(code_sizes(DEAD_CODE, Flags { no_optimize: true })[0] > 1);
+}
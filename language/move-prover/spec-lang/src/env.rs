@@ -586,6 +586,22 @@ impl GlobalEnv {
         }
     }
 
+    /// Calls `f` on each accumulated diagnostic with the given severity or worse, in the order
+    /// in which they were added.
+    pub fn visit_diags<F>(&self, min_severity: Severity, mut f: F)
+    where
+        F: FnMut(&Diagnostic),
+    {
+        for diag in self
+            .diags
+            .borrow()
+            .iter()
+            .filter(|d| d.severity >= min_severity)
+        {
+            f(diag)
+        }
+    }
+
     /// Adds a new module to the environment. StructData and FunctionData need to be provided
     /// in definition index order. See `create_function_data` and `create_struct_data` for how
     /// to create them.
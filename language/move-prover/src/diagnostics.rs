@@ -0,0 +1,96 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Structured diagnostics of a prover run, for clients which want to inspect the results of
+//! verification programmatically instead of reading rendered error messages.
+
+use codespan_reporting::diagnostic::{Diagnostic, Severity};
+use serde::{Deserialize, Serialize};
+use spec_lang::env::{GlobalEnv, Loc};
+
+/// The severity of a diagnostic reported by the prover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum DiagnosticSeverity {
+    Note,
+    Warning,
+    Error,
+}
+
+/// A diagnostic reported by the prover, with its primary location resolved to a position in
+/// a source file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProverDiagnostic {
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+    /// The file the diagnostic points to, if the location is known.
+    pub file: Option<String>,
+    /// The 1-based line the diagnostic points to, if the location is known.
+    pub line: Option<usize>,
+    /// The 1-based column the diagnostic points to, if the location is known.
+    pub column: Option<usize>,
+    /// Additional information, like the execution trace leading to a verification error.
+    pub notes: Vec<String>,
+}
+
+/// The diagnostics of a prover run, in the order in which they were reported.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProverReport {
+    pub diagnostics: Vec<ProverDiagnostic>,
+}
+
+impl ProverReport {
+    /// Collects the diagnostics accumulated in the environment.
+    pub(crate) fn from_env(env: &GlobalEnv) -> Self {
+        let mut diagnostics = vec![];
+        env.visit_diags(Severity::Note, |diag| {
+            diagnostics.push(ProverDiagnostic::from_diag(env, diag))
+        });
+        Self { diagnostics }
+    }
+
+    /// Returns true if the run reported errors, in which case the sources did not verify.
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    /// Returns the diagnostics with error severity.
+    pub fn errors(&self) -> impl Iterator<Item = &ProverDiagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == DiagnosticSeverity::Error)
+    }
+
+    /// Returns the diagnostics with warning severity.
+    pub fn warnings(&self) -> impl Iterator<Item = &ProverDiagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == DiagnosticSeverity::Warning)
+    }
+}
+
+impl ProverDiagnostic {
+    fn from_diag(env: &GlobalEnv, diag: &Diagnostic) -> Self {
+        let severity = match diag.severity {
+            Severity::Bug | Severity::Error => DiagnosticSeverity::Error,
+            Severity::Warning => DiagnosticSeverity::Warning,
+            Severity::Note | Severity::Help => DiagnosticSeverity::Note,
+        };
+        let label = &diag.primary_label;
+        let (file, line, column) = match env.get_position(Loc::new(label.file_id, label.span)) {
+            Some((file, location)) => (
+                Some(file),
+                Some(location.line.0 as usize + 1),
+                Some(location.column.0 as usize + 1),
+            ),
+            None => (None, None, None),
+        };
+        Self {
+            severity,
+            message: diag.message.clone(),
+            file,
+            line,
+            column,
+            notes: diag.notes.clone(),
+        }
+    }
+}
@@ -3,6 +3,8 @@
 
 #![forbid(unsafe_code)]
 
+pub use crate::diagnostics::{DiagnosticSeverity, ProverDiagnostic, ProverReport};
+
 use crate::{
     boogie_wrapper::BoogieWrapper,
     bytecode_translator::BoogieTranslator,
@@ -11,7 +13,7 @@ use crate::{
 };
 use abigen::Abigen;
 use anyhow::anyhow;
-use codespan_reporting::term::termcolor::{ColorChoice, StandardStream, WriteColor};
+use codespan_reporting::term::termcolor::{Buffer, ColorChoice, StandardStream, WriteColor};
 use docgen::Docgen;
 use handlebars::Handlebars;
 use itertools::Itertools;
@@ -45,6 +47,7 @@ mod boogie_helpers;
 mod boogie_wrapper;
 mod bytecode_translator;
 pub mod cli;
+mod diagnostics;
 mod prelude_template_helpers;
 mod spec_translator;
 
@@ -59,11 +62,42 @@ pub fn run_move_prover<W: WriteColor>(
     options: Options,
 ) -> anyhow::Result<()> {
     let now = Instant::now();
+    let mut env = create_env(&options)?;
+    run_move_prover_in_env(error_writer, &mut env, &options, now)
+}
+
+/// Runs the prover like `run_move_prover`, but instead of writing diagnostics, returns them
+/// as structured data. An error is only returned if the prover could not run to completion
+/// for reasons other than errors in the verified sources, for example if Boogie could not
+/// be started.
+pub fn run_move_prover_with_diagnostics(options: Options) -> anyhow::Result<ProverReport> {
+    let now = Instant::now();
+    let mut env = create_env(&options)?;
+    let mut error_writer = Buffer::no_color();
+    let result = run_move_prover_in_env(&mut error_writer, &mut env, &options, now);
+    if let Err(err) = result {
+        if !env.has_errors() {
+            return Err(err);
+        }
+    }
+    Ok(ProverReport::from_env(&env))
+}
+
+/// Parses and checks the sources and their dependencies.
+fn create_env(options: &Options) -> anyhow::Result<GlobalEnv> {
     let sources = find_move_filenames(&options.move_sources)?;
     let deps = calculate_deps(&sources, &find_move_filenames(&options.move_deps)?)?;
     let address = Some(options.account_address.as_ref());
     debug!("parsing and checking sources");
-    let mut env: GlobalEnv = run_spec_lang_compiler(sources, deps, address)?;
+    run_spec_lang_compiler(sources, deps, address)
+}
+
+fn run_move_prover_in_env<W: WriteColor>(
+    error_writer: &mut W,
+    env: &mut GlobalEnv,
+    options: &Options,
+    now: Instant,
+) -> anyhow::Result<()> {
     if env.has_errors() {
         env.report_errors(error_writer);
         return Err(anyhow!("exiting with checking errors"));
@@ -74,20 +108,20 @@ pub fn run_move_prover<W: WriteColor>(
 
     // Until this point, prover and docgen have same code. Here we part ways.
     if options.run_docgen {
-        return run_docgen(&env, &options, now);
+        return run_docgen(env, options, now);
     }
     // Same for ABI generator.
     if options.run_abigen {
-        return run_abigen(&env, &options, now);
+        return run_abigen(env, options, now);
     }
-    let targets = create_and_process_bytecode(&options, &env);
+    let targets = create_and_process_bytecode(options, env);
     if env.has_errors() {
         env.report_errors(error_writer);
         return Err(anyhow!("exiting with transformation errors"));
     }
     let writer = CodeWriter::new(env.internal_loc());
-    add_prelude(&options, &writer)?;
-    let mut translator = BoogieTranslator::new(&env, &options, &targets, &writer);
+    add_prelude(options, &writer)?;
+    let mut translator = BoogieTranslator::new(env, options, &targets, &writer);
     translator.translate();
     if env.has_errors() {
         env.report_errors(error_writer);
@@ -100,10 +134,10 @@ pub fn run_move_prover<W: WriteColor>(
         let boogie_file_id =
             writer.process_result(|result| env.add_source(&options.output_path, result, false));
         let boogie = BoogieWrapper {
-            env: &env,
+            env,
             targets: &targets,
             writer: &writer,
-            options: &options,
+            options,
             boogie_file_id,
         };
         boogie.call_boogie_and_verify_output(options.backend.bench_repeat, &options.output_path)?;
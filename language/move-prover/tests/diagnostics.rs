@@ -0,0 +1,39 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use libra_temppath::TempPath;
+use move_prover::{cli::Options, run_move_prover_with_diagnostics, DiagnosticSeverity};
+
+const SOURCE: &str = r#"
+module TestDiagnostics {
+    fun identity(x: u64): u64 { x }
+    spec fun identity {
+        ensures result == y;
+    }
+}
+"#;
+
+#[test]
+fn checking_errors_are_reported_as_diagnostics() {
+    let temp_dir = TempPath::new();
+    std::fs::create_dir_all(temp_dir.path()).unwrap();
+    let source_path = temp_dir.path().join("diagnostics.move");
+    std::fs::write(&source_path, SOURCE).unwrap();
+
+    let mut options = Options::default();
+    options.move_sources = vec![source_path.to_string_lossy().to_string()];
+    options.output_path = temp_dir
+        .path()
+        .join("output.bpl")
+        .to_string_lossy()
+        .to_string();
+    options.prover.generate_only = true;
+    options.setup_logging_for_test();
+
+    let report = run_move_prover_with_diagnostics(options).unwrap();
+    assert!(report.has_errors());
+    let error = report.errors().next().unwrap();
+    assert_eq!(error.severity, DiagnosticSeverity::Error);
+    assert!(error.file.as_ref().unwrap().ends_with("diagnostics.move"));
+    assert_eq!(error.line, Some(5));
+}
@@ -0,0 +1,50 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use libra_metrics::{
+    register_int_counter, register_int_counter_vec, register_int_gauge_vec, IntCounter,
+    IntCounterVec, IntGaugeVec,
+};
+use once_cell::sync::Lazy;
+
+/// Count the number of lookups served by the loader caches, with a "cache" label to
+/// distinguish modules, scripts and types.
+pub static LOADER_CACHE_HITS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "libra_move_vm_loader_cache_hits",
+        "Number of lookups served by the loader caches",
+        &["cache"]
+    )
+    .unwrap()
+});
+
+/// Count the number of lookups that missed the loader caches, with a "cache" label to
+/// distinguish modules, scripts and types.
+pub static LOADER_CACHE_MISSES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "libra_move_vm_loader_cache_misses",
+        "Number of lookups that missed the loader caches",
+        &["cache"]
+    )
+    .unwrap()
+});
+
+/// Size of the loader caches: bytes of binaries for modules and scripts, number of entries for
+/// types.
+pub static LOADER_CACHE_SIZE: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "libra_move_vm_loader_cache_size",
+        "Size of the loader caches",
+        &["cache"]
+    )
+    .unwrap()
+});
+
+/// Count the number of times the loader caches were flushed for exceeding their budget.
+pub static LOADER_CACHE_FLUSHES: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "libra_move_vm_loader_cache_flushes",
+        "Number of times the loader caches were flushed"
+    )
+    .unwrap()
+});
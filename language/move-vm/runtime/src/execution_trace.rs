@@ -0,0 +1,88 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Opt-in recording of everything the interpreter does while executing a transaction.
+//!
+//! Unlike the `MOVE_VM_TRACE` file tracing in debug builds, an `ExecutionTrace` is attached to a
+//! `Session` and can be inspected programmatically once execution is done. It is meant for tests
+//! and tooling (step debuggers, abort diagnostics) and has a significant cost, so it must not be
+//! enabled on production paths.
+
+use std::sync::{Arc, Mutex};
+use vm::file_format::Bytecode;
+
+/// A single step of execution.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TraceEvent {
+    /// A bytecode instruction is about to be executed.
+    Instruction {
+        /// Fully qualified name of the function being executed.
+        function: String,
+        pc: u16,
+        instruction: Bytecode,
+        /// Height of the operand stack before the instruction is executed. The stack effect of
+        /// an instruction is the difference with the height recorded in the following event.
+        stack_height: usize,
+        /// The locals of the function before the instruction is executed, rendered as strings.
+        /// Only recorded by traces created with `ExecutionTrace::with_locals`.
+        locals: Vec<String>,
+    },
+    /// A native function is about to be called.
+    NativeCall {
+        /// Fully qualified name of the native function.
+        function: String,
+        num_ty_args: usize,
+        num_args: usize,
+        stack_height: usize,
+    },
+}
+
+impl TraceEvent {
+    pub fn stack_height(&self) -> usize {
+        match self {
+            TraceEvent::Instruction { stack_height, .. }
+            | TraceEvent::NativeCall { stack_height, .. } => *stack_height,
+        }
+    }
+}
+
+/// A handle to a buffer of `TraceEvent`s. Clones share the same buffer, so a handle can be given
+/// to a `Session` and read back by the caller after the session is gone.
+#[derive(Clone, Debug, Default)]
+pub struct ExecutionTrace {
+    events: Arc<Mutex<Vec<TraceEvent>>>,
+    capture_locals: bool,
+}
+
+impl ExecutionTrace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a trace which also records the locals of the executing function with every
+    /// instruction. This is considerably more expensive and meant for debuggers.
+    pub fn with_locals() -> Self {
+        Self {
+            events: Arc::default(),
+            capture_locals: true,
+        }
+    }
+
+    pub(crate) fn captures_locals(&self) -> bool {
+        self.capture_locals
+    }
+
+    pub(crate) fn record(&self, event: TraceEvent) {
+        self.events.lock().unwrap().push(event)
+    }
+
+    /// Returns a copy of the events recorded so far.
+    pub fn events(&self) -> Vec<TraceEvent> {
+        self.events.lock().unwrap().clone()
+    }
+
+    /// Returns the events recorded so far and clears the buffer.
+    pub fn take_events(&self) -> Vec<TraceEvent> {
+        std::mem::take(&mut *self.events.lock().unwrap())
+    }
+}
@@ -3,6 +3,7 @@
 
 use crate::{
     data_operations::{borrow_global, move_resource_from, move_resource_to, resource_exists},
+    execution_trace::{ExecutionTrace, TraceEvent},
     loader::{Function, Loader, Resolver},
     native_functions::FunctionContext,
     trace,
@@ -11,7 +12,7 @@ use libra_logger::prelude::*;
 use move_core_types::{
     account_address::AccountAddress,
     gas_schedule::{AbstractMemorySize, GasAlgebra, GasCarrier},
-    vm_status::{StatusCode, StatusType},
+    vm_status::{AbortFrame, StatusCode, StatusType},
 };
 use move_vm_types::{
     data_store::DataStore,
@@ -53,6 +54,8 @@ pub(crate) struct Interpreter {
     operand_stack: Stack,
     /// The stack of active functions.
     call_stack: CallStack,
+    /// If set, every instruction and native call is recorded there.
+    trace: Option<ExecutionTrace>,
 }
 
 impl Interpreter {
@@ -65,19 +68,21 @@ impl Interpreter {
         data_store: &mut dyn DataStore,
         cost_strategy: &mut CostStrategy,
         loader: &Loader,
+        trace: Option<ExecutionTrace>,
     ) -> VMResult<()> {
         // We count the intrinsic cost of the transaction here, since that needs to also cover the
         // setup of the function.
-        let mut interp = Self::new();
+        let mut interp = Self::new(trace);
         interp.execute(loader, data_store, cost_strategy, function, ty_args, args)
     }
 
     /// Create a new instance of an `Interpreter` in the context of a transaction with a
     /// given module cache and gas schedule.
-    fn new() -> Self {
+    fn new(trace: Option<ExecutionTrace>) -> Self {
         Interpreter {
             operand_stack: Stack::new(),
             call_stack: CallStack::new(),
+            trace,
         }
     }
 
@@ -248,6 +253,14 @@ impl Interpreter {
         function: Arc<Function>,
         ty_args: Vec<Type>,
     ) -> PartialVMResult<()> {
+        if let Some(trace) = &self.trace {
+            trace.record(TraceEvent::NativeCall {
+                function: function.pretty_string(),
+                num_ty_args: ty_args.len(),
+                num_args: function.arg_count(),
+                stack_height: self.operand_stack.0.len(),
+            });
+        }
         let mut arguments = VecDeque::new();
         let expected_args = function.arg_count();
         for _ in 0..expected_args {
@@ -657,6 +670,19 @@ impl Frame {
         loop {
             for instruction in &code[self.pc as usize..] {
                 trace!(self.function.pretty_string(), self.pc, instruction);
+                if let Some(trace) = &interpreter.trace {
+                    trace.record(TraceEvent::Instruction {
+                        function: self.function.pretty_string(),
+                        pc: self.pc,
+                        instruction: instruction.clone(),
+                        stack_height: interpreter.operand_stack.0.len(),
+                        locals: if trace.captures_locals() {
+                            self.locals.display_each()
+                        } else {
+                            vec![]
+                        },
+                    });
+                }
                 self.pc += 1;
 
                 match instruction {
@@ -948,12 +974,18 @@ impl Frame {
                     Bytecode::Abort => {
                         cost_strategy.charge_instr(Opcodes::ABORT)?;
                         let error_code = interpreter.operand_stack.pop_as::<u64>()?;
+                        // `self.pc` was already advanced past the `abort`.
+                        let code_offset = self.pc - 1;
                         return Err(PartialVMError::new(StatusCode::ABORTED)
                             .with_sub_status(error_code)
+                            .with_abort_frame(AbortFrame {
+                                function: self.function.identifier().to_owned(),
+                                code_offset,
+                            })
                             .with_message(format!(
                                 "{} at offset {}",
                                 self.function.pretty_string(),
-                                self.pc,
+                                code_offset,
                             )));
                     }
                     Bytecode::Eq => {
@@ -12,8 +12,10 @@
 #[macro_use]
 extern crate mirai_annotations;
 
+mod counters;
 pub mod data_cache;
 mod data_operations;
+pub mod execution_trace;
 mod interpreter;
 mod loader;
 pub mod move_vm;
@@ -22,3 +24,6 @@ mod runtime;
 pub mod session;
 #[macro_use]
 mod tracing;
+
+#[cfg(feature = "native_plugins")]
+pub use native_functions::{NativeFunctionImpl, NativeFunctionTable};
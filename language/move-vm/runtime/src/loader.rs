@@ -1,11 +1,15 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::native_functions::NativeFunction;
+use crate::{
+    counters,
+    native_functions::{NativeFunction, NativeFunctionTable, RegisteredNative},
+};
 use bytecode_verifier::{
     constants, instantiation_loops::InstantiationLoopChecker, verify_main_signature,
     CodeUnitVerifier, DependencyChecker, DuplicationChecker, InstructionConsistency,
-    RecursiveStructDefChecker, ResourceTransitiveChecker, SignatureChecker,
+    LimitsVerifier, RecursiveStructDefChecker, ResourceTransitiveChecker, SignatureChecker,
+    VerifierConfig,
 };
 use libra_crypto::HashValue;
 use libra_logger::prelude::*;
@@ -80,12 +84,15 @@ where
 // does not require further verification (except for parameters and type parameters)
 struct ScriptCache {
     scripts: BinaryCache<HashValue, Script>,
+    // total size of the serialized scripts in the cache
+    binary_size: usize,
 }
 
 impl ScriptCache {
     fn new() -> Self {
         Self {
             scripts: BinaryCache::new(),
+            binary_size: 0,
         }
     }
 
@@ -93,10 +100,21 @@ impl ScriptCache {
         self.scripts.get(hash).map(|script| script.entry_point())
     }
 
-    fn insert(&mut self, hash: HashValue, script: Script) -> PartialVMResult<Arc<Function>> {
+    fn insert(
+        &mut self,
+        hash: HashValue,
+        script: Script,
+        size: usize,
+    ) -> PartialVMResult<Arc<Function>> {
         match self.get(&hash) {
             Some(script) => Ok(script),
-            None => Ok(self.scripts.insert(hash, script).entry_point()),
+            None => {
+                self.binary_size += size;
+                counters::LOADER_CACHE_SIZE
+                    .with_label_values(&["scripts"])
+                    .set(self.binary_size as i64);
+                Ok(self.scripts.insert(hash, script).entry_point())
+            }
         }
     }
 }
@@ -109,6 +127,8 @@ pub struct ModuleCache {
     modules: BinaryCache<ModuleId, Module>,
     structs: Vec<Arc<StructType>>,
     functions: Vec<Arc<Function>>,
+    // total size of the serialized modules in the cache
+    binary_size: usize,
 }
 
 impl ModuleCache {
@@ -117,6 +137,7 @@ impl ModuleCache {
             modules: BinaryCache::new(),
             structs: vec![],
             functions: vec![],
+            binary_size: 0,
         }
     }
 
@@ -145,16 +166,28 @@ impl ModuleCache {
     // The VM is pretty much stopped waiting for this to finish
     //
 
-    fn insert(&mut self, id: ModuleId, module: CompiledModule) -> VMResult<Arc<Module>> {
+    fn insert(
+        &mut self,
+        id: ModuleId,
+        module: CompiledModule,
+        size: usize,
+        natives: &NativeFunctionTable,
+    ) -> VMResult<Arc<Module>> {
         if let Some(module) = self.module_at(&id) {
             return Ok(module);
         }
 
         // we need this operation to be transactional, if an error occurs we must
         // leave a clean state
-        self.add_module(&module)?;
+        self.add_module(&module, natives)?;
         match Module::new(module, self) {
-            Ok(module) => Ok(Arc::clone(self.modules.insert(id, module))),
+            Ok(module) => {
+                self.binary_size += size;
+                counters::LOADER_CACHE_SIZE
+                    .with_label_values(&["modules"])
+                    .set(self.binary_size as i64);
+                Ok(Arc::clone(self.modules.insert(id, module)))
+            }
             Err((err, module)) => {
                 // remove all structs and functions that have been pushed
                 let strut_def_count = module.struct_defs().len();
@@ -167,7 +200,11 @@ impl ModuleCache {
         }
     }
 
-    fn add_module(&mut self, module: &CompiledModule) -> VMResult<()> {
+    fn add_module(
+        &mut self,
+        module: &CompiledModule,
+        natives: &NativeFunctionTable,
+    ) -> VMResult<()> {
         let starting_idx = self.structs.len();
         for (idx, struct_def) in module.struct_defs().iter().enumerate() {
             let st = self.make_struct_type(module, struct_def, StructDefinitionIndex(idx as u16));
@@ -179,7 +216,7 @@ impl ModuleCache {
             Err(err.finish(Location::Undefined))
         })?;
         for func in module.function_defs() {
-            let function = Function::new(func, module);
+            let function = Function::new(func, module, natives);
             self.functions.push(Arc::new(function));
         }
         Ok(())
@@ -405,15 +442,72 @@ pub(crate) struct Loader {
     scripts: Mutex<ScriptCache>,
     module_cache: Mutex<ModuleCache>,
     type_cache: Mutex<TypeCache>,
+    config: LoaderConfig,
+    natives: NativeFunctionTable,
+}
+
+/// Memory budget for the caches of the `Loader`.
+///
+/// Loaded modules, scripts and types refer to each other by index, so entries cannot be evicted
+/// one at a time. Once a budget is exceeded all caches are flushed together, the next time the
+/// loader is not used by any session.
+#[derive(Clone, Copy, Debug)]
+pub struct LoaderConfig {
+    /// Maximum total size, in bytes, of the serialized modules and scripts kept loaded.
+    pub max_binary_size: usize,
+    /// Maximum number of struct instantiations kept in the type cache.
+    pub max_type_cache_entries: usize,
+    /// Complexity limits enforced on modules and scripts before they are verified further.
+    pub verifier_config: VerifierConfig,
+}
+
+impl Default for LoaderConfig {
+    fn default() -> Self {
+        Self {
+            max_binary_size: 64 * 1024 * 1024,
+            max_type_cache_entries: 100_000,
+            verifier_config: VerifierConfig::default(),
+        }
+    }
 }
 
 impl Loader {
-    pub(crate) fn new() -> Self {
-        //println!("new loader");
+    pub(crate) fn new(config: LoaderConfig, natives: NativeFunctionTable) -> Self {
         Self {
             scripts: Mutex::new(ScriptCache::new()),
             module_cache: Mutex::new(ModuleCache::new()),
             type_cache: Mutex::new(TypeCache::new()),
+            config,
+            natives,
+        }
+    }
+
+    // Drops everything loaded if any of the caches is over budget.
+    // Must only be called when no session uses the loader: values and frames of a running
+    // session refer to the cached functions and types by index.
+    pub(crate) fn flush_if_over_budget(&self) {
+        let mut scripts = self.scripts.lock().unwrap();
+        let mut module_cache = self.module_cache.lock().unwrap();
+        let mut type_cache = self.type_cache.lock().unwrap();
+        let binary_size = scripts.binary_size + module_cache.binary_size;
+        if binary_size <= self.config.max_binary_size
+            && type_cache.num_entries <= self.config.max_type_cache_entries
+        {
+            return;
+        }
+
+        info!(
+            "[VM] Flushing loader caches: {} bytes of binaries, {} type cache entries",
+            binary_size, type_cache.num_entries
+        );
+        *scripts = ScriptCache::new();
+        *module_cache = ModuleCache::new();
+        *type_cache = TypeCache::new();
+        counters::LOADER_CACHE_FLUSHES.inc();
+        for cache in &["scripts", "modules", "types"] {
+            counters::LOADER_CACHE_SIZE
+                .with_label_values(&[cache])
+                .set(0);
         }
     }
 
@@ -439,15 +533,23 @@ impl Loader {
         let hash_value = HashValue::sha3_256_of(script_blob);
         let opt_main = self.scripts.lock().unwrap().get(&hash_value);
         let main = match opt_main {
-            Some(main) => main,
+            Some(main) => {
+                counters::LOADER_CACHE_HITS
+                    .with_label_values(&["scripts"])
+                    .inc();
+                main
+            }
             None => {
+                counters::LOADER_CACHE_MISSES
+                    .with_label_values(&["scripts"])
+                    .inc();
                 let ver_script = self.deserialize_and_verify_script(script_blob, data_store)?;
                 let script =
                     Script::new(ver_script, &hash_value, &self.module_cache.lock().unwrap())?;
                 self.scripts
                     .lock()
                     .unwrap()
-                    .insert(hash_value, script)
+                    .insert(hash_value, script, script_blob.len())
                     .map_err(|e| e.finish(Location::Script))?
             }
         };
@@ -510,6 +612,7 @@ impl Loader {
         SignatureChecker::verify_script(&script)?;
         InstructionConsistency::verify_script(&script)?;
         constants::verify_script(&script)?;
+        LimitsVerifier::verify_script(&self.config.verifier_config, &script)?;
         CodeUnitVerifier::verify_script(&script)?;
         verify_main_signature(&script)
     }
@@ -573,8 +676,9 @@ impl Loader {
         constants::verify_module(&module)?;
         RecursiveStructDefChecker::verify_module(&module)?;
         InstantiationLoopChecker::verify_module(&module)?;
+        LimitsVerifier::verify_module(&self.config.verifier_config, &module)?;
         CodeUnitVerifier::verify_module(&module)?;
-        Self::check_natives(&module)
+        self.check_natives(&module)
     }
 
     // The process of loading is recursive, and module are cached by the loader as soon as
@@ -594,12 +698,15 @@ impl Loader {
         &self,
         id: &ModuleId,
         data_store: &mut impl DataStore,
-    ) -> VMResult<CompiledModule> {
-        let module = match data_store.load_module(id) {
-            Ok(bytes) => CompiledModule::deserialize(&bytes).map_err(|_| {
-                PartialVMError::new(StatusCode::CODE_DESERIALIZATION_ERROR)
-                    .finish(Location::Undefined)
-            })?,
+    ) -> VMResult<(CompiledModule, usize)> {
+        let (module, size) = match data_store.load_module(id) {
+            Ok(bytes) => {
+                let module = CompiledModule::deserialize(&bytes).map_err(|_| {
+                    PartialVMError::new(StatusCode::CODE_DESERIALIZATION_ERROR)
+                        .finish(Location::Undefined)
+                })?;
+                (module, bytes.len())
+            }
             Err(err) => {
                 crit!("[VM] Error fetching module with id {:?}", id);
                 return Err(err);
@@ -607,7 +714,7 @@ impl Loader {
         };
         self.verify_module(&module)?;
         self.check_dependencies(&module, data_store)?;
-        Ok(module)
+        Ok((module, size))
     }
 
     fn check_dependencies(
@@ -636,8 +743,11 @@ impl Loader {
     }
 
     // All native functions must be known to the loader
-    fn check_natives(module: &CompiledModule) -> VMResult<()> {
-        fn check_natives_impl(module: &CompiledModule) -> PartialVMResult<()> {
+    fn check_natives(&self, module: &CompiledModule) -> VMResult<()> {
+        fn check_natives_impl(
+            module: &CompiledModule,
+            natives: &NativeFunctionTable,
+        ) -> PartialVMResult<()> {
             for (idx, native_function) in module
                 .function_defs()
                 .iter()
@@ -650,6 +760,7 @@ impl Loader {
                     module.address_identifier_at(mh.address),
                     module.identifier_at(mh.name).as_str(),
                     module.identifier_at(fh.name).as_str(),
+                    natives,
                 )
                 .ok_or_else(|| {
                     verification_error(
@@ -672,7 +783,8 @@ impl Loader {
             }
             Ok(())
         }
-        check_natives_impl(module).map_err(|e| e.finish(Location::Module(module.self_id())))
+        check_natives_impl(module, &self.natives)
+            .map_err(|e| e.finish(Location::Module(module.self_id())))
     }
 
     //
@@ -715,10 +827,19 @@ impl Loader {
 
     fn load_module(&self, id: &ModuleId, data_store: &mut impl DataStore) -> VMResult<Arc<Module>> {
         if let Some(module) = self.module_cache.lock().unwrap().module_at(id) {
+            counters::LOADER_CACHE_HITS
+                .with_label_values(&["modules"])
+                .inc();
             return Ok(module);
         }
-        let module = self.deserialize_and_verify_module(id, data_store)?;
-        self.module_cache.lock().unwrap().insert(id.clone(), module)
+        counters::LOADER_CACHE_MISSES
+            .with_label_values(&["modules"])
+            .inc();
+        let (module, size) = self.deserialize_and_verify_module(id, data_store)?;
+        self.module_cache
+            .lock()
+            .unwrap()
+            .insert(id.clone(), module, size, &self.natives)
     }
 
     // Verify the kind (constraints) of an instantiation.
@@ -838,6 +959,13 @@ impl<'a> Resolver<'a> {
         Self { loader, binary }
     }
 
+    pub(crate) fn registered_native(&self, idx: usize) -> PartialVMResult<&RegisteredNative> {
+        self.loader.natives.get(idx).ok_or_else(|| {
+            PartialVMError::new(StatusCode::UNREACHABLE)
+                .with_message("Missing registered native function".to_string())
+        })
+    }
+
     //
     // Constant resolution
     //
@@ -1412,7 +1540,11 @@ pub(crate) struct Function {
 }
 
 impl Function {
-    fn new(def: &FunctionDefinition, module: &CompiledModule) -> Self {
+    fn new(
+        def: &FunctionDefinition,
+        module: &CompiledModule,
+        natives: &NativeFunctionTable,
+    ) -> Self {
         let handle = module.function_handle_at(def.function);
         let name = module.identifier_at(handle.name).to_owned();
         let module_id = module.self_id();
@@ -1421,6 +1553,7 @@ impl Function {
                 module_id.address(),
                 module_id.name().as_str(),
                 name.as_str(),
+                natives,
             )
         } else {
             None
@@ -1492,6 +1625,10 @@ impl Function {
         self.name.as_str()
     }
 
+    pub(crate) fn identifier(&self) -> &IdentStr {
+        &self.name
+    }
+
     pub(crate) fn code(&self) -> &[Bytecode] {
         &self.code
     }
@@ -1629,13 +1766,35 @@ impl StructInfo {
 
 pub(crate) struct TypeCache {
     structs: HashMap<usize, HashMap<Vec<Type>, StructInfo>>,
+    // number of struct instantiations in the cache
+    num_entries: usize,
 }
 
 impl TypeCache {
     fn new() -> Self {
         Self {
             structs: HashMap::new(),
+            num_entries: 0,
+        }
+    }
+
+    fn get(&self, gidx: usize, ty_args: &[Type]) -> Option<&StructInfo> {
+        self.structs
+            .get(&gidx)
+            .and_then(|struct_map| struct_map.get(ty_args))
+    }
+
+    fn get_or_insert(&mut self, gidx: usize, ty_args: &[Type]) -> &mut StructInfo {
+        let struct_map = self.structs.entry(gidx).or_insert_with(HashMap::new);
+        if !struct_map.contains_key(ty_args) {
+            self.num_entries += 1;
+            counters::LOADER_CACHE_SIZE
+                .with_label_values(&["types"])
+                .set(self.num_entries as i64);
         }
+        struct_map
+            .entry(ty_args.to_vec())
+            .or_insert_with(StructInfo::new)
     }
 }
 
@@ -1668,13 +1827,17 @@ impl Loader {
     }
 
     fn struct_gidx_to_type_tag(&self, gidx: usize, ty_args: &[Type]) -> PartialVMResult<StructTag> {
-        if let Some(struct_map) = self.type_cache.lock().unwrap().structs.get(&gidx) {
-            if let Some(struct_info) = struct_map.get(ty_args) {
-                if let Some(struct_tag) = &struct_info.struct_tag {
-                    return Ok(struct_tag.clone());
-                }
+        if let Some(struct_info) = self.type_cache.lock().unwrap().get(gidx, ty_args) {
+            if let Some(struct_tag) = &struct_info.struct_tag {
+                counters::LOADER_CACHE_HITS
+                    .with_label_values(&["types"])
+                    .inc();
+                return Ok(struct_tag.clone());
             }
         }
+        counters::LOADER_CACHE_MISSES
+            .with_label_values(&["types"])
+            .inc();
 
         let ty_arg_tags = ty_args
             .iter()
@@ -1691,11 +1854,7 @@ impl Loader {
         self.type_cache
             .lock()
             .unwrap()
-            .structs
-            .entry(gidx)
-            .or_insert_with(HashMap::new)
-            .entry(ty_args.to_vec())
-            .or_insert_with(StructInfo::new)
+            .get_or_insert(gidx, ty_args)
             .struct_tag = Some(struct_tag.clone());
 
         Ok(struct_tag)
@@ -1729,13 +1888,17 @@ impl Loader {
         ty_args: &[Type],
         depth: usize,
     ) -> PartialVMResult<MoveStructLayout> {
-        if let Some(struct_map) = self.type_cache.lock().unwrap().structs.get(&gidx) {
-            if let Some(struct_info) = struct_map.get(ty_args) {
-                if let Some(layout) = &struct_info.struct_layout {
-                    return Ok(layout.clone());
-                }
+        if let Some(struct_info) = self.type_cache.lock().unwrap().get(gidx, ty_args) {
+            if let Some(layout) = &struct_info.struct_layout {
+                counters::LOADER_CACHE_HITS
+                    .with_label_values(&["types"])
+                    .inc();
+                return Ok(layout.clone());
             }
         }
+        counters::LOADER_CACHE_MISSES
+            .with_label_values(&["types"])
+            .inc();
 
         let struct_type = self.module_cache.lock().unwrap().struct_at(gidx);
         let field_tys = struct_type
@@ -1752,11 +1915,7 @@ impl Loader {
         self.type_cache
             .lock()
             .unwrap()
-            .structs
-            .entry(gidx)
-            .or_insert_with(HashMap::new)
-            .entry(ty_args.to_vec())
-            .or_insert_with(StructInfo::new)
+            .get_or_insert(gidx, ty_args)
             .struct_layout = Some(struct_layout.clone());
 
         Ok(struct_layout)
@@ -1801,13 +1960,17 @@ impl Loader {
         ty_args: &[Type],
         depth: usize,
     ) -> PartialVMResult<(MoveKind, Vec<MoveKindInfo>)> {
-        if let Some(struct_map) = self.type_cache.lock().unwrap().structs.get(&gidx) {
-            if let Some(struct_info) = struct_map.get(ty_args) {
-                if let Some(kind_info) = &struct_info.kind_info {
-                    return Ok(kind_info.clone());
-                }
+        if let Some(struct_info) = self.type_cache.lock().unwrap().get(gidx, ty_args) {
+            if let Some(kind_info) = &struct_info.kind_info {
+                counters::LOADER_CACHE_HITS
+                    .with_label_values(&["types"])
+                    .inc();
+                return Ok(kind_info.clone());
             }
         }
+        counters::LOADER_CACHE_MISSES
+            .with_label_values(&["types"])
+            .inc();
 
         let struct_type = self.module_cache.lock().unwrap().struct_at(gidx);
 
@@ -1833,11 +1996,7 @@ impl Loader {
         self.type_cache
             .lock()
             .unwrap()
-            .structs
-            .entry(gidx)
-            .or_insert_with(HashMap::new)
-            .entry(ty_args.to_vec())
-            .or_insert_with(StructInfo::new)
+            .get_or_insert(gidx, ty_args)
             .kind_info = Some(kind_info.clone());
 
         Ok(kind_info)
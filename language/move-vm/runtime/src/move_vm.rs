@@ -1,7 +1,12 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{data_cache::RemoteCache, runtime::VMRuntime, session::Session};
+use crate::{
+    data_cache::RemoteCache, native_functions::NativeFunctionTable, runtime::VMRuntime,
+    session::Session,
+};
+
+pub use crate::loader::LoaderConfig;
 
 pub struct MoveVM {
     runtime: VMRuntime,
@@ -9,8 +14,22 @@ pub struct MoveVM {
 
 impl MoveVM {
     pub fn new() -> Self {
+        Self::new_with_config(LoaderConfig::default())
+    }
+
+    /// Creates a VM whose loader caches are bounded by `config`.
+    pub fn new_with_config(config: LoaderConfig) -> Self {
+        Self {
+            runtime: VMRuntime::new(config, NativeFunctionTable::default()),
+        }
+    }
+
+    /// Creates a VM that, in addition to the natives it supports, links the native functions
+    /// registered in `natives`.
+    #[cfg(feature = "native_plugins")]
+    pub fn new_with_natives(config: LoaderConfig, natives: NativeFunctionTable) -> Self {
         Self {
-            runtime: VMRuntime::new(),
+            runtime: VMRuntime::new(config, natives),
         }
     }
 
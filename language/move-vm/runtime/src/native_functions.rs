@@ -3,7 +3,12 @@
 
 use crate::{interpreter::Interpreter, loader::Resolver};
 use libra_types::account_config::CORE_CODE_ADDRESS;
-use move_core_types::{account_address::AccountAddress, gas_schedule::CostTable};
+use move_core_types::{
+    account_address::AccountAddress,
+    gas_schedule::{CostTable, GasCost},
+};
+#[cfg(feature = "native_plugins")]
+use move_core_types::{identifier::IdentStr, language_storage::ModuleId, vm_status::StatusCode};
 use move_vm_natives::{account, debug, event, hash, lcs, signature, signer, vector};
 use move_vm_types::{
     data_store::DataStore,
@@ -12,7 +17,15 @@ use move_vm_types::{
     natives::function::{NativeContext, NativeResult},
     values::Value,
 };
-use std::{collections::VecDeque, fmt::Write};
+#[cfg(feature = "native_plugins")]
+use std::collections::hash_map::Entry;
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Write,
+    sync::Arc,
+};
+#[cfg(feature = "native_plugins")]
+use vm::errors::PartialVMError;
 use vm::errors::PartialVMResult;
 
 // The set of native functions the VM supports.
@@ -21,6 +34,8 @@ use vm::errors::PartialVMResult;
 // - `resolve` which given a function unique name ModuleAddress::ModuleName::FunctionName
 // returns a `NativeFunction`
 // - `dispatch` which given a `NativeFunction` invokes the native
+// Natives registered by the embedder of the VM in a `NativeFunctionTable` are resolved to
+// `Registered` with their index in the table.
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum NativeFunction {
     HashSha2_256,
@@ -42,6 +57,7 @@ pub(crate) enum NativeFunction {
     SignerBorrowAddress,
     CreateSigner,
     DestroySigner,
+    Registered(usize),
 }
 
 impl NativeFunction {
@@ -49,6 +65,7 @@ impl NativeFunction {
         module_address: &AccountAddress,
         module_name: &str,
         function_name: &str,
+        natives: &NativeFunctionTable,
     ) -> Option<NativeFunction> {
         use NativeFunction::*;
 
@@ -73,14 +90,18 @@ impl NativeFunction {
             (&CORE_CODE_ADDRESS, "Debug", "print") => DebugPrint,
             (&CORE_CODE_ADDRESS, "Debug", "print_stack_trace") => DebugPrintStackTrace,
             (&CORE_CODE_ADDRESS, "Signer", "borrow_address") => SignerBorrowAddress,
-            _ => return None,
+            _ => {
+                return natives
+                    .index_of(module_address, module_name, function_name)
+                    .map(Registered)
+            }
         })
     }
 
     /// Given the vector of aguments, it executes the native function.
     pub(crate) fn dispatch(
         self,
-        ctx: &mut impl NativeContext,
+        ctx: &mut FunctionContext,
         t: Vec<Type>,
         v: VecDeque<Value>,
     ) -> PartialVMResult<NativeResult> {
@@ -105,10 +126,101 @@ impl NativeFunction {
             Self::SignerBorrowAddress => signer::native_borrow_address(ctx, t, v),
             Self::CreateSigner => account::native_create_signer(ctx, t, v),
             Self::DestroySigner => account::native_destroy_signer(ctx, t, v),
+            Self::Registered(idx) => {
+                let native = ctx.resolver.registered_native(idx)?;
+                (native.implementation)(&native.cost, t, v)
+            }
         }
     }
 }
 
+/// The implementation of a native function registered by the embedder of the VM.
+///
+/// It is called with the gas cost the function was registered with, the type arguments and the
+/// arguments of the call, and must return the total cost of the call along with its result.
+pub type NativeFunctionImpl = Arc<
+    dyn Fn(&GasCost, Vec<Type>, VecDeque<Value>) -> PartialVMResult<NativeResult> + Send + Sync,
+>;
+
+pub(crate) struct RegisteredNative {
+    pub(crate) cost: GasCost,
+    pub(crate) implementation: NativeFunctionImpl,
+}
+
+/// Native functions provided by the embedder of the VM, in addition to the ones the VM
+/// supports. A Move module can declare any of them as `native` and call it like any other
+/// function.
+#[derive(Default)]
+pub struct NativeFunctionTable {
+    index: HashMap<(AccountAddress, String, String), usize>,
+    natives: Vec<RegisteredNative>,
+}
+
+impl NativeFunctionTable {
+    #[cfg(feature = "native_plugins")]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `implementation` as the native `function` of `module`, charging `cost`.
+    ///
+    /// Fails with `DUPLICATE_ELEMENT` if the VM or this table already provides that function.
+    #[cfg(feature = "native_plugins")]
+    pub fn register(
+        &mut self,
+        module: &ModuleId,
+        function: &IdentStr,
+        cost: GasCost,
+        implementation: NativeFunctionImpl,
+    ) -> PartialVMResult<()> {
+        let (address, module_name, function_name) =
+            (*module.address(), module.name().as_str(), function.as_str());
+        let duplicate = || {
+            PartialVMError::new(StatusCode::DUPLICATE_ELEMENT).with_message(format!(
+                "Native function {}::{} is already defined",
+                module, function
+            ))
+        };
+        if NativeFunction::resolve(&address, module_name, function_name, &Self::default()).is_some()
+        {
+            return Err(duplicate());
+        }
+        match self
+            .index
+            .entry((address, module_name.to_string(), function_name.to_string()))
+        {
+            Entry::Occupied(_) => Err(duplicate()),
+            Entry::Vacant(entry) => {
+                entry.insert(self.natives.len());
+                self.natives.push(RegisteredNative {
+                    cost,
+                    implementation,
+                });
+                Ok(())
+            }
+        }
+    }
+
+    fn index_of(
+        &self,
+        module_address: &AccountAddress,
+        module_name: &str,
+        function_name: &str,
+    ) -> Option<usize> {
+        self.index
+            .get(&(
+                *module_address,
+                module_name.to_string(),
+                function_name.to_string(),
+            ))
+            .copied()
+    }
+
+    pub(crate) fn get(&self, idx: usize) -> Option<&RegisteredNative> {
+        self.natives.get(idx)
+    }
+}
+
 pub(crate) struct FunctionContext<'a> {
     interpreter: &'a mut Interpreter,
     data_store: &'a mut dyn DataStore,
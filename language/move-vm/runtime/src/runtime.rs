@@ -3,10 +3,13 @@
 
 use crate::{
     data_cache::{RemoteCache, TransactionDataCache},
+    execution_trace::ExecutionTrace,
     interpreter::Interpreter,
-    loader::Loader,
+    loader::{Loader, LoaderConfig},
+    native_functions::NativeFunctionTable,
     session::Session,
 };
+use bytecode_verifier::CompatibilityChecker;
 use libra_logger::prelude::*;
 use move_core_types::{
     account_address::AccountAddress,
@@ -15,6 +18,7 @@ use move_core_types::{
     vm_status::StatusCode,
 };
 use move_vm_types::{data_store::DataStore, gas_schedule::CostStrategy, values::Value};
+use std::sync::Mutex;
 use vm::{
     access::ModuleAccess,
     errors::{verification_error, Location, PartialVMError, PartialVMResult, VMResult},
@@ -25,12 +29,15 @@ use vm::{
 /// An instantiation of the MoveVM.
 pub(crate) struct VMRuntime {
     loader: Loader,
+    // Number of sessions alive. The loader caches can only be flushed when no session is alive.
+    live_sessions: Mutex<usize>,
 }
 
 impl VMRuntime {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(config: LoaderConfig, natives: NativeFunctionTable) -> Self {
         VMRuntime {
-            loader: Loader::new(),
+            loader: Loader::new(config, natives),
+            live_sessions: Mutex::new(0),
         }
     }
 
@@ -38,6 +45,8 @@ impl VMRuntime {
         Session {
             runtime: self,
             data_cache: TransactionDataCache::new(remote, &self.loader),
+            trace: None,
+            _guard: SessionGuard::new(self),
         }
     }
 
@@ -70,13 +79,17 @@ impl VMRuntime {
             .finish(Location::Undefined));
         }
 
-        // Make sure that there is not already a module with this name published
-        // under the transaction sender's account.
+        // If a module with this name is already published under the transaction sender's
+        // account, this is an upgrade: the new version must not break the layout of existing
+        // data nor any existing caller.
         let module_id = compiled_module.self_id();
         if data_store.exists_module(&module_id)? {
-            return Err(
-                PartialVMError::new(StatusCode::DUPLICATE_MODULE_NAME).finish(Location::Undefined)
-            );
+            let old_module = CompiledModule::deserialize(&data_store.load_module(&module_id)?)
+                .map_err(|_| {
+                    PartialVMError::new(StatusCode::CODE_DESERIALIZATION_ERROR)
+                        .finish(Location::Undefined)
+                })?;
+            CompatibilityChecker::verify_module_upgrade(&old_module, &compiled_module)?;
         };
 
         // perform bytecode and loading verification
@@ -89,10 +102,11 @@ impl VMRuntime {
         &self,
         script: Vec<u8>,
         ty_args: Vec<TypeTag>,
-        mut args: Vec<Value>,
-        sender: AccountAddress,
+        args: Vec<Value>,
+        senders: Vec<AccountAddress>,
         data_store: &mut impl DataStore,
         cost_strategy: &mut CostStrategy,
+        trace: Option<ExecutionTrace>,
     ) -> VMResult<()> {
         // signer helper closure
         fn is_signer_reference(s: &SignatureToken) -> bool {
@@ -106,11 +120,33 @@ impl VMRuntime {
         // load the script, perform verification
         let (main, type_params) = self.loader.load_script(&script, &ty_args, data_store)?;
 
-        // build the arguments list for the main and check the arguments are of restricted types
-        let first_param_opt = main.parameters().0.get(0);
-        if first_param_opt.map_or(false, |sig| is_signer_reference(sig)) {
-            args.insert(0, Value::transaction_argument_signer_reference(sender))
-        }
+        // build the arguments list for the main and check the arguments are of restricted types.
+        // Scripts either take no `&signer` at all, or one for each signer, in order.
+        let num_signer_params = main
+            .parameters()
+            .0
+            .iter()
+            .take_while(|sig| is_signer_reference(sig))
+            .count();
+        let args = if num_signer_params == 0 {
+            args
+        } else if num_signer_params == senders.len() {
+            senders
+                .into_iter()
+                .map(Value::transaction_argument_signer_reference)
+                .chain(args)
+                .collect()
+        } else {
+            return Err(
+                PartialVMError::new(StatusCode::NUMBER_OF_SIGNER_ARGUMENTS_MISMATCH)
+                    .with_message(format!(
+                        "script takes {} signers, {} given",
+                        num_signer_params,
+                        senders.len()
+                    ))
+                    .finish(Location::Script),
+            );
+        };
         check_args(&args).map_err(|e| e.finish(Location::Script))?;
 
         // run the script
@@ -121,6 +157,7 @@ impl VMRuntime {
             data_store,
             cost_strategy,
             &self.loader,
+            trace,
         )
     }
 
@@ -132,6 +169,7 @@ impl VMRuntime {
         args: Vec<Value>,
         data_store: &mut impl DataStore,
         cost_strategy: &mut CostStrategy,
+        trace: Option<ExecutionTrace>,
     ) -> VMResult<()> {
         // load the function in the given module, perform verification of the module and
         // its dependencies if the module was not loaded
@@ -150,10 +188,34 @@ impl VMRuntime {
             data_store,
             cost_strategy,
             &self.loader,
+            trace,
         )
     }
 }
 
+/// Keeps track of a live session, so that the loader caches are only flushed when no session
+/// refers to them.
+pub(crate) struct SessionGuard<'l> {
+    runtime: &'l VMRuntime,
+}
+
+impl<'l> SessionGuard<'l> {
+    fn new(runtime: &'l VMRuntime) -> Self {
+        let mut live_sessions = runtime.live_sessions.lock().unwrap();
+        if *live_sessions == 0 {
+            runtime.loader.flush_if_over_budget();
+        }
+        *live_sessions += 1;
+        Self { runtime }
+    }
+}
+
+impl Drop for SessionGuard<'_> {
+    fn drop(&mut self) {
+        *self.runtime.live_sessions.lock().unwrap() -= 1;
+    }
+}
+
 /// Check that the transaction arguments are acceptable by the VM.
 /// Constants are the only arguments allowed.
 fn check_args(args: &[Value]) -> PartialVMResult<()> {
@@ -3,7 +3,8 @@
 
 use crate::{
     data_cache::{RemoteCache, TransactionDataCache, TransactionEffects},
-    runtime::VMRuntime,
+    execution_trace::ExecutionTrace,
+    runtime::{SessionGuard, VMRuntime},
 };
 use move_core_types::{
     account_address::AccountAddress,
@@ -16,9 +17,16 @@ use vm::errors::*;
 pub struct Session<'r, 'l, R> {
     pub(crate) runtime: &'l VMRuntime,
     pub(crate) data_cache: TransactionDataCache<'r, 'l, R>,
+    pub(crate) trace: Option<ExecutionTrace>,
+    pub(crate) _guard: SessionGuard<'l>,
 }
 
 impl<'r, 'l, R: RemoteCache> Session<'r, 'l, R> {
+    /// Records every instruction and native call executed in this session into `trace`.
+    pub fn enable_tracing(&mut self, trace: ExecutionTrace) {
+        self.trace = Some(trace);
+    }
+
     pub fn execute_function(
         &mut self,
         module: &ModuleId,
@@ -35,24 +43,27 @@ impl<'r, 'l, R: RemoteCache> Session<'r, 'l, R> {
             args,
             &mut self.data_cache,
             cost_strategy,
+            self.trace.clone(),
         )
     }
 
+    /// Executes the script's `main`, binding its `&signer` parameters to `senders` in order.
     pub fn execute_script(
         &mut self,
         script: Vec<u8>,
         ty_args: Vec<TypeTag>,
         args: Vec<Value>,
-        sender: AccountAddress,
+        senders: Vec<AccountAddress>,
         cost_strategy: &mut CostStrategy,
     ) -> VMResult<()> {
         self.runtime.execute_script(
             script,
             ty_args,
             args,
-            sender,
+            senders,
             &mut self.data_cache,
             cost_strategy,
+            self.trace.clone(),
         )
     }
 
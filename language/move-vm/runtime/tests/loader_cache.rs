@@ -0,0 +1,144 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use compiler::Compiler;
+use move_core_types::{
+    account_address::AccountAddress,
+    gas_schedule::{GasAlgebra, GasUnits},
+    identifier::{IdentStr, Identifier},
+    language_storage::{ModuleId, TypeTag},
+};
+use move_vm_runtime::{
+    data_cache::RemoteCache,
+    move_vm::{LoaderConfig, MoveVM},
+    session::Session,
+};
+use move_vm_types::gas_schedule::{zero_cost_schedule, CostStrategy};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use vm::errors::{PartialVMResult, VMResult};
+
+const TEST_ADDR: AccountAddress = AccountAddress::new([42; AccountAddress::LENGTH]);
+
+const TEST_MODULE: &str = "
+module M {
+    public noop() {
+        return;
+    }
+}
+";
+
+/// Serves a single module, and counts how many times the loader fetched it.
+struct ModuleStore {
+    blob: Vec<u8>,
+    loads: AtomicUsize,
+}
+
+impl ModuleStore {
+    fn new() -> Self {
+        let compiler = Compiler {
+            address: TEST_ADDR,
+            skip_stdlib_deps: true,
+            ..Compiler::default()
+        };
+        Self {
+            blob: compiler
+                .into_module_blob("file_name", TEST_MODULE)
+                .expect("Module should compile"),
+            loads: AtomicUsize::new(0),
+        }
+    }
+
+    fn loads(&self) -> usize {
+        self.loads.load(Ordering::SeqCst)
+    }
+}
+
+impl RemoteCache for ModuleStore {
+    fn get_module(&self, module_id: &ModuleId) -> VMResult<Option<Vec<u8>>> {
+        if module_id != &test_module_id() {
+            return Ok(None);
+        }
+        self.loads.fetch_add(1, Ordering::SeqCst);
+        Ok(Some(self.blob.clone()))
+    }
+
+    fn get_resource(
+        &self,
+        _address: &AccountAddress,
+        _tag: &TypeTag,
+    ) -> PartialVMResult<Option<Vec<u8>>> {
+        Ok(None)
+    }
+}
+
+fn test_module_id() -> ModuleId {
+    ModuleId::new(TEST_ADDR, Identifier::new("M").unwrap())
+}
+
+fn call_noop(session: &mut Session<ModuleStore>) {
+    let cost_table = zero_cost_schedule();
+    let mut cost_strategy = CostStrategy::system(&cost_table, GasUnits::new(100_000));
+    session
+        .execute_function(
+            &test_module_id(),
+            IdentStr::new("noop").unwrap(),
+            vec![],
+            vec![],
+            TEST_ADDR,
+            &mut cost_strategy,
+        )
+        .unwrap();
+}
+
+fn vm_with_binary_budget(max_binary_size: usize) -> MoveVM {
+    MoveVM::new_with_config(LoaderConfig {
+        max_binary_size,
+        ..LoaderConfig::default()
+    })
+}
+
+#[test]
+fn modules_stay_loaded_within_budget() {
+    let vm = MoveVM::new();
+    let store = ModuleStore::new();
+
+    call_noop(&mut vm.new_session(&store));
+    call_noop(&mut vm.new_session(&store));
+    assert_eq!(store.loads(), 1);
+}
+
+#[test]
+fn caches_are_flushed_over_budget() {
+    let store = ModuleStore::new();
+    let vm = vm_with_binary_budget(store.blob.len() - 1);
+
+    call_noop(&mut vm.new_session(&store));
+    // The module alone exceeds the budget, so it is dropped before the next session starts.
+    call_noop(&mut vm.new_session(&store));
+    assert_eq!(store.loads(), 2);
+
+    let vm = vm_with_binary_budget(store.blob.len());
+    call_noop(&mut vm.new_session(&store));
+    call_noop(&mut vm.new_session(&store));
+    assert_eq!(store.loads(), 3);
+}
+
+#[test]
+fn caches_are_not_flushed_while_a_session_is_alive() {
+    let store = ModuleStore::new();
+    let vm = vm_with_binary_budget(0);
+
+    let mut first_session = vm.new_session(&store);
+    call_noop(&mut first_session);
+    let mut second_session = vm.new_session(&store);
+    call_noop(&mut second_session);
+    assert_eq!(store.loads(), 1);
+
+    drop(first_session);
+    call_noop(&mut vm.new_session(&store));
+    assert_eq!(store.loads(), 1);
+
+    drop(second_session);
+    call_noop(&mut vm.new_session(&store));
+    assert_eq!(store.loads(), 2);
+}
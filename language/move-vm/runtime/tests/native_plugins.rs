@@ -0,0 +1,166 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![cfg(feature = "native_plugins")]
+
+use compiler::Compiler;
+use libra_types::account_config::CORE_CODE_ADDRESS;
+use move_core_types::{
+    account_address::AccountAddress,
+    gas_schedule::{GasAlgebra, GasCost, GasUnits},
+    identifier::{IdentStr, Identifier},
+    language_storage::{ModuleId, TypeTag},
+    vm_status::StatusCode,
+};
+use move_vm_runtime::{
+    data_cache::RemoteCache,
+    move_vm::{LoaderConfig, MoveVM},
+    NativeFunctionTable,
+};
+use move_vm_types::{
+    gas_schedule::{zero_cost_schedule, CostStrategy},
+    loaded_data::runtime_types::Type,
+    natives::function::NativeResult,
+    values::Value,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+use vm::errors::{PartialVMResult, VMResult};
+
+const TEST_ADDR: AccountAddress = AccountAddress::new([42; AccountAddress::LENGTH]);
+
+const PLUGIN_MODULE: &str = "
+module Plugin {
+    native public double(x: u64): u64;
+
+    public check_double(x: u64) {
+        let y: u64;
+        y = Self.double(copy(x));
+        assert(move(y) == move(x) * 2, 42);
+        return;
+    }
+}
+";
+
+struct ModuleStore(HashMap<ModuleId, Vec<u8>>);
+
+impl RemoteCache for ModuleStore {
+    fn get_module(&self, module_id: &ModuleId) -> VMResult<Option<Vec<u8>>> {
+        Ok(self.0.get(module_id).cloned())
+    }
+
+    fn get_resource(
+        &self,
+        _address: &AccountAddress,
+        _tag: &TypeTag,
+    ) -> PartialVMResult<Option<Vec<u8>>> {
+        Ok(None)
+    }
+}
+
+fn plugin_module_id() -> ModuleId {
+    ModuleId::new(TEST_ADDR, Identifier::new("Plugin").unwrap())
+}
+
+fn double_native(calls: Arc<AtomicUsize>) -> move_vm_runtime::NativeFunctionImpl {
+    Arc::new(
+        move |cost: &GasCost, _ty_args: Vec<Type>, mut args: VecDeque<Value>| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            let x = args.pop_back().unwrap().value_as::<u64>()?;
+            Ok(NativeResult::ok(cost.total(), vec![Value::u64(x * 2)]))
+        },
+    )
+}
+
+fn call_check_double(vm: &MoveVM, store: &ModuleStore) -> VMResult<()> {
+    let cost_table = zero_cost_schedule();
+    let mut cost_strategy = CostStrategy::system(&cost_table, GasUnits::new(100_000));
+    let mut session = vm.new_session(store);
+    session.execute_function(
+        &plugin_module_id(),
+        IdentStr::new("check_double").unwrap(),
+        vec![],
+        vec![Value::u64(21)],
+        TEST_ADDR,
+        &mut cost_strategy,
+    )
+}
+
+fn module_store() -> ModuleStore {
+    let compiler = Compiler {
+        address: TEST_ADDR,
+        skip_stdlib_deps: true,
+        ..Compiler::default()
+    };
+    let blob = compiler
+        .into_module_blob("file_name", PLUGIN_MODULE)
+        .expect("Module should compile");
+    let mut modules = HashMap::new();
+    modules.insert(plugin_module_id(), blob);
+    ModuleStore(modules)
+}
+
+#[test]
+fn call_registered_native() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let mut natives = NativeFunctionTable::new();
+    natives
+        .register(
+            &plugin_module_id(),
+            IdentStr::new("double").unwrap(),
+            GasCost::new(1, 1),
+            double_native(Arc::clone(&calls)),
+        )
+        .unwrap();
+    let vm = MoveVM::new_with_natives(LoaderConfig::default(), natives);
+
+    call_check_double(&vm, &module_store()).unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn unregistered_native_is_missing() {
+    let vm = MoveVM::new();
+    let err = call_check_double(&vm, &module_store()).unwrap_err();
+    assert_eq!(err.major_status(), StatusCode::MISSING_DEPENDENCY);
+}
+
+#[test]
+fn cannot_register_twice() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let mut natives = NativeFunctionTable::new();
+    let double = IdentStr::new("double").unwrap();
+    natives
+        .register(
+            &plugin_module_id(),
+            double,
+            GasCost::new(1, 1),
+            double_native(Arc::clone(&calls)),
+        )
+        .unwrap();
+    let err = natives
+        .register(
+            &plugin_module_id(),
+            double,
+            GasCost::new(1, 1),
+            double_native(Arc::clone(&calls)),
+        )
+        .unwrap_err();
+    assert_eq!(err.major_status(), StatusCode::DUPLICATE_ELEMENT);
+
+    // natives supported by the VM cannot be replaced either
+    let err = natives
+        .register(
+            &ModuleId::new(CORE_CODE_ADDRESS, Identifier::new("Hash").unwrap()),
+            IdentStr::new("sha3_256").unwrap(),
+            GasCost::new(1, 1),
+            double_native(calls),
+        )
+        .unwrap_err();
+    assert_eq!(err.major_status(), StatusCode::DUPLICATE_ELEMENT);
+}
@@ -274,6 +274,14 @@ impl Value {
                 | (SignatureToken::U64, Container::VecU64(_))
                 | (SignatureToken::U128, Container::VecU128(_))
                 | (SignatureToken::Address, Container::VecAddress(_)) => true,
+                (SignatureToken::Vector(inner_ty), Container::VecC(v))
+                    if **inner_ty == SignatureToken::U8 =>
+                {
+                    v.iter().all(|elem| match elem {
+                        ValueImpl::Container(r) => matches!(&*r.borrow(), Container::VecU8(_)),
+                        _ => false,
+                    })
+                }
                 _ => false,
             },
             (
@@ -303,8 +311,11 @@ impl Value {
                     | Container::VecU64(_)
                     | Container::VecU128(_)
                     | Container::VecAddress(_) => true,
-                    // TODO: we need to enable vector of vectors and move Value will change
-                    // to have a knowledge of vectors vs structs
+                    // vector<vector<u8>> is the only nested vector accepted
+                    Container::VecC(v) => v.iter().all(|elem| match elem {
+                        ValueImpl::Container(r) => matches!(&*r.borrow(), Container::VecU8(_)),
+                        _ => false,
+                    }),
                     _ => false,
                 }
             }
@@ -995,6 +1006,24 @@ impl Locals {
         ))))
     }
 
+    /// Renders every local, including the ones which are not set, e.g. for inspection in a
+    /// debugger.
+    pub fn display_each(&self) -> Vec<String> {
+        match &*self.0.borrow() {
+            Container::Locals(v) => v.iter().map(|val| val.to_string()).collect(),
+
+            Container::VecC(_)
+            | Container::VecR(_)
+            | Container::StructC(_)
+            | Container::StructR(_)
+            | Container::VecU8(_)
+            | Container::VecU64(_)
+            | Container::VecU128(_)
+            | Container::VecBool(_)
+            | Container::VecAddress(_) => unreachable!(),
+        }
+    }
+
     pub fn check_resources_for_return(&self) -> PartialVMResult<()> {
         match &*self.0.borrow() {
             Container::Locals(vals) => {
@@ -1185,6 +1214,14 @@ impl Value {
         )))
     }
 
+    pub fn vector_vector_u8(it: impl IntoIterator<Item = Vec<u8>>) -> Self {
+        Self(ValueImpl::new_container(Container::VecC(
+            it.into_iter()
+                .map(|v| ValueImpl::new_container(Container::VecU8(v)))
+                .collect(),
+        )))
+    }
+
     // REVIEW: This API can break
     pub fn vector_resource_for_testing_only(it: impl IntoIterator<Item = Value>) -> Self {
         Self(ValueImpl::new_container(Container::VecR(
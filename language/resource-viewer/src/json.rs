@@ -0,0 +1,130 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! JSON serialization of annotated values, for explorers and other clients which do not know
+//! the Move types of the data they display.
+//!
+//! An account state serializes to
+//! `{"schema": JSON_SCHEMA, "resources": [<struct>, ...]}`, resources being ordered by type.
+//! A struct serializes to
+//! `{"struct_tag": "0x..::M::S<..>", "is_resource": bool, "fields": [{"name": .., "value": ..}]}`,
+//! the struct tag being in Move syntax with full addresses, and the fields in declaration order.
+//! Every value is tagged with its type:
+//! `{"type": "u8" | "u64" | "u128" | "bool" | "address" | "bytes" | "vector" | "struct",
+//! "value": ..}`, where `u128` values are decimal strings, and addresses and bytes are hex
+//! strings.
+//!
+//! Changes to this format must come with a new `JSON_SCHEMA`.
+
+use crate::{AnnotatedAccountStateBlob, AnnotatedMoveStruct, AnnotatedMoveValue};
+use move_core_types::{
+    identifier::Identifier,
+    language_storage::{StructTag, TypeTag},
+};
+use serde::{
+    ser::{SerializeStruct, Serializer},
+    Serialize,
+};
+
+/// Identifies the version of the JSON format produced for account states.
+pub const JSON_SCHEMA: &str = "libra.annotated_account_state.v1";
+
+impl Serialize for AnnotatedAccountStateBlob {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let resources: Vec<_> = self.0.values().collect();
+        let mut state = serializer.serialize_struct("AnnotatedAccountStateBlob", 2)?;
+        state.serialize_field("schema", JSON_SCHEMA)?;
+        state.serialize_field("resources", &resources)?;
+        state.end()
+    }
+}
+
+impl Serialize for AnnotatedMoveStruct {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let fields: Vec<_> = self
+            .value
+            .iter()
+            .map(|(name, value)| Field { name, value })
+            .collect();
+        let mut state = serializer.serialize_struct("AnnotatedMoveStruct", 3)?;
+        state.serialize_field("struct_tag", &struct_tag_string(&self.type_))?;
+        state.serialize_field("is_resource", &self.is_resource)?;
+        state.serialize_field("fields", &fields)?;
+        state.end()
+    }
+}
+
+impl Serialize for AnnotatedMoveValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AnnotatedMoveValue", 2)?;
+        match self {
+            AnnotatedMoveValue::U8(v) => {
+                state.serialize_field("type", "u8")?;
+                state.serialize_field("value", v)?;
+            }
+            AnnotatedMoveValue::U64(v) => {
+                state.serialize_field("type", "u64")?;
+                state.serialize_field("value", v)?;
+            }
+            AnnotatedMoveValue::U128(v) => {
+                state.serialize_field("type", "u128")?;
+                state.serialize_field("value", &v.to_string())?;
+            }
+            AnnotatedMoveValue::Bool(v) => {
+                state.serialize_field("type", "bool")?;
+                state.serialize_field("value", v)?;
+            }
+            AnnotatedMoveValue::Address(v) => {
+                state.serialize_field("type", "address")?;
+                state.serialize_field("value", &hex::encode(v))?;
+            }
+            AnnotatedMoveValue::Bytes(v) => {
+                state.serialize_field("type", "bytes")?;
+                state.serialize_field("value", &hex::encode(v))?;
+            }
+            AnnotatedMoveValue::Vector(v) => {
+                state.serialize_field("type", "vector")?;
+                state.serialize_field("value", v)?;
+            }
+            AnnotatedMoveValue::Struct(v) => {
+                state.serialize_field("type", "struct")?;
+                state.serialize_field("value", v)?;
+            }
+        }
+        state.end()
+    }
+}
+
+#[derive(Serialize)]
+struct Field<'a> {
+    name: &'a Identifier,
+    value: &'a AnnotatedMoveValue,
+}
+
+/// Formats `tag` in Move syntax, unlike its `Display` implementation which shortens addresses.
+fn struct_tag_string(tag: &StructTag) -> String {
+    let mut s = format!(
+        "0x{}::{}::{}",
+        hex::encode(tag.address),
+        tag.module,
+        tag.name
+    );
+    if !tag.type_params.is_empty() {
+        let type_params: Vec<_> = tag.type_params.iter().map(type_tag_string).collect();
+        s.push_str(&format!("<{}>", type_params.join(", ")));
+    }
+    s
+}
+
+fn type_tag_string(tag: &TypeTag) -> String {
+    match tag {
+        TypeTag::Struct(tag) => struct_tag_string(tag),
+        TypeTag::Vector(tag) => format!("vector<{}>", type_tag_string(tag)),
+        TypeTag::U8 => "u8".to_string(),
+        TypeTag::U64 => "u64".to_string(),
+        TypeTag::U128 => "u128".to_string(),
+        TypeTag::Address => "address".to_string(),
+        TypeTag::Signer => "signer".to_string(),
+        TypeTag::Bool => "bool".to_string(),
+    }
+}
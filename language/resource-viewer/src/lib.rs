@@ -5,8 +5,11 @@ use crate::{cached_access_path_table::resource_vec_to_type_tag, resolver::Resolv
 use anyhow::{anyhow, Result};
 use libra_state_view::StateView;
 use libra_types::{
-    access_path::AccessPath, account_address::AccountAddress, account_state::AccountState,
+    access_path::AccessPath,
+    account_address::AccountAddress,
+    account_state::AccountState,
     contract_event::ContractEvent,
+    write_set::{WriteOp, WriteSet},
 };
 use move_core_types::{
     identifier::Identifier,
@@ -21,12 +24,17 @@ use std::{
 use vm::errors::{Location, PartialVMError};
 
 pub use cached_access_path_table::update_mapping;
+pub use json::JSON_SCHEMA;
 use move_vm_types::loaded_data::types::{FatStructType, FatType};
 
 mod cached_access_path_table;
+mod json;
 mod module_cache;
 mod resolver;
 
+#[cfg(test)]
+mod unit_tests;
+
 #[derive(Debug)]
 pub struct AnnotatedAccountStateBlob(BTreeMap<StructTag, AnnotatedMoveStruct>);
 
@@ -53,16 +61,29 @@ pub enum AnnotatedMoveValue {
     Struct(AnnotatedMoveStruct),
 }
 
+/// An entry of a write set, along with the resources it replaces and writes, annotated. Only the
+/// resources of types known to the annotator are annotated: the values of modules and of other
+/// resources are `None`.
+#[derive(Debug)]
+pub struct AnnotatedWriteSetEntry {
+    pub access_path: AccessPath,
+    pub deletion: bool,
+    /// The resource at `access_path` before the write set is applied.
+    pub before: Option<AnnotatedMoveStruct>,
+    /// The resource written at `access_path`, `None` if it is deleted.
+    pub after: Option<AnnotatedMoveStruct>,
+}
+
 pub struct MoveValueAnnotator<'a> {
     cache: Resolver<'a>,
-    _data_view: &'a dyn StateView,
+    data_view: &'a dyn StateView,
 }
 
 impl<'a> MoveValueAnnotator<'a> {
     pub fn new(view: &'a dyn StateView) -> Self {
         Self {
             cache: Resolver::new(view, true),
-            _data_view: view,
+            data_view: view,
         }
     }
 
@@ -89,6 +110,27 @@ impl<'a> MoveValueAnnotator<'a> {
         self.annotate_value(&move_value, &ty)
     }
 
+    /// Annotates the entries of `write_set`, reading the values they replace from the state view
+    /// of the annotator, which must be the state `write_set` applies to.
+    pub fn view_write_set(&self, write_set: &WriteSet) -> Result<Vec<AnnotatedWriteSetEntry>> {
+        write_set
+            .iter()
+            .map(|(access_path, write_op)| {
+                let annotate = |blob: &[u8]| self.view_access_path(access_path.clone(), blob).ok();
+                let before = self.data_view.get(access_path)?;
+                Ok(AnnotatedWriteSetEntry {
+                    access_path: access_path.clone(),
+                    deletion: write_op.is_deletion(),
+                    before: before.and_then(|blob| annotate(&blob)),
+                    after: match write_op {
+                        WriteOp::Value(blob) => annotate(blob),
+                        WriteOp::Deletion => None,
+                    },
+                })
+            })
+            .collect()
+    }
+
     pub fn view_account_state(&self, state: &AccountState) -> Result<AnnotatedAccountStateBlob> {
         let mut output = BTreeMap::new();
         for (k, v) in state.iter() {
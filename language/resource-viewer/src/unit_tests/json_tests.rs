@@ -0,0 +1,92 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{AnnotatedAccountStateBlob, AnnotatedMoveStruct, AnnotatedMoveValue, JSON_SCHEMA};
+use move_core_types::{
+    account_address::AccountAddress, identifier::Identifier, language_storage::StructTag,
+};
+use serde_json::json;
+use std::collections::BTreeMap;
+
+fn struct_tag(name: &str) -> StructTag {
+    StructTag {
+        address: AccountAddress::new([1; AccountAddress::LENGTH]),
+        module: Identifier::new("M").unwrap(),
+        name: Identifier::new(name).unwrap(),
+        type_params: vec![],
+    }
+}
+
+#[test]
+fn account_state_to_json() {
+    let inner = AnnotatedMoveStruct {
+        is_resource: false,
+        type_: struct_tag("Inner"),
+        value: vec![(
+            Identifier::new("big").unwrap(),
+            AnnotatedMoveValue::U128(u128::max_value()),
+        )],
+    };
+    let resource = AnnotatedMoveStruct {
+        is_resource: true,
+        type_: struct_tag("R"),
+        value: vec![
+            (
+                Identifier::new("flag").unwrap(),
+                AnnotatedMoveValue::Bool(true),
+            ),
+            (
+                Identifier::new("key").unwrap(),
+                AnnotatedMoveValue::Bytes(vec![0xca, 0xfe]),
+            ),
+            (
+                Identifier::new("counts").unwrap(),
+                AnnotatedMoveValue::Vector(vec![AnnotatedMoveValue::U64(7)]),
+            ),
+            (
+                Identifier::new("inner").unwrap(),
+                AnnotatedMoveValue::Struct(inner),
+            ),
+        ],
+    };
+    let mut resources = BTreeMap::new();
+    resources.insert(resource.type_.clone(), resource);
+    let blob = AnnotatedAccountStateBlob(resources);
+
+    let address = format!("0x{}", "01".repeat(AccountAddress::LENGTH));
+    assert_eq!(
+        serde_json::to_value(&blob).unwrap(),
+        json!({
+            "schema": JSON_SCHEMA,
+            "resources": [{
+                "struct_tag": format!("{}::M::R", address),
+                "is_resource": true,
+                "fields": [
+                    { "name": "flag", "value": { "type": "bool", "value": true } },
+                    { "name": "key", "value": { "type": "bytes", "value": "cafe" } },
+                    {
+                        "name": "counts",
+                        "value": { "type": "vector", "value": [{ "type": "u64", "value": 7 }] },
+                    },
+                    {
+                        "name": "inner",
+                        "value": {
+                            "type": "struct",
+                            "value": {
+                                "struct_tag": format!("{}::M::Inner", address),
+                                "is_resource": false,
+                                "fields": [{
+                                    "name": "big",
+                                    "value": {
+                                        "type": "u128",
+                                        "value": u128::max_value().to_string(),
+                                    },
+                                }],
+                            },
+                        },
+                    },
+                ],
+            }],
+        })
+    );
+}
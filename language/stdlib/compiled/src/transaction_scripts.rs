@@ -21,12 +21,15 @@ const TXN_SCRIPTS_ABI_DIR: Dir = include_dir!("transaction_scripts/abi");
 /// All of the Move transaction scripts that can be executed on the Libra blockchain
 #[derive(Clone, Copy, Eq, PartialEq)]
 pub enum StdlibScript {
+    ActivateDelayedKeyRotation,
     AddCurrencyToAccount,
+    AddDesignatedDealerTier,
     AddRecoveryRotationCapability,
     AddValidator,
     Burn,
     BurnTxnFees,
     CancelBurn,
+    CancelDelayedKeyRotation,
     CreateChildVaspAccount,
     CreateDesignatedDealer,
     CreateParentVaspAccount,
@@ -34,6 +37,7 @@ pub enum StdlibScript {
     CreateTestingAccount,
     CreateValidatorAccount,
     CreateValidatorOperatorAccount,
+    EnableDelayedKeyRotation,
     FreezeAccount,
     MintLbr,
     ModifyPublishingOption,
@@ -42,13 +46,16 @@ pub enum StdlibScript {
     PublishAccountLimitDefinition,
     PublishSharedEd2551PublicKey,
     Reconfigure,
+    RemoveDesignatedDealerTier,
     RemoveValidator,
     RotateAuthenticationKey,
     RotateAuthenticationKeyWithNonce,
     RotateAuthenticationKeyWithRecoveryAddress,
     RotateDualAttestationInfo,
     RotateSharedEd2551PublicKey,
+    ScheduleDelayedKeyRotation,
     UpdateAccountLimitWindowInfo,
+    SetExchangeRateUpdateBounds,
     SetValidatorConfig,
     SetValidatorOperator,
     TestnetMint,
@@ -56,7 +63,10 @@ pub enum StdlibScript {
     UnfreezeAccount,
     UnmintLbr,
     UpdateAccountLimitDefinition,
+    UpdateDesignatedDealerTier,
     UpdateExchangeRate,
+    UpdateExchangeRates,
+    UpdateGasSchedule,
     UpdateLibraVersion,
     UpdateMintingAbility,
     UpdateDualAttestationLimit,
@@ -69,12 +79,15 @@ impl StdlibScript {
     pub fn all() -> Vec<Self> {
         use StdlibScript::*;
         vec![
+            ActivateDelayedKeyRotation,
             AddCurrencyToAccount,
+            AddDesignatedDealerTier,
             AddRecoveryRotationCapability,
             AddValidator,
             Burn,
             BurnTxnFees,
             CancelBurn,
+            CancelDelayedKeyRotation,
             CreateChildVaspAccount,
             CreateDesignatedDealer,
             CreateParentVaspAccount,
@@ -82,6 +95,7 @@ impl StdlibScript {
             CreateTestingAccount,
             CreateValidatorAccount,
             CreateValidatorOperatorAccount,
+            EnableDelayedKeyRotation,
             FreezeAccount,
             MintLbr,
             ModifyPublishingOption,
@@ -90,13 +104,16 @@ impl StdlibScript {
             PublishAccountLimitDefinition,
             PublishSharedEd2551PublicKey,
             Reconfigure,
+            RemoveDesignatedDealerTier,
             RemoveValidator,
             RotateAuthenticationKey,
             RotateAuthenticationKeyWithNonce,
             RotateAuthenticationKeyWithRecoveryAddress,
             RotateDualAttestationInfo,
             RotateSharedEd2551PublicKey,
+            ScheduleDelayedKeyRotation,
             UpdateAccountLimitWindowInfo,
+            SetExchangeRateUpdateBounds,
             SetValidatorConfig,
             SetValidatorOperator,
             TestnetMint,
@@ -104,7 +121,10 @@ impl StdlibScript {
             UnfreezeAccount,
             UnmintLbr,
             UpdateAccountLimitDefinition,
+            UpdateDesignatedDealerTier,
             UpdateExchangeRate,
+            UpdateExchangeRates,
+            UpdateGasSchedule,
             UpdateLibraVersion,
             UpdateMintingAbility,
             UpdateDualAttestationLimit,
@@ -197,12 +217,15 @@ impl fmt::Display for StdlibScript {
             f,
             "{}",
             match self {
+                ActivateDelayedKeyRotation => "activate_delayed_key_rotation",
                 AddValidator => "add_validator",
                 AddCurrencyToAccount => "add_currency_to_account",
+                AddDesignatedDealerTier => "add_designated_dealer_tier",
                 AddRecoveryRotationCapability => "add_recovery_rotation_capability",
                 Burn => "burn",
                 BurnTxnFees => "burn_txn_fees",
                 CancelBurn => "cancel_burn",
+                CancelDelayedKeyRotation => "cancel_delayed_key_rotation",
                 CreateChildVaspAccount => "create_child_vasp_account",
                 CreateDesignatedDealer => "create_designated_dealer",
                 CreateParentVaspAccount => "create_parent_vasp_account",
@@ -210,6 +233,7 @@ impl fmt::Display for StdlibScript {
                 CreateTestingAccount => "create_testing_account",
                 CreateValidatorAccount => "create_validator_account",
                 CreateValidatorOperatorAccount => "create_validator_operator_account",
+                EnableDelayedKeyRotation => "enable_delayed_key_rotation",
                 FreezeAccount => "freeze_account",
                 TestnetMint => "testnet_mint",
                 MintLbr => "mint_lbr",
@@ -219,6 +243,7 @@ impl fmt::Display for StdlibScript {
                 PublishAccountLimitDefinition => "publish_account_limit_definition",
                 PublishSharedEd2551PublicKey => "publish_shared_ed25519_public_key",
                 Reconfigure => "reconfigure",
+                RemoveDesignatedDealerTier => "remove_designated_dealer_tier",
                 RemoveValidator => "remove_validator",
                 RotateAuthenticationKey => "rotate_authentication_key",
                 RotateAuthenticationKeyWithNonce => "rotate_authentication_key_with_nonce",
@@ -226,7 +251,9 @@ impl fmt::Display for StdlibScript {
                     "rotate_authentication_key_with_recovery_address",
                 RotateDualAttestationInfo => "rotate_dual_attestation_info",
                 RotateSharedEd2551PublicKey => "rotate_shared_ed25519_public_key",
+                ScheduleDelayedKeyRotation => "schedule_delayed_key_rotation",
                 UpdateAccountLimitWindowInfo => "update_account_limit_window_info",
+                SetExchangeRateUpdateBounds => "set_exchange_rate_update_bounds",
                 SetValidatorConfig => "set_validator_config",
                 SetValidatorOperator => "set_validator_operator",
                 TieredMint => "tiered_mint",
@@ -234,8 +261,11 @@ impl fmt::Display for StdlibScript {
                 UnfreezeAccount => "unfreeze_account",
                 UnmintLbr => "unmint_lbr",
                 UpdateAccountLimitDefinition => "update_account_limit_definition",
+                UpdateDesignatedDealerTier => "update_designated_dealer_tier",
                 UpdateLibraVersion => "update_libra_version",
                 UpdateExchangeRate => "update_exchange_rate",
+                UpdateExchangeRates => "update_exchange_rates",
+                UpdateGasSchedule => "update_gas_schedule",
                 UpdateMintingAbility => "update_minting_ability",
             }
         )
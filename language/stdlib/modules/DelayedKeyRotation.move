@@ -0,0 +1,152 @@
+address 0x1 {
+
+/// Rotations of the authentication key of an account which only take effect after a delay, for
+/// accounts to protect themselves against a compromised key rotating their key to lock them out.
+/// An account opting in hands its `KeyRotationCapability` over to a `KeyRotationDelay` resource for
+/// good: a rotation of its key is then scheduled, can be activated once the delay of the account
+/// has passed, and can be canceled with the current key until it is activated.
+module DelayedKeyRotation {
+    use 0x1::LibraAccount::{Self, KeyRotationCapability};
+    use 0x1::LibraTimestamp;
+    use 0x1::Option::{Self, Option};
+    use 0x1::Signer;
+    use 0x1::Vector;
+
+    /// A rotation of the authentication key to `new_key`, which can be activated from
+    /// `activation_time_microseconds` on.
+    struct PendingKeyRotation {
+        new_key: vector<u8>,
+        activation_time_microseconds: u64,
+    }
+
+    /// Published under an account whose authentication key can only be rotated
+    /// `delay_microseconds` after the rotation is scheduled. It holds the `KeyRotationCapability`
+    /// of the account and the rotation of its key pending, if any.
+    resource struct KeyRotationDelay {
+        rotation_cap: KeyRotationCapability,
+        delay_microseconds: u64,
+        pending_rotation: Option<PendingKeyRotation>,
+    }
+
+    const EKEY_ROTATION_DELAY_EXISTS: u64 = 0;
+    const EKEY_ROTATION_DELAY_DOES_NOT_EXIST: u64 = 1;
+    const EINVALID_DELAY: u64 = 2;
+    const EMALFORMED_AUTHENTICATION_KEY: u64 = 3;
+    const EKEY_ROTATION_PENDING: u64 = 4;
+    const ENO_KEY_ROTATION_PENDING: u64 = 5;
+    const EKEY_ROTATION_DELAY_NOT_PASSED: u64 = 6;
+
+    /// The longest delay an account can choose, a year.
+    const MAX_DELAY_SECONDS: u64 = 31536000;
+
+    /// Extract the `KeyRotationCapability` of `account` for good, after which its authentication
+    /// key can only be rotated `delay_seconds` after the rotation is scheduled.
+    /// Aborts if `account` has delegated its `KeyRotationCapability`, already has a
+    /// `KeyRotationDelay` resource, or if `delay_seconds` is 0 or longer than a year.
+    public fun publish(account: &signer, delay_seconds: u64) {
+        assert(
+            !exists<KeyRotationDelay>(Signer::address_of(account)),
+            EKEY_ROTATION_DELAY_EXISTS
+        );
+        assert(delay_seconds > 0 && delay_seconds <= MAX_DELAY_SECONDS, EINVALID_DELAY);
+        move_to(
+            account,
+            KeyRotationDelay {
+                rotation_cap: LibraAccount::extract_key_rotation_capability(account),
+                delay_microseconds: delay_seconds * 1000000,
+                pending_rotation: Option::none(),
+            }
+        )
+    }
+
+    /// Schedule the rotation of the authentication key of `account` to `new_key`, which can be
+    /// activated once the delay of `account` has passed.
+    /// Aborts if `account` has no `KeyRotationDelay` resource, if a rotation is already pending,
+    /// which must be canceled first, or if `new_key` is not 32 bytes long.
+    public fun schedule_rotation(account: &signer, new_key: vector<u8>) acquires KeyRotationDelay {
+        let addr = Signer::address_of(account);
+        assert(exists<KeyRotationDelay>(addr), EKEY_ROTATION_DELAY_DOES_NOT_EXIST);
+        // Don't allow scheduling a rotation to clearly invalid key
+        assert(Vector::length(&new_key) == 32, EMALFORMED_AUTHENTICATION_KEY);
+        let delay = borrow_global_mut<KeyRotationDelay>(addr);
+        assert(Option::is_none(&delay.pending_rotation), EKEY_ROTATION_PENDING);
+        let activation_time_microseconds =
+            LibraTimestamp::now_microseconds() + delay.delay_microseconds;
+        Option::fill(
+            &mut delay.pending_rotation,
+            PendingKeyRotation { new_key, activation_time_microseconds }
+        )
+    }
+
+    /// Cancel the rotation of the authentication key of `account` pending.
+    /// Aborts if `account` has no `KeyRotationDelay` resource or no rotation is pending.
+    public fun cancel_rotation(account: &signer) acquires KeyRotationDelay {
+        let addr = Signer::address_of(account);
+        assert(exists<KeyRotationDelay>(addr), EKEY_ROTATION_DELAY_DOES_NOT_EXIST);
+        let delay = borrow_global_mut<KeyRotationDelay>(addr);
+        assert(Option::is_some(&delay.pending_rotation), ENO_KEY_ROTATION_PENDING);
+        delay.pending_rotation = Option::none();
+    }
+
+    /// Rotate the authentication key of `addr` to the key of the rotation pending. Anyone can
+    /// activate the rotation, e.g. from another account of the holder of the new key.
+    /// Aborts if `addr` has no `KeyRotationDelay` resource, if no rotation is pending, or if the
+    /// delay of the rotation has not passed.
+    public fun activate_rotation(addr: address) acquires KeyRotationDelay {
+        assert(exists<KeyRotationDelay>(addr), EKEY_ROTATION_DELAY_DOES_NOT_EXIST);
+        let delay = borrow_global_mut<KeyRotationDelay>(addr);
+        assert(Option::is_some(&delay.pending_rotation), ENO_KEY_ROTATION_PENDING);
+        assert(
+            LibraTimestamp::now_microseconds() >=
+                Option::borrow(&delay.pending_rotation).activation_time_microseconds,
+            EKEY_ROTATION_DELAY_NOT_PASSED
+        );
+        let PendingKeyRotation { new_key, activation_time_microseconds: _ } =
+            Option::extract(&mut delay.pending_rotation);
+        LibraAccount::rotate_authentication_key(&delay.rotation_cap, new_key);
+    }
+
+    /// Returns true if the authentication key of `addr` can only be rotated with a delay.
+    public fun has_key_rotation_delay(addr: address): bool {
+        exists<KeyRotationDelay>(addr)
+    }
+
+    /// Returns true if a rotation of the authentication key of `addr` is pending.
+    public fun has_pending_rotation(addr: address): bool acquires KeyRotationDelay {
+        exists<KeyRotationDelay>(addr) &&
+            Option::is_some(&borrow_global<KeyRotationDelay>(addr).pending_rotation)
+    }
+
+    // ****************** SPECIFICATIONS *******************
+
+    spec module {
+        pragma verify = true;
+    }
+
+    spec module {
+        /// Returns true if the authentication key of `addr` can only be rotated with a delay.
+        define spec_has_key_rotation_delay(addr: address): bool {
+            exists<KeyRotationDelay>(addr)
+        }
+    }
+
+    /// ## A KeyRotationDelay stays, so the key can never be rotated at once again
+
+    spec schema KeyRotationDelayStays {
+        ensures forall addr: address:
+            old(spec_has_key_rotation_delay(addr)) ==> spec_has_key_rotation_delay(addr);
+    }
+
+    spec module {
+        apply KeyRotationDelayStays to *;
+    }
+
+    spec fun publish {
+        aborts_if spec_has_key_rotation_delay(Signer::spec_address_of(account));
+        aborts_if delay_seconds == 0 || delay_seconds > 31536000;
+        aborts_if !exists<LibraAccount::LibraAccount>(Signer::spec_address_of(account));
+        aborts_if !LibraAccount::spec_holds_own_key_rotation_cap(Signer::spec_address_of(account));
+        ensures spec_has_key_rotation_delay(Signer::spec_address_of(account));
+    }
+}
+}
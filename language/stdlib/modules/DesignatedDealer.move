@@ -47,6 +47,8 @@ module DesignatedDealer {
     const EINVALID_MINT_AMOUNT: u64 = 4;
     const ENOT_A_DD: u64 = 5;
     const EINVALID_AMOUNT_FOR_TIER: u64 = 6;
+    const ETIER_INFO_DOES_NOT_EXIST: u64 = 7;
+    const EINVALID_TIER_REMOVAL: u64 = 8;
 
     /// Number of microseconds in a day
     const ONE_DAY: u64 = 86400000000;
@@ -112,6 +114,7 @@ module DesignatedDealer {
         tier_upperbound: u64
     ) acquires TierInfo {
         assert(Roles::has_treasury_compliance_role(tc_account), EACCOUNT_NOT_TREASURY_COMPLIANCE);
+        assert(exists<TierInfo<CoinType>>(dd_addr), ETIER_INFO_DOES_NOT_EXIST);
         let tiers = &mut borrow_global_mut<TierInfo<CoinType>>(dd_addr).tiers;
         let number_of_tiers = Vector::length(tiers);
         assert(number_of_tiers + 1 <= MAX_NUM_TIERS, EINVALID_TIER_ADDITION);
@@ -135,6 +138,7 @@ module DesignatedDealer {
         new_upperbound: u64
     ) acquires TierInfo {
         assert(Roles::has_treasury_compliance_role(tc_account), EACCOUNT_NOT_TREASURY_COMPLIANCE);
+        assert(exists<TierInfo<CoinType>>(dd_addr), ETIER_INFO_DOES_NOT_EXIST);
         let tiers = &mut borrow_global_mut<TierInfo<CoinType>>(dd_addr).tiers;
         let number_of_tiers = Vector::length(tiers);
         assert(tier_index < number_of_tiers, EINVALID_TIER_INDEX);
@@ -160,6 +164,27 @@ module DesignatedDealer {
         ensures global<TierInfo<CoinType>>(dd_addr).tiers[tier_index] == new_upperbound;
     }
 
+    /// Removes the tier at `tier_index`. The tiers above it are shifted down by one index, so
+    /// the remaining tiers stay sorted. A DD always keeps at least one tier.
+    public fun remove_tier<CoinType>(
+        tc_account: &signer,
+        dd_addr: address,
+        tier_index: u64
+    ) acquires TierInfo {
+        assert(Roles::has_treasury_compliance_role(tc_account), EACCOUNT_NOT_TREASURY_COMPLIANCE);
+        assert(exists<TierInfo<CoinType>>(dd_addr), ETIER_INFO_DOES_NOT_EXIST);
+        let tiers = &mut borrow_global_mut<TierInfo<CoinType>>(dd_addr).tiers;
+        let number_of_tiers = Vector::length(tiers);
+        assert(tier_index < number_of_tiers, EINVALID_TIER_INDEX);
+        assert(number_of_tiers > 1, EINVALID_TIER_REMOVAL);
+        _ = Vector::remove(tiers, tier_index);
+    }
+
+    spec fun remove_tier {
+        // modifies global<TierInfo<CoinType>>@dd_addr.tiers;
+        ensures len(global<TierInfo<CoinType>>(dd_addr).tiers) == len(old(global<TierInfo<CoinType>>(dd_addr)).tiers) - 1;
+    }
+
     public fun tiered_mint<CoinType>(
         tc_account: &signer,
         amount: u64,
@@ -96,6 +96,28 @@ module FixedPoint32 {
         num.value
     }
 
+    // **************** TESTS ****************
+
+    #[test]
+    fun multiply_and_divide_by_rational() {
+        let half = create_from_rational(1, 2);
+        assert(get_raw_value(copy half) == 1 << 31, 0);
+        assert(multiply_u64(11, copy half) == 5, 1);
+        assert(divide_u64(11, half) == 22, 2);
+    }
+
+    #[test]
+    #[expected_failure]
+    fun create_from_rational_with_zero_denominator() {
+        let _ = create_from_rational(1, 0);
+    }
+
+    #[test]
+    #[expected_failure]
+    fun create_from_rational_too_small() {
+        let _ = create_from_rational(1, 1 << 33);
+    }
+
     // **************** SPECIFICATIONS ****************
 
     spec module {
@@ -6,6 +6,7 @@ address 0x1 {
 module Genesis {
     use 0x1::AccountFreezing;
     use 0x1::AccountLimits;
+    use 0x1::ChainId;
     use 0x1::VASP;
     use 0x1::Coin1;
     use 0x1::Coin2;
@@ -18,11 +19,13 @@ module Genesis {
     use 0x1::LibraConfig;
     use 0x1::LibraSystem;
     use 0x1::LibraTimestamp;
+    use 0x1::LibraTransactionPublishingOption;
     use 0x1::LibraTransactionTimeout;
     use 0x1::LibraVersion;
     use 0x1::LibraWriteSetManager;
     use 0x1::Signer;
     use 0x1::TransactionFee;
+    use 0x1::TransactionFeeDistribution;
     use 0x1::Roles;
     use 0x1::LibraVMConfig;
 
@@ -31,9 +34,12 @@ module Genesis {
         tc_account: &signer,
         tc_addr: address,
         genesis_auth_key: vector<u8>,
-        publishing_option: vector<u8>,
+        script_allow_list: vector<vector<u8>>,
+        is_open_script: bool,
+        is_open_module: bool,
         instruction_schedule: vector<u8>,
         native_schedule: vector<u8>,
+        chain_id: u8,
     ) {
         let dummy_auth_key_prefix = x"00000000000000000000000000000000";
 
@@ -71,6 +77,8 @@ module Genesis {
             tc_account,
         );
 
+        TransactionFeeDistribution::initialize(lr_account);
+
         // Create the treasury compliance account
         LibraAccount::create_treasury_compliance_account(
             lr_account,
@@ -78,6 +86,7 @@ module Genesis {
             copy dummy_auth_key_prefix,
         );
 
+        ChainId::initialize(lr_account, chain_id);
         LibraTransactionTimeout::initialize(lr_account);
         LibraSystem::initialize_validator_set(
             lr_account,
@@ -98,10 +107,15 @@ module Genesis {
 
         LibraVMConfig::initialize(
             lr_account,
-            publishing_option,
             instruction_schedule,
             native_schedule,
         );
+        LibraTransactionPublishingOption::initialize(
+            lr_account,
+            script_allow_list,
+            is_open_script,
+            is_open_module,
+        );
 
         let config_rotate_key_cap = LibraAccount::extract_key_rotation_capability(lr_account);
         LibraAccount::rotate_authentication_key(&config_rotate_key_cap, copy genesis_auth_key);
@@ -177,6 +177,15 @@ module Libra {
         to_burn: Libra<CoinType>,
     }
 
+    /// Bounds the change of the exchange rate to LBR of a currency in a single update by the
+    /// treasury compliance account under which it is published. Updates are unbounded until it
+    /// is published.
+    resource struct ExchangeRateUpdateBounds {
+        /// The maximum change of an exchange rate in a single update, as a fraction of the
+        /// current exchange rate
+        max_relative_change: FixedPoint32,
+    }
+
     const ENOT_GENESIS: u64 = 0;
     const EINVALID_SINGLETON_ADDRESS: u64 = 1;
     const ENOT_TREASURY_COMPLIANCE: u64 = 2;
@@ -188,6 +197,7 @@ module Libra {
     const ENOT_A_REGISTERED_CURRENCY: u64 = 8;
     const ENOT_AN_SCS_CURRENCY: u64 = 9;
     const EDOES_NOT_HAVE_TREASURY_COMPLIANCE_ROLE: u64 = 10;
+    const EEXCHANGE_RATE_CHANGE_TOO_LARGE: u64 = 11;
 
     ///////////////////////////////////////////////////////////////////////////
     // Initialization and granting of privileges
@@ -818,13 +828,20 @@ module Libra {
 
     /// Updates the `to_lbr_exchange_rate` held in the `CurrencyInfo` for
     /// `FromCoinType` to the new passed-in `lbr_exchange_rate`.
+    /// Aborts if the change from the current exchange rate exceeds the
+    /// `ExchangeRateUpdateBounds` of `tr_account`, if any.
     public fun update_lbr_exchange_rate<FromCoinType>(
         tr_account: &signer,
         lbr_exchange_rate: FixedPoint32
-    ) acquires CurrencyInfo {
+    ) acquires CurrencyInfo, ExchangeRateUpdateBounds {
         assert(Roles::has_treasury_compliance_role(tr_account), ENOT_TREASURY_COMPLIANCE);
         assert_is_currency<FromCoinType>();
         let currency_info = borrow_global_mut<CurrencyInfo<FromCoinType>>(CoreAddresses::CURRENCY_INFO_ADDRESS());
+        assert_exchange_rate_change_in_bounds(
+            Signer::address_of(tr_account),
+            *&currency_info.to_lbr_exchange_rate,
+            copy lbr_exchange_rate,
+        );
         currency_info.to_lbr_exchange_rate = lbr_exchange_rate;
         Event::emit_event(
             &mut currency_info.exchange_rate_update_events,
@@ -836,6 +853,50 @@ module Libra {
 
     }
 
+    fun assert_exchange_rate_change_in_bounds(
+        tr_address: address,
+        old_exchange_rate: FixedPoint32,
+        new_exchange_rate: FixedPoint32,
+    ) acquires ExchangeRateUpdateBounds {
+        if (!exists<ExchangeRateUpdateBounds>(tr_address)) {
+            return ()
+        };
+        let old_value = FixedPoint32::get_raw_value(old_exchange_rate);
+        let new_value = FixedPoint32::get_raw_value(new_exchange_rate);
+        let change = if (new_value > old_value) {
+            new_value - old_value
+        } else {
+            old_value - new_value
+        };
+        let max_change = FixedPoint32::multiply_u64(
+            old_value,
+            *&borrow_global<ExchangeRateUpdateBounds>(tr_address).max_relative_change
+        );
+        assert(change <= max_change, EEXCHANGE_RATE_CHANGE_TOO_LARGE);
+    }
+
+    /// Bounds the change of the exchange rates updated by `tr_account` to `max_relative_change`
+    /// times the current exchange rate.
+    public fun set_exchange_rate_update_bounds(
+        tr_account: &signer,
+        max_relative_change: FixedPoint32,
+    ) acquires ExchangeRateUpdateBounds {
+        assert(Roles::has_treasury_compliance_role(tr_account), ENOT_TREASURY_COMPLIANCE);
+        let tr_address = Signer::address_of(tr_account);
+        if (exists<ExchangeRateUpdateBounds>(tr_address)) {
+            borrow_global_mut<ExchangeRateUpdateBounds>(tr_address).max_relative_change =
+                max_relative_change;
+        } else {
+            move_to(tr_account, ExchangeRateUpdateBounds { max_relative_change });
+        }
+    }
+
+    spec fun set_exchange_rate_update_bounds {
+        aborts_if !Roles::spec_has_treasury_compliance_role_addr(Signer::spec_address_of(tr_account));
+        ensures global<ExchangeRateUpdateBounds>(Signer::spec_address_of(tr_account))
+            .max_relative_change == max_relative_change;
+    }
+
     /// Returns the (rough) exchange rate between `CoinType` and `LBR`
     public fun lbr_exchange_rate<CoinType>(): FixedPoint32
     acquires CurrencyInfo {
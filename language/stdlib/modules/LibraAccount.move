@@ -3,6 +3,7 @@ address 0x1 {
 // The module for the account resource that governs every Libra account
 module LibraAccount {
     use 0x1::AccountFreezing;
+    use 0x1::ChainId;
     use 0x1::CoreAddresses;
     use 0x1::AccountLimits::{Self, AccountLimitMutationCapability};
     use 0x1::Coin1::Coin1;
@@ -122,6 +123,8 @@ module LibraAccount {
     /// Attempting to send funds in (e.g.) LBR to an account that exists, but does not have a
     /// Balance<LBR> resource
     const EPAYEE_CANT_ACCEPT_CURRENCY_TYPE: u64 = 18;
+    /// Attempting to deposit transaction fees from an account other than the VM
+    const ESENDER_NOT_VM: u64 = 19;
 
     /// Prologue errors. These are separated out from the other errors in this
     /// module since they are mapped separately to major VM statuses, and are
@@ -133,6 +136,9 @@ module LibraAccount {
     const EPROLOGUE_ACCOUNT_DNE: u64 = 4;
     const EPROLOGUE_CANT_PAY_GAS_DEPOSIT: u64 = 5;
     const EPROLOGUE_TRANSACTION_EXPIRED: u64 = 6;
+    const EPROLOGUE_SIGNERS_CONTAIN_DUPLICATES: u64 = 7;
+    const EPROLOGUE_SECONDARY_ACCOUNT_DNE: u64 = 8;
+    const EPROLOGUE_BAD_CHAIN_ID: u64 = 9;
 
     /// Initialize this module. This is only callable from genesis.
     public fun initialize(
@@ -296,6 +302,19 @@ module LibraAccount {
             == old(global<Balance<Token>>(payee).coin.value) + to_deposit.value;
     }
 
+    /// Deposit the share `to_deposit` of the transaction fees into the balance of the validator
+    /// `payee`. Only callable by the VM when distributing the fees collected during an epoch.
+    /// The payer recorded in the `ReceivedPaymentEvent` is the Libra root account, which holds the
+    /// transaction fees.
+    public fun deposit_transaction_fees<Token>(
+        vm: &signer,
+        payee: address,
+        to_deposit: Libra<Token>,
+    ) acquires LibraAccount, Balance, AccountOperationsCapability {
+        assert(Signer::address_of(vm) == CoreAddresses::VM_RESERVED_ADDRESS(), ESENDER_NOT_VM);
+        deposit(CoreAddresses::LIBRA_ROOT_ADDRESS(), payee, to_deposit, x"", x"")
+    }
+
     /// Mint 'mint_amount' to 'designated_dealer_address' for 'tier_index' tier.
     /// Max valid tier index is 3 since there are max 4 tiers per DD.
     /// Sender should be treasury compliance account and receiver authorized DD.
@@ -710,6 +729,7 @@ module LibraAccount {
     /// - The account's auth key matches the transaction's public key
     /// - That the account has enough balance to pay for all of the gas
     /// - That the sequence number matches the transaction's sequence key
+    /// - That the transaction was signed for this chain
     fun prologue<Token>(
         sender: &signer,
         txn_sequence_number: u64,
@@ -717,9 +737,13 @@ module LibraAccount {
         txn_gas_price: u64,
         txn_max_gas_units: u64,
         txn_expiration_time: u64,
+        chain_id: u8,
     ) acquires LibraAccount, Balance {
         let transaction_sender = Signer::address_of(sender);
 
+        // Check that the chain ID stored on-chain matches the chain ID specified by the transaction
+        assert(ChainId::get() == chain_id, EPROLOGUE_BAD_CHAIN_ID);
+
         // Verify that the transaction sender's account exists
         assert(exists_at(transaction_sender), EPROLOGUE_ACCOUNT_DNE);
 
@@ -749,6 +773,56 @@ module LibraAccount {
         assert(LibraTransactionTimeout::is_valid_transaction_timestamp(txn_expiration_time), EPROLOGUE_TRANSACTION_EXPIRED);
     }
 
+    /// The prologue of a transaction signed by secondary signers along with its sender. On top of
+    /// the checks of `prologue`, it verifies for each secondary signer:
+    /// - That it is not the sender or another secondary signer
+    /// - That its account exists and is not frozen
+    /// - That the account's auth key matches the signer's public key
+    fun multi_agent_prologue<Token>(
+        sender: &signer,
+        txn_sequence_number: u64,
+        txn_public_key: vector<u8>,
+        secondary_signer_addresses: vector<address>,
+        secondary_signer_public_keys: vector<vector<u8>>,
+        txn_gas_price: u64,
+        txn_max_gas_units: u64,
+        txn_expiration_time: u64,
+        chain_id: u8,
+    ) acquires LibraAccount, Balance {
+        let num_secondary_signers = Vector::length(&secondary_signer_addresses);
+        assert(
+            Vector::length(&secondary_signer_public_keys) == num_secondary_signers,
+            EPROLOGUE_INVALID_ACCOUNT_AUTH_KEY
+        );
+
+        let signers = Vector::singleton(Signer::address_of(sender));
+        let i = 0;
+        while (i < num_secondary_signers) {
+            let secondary_address = *Vector::borrow(&secondary_signer_addresses, i);
+            assert(!Vector::contains(&signers, &secondary_address), EPROLOGUE_SIGNERS_CONTAIN_DUPLICATES);
+            Vector::push_back(&mut signers, secondary_address);
+
+            assert(exists_at(secondary_address), EPROLOGUE_SECONDARY_ACCOUNT_DNE);
+            assert(!AccountFreezing::account_is_frozen(secondary_address), EPROLOGUE_ACCOUNT_FROZEN);
+            let secondary_account = borrow_global<LibraAccount>(secondary_address);
+            assert(
+                Hash::sha3_256(*Vector::borrow(&secondary_signer_public_keys, i)) == *&secondary_account.authentication_key,
+                EPROLOGUE_INVALID_ACCOUNT_AUTH_KEY
+            );
+            i = i + 1;
+        };
+
+        prologue<Token>(
+            sender,
+            txn_sequence_number,
+            txn_public_key,
+            txn_gas_price,
+            txn_max_gas_units,
+            txn_expiration_time,
+            chain_id,
+        )
+    }
+
     /// Collects gas and bumps the sequence number for executing a transaction
     fun epilogue<Token>(
         sender: address,
@@ -827,6 +901,8 @@ module LibraAccount {
     ) {
         assert(Roles::has_libra_root_role(creator_account), ENOT_LIBRA_ROOT);
         let new_account = create_signer(new_account_address);
+        // The validator role lets the account hold the balances its share of the fees is paid to
+        Roles::new_validator_role(creator_account, &new_account);
         Event::publish_generator(&new_account);
         ValidatorConfig::publish(&new_account, creator_account);
         make_account(new_account, auth_key_prefix)
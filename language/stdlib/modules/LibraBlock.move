@@ -7,6 +7,7 @@ module LibraBlock {
     use 0x1::LibraConfig;
     use 0x1::LibraTimestamp;
     use 0x1::Signer;
+    use 0x1::TransactionFeeDistribution;
 
     resource struct BlockMetadata {
         /// Height of the current block
@@ -77,6 +78,10 @@ module LibraBlock {
 
         process_block_prologue(vm,  round, timestamp, previous_block_votes, proposer);
 
+        // Pay the fees collected during the previous epoch to its validators on the first block of
+        // a new epoch.
+        TransactionFeeDistribution::process_block(vm);
+
         // TODO(valerini): call regular reconfiguration here LibraSystem2::update_all_validator_info()
     }
 
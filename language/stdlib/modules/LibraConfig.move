@@ -127,6 +127,11 @@ module LibraConfig {
         pragma assume_no_abort_from_here = true;
     }
 
+    // Return the current epoch, which is bumped by every reconfiguration.
+    public fun get_current_epoch(): u64 acquires Configuration {
+        borrow_global<Configuration>(CoreAddresses::LIBRA_ROOT_ADDRESS()).epoch
+    }
+
     // Emit a reconfiguration event. This function will be invoked by the genesis directly to generate the very first
     // reconfiguration event.
     fun emit_reconfiguration_event() acquires Configuration {
@@ -0,0 +1,123 @@
+address 0x1 {
+
+module LibraTransactionPublishingOption {
+    use 0x1::LibraConfig;
+    use 0x1::LibraTimestamp;
+    use 0x1::Roles;
+    use 0x1::Vector;
+
+    // Defines which transaction scripts may be executed and who may publish modules. The VM reads
+    // this config and enforces it when validating and executing transactions.
+    // * script_allow_list: The SHA3-256 hashes of the scripts which may always be executed.
+    // * is_open_script: Whether scripts outside of `script_allow_list` may be executed as well.
+    // * is_open_module: Whether any account may publish modules. Otherwise only the libra root
+    //   account may publish modules, under the core code address.
+    struct LibraTransactionPublishingOption {
+        script_allow_list: vector<vector<u8>>,
+        is_open_script: bool,
+        is_open_module: bool,
+    }
+
+    const ENOT_GENESIS: u64 = 0;
+    const ENOT_LIBRA_ROOT: u64 = 1;
+    const EINVALID_SCRIPT_HASH: u64 = 2;
+    const EALLOWED_SCRIPT_ALREADY_IN_LIST: u64 = 3;
+    const EALLOWED_SCRIPT_NOT_IN_LIST: u64 = 4;
+
+    const SCRIPT_HASH_LENGTH: u64 = 32;
+
+    public fun initialize(
+        lr_account: &signer,
+        script_allow_list: vector<vector<u8>>,
+        is_open_script: bool,
+        is_open_module: bool,
+    ) {
+        assert(LibraTimestamp::is_genesis(), ENOT_GENESIS);
+        assert(Roles::has_libra_root_role(lr_account), ENOT_LIBRA_ROOT);
+
+        LibraConfig::publish_new_config(
+            lr_account,
+            LibraTransactionPublishingOption {
+                script_allow_list, is_open_script, is_open_module
+            }
+        );
+    }
+
+    // Check whether the script with the given hash may be executed.
+    public fun is_script_allowed(hash: &vector<u8>): bool {
+        let publish_option = LibraConfig::get<LibraTransactionPublishingOption>();
+        publish_option.is_open_script || Vector::contains(&publish_option.script_allow_list, hash)
+    }
+
+    // Allow the script with the given hash to be executed, whether scripts are open or not.
+    public fun add_to_script_allow_list(lr_account: &signer, new_hash: vector<u8>) {
+        assert(Roles::has_libra_root_role(lr_account), ENOT_LIBRA_ROOT);
+        assert(Vector::length(&new_hash) == SCRIPT_HASH_LENGTH, EINVALID_SCRIPT_HASH);
+
+        let publish_option = LibraConfig::get<LibraTransactionPublishingOption>();
+        assert(
+            !Vector::contains(&publish_option.script_allow_list, &new_hash),
+            EALLOWED_SCRIPT_ALREADY_IN_LIST
+        );
+        Vector::push_back(&mut publish_option.script_allow_list, new_hash);
+        LibraConfig::set<LibraTransactionPublishingOption>(lr_account, publish_option);
+    }
+
+    // Remove the script with the given hash from the allow list. Unless scripts are open, it may
+    // no longer be executed.
+    public fun remove_from_script_allow_list(lr_account: &signer, hash: vector<u8>) {
+        assert(Roles::has_libra_root_role(lr_account), ENOT_LIBRA_ROOT);
+
+        let publish_option = LibraConfig::get<LibraTransactionPublishingOption>();
+        let (found, index) = Vector::index_of(&publish_option.script_allow_list, &hash);
+        assert(found, EALLOWED_SCRIPT_NOT_IN_LIST);
+        Vector::remove(&mut publish_option.script_allow_list, index);
+        LibraConfig::set<LibraTransactionPublishingOption>(lr_account, publish_option);
+    }
+
+    // Allow or disallow scripts which are not in the allow list.
+    public fun set_open_script(lr_account: &signer, is_open_script: bool) {
+        assert(Roles::has_libra_root_role(lr_account), ENOT_LIBRA_ROOT);
+
+        let publish_option = LibraConfig::get<LibraTransactionPublishingOption>();
+        publish_option.is_open_script = is_open_script;
+        LibraConfig::set<LibraTransactionPublishingOption>(lr_account, publish_option);
+    }
+
+    // Allow or disallow module publishing by accounts other than libra root.
+    public fun set_open_module(lr_account: &signer, is_open_module: bool) {
+        assert(Roles::has_libra_root_role(lr_account), ENOT_LIBRA_ROOT);
+
+        let publish_option = LibraConfig::get<LibraTransactionPublishingOption>();
+        publish_option.is_open_module = is_open_module;
+        LibraConfig::set<LibraTransactionPublishingOption>(lr_account, publish_option);
+    }
+
+    // Replace the whole publishing option at once.
+    public fun set(
+        lr_account: &signer,
+        script_allow_list: vector<vector<u8>>,
+        is_open_script: bool,
+        is_open_module: bool,
+    ) {
+        assert(Roles::has_libra_root_role(lr_account), ENOT_LIBRA_ROOT);
+        let i = 0;
+        let len = Vector::length(&script_allow_list);
+        while (i < len) {
+            assert(
+                Vector::length(Vector::borrow(&script_allow_list, i)) == SCRIPT_HASH_LENGTH,
+                EINVALID_SCRIPT_HASH
+            );
+            i = i + 1;
+        };
+
+        LibraConfig::set<LibraTransactionPublishingOption>(
+            lr_account,
+            LibraTransactionPublishingOption {
+                script_allow_list, is_open_script, is_open_module
+            }
+        );
+    }
+}
+
+}
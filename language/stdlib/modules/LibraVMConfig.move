@@ -2,9 +2,11 @@ address 0x1 {
 
 module LibraVMConfig {
     use 0x1::LibraConfig;
+    use 0x1::Vector;
 
     // The struct to hold all config data needed to operate the LibraVM.
-    // * publishing_option: Defines Scripts/Modules that are allowed to execute in the current configruation.
+    // * publishing_option: Unused, superseded by the LibraTransactionPublishingOption config. It is
+    //   kept empty so that the layout of the config does not change.
     // * gas_schedule: Cost of running the VM.
     struct LibraVMConfig {
         publishing_option: vector<u8>,
@@ -26,6 +28,8 @@ module LibraVMConfig {
         instruction_schedule: vector<u8>,
         native_schedule: vector<u8>,
         gas_constants: GasConstants,
+        // Increases with every update of the schedule, see `set_gas_schedule`.
+        version: u64,
     }
 
     struct GasConstants {
@@ -61,10 +65,11 @@ module LibraVMConfig {
         default_account_size: u64,
     }
 
+    const EINVALID_GAS_SCHEDULE_VERSION: u64 = 0;
+
     // Initialize the table under the libra root account
     public fun initialize(
         lr_account: &signer,
-        publishing_option: vector<u8>,
         instruction_schedule: vector<u8>,
         native_schedule: vector<u8>,
     ) {
@@ -85,19 +90,34 @@ module LibraVMConfig {
         LibraConfig::publish_new_config(
             lr_account,
             LibraVMConfig {
-                publishing_option,
+                publishing_option: Vector::empty(),
                 gas_schedule: GasSchedule {
                     instruction_schedule,
                     native_schedule,
                     gas_constants,
+                    version: 0,
                 }
             },
         );
     }
 
-    public fun set_publishing_option(account: &signer, publishing_option: vector<u8>) {
+    // Replace the instruction and native schedules. The `version` of the new schedule must be
+    // greater than the current one, so that an update can neither be replayed nor applied out
+    // of order. Like any config change, the new schedule takes effect after reconfiguration.
+    public fun set_gas_schedule(
+        account: &signer,
+        version: u64,
+        instruction_schedule: vector<u8>,
+        native_schedule: vector<u8>,
+    ) {
         let current_config = LibraConfig::get<LibraVMConfig>();
-        current_config.publishing_option = publishing_option;
+        assert(
+            current_config.gas_schedule.version < version,
+            EINVALID_GAS_SCHEDULE_VERSION
+        );
+        current_config.gas_schedule.instruction_schedule = instruction_schedule;
+        current_config.gas_schedule.native_schedule = native_schedule;
+        current_config.gas_schedule.version = version;
         LibraConfig::set<LibraVMConfig>(account, current_config);
     }
 }
@@ -1,6 +1,7 @@
 address 0x1 {
 
 module LibraWriteSetManager {
+    use 0x1::ChainId;
     use 0x1::CoreAddresses;
     use 0x1::LibraAccount;
     use 0x1::Event;
@@ -22,6 +23,7 @@ module LibraWriteSetManager {
     const EINVALID_WRITESET_SENDER: u64 = 33;
     const EPROLOGUE_INVALID_ACCOUNT_AUTH_KEY: u64 = 1;
     const EPROLOGUE_SEQUENCE_NUMBER_TOO_OLD: u64 = 2;
+    const EPROLOGUE_BAD_CHAIN_ID: u64 = 9;
     const EWS_PROLOGUE_SEQUENCE_NUMBER_TOO_NEW: u64 = 11;
 
     public fun initialize(account: &signer) {
@@ -41,7 +43,10 @@ module LibraWriteSetManager {
         account: &signer,
         writeset_sequence_number: u64,
         writeset_public_key: vector<u8>,
+        chain_id: u8,
     ) {
+        assert(ChainId::get() == chain_id, EPROLOGUE_BAD_CHAIN_ID);
+
         let sender = Signer::address_of(account);
         assert(sender == CoreAddresses::LIBRA_ROOT_ADDRESS(), EINVALID_WRITESET_SENDER);
 
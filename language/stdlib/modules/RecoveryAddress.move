@@ -63,10 +63,10 @@ module RecoveryAddress {
         let len = Vector::length(caps);
         while ({
             spec {
-                assert i <= len + 1;
+                assert i <= len;
                 assert forall j in 0..i: caps[j].account_address != to_recover;
             };
-            (i <= len)
+            (i < len)
         })
         {
             let cap = Vector::borrow(caps, i);
@@ -77,7 +77,7 @@ module RecoveryAddress {
             i = i + 1
         };
         spec {
-            assert i == len + 1;
+            assert i == len;
             assert forall j in 0..len: caps[j].account_address != to_recover;
         };
         // Couldn't find `to_recover` in the account recovery resource; abort
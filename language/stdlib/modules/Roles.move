@@ -242,18 +242,23 @@ module Roles {
 
     /// Return true if `addr` is allowed to receive and send `Libra<T>` for any T
     public fun can_hold_balance(account: &signer): bool acquires RoleId {
-        // VASP accounts, designated_dealers, and unhosted accounts can hold balances.
-        // Administrative accounts (`Validator`, `ValidatorOperator`, `TreasuryCompliance`, and
+        // VASP accounts, designated_dealers, validators, and unhosted accounts can hold balances.
+        // Validators hold balances to receive their share of the transaction fees.
+        // Other administrative accounts (`ValidatorOperator`, `TreasuryCompliance`, and
         // `LibraRoot`) cannot.
         has_parent_VASP_role(account) ||
         has_child_VASP_role(account) ||
-        has_designated_dealer_role(account)
+        has_designated_dealer_role(account) ||
+        has_validator_role(account)
     }
 
     /// Return true if `account` must have limits on sending/receiving/holding of funds
     public fun needs_account_limits(account: &signer): bool acquires RoleId {
-        // All accounts that hold balances are subject to limits except designated dealers
-        can_hold_balance(account) && !has_designated_dealer_role(account)
+        // All accounts that hold balances are subject to limits except designated dealers and
+        // validators
+        can_hold_balance(account) &&
+        !has_designated_dealer_role(account) &&
+        !has_validator_role(account)
     }
 
 //**************** Specifications ****************
@@ -328,11 +333,14 @@ module Roles {
         define spec_can_hold_balance_addr(addr: address): bool {
             spec_has_parent_VASP_role_addr(addr) ||
                 spec_has_child_VASP_role_addr(addr) ||
-                spec_has_designated_dealer_role_addr(addr)
+                spec_has_designated_dealer_role_addr(addr) ||
+                spec_has_validator_role_addr(addr)
         }
 
         define spec_needs_account_limits_addr(addr: address): bool {
-            spec_can_hold_balance_addr(addr) && !spec_has_designated_dealer_role_addr(addr)
+            spec_can_hold_balance_addr(addr) &&
+                !spec_has_designated_dealer_role_addr(addr) &&
+                !spec_has_validator_role_addr(addr)
         }
     }
 
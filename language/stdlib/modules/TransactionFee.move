@@ -20,6 +20,7 @@ module TransactionFee {
     const ENOT_GENESIS: u64 = 0;
     const ENOT_TREASURY_COMPLIANCE: u64 = 1;
     const EINVALID_SINGLETON_ADDRESS: u64 = 2;
+    const ESENDER_NOT_VM: u64 = 3;
 
     /// Called in genesis. Sets up the needed resources to collect transaction fees from the
     /// `TransactionFee` resource with the TreasuryCompliance account.
@@ -51,6 +52,28 @@ module TransactionFee {
         ensures spec_txn_fee_balance<Coin2>() == 0;
     }
 
+    /// Sets up the collection of transaction fees in `CoinType`, a currency registered after
+    /// genesis.
+    public fun add_currency<CoinType>(
+        lr_account: &signer,
+        tc_account: &signer,
+    ) {
+        assert(
+            Signer::address_of(lr_account) == CoreAddresses::LIBRA_ROOT_ADDRESS(),
+            EINVALID_SINGLETON_ADDRESS
+        );
+        assert(Roles::has_treasury_compliance_role(tc_account), ENOT_TREASURY_COMPLIANCE);
+        add_txn_fee_currency<CoinType>(lr_account, tc_account);
+    }
+
+    spec fun add_currency {
+        aborts_if Signer::spec_address_of(lr_account) != CoreAddresses::SPEC_LIBRA_ROOT_ADDRESS();
+        aborts_if !Roles::spec_has_treasury_compliance_role_addr(Signer::spec_address_of(tc_account));
+        aborts_if spec_is_initialized<CoinType>();
+        aborts_if !Libra::spec_is_currency<CoinType>();
+        ensures spec_is_initialized<CoinType>();
+    }
+
     spec module {
         /// Returns true if the TransactionFee resource for CoinType has been
         /// initialized.
@@ -106,6 +129,23 @@ module TransactionFee {
 
     }
 
+    /// Withdraws all the transaction fees collected in the `CoinType` currency so that they can be
+    /// distributed to the validators. Only callable by the VM.
+    public fun withdraw_all_fees<CoinType>(vm: &signer): Libra<CoinType> acquires TransactionFee {
+        assert(Signer::address_of(vm) == CoreAddresses::VM_RESERVED_ADDRESS(), ESENDER_NOT_VM);
+        let fees = borrow_global_mut<TransactionFee<CoinType>>(
+            CoreAddresses::LIBRA_ROOT_ADDRESS()
+        );
+        Libra::withdraw_all(&mut fees.balance)
+    }
+
+    spec fun withdraw_all_fees {
+        aborts_if Signer::spec_address_of(vm) != CoreAddresses::SPEC_VM_RESERVED_ADDRESS();
+        aborts_if !spec_is_initialized<CoinType>();
+        ensures result.value == old(spec_txn_fee_balance<CoinType>());
+        ensures spec_txn_fee_balance<CoinType>() == 0;
+    }
+
     /// Preburns the transaction fees collected in the `CoinType` currency.
     /// If the `CoinType` is LBR, it unpacks the coin and preburns the
     /// underlying fiat.
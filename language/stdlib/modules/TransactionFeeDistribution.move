@@ -0,0 +1,179 @@
+address 0x1 {
+
+/// Distributes the transaction fees collected during an epoch to the validators of that epoch.
+/// The block prologue checks for an epoch change on every block: the first block of a new epoch
+/// splits the fees collected in each currency equally among the validators of the previous epoch
+/// that hold a balance in that currency, and records the validators of the new epoch.
+/// Fees that cannot be split evenly, or that no validator can receive, stay in the
+/// `TransactionFee` resource and are distributed at the end of the next epoch.
+module TransactionFeeDistribution {
+    use 0x1::AccountFreezing;
+    use 0x1::Coin1::Coin1;
+    use 0x1::Coin2::Coin2;
+    use 0x1::CoreAddresses;
+    use 0x1::Event::{Self, EventHandle};
+    use 0x1::LBR::LBR;
+    use 0x1::Libra::{Self, Libra};
+    use 0x1::LibraAccount;
+    use 0x1::LibraConfig;
+    use 0x1::LibraSystem;
+    use 0x1::LibraTimestamp;
+    use 0x1::Signer;
+    use 0x1::TransactionFee;
+    use 0x1::Vector;
+
+    resource struct TransactionFeeDistribution {
+        /// The epoch during which the fees currently held in `TransactionFee` are collected
+        epoch: u64,
+        /// The validators of `epoch`, who receive its fees at the next reconfiguration
+        validators: vector<address>,
+        /// Handle where an event is emitted for every currency distributed
+        distribution_events: EventHandle<DistributionEvent>,
+    }
+
+    struct DistributionEvent {
+        /// The epoch whose fees are distributed
+        epoch: u64,
+        /// The code symbol for the currency of the distributed fees
+        currency_code: vector<u8>,
+        /// The amount received by each validator
+        amount_per_validator: u64,
+        /// The number of validators who received `amount_per_validator`
+        num_validators: u64,
+    }
+
+    const ENOT_GENESIS: u64 = 0;
+    const EINVALID_SINGLETON_ADDRESS: u64 = 1;
+    const ESENDER_NOT_VM: u64 = 2;
+
+    /// Called in genesis. The validators of the genesis epoch are recorded by the first block
+    /// prologue.
+    public fun initialize(lr_account: &signer) {
+        assert(LibraTimestamp::is_genesis(), ENOT_GENESIS);
+        assert(
+            Signer::address_of(lr_account) == CoreAddresses::LIBRA_ROOT_ADDRESS(),
+            EINVALID_SINGLETON_ADDRESS
+        );
+        move_to(
+            lr_account,
+            TransactionFeeDistribution {
+                epoch: 0,
+                validators: Vector::empty(),
+                distribution_events: Event::new_event_handle<DistributionEvent>(lr_account),
+            }
+        );
+    }
+
+    spec fun initialize {
+        aborts_if !LibraTimestamp::spec_is_genesis();
+        aborts_if Signer::spec_address_of(lr_account) != CoreAddresses::SPEC_LIBRA_ROOT_ADDRESS();
+        aborts_if spec_is_initialized();
+        ensures spec_is_initialized();
+    }
+
+    spec module {
+        define spec_is_initialized(): bool {
+            exists<TransactionFeeDistribution>(CoreAddresses::SPEC_LIBRA_ROOT_ADDRESS())
+        }
+    }
+
+    /// Called by the block prologue. If a reconfiguration happened since the last block, the fees
+    /// collected during the previous epoch are distributed to its validators.
+    public fun process_block(vm: &signer) acquires TransactionFeeDistribution {
+        assert(Signer::address_of(vm) == CoreAddresses::VM_RESERVED_ADDRESS(), ESENDER_NOT_VM);
+        let current_epoch = LibraConfig::get_current_epoch();
+        let distribution = borrow_global_mut<TransactionFeeDistribution>(
+            CoreAddresses::LIBRA_ROOT_ADDRESS()
+        );
+        if (distribution.epoch == current_epoch) {
+            return ()
+        };
+
+        distribute_fees<Coin1>(vm, distribution);
+        distribute_fees<Coin2>(vm, distribution);
+        distribute_fees<LBR>(vm, distribution);
+
+        distribution.epoch = current_epoch;
+        distribution.validators = current_validators();
+    }
+
+    /// Split the fees collected in `CoinType` equally among the validators of `distribution`
+    /// that can receive them, and put the remainder back into the fees.
+    fun distribute_fees<CoinType>(
+        vm: &signer,
+        distribution: &mut TransactionFeeDistribution,
+    ) {
+        let fees = TransactionFee::withdraw_all_fees<CoinType>(vm);
+        let payees = payees<CoinType>(&distribution.validators);
+        let num_validators = Vector::length(&payees);
+        let amount_per_validator = if (num_validators == 0) {
+            0
+        } else {
+            Libra::value(&fees) / num_validators
+        };
+        if (amount_per_validator > 0) {
+            let i = 0;
+            while (i < num_validators) {
+                LibraAccount::deposit_transaction_fees<CoinType>(
+                    vm,
+                    *Vector::borrow(&payees, i),
+                    Libra::withdraw(&mut fees, amount_per_validator),
+                );
+                i = i + 1;
+            };
+            Event::emit_event(
+                &mut distribution.distribution_events,
+                DistributionEvent {
+                    epoch: distribution.epoch,
+                    currency_code: Libra::currency_code<CoinType>(),
+                    amount_per_validator,
+                    num_validators,
+                }
+            );
+        };
+        return_fees(fees)
+    }
+
+    /// The validators in `validators` that can receive fees in `CoinType`.
+    fun payees<CoinType>(validators: &vector<address>): vector<address> {
+        let payees = Vector::empty();
+        let i = 0;
+        let size = Vector::length(validators);
+        while (i < size) {
+            let addr = *Vector::borrow(validators, i);
+            if (LibraAccount::accepts_currency<CoinType>(addr) &&
+                !AccountFreezing::account_is_frozen(addr)) {
+                Vector::push_back(&mut payees, addr);
+            };
+            i = i + 1;
+        };
+        payees
+    }
+
+    fun return_fees<CoinType>(fees: Libra<CoinType>) {
+        if (Libra::value(&fees) > 0) {
+            TransactionFee::pay_fee(fees)
+        } else {
+            Libra::destroy_zero(fees)
+        }
+    }
+
+    fun current_validators(): vector<address> {
+        let validators = Vector::empty();
+        let i = 0;
+        let size = LibraSystem::validator_set_size();
+        while (i < size) {
+            Vector::push_back(&mut validators, LibraSystem::get_ith_validator_address(i));
+            i = i + 1;
+        };
+        validators
+    }
+
+    // **************** FUNCTION SPECIFICATIONS ****************
+
+    spec module {
+        pragma verify = true;
+    }
+}
+
+}
@@ -0,0 +1,13 @@
+script {
+use 0x1::DelayedKeyRotation;
+
+/// Rotate the authentication key of `rotated_address` to the key of its pending rotation. Can be
+/// sent by any account, e.g. by another account of the holder of the new key.
+/// Aborts with `DelayedKeyRotation::EKEY_ROTATION_DELAY_DOES_NOT_EXIST` if `rotated_address` did
+/// not enable delayed key rotations, with `DelayedKeyRotation::ENO_KEY_ROTATION_PENDING` if no
+/// rotation is pending, and with `DelayedKeyRotation::EKEY_ROTATION_DELAY_NOT_PASSED` if the delay
+/// of the rotation has not passed.
+fun activate_delayed_key_rotation(_account: &signer, rotated_address: address) {
+    DelayedKeyRotation::activate_rotation(rotated_address)
+}
+}
@@ -0,0 +1,21 @@
+script {
+use 0x1::DesignatedDealer;
+use 0x1::SlidingNonce;
+
+/// Add a tier with upper bound `tier_upperbound` for minting `CoinType` to the designated dealer
+/// at `designated_dealer_address`, above its existing tiers.
+/// Aborts with `DesignatedDealer::EINVALID_TIER_ADDITION` if the DD already has 4 tiers, and with
+/// `DesignatedDealer::EINVALID_TIER_START` if `tier_upperbound` is not greater than the upper bound of
+/// the last tier.
+/// Sender should be treasury compliance account.
+/// `sliding_nonce` is a unique nonce for operation, see sliding_nonce.move for details.
+fun add_designated_dealer_tier<CoinType>(
+    tc_account: &signer,
+    sliding_nonce: u64,
+    designated_dealer_address: address,
+    tier_upperbound: u64
+) {
+    SlidingNonce::record_nonce_or_abort(tc_account, sliding_nonce);
+    DesignatedDealer::add_tier<CoinType>(tc_account, designated_dealer_address, tier_upperbound);
+}
+}
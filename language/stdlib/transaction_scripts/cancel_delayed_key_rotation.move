@@ -0,0 +1,11 @@
+script {
+use 0x1::DelayedKeyRotation;
+
+/// Cancel the rotation of the sender's authentication key pending.
+/// Aborts with `DelayedKeyRotation::EKEY_ROTATION_DELAY_DOES_NOT_EXIST` if the sender did not
+/// enable delayed key rotations, and with `DelayedKeyRotation::ENO_KEY_ROTATION_PENDING` if no
+/// rotation is pending.
+fun cancel_delayed_key_rotation(account: &signer) {
+    DelayedKeyRotation::cancel_rotation(account)
+}
+}
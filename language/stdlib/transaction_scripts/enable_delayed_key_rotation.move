@@ -0,0 +1,15 @@
+script {
+use 0x1::DelayedKeyRotation;
+
+/// Make the authentication key of the sender only rotatable `delay_seconds` after the rotation is
+/// scheduled with `schedule_delayed_key_rotation`, until which the current key can cancel it.
+/// This is permanent: the sender hands its `KeyRotationCapability` over for good, so its key can
+/// no longer be rotated at once, e.g. with `rotate_authentication_key`.
+/// Aborts with `LibraAccount::EKEY_ROTATION_CAPABILITY_ALREADY_EXTRACTED` if the sender has
+/// delegated its `KeyRotationCapability`, with `DelayedKeyRotation::EKEY_ROTATION_DELAY_EXISTS` if
+/// it already has a delay, and with `DelayedKeyRotation::EINVALID_DELAY` if `delay_seconds` is 0 or
+/// longer than a year.
+fun enable_delayed_key_rotation(account: &signer, delay_seconds: u64) {
+    DelayedKeyRotation::publish(account, delay_seconds)
+}
+}
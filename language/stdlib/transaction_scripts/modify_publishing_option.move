@@ -1,8 +1,14 @@
 script {
-use 0x1::LibraVMConfig;
+use 0x1::LibraTransactionPublishingOption;
 
-/// Modify publishing options. Takes the LCS bytes of a `VMPublishingOption` object as input.
-fun modify_publishing_option(account: &signer, args: vector<u8>) {
-    LibraVMConfig::set_publishing_option(account, args)
+/// Modify publishing options. Takes the SHA3-256 hashes of the scripts which are always allowed,
+/// whether scripts outside of this list are allowed, and whether any account may publish modules.
+fun modify_publishing_option(
+    account: &signer,
+    script_allow_list: vector<vector<u8>>,
+    is_open_script: bool,
+    is_open_module: bool,
+) {
+    LibraTransactionPublishingOption::set(account, script_allow_list, is_open_script, is_open_module)
 }
 }
@@ -0,0 +1,20 @@
+script {
+use 0x1::DesignatedDealer;
+use 0x1::SlidingNonce;
+
+/// Remove the tier `tier_index` for minting `CoinType` from the designated dealer at
+/// `designated_dealer_address`. The tiers above it move down by one index.
+/// Aborts with `DesignatedDealer::EINVALID_TIER_INDEX` if the DD has no tier `tier_index`, and with
+/// `DesignatedDealer::EINVALID_TIER_REMOVAL` if it is the last remaining tier of the DD.
+/// Sender should be treasury compliance account.
+/// `sliding_nonce` is a unique nonce for operation, see sliding_nonce.move for details.
+fun remove_designated_dealer_tier<CoinType>(
+    tc_account: &signer,
+    sliding_nonce: u64,
+    designated_dealer_address: address,
+    tier_index: u64
+) {
+    SlidingNonce::record_nonce_or_abort(tc_account, sliding_nonce);
+    DesignatedDealer::remove_tier<CoinType>(tc_account, designated_dealer_address, tier_index);
+}
+}
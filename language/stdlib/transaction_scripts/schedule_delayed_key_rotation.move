@@ -0,0 +1,14 @@
+script {
+use 0x1::DelayedKeyRotation;
+
+/// Schedule the rotation of the sender's authentication key to `new_key`, which can be activated
+/// with `activate_delayed_key_rotation` once the delay of the sender has passed.
+/// `new_key` should be a 256 bit sha3 hash of an ed25519 public key.
+/// Aborts with `DelayedKeyRotation::EKEY_ROTATION_DELAY_DOES_NOT_EXIST` if the sender did not
+/// enable delayed key rotations, with `DelayedKeyRotation::EKEY_ROTATION_PENDING` if a rotation is
+/// already pending, and with `DelayedKeyRotation::EMALFORMED_AUTHENTICATION_KEY` if `new_key` is
+/// not 32 bytes long.
+fun schedule_delayed_key_rotation(account: &signer, new_key: vector<u8>) {
+    DelayedKeyRotation::schedule_rotation(account, new_key)
+}
+}
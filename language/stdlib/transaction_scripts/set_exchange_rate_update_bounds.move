@@ -0,0 +1,21 @@
+script {
+use 0x1::Libra;
+use 0x1::FixedPoint32;
+use 0x1::SlidingNonce;
+
+/// Bound the change of the on-chain exchange rates to LBR updated by `tc_account` to
+/// `max_change_numerator/max_change_denominator` times the current exchange rate.
+fun set_exchange_rate_update_bounds(
+    tc_account: &signer,
+    sliding_nonce: u64,
+    max_change_numerator: u64,
+    max_change_denominator: u64,
+) {
+    SlidingNonce::record_nonce_or_abort(tc_account, sliding_nonce);
+    let max_relative_change = FixedPoint32::create_from_rational(
+        max_change_numerator,
+        max_change_denominator,
+    );
+    Libra::set_exchange_rate_update_bounds(tc_account, max_relative_change);
+}
+}
@@ -0,0 +1,24 @@
+script {
+use 0x1::DesignatedDealer;
+use 0x1::SlidingNonce;
+
+/// Update the upper bound of the tier `tier_index` for minting `CoinType` to the designated dealer
+/// at `designated_dealer_address` to `new_upperbound`.
+/// Aborts with `DesignatedDealer::EINVALID_TIER_INDEX` if the DD has no tier `tier_index`, and with
+/// `DesignatedDealer::EINVALID_TIER_START` if `new_upperbound` is not between the upper bounds of the
+/// tiers below and above it.
+/// Sender should be treasury compliance account.
+/// `sliding_nonce` is a unique nonce for operation, see sliding_nonce.move for details.
+fun update_designated_dealer_tier<CoinType>(
+    tc_account: &signer,
+    sliding_nonce: u64,
+    designated_dealer_address: address,
+    tier_index: u64,
+    new_upperbound: u64
+) {
+    SlidingNonce::record_nonce_or_abort(tc_account, sliding_nonce);
+    DesignatedDealer::update_tier<CoinType>(
+        tc_account, designated_dealer_address, tier_index, new_upperbound
+    );
+}
+}
@@ -0,0 +1,27 @@
+script {
+use 0x1::Libra;
+use 0x1::FixedPoint32;
+use 0x1::SlidingNonce;
+
+/// Update the on-chain exchange rates to LBR of `Currency1` and `Currency2` in a single
+/// transaction, to `numerator1/denominator1` and `numerator2/denominator2` respectively. Either
+/// both rates are updated or neither is.
+fun update_exchange_rates<Currency1, Currency2>(
+    tc_account: &signer,
+    sliding_nonce: u64,
+    numerator1: u64,
+    denominator1: u64,
+    numerator2: u64,
+    denominator2: u64,
+) {
+    SlidingNonce::record_nonce_or_abort(tc_account, sliding_nonce);
+    Libra::update_lbr_exchange_rate<Currency1>(
+        tc_account,
+        FixedPoint32::create_from_rational(numerator1, denominator1),
+    );
+    Libra::update_lbr_exchange_rate<Currency2>(
+        tc_account,
+        FixedPoint32::create_from_rational(numerator2, denominator2),
+    );
+}
+}
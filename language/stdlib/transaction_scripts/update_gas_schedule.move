@@ -0,0 +1,14 @@
+script {
+use 0x1::LibraVMConfig;
+
+/// Update the gas schedule. Takes the version of the new schedule, which must be greater than the
+/// current one, and the LCS bytes of its instruction and native cost tables.
+fun update_gas_schedule(
+    account: &signer,
+    version: u64,
+    instruction_schedule: vector<u8>,
+    native_schedule: vector<u8>,
+) {
+    LibraVMConfig::set_gas_schedule(account, version, instruction_schedule, native_schedule)
+}
+}
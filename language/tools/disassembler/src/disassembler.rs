@@ -67,6 +67,10 @@ impl<Location: Clone + Eq> Disassembler<Location> {
         self.coverage_map = Some(coverage_map);
     }
 
+    pub(crate) fn source_mapper(&self) -> &SourceMapping<Location> {
+        &self.source_mapper
+    }
+
     //***************************************************************************
     // Helpers
     //***************************************************************************
@@ -313,7 +317,7 @@ impl<Location: Clone + Eq> Disassembler<Location> {
 
     // These need to be in the context of a function or a struct definition since type parameters
     // can refer to function/struct type parameters.
-    fn disassemble_sig_tok(
+    pub(crate) fn disassemble_sig_tok(
         &self,
         sig_tok: SignatureToken,
         type_param_context: &[SourceName<Location>],
@@ -378,7 +382,7 @@ impl<Location: Clone + Eq> Disassembler<Location> {
         })
     }
 
-    fn disassemble_instruction(
+    pub(crate) fn disassemble_instruction(
         &self,
         parameters: &Signature,
         instruction: &Bytecode,
@@ -2,3 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod disassembler;
+pub mod structured;
+
+#[cfg(test)]
+mod unit_tests;
@@ -0,0 +1,422 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A structured view of compiled Move code, for tools which render published modules and
+//! scripts instead of printing them.
+//!
+//! A `DisassembledUnit` holds the structs and functions of a `CompiledModule` or
+//! `CompiledScript`, with their types rendered as strings and every declaration and instruction
+//! paired with its location in the source map. The bytecode of the functions can be edited and
+//! assembled back, along with a source map built from the locations held by the unit.
+
+use crate::disassembler::{Disassembler, DisassemblerOptions};
+use anyhow::{bail, format_err, Result};
+use bytecode_source_map::{
+    mapping::SourceMapping,
+    source_map::{SourceMap, SourceName},
+};
+use libra_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_ir_types::ast::NopLabel;
+use std::collections::BTreeMap;
+use vm::{
+    access::ModuleAccess,
+    file_format::{
+        Bytecode, CodeOffset, CompiledModule, CompiledScript, FunctionDefinitionIndex, Kind,
+        Signature, StructDefinitionIndex, StructFieldInformation, TableIndex,
+    },
+};
+
+/// The compiled code a `DisassembledUnit` comes from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CompiledUnit {
+    Module(CompiledModule),
+    Script(CompiledScript),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TypeParameter<Location> {
+    pub name: String,
+    pub kind: Kind,
+    pub location: Location,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisassembledField<Location> {
+    pub name: String,
+    pub type_: String,
+    pub location: Location,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisassembledStruct<Location> {
+    pub name: String,
+    pub is_resource: bool,
+    /// Native structs have no fields.
+    pub is_native: bool,
+    pub type_parameters: Vec<TypeParameter<Location>>,
+    pub fields: Vec<DisassembledField<Location>>,
+    pub location: Location,
+}
+
+/// A parameter or local of a function.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Local<Location> {
+    pub name: String,
+    pub type_: String,
+    pub location: Location,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisassembledInstruction<Location> {
+    pub bytecode: Bytecode,
+    /// The instruction as printed by the disassembler, with names resolved.
+    pub text: String,
+    /// The source location of the instruction, if the source map has one.
+    pub location: Option<Location>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisassembledFunction<Location> {
+    pub name: String,
+    pub is_public: bool,
+    /// Native functions have no locals and no code.
+    pub is_native: bool,
+    pub type_parameters: Vec<TypeParameter<Location>>,
+    pub parameters: Vec<Local<Location>>,
+    pub return_types: Vec<String>,
+    pub locals: Vec<Local<Location>>,
+    /// The names of the structs the function acquires.
+    pub acquires: Vec<String>,
+    pub nops: BTreeMap<NopLabel, CodeOffset>,
+    pub code: Vec<DisassembledInstruction<Location>>,
+    pub location: Location,
+}
+
+/// A disassembled module or script. The functions of a script are only its `main`.
+#[derive(Clone, Debug)]
+pub struct DisassembledUnit<Location: Clone + Eq> {
+    /// The address and name of the module, or `None` for a script.
+    pub module_name: Option<(AccountAddress, Identifier)>,
+    pub structs: Vec<DisassembledStruct<Location>>,
+    pub functions: Vec<DisassembledFunction<Location>>,
+    unit: CompiledUnit,
+}
+
+impl<Location: Clone + Eq> DisassembledUnit<Location> {
+    pub fn from_module(module: CompiledModule, source_map: SourceMap<Location>) -> Result<Self> {
+        let disassembler = Disassembler::new(
+            SourceMapping::new(source_map, module.clone()),
+            DisassemblerOptions::new(),
+        );
+        Self::new(&disassembler, CompiledUnit::Module(module))
+    }
+
+    pub fn from_script(script: CompiledScript, source_map: SourceMap<Location>) -> Result<Self> {
+        let disassembler = Disassembler::new(
+            SourceMapping::new_from_script(source_map, script.clone()),
+            DisassemblerOptions::new(),
+        );
+        Self::new(&disassembler, CompiledUnit::Script(script))
+    }
+
+    fn new(disassembler: &Disassembler<Location>, unit: CompiledUnit) -> Result<Self> {
+        let source_mapper = disassembler.source_mapper();
+        let module = &source_mapper.bytecode;
+        let structs = (0..module.struct_defs().len())
+            .map(|idx| disassemble_struct(disassembler, StructDefinitionIndex(idx as TableIndex)))
+            .collect::<Result<_>>()?;
+        let functions = (0..module.function_defs().len())
+            .map(|idx| {
+                disassemble_function(disassembler, FunctionDefinitionIndex(idx as TableIndex))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            module_name: source_mapper.source_map.module_name_opt.clone(),
+            structs,
+            functions,
+            unit,
+        })
+    }
+
+    /// Assembles the unit back into compiled code, with the bytecode of its functions, and a
+    /// source map with the locations it holds. Everything else is taken from the compiled code
+    /// the unit was disassembled from.
+    pub fn assemble(&self) -> Result<(CompiledUnit, SourceMap<Location>)> {
+        let unit = match &self.unit {
+            CompiledUnit::Module(module) => {
+                let mut module = module.clone().into_inner();
+                if module.function_defs.len() != self.functions.len() {
+                    bail!(
+                        "Expected {} functions, found {}",
+                        module.function_defs.len(),
+                        self.functions.len()
+                    );
+                }
+                for (def, function) in module.function_defs.iter_mut().zip(&self.functions) {
+                    if let Some(code) = &mut def.code {
+                        code.code = function.bytecode();
+                    }
+                }
+                CompiledUnit::Module(
+                    module
+                        .freeze()
+                        .map_err(|e| format_err!("Unable to assemble module: {:?}", e))?,
+                )
+            }
+            CompiledUnit::Script(script) => {
+                let main = match self.functions.as_slice() {
+                    [main] => main,
+                    _ => bail!("Expected a single function in a script"),
+                };
+                let mut script = script.clone().into_inner();
+                script.code.code = main.bytecode();
+                CompiledUnit::Script(
+                    script
+                        .freeze()
+                        .map_err(|e| format_err!("Unable to assemble script: {:?}", e))?,
+                )
+            }
+        };
+        Ok((unit, self.source_map()?))
+    }
+
+    fn source_map(&self) -> Result<SourceMap<Location>> {
+        let mut source_map = SourceMap::new(None);
+        source_map.module_name_opt = self.module_name.clone();
+        for (idx, struct_) in self.structs.iter().enumerate() {
+            let idx = StructDefinitionIndex(idx as TableIndex);
+            source_map.add_top_level_struct_mapping(idx, struct_.location.clone())?;
+            for type_parameter in &struct_.type_parameters {
+                source_map.add_struct_type_parameter_mapping(idx, type_parameter.source_name())?;
+            }
+            for field in &struct_.fields {
+                source_map.add_struct_field_mapping(idx, field.location.clone())?;
+            }
+        }
+        for (idx, function) in self.functions.iter().enumerate() {
+            let idx = FunctionDefinitionIndex(idx as TableIndex);
+            source_map.add_top_level_function_mapping(idx, function.location.clone())?;
+            for type_parameter in &function.type_parameters {
+                source_map
+                    .add_function_type_parameter_mapping(idx, type_parameter.source_name())?;
+            }
+            for parameter in &function.parameters {
+                source_map.add_parameter_mapping(idx, parameter.source_name())?;
+            }
+            for local in &function.locals {
+                source_map.add_local_mapping(idx, local.source_name())?;
+            }
+            for (label, offset) in &function.nops {
+                source_map.add_nop_mapping(idx, label.clone(), *offset)?;
+            }
+            for (offset, instruction) in function.code.iter().enumerate() {
+                if let Some(location) = &instruction.location {
+                    source_map.add_code_mapping(idx, offset as CodeOffset, location.clone())?;
+                }
+            }
+        }
+        Ok(source_map)
+    }
+}
+
+impl<Location> DisassembledFunction<Location> {
+    fn bytecode(&self) -> Vec<Bytecode> {
+        self.code
+            .iter()
+            .map(|instruction| instruction.bytecode.clone())
+            .collect()
+    }
+}
+
+impl<Location: Clone> TypeParameter<Location> {
+    fn source_name(&self) -> SourceName<Location> {
+        (self.name.clone(), self.location.clone())
+    }
+}
+
+impl<Location: Clone> Local<Location> {
+    fn source_name(&self) -> SourceName<Location> {
+        (self.name.clone(), self.location.clone())
+    }
+}
+
+fn disassemble_struct<Location: Clone + Eq>(
+    disassembler: &Disassembler<Location>,
+    idx: StructDefinitionIndex,
+) -> Result<DisassembledStruct<Location>> {
+    let module = &disassembler.source_mapper().bytecode;
+    let struct_source_map = disassembler
+        .source_mapper()
+        .source_map
+        .get_struct_source_map(idx)?;
+    let struct_def = module.struct_def_at(idx);
+    let struct_handle = module.struct_handle_at(struct_def.struct_handle);
+    let type_parameters = type_parameters(
+        &struct_source_map.type_parameters,
+        &struct_handle.type_parameters,
+    )?;
+    let (is_native, fields) = match &struct_def.field_information {
+        StructFieldInformation::Native => (true, vec![]),
+        StructFieldInformation::Declared(fields) => (
+            false,
+            fields
+                .iter()
+                .enumerate()
+                .map(|(field_idx, field)| {
+                    Ok(DisassembledField {
+                        name: module.identifier_at(field.name).to_string(),
+                        type_: disassembler.disassemble_sig_tok(
+                            field.signature.0.clone(),
+                            &struct_source_map.type_parameters,
+                        )?,
+                        location: struct_source_map
+                            .fields
+                            .get(field_idx)
+                            .cloned()
+                            .ok_or_else(|| {
+                                format_err!("Missing location of field {}", field_idx)
+                            })?,
+                    })
+                })
+                .collect::<Result<_>>()?,
+        ),
+    };
+    Ok(DisassembledStruct {
+        name: module.identifier_at(struct_handle.name).to_string(),
+        is_resource: struct_handle.is_nominal_resource,
+        is_native,
+        type_parameters,
+        fields,
+        location: struct_source_map.decl_location.clone(),
+    })
+}
+
+fn disassemble_function<Location: Clone + Eq>(
+    disassembler: &Disassembler<Location>,
+    idx: FunctionDefinitionIndex,
+) -> Result<DisassembledFunction<Location>> {
+    let module = &disassembler.source_mapper().bytecode;
+    let function_source_map = disassembler
+        .source_mapper()
+        .source_map
+        .get_function_source_map(idx)?;
+    let function_def = module.function_def_at(idx);
+    let function_handle = module.function_handle_at(function_def.function);
+    let type_context = &function_source_map.type_parameters;
+    let type_parameters = type_parameters(type_context, &function_handle.type_parameters)?;
+    let parameters_sig = module.signature_at(function_handle.parameters);
+    let parameters = locals(
+        disassembler,
+        &function_source_map.parameters,
+        parameters_sig,
+        type_context,
+    )?;
+    let return_types = module
+        .signature_at(function_handle.return_)
+        .0
+        .iter()
+        .map(|tok| disassembler.disassemble_sig_tok(tok.clone(), type_context))
+        .collect::<Result<_>>()?;
+    let acquires = function_def
+        .acquires_global_resources
+        .iter()
+        .map(|struct_idx| {
+            let struct_handle =
+                module.struct_handle_at(module.struct_def_at(*struct_idx).struct_handle);
+            module.identifier_at(struct_handle.name).to_string()
+        })
+        .collect();
+    let (locals, code) = match &function_def.code {
+        None => (vec![], vec![]),
+        Some(code) => {
+            let locals_sig = module.signature_at(code.locals);
+            let locals = locals(
+                disassembler,
+                &function_source_map.locals,
+                locals_sig,
+                type_context,
+            )?;
+            let instructions = code
+                .code
+                .iter()
+                .enumerate()
+                .map(|(offset, bytecode)| {
+                    Ok(DisassembledInstruction {
+                        bytecode: bytecode.clone(),
+                        text: disassembler.disassemble_instruction(
+                            parameters_sig,
+                            bytecode,
+                            locals_sig,
+                            function_source_map,
+                            &function_source_map.decl_location,
+                        )?,
+                        location: function_source_map.get_code_location(offset as CodeOffset),
+                    })
+                })
+                .collect::<Result<_>>()?;
+            (locals, instructions)
+        }
+    };
+    Ok(DisassembledFunction {
+        name: module.identifier_at(function_handle.name).to_string(),
+        is_public: function_def.is_public(),
+        is_native: function_def.is_native(),
+        type_parameters,
+        parameters,
+        return_types,
+        locals,
+        acquires,
+        nops: function_source_map.nops.clone(),
+        code,
+        location: function_source_map.decl_location.clone(),
+    })
+}
+
+fn type_parameters<Location: Clone>(
+    names: &[SourceName<Location>],
+    kinds: &[Kind],
+) -> Result<Vec<TypeParameter<Location>>> {
+    if names.len() != kinds.len() {
+        bail!(
+            "Expected {} type parameters in the source map, found {}",
+            kinds.len(),
+            names.len()
+        );
+    }
+    Ok(names
+        .iter()
+        .zip(kinds)
+        .map(|((name, location), kind)| TypeParameter {
+            name: name.clone(),
+            kind: *kind,
+            location: location.clone(),
+        })
+        .collect())
+}
+
+fn locals<Location: Clone + Eq>(
+    disassembler: &Disassembler<Location>,
+    names: &[SourceName<Location>],
+    signature: &Signature,
+    type_context: &[SourceName<Location>],
+) -> Result<Vec<Local<Location>>> {
+    if names.len() != signature.len() {
+        bail!(
+            "Expected {} locals in the source map, found {}",
+            signature.len(),
+            names.len()
+        );
+    }
+    names
+        .iter()
+        .zip(&signature.0)
+        .map(|((name, location), tok)| {
+            Ok(Local {
+                name: name.clone(),
+                type_: disassembler.disassemble_sig_tok(tok.clone(), type_context)?,
+                location: location.clone(),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,119 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::structured::{CompiledUnit, DisassembledUnit};
+use bytecode_source_map::source_map::SourceMap;
+use ir_to_bytecode::{
+    compiler::{compile_module, compile_script},
+    parser::{parse_module, parse_script},
+};
+use libra_types::account_address::AccountAddress;
+use move_ir_types::location::Loc;
+use vm::file_format::{Bytecode, CompiledModule};
+
+const MODULE: &str = "
+    module Coin {
+        resource T<Tag> { value: u64, tag: Tag }
+
+        public value<Tag: copyable>(this: &Self.T<Tag>): u64 {
+            let value_ref: &u64;
+            value_ref = &move(this).value;
+            return *move(value_ref);
+        }
+
+        public add(x: u64, y: u64): u64 {
+            let sum: u64;
+            sum = move(x) + move(y);
+            return move(sum);
+        }
+    }
+";
+
+const SCRIPT: &str = "
+    main(x: u64) {
+        let y: u64;
+        y = move(x) * 2;
+        return;
+    }
+";
+
+fn compiled_module() -> (CompiledModule, SourceMap<Loc>) {
+    let module = parse_module("file_name", MODULE).unwrap();
+    compile_module::<CompiledModule>(AccountAddress::ZERO, module, &[]).unwrap()
+}
+
+#[test]
+fn disassemble_module() {
+    let (module, source_map) = compiled_module();
+    let unit = DisassembledUnit::from_module(module, source_map).unwrap();
+
+    assert_eq!(unit.structs.len(), 1);
+    let struct_ = &unit.structs[0];
+    assert_eq!(struct_.name, "T");
+    assert!(struct_.is_resource);
+    assert_eq!(struct_.type_parameters[0].name, "Tag");
+    let fields: Vec<_> = struct_
+        .fields
+        .iter()
+        .map(|field| (field.name.as_str(), field.type_.as_str()))
+        .collect();
+    assert_eq!(fields, vec![("value", "u64"), ("tag", "Tag")]);
+
+    let value = &unit.functions[0];
+    assert_eq!(value.name, "value");
+    assert!(value.is_public);
+    assert_eq!(value.parameters[0].name, "this");
+    assert_eq!(value.parameters[0].type_, "&T<Tag>");
+    assert_eq!(value.return_types, vec!["u64".to_string()]);
+    assert_eq!(value.locals[0].name, "value_ref");
+    assert!(value
+        .code
+        .iter()
+        .all(|instruction| instruction.location.is_some()));
+}
+
+#[test]
+fn module_round_trip() {
+    let (module, source_map) = compiled_module();
+    let unit = DisassembledUnit::from_module(module.clone(), source_map.clone()).unwrap();
+    let (assembled, assembled_source_map) = unit.assemble().unwrap();
+    assert_eq!(assembled, CompiledUnit::Module(module));
+    assert_eq!(assembled_source_map, source_map);
+}
+
+#[test]
+fn script_round_trip() {
+    let script = parse_script("file_name", SCRIPT).unwrap();
+    let (script, source_map) = compile_script::<CompiledModule>(None, script, &[]).unwrap();
+    let unit = DisassembledUnit::from_script(script.clone(), source_map.clone()).unwrap();
+    assert_eq!(unit.module_name, None);
+    assert_eq!(unit.functions.len(), 1);
+    assert_eq!(unit.functions[0].parameters[0].name, "x");
+    let (assembled, assembled_source_map) = unit.assemble().unwrap();
+    assert_eq!(assembled, CompiledUnit::Script(script));
+    assert_eq!(assembled_source_map, source_map);
+}
+
+#[test]
+fn assemble_edited_code() {
+    let (module, source_map) = compiled_module();
+    let mut unit = DisassembledUnit::from_module(module, source_map).unwrap();
+    let add = &mut unit.functions[1];
+    for instruction in &mut add.code {
+        if instruction.bytecode == Bytecode::Add {
+            instruction.bytecode = Bytecode::Mul;
+        }
+    }
+    let (assembled, _) = unit.assemble().unwrap();
+    let module = match assembled {
+        CompiledUnit::Module(module) => module,
+        CompiledUnit::Script(_) => panic!("Expected a module"),
+    };
+    let code = &module.as_inner().function_defs[1]
+        .code
+        .as_ref()
+        .unwrap()
+        .code;
+    assert!(code.contains(&Bytecode::Mul));
+    assert!(!code.contains(&Bytecode::Add));
+}
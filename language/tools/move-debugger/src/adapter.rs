@@ -0,0 +1,269 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A debug adapter serving a `DebugSession` to a client speaking the Debug Adapter Protocol.
+
+use crate::{
+    protocol::{read_message, write_message, Event, Message, Request, Response},
+    session::{DebugSession, StopReason, Variable},
+};
+use anyhow::{bail, format_err, Result};
+use libra_types::account_config;
+use move_core_types::account_address::AccountAddress;
+use serde_json::{json, Value};
+use std::{
+    io::{BufRead, Write},
+    path::Path,
+};
+
+/// The only thread of a Move script.
+const THREAD_ID: u64 = 1;
+/// The only stack frame shown, the script's `main`.
+const FRAME_ID: u64 = 1;
+const LOCALS_REFERENCE: u64 = 1;
+const RESOURCES_REFERENCE: u64 = 2;
+
+pub struct DebugAdapter<R, W> {
+    reader: R,
+    writer: W,
+    seq: u64,
+    session: Option<DebugSession>,
+    /// Breakpoint lines, kept to be set on the session once the program is launched.
+    breakpoints: Vec<usize>,
+    stop_on_entry: bool,
+    /// Whether the client is done configuring the adapter. Execution starts once the program
+    /// is both launched and configured, whichever comes first.
+    configured: bool,
+}
+
+impl<R: BufRead, W: Write> DebugAdapter<R, W> {
+    pub fn new(reader: R, writer: W) -> Self {
+        Self {
+            reader,
+            writer,
+            seq: 0,
+            session: None,
+            breakpoints: vec![],
+            stop_on_entry: false,
+            configured: false,
+        }
+    }
+
+    /// Serves requests until the client disconnects or closes the stream.
+    pub fn run(&mut self) -> Result<()> {
+        while let Some(message) = read_message(&mut self.reader)? {
+            let request = match message {
+                Message::Request(request) => request,
+                // Clients do not send anything else the adapter has to react to.
+                Message::Response(_) | Message::Event(_) => continue,
+            };
+            let disconnect = request.command == "disconnect";
+            let response = match self.handle(&request) {
+                Ok(body) => Response {
+                    request_seq: request.seq,
+                    success: true,
+                    command: request.command.clone(),
+                    message: None,
+                    body,
+                },
+                Err(err) => Response {
+                    request_seq: request.seq,
+                    success: false,
+                    command: request.command.clone(),
+                    message: Some(err.to_string()),
+                    body: None,
+                },
+            };
+            let success = response.success;
+            self.send(Message::Response(response))?;
+            if success {
+                self.after(&request.command)?;
+            }
+            if disconnect {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn handle(&mut self, request: &Request) -> Result<Option<Value>> {
+        let args = &request.arguments;
+        match request.command.as_str() {
+            "initialize" => Ok(Some(json!({
+                "supportsConfigurationDoneRequest": true,
+            }))),
+            "launch" => {
+                let program = args["program"]
+                    .as_str()
+                    .ok_or_else(|| format_err!("Missing `program` to debug"))?;
+                let sender = match args["sender"].as_str() {
+                    Some(sender) => AccountAddress::from_hex_literal(sender)?,
+                    None => account_config::libra_root_address(),
+                };
+                self.stop_on_entry = args["stopOnEntry"].as_bool().unwrap_or(false);
+                let mut session = DebugSession::launch(Path::new(program), sender)?;
+                session.set_breakpoints(&self.breakpoints);
+                self.session = Some(session);
+                Ok(None)
+            }
+            "setBreakpoints" => {
+                let lines = args["breakpoints"]
+                    .as_array()
+                    .map(|breakpoints| {
+                        breakpoints
+                            .iter()
+                            .filter_map(|breakpoint| breakpoint["line"].as_u64())
+                            .map(|line| line as usize)
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+                let verified = match &mut self.session {
+                    Some(session) => session.set_breakpoints(&lines),
+                    None => vec![false; lines.len()],
+                };
+                self.breakpoints = lines.clone();
+                let breakpoints = lines
+                    .iter()
+                    .zip(verified)
+                    .map(|(line, verified)| json!({ "line": line, "verified": verified }))
+                    .collect::<Vec<_>>();
+                Ok(Some(json!({ "breakpoints": breakpoints })))
+            }
+            "configurationDone" | "disconnect" => Ok(None),
+            "continue" | "next" | "stepIn" | "stepOut" => self.session().map(|_| None),
+            "threads" => Ok(Some(json!({
+                "threads": [{ "id": THREAD_ID, "name": "main" }],
+            }))),
+            "stackTrace" => {
+                let session = self.session()?;
+                let frames = match session.current_line() {
+                    Some(line) => vec![json!({
+                        "id": FRAME_ID,
+                        "name": format!("main (pc {})", session.current_pc().unwrap_or(0)),
+                        "source": { "path": session.file_name() },
+                        "line": line,
+                        "column": 1,
+                    })],
+                    None => vec![],
+                };
+                Ok(Some(
+                    json!({ "stackFrames": frames, "totalFrames": frames.len() }),
+                ))
+            }
+            "scopes" => Ok(Some(json!({
+                "scopes": [
+                    scope("Locals", LOCALS_REFERENCE),
+                    scope("Resources", RESOURCES_REFERENCE),
+                ],
+            }))),
+            "variables" => {
+                let session = self.session()?;
+                let variables = match args["variablesReference"].as_u64() {
+                    Some(LOCALS_REFERENCE) => session.locals(),
+                    Some(RESOURCES_REFERENCE) => session.resources().to_vec(),
+                    _ => bail!("Unknown variables reference"),
+                };
+                Ok(Some(json!({ "variables": to_json(&variables) })))
+            }
+            command => bail!("Unsupported request `{}`", command),
+        }
+    }
+
+    /// Runs the effects of a request which must come after its response.
+    fn after(&mut self, command: &str) -> Result<()> {
+        match command {
+            "initialize" => self.send_event("initialized", None),
+            "launch" => self.start_if_ready(),
+            "configurationDone" => {
+                self.configured = true;
+                self.start_if_ready()
+            }
+            "continue" => {
+                let reason = self.session_mut()?.continue_();
+                self.report(reason)
+            }
+            // Calls into modules are always stepped over, so there is nothing to step into or
+            // out of.
+            "next" | "stepIn" | "stepOut" => {
+                let reason = self.session_mut()?.step();
+                self.report(reason)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn start_if_ready(&mut self) -> Result<()> {
+        if !self.configured {
+            return Ok(());
+        }
+        let stop_on_entry = self.stop_on_entry;
+        let reason = match &mut self.session {
+            Some(session) => match session.start() {
+                StopReason::Entry if !stop_on_entry => session.continue_(),
+                reason => reason,
+            },
+            None => return Ok(()),
+        };
+        self.report(reason)
+    }
+
+    fn report(&mut self, reason: StopReason) -> Result<()> {
+        let (reason, text) = match reason {
+            StopReason::Entry => ("entry", None),
+            StopReason::Step => ("step", None),
+            StopReason::Breakpoint => ("breakpoint", None),
+            StopReason::Exception(text) => ("exception", Some(text)),
+            StopReason::Terminated => return self.send_event("terminated", None),
+        };
+        let mut body = json!({ "reason": reason, "threadId": THREAD_ID });
+        if let Some(text) = text {
+            body["text"] = Value::from(text);
+        }
+        self.send_event("stopped", Some(body))
+    }
+
+    fn session(&self) -> Result<&DebugSession> {
+        self.session
+            .as_ref()
+            .ok_or_else(|| format_err!("No program launched"))
+    }
+
+    fn session_mut(&mut self) -> Result<&mut DebugSession> {
+        self.session
+            .as_mut()
+            .ok_or_else(|| format_err!("No program launched"))
+    }
+
+    fn send_event(&mut self, event: &str, body: Option<Value>) -> Result<()> {
+        self.send(Message::Event(Event {
+            event: event.to_string(),
+            body,
+        }))
+    }
+
+    fn send(&mut self, message: Message) -> Result<()> {
+        self.seq += 1;
+        write_message(&mut self.writer, self.seq, &message)
+    }
+}
+
+fn scope(name: &str, variables_reference: u64) -> Value {
+    json!({
+        "name": name,
+        "variablesReference": variables_reference,
+        "expensive": false,
+    })
+}
+
+fn to_json(variables: &[Variable]) -> Vec<Value> {
+    variables
+        .iter()
+        .map(|variable| {
+            json!({
+                "name": variable.name,
+                "value": variable.value,
+                "variablesReference": 0,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,13 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! A source-level debugger for Move IR scripts, served over the Debug Adapter Protocol.
+
+pub mod adapter;
+pub mod protocol;
+pub mod session;
+
+pub use adapter::DebugAdapter;
+pub use session::{DebugSession, StopReason, Variable};
@@ -0,0 +1,14 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+use move_debugger::DebugAdapter;
+use std::io;
+
+/// Serves the Debug Adapter Protocol over stdin and stdout.
+fn main() -> anyhow::Result<()> {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    DebugAdapter::new(stdin.lock(), stdout.lock()).run()
+}
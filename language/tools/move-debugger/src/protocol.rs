@@ -0,0 +1,93 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Messages of the Debug Adapter Protocol, and their framing on a byte stream.
+//!
+//! Only the subset of the protocol used by `DebugAdapter` is modeled. Request arguments and
+//! response bodies are kept as JSON values and interpreted by the adapter.
+
+use anyhow::{bail, format_err, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{BufRead, Write};
+
+const CONTENT_LENGTH: &str = "Content-Length:";
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Request {
+    pub seq: u64,
+    pub command: String,
+    #[serde(default)]
+    pub arguments: Value,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Response {
+    pub request_seq: u64,
+    pub success: bool,
+    pub command: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Event {
+    pub event: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+}
+
+/// A protocol message. The `seq` of outgoing messages is assigned when they are written.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Message {
+    Request(Request),
+    Response(Response),
+    Event(Event),
+}
+
+/// Reads the next message, or returns `None` if the stream is closed.
+pub fn read_message<R: BufRead>(reader: &mut R) -> Result<Option<Message>> {
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if header.starts_with(CONTENT_LENGTH) {
+            content_length = Some(header[CONTENT_LENGTH.len()..].trim().parse::<usize>()?);
+        }
+    }
+    let content_length =
+        content_length.ok_or_else(|| format_err!("Message without a Content-Length header"))?;
+    let mut content = vec![0; content_length];
+    reader.read_exact(&mut content)?;
+    Ok(Some(serde_json::from_slice(&content)?))
+}
+
+/// Writes `message` with the sequence number `seq`.
+pub fn write_message<W: Write>(writer: &mut W, seq: u64, message: &Message) -> Result<()> {
+    let mut value = serde_json::to_value(message)?;
+    match value.as_object_mut() {
+        Some(object) => {
+            object.insert("seq".to_string(), Value::from(seq));
+        }
+        None => bail!("Messages must serialize to JSON objects"),
+    }
+    let content = serde_json::to_string(&value)?;
+    write!(
+        writer,
+        "{} {}\r\n\r\n{}",
+        CONTENT_LENGTH,
+        content.len(),
+        content
+    )?;
+    writer.flush()?;
+    Ok(())
+}
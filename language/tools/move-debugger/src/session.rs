@@ -0,0 +1,309 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A debug session over a single execution of a Move IR script.
+//!
+//! The script is executed to completion against a `FakeDataStore` holding the genesis state,
+//! with the VM recording a trace of every instruction along with the locals of the executing
+//! function. Stepping and breakpoints then replay that trace, mapping the instructions of the
+//! script's `main` back to source lines through the source map produced by the compiler.
+//! Calls into modules are stepped over, as their sources are not available.
+
+use anyhow::{format_err, Result};
+use bytecode_source_map::source_map::SourceMap;
+use compiler::Compiler;
+use language_e2e_tests::data_store::{FakeDataStore, GENESIS_CHANGE_SET};
+use move_core_types::{
+    account_address::AccountAddress,
+    gas_schedule::{GasAlgebra, GasUnits},
+};
+use move_ir_types::location::Loc;
+use move_vm_runtime::{
+    execution_trace::{ExecutionTrace, TraceEvent},
+    move_vm::MoveVM,
+};
+use move_vm_types::gas_schedule::{zero_cost_schedule, CostStrategy};
+use std::{collections::BTreeSet, path::Path};
+use vm::{
+    access::ScriptAccess,
+    errors::VMResult,
+    file_format::{CodeOffset, CompiledScript},
+};
+
+/// The name the VM gives to the `main` function of a script in its traces.
+const SCRIPT_MAIN: &str = "Script::main";
+
+/// Gas available to the script. Nothing is charged, this only bounds the execution.
+const MAX_GAS: u64 = 1_000_000;
+
+/// An instruction of the script's `main`, as it was executed.
+#[derive(Clone, Debug)]
+struct Step {
+    pc: CodeOffset,
+    /// 1-based source line of the instruction.
+    line: usize,
+    /// Whether this is the first instruction executed on its line, since the previous step.
+    /// Breakpoints and line stepping only stop on those.
+    line_entry: bool,
+    locals: Vec<String>,
+}
+
+/// A named value shown by the debugger.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Variable {
+    pub name: String,
+    pub value: String,
+}
+
+/// Why a session stopped after a step or continue request.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StopReason {
+    /// Stopped at the first line of the script.
+    Entry,
+    /// Stopped at the next line.
+    Step,
+    /// Stopped at a line with a breakpoint.
+    Breakpoint,
+    /// Stopped after the last instruction because the script failed, with a description of
+    /// the error.
+    Exception(String),
+    /// The script is done executing.
+    Terminated,
+}
+
+pub struct DebugSession {
+    file_name: String,
+    steps: Vec<Step>,
+    /// Index in `steps` of the instruction about to be executed.
+    position: usize,
+    breakpoints: BTreeSet<usize>,
+    local_names: Vec<String>,
+    resources: Vec<Variable>,
+    error: Option<String>,
+    /// Whether the failure of the script has already been reported by a stop.
+    error_reported: bool,
+}
+
+impl DebugSession {
+    /// Compiles the Move IR script at `path` and executes it on behalf of `sender`.
+    pub fn launch(path: &Path, sender: AccountAddress) -> Result<Self> {
+        let source = std::fs::read_to_string(path)?;
+        let file_name = path.to_string_lossy().to_string();
+        let compiler = Compiler {
+            address: sender,
+            ..Compiler::default()
+        };
+        let (script, source_map) =
+            compiler.into_compiled_script_and_source_map(&file_name, &source)?;
+        let mut blob = vec![];
+        script.serialize(&mut blob)?;
+
+        let mut data_store = FakeDataStore::default();
+        data_store.add_write_set(GENESIS_CHANGE_SET.write_set());
+
+        let trace = ExecutionTrace::with_locals();
+        let vm = MoveVM::new();
+        let mut session = vm.new_session(&data_store);
+        session.enable_tracing(trace.clone());
+        let cost_table = zero_cost_schedule();
+        let mut cost_strategy = CostStrategy::system(&cost_table, GasUnits::new(MAX_GAS));
+        let result: VMResult<Vec<Variable>> = session
+            .execute_script(blob, vec![], vec![], vec![sender], &mut cost_strategy)
+            .and_then(|()| session.finish())
+            .map(|effects| {
+                let mut resources = vec![];
+                for (address, changes) in effects.resources {
+                    for (tag, value) in changes {
+                        resources.push(Variable {
+                            name: format!("{}::{}", address.short_str(), tag),
+                            value: match value {
+                                Some((_, value)) => value.to_string(),
+                                None => "(deleted)".to_string(),
+                            },
+                        })
+                    }
+                }
+                resources
+            });
+        let (resources, error) = match result {
+            Ok(resources) => (resources, None),
+            Err(err) => (vec![], Some(format!("{:?}", err))),
+        };
+
+        Ok(Self {
+            file_name,
+            steps: script_steps(&source, &source_map, trace.take_events())?,
+            position: 0,
+            breakpoints: BTreeSet::new(),
+            local_names: local_names(&script, &source_map),
+            resources,
+            error,
+            error_reported: false,
+        })
+    }
+
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    /// Replaces the breakpoints. Returns, for each requested line, whether execution ever
+    /// reaches it and the breakpoint can therefore be hit.
+    pub fn set_breakpoints(&mut self, lines: &[usize]) -> Vec<bool> {
+        self.breakpoints = lines.iter().cloned().collect();
+        lines
+            .iter()
+            .map(|line| {
+                self.steps
+                    .iter()
+                    .any(|step| step.line_entry && step.line == *line)
+            })
+            .collect()
+    }
+
+    /// Stops at the first line of the script.
+    pub fn start(&mut self) -> StopReason {
+        self.position = 0;
+        if self.steps.is_empty() {
+            return self.finish();
+        }
+        StopReason::Entry
+    }
+
+    /// Runs until the next line with a breakpoint.
+    pub fn continue_(&mut self) -> StopReason {
+        self.advance(
+            |step, breakpoints| breakpoints.contains(&step.line),
+            StopReason::Breakpoint,
+        )
+    }
+
+    /// Runs until the next line.
+    pub fn step(&mut self) -> StopReason {
+        self.advance(|_, _| true, StopReason::Step)
+    }
+
+    fn advance<F>(&mut self, stop_at: F, reason: StopReason) -> StopReason
+    where
+        F: Fn(&Step, &BTreeSet<usize>) -> bool,
+    {
+        let next = self
+            .steps
+            .iter()
+            .enumerate()
+            .skip(self.position + 1)
+            .find(|(_, step)| step.line_entry && stop_at(step, &self.breakpoints))
+            .map(|(position, _)| position);
+        match next {
+            Some(position) => {
+                self.position = position;
+                reason
+            }
+            None => self.finish(),
+        }
+    }
+
+    fn finish(&mut self) -> StopReason {
+        match &self.error {
+            Some(error) if !self.error_reported => {
+                self.error_reported = true;
+                StopReason::Exception(error.clone())
+            }
+            _ => StopReason::Terminated,
+        }
+    }
+
+    /// The line the session is stopped at, if the script executed any instruction.
+    pub fn current_line(&self) -> Option<usize> {
+        self.steps.get(self.position).map(|step| step.line)
+    }
+
+    /// The code offset the session is stopped at, if the script executed any instruction.
+    pub fn current_pc(&self) -> Option<CodeOffset> {
+        self.steps.get(self.position).map(|step| step.pc)
+    }
+
+    /// The locals of `main` before the current instruction is executed.
+    pub fn locals(&self) -> Vec<Variable> {
+        let step = match self.steps.get(self.position) {
+            Some(step) => step,
+            None => return vec![],
+        };
+        step.locals
+            .iter()
+            .enumerate()
+            .map(|(idx, value)| Variable {
+                name: self
+                    .local_names
+                    .get(idx)
+                    .cloned()
+                    .unwrap_or_else(|| format!("[{}]", idx)),
+                value: value.clone(),
+            })
+            .collect()
+    }
+
+    /// The resources written by the script. They are only known once execution is over, and
+    /// are empty if the script failed.
+    pub fn resources(&self) -> &[Variable] {
+        &self.resources
+    }
+}
+
+fn script_steps(
+    source: &str,
+    source_map: &SourceMap<Loc>,
+    events: Vec<TraceEvent>,
+) -> Result<Vec<Step>> {
+    let mut steps: Vec<Step> = vec![];
+    for event in events {
+        if let TraceEvent::Instruction {
+            function,
+            pc,
+            locals,
+            ..
+        } = event
+        {
+            if function != SCRIPT_MAIN {
+                continue;
+            }
+            let loc = source_map.get_code_location(CompiledScript::MAIN_INDEX, pc)?;
+            let line = line_of(source, loc.span().start().to_usize())
+                .ok_or_else(|| format_err!("Code offset {} maps outside of the source", pc))?;
+            let line_entry = steps.last().map_or(true, |prev| prev.line != line);
+            steps.push(Step {
+                pc,
+                line,
+                line_entry,
+                locals,
+            });
+        }
+    }
+    Ok(steps)
+}
+
+fn local_names(script: &CompiledScript, source_map: &SourceMap<Loc>) -> Vec<String> {
+    let num_locals = script.signature_at(script.as_inner().parameters).len()
+        + script.signature_at(script.code().locals).len();
+    (0..num_locals)
+        .map(|idx| {
+            source_map
+                .get_parameter_or_local_name(CompiledScript::MAIN_INDEX, idx as u64)
+                .map(|(name, _)| name)
+                .unwrap_or_else(|_| format!("[{}]", idx))
+        })
+        .collect()
+}
+
+/// Returns the 1-based line of the byte at `offset`.
+fn line_of(source: &str, offset: usize) -> Option<usize> {
+    if offset > source.len() {
+        return None;
+    }
+    Some(
+        source.as_bytes()[..offset]
+            .iter()
+            .filter(|b| **b == b'\n')
+            .count()
+            + 1,
+    )
+}
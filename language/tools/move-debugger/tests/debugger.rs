@@ -0,0 +1,111 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use libra_temppath::TempPath;
+use libra_types::account_config;
+use move_debugger::{
+    protocol::{read_message, write_message, Message, Request},
+    DebugAdapter, DebugSession, StopReason,
+};
+use serde_json::json;
+use std::{io::Cursor, path::PathBuf};
+
+const SCRIPT: &str = "\
+main() {
+    let x: u64;
+    let y: u64;
+    x = 1;
+    y = move(x) + 2;
+    return;
+}
+";
+
+fn write_script(temp_dir: &TempPath) -> PathBuf {
+    std::fs::create_dir_all(temp_dir.path()).unwrap();
+    let path = temp_dir.path().join("script.mvir");
+    std::fs::write(&path, SCRIPT).unwrap();
+    path
+}
+
+#[test]
+fn step_and_break() {
+    let temp_dir = TempPath::new();
+    let path = write_script(&temp_dir);
+    let mut session = DebugSession::launch(&path, account_config::libra_root_address()).unwrap();
+
+    assert_eq!(session.set_breakpoints(&[5, 100]), vec![true, false]);
+    assert_eq!(session.start(), StopReason::Entry);
+    assert_eq!(session.current_line(), Some(4));
+
+    assert_eq!(session.continue_(), StopReason::Breakpoint);
+    assert_eq!(session.current_line(), Some(5));
+    let locals = session.locals();
+    assert_eq!(locals[0].name, "x");
+    assert_eq!(locals[0].value, "U64(1)");
+    assert_eq!(locals[1].name, "y");
+    assert_eq!(locals[1].value, "Invalid");
+
+    assert_eq!(session.step(), StopReason::Step);
+    assert_eq!(session.current_line(), Some(6));
+    assert_eq!(session.locals()[1].value, "U64(3)");
+
+    assert_eq!(session.step(), StopReason::Terminated);
+}
+
+#[test]
+fn serve_requests() {
+    let temp_dir = TempPath::new();
+    let path = write_script(&temp_dir);
+    let requests = vec![
+        ("initialize", json!({})),
+        ("setBreakpoints", json!({ "breakpoints": [{ "line": 5 }] })),
+        (
+            "launch",
+            json!({ "program": path.to_string_lossy().to_string() }),
+        ),
+        ("configurationDone", json!({})),
+        ("variables", json!({ "variablesReference": 1 })),
+        ("continue", json!({})),
+        ("disconnect", json!({})),
+    ];
+    let mut input = vec![];
+    for (seq, (command, arguments)) in requests.into_iter().enumerate() {
+        let request = Message::Request(Request {
+            seq: seq as u64,
+            command: command.to_string(),
+            arguments,
+        });
+        write_message(&mut input, seq as u64, &request).unwrap();
+    }
+
+    let mut output = vec![];
+    DebugAdapter::new(Cursor::new(input), &mut output)
+        .run()
+        .unwrap();
+
+    let mut reader = Cursor::new(output);
+    let mut messages = vec![];
+    while let Some(message) = read_message(&mut reader).unwrap() {
+        messages.push(message);
+    }
+    let events = messages
+        .iter()
+        .filter_map(|message| match message {
+            Message::Event(event) => Some(event.event.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(events, vec!["initialized", "stopped", "terminated"]);
+    assert!(messages.iter().all(|message| match message {
+        Message::Response(response) => response.success,
+        _ => true,
+    }));
+    let variables = messages
+        .iter()
+        .find_map(|message| match message {
+            Message::Response(response) if response.command == "variables" => response.body.clone(),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(variables["variables"][0]["value"], "U64(1)");
+}
@@ -0,0 +1,254 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! A runner for Move unit tests, the module functions annotated with `#[test]`.
+//!
+//! The sources are compiled together with their dependencies, and every test declared in the
+//! sources is executed in an in-memory VM in which only the compiled modules are published.
+//! A test may take a `&signer`, in which case it is given a signer for the sender the tests are
+//! run with. A test passes if it runs to completion, or if it fails when it is annotated with
+//! `#[expected_failure]`. A test running out of gas fails, gas bounding the number of
+//! instructions it can execute.
+
+use anyhow::{bail, Result};
+use language_e2e_tests::data_store::FakeDataStore;
+use move_core_types::{
+    account_address::AccountAddress,
+    gas_schedule::{CostTable, GasAlgebra, GasCost, GasUnits},
+    identifier::Identifier,
+    language_storage::ModuleId,
+    vm_status::StatusCode,
+};
+use move_lang::{
+    compiled_unit::CompiledUnit, errors::report_errors_to_buffer,
+    move_compile_unit_tests_no_report, shared::Address, unit_test::TestFunction,
+};
+use move_vm_runtime::move_vm::MoveVM;
+use move_vm_types::{
+    gas_schedule::{zero_cost_schedule, CostStrategy},
+    values::Value,
+};
+use std::{collections::BTreeMap, fmt};
+use vm::{
+    access::ModuleAccess,
+    errors::VMError,
+    file_format::{CompiledModule, SignatureToken},
+};
+
+/// Gas available to each test, so that a test which does not terminate fails instead of hanging
+/// the run. See `unit_cost_schedule` for what it is charged for.
+const MAX_GAS: u64 = 1_000_000;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TestOutcome {
+    Passed,
+    /// The test failed, with a description of the failure.
+    Failed(String),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TestResult {
+    /// The name of the test, in the form `Module::function`.
+    pub name: String,
+    pub outcome: TestOutcome,
+}
+
+/// The results of a test run, in the order in which the tests are declared.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TestReport {
+    pub results: Vec<TestResult>,
+}
+
+impl TestReport {
+    pub fn passed(&self) -> impl Iterator<Item = &TestResult> {
+        self.results
+            .iter()
+            .filter(|r| r.outcome == TestOutcome::Passed)
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &TestResult> {
+        self.results
+            .iter()
+            .filter(|r| r.outcome != TestOutcome::Passed)
+    }
+
+    pub fn all_passed(&self) -> bool {
+        self.failed().next().is_none()
+    }
+}
+
+impl fmt::Display for TestReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for result in &self.results {
+            match &result.outcome {
+                TestOutcome::Passed => writeln!(f, "test {} ... ok", result.name)?,
+                TestOutcome::Failed(reason) => {
+                    writeln!(f, "test {} ... FAILED: {}", result.name, reason)?
+                }
+            }
+        }
+        write!(
+            f,
+            "test result: {} passed; {} failed",
+            self.passed().count(),
+            self.failed().count()
+        )
+    }
+}
+
+/// Compiles `targets` with `deps`, modules without an address being published under `sender`,
+/// and runs the tests declared in `targets`.
+pub fn run_move_unit_tests(
+    targets: &[String],
+    deps: &[String],
+    sender: Address,
+) -> Result<TestReport> {
+    let (files, units_and_tests) = move_compile_unit_tests_no_report(targets, deps, Some(sender))?;
+    let (units, tests) = match units_and_tests {
+        Ok(units_and_tests) => units_and_tests,
+        Err(errors) => bail!(
+            "Unable to compile the tests:\n{}",
+            String::from_utf8_lossy(&report_errors_to_buffer(files, errors))
+        ),
+    };
+
+    let mut data_store = FakeDataStore::default();
+    let mut modules = BTreeMap::new();
+    for unit in units {
+        if let CompiledUnit::Module { module, .. } = unit {
+            let module_id = module.self_id();
+            data_store.add_module(&module_id, &module);
+            modules.insert(module_id, module);
+        }
+    }
+
+    let vm = MoveVM::new();
+    let cost_table = unit_cost_schedule();
+    let signer = AccountAddress::new(sender.to_u8());
+    let results = tests
+        .iter()
+        .map(|test| TestResult {
+            name: test.name(),
+            outcome: run_test(&vm, &data_store, &cost_table, &modules, test, signer),
+        })
+        .collect();
+    Ok(TestReport { results })
+}
+
+/// Runs the tests like `run_move_unit_tests` and panics with the report if any of them fails,
+/// so that Move tests can be run as part of a Rust test.
+pub fn assert_move_unit_tests_pass(targets: &[String], deps: &[String], sender: Address) {
+    let report = match run_move_unit_tests(targets, deps, sender) {
+        Ok(report) => report,
+        Err(err) => panic!("{}", err),
+    };
+    if !report.all_passed() {
+        panic!("Move unit tests failed:\n{}", report);
+    }
+}
+
+/// A cost table in which every instruction and native function costs one unit of gas, times the
+/// size of the data it is charged for, if any. The gas used by a test then counts the
+/// instructions it executed, large values counting for more.
+fn unit_cost_schedule() -> CostTable {
+    let mut cost_table = zero_cost_schedule();
+    for cost in cost_table
+        .instruction_table
+        .iter_mut()
+        .chain(cost_table.native_table.iter_mut())
+    {
+        *cost = GasCost::new(1, 0);
+    }
+    cost_table.gas_constants.gas_unit_scaling_factor = 1;
+    cost_table
+}
+
+fn run_test(
+    vm: &MoveVM,
+    data_store: &FakeDataStore,
+    cost_table: &CostTable,
+    modules: &BTreeMap<ModuleId, CompiledModule>,
+    test: &TestFunction,
+    signer: AccountAddress,
+) -> TestOutcome {
+    let args = match test_arguments(modules, test, signer) {
+        Ok(args) => args,
+        Err(err) => return TestOutcome::Failed(err.to_string()),
+    };
+    let module_id = test_module_id(test);
+    let function_name =
+        Identifier::new(test.function_name.as_str()).expect("function names are valid identifiers");
+
+    let mut session = vm.new_session(data_store);
+    let mut cost_strategy = CostStrategy::transaction(cost_table, GasUnits::new(MAX_GAS));
+    let result = session.execute_function(
+        &module_id,
+        &function_name,
+        vec![],
+        args,
+        signer,
+        &mut cost_strategy,
+    );
+    match (result, test.expected_failure) {
+        (Ok(()), false) | (Err(_), true) => TestOutcome::Passed,
+        (Ok(()), true) => TestOutcome::Failed("Expected the test to fail".to_string()),
+        (Err(err), false) => TestOutcome::Failed(describe_failure(&err)),
+    }
+}
+
+/// Returns the arguments of a test, checking that it takes no type arguments, at most a
+/// `&signer`, and returns nothing.
+fn test_arguments(
+    modules: &BTreeMap<ModuleId, CompiledModule>,
+    test: &TestFunction,
+    signer: AccountAddress,
+) -> Result<Vec<Value>> {
+    let module = match modules.get(&test_module_id(test)) {
+        Some(module) => module,
+        None => bail!("Module {} not found", test.module_name),
+    };
+    let handle = module
+        .function_defs()
+        .iter()
+        .map(|def| module.function_handle_at(def.function))
+        .find(|handle| module.identifier_at(handle.name).as_str() == test.function_name);
+    let handle = match handle {
+        Some(handle) => handle,
+        None => bail!("Function {} not found", test.name()),
+    };
+    let parameters = &module.signature_at(handle.parameters).0;
+    if !handle.type_parameters.is_empty()
+        || !module.signature_at(handle.return_).is_empty()
+        || parameters.len() > 1
+        || !parameters.iter().all(is_signer_ref)
+    {
+        bail!("Tests must take no type arguments, at most a '&signer', and return nothing");
+    }
+    Ok(parameters
+        .iter()
+        .map(|_| Value::transaction_argument_signer_reference(signer))
+        .collect())
+}
+
+fn is_signer_ref(tok: &SignatureToken) -> bool {
+    match tok {
+        SignatureToken::Reference(inner) => **inner == SignatureToken::Signer,
+        _ => false,
+    }
+}
+
+fn test_module_id(test: &TestFunction) -> ModuleId {
+    ModuleId::new(
+        AccountAddress::new(test.address.to_u8()),
+        Identifier::new(test.module_name.as_str()).expect("module names are valid identifiers"),
+    )
+}
+
+fn describe_failure(err: &VMError) -> String {
+    match err.major_status() {
+        StatusCode::ABORTED => format!("Aborted with code {}", err.sub_status().unwrap_or(0)),
+        status => format!("Failed with {:?}", status),
+    }
+}
@@ -0,0 +1,50 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+use move_lang::{command_line as cli, shared::*};
+use structopt::*;
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "Move Unit Test",
+    about = "Run the functions of Move modules annotated with #[test]."
+)]
+pub struct Options {
+    /// The source files containing the tests
+    #[structopt(name = "PATH_TO_SOURCE_FILE")]
+    pub source_files: Vec<String>,
+
+    /// The library files needed as dependencies
+    #[structopt(
+        name = "PATH_TO_DEPENDENCY_FILE",
+        short = cli::DEPENDENCY_SHORT,
+        long = cli::DEPENDENCY,
+    )]
+    pub dependencies: Vec<String>,
+
+    /// The sender address for modules, and the address of the signer given to tests
+    #[structopt(
+        name = "ADDRESS",
+        short = cli::SENDER_SHORT,
+        long = cli::SENDER,
+        parse(try_from_str = cli::parse_address),
+        default_value = "0x1",
+    )]
+    pub sender: Address,
+}
+
+pub fn main() -> anyhow::Result<()> {
+    let Options {
+        source_files,
+        dependencies,
+        sender,
+    } = Options::from_args();
+    let report = move_unit_test::run_move_unit_tests(&source_files, &dependencies, sender)?;
+    println!("{}", report);
+    if !report.all_passed() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
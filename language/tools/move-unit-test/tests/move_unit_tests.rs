@@ -0,0 +1,86 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_lang::{compiled_unit::CompiledUnit, move_compile_no_report, shared::Address};
+use move_unit_test::{assert_move_unit_tests_pass, run_move_unit_tests, TestOutcome, TestResult};
+use vm::access::ModuleAccess;
+
+fn sender() -> Address {
+    Address::parse_str("0x2").unwrap()
+}
+
+#[test]
+fn passing_tests() {
+    assert_move_unit_tests_pass(
+        &["tests/sources/passing.move".to_string()],
+        &stdlib::stdlib_files(),
+        sender(),
+    );
+}
+
+#[test]
+fn stdlib_tests() {
+    assert_move_unit_tests_pass(&stdlib::stdlib_files(), &[], Address::LIBRA_CORE);
+}
+
+#[test]
+fn tests_are_left_out_of_regular_builds() {
+    let (_, units) = move_compile_no_report(
+        &["tests/sources/passing.move".to_string()],
+        &stdlib::stdlib_files(),
+        Some(sender()),
+    )
+    .unwrap();
+    let module = match units.unwrap().pop() {
+        Some(CompiledUnit::Module { module, .. }) => module,
+        _ => panic!("Expected a single module"),
+    };
+    let function_names: Vec<_> = module
+        .function_defs()
+        .iter()
+        .map(|def| {
+            module
+                .identifier_at(module.function_handle_at(def.function).name)
+                .to_string()
+        })
+        .collect();
+    assert_eq!(function_names, vec!["not_a_test"]);
+}
+
+#[test]
+fn failing_tests() {
+    let report = run_move_unit_tests(
+        &["tests/sources/failing.move".to_string()],
+        &stdlib::stdlib_files(),
+        sender(),
+    )
+    .unwrap();
+    let failed = |name: &str, reason: &str| TestResult {
+        name: name.to_string(),
+        outcome: TestOutcome::Failed(reason.to_string()),
+    };
+    assert_eq!(
+        report.results,
+        vec![
+            failed("FailingTests::aborts", "Aborted with code 42"),
+            failed("FailingTests::does_not_fail", "Expected the test to fail"),
+            failed(
+                "FailingTests::takes_an_integer",
+                "Tests must take no type arguments, at most a '&signer', and return nothing",
+            ),
+            failed("FailingTests::loops_forever", "Failed with OUT_OF_GAS"),
+        ]
+    );
+    assert!(!report.all_passed());
+}
+
+#[test]
+fn unknown_attribute_does_not_compile() {
+    let err = run_move_unit_tests(
+        &["tests/sources/unknown_attribute.move".to_string()],
+        &[],
+        sender(),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("Unknown attribute 'tset'"));
+}
@@ -0,0 +1,20 @@
+address 0x2 {
+module FailingTests {
+    #[test]
+    fun aborts() {
+        abort 42
+    }
+
+    #[test]
+    #[expected_failure]
+    fun does_not_fail() {}
+
+    #[test]
+    fun takes_an_integer(_x: u64) {}
+
+    #[test]
+    fun loops_forever() {
+        loop {}
+    }
+}
+}
@@ -0,0 +1,29 @@
+address 0x2 {
+module VectorTests {
+    use 0x1::Signer;
+    use 0x1::Vector;
+
+    #[test]
+    fun push_and_pop() {
+        let v = Vector::empty<u64>();
+        Vector::push_back(&mut v, 1);
+        assert(Vector::pop_back(&mut v) == 1, 0);
+        assert(Vector::is_empty(&v), 1);
+    }
+
+    #[test]
+    fun signer_is_sender(account: &signer) {
+        assert(Signer::address_of(account) == 0x2, 0);
+    }
+
+    #[test, expected_failure]
+    fun pop_empty() {
+        let v = Vector::empty<u64>();
+        let _x = Vector::pop_back(&mut v);
+    }
+
+    fun not_a_test() {
+        abort 0
+    }
+}
+}
@@ -0,0 +1,6 @@
+address 0x2 {
+module UnknownAttribute {
+    #[tset]
+    fun misspelled() {}
+}
+}
@@ -0,0 +1,129 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{encode_change_set, OperatorAssignment, OperatorRegistration};
+use compiled_stdlib::{stdlib_modules, transaction_scripts::StdlibScript, StdLibOptions};
+use libra_crypto::ed25519::Ed25519PublicKey;
+use libra_types::{
+    account_address, account_config,
+    chain_id::ChainId,
+    on_chain_config::VMPublishingOption,
+    transaction::{ChangeSet, Transaction},
+};
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::{StructTag, TypeTag},
+};
+use std::collections::BTreeMap;
+use vm::CompiledModule;
+
+/// An account created at genesis, with initial balances in currencies registered at genesis.
+#[derive(Clone, Debug)]
+pub struct GenesisAccount {
+    pub public_key: Ed25519PublicKey,
+    pub balances: Vec<(Identifier, u64)>,
+}
+
+impl GenesisAccount {
+    pub fn new(public_key: Ed25519PublicKey) -> Self {
+        Self {
+            public_key,
+            balances: vec![],
+        }
+    }
+
+    /// Adds an initial balance of `amount` in `currency_code`, which must be one of the currencies
+    /// that can be minted at genesis (e.g., Coin1 or Coin2).
+    pub fn with_balance(mut self, currency_code: Identifier, amount: u64) -> Self {
+        self.balances.push((currency_code, amount));
+        self
+    }
+
+    pub fn address(&self) -> AccountAddress {
+        account_address::from_public_key(&self.public_key)
+    }
+
+    pub(crate) fn currency_tags(&self) -> impl Iterator<Item = (TypeTag, u64)> + '_ {
+        self.balances.iter().map(|(currency_code, amount)| {
+            (
+                account_config::type_tag_for_currency_code(currency_code.clone()),
+                *amount,
+            )
+        })
+    }
+}
+
+/// Builds the genesis transaction of a chain from its root key, validators, initial accounts and
+/// on-chain configuration, instead of the hard-coded testnet defaults.
+pub struct GenesisBuilder {
+    pub(crate) root_key: Ed25519PublicKey,
+    pub(crate) chain_id: ChainId,
+    pub(crate) operator_assignments: Vec<OperatorAssignment>,
+    pub(crate) operator_registrations: Vec<OperatorRegistration>,
+    pub(crate) vm_publishing_option: VMPublishingOption,
+    pub(crate) accounts: Vec<GenesisAccount>,
+    pub(crate) testnet_minting: bool,
+}
+
+impl GenesisBuilder {
+    /// The `root_key` is the key of the Libra root and treasury compliance accounts.
+    pub fn new(root_key: Ed25519PublicKey, chain_id: ChainId) -> Self {
+        Self {
+            root_key,
+            chain_id,
+            operator_assignments: vec![],
+            operator_registrations: vec![],
+            vm_publishing_option: VMPublishingOption::locked(StdlibScript::whitelist()),
+            accounts: vec![],
+            testnet_minting: true,
+        }
+    }
+
+    /// Sets the validator set: each owner is assigned an operator, and each operator registers a
+    /// validator config.
+    pub fn with_validators(
+        mut self,
+        operator_assignments: Vec<OperatorAssignment>,
+        operator_registrations: Vec<OperatorRegistration>,
+    ) -> Self {
+        self.operator_assignments = operator_assignments;
+        self.operator_registrations = operator_registrations;
+        self
+    }
+
+    pub fn with_vm_publishing_option(mut self, vm_publishing_option: VMPublishingOption) -> Self {
+        self.vm_publishing_option = vm_publishing_option;
+        self
+    }
+
+    pub fn with_account(mut self, account: GenesisAccount) -> Self {
+        self.accounts.push(account);
+        self
+    }
+
+    /// Skips the testnet designated dealer and its funds, which private chains do not need.
+    pub fn without_testnet_minting(mut self) -> Self {
+        self.testnet_minting = false;
+        self
+    }
+
+    /// Builds the genesis transaction with the compiled stdlib.
+    pub fn build(&self) -> Transaction {
+        // Must use compiled stdlib
+        Transaction::WaypointWriteSet(
+            self.build_change_set(stdlib_modules(StdLibOptions::Compiled)),
+        )
+    }
+
+    pub fn build_change_set(&self, stdlib_modules: &[CompiledModule]) -> ChangeSet {
+        encode_change_set(self, stdlib_modules).0
+    }
+
+    pub fn build_change_set_and_type_mapping(
+        &self,
+        stdlib_modules: &[CompiledModule],
+    ) -> (ChangeSet, BTreeMap<Vec<u8>, StructTag>) {
+        encode_change_set(self, stdlib_modules)
+    }
+}
@@ -3,11 +3,14 @@
 
 #![forbid(unsafe_code)]
 
+mod genesis_builder;
 mod genesis_context;
 mod genesis_gas_schedule;
 
+pub use crate::genesis_builder::{GenesisAccount, GenesisBuilder};
+
 use crate::{genesis_context::GenesisStateView, genesis_gas_schedule::INITIAL_GAS_SCHEDULE};
-use compiled_stdlib::{stdlib_modules, transaction_scripts::StdlibScript, StdLibOptions};
+use compiled_stdlib::{stdlib_modules, StdLibOptions};
 use libra_config::config::{NodeConfig, HANDSHAKE_VERSION};
 use libra_crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
@@ -21,6 +24,7 @@ use libra_network_address::{
 };
 use libra_types::{
     account_address, account_config,
+    chain_id::ChainId,
     contract_event::ContractEvent,
     on_chain_config::{new_epoch_event_key, VMPublishingOption},
     transaction::{
@@ -67,18 +71,16 @@ pub fn encode_genesis_transaction(
     operator_assignments: &[OperatorAssignment],
     operator_registrations: &[OperatorRegistration],
     vm_publishing_option: Option<VMPublishingOption>,
+    chain_id: ChainId,
 ) -> Transaction {
-    Transaction::WaypointWriteSet(
-        encode_genesis_change_set(
-            &public_key,
-            operator_assignments,
-            operator_registrations,
-            stdlib_modules(StdLibOptions::Compiled), // Must use compiled stdlib,
-            vm_publishing_option
-                .unwrap_or_else(|| VMPublishingOption::locked(StdlibScript::whitelist())),
-        )
-        .0,
-    )
+    let mut builder = GenesisBuilder::new(public_key, chain_id).with_validators(
+        operator_assignments.to_vec(),
+        operator_registrations.to_vec(),
+    );
+    if let Some(vm_publishing_option) = vm_publishing_option {
+        builder = builder.with_vm_publishing_option(vm_publishing_option);
+    }
+    builder.build()
 }
 
 fn merge_txn_effects(
@@ -97,6 +99,20 @@ pub fn encode_genesis_change_set(
     operator_registrations: &[OperatorRegistration],
     stdlib_modules: &[CompiledModule],
     vm_publishing_option: VMPublishingOption,
+    chain_id: ChainId,
+) -> (ChangeSet, BTreeMap<Vec<u8>, StructTag>) {
+    GenesisBuilder::new(public_key.clone(), chain_id)
+        .with_validators(
+            operator_assignments.to_vec(),
+            operator_registrations.to_vec(),
+        )
+        .with_vm_publishing_option(vm_publishing_option)
+        .build_change_set_and_type_mapping(stdlib_modules)
+}
+
+fn encode_change_set(
+    builder: &GenesisBuilder,
+    stdlib_modules: &[CompiledModule],
 ) -> (ChangeSet, BTreeMap<Vec<u8>, StructTag>) {
     // create a data view for move_vm
     let mut state_view = GenesisStateView::new();
@@ -117,16 +133,25 @@ pub fn encode_genesis_change_set(
     });
 
     // generate the genesis WriteSet
-    create_and_initialize_main_accounts(&mut session, &public_key, vm_publishing_option, &lbr_ty);
+    create_and_initialize_main_accounts(
+        &mut session,
+        &builder.root_key,
+        builder.vm_publishing_option.clone(),
+        builder.chain_id,
+        &lbr_ty,
+    );
     create_and_initialize_owners_operators(
         &mut session,
-        &operator_assignments,
-        &operator_registrations,
+        &builder.operator_assignments,
+        &builder.operator_registrations,
     );
     reconfigure(&mut session);
 
     // XXX/TODO: for testnet only
-    create_and_initialize_testnet_minting(&mut session, &public_key);
+    if builder.testnet_minting {
+        create_and_initialize_testnet_minting(&mut session, &builder.root_key);
+    }
+    create_and_fund_accounts(&mut session, &builder.accounts);
 
     let effects_1 = session.finish().unwrap();
 
@@ -165,6 +190,7 @@ fn convert_txn_args(args: &[TransactionArgument]) -> Vec<Value> {
             TransactionArgument::Address(a) => Value::address(*a),
             TransactionArgument::Bool(b) => Value::bool(*b),
             TransactionArgument::U8Vector(v) => Value::vector_u8(v.clone()),
+            TransactionArgument::U8VectorVector(vs) => Value::vector_vector_u8(vs.clone()),
         })
         .collect()
 }
@@ -198,7 +224,7 @@ fn exec_script(session: &mut Session<StateViewCache>, sender: AccountAddress, sc
             script.code().to_vec(),
             script.ty_args().to_vec(),
             convert_txn_args(script.args()),
-            sender,
+            vec![sender],
             &mut CostStrategy::system(&ZERO_COST_SCHEDULE, GasUnits::new(100_000_000)),
         )
         .unwrap()
@@ -209,6 +235,7 @@ fn create_and_initialize_main_accounts(
     session: &mut Session<StateViewCache>,
     public_key: &Ed25519PublicKey,
     publishing_option: VMPublishingOption,
+    chain_id: ChainId,
     lbr_ty: &TypeTag,
 ) {
     let genesis_auth_key = AuthenticationKey::ed25519(public_key);
@@ -216,9 +243,6 @@ fn create_and_initialize_main_accounts(
     let root_libra_root_address = account_config::libra_root_address();
     let tc_account_address = account_config::treasury_compliance_account_address();
 
-    let option_bytes =
-        lcs::to_bytes(&publishing_option).expect("Cannot serialize publishing option");
-
     exec_function(
         session,
         root_libra_root_address,
@@ -230,9 +254,17 @@ fn create_and_initialize_main_accounts(
             Value::transaction_argument_signer_reference(tc_account_address),
             Value::address(tc_account_address),
             Value::vector_u8(genesis_auth_key.to_vec()),
-            Value::vector_u8(option_bytes),
+            Value::vector_vector_u8(
+                publishing_option
+                    .script_allow_list
+                    .iter()
+                    .map(|hash| hash.to_vec()),
+            ),
+            Value::bool(publishing_option.is_open_script),
+            Value::bool(publishing_option.is_open_module),
             Value::vector_u8(INITIAL_GAS_SCHEDULE.0.clone()),
             Value::vector_u8(INITIAL_GAS_SCHEDULE.1.clone()),
+            Value::u8(chain_id.id()),
         ],
     );
 
@@ -360,6 +392,51 @@ fn create_and_initialize_testnet_minting(
     );
 }
 
+/// Creates each initial account as a designated dealer holding all the currencies, and mints its
+/// initial balances.
+fn create_and_fund_accounts(session: &mut Session<StateViewCache>, accounts: &[GenesisAccount]) {
+    let tc_account_address = account_config::treasury_compliance_account_address();
+    for account in accounts {
+        let auth_key = AuthenticationKey::ed25519(&account.public_key);
+        let address = account.address();
+        exec_script(
+            session,
+            tc_account_address,
+            &encode_create_designated_dealer_script(
+                account_config::coin1_tag(),
+                0,
+                address,
+                auth_key.prefix().to_vec(),
+                b"genesis".to_vec(),                    // name
+                b"https://libra.org".to_vec(),          // base_url
+                account.public_key.to_bytes().to_vec(), // compliance_public_key
+                true,                                   // add_all_currencies
+            ),
+        );
+        for (currency, amount) in account.currency_tags() {
+            // Lifts the limit of the highest tier so that any balance can be minted at once
+            exec_function(
+                session,
+                tc_account_address,
+                "DesignatedDealer",
+                "update_tier",
+                vec![currency.clone()],
+                vec![
+                    Value::transaction_argument_signer_reference(tc_account_address),
+                    Value::address(address),
+                    Value::u64(3),
+                    Value::u64(std::u64::MAX),
+                ],
+            );
+            exec_script(
+                session,
+                tc_account_address,
+                &transaction_builder::encode_tiered_mint_script(currency, 0, address, amount, 3),
+            );
+        }
+    }
+}
+
 /// Creates and initializes each validator owner and validator operator. This method creates all
 /// the required accounts, sets the validator operators for each validator owner, and sets the
 /// validator config on-chain.
@@ -502,6 +579,7 @@ pub fn generate_genesis_change_set_for_testing(stdlib_options: StdLibOptions) ->
         &operator_registrations(&swarm.nodes),
         stdlib_modules,
         VMPublishingOption::open(),
+        ChainId::test(),
     )
     .0
 }
@@ -517,6 +595,7 @@ pub fn generate_genesis_type_mapping() -> BTreeMap<Vec<u8>, StructTag> {
         &operator_registrations(&swarm.nodes),
         stdlib_modules,
         VMPublishingOption::open(),
+        ChainId::test(),
     )
     .1
 }
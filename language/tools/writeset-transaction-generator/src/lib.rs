@@ -0,0 +1,102 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! Generates the change sets of administrative operations that cannot be performed by a regular
+//! transaction. The libra root account applies them with a `WriteSet` transaction, whose epilogue
+//! triggers a reconfiguration.
+
+use anyhow::{ensure, format_err, Result};
+use libra_state_view::StateView;
+use libra_types::{
+    access_path::AccessPath,
+    account_config,
+    on_chain_config::{new_epoch_event_key, ConfigurationResource},
+    transaction::ChangeSet,
+    write_set::WriteSetMut,
+};
+use libra_vm::{data_cache::StateViewCache, txn_effects_to_writeset_and_events};
+use move_core_types::{
+    gas_schedule::{GasAlgebra, GasUnits},
+    identifier::Identifier,
+    move_resource::MoveResource,
+};
+use move_vm_runtime::move_vm::MoveVM;
+use move_vm_types::{
+    gas_schedule::{zero_cost_schedule, CostStrategy},
+    values::Value,
+};
+use vm::CompiledModule;
+
+const CURRENCY_INITIALIZE_NAME: &str = "initialize";
+
+/// Returns the change set registering the currency defined by `currency_module` on top of
+/// `state_view`.
+///
+/// The module is published under the core code address and its
+/// `initialize(lr_account: &signer, tc_account: &signer)` function is called with the libra root
+/// and treasury compliance accounts, in the same way as `Coin1::initialize` during genesis. It is
+/// expected to register the currency with `Libra::register_SCS_currency`, publish its
+/// `AccountLimits::LimitsDefinition` under the libra root account, and set up the collection of
+/// transaction fees with `TransactionFee::add_currency`.
+pub fn encode_register_currency_change_set(
+    state_view: &dyn StateView,
+    currency_module: &CompiledModule,
+) -> Result<ChangeSet> {
+    let module_id = currency_module.self_id();
+    ensure!(
+        module_id.address() == &account_config::CORE_CODE_ADDRESS,
+        "Currency module {} must be published under the core code address",
+        module_id
+    );
+    let mut module_bytes = vec![];
+    currency_module.serialize(&mut module_bytes)?;
+
+    let libra_root_address = account_config::libra_root_address();
+    let tc_address = account_config::treasury_compliance_account_address();
+    let gas_schedule = zero_cost_schedule();
+    let mut cost_strategy = CostStrategy::system(&gas_schedule, GasUnits::new(100_000_000));
+
+    let data_cache = StateViewCache::new(state_view);
+    let move_vm = MoveVM::new();
+    let mut session = move_vm.new_session(&data_cache);
+    session
+        .publish_module(module_bytes, *module_id.address(), &mut cost_strategy)
+        .map_err(|e| format_err!("Failed to publish module {}: {:?}", module_id, e))?;
+    session
+        .execute_function(
+            &module_id,
+            &Identifier::new(CURRENCY_INITIALIZE_NAME)?,
+            vec![],
+            vec![
+                Value::transaction_argument_signer_reference(libra_root_address),
+                Value::transaction_argument_signer_reference(tc_address),
+            ],
+            libra_root_address,
+            &mut cost_strategy,
+        )
+        .map_err(|e| format_err!("Failed to initialize currency {}: {:?}", module_id, e))?;
+    let effects = session
+        .finish()
+        .map_err(|e| format_err!("Failed to finish session: {:?}", e))?;
+    let (write_set, events) = txn_effects_to_writeset_and_events(effects)
+        .map_err(|e| format_err!("Failed to convert effects to a change set: {:?}", e))?;
+
+    // Adding the currency code to `RegisteredCurrencies` reconfigures the system. The epilogue of
+    // the `WriteSet` transaction takes care of it, and rejects change sets that also do.
+    let configuration_path =
+        AccessPath::new(libra_root_address, ConfigurationResource::resource_path());
+    let write_set = WriteSetMut::new(
+        write_set
+            .into_iter()
+            .filter(|(access_path, _)| access_path != &configuration_path)
+            .collect(),
+    )
+    .freeze()?;
+    let events = events
+        .into_iter()
+        .filter(|event| event.key() != &new_epoch_event_key())
+        .collect();
+    Ok(ChangeSet::new(write_set, events))
+}
@@ -188,6 +188,7 @@ fn quote_type(type_tag: &TypeTag) -> String {
         Address => "AccountAddress".into(),
         Vector(type_tag) => match type_tag.as_ref() {
             U8 => "std::vector<uint8_t>".into(),
+            Vector(inner) if inner.as_ref() == &U8 => "std::vector<std::vector<uint8_t>>".into(),
             _ => type_not_allowed(type_tag),
         },
 
@@ -206,6 +207,10 @@ fn make_transaction_argument(type_tag: &TypeTag, name: &str) -> String {
         Address => format!("{{TransactionArgument::Address {{std::move({})}}}}", name),
         Vector(type_tag) => match type_tag.as_ref() {
             U8 => format!("{{TransactionArgument::U8Vector {{std::move({})}}}}", name),
+            Vector(inner) if inner.as_ref() == &U8 => format!(
+                "{{TransactionArgument::U8VectorVector {{std::move({})}}}}",
+                name
+            ),
             _ => type_not_allowed(type_tag),
         },
 
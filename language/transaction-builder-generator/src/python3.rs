@@ -55,7 +55,7 @@ fn output_preamble(
         out,
         r#"import typing
 {}import serde_types as st
-{}import Script, TypeTag, AccountAddress, TransactionArgument__Bool, TransactionArgument__U8, TransactionArgument__U64, TransactionArgument__U128, TransactionArgument__Address, TransactionArgument__U8Vector
+{}import Script, TypeTag, AccountAddress, TransactionArgument__Bool, TransactionArgument__U8, TransactionArgument__U64, TransactionArgument__U128, TransactionArgument__Address, TransactionArgument__U8Vector, TransactionArgument__U8VectorVector
 "#,
         quote_from_package(serde_package_name),
         quote_from_package_and_module(libra_package_name, "libra_types"),
@@ -153,6 +153,7 @@ fn quote_type(type_tag: &TypeTag) -> String {
         Address => "AccountAddress".into(),
         Vector(type_tag) => match type_tag.as_ref() {
             U8 => "bytes".into(),
+            Vector(inner) if inner.as_ref() == &U8 => "typing.Sequence[bytes]".into(),
             _ => type_not_allowed(type_tag),
         },
 
@@ -170,6 +171,9 @@ fn make_transaction_argument(type_tag: &TypeTag, name: &str) -> String {
         Address => format!("TransactionArgument__Address({})", name),
         Vector(type_tag) => match type_tag.as_ref() {
             U8 => format!("TransactionArgument__U8Vector({})", name),
+            Vector(inner) if inner.as_ref() == &U8 => {
+                format!("TransactionArgument__U8VectorVector({})", name)
+            }
             _ => type_not_allowed(type_tag),
         },
 
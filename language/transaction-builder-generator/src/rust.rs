@@ -140,6 +140,7 @@ fn quote_type(type_tag: &TypeTag) -> String {
         Address => "AccountAddress".into(),
         Vector(type_tag) => match type_tag.as_ref() {
             U8 => "Vec<u8>".into(),
+            Vector(inner) if inner.as_ref() == &U8 => "Vec<Vec<u8>>".into(),
             _ => type_not_allowed(type_tag),
         },
 
@@ -163,6 +164,9 @@ fn make_transaction_argument(type_tag: &TypeTag, name: &str, local_types: bool)
                     format!("TransactionArgument::U8Vector(ByteBuf::from({}))", name)
                 }
             }
+            Vector(inner) if inner.as_ref() == &U8 => {
+                format!("TransactionArgument::U8VectorVector({})", name)
+            }
             _ => type_not_allowed(type_tag),
         },
 
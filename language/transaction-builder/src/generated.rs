@@ -11,6 +11,27 @@ use libra_types::{
 };
 use move_core_types::language_storage::TypeTag;
 
+/// Rotate the authentication key of `rotated_address` to the key of its pending rotation.
+/// Can be sent by any account, e.g. by another account of the holder of the new key.
+/// Aborts with `DelayedKeyRotation::EKEY_ROTATION_DELAY_DOES_NOT_EXIST` if
+/// `rotated_address` did not enable delayed key rotations, with
+/// `DelayedKeyRotation::ENO_KEY_ROTATION_PENDING` if no rotation is pending, and with
+/// `DelayedKeyRotation::EKEY_ROTATION_DELAY_NOT_PASSED` if the delay of the rotation has
+/// not passed.
+pub fn encode_activate_delayed_key_rotation_script(rotated_address: AccountAddress) -> Script {
+    Script::new(
+        vec![
+            161, 28, 235, 11, 1, 0, 0, 0, 5, 1, 0, 2, 3, 2, 5, 5, 7, 7, 7, 14, 37, 8, 51, 16, 0, 0,
+            0, 1, 0, 1, 0, 1, 5, 0, 2, 6, 12, 5, 18, 68, 101, 108, 97, 121, 101, 100, 75, 101, 121,
+            82, 111, 116, 97, 116, 105, 111, 110, 17, 97, 99, 116, 105, 118, 97, 116, 101, 95, 114,
+            111, 116, 97, 116, 105, 111, 110, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 2,
+            1, 3, 10, 1, 17, 0, 2,
+        ],
+        vec![],
+        vec![TransactionArgument::Address(rotated_address)],
+    )
+}
+
 /// Add a `Currency` balance to `account`, which will enable `account` to send and receive
 /// `Libra<Currency>`. Aborts with NOT_A_CURRENCY if `Currency` is not an accepted
 /// currency type in the Libra system Aborts with `LibraAccount::ADD_EXISTING_CURRENCY` if
@@ -30,6 +51,37 @@ pub fn encode_add_currency_to_account_script(currency: TypeTag) -> Script {
     )
 }
 
+/// Add a tier with upper bound `tier_upperbound` for minting `CoinType` to the designated
+/// dealer at `designated_dealer_address`, above its existing tiers. Aborts with
+/// `DesignatedDealer::EINVALID_TIER_ADDITION` if the DD already has 4 tiers, and with
+/// `DesignatedDealer::EINVALID_TIER_START` if `tier_upperbound` is not greater than the
+/// upper bound of the last tier. Sender should be treasury compliance account.
+/// `sliding_nonce` is a unique nonce for operation, see sliding_nonce.move for details.
+pub fn encode_add_designated_dealer_tier_script(
+    coin_type: TypeTag,
+    sliding_nonce: u64,
+    designated_dealer_address: AccountAddress,
+    tier_upperbound: u64,
+) -> Script {
+    Script::new(
+        vec![
+            161, 28, 235, 11, 1, 0, 0, 0, 6, 1, 0, 4, 3, 4, 11, 4, 15, 2, 5, 17, 19, 7, 36, 61, 8,
+            97, 16, 0, 0, 0, 1, 1, 2, 0, 1, 0, 0, 3, 2, 1, 1, 1, 1, 4, 2, 6, 12, 3, 0, 3, 6, 12, 5,
+            3, 4, 6, 12, 3, 5, 3, 1, 9, 0, 16, 68, 101, 115, 105, 103, 110, 97, 116, 101, 100, 68,
+            101, 97, 108, 101, 114, 12, 83, 108, 105, 100, 105, 110, 103, 78, 111, 110, 99, 101,
+            21, 114, 101, 99, 111, 114, 100, 95, 110, 111, 110, 99, 101, 95, 111, 114, 95, 97, 98,
+            111, 114, 116, 8, 97, 100, 100, 95, 116, 105, 101, 114, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 1, 1, 1, 3, 1, 8, 10, 0, 10, 1, 17, 0, 11, 0, 10, 2, 10, 3, 56, 0, 2,
+        ],
+        vec![coin_type],
+        vec![
+            TransactionArgument::U64(sliding_nonce),
+            TransactionArgument::Address(designated_dealer_address),
+            TransactionArgument::U64(tier_upperbound),
+        ],
+    )
+}
+
 /// Add the `KeyRotationCapability` for `to_recover_account` to the `RecoveryAddress`
 /// resource under `recovery_address`. Aborts if `to_recovery_account` and
 /// `to_recovery_address belong to different VASPs, if `recovery_address` does not have a
@@ -122,6 +174,24 @@ pub fn encode_cancel_burn_script(token: TypeTag, preburn_address: AccountAddress
     )
 }
 
+/// Cancel the rotation of the sender's authentication key pending. Aborts with
+/// `DelayedKeyRotation::EKEY_ROTATION_DELAY_DOES_NOT_EXIST` if the sender did not enable
+/// delayed key rotations, and with `DelayedKeyRotation::ENO_KEY_ROTATION_PENDING` if no
+/// rotation is pending.
+pub fn encode_cancel_delayed_key_rotation_script() -> Script {
+    Script::new(
+        vec![
+            161, 28, 235, 11, 1, 0, 0, 0, 5, 1, 0, 2, 3, 2, 5, 5, 7, 4, 7, 11, 35, 8, 46, 16, 0, 0,
+            0, 1, 0, 1, 0, 1, 6, 12, 0, 18, 68, 101, 108, 97, 121, 101, 100, 75, 101, 121, 82, 111,
+            116, 97, 116, 105, 111, 110, 15, 99, 97, 110, 99, 101, 108, 95, 114, 111, 116, 97, 116,
+            105, 111, 110, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 1, 3, 11, 0, 17,
+            0, 2,
+        ],
+        vec![],
+        vec![],
+    )
+}
+
 /// Create a `ChildVASP` account for sender `parent_vasp` at `child_address` with a
 /// balance of `child_initial_balance` in `CoinType` and an initial authentication_key
 /// `auth_key_prefix | child_address`. If `add_all_currencies` is true, the child address
@@ -327,6 +397,28 @@ pub fn encode_create_validator_operator_account_script(
     )
 }
 
+/// Make the authentication key of the sender only rotatable `delay_seconds` after the
+/// rotation is scheduled with `schedule_delayed_key_rotation`, until which the current
+/// key can cancel it. This is permanent: the sender hands its `KeyRotationCapability`
+/// over for good, so its key can no longer be rotated at once, e.g. with
+/// `rotate_authentication_key`. Aborts with
+/// `LibraAccount::EKEY_ROTATION_CAPABILITY_ALREADY_EXTRACTED` if the sender has delegated
+/// its `KeyRotationCapability`, with `DelayedKeyRotation::EKEY_ROTATION_DELAY_EXISTS` if
+/// it already has a delay, and with `DelayedKeyRotation::EINVALID_DELAY` if
+/// `delay_seconds` is 0 or longer than a year.
+pub fn encode_enable_delayed_key_rotation_script(delay_seconds: u64) -> Script {
+    Script::new(
+        vec![
+            161, 28, 235, 11, 1, 0, 0, 0, 5, 1, 0, 2, 3, 2, 5, 5, 7, 5, 7, 12, 27, 8, 39, 16, 0, 0,
+            0, 1, 0, 1, 0, 2, 6, 12, 3, 0, 18, 68, 101, 108, 97, 121, 101, 100, 75, 101, 121, 82,
+            111, 116, 97, 116, 105, 111, 110, 7, 112, 117, 98, 108, 105, 115, 104, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 1, 4, 11, 0, 10, 1, 17, 0, 2,
+        ],
+        vec![],
+        vec![TransactionArgument::U64(delay_seconds)],
+    )
+}
+
 /// Freeze account `address`. Initiator must be authorized. `sliding_nonce` is a unique
 /// nonce for operation, see sliding_nonce.move for details.
 pub fn encode_freeze_account_script(
@@ -372,19 +464,28 @@ pub fn encode_mint_lbr_script(amount_lbr: u64) -> Script {
     )
 }
 
-/// Modify publishing options. Takes the LCS bytes of a `VMPublishingOption` object as
-/// input.
-pub fn encode_modify_publishing_option_script(args: Vec<u8>) -> Script {
+/// Modify publishing options. Takes the SHA3-256 hashes of the scripts which are always
+/// allowed, whether scripts outside of this list are allowed, and whether any account may
+/// publish modules.
+pub fn encode_modify_publishing_option_script(
+    script_allow_list: Vec<Vec<u8>>,
+    is_open_script: bool,
+    is_open_module: bool,
+) -> Script {
     Script::new(
         vec![
-            161, 28, 235, 11, 1, 0, 0, 0, 5, 1, 0, 2, 3, 2, 5, 5, 7, 6, 7, 13, 36, 8, 49, 16, 0, 0,
-            0, 1, 0, 1, 0, 2, 6, 12, 10, 2, 0, 13, 76, 105, 98, 114, 97, 86, 77, 67, 111, 110, 102,
-            105, 103, 21, 115, 101, 116, 95, 112, 117, 98, 108, 105, 115, 104, 105, 110, 103, 95,
-            111, 112, 116, 105, 111, 110, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 1,
-            4, 11, 0, 11, 1, 17, 0, 2,
+            161, 28, 235, 11, 1, 0, 0, 0, 5, 1, 0, 2, 3, 2, 5, 5, 7, 9, 7, 16, 37, 8, 53, 16, 0, 0,
+            0, 1, 0, 1, 0, 4, 6, 12, 10, 10, 2, 1, 1, 0, 32, 76, 105, 98, 114, 97, 84, 114, 97,
+            110, 115, 97, 99, 116, 105, 111, 110, 80, 117, 98, 108, 105, 115, 104, 105, 110, 103,
+            79, 112, 116, 105, 111, 110, 3, 115, 101, 116, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 1, 0, 0, 1, 6, 11, 0, 11, 1, 10, 2, 10, 3, 17, 0, 2,
         ],
         vec![],
-        vec![TransactionArgument::U8Vector(args)],
+        vec![
+            TransactionArgument::U8VectorVector(script_allow_list),
+            TransactionArgument::Bool(is_open_script),
+            TransactionArgument::Bool(is_open_module),
+        ],
     )
 }
 
@@ -517,6 +618,38 @@ pub fn encode_reconfigure_script() -> Script {
     )
 }
 
+/// Remove the tier `tier_index` for minting `CoinType` from the designated dealer at
+/// `designated_dealer_address`. The tiers above it move down by one index. Aborts with
+/// `DesignatedDealer::EINVALID_TIER_INDEX` if the DD has no tier `tier_index`, and with
+/// `DesignatedDealer::EINVALID_TIER_REMOVAL` if it is the last remaining tier of the DD.
+/// Sender should be treasury compliance account. `sliding_nonce` is a unique nonce for
+/// operation, see sliding_nonce.move for details.
+pub fn encode_remove_designated_dealer_tier_script(
+    coin_type: TypeTag,
+    sliding_nonce: u64,
+    designated_dealer_address: AccountAddress,
+    tier_index: u64,
+) -> Script {
+    Script::new(
+        vec![
+            161, 28, 235, 11, 1, 0, 0, 0, 6, 1, 0, 4, 3, 4, 11, 4, 15, 2, 5, 17, 19, 7, 36, 64, 8,
+            100, 16, 0, 0, 0, 1, 1, 2, 0, 1, 0, 0, 3, 2, 1, 1, 1, 1, 4, 2, 6, 12, 3, 0, 3, 6, 12,
+            5, 3, 4, 6, 12, 3, 5, 3, 1, 9, 0, 16, 68, 101, 115, 105, 103, 110, 97, 116, 101, 100,
+            68, 101, 97, 108, 101, 114, 12, 83, 108, 105, 100, 105, 110, 103, 78, 111, 110, 99,
+            101, 21, 114, 101, 99, 111, 114, 100, 95, 110, 111, 110, 99, 101, 95, 111, 114, 95, 97,
+            98, 111, 114, 116, 11, 114, 101, 109, 111, 118, 101, 95, 116, 105, 101, 114, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 3, 1, 8, 10, 0, 10, 1, 17, 0, 11, 0, 10,
+            2, 10, 3, 56, 0, 2,
+        ],
+        vec![coin_type],
+        vec![
+            TransactionArgument::U64(sliding_nonce),
+            TransactionArgument::Address(designated_dealer_address),
+            TransactionArgument::U64(tier_index),
+        ],
+    )
+}
+
 /// Adding `to_remove` to the set of pending validator removals. Fails if the `to_remove`
 /// address is already in the validator set or already in the pending removals. Callable
 /// by Validator's operator.
@@ -653,6 +786,57 @@ pub fn encode_rotate_shared_ed25519_public_key_script(public_key: Vec<u8>) -> Sc
     )
 }
 
+/// Schedule the rotation of the sender's authentication key to `new_key`, which can be
+/// activated with `activate_delayed_key_rotation` once the delay of the sender has
+/// passed. `new_key` should be a 256 bit sha3 hash of an ed25519 public key. Aborts with
+/// `DelayedKeyRotation::EKEY_ROTATION_DELAY_DOES_NOT_EXIST` if the sender did not enable
+/// delayed key rotations, with `DelayedKeyRotation::EKEY_ROTATION_PENDING` if a rotation
+/// is already pending, and with `DelayedKeyRotation::EMALFORMED_AUTHENTICATION_KEY` if
+/// `new_key` is not 32 bytes long.
+pub fn encode_schedule_delayed_key_rotation_script(new_key: Vec<u8>) -> Script {
+    Script::new(
+        vec![
+            161, 28, 235, 11, 1, 0, 0, 0, 5, 1, 0, 2, 3, 2, 5, 5, 7, 6, 7, 13, 37, 8, 50, 16, 0, 0,
+            0, 1, 0, 1, 0, 2, 6, 12, 10, 2, 0, 18, 68, 101, 108, 97, 121, 101, 100, 75, 101, 121,
+            82, 111, 116, 97, 116, 105, 111, 110, 17, 115, 99, 104, 101, 100, 117, 108, 101, 95,
+            114, 111, 116, 97, 116, 105, 111, 110, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+            0, 0, 1, 4, 11, 0, 11, 1, 17, 0, 2,
+        ],
+        vec![],
+        vec![TransactionArgument::U8Vector(new_key)],
+    )
+}
+
+/// Bound the change of the on-chain exchange rates to LBR updated by `tc_account` to
+/// `max_change_numerator/max_change_denominator` times the current exchange rate.
+pub fn encode_set_exchange_rate_update_bounds_script(
+    sliding_nonce: u64,
+    max_change_numerator: u64,
+    max_change_denominator: u64,
+) -> Script {
+    Script::new(
+        vec![
+            161, 28, 235, 11, 1, 0, 0, 0, 6, 1, 0, 6, 2, 6, 4, 3, 10, 15, 5, 25, 22, 7, 47, 107, 8,
+            154, 1, 16, 0, 0, 0, 1, 0, 2, 0, 0, 2, 0, 0, 3, 0, 1, 0, 2, 4, 2, 3, 0, 1, 5, 4, 3, 0,
+            2, 3, 3, 1, 8, 0, 2, 6, 12, 3, 0, 2, 6, 12, 8, 0, 4, 6, 12, 3, 3, 3, 12, 70, 105, 120,
+            101, 100, 80, 111, 105, 110, 116, 51, 50, 5, 76, 105, 98, 114, 97, 12, 83, 108, 105,
+            100, 105, 110, 103, 78, 111, 110, 99, 101, 20, 99, 114, 101, 97, 116, 101, 95, 102,
+            114, 111, 109, 95, 114, 97, 116, 105, 111, 110, 97, 108, 21, 114, 101, 99, 111, 114,
+            100, 95, 110, 111, 110, 99, 101, 95, 111, 114, 95, 97, 98, 111, 114, 116, 31, 115, 101,
+            116, 95, 101, 120, 99, 104, 97, 110, 103, 101, 95, 114, 97, 116, 101, 95, 117, 112,
+            100, 97, 116, 101, 95, 98, 111, 117, 110, 100, 115, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 1, 0, 5, 1, 11, 10, 0, 10, 1, 17, 1, 10, 2, 10, 3, 17, 0, 12, 4, 11, 0, 11, 4,
+            17, 2, 2,
+        ],
+        vec![],
+        vec![
+            TransactionArgument::U64(sliding_nonce),
+            TransactionArgument::U64(max_change_numerator),
+            TransactionArgument::U64(max_change_denominator),
+        ],
+    )
+}
+
 /// Set validator's config.
 pub fn encode_set_validator_config_script(
     validator_account: AccountAddress,
@@ -872,6 +1056,40 @@ pub fn encode_update_account_limit_window_info_script(
     )
 }
 
+/// Update the upper bound of the tier `tier_index` for minting `CoinType` to the
+/// designated dealer at `designated_dealer_address` to `new_upperbound`. Aborts with
+/// `DesignatedDealer::EINVALID_TIER_INDEX` if the DD has no tier `tier_index`, and with
+/// `DesignatedDealer::EINVALID_TIER_START` if `new_upperbound` is not between the upper
+/// bounds of the tiers below and above it. Sender should be treasury compliance account.
+/// `sliding_nonce` is a unique nonce for operation, see sliding_nonce.move for details.
+pub fn encode_update_designated_dealer_tier_script(
+    coin_type: TypeTag,
+    sliding_nonce: u64,
+    designated_dealer_address: AccountAddress,
+    tier_index: u64,
+    new_upperbound: u64,
+) -> Script {
+    Script::new(
+        vec![
+            161, 28, 235, 11, 1, 0, 0, 0, 6, 1, 0, 4, 3, 4, 11, 4, 15, 2, 5, 17, 21, 7, 38, 64, 8,
+            102, 16, 0, 0, 0, 1, 1, 2, 0, 1, 0, 0, 3, 2, 1, 1, 1, 1, 4, 2, 6, 12, 3, 0, 4, 6, 12,
+            5, 3, 3, 5, 6, 12, 3, 5, 3, 3, 1, 9, 0, 16, 68, 101, 115, 105, 103, 110, 97, 116, 101,
+            100, 68, 101, 97, 108, 101, 114, 12, 83, 108, 105, 100, 105, 110, 103, 78, 111, 110,
+            99, 101, 21, 114, 101, 99, 111, 114, 100, 95, 110, 111, 110, 99, 101, 95, 111, 114, 95,
+            97, 98, 111, 114, 116, 11, 117, 112, 100, 97, 116, 101, 95, 116, 105, 101, 114, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 3, 1, 9, 10, 0, 10, 1, 17, 0, 11, 0,
+            10, 2, 10, 3, 10, 4, 56, 0, 2,
+        ],
+        vec![coin_type],
+        vec![
+            TransactionArgument::U64(sliding_nonce),
+            TransactionArgument::Address(designated_dealer_address),
+            TransactionArgument::U64(tier_index),
+            TransactionArgument::U64(new_upperbound),
+        ],
+    )
+}
+
 /// Update the dual attesation limit to `new_micro_lbr_limit`.
 pub fn encode_update_dual_attestation_limit_script(
     sliding_nonce: u64,
@@ -926,6 +1144,67 @@ pub fn encode_update_exchange_rate_script(
     )
 }
 
+/// Update the on-chain exchange rates to LBR of `Currency1` and `Currency2` in a single
+/// transaction, to `numerator1/denominator1` and `numerator2/denominator2` respectively.
+/// Either both rates are updated or neither is.
+pub fn encode_update_exchange_rates_script(
+    currency1: TypeTag,
+    currency2: TypeTag,
+    sliding_nonce: u64,
+    numerator1: u64,
+    denominator1: u64,
+    numerator2: u64,
+    denominator2: u64,
+) -> Script {
+    Script::new(
+        vec![
+            161, 28, 235, 11, 1, 0, 0, 0, 7, 1, 0, 6, 2, 6, 4, 3, 10, 16, 4, 26, 4, 5, 30, 30, 7,
+            60, 100, 8, 160, 1, 16, 0, 0, 0, 1, 0, 2, 0, 0, 2, 0, 0, 3, 0, 1, 0, 2, 4, 2, 3, 0, 1,
+            5, 4, 3, 1, 1, 2, 6, 2, 7, 2, 3, 3, 1, 8, 0, 2, 6, 12, 3, 0, 2, 6, 12, 8, 0, 6, 6, 12,
+            3, 3, 3, 3, 3, 1, 9, 0, 1, 9, 1, 12, 70, 105, 120, 101, 100, 80, 111, 105, 110, 116,
+            51, 50, 5, 76, 105, 98, 114, 97, 12, 83, 108, 105, 100, 105, 110, 103, 78, 111, 110,
+            99, 101, 20, 99, 114, 101, 97, 116, 101, 95, 102, 114, 111, 109, 95, 114, 97, 116, 105,
+            111, 110, 97, 108, 21, 114, 101, 99, 111, 114, 100, 95, 110, 111, 110, 99, 101, 95,
+            111, 114, 95, 97, 98, 111, 114, 116, 24, 117, 112, 100, 97, 116, 101, 95, 108, 98, 114,
+            95, 101, 120, 99, 104, 97, 110, 103, 101, 95, 114, 97, 116, 101, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 1, 1, 5, 3, 14, 10, 0, 10, 1, 17, 1, 10, 0, 10, 2, 10, 3,
+            17, 0, 56, 0, 11, 0, 10, 4, 10, 5, 17, 0, 56, 1, 2,
+        ],
+        vec![currency1, currency2],
+        vec![
+            TransactionArgument::U64(sliding_nonce),
+            TransactionArgument::U64(numerator1),
+            TransactionArgument::U64(denominator1),
+            TransactionArgument::U64(numerator2),
+            TransactionArgument::U64(denominator2),
+        ],
+    )
+}
+
+/// Update the gas schedule. Takes the version of the new schedule, which must be greater
+/// than the current one, and the LCS bytes of its instruction and native cost tables.
+pub fn encode_update_gas_schedule_script(
+    version: u64,
+    instruction_schedule: Vec<u8>,
+    native_schedule: Vec<u8>,
+) -> Script {
+    Script::new(
+        vec![
+            161, 28, 235, 11, 1, 0, 0, 0, 5, 1, 0, 2, 3, 2, 5, 5, 7, 9, 7, 16, 31, 8, 47, 16, 0, 0,
+            0, 1, 0, 1, 0, 4, 6, 12, 3, 10, 2, 10, 2, 0, 13, 76, 105, 98, 114, 97, 86, 77, 67, 111,
+            110, 102, 105, 103, 16, 115, 101, 116, 95, 103, 97, 115, 95, 115, 99, 104, 101, 100,
+            117, 108, 101, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 1, 6, 11, 0, 10,
+            1, 11, 2, 11, 3, 17, 0, 2,
+        ],
+        vec![],
+        vec![
+            TransactionArgument::U64(version),
+            TransactionArgument::U8Vector(instruction_schedule),
+            TransactionArgument::U8Vector(native_schedule),
+        ],
+    )
+}
+
 /// Update Libra version.
 pub fn encode_update_libra_version_script(major: u64) -> Script {
     Script::new(
@@ -32,8 +32,15 @@ pub fn encode_stdlib_script(
 }
 
 pub fn encode_modify_publishing_option_script(config: VMPublishingOption) -> Script {
-    let bytes = lcs::to_bytes(&config).expect("Cannot deserialize VMPublishingOption");
-    generated::encode_modify_publishing_option_script(bytes)
+    generated::encode_modify_publishing_option_script(
+        config
+            .script_allow_list
+            .iter()
+            .map(|hash| hash.to_vec())
+            .collect(),
+        config.is_open_script,
+        config.is_open_module,
+    )
 }
 
 pub fn encode_update_libra_version_script(libra_version: LibraVersion) -> Script {
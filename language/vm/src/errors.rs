@@ -7,7 +7,7 @@ use crate::{
 };
 use move_core_types::{
     language_storage::ModuleId,
-    vm_status::{self, StatusCode, StatusType, VMStatus},
+    vm_status::{self, AbortFrame, StatusCode, StatusType, VMStatus},
 };
 use std::fmt;
 
@@ -30,6 +30,7 @@ pub struct VMError {
     location: Location,
     indices: Vec<(IndexKind, TableIndex)>,
     offsets: Vec<(FunctionDefinitionIndex, CodeOffset)>,
+    abort_frame: Option<AbortFrame>,
 }
 
 impl VMError {
@@ -38,6 +39,7 @@ impl VMError {
             major_status,
             sub_status,
             location,
+            abort_frame,
             ..
         } = self;
         match (major_status, sub_status, location) {
@@ -46,10 +48,10 @@ impl VMError {
                 VMStatus::Executed
             }
             (StatusCode::ABORTED, Some(code), Location::Script) => {
-                VMStatus::MoveAbort(vm_status::AbortLocation::Script, code)
+                VMStatus::MoveAbort(vm_status::AbortLocation::Script, code, abort_frame)
             }
             (StatusCode::ABORTED, Some(code), Location::Module(id)) => {
-                VMStatus::MoveAbort(vm_status::AbortLocation::Module(id), code)
+                VMStatus::MoveAbort(vm_status::AbortLocation::Module(id), code, abort_frame)
             }
 
             (StatusCode::ABORTED, sub_status, location) => {
@@ -89,6 +91,10 @@ impl VMError {
         &self.offsets
     }
 
+    pub fn abort_frame(&self) -> Option<&AbortFrame> {
+        self.abort_frame.as_ref()
+    }
+
     pub fn status_type(&self) -> StatusType {
         self.major_status.status_type()
     }
@@ -101,6 +107,7 @@ pub struct PartialVMError {
     message: Option<String>,
     indices: Vec<(IndexKind, TableIndex)>,
     offsets: Vec<(FunctionDefinitionIndex, CodeOffset)>,
+    abort_frame: Option<AbortFrame>,
 }
 
 impl PartialVMError {
@@ -111,6 +118,7 @@ impl PartialVMError {
             message,
             indices,
             offsets,
+            abort_frame,
         } = self;
         VMError {
             major_status,
@@ -119,6 +127,7 @@ impl PartialVMError {
             message,
             indices,
             offsets,
+            abort_frame,
         }
     }
 
@@ -129,6 +138,7 @@ impl PartialVMError {
             message: None,
             indices: vec![],
             offsets: vec![],
+            abort_frame: None,
         }
     }
 
@@ -152,6 +162,14 @@ impl PartialVMError {
         }
     }
 
+    pub fn with_abort_frame(self, abort_frame: AbortFrame) -> Self {
+        debug_assert!(self.abort_frame.is_none());
+        Self {
+            abort_frame: Some(abort_frame),
+            ..self
+        }
+    }
+
     pub fn at_index(self, kind: IndexKind, index: TableIndex) -> Self {
         let mut indices = self.indices;
         indices.push((kind, index));
@@ -0,0 +1,104 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Applies the reloadable fields of the node config file to the running node, on SIGHUP or on a
+//! request to the debug interface.
+
+use futures::channel::mpsc;
+use libra_config::config::{
+    ConfigChange, ConfigIssue, Error, NodeConfig, PersistableConfig, UpstreamConfig,
+};
+use libra_logger::prelude::*;
+use libradb::LibraDB;
+use state_synchronizer::StateSyncClient;
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+pub struct ConfigReloader {
+    path: PathBuf,
+    // The config as last read from `path`, before `NodeConfig::load` fills in derived fields
+    config: Mutex<NodeConfig>,
+    libra_db: Arc<LibraDB>,
+    // None if the node runs without state sync
+    state_sync_client: Option<Arc<StateSyncClient>>,
+    mempool_upstream_sender: mpsc::UnboundedSender<UpstreamConfig>,
+}
+
+impl ConfigReloader {
+    pub fn new(
+        path: &Path,
+        libra_db: Arc<LibraDB>,
+        state_sync_client: Option<Arc<StateSyncClient>>,
+        mempool_upstream_sender: mpsc::UnboundedSender<UpstreamConfig>,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            path: path.to_path_buf(),
+            config: Mutex::new(NodeConfig::load_config(path)?),
+            libra_db,
+            state_sync_client,
+            mempool_upstream_sender,
+        })
+    }
+
+    /// Reads the config file again and applies the changes to reloadable fields. The whole
+    /// config is rejected if any other field changed. Every change applied is audit logged.
+    pub fn reload(&self) -> Result<Vec<ConfigChange>, Vec<ConfigIssue>> {
+        let new_config = NodeConfig::load_config(&self.path).map_err(|e| {
+            vec![ConfigIssue::new(
+                "node_config",
+                format!("unable to read {}: {}", self.path.display(), e),
+            )]
+        })?;
+        let mut config = self
+            .config
+            .lock()
+            .expect("Config lock should not be poisoned");
+        let changes = config.reloadable_changes(&new_config).map_err(|issues| {
+            for issue in &issues {
+                warn!("[config] Rejected reload of {:?}, {}", self.path, issue);
+            }
+            issues
+        })?;
+
+        for change in &changes {
+            if let Err(issue) = self.apply(change, &new_config) {
+                error!("[config] Failed to reload {}", issue);
+                return Err(vec![issue]);
+            }
+            send_struct_log!(StructuredLogEntry::new_named("config", "reload")
+                .data("field", change.field)
+                .data("old", &change.old)
+                .data("new", &change.new));
+            info!("[config] Reloaded {}", change);
+        }
+        *config = new_config;
+        Ok(changes)
+    }
+
+    fn apply(&self, change: &ConfigChange, config: &NodeConfig) -> Result<(), ConfigIssue> {
+        match change.field {
+            "logger.level" => libra_logger::set_level(config.logger.level),
+            "storage.prune_window" => {
+                // Enabling or disabling the pruner is rejected by `reloadable_changes`
+                let prune_window = config.storage.prune_window.expect("The pruner is enabled");
+                self.libra_db
+                    .set_prune_window(prune_window)
+                    .map_err(|e| ConfigIssue::new(change.field, e.to_string()))?;
+            }
+            "upstream.networks" => {
+                if let Some(state_sync_client) = &self.state_sync_client {
+                    state_sync_client
+                        .update_upstream_config(config.upstream.clone())
+                        .map_err(|e| ConfigIssue::new(change.field, e.to_string()))?;
+                }
+                self.mempool_upstream_sender
+                    .unbounded_send(config.upstream.clone())
+                    .map_err(|e| ConfigIssue::new(change.field, e.to_string()))?;
+            }
+            field => unreachable!("{} is not a reloadable field", field),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Drains the node before a planned shutdown: mempool stops taking transactions, the consensus
+//! rounds in flight get committed, storage is flushed, and the process exits.
+
+use libra_config::config::RoleType;
+use libra_logger::prelude::*;
+use libradb::LibraDB;
+use std::{
+    process,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+use storage_interface::DbReader;
+
+/// A block is committed once it heads a 3-chain, so the blocks in flight when draining starts are
+/// either committed or abandoned once the committed round moved this many rounds ahead.
+const IN_FLIGHT_ROUNDS: u64 = 3;
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(60);
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub struct Drainer {
+    role: RoleType,
+    libra_db: Arc<LibraDB>,
+    // Shared with mempool, which rejects new transactions once it is set
+    draining: Arc<AtomicBool>,
+}
+
+impl Drainer {
+    pub fn new(role: RoleType, libra_db: Arc<LibraDB>, draining: Arc<AtomicBool>) -> Self {
+        Self {
+            role,
+            libra_db,
+            draining,
+        }
+    }
+
+    /// Starts draining in the background, the process exits once it is done.
+    pub fn start(self: Arc<Self>) -> Result<(), String> {
+        if self.draining.swap(true, Ordering::SeqCst) {
+            return Err("the node is already draining".to_string());
+        }
+        info!("[drain] Draining the node, new transactions are rejected");
+        thread::Builder::new()
+            .name("drain".into())
+            .spawn(move || {
+                self.drain();
+                process::exit(0);
+            })
+            .map(|_| ())
+            .map_err(|e| format!("unable to start draining: {}", e))
+    }
+
+    fn drain(&self) {
+        if self.role.is_validator() {
+            self.wait_for_rounds_in_flight();
+        }
+        match self.libra_db.flush() {
+            Ok(()) => info!("[drain] Flushed storage"),
+            Err(e) => error!("[drain] Failed to flush storage: {}", e),
+        }
+        info!("[drain] Node drained, exiting");
+    }
+
+    fn wait_for_rounds_in_flight(&self) {
+        let (start_epoch, start_round) = match self.committed_epoch_and_round() {
+            Some(epoch_and_round) => epoch_and_round,
+            None => return,
+        };
+        let deadline = Instant::now() + DRAIN_TIMEOUT;
+        while Instant::now() < deadline {
+            match self.committed_epoch_and_round() {
+                Some((epoch, round))
+                    if epoch > start_epoch || round >= start_round + IN_FLIGHT_ROUNDS =>
+                {
+                    info!(
+                        "[drain] Rounds in flight committed, at epoch {} round {}",
+                        epoch, round
+                    );
+                    return;
+                }
+                Some(_) => thread::sleep(POLL_INTERVAL),
+                None => return,
+            }
+        }
+        warn!(
+            "[drain] Rounds in flight not committed after {} seconds",
+            DRAIN_TIMEOUT.as_secs()
+        );
+    }
+
+    fn committed_epoch_and_round(&self) -> Option<(u64, u64)> {
+        match self.libra_db.get_latest_ledger_info() {
+            Ok(ledger_info) => Some((
+                ledger_info.ledger_info().epoch(),
+                ledger_info.ledger_info().round(),
+            )),
+            Err(e) => {
+                error!("[drain] Failed to read the latest ledger info: {}", e);
+                None
+            }
+        }
+    }
+}
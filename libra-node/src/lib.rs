@@ -3,4 +3,7 @@
 
 #![forbid(unsafe_code)]
 
+pub mod config_reloader;
+pub mod drain;
 pub mod main_node;
+pub mod peer_directory;
@@ -7,12 +7,19 @@ use libra_config::config::NodeConfig;
 use libra_types::PeerId;
 use std::{
     path::PathBuf,
+    process,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
     },
+    thread,
+    time::Duration,
 };
 use structopt::StructOpt;
+use tokio::{
+    runtime,
+    signal::unix::{signal, SignalKind},
+};
 
 #[derive(Debug, StructOpt)]
 #[structopt(about = "Libra Node")]
@@ -23,6 +30,9 @@ struct Args {
     #[structopt(short = "d", long)]
     /// Disable logging
     no_logging: bool,
+    #[structopt(long)]
+    /// Check the config for inconsistencies and exit
+    check_config: bool,
 }
 
 #[global_allocator]
@@ -31,13 +41,37 @@ static ALLOC: jemallocator::Jemalloc = jemallocator::Jemalloc;
 fn main() {
     let args = Args::from_args();
 
-    let mut config = NodeConfig::load(args.config).expect("Failed to load node config");
+    let mut config = match NodeConfig::load(&args.config) {
+        Ok(config) => config,
+        Err(e) if args.check_config => {
+            eprintln!("Failed to load node config: {}", e);
+            process::exit(1);
+        }
+        Err(e) => panic!("Failed to load node config: {:?}", e),
+    };
+    // Replaying the genesis is only worth it when explicitly checking the config
+    let issues = if args.check_config {
+        libra_node::main_node::check_config(&config)
+    } else {
+        config.validate()
+    };
+    for issue in &issues {
+        eprintln!("Invalid node config, {}", issue);
+    }
+    if !issues.is_empty() {
+        process::exit(1);
+    }
+    if args.check_config {
+        println!("Node config is valid");
+        return;
+    }
     println!("Using node config {:?}", &config);
     crash_handler::setup_panic_handler();
 
     if !args.no_logging {
         libra_logger::Logger::new()
             .channel_size(config.logger.chan_size)
+            .format(config.logger.format)
             .is_async(config.logger.is_async)
             .level(config.logger.level)
             .init();
@@ -56,7 +90,42 @@ fn main() {
         }
     }
 
-    let _node_handle = libra_node::main_node::setup_environment(&mut config);
+    if let Some(push_gateway) = config.metrics.push_gateway.clone() {
+        // One push group per node, named after its validator peer id if it has one
+        let instance = config
+            .validator_network
+            .iter()
+            .chain(config.full_node_networks.iter())
+            .next()
+            .map_or_else(
+                || "unknown".to_string(),
+                |network| network.peer_id().to_string(),
+            );
+        libra_metrics::push_gateway::start_push_gateway(
+            push_gateway.url,
+            push_gateway.job,
+            instance,
+            Duration::from_millis(push_gateway.push_interval_ms),
+        );
+    }
+
+    let node_handle = libra_node::main_node::setup_environment(&mut config, &args.config);
+
+    // Reload the operational fields of the config on SIGHUP, the outcome is logged by the reloader
+    let config_reloader = node_handle.config_reloader();
+    thread::spawn(move || {
+        let mut runtime = runtime::Builder::new()
+            .basic_scheduler()
+            .enable_all()
+            .build()
+            .expect("Failed to create the SIGHUP runtime");
+        runtime.block_on(async move {
+            let mut hangups = signal(SignalKind::hangup()).expect("Failed to listen to SIGHUP");
+            while hangups.recv().await.is_some() {
+                let _ = config_reloader.reload();
+            }
+        });
+    });
 
     let term = Arc::new(AtomicBool::new(false));
 
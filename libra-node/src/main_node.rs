@@ -1,47 +1,147 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::{config_reloader::ConfigReloader, drain::Drainer, peer_directory::PeerDirectory};
 use backup_service::start_backup_service;
+use consensus::network_interface::{ConsensusNetworkEvents, ConsensusNetworkSender};
 use consensus::{consensus_provider::start_consensus, gen_consensus_reconfig_subscription};
-use debug_interface::node_debug_service::NodeDebugService;
+use debug_interface::node_debug_service::{
+    DrainHandler, ManagementHandlers, NodeDebugService, PeersHandler, ReloadHandler,
+};
 use executor::{db_bootstrapper::bootstrap_db_if_empty, Executor};
 use executor_types::ChunkExecutor;
-use futures::{channel::mpsc::channel, executor::block_on};
+use futures::{
+    channel::mpsc::{self, channel, unbounded},
+    executor::block_on,
+};
 use libra_config::{
-    config::{NetworkConfig, NodeConfig, RoleType},
+    config::{ConfigIssue, NetworkConfig, NodeConfig, RoleType},
     utils::get_genesis_txn,
 };
 use libra_json_rpc::bootstrap_from_config as bootstrap_rpc;
 use libra_logger::prelude::*;
-use libra_mempool::gen_mempool_reconfig_subscription;
-use libra_metrics::metric_server;
+use libra_mempool::{
+    gen_mempool_reconfig_subscription, AdmissionControl, ConsensusRequest, MempoolClientSender,
+};
+use libra_metrics::{health, metric_server};
+use libra_temppath::TempPath;
 use libra_vm::LibraVM;
 use libradb::LibraDB;
 use network_builder::builder::NetworkBuilder;
-use state_synchronizer::StateSynchronizer;
-use std::{boxed::Box, net::ToSocketAddrs, sync::Arc, thread, time::Instant};
+use state_synchronizer::{StateSyncClient, StateSynchronizer};
+use std::{
+    any::Any,
+    boxed::Box,
+    net::ToSocketAddrs,
+    path::Path,
+    sync::{atomic::AtomicBool, Arc},
+    thread,
+    time::Instant,
+};
 use storage_interface::DbReaderWriter;
 use storage_service::start_storage_service_with_db;
+use subscription_service::ReconfigEvents;
 use tokio::runtime::Runtime;
 
 const AC_SMP_CHANNEL_BUFFER_SIZE: usize = 1_024;
 const INTRA_NODE_CHANNEL_BUFFER_SIZE: usize = 1;
 
+/// Checks the config for inconsistencies before starting the node, including that a genesis
+/// waypoint matches the genesis transaction.
+pub fn check_config(node_config: &NodeConfig) -> Vec<ConfigIssue> {
+    let mut issues = node_config.validate();
+    // The waypoint can only be read once its backend is known to be reachable
+    if issues.is_empty() {
+        issues.extend(check_genesis_waypoint(node_config));
+    }
+    issues
+}
+
+fn check_genesis_waypoint(node_config: &NodeConfig) -> Option<ConfigIssue> {
+    let genesis = get_genesis_txn(node_config)?;
+    let waypoint = node_config.base.waypoint.waypoint();
+    // Waypoints of later epochs can not be checked against the genesis
+    if waypoint.version() != 0 {
+        return None;
+    }
+
+    let path = TempPath::new();
+    let genesis_waypoint = LibraDB::open(&path, false /* readonly */, None /* pruner */)
+        .and_then(|db| bootstrap_db_if_empty::<LibraVM>(&DbReaderWriter::new(db), genesis));
+    match genesis_waypoint {
+        Ok(Some(genesis_waypoint)) if genesis_waypoint == waypoint => None,
+        Ok(genesis_waypoint) => Some(ConfigIssue::new(
+            "base.waypoint",
+            format!(
+                "{} does not match the waypoint {:?} of the genesis in \
+                 execution.genesis_file_location",
+                waypoint, genesis_waypoint
+            ),
+        )),
+        Err(e) => Some(ConfigIssue::new(
+            "execution.genesis_file_location",
+            format!("unable to apply the genesis: {}", e),
+        )),
+    }
+}
+
+/// Starts JSON-RPC in place of the default server, the runtime is kept alive by the node.
+pub type JsonRpcService =
+    Box<dyn FnOnce(&NodeConfig, Arc<LibraDB>, MempoolClientSender, AdmissionControl) -> Runtime>;
+
+/// Starts consensus in place of the default one, the runtime is kept alive by the node.
+pub type ConsensusService = Box<dyn FnOnce(&mut NodeConfig, ConsensusContext) -> Runtime>;
+
+/// Starts a service alongside the node components, e.g. a key manager. The returned value is
+/// kept alive by the node.
+pub type NodeService = Box<dyn FnOnce(&NodeContext) -> Box<dyn Any + Send>>;
+
+/// What consensus is wired to.
+pub struct ConsensusContext {
+    pub network_sender: ConsensusNetworkSender,
+    pub network_events: ConsensusNetworkEvents,
+    pub state_sync_client: Arc<StateSyncClient>,
+    pub mempool_sender: mpsc::Sender<ConsensusRequest>,
+    pub libra_db: Arc<LibraDB>,
+    pub reconfig_events: ReconfigEvents,
+}
+
+/// The components of a started node that additional services can use.
+pub struct NodeContext<'a> {
+    pub node_config: &'a NodeConfig,
+    pub libra_db: Arc<LibraDB>,
+    /// None if the node runs without state sync
+    pub state_sync_client: Option<Arc<StateSyncClient>>,
+}
+
 pub struct LibraHandle {
-    _rpc: Runtime,
+    _rpc: Option<Runtime>,
     _mempool: Runtime,
-    _state_synchronizer: StateSynchronizer,
+    _state_synchronizer: Option<StateSynchronizer>,
     _network_runtimes: Vec<Runtime>,
     _consensus_runtime: Option<Runtime>,
     _debug: NodeDebugService,
     _backup: Runtime,
+    _services: Vec<Box<dyn Any + Send>>,
+    config_reloader: Arc<ConfigReloader>,
+}
+
+impl LibraHandle {
+    pub fn config_reloader(&self) -> Arc<ConfigReloader> {
+        Arc::clone(&self.config_reloader)
+    }
 }
 
 fn setup_chunk_executor(db: DbReaderWriter) -> Box<dyn ChunkExecutor> {
     Box::new(Executor::<LibraVM>::new(db))
 }
 
-fn setup_debug_interface(config: &NodeConfig) -> NodeDebugService {
+fn setup_debug_interface(
+    config: &NodeConfig,
+    config_reloader: Arc<ConfigReloader>,
+    drainer: Arc<Drainer>,
+    peer_directory: PeerDirectory,
+) -> NodeDebugService {
     let addr = format!(
         "{}:{}",
         config.debug_interface.address, config.debug_interface.admission_control_node_debug_port,
@@ -54,201 +154,413 @@ fn setup_debug_interface(config: &NodeConfig) -> NodeDebugService {
     libra_trace::set_libra_trace(&config.debug_interface.libra_trace.sampling)
         .expect("Failed to set libra trace sampling rate.");
 
-    NodeDebugService::new(addr)
+    let reload: ReloadHandler = Arc::new(move || {
+        config_reloader
+            .reload()
+            .map(|changes| changes.iter().map(ToString::to_string).collect())
+            .map_err(|issues| issues.iter().map(ToString::to_string).collect())
+    });
+    let drain: DrainHandler = Arc::new(move || Arc::clone(&drainer).start());
+    let peers: PeersHandler = Arc::new(move || peer_directory.peers().map_err(|e| e.to_string()));
+    NodeDebugService::new(
+        addr,
+        ManagementHandlers {
+            reload: Some(reload),
+            drain: Some(drain),
+            peers: Some(peers),
+        },
+    )
 }
 
-pub fn setup_environment(node_config: &mut NodeConfig) -> LibraHandle {
-    // Some of our code uses the rayon global thread pool. Name the rayon threads so it doesn't
-    // cause confusion, otherwise the threads would have their parent's name.
-    rayon::ThreadPoolBuilder::new()
-        .thread_name(|index| format!("rayon-global-{}", index))
-        .build_global()
-        .expect("Building rayon global thread pool should work.");
-
-    let mut instant = Instant::now();
-    let (libra_db, db_rw) = DbReaderWriter::wrap(
-        LibraDB::open(
-            &node_config.storage.dir(),
-            false, /* readonly */
-            node_config.storage.prune_window,
-        )
-        .expect("DB should open."),
-    );
-    let _simple_storage_service =
-        start_storage_service_with_db(&node_config, Arc::clone(&libra_db));
-    let backup_service = start_backup_service(
-        node_config.storage.backup_service_port,
-        Arc::clone(&libra_db),
-    );
-
-    bootstrap_db_if_empty::<LibraVM>(&db_rw, get_genesis_txn(&node_config).unwrap())
-        .expect("Db-bootstrapper should not fail.");
-
-    debug!(
-        "Storage service started in {} ms",
-        instant.elapsed().as_millis()
-    );
-
-    instant = Instant::now();
-    let chunk_executor = setup_chunk_executor(db_rw.clone());
-    debug!(
-        "ChunkExecutor setup in {} ms",
-        instant.elapsed().as_millis()
-    );
-    let mut network_runtimes = vec![];
-    let mut state_sync_network_handles = vec![];
-    let mut mempool_network_handles = vec![];
-    let mut consensus_network_handles = None;
-    let mut reconfig_subscriptions = vec![];
-
-    let (mempool_reconfig_subscription, mempool_reconfig_events) =
-        gen_mempool_reconfig_subscription();
-    reconfig_subscriptions.push(mempool_reconfig_subscription);
-    // consensus has to subscribe to ALL on-chain configs
-    let (consensus_reconfig_subscription, consensus_reconfig_events) =
-        gen_consensus_reconfig_subscription();
-    reconfig_subscriptions.push(consensus_reconfig_subscription);
+/// Starts all the components of the node with their default implementations. `config_path` is
+/// the file `node_config` was loaded from, which is read again when the config is reloaded.
+pub fn setup_environment(node_config: &mut NodeConfig, config_path: &Path) -> LibraHandle {
+    NodeBuilder::new(node_config, config_path).build()
+}
 
-    let waypoint = node_config.base.waypoint.waypoint();
+/// Assembles a node from its components, any of JSON-RPC, state sync and consensus can be left
+/// out or replaced, and services can be added, to build custom flavors of node. Storage, mempool
+/// and the debug interface are always started.
+///
+/// ```ignore
+/// // A read only node serving the content of its DB over JSON-RPC
+/// let node = NodeBuilder::new(&mut config, &config_path)
+///     .without_state_sync()
+///     .without_consensus()
+///     .build();
+/// ```
+pub struct NodeBuilder<'a> {
+    node_config: &'a mut NodeConfig,
+    config_path: &'a Path,
+    json_rpc: Option<JsonRpcService>,
+    state_sync: bool,
+    consensus: Option<ConsensusService>,
+    services: Vec<NodeService>,
+}
 
-    // Gather all network configs into a single vector.
-    // TODO:  consider explicitly encoding the role in the NetworkConfig
-    let mut network_configs: Vec<(RoleType, &mut NetworkConfig)> = node_config
-        .full_node_networks
-        .iter_mut()
-        .map(|network_config| (RoleType::FullNode, network_config))
-        .collect();
-    if let Some(network_config) = node_config.validator_network.as_mut() {
-        network_configs.push((RoleType::Validator, network_config));
+impl<'a> NodeBuilder<'a> {
+    pub fn new(node_config: &'a mut NodeConfig, config_path: &'a Path) -> Self {
+        Self {
+            node_config,
+            config_path,
+            json_rpc: Some(Box::new(
+                |config: &NodeConfig,
+                 libra_db: Arc<LibraDB>,
+                 mempool_sender: MempoolClientSender,
+                 admission_control: AdmissionControl| {
+                    bootstrap_rpc(config, libra_db, mempool_sender, admission_control)
+                },
+            )),
+            state_sync: true,
+            consensus: Some(Box::new(
+                |config: &mut NodeConfig, context: ConsensusContext| {
+                    start_consensus(
+                        config,
+                        context.network_sender,
+                        context.network_events,
+                        context.state_sync_client,
+                        context.mempool_sender,
+                        context.libra_db,
+                        context.reconfig_events,
+                    )
+                },
+            )),
+            services: vec![],
+        }
     }
 
-    // Instantiate every network and collect the requisite endpoints for state_sync, mempool, and consensus.
-    for (role, network_config) in network_configs {
-        // Perform common instantiation steps
-        let (runtime, mut network_builder) =
-            NetworkBuilder::create(node_config.base.chain_id, role, network_config);
-        let network_id = network_config.network_id.clone();
-
-        // Create the endpoints to connect the Network to StateSynchronizer.
-        let (state_sync_sender, state_sync_events) = network_builder
-            .add_protocol_handler(state_synchronizer::network::network_endpoint_config());
-        state_sync_network_handles.push((network_id.clone(), state_sync_sender, state_sync_events));
-
-        // Create the endpoints t connect the Network to MemPool.
-        let (mempool_sender, mempool_events) =
-            network_builder.add_protocol_handler(libra_mempool::network::network_endpoint_config(
-                // TODO:  Make this configuration option more clear.
-                node_config.mempool.max_broadcasts_per_peer,
-            ));
-        mempool_network_handles.push((network_id, mempool_sender, mempool_events));
-
-        match role {
-            // Perform steps relevant specifically to Validator networks.
-            RoleType::Validator => {
-                // A valid config is allowed to have at most one ValidatorNetwork
-                // TODO:  `expect_none` would be perfect here, once it is stable.
-                if consensus_network_handles.is_some() {
-                    panic!("There can be at most one validator network!");
-                }
+    pub fn with_json_rpc(mut self, json_rpc: JsonRpcService) -> Self {
+        self.json_rpc = Some(json_rpc);
+        self
+    }
 
-                consensus_network_handles =
-                    Some(network_builder.add_protocol_handler(
-                        consensus::network_interface::network_endpoint_config(),
-                    ));
-            }
-            // Currently no FullNode network specific steps.
-            RoleType::FullNode => (),
-        }
+    pub fn without_json_rpc(mut self) -> Self {
+        self.json_rpc = None;
+        self
+    }
+
+    /// Without state sync, the node does not follow the chain and only serves the content of its
+    /// DB. Consensus depends on state sync and has to be left out too.
+    pub fn without_state_sync(mut self) -> Self {
+        self.state_sync = false;
+        self
+    }
+
+    /// Consensus only runs on validators, this has no effect on full nodes.
+    pub fn with_consensus(mut self, consensus: ConsensusService) -> Self {
+        self.consensus = Some(consensus);
+        self
+    }
 
-        reconfig_subscriptions.append(network_builder.reconfig_subscriptions());
+    pub fn without_consensus(mut self) -> Self {
+        self.consensus = None;
+        self
+    }
 
-        // Start the network and cache the runtime so it does not go out of scope.
-        // TODO:  move all 'start' commands to a second phase at the end of setup_environment.  Target is to have one pass to wire the pieces together and a second pass to start processing in an appropriate order.
-        let peer_id = network_builder.peer_id();
-        let _listen_addr = network_builder.build();
-        network_runtimes.push(runtime);
-        debug!("Network started for peer_id: {}", peer_id);
+    pub fn add_service(mut self, service: NodeService) -> Self {
+        self.services.push(service);
+        self
     }
 
-    // TODO set up on-chain discovery network based on UpstreamConfig.fallback_network
-    // and pass network handles to mempool/state sync
-
-    // for state sync to send requests to mempool
-    let (state_sync_to_mempool_sender, state_sync_requests) =
-        channel(INTRA_NODE_CHANNEL_BUFFER_SIZE);
-    let state_synchronizer = StateSynchronizer::bootstrap(
-        state_sync_network_handles,
-        state_sync_to_mempool_sender,
-        Arc::clone(&db_rw.reader),
-        chunk_executor,
-        &node_config,
-        waypoint,
-        reconfig_subscriptions,
-    );
-    let (mp_client_sender, mp_client_events) = channel(AC_SMP_CHANNEL_BUFFER_SIZE);
-
-    let rpc_runtime = bootstrap_rpc(&node_config, libra_db.clone(), mp_client_sender);
-
-    let mut consensus_runtime = None;
-    let (consensus_to_mempool_sender, consensus_requests) = channel(INTRA_NODE_CHANNEL_BUFFER_SIZE);
-
-    instant = Instant::now();
-    let mempool = libra_mempool::bootstrap(
-        node_config,
-        Arc::clone(&db_rw.reader),
-        mempool_network_handles,
-        mp_client_events,
-        consensus_requests,
-        state_sync_requests,
-        mempool_reconfig_events,
-    );
-    debug!("Mempool started in {} ms", instant.elapsed().as_millis());
-
-    // StateSync should be instantiated and started before Consensus to avoid a cyclic dependency:
-    // network provider -> consensus -> state synchronizer -> network provider.  This has resulted
-    // in a deadlock as observed in GitHub issue #749.
-    if let Some((consensus_network_sender, consensus_network_events)) = consensus_network_handles {
-        // Make sure that state synchronizer is caught up at least to its waypoint
-        // (in case it's present). There is no sense to start consensus prior to that.
-        // TODO: Note that we need the networking layer to be able to discover & connect to the
-        // peers with potentially outdated network identity public keys.
-        debug!("Wait until state synchronizer is initialized");
-        block_on(state_synchronizer.wait_until_initialized())
-            .expect("State synchronizer initialization failure");
-        debug!("State synchronizer initialization complete.");
-
-        // Initialize and start consensus.
+    pub fn build(self) -> LibraHandle {
+        let NodeBuilder {
+            node_config,
+            config_path,
+            json_rpc,
+            state_sync,
+            consensus,
+            services,
+        } = self;
+        assert!(
+            state_sync || consensus.is_none(),
+            "Consensus can not run without state sync"
+        );
+
+        // Some of our code uses the rayon global thread pool. Name the rayon threads so it doesn't
+        // cause confusion, otherwise the threads would have their parent's name.
+        rayon::ThreadPoolBuilder::new()
+            .thread_name(|index| format!("rayon-global-{}", index))
+            .build_global()
+            .expect("Building rayon global thread pool should work.");
+        libra_vm::signature_verification::set_num_threads(
+            node_config.execution.signature_verification_threads,
+        )
+        .expect("Starting signature verification pool should work.");
+        libra_vm::set_loader_cache_budget(
+            node_config.execution.vm_max_loaded_binary_size,
+            node_config.execution.vm_max_type_cache_entries,
+        )
+        .expect("Setting the VM loader cache budget should work.");
+
+        let mut instant = Instant::now();
+        let (libra_db, db_rw) = DbReaderWriter::wrap(
+            LibraDB::open_with_rocksdb_config(
+                &node_config.storage.dir(),
+                false, /* readonly */
+                node_config.storage.prune_window,
+                &node_config.storage.rocksdb_config,
+            )
+            .expect("DB should open."),
+        );
+        libra_db.set_accumulator_cache_size(node_config.storage.accumulator_cache_size);
+        libra_db.set_index_events_by_type(node_config.storage.index_events_by_type);
+        let _simple_storage_service =
+            start_storage_service_with_db(&node_config, Arc::clone(&libra_db));
+        let backup_service = start_backup_service(
+            node_config.storage.backup_service_port,
+            Arc::clone(&libra_db),
+        );
+
+        bootstrap_db_if_empty::<LibraVM>(&db_rw, get_genesis_txn(&node_config).unwrap())
+            .expect("Db-bootstrapper should not fail.");
+        let db = Arc::clone(&libra_db);
+        health::register_check(
+            "storage",
+            Box::new(move || db.check_health().map_err(|e| e.to_string())),
+        );
+
+        debug!(
+            "Storage service started in {} ms",
+            instant.elapsed().as_millis()
+        );
+
+        let mut network_runtimes = vec![];
+        let mut state_sync_network_handles = vec![];
+        let mut mempool_network_handles = vec![];
+        let mut consensus_network_handles = None;
+        let mut reconfig_subscriptions = vec![];
+
+        let (mempool_reconfig_subscription, mempool_reconfig_events) =
+            gen_mempool_reconfig_subscription();
+        reconfig_subscriptions.push(mempool_reconfig_subscription);
+        // consensus has to subscribe to ALL on-chain configs
+        let (consensus_reconfig_subscription, consensus_reconfig_events) =
+            gen_consensus_reconfig_subscription();
+        reconfig_subscriptions.push(consensus_reconfig_subscription);
+
+        let waypoint = node_config.base.waypoint.waypoint();
+        let chain_id = node_config.base.chain_id;
+        let max_broadcasts_per_peer = node_config.mempool.max_broadcasts_per_peer;
+
+        // Gather all network configs into a single vector.
+        // TODO:  consider explicitly encoding the role in the NetworkConfig
+        let mut network_configs: Vec<(RoleType, &mut NetworkConfig)> = node_config
+            .full_node_networks
+            .iter_mut()
+            .map(|network_config| (RoleType::FullNode, network_config))
+            .collect();
+        if let Some(network_config) = node_config.validator_network.as_mut() {
+            network_configs.push((RoleType::Validator, network_config));
+        }
+
+        // Instantiate every network and collect the requisite endpoints for state_sync, mempool, and consensus.
+        for (role, network_config) in network_configs {
+            // Perform common instantiation steps
+            let (runtime, mut network_builder) =
+                NetworkBuilder::create(chain_id, role, network_config);
+            let network_id = network_config.network_id.clone();
+
+            // Create the endpoints to connect the Network to StateSynchronizer.
+            if state_sync {
+                let (state_sync_sender, state_sync_events) = network_builder
+                    .add_protocol_handler(state_synchronizer::network::network_endpoint_config());
+                state_sync_network_handles.push((
+                    network_id.clone(),
+                    state_sync_sender,
+                    state_sync_events,
+                ));
+            }
+
+            // Create the endpoints t connect the Network to MemPool.
+            let (mempool_sender, mempool_events) = network_builder.add_protocol_handler(
+                libra_mempool::network::network_endpoint_config(
+                    // TODO:  Make this configuration option more clear.
+                    max_broadcasts_per_peer,
+                ),
+            );
+            mempool_network_handles.push((network_id, mempool_sender, mempool_events));
+
+            match role {
+                // Perform steps relevant specifically to Validator networks.
+                RoleType::Validator => {
+                    // A valid config is allowed to have at most one ValidatorNetwork
+                    // TODO:  `expect_none` would be perfect here, once it is stable.
+                    if consensus_network_handles.is_some() {
+                        panic!("There can be at most one validator network!");
+                    }
+
+                    if consensus.is_some() {
+                        consensus_network_handles = Some(network_builder.add_protocol_handler(
+                            consensus::network_interface::network_endpoint_config(),
+                        ));
+                    }
+                }
+                // Currently no FullNode network specific steps.
+                RoleType::FullNode => (),
+            }
+
+            reconfig_subscriptions.append(network_builder.reconfig_subscriptions());
+
+            // Start the network and cache the runtime so it does not go out of scope.
+            // TODO:  move all 'start' commands to a second phase at the end of setup_environment.  Target is to have one pass to wire the pieces together and a second pass to start processing in an appropriate order.
+            let peer_id = network_builder.peer_id();
+            let _listen_addr = network_builder.build();
+            network_runtimes.push(runtime);
+            debug!("Network started for peer_id: {}", peer_id);
+        }
+
+        // TODO set up on-chain discovery network based on UpstreamConfig.fallback_network
+        // and pass network handles to mempool/state sync
+
+        // for state sync to send requests to mempool
+        let (state_sync_to_mempool_sender, state_sync_requests) =
+            channel(INTRA_NODE_CHANNEL_BUFFER_SIZE);
+        let state_synchronizer = if state_sync {
+            instant = Instant::now();
+            let chunk_executor = setup_chunk_executor(db_rw.clone());
+            debug!(
+                "ChunkExecutor setup in {} ms",
+                instant.elapsed().as_millis()
+            );
+            Some(StateSynchronizer::bootstrap(
+                state_sync_network_handles,
+                state_sync_to_mempool_sender,
+                Arc::clone(&db_rw.reader),
+                chunk_executor,
+                &node_config,
+                waypoint,
+                reconfig_subscriptions,
+            ))
+        } else {
+            // Nothing publishes on-chain config changes without state sync
+            None
+        };
+        let state_sync_client = state_synchronizer
+            .as_ref()
+            .map(StateSynchronizer::create_client);
+        let (mp_client_sender, mp_client_events) = channel(AC_SMP_CHANNEL_BUFFER_SIZE);
+
+        let admission_control = AdmissionControl::new(Arc::clone(&db_rw.reader));
+
+        let rpc_runtime = json_rpc.map(|json_rpc| {
+            json_rpc(
+                &*node_config,
+                libra_db.clone(),
+                mp_client_sender,
+                admission_control.clone(),
+            )
+        });
+
+        let mut consensus_runtime = None;
+        let (consensus_to_mempool_sender, consensus_requests) =
+            channel(INTRA_NODE_CHANNEL_BUFFER_SIZE);
+
         instant = Instant::now();
-        consensus_runtime = Some(start_consensus(
+        let (mempool_upstream_sender, mempool_upstream_updates) = unbounded();
+        let draining = Arc::new(AtomicBool::new(false));
+        let mempool = libra_mempool::bootstrap(
             node_config,
-            consensus_network_sender,
-            consensus_network_events,
-            state_synchronizer.create_client(),
-            consensus_to_mempool_sender,
-            libra_db,
-            consensus_reconfig_events,
+            Arc::clone(&db_rw.reader),
+            mempool_network_handles,
+            mp_client_events,
+            consensus_requests,
+            state_sync_requests,
+            mempool_reconfig_events,
+            mempool_upstream_updates,
+            Arc::clone(&draining),
+            admission_control,
+        );
+        debug!("Mempool started in {} ms", instant.elapsed().as_millis());
+
+        let config_reloader = Arc::new(
+            ConfigReloader::new(
+                config_path,
+                Arc::clone(&libra_db),
+                state_sync_client.clone(),
+                mempool_upstream_sender,
+            )
+            .expect("Failed to read the node config for reloads"),
+        );
+        let drainer = Arc::new(Drainer::new(
+            node_config.base.role,
+            Arc::clone(&libra_db),
+            draining,
         ));
-        debug!("Consensus started in {} ms", instant.elapsed().as_millis());
-    }
-
-    let debug_if = setup_debug_interface(&node_config);
 
-    let metrics_port = node_config.debug_interface.metrics_server_port;
-    let metric_host = node_config.debug_interface.address.clone();
-    thread::spawn(move || metric_server::start_server(metric_host, metrics_port, false));
-    let public_metrics_port = node_config.debug_interface.public_metrics_server_port;
-    let public_metric_host = node_config.debug_interface.address.clone();
-    thread::spawn(move || {
-        metric_server::start_server(public_metric_host, public_metrics_port, true)
-    });
+        // StateSync should be instantiated and started before Consensus to avoid a cyclic dependency:
+        // network provider -> consensus -> state synchronizer -> network provider.  This has resulted
+        // in a deadlock as observed in GitHub issue #749.
+        if let (
+            Some(consensus),
+            Some(state_synchronizer),
+            Some((consensus_network_sender, consensus_network_events)),
+        ) = (
+            consensus,
+            state_synchronizer.as_ref(),
+            consensus_network_handles,
+        ) {
+            // Make sure that state synchronizer is caught up at least to its waypoint
+            // (in case it's present). There is no sense to start consensus prior to that.
+            // TODO: Note that we need the networking layer to be able to discover & connect to the
+            // peers with potentially outdated network identity public keys.
+            debug!("Wait until state synchronizer is initialized");
+            block_on(state_synchronizer.wait_until_initialized())
+                .expect("State synchronizer initialization failure");
+            debug!("State synchronizer initialization complete.");
+
+            // Initialize and start consensus.
+            instant = Instant::now();
+            consensus_runtime = Some(consensus(
+                &mut *node_config,
+                ConsensusContext {
+                    network_sender: consensus_network_sender,
+                    network_events: consensus_network_events,
+                    state_sync_client: state_synchronizer.create_client(),
+                    mempool_sender: consensus_to_mempool_sender,
+                    libra_db: Arc::clone(&libra_db),
+                    reconfig_events: consensus_reconfig_events,
+                },
+            ));
+            debug!("Consensus started in {} ms", instant.elapsed().as_millis());
+        }
 
-    LibraHandle {
-        _network_runtimes: network_runtimes,
-        _rpc: rpc_runtime,
-        _mempool: mempool,
-        _state_synchronizer: state_synchronizer,
-        _consensus_runtime: consensus_runtime,
-        _debug: debug_if,
-        _backup: backup_service,
+        let peer_directory = PeerDirectory::new(Arc::clone(&libra_db));
+        let context = NodeContext {
+            node_config: &*node_config,
+            libra_db,
+            state_sync_client,
+        };
+        let services = services
+            .into_iter()
+            .map(|service| service(&context))
+            .collect();
+
+        let debug_if = setup_debug_interface(
+            &node_config,
+            Arc::clone(&config_reloader),
+            drainer,
+            peer_directory,
+        );
+
+        let metrics_port = node_config.debug_interface.metrics_server_port;
+        let metric_host = node_config.debug_interface.address.clone();
+        thread::spawn(move || metric_server::start_server(metric_host, metrics_port, false));
+        let public_metrics_port = node_config.debug_interface.public_metrics_server_port;
+        let public_metric_host = node_config.debug_interface.address.clone();
+        thread::spawn(move || {
+            metric_server::start_server(public_metric_host, public_metrics_port, true)
+        });
+
+        LibraHandle {
+            _network_runtimes: network_runtimes,
+            _rpc: rpc_runtime,
+            _mempool: mempool,
+            _state_synchronizer: state_synchronizer,
+            _consensus_runtime: consensus_runtime,
+            _debug: debug_if,
+            _backup: backup_service,
+            _services: services,
+            config_reloader,
+        }
     }
 }
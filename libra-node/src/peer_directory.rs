@@ -0,0 +1,113 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Maps network PeerIds to the on-chain accounts of the validators and of their operators, so
+//! the PeerIds in log lines can be attributed. The mapping is derived from the ValidatorSet and
+//! the ValidatorConfig resources, and rebuilt when a new epoch starts.
+
+use anyhow::{format_err, Result};
+use debug_interface::node_debug_service::PeerAccounts;
+use libra_types::{
+    account_address::AccountAddress, account_config::libra_root_address,
+    account_state::AccountState, PeerId,
+};
+use libradb::LibraDB;
+use std::{
+    convert::TryFrom,
+    sync::{Arc, Mutex},
+};
+use storage_interface::DbReader;
+
+const VALIDATOR_NETWORK: &str = "validator";
+const FULL_NODE_NETWORK: &str = "full_node";
+
+pub struct PeerDirectory {
+    libra_db: Arc<LibraDB>,
+    // The epoch the mapping was built for, and the mapping
+    cache: Mutex<Option<(u64, Vec<PeerAccounts>)>>,
+}
+
+impl PeerDirectory {
+    pub fn new(libra_db: Arc<LibraDB>) -> Self {
+        Self {
+            libra_db,
+            cache: Mutex::new(None),
+        }
+    }
+
+    /// Returns the PeerIds of the current validators on the validator and full node networks,
+    /// with the accounts they belong to.
+    pub fn peers(&self) -> Result<Vec<PeerAccounts>> {
+        let epoch = self
+            .libra_db
+            .get_latest_ledger_info()?
+            .ledger_info()
+            .next_block_epoch();
+        let mut cache = self.cache.lock().unwrap();
+        if let Some((cached_epoch, peers)) = &*cache {
+            if *cached_epoch == epoch {
+                return Ok(peers.clone());
+            }
+        }
+        let peers = self.load()?;
+        *cache = Some((epoch, peers.clone()));
+        Ok(peers)
+    }
+
+    /// Returns the accounts `peer_id` belongs to, on every network it is used on.
+    pub fn lookup(&self, peer_id: PeerId) -> Result<Vec<PeerAccounts>> {
+        let peer_id = peer_id.to_string();
+        Ok(self
+            .peers()?
+            .into_iter()
+            .filter(|peer| peer.peer_id == peer_id)
+            .collect())
+    }
+
+    fn load(&self) -> Result<Vec<PeerAccounts>> {
+        let validator_set = self
+            .account_state(libra_root_address())?
+            .get_validator_set()?
+            .ok_or_else(|| format_err!("no ValidatorSet on chain"))?;
+
+        let mut peers = vec![];
+        for info in validator_set.payload() {
+            let owner = *info.account_address();
+            let operator = self
+                .account_state(owner)?
+                .get_validator_config_resource()?
+                .and_then(|resource| resource.delegated_account);
+            let config = info.config();
+            // Discovery addresses validators by their account, while the PeerIds of inbound
+            // connections are derived from the network identity keys.
+            let mut peer_ids = vec![
+                (VALIDATOR_NETWORK, owner),
+                (
+                    VALIDATOR_NETWORK,
+                    PeerId::from_identity_public_key(config.validator_network_identity_public_key),
+                ),
+                (FULL_NODE_NETWORK, owner),
+                (
+                    FULL_NODE_NETWORK,
+                    PeerId::from_identity_public_key(config.full_node_network_identity_public_key),
+                ),
+            ];
+            peer_ids.dedup();
+            peers.extend(peer_ids.into_iter().map(|(network, peer_id)| PeerAccounts {
+                peer_id: peer_id.to_string(),
+                network: network.to_string(),
+                owner: owner.to_string(),
+                operator: operator.map(|operator| operator.to_string()),
+            }));
+        }
+        Ok(peers)
+    }
+
+    fn account_state(&self, address: AccountAddress) -> Result<AccountState> {
+        let blob = self
+            .libra_db
+            .get_latest_account_state(address)?
+            .ok_or_else(|| format_err!("no account at {}", address))?;
+        AccountState::try_from(&blob)
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Admission control of submitted transactions, shared by JSON-RPC and mempool.
+//!
+//! Spammers can cheaply submit transactions that will obviously never be executed, each of which
+//! would otherwise cost a run of the prologue in the VM validator. Admission control rejects
+//! them up front from the committed state of their sender, read through the account state cache
+//! mempool shares with the VM validator: JSON-RPC checks submissions before sending them to
+//! mempool, and mempool checks the transactions of its peers before validating them. The checks
+//! are a subset of those of the prologue, run in the same order, so only transactions the VM
+//! validator would reject are rejected.
+
+use crate::counters::ADMISSION_CONTROL_REJECTIONS;
+use anyhow::Result;
+use libra_types::{
+    account_config::from_currency_code_string,
+    account_state::AccountState,
+    transaction::SignedTransaction,
+    vm_status::{
+        StatusCode::{
+            INSUFFICIENT_BALANCE_FOR_TRANSACTION_FEE, RESOURCE_DOES_NOT_EXIST,
+            SEQUENCE_NUMBER_TOO_OLD,
+        },
+        VMStatus,
+    },
+};
+use std::{convert::TryFrom, sync::Arc};
+use storage_interface::DbReader;
+use vm_validator::account_state_cache::AccountStateCache;
+
+/// Rejects the transactions whose sender's committed state shows they can't be executed.
+#[derive(Clone)]
+pub struct AdmissionControl {
+    db: Arc<dyn DbReader>,
+    account_cache: Arc<AccountStateCache>,
+}
+
+impl AdmissionControl {
+    /// Creates an admission control reading account states from `db` through a new cache, to be
+    /// shared with mempool.
+    pub fn new(db: Arc<dyn DbReader>) -> Self {
+        Self::with_account_cache(db, Arc::new(AccountStateCache::new()))
+    }
+
+    /// Creates an admission control reading account states through `account_cache`.
+    pub fn with_account_cache(
+        db: Arc<dyn DbReader>,
+        account_cache: Arc<AccountStateCache>,
+    ) -> Self {
+        Self { db, account_cache }
+    }
+
+    /// The cache account states are read through.
+    pub fn account_cache(&self) -> &Arc<AccountStateCache> {
+        &self.account_cache
+    }
+
+    /// Returns the committed sequence number of the sender of `txn`, 0 if the account does not
+    /// exist, or the status to reject `txn` with:
+    /// * `INSUFFICIENT_BALANCE_FOR_TRANSACTION_FEE` if the sender can't pay for the maximum gas
+    ///   of `txn` at its gas price,
+    /// * `SEQUENCE_NUMBER_TOO_OLD` if `txn` reuses a committed sequence number,
+    /// * `RESOURCE_DOES_NOT_EXIST` if the state of the sender can't be read.
+    pub fn check(&self, txn: &SignedTransaction) -> Result<u64, VMStatus> {
+        let (sequence_number, balance) = self.read_sender_state(txn).map_err(|_| {
+            ADMISSION_CONTROL_REJECTIONS
+                .with_label_values(&["storage_error"])
+                .inc();
+            VMStatus::Error(RESOURCE_DOES_NOT_EXIST)
+        })?;
+
+        if let Some(balance) = balance {
+            let can_pay_gas = txn
+                .max_gas_amount()
+                .checked_mul(txn.gas_unit_price())
+                .map_or(false, |max_transaction_fee| balance >= max_transaction_fee);
+            if !can_pay_gas {
+                ADMISSION_CONTROL_REJECTIONS
+                    .with_label_values(&["insufficient_balance_for_gas"])
+                    .inc();
+                return Err(VMStatus::Error(INSUFFICIENT_BALANCE_FOR_TRANSACTION_FEE));
+            }
+        }
+        if txn.sequence_number() < sequence_number {
+            ADMISSION_CONTROL_REJECTIONS
+                .with_label_values(&["sequence_number_too_old"])
+                .inc();
+            return Err(VMStatus::Error(SEQUENCE_NUMBER_TOO_OLD));
+        }
+        Ok(sequence_number)
+    }
+
+    /// Reads the sequence number of the sender of `txn` and its balance in the gas currency of
+    /// `txn`. The balance is `None` if the sender does not hold the gas currency, or the gas
+    /// currency is invalid, which is left to the VM validator to reject.
+    fn read_sender_state(&self, txn: &SignedTransaction) -> Result<(u64, Option<u64>)> {
+        let (version, _state_root) = self.db.get_latest_state_root()?;
+        let blob = self
+            .account_cache
+            .get_account_state(self.db.as_ref(), txn.sender(), version)?;
+        let account_state = match blob {
+            Some(blob) => AccountState::try_from(&blob)?,
+            None => return Ok((0, None)),
+        };
+        let sequence_number = account_state
+            .get_account_resource()?
+            .map_or(0, |account| account.sequence_number());
+        let balance = match from_currency_code_string(txn.gas_currency_code()) {
+            Ok(currency_code) => account_state
+                .get_balance_resources(&[currency_code])?
+                .values()
+                .next()
+                .map(|balance| balance.coin()),
+            Err(_) => None,
+        };
+        Ok((sequence_number, balance))
+    }
+}
@@ -51,6 +51,15 @@ impl Mempool {
         }
     }
 
+    /// Fetches a transaction by account address + sequence number, whatever its state in mempool.
+    pub(crate) fn get_transaction(
+        &self,
+        sender: &AccountAddress,
+        sequence_number: u64,
+    ) -> Option<SignedTransaction> {
+        self.transactions.get(sender, sequence_number)
+    }
+
     /// This function will be called once the transaction has been stored
     pub(crate) fn remove_transaction(
         &mut self,
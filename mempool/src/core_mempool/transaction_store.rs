@@ -9,7 +9,7 @@ use crate::{
         },
         transaction::{MempoolTransaction, TimelineState},
     },
-    OP_COUNTERS,
+    counters, OP_COUNTERS,
 };
 use anyhow::{format_err, Result};
 use libra_config::config::MempoolConfig;
@@ -141,6 +141,7 @@ impl TransactionStore {
         OP_COUNTERS.set("txn.system_ttl_index", self.system_ttl_index.size());
         OP_COUNTERS.set("txn.parking_lot_index", self.parking_lot_index.size());
         OP_COUNTERS.set("txn.priority_index", self.priority_index.size());
+        counters::CORE_MEMPOOL_READY_TXNS.set(self.priority_index.size() as i64);
     }
 
     /// checks if Mempool is full
@@ -1,9 +1,21 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use libra_metrics::{register_int_counter, register_int_counter_vec, IntCounter, IntCounterVec};
+use libra_metrics::{
+    register_int_counter, register_int_counter_vec, register_int_gauge, IntCounter, IntCounterVec,
+    IntGauge,
+};
 use once_cell::sync::Lazy;
 
+/// Number of transactions in core mempool that are ready to be included in a block
+pub static CORE_MEMPOOL_READY_TXNS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "libra_core_mempool_ready_txns",
+        "Number of transactions in core mempool ready to be included in a block"
+    )
+    .unwrap()
+});
+
 /// Counter of pending network events to Mempool
 pub static PENDING_MEMPOOL_NETWORK_EVENTS: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
@@ -20,7 +32,7 @@ pub static MEMPOOL_SERVICE: Lazy<IntCounterVec> = Lazy::new(|| {
         "Number of txns processed by Mempool Service",
         &[
             // type of request through which the transactions were delivered to MempoolService:
-            // "get_block", "commit_transactions"
+            // "get_block", "commit_transactions", "get_transactions"
             "req_type",
             // direction of the request: "requested", "returned"
             "req_status",
@@ -58,3 +70,14 @@ pub static SHARED_MEMPOOL_TRANSACTION_BROADCAST: Lazy<IntCounter> = Lazy::new(||
     )
     .unwrap()
 });
+
+/// Number of transactions rejected by admission control, by reason: "sequence_number_too_old",
+/// "insufficient_balance_for_gas", "storage_error"
+pub static ADMISSION_CONTROL_REJECTIONS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "libra_mempool_admission_control_rejections",
+        "Number of transactions rejected by admission control",
+        &["reason"]
+    )
+    .unwrap()
+});
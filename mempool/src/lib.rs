@@ -59,6 +59,7 @@
 /// This module provides mocks of shared mempool for tests.
 #[cfg(any(test, feature = "fuzzing"))]
 mod tests;
+pub use admission_control::AdmissionControl;
 pub use shared_mempool::{
     bootstrap, network,
     types::{
@@ -70,8 +71,9 @@ pub use shared_mempool::{
 #[cfg(feature = "fuzzing")]
 pub use tests::mocks;
 
+mod admission_control;
 mod core_mempool;
-mod counters;
+pub mod counters;
 mod shared_mempool;
 
 // module op counters
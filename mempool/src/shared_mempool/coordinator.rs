@@ -22,8 +22,12 @@ use futures::{
     stream::{select_all, FuturesUnordered},
     StreamExt,
 };
-use libra_config::{config::PeerNetworkId, network_id::NetworkId};
+use libra_config::{
+    config::{PeerNetworkId, UpstreamConfig},
+    network_id::NetworkId,
+};
 use libra_logger::prelude::*;
+use libra_metrics::health;
 use libra_trace::prelude::*;
 use libra_types::{on_chain_config::OnChainConfigPayload, transaction::SignedTransaction};
 use std::{
@@ -46,6 +50,7 @@ pub(crate) async fn coordinator<V>(
     mut consensus_requests: mpsc::Receiver<ConsensusRequest>,
     mut state_sync_requests: mpsc::Receiver<CommitNotification>,
     mut mempool_reconfig_events: libra_channel::Receiver<(), OnChainConfigPayload>,
+    mut upstream_config_updates: mpsc::UnboundedReceiver<UpstreamConfig>,
 ) where
     V: TransactionValidation,
 {
@@ -80,13 +85,25 @@ pub(crate) async fn coordinator<V>(
                 tasks::process_consensus_request(&mempool, msg).await;
             }
             msg = state_sync_requests.select_next_some() => {
-                tokio::spawn(tasks::process_state_sync_request(mempool.clone(), msg));
+                tokio::spawn(tasks::process_state_sync_request(
+                    mempool.clone(),
+                    smp.db.clone(),
+                    smp.account_cache.clone(),
+                    smp.validator.clone(),
+                    msg,
+                ));
             }
             config_update = mempool_reconfig_events.select_next_some() => {
                 bounded_executor
                 .spawn(tasks::process_config_update(config_update, smp.validator.clone()))
                 .await;
             },
+            upstream_config = upstream_config_updates.select_next_some() => {
+                for peer in peer_manager.update_upstream_config(upstream_config) {
+                    tasks::execute_broadcast(peer, false, &mut smp, &mut scheduled_broadcasts, executor.clone());
+                }
+                notify_subscribers(SharedMempoolNotification::PeerStateChange, &subscribers);
+            },
             (peer, backoff) = scheduled_broadcasts.select_next_some() => {
                 tasks::execute_broadcast(peer, backoff, &mut smp, &mut scheduled_broadcasts, executor.clone());
             },
@@ -169,8 +186,12 @@ pub(crate) async fn coordinator<V>(
 
 /// GC all expired transactions by SystemTTL
 pub(crate) async fn gc_coordinator(mempool: Arc<Mutex<CoreMempool>>, gc_interval_ms: u64) {
-    let mut interval = interval(Duration::from_millis(gc_interval_ms));
+    let gc_interval = Duration::from_millis(gc_interval_ms);
+    let mut interval = interval(gc_interval);
+    // Garbage collection takes the mempool lock, so the heartbeats also stop if mempool is stuck
+    let health = health::register_heartbeat("mempool", gc_interval * 10);
     while let Some(_interval) = interval.next().await {
+        health.heartbeat();
         mempool
             .lock()
             .expect("[shared mempool] failed to acquire mempool lock")
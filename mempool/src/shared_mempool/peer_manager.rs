@@ -10,7 +10,7 @@ use rand::seq::SliceRandom;
 use std::{
     collections::{BTreeSet, HashMap, HashSet},
     ops::{Deref, DerefMut},
-    sync::Mutex,
+    sync::{Mutex, RwLock},
 };
 
 /// stores only peers that receive txns from this node
@@ -27,8 +27,10 @@ pub(crate) struct PeerSyncState {
 }
 
 pub(crate) struct PeerManager {
-    upstream_config: UpstreamConfig,
+    upstream_config: RwLock<UpstreamConfig>,
     peer_info: Mutex<PeerInfo>,
+    // live peers that are not upstream, kept in case a config reload makes their network upstream
+    downstream_peers: Mutex<HashSet<PeerNetworkId>>,
     // the upstream peer to failover to if all peers in the primary upstream network are dead
     // the number of failover peers is limited to 1 to avoid network competition in the failover networks
     failover_peer: Mutex<Option<PeerNetworkId>>,
@@ -57,8 +59,9 @@ impl BroadcastInfo {
 impl PeerManager {
     pub fn new(upstream_config: UpstreamConfig) -> Self {
         Self {
-            upstream_config,
+            upstream_config: RwLock::new(upstream_config),
             peer_info: Mutex::new(PeerInfo::new()),
+            downstream_peers: Mutex::new(HashSet::new()),
             failover_peer: Mutex::new(None),
         }
     }
@@ -95,6 +98,11 @@ impl PeerManager {
                     },
                 );
             }
+        } else {
+            self.downstream_peers
+                .lock()
+                .expect("failed to acquire downstream peers lock")
+                .insert(peer);
         }
         drop(peer_info);
         self.update_failover();
@@ -102,6 +110,10 @@ impl PeerManager {
     }
 
     pub fn disable_peer(&self, peer: PeerNetworkId) {
+        self.downstream_peers
+            .lock()
+            .expect("failed to acquire downstream peers lock")
+            .remove(&peer);
         if let Some(state) = self
             .peer_info
             .lock()
@@ -113,19 +125,54 @@ impl PeerManager {
         self.update_failover();
     }
 
+    /// Replaces the upstream networks, and returns the live peers that became upstream and need
+    /// to be broadcast to. Peers that are no longer upstream are not picked for broadcasts anymore.
+    pub fn update_upstream_config(&self, upstream_config: UpstreamConfig) -> Vec<PeerNetworkId> {
+        *self
+            .upstream_config
+            .write()
+            .expect("failed to acquire upstream config lock") = upstream_config;
+
+        let new_upstream_peers: Vec<_> = {
+            let mut downstream_peers = self
+                .downstream_peers
+                .lock()
+                .expect("failed to acquire downstream peers lock");
+            let new_upstream_peers: Vec<_> = downstream_peers
+                .iter()
+                .filter(|peer| self.is_upstream_peer(peer))
+                .cloned()
+                .collect();
+            for peer in &new_upstream_peers {
+                downstream_peers.remove(peer);
+            }
+            new_upstream_peers
+        };
+        self.update_failover();
+        new_upstream_peers
+            .into_iter()
+            .filter(|peer| self.add_peer(peer.clone()))
+            .collect()
+    }
+
     // updates the peer chosen to failover to if all peers in the primary upstream network are down
     fn update_failover(&self) {
-        // failover is enabled only if there are multiple upstream networks
-        if self.upstream_config.networks.len() < 2 {
-            return;
-        }
-
+        let upstream_config = self
+            .upstream_config
+            .read()
+            .expect("failed to acquire upstream config lock");
         // declare `failover` as standalone to satisfy lifetime requirement
         let mut failover = self
             .failover_peer
             .lock()
             .expect("failed to acquire failover lock");
         let current_failover = failover.deref_mut();
+        // failover is enabled only if there are multiple upstream networks
+        if upstream_config.networks.len() < 2 {
+            *current_failover = None;
+            return;
+        }
+
         let peer_info = self.peer_info.lock().expect("can not get peer info lock");
         let active_peers_by_network = peer_info
             .iter()
@@ -138,8 +185,7 @@ impl PeerManager {
             })
             .into_group_map();
 
-        let primary_upstream = self
-            .upstream_config
+        let primary_upstream = upstream_config
             .networks
             .get(0)
             .expect("missing primary upstream network");
@@ -149,7 +195,7 @@ impl PeerManager {
             let mut failover_candidate = None;
             // find the highest-pref'ed network (based on preference defined in upstream config)
             // with any live peer and pick a peer from that network
-            for failover_network in self.upstream_config.networks[1..].iter() {
+            for failover_network in upstream_config.networks[1..].iter() {
                 if let Some(active_peers) = active_peers_by_network.get(failover_network) {
                     failover_candidate = active_peers.choose(&mut rand::thread_rng());
                     if failover_candidate.is_some() {
@@ -257,12 +303,16 @@ impl PeerManager {
 
     pub fn is_upstream_peer(&self, peer: &PeerNetworkId) -> bool {
         self.upstream_config
+            .read()
+            .expect("failed to acquire upstream config lock")
             .get_upstream_preference(peer.network_id())
             .is_some()
     }
 
     fn is_primary_upstream_peer(&self, peer: &PeerNetworkId) -> bool {
         self.upstream_config
+            .read()
+            .expect("failed to acquire upstream config lock")
             .get_upstream_preference(peer.network_id())
             == Some(0)
     }
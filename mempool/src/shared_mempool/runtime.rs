@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    admission_control::AdmissionControl,
     core_mempool::CoreMempool,
     network::{MempoolNetworkEvents, MempoolNetworkSender},
     shared_mempool::{
@@ -14,18 +15,24 @@ use crate::{
 use anyhow::Result;
 use channel::libra_channel;
 use futures::channel::{
-    mpsc::{self, Receiver, UnboundedSender},
+    mpsc::{self, Receiver, UnboundedReceiver, UnboundedSender},
     oneshot,
 };
-use libra_config::{config::NodeConfig, network_id::NetworkId};
+use libra_config::{
+    config::{NodeConfig, UpstreamConfig},
+    network_id::NetworkId,
+};
 use libra_types::{on_chain_config::OnChainConfigPayload, transaction::SignedTransaction};
 use std::{
     collections::HashMap,
-    sync::{Arc, Mutex, RwLock},
+    sync::{atomic::AtomicBool, Arc, Mutex, RwLock},
 };
 use storage_interface::DbReader;
 use tokio::runtime::{Builder, Handle, Runtime};
-use vm_validator::vm_validator::{TransactionValidation, VMValidator};
+use vm_validator::{
+    account_state_cache::AccountStateCache,
+    vm_validator::{TransactionValidation, VMValidator},
+};
 
 /// bootstrap of SharedMempool
 /// creates separate Tokio Runtime that runs following routines:
@@ -43,7 +50,10 @@ pub(crate) fn start_shared_mempool<V>(
     consensus_requests: mpsc::Receiver<ConsensusRequest>,
     state_sync_requests: mpsc::Receiver<CommitNotification>,
     mempool_reconfig_events: libra_channel::Receiver<(), OnChainConfigPayload>,
+    upstream_config_updates: UnboundedReceiver<UpstreamConfig>,
+    draining: Arc<AtomicBool>,
     db: Arc<dyn DbReader>,
+    account_cache: Arc<AccountStateCache>,
     validator: Arc<RwLock<V>>,
     subscribers: Vec<UnboundedSender<SharedMempoolNotification>>,
 ) where
@@ -59,14 +69,19 @@ pub(crate) fn start_shared_mempool<V>(
         network_senders.insert(network_id, network_sender);
     }
 
+    let admission_control =
+        AdmissionControl::with_account_cache(Arc::clone(&db), Arc::clone(&account_cache));
     let smp = SharedMempool {
         mempool: mempool.clone(),
         config: config.mempool.clone(),
         network_senders,
         db,
+        account_cache,
+        admission_control,
         validator,
         peer_manager,
         subscribers,
+        draining,
     };
 
     executor.spawn(coordinator(
@@ -77,6 +92,7 @@ pub(crate) fn start_shared_mempool<V>(
         consensus_requests,
         state_sync_requests,
         mempool_reconfig_events,
+        upstream_config_updates,
     ));
 
     executor.spawn(gc_coordinator(
@@ -96,6 +112,12 @@ pub fn bootstrap(
     consensus_requests: Receiver<ConsensusRequest>,
     state_sync_requests: Receiver<CommitNotification>,
     mempool_reconfig_events: libra_channel::Receiver<(), OnChainConfigPayload>,
+    // Upstream networks replacing `config.upstream` when the node config is reloaded
+    upstream_config_updates: UnboundedReceiver<UpstreamConfig>,
+    // Set when the node drains before a shutdown, to stop taking new transactions
+    draining: Arc<AtomicBool>,
+    // Shared with JSON-RPC, which checks submissions before sending them to mempool
+    admission_control: AdmissionControl,
 ) -> Runtime {
     let runtime = Builder::new()
         .thread_name("shared-mem-")
@@ -104,7 +126,11 @@ pub fn bootstrap(
         .build()
         .expect("[shared mempool] failed to create runtime");
     let mempool = Arc::new(Mutex::new(CoreMempool::new(&config)));
-    let vm_validator = Arc::new(RwLock::new(VMValidator::new(Arc::clone(&db))));
+    let account_cache = Arc::clone(admission_control.account_cache());
+    let vm_validator = Arc::new(RwLock::new(VMValidator::new(
+        Arc::clone(&db),
+        Arc::clone(&account_cache),
+    )));
     start_shared_mempool(
         runtime.handle(),
         config,
@@ -114,7 +140,10 @@ pub fn bootstrap(
         consensus_requests,
         state_sync_requests,
         mempool_reconfig_events,
+        upstream_config_updates,
+        draining,
         db,
+        account_cache,
         vm_validator,
         vec![],
     );
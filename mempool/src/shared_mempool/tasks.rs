@@ -16,26 +16,31 @@ use crate::{
 use anyhow::{format_err, Result};
 use futures::{channel::oneshot, stream::FuturesUnordered};
 use libra_config::config::PeerNetworkId;
-use libra_logger::prelude::*;
+use libra_crypto::hash::CryptoHash;
+use libra_logger::{
+    context::{with_context, TXN_HASH},
+    prelude::*,
+};
 use libra_types::{
     mempool_status::{MempoolStatus, MempoolStatusCode},
     on_chain_config::OnChainConfigPayload,
-    transaction::SignedTransaction,
-    vm_status::{
-        StatusCode::{RESOURCE_DOES_NOT_EXIST, SEQUENCE_NUMBER_TOO_OLD},
-        VMStatus,
-    },
+    transaction::{SignedTransaction, Transaction},
+    vm_status::VMStatus,
     PeerId,
 };
 use std::{
     cmp,
     collections::HashSet,
     ops::Deref,
-    sync::{Arc, Mutex, RwLock},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, RwLock,
+    },
     time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
+use storage_interface::DbReader;
 use tokio::runtime::Handle;
-use vm_validator::vm_validator::{get_account_sequence_number, TransactionValidation};
+use vm_validator::{account_state_cache::AccountStateCache, vm_validator::TransactionValidation};
 
 // ============================== //
 //  broadcast_coordinator tasks  //
@@ -279,7 +284,9 @@ fn gen_ack_response(request_id: String, results: Vec<SubmissionStatus>) -> Mempo
         .into_iter()
         .enumerate()
         .filter_map(|(idx, result)| {
-            backoff = backoff || result.0.code == MempoolStatusCode::MempoolIsFull;
+            backoff = backoff
+                || result.0.code == MempoolStatusCode::MempoolIsFull
+                || result.0.code == MempoolStatusCode::Draining;
 
             if is_txn_retryable(result) {
                 Some(idx as u64)
@@ -300,6 +307,7 @@ fn is_txn_retryable(result: SubmissionStatus) -> bool {
     let mempool_status = result.0.code;
     mempool_status == MempoolStatusCode::TooManyTransactions
         || mempool_status == MempoolStatusCode::MempoolIsFull
+        || mempool_status == MempoolStatusCode::Draining
 }
 
 /// submits a list of SignedTransaction to the local mempool
@@ -312,75 +320,74 @@ async fn process_incoming_transactions<V>(
 where
     V: TransactionValidation,
 {
-    let mut statuses = vec![];
+    if smp.draining.load(Ordering::Relaxed) {
+        return transactions
+            .iter()
+            .map(|_| {
+                (
+                    MempoolStatus::new(MempoolStatusCode::Draining)
+                        .with_message("node is shutting down".to_string()),
+                    None,
+                )
+            })
+            .collect();
+    }
 
-    let seq_numbers = transactions
-        .iter()
-        .map(|t| get_account_sequence_number(smp.db.as_ref(), t.sender()))
-        .collect::<Vec<_>>();
+    let mut statuses = vec![];
 
     let transactions: Vec<_> = transactions
         .into_iter()
-        .enumerate()
-        .filter_map(|(idx, t)| {
-            if let Ok(sequence_number) = seq_numbers[idx] {
-                if t.sequence_number() >= sequence_number {
-                    return Some((t, sequence_number));
-                } else {
-                    statuses.push((
-                        MempoolStatus::new(MempoolStatusCode::VmError),
-                        Some(VMStatus::Error(SEQUENCE_NUMBER_TOO_OLD)),
-                    ));
-                }
-            } else {
-                // failed to get transaction
-                statuses.push((
-                    MempoolStatus::new(MempoolStatusCode::VmError),
-                    Some(VMStatus::Error(RESOURCE_DOES_NOT_EXIST)),
-                ));
+        .filter_map(|t| match smp.admission_control.check(&t) {
+            Ok(sequence_number) => Some((t, sequence_number)),
+            Err(status) => {
+                statuses.push((MempoolStatus::new(MempoolStatusCode::VmError), Some(status)));
+                None
             }
-            None
         })
         .collect();
 
-    let validation_results = transactions
-        .iter()
-        .map(|t| {
-            smp.validator
-                .read()
-                .unwrap()
-                .validate_transaction(t.0.clone())
-        })
-        .collect::<Vec<_>>();
+    let validation_results = smp
+        .validator
+        .read()
+        .unwrap()
+        .validate_transactions(transactions.iter().map(|t| t.0.clone()).collect())
+        .unwrap_or_else(|e| {
+            error!("[shared mempool] failed to validate transactions: {:?}", e);
+            vec![]
+        });
 
     {
         let mut mempool = smp
             .mempool
             .lock()
             .expect("[shared mempool] failed to acquire mempool lock");
-        for (idx, (transaction, sequence_number)) in transactions.into_iter().enumerate() {
-            if let Ok(validation_result) = &validation_results[idx] {
-                match validation_result.status() {
-                    None => {
-                        let gas_amount = transaction.max_gas_amount();
-                        let rankin_score = validation_result.score();
-                        let is_governance_txn = validation_result.is_governance_txn();
-                        let mempool_status = mempool.add_txn(
+        for ((transaction, sequence_number), validation_result) in
+            transactions.into_iter().zip(validation_results)
+        {
+            match validation_result.status() {
+                None => {
+                    let gas_amount = transaction.max_gas_amount();
+                    let rankin_score = validation_result.score();
+                    let is_governance_txn = validation_result.is_governance_txn();
+                    // The hash the transaction is committed under, to follow it in the logs
+                    let txn_hash = Transaction::UserTransaction(transaction.clone()).hash();
+                    let mempool_status = with_context(TXN_HASH, txn_hash.to_hex(), || {
+                        mempool.add_txn(
                             transaction,
                             gas_amount,
                             rankin_score,
                             sequence_number,
                             timeline_state,
                             is_governance_txn,
-                        );
-                        statuses.push((mempool_status, None));
-                    }
-                    Some(validation_status) => {
-                        statuses.push((
-                            MempoolStatus::new(MempoolStatusCode::VmError),
-                            Some(validation_status.clone()),
-                        ));
-                    }
+                        )
+                    });
+                    statuses.push((mempool_status, None));
+                }
+                Some(validation_status) => {
+                    statuses.push((
+                        MempoolStatus::new(MempoolStatusCode::VmError),
+                        Some(validation_status.clone()),
+                    ));
                 }
             }
         }
@@ -421,11 +428,34 @@ fn log_txn_process_results(results: &[SubmissionStatus], sender: Option<PeerId>)
 // ================================= //
 // intra-node communication handlers //
 // ================================= //
-pub(crate) async fn process_state_sync_request(
+pub(crate) async fn process_state_sync_request<V>(
     mempool: Arc<Mutex<CoreMempool>>,
+    db: Arc<dyn DbReader>,
+    account_cache: Arc<AccountStateCache>,
+    validator: Arc<RwLock<V>>,
     req: CommitNotification,
-) {
+) where
+    V: TransactionValidation,
+{
+    let senders: HashSet<_> = req.transactions.iter().map(|txn| txn.sender).collect();
     commit_txns(&mempool, req.transactions, req.block_timestamp_usecs, false).await;
+    // the VM validator must not validate transactions against stale code
+    if req.publishes_modules {
+        if let Err(e) = validator.write().unwrap().flush_modules() {
+            error!(
+                "[shared mempool] failed to flush modules of VM validator with error: {:?}",
+                e
+            );
+        }
+    }
+    // the senders of a block are likely to submit again, so read their new states ahead of
+    // validating their next transactions
+    if let Err(e) = account_cache.prime(db.as_ref(), senders) {
+        error!(
+            "[shared mempool] failed to prime account state cache with error: {:?}",
+            e
+        );
+    }
     // send back to callback
     if let Err(e) = req
         .callback
@@ -475,6 +505,24 @@ pub(crate) async fn process_consensus_request(mempool: &Mutex<CoreMempool>, req:
             commit_txns(mempool, transactions, 0, true).await;
             (ConsensusResponse::CommitResponse(), callback)
         }
+        ConsensusRequest::GetTransactionsRequest(txn_pointers, callback) => {
+            counters::MEMPOOL_SERVICE
+                .with_label_values(&["get_transactions", "requested"])
+                .inc_by(txn_pointers.len() as i64);
+            let txns: Vec<_> = {
+                let mempool = mempool.lock().expect("failed to acquire mempool lock");
+                txn_pointers
+                    .iter()
+                    .map(|(sender, sequence_number)| {
+                        mempool.get_transaction(sender, *sequence_number)
+                    })
+                    .collect()
+            };
+            counters::MEMPOOL_SERVICE
+                .with_label_values(&["get_transactions", "returned"])
+                .inc_by(txns.iter().filter(|txn| txn.is_some()).count() as i64);
+            (ConsensusResponse::GetTransactionsResponse(txns), callback)
+        }
     };
     // send back to callback
     if let Err(e) = callback.send(Ok(resp)).map_err(|_| {
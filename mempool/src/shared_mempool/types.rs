@@ -4,6 +4,7 @@
 //! Objects used by/related to shared mempool
 
 use crate::{
+    admission_control::AdmissionControl,
     core_mempool::CoreMempool,
     shared_mempool::{network::MempoolNetworkSender, peer_manager::PeerManager},
 };
@@ -28,14 +29,14 @@ use libra_types::{
 use std::{
     collections::HashMap,
     pin::Pin,
-    sync::{Arc, Mutex, RwLock},
+    sync::{atomic::AtomicBool, Arc, Mutex, RwLock},
     task::Waker,
     time::Instant,
 };
 use storage_interface::DbReader;
 use subscription_service::ReconfigSubscription;
 use tokio::runtime::Handle;
-use vm_validator::vm_validator::TransactionValidation;
+use vm_validator::{account_state_cache::AccountStateCache, vm_validator::TransactionValidation};
 
 /// Struct that owns all dependencies required by shared mempool routines
 #[derive(Clone)]
@@ -47,9 +48,15 @@ where
     pub config: MempoolConfig,
     pub network_senders: HashMap<NetworkId, MempoolNetworkSender>,
     pub db: Arc<dyn DbReader>,
+    /// Account states at the latest committed version, shared with the validator
+    pub account_cache: Arc<AccountStateCache>,
+    /// Rejects the transactions that obviously can't be executed before they are validated
+    pub admission_control: AdmissionControl,
     pub validator: Arc<RwLock<V>>,
     pub peer_manager: Arc<PeerManager>,
     pub subscribers: Vec<UnboundedSender<SharedMempoolNotification>>,
+    /// Set when the node drains before a shutdown, new transactions are rejected from then on
+    pub draining: Arc<AtomicBool>,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -140,6 +147,13 @@ pub enum ConsensusRequest {
         // callback to send response back to sender
         oneshot::Sender<Result<ConsensusResponse>>,
     ),
+    /// request to look up transactions of a proposal by account address + sequence number
+    GetTransactionsRequest(
+        // sender and sequence number of each requested transaction
+        Vec<(AccountAddress, u64)>,
+        // callback to send response back to sender
+        oneshot::Sender<Result<ConsensusResponse>>,
+    ),
 }
 
 /// Response setn from mempool to consensus
@@ -151,6 +165,8 @@ pub enum ConsensusResponse {
     ),
     /// ACK for commit notification
     CommitResponse(),
+    /// transactions found in mempool, in the order they were requested
+    GetTransactionsResponse(Vec<Option<SignedTransaction>>),
 }
 
 /// notification from state sync to mempool of commit event
@@ -160,6 +176,8 @@ pub struct CommitNotification {
     pub transactions: Vec<CommittedTransaction>,
     /// timestamp of committed block
     pub block_timestamp_usecs: u64,
+    /// whether the committed transactions published modules, which the VM validator must reload
+    pub publishes_modules: bool,
     /// callback to send back response from mempool to State Sync
     pub callback: oneshot::Sender<Result<CommitResponse>>,
 }
@@ -21,11 +21,13 @@ use network::{
 };
 use std::{
     num::NonZeroUsize,
-    sync::{Arc, Mutex, RwLock},
+    sync::{atomic::AtomicBool, Arc, Mutex, RwLock},
 };
 use storage_interface::mock::MockDbReader;
 use tokio::runtime::{Builder, Runtime};
-use vm_validator::mocks::mock_vm_validator::MockVMValidator;
+use vm_validator::{
+    account_state_cache::AccountStateCache, mocks::mock_vm_validator::MockVMValidator,
+};
 
 /// Mock of a running instance of shared mempool
 pub struct MockSharedMempool {
@@ -38,6 +40,8 @@ pub struct MockSharedMempool {
     pub consensus_sender: mpsc::Sender<ConsensusRequest>,
     /// sender from state sync to shared mempool
     pub state_sync_sender: Option<mpsc::Sender<CommitNotification>>,
+    /// flag to make shared mempool reject new transactions
+    pub draining: Arc<AtomicBool>,
 }
 
 impl MockSharedMempool {
@@ -80,6 +84,7 @@ impl MockSharedMempool {
         let (_reconfig_event_publisher, reconfig_event_subscriber) =
             libra_channel::new(QueueStyle::LIFO, NonZeroUsize::new(1).unwrap(), None);
         let network_handles = vec![(NetworkId::Validator, network_sender, network_events)];
+        let draining = Arc::new(AtomicBool::new(false));
 
         start_shared_mempool(
             runtime.handle(),
@@ -90,7 +95,10 @@ impl MockSharedMempool {
             consensus_events,
             state_sync_events,
             reconfig_event_subscriber,
+            mpsc::unbounded().1,
+            Arc::clone(&draining),
             Arc::new(MockDbReader),
+            Arc::new(AccountStateCache::new()),
             Arc::new(RwLock::new(MockVMValidator)),
             vec![],
         );
@@ -101,6 +109,7 @@ impl MockSharedMempool {
             mempool,
             consensus_sender,
             state_sync_sender,
+            draining,
         }
     }
 
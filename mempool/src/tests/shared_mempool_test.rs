@@ -25,7 +25,7 @@ use libra_config::{
     network_id::{NetworkContext, NetworkId},
 };
 use libra_network_address::NetworkAddress;
-use libra_types::{transaction::SignedTransaction, PeerId};
+use libra_types::{mempool_status::MempoolStatusCode, transaction::SignedTransaction, PeerId};
 use netcore::transport::ConnectionOrigin;
 use network::{
     peer_manager::{
@@ -38,12 +38,17 @@ use network::{
 use std::{
     collections::{HashMap, HashSet},
     num::NonZeroUsize,
-    sync::{Arc, Mutex, RwLock},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, RwLock,
+    },
     time::Duration,
 };
 use storage_interface::mock::MockDbReader;
 use tokio::runtime::{Builder, Runtime};
-use vm_validator::mocks::mock_vm_validator::MockVMValidator;
+use vm_validator::{
+    account_state_cache::AccountStateCache, mocks::mock_vm_validator::MockVMValidator,
+};
 
 #[derive(Default)]
 struct SharedMempoolNetwork {
@@ -102,7 +107,10 @@ fn init_single_shared_mempool(
         consensus_events,
         state_sync_events,
         reconfig_events_receiver,
+        mpsc::unbounded().1,
+        Arc::new(AtomicBool::new(false)),
         Arc::new(MockDbReader),
+        Arc::new(AccountStateCache::new()),
         Arc::new(RwLock::new(MockVMValidator)),
         vec![sender],
     );
@@ -168,7 +176,10 @@ fn init_smp_multiple_networks(
         consensus_events,
         state_sync_events,
         reconfig_events_receiver,
+        mpsc::unbounded().1,
+        Arc::new(AtomicBool::new(false)),
         Arc::new(MockDbReader),
+        Arc::new(AccountStateCache::new()),
         Arc::new(RwLock::new(MockVMValidator)),
         vec![sender],
     );
@@ -653,6 +664,22 @@ fn test_consensus_events_rejected_txns() {
     assert_eq!(timeline.get(0).unwrap().1, kept_txn);
 }
 
+#[test]
+fn test_draining_rejects_client_transactions() {
+    let smp = MockSharedMempool::new(None);
+    smp.draining.store(true, Ordering::Relaxed);
+
+    let txn = TestTransaction::new(0, 0, 1).make_signed_transaction();
+    let (callback, callback_rcv) = oneshot::channel();
+    let mut ac_client = smp.ac_client.clone();
+    let (status, _) = block_on(async {
+        assert!(ac_client.send((txn, callback)).await.is_ok());
+        callback_rcv.await.unwrap().unwrap()
+    });
+    assert_eq!(status.code, MempoolStatusCode::Draining);
+    assert!(smp.read_timeline(0, 10).is_empty());
+}
+
 #[test]
 fn test_state_sync_events_committed_txns() {
     let (mut state_sync_sender, state_sync_events) = mpsc::channel(1_024);
@@ -690,6 +717,7 @@ fn test_state_sync_events_committed_txns() {
     let req = CommitNotification {
         transactions: committed_txns,
         block_timestamp_usecs: 1,
+        publishes_modules: false,
         callback,
     };
     block_on(async {
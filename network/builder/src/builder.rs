@@ -11,7 +11,9 @@
 //! long as the latter is in its trusted peers set.
 use channel::{self, message_queues::QueueStyle};
 use libra_config::{
-    config::{DiscoveryMethod, NetworkConfig, RoleType, HANDSHAKE_VERSION},
+    config::{
+        DiscoveryMethod, Identity, NetworkConfig, NoiseRekeyConfig, RoleType, HANDSHAKE_VERSION,
+    },
     network_id::{NetworkContext, NetworkId},
 };
 use libra_crypto::x25519;
@@ -38,6 +40,7 @@ use network::{
 };
 use network_simple_onchain_discovery::{
     builder::ConfigurationChangeListenerBuilder, gen_simple_discovery_reconfig_subscription,
+    IdentityRoller,
 };
 use std::{
     clone::Clone,
@@ -61,6 +64,7 @@ pub struct NetworkBuilder {
     trusted_peers: Arc<RwLock<HashMap<PeerId, HashSet<x25519::PublicKey>>>>,
     seed_addrs: HashMap<PeerId, Vec<NetworkAddress>>,
     seed_pubkeys: HashMap<PeerId, HashSet<x25519::PublicKey>>,
+    denied_peers: HashSet<PeerId>,
     channel_size: usize,
     connectivity_check_interval_ms: u64,
     max_connection_delay_ms: u64,
@@ -115,6 +119,7 @@ impl NetworkBuilder {
             trusted_peers,
             seed_addrs: HashMap::new(),
             seed_pubkeys: HashMap::new(),
+            denied_peers: HashSet::new(),
             channel_size: constants::NETWORK_CHANNEL_SIZE,
             connectivity_check_interval_ms: constants::CONNECTIVITY_CHECK_INTERNAL_MS,
             max_connection_delay_ms: constants::MAX_CONNECTION_DELAY_MS,
@@ -163,7 +168,9 @@ impl NetworkBuilder {
         network_builder
             .seed_addrs(config.seed_addrs.clone())
             .seed_pubkeys(config.seed_pubkeys.clone())
+            .denied_peers(config.denied_peers.clone())
             .connectivity_check_interval_ms(config.connectivity_check_interval_ms)
+            .noise_rekey(config.noise_rekey)
             .add_connection_monitoring(
                 // TODO: Move these values into NetworkConfig
                 constants::PING_INTERVAL_MS,
@@ -171,10 +178,13 @@ impl NetworkBuilder {
                 constants::PING_FAILURES_TOLERATED,
             );
 
-        // Sanity check seed addresses.
+        // Sanity check seed addresses and public keys.
         config
             .verify_seed_addrs()
             .expect("Seed addresses must be well-formed");
+        config
+            .verify_seed_pubkeys()
+            .expect("Seed public keys must be valid");
 
         // Don't turn on connectivity manager if we're a public-facing server,
         // for example.
@@ -194,6 +204,17 @@ impl NetworkBuilder {
             network_builder.add_connectivity_manager();
         }
 
+        // Keys in secure storage may be rotated (e.g., by the key manager), follow them on-chain.
+        let identity_roller = match &config.identity {
+            Identity::FromStorage(identity) => Some(IdentityRoller::new(
+                role,
+                peer_id,
+                identity.clone(),
+                network_builder.peer_manager_builder.noise_identity(),
+            )),
+            _ => None,
+        };
+
         match &config.discovery_method {
             DiscoveryMethod::Gossip(gossip_config) => {
                 network_builder.add_gossip_discovery(
@@ -203,11 +224,11 @@ impl NetworkBuilder {
                 );
                 // HACK: gossip relies on on-chain discovery for the eligible peers update.
                 if role == RoleType::Validator {
-                    network_builder.add_configuration_change_listener(role);
+                    network_builder.add_configuration_change_listener(role, identity_roller);
                 }
             }
             DiscoveryMethod::Onchain => {
-                network_builder.add_configuration_change_listener(role);
+                network_builder.add_configuration_change_listener(role, identity_roller);
             }
             DiscoveryMethod::None => {}
         }
@@ -242,6 +263,12 @@ impl NetworkBuilder {
         self
     }
 
+    /// Set peers that are never eligible to connect with
+    pub fn denied_peers(&mut self, denied_peers: HashSet<PeerId>) -> &mut Self {
+        self.denied_peers = denied_peers;
+        self
+    }
+
     /// Set connectivity check ticker interval
     pub fn connectivity_check_interval_ms(
         &mut self,
@@ -251,6 +278,12 @@ impl NetworkBuilder {
         self
     }
 
+    /// Set how the noise streams of this network are rekeyed.
+    pub fn noise_rekey(&mut self, noise_rekey: NoiseRekeyConfig) -> &mut Self {
+        self.peer_manager_builder.noise_rekey(noise_rekey);
+        self
+    }
+
     pub fn conn_mgr_reqs_tx(&self) -> Option<channel::Sender<ConnectivityRequest>> {
         match self.connectivity_manager_builder.as_ref() {
             Some(conn_mgr_builder) => Some(conn_mgr_builder.conn_mgr_reqs_tx()),
@@ -312,6 +345,7 @@ impl NetworkBuilder {
             trusted_peers,
             seed_addrs,
             seed_pubkeys,
+            self.denied_peers.clone(),
             connectivity_check_interval_ms,
             // TODO:  move this into a config
             2, // Legacy hardcoded value,
@@ -339,7 +373,11 @@ impl NetworkBuilder {
         self
     }
 
-    fn add_configuration_change_listener(&mut self, role: RoleType) -> &mut Self {
+    fn add_configuration_change_listener(
+        &mut self,
+        role: RoleType,
+        identity_roller: Option<IdentityRoller>,
+    ) -> &mut Self {
         let conn_mgr_reqs_tx = self
             .conn_mgr_reqs_tx()
             .expect("ConnectivityManager must be installed for validator");
@@ -361,6 +399,7 @@ impl NetworkBuilder {
                 shared_val_netaddr_key_map,
                 conn_mgr_reqs_tx,
                 simple_discovery_reconfig_rx,
+                identity_roller,
             ));
         self.build_configuration_change_listener()
             .start_configuration_change_listener()
@@ -1,7 +1,7 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::ConfigurationChangeListener;
+use crate::{ConfigurationChangeListener, IdentityRoller};
 use channel::libra_channel;
 use libra_config::config::RoleType;
 use libra_network_address::encrypted::{Key, KeyVersion};
@@ -15,6 +15,7 @@ struct ConfigurationChangeListenerConfig {
     shared_val_netaddr_key_map: HashMap<KeyVersion, Key>,
     conn_mgr_reqs_tx: channel::Sender<ConnectivityRequest>,
     reconfig_events: libra_channel::Receiver<(), OnChainConfigPayload>,
+    identity_roller: Option<IdentityRoller>,
 }
 
 impl ConfigurationChangeListenerConfig {
@@ -23,12 +24,14 @@ impl ConfigurationChangeListenerConfig {
         shared_val_netaddr_key_map: HashMap<KeyVersion, Key>,
         conn_mgr_reqs_tx: channel::Sender<ConnectivityRequest>,
         reconfig_events: libra_channel::Receiver<(), OnChainConfigPayload>,
+        identity_roller: Option<IdentityRoller>,
     ) -> Self {
         Self {
             role,
             shared_val_netaddr_key_map,
             conn_mgr_reqs_tx,
             reconfig_events,
+            identity_roller,
         }
     }
 }
@@ -52,6 +55,7 @@ impl ConfigurationChangeListenerBuilder {
         shared_val_netaddr_key_map: HashMap<KeyVersion, Key>,
         conn_mgr_reqs_tx: channel::Sender<ConnectivityRequest>,
         reconfig_events: libra_channel::Receiver<(), OnChainConfigPayload>,
+        identity_roller: Option<IdentityRoller>,
     ) -> ConfigurationChangeListenerBuilder {
         Self {
             config: Some(ConfigurationChangeListenerConfig::new(
@@ -59,6 +63,7 @@ impl ConfigurationChangeListenerBuilder {
                 shared_val_netaddr_key_map,
                 conn_mgr_reqs_tx,
                 reconfig_events,
+                identity_roller,
             )),
             listener: None,
             state: State::CREATED,
@@ -74,6 +79,7 @@ impl ConfigurationChangeListenerBuilder {
             config.shared_val_netaddr_key_map,
             config.conn_mgr_reqs_tx,
             config.reconfig_events,
+            config.identity_roller,
         ));
         self
     }
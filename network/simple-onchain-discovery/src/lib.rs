@@ -4,7 +4,7 @@
 use anyhow::{format_err, Context, Result};
 use channel::libra_channel::{self, Receiver};
 use futures::{sink::SinkExt, StreamExt};
-use libra_config::config::RoleType;
+use libra_config::config::{IdentityFromStorage, RoleType};
 use libra_crypto::x25519;
 use libra_logger::prelude::*;
 use libra_metrics::{register_histogram, DurationHistogram};
@@ -12,9 +12,15 @@ use libra_network_address::{
     encrypted::{EncNetworkAddress, Key, KeyVersion, RawEncNetworkAddress},
     NetworkAddress, RawNetworkAddress,
 };
-use libra_types::on_chain_config::{OnChainConfigPayload, ValidatorSet, ON_CHAIN_CONFIG_REGISTRY};
+use libra_types::{
+    on_chain_config::{OnChainConfigPayload, ValidatorSet},
+    PeerId,
+};
 use move_core_types::account_address::AccountAddress;
-use network::connectivity_manager::{ConnectivityRequest, DiscoverySource};
+use network::{
+    connectivity_manager::{ConnectivityRequest, DiscoverySource},
+    noise::NoiseIdentity,
+};
 use once_cell::sync::Lazy;
 use std::{
     collections::{HashMap, HashSet},
@@ -54,11 +60,80 @@ pub struct ConfigurationChangeListener {
     shared_val_netaddr_key_map: HashMap<KeyVersion, Key>,
     conn_mgr_reqs_tx: channel::Sender<ConnectivityRequest>,
     reconfig_events: libra_channel::Receiver<(), OnChainConfigPayload>,
+    identity_roller: Option<IdentityRoller>,
+}
+
+/// Keeps the local network identity key in sync with the key registered on-chain, so that a key
+/// rotated in secure storage (e.g., by the key manager) is rolled into the network stack once the
+/// rotation is reconfigured in, without restarting the node.
+pub struct IdentityRoller {
+    role: RoleType,
+    peer_id: PeerId,
+    identity: IdentityFromStorage,
+    noise_identity: NoiseIdentity,
+}
+
+impl IdentityRoller {
+    pub fn new(
+        role: RoleType,
+        peer_id: PeerId,
+        identity: IdentityFromStorage,
+        noise_identity: NoiseIdentity,
+    ) -> Self {
+        Self {
+            role,
+            peer_id,
+            identity,
+            noise_identity,
+        }
+    }
+
+    /// Rolls the noise identity to the key in storage if our own on-chain identity public key
+    /// changed and the key in storage matches it.
+    fn roll(&self, node_set: &ValidatorSet) {
+        let onchain_pubkey = match node_set
+            .payload()
+            .iter()
+            .find(|info| info.account_address() == &self.peer_id)
+        {
+            Some(info) => match self.role {
+                RoleType::Validator => info.config().validator_network_identity_public_key,
+                RoleType::FullNode => info.config().full_node_network_identity_public_key,
+            },
+            None => return,
+        };
+        if onchain_pubkey == self.noise_identity.public_key() {
+            return;
+        }
+
+        let key = match self.identity.identity_key() {
+            Ok(key) => key,
+            Err(e) => {
+                error!(
+                    "Unable to load the {} network identity key: {}",
+                    self.role, e
+                );
+                return;
+            }
+        };
+        if key.public_key() == onchain_pubkey {
+            self.noise_identity.rotate(key);
+            info!(
+                "Rolled the {} network identity key to {}",
+                self.role, onchain_pubkey
+            );
+        } else {
+            warn!(
+                "The on-chain {} network identity key {} does not match the key in storage",
+                self.role, onchain_pubkey
+            );
+        }
+    }
 }
 
 pub fn gen_simple_discovery_reconfig_subscription(
 ) -> (ReconfigSubscription, Receiver<(), OnChainConfigPayload>) {
-    ReconfigSubscription::subscribe_all(ON_CHAIN_CONFIG_REGISTRY.to_vec(), vec![])
+    ReconfigSubscription::subscribe_to_registry(vec![])
 }
 
 fn decrypt_validator_netaddr(
@@ -94,13 +169,23 @@ fn extract_updates(
     let explicit_pubkeys: HashMap<_, _> = node_set
         .payload()
         .iter()
-        .map(|info| {
+        .filter_map(|info| {
             let peer_id = *info.account_address();
             let pubkey = match role {
                 RoleType::Validator => info.config().validator_network_identity_public_key,
                 RoleType::FullNode => info.config().full_node_network_identity_public_key,
             };
-            (peer_id, pubkey)
+            // ignore identity keys that are unsafe to perform a handshake with; just log the error.
+            match pubkey.validate() {
+                Ok(()) => Some((peer_id, pubkey)),
+                Err(err) => {
+                    warn!(
+                        "Invalid network identity public key: role: {}, peer: {}, err: {:?}",
+                        role, peer_id, err
+                    );
+                    None
+                }
+            }
         })
         .collect();
 
@@ -132,11 +217,19 @@ fn extract_updates(
                     .with_context(|| {
                         format_err!("error deserializing network address: {:?}", raw_addr)
                     })?;
+                if let Some(pubkey) = addr.find_noise_proto() {
+                    pubkey
+                        .validate()
+                        .map_err(anyhow::Error::new)
+                        .with_context(|| {
+                            format_err!("invalid network identity public key: {}", addr)
+                        })?;
+                }
                 Ok(addr)
             });
 
-            // ignore network addresses that fail to decrypt or deserialize; just
-            // log the error.
+            // ignore network addresses that fail to decrypt, deserialize or contain an
+            // invalid identity public key; just log the error.
             let addrs = match addr_res {
                 Ok(addr) => vec![addr],
                 Err(err) => {
@@ -179,22 +272,29 @@ impl ConfigurationChangeListener {
         shared_val_netaddr_key_map: HashMap<KeyVersion, Key>,
         conn_mgr_reqs_tx: channel::Sender<ConnectivityRequest>,
         reconfig_events: libra_channel::Receiver<(), OnChainConfigPayload>,
+        identity_roller: Option<IdentityRoller>,
     ) -> Self {
         Self {
             role,
             shared_val_netaddr_key_map,
             conn_mgr_reqs_tx,
             reconfig_events,
+            identity_roller,
         }
     }
 
     /// Processes a received OnChainConfigPayload.  Depending on role (Validator or FullNode), parses
-    /// the appropriate configuration changes and passes it to the ConnectionManager channel.
+    /// the appropriate configuration changes and passes it to the ConnectionManager channel. Our
+    /// own identity key is rolled first if it was rotated on-chain.
     async fn process_payload(&mut self, payload: OnChainConfigPayload) {
         let node_set: ValidatorSet = payload
             .get()
             .expect("failed to get ValidatorSet from payload");
 
+        if let Some(identity_roller) = self.identity_roller.as_ref() {
+            identity_roller.roll(&node_set);
+        }
+
         let updates = extract_updates(self.role, &self.shared_val_netaddr_key_map, node_set);
 
         info!(
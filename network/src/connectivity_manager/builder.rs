@@ -31,6 +31,7 @@ struct ConnectivityManagerBuilderConfig {
     eligible: Arc<RwLock<HashMap<PeerId, HashSet<x25519::PublicKey>>>>,
     seed_addrs: HashMap<PeerId, Vec<NetworkAddress>>,
     seed_pubkeys: HashMap<PeerId, HashSet<x25519::PublicKey>>,
+    denied_peers: HashSet<PeerId>,
     connectivity_check_interval_ms: u64,
     backoff_base: u64,
     max_connection_delay_ms: u64,
@@ -60,6 +61,7 @@ impl ConnectivityManagerBuilder {
         eligible: Arc<RwLock<HashMap<PeerId, HashSet<x25519::PublicKey>>>>,
         seed_addrs: HashMap<PeerId, Vec<NetworkAddress>>,
         seed_pubkeys: HashMap<PeerId, HashSet<x25519::PublicKey>>,
+        denied_peers: HashSet<PeerId>,
         connectivity_check_interval_ms: u64,
         backoff_base: u64,
         max_connection_delay_ms: u64,
@@ -78,6 +80,7 @@ impl ConnectivityManagerBuilder {
                 eligible,
                 seed_addrs,
                 seed_pubkeys,
+                denied_peers,
                 connectivity_check_interval_ms,
                 backoff_base,
                 max_connection_delay_ms,
@@ -111,6 +114,7 @@ impl ConnectivityManagerBuilder {
                     config.eligible,
                     config.seed_addrs,
                     config.seed_pubkeys,
+                    config.denied_peers,
                     interval(Duration::from_millis(config.connectivity_check_interval_ms)).fuse(),
                     config.connection_reqs_tx,
                     config.connection_notifs_rx,
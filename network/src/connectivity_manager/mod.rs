@@ -71,6 +71,8 @@ pub struct ConnectivityManager<TTicker, TBackoff> {
     peer_addrs: PeerAddresses,
     /// Public key sets of peers received from discovery sources.
     peer_pubkeys: PeerPublicKeys,
+    /// Peers which are never eligible, whatever the discovery sources say.
+    denied_peers: HashSet<PeerId>,
     /// Ticker to trigger connectivity checks to provide the guarantees stated above.
     ticker: TTicker,
     /// Channel to send connection requests to PeerManager.
@@ -171,6 +173,7 @@ where
         eligible: Arc<RwLock<HashMap<PeerId, HashSet<x25519::PublicKey>>>>,
         seed_addrs: HashMap<PeerId, Vec<NetworkAddress>>,
         seed_pubkeys: HashMap<PeerId, HashSet<x25519::PublicKey>>,
+        denied_peers: HashSet<PeerId>,
         ticker: TTicker,
         connection_reqs_tx: ConnectionRequestSender,
         connection_notifs_rx: conn_notifs_channel::Receiver,
@@ -193,6 +196,7 @@ where
             connected: HashMap::new(),
             peer_addrs: PeerAddresses::new(),
             peer_pubkeys: PeerPublicKeys::new(),
+            denied_peers,
             ticker,
             connection_reqs_tx,
             connection_notifs_rx,
@@ -541,7 +545,7 @@ where
 
         // 2. add or update pubkeys in intersection
         for (peer_id, new_pubkeys) in new_peer_pubkeys {
-            if peer_id == self_peer_id {
+            if peer_id == self_peer_id || self.denied_peers.contains(&peer_id) {
                 continue;
             }
 
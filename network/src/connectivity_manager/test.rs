@@ -69,6 +69,7 @@ fn setup_conn_mgr_with_context(
             Arc::new(RwLock::new(HashMap::new())),
             seed_addrs,
             seed_pubkeys,
+            HashSet::new(),
             ticker_rx,
             ConnectionRequestSender::new(connection_reqs_tx),
             connection_notifs_rx,
@@ -1279,6 +1280,7 @@ fn basic_update_eligible_peers() {
         trusted_peers.clone(),
         seed_addrs,
         seed_pubkeys,
+        HashSet::new(),
         ticker_rx,
         ConnectionRequestSender::new(connection_reqs_tx),
         connection_notifs_rx,
@@ -1345,3 +1347,66 @@ fn basic_update_eligible_peers() {
     conn_mgr.handle_update_eligible_peers(DiscoverySource::Gossip, pubkeys_map_empty.clone());
     assert_eq!(&*trusted_peers.read().unwrap(), &pubkeys_map_empty);
 }
+
+#[test]
+fn denied_peers_not_eligible() {
+    ::libra_logger::Logger::new().environment_only(true).init();
+    let mut rt = Runtime::new().unwrap();
+
+    let network_context = Arc::new(NetworkContext::new(
+        NetworkId::Validator,
+        RoleType::Validator,
+        PeerId::random(),
+    ));
+    let (connection_reqs_tx, _connection_reqs_rx) =
+        libra_channel::new(QueueStyle::FIFO, NonZeroUsize::new(1).unwrap(), None);
+    let (_connection_notifs_tx, connection_notifs_rx) = conn_notifs_channel::new();
+    let (_conn_mgr_reqs_tx, conn_mgr_reqs_rx) = channel::new_test(0);
+    let (_ticker_tx, ticker_rx) = channel::new_test::<()>(0);
+    let trusted_peers = Arc::new(RwLock::new(HashMap::new()));
+
+    let (allowed_peer_id, _, allowed_pubkeys, allowed_addr) = gen_peer();
+    let (denied_peer_id, _, denied_pubkeys, denied_addr) = gen_peer();
+    let seed_addrs: HashMap<_, _> = vec![
+        (allowed_peer_id, vec![allowed_addr]),
+        (denied_peer_id, vec![denied_addr]),
+    ]
+    .into_iter()
+    .collect();
+    let denied_peers: HashSet<_> = vec![denied_peer_id].into_iter().collect();
+
+    let mut conn_mgr = rt.enter(|| {
+        ConnectivityManager::new(
+            network_context,
+            trusted_peers.clone(),
+            seed_addrs,
+            HashMap::new(),
+            denied_peers,
+            ticker_rx,
+            ConnectionRequestSender::new(connection_reqs_tx),
+            connection_notifs_rx,
+            conn_mgr_reqs_rx,
+            FixedInterval::from_millis(100),
+            300,  /* ms */
+            None, /* connection limit */
+        )
+    });
+
+    // Denied peers are dropped from the seeds as well as from later discovery updates
+    let update: HashMap<_, _> = vec![
+        (allowed_peer_id, allowed_pubkeys.clone()),
+        (denied_peer_id, denied_pubkeys),
+    ]
+    .into_iter()
+    .collect();
+    conn_mgr.handle_update_eligible_peers(DiscoverySource::OnChain, update);
+    let expected: HashMap<_, _> = vec![(allowed_peer_id, allowed_pubkeys)]
+        .into_iter()
+        .collect();
+    assert_eq!(&*trusted_peers.read().unwrap(), &expected);
+
+    // Only the allowed peer is dialed
+    rt.block_on(conn_mgr.dial_eligible_peers(&mut FuturesUnordered::new()));
+    assert!(conn_mgr.dial_queue.contains_key(&allowed_peer_id));
+    assert!(!conn_mgr.dial_queue.contains_key(&denied_peer_id));
+}
@@ -155,9 +155,35 @@ pub static PENDING_CONNECTION_HANDLER_NOTIFICATIONS: Lazy<IntGauge> =
 pub static PENDING_PEER_MANAGER_DIAL_REQUESTS: Lazy<IntGauge> =
     Lazy::new(|| OP_COUNTERS.gauge("pending_peer_manager_dial_requests"));
 
-/// Counter of messages pending in queue to be sent out on the wire.
-pub static PENDING_WIRE_MESSAGES: Lazy<IntGauge> =
-    Lazy::new(|| OP_COUNTERS.gauge("pending_wire_messages"));
+/// Gauge of pending outbound messages in the Peer writer queues, by priority
+pub static LIBRA_NETWORK_PENDING_WIRE_MESSAGES: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "libra_network_pending_wire_messages",
+        "Libra network pending outbound wire messages gauge",
+        &["priority"]
+    )
+    .unwrap()
+});
+
+/// Counter of outbound messages dropped because their Peer writer queue was full
+pub static LIBRA_NETWORK_OUTBOUND_DROPPED_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "libra_network_outbound_dropped_messages",
+        "Libra network outbound messages dropped on a full writer queue",
+        &["priority"]
+    )
+    .unwrap()
+});
+
+/// Histogram of time outbound messages spend in the Peer writer queues
+pub static LIBRA_NETWORK_OUTBOUND_QUEUE_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "libra_network_outbound_queue_latency_seconds",
+        "Libra network outbound writer queue latency histogram",
+        &["priority"]
+    )
+    .unwrap()
+});
 
 /// Counter of pending requests in Direct Send
 pub static PENDING_DIRECT_SEND_REQUESTS: &str = "pending_direct_send_requests";
@@ -12,6 +12,7 @@
 
 use crate::noise::stream::NoiseStream;
 use futures::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use libra_config::config::NoiseRekeyConfig;
 use libra_crypto::{noise, x25519};
 use libra_types::PeerId;
 use netcore::transport::ConnectionOrigin;
@@ -133,26 +134,80 @@ impl HandshakeAuthMode {
 //   in order to pass them to the noise implementaiton
 //
 
+/// A shared handle on the static key used by a `NoiseUpgrader`.
+///
+/// Rotating the key through any clone of the handle takes effect for all handshakes started
+/// afterwards. Handshakes already in flight, as well as established noise streams, keep using
+/// the key they started with.
+#[derive(Clone)]
+pub struct NoiseIdentity(Arc<RwLock<Arc<noise::NoiseConfig>>>);
+
+impl NoiseIdentity {
+    pub fn new(key: x25519::PrivateKey) -> Self {
+        Self(Arc::new(RwLock::new(Arc::new(noise::NoiseConfig::new(
+            key,
+        )))))
+    }
+
+    /// Returns the public key currently used for new handshakes.
+    pub fn public_key(&self) -> x25519::PublicKey {
+        self.current().public_key()
+    }
+
+    /// Replaces the static key used for new handshakes.
+    pub fn rotate(&self, key: x25519::PrivateKey) {
+        *self.0.write().unwrap() = Arc::new(noise::NoiseConfig::new(key));
+    }
+
+    fn current(&self) -> Arc<noise::NoiseConfig> {
+        self.0.read().unwrap().clone()
+    }
+}
+
+impl From<x25519::PrivateKey> for NoiseIdentity {
+    fn from(key: x25519::PrivateKey) -> Self {
+        Self::new(key)
+    }
+}
+
 /// The Noise configuration to be used to perform a protocol upgrade on an underlying socket.
 pub struct NoiseUpgrader {
     /// The validator's own peer id.
     self_peer_id: PeerId,
     /// Config for executing Noise handshakes. Includes our static private key.
-    noise_config: noise::NoiseConfig,
+    noise_identity: NoiseIdentity,
     /// Handshake authentication can be either mutual or server-only authentication.
     auth_mode: HandshakeAuthMode,
+    /// Rekeying of the established noise streams.
+    rekey_config: NoiseRekeyConfig,
 }
 
 impl NoiseUpgrader {
     /// Create a new NoiseConfig with the provided keypair and authentication mode.
-    pub fn new(peer_id: PeerId, key: x25519::PrivateKey, auth_mode: HandshakeAuthMode) -> Self {
+    pub fn new(
+        peer_id: PeerId,
+        key: impl Into<NoiseIdentity>,
+        auth_mode: HandshakeAuthMode,
+    ) -> Self {
         Self {
             self_peer_id: peer_id,
-            noise_config: noise::NoiseConfig::new(key),
+            noise_identity: key.into(),
             auth_mode,
+            rekey_config: NoiseRekeyConfig::default(),
         }
     }
 
+    /// Set how the noise streams established by this upgrader are rekeyed.
+    pub fn with_rekey_config(mut self, rekey_config: NoiseRekeyConfig) -> Self {
+        self.rekey_config = rekey_config;
+        self
+    }
+
+    /// Returns a handle on the static key of this upgrader, through which it can be rotated.
+    pub fn identity(&self) -> NoiseIdentity {
+        self.noise_identity.clone()
+    }
+
     /// Perform a protocol upgrade on an underlying connection. In addition perform the noise IK
     /// handshake to establish a noise stream and exchange static public keys. Upon success,
     /// returns the static public key of the remote as well as a NoiseStream.
@@ -221,6 +276,9 @@ impl NoiseUpgrader {
         TSocket: AsyncRead + AsyncWrite + Unpin,
         F: Fn() -> [u8; AntiReplayTimestamps::TIMESTAMP_SIZE],
     {
+        // use the same static key for the whole handshake, even if it is rotated meanwhile
+        let noise_config = self.noise_identity.current();
+
         // buffer to hold prologue + first noise handshake message
         let mut client_message = [0; Self::CLIENT_MESSAGE_SIZE];
 
@@ -236,8 +294,7 @@ impl NoiseUpgrader {
 
         // craft first handshake message  (-> e, es, s, ss)
         let mut rng = rand::rngs::OsRng;
-        let initiator_state = self
-            .noise_config
+        let initiator_state = noise_config
             .initiate_connection(
                 &mut rng,
                 &prologue_msg,
@@ -257,13 +314,12 @@ impl NoiseUpgrader {
 
         // parse the server's response
         // TODO: security logging here? (mimoo)
-        let (_, session) = self
-            .noise_config
+        let (_, session) = noise_config
             .finalize_connection(initiator_state, &server_response)
             .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
 
         // finalize the connection
-        Ok(NoiseStream::new(socket, session))
+        Ok(NoiseStream::new(socket, session, self.rekey_config))
     }
 
     /// Perform an inbound protocol upgrade on this connection.
@@ -281,6 +337,9 @@ impl NoiseUpgrader {
     where
         TSocket: AsyncRead + AsyncWrite + Unpin,
     {
+        // use the same static key for the whole handshake, even if it is rotated meanwhile
+        let noise_config = self.noise_identity.current();
+
         // buffer to contain the client first message
         let mut client_message = [0; Self::CLIENT_MESSAGE_SIZE];
 
@@ -318,7 +377,7 @@ impl NoiseUpgrader {
         }
 
         // verify that this is indeed our public key
-        if self_expected_public_key != self.noise_config.public_key().as_slice() {
+        if self_expected_public_key != noise_config.public_key().as_slice() {
             // TODO: security logging (mimoo)
             return Err(io::Error::new(
                 io::ErrorKind::InvalidData,
@@ -331,8 +390,7 @@ impl NoiseUpgrader {
 
         // parse it
         let (prologue, client_init_message) = client_message.split_at(Self::PROLOGUE_SIZE);
-        let (remote_public_key, handshake_state, payload) = self
-            .noise_config
+        let (remote_public_key, handshake_state, payload) = noise_config
             .parse_client_init_message(&prologue, &client_init_message)
             .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
 
@@ -425,8 +483,7 @@ impl NoiseUpgrader {
         // construct the response
         let mut rng = rand::rngs::OsRng;
         let mut server_response = [0u8; Self::SERVER_MESSAGE_SIZE];
-        let session = self
-            .noise_config
+        let session = noise_config
             .respond_to_client(&mut rng, handshake_state, None, &mut server_response)
             .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
 
@@ -434,7 +491,10 @@ impl NoiseUpgrader {
         socket.write_all(&server_response).await?;
 
         // finalize the connection
-        Ok((NoiseStream::new(socket, session), remote_peer_id))
+        Ok((
+            NoiseStream::new(socket, session, self.rekey_config),
+            remote_peer_id,
+        ))
     }
 }
 
@@ -617,6 +677,29 @@ mod test {
         test_handshake_self_fails(true /* is_mutual_auth */);
     }
 
+    #[test]
+    fn test_handshake_after_identity_rotation() {
+        let ((client, _), (server, old_public_key)) = build_peers(false /* is_mutual_auth */);
+
+        // rotate the server's static key
+        let mut rng = ::rand::rngs::StdRng::from_seed([1u8; 32]);
+        let new_private_key = x25519::PrivateKey::generate(&mut rng);
+        let new_public_key = new_private_key.public_key();
+        server.identity().rotate(new_private_key);
+        assert_eq!(server.identity().public_key(), new_public_key);
+
+        // dialing the old key should now fail
+        let (client_res, server_res) = perform_handshake(&client, &server, old_public_key);
+        client_res.unwrap_err();
+        server_res.unwrap_err();
+
+        // dialing the new key should succeed
+        let (client_res, server_res) = perform_handshake(&client, &server, new_public_key);
+        let client_stream = client_res.unwrap();
+        server_res.unwrap();
+        assert_eq!(client_stream.get_remote_static(), new_public_key);
+    }
+
     #[test]
     fn test_handshake_fragmented_reads() {
         // create an in-memory socket for testing
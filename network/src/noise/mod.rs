@@ -89,4 +89,4 @@ pub mod stream;
 #[cfg(any(test, feature = "fuzzing"))]
 pub mod fuzzing;
 
-pub use handshake::{AntiReplayTimestamps, HandshakeAuthMode, NoiseUpgrader};
+pub use handshake::{AntiReplayTimestamps, HandshakeAuthMode, NoiseIdentity, NoiseUpgrader};
@@ -6,6 +6,16 @@
 //! functions in this module enables encrypting and decrypting messages from a socket.
 //! Note that since noise is length-unaware, we have to prefix every noise message with its length
 //!
+//! Long-lived streams can periodically rekey (see [NoiseRekeyConfig]) without interrupting the
+//! connection. This relies on two kinds of frames that streams without rekeying support never
+//! send, and that they handle gracefully:
+//!
+//! - an empty frame (a zero length prefix) announces that the sender understands rekeying.
+//!   Older peers silently skip empty frames.
+//! - an encrypted empty payload signals that the sender rekeys its outbound direction right after
+//!   this frame, using the REKEY() function of the Noise specification. It is only ever sent to a
+//!   peer that announced rekeying support.
+//!
 //! [handshake]: network::noise::handshake
 
 use futures::{
@@ -17,8 +27,10 @@ use std::{
     io,
     pin::Pin,
     task::{Context, Poll},
+    time::{Duration, Instant},
 };
 
+use libra_config::config::NoiseRekeyConfig;
 use libra_crypto::{noise, x25519};
 use libra_logger::prelude::*;
 
@@ -43,17 +55,24 @@ pub struct NoiseStream<TSocket> {
     read_state: ReadState,
     /// an enum used for progressively writing a noise payload
     write_state: WriteState,
+    /// when and how often the outbound direction is rekeyed
+    rekey: RekeyState,
 }
 
 impl<TSocket> NoiseStream<TSocket> {
     /// Create a NoiseStream from a socket and a noise post-handshake session
-    pub fn new(socket: TSocket, session: noise::NoiseSession) -> Self {
+    pub fn new(
+        socket: TSocket,
+        session: noise::NoiseSession,
+        rekey_config: NoiseRekeyConfig,
+    ) -> Self {
         Self {
             socket,
             session,
             buffers: Box::new(NoiseBuffers::new()),
             read_state: ReadState::Init,
             write_state: WriteState::Init,
+            rekey: RekeyState::new(rekey_config),
         }
     }
 
@@ -63,6 +82,71 @@ impl<TSocket> NoiseStream<TSocket> {
     }
 }
 
+//
+// Rekeying
+// --------
+//
+
+/// Keeps track of the rekeying of a [NoiseStream]
+#[derive(Debug)]
+struct RekeyState {
+    /// whether we announce and perform rekeying at all
+    enabled: bool,
+    /// the number of plaintext bytes after which the outbound direction is rekeyed
+    max_bytes: u64,
+    /// the duration after which the outbound direction is rekeyed
+    interval: Duration,
+    /// whether we announced our rekeying support to the remote
+    announced: bool,
+    /// whether the remote announced its rekeying support to us
+    remote_supports_rekey: bool,
+    /// the number of plaintext bytes written with the current outbound key
+    bytes_written: u64,
+    /// when the current outbound key started being used
+    last_rekey: Instant,
+    /// the number of times we rekeyed the outbound direction
+    num_write_rekeys: u64,
+    /// the number of times the remote rekeyed the inbound direction
+    num_read_rekeys: u64,
+}
+
+impl RekeyState {
+    fn new(config: NoiseRekeyConfig) -> Self {
+        Self {
+            enabled: config.enabled,
+            max_bytes: config.max_bytes,
+            interval: Duration::from_millis(config.interval_ms),
+            announced: false,
+            remote_supports_rekey: false,
+            bytes_written: 0,
+            last_rekey: Instant::now(),
+            num_write_rekeys: 0,
+            num_read_rekeys: 0,
+        }
+    }
+
+    /// Returns true if we still need to announce our rekeying support to the remote
+    fn should_announce(&self) -> bool {
+        self.enabled && !self.announced
+    }
+
+    /// Returns true if the outbound direction should be rekeyed before writing more data. A key
+    /// that has not encrypted anything yet is never replaced.
+    fn should_rekey(&self) -> bool {
+        self.enabled
+            && self.remote_supports_rekey
+            && self.bytes_written > 0
+            && (self.bytes_written >= self.max_bytes || self.last_rekey.elapsed() >= self.interval)
+    }
+
+    /// Records that the outbound direction has been rekeyed
+    fn rekeyed_write(&mut self) {
+        self.bytes_written = 0;
+        self.last_rekey = Instant::now();
+        self.num_write_rekeys += 1;
+    }
+}
+
 //
 // Reading a stream
 // ----------------
@@ -110,8 +194,11 @@ where
                         offset
                     )) {
                         Ok(Some(frame_len)) => {
-                            // Empty Frame
+                            // Empty Frame, which announces rekeying support
                             if frame_len == 0 {
+                                if self.rekey.enabled {
+                                    self.rekey.remote_supports_rekey = true;
+                                }
                                 self.read_state = ReadState::Init;
                             } else {
                                 self.read_state = ReadState::ReadFrame {
@@ -145,6 +232,19 @@ where
                             match self.session.read_message_in_place(
                                 &mut self.buffers.read_buffer[..(frame_len as usize)],
                             ) {
+                                // Empty payload, the remote rekeys after this frame
+                                Ok(decrypted) if decrypted.is_empty() && self.rekey.enabled => {
+                                    match self.session.rekey_read() {
+                                        Ok(()) => {
+                                            self.rekey.num_read_rekeys += 1;
+                                            self.read_state = ReadState::Init;
+                                        }
+                                        Err(e) => {
+                                            error!("Decryption Error: {}", e);
+                                            self.read_state = ReadState::DecryptionError(e);
+                                        }
+                                    }
+                                }
                                 Ok(decrypted) => {
                                     self.read_state = ReadState::CopyDecryptedFrame {
                                         decrypted_len: decrypted.len(),
@@ -249,10 +349,47 @@ where
             );
             match self.write_state {
                 WriteState::Init => {
-                    if buf.is_some() {
-                        self.write_state = WriteState::BufferData { offset: 0 };
-                    } else {
+                    if buf.is_none() {
                         return Poll::Ready(Ok(None));
+                    } else if self.rekey.should_announce() {
+                        // announce our rekeying support with an empty frame
+                        self.rekey.announced = true;
+                        self.write_state = WriteState::WriteFrameLen {
+                            frame_len: 0,
+                            buf: [0, 0],
+                            offset: 0,
+                        };
+                    } else if self.rekey.should_rekey() {
+                        // send an encrypted empty payload, then rekey
+                        let result = self.session.write_message_in_place(&mut []).and_then(
+                            |authentication_tag| {
+                                self.session.rekey_write().map(|()| authentication_tag)
+                            },
+                        );
+                        match result {
+                            Ok(authentication_tag) => {
+                                self.buffers.write_buffer[..noise::AES_GCM_TAGLEN]
+                                    .copy_from_slice(&authentication_tag);
+                                self.rekey.rekeyed_write();
+                                let frame_len = noise::AES_GCM_TAGLEN as u16;
+                                self.write_state = WriteState::WriteFrameLen {
+                                    frame_len,
+                                    buf: u16::to_be_bytes(frame_len),
+                                    offset: 0,
+                                };
+                            }
+                            Err(e) => {
+                                error!("Encryption Error: {}", e);
+                                let err = io::Error::new(
+                                    io::ErrorKind::InvalidData,
+                                    format!("EncryptionError: {}", e),
+                                );
+                                self.write_state = WriteState::EncryptionError(e);
+                                return Poll::Ready(Err(err));
+                            }
+                        }
+                    } else {
+                        self.write_state = WriteState::BufferData { offset: 0 };
                     }
                 }
                 WriteState::BufferData { ref mut offset } => {
@@ -274,6 +411,7 @@ where
                             .write_message_in_place(&mut self.buffers.write_buffer[..*offset])
                         {
                             Ok(authentication_tag) => {
+                                self.rekey.bytes_written += *offset as u64;
                                 // append the authentication tag
                                 self.buffers.write_buffer[*offset..*offset + noise::AES_GCM_TAGLEN]
                                     .copy_from_slice(&authentication_tag);
@@ -315,6 +453,10 @@ where
                         buf,
                         offset
                     )) {
+                        // an empty frame has nothing more to write
+                        Ok(()) if frame_len == 0 => {
+                            self.write_state = WriteState::Flush;
+                        }
                         Ok(()) => {
                             self.write_state = WriteState::WriteEncryptedFrame {
                                 frame_len,
@@ -610,6 +752,51 @@ mod test {
         (client_session, server_session)
     }
 
+    /// helper to build a rekeying configuration
+    fn rekey_config(enabled: bool, max_bytes: u64, interval_ms: u64) -> NoiseRekeyConfig {
+        NoiseRekeyConfig {
+            enabled,
+            max_bytes,
+            interval_ms,
+        }
+    }
+
+    /// helper to perform a noise handshake with two peers using the given rekeying configurations
+    fn perform_handshake_with_rekey(
+        client_rekey: NoiseRekeyConfig,
+        server_rekey: NoiseRekeyConfig,
+    ) -> (NoiseStream<MemorySocket>, NoiseStream<MemorySocket>) {
+        let ((client, _client_public), (server, server_public)) = build_peers();
+        perform_handshake(
+            client.with_rekey_config(client_rekey),
+            server_public,
+            server.with_rekey_config(server_rekey),
+        )
+    }
+
+    /// helper to exchange messages back and forth, checking that they are received intact
+    fn exchange_messages(
+        client: &mut NoiseStream<MemorySocket>,
+        server: &mut NoiseStream<MemorySocket>,
+        rounds: u8,
+    ) {
+        for round in 0..rounds {
+            let message = [round; 20];
+
+            block_on(client.write_all(&message)).unwrap();
+            block_on(client.flush()).unwrap();
+            let mut buf = [0; 20];
+            block_on(server.read_exact(&mut buf)).unwrap();
+            assert_eq!(buf, message);
+
+            block_on(server.write_all(&message)).unwrap();
+            block_on(server.flush()).unwrap();
+            let mut buf = [0; 20];
+            block_on(client.read_exact(&mut buf)).unwrap();
+            assert_eq!(buf, message);
+        }
+    }
+
     #[test]
     fn simple_test() -> io::Result<()> {
         // perform handshake with two testing peers
@@ -713,4 +900,53 @@ mod test {
         block_on(client.read_exact(&mut buf)).unwrap();
         assert_eq!(&buf, b"The Doors of Stone");
     }
+
+    #[test]
+    fn rekey_after_max_bytes() {
+        let rekey = rekey_config(true, 16, u64::max_value());
+        let (mut client, mut server) = perform_handshake_with_rekey(rekey, rekey);
+
+        // the first round only announces rekeying support, every following round rekeys
+        exchange_messages(&mut client, &mut server, 10);
+        assert_eq!(client.rekey.num_write_rekeys, 9);
+        assert_eq!(server.rekey.num_read_rekeys, 9);
+        assert_eq!(server.rekey.num_write_rekeys, 9);
+        assert_eq!(client.rekey.num_read_rekeys, 9);
+
+        // large writes still go through after rekeying
+        let buf_send = [1; noise::MAX_SIZE_NOISE_MSG];
+        block_on(client.write_all(&buf_send)).unwrap();
+        block_on(client.flush()).unwrap();
+        let mut buf_receive = [0; noise::MAX_SIZE_NOISE_MSG];
+        block_on(server.read_exact(&mut buf_receive)).unwrap();
+        assert_eq!(&buf_receive[..], &buf_send[..]);
+    }
+
+    #[test]
+    fn rekey_after_interval() {
+        let rekey = rekey_config(true, u64::max_value(), 0);
+        let (mut client, mut server) = perform_handshake_with_rekey(rekey, rekey);
+
+        exchange_messages(&mut client, &mut server, 5);
+        assert_eq!(client.rekey.num_write_rekeys, 4);
+        assert_eq!(server.rekey.num_read_rekeys, 4);
+    }
+
+    #[test]
+    fn rekey_interop_with_non_rekeying_peer() {
+        let enabled = rekey_config(true, 16, 0);
+        let disabled = NoiseRekeyConfig::default();
+
+        for (client_rekey, server_rekey) in &[(enabled, disabled), (disabled, enabled)] {
+            let (mut client, mut server) =
+                perform_handshake_with_rekey(*client_rekey, *server_rekey);
+
+            // neither side rekeys, as one of them never announced rekeying support
+            exchange_messages(&mut client, &mut server, 10);
+            for stream in &[&client, &server] {
+                assert_eq!(stream.rekey.num_write_rekeys, 0);
+                assert_eq!(stream.rekey.num_read_rekeys, 0);
+            }
+        }
+    }
 }
@@ -15,6 +15,7 @@ use bytes::BytesMut;
 use futures::{
     self,
     channel::oneshot,
+    future,
     io::{AsyncRead, AsyncWrite},
     stream::StreamExt,
     FutureExt, SinkExt, TryFutureExt,
@@ -23,7 +24,11 @@ use libra_logger::prelude::*;
 use libra_types::PeerId;
 use netcore::compat::IoCompat;
 use serde::Serialize;
-use std::{fmt::Debug, io, time::Duration};
+use std::{
+    fmt::Debug,
+    io,
+    time::{Duration, Instant},
+};
 use stream_ratelimiter::*;
 use tokio::runtime::Handle;
 use tokio_util::codec::{FramedRead, FramedWrite, LengthDelimitedCodec};
@@ -31,6 +36,8 @@ use tokio_util::codec::{FramedRead, FramedWrite, LengthDelimitedCodec};
 // Rate-limit configuration for inbound messages. Allows 100 messages for every 10ms window.
 pub const MESSAGE_RATE_LIMIT_WINDOW: Duration = Duration::from_millis(10);
 pub const MESSAGE_RATE_LIMIT_COUNT: usize = 100;
+// Capacity of each of the per-priority outbound queues feeding the writer task.
+pub const OUTBOUND_QUEUE_SIZE: usize = 1024;
 
 #[cfg(test)]
 mod test;
@@ -62,6 +69,101 @@ enum State {
     ShuttingDown(DisconnectReason),
 }
 
+/// Priority class of an outbound message. The writer task always drains higher priority queues
+/// first, so consensus traffic never waits behind state sync chunks or mempool batches on a
+/// congested connection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MessagePriority {
+    High,
+    Normal,
+    Low,
+}
+
+impl MessagePriority {
+    pub fn from_protocol(protocol: ProtocolId) -> Self {
+        match protocol {
+            ProtocolId::ConsensusRpc
+            | ProtocolId::ConsensusDirectSend
+            | ProtocolId::HealthCheckerRpc => MessagePriority::High,
+            ProtocolId::DiscoveryDirectSend => MessagePriority::Normal,
+            ProtocolId::MempoolDirectSend | ProtocolId::StateSynchronizerDirectSend => {
+                MessagePriority::Low
+            }
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MessagePriority::High => "high",
+            MessagePriority::Normal => "normal",
+            MessagePriority::Low => "low",
+        }
+    }
+}
+
+type WriteRequest = (
+    NetworkMessage,
+    oneshot::Sender<Result<(), PeerManagerError>>,
+    Instant,
+);
+
+/// Sending half of the writer task's per-priority outbound queues.
+struct OutboundQueues {
+    high: channel::Sender<WriteRequest>,
+    normal: channel::Sender<WriteRequest>,
+    low: channel::Sender<WriteRequest>,
+}
+
+impl OutboundQueues {
+    fn new() -> (
+        Self,
+        channel::Receiver<WriteRequest>,
+        channel::Receiver<WriteRequest>,
+        channel::Receiver<WriteRequest>,
+    ) {
+        let queue = |priority: MessagePriority| {
+            channel::new(
+                OUTBOUND_QUEUE_SIZE,
+                &counters::LIBRA_NETWORK_PENDING_WIRE_MESSAGES
+                    .with_label_values(&[priority.as_str()]),
+            )
+        };
+        let (high, high_rx) = queue(MessagePriority::High);
+        let (normal, normal_rx) = queue(MessagePriority::Normal);
+        let (low, low_rx) = queue(MessagePriority::Low);
+        (Self { high, normal, low }, high_rx, normal_rx, low_rx)
+    }
+
+    /// Queue `message` for the writer task. High priority messages wait for room in their queue,
+    /// applying backpressure to the Peer actor. Lower priority messages are dropped instead when
+    /// their queue is full, and `ack_ch` is told so.
+    async fn push(
+        &mut self,
+        priority: MessagePriority,
+        message: NetworkMessage,
+        ack_ch: oneshot::Sender<Result<(), PeerManagerError>>,
+    ) -> Result<(), PeerManagerError> {
+        let request = (message, ack_ch, Instant::now());
+        let queue = match priority {
+            MessagePriority::High => return Ok(self.high.send(request).await?),
+            MessagePriority::Normal => &mut self.normal,
+            MessagePriority::Low => &mut self.low,
+        };
+        match future::poll_fn(|cx| queue.poll_ready_unpin(cx)).now_or_never() {
+            Some(Ok(())) => Ok(queue.start_send_unpin(request)?),
+            Some(Err(e)) => Err(e.into()),
+            None => {
+                counters::LIBRA_NETWORK_OUTBOUND_DROPPED_MESSAGES
+                    .with_label_values(&[priority.as_str()])
+                    .inc();
+                let (_, ack_ch, _) = request;
+                let _ = ack_ch.send(Err(PeerManagerError::OutboundQueueFull(priority)));
+                Ok(())
+            }
+        }
+    }
+}
+
 pub struct Peer<TSocket> {
     /// A handle to a tokio executor.
     executor: Handle,
@@ -144,9 +246,9 @@ where
         let writer = FramedWrite::new(writer, codec_builder.new_codec());
         // Start writer "process" as a separate task. We receive two handles to communicate with
         // the task:
-        // `write_reqs_tx`: Instruction to send a NetworkMessage on the wire.
+        // `outbound_queues`: Instruction to send a NetworkMessage on the wire at a given priority.
         // `close_tx`: Instruction to close the underlying connection.
-        let (write_reqs_tx, close_tx) =
+        let (mut outbound_queues, close_tx) =
             Self::start_writer_task(&self.executor, self_peer_id, writer);
         // Start main Peer event loop.
         loop {
@@ -155,7 +257,7 @@ where
                     futures::select! {
                         maybe_req = self.requests_rx.next() => {
                             if let Some(request) = maybe_req {
-                                self.handle_request(request, write_reqs_tx.clone()).await;
+                                self.handle_request(request, &mut outbound_queues).await;
                             } else {
                                 // This branch will only be taken if all PeerRequest senders for this Peer
                                 // get dropped.
@@ -165,7 +267,7 @@ where
                         maybe_message = reader.next() => {
                             match maybe_message {
                                 Some(Ok(message)) =>  {
-                                    if let Err(err) = self.handle_inbound_message(message, write_reqs_tx.clone()).await {
+                                    if let Err(err) = self.handle_inbound_message(message, &mut outbound_queues).await {
                                         warn!("Error in handling inbound message from peer: {:?}. Error: {:?}",
                                             self_peer_id.short_str(), err);
                                     }
@@ -216,9 +318,10 @@ where
     }
 
     // Start a new task on the given executor which is responsible for writing outbound messages on
-    // the wire. The function returns two channels which can be used to send intructions to the
+    // the wire. The function returns two handles which can be used to send intructions to the
     // task:
-    // 1. The first channel is used to send outbound NetworkMessages to the task
+    // 1. The first handle is used to queue outbound NetworkMessages for the task at a given
+    //    priority. The task always drains higher priority queues before lower priority ones.
     // 2. The second channel is used to instruct the task to close the connection and terminate.
     // If outbound messages are queued when the task receives a close instruction, it discards
     // them and immediately closes the connection.
@@ -226,46 +329,36 @@ where
         executor: &Handle,
         self_peer_id: PeerId,
         mut writer: FramedWrite<T, LengthDelimitedCodec>,
-    ) -> (
-        channel::Sender<(
-            NetworkMessage,
-            oneshot::Sender<Result<(), PeerManagerError>>,
-        )>,
-        oneshot::Sender<()>,
-    ) {
-        let (write_reqs_tx, mut write_reqs_rx): (
-            channel::Sender<(
-                NetworkMessage,
-                oneshot::Sender<Result<(), PeerManagerError>>,
-            )>,
-            _,
-        ) = channel::new(1024, &counters::PENDING_WIRE_MESSAGES);
+    ) -> (OutboundQueues, oneshot::Sender<()>) {
+        let (outbound_queues, mut high_rx, mut normal_rx, mut low_rx) = OutboundQueues::new();
         let (close_tx, close_rx) = oneshot::channel();
         let writer_task = async move {
             let mut close_rx = close_rx.into_stream();
             loop {
-                futures::select! {
-                    (message, ack_ch) = write_reqs_rx.select_next_some() => {
-                        if let Err(e) = writer
-                            .send(
-                                lcs::to_bytes(&message)
-                                    .expect("Outbound message failed to serialize")
-                                    .into(),
-                            )
-                            .map_ok(|_| ack_ch.send(Ok(())))
-                            .await
-                        {
-                            warn!(
-                                "Error in sending message to peer: {:?}. Error: {:?}",
-                                self_peer_id.short_str(),
-                                e
-                            );
-                            break;
-                        }
-                    },
-                    _ = close_rx.select_next_some() => {
-                        break;
-                    }
+                let (priority, (message, ack_ch, enqueued_at)) = futures::select_biased! {
+                    _ = close_rx.select_next_some() => break,
+                    req = high_rx.select_next_some() => (MessagePriority::High, req),
+                    req = normal_rx.select_next_some() => (MessagePriority::Normal, req),
+                    req = low_rx.select_next_some() => (MessagePriority::Low, req),
+                };
+                counters::LIBRA_NETWORK_OUTBOUND_QUEUE_LATENCY
+                    .with_label_values(&[priority.as_str()])
+                    .observe(enqueued_at.elapsed().as_secs_f64());
+                if let Err(e) = writer
+                    .send(
+                        lcs::to_bytes(&message)
+                            .expect("Outbound message failed to serialize")
+                            .into(),
+                    )
+                    .map_ok(|_| ack_ch.send(Ok(())))
+                    .await
+                {
+                    warn!(
+                        "Error in sending message to peer: {:?}. Error: {:?}",
+                        self_peer_id.short_str(),
+                        e
+                    );
+                    break;
                 }
             }
             info!("Closing connection to peer: {:?}", self_peer_id.short_str());
@@ -300,10 +393,7 @@ where
     async fn handle_inbound_message(
         &mut self,
         message: BytesMut,
-        mut write_reqs_tx: channel::Sender<(
-            NetworkMessage,
-            oneshot::Sender<Result<(), PeerManagerError>>,
-        )>,
+        outbound_queues: &mut OutboundQueues,
     ) -> Result<(), PeerManagerError> {
         trace!("Received message from Peer {}", self.peer_id().short_str(),);
         // Read inbound message from stream.
@@ -336,7 +426,9 @@ where
                 let pong = NetworkMessage::Pong(nonce);
                 let (ack_tx, _) = oneshot::channel();
                 // Resond to a ping right away.
-                write_reqs_tx.send((pong, ack_tx)).await?;
+                outbound_queues
+                    .push(MessagePriority::High, pong, ack_tx)
+                    .await?;
                 Ok(())
             }
             _ => unreachable!("Unhandled"),
@@ -346,10 +438,7 @@ where
     async fn handle_request<'a>(
         &'a mut self,
         request: PeerRequest,
-        mut write_reqs_tx: channel::Sender<(
-            NetworkMessage,
-            oneshot::Sender<Result<(), PeerManagerError>>,
-        )>,
+        outbound_queues: &mut OutboundQueues,
     ) {
         trace!(
             "Peer {} PeerRequest::{:?}",
@@ -358,7 +447,10 @@ where
         );
         match request {
             PeerRequest::SendMessage(message, protocol, channel) => {
-                if let Err(e) = write_reqs_tx.send((message, channel)).await {
+                if let Err(e) = outbound_queues
+                    .push(MessagePriority::from_protocol(protocol), message, channel)
+                    .await
+                {
                     error!(
                         "Failed to send message for protocol {:?} to peer: {:?}. Error: {:?}",
                         protocol,
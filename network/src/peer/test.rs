@@ -3,7 +3,10 @@
 
 use crate::{
     constants,
-    peer::{DisconnectReason, Peer, PeerHandle, PeerNotification},
+    peer::{
+        DisconnectReason, MessagePriority, Peer, PeerHandle, PeerNotification, OUTBOUND_QUEUE_SIZE,
+    },
+    peer_manager::PeerManagerError,
     protocols::wire::{
         handshake::v1::MessagingProtocolVersion,
         messaging::v1::{DirectSendMsg, NetworkMessage},
@@ -11,17 +14,17 @@ use crate::{
     transport::{Connection, ConnectionId, ConnectionMetadata},
     ProtocolId,
 };
-use futures::{future::join, io::AsyncWriteExt, stream::StreamExt, SinkExt};
+use futures::{channel::oneshot, future::join, io::AsyncWriteExt, stream::StreamExt, SinkExt};
 use libra_network_address::NetworkAddress;
 use libra_types::PeerId;
 use memsocket::MemorySocket;
 use netcore::{compat::IoCompat, transport::ConnectionOrigin};
 use std::{mem::ManuallyDrop, str::FromStr, time::Duration};
 use tokio::{
-    runtime::{Handle, Runtime},
+    runtime::{Builder, Handle, Runtime},
     time::timeout,
 };
-use tokio_util::codec::{Framed, LengthDelimitedCodec};
+use tokio_util::codec::{Framed, FramedWrite, LengthDelimitedCodec};
 
 static PROTOCOL: ProtocolId = ProtocolId::MempoolDirectSend;
 
@@ -383,3 +386,99 @@ fn peer_terminates_when_request_tx_has_dropped() {
     };
     rt.block_on(join(peer.start(), drop));
 }
+
+fn direct_send_msg(raw_msg: &str) -> NetworkMessage {
+    NetworkMessage::DirectSendMsg(DirectSendMsg {
+        protocol_id: PROTOCOL,
+        priority: 0,
+        raw_msg: Vec::from(raw_msg),
+    })
+}
+
+#[test]
+fn peer_writer_sends_high_priority_messages_first() {
+    ::libra_logger::Logger::new().environment_only(true).init();
+    // The basic scheduler only runs the writer task once `block_on` yields, so both messages are
+    // queued before the writer picks one.
+    let mut rt = Builder::new()
+        .basic_scheduler()
+        .enable_all()
+        .build()
+        .unwrap();
+    let (a, b) = MemorySocket::new_pair();
+    let writer = FramedWrite::new(IoCompat::new(a), LengthDelimitedCodec::new());
+    let (mut outbound_queues, _close_tx) =
+        Peer::<MemorySocket>::start_writer_task(rt.handle(), PeerId::random(), writer);
+
+    let low_msg = direct_send_msg("state sync chunk");
+    let high_msg = direct_send_msg("consensus vote");
+    let test = async move {
+        let (low_ack_tx, low_ack_rx) = oneshot::channel();
+        outbound_queues
+            .push(MessagePriority::Low, low_msg.clone(), low_ack_tx)
+            .await
+            .unwrap();
+        let (high_ack_tx, high_ack_rx) = oneshot::channel();
+        outbound_queues
+            .push(MessagePriority::High, high_msg.clone(), high_ack_tx)
+            .await
+            .unwrap();
+
+        let mut connection = Framed::new(IoCompat::new(b), LengthDelimitedCodec::new());
+        for expected in &[high_msg, low_msg] {
+            let msg = connection.next().await.unwrap().unwrap().freeze();
+            let msg: NetworkMessage = lcs::from_bytes(&msg).unwrap();
+            assert_eq!(&msg, expected);
+        }
+        high_ack_rx.await.unwrap().unwrap();
+        low_ack_rx.await.unwrap().unwrap();
+    };
+    rt.block_on(test);
+}
+
+#[test]
+fn peer_writer_drops_low_priority_messages_when_full() {
+    ::libra_logger::Logger::new().environment_only(true).init();
+    let mut rt = Builder::new()
+        .basic_scheduler()
+        .enable_all()
+        .build()
+        .unwrap();
+    let (a, _b) = MemorySocket::new_pair();
+    let writer = FramedWrite::new(IoCompat::new(a), LengthDelimitedCodec::new());
+    let (mut outbound_queues, _close_tx) =
+        Peer::<MemorySocket>::start_writer_task(rt.handle(), PeerId::random(), writer);
+
+    let test = async move {
+        // The writer task never runs while the queue fills up, so the queue's capacity (plus the
+        // one slot mpsc guarantees each sender) is accepted and everything after it is dropped.
+        let mut acks = vec![];
+        for _ in 0..OUTBOUND_QUEUE_SIZE + 2 {
+            let (ack_tx, ack_rx) = oneshot::channel();
+            outbound_queues
+                .push(
+                    MessagePriority::Low,
+                    direct_send_msg("mempool batch"),
+                    ack_tx,
+                )
+                .await
+                .unwrap();
+            acks.push(ack_rx);
+        }
+        let mut dropped = acks.pop().unwrap();
+        match dropped.try_recv() {
+            Ok(Some(Err(PeerManagerError::OutboundQueueFull(MessagePriority::Low)))) => (),
+            other => panic!("Expected OutboundQueueFull, got: {:?}", other),
+        }
+        assert!(acks[0].try_recv().unwrap().is_none());
+
+        // High priority messages are never dropped.
+        let (ack_tx, mut ack_rx) = oneshot::channel();
+        outbound_queues
+            .push(MessagePriority::High, direct_send_msg("proposal"), ack_tx)
+            .await
+            .unwrap();
+        assert!(ack_rx.try_recv().unwrap().is_none());
+    };
+    rt.block_on(test);
+}
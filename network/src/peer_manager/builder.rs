@@ -3,7 +3,7 @@
 
 use crate::{
     counters,
-    noise::stream::NoiseStream,
+    noise::{stream::NoiseStream, NoiseIdentity},
     peer_manager::{
         conn_notifs_channel, ConnectionRequest, ConnectionRequestSender, PeerManager,
         PeerManagerNotification, PeerManagerRequest, PeerManagerRequestSender,
@@ -13,7 +13,10 @@ use crate::{
     ProtocolId,
 };
 use channel::{self, libra_channel, message_queues::QueueStyle};
-use libra_config::{config::HANDSHAKE_VERSION, network_id::NetworkContext};
+use libra_config::{
+    config::{NoiseRekeyConfig, HANDSHAKE_VERSION},
+    network_id::NetworkContext,
+};
 use libra_crypto::x25519;
 use libra_logger::prelude::*;
 use libra_metrics::IntCounterVec;
@@ -65,8 +68,10 @@ struct TransportContext {
     chain_id: ChainId,
     direct_send_protocols: Vec<ProtocolId>,
     rpc_protocols: Vec<ProtocolId>,
-    authentication_mode: AuthenticationMode,
+    noise_identity: NoiseIdentity,
+    mutual_authentication: bool,
     trusted_peers: Arc<RwLock<HashMap<PeerId, HashSet<x25519::PublicKey>>>>,
+    noise_rekey: NoiseRekeyConfig,
 }
 
 impl TransportContext {
@@ -77,12 +82,18 @@ impl TransportContext {
         authentication_mode: AuthenticationMode,
         trusted_peers: Arc<RwLock<HashMap<PeerId, HashSet<x25519::PublicKey>>>>,
     ) -> Self {
+        let (key, mutual_authentication) = match authentication_mode {
+            AuthenticationMode::ServerOnly(key) => (key, false),
+            AuthenticationMode::Mutual(key) => (key, true),
+        };
         Self {
             chain_id,
             direct_send_protocols,
             rpc_protocols,
-            authentication_mode,
+            noise_identity: NoiseIdentity::new(key),
+            mutual_authentication,
             trusted_peers,
+            noise_rekey: NoiseRekeyConfig::default(),
         }
     }
 
@@ -269,6 +280,25 @@ impl PeerManagerBuilder {
             .add_connection_event_listener()
     }
 
+    /// Returns a handle on the network identity key, through which the key can be rotated without
+    /// restarting the network. Connections already established keep using the previous key.
+    pub fn noise_identity(&self) -> NoiseIdentity {
+        self.transport_context
+            .as_ref()
+            .expect("Cannot access the noise identity once PeerManager has been built")
+            .noise_identity
+            .clone()
+    }
+
+    /// Set how the noise streams of this network are rekeyed.
+    pub fn noise_rekey(&mut self, noise_rekey: NoiseRekeyConfig) -> &mut Self {
+        self.transport_context
+            .as_mut()
+            .expect("Cannot configure rekeying once PeerManager has been built")
+            .noise_rekey = noise_rekey;
+        self
+    }
+
     /// Create the configured transport and start PeerManager.
     /// Return the actual NetworkAddress over which this peer is listening.
     pub fn build(&mut self, executor: &Handle) -> &mut Self {
@@ -285,20 +315,20 @@ impl PeerManagerBuilder {
         let chain_id = transport_context.chain_id;
         let network_id = self.network_context.network_id().clone();
         let peer_id = self.network_context.peer_id();
+        let noise_rekey = transport_context.noise_rekey;
 
-        let (key, maybe_trusted_peers, peer_id) = match transport_context.authentication_mode {
+        let key = transport_context.noise_identity;
+        let (maybe_trusted_peers, peer_id) = match transport_context.mutual_authentication {
             // validator-operated full node
-            AuthenticationMode::ServerOnly(key) if peer_id == PeerId::ZERO => {
+            false if peer_id == PeerId::ZERO => {
                 let public_key = key.public_key();
                 let peer_id = PeerId::from_identity_public_key(public_key);
-                (key, None, peer_id)
+                (None, peer_id)
             }
             // full node
-            AuthenticationMode::ServerOnly(key) => (key, None, peer_id),
+            false => (None, peer_id),
             // validator
-            AuthenticationMode::Mutual(key) => {
-                (key, Some(transport_context.trusted_peers), peer_id)
-            }
+            true => (Some(transport_context.trusted_peers), peer_id),
         };
 
         match self.listen_address.as_slice() {
@@ -313,6 +343,7 @@ impl PeerManagerBuilder {
                         chain_id,
                         network_id,
                         protos,
+                        noise_rekey,
                     ),
                     executor,
                 ))
@@ -328,6 +359,7 @@ impl PeerManagerBuilder {
                         chain_id,
                         network_id,
                         protos,
+                        noise_rekey,
                     ),
                     executor,
                 ))
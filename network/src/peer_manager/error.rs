@@ -3,6 +3,7 @@
 
 //! Errors that originate from the PeerManager module
 
+use crate::peer::MessagePriority;
 use futures::channel::{mpsc, oneshot};
 use libra_network_address::NetworkAddress;
 use libra_types::PeerId;
@@ -36,6 +37,9 @@ pub enum PeerManagerError {
 
     #[error("Serialization error {0}")]
     LcsError(lcs::Error),
+
+    #[error("Outbound queue full, dropped {0:?} priority message")]
+    OutboundQueueFull(MessagePriority),
 }
 
 impl PeerManagerError {
@@ -3,7 +3,9 @@
 
 use crate::{
     logging::network_events,
-    noise::{stream::NoiseStream, AntiReplayTimestamps, HandshakeAuthMode, NoiseUpgrader},
+    noise::{
+        stream::NoiseStream, AntiReplayTimestamps, HandshakeAuthMode, NoiseIdentity, NoiseUpgrader,
+    },
     protocols::{
         identity::exchange_handshake,
         wire::handshake::v1::{HandshakeMsg, MessagingProtocolVersion, SupportedProtocols},
@@ -14,7 +16,10 @@ use futures::{
     io::{AsyncRead, AsyncWrite},
     stream::{Stream, StreamExt, TryStreamExt},
 };
-use libra_config::{config::HANDSHAKE_VERSION, network_id::NetworkId};
+use libra_config::{
+    config::{NoiseRekeyConfig, HANDSHAKE_VERSION},
+    network_id::NetworkId,
+};
 use libra_crypto::x25519;
 use libra_logger::prelude::*;
 use libra_network_address::{parse_dns_tcp, parse_ip_tcp, parse_memory, NetworkAddress};
@@ -274,7 +279,6 @@ async fn upgrade_outbound<T: TSocket>(
 pub struct LibraNetTransport<TTransport> {
     base_transport: TTransport,
     ctxt: Arc<UpgradeContext>,
-    identity_pubkey: x25519::PublicKey,
 }
 
 impl<TTransport> LibraNetTransport<TTransport>
@@ -288,16 +292,16 @@ where
     pub fn new(
         base_transport: TTransport,
         self_peer_id: PeerId,
-        identity_key: x25519::PrivateKey,
+        identity_key: impl Into<NoiseIdentity>,
         trusted_peers: Option<Arc<RwLock<HashMap<PeerId, HashSet<x25519::PublicKey>>>>>,
         handshake_version: u8,
         chain_id: ChainId,
         network_id: NetworkId,
         application_protocols: SupportedProtocols,
+        noise_rekey: NoiseRekeyConfig,
     ) -> Self {
         let mut own_handshake = HandshakeMsg::new(chain_id, network_id);
         own_handshake.add(SUPPORTED_MESSAGING_PROTOCOL, application_protocols);
-        let identity_pubkey = identity_key.public_key();
 
         let auth_mode = match trusted_peers.as_ref() {
             Some(trusted_peers) => HandshakeAuthMode::mutual(trusted_peers.clone()),
@@ -306,15 +310,21 @@ where
 
         Self {
             ctxt: Arc::new(UpgradeContext {
-                noise: NoiseUpgrader::new(self_peer_id, identity_key, auth_mode),
+                noise: NoiseUpgrader::new(self_peer_id, identity_key, auth_mode)
+                    .with_rekey_config(noise_rekey),
                 handshake_version,
                 own_handshake,
             }),
             base_transport,
-            identity_pubkey,
         }
     }
 
+    /// Returns a handle on the network identity key of this transport. Rotating the key through
+    /// the handle applies to all connections upgraded afterwards.
+    pub fn noise_identity(&self) -> NoiseIdentity {
+        self.ctxt.noise.identity()
+    }
+
     fn parse_dial_addr(
         addr: &NetworkAddress,
     ) -> io::Result<(NetworkAddress, x25519::PublicKey, u8)> {
@@ -449,8 +459,10 @@ where
         // (e.g., `/memory/<port>` with no trailers), so we don't need to do any
         // parsing here.
         let (listener, listen_addr) = self.base_transport.listen_on(addr)?;
-        let listen_addr =
-            listen_addr.append_prod_protos(self.identity_pubkey, self.ctxt.handshake_version);
+        let listen_addr = listen_addr.append_prod_protos(
+            self.ctxt.noise.identity().public_key(),
+            self.ctxt.handshake_version,
+        );
 
         // need to move a ctxt into stream task
         let ctxt = self.ctxt.clone();
@@ -599,6 +611,7 @@ mod test {
             chain_id,
             NetworkId::Validator,
             supported_protocols.clone(),
+            NoiseRekeyConfig::default(),
         );
 
         let dialer_transport = LibraNetTransport::new(
@@ -610,6 +623,7 @@ mod test {
             chain_id,
             NetworkId::Validator,
             supported_protocols.clone(),
+            NoiseRekeyConfig::default(),
         );
 
         (
@@ -265,8 +265,9 @@ mod test {
         account_state::AccountState,
         account_state_blob::{AccountStateBlob, AccountStateWithProof},
         block_info::BlockInfo,
-        contract_event::ContractEvent,
+        contract_event::{ContractEvent, EventWithProof},
         epoch_change::EpochChangeProof,
+        epoch_participation::EpochParticipation,
         event::{EventHandle, EventKey},
         ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
         mempool_status::{MempoolStatus, MempoolStatusCode},
@@ -527,6 +528,17 @@ mod test {
             unimplemented!()
         }
 
+        fn get_events_with_proofs(
+            &self,
+            _event_key: &EventKey,
+            _start: u64,
+            _ascending: bool,
+            _limit: u64,
+            _ledger_version: Version,
+        ) -> Result<Vec<EventWithProof>> {
+            unimplemented!()
+        }
+
         fn get_latest_account_state(
             &self,
             _address: AccountAddress,
@@ -558,6 +570,15 @@ mod test {
             unimplemented!()
         }
 
+        fn get_transaction_by_hash(
+            &self,
+            _hash: HashValue,
+            _ledger_version: u64,
+            _fetch_events: bool,
+        ) -> Result<Option<TransactionWithProof>> {
+            unimplemented!()
+        }
+
         fn get_state_proof_with_ledger_info(
             &self,
             _known_version: u64,
@@ -577,6 +598,14 @@ mod test {
             unimplemented!()
         }
 
+        fn get_accumulator_consistency_proof(
+            &self,
+            _client_known_version: Version,
+            _target_version: Version,
+        ) -> Result<AccumulatorConsistencyProof> {
+            unimplemented!()
+        }
+
         /// Return the associated AccountStateWithProof for the given account address. If the
         /// AccountStateWithProof doesn't exist, an error is returned.
         fn get_account_state_with_proof(
@@ -619,5 +648,9 @@ mod test {
         fn get_block_timestamp(&self, _: u64) -> Result<u64> {
             unimplemented!()
         }
+
+        fn get_epoch_participation(&self, _: u64) -> Result<Option<EpochParticipation>> {
+            unimplemented!()
+        }
     }
 }
@@ -18,6 +18,14 @@ define_counters![
         consensus_rotation_tx_resubmissions: Counter,
         "counts the number of times the key manager had to resubmit a consensus rotation transaction to the blockchain"
     ),
+    (
+        completed_network_key_rotations: Counter,
+        "counts the number of completed validator network key rotations performed by the key manager"
+    ),
+    (
+        network_rotation_tx_resubmissions: Counter,
+        "counts the number of times the key manager had to resubmit a network rotation transaction to the blockchain"
+    ),
     (
         no_actions_required: Counter,
         "counts the number of times the key manager determined that no actions were required"
@@ -26,6 +34,10 @@ define_counters![
         waiting_on_consensus_reconfiguration: Counter,
         "counts the number of times the key manager had to wait for a reconfiguration event for the consensus key"
     ),
+    (
+        waiting_on_network_reconfiguration: Counter,
+        "counts the number of times the key manager had to wait for a reconfiguration event for the network key"
+    ),
     (
         sleeps: Counter,
         "counts the number of times the key manager went to sleep"
@@ -0,0 +1,308 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A dry run rehearses a single iteration of the key manager without touching the keys in secure
+//! storage or submitting anything to the blockchain. Key rotations are applied to an in-memory
+//! overlay on top of secure storage, and transactions are validated (signature and operator
+//! authorization) and recorded instead of being submitted. Everything else, i.e., reading the
+//! blockchain and signing with the operator key, goes through the real interfaces, so that a
+//! successful dry run shows that the key manager holds the permissions it needs.
+
+use crate::{libra_interface::LibraInterface, Action, Error, KeyManager};
+use libra_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature};
+use libra_global_constants::{CONSENSUS_KEY, VALIDATOR_NETWORK_KEY};
+use libra_secure_storage::{
+    CryptoStorage, Error as StorageError, GetResponse, InMemoryStorageInternal, KVStorage,
+    PublicKeyResponse, Value,
+};
+use libra_secure_time::TimeService;
+use libra_types::{
+    account_address::AccountAddress,
+    account_state::AccountState,
+    transaction::{SignedTransaction, Transaction},
+    validator_config::ValidatorConfig,
+    validator_info::ValidatorInfo,
+};
+use std::{cell::RefCell, fmt};
+
+/// What a key manager iteration would have done.
+#[derive(Debug)]
+pub struct DryRunReport {
+    /// The action the key manager decided to perform.
+    pub action: Action,
+    /// The keys that would have been rotated in secure storage, as (name, current, new).
+    pub rotated_keys: Vec<(String, Ed25519PublicKey, Ed25519PublicKey)>,
+    /// The transactions that would have been submitted to the blockchain.
+    pub transactions: Vec<SignedTransaction>,
+}
+
+impl fmt::Display for DryRunReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Action: {:?}", self.action)?;
+        for (name, current, new) in &self.rotated_keys {
+            writeln!(f, "Key {} would rotate from {} to {}", name, current, new)?;
+        }
+        for transaction in &self.transactions {
+            writeln!(
+                f,
+                "Transaction from {} with sequence number {} would be submitted",
+                transaction.sender(),
+                transaction.sequence_number()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<LI, S, T> KeyManager<LI, S, T>
+where
+    LI: LibraInterface,
+    S: KVStorage + CryptoStorage,
+    T: TimeService + Clone + Send + Sync,
+{
+    /// Evaluates the current status and performs the resulting action against a dry run view of
+    /// storage and the blockchain, returning what would have changed. Neither secure storage nor
+    /// the blockchain are modified, and the state of this key manager is left untouched.
+    pub fn dry_run(&mut self) -> Result<DryRunReport, Error> {
+        let mut key_manager = KeyManager {
+            libra: DryRunLibraInterface::new(&self.libra),
+            storage: DryRunStorage::new(&mut self.storage, self.time_service.clone()),
+            time_service: self.time_service.clone(),
+            last_checked_libra_timestamp: self.last_checked_libra_timestamp,
+            rotation_period_secs: self.rotation_period_secs,
+            sleep_period_secs: self.sleep_period_secs,
+            txn_expiration_secs: self.txn_expiration_secs,
+            chain_id: self.chain_id,
+            network_rotation_period_secs: self.network_rotation_period_secs,
+        };
+
+        let action = key_manager.evaluate_status()?;
+        key_manager.perform_action(action.clone())?;
+
+        let mut rotated_keys = Vec::new();
+        for name in &[CONSENSUS_KEY, VALIDATOR_NETWORK_KEY] {
+            if let Ok(response) = key_manager.storage.overlay.get_public_key(name) {
+                let current = key_manager.storage.storage.get_public_key(name)?.public_key;
+                rotated_keys.push((name.to_string(), current, response.public_key));
+            }
+        }
+
+        Ok(DryRunReport {
+            action,
+            rotated_keys,
+            transactions: key_manager.libra.transactions.into_inner(),
+        })
+    }
+}
+
+/// A LibraInterface that reads from the blockchain but only validates and records the
+/// transactions submitted to it.
+struct DryRunLibraInterface<'a, LI> {
+    libra: &'a LI,
+    transactions: RefCell<Vec<SignedTransaction>>,
+}
+
+impl<'a, LI: LibraInterface> DryRunLibraInterface<'a, LI> {
+    fn new(libra: &'a LI) -> Self {
+        Self {
+            libra,
+            transactions: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Ensures the transaction is properly signed by a key authorized for the sender account.
+    fn validate_transaction(&self, transaction: &SignedTransaction) -> Result<(), Error> {
+        let sender = transaction.sender();
+        let authentication_key = transaction.authenticator().authentication_key();
+        let account_resource = self
+            .libra
+            .retrieve_account_state(sender)?
+            .get_account_resource()
+            .map_err(|e| Error::UnknownError(e.to_string()))?
+            .ok_or_else(|| Error::DataDoesNotExist(format!("AccountResource: {}", sender)))?;
+        if account_resource.authentication_key() != authentication_key.as_ref() {
+            return Err(Error::DryRunFailure(format!(
+                "The signing key is not authorized for account: {}",
+                sender
+            )));
+        }
+
+        transaction
+            .clone()
+            .check_signature()
+            .map_err(|e| Error::DryRunFailure(format!("Invalid signature: {}", e)))?;
+        Ok(())
+    }
+}
+
+impl<'a, LI: LibraInterface> LibraInterface for DryRunLibraInterface<'a, LI> {
+    fn libra_timestamp(&self) -> Result<u64, Error> {
+        self.libra.libra_timestamp()
+    }
+
+    fn last_reconfiguration(&self) -> Result<u64, Error> {
+        self.libra.last_reconfiguration()
+    }
+
+    fn retrieve_sequence_number(&self, account: AccountAddress) -> Result<u64, Error> {
+        self.libra.retrieve_sequence_number(account)
+    }
+
+    fn submit_transaction(&self, transaction: Transaction) -> Result<(), Error> {
+        let transaction = match transaction {
+            Transaction::UserTransaction(transaction) => transaction,
+            _ => {
+                return Err(Error::DryRunFailure(
+                    "Only user transactions are submitted by the key manager".into(),
+                ))
+            }
+        };
+        self.validate_transaction(&transaction)?;
+        self.transactions.borrow_mut().push(transaction);
+        Ok(())
+    }
+
+    fn retrieve_validator_config(&self, account: AccountAddress) -> Result<ValidatorConfig, Error> {
+        self.libra.retrieve_validator_config(account)
+    }
+
+    fn retrieve_validator_info(&self, account: AccountAddress) -> Result<ValidatorInfo, Error> {
+        self.libra.retrieve_validator_info(account)
+    }
+
+    fn retrieve_account_state(&self, account: AccountAddress) -> Result<AccountState, Error> {
+        self.libra.retrieve_account_state(account)
+    }
+}
+
+/// A storage that writes to an in-memory overlay, and reads from the overlay first and from the
+/// underlying storage for anything not written in the dry run.
+struct DryRunStorage<'a, S, T> {
+    storage: &'a mut S,
+    overlay: InMemoryStorageInternal<T>,
+}
+
+impl<'a, S, T: TimeService> DryRunStorage<'a, S, T> {
+    fn new(storage: &'a mut S, time_service: T) -> Self {
+        Self {
+            storage,
+            overlay: InMemoryStorageInternal::new_with_time_service(time_service),
+        }
+    }
+}
+
+/// Falls back to the underlying storage if the overlay doesn't hold the key.
+fn or_else<R>(
+    overlay_result: Result<R, StorageError>,
+    storage_result: impl FnOnce() -> Result<R, StorageError>,
+) -> Result<R, StorageError> {
+    match overlay_result {
+        Err(StorageError::KeyNotSet(_)) => storage_result(),
+        result => result,
+    }
+}
+
+impl<'a, S, T> KVStorage for DryRunStorage<'a, S, T>
+where
+    S: KVStorage + CryptoStorage,
+    T: TimeService + Send + Sync,
+{
+    fn available(&self) -> Result<(), StorageError> {
+        self.storage.available()
+    }
+
+    fn get(&self, key: &str) -> Result<GetResponse, StorageError> {
+        or_else(self.overlay.get(key), || self.storage.get(key))
+    }
+
+    fn set(&mut self, key: &str, value: Value) -> Result<(), StorageError> {
+        self.overlay.set(key, value)
+    }
+
+    fn reset_and_clear(&mut self) -> Result<(), StorageError> {
+        self.overlay.reset_and_clear()
+    }
+}
+
+impl<'a, S, T> CryptoStorage for DryRunStorage<'a, S, T>
+where
+    S: KVStorage + CryptoStorage,
+    T: TimeService + Send + Sync,
+{
+    fn create_key(&mut self, name: &str) -> Result<Ed25519PublicKey, StorageError> {
+        self.overlay.create_key(name)
+    }
+
+    fn export_private_key(&self, name: &str) -> Result<Ed25519PrivateKey, StorageError> {
+        or_else(self.overlay.export_private_key(name), || {
+            self.storage.export_private_key(name)
+        })
+    }
+
+    fn import_private_key(
+        &mut self,
+        name: &str,
+        key: Ed25519PrivateKey,
+    ) -> Result<(), StorageError> {
+        self.overlay.import_private_key(name, key)
+    }
+
+    fn export_private_key_for_version(
+        &self,
+        name: &str,
+        version: Ed25519PublicKey,
+    ) -> Result<Ed25519PrivateKey, StorageError> {
+        match self
+            .overlay
+            .export_private_key_for_version(name, version.clone())
+        {
+            Err(StorageError::KeyNotSet(_)) | Err(StorageError::KeyVersionNotFound(_)) => {
+                self.storage.export_private_key_for_version(name, version)
+            }
+            result => result,
+        }
+    }
+
+    fn get_public_key(&self, name: &str) -> Result<PublicKeyResponse, StorageError> {
+        or_else(self.overlay.get_public_key(name), || {
+            self.storage.get_public_key(name)
+        })
+    }
+
+    /// The rotated key is only created in the overlay, the key in the underlying storage is left
+    /// untouched and remains accessible as the previous version.
+    fn rotate_key(&mut self, name: &str) -> Result<Ed25519PublicKey, StorageError> {
+        // Ensure the key exists before rotating it
+        self.storage.get_public_key(name)?;
+        self.overlay.create_key(name)
+    }
+
+    fn sign<M: libra_crypto::hash::CryptoHash + serde::Serialize>(
+        &mut self,
+        name: &str,
+        message: &M,
+    ) -> Result<Ed25519Signature, StorageError> {
+        let storage = &mut self.storage;
+        or_else(self.overlay.sign(name, message), || {
+            storage.sign(name, message)
+        })
+    }
+
+    fn sign_using_version<M: libra_crypto::hash::CryptoHash + serde::Serialize>(
+        &mut self,
+        name: &str,
+        version: Ed25519PublicKey,
+        message: &M,
+    ) -> Result<Ed25519Signature, StorageError> {
+        let storage = &mut self.storage;
+        match self
+            .overlay
+            .sign_using_version(name, version.clone(), message)
+        {
+            Err(StorageError::KeyNotSet(_)) | Err(StorageError::KeyVersionNotFound(_)) => {
+                storage.sign_using_version(name, version, message)
+            }
+            result => result,
+        }
+    }
+}
@@ -1,8 +1,9 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-//! The purpose of KeyManager is to rotate consensus key (and eventually the network key). It is not
-//! responsible for generating the first key and fails if the stores have not been properly setup.
+//! The purpose of KeyManager is to rotate the consensus key and, optionally, the validator network
+//! key. It is not responsible for generating the first keys and fails if the stores have not been
+//! properly setup.
 //! During rotation, it first updates the local store, then submits a transaction to rotate to the
 //! new key. After some period of time and upon restarts of the process, it will evaluate the
 //! current status of the system including:
@@ -14,6 +15,10 @@
 //! evaluates the current time from the last reconfiguration and logs that delta with greater
 //! levels of severity depending on the delta.
 //!
+//! The validator network key goes through the same checks, after the consensus key is in sync.
+//! Once its rotation is reconfigured in, the network stack of the validator picks up the new key
+//! from storage without a restart.
+//!
 //! KeyManager talks to Libra via the LibraInterface that may either be a direct link into
 //! `LibraDB`/`Executor`, JSON-RPC, or some other concoction.
 //! KeyManager talks to its own storage through the `LibraSecureStorage::Storage trait.
@@ -21,9 +26,17 @@
 
 use crate::{counters::COUNTERS, libra_interface::LibraInterface};
 use libra_crypto::{ed25519::Ed25519PublicKey, x25519};
-use libra_global_constants::{CONSENSUS_KEY, OPERATOR_ACCOUNT, OPERATOR_KEY, OWNER_ACCOUNT};
+use libra_global_constants::{
+    CONSENSUS_KEY, OPERATOR_ACCOUNT, OPERATOR_KEY, OWNER_ACCOUNT, VALIDATOR_NETWORK_KEY,
+};
 use libra_logger::{error, info};
-use libra_network_address::{encrypted::RawEncNetworkAddress, RawNetworkAddress};
+use libra_network_address::{
+    encrypted::{
+        EncNetworkAddress, RawEncNetworkAddress, TEST_SHARED_VAL_NETADDR_KEY,
+        TEST_SHARED_VAL_NETADDR_KEY_VERSION,
+    },
+    NetworkAddress, RawNetworkAddress,
+};
 use libra_secure_storage::{CryptoStorage, KVStorage};
 use libra_secure_time::TimeService;
 use libra_types::{
@@ -31,11 +44,13 @@ use libra_types::{
     account_config::LBR_NAME,
     chain_id::ChainId,
     transaction::{RawTransaction, Script, SignedTransaction, Transaction, TransactionArgument},
+    validator_config::ValidatorConfig,
 };
-use std::{str::FromStr, time::Duration};
+use std::{convert::TryFrom, str::FromStr, time::Duration};
 use thiserror::Error;
 
 pub mod counters;
+pub mod dry_run;
 pub mod libra_interface;
 
 #[cfg(test)]
@@ -45,7 +60,7 @@ const GAS_UNIT_PRICE: u64 = 0;
 const MAX_GAS_AMOUNT: u64 = 400_000;
 
 /// Defines actions that KeyManager should perform after a check of all associated state.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Action {
     /// The system is in a healthy state and there is no need to perform a rotation
     NoAction,
@@ -53,6 +68,10 @@ pub enum Action {
     FullKeyRotation,
     /// Storage and the blockchain are inconsistent, submit a new rotation
     SubmitKeyRotationTransaction,
+    /// Sufficient time has passed for another validator network key rotation
+    FullNetworkKeyRotation,
+    /// Storage and the blockchain are inconsistent on the network key, submit a new rotation
+    SubmitNetworkKeyRotationTransaction,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -62,6 +81,8 @@ pub enum Error {
     ConfigInfoKeyMismatch(Ed25519PublicKey, Ed25519PublicKey),
     #[error("Key mismatch, config: {0}, storage: {0}")]
     ConfigStorageKeyMismatch(Ed25519PublicKey, Ed25519PublicKey),
+    #[error("Dry run failed: {0}")]
+    DryRunFailure(String),
     #[error("Data does not exist: {0}")]
     DataDoesNotExist(String),
     #[error(
@@ -70,6 +91,12 @@ pub enum Error {
     LivenessError(u64, u64),
     #[error("Unable to retrieve the operator account address. Storage error: {0}")]
     MissingAccountAddress(#[from] libra_secure_storage::Error),
+    #[error("Network key mismatch, config: {0}, info: {1}")]
+    NetworkConfigInfoKeyMismatch(x25519::PublicKey, x25519::PublicKey),
+    #[error("Network key mismatch, config: {0}, storage: {1}")]
+    NetworkConfigStorageKeyMismatch(x25519::PublicKey, x25519::PublicKey),
+    #[error("Unable to update the validator network address: {0}")]
+    NetworkAddressError(String),
     #[error("ValidatorInfo not found in ValidatorConfig: {0}")]
     ValidatorInfoNotFound(AccountAddress),
     #[error("Unknown error: {0}")]
@@ -92,6 +119,8 @@ pub struct KeyManager<LI, S, T> {
     sleep_period_secs: u64,    // The amount of time to sleep between key management checks
     txn_expiration_secs: u64,  // The time after which a rotation transaction expires
     chain_id: ChainId,
+    // The frequency by which to rotate the validator network key, if it is rotated at all
+    network_rotation_period_secs: Option<u64>,
 }
 
 impl<LI, S, T> KeyManager<LI, S, T>
@@ -118,9 +147,17 @@ where
             sleep_period_secs,
             txn_expiration_secs,
             chain_id,
+            network_rotation_period_secs: None,
         }
     }
 
+    /// Enables the rotation of the validator network key with the given period. The network key is
+    /// not rotated by default.
+    pub fn network_rotation_period_secs(mut self, network_rotation_period_secs: u64) -> Self {
+        self.network_rotation_period_secs = Some(network_rotation_period_secs);
+        self
+    }
+
     /// Begins execution of the key manager by running an infinite loop where the key manager will
     /// periodically wake up, verify the state of the validator keys (e.g., the consensus key), and
     /// initiate a key rotation when required. If something goes wrong that we can't handle, an
@@ -189,6 +226,38 @@ where
         Ok(())
     }
 
+    pub fn compare_network_storage_to_config(&self) -> Result<(), Error> {
+        let owner_account = self.get_account_from_storage(OWNER_ACCOUNT)?;
+        let validator_config = self.libra.retrieve_validator_config(owner_account)?;
+
+        let storage_key = self.network_key_from_storage()?;
+        let config_key = validator_config.validator_network_identity_public_key;
+        if storage_key != config_key {
+            return Err(Error::NetworkConfigStorageKeyMismatch(
+                config_key,
+                storage_key,
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn compare_network_info_to_config(&self) -> Result<(), Error> {
+        let owner_account = self.get_account_from_storage(OWNER_ACCOUNT)?;
+        let validator_config = self.libra.retrieve_validator_config(owner_account)?;
+        let validator_info = self.libra.retrieve_validator_info(owner_account)?;
+
+        let info_key = validator_info
+            .config()
+            .validator_network_identity_public_key;
+        let config_key = validator_config.validator_network_identity_public_key;
+        if config_key != info_key {
+            return Err(Error::NetworkConfigInfoKeyMismatch(config_key, info_key));
+        }
+
+        Ok(())
+    }
+
     pub fn last_reconfiguration(&self) -> Result<u64, Error> {
         // Convert the time to seconds
         Ok(self.libra.last_reconfiguration()? / 1_000_000)
@@ -198,6 +267,13 @@ where
         Ok(self.storage.get_public_key(CONSENSUS_KEY)?.last_update)
     }
 
+    pub fn last_network_rotation(&self) -> Result<u64, Error> {
+        Ok(self
+            .storage
+            .get_public_key(VALIDATOR_NETWORK_KEY)?
+            .last_update)
+    }
+
     pub fn libra_timestamp(&self) -> Result<u64, Error> {
         // Convert the time to seconds
         Ok(self.libra.libra_timestamp()? / 1_000_000)
@@ -221,27 +297,69 @@ where
         &mut self,
         consensus_key: Ed25519PublicKey,
     ) -> Result<Ed25519PublicKey, Error> {
+        // Retrieve existing network information as registered on-chain
+        let owner_account = self.get_account_from_storage(OWNER_ACCOUNT)?;
+        let mut validator_config = self.libra.retrieve_validator_config(owner_account)?;
+        validator_config.consensus_public_key = consensus_key.clone();
+
+        self.submit_validator_config_transaction(owner_account, &validator_config)?;
+        Ok(consensus_key)
+    }
+
+    pub fn resubmit_network_key_transaction(&mut self) -> Result<(), Error> {
+        let network_key = self.network_key_from_storage()?;
+        COUNTERS.network_rotation_tx_resubmissions.inc();
+        self.submit_network_key_rotation_transaction(network_key)
+            .map(|_| ())
+    }
+
+    pub fn rotate_network_key(&mut self) -> Result<x25519::PublicKey, Error> {
+        let network_key = self.storage.rotate_key(VALIDATOR_NETWORK_KEY)?;
+        let network_key = to_x25519(&network_key)?;
+        info!("Successfully rotated the validator network key in secure storage.");
+        COUNTERS.completed_network_key_rotations.inc();
+        self.submit_network_key_rotation_transaction(network_key)
+    }
+
+    /// Submits a transaction registering the given validator network key on-chain, along with the
+    /// validator network address updated to the new key. The consensus and full node network
+    /// information registered on-chain is kept as is.
+    pub fn submit_network_key_rotation_transaction(
+        &mut self,
+        network_key: x25519::PublicKey,
+    ) -> Result<x25519::PublicKey, Error> {
+        let owner_account = self.get_account_from_storage(OWNER_ACCOUNT)?;
+        let mut validator_config = self.libra.retrieve_validator_config(owner_account)?;
+        validator_config.validator_network_address = rotate_network_address(
+            owner_account,
+            &validator_config.validator_network_address,
+            &validator_config.validator_network_identity_public_key,
+            &network_key,
+        )?;
+        validator_config.validator_network_identity_public_key = network_key;
+
+        self.submit_validator_config_transaction(owner_account, &validator_config)?;
+        Ok(network_key)
+    }
+
+    fn submit_validator_config_transaction(
+        &mut self,
+        owner_account: AccountAddress,
+        validator_config: &ValidatorConfig,
+    ) -> Result<(), Error> {
         let operator_account = self.get_account_from_storage(OPERATOR_ACCOUNT)?;
         let seq_id = self.libra.retrieve_sequence_number(operator_account)?;
         let expiration = Duration::from_secs(self.time_service.now() + self.txn_expiration_secs);
 
-        // Retrieve existing network information as registered on-chain
-        let owner_account = self.get_account_from_storage(OWNER_ACCOUNT)?;
-        let validator_config = self.libra.retrieve_validator_config(owner_account)?;
-        let network_key = validator_config.validator_network_identity_public_key;
-        let network_address = validator_config.validator_network_address;
-        let fullnode_network_key = validator_config.full_node_network_identity_public_key;
-        let fullnode_network_address = validator_config.full_node_network_address;
-
         let txn = build_rotation_transaction(
             owner_account,
             operator_account,
             seq_id,
-            &consensus_key,
-            &network_key,
-            &network_address,
-            &fullnode_network_key,
-            &fullnode_network_address,
+            &validator_config.consensus_public_key,
+            &validator_config.validator_network_identity_public_key,
+            &validator_config.validator_network_address,
+            &validator_config.full_node_network_identity_public_key,
+            &validator_config.full_node_network_address,
             expiration,
             self.chain_id,
         );
@@ -254,7 +372,7 @@ where
             .submit_transaction(Transaction::UserTransaction(signed_txn))?;
         info!("Submitted the rotation transaction to the blockchain.");
 
-        Ok(consensus_key)
+        Ok(())
     }
 
     /// Ensures that the libra_timestamp() value registered on-chain is strictly monotonically
@@ -300,7 +418,42 @@ where
         }
 
         if last_rotation + self.rotation_period_secs <= self.time_service.now() {
-            Ok(Action::FullKeyRotation)
+            return Ok(Action::FullKeyRotation);
+        }
+
+        match self.network_rotation_period_secs {
+            Some(network_rotation_period_secs) => {
+                self.evaluate_network_status(network_rotation_period_secs)
+            }
+            None => Ok(Action::NoAction),
+        }
+    }
+
+    /// Evaluates the status of the validator network key, once the consensus key is known to be
+    /// in sync between secure storage and the blockchain.
+    fn evaluate_network_status(&self, network_rotation_period_secs: u64) -> Result<Action, Error> {
+        // If this is inconsistent, then we are waiting on a reconfiguration...
+        if let Err(Error::NetworkConfigInfoKeyMismatch(..)) = self.compare_network_info_to_config()
+        {
+            COUNTERS.waiting_on_network_reconfiguration.inc();
+            return Ok(Action::NoAction);
+        }
+
+        let last_rotation = self.last_network_rotation()?;
+
+        // If this is inconsistent, then the transaction either failed or was never submitted.
+        if let Err(Error::NetworkConfigStorageKeyMismatch(..)) =
+            self.compare_network_storage_to_config()
+        {
+            return if last_rotation + self.txn_expiration_secs <= self.time_service.now() {
+                Ok(Action::SubmitNetworkKeyRotationTransaction)
+            } else {
+                Ok(Action::NoAction)
+            };
+        }
+
+        if last_rotation + network_rotation_period_secs <= self.time_service.now() {
+            Ok(Action::FullNetworkKeyRotation)
         } else {
             Ok(Action::NoAction)
         }
@@ -316,6 +469,14 @@ where
                 info!("The consensus key rotation transaction needs to be resubmitted");
                 self.resubmit_consensus_key_transaction()
             }
+            Action::FullNetworkKeyRotation => {
+                info!("A full validator network key rotation needs to be performed.");
+                self.rotate_network_key().map(|_| ())
+            }
+            Action::SubmitNetworkKeyRotationTransaction => {
+                info!("The validator network key rotation transaction needs to be resubmitted");
+                self.resubmit_network_key_transaction()
+            }
             Action::NoAction => {
                 info!("No actions need to be performed.");
                 COUNTERS.no_actions_required.inc();
@@ -335,6 +496,54 @@ where
             Err(e) => Err(Error::MissingAccountAddress(e)),
         }
     }
+
+    fn network_key_from_storage(&self) -> Result<x25519::PublicKey, Error> {
+        let network_key = self
+            .storage
+            .get_public_key(VALIDATOR_NETWORK_KEY)?
+            .public_key;
+        to_x25519(&network_key)
+    }
+}
+
+/// Converts a validator network key, kept as an Ed25519 key in secure storage, to the x25519 key
+/// used by the network.
+fn to_x25519(network_key: &Ed25519PublicKey) -> Result<x25519::PublicKey, Error> {
+    x25519::PublicKey::from_ed25519_public_bytes(&network_key.to_bytes())
+        .map_err(|e| Error::UnknownError(e.to_string()))
+}
+
+/// Replaces the noise public key in an encrypted validator network address, re-encrypting the
+/// address with the next sequence number.
+fn rotate_network_address(
+    owner_account: AccountAddress,
+    raw_enc_addr: &RawEncNetworkAddress,
+    old_network_key: &x25519::PublicKey,
+    new_network_key: &x25519::PublicKey,
+) -> Result<RawEncNetworkAddress, Error> {
+    // TODO: use the real shared validator network address key once the key manager has it
+    let addr_idx = 0;
+    let enc_addr = EncNetworkAddress::try_from(raw_enc_addr)
+        .map_err(|e| Error::NetworkAddressError(e.to_string()))?;
+    let seq_num = enc_addr.seq_num() + 1;
+    let raw_addr = enc_addr
+        .decrypt(&TEST_SHARED_VAL_NETADDR_KEY, &owner_account, addr_idx)
+        .map_err(|e| Error::NetworkAddressError(e.to_string()))?;
+    let mut addr = NetworkAddress::try_from(&raw_addr)
+        .map_err(|e| Error::NetworkAddressError(e.to_string()))?;
+
+    addr.rotate_noise_public_key(old_network_key, new_network_key);
+
+    let raw_addr = RawNetworkAddress::try_from(&addr)
+        .map_err(|e| Error::NetworkAddressError(e.to_string()))?;
+    let enc_addr = raw_addr.encrypt(
+        &TEST_SHARED_VAL_NETADDR_KEY,
+        TEST_SHARED_VAL_NETADDR_KEY_VERSION,
+        &owner_account,
+        seq_num,
+        addr_idx,
+    );
+    RawEncNetworkAddress::try_from(&enc_addr).map_err(|e| Error::NetworkAddressError(e.to_string()))
 }
 
 pub fn build_rotation_transaction(
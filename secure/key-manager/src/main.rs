@@ -33,6 +33,7 @@ fn main() {
 
     libra_logger::Logger::new()
         .channel_size(key_manager_config.logger.chan_size)
+        .format(key_manager_config.logger.format)
         .is_async(key_manager_config.logger.is_async)
         .level(key_manager_config.logger.level)
         .init();
@@ -53,7 +54,7 @@ fn create_and_execute_key_manager(key_manager_config: KeyManagerConfig) -> Resul
         .expect("Unable to initialize storage");
     let time_service = RealTimeService::new();
 
-    KeyManager::new(
+    let mut key_manager = KeyManager::new(
         libra_interface,
         storage,
         time_service,
@@ -61,8 +62,16 @@ fn create_and_execute_key_manager(key_manager_config: KeyManagerConfig) -> Resul
         key_manager_config.sleep_period_secs,
         key_manager_config.txn_expiration_secs,
         key_manager_config.chain_id,
-    )
-    .execute()
+    );
+    if let Some(network_rotation_period_secs) = key_manager_config.network_rotation_period_secs {
+        key_manager = key_manager.network_rotation_period_secs(network_rotation_period_secs);
+    }
+    if key_manager_config.dry_run {
+        let report = key_manager.dry_run()?;
+        println!("Dry run completed successfully:\n{}", report);
+        return Ok(());
+    }
+    key_manager.execute()
 }
 
 fn create_libra_interface(json_rpc_endpoint: String) -> JsonRpcLibraInterface {
@@ -16,9 +16,12 @@ use libra_config::{
 };
 use libra_crypto::{ed25519::Ed25519PrivateKey, x25519, HashValue, PrivateKey, Uniform};
 use libra_global_constants::{
-    CONSENSUS_KEY, OPERATOR_ACCOUNT, OPERATOR_KEY, OWNER_ACCOUNT, OWNER_KEY,
+    CONSENSUS_KEY, OPERATOR_ACCOUNT, OPERATOR_KEY, OWNER_ACCOUNT, OWNER_KEY, VALIDATOR_NETWORK_KEY,
+};
+use libra_network_address::{
+    encrypted::{EncNetworkAddress, RawEncNetworkAddress, TEST_SHARED_VAL_NETADDR_KEY},
+    NetworkAddress, RawNetworkAddress,
 };
-use libra_network_address::{encrypted::RawEncNetworkAddress, RawNetworkAddress};
 use libra_secure_storage::{InMemoryStorageInternal, KVStorage, Value};
 use libra_secure_time::{MockTimeService, TimeService};
 use libra_types::{
@@ -393,7 +396,7 @@ fn setup_node<T: LibraInterface + Clone>(
 }
 
 // Creates and returns a secure storage implementation (based on an in memory storage engine) for
-// testing. As part of the initialization, the consensus key is created.
+// testing. As part of the initialization, the consensus and validator network keys are created.
 fn setup_secure_storage(
     config: &NodeConfig,
     time: MockTimeService,
@@ -436,6 +439,17 @@ fn setup_secure_storage(
         )
         .unwrap();
 
+    // Initialize the validator network key in storage. This key is not registered on-chain by
+    // genesis, so it only becomes the validator network key once the key manager rotates to it.
+    let mut rng = StdRng::from_seed([45u8; 32]);
+    let network_prikey = Ed25519PrivateKey::generate(&mut rng);
+    sec_storage
+        .set(
+            VALIDATOR_NETWORK_KEY,
+            Value::Ed25519PrivateKey(network_prikey),
+        )
+        .unwrap();
+
     sec_storage
 }
 
@@ -691,6 +705,149 @@ fn verify_execute<T: LibraInterface>(mut node: Node<T>) {
     assert_ne!(0, node.libra.last_reconfiguration().unwrap());
 }
 
+#[test]
+// This verifies that the key manager rotates the validator network key once enabled, and that the
+// rotation (including the encrypted validator network address) is reconfigured in on-chain.
+fn test_network_key_rotation() {
+    // Test the mock libra interface implementation
+    let node = setup_node_using_test_mocks();
+    verify_network_key_rotation(node);
+
+    // Test the json libra interface implementation
+    let (node, _runtime) = setup_node_using_json_rpc();
+    verify_network_key_rotation(node);
+}
+
+fn verify_network_key_rotation<T: LibraInterface>(mut node: Node<T>) {
+    let (_, key_manager_config) = get_test_configs();
+    let network_rotation_period_secs = key_manager_config.rotation_period_secs / 2;
+    node.key_manager = node
+        .key_manager
+        .network_rotation_period_secs(network_rotation_period_secs);
+
+    // Verify the network key in storage is not yet registered on-chain
+    let owner_account = node.get_account_from_storage(OWNER_ACCOUNT);
+    let genesis_config = node.libra.retrieve_validator_config(owner_account).unwrap();
+    node.key_manager.compare_network_info_to_config().unwrap();
+    assert!(matches!(
+        node.key_manager.compare_network_storage_to_config(),
+        Err(Error::NetworkConfigStorageKeyMismatch(..))
+    ));
+
+    // Verify rotation required after enough time, and perform it in a single iteration
+    node.time.increment_by(network_rotation_period_secs);
+    node.update_libra_timestamp();
+    assert_eq!(
+        Action::FullNetworkKeyRotation,
+        node.key_manager.evaluate_status().unwrap()
+    );
+    node.key_manager.execute_once().unwrap();
+
+    // Verify nothing to be done while waiting on the execution of the rotation
+    node.update_libra_timestamp();
+    assert_eq!(
+        Action::NoAction,
+        node.key_manager.evaluate_status().unwrap()
+    );
+
+    // Execute the rotation and reconfigure it in
+    submit_reconfiguration_transaction(&node);
+    node.execute_and_commit(node.libra.take_all_transactions());
+    node.key_manager
+        .compare_network_storage_to_config()
+        .unwrap();
+    node.key_manager.compare_network_info_to_config().unwrap();
+    node.update_libra_timestamp();
+    assert_eq!(
+        Action::NoAction,
+        node.key_manager.evaluate_status().unwrap()
+    );
+
+    // Verify the validator network address now carries the new key
+    let new_config = node.libra.retrieve_validator_config(owner_account).unwrap();
+    let new_key = new_config.validator_network_identity_public_key;
+    assert_ne!(
+        genesis_config.validator_network_identity_public_key,
+        new_key
+    );
+    let enc_addr = EncNetworkAddress::try_from(&new_config.validator_network_address).unwrap();
+    assert_eq!(1, enc_addr.seq_num());
+    let raw_addr = enc_addr
+        .decrypt(&TEST_SHARED_VAL_NETADDR_KEY, &owner_account, 0)
+        .unwrap();
+    let addr = NetworkAddress::try_from(&raw_addr).unwrap();
+    assert_eq!(Some(new_key), addr.find_noise_proto());
+
+    // The consensus key was left untouched
+    assert_eq!(
+        genesis_config.consensus_public_key,
+        new_config.consensus_public_key
+    );
+}
+
+#[test]
+// This verifies that a dry run reports the rotation the key manager would perform, without
+// modifying secure storage or submitting anything, and that it fails without the permissions a
+// real rotation needs.
+fn test_dry_run() {
+    // Test the mock libra interface implementation
+    let node = setup_node_using_test_mocks();
+    verify_dry_run(node);
+
+    // Test the json libra interface implementation
+    let (node, _runtime) = setup_node_using_json_rpc();
+    verify_dry_run(node);
+}
+
+fn verify_dry_run<T: LibraInterface>(mut node: Node<T>) {
+    let (_, key_manager_config) = get_test_configs();
+
+    // Verify nothing would be done initially
+    node.update_libra_timestamp();
+    let report = node.key_manager.dry_run().unwrap();
+    assert_eq!(Action::NoAction, report.action);
+    assert!(report.rotated_keys.is_empty());
+    assert!(report.transactions.is_empty());
+
+    // Verify a full rotation would be reported after enough time
+    node.time
+        .increment_by(key_manager_config.rotation_period_secs);
+    node.update_libra_timestamp();
+    let consensus_key = node.get_key_from_storage(CONSENSUS_KEY).public_key();
+    let report = node.key_manager.dry_run().unwrap();
+    assert_eq!(Action::FullKeyRotation, report.action);
+    assert_eq!(1, report.rotated_keys.len());
+    let (name, current, new) = &report.rotated_keys[0];
+    assert_eq!(CONSENSUS_KEY, name.as_str());
+    assert_eq!(&consensus_key, current);
+    assert_ne!(&consensus_key, new);
+    assert_eq!(1, report.transactions.len());
+
+    // Verify nothing was modified in storage or submitted on-chain
+    assert_eq!(
+        consensus_key,
+        node.get_key_from_storage(CONSENSUS_KEY).public_key()
+    );
+    assert!(node.libra.take_all_transactions().is_empty());
+    assert_eq!(
+        Action::FullKeyRotation,
+        node.key_manager.evaluate_status().unwrap()
+    );
+
+    // Verify the dry run fails if the operator key isn't authorized for the operator account
+    let mut rng = StdRng::from_seed([46u8; 32]);
+    let unauthorized_key = Ed25519PrivateKey::generate(&mut rng);
+    node.key_manager
+        .storage
+        .set(OPERATOR_KEY, Value::Ed25519PrivateKey(unauthorized_key))
+        .unwrap();
+    node.update_libra_timestamp();
+    assert!(matches!(
+        node.key_manager.dry_run(),
+        Err(Error::DryRunFailure(_))
+    ));
+}
+
 #[test]
 // This test ensures that execute() will return an error and halt the key manager if something goes
 // wrong.
@@ -15,10 +15,14 @@ pub enum Error {
     KeyAlreadyExists(String),
     #[error("Key not set: {0}")]
     KeyNotSet(String),
+    #[error("Key cannot be exported: {0}")]
+    KeyNotExportable(String),
     #[error("Permission denied")]
     PermissionDenied,
     #[error("Serialization error: {0}")]
     SerializationError(String),
+    #[error("Trusted state regression: {0}")]
+    TrustedStateRegression(String),
     #[error("Unexpected value type")]
     UnexpectedValueType,
     #[error("Key version not found: {0}")]
@@ -74,3 +78,15 @@ impl From<libra_github_client::Error> for Error {
         }
     }
 }
+
+impl From<::pkcs11::errors::Error> for Error {
+    fn from(error: ::pkcs11::errors::Error) -> Self {
+        match error {
+            ::pkcs11::errors::Error::Pkcs11(::pkcs11::types::CKR_PIN_INCORRECT)
+            | ::pkcs11::errors::Error::Pkcs11(::pkcs11::types::CKR_USER_NOT_LOGGED_IN) => {
+                Self::PermissionDenied
+            }
+            _ => Self::InternalError(format!("{}", error)),
+        }
+    }
+}
@@ -72,6 +72,16 @@ impl<T: Send + Sync + TimeService> KVStorage for InMemoryStorageInternal<T> {
         Ok(())
     }
 
+    fn batch_set(&mut self, values: Vec<(String, Value)>) -> Result<(), Error> {
+        let now = self.time_service.now();
+        self.data.extend(
+            values
+                .into_iter()
+                .map(|(key, value)| (key, GetResponse::new(value, now))),
+        );
+        Ok(())
+    }
+
     #[cfg(any(test, feature = "testing"))]
     fn reset_and_clear(&mut self) -> Result<(), Error> {
         self.data.clear();
@@ -22,6 +22,17 @@ pub trait KVStorage: Send + Sync {
     /// invalid permissions.
     fn set(&mut self, key: &str, value: Value) -> Result<(), Error>;
 
+    /// Sets multiple values in storage, in order, and fails if the backend is unavailable or the
+    /// process has invalid permissions. Backends that support it apply the update atomically,
+    /// i.e., on failure none of the values are set. Other backends stop at the first failure,
+    /// leaving the values prior to it set.
+    fn batch_set(&mut self, values: Vec<(String, Value)>) -> Result<(), Error> {
+        for (key, value) in values {
+            self.set(&key, value)?;
+        }
+        Ok(())
+    }
+
     /// Resets and clears all data held in the storage engine.
     /// Note: this should only be exposed and used for testing. Resetting the storage engine is not
     /// something that should be supported in production.
@@ -11,8 +11,11 @@ mod in_memory;
 mod kv_storage;
 mod namespaced_storage;
 mod on_disk;
+mod pkcs11;
 mod policy;
+mod sealed;
 mod storage;
+mod trusted_state_storage;
 mod value;
 mod vault;
 
@@ -25,10 +28,13 @@ pub use crate::{
     kv_storage::{GetResponse, KVStorage},
     namespaced_storage::NamespacedStorage,
     on_disk::{OnDiskStorage, OnDiskStorageInternal},
+    pkcs11::Pkcs11Storage,
     policy::{Capability, Identity, Permission, Policy},
+    sealed::{SealingKey, SEALING_KEY_LEN},
     storage::Storage,
+    trusted_state_storage::TrustedStateStorage,
     value::Value,
-    vault::VaultStorage,
+    vault::{RetryPolicy, VaultStorage},
 };
 
 #[cfg(test)]
@@ -25,6 +25,14 @@ impl KVStorage for NamespacedStorage {
         self.inner.set(&self.ns_name(key), value)
     }
 
+    fn batch_set(&mut self, values: Vec<(String, Value)>) -> Result<(), Error> {
+        let values = values
+            .into_iter()
+            .map(|(key, value)| (self.ns_name(&key), value))
+            .collect();
+        self.inner.batch_set(values)
+    }
+
     /// Note: This is not a namespace function
     #[cfg(any(test, feature = "testing"))]
     fn reset_and_clear(&mut self) -> Result<(), Error> {
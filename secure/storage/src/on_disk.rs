@@ -1,7 +1,7 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{CryptoKVStorage, Error, GetResponse, KVStorage, Value};
+use crate::{sealed, CryptoKVStorage, Error, GetResponse, KVStorage, SealingKey, Value};
 use libra_secure_time::{RealTimeService, TimeService};
 use libra_temppath::TempPath;
 use std::{
@@ -69,6 +69,28 @@ impl<T: TimeService> OnDiskStorageInternal<T> {
         fs::rename(&self.temp_path, &self.file_path)?;
         Ok(())
     }
+
+    /// Exports all of the data in this storage as a backup sealed under `sealing_key`, so that it
+    /// can be moved to another host without exposing the keys it contains.
+    pub fn export_sealed(&self, sealing_key: &SealingKey) -> Result<Vec<u8>, Error> {
+        let contents = serde_json::to_vec(&self.read()?)?;
+        sealed::seal(&contents, sealing_key)
+    }
+
+    /// Imports a backup produced by `export_sealed`, preserving the last update time of every
+    /// value. Nothing is imported if the backup cannot be unsealed or if any of its values is
+    /// already set in this storage.
+    pub fn import_sealed(&mut self, backup: &[u8], sealing_key: &SealingKey) -> Result<(), Error> {
+        let contents = sealed::unseal(backup, sealing_key)?;
+        let imported: HashMap<String, GetResponse> = serde_json::from_slice(&contents)?;
+
+        let mut data = self.read()?;
+        if let Some(key) = imported.keys().find(|key| data.contains_key(*key)) {
+            return Err(Error::KeyAlreadyExists(key.clone()));
+        }
+        data.extend(imported);
+        self.write(&data)
+    }
 }
 
 impl<T: Send + Sync + TimeService> KVStorage for OnDiskStorageInternal<T> {
@@ -91,6 +113,18 @@ impl<T: Send + Sync + TimeService> KVStorage for OnDiskStorageInternal<T> {
         self.write(&data)
     }
 
+    /// All values are written to disk at once, so either all or none of them are set.
+    fn batch_set(&mut self, values: Vec<(String, Value)>) -> Result<(), Error> {
+        let mut data = self.read()?;
+        let now = self.time_service.now();
+        data.extend(
+            values
+                .into_iter()
+                .map(|(key, value)| (key, GetResponse::new(value, now))),
+        );
+        self.write(&data)
+    }
+
     #[cfg(any(test, feature = "testing"))]
     fn reset_and_clear(&mut self) -> Result<(), Error> {
         self.write(&HashMap::new())
@@ -0,0 +1,402 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{CryptoStorage, Error, GetResponse, KVStorage, PublicKeyResponse, Value};
+use ::pkcs11::{types::*, Ctx};
+use libra_crypto::{
+    ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature, ED25519_PUBLIC_KEY_LENGTH},
+    hash::CryptoHash,
+    PrivateKey,
+};
+use libra_secure_time::{RealTimeService, TimeService};
+use serde::ser::Serialize;
+use std::{
+    convert::TryFrom,
+    path::Path,
+    ptr,
+    sync::{Mutex, MutexGuard},
+};
+
+/// EdDSA identifiers introduced by PKCS#11 v3.0, which are not part of the v2.40 bindings.
+const CKK_EC_EDWARDS: CK_KEY_TYPE = 0x0000_0040;
+const CKM_EC_EDWARDS_KEY_PAIR_GEN: CK_MECHANISM_TYPE = 0x0000_1055;
+const CKM_EDDSA: CK_MECHANISM_TYPE = 0x0000_1057;
+
+/// The DER encoding of the edwards25519 curve OID (1.3.101.112), used as CKA_EC_PARAMS.
+const ED25519_EC_PARAMS: [u8; 5] = [0x06, 0x03, 0x2b, 0x65, 0x70];
+/// The DER tag of an OCTET STRING, which tokens use to wrap the public key in CKA_EC_POINT.
+const DER_OCTET_STRING_TAG: u8 = 0x04;
+
+/// The application attribute of the data objects holding key/value entries.
+const APPLICATION: &str = "libra-secure-storage";
+const PREVIOUS_VERSION_SUFFIX: &str = "_previous";
+const FIND_OBJECTS_BATCH_SIZE: CK_ULONG = 16;
+
+/// Pkcs11Storage delegates all cryptographic operations to a hardware security module (HSM) via
+/// its PKCS#11 interface. Private keys are generated on the token as sensitive, non-extractable
+/// objects so that they can sign but never leave the HSM. As such, this storage refuses to
+/// export private keys. Key/value entries are kept on the token as data objects that hold the
+/// JSON encoding of each entry. Keys are versioned like in CryptoKVStorage: rotating a key keeps
+/// the previous key pair under the name suffixed with "_previous".
+pub struct Pkcs11Storage {
+    ctx: Ctx,
+    session: CK_SESSION_HANDLE,
+    namespace: Option<String>,
+    // Multi-part operations (i.e., searching and signing) cannot interleave within a session.
+    operation_lock: Mutex<()>,
+    time_service: RealTimeService,
+}
+
+impl Pkcs11Storage {
+    /// Loads the PKCS#11 module at `library`, opens a session on the token labeled `token_label`
+    /// and logs into it as the normal user with `pin`.
+    pub fn new(
+        library: &Path,
+        token_label: &str,
+        pin: &str,
+        namespace: Option<String>,
+    ) -> Result<Self, Error> {
+        let ctx = Ctx::new_and_initialize(library)?;
+        let slot = ctx
+            .get_slot_list(true)?
+            .into_iter()
+            .find(|slot| {
+                ctx.get_token_info(*slot)
+                    .map(|info| String::from_utf8_lossy(&info.label).trim_end() == token_label)
+                    .unwrap_or(false)
+            })
+            .ok_or_else(|| Error::InternalError(format!("Token not found: {}", token_label)))?;
+
+        let session = ctx.open_session(slot, CKF_SERIAL_SESSION | CKF_RW_SESSION, None, None)?;
+        match ctx.login(session, CKU_USER, Some(pin)) {
+            Ok(()) | Err(::pkcs11::errors::Error::Pkcs11(CKR_USER_ALREADY_LOGGED_IN)) => (),
+            Err(e) => return Err(e.into()),
+        }
+
+        Ok(Self {
+            ctx,
+            session,
+            namespace,
+            operation_lock: Mutex::new(()),
+            time_service: RealTimeService::new(),
+        })
+    }
+
+    fn ns_name(&self, name: &str) -> String {
+        if let Some(namespace) = &self.namespace {
+            format!("{}/{}", namespace, name)
+        } else {
+            name.into()
+        }
+    }
+
+    fn lock(&self) -> Result<MutexGuard<()>, Error> {
+        self.operation_lock
+            .lock()
+            .map_err(|_| Error::InternalError("PKCS#11 session lock is poisoned".into()))
+    }
+
+    fn find_objects(&self, template: &[CK_ATTRIBUTE]) -> Result<Vec<CK_OBJECT_HANDLE>, Error> {
+        let _guard = self.lock()?;
+        self.ctx.find_objects_init(self.session, template)?;
+        let mut objects = vec![];
+        let result = loop {
+            match self.ctx.find_objects(self.session, FIND_OBJECTS_BATCH_SIZE) {
+                Ok(batch) if batch.is_empty() => break Ok(objects),
+                Ok(batch) => objects.extend(batch),
+                Err(e) => break Err(e.into()),
+            }
+        };
+        self.ctx.find_objects_final(self.session)?;
+        result
+    }
+
+    fn find_object(
+        &self,
+        class: CK_OBJECT_CLASS,
+        label: &str,
+    ) -> Result<Option<CK_OBJECT_HANDLE>, Error> {
+        let mut template = vec![
+            CK_ATTRIBUTE::new(CKA_CLASS).with_ck_ulong(&class),
+            CK_ATTRIBUTE::new(CKA_LABEL).with_string(label),
+        ];
+        if class == CKO_DATA {
+            template.push(CK_ATTRIBUTE::new(CKA_APPLICATION).with_string(APPLICATION));
+        }
+        Ok(self.find_objects(&template)?.into_iter().next())
+    }
+
+    fn get_attribute(
+        &self,
+        object: CK_OBJECT_HANDLE,
+        attribute_type: CK_ATTRIBUTE_TYPE,
+    ) -> Result<Vec<u8>, Error> {
+        // The first call retrieves the length of the value and the second one the value itself.
+        let mut template = vec![CK_ATTRIBUTE::new(attribute_type)];
+        self.ctx
+            .get_attribute_value(self.session, object, &mut template)?;
+        let value = vec![0u8; template[0].ulValueLen as usize];
+        let mut template = vec![CK_ATTRIBUTE::new(attribute_type).with_bytes(&value)];
+        self.ctx
+            .get_attribute_value(self.session, object, &mut template)?;
+        Ok(value)
+    }
+
+    fn read_public_key(&self, object: CK_OBJECT_HANDLE) -> Result<Ed25519PublicKey, Error> {
+        let point = self.get_attribute(object, CKA_EC_POINT)?;
+        let bytes = match point.as_slice() {
+            [DER_OCTET_STRING_TAG, len, bytes @ ..] if *len as usize == bytes.len() => bytes,
+            bytes => bytes,
+        };
+        Ed25519PublicKey::try_from(bytes).map_err(|e| Error::InternalError(e.to_string()))
+    }
+
+    fn public_key_object(&self, label: &str) -> Result<CK_OBJECT_HANDLE, Error> {
+        self.find_object(CKO_PUBLIC_KEY, label)?
+            .ok_or_else(|| Error::KeyNotSet(label.into()))
+    }
+
+    fn private_key_object(&self, label: &str) -> Result<CK_OBJECT_HANDLE, Error> {
+        self.find_object(CKO_PRIVATE_KEY, label)?
+            .ok_or_else(|| Error::KeyNotSet(label.into()))
+    }
+
+    /// Returns the label of the key pair of 'name' whose public key is 'version'.
+    fn version_label(&self, name: &str, version: &Ed25519PublicKey) -> Result<String, Error> {
+        let label = self.ns_name(name);
+        let previous_label = format!("{}{}", label, PREVIOUS_VERSION_SUFFIX);
+        for label in vec![label, previous_label] {
+            if let Some(object) = self.find_object(CKO_PUBLIC_KEY, &label)? {
+                if &self.read_public_key(object)? == version {
+                    return Ok(label);
+                }
+            }
+        }
+        Err(Error::KeyVersionNotFound(version.to_string()))
+    }
+
+    /// Generates a key pair on the token. Its creation time is recorded as the CKA_ID of both
+    /// keys, which also binds the public key to its private key.
+    fn generate_key_pair(&self, label: &str) -> Result<Ed25519PublicKey, Error> {
+        let id = self.time_service.now().to_be_bytes();
+        let mechanism = CK_MECHANISM {
+            mechanism: CKM_EC_EDWARDS_KEY_PAIR_GEN,
+            pParameter: ptr::null_mut(),
+            ulParameterLen: 0,
+        };
+        let public_key_template = vec![
+            CK_ATTRIBUTE::new(CKA_TOKEN).with_bool(&CK_TRUE),
+            CK_ATTRIBUTE::new(CKA_VERIFY).with_bool(&CK_TRUE),
+            CK_ATTRIBUTE::new(CKA_EC_PARAMS).with_bytes(&ED25519_EC_PARAMS),
+            CK_ATTRIBUTE::new(CKA_LABEL).with_string(label),
+            CK_ATTRIBUTE::new(CKA_ID).with_bytes(&id),
+        ];
+        let private_key_template = vec![
+            CK_ATTRIBUTE::new(CKA_TOKEN).with_bool(&CK_TRUE),
+            CK_ATTRIBUTE::new(CKA_PRIVATE).with_bool(&CK_TRUE),
+            CK_ATTRIBUTE::new(CKA_SENSITIVE).with_bool(&CK_TRUE),
+            CK_ATTRIBUTE::new(CKA_EXTRACTABLE).with_bool(&CK_FALSE),
+            CK_ATTRIBUTE::new(CKA_SIGN).with_bool(&CK_TRUE),
+            CK_ATTRIBUTE::new(CKA_LABEL).with_string(label),
+            CK_ATTRIBUTE::new(CKA_ID).with_bytes(&id),
+        ];
+        let (public_key, _private_key) = self.ctx.generate_key_pair(
+            self.session,
+            &mechanism,
+            &public_key_template,
+            &private_key_template,
+        )?;
+        self.read_public_key(public_key)
+    }
+
+    fn sign_bytes(&self, label: &str, message: &[u8]) -> Result<Ed25519Signature, Error> {
+        let private_key = self.private_key_object(label)?;
+        let mechanism = CK_MECHANISM {
+            mechanism: CKM_EDDSA,
+            pParameter: ptr::null_mut(),
+            ulParameterLen: 0,
+        };
+        let signature = {
+            let _guard = self.lock()?;
+            self.ctx.sign_init(self.session, &mechanism, private_key)?;
+            self.ctx.sign(self.session, message)?
+        };
+        Ed25519Signature::try_from(signature.as_slice())
+            .map_err(|e| Error::InternalError(e.to_string()))
+    }
+
+    fn ensure_key_does_not_exist(&self, label: &str) -> Result<(), Error> {
+        match self.find_object(CKO_PUBLIC_KEY, label)? {
+            Some(_) => Err(Error::KeyAlreadyExists(label.into())),
+            None => Ok(()),
+        }
+    }
+}
+
+impl KVStorage for Pkcs11Storage {
+    fn available(&self) -> Result<(), Error> {
+        self.ctx.get_session_info(self.session)?;
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> Result<GetResponse, Error> {
+        let key = self.ns_name(key);
+        let object = self
+            .find_object(CKO_DATA, &key)?
+            .ok_or_else(|| Error::KeyNotSet(key.clone()))?;
+        let value = self.get_attribute(object, CKA_VALUE)?;
+        Ok(serde_json::from_slice(&value)?)
+    }
+
+    fn set(&mut self, key: &str, value: Value) -> Result<(), Error> {
+        let key = self.ns_name(key);
+        let value = serde_json::to_vec(&GetResponse::new(value, self.time_service.now()))?;
+        if let Some(object) = self.find_object(CKO_DATA, &key)? {
+            self.ctx.destroy_object(self.session, object)?;
+        }
+
+        let template = vec![
+            CK_ATTRIBUTE::new(CKA_CLASS).with_ck_ulong(&CKO_DATA),
+            CK_ATTRIBUTE::new(CKA_TOKEN).with_bool(&CK_TRUE),
+            CK_ATTRIBUTE::new(CKA_PRIVATE).with_bool(&CK_TRUE),
+            CK_ATTRIBUTE::new(CKA_APPLICATION).with_string(APPLICATION),
+            CK_ATTRIBUTE::new(CKA_LABEL).with_string(&key),
+            CK_ATTRIBUTE::new(CKA_VALUE).with_bytes(&value),
+        ];
+        self.ctx.create_object(self.session, &template)?;
+        Ok(())
+    }
+
+    /// Note: This destroys every object on the token, regardless of the namespace.
+    #[cfg(any(test, feature = "testing"))]
+    fn reset_and_clear(&mut self) -> Result<(), Error> {
+        let template = vec![CK_ATTRIBUTE::new(CKA_TOKEN).with_bool(&CK_TRUE)];
+        for object in self.find_objects(&template)? {
+            self.ctx.destroy_object(self.session, object)?;
+        }
+        Ok(())
+    }
+}
+
+impl CryptoStorage for Pkcs11Storage {
+    fn create_key(&mut self, name: &str) -> Result<Ed25519PublicKey, Error> {
+        let label = self.ns_name(name);
+        self.ensure_key_does_not_exist(&label)?;
+        self.generate_key_pair(&label)
+    }
+
+    fn export_private_key(&self, name: &str) -> Result<Ed25519PrivateKey, Error> {
+        let label = self.ns_name(name);
+        self.private_key_object(&label)?;
+        Err(Error::KeyNotExportable(label))
+    }
+
+    fn export_private_key_for_version(
+        &self,
+        name: &str,
+        version: Ed25519PublicKey,
+    ) -> Result<Ed25519PrivateKey, Error> {
+        let label = self.version_label(name, &version)?;
+        Err(Error::KeyNotExportable(label))
+    }
+
+    /// Imported keys are as protected as generated ones from then on, but they were exposed
+    /// before being imported.
+    fn import_private_key(&mut self, name: &str, key: Ed25519PrivateKey) -> Result<(), Error> {
+        let label = self.ns_name(name);
+        self.ensure_key_does_not_exist(&label)?;
+
+        let id = self.time_service.now().to_be_bytes();
+        let mut point = vec![DER_OCTET_STRING_TAG, ED25519_PUBLIC_KEY_LENGTH as u8];
+        point.extend_from_slice(&key.public_key().to_bytes());
+        let public_key_template = vec![
+            CK_ATTRIBUTE::new(CKA_CLASS).with_ck_ulong(&CKO_PUBLIC_KEY),
+            CK_ATTRIBUTE::new(CKA_KEY_TYPE).with_ck_ulong(&CKK_EC_EDWARDS),
+            CK_ATTRIBUTE::new(CKA_TOKEN).with_bool(&CK_TRUE),
+            CK_ATTRIBUTE::new(CKA_VERIFY).with_bool(&CK_TRUE),
+            CK_ATTRIBUTE::new(CKA_EC_PARAMS).with_bytes(&ED25519_EC_PARAMS),
+            CK_ATTRIBUTE::new(CKA_EC_POINT).with_bytes(&point),
+            CK_ATTRIBUTE::new(CKA_LABEL).with_string(&label),
+            CK_ATTRIBUTE::new(CKA_ID).with_bytes(&id),
+        ];
+        let private_key_bytes = key.to_bytes();
+        let private_key_template = vec![
+            CK_ATTRIBUTE::new(CKA_CLASS).with_ck_ulong(&CKO_PRIVATE_KEY),
+            CK_ATTRIBUTE::new(CKA_KEY_TYPE).with_ck_ulong(&CKK_EC_EDWARDS),
+            CK_ATTRIBUTE::new(CKA_TOKEN).with_bool(&CK_TRUE),
+            CK_ATTRIBUTE::new(CKA_PRIVATE).with_bool(&CK_TRUE),
+            CK_ATTRIBUTE::new(CKA_SENSITIVE).with_bool(&CK_TRUE),
+            CK_ATTRIBUTE::new(CKA_EXTRACTABLE).with_bool(&CK_FALSE),
+            CK_ATTRIBUTE::new(CKA_SIGN).with_bool(&CK_TRUE),
+            CK_ATTRIBUTE::new(CKA_EC_PARAMS).with_bytes(&ED25519_EC_PARAMS),
+            CK_ATTRIBUTE::new(CKA_VALUE).with_bytes(&private_key_bytes),
+            CK_ATTRIBUTE::new(CKA_LABEL).with_string(&label),
+            CK_ATTRIBUTE::new(CKA_ID).with_bytes(&id),
+        ];
+        self.ctx
+            .create_object(self.session, &private_key_template)?;
+        self.ctx.create_object(self.session, &public_key_template)?;
+        Ok(())
+    }
+
+    fn get_public_key(&self, name: &str) -> Result<PublicKeyResponse, Error> {
+        let label = self.ns_name(name);
+        let object = self.public_key_object(&label)?;
+        let id = self.get_attribute(object, CKA_ID)?;
+        let last_update = <[u8; 8]>::try_from(id.as_slice())
+            .map(u64::from_be_bytes)
+            .map_err(|_| Error::InternalError(format!("Unexpected CKA_ID for key: {}", label)))?;
+
+        Ok(PublicKeyResponse {
+            last_update,
+            public_key: self.read_public_key(object)?,
+        })
+    }
+
+    fn rotate_key(&mut self, name: &str) -> Result<Ed25519PublicKey, Error> {
+        let label = self.ns_name(name);
+        let previous_label = format!("{}{}", label, PREVIOUS_VERSION_SUFFIX);
+        let public_key = self.public_key_object(&label)?;
+        let private_key = self.private_key_object(&label)?;
+
+        for class in &[CKO_PUBLIC_KEY, CKO_PRIVATE_KEY] {
+            if let Some(object) = self.find_object(*class, &previous_label)? {
+                self.ctx.destroy_object(self.session, object)?;
+            }
+        }
+        let template = vec![CK_ATTRIBUTE::new(CKA_LABEL).with_string(&previous_label)];
+        self.ctx
+            .set_attribute_value(self.session, public_key, &template)?;
+        self.ctx
+            .set_attribute_value(self.session, private_key, &template)?;
+
+        self.generate_key_pair(&label)
+    }
+
+    fn sign<T: CryptoHash + Serialize>(
+        &mut self,
+        name: &str,
+        message: &T,
+    ) -> Result<Ed25519Signature, Error> {
+        let label = self.ns_name(name);
+        self.sign_bytes(&label, &signing_message(message))
+    }
+
+    fn sign_using_version<T: CryptoHash + Serialize>(
+        &mut self,
+        name: &str,
+        version: Ed25519PublicKey,
+        message: &T,
+    ) -> Result<Ed25519Signature, Error> {
+        let label = self.version_label(name, &version)?;
+        self.sign_bytes(&label, &signing_message(message))
+    }
+}
+
+fn signing_message<T: CryptoHash + Serialize>(message: &T) -> Vec<u8> {
+    let mut bytes = <T::Hasher as libra_crypto::hash::CryptoHasher>::seed().to_vec();
+    lcs::serialize_into(&mut bytes, &message)
+        .map_err(|_| libra_crypto::traits::CryptoMaterialError::SerializationError)
+        .expect("Serialization of signable material should not fail.");
+    bytes
+}
@@ -0,0 +1,142 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sealed backups protect exported storage data with AES-256-GCM, so that keys can be moved
+//! between hosts without ever being written out in plaintext. The sealing key is either derived
+//! from an operator-provided passphrase (PBKDF2-HMAC-SHA3-256 over a random salt) or provided
+//! directly, e.g., as a data key generated by a KMS. A sealed backup is the LCS serialization of:
+//!
+//! ```text
+//! version || salt || nonce || ciphertext || tag
+//! ```
+//!
+//! where the version and salt are authenticated as associated data.
+
+use crate::Error;
+use aes_gcm::{
+    aead::{generic_array::GenericArray, Aead, NewAead, Payload},
+    Aes256Gcm,
+};
+use hmac::Hmac;
+use libra_crypto::{compat::Sha3_256, hkdf::Hkdf};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+/// The current version of the sealed backup format.
+const SEALED_VERSION: u8 = 1;
+/// The length in bytes of the AES-256-GCM key.
+pub const SEALING_KEY_LEN: usize = 32;
+/// The length in bytes of the random salt used for key derivation.
+const SALT_LEN: usize = 32;
+/// The length in bytes of the AES-256-GCM nonce.
+const NONCE_LEN: usize = 12;
+/// The number of PBKDF2 iterations used to derive a sealing key from a passphrase.
+const PBKDF2_ITERATIONS: u32 = 100_000;
+/// The HKDF info used to derive a sealing key from a provided key.
+const HKDF_INFO: &[u8] = b"LIBRA SECURE STORAGE: sealed backup";
+
+/// The secret under which a backup is sealed.
+pub enum SealingKey {
+    /// A passphrase chosen by the operator, stretched into a sealing key.
+    Passphrase(String),
+    /// A high-entropy key provided by the operator, e.g., a data key generated by a KMS.
+    Key([u8; SEALING_KEY_LEN]),
+}
+
+impl SealingKey {
+    fn derive(&self, salt: &[u8]) -> Result<[u8; SEALING_KEY_LEN], Error> {
+        let mut derived_key = [0u8; SEALING_KEY_LEN];
+        match self {
+            SealingKey::Passphrase(passphrase) => pbkdf2::pbkdf2::<Hmac<Sha3_256>>(
+                passphrase.as_bytes(),
+                salt,
+                PBKDF2_ITERATIONS,
+                &mut derived_key,
+            ),
+            SealingKey::Key(key) => {
+                let okm = Hkdf::<Sha3_256>::extract_then_expand(
+                    Some(salt),
+                    key,
+                    Some(HKDF_INFO),
+                    SEALING_KEY_LEN,
+                )
+                .map_err(|e| Error::InternalError(e.to_string()))?;
+                derived_key.copy_from_slice(&okm);
+            }
+        }
+        Ok(derived_key)
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+struct SealedBackup {
+    version: u8,
+    salt: Vec<u8>,
+    nonce: Vec<u8>,
+    ciphertext: Vec<u8>,
+}
+
+impl SealedBackup {
+    fn associated_data(version: u8, salt: &[u8]) -> Vec<u8> {
+        let mut associated_data = vec![version];
+        associated_data.extend_from_slice(salt);
+        associated_data
+    }
+}
+
+/// Seals `plaintext` under `sealing_key`, returning the serialized sealed backup.
+pub fn seal(plaintext: &[u8], sealing_key: &SealingKey) -> Result<Vec<u8>, Error> {
+    let mut salt = vec![0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let mut nonce = vec![0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+
+    let key = sealing_key.derive(&salt)?;
+    let aead = Aes256Gcm::new(GenericArray::from_slice(&key));
+    let associated_data = SealedBackup::associated_data(SEALED_VERSION, &salt);
+    let ciphertext = aead
+        .encrypt(
+            GenericArray::from_slice(&nonce),
+            Payload {
+                msg: plaintext,
+                aad: &associated_data,
+            },
+        )
+        .map_err(|_| Error::InternalError("Unable to seal backup".into()))?;
+
+    Ok(lcs::to_bytes(&SealedBackup {
+        version: SEALED_VERSION,
+        salt,
+        nonce,
+        ciphertext,
+    })?)
+}
+
+/// Unseals a serialized sealed backup with `sealing_key`, returning the plaintext. Fails if the
+/// key is wrong or if the backup has been tampered with.
+pub fn unseal(sealed: &[u8], sealing_key: &SealingKey) -> Result<Vec<u8>, Error> {
+    let backup: SealedBackup = lcs::from_bytes(sealed)?;
+    if backup.version != SEALED_VERSION {
+        return Err(Error::SerializationError(format!(
+            "Unsupported sealed backup version: {}",
+            backup.version
+        )));
+    }
+    if backup.nonce.len() != NONCE_LEN {
+        return Err(Error::SerializationError(
+            "Invalid sealed backup nonce".into(),
+        ));
+    }
+
+    let key = sealing_key.derive(&backup.salt)?;
+    let aead = Aes256Gcm::new(GenericArray::from_slice(&key));
+    let associated_data = SealedBackup::associated_data(backup.version, &backup.salt);
+    aead.decrypt(
+        GenericArray::from_slice(&backup.nonce),
+        Payload {
+            msg: &backup.ciphertext,
+            aad: &associated_data,
+        },
+    )
+    .map_err(|_| Error::PermissionDenied)
+}
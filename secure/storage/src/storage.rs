@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
     CryptoStorage, Error, GetResponse, GitHubStorage, InMemoryStorage, KVStorage,
-    NamespacedStorage, OnDiskStorage, PublicKeyResponse, Value, VaultStorage,
+    NamespacedStorage, OnDiskStorage, Pkcs11Storage, PublicKeyResponse, Value, VaultStorage,
 };
 use enum_dispatch::enum_dispatch;
 use libra_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature};
@@ -20,4 +20,5 @@ pub enum Storage {
     InMemoryStorage(InMemoryStorage),
     NamespacedStorage(NamespacedStorage),
     OnDiskStorage(OnDiskStorage),
+    Pkcs11Storage(Pkcs11Storage),
 }
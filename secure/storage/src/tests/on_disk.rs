@@ -1,7 +1,10 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{tests::suite, OnDiskStorage, Storage};
+use crate::{
+    tests::suite, CryptoStorage, Error, KVStorage, OnDiskStorage, SealingKey, Storage, Value,
+    SEALING_KEY_LEN,
+};
 use libra_temppath::TempPath;
 
 #[test]
@@ -10,3 +13,65 @@ fn on_disk() {
     let mut storage = Storage::from(OnDiskStorage::new(path_buf));
     suite::execute_all_storage_tests(&mut storage);
 }
+
+#[test]
+fn on_disk_sealed_export_import() {
+    let mut source = OnDiskStorage::new(TempPath::new().path().to_path_buf());
+    source.set("string", Value::String("value".into())).unwrap();
+    let public_key = source.create_key("key").unwrap();
+    let exported = source.get("key").unwrap();
+
+    let passphrase = SealingKey::Passphrase("correct horse battery staple".into());
+    let backup = source.export_sealed(&passphrase).unwrap();
+
+    // The backup can't be imported without the right passphrase
+    let mut target = OnDiskStorage::new(TempPath::new().path().to_path_buf());
+    let wrong_passphrase = SealingKey::Passphrase("wrong horse".into());
+    assert_eq!(
+        target.import_sealed(&backup, &wrong_passphrase),
+        Err(Error::PermissionDenied)
+    );
+    assert!(target.get("string").is_err());
+
+    // With the right passphrase, all values are imported as is
+    target.import_sealed(&backup, &passphrase).unwrap();
+    assert_eq!(target.get("key").unwrap(), exported);
+    assert_eq!(target.get_public_key("key").unwrap().public_key, public_key);
+    assert_eq!(
+        target.get("string").unwrap().value,
+        Value::String("value".into())
+    );
+
+    // Importing never overwrites existing values
+    assert!(matches!(
+        target.import_sealed(&backup, &passphrase),
+        Err(Error::KeyAlreadyExists(_))
+    ));
+}
+
+#[test]
+fn on_disk_sealed_export_import_with_key() {
+    let mut source = OnDiskStorage::new(TempPath::new().path().to_path_buf());
+    source.create_key("key").unwrap();
+
+    let sealing_key = SealingKey::Key([7u8; SEALING_KEY_LEN]);
+    let mut backup = source.export_sealed(&sealing_key).unwrap();
+
+    let mut target = OnDiskStorage::new(TempPath::new().path().to_path_buf());
+    assert_eq!(
+        target.import_sealed(&backup, &SealingKey::Key([8u8; SEALING_KEY_LEN])),
+        Err(Error::PermissionDenied)
+    );
+
+    // A tampered backup is rejected
+    let last = backup.len() - 1;
+    backup[last] ^= 1;
+    assert_eq!(
+        target.import_sealed(&backup, &sealing_key),
+        Err(Error::PermissionDenied)
+    );
+    backup[last] ^= 1;
+
+    target.import_sealed(&backup, &sealing_key).unwrap();
+    assert_eq!(target.get("key").unwrap(), source.get("key").unwrap());
+}
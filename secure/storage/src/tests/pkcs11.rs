@@ -0,0 +1,85 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{tests::suite, CryptoStorage, Error, KVStorage, Pkcs11Storage, Storage};
+use libra_crypto::{test_utils::TestLibraCrypto, Signature};
+use std::path::Path;
+
+/// Pkcs11Storage test constants
+const SOFTHSM_LIBRARY: &str = "/usr/lib/softhsm/libsofthsm2.so";
+const TOKEN_LABEL: &str = "libra";
+const USER_PIN: &str = "1234";
+const NAMESPACE_1: &str = "namespace_1";
+const NAMESPACE_2: &str = "namespace_2";
+
+/// This holds the canonical list of PKCS#11 storage tests. As with vault, each test uses the same
+/// token and resetting the storage clears the whole token, so the tests are run sequentially.
+const PKCS11_TESTS: &[fn()] = &[
+    test_suite_multiple_namespaces,
+    test_suite_no_namespaces,
+    test_private_keys_are_not_exportable,
+];
+
+/// A test for verifying Pkcs11Storage properly implements the LibraSecureStorage API. This test
+/// depends on SoftHSM, with a token initialized by running:
+/// `softhsm2-util --init-token --free --label libra --so-pin 1234 --pin 1234`
+#[test]
+#[ignore]
+fn execute_storage_tests_pkcs11() {
+    let mut storage = create_pkcs11_with_namespace(None);
+    storage.reset_and_clear().unwrap();
+
+    for test in PKCS11_TESTS.iter() {
+        test();
+        storage.reset_and_clear().unwrap();
+    }
+}
+
+/// Runs the test suite on a Pkcs11Storage instance that does not use distinct namespaces
+fn test_suite_no_namespaces() {
+    let mut storage = Storage::from(create_pkcs11_with_namespace(None));
+    suite::execute_storage_tests_without_key_export(&mut storage);
+}
+
+/// Runs the test suite on Pkcs11Storage instances that use distinct namespaces on the same token.
+/// Tests should be able to run across namespaces without interfering.
+fn test_suite_multiple_namespaces() {
+    let mut storage_1 = Storage::from(create_pkcs11_with_namespace(Some(NAMESPACE_1.into())));
+    let mut storage_2 = Storage::from(create_pkcs11_with_namespace(Some(NAMESPACE_2.into())));
+
+    suite::execute_storage_tests_without_key_export(&mut storage_1);
+    suite::execute_storage_tests_without_key_export(&mut storage_2);
+}
+
+/// Creates a Pkcs11Storage instance logged into the SoftHSM test token. If a namespace is
+/// specified, the instance will perform all storage operations under that namespace.
+fn create_pkcs11_with_namespace(namespace: Option<String>) -> Pkcs11Storage {
+    Pkcs11Storage::new(Path::new(SOFTHSM_LIBRARY), TOKEN_LABEL, USER_PIN, namespace).unwrap()
+}
+
+/// Ensures that private keys never leave the token, while all versions remain usable for signing.
+fn test_private_keys_are_not_exportable() {
+    let mut storage = create_pkcs11_with_namespace(None);
+    let key_name = "consensus";
+
+    let public_key = storage.create_key(key_name).unwrap();
+    assert_eq!(
+        storage.export_private_key(key_name).unwrap_err(),
+        Error::KeyNotExportable(key_name.into())
+    );
+
+    let rotated_public_key = storage.rotate_key(key_name).unwrap();
+    assert_ne!(public_key, rotated_public_key);
+    assert!(matches!(
+        storage.export_private_key_for_version(key_name, public_key.clone()),
+        Err(Error::KeyNotExportable(_))
+    ));
+
+    let message = TestLibraCrypto("Hello, World".to_string());
+    let signature = storage
+        .sign_using_version(key_name, public_key.clone(), &message)
+        .unwrap();
+    signature.verify(&message, &public_key).unwrap();
+    let signature = storage.sign(key_name, &message).unwrap();
+    signature.verify(&message, &rotated_public_key).unwrap();
+}
@@ -1,12 +1,17 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{CryptoStorage, Error, KVStorage, Storage, Value};
+use crate::{CryptoStorage, Error, KVStorage, Storage, TrustedStateStorage, Value};
 
 use libra_crypto::{
     ed25519::Ed25519PrivateKey, test_utils::TestLibraCrypto, HashValue, PrivateKey, Signature,
     Uniform,
 };
+use libra_types::{
+    block_info::BlockInfo, epoch_state::EpochState, ledger_info::LedgerInfo, transaction::Version,
+    trusted_state::TrustedState,
+};
+use std::convert::TryFrom;
 
 /// This suite contains tests for secure storage backends. We test the correct functionality
 /// of both key/value and cryptographic operations for storage implementations. All storage backend
@@ -19,9 +24,9 @@ use libra_crypto::{
 /// tests cannot currently be run in parallel, as each test uses the same vault instance.
 const STORAGE_TESTS: &[fn(&mut Storage)] = &[
     test_set_reset_get,
+    test_batch_set,
     test_create_and_get_non_existent_version,
     test_create_get_key_pair,
-    test_create_key_pair_and_perform_rotations,
     test_create_sign_rotate_sign,
     test_ensure_storage_is_available,
     test_get_non_existent,
@@ -29,17 +34,32 @@ const STORAGE_TESTS: &[fn(&mut Storage)] = &[
     test_get_uncreated_key_pair,
     test_hash_value,
     test_incremental_timestamp,
-    test_import_key,
+    test_trusted_state_ratchet,
     test_verify_incorrect_value_types,
 ];
 
+/// The secure storage tests that export private keys, which backends keeping their keys in
+/// hardware (e.g., PKCS#11) do not support.
+const KEY_EXPORT_TESTS: &[fn(&mut Storage)] =
+    &[test_create_key_pair_and_perform_rotations, test_import_key];
+
 /// Storage data constants for testing purposes.
 const CRYPTO_KEY: &str = "Private_Key";
 const U64_KEY: &str = "U64_Key";
+const STRING_KEY: &str = "String_Key";
 const CRYPTO_NAME: &str = "Test_Key_Name";
 
 /// Executes all storage tests on a given storage backend.
 pub fn execute_all_storage_tests(storage: &mut Storage) {
+    execute_storage_tests_without_key_export(storage);
+    for test in KEY_EXPORT_TESTS.iter() {
+        test(storage);
+        storage.reset_and_clear().unwrap();
+    }
+}
+
+/// Executes all storage tests that do not export private keys on a given storage backend.
+pub fn execute_storage_tests_without_key_export(storage: &mut Storage) {
     for test in STORAGE_TESTS.iter() {
         test(storage);
         storage.reset_and_clear().unwrap();
@@ -119,6 +139,44 @@ fn test_get_set(storage: &mut Storage) {
     );
 }
 
+/// This test sets multiple key/value pairs in a single batch, including an update of an existing
+/// key, and ensures that all values can be retrieved afterward.
+fn test_batch_set(storage: &mut Storage) {
+    let crypto_private = Ed25519PrivateKey::generate_for_testing();
+    let string = "batch";
+
+    storage.set(U64_KEY, Value::U64(1)).unwrap();
+    storage
+        .batch_set(vec![
+            (
+                CRYPTO_KEY.to_string(),
+                Value::Ed25519PrivateKey(crypto_private.clone()),
+            ),
+            (STRING_KEY.to_string(), Value::String(string.into())),
+            (U64_KEY.to_string(), Value::U64(2)),
+        ])
+        .unwrap();
+
+    assert_eq!(
+        storage
+            .get(CRYPTO_KEY)
+            .unwrap()
+            .value
+            .ed25519_private_key()
+            .unwrap(),
+        crypto_private
+    );
+    assert_eq!(
+        storage.get(STRING_KEY).unwrap().value.string().unwrap(),
+        string
+    );
+    assert_eq!(storage.get(U64_KEY).unwrap().value.u64().unwrap(), 2);
+
+    // An empty batch is a no-op
+    storage.batch_set(vec![]).unwrap();
+    assert_eq!(storage.get(U64_KEY).unwrap().value.u64().unwrap(), 2);
+}
+
 /// This test ensures that a key can reasonably be imported.
 fn test_import_key(storage: &mut Storage) {
     let key_name = "key";
@@ -310,3 +368,45 @@ fn test_incremental_timestamp(storage: &mut Storage) {
     assert_ne!(first.value, second.value);
     assert!(first.last_update < second.last_update);
 }
+
+/// This test stores a trusted state and ensures that it can only be ratcheted forward.
+fn test_trusted_state_ratchet(storage: &mut Storage) {
+    let trusted_state_at = |epoch: u64, version: Version| {
+        let mut epoch_state = EpochState::empty();
+        epoch_state.epoch = epoch + 1;
+        let ledger_info = LedgerInfo::new(
+            BlockInfo::new(
+                epoch,
+                0,
+                HashValue::zero(),
+                HashValue::zero(),
+                version,
+                0,
+                Some(epoch_state),
+            ),
+            HashValue::zero(),
+        );
+        TrustedState::try_from(&ledger_info).unwrap()
+    };
+
+    assert_eq!(storage.trusted_state().unwrap(), None);
+
+    let trusted_state = trusted_state_at(1, 5);
+    storage.ratchet_trusted_state(&trusted_state).unwrap();
+    assert_eq!(
+        storage.trusted_state().unwrap(),
+        Some(trusted_state.clone())
+    );
+
+    for stale_state in &[trusted_state_at(1, 3), trusted_state_at(0, 10)] {
+        assert!(matches!(
+            storage.ratchet_trusted_state(stale_state).unwrap_err(),
+            Error::TrustedStateRegression(_)
+        ));
+    }
+    assert_eq!(storage.trusted_state().unwrap(), Some(trusted_state));
+
+    let trusted_state = trusted_state_at(2, 10);
+    storage.ratchet_trusted_state(&trusted_state).unwrap();
+    assert_eq!(storage.trusted_state().unwrap(), Some(trusted_state));
+}
@@ -3,10 +3,11 @@
 
 use crate::{
     tests::suite,
-    vault::{VaultEngine, VaultStorage},
+    vault::{RetryPolicy, VaultEngine, VaultStorage},
     Capability, CryptoStorage, Error, Identity, KVStorage, Permission, Policy, Storage, Value,
 };
 use libra_crypto::{test_utils::TestLibraCrypto, Signature};
+use std::time::Duration;
 
 /// VaultStorage test constants
 const VAULT_HOST: &str = "http://localhost:8200";
@@ -24,6 +25,7 @@ const VAULT_TESTS: &[fn()] = &[
     test_suite_no_namespaces,
     test_vault_crypto_policies,
     test_vault_key_value_policies,
+    test_vault_token_renewal,
 ];
 
 /// A test for verifying VaultStorage properly implements the LibraSecureStorage API and enforces
@@ -62,7 +64,29 @@ fn test_suite_multiple_namespaces() {
 /// Creates and initializes a VaultStorage instance for testing. If a namespace is specified, the
 /// instance will perform all storage operations under that namespace.
 fn create_vault_with_namespace(namespace: Option<String>) -> VaultStorage {
-    VaultStorage::new(VAULT_HOST.into(), VAULT_ROOT_TOKEN.into(), namespace, None)
+    create_vault_storage(VAULT_ROOT_TOKEN.into(), namespace)
+}
+
+/// Creates a VaultStorage instance for testing that accesses Vault with the given token.
+fn create_vault_storage(token: String, namespace: Option<String>) -> VaultStorage {
+    create_renewing_vault_storage(token, namespace, None)
+}
+
+/// Creates a VaultStorage instance for testing that renews its token to the given TTL.
+fn create_renewing_vault_storage(
+    token: String,
+    namespace: Option<String>,
+    renew_ttl_secs: Option<u32>,
+) -> VaultStorage {
+    VaultStorage::new(
+        VAULT_HOST.into(),
+        token,
+        namespace,
+        None,
+        None,
+        renew_ttl_secs,
+        RetryPolicy::default(),
+    )
 }
 
 /// Initializes test policies for a VaultStorage instance and checks the instance is
@@ -125,14 +149,14 @@ fn test_vault_key_value_policies() {
     assert_eq!(storage.get("full").unwrap().value, Value::U64(4));
 
     let writer_token = storage.create_token(vec![&writer]).unwrap();
-    let mut writer = VaultStorage::new(VAULT_HOST.into(), writer_token, None, None);
+    let mut writer = create_vault_storage(writer_token, None);
     assert_eq!(writer.get("anyone").unwrap().value, Value::U64(1));
     assert_eq!(writer.get("root"), Err(Error::PermissionDenied));
     assert_eq!(writer.get("partial").unwrap().value, Value::U64(3));
     assert_eq!(writer.get("full").unwrap().value, Value::U64(4));
 
     let reader_token = storage.create_token(vec![&reader]).unwrap();
-    let mut reader = VaultStorage::new(VAULT_HOST.into(), reader_token, None, None);
+    let mut reader = create_vault_storage(reader_token, None);
     assert_eq!(reader.get("anyone").unwrap().value, Value::U64(1));
     assert_eq!(reader.get("root"), Err(Error::PermissionDenied));
     assert_eq!(reader.get("partial").unwrap().value, Value::U64(3));
@@ -200,7 +224,7 @@ fn test_vault_crypto_policies() {
 
     // Verify exporter policy
     let exporter_token = storage.create_token(vec![&exporter]).unwrap();
-    let mut exporter_store = VaultStorage::new(VAULT_HOST.into(), exporter_token, None, None);
+    let mut exporter_store = create_vault_storage(exporter_token, None);
     exporter_store.export_private_key(key_name).unwrap();
     exporter_store.get_public_key(key_name).unwrap_err();
     exporter_store.rotate_key(key_name).unwrap_err();
@@ -208,7 +232,7 @@ fn test_vault_crypto_policies() {
 
     // Verify noone policy
     let noone_token = storage.create_token(vec![&noone]).unwrap();
-    let mut noone_store = VaultStorage::new(VAULT_HOST.into(), noone_token, None, None);
+    let mut noone_store = create_vault_storage(noone_token, None);
     noone_store.export_private_key(key_name).unwrap_err();
     noone_store.get_public_key(key_name).unwrap_err();
     noone_store.rotate_key(key_name).unwrap_err();
@@ -216,7 +240,7 @@ fn test_vault_crypto_policies() {
 
     // Verify reader policy
     let reader_token = storage.create_token(vec![&reader]).unwrap();
-    let mut reader_store = VaultStorage::new(VAULT_HOST.into(), reader_token, None, None);
+    let mut reader_store = create_vault_storage(reader_token, None);
     reader_store.export_private_key(key_name).unwrap_err();
     assert_eq!(
         reader_store.get_public_key(key_name).unwrap().public_key,
@@ -227,7 +251,7 @@ fn test_vault_crypto_policies() {
 
     // Verify rotater policy
     let rotater_token = storage.create_token(vec![&rotater]).unwrap();
-    let mut rotater_store = VaultStorage::new(VAULT_HOST.into(), rotater_token, None, None);
+    let mut rotater_store = create_vault_storage(rotater_token, None);
     rotater_store.export_private_key(key_name).unwrap_err();
     assert_eq!(
         rotater_store.get_public_key(key_name).unwrap().public_key,
@@ -240,7 +264,7 @@ fn test_vault_crypto_policies() {
 
     // Verify signer policy
     let signer_token = storage.create_token(vec![&signer]).unwrap();
-    let mut signer_store = VaultStorage::new(VAULT_HOST.into(), signer_token, None, None);
+    let mut signer_store = create_vault_storage(signer_token, None);
     signer_store.export_private_key(key_name).unwrap_err();
     signer_store.get_public_key(key_name).unwrap_err();
     signer_store.rotate_key(key_name).unwrap_err();
@@ -248,3 +272,34 @@ fn test_vault_crypto_policies() {
     signature.verify(&message, &pubkey).unwrap_err();
     signature.verify(&message, &new_pubkey).unwrap();
 }
+
+/// Verifies that a VaultStorage instance configured for token renewal renews its token before
+/// accessing Vault and can continue to operate afterward.
+fn test_vault_token_renewal() {
+    let mut storage = create_vault_with_namespace(None);
+    storage.set("anyone", Value::U64(1)).unwrap();
+    storage
+        .set_policies("anyone", &VaultEngine::KVSecrets, &Policy::public())
+        .unwrap();
+
+    let token = storage.create_token(vec![]).unwrap();
+    let renewing = create_renewing_vault_storage(token, None, Some(3600));
+    assert_eq!(renewing.get("anyone").unwrap().value, Value::U64(1));
+    assert_eq!(renewing.get("anyone").unwrap().value, Value::U64(1));
+    assert!(renewing.client.renew_token_self(None).unwrap() > 0);
+}
+
+#[test]
+fn test_retry_policy_delays() {
+    let policy = RetryPolicy {
+        max_retries: 10,
+        initial_delay_ms: 100,
+        max_delay_ms: 1_000,
+    };
+    assert_eq!(policy.delay(1), Duration::from_millis(100));
+    assert_eq!(policy.delay(2), Duration::from_millis(200));
+    assert_eq!(policy.delay(4), Duration::from_millis(800));
+    assert_eq!(policy.delay(5), Duration::from_millis(1_000));
+    assert_eq!(policy.delay(u32::MAX), Duration::from_millis(1_000));
+    assert_eq!(RetryPolicy::none().max_retries, 0);
+}
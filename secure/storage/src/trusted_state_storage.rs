@@ -0,0 +1,37 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{Error, KVStorage, Value};
+use libra_global_constants::TRUSTED_STATE;
+use libra_types::trusted_state::TrustedState;
+
+/// Persists the `TrustedState` of a light client (e.g., the CLI) so that it can resume from its
+/// latest verified state after a restart instead of starting over from its waypoint. A stored
+/// trusted state only ever moves forward: attempts to replace it with an older one are rejected.
+pub trait TrustedStateStorage {
+    /// Returns the stored trusted state, or `None` if no trusted state has been stored yet.
+    fn trusted_state(&self) -> Result<Option<TrustedState>, Error>;
+
+    /// Replaces the stored trusted state with `new_state`, failing if `new_state` is older than
+    /// the one already stored.
+    fn ratchet_trusted_state(&mut self, new_state: &TrustedState) -> Result<(), Error>;
+}
+
+impl<T: KVStorage> TrustedStateStorage for T {
+    fn trusted_state(&self) -> Result<Option<TrustedState>, Error> {
+        match self.get(TRUSTED_STATE) {
+            Ok(response) => Ok(Some(lcs::from_bytes(&response.value.bytes()?)?)),
+            Err(Error::KeyNotSet(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn ratchet_trusted_state(&mut self, new_state: &TrustedState) -> Result<(), Error> {
+        if let Some(trusted_state) = self.trusted_state()? {
+            trusted_state
+                .ensure_not_regressed_by(new_state)
+                .map_err(|e| Error::TrustedStateRegression(e.to_string()))?;
+        }
+        self.set(TRUSTED_STATE, Value::Bytes(lcs::to_bytes(new_state)?))
+    }
+}
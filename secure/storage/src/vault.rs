@@ -10,11 +10,71 @@ use libra_crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
     hash::CryptoHash,
 };
+use libra_secure_time::{RealTimeService, TimeService};
 use libra_vault_client::{self as vault, Client};
-use serde::ser::Serialize;
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    thread,
+    time::Duration,
+};
 
 const LIBRA_DEFAULT: &str = "libra_default";
 
+/// Describes how failed requests to Vault are retried. Only failures that may be transient, such
+/// as network errors, throttling, or server errors (e.g., a sealed Vault), are retried. The n-th
+/// retry waits initial_delay_ms * 2^(n - 1), capped at max_delay_ms.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetryPolicy {
+    /// The number of retries after the initial attempt, 0 disables retries
+    pub max_retries: u32,
+    /// The delay before the first retry
+    pub initial_delay_ms: u64,
+    /// The upper bound on the delay between two attempts
+    pub max_delay_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_delay_ms: 100,
+            max_delay_ms: 5_000,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the delay to wait before the given retry, starting at 1.
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = 1u64
+            .checked_shl(retry.saturating_sub(1))
+            .unwrap_or(u64::MAX);
+        let delay_ms = self
+            .initial_delay_ms
+            .saturating_mul(factor)
+            .min(self.max_delay_ms);
+        Duration::from_millis(delay_ms)
+    }
+
+    fn should_retry(error: &vault::Error) -> bool {
+        match error {
+            vault::Error::InternalError(_) => true,
+            vault::Error::HttpError(status, _) => *status == 429 || *status >= 500,
+            _ => false,
+        }
+    }
+}
+
 /// VaultStorage utilizes Vault for maintaining encrypted, authenticated data for Libra. This
 /// version currently matches the behavior of OnDiskStorage and InMemoryStorage. In the future,
 /// Vault will be able to create keys, sign messages, and handle permissions across different
@@ -22,9 +82,17 @@ const LIBRA_DEFAULT: &str = "libra_default";
 /// Version 2 (https://www.vaultproject.io/api/secret/kv/kv-v2.html). So while Libra Secure Storage
 /// calls pointers to data keys, Vault has actually a secret that contains multiple key value
 /// pairs.
+///
+/// If a renewal TTL is specified, the token is renewed for that TTL before its first use and then
+/// again whenever half of the granted TTL has elapsed, so that long-running processes keep access
+/// to Vault as long as the token's max TTL permits.
 pub struct VaultStorage {
     pub client: Client,
     namespace: Option<String>,
+    renew_ttl_secs: Option<u32>,
+    next_renewal: AtomicU64,
+    retry_policy: RetryPolicy,
+    time_service: RealTimeService,
 }
 
 impl VaultStorage {
@@ -33,21 +101,79 @@ impl VaultStorage {
         token: String,
         namespace: Option<String>,
         certificate: Option<String>,
+        enterprise_namespace: Option<String>,
+        renew_ttl_secs: Option<u32>,
+        retry_policy: RetryPolicy,
     ) -> Self {
         Self {
-            client: Client::new(host, token, certificate),
+            client: Client::new(host, token, enterprise_namespace, certificate),
             namespace,
+            renew_ttl_secs,
+            next_renewal: AtomicU64::new(0),
+            retry_policy,
+            time_service: RealTimeService::new(),
         }
     }
 
+    /// Executes the operation against the client, renewing the token first if it is due and
+    /// retrying transient failures according to the retry policy.
+    fn execute<T, F>(&self, operation: F) -> Result<T, Error>
+    where
+        F: Fn(&Client) -> Result<T, vault::Error>,
+    {
+        self.renew_token_if_needed()?;
+        Ok(self.retry(operation)?)
+    }
+
+    fn retry<T, F>(&self, operation: F) -> Result<T, vault::Error>
+    where
+        F: Fn(&Client) -> Result<T, vault::Error>,
+    {
+        let mut retry = 0;
+        loop {
+            match operation(&self.client) {
+                Err(e)
+                    if retry < self.retry_policy.max_retries && RetryPolicy::should_retry(&e) =>
+                {
+                    retry += 1;
+                    thread::sleep(self.retry_policy.delay(retry));
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn renew_token_if_needed(&self) -> Result<(), Error> {
+        let ttl = match self.renew_ttl_secs {
+            Some(ttl) => ttl,
+            None => return Ok(()),
+        };
+
+        let now = self.time_service.now();
+        if now < self.next_renewal.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        let granted_ttl = self.retry(|client| client.renew_token_self(Some(ttl)))?;
+        // A token without a TTL never expires, so there is no need to renew it again.
+        let next_renewal = if granted_ttl == 0 {
+            u64::MAX
+        } else {
+            now + u64::from(granted_ttl / 2)
+        };
+        self.next_renewal.store(next_renewal, Ordering::Relaxed);
+        Ok(())
+    }
+
     #[cfg(any(test, feature = "testing"))]
     fn reset_kv(&self, path: &str) -> Result<(), Error> {
-        let secrets = self.client.list_secrets(path)?;
+        let secrets = self.execute(|client| client.list_secrets(path))?;
         for secret in secrets {
             if secret.ends_with('/') {
                 self.reset_kv(&secret)?;
             } else {
-                self.client.delete_secret(&format!("{}{}", path, secret))?;
+                let secret = format!("{}{}", path, secret);
+                self.execute(|client| client.delete_secret(&secret))?;
             }
         }
         Ok(())
@@ -55,21 +181,21 @@ impl VaultStorage {
 
     #[cfg(any(test, feature = "testing"))]
     fn reset_crypto(&self) -> Result<(), Error> {
-        let keys = match self.client.list_keys() {
+        let keys = match self.retry(|client| client.list_keys()) {
             Ok(keys) => keys,
             // No keys were found, so there's no need to reset.
             Err(libra_vault_client::Error::NotFound(_, _)) => return Ok(()),
             Err(e) => return Err(e.into()),
         };
         for key in keys {
-            self.client.delete_key(&key)?;
+            self.execute(|client| client.delete_key(&key))?;
         }
         Ok(())
     }
 
     #[cfg(any(test, feature = "testing"))]
     fn reset_policies(&self) -> Result<(), Error> {
-        let policies = match self.client.list_policies() {
+        let policies = match self.retry(|client| client.list_policies()) {
             Ok(policies) => policies,
             Err(libra_vault_client::Error::NotFound(_, _)) => return Ok(()),
             Err(e) => return Err(e.into()),
@@ -81,7 +207,7 @@ impl VaultStorage {
                 continue;
             }
 
-            self.client.delete_policy(&policy)?;
+            self.execute(|client| client.delete_policy(&policy))?;
         }
         Ok(())
     }
@@ -91,10 +217,9 @@ impl VaultStorage {
         policies.push(LIBRA_DEFAULT);
         let result = if let Some(ns) = &self.namespace {
             let policies: Vec<_> = policies.iter().map(|p| format!("{}/{}", ns, p)).collect();
-            self.client
-                .create_token(policies.iter().map(|p| &**p).collect())?
+            self.execute(|client| client.create_token(policies.iter().map(|p| &**p).collect()))?
         } else {
-            self.client.create_token(policies)?
+            self.execute(|client| client.create_token(policies.clone()))?
         };
         Ok(result)
     }
@@ -111,7 +236,9 @@ impl VaultStorage {
     ) -> Result<(), Error> {
         let policy_name = self.name(policy_name, engine);
 
-        let mut vault_policy = self.client.read_policy(&policy_name).unwrap_or_default();
+        let mut vault_policy = self
+            .execute(|client| client.read_policy(&policy_name))
+            .unwrap_or_default();
         let mut core_capabilities = Vec::new();
         for capability in capabilities {
             match capability {
@@ -137,12 +264,12 @@ impl VaultStorage {
 
         let path = format!("{}/{}", engine.to_policy_path(), self.name(key, engine));
         vault_policy.add_policy(&path, core_capabilities);
-        self.client.set_policy(&policy_name, &vault_policy)?;
+        self.execute(|client| client.set_policy(&policy_name, &vault_policy))?;
         Ok(())
     }
 
     fn key_version(&self, name: &str, version: &Ed25519PublicKey) -> Result<u32, Error> {
-        let pubkeys = self.client.read_ed25519_key(name)?;
+        let pubkeys = self.execute(|client| client.read_ed25519_key(name))?;
         let pubkey = pubkeys.iter().find(|pubkey| version == &pubkey.value);
         Ok(pubkey
             .ok_or_else(|| Error::KeyVersionNotFound(name.into()))?
@@ -186,7 +313,7 @@ impl VaultStorage {
 
 impl KVStorage for VaultStorage {
     fn available(&self) -> Result<(), Error> {
-        if !self.client.unsealed()? {
+        if !self.retry(|client| client.unsealed())? {
             Err(Error::InternalError("Vault is not unsealed".into()))
         } else {
             Ok(())
@@ -195,7 +322,7 @@ impl KVStorage for VaultStorage {
 
     fn get(&self, key: &str) -> Result<GetResponse, Error> {
         let secret = self.secret_name(key);
-        let resp = self.client.read_secret(&secret, key)?;
+        let resp = self.execute(|client| client.read_secret(&secret, key))?;
         let last_update = DateTime::parse_from_rfc3339(&resp.creation_time)?.timestamp() as u64;
         let value: Value = serde_json::from_str(&resp.value)?;
         Ok(GetResponse { last_update, value })
@@ -203,8 +330,8 @@ impl KVStorage for VaultStorage {
 
     fn set(&mut self, key: &str, value: Value) -> Result<(), Error> {
         let secret = self.secret_name(key);
-        self.client
-            .write_secret(&secret, key, &serde_json::to_string(&value)?)?;
+        let value = serde_json::to_string(&value)?;
+        self.execute(|client| client.write_secret(&secret, key, &value))?;
         Ok(())
     }
 
@@ -225,13 +352,13 @@ impl CryptoStorage for VaultStorage {
             Err(e) => return Err(e),
         }
 
-        self.client.create_ed25519_key(&ns_name, true)?;
+        self.execute(|client| client.create_ed25519_key(&ns_name, true))?;
         self.get_public_key(name).map(|v| v.public_key)
     }
 
     fn export_private_key(&self, name: &str) -> Result<Ed25519PrivateKey, Error> {
         let name = self.crypto_name(name);
-        Ok(self.client.export_ed25519_key(&name, None)?)
+        self.execute(|client| client.export_ed25519_key(&name, None))
     }
 
     fn export_private_key_for_version(
@@ -241,7 +368,7 @@ impl CryptoStorage for VaultStorage {
     ) -> Result<Ed25519PrivateKey, Error> {
         let name = self.crypto_name(name);
         let vers = self.key_version(&name, &version)?;
-        Ok(self.client.export_ed25519_key(&name, Some(vers))?)
+        self.execute(|client| client.export_ed25519_key(&name, Some(vers)))
     }
 
     fn import_private_key(&mut self, name: &str, key: Ed25519PrivateKey) -> Result<(), Error> {
@@ -252,14 +379,12 @@ impl CryptoStorage for VaultStorage {
             Err(e) => return Err(e),
         }
 
-        self.client
-            .import_ed25519_key(&ns_name, &key)
-            .map_err(|e| e.into())
+        self.execute(|client| client.import_ed25519_key(&ns_name, &key))
     }
 
     fn get_public_key(&self, name: &str) -> Result<PublicKeyResponse, Error> {
         let name = self.crypto_name(name);
-        let resp = self.client.read_ed25519_key(&name)?;
+        let resp = self.execute(|client| client.read_ed25519_key(&name))?;
         let mut last_key = resp.first().ok_or_else(|| Error::KeyNotSet(name))?;
         for key in &resp {
             last_key = if last_key.version > key.version {
@@ -277,7 +402,7 @@ impl CryptoStorage for VaultStorage {
 
     fn rotate_key(&mut self, name: &str) -> Result<Ed25519PublicKey, Error> {
         let ns_name = self.crypto_name(name);
-        self.client.rotate_key(&ns_name)?;
+        self.execute(|client| client.rotate_key(&ns_name))?;
         self.get_public_key(name).map(|v| v.public_key)
     }
 
@@ -291,7 +416,7 @@ impl CryptoStorage for VaultStorage {
         lcs::serialize_into(&mut bytes, &message)
             .map_err(|_| libra_crypto::traits::CryptoMaterialError::SerializationError)
             .expect("Serialization of signable material should not fail.");
-        Ok(self.client.sign_ed25519(&name, &bytes, None)?)
+        self.execute(|client| client.sign_ed25519(&name, &bytes, None))
     }
 
     fn sign_using_version<T: CryptoHash + Serialize>(
@@ -306,7 +431,7 @@ impl CryptoStorage for VaultStorage {
         lcs::serialize_into(&mut bytes, &message)
             .map_err(|_| libra_crypto::traits::CryptoMaterialError::SerializationError)
             .expect("Serialization of signable material should not fail.");
-        Ok(self.client.sign_ed25519(&name, &bytes, Some(vers))?)
+        self.execute(|client| client.sign_ed25519(&name, &bytes, Some(vers)))
     }
 }
 
@@ -87,15 +87,23 @@ impl From<serde_json::Error> for Error {
 /// * Data is accessed in Vault via tokens. Policies can only be granted during creation of a
 /// token, but policies can be amended afterward. So you cannot add new policies to a token, but
 /// you can increase the tokens abilities by modifying the underlying policies.
+/// * Vault Enterprise can partition a single server into namespaces, each with its own secrets,
+/// policies, and tokens. A request is scoped to a namespace via the X-Vault-Namespace header.
 pub struct Client {
     agent: ureq::Agent,
     host: String,
     token: String,
+    namespace: Option<String>,
     tls_config: Option<Arc<rustls::ClientConfig>>,
 }
 
 impl Client {
-    pub fn new(host: String, token: String, ca_certificate: Option<String>) -> Self {
+    pub fn new(
+        host: String,
+        token: String,
+        namespace: Option<String>,
+        ca_certificate: Option<String>,
+    ) -> Self {
         let tls_config = if let Some(certificate) = ca_certificate {
             let mut tls_config = rustls::ClientConfig::new();
             // First try the certificate as a DER encoded cert, then as a PEM, and then panic.
@@ -113,6 +121,7 @@ impl Client {
             agent: ureq::Agent::new().set("connection", "keep-alive").build(),
             host,
             token,
+            namespace,
             tls_config,
         }
     }
@@ -195,6 +204,27 @@ impl Client {
         }
     }
 
+    /// Renews the lease of the token used by this client. The increment is the requested new TTL
+    /// of the token in seconds, though Vault may cap it by the token's max TTL. Returns the TTL
+    /// actually granted, in seconds.
+    pub fn renew_token_self(&self, increment: Option<u32>) -> Result<u32, Error> {
+        let request = self
+            .agent
+            .post(&format!("{}/v1/auth/token/renew-self", self.host));
+        let mut request = self.upgrade_request(request);
+        let resp = if let Some(increment) = increment {
+            request.send_json(json!({ "increment": increment }))
+        } else {
+            request.call()
+        };
+        if resp.ok() {
+            let resp: RenewTokenResponse = serde_json::from_str(&resp.into_string()?)?;
+            Ok(resp.auth.lease_duration)
+        } else {
+            Err(resp.into())
+        }
+    }
+
     /// List all stored secrets
     pub fn list_secrets(&self, secret: &str) -> Result<Vec<String>, Error> {
         let request = self.agent.request(
@@ -482,6 +512,9 @@ impl Client {
     fn upgrade_request(&self, request: ureq::Request) -> ureq::Request {
         let mut request = self.upgrade_request_without_token(request);
         request.set("X-Vault-Token", &self.token);
+        if let Some(namespace) = &self.namespace {
+            request.set("X-Vault-Namespace", namespace);
+        }
         request
     }
 
@@ -678,6 +711,30 @@ struct CreateTokenAuth {
     client_token: String,
 }
 
+/// Below is a sample output of a RenewTokenResponse. Only the fields leveraged by this framework
+/// are decoded.
+/// {
+///   "auth": {
+///     "client_token": "ABCD",
+///     "policies": ["web", "stage"],
+///     "metadata": {
+///       "user": "armon"
+///     },
+///     "lease_duration": 3600,
+///     "renewable": true
+///   }
+/// }
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct RenewTokenResponse {
+    auth: RenewTokenAuth,
+}
+
+/// See RenewTokenResponse
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct RenewTokenAuth {
+    lease_duration: u32,
+}
+
 /// Below is a sample output of ExportKeyResponse
 /// {
 ///   "data": {
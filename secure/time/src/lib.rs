@@ -24,7 +24,7 @@ pub trait TimeService {
 }
 
 /// A real-time TimeService
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct RealTimeService;
 
 impl RealTimeService {
@@ -22,11 +22,13 @@ use libra_config::{
 };
 use libra_logger::prelude::*;
 use libra_mempool::{CommitNotification, CommitResponse, CommittedTransaction};
+use libra_metrics::health;
 use libra_types::{
+    access_path::AccessPath,
     contract_event::ContractEvent,
     epoch_change::Verifier,
     ledger_info::LedgerInfoWithSignatures,
-    transaction::{Transaction, TransactionListWithProof, Version},
+    transaction::{Transaction, TransactionListWithProof, TransactionPayload, Version},
     waypoint::Waypoint,
 };
 use network::protocols::network::Event;
@@ -61,6 +63,8 @@ pub enum CoordinatorMessage {
     GetState(oneshot::Sender<SynchronizerState>),
     // Receive a notification via a given channel when coordinator is initialized.
     WaitInitialize(oneshot::Sender<Result<()>>),
+    // used to apply the upstream networks of a reloaded node config
+    UpdateUpstream(UpstreamConfig),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -134,8 +138,9 @@ impl PendingLedgerInfos {
 /// runs an infinite event loop and triggers actions based on external / internal requests.
 /// The coordinator can work in two modes:
 /// * FullNode: infinite stream of ChunkRequests is sent to the predefined static peers
-/// (the parent is going to reply with a ChunkResponse if its committed version becomes
-/// higher within the timeout interval).
+/// (the parent is going to reply with a ChunkResponse as soon as its committed version becomes
+/// higher within the timeout interval, or with an empty ChunkResponse once the timeout expires,
+/// upon which the next ChunkRequest is sent right away).
 /// * Validator: the ChunkRequests are generated on demand for a specific target LedgerInfo to
 /// synchronize to.
 pub(crate) struct SyncCoordinator<T> {
@@ -213,7 +218,10 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
         mut self,
         network_handles: Vec<(NetworkId, StateSynchronizerSender, StateSynchronizerEvents)>,
     ) {
-        let mut interval = interval(Duration::from_millis(self.config.tick_interval_ms)).fuse();
+        let tick_interval = Duration::from_millis(self.config.tick_interval_ms);
+        let mut interval = interval(tick_interval).fuse();
+        // The coordinator is stuck if it misses this many ticks in a row
+        let health = health::register_heartbeat("state_sync", tick_interval * 10);
 
         let events: Vec<_> = network_handles
             .into_iter()
@@ -244,6 +252,10 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
                         CoordinatorMessage::WaitInitialize(cb_sender) => {
                             self.set_initialization_listener(cb_sender);
                         }
+                        CoordinatorMessage::UpdateUpstream(upstream_config) => {
+                            self.peer_manager.update_upstream_config(upstream_config);
+                            self.check_progress();
+                        }
                     };
                 },
                 (network_id, network_event) = network_events.select_next_some() => {
@@ -269,7 +281,9 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
                     }
                 },
                 _ = interval.select_next_some() => {
+                    health.heartbeat();
                     self.check_progress();
+                    self.check_subscriptions();
                 }
             }
         }
@@ -283,6 +297,12 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
                 }
             }
             StateSynchronizerMsg::GetChunkResponse(response) => {
+                if self.is_subscription_expiry(&peer, &response) {
+                    if let Err(err) = self.renew_subscription(&peer) {
+                        error!("[state sync] failed to renew subscription: {}", err);
+                    }
+                    return;
+                }
                 if let Err(err) = self
                     .process_chunk_response(&peer.clone(), *response.clone())
                     .await
@@ -409,6 +429,7 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
 
         // send notif to shared mempool
         // filter for user transactions here
+        let publishes_modules = transactions.iter().any(publishes_modules);
         let mut committed_user_txns = vec![];
         for txn in transactions {
             if let Transaction::UserTransaction(signed_txn) = txn {
@@ -422,6 +443,7 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
         let req = CommitNotification {
             transactions: committed_user_txns,
             block_timestamp_usecs,
+            publishes_modules,
             callback,
         };
         let mut mempool_channel = self.state_sync_to_mempool_sender.clone();
@@ -661,9 +683,12 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
         let txns = self
             .executor_proxy
             .get_chunk(known_version, limit, response_li.version())?;
-        let chunk_response = GetChunkResponse::new(response_li, txns);
-        let msg = StateSynchronizerMsg::GetChunkResponse(Box::new(chunk_response));
+        self.send_chunk_response(peer, GetChunkResponse::new(response_li, txns));
+        Ok(())
+    }
 
+    fn send_chunk_response(&mut self, peer: PeerNetworkId, chunk_response: GetChunkResponse) {
+        let msg = StateSynchronizerMsg::GetChunkResponse(Box::new(chunk_response));
         let network_sender = self
             .network_senders
             .get_mut(&peer.network_id())
@@ -671,7 +696,6 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
         if network_sender.send_to(peer.peer_id(), msg).is_err() {
             error!("[state sync] failed to send p2p message");
         }
-        Ok(())
     }
 
     /// The choice of the LedgerInfo in the response follows the following logic:
@@ -1009,27 +1033,38 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
         )
     }
 
-    /// The function is called after the local storage is updated with new transactions:
-    /// it might deliver chunks for the subscribers that have been waiting with the long polls.
+    /// Tells a subscriber that its long poll expired before any new transaction was committed,
+    /// so that it renews the subscription right away rather than after its retry timeout.
+    /// The notification is an empty chunk carrying the highest local ledger info.
+    fn notify_subscription_expiry(&mut self, peer: PeerNetworkId) {
+        let chunk_response = GetChunkResponse::new(
+            ResponseLedgerInfo::VerifiableLedgerInfo(self.local_state.highest_local_li.clone()),
+            TransactionListWithProof::new_empty(),
+        );
+        self.send_chunk_response(peer, chunk_response);
+    }
+
+    /// The function is called after the local storage is updated with new transactions, and on
+    /// every tick: it might deliver chunks for the subscribers that have been waiting with the
+    /// long polls, and notifies the subscribers whose long polls expired.
     ///
     /// Note that it is possible to help the subscribers only with the transactions that match
     /// the highest ledger info in the local storage (some committed transactions are ahead of the
     /// latest ledger info and are not going to be used for helping the remote subscribers).
-    /// The function assumes that the local state has been synced with storage.
     fn check_subscriptions(&mut self) {
         let highest_li_version = self.local_state.highest_local_li.ledger_info().version();
 
         let mut ready = vec![];
+        let mut expired = vec![];
         self.subscriptions.retain(|peer, request_info| {
-            // filter out expired peer requests
-            if SystemTime::now()
+            if request_info.known_version < highest_li_version {
+                ready.push((peer.clone(), request_info.clone()));
+                false
+            } else if SystemTime::now()
                 .duration_since(request_info.expiration_time)
                 .is_ok()
             {
-                return false;
-            }
-            if request_info.known_version < highest_li_version {
-                ready.push((peer.clone(), request_info.clone()));
+                expired.push(peer.clone());
                 false
             } else {
                 true
@@ -1041,5 +1076,52 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
                 error!("[state sync] failed to notify subscriber {}", err);
             }
         });
+        expired
+            .into_iter()
+            .for_each(|peer| self.notify_subscription_expiry(peer));
+    }
+
+    /// Whether `response` notifies that the long poll of the last chunk request, sent to `peer`,
+    /// expired with nothing new to sync.
+    fn is_subscription_expiry(&self, peer: &PeerNetworkId, response: &GetChunkResponse) -> bool {
+        let known_version = self.local_state.highest_version_in_local_storage();
+        let nothing_new = match &response.response_li {
+            ResponseLedgerInfo::VerifiableLedgerInfo(li) => {
+                li.ledger_info().version() <= known_version
+            }
+            _ => false,
+        };
+        nothing_new
+            && response.txn_list_with_proof.is_empty()
+            && self.sync_request.is_none()
+            && self.is_initialized()
+            && self.peer_manager.get_last_request_peer(known_version + 1) == Some(peer)
     }
+
+    /// Subscribes again for the transactions following the local storage.
+    fn renew_subscription(&mut self, peer: &PeerNetworkId) -> Result<()> {
+        debug!("[state sync] long poll to {:?} expired, renewing", peer);
+        counters::SUBSCRIPTION_RENEWALS.inc();
+        self.send_chunk_request(
+            self.local_state.highest_version_in_local_storage(),
+            self.local_state.epoch(),
+        )
+    }
+}
+
+/// Returns whether `txn` publishes or republishes modules, which the VM validator has to reload.
+fn publishes_modules(txn: &Transaction) -> bool {
+    let change_set = match txn {
+        Transaction::UserTransaction(signed_txn) => match signed_txn.payload() {
+            TransactionPayload::Module(_) => return true,
+            TransactionPayload::WriteSet(change_set) => change_set,
+            TransactionPayload::Script(_) => return false,
+        },
+        Transaction::WaypointWriteSet(change_set) => change_set,
+        Transaction::BlockMetadata(_) => return false,
+    };
+    change_set
+        .write_set()
+        .iter()
+        .any(|(access_path, _)| access_path.path.first() == Some(&AccessPath::CODE_TAG))
 }
@@ -118,6 +118,15 @@ pub static TIMEOUT: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Number of long polls renewed after the upstream peer notified their expiry
+pub static SUBSCRIPTION_RENEWALS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "libra_state_sync_subscription_renewals_total",
+        "Number of long polls renewed after the upstream peer notified their expiry"
+    )
+    .unwrap()
+});
+
 /// Number of timeouts that occur during the commit flow across consensus, state sync, and mempool
 pub static COMMIT_TIMEOUT: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
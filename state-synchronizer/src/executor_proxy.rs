@@ -185,11 +185,6 @@ impl ExecutorProxyTrait for ExecutorProxy {
         if events.is_empty() {
             return Ok(());
         }
-        let event_keys = events
-            .iter()
-            .map(|event| *event.key())
-            .collect::<HashSet<_>>();
-
         // calculate deltas
         let new_configs = Self::fetch_all_configs(&*self.storage)?;
         let changed_configs = new_configs
@@ -212,7 +207,9 @@ impl ExecutorProxyTrait for ExecutorProxy {
             // or any of the subscribed events were emitted
             let subscribed_items = subscription.subscribed_items();
             if !changed_configs.is_disjoint(&subscribed_items.configs)
-                || !event_keys.is_disjoint(&subscribed_items.events)
+                || events
+                    .iter()
+                    .any(|event| subscribed_items.events.matches(event))
             {
                 subscription.publish(new_configs.clone())?;
             }
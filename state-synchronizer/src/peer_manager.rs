@@ -10,7 +10,7 @@ use rand::{
     thread_rng,
 };
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, HashSet},
     time::SystemTime,
 };
 
@@ -65,6 +65,8 @@ pub struct PeerManager {
     // list of peers that are eligible for this node to send sync requests to
     eligible_peers: Vec<PeerNetworkId>,
     peers: HashMap<PeerNetworkId, PeerInfo>,
+    // live peers that are not upstream, kept in case a config reload makes their network upstream
+    downstream_peers: HashSet<PeerNetworkId>,
     requests: BTreeMap<u64, ChunkRequestInfo>,
     upstream_config: UpstreamConfig,
     weighted_index: Option<WeightedIndex<f64>>,
@@ -75,6 +77,7 @@ impl PeerManager {
         Self {
             eligible_peers: vec![],
             peers: HashMap::new(),
+            downstream_peers: HashSet::new(),
             requests: BTreeMap::new(),
             upstream_config,
             weighted_index: None,
@@ -83,6 +86,7 @@ impl PeerManager {
 
     pub fn enable_peer(&mut self, peer: PeerNetworkId) {
         if !self.is_upstream_peer(&peer) {
+            self.downstream_peers.insert(peer);
             return;
         }
 
@@ -97,12 +101,45 @@ impl PeerManager {
     }
 
     pub fn disable_peer(&mut self, peer: &PeerNetworkId) {
+        self.downstream_peers.remove(peer);
         if let Some(peer_info) = self.peers.get_mut(peer) {
             peer_info.is_alive = false;
         };
         self.update_peer_selection_data();
     }
 
+    /// Replaces the upstream networks: live peers of networks that became upstream can be picked
+    /// for sync requests right away, and peers of networks that are no longer upstream are not
+    /// picked anymore.
+    pub fn update_upstream_config(&mut self, upstream_config: UpstreamConfig) {
+        self.upstream_config = upstream_config;
+
+        let new_downstream_peers: Vec<_> = self
+            .peers
+            .iter()
+            .filter(|(peer, _)| !self.is_upstream_peer(peer))
+            .map(|(peer, peer_info)| (peer.clone(), peer_info.is_alive))
+            .collect();
+        for (peer, is_alive) in new_downstream_peers {
+            self.peers.remove(&peer);
+            if is_alive {
+                self.downstream_peers.insert(peer);
+            }
+        }
+
+        let new_upstream_peers: Vec<_> = self
+            .downstream_peers
+            .iter()
+            .filter(|peer| self.is_upstream_peer(peer))
+            .cloned()
+            .collect();
+        for peer in new_upstream_peers {
+            self.downstream_peers.remove(&peer);
+            self.peers.insert(peer, PeerInfo::new(true, MAX_SCORE));
+        }
+        self.update_peer_selection_data();
+    }
+
     pub fn is_empty(&self) -> bool {
         self.eligible_peers.is_empty()
     }
@@ -218,6 +255,12 @@ impl PeerManager {
             .map(|req_info| req_info.last_request_time)
     }
 
+    pub fn get_last_request_peer(&self, version: u64) -> Option<&PeerNetworkId> {
+        self.requests
+            .get(&version)
+            .map(|req_info| &req_info.last_request_peer)
+    }
+
     pub fn get_first_request_time(&self, version: u64) -> Option<SystemTime> {
         self.requests
             .get(&version)
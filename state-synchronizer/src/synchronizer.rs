@@ -206,4 +206,11 @@ impl StateSyncClient {
             Ok(info)
         }
     }
+
+    /// Replaces the upstream networks that sync requests are sent to
+    pub fn update_upstream_config(&self, upstream_config: UpstreamConfig) -> Result<()> {
+        self.coordinator_sender
+            .unbounded_send(CoordinatorMessage::UpdateUpstream(upstream_config))
+            .map_err(|e| format_err!("[state sync client] failed to update upstream: {}", e))
+    }
 }
@@ -12,7 +12,7 @@ use channel::{libra_channel, message_queues::QueueStyle};
 use executor_types::ExecutedTrees;
 use futures::{executor::block_on, future::FutureExt, StreamExt};
 use libra_config::{
-    config::RoleType,
+    config::{RoleType, StateSyncConfig},
     network_id::{NetworkContext, NetworkId},
 };
 use libra_crypto::{hash::ACCUMULATOR_PLACEHOLDER_HASH, test_utils::TEST_SEED, x25519, Uniform};
@@ -150,6 +150,8 @@ struct SynchronizerEnv {
         HashMap<PeerId, libra_channel::Sender<(PeerId, ProtocolId), PeerManagerNotification>>,
     network_conn_event_notifs_txs: HashMap<PeerId, conn_notifs_channel::Sender>,
     multi_peer_ids: Vec<Vec<PeerId>>, // maps peer's synchronizer env index to that peer's PeerIds, to support node with multiple network IDs
+    // maximum long poll timeout of the next synchronizers
+    max_timeout_ms: u64,
 }
 
 impl SynchronizerEnv {
@@ -252,6 +254,7 @@ impl SynchronizerEnv {
             network_notifs_txs: HashMap::new(),
             network_conn_event_notifs_txs: HashMap::new(),
             multi_peer_ids: vec![],
+            max_timeout_ms: StateSyncConfig::default().max_timeout_ms,
         }
     }
 
@@ -298,6 +301,7 @@ impl SynchronizerEnv {
         let mut config = config_builder::test_config().0;
         config.base.role = role;
         config.state_sync.sync_request_timeout_ms = timeout_ms;
+        config.state_sync.max_timeout_ms = self.max_timeout_ms;
 
         let network = config.validator_network.unwrap();
         let network_id = if role.is_validator() {
@@ -701,6 +705,43 @@ fn test_full_node() {
     assert!(env.wait_for_version(1, 20, None));
 }
 
+#[test]
+fn test_full_node_long_poll_expiry() {
+    let mut env = SynchronizerEnv::new(2);
+    // The validator holds long polls for at most 1s, much less than the full node asks for.
+    env.max_timeout_ms = 1_000;
+    env.start_next_synchronizer(
+        SynchronizerEnv::default_handler(),
+        RoleType::Validator,
+        Waypoint::default(),
+        true,
+        None,
+    );
+    env.start_next_synchronizer(
+        SynchronizerEnv::default_handler(),
+        RoleType::FullNode,
+        Waypoint::default(),
+        true,
+        None,
+    );
+
+    let validator = (0, 0);
+    let full_node = (1, 0);
+    env.send_peer_event(full_node, validator, true, Inbound);
+    env.send_peer_event(validator, full_node, true, Outbound);
+
+    // the validator has nothing new for the full node, which subscribes
+    env.deliver_msg(full_node);
+    // the validator notifies the expiry of the subscription, which the full node renews right away
+    env.deliver_msg(validator);
+    env.deliver_msg(full_node);
+
+    // the renewed subscription is fulfilled as soon as the validator commits
+    env.commit(0, 10);
+    env.deliver_msg(validator);
+    assert!(env.wait_for_version(1, 10, None));
+}
+
 #[test]
 fn catch_up_through_epochs_validators() {
     let mut env = SynchronizerEnv::new(2);
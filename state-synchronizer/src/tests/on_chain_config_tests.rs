@@ -19,7 +19,7 @@ use libra_crypto::{
 use libra_types::{
     account_address,
     account_config::{lbr_type_tag, libra_root_address},
-    on_chain_config::{OnChainConfig, VMConfig, VMPublishingOption},
+    on_chain_config::{OnChainConfig, VMPublishingOption},
 };
 use libra_vm::LibraVM;
 use libradb::LibraDB;
@@ -38,7 +38,7 @@ fn test_on_chain_config_pub_sub() {
     let mut rt = tokio::runtime::Runtime::new().unwrap();
     // set up reconfig subscription
     let (subscription, mut reconfig_receiver) =
-        ReconfigSubscription::subscribe_all(vec![VMConfig::CONFIG_ID], vec![]);
+        ReconfigSubscription::subscribe_all(vec![VMPublishingOption::CONFIG_ID], vec![]);
 
     let (mut config, genesis_key) = config_builder::test_config();
     let (db, db_rw) = DbReaderWriter::wrap(LibraDB::new_for_test(&config.storage.dir()));
@@ -139,8 +139,8 @@ fn test_on_chain_config_pub_sub() {
 
     let receive_reconfig = async {
         let payload = reconfig_receiver.select_next_some().await;
-        let received_config = payload.get::<VMConfig>().unwrap();
-        assert_eq!(received_config.publishing_option, vm_publishing_option);
+        let received_config = payload.get::<VMPublishingOption>().unwrap();
+        assert_eq!(received_config, vm_publishing_option);
     };
 
     rt.block_on(receive_reconfig);
@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::peer_manager::{PeerManager, PeerScoreUpdateType};
-use libra_config::config::{PeerNetworkId, UpstreamConfig};
+use libra_config::{
+    config::{PeerNetworkId, UpstreamConfig},
+    network_id::NetworkId,
+};
 use std::collections::HashMap;
 
 #[test]
@@ -83,3 +86,26 @@ fn test_peer_manager_request_metadata() {
             <= peer_manager.get_last_request_time(1).unwrap()
     );
 }
+
+#[test]
+fn test_update_upstream_config() {
+    let validator_peer = PeerNetworkId::random_validator();
+    let public_peer = PeerNetworkId(NetworkId::Public, validator_peer.peer_id());
+    let mut peer_manager = PeerManager::new(UpstreamConfig::default());
+    peer_manager.enable_peer(validator_peer.clone());
+    peer_manager.enable_peer(public_peer.clone());
+    peer_manager.disable_peer(&validator_peer);
+    assert!(peer_manager.pick_peer().is_none());
+
+    // the public peer connected before its network became upstream
+    peer_manager.update_upstream_config(UpstreamConfig {
+        networks: vec![NetworkId::Public],
+    });
+    assert_eq!(peer_manager.pick_peer(), Some(public_peer.clone()));
+
+    peer_manager.enable_peer(validator_peer.clone());
+    peer_manager.update_upstream_config(UpstreamConfig::default());
+    for _ in 0..10 {
+        assert_eq!(peer_manager.pick_peer(), Some(validator_peer.clone()));
+    }
+}
@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    backup_types::transaction::manifest::TransactionBackup,
-    storage::{BackupStorage, FileHandle},
+    backup_types::transaction::manifest::{TransactionBackup, TransactionChunk},
+    storage::{BackupStorage, FileHandle, FileHandleRef},
     utils::read_record_bytes::ReadRecordBytes,
 };
 use anyhow::{ensure, Result};
@@ -24,7 +24,7 @@ pub struct TransactionRestoreOpt {
 }
 
 pub struct TransactionRestoreController {
-    storage: Arc<dyn BackupStorage>,
+    reader: TransactionBackupReader,
     restore_handler: Arc<RestoreHandler>,
     manifest_handle: FileHandle,
 }
@@ -36,41 +36,18 @@ impl TransactionRestoreController {
         restore_handler: Arc<RestoreHandler>,
     ) -> Self {
         Self {
-            storage,
+            reader: TransactionBackupReader::new(storage),
             restore_handler,
             manifest_handle: opt.manifest_handle,
         }
     }
 
     pub async fn run(self) -> Result<()> {
-        let mut manifest_bytes = Vec::new();
-        self.storage
-            .open_for_read(&self.manifest_handle)
-            .await?
-            .read_to_end(&mut manifest_bytes)
-            .await?;
-        let manifest: TransactionBackup = serde_json::from_slice(&manifest_bytes)?;
-        manifest.verify()?;
+        let manifest = self.reader.read_manifest(&self.manifest_handle).await?;
 
         let mut first_chunk = true;
-        for chunk in manifest.chunks {
-            let (txns, txn_infos) = self.read_chunk(chunk.transactions).await?;
-            let (proof, ledger_info) = self.read_proof(chunk.proof).await?;
-            ensure!(
-                chunk.first_version + (txns.len() as Version) == chunk.last_version + 1,
-                "Number of items in chunks doesn't match that in manifest. first_version: {}, last_version: {}, items in chunk: {}",
-                chunk.first_version,
-                chunk.last_version,
-                txns.len(),
-            );
-
-            let txn_list_with_proof = TransactionListWithProof::new(
-                txns,
-                None,
-                Some(chunk.first_version),
-                TransactionListProof::new(proof, txn_infos),
-            );
-            txn_list_with_proof.verify(ledger_info.ledger_info(), Some(chunk.first_version))?;
+        for chunk in &manifest.chunks {
+            let (txn_list_with_proof, ledger_info) = self.reader.read_chunk(chunk).await?;
 
             // write to db
             self.restore_handler.save_transactions(
@@ -85,12 +62,61 @@ impl TransactionRestoreController {
     }
 }
 
-impl TransactionRestoreController {
-    async fn read_chunk(
+/// Reads transaction backups, verifying every chunk against the ledger info it comes with. Used
+/// by the restore controller, as well as by tools consuming the transactions without a DB.
+pub struct TransactionBackupReader {
+    storage: Arc<dyn BackupStorage>,
+}
+
+impl TransactionBackupReader {
+    pub fn new(storage: Arc<dyn BackupStorage>) -> Self {
+        Self { storage }
+    }
+
+    pub async fn read_manifest(
+        &self,
+        manifest_handle: &FileHandleRef,
+    ) -> Result<TransactionBackup> {
+        let mut manifest_bytes = Vec::new();
+        self.storage
+            .open_for_read(manifest_handle)
+            .await?
+            .read_to_end(&mut manifest_bytes)
+            .await?;
+        let manifest: TransactionBackup = serde_json::from_slice(&manifest_bytes)?;
+        manifest.verify()?;
+        Ok(manifest)
+    }
+
+    pub async fn read_chunk(
+        &self,
+        chunk: &TransactionChunk,
+    ) -> Result<(TransactionListWithProof, LedgerInfoWithSignatures)> {
+        let (txns, txn_infos) = self.read_transactions(&chunk.transactions).await?;
+        let (proof, ledger_info) = self.read_proof(&chunk.proof).await?;
+        ensure!(
+            chunk.first_version + (txns.len() as Version) == chunk.last_version + 1,
+            "Number of items in chunks doesn't match that in manifest. first_version: {}, last_version: {}, items in chunk: {}",
+            chunk.first_version,
+            chunk.last_version,
+            txns.len(),
+        );
+
+        let txn_list_with_proof = TransactionListWithProof::new(
+            txns,
+            None,
+            Some(chunk.first_version),
+            TransactionListProof::new(proof, txn_infos),
+        );
+        txn_list_with_proof.verify(ledger_info.ledger_info(), Some(chunk.first_version))?;
+        Ok((txn_list_with_proof, ledger_info))
+    }
+
+    async fn read_transactions(
         &self,
-        file_handle: FileHandle,
+        file_handle: &FileHandleRef,
     ) -> Result<(Vec<Transaction>, Vec<TransactionInfo>)> {
-        let mut file = self.storage.open_for_read(&file_handle).await?;
+        let mut file = self.storage.open_for_read(file_handle).await?;
         let mut txns = Vec::new();
         let mut txn_infos = Vec::new();
 
@@ -105,9 +131,9 @@ impl TransactionRestoreController {
 
     async fn read_proof(
         &self,
-        file_handle: FileHandle,
+        file_handle: &FileHandleRef,
     ) -> Result<(TransactionAccumulatorRangeProof, LedgerInfoWithSignatures)> {
-        let mut file = self.storage.open_for_read(&file_handle).await?;
+        let mut file = self.storage.open_for_read(file_handle).await?;
         let mut bytes = Vec::new();
         file.read_to_end(&mut bytes).await?;
         Ok(lcs::from_bytes(&bytes)?)
@@ -10,7 +10,8 @@ use backup_cli::{
         GlobalBackupOpt,
     },
 };
-use std::sync::Arc;
+use libra_types::transaction::Version;
+use std::{path::PathBuf, sync::Arc};
 use structopt::StructOpt;
 
 #[derive(StructOpt)]
@@ -26,6 +27,11 @@ enum OneShotCommand {
     Query(OneShotQueryOpt),
     #[structopt(about = "Do a one shot backup.")]
     Backup(OneShotBackupOpt),
+    #[structopt(
+        about = "Export the summary of the transaction accumulator over a version range for \
+                 auditors."
+    )]
+    AccumulatorSummary(OneShotAccumulatorSummaryOpt),
 }
 
 #[derive(StructOpt)]
@@ -54,6 +60,33 @@ struct OneShotBackupOpt {
     storage: StorageOpt,
 }
 
+#[derive(StructOpt)]
+struct OneShotAccumulatorSummaryOpt {
+    #[structopt(flatten)]
+    client: BackupServiceClientOpt,
+
+    #[structopt(long = "first-version")]
+    first_version: Version,
+
+    #[structopt(long = "last-version")]
+    last_version: Version,
+
+    #[structopt(
+        long = "checkpoint-interval",
+        default_value = "10000",
+        help = "Number of versions between two accumulator root hashes in the summary, besides \
+                the ones at epoch endings."
+    )]
+    interval: u64,
+
+    #[structopt(
+        long = "output",
+        parse(from_os_str),
+        help = "File to write the summary to."
+    )]
+    output: PathBuf,
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let cmd = Command::from_args();
@@ -82,6 +115,16 @@ async fn main() -> Result<()> {
 
                 println!("Success. Manifest saved to {}", &manifest);
             }
+            OneShotCommand::AccumulatorSummary(opt) => {
+                let client = BackupServiceClient::new_with_opt(opt.client);
+                let summary = client
+                    .get_accumulator_summary(opt.first_version, opt.last_version, opt.interval)
+                    .await
+                    .context("Failed to get accumulator summary.")?;
+                std::fs::write(&opt.output, summary)?;
+
+                println!("Success. Summary saved to {}", opt.output.display());
+            }
         },
     }
     Ok(())
@@ -0,0 +1,48 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Context, Result};
+use libra_types::{accumulator_summary::AccumulatorSummary, waypoint::Waypoint};
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+#[structopt(
+    about = "Verifies a transaction accumulator summary exported by db-backup, trusting only \
+             a waypoint."
+)]
+struct Opt {
+    #[structopt(long = "summary-file", parse(from_os_str))]
+    summary_file: PathBuf,
+
+    #[structopt(
+        long = "waypoint",
+        help = "Waypoint of an epoch-ending LedgerInfo in the summary, e.g. the one preceding \
+                its first version."
+    )]
+    waypoint: Waypoint,
+}
+
+fn main() -> Result<()> {
+    let opt = Opt::from_args();
+    let summary: AccumulatorSummary = lcs::from_bytes(&std::fs::read(&opt.summary_file)?)?;
+    summary
+        .verify(&opt.waypoint)
+        .context("Failed to verify accumulator summary.")?;
+
+    for li in &summary.epoch_change_proof.ledger_info_with_sigs {
+        println!(
+            "epoch {} ends at version {}",
+            li.ledger_info().epoch(),
+            li.ledger_info().version()
+        );
+    }
+    for checkpoint in &summary.checkpoints {
+        println!("{}: {:x}", checkpoint.version, checkpoint.root_hash);
+    }
+    println!(
+        "Success. Accumulator summary verified up to version {}.",
+        summary.ledger_info_with_sigs.ledger_info().version()
+    );
+    Ok(())
+}
@@ -117,4 +117,21 @@ impl BackupServiceClient {
         ))
         .await
     }
+
+    pub async fn get_accumulator_summary(
+        &self,
+        first_version: Version,
+        last_version: Version,
+        interval: u64,
+    ) -> Result<Vec<u8>> {
+        let mut buf = Vec::new();
+        self.get(&format!(
+            "accumulator_summary/{}/{}/{}",
+            first_version, last_version, interval,
+        ))
+        .await?
+        .read_to_end(&mut buf)
+        .await?;
+        Ok(buf)
+    }
 }
@@ -82,7 +82,7 @@ pub(crate) fn get_routes(backup_handler: BackupHandler) -> BoxedFilter<(impl Rep
         .recover(handle_rejection);
 
     // GET transaction_range_proof/<first_version>/<last_version>
-    let bh = backup_handler;
+    let bh = backup_handler.clone();
     let transaction_range_proof = warp::path!(Version / Version)
         .map(move |first_version, last_version| {
             reply_with_lcs_bytes(&bh.get_transaction_range_proof(first_version, last_version)?)
@@ -90,6 +90,19 @@ pub(crate) fn get_routes(backup_handler: BackupHandler) -> BoxedFilter<(impl Rep
         .map(unwrap_or_500)
         .recover(handle_rejection);
 
+    // GET accumulator_summary/<first_version>/<last_version>/<interval>
+    let bh = backup_handler;
+    let accumulator_summary = warp::path!(Version / Version / u64)
+        .map(move |first_version, last_version, interval| {
+            reply_with_lcs_bytes(&bh.get_accumulator_summary(
+                first_version,
+                last_version,
+                interval,
+            )?)
+        })
+        .map(unwrap_or_500)
+        .recover(handle_rejection);
+
     // Route by endpoint name.
     let routes = warp::any()
         .and(warp::path("latest_state_root").and(latest_state_root))
@@ -98,7 +111,8 @@ pub(crate) fn get_routes(backup_handler: BackupHandler) -> BoxedFilter<(impl Rep
         .or(warp::path("state_root_proof").and(state_root_proof))
         .or(warp::path("epoch_ending_ledger_infos").and(epoch_ending_ledger_infos))
         .or(warp::path("transactions").and(transactions))
-        .or(warp::path("transaction_range_proof").and(transaction_range_proof));
+        .or(warp::path("transaction_range_proof").and(transaction_range_proof))
+        .or(warp::path("accumulator_summary").and(accumulator_summary));
 
     // Serve all routes for GET only.
     warp::get().and(routes).boxed()
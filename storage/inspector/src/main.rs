@@ -38,6 +38,23 @@ enum Command {
     },
     #[structopt(name = "list-accounts")]
     ListAccounts,
+    #[structopt(name = "verify")]
+    Verify {
+        #[structopt(long)]
+        start_version: Option<u64>,
+        #[structopt(long)]
+        end_version: Option<u64>,
+        /// Also rebuild the state tree at the end version and check its root hash.
+        #[structopt(long)]
+        state: bool,
+    },
+    /// Apply the pending schema migrations to the DB.
+    #[structopt(name = "migrate")]
+    Migrate {
+        /// Only report the pending migrations, without opening the DB for writing.
+        #[structopt(long)]
+        dry_run: bool,
+    },
 }
 
 /// Print out latest information stored in the DB.
@@ -150,6 +167,53 @@ fn list_accounts(db: &LibraDB) {
     info!("Total Accounts: {}", num_account);
 }
 
+/// Re-hash the ledger history in `[start_version, end_version]` and report the first corruption.
+fn verify(
+    db: &LibraDB,
+    start_version: Option<u64>,
+    end_version: Option<u64>,
+    state: bool,
+) -> Result<bool> {
+    let start_version = start_version.unwrap_or(0);
+    let end_version = match end_version {
+        Some(v) => v,
+        None => db.get_latest_version()?,
+    };
+    let verifier = db.get_verifier();
+
+    let mut corruption = verifier.verify_range(start_version, end_version)?;
+    if corruption.is_none() && state {
+        corruption = verifier.verify_state(end_version)?;
+    }
+
+    match corruption {
+        Some(c) => {
+            println!("DB corrupted: {}", c);
+            Ok(false)
+        }
+        None => {
+            println!(
+                "DB intact between versions {} and {}.",
+                start_version, end_version
+            );
+            Ok(true)
+        }
+    }
+}
+
+fn print_migrations(db: &LibraDB) -> Result<()> {
+    match db.get_schema_version()? {
+        Some(schema_version) => println!("Schema version: {}", schema_version),
+        None => println!("Schema version: none"),
+    }
+    let pending_migrations = db.get_pending_migrations()?;
+    println!("{} pending migrations.", pending_migrations.len());
+    for migration in pending_migrations {
+        println!("Migration {}: {}", migration.version, migration.description);
+    }
+    Ok(())
+}
+
 fn main() {
     ::libra_logger::Logger::new().init();
 
@@ -165,8 +229,9 @@ fn main() {
     let log_dir = tempfile::tempdir().expect("Unable to get temp dir");
     info!("Opening DB at: {:?}, log at {:?}", p, log_dir.path());
 
-    let db =
-        LibraDB::open(p, true /* readonly */, None /* pruner */).expect("Unable to open LibraDB");
+    // Pending migrations are applied when the DB is opened for writing.
+    let readonly = !matches!(opt.cmd, Some(Command::Migrate { dry_run: false }));
+    let db = LibraDB::open(p, readonly, None /* pruner */).expect("Unable to open LibraDB");
     info!("DB opened successfully.");
 
     if let Some(cmd) = opt.cmd {
@@ -183,6 +248,18 @@ fn main() {
             Command::ListAccounts => {
                 list_accounts(&db);
             }
+            Command::Verify {
+                start_version,
+                end_version,
+                state,
+            } => {
+                if !verify(&db, start_version, end_version, state).expect("Unable to verify DB") {
+                    std::process::exit(1);
+                }
+            }
+            Command::Migrate { .. } => {
+                print_migrations(&db).expect("Unable to read migrations from DB");
+            }
         }
     } else {
         print_head(&db).expect("Unable to read information from DB");
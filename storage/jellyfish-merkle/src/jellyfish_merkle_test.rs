@@ -528,6 +528,54 @@ fn test_put_blob_sets() {
     }
 }
 
+#[test]
+fn test_batch_build_from_sorted_iter() {
+    let mut rng: StdRng = StdRng::from_seed([0; 32]);
+    for num_keys in &[1, 2, 1000] {
+        let mut kvs: Vec<_> = (0..*num_keys)
+            .map(|_| {
+                (
+                    HashValue::random_with_rng(&mut rng),
+                    AccountStateBlob::from(HashValue::random_with_rng(&mut rng).to_vec()),
+                )
+            })
+            .collect();
+        kvs.sort_by_key(|(key, _)| *key);
+
+        let db = MockTreeStore::default();
+        let tree = JellyfishMerkleTree::new(&db);
+        let expected = tree.put_blob_set(kvs.clone(), 0 /* version */).unwrap();
+        let (root, batch) = tree
+            .batch_build_from_sorted_iter(0 /* version */, kvs.clone())
+            .unwrap();
+        assert_eq!((root, batch.clone()), expected);
+
+        db.write_tree_update_batch(batch).unwrap();
+        for (key, value) in &kvs {
+            let (account, proof) = tree.get_with_proof(*key, 0).unwrap();
+            assert_eq!(account.as_ref(), Some(value));
+            assert!(proof.verify(root, *key, account.as_ref()).is_ok());
+        }
+    }
+}
+
+#[test]
+fn test_batch_build_from_sorted_iter_empty_and_unsorted() {
+    let db = MockTreeStore::default();
+    let tree = JellyfishMerkleTree::new(&db);
+    let (root, batch) = tree
+        .batch_build_from_sorted_iter(0 /* version */, vec![])
+        .unwrap();
+    assert_eq!(root, *SPARSE_MERKLE_PLACEHOLDER_HASH);
+    assert_eq!(batch.num_new_leaves, 0);
+
+    let key = HashValue::random();
+    let blob = AccountStateBlob::from(vec![1u8, 2u8]);
+    assert!(tree
+        .batch_build_from_sorted_iter(0 /* version */, vec![(key, blob.clone()), (key, blob)])
+        .is_err());
+}
+
 fn many_keys_get_proof_and_verify_tree_root(seed: &[u8], num_keys: usize) {
     assert!(seed.len() < 32);
     let mut actual_seed = [0u8; 32];
@@ -75,6 +75,7 @@ mod jellyfish_merkle_test;
 mod mock_tree_store;
 mod nibble_path;
 pub mod node_type;
+pub mod pruner;
 pub mod restore;
 #[cfg(test)]
 mod test_helper;
@@ -120,6 +121,17 @@ pub trait TreeWriter {
     fn write_node_batch(&self, node_batch: &NodeBatch) -> Result<()>;
 }
 
+/// `StaleNodeIndexReader` gives the [`StaleNodePruner`](pruner/struct.StaleNodePruner.html)
+/// access to the stale node indices in storage.
+pub trait StaleNodeIndexReader {
+    /// Returns the stale node indices in increasing order, starting from the first one that
+    /// becomes stale at or after `start_version`.
+    fn get_stale_node_indices(
+        &self,
+        start_version: Version,
+    ) -> Result<Box<dyn Iterator<Item = Result<StaleNodeIndex>> + '_>>;
+}
+
 /// Node batch that will be written into db atomically with other batches.
 pub type NodeBatch = BTreeMap<NodeKey, Node>;
 /// [`StaleNodeIndex`](struct.StaleNodeIndex.html) batch that will be written into db atomically
@@ -245,6 +257,89 @@ where
         Ok(tree_cache.into())
     }
 
+    /// Builds the whole tree at `version` from `blob_iter`, which must yield keys in strictly
+    /// increasing order, e.g. the accounts of a state snapshot backup. Unlike
+    /// [`put_blob_sets`](struct.JellyfishMerkleTree.html#method.put_blob_sets), nothing is read
+    /// from storage and every node is created exactly once, bottom-up, so this is much faster than
+    /// inserting the accounts batch by batch when restoring a large state. The returned batch is
+    /// the same as the one produced by putting all the blobs into an empty tree.
+    pub fn batch_build_from_sorted_iter<I>(
+        &self,
+        version: Version,
+        blob_iter: I,
+    ) -> Result<(HashValue, TreeUpdateBatch)>
+    where
+        I: IntoIterator<Item = (HashValue, AccountStateBlob)>,
+    {
+        let mut blobs: Vec<(HashValue, AccountStateBlob)> = vec![];
+        for (key, blob) in blob_iter {
+            if let Some((prev_key, _)) = blobs.last() {
+                ensure!(
+                    key > *prev_key,
+                    "Keys must come in strictly increasing order, got {:x} after {:x}.",
+                    key,
+                    prev_key,
+                );
+            }
+            blobs.push((key, blob));
+        }
+
+        let mut batch = TreeUpdateBatch {
+            num_new_leaves: blobs.len(),
+            ..TreeUpdateBatch::default()
+        };
+        let root_node_key = NodeKey::new_empty_path(version);
+        let root_hash = if blobs.is_empty() {
+            let root_node = Node::new_null();
+            let root_hash = root_node.hash();
+            batch.node_batch.insert(root_node_key, root_node);
+            root_hash
+        } else {
+            Self::build_subtree(root_node_key, &blobs, &mut batch.node_batch).hash
+        };
+        Ok((root_hash, batch))
+    }
+
+    /// Builds the subtree at `node_key` holding `blobs`, which are sorted and all share the nibble
+    /// path of `node_key` as prefix, and returns it as a child of its parent. A single blob is
+    /// stored as a leaf right here, otherwise an internal node is created with one subtree per
+    /// distinct next nibble.
+    fn build_subtree(
+        node_key: NodeKey,
+        blobs: &[(HashValue, AccountStateBlob)],
+        node_batch: &mut NodeBatch,
+    ) -> Child {
+        let version = node_key.version();
+        if let [(key, blob)] = blobs {
+            let leaf_node = LeafNode::new(*key, blob.clone());
+            let child = Child::new(leaf_node.hash(), version, true /* is_leaf */);
+            node_batch.insert(node_key, leaf_node.into());
+            return child;
+        }
+
+        let depth = node_key.nibble_path().num_nibbles();
+        let mut children = Children::new();
+        let mut start = 0;
+        while start < blobs.len() {
+            let nibble = blobs[start].0.get_nibble(depth);
+            let end = blobs[start..]
+                .iter()
+                .position(|(key, _)| key.get_nibble(depth) != nibble)
+                .map_or(blobs.len(), |len| start + len);
+            let child_node_key = node_key.gen_child_node_key(version, nibble);
+            children.insert(
+                nibble,
+                Self::build_subtree(child_node_key, &blobs[start..end], node_batch),
+            );
+            start = end;
+        }
+
+        let internal_node = InternalNode::new(children);
+        let child = Child::new(internal_node.hash(), version, false /* is_leaf */);
+        node_batch.insert(node_key, internal_node.into());
+        child
+    }
+
     fn put(
         key: HashValue,
         blob: AccountStateBlob,
@@ -3,7 +3,7 @@
 
 use crate::{
     node_type::{LeafNode, Node, NodeKey},
-    NodeBatch, StaleNodeIndex, TreeReader, TreeUpdateBatch, TreeWriter,
+    NodeBatch, StaleNodeIndex, StaleNodeIndexReader, TreeReader, TreeUpdateBatch, TreeWriter,
 };
 use anyhow::{bail, ensure, Result};
 use libra_types::transaction::Version;
@@ -48,6 +48,25 @@ impl TreeWriter for MockTreeStore {
     }
 }
 
+impl StaleNodeIndexReader for MockTreeStore {
+    fn get_stale_node_indices(
+        &self,
+        start_version: Version,
+    ) -> Result<Box<dyn Iterator<Item = Result<StaleNodeIndex>> + '_>> {
+        let indices = self
+            .0
+            .read()
+            .unwrap()
+            .1
+            .iter()
+            .filter(|index| index.stale_since_version >= start_version)
+            .cloned()
+            .map(Ok)
+            .collect::<Vec<_>>();
+        Ok(Box::new(indices.into_iter()))
+    }
+}
+
 impl MockTreeStore {
     pub fn put_node(&self, node_key: NodeKey, node: Node) -> Result<()> {
         match self.0.write().unwrap().0.entry(node_key) {
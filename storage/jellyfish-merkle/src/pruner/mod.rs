@@ -0,0 +1,115 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements `StaleNodePruner`, which walks the stale node indices and yields the
+//! nodes no longer reachable from any readable version in batches, so that the caller can delete
+//! them incrementally. Each batch carries the version pruning has progressed to once it is
+//! deleted, which is where pruning resumes, e.g. after a restart.
+
+#[cfg(test)]
+mod pruner_test;
+
+use crate::{node_type::NodeKey, StaleNodeIndex, StaleNodeIndexReader};
+use anyhow::{ensure, Result};
+use libra_types::transaction::Version;
+use std::iter::Peekable;
+
+/// The stale nodes to delete in one go.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StaleNodePruneBatch {
+    /// The keys of the nodes to delete.
+    pub node_keys: Vec<NodeKey>,
+    /// Once the nodes are deleted, versions before this one are no longer readable. This is the
+    /// checkpoint to resume pruning from.
+    pub least_readable_version: Version,
+}
+
+/// Iterates over the stale nodes to delete in order to make `target_least_readable_version` the
+/// oldest readable version, in batches covering at most `max_versions_per_batch` versions.
+pub struct StaleNodePruner<'a> {
+    stale_node_indices: Peekable<Box<dyn Iterator<Item = Result<StaleNodeIndex>> + 'a>>,
+    target_least_readable_version: Version,
+    max_versions_per_batch: usize,
+}
+
+impl<'a> StaleNodePruner<'a> {
+    /// Starts pruning from `least_readable_version`, the checkpoint of the last deleted batch or 0
+    /// if nothing was pruned yet.
+    pub fn new<R: StaleNodeIndexReader>(
+        reader: &'a R,
+        least_readable_version: Version,
+        target_least_readable_version: Version,
+        max_versions_per_batch: usize,
+    ) -> Result<Self> {
+        ensure!(
+            max_versions_per_batch > 0,
+            "Batches must cover at least one version."
+        );
+        Ok(Self {
+            stale_node_indices: reader
+                .get_stale_node_indices(least_readable_version)?
+                .peekable(),
+            target_least_readable_version,
+            max_versions_per_batch,
+        })
+    }
+
+    /// Returns the nodes becoming stale at the next version, unless it is after the target.
+    fn next_version(&mut self) -> Result<Option<(Version, Vec<NodeKey>)>> {
+        let index = match self.stale_node_indices.next().transpose()? {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+        let version = index.stale_since_version;
+        if version > self.target_least_readable_version {
+            return Ok(None);
+        }
+
+        let mut node_keys = vec![index.node_key];
+        while let Some(res) = self.stale_node_indices.peek() {
+            if let Ok(index_ref) = res {
+                if index_ref.stale_since_version != version {
+                    break;
+                }
+            }
+
+            let index = self
+                .stale_node_indices
+                .next()
+                .transpose()?
+                .expect("Should be Some.");
+            node_keys.push(index.node_key);
+        }
+
+        Ok(Some((version, node_keys)))
+    }
+
+    fn next_batch(&mut self) -> Result<Option<StaleNodePruneBatch>> {
+        let mut node_keys = vec![];
+        let mut least_readable_version = None;
+        for _ in 0..self.max_versions_per_batch {
+            match self.next_version()? {
+                Some((version, keys)) => {
+                    node_keys.extend(keys);
+                    least_readable_version = Some(version);
+                }
+                None => break,
+            }
+        }
+
+        Ok(
+            least_readable_version.map(|least_readable_version| StaleNodePruneBatch {
+                node_keys,
+                least_readable_version,
+            }),
+        )
+    }
+}
+
+impl<'a> Iterator for StaleNodePruner<'a> {
+    type Item = Result<StaleNodePruneBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_batch().transpose()
+    }
+}
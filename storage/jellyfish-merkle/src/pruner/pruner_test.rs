@@ -0,0 +1,81 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    mock_tree_store::MockTreeStore, node_type::NodeKey, pruner::StaleNodePruner,
+    JellyfishMerkleTree,
+};
+use libra_crypto::HashValue;
+use libra_types::{account_state_blob::AccountStateBlob, transaction::Version};
+
+/// Updates the same key at every version. The tree holds a single leaf at the root, which becomes
+/// stale at the next version.
+fn init_db(num_versions: Version) -> MockTreeStore {
+    let db = MockTreeStore::default();
+    let tree = JellyfishMerkleTree::new(&db);
+    let key = HashValue::random();
+    for version in 0..num_versions {
+        let blob = AccountStateBlob::from(version.to_le_bytes().to_vec());
+        let (_root_hash, batch) = tree.put_blob_set(vec![(key, blob)], version).unwrap();
+        db.write_tree_update_batch(batch).unwrap();
+    }
+    db
+}
+
+#[test]
+fn test_prune_in_batches() {
+    let db = init_db(10);
+    let batches = StaleNodePruner::new(&db, 0, 5, 2 /* max_versions_per_batch */)
+        .unwrap()
+        .collect::<anyhow::Result<Vec<_>>>()
+        .unwrap();
+
+    // Nodes become stale from version 1 on, so versions 1 to 5 are covered by 3 batches.
+    assert_eq!(
+        batches
+            .iter()
+            .map(|batch| batch.least_readable_version)
+            .collect::<Vec<_>>(),
+        vec![2, 4, 5]
+    );
+    let node_keys: Vec<_> = batches.into_iter().flat_map(|b| b.node_keys).collect();
+    // The roots of the versions 0 to 4.
+    assert_eq!(node_keys.len(), 5);
+    for version in 0..5 {
+        assert!(node_keys.contains(&NodeKey::new_empty_path(version)));
+    }
+    assert!(!node_keys.contains(&NodeKey::new_empty_path(5)));
+}
+
+#[test]
+fn test_resume_from_checkpoint() {
+    let db = init_db(10);
+    let mut pruner = StaleNodePruner::new(&db, 0, 9, 3 /* max_versions_per_batch */).unwrap();
+    let first_batch = pruner.next().unwrap().unwrap();
+    let remaining = pruner.collect::<anyhow::Result<Vec<_>>>().unwrap();
+
+    // Resuming from the checkpoint yields the same batches, except that the nodes becoming
+    // stale at the checkpoint are deleted again, which is harmless.
+    let resumed = StaleNodePruner::new(&db, first_batch.least_readable_version, 9, 3)
+        .unwrap()
+        .collect::<anyhow::Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(
+        resumed.last().unwrap().least_readable_version,
+        remaining.last().unwrap().least_readable_version
+    );
+    let resumed_node_keys: Vec<_> = resumed.into_iter().flat_map(|b| b.node_keys).collect();
+    for node_key in remaining.into_iter().flat_map(|b| b.node_keys) {
+        assert!(resumed_node_keys.contains(&node_key));
+    }
+}
+
+#[test]
+fn test_nothing_to_prune() {
+    let db = init_db(10);
+    assert!(StaleNodePruner::new(&db, 0, 0, 10)
+        .unwrap()
+        .next()
+        .is_none());
+    assert!(StaleNodePruner::new(&db, 0, 0, 0).is_err());
+}
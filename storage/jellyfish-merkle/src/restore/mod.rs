@@ -22,7 +22,7 @@ use libra_crypto::{
 use libra_nibble::Nibble;
 use libra_types::{
     account_state_blob::AccountStateBlob,
-    proof::{SparseMerkleInternalNode, SparseMerkleRangeProof},
+    proof::{CompressedSparseMerkleRangeProof, SparseMerkleInternalNode, SparseMerkleRangeProof},
     transaction::Version,
 };
 use mirai_annotations::*;
@@ -284,6 +284,16 @@ where
         Ok(())
     }
 
+    /// Same as `add_chunk`, with the proof in the compressed form, which leaves out the default
+    /// siblings.
+    pub fn add_chunk_with_compressed_proof(
+        &mut self,
+        chunk: Vec<(HashValue, AccountStateBlob)>,
+        proof: CompressedSparseMerkleRangeProof,
+    ) -> Result<()> {
+        self.add_chunk(chunk, proof.decompress()?)
+    }
+
     /// Restores one account.
     fn add_one(&mut self, new_key: HashValue, new_value: AccountStateBlob) {
         let nibble_path = NibblePath::new(new_key.to_vec());
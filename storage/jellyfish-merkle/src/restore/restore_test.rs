@@ -82,7 +82,9 @@ proptest! {
                     version,
                 )
                 .unwrap();
-            restore.add_chunk(remaining_accounts, proof).unwrap();
+            restore
+                .add_chunk_with_compressed_proof(remaining_accounts, proof.compress())
+                .unwrap();
             restore.finish().unwrap();
         }
 
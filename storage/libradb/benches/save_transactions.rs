@@ -0,0 +1,120 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Measures the throughput of `save_transactions` across RocksDB tuning knobs, to guide the
+//! `rocksdb_config` of the storage config.
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use libra_config::config::{ColumnFamilyConfig, RocksdbConfig};
+use libra_temppath::TempPath;
+use libra_types::{ledger_info::LedgerInfoWithSignatures, transaction::TransactionToCommit};
+use libradb::{test_helper::arb_blocks_to_commit_impl, LibraDB};
+use proptest::{
+    strategy::{Strategy, ValueTree},
+    test_runner::TestRunner,
+};
+use storage_interface::DbWriter;
+
+/// Column families written by every commit.
+const HOT_COLUMN_FAMILIES: &[&str] = &[
+    "default",
+    "event",
+    "event_accumulator",
+    "event_by_key",
+    "jellyfish_merkle_node",
+    "stale_node_index",
+    "transaction",
+    "transaction_accumulator",
+    "transaction_by_account",
+    "transaction_by_hash",
+    "transaction_info",
+];
+
+fn with_column_families(cf_config: ColumnFamilyConfig) -> RocksdbConfig {
+    RocksdbConfig {
+        column_families: HOT_COLUMN_FAMILIES
+            .iter()
+            .map(|cf_name| (cf_name.to_string(), cf_config.clone()))
+            .collect(),
+        ..RocksdbConfig::default()
+    }
+}
+
+fn rocksdb_configs() -> Vec<(&'static str, RocksdbConfig)> {
+    vec![
+        ("default", RocksdbConfig::default()),
+        (
+            "max_background_jobs_8",
+            RocksdbConfig {
+                max_background_jobs: 8,
+                ..RocksdbConfig::default()
+            },
+        ),
+        (
+            "write_buffer_size_256m",
+            with_column_families(ColumnFamilyConfig {
+                write_buffer_size: Some(256 << 20),
+                ..ColumnFamilyConfig::default()
+            }),
+        ),
+        (
+            "unsynced_writes",
+            with_column_families(ColumnFamilyConfig {
+                sync_writes: false,
+                ..ColumnFamilyConfig::default()
+            }),
+        ),
+    ]
+}
+
+fn save_blocks(db: &LibraDB, blocks: &[(Vec<TransactionToCommit>, LedgerInfoWithSignatures)]) {
+    let mut cur_ver = 0;
+    for (txns_to_commit, ledger_info_with_sigs) in blocks {
+        db.save_transactions(txns_to_commit, cur_ver, Some(ledger_info_with_sigs))
+            .unwrap();
+        cur_ver += txns_to_commit.len() as u64;
+    }
+}
+
+fn save_transactions(c: &mut Criterion) {
+    let blocks = arb_blocks_to_commit_impl(
+        100, /* num_accounts */
+        100, /* max_txn_per_block */
+        20,  /* max_blocks */
+    )
+    .new_tree(&mut TestRunner::default())
+    .unwrap()
+    .current();
+    let num_txns: usize = blocks.iter().map(|(txns, _)| txns.len()).sum();
+
+    let mut group = c.benchmark_group("save_transactions");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(num_txns as u64));
+    for (name, rocksdb_config) in rocksdb_configs() {
+        group.bench_function(name, |b| {
+            b.iter_batched(
+                || {
+                    let tmp_dir = TempPath::new();
+                    let db = LibraDB::open_with_rocksdb_config(
+                        &tmp_dir,
+                        false, /* readonly */
+                        None,  /* pruner */
+                        &rocksdb_config,
+                    )
+                    .unwrap();
+                    (tmp_dir, db)
+                },
+                |(tmp_dir, db)| {
+                    save_blocks(&db, &blocks);
+                    // Dropped out of the measurement.
+                    (tmp_dir, db)
+                },
+                BatchSize::PerIteration,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, save_transactions);
+criterion_main!(benches);
@@ -9,14 +9,19 @@ use crate::{
 use anyhow::{ensure, Result};
 use itertools::zip_eq;
 use jellyfish_merkle::iterator::JellyfishMerkleIterator;
-use libra_crypto::hash::HashValue;
+use libra_crypto::hash::{HashValue, TransactionAccumulatorHasher};
 use libra_types::{
     account_state_blob::AccountStateBlob,
+    accumulator_summary::{AccumulatorCheckpoint, AccumulatorSummary},
+    epoch_change::EpochChangeProof,
     ledger_info::LedgerInfoWithSignatures,
-    proof::{SparseMerkleRangeProof, TransactionAccumulatorRangeProof, TransactionInfoWithProof},
+    proof::{
+        accumulator::InMemoryAccumulator, SparseMerkleRangeProof, TransactionAccumulatorRangeProof,
+        TransactionInfoWithProof,
+    },
     transaction::{Transaction, TransactionInfo, Version},
 };
-use std::sync::Arc;
+use std::{collections::BTreeSet, sync::Arc};
 
 /// `BackupHandler` provides functionalities for LibraDB data backup.
 #[derive(Clone)]
@@ -132,4 +137,88 @@ impl BackupHandler {
         self.ledger_store
             .get_epoch_ending_ledger_info_iter(start_epoch, end_epoch)
     }
+
+    /// Gets the summary of the transaction accumulator over `[first_version, last_version]`, with
+    /// a checkpoint every `interval` versions and at the end of every epoch.
+    /// N.B. the `LedgerInfo` the summary ends with will always be in the same epoch of the
+    /// `last_version`, so the summary can extend beyond `last_version`.
+    pub fn get_accumulator_summary(
+        &self,
+        first_version: Version,
+        last_version: Version,
+        interval: u64,
+    ) -> Result<AccumulatorSummary> {
+        ensure!(
+            last_version >= first_version,
+            "Bad transaction range: [{}, {}]",
+            first_version,
+            last_version
+        );
+        ensure!(interval > 0, "Checkpoint interval must be positive.");
+
+        let epoch = self.ledger_store.get_epoch(last_version)?;
+        let ledger_info_with_sigs = self.ledger_store.get_latest_ledger_info_in_epoch(epoch)?;
+        let ledger_info = ledger_info_with_sigs.ledger_info();
+        let ledger_version = ledger_info.version();
+
+        // From the epoch-ending LedgerInfo preceding `first_version`, or genesis, up to the one
+        // preceding or equal to `ledger_info_with_sigs`.
+        let start_epoch = self
+            .ledger_store
+            .get_epoch(first_version)?
+            .saturating_sub(1);
+        let end_epoch = if ledger_info.ends_epoch() {
+            epoch + 1
+        } else {
+            epoch
+        };
+        let epoch_ending_ledger_infos = self
+            .ledger_store
+            .get_epoch_ending_ledger_info_iter(start_epoch, end_epoch)?
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut checkpoint_versions: BTreeSet<Version> = (first_version..=last_version)
+            .skip(interval as usize - 1)
+            .step_by(interval as usize)
+            .collect();
+        checkpoint_versions.extend(
+            epoch_ending_ledger_infos
+                .iter()
+                .map(|li| li.ledger_info().version())
+                .filter(|version| *version >= first_version),
+        );
+        checkpoint_versions.insert(last_version);
+        checkpoint_versions.insert(ledger_version);
+
+        let frozen_subtree_roots = self.ledger_store.get_frozen_subtree_hashes(first_version)?;
+        let mut accumulator = InMemoryAccumulator::<TransactionAccumulatorHasher>::new(
+            frozen_subtree_roots.clone(),
+            first_version,
+        )?;
+        let checkpoints = checkpoint_versions
+            .into_iter()
+            .map(|version| {
+                let consistency_proof = self
+                    .ledger_store
+                    .get_accumulator_extension_subtrees(accumulator.num_leaves(), version)?;
+                accumulator = accumulator.append_subtrees(
+                    consistency_proof.subtrees(),
+                    version + 1 - accumulator.num_leaves(),
+                )?;
+                Ok(AccumulatorCheckpoint {
+                    version,
+                    root_hash: accumulator.root_hash(),
+                    consistency_proof,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(AccumulatorSummary::new(
+            first_version,
+            frozen_subtree_roots,
+            checkpoints,
+            EpochChangeProof::new(epoch_ending_ledger_infos, false /* more */),
+            ledger_info_with_sigs,
+        ))
+    }
 }
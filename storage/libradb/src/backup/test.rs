@@ -41,4 +41,36 @@ proptest! {
             .unwrap();
         prop_assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_get_accumulator_summary(input in arb_blocks_to_commit(), interval in 1u64..4) {
+        let tmp_dir = TempPath::new();
+        let db = LibraDB::new_for_test(&tmp_dir);
+
+        let mut cur_ver = 0;
+        for (txns_to_commit, ledger_info_with_sigs) in input.iter() {
+            db.save_transactions(&txns_to_commit, cur_ver, Some(ledger_info_with_sigs))
+                .unwrap();
+            cur_ver += txns_to_commit.len() as u64;
+        }
+
+        let first_version = cur_ver / 2;
+        let summary = db
+            .get_backup_handler()
+            .get_accumulator_summary(first_version, cur_ver - 1, interval)
+            .unwrap();
+        summary.verify_accumulator().unwrap();
+
+        // Checkpoints agree with the LedgerInfos committed at their versions.
+        for (_, ledger_info_with_sigs) in input.iter() {
+            let ledger_info = ledger_info_with_sigs.ledger_info();
+            let checkpoint = summary
+                .checkpoints
+                .iter()
+                .find(|checkpoint| checkpoint.version == ledger_info.version());
+            if let Some(checkpoint) = checkpoint {
+                prop_assert_eq!(checkpoint.root_hash, ledger_info.transaction_accumulator_hash());
+            }
+        }
+    }
 }
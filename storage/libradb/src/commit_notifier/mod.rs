@@ -0,0 +1,99 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module publishes the transactions committed to [`LibraDB`](crate::LibraDB) to the
+//! components interested in them, e.g. JSON-RPC subscriptions, mempool or indexers, so that they
+//! don't need to poll the latest version.
+
+use channel::{
+    libra_channel::{self, Receiver, Sender},
+    message_queues::QueueStyle,
+};
+use libra_types::{
+    contract_event::ContractEvent,
+    ledger_info::LedgerInfoWithSignatures,
+    transaction::{Transaction, TransactionToCommit, Version},
+};
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+/// The transactions committed together with a ledger info.
+///
+/// Transactions saved without a ledger info are not notified, a subscriber observing a gap
+/// between the versions of two consecutive notifications can read the missing ones from the DB.
+/// The same applies when a slow subscriber has older notifications dropped from its queue.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommitNotification {
+    /// The version of the first of `transactions`.
+    pub first_version: Version,
+    pub transactions: Vec<Transaction>,
+    /// The events emitted by each of `transactions`.
+    pub events: Vec<Vec<ContractEvent>>,
+    /// The ledger info certifying `transactions`.
+    pub ledger_info_with_sigs: LedgerInfoWithSignatures,
+}
+
+/// The receiving end of a subscription to the commits.
+pub type CommitSubscription = Receiver<(), Arc<CommitNotification>>;
+
+/// Fans the commit notifications out to all the live subscriptions.
+pub(crate) struct CommitNotifier {
+    subscribers: Mutex<Vec<Sender<(), Arc<CommitNotification>>>>,
+}
+
+impl CommitNotifier {
+    pub fn new() -> Self {
+        Self {
+            subscribers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns a subscription queuing up to `capacity` notifications, the oldest ones being
+    /// dropped first when the subscriber falls behind.
+    pub fn subscribe(&self, capacity: NonZeroUsize) -> CommitSubscription {
+        let (sender, receiver) = libra_channel::new(QueueStyle::KLAST, capacity, None);
+        self.subscribers.lock().unwrap().push(sender);
+        receiver
+    }
+
+    /// Publishes the transactions that just got committed, dropping the subscriptions that have
+    /// been closed.
+    pub fn notify(
+        &self,
+        first_version: Version,
+        txns_to_commit: &[TransactionToCommit],
+        ledger_info_with_sigs: &LedgerInfoWithSignatures,
+    ) {
+        let mut subscribers = self.subscribers.lock().unwrap();
+        if subscribers.is_empty() {
+            return;
+        }
+
+        let notification = Arc::new(CommitNotification {
+            first_version,
+            transactions: txns_to_commit
+                .iter()
+                .map(|txn_to_commit| txn_to_commit.transaction().clone())
+                .collect(),
+            events: txns_to_commit
+                .iter()
+                .map(|txn_to_commit| txn_to_commit.events().to_vec())
+                .collect(),
+            ledger_info_with_sigs: ledger_info_with_sigs.clone(),
+        });
+        *subscribers = subscribers
+            .drain(..)
+            .filter_map(|mut sender| {
+                sender
+                    .push((), Arc::clone(&notification))
+                    .ok()
+                    .map(|_| sender)
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod test;
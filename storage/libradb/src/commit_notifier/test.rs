@@ -0,0 +1,69 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{test_helper::arb_blocks_to_commit, LibraDB};
+use futures::{executor::block_on, StreamExt};
+use itertools::izip;
+use libra_temppath::TempPath;
+use proptest::prelude::*;
+use storage_interface::DbWriter;
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(10))]
+
+    #[test]
+    fn test_commit_notifications(blocks in arb_blocks_to_commit()) {
+        let tmp_dir = TempPath::new();
+        let db = LibraDB::new_for_test(&tmp_dir);
+        let mut subscription = db.subscribe_to_commits(NonZeroUsize::new(blocks.len()).unwrap());
+        // A closed subscription doesn't get in the way of the others.
+        drop(db.subscribe_to_commits(NonZeroUsize::new(1).unwrap()));
+
+        let mut cur_ver = 0;
+        for (txns_to_commit, ledger_info_with_sigs) in &blocks {
+            db.save_transactions(txns_to_commit, cur_ver, Some(ledger_info_with_sigs))
+                .unwrap();
+            cur_ver += txns_to_commit.len() as u64;
+        }
+
+        let mut cur_ver = 0;
+        for (txns_to_commit, ledger_info_with_sigs) in &blocks {
+            let notification = block_on(subscription.next()).unwrap();
+            prop_assert_eq!(notification.first_version, cur_ver);
+            prop_assert_eq!(&notification.ledger_info_with_sigs, ledger_info_with_sigs);
+            prop_assert_eq!(notification.transactions.len(), txns_to_commit.len());
+            for (txn, events, txn_to_commit) in
+                izip!(&notification.transactions, &notification.events, txns_to_commit)
+            {
+                prop_assert_eq!(txn, txn_to_commit.transaction());
+                prop_assert_eq!(events.as_slice(), txn_to_commit.events());
+            }
+            cur_ver += txns_to_commit.len() as u64;
+        }
+        prop_assert_eq!(db.commit_notifier.subscribers.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_slow_subscriber(blocks in arb_blocks_to_commit()) {
+        let tmp_dir = TempPath::new();
+        let db = LibraDB::new_for_test(&tmp_dir);
+        let mut subscription = db.subscribe_to_commits(NonZeroUsize::new(1).unwrap());
+
+        let mut cur_ver = 0;
+        for (txns_to_commit, ledger_info_with_sigs) in &blocks {
+            db.save_transactions(txns_to_commit, cur_ver, Some(ledger_info_with_sigs))
+                .unwrap();
+            cur_ver += txns_to_commit.len() as u64;
+        }
+
+        // Only the latest notification is kept.
+        let (last_txns_to_commit, last_ledger_info_with_sigs) = blocks.last().unwrap();
+        let notification = block_on(subscription.next()).unwrap();
+        prop_assert_eq!(
+            notification.first_version,
+            cur_ver - last_txns_to_commit.len() as u64
+        );
+        prop_assert_eq!(&notification.ledger_info_with_sigs, last_ledger_info_with_sigs);
+    }
+}
@@ -12,7 +12,7 @@ use crate::{
     ledger_counters::LedgerCounter,
     schema::{
         event::EventSchema, event_accumulator::EventAccumulatorSchema,
-        event_by_key::EventByKeySchema,
+        event_by_key::EventByKeySchema, event_by_type::EventByTypeSchema,
     },
 };
 use accumulator::{HashReader, MerkleAccumulator};
@@ -23,21 +23,37 @@ use libra_crypto::{
 };
 use libra_types::{
     account_address::AccountAddress,
-    contract_event::ContractEvent,
+    contract_event::{ContractEvent, TypeTag},
     event::EventKey,
     proof::{position::Position, EventAccumulatorProof, EventProof},
     transaction::Version,
 };
 use schemadb::{schema::ValueCodec, ReadOptions, DB};
-use std::{convert::TryFrom, sync::Arc};
+use std::{
+    convert::TryFrom,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 
 pub(crate) struct EventStore {
     db: Arc<DB>,
+    /// Whether `put_events` also indexes the events by type in `EventByTypeSchema`.
+    index_by_type: AtomicBool,
 }
 
 impl EventStore {
     pub fn new(db: Arc<DB>) -> Self {
-        Self { db }
+        Self {
+            db,
+            index_by_type: AtomicBool::new(false),
+        }
+    }
+
+    /// Enables or disables indexing the events saved from now on by type.
+    pub fn set_index_by_type(&self, enabled: bool) {
+        self.index_by_type.store(enabled, Ordering::Relaxed);
     }
 
     /// Get all of the events given a transaction version.
@@ -59,6 +75,19 @@ impl EventStore {
         Ok(events)
     }
 
+    /// Get the event given transaction version and the index of the event queried.
+    pub fn get_event_by_version_and_index(
+        &self,
+        version: Version,
+        index: u64,
+    ) -> Result<ContractEvent> {
+        self.db
+            .get::<EventSchema>(&(version, index))?
+            .ok_or_else(|| {
+                LibraDbError::NotFound(format!("Event {} of Txn {}", index, version)).into()
+            })
+    }
+
     /// Get the event raw data given transaction version and the index of the event queried.
     pub fn get_event_with_proof_by_version_and_index(
         &self,
@@ -66,10 +95,7 @@ impl EventStore {
         index: u64,
     ) -> Result<(ContractEvent, EventAccumulatorProof)> {
         // Get event content.
-        let event = self
-            .db
-            .get::<EventSchema>(&(version, index))?
-            .ok_or_else(|| LibraDbError::NotFound(format!("Event {} of Txn {}", index, version)))?;
+        let event = self.get_event_by_version_and_index(version, index)?;
 
         // Get the number of events in total for the transaction at `version`.
         let mut iter = self.db.iter::<EventSchema>(ReadOptions::default())?;
@@ -181,6 +207,38 @@ impl EventStore {
         Ok(result)
     }
 
+    /// Given `type_tag` and `start_version`, returns the events of that type identified by
+    /// transaction version and index among all events emitted by the same transaction. Result
+    /// won't contain records with a transaction version > `ledger_version` and is in ascending
+    /// order. Only the events saved while indexing by type was enabled are found.
+    pub fn lookup_events_by_type(
+        &self,
+        type_tag: &TypeTag,
+        start_version: Version,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<
+        Vec<(
+            Version, // transaction version it belongs to
+            u64,     // index among events for the same transaction
+        )>,
+    > {
+        let type_tag_hash = hash_type_tag(type_tag)?;
+        let mut iter = self.db.iter::<EventByTypeSchema>(ReadOptions::default())?;
+        iter.seek(&(type_tag_hash, start_version, 0))?;
+
+        let mut result = Vec::new();
+        for res in iter.take(limit as usize) {
+            let ((hash, ver, idx), ()) = res?;
+            if hash != type_tag_hash || ver > ledger_version {
+                break;
+            }
+            result.push((ver, idx));
+        }
+
+        Ok(result)
+    }
+
     /// Save contract events yielded by the transaction at `version` and return root hash of the
     /// event accumulator formed by these events.
     pub fn put_events(
@@ -202,6 +260,12 @@ impl EventStore {
                     &(*event.key(), event.sequence_number()),
                     &(version, idx as u64),
                 )?;
+                if self.index_by_type.load(Ordering::Relaxed) {
+                    cs.batch.put::<EventByTypeSchema>(
+                        &(hash_type_tag(event.type_tag())?, version, idx as u64),
+                        &(),
+                    )?;
+                }
                 Ok(())
             })
             .collect::<Result<()>>()?;
@@ -221,6 +285,11 @@ impl EventStore {
     }
 }
 
+/// Type tags are variable in length, `EventByTypeSchema` is keyed by their hash instead.
+fn hash_type_tag(type_tag: &TypeTag) -> Result<HashValue> {
+    Ok(HashValue::sha3_256_of(&lcs::to_bytes(type_tag)?))
+}
+
 type Accumulator<'a> = MerkleAccumulator<EventHashReader<'a>, EventAccumulatorHasher>;
 
 struct EventHashReader<'a> {
@@ -9,7 +9,7 @@ use libra_proptest_helpers::Index;
 use libra_temppath::TempPath;
 use libra_types::{
     account_address::AccountAddress,
-    contract_event::ContractEvent,
+    contract_event::{ContractEvent, TypeTag},
     event::EventKey,
     proptest_types::{AccountInfoUniverse, ContractEventGen},
 };
@@ -117,6 +117,96 @@ proptest! {
     }
 }
 
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(10))]
+
+    #[test]
+    fn test_lookup_events_by_type(
+        event_batches in vec(
+            vec((any::<ContractEvent>().no_shrink(), 0..3usize), 0..10),
+            1..20
+        ),
+    ) {
+        let type_tags = [TypeTag::Bool, TypeTag::U64, TypeTag::Address];
+        let event_batches: Vec<Vec<ContractEvent>> = event_batches
+            .into_iter()
+            .map(|events| {
+                events
+                    .into_iter()
+                    .map(|(event, type_idx)| {
+                        ContractEvent::new(
+                            *event.key(),
+                            event.sequence_number(),
+                            type_tags[type_idx].clone(),
+                            event.event_data().to_vec(),
+                        )
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let tmp_dir = TempPath::new();
+        let db = LibraDB::new_for_test(&tmp_dir);
+        let store = &db.event_store;
+        // The first batch is saved before indexing by type is enabled.
+        save(store, 0, &event_batches[0]);
+        store.set_index_by_type(true);
+        for (ver, events) in event_batches.iter().enumerate().skip(1) {
+            save(store, ver as Version, events);
+        }
+
+        let ledger_version = event_batches.len() as Version - 1;
+        let mid_version = ledger_version / 2;
+        for type_tag in &type_tags {
+            let expected: Vec<_> = event_batches
+                .iter()
+                .enumerate()
+                .skip(1)
+                .flat_map(|(ver, events)| {
+                    events
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, event)| event.type_tag() == type_tag)
+                        .map(move |(idx, _)| (ver as Version, idx as u64))
+                })
+                .collect();
+
+            prop_assert_eq!(
+                &store
+                    .lookup_events_by_type(type_tag, 0, 1000, ledger_version)
+                    .unwrap(),
+                &expected
+            );
+            prop_assert_eq!(
+                store
+                    .lookup_events_by_type(type_tag, 0, 2, ledger_version)
+                    .unwrap(),
+                expected.iter().take(2).cloned().collect::<Vec<_>>()
+            );
+            prop_assert_eq!(
+                store
+                    .lookup_events_by_type(type_tag, mid_version, 1000, ledger_version)
+                    .unwrap(),
+                expected
+                    .iter()
+                    .filter(|(ver, _)| *ver >= mid_version)
+                    .cloned()
+                    .collect::<Vec<_>>()
+            );
+            prop_assert_eq!(
+                store
+                    .lookup_events_by_type(type_tag, 0, 1000, mid_version)
+                    .unwrap(),
+                expected
+                    .iter()
+                    .filter(|(ver, _)| *ver <= mid_version)
+                    .cloned()
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+}
+
 fn traverse_events_by_key(
     store: &EventStore,
     event_key: &EventKey,
@@ -0,0 +1,85 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This file defines `AccumulatorCache`, which keeps the hashes of frozen subtrees of the
+//! transaction accumulator in memory. A frozen subtree never changes once written, so its hash can
+//! be cached without invalidation. Serving proofs for recent versions reads the same few positions
+//! over and over, which would otherwise each be a DB read.
+
+use crate::LIBRA_STORAGE_ACCUMULATOR_CACHE_LOOKUPS;
+use libra_crypto::HashValue;
+use libra_types::proof::position::Position;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+};
+
+/// The number of positions cached by default.
+pub(crate) const DEFAULT_ACCUMULATOR_CACHE_SIZE: usize = 100_000;
+
+/// A cache of bounded size, which evicts the positions in the order they were inserted.
+pub(crate) struct AccumulatorCache {
+    inner: Mutex<Inner>,
+}
+
+struct Inner {
+    capacity: usize,
+    hashes: HashMap<Position, HashValue>,
+    insertion_order: VecDeque<Position>,
+}
+
+impl Inner {
+    fn evict_to(&mut self, size: usize) {
+        while self.hashes.len() > size {
+            let position = self
+                .insertion_order
+                .pop_front()
+                .expect("Every cached position is in the insertion order.");
+            self.hashes.remove(&position);
+        }
+    }
+}
+
+impl AccumulatorCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                capacity,
+                hashes: HashMap::new(),
+                insertion_order: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Changes the maximum number of positions cached, 0 disables the cache.
+    pub fn set_capacity(&self, capacity: usize) {
+        let mut inner = self.lock();
+        inner.capacity = capacity;
+        inner.evict_to(capacity);
+    }
+
+    pub fn get(&self, position: Position) -> Option<HashValue> {
+        let hash = self.lock().hashes.get(&position).copied();
+        LIBRA_STORAGE_ACCUMULATOR_CACHE_LOOKUPS
+            .with_label_values(&[if hash.is_some() { "hit" } else { "miss" }])
+            .inc();
+        hash
+    }
+
+    pub fn insert(&self, position: Position, hash: HashValue) {
+        let mut inner = self.lock();
+        if inner.capacity == 0 || inner.hashes.contains_key(&position) {
+            return;
+        }
+        let capacity = inner.capacity;
+        inner.evict_to(capacity - 1);
+        inner.hashes.insert(position, hash);
+        inner.insertion_order.push_back(position);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<Inner> {
+        self.inner
+            .lock()
+            .expect("Accumulator cache lock should not be poisoned.")
+    }
+}
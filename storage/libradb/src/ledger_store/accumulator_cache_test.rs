@@ -0,0 +1,26 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::accumulator_cache::AccumulatorCache;
+use libra_crypto::HashValue;
+use libra_types::proof::position::Position;
+
+#[test]
+fn test_evict_in_insertion_order() {
+    let cache = AccumulatorCache::new(2);
+    let hash = HashValue::random();
+    for i in 0..3 {
+        cache.insert(Position::from_inorder_index(i), hash);
+    }
+    assert_eq!(cache.get(Position::from_inorder_index(0)), None);
+    assert_eq!(cache.get(Position::from_inorder_index(1)), Some(hash));
+    assert_eq!(cache.get(Position::from_inorder_index(2)), Some(hash));
+
+    cache.set_capacity(1);
+    assert_eq!(cache.get(Position::from_inorder_index(1)), None);
+    assert_eq!(cache.get(Position::from_inorder_index(2)), Some(hash));
+
+    cache.set_capacity(0);
+    cache.insert(Position::from_inorder_index(3), hash);
+    assert_eq!(cache.get(Position::from_inorder_index(3)), None);
+}
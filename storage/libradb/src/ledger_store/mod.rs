@@ -4,9 +4,12 @@
 //! This file defines ledger store APIs that are related to the main ledger accumulator, from the
 //! root(LedgerInfo) to leaf(TransactionInfo).
 
+mod accumulator_cache;
+
 use crate::{
     change_set::ChangeSet,
     errors::LibraDbError,
+    ledger_store::accumulator_cache::{AccumulatorCache, DEFAULT_ACCUMULATOR_CACHE_SIZE},
     schema::{
         epoch_by_version::EpochByVersionSchema, ledger_info::LedgerInfoSchema,
         transaction_accumulator::TransactionAccumulatorSchema,
@@ -41,6 +44,10 @@ pub(crate) struct LedgerStore {
     /// cache it in memory in order to avoid reading DB and deserializing the object frequently. It
     /// should be updated every time new ledger info and signatures are persisted.
     latest_ledger_info: ArcSwap<Option<LedgerInfoWithSignatures>>,
+
+    /// Hashes of the transaction accumulator nodes recently read, to serve proofs without reading
+    /// DB for the positions shared by most of them.
+    accumulator_cache: AccumulatorCache,
 }
 
 impl LedgerStore {
@@ -60,9 +67,15 @@ impl LedgerStore {
         Self {
             db,
             latest_ledger_info: ArcSwap::from(Arc::new(ledger_info)),
+            accumulator_cache: AccumulatorCache::new(DEFAULT_ACCUMULATOR_CACHE_SIZE),
         }
     }
 
+    /// Changes the number of transaction accumulator nodes cached in memory, 0 disables the cache.
+    pub fn set_accumulator_cache_size(&self, size: usize) {
+        self.accumulator_cache.set_capacity(size);
+    }
+
     pub fn get_epoch(&self, version: Version) -> Result<u64> {
         let mut iter = self
             .db
@@ -155,7 +168,7 @@ impl LedgerStore {
     ) -> Result<TreeState> {
         Ok(TreeState::new(
             num_transactions,
-            Accumulator::get_frozen_subtree_hashes(self, num_transactions)?,
+            self.get_frozen_subtree_hashes(num_transactions)?,
             transaction_info.state_root_hash(),
         ))
     }
@@ -303,6 +316,22 @@ impl LedgerStore {
         Accumulator::get_consistency_proof(self, ledger_version + 1, client_known_version + 1)
     }
 
+    /// Gets the subtrees extending the accumulator of the first `num_known_leaves` transactions,
+    /// possibly none, to the ledger at `ledger_version`.
+    pub fn get_accumulator_extension_subtrees(
+        &self,
+        num_known_leaves: LeafCount,
+        ledger_version: Version,
+    ) -> Result<AccumulatorConsistencyProof> {
+        Accumulator::get_consistency_proof(self, ledger_version + 1, num_known_leaves)
+    }
+
+    /// Gets the roots of the frozen subtrees of the accumulator of the first `num_leaves`
+    /// transactions.
+    pub fn get_frozen_subtree_hashes(&self, num_leaves: LeafCount) -> Result<Vec<HashValue>> {
+        Accumulator::get_frozen_subtree_hashes(self, num_leaves)
+    }
+
     /// Write `txn_infos` to `batch`. Assigned `first_version` to the the version number of the
     /// first transaction, and so on.
     pub fn put_transaction_infos(
@@ -353,9 +382,16 @@ pub(crate) type Accumulator = MerkleAccumulator<LedgerStore, TransactionAccumula
 
 impl HashReader for LedgerStore {
     fn get(&self, position: Position) -> Result<HashValue> {
-        self.db
+        if let Some(hash) = self.accumulator_cache.get(position) {
+            return Ok(hash);
+        }
+        let hash = self
+            .db
             .get::<TransactionAccumulatorSchema>(&position)?
-            .ok_or_else(|| format_err!("{} does not exist.", position))
+            .ok_or_else(|| format_err!("{} does not exist.", position))?;
+        // Only nodes of frozen subtrees are persisted, so the hash never changes.
+        self.accumulator_cache.insert(position, hash);
+        Ok(hash)
     }
 }
 
@@ -432,6 +468,8 @@ impl<'a> Iterator for EpochEndingLedgerInfoIter<'a> {
     }
 }
 
+#[cfg(test)]
+mod accumulator_cache_test;
 #[cfg(test)]
 mod ledger_info_test;
 #[cfg(test)]
@@ -14,8 +14,11 @@
 pub mod test_helper;
 
 pub mod backup;
+pub mod commit_notifier;
 pub mod errors;
+pub mod migration;
 pub mod schema;
+pub mod verifier;
 
 mod change_set;
 mod event_store;
@@ -36,29 +39,37 @@ pub use libradb_test::test_save_blocks_impl;
 use crate::{
     backup::{backup_handler::BackupHandler, restore_handler::RestoreHandler},
     change_set::{ChangeSet, SealedChangeSet},
+    commit_notifier::{CommitNotifier, CommitSubscription},
     errors::LibraDbError,
     event_store::EventStore,
     ledger_counters::LedgerCounters,
     ledger_store::LedgerStore,
+    migration::Migration,
     pruner::Pruner,
     schema::*,
     state_store::StateStore,
     system_store::SystemStore,
     transaction_store::TransactionStore,
+    verifier::DbVerifier,
 };
-use anyhow::{ensure, Result};
+use anyhow::{ensure, format_err, Result};
 use itertools::{izip, zip_eq};
+use libra_config::config::RocksdbConfig;
 use libra_crypto::hash::{CryptoHash, HashValue, SPARSE_MERKLE_PLACEHOLDER_HASH};
-use libra_logger::prelude::*;
+use libra_logger::{
+    context::{with_context, TXN_HASH},
+    prelude::*,
+};
 use libra_metrics::{
-    register_int_counter, register_int_gauge, register_int_gauge_vec, IntCounter, IntGauge,
-    IntGaugeVec, OpMetrics,
+    register_int_counter, register_int_counter_vec, register_int_gauge, register_int_gauge_vec,
+    IntCounter, IntCounterVec, IntGauge, IntGaugeVec, OpMetrics,
 };
 use libra_types::{
     account_address::AccountAddress,
     account_state_blob::{AccountStateBlob, AccountStateWithProof},
-    contract_event::{ContractEvent, EventWithProof},
+    contract_event::{ContractEvent, EventWithProof, TypeTag},
     epoch_change::EpochChangeProof,
+    epoch_participation::EpochParticipation,
     event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
     proof::{
@@ -71,8 +82,8 @@ use libra_types::{
     },
 };
 use once_cell::sync::Lazy;
-use schemadb::{DB, DEFAULT_CF_NAME};
-use std::{iter::Iterator, path::Path, sync::Arc, time::Instant};
+use schemadb::{ColumnFamilyOptions, DBOptions, DB, DEFAULT_CF_NAME};
+use std::{iter::Iterator, num::NonZeroUsize, path::Path, sync::Arc, time::Instant};
 use storage_interface::{DbReader, DbWriter, StartupInfo, TreeState};
 
 static OP_COUNTER: Lazy<OpMetrics> = Lazy::new(|| OpMetrics::new_and_registered("storage"));
@@ -105,6 +116,18 @@ pub static LIBRA_STORAGE_LATEST_TXN_VERSION: Lazy<IntGauge> = Lazy::new(|| {
     .unwrap()
 });
 
+pub static LIBRA_STORAGE_ACCUMULATOR_CACHE_LOOKUPS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        // metric name
+        "libra_storage_accumulator_cache_lookups",
+        // metric description
+        "Libra storage transaction accumulator cache lookups",
+        // metric labels (dimensions)
+        &["result"]
+    )
+    .unwrap()
+});
+
 const MAX_LIMIT: u64 = 1000;
 
 // TODO: Either implement an iteration API to allow a very old client to loop through a long history
@@ -119,6 +142,26 @@ fn error_if_too_many_requested(num_requested: u64, max_allowed: u64) -> Result<(
     }
 }
 
+fn db_options(rocksdb_config: &RocksdbConfig) -> DBOptions {
+    DBOptions {
+        max_background_jobs: rocksdb_config.max_background_jobs,
+        max_total_wal_size: rocksdb_config.max_total_wal_size,
+        column_families: rocksdb_config
+            .column_families
+            .iter()
+            .map(|(cf_name, cf_config)| {
+                (
+                    cf_name.clone(),
+                    ColumnFamilyOptions {
+                        write_buffer_size: cf_config.write_buffer_size,
+                        sync_writes: cf_config.sync_writes,
+                    },
+                )
+            })
+            .collect(),
+    }
+}
+
 /// This holds a handle to the underlying DB responsible for physical storage and provides APIs for
 /// access to the core Libra data structures.
 pub struct LibraDB {
@@ -126,9 +169,10 @@ pub struct LibraDB {
     ledger_store: Arc<LedgerStore>,
     transaction_store: Arc<TransactionStore>,
     state_store: Arc<StateStore>,
-    event_store: EventStore,
+    event_store: Arc<EventStore>,
     system_store: SystemStore,
     pruner: Option<Pruner>,
+    commit_notifier: CommitNotifier,
 }
 
 impl LibraDB {
@@ -136,19 +180,39 @@ impl LibraDB {
         db_root_path: P,
         readonly: bool,
         prune_window: Option<u64>,
+    ) -> Result<Self> {
+        Self::open_with_rocksdb_config(
+            db_root_path,
+            readonly,
+            prune_window,
+            &RocksdbConfig::default(),
+        )
+    }
+
+    /// Same as [`LibraDB::open`], tuning RocksDB with `rocksdb_config`. The tuning doesn't apply
+    /// to a readonly DB.
+    pub fn open_with_rocksdb_config<P: AsRef<Path> + Clone>(
+        db_root_path: P,
+        readonly: bool,
+        prune_window: Option<u64>,
+        rocksdb_config: &RocksdbConfig,
     ) -> Result<Self> {
         let column_families = vec![
             /* LedgerInfo CF = */ DEFAULT_CF_NAME,
             EPOCH_BY_VERSION_CF_NAME,
+            EPOCH_PARTICIPATION_CF_NAME,
             EVENT_ACCUMULATOR_CF_NAME,
             EVENT_BY_KEY_CF_NAME,
+            EVENT_BY_TYPE_CF_NAME,
             EVENT_CF_NAME,
             JELLYFISH_MERKLE_NODE_CF_NAME,
             LEDGER_COUNTERS_CF_NAME,
+            SCHEMA_VERSION_CF_NAME,
             STALE_NODE_INDEX_CF_NAME,
             TRANSACTION_CF_NAME,
             TRANSACTION_ACCUMULATOR_CF_NAME,
             TRANSACTION_BY_ACCOUNT_CF_NAME,
+            TRANSACTION_BY_HASH_CF_NAME,
             TRANSACTION_INFO_CF_NAME,
         ];
 
@@ -158,9 +222,26 @@ impl LibraDB {
         let db = Arc::new(if readonly {
             DB::open_readonly(path.clone(), "libradb_ro", column_families)?
         } else {
-            DB::open(path.clone(), "libradb", column_families)?
+            DB::open_with_options(
+                path.clone(),
+                "libradb",
+                column_families,
+                &db_options(rocksdb_config),
+            )?
         });
 
+        if readonly {
+            let pending_migrations = migration::get_pending_migrations(&db)?;
+            if !pending_migrations.is_empty() {
+                warn!(
+                    "LibraDB opened readonly with {} pending migrations.",
+                    pending_migrations.len()
+                );
+            }
+        } else {
+            migration::migrate(&db)?;
+        }
+
         info!(
             "Opened LibraDB at {:?} in {} ms",
             path,
@@ -169,12 +250,13 @@ impl LibraDB {
 
         Ok(LibraDB {
             db: Arc::clone(&db),
-            event_store: EventStore::new(Arc::clone(&db)),
+            event_store: Arc::new(EventStore::new(Arc::clone(&db))),
             ledger_store: Arc::new(LedgerStore::new(Arc::clone(&db))),
             state_store: Arc::new(StateStore::new(Arc::clone(&db))),
             transaction_store: Arc::new(TransactionStore::new(Arc::clone(&db))),
             system_store: SystemStore::new(Arc::clone(&db)),
             pruner: prune_window.map(|n| Pruner::new(Arc::clone(&db), n)),
+            commit_notifier: CommitNotifier::new(),
         })
     }
 
@@ -189,6 +271,57 @@ impl LibraDB {
         .expect("Unable to open LibraDB")
     }
 
+    /// Changes the number of historical versions kept by the pruner. The pruner can not be
+    /// enabled on a db opened without it.
+    pub fn set_prune_window(&self, prune_window: u64) -> Result<()> {
+        let pruner = self
+            .pruner
+            .as_ref()
+            .ok_or_else(|| format_err!("LibraDB was opened without a pruner"))?;
+        pruner.set_historical_versions_to_keep(prune_window);
+        Ok(())
+    }
+
+    /// Changes the number of transaction accumulator nodes cached in memory to serve proofs, 0
+    /// disables the cache.
+    pub fn set_accumulator_cache_size(&self, size: usize) {
+        self.ledger_store.set_accumulator_cache_size(size);
+    }
+
+    /// Enables or disables indexing the events committed from now on by their type, which
+    /// `get_events_by_type` relies on.
+    pub fn set_index_events_by_type(&self, enabled: bool) {
+        self.event_store.set_index_by_type(enabled);
+    }
+
+    /// Returns the schema version the DB is written with, `None` if it predates schema versioning
+    /// or is empty.
+    pub fn get_schema_version(&self) -> Result<Option<u64>> {
+        migration::get_schema_version(&self.db)
+    }
+
+    /// Returns the migrations that will be applied once the DB is opened for writing.
+    pub fn get_pending_migrations(&self) -> Result<Vec<&'static Migration>> {
+        migration::get_pending_migrations(&self.db)
+    }
+
+    /// Subscribes to the transactions committed from now on with a ledger info. Up to `capacity`
+    /// notifications are queued, the oldest ones being dropped when the subscriber falls behind.
+    pub fn subscribe_to_commits(&self, capacity: NonZeroUsize) -> CommitSubscription {
+        self.commit_notifier.subscribe(capacity)
+    }
+
+    /// Flushes all the data written so far to disk.
+    pub fn flush(&self) -> Result<()> {
+        self.db.flush_all()
+    }
+
+    /// Reads the latest transaction info from disk, to check that the DB can still serve reads.
+    pub fn check_health(&self) -> Result<()> {
+        self.ledger_store.get_latest_transaction_info_option()?;
+        Ok(())
+    }
+
     // ================================== Public API ==================================
 
     /// Returns ledger infos reflecting epoch bumps starting with the given epoch. If there are no
@@ -300,6 +433,49 @@ impl LibraDB {
         )
     }
 
+    /// Gets an instance of `DbVerifier` to check the integrity of the data stored.
+    pub fn get_verifier(&self) -> DbVerifier {
+        DbVerifier::new(
+            Arc::clone(&self.ledger_store),
+            Arc::clone(&self.transaction_store),
+            Arc::clone(&self.state_store),
+            Arc::clone(&self.event_store),
+        )
+    }
+
+    /// Returns up to `limit` committed events of type `type_tag` emitted by the transactions at or
+    /// after `start_version`, with the versions of the transactions, in ascending order. Only the
+    /// events committed while indexing events by type was enabled are returned, see
+    /// `set_index_events_by_type`.
+    pub fn get_events_by_type(
+        &self,
+        type_tag: &TypeTag,
+        start_version: Version,
+        limit: u64,
+    ) -> Result<Vec<(Version, ContractEvent)>> {
+        error_if_too_many_requested(limit, MAX_LIMIT)?;
+
+        let ledger_version = match self.ledger_store.get_latest_ledger_info_option() {
+            Some(ledger_info_with_sigs) => ledger_info_with_sigs.ledger_info().version(),
+            None => return Ok(Vec::new()),
+        };
+
+        self.event_store
+            .lookup_events_by_type(type_tag, start_version, limit, ledger_version)?
+            .into_iter()
+            .map(|(ver, idx)| {
+                let event = self.event_store.get_event_by_version_and_index(ver, idx)?;
+                ensure!(
+                    event.type_tag() == type_tag,
+                    "Index broken, expected type: {}, actual: {}",
+                    type_tag,
+                    event.type_tag()
+                );
+                Ok((ver, event))
+            })
+            .collect()
+    }
+
     // ================================== Private APIs ==================================
     fn get_events_by_event_key(
         &self,
@@ -432,19 +608,33 @@ impl LibraDB {
             .collect::<Result<()>>()?;
 
         // Transaction accumulator updates. Get result root hash.
-        let txn_infos = izip!(txns_to_commit, state_root_hashes, event_root_hashes)
-            .map(|(t, s, e)| {
-                Ok(TransactionInfo::new(
-                    t.transaction().hash(),
-                    s,
-                    e,
-                    t.gas_used(),
-                    t.major_status(),
-                ))
-            })
-            .collect::<Result<Vec<_>>>()?;
+        let txn_infos = izip!(
+            first_version..=last_version,
+            txns_to_commit,
+            state_root_hashes,
+            event_root_hashes
+        )
+        .map(|(ver, t, s, e)| {
+            let txn_hash = t.transaction().hash();
+            with_context(TXN_HASH, txn_hash.to_hex(), || {
+                debug!("[storage] Saving transaction at version {}", ver)
+            });
+            Ok(TransactionInfo::new(
+                txn_hash,
+                s,
+                e,
+                t.gas_used(),
+                t.major_status(),
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?;
         assert_eq!(txn_infos.len(), txns_to_commit.len());
 
+        // Validator participation updates.
+        let first_epoch = self.ledger_store.get_epoch(first_version)?;
+        self.system_store
+            .put_epoch_participation(first_epoch, txns_to_commit, &mut cs.batch)?;
+
         let new_root_hash =
             self.ledger_store
                 .put_transaction_infos(first_version, &txn_infos, &mut cs)?;
@@ -525,6 +715,20 @@ impl DbReader for LibraDB {
             .transpose()
     }
 
+    /// Looks up the version of the transaction in the hash index maintained when transactions are
+    /// saved, then gets it with proof based on `ledger_version`.
+    fn get_transaction_by_hash(
+        &self,
+        hash: HashValue,
+        ledger_version: Version,
+        fetch_events: bool,
+    ) -> Result<Option<TransactionWithProof>> {
+        self.transaction_store
+            .lookup_transaction_by_hash(hash, ledger_version)?
+            .map(|version| self.get_transaction_with_proof(version, ledger_version, fetch_events))
+            .transpose()
+    }
+
     // ======================= State Synchronizer Internal APIs ===================================
     /// Gets a batch of transactions for the purpose of synchronizing state to another node.
     ///
@@ -596,6 +800,17 @@ impl DbReader for LibraDB {
         Ok(events)
     }
 
+    fn get_events_with_proofs(
+        &self,
+        event_key: &EventKey,
+        start: u64,
+        ascending: bool,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<EventWithProof>> {
+        self.get_events_by_event_key(event_key, start, ascending, limit, ledger_version)
+    }
+
     /// Gets ledger info at specified version and ensures it's an epoch change.
     fn get_epoch_ending_ledger_info(&self, version: u64) -> Result<LedgerInfoWithSignatures> {
         self.ledger_store.get_epoch_ending_ledger_info(version)
@@ -640,6 +855,32 @@ impl DbReader for LibraDB {
         ))
     }
 
+    /// Gets the subtrees of the transaction accumulator at `target_version` covering the
+    /// transactions after `client_known_version`. Appending them to the frozen subtrees of the
+    /// accumulator at `client_known_version` gives the accumulator at `target_version`.
+    fn get_accumulator_consistency_proof(
+        &self,
+        client_known_version: Version,
+        target_version: Version,
+    ) -> Result<AccumulatorConsistencyProof> {
+        ensure!(
+            client_known_version <= target_version,
+            "The client known version {} should be equal to or older than target version {}.",
+            client_known_version,
+            target_version
+        );
+        let latest_version = self.get_latest_version()?;
+        ensure!(
+            target_version <= latest_version,
+            "The target version {} is greater than the latest version currently in ledger: {}",
+            target_version,
+            latest_version
+        );
+
+        self.ledger_store
+            .get_consistency_proof(client_known_version, target_version)
+    }
+
     fn get_account_state_with_proof(
         &self,
         address: AccountAddress,
@@ -714,6 +955,10 @@ impl DbReader for LibraDB {
         };
         Ok(ts)
     }
+
+    fn get_epoch_participation(&self, epoch: u64) -> Result<Option<EpochParticipation>> {
+        self.system_store.get_epoch_participation(epoch)
+    }
 }
 
 impl DbWriter for LibraDB {
@@ -771,6 +1016,8 @@ impl DbWriter for LibraDB {
         // Once everything is successfully persisted, update the latest in-memory ledger info.
         if let Some(x) = ledger_info_with_sigs {
             self.ledger_store.set_latest_ledger_info(x.clone());
+            self.commit_notifier
+                .notify(first_version, txns_to_commit, x);
         }
 
         // Only increment counter if commit succeeds and there are at least one transaction written
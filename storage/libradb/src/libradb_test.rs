@@ -9,12 +9,15 @@ use crate::{
 };
 #[allow(unused_imports)]
 use jellyfish_merkle::node_type::{Node, NodeKey};
-use libra_crypto::hash::CryptoHash;
+use libra_crypto::hash::{CryptoHash, TransactionAccumulatorHasher};
 use libra_temppath::TempPath;
 #[allow(unused_imports)]
 use libra_types::{
-    account_config::AccountResource, contract_event::ContractEvent, ledger_info::LedgerInfo,
-    proof::SparseMerkleLeafNode, vm_status::StatusCode,
+    account_config::AccountResource,
+    contract_event::ContractEvent,
+    ledger_info::LedgerInfo,
+    proof::{accumulator::InMemoryAccumulator, SparseMerkleLeafNode},
+    vm_status::StatusCode,
 };
 use proptest::prelude::*;
 use std::collections::HashMap;
@@ -103,6 +106,50 @@ pub fn test_save_blocks_impl(input: Vec<(Vec<TransactionToCommit>, LedgerInfoWit
     );
     let (_, ledger_infos_with_sigs): (Vec<_>, Vec<_>) = input.iter().cloned().unzip();
     verify_epochs(&db, &ledger_infos_with_sigs);
+    verify_accumulator_consistency(&db, cur_ver - 1);
+}
+
+fn verify_accumulator_consistency(db: &LibraDB, latest_version: Version) {
+    let txn_info_hashes: Vec<_> = (0..=latest_version)
+        .map(|version| {
+            db.ledger_store
+                .get_transaction_info(version)
+                .unwrap()
+                .hash()
+        })
+        .collect();
+    let accumulator_at = |version: Version| {
+        InMemoryAccumulator::<TransactionAccumulatorHasher>::from_leaves(
+            &txn_info_hashes[..=version as usize],
+        )
+    };
+
+    for &(client_known_version, target_version) in &[
+        (0, latest_version),
+        (latest_version / 2, latest_version),
+        (0, latest_version / 2),
+        (latest_version, latest_version),
+    ] {
+        let proof = db
+            .get_accumulator_consistency_proof(client_known_version, target_version)
+            .unwrap();
+        let extended = accumulator_at(client_known_version)
+            .append_subtrees(proof.subtrees(), target_version - client_known_version)
+            .unwrap();
+        assert_eq!(
+            extended.root_hash(),
+            accumulator_at(target_version).root_hash()
+        );
+    }
+
+    if latest_version > 0 {
+        assert!(db
+            .get_accumulator_consistency_proof(latest_version, 0)
+            .is_err());
+    }
+    assert!(db
+        .get_accumulator_consistency_proof(0, latest_version + 1)
+        .is_err());
 }
 
 fn test_sync_transactions_impl(input: Vec<(Vec<TransactionToCommit>, LedgerInfoWithSignatures)>) {
@@ -0,0 +1,112 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module keeps the physical layout of an existing LibraDB up to date with the running code.
+//!
+//! The version of the schemas a DB is written with is persisted in
+//! [`SchemaVersionSchema`](crate::schema::schema_version::SchemaVersionSchema). Every change to
+//! the column families or to the encoding of their records bumps [`LATEST_SCHEMA_VERSION`] and
+//! registers a [`Migration`] in [`MIGRATIONS`], which is applied when the DB is opened for
+//! writing, so that nodes don't need to re-sync from genesis.
+
+use crate::schema::{schema_version::SchemaVersionSchema, transaction_info::TransactionInfoSchema};
+use anyhow::{ensure, Result};
+use libra_logger::prelude::*;
+use schemadb::{ReadOptions, SchemaBatch, DB};
+
+/// A step bringing the DB from schema version `version - 1` to `version`.
+pub struct Migration {
+    /// The schema version of the DB once the migration is applied.
+    pub version: u64,
+    /// What the migration changes, for operators.
+    pub description: &'static str,
+    /// Puts the changes to the DB in the batch, which is committed together with the new schema
+    /// version.
+    apply: fn(&DB, &mut SchemaBatch) -> Result<()>,
+}
+
+/// All the migrations, in order of the version they bring the DB to.
+pub static MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    description: "Start tracking the schema version of DBs created before it was persisted.",
+    apply: start_tracking_schema_version,
+}];
+
+/// The schema version the current code reads and writes.
+pub const LATEST_SCHEMA_VERSION: u64 = 1;
+
+/// Returns the schema version persisted in the DB, `None` if it predates schema versioning or is
+/// empty.
+pub(crate) fn get_schema_version(db: &DB) -> Result<Option<u64>> {
+    db.get::<SchemaVersionSchema>(&())
+}
+
+/// Returns the migrations that are yet to be applied to the DB.
+pub(crate) fn get_pending_migrations(db: &DB) -> Result<Vec<&'static Migration>> {
+    get_pending_migrations_impl(db, MIGRATIONS, LATEST_SCHEMA_VERSION)
+}
+
+/// Applies the pending migrations to the DB one by one, persisting the schema version after each
+/// of them.
+pub(crate) fn migrate(db: &DB) -> Result<()> {
+    migrate_impl(db, MIGRATIONS, LATEST_SCHEMA_VERSION)
+}
+
+fn get_pending_migrations_impl<'a>(
+    db: &DB,
+    migrations: &'a [Migration],
+    latest_schema_version: u64,
+) -> Result<Vec<&'a Migration>> {
+    let schema_version = match get_schema_version(db)? {
+        Some(schema_version) => schema_version,
+        // A new DB is written with the latest schemas from the start.
+        None if is_empty(db)? => latest_schema_version,
+        None => 0,
+    };
+    ensure!(
+        schema_version <= latest_schema_version,
+        "DB schema version {} is newer than the latest one known: {}.",
+        schema_version,
+        latest_schema_version,
+    );
+
+    Ok(migrations
+        .iter()
+        .filter(|migration| migration.version > schema_version)
+        .collect())
+}
+
+fn migrate_impl(db: &DB, migrations: &[Migration], latest_schema_version: u64) -> Result<()> {
+    let pending_migrations = get_pending_migrations_impl(db, migrations, latest_schema_version)?;
+    if pending_migrations.is_empty() {
+        if get_schema_version(db)?.is_none() {
+            db.put::<SchemaVersionSchema>(&(), &latest_schema_version)?;
+        }
+        return Ok(());
+    }
+
+    for migration in pending_migrations {
+        info!(
+            "Migrating DB to schema version {}: {}",
+            migration.version, migration.description
+        );
+        let mut batch = SchemaBatch::new();
+        (migration.apply)(db, &mut batch)?;
+        batch.put::<SchemaVersionSchema>(&(), &migration.version)?;
+        db.write_schemas(batch)?;
+    }
+    Ok(())
+}
+
+fn start_tracking_schema_version(_db: &DB, _batch: &mut SchemaBatch) -> Result<()> {
+    Ok(())
+}
+
+fn is_empty(db: &DB) -> Result<bool> {
+    let mut iter = db.iter::<TransactionInfoSchema>(ReadOptions::default())?;
+    iter.seek_to_first();
+    Ok(iter.next().is_none())
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,110 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{test_helper::arb_blocks_to_commit, LibraDB};
+use anyhow::bail;
+use libra_temppath::TempPath;
+use proptest::prelude::*;
+use storage_interface::DbWriter;
+
+fn succeed(_db: &DB, _batch: &mut SchemaBatch) -> Result<()> {
+    Ok(())
+}
+
+fn fail(_db: &DB, _batch: &mut SchemaBatch) -> Result<()> {
+    bail!("Injected failure.")
+}
+
+fn forget_schema_version(db: &DB) {
+    let mut batch = SchemaBatch::new();
+    batch.delete::<SchemaVersionSchema>(&()).unwrap();
+    db.write_schemas(batch).unwrap();
+}
+
+#[test]
+fn test_migrations_are_in_order() {
+    for (i, migration) in MIGRATIONS.iter().enumerate() {
+        assert_eq!(migration.version, i as u64 + 1);
+    }
+    assert_eq!(MIGRATIONS.len() as u64, LATEST_SCHEMA_VERSION);
+}
+
+#[test]
+fn test_new_db_at_latest_schema_version() {
+    let tmp_dir = TempPath::new();
+    let db = LibraDB::new_for_test(&tmp_dir);
+
+    assert_eq!(
+        db.get_schema_version().unwrap(),
+        Some(LATEST_SCHEMA_VERSION)
+    );
+    assert!(db.get_pending_migrations().unwrap().is_empty());
+}
+
+#[test]
+fn test_newer_schema_version() {
+    let tmp_dir = TempPath::new();
+    let db = LibraDB::new_for_test(&tmp_dir);
+    db.db
+        .put::<SchemaVersionSchema>(&(), &(LATEST_SCHEMA_VERSION + 1))
+        .unwrap();
+
+    assert!(db.get_pending_migrations().is_err());
+    assert!(migrate(&db.db).is_err());
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(5))]
+
+    #[test]
+    fn test_migrate(blocks in arb_blocks_to_commit()) {
+        let tmp_dir = TempPath::new();
+        let db = LibraDB::new_for_test(&tmp_dir);
+        let mut cur_ver = 0;
+        for (txns_to_commit, ledger_info_with_sigs) in &blocks {
+            db.save_transactions(txns_to_commit, cur_ver, Some(ledger_info_with_sigs))
+                .unwrap();
+            cur_ver += txns_to_commit.len() as u64;
+        }
+        forget_schema_version(&db.db);
+
+        let migrations = [
+            Migration { version: 1, description: "first", apply: succeed },
+            Migration { version: 2, description: "second", apply: succeed },
+        ];
+        let pending_migrations = get_pending_migrations_impl(&db.db, &migrations, 2).unwrap();
+        prop_assert_eq!(pending_migrations.len(), 2);
+
+        migrate_impl(&db.db, &migrations, 2).unwrap();
+        prop_assert_eq!(get_schema_version(&db.db).unwrap(), Some(2));
+        prop_assert!(get_pending_migrations_impl(&db.db, &migrations, 2).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_migrate_stops_at_failure(blocks in arb_blocks_to_commit()) {
+        let tmp_dir = TempPath::new();
+        let db = LibraDB::new_for_test(&tmp_dir);
+        let mut cur_ver = 0;
+        for (txns_to_commit, ledger_info_with_sigs) in &blocks {
+            db.save_transactions(txns_to_commit, cur_ver, Some(ledger_info_with_sigs))
+                .unwrap();
+            cur_ver += txns_to_commit.len() as u64;
+        }
+        forget_schema_version(&db.db);
+
+        let migrations = [
+            Migration { version: 1, description: "first", apply: succeed },
+            Migration { version: 2, description: "second", apply: fail },
+            Migration { version: 3, description: "third", apply: succeed },
+        ];
+        prop_assert!(migrate_impl(&db.db, &migrations, 3).is_err());
+        prop_assert_eq!(get_schema_version(&db.db).unwrap(), Some(1));
+        let pending_versions: Vec<_> = get_pending_migrations_impl(&db.db, &migrations, 3)
+            .unwrap()
+            .iter()
+            .map(|migration| migration.version)
+            .collect();
+        prop_assert_eq!(pending_versions, vec![2, 3]);
+    }
+}
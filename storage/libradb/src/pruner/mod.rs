@@ -8,17 +8,17 @@ use crate::{
     schema::{
         jellyfish_merkle_node::JellyfishMerkleNodeSchema, stale_node_index::StaleNodeIndexSchema,
     },
+    state_store::StateStore,
     OP_COUNTER,
 };
 use anyhow::Result;
-use jellyfish_merkle::StaleNodeIndex;
+use jellyfish_merkle::pruner::StaleNodePruner;
 use libra_logger::prelude::*;
 use libra_types::transaction::Version;
-use schemadb::{ReadOptions, SchemaBatch, SchemaIterator, DB};
+use schemadb::{ReadOptions, SchemaBatch, DB};
 #[cfg(test)]
 use std::thread::sleep;
 use std::{
-    iter::Peekable,
     sync::{
         atomic::{AtomicU64, Ordering},
         mpsc::{channel, Receiver, Sender},
@@ -35,8 +35,9 @@ use std::{
 /// quits the worker thread eagerly without waiting for all pending work to be done.
 pub(crate) struct Pruner {
     /// Other than the latest version, how many historical versions to keep being readable. For
-    /// example, this being 0 means keep only the latest version.
-    historical_versions_to_keep: u64,
+    /// example, this being 0 means keep only the latest version. It can be changed while the
+    /// pruner runs.
+    historical_versions_to_keep: AtomicU64,
     /// The worker thread handle, created upon Pruner instance construction and joined upon its
     /// destruction. It only becomes `None` after joined in `drop()`.
     worker_thread: Option<JoinHandle<()>>,
@@ -61,17 +62,25 @@ impl Pruner {
             .expect("Creating pruner thread should succeed.");
 
         Self {
-            historical_versions_to_keep,
+            historical_versions_to_keep: AtomicU64::new(historical_versions_to_keep),
             worker_thread: Some(worker_thread),
             command_sender: Mutex::new(command_sender),
             worker_progress,
         }
     }
 
+    /// Changes how many historical versions are kept from the next wake up on. Versions that are
+    /// already pruned stay pruned when the window grows.
+    pub fn set_historical_versions_to_keep(&self, historical_versions_to_keep: u64) {
+        self.historical_versions_to_keep
+            .store(historical_versions_to_keep, Ordering::Relaxed);
+    }
+
     /// Sends pruning command to the worker thread when necessary.
     pub fn wake(&self, latest_version: Version) {
-        if latest_version > self.historical_versions_to_keep {
-            let least_readable_version = latest_version - self.historical_versions_to_keep;
+        let historical_versions_to_keep = self.historical_versions_to_keep.load(Ordering::Relaxed);
+        if latest_version > historical_versions_to_keep {
+            let least_readable_version = latest_version - historical_versions_to_keep;
             self.command_sender
                 .lock()
                 .expect("command_sender to pruner thread should lock.")
@@ -88,8 +97,9 @@ impl Pruner {
     pub fn wake_and_wait(&self, latest_version: Version) -> Result<()> {
         self.wake(latest_version);
 
-        if latest_version > self.historical_versions_to_keep {
-            let least_readable_version = latest_version - self.historical_versions_to_keep;
+        let historical_versions_to_keep = self.historical_versions_to_keep.load(Ordering::Relaxed);
+        if latest_version > historical_versions_to_keep {
+            let least_readable_version = latest_version - historical_versions_to_keep;
             // Assuming no big pruning chunks will be issued by a test.
             const TIMEOUT: Duration = Duration::from_secs(10);
             let end = Instant::now() + TIMEOUT;
@@ -149,13 +159,21 @@ impl Worker {
         command_receiver: Receiver<Command>,
         least_readable_version: Arc<AtomicU64>,
     ) -> Self {
+        let resumed_version = resume_least_readable_version(&db).unwrap_or_else(|e| {
+            crit!(
+                "Failed to resume pruning progress, starting over. Err: {}",
+                e
+            );
+            0
+        });
+        least_readable_version.store(resumed_version, Ordering::Relaxed);
         Self {
             db,
             command_receiver,
             least_readable_version,
-            target_least_readable_version: 0,
+            target_least_readable_version: resumed_version,
             blocking_recv: true,
-            index_min_nonpurged_version: 0,
+            index_min_nonpurged_version: resumed_version,
             index_purged_at: Instant::now(),
         }
     }
@@ -267,91 +285,53 @@ impl Worker {
     }
 }
 
-struct StaleNodeIndicesByVersionIterator<'a> {
-    inner: Peekable<SchemaIterator<'a, StaleNodeIndexSchema>>,
-    target_least_readable_version: Version,
-}
-
-impl<'a> StaleNodeIndicesByVersionIterator<'a> {
-    fn new(
-        db: &'a DB,
-        least_readable_version: Version,
-        target_least_readable_version: Version,
-    ) -> Result<Self> {
-        let mut iter = db.iter::<StaleNodeIndexSchema>(ReadOptions::default())?;
-        iter.seek(&least_readable_version)?;
-
-        Ok(Self {
-            inner: iter.peekable(),
-            target_least_readable_version,
-        })
-    }
-
-    fn next_result(&mut self) -> Result<Option<Vec<StaleNodeIndex>>> {
-        match self.inner.next().transpose()? {
-            None => Ok(None),
-            Some((index, _)) => {
-                let version = index.stale_since_version;
-                if version > self.target_least_readable_version {
-                    return Ok(None);
-                }
-
-                let mut indices = vec![index];
-                while let Some(res) = self.inner.peek() {
-                    if let Ok((index_ref, _)) = res {
-                        if index_ref.stale_since_version != version {
-                            break;
-                        }
-                    }
-
-                    let (index, _) = self.inner.next().transpose()?.expect("Should be Some.");
-                    indices.push(index);
-                }
-
-                Ok(Some(indices))
-            }
-        }
-    }
-}
-
-impl<'a> Iterator for StaleNodeIndicesByVersionIterator<'a> {
-    type Item = Result<Vec<StaleNodeIndex>>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.next_result().transpose()
-    }
-}
-
+/// Deletes the stale nodes of at most `max_versions` versions, starting from
+/// `least_readable_version` and up to `target_least_readable_version`, and returns the least
+/// readable version afterwards.
 pub fn prune_state(
     db: Arc<DB>,
     least_readable_version: Version,
     target_least_readable_version: Version,
     max_versions: usize,
 ) -> Result<Version> {
-    let indices = StaleNodeIndicesByVersionIterator::new(
-        &db,
+    if max_versions == 0 {
+        return Ok(least_readable_version);
+    }
+    let state_store = StateStore::new(Arc::clone(&db));
+    let prune_batch = StaleNodePruner::new(
+        &state_store,
         least_readable_version,
         target_least_readable_version,
+        max_versions,
     )?
-    .take(max_versions) // Iterator<Item = Result<Vec<StaleNodeIndex>>>
-    .collect::<Result<Vec<_>>>()? // now Vec<Vec<StaleNodeIndex>>
-    .into_iter()
-    .flatten()
-    .collect::<Vec<_>>();
-
-    if indices.is_empty() {
-        Ok(least_readable_version)
-    } else {
-        let new_least_readable_version = indices.last().expect("Should exist.").stale_since_version;
-        let mut batch = SchemaBatch::new();
-        indices
-            .into_iter()
-            .map(|index| batch.delete::<JellyfishMerkleNodeSchema>(&index.node_key))
-            .collect::<Result<_>>()?;
-        db.write_schemas(batch)?;
-        Ok(new_least_readable_version)
+    .next()
+    .transpose()?;
+    match prune_batch {
+        None => Ok(least_readable_version),
+        Some(prune_batch) => {
+            let mut batch = SchemaBatch::new();
+            prune_batch
+                .node_keys
+                .iter()
+                .map(|node_key| batch.delete::<JellyfishMerkleNodeSchema>(node_key))
+                .collect::<Result<_>>()?;
+            db.write_schemas(batch)?;
+            Ok(prune_batch.least_readable_version)
+        }
     }
 }
 
+/// Returns where pruning resumes after a restart. The stale node indices are only purged once the
+/// nodes they refer to are deleted, so if the oldest one left becomes stale at version `V`, the
+/// version before it is the oldest one still readable.
+fn resume_least_readable_version(db: &DB) -> Result<Version> {
+    let mut iter = db.iter::<StaleNodeIndexSchema>(ReadOptions::default())?;
+    iter.seek_to_first();
+    Ok(iter
+        .next()
+        .transpose()?
+        .map_or(0, |(index, _)| index.stale_since_version.saturating_sub(1)))
+}
+
 #[cfg(test)]
 mod test;
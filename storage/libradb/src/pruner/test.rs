@@ -155,3 +155,41 @@ fn test_worker_quit_eagerly() {
         verify_state_in_store(state_store, address, Some(&value2), 2);
     }
 }
+
+#[test]
+fn test_worker_resumes_progress() {
+    let address = AccountAddress::new([1u8; AccountAddress::LENGTH]);
+
+    let tmp_dir = TempPath::new();
+    let db = LibraDB::new_for_test(&tmp_dir).db;
+    let state_store = &StateStore::new(Arc::clone(&db));
+    for version in 0..4 {
+        put_account_state_set(
+            &db,
+            state_store,
+            vec![(address, AccountStateBlob::from(vec![version as u8]))],
+            version,
+        );
+    }
+
+    let new_worker_progress = || {
+        let (_command_sender, command_receiver) = channel();
+        let progress = Arc::new(AtomicU64::new(0));
+        Worker::new(Arc::clone(&db), command_receiver, Arc::clone(&progress));
+        progress.load(Ordering::Relaxed)
+    };
+    // Nothing pruned yet, the nodes becoming stale at version 1 are still there.
+    assert_eq!(new_worker_progress(), 0);
+
+    // Prune till version 2 and purge the index like the worker does.
+    assert_eq!(prune_state(Arc::clone(&db), 0, 2, 100).unwrap(), 2);
+    db.range_delete::<StaleNodeIndexSchema, Version>(&0, &3)
+        .unwrap();
+    assert_eq!(new_worker_progress(), 2);
+    verify_state_in_store(
+        state_store,
+        address,
+        Some(&AccountStateBlob::from(vec![2])),
+        2,
+    );
+}
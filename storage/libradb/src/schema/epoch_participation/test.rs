@@ -0,0 +1,16 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use proptest::prelude::*;
+use schemadb::schema::assert_encode_decode;
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        epoch in any::<u64>(),
+        participation in any::<EpochParticipation>(),
+    ) {
+        assert_encode_decode::<EpochParticipationSchema>(&epoch, &participation);
+    }
+}
@@ -0,0 +1,69 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for an event index via which the ContractEvents
+//! (represented by <txn_version, event_idx> tuples so that they can be fetched from `EventSchema`)
+//! of a Move type can be found in the order of the transactions emitting them.
+//!
+//! The type tag is variable in length, its hash is stored instead so that all the entries of the
+//! same type are adjacent and can be scanned from a version on.
+//!
+//! ```text
+//! |<-------------key------------->|
+//! | type_tag_hash | txn_ver | idx |
+//! ```
+
+use crate::schema::{ensure_slice_len_eq, EVENT_BY_TYPE_CF_NAME};
+use anyhow::Result;
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use libra_crypto::HashValue;
+use libra_types::transaction::Version;
+use schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use std::mem::size_of;
+
+define_schema!(EventByTypeSchema, Key, (), EVENT_BY_TYPE_CF_NAME);
+
+type TypeTagHash = HashValue;
+type Index = u64;
+type Key = (TypeTagHash, Version, Index);
+
+impl KeyCodec<EventByTypeSchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let (ref type_tag_hash, version, index) = *self;
+
+        let mut encoded = type_tag_hash.to_vec();
+        encoded.write_u64::<BigEndian>(version)?;
+        encoded.write_u64::<BigEndian>(index)?;
+
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        const HASH_SIZE: usize = HashValue::LENGTH;
+        const VERSION_SIZE: usize = size_of::<Version>();
+        ensure_slice_len_eq(data, HASH_SIZE + VERSION_SIZE + size_of::<Index>())?;
+
+        let type_tag_hash = HashValue::from_slice(&data[..HASH_SIZE])?;
+        let version = (&data[HASH_SIZE..HASH_SIZE + VERSION_SIZE]).read_u64::<BigEndian>()?;
+        let index = (&data[HASH_SIZE + VERSION_SIZE..]).read_u64::<BigEndian>()?;
+
+        Ok((type_tag_hash, version, index))
+    }
+}
+
+impl ValueCodec<EventByTypeSchema> for () {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(Vec::new())
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, 0)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,48 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for the version of the LibraDB schemas, which
+//! tells which migrations have been applied to the DB.
+//!
+//! There is only one record, under an empty key.
+//! ```text
+//! |<--key-->|<----value----->|
+//! |   ()    | schema version |
+//! ```
+
+use super::SCHEMA_VERSION_CF_NAME;
+use crate::schema::ensure_slice_len_eq;
+use anyhow::Result;
+use byteorder::{BigEndian, ReadBytesExt};
+use schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use std::mem::size_of;
+
+define_schema!(SchemaVersionSchema, (), u64, SCHEMA_VERSION_CF_NAME);
+
+impl KeyCodec<SchemaVersionSchema> for () {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(Vec::new())
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, 0)?;
+        Ok(())
+    }
+}
+
+impl ValueCodec<SchemaVersionSchema> for u64 {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, size_of::<u64>())?;
+        Ok((&data[..]).read_u64::<BigEndian>()?)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -16,7 +16,8 @@ use crate::{
 use anyhow::Result;
 use jellyfish_merkle::{
     node_type::{LeafNode, Node, NodeKey},
-    JellyfishMerkleTree, NodeBatch, TreeReader, TreeWriter, ROOT_NIBBLE_HEIGHT,
+    JellyfishMerkleTree, NodeBatch, StaleNodeIndex, StaleNodeIndexReader, TreeReader, TreeWriter,
+    ROOT_NIBBLE_HEIGHT,
 };
 use libra_crypto::{hash::CryptoHash, HashValue};
 use libra_types::{
@@ -25,7 +26,7 @@ use libra_types::{
     proof::{SparseMerkleProof, SparseMerkleRangeProof},
     transaction::Version,
 };
-use schemadb::{SchemaBatch, DB};
+use schemadb::{ReadOptions, SchemaBatch, DB};
 use std::{collections::HashMap, sync::Arc};
 
 pub(crate) struct StateStore {
@@ -206,6 +207,19 @@ impl TreeReader for StateStore {
     }
 }
 
+impl StaleNodeIndexReader for StateStore {
+    fn get_stale_node_indices(
+        &self,
+        start_version: Version,
+    ) -> Result<Box<dyn Iterator<Item = Result<StaleNodeIndex>> + '_>> {
+        let mut iter = self
+            .db
+            .iter::<StaleNodeIndexSchema>(ReadOptions::default())?;
+        iter.seek(&start_version)?;
+        Ok(Box::new(iter.map(|res| res.map(|(index, _)| index))))
+    }
+}
+
 impl TreeWriter for StateStore {
     fn write_node_batch(&self, node_batch: &NodeBatch) -> Result<()> {
         let mut batch = SchemaBatch::new();
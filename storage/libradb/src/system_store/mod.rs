@@ -6,11 +6,17 @@
 
 use crate::{
     ledger_counters::{LedgerCounterBumps, LedgerCounters},
-    schema::ledger_counters::LedgerCountersSchema,
+    schema::{
+        epoch_participation::EpochParticipationSchema, ledger_counters::LedgerCountersSchema,
+    },
 };
 use anyhow::Result;
 use libra_logger::prelude::*;
-use libra_types::transaction::Version;
+use libra_types::{
+    epoch_participation::EpochParticipation,
+    on_chain_config::new_epoch_event_key,
+    transaction::{Transaction, TransactionToCommit, Version},
+};
 use schemadb::{SchemaBatch, DB};
 use std::sync::Arc;
 
@@ -56,6 +62,51 @@ impl SystemStore {
 
         Ok(counters)
     }
+
+    /// Returns the validator participation statistics of `epoch`, or `None` if no block has been
+    /// committed in it yet.
+    pub fn get_epoch_participation(&self, epoch: u64) -> Result<Option<EpochParticipation>> {
+        self.db.get::<EpochParticipationSchema>(&epoch)
+    }
+
+    /// Records the blocks committed among `txns_to_commit` into the validator participation
+    /// statistics of their epochs. The first transaction is in `epoch`, each reconfiguration
+    /// moves the following ones to the next epoch.
+    pub fn put_epoch_participation(
+        &self,
+        mut epoch: u64,
+        txns_to_commit: &[TransactionToCommit],
+        batch: &mut SchemaBatch,
+    ) -> Result<()> {
+        let new_epoch_event_key = new_epoch_event_key();
+        let mut participation = None;
+
+        for txn_to_commit in txns_to_commit {
+            if let Transaction::BlockMetadata(block) = txn_to_commit.transaction() {
+                let mut current = match participation.take() {
+                    Some(current) => current,
+                    None => self.get_epoch_participation(epoch)?.unwrap_or_default(),
+                };
+                current.record_block(block);
+                participation = Some(current);
+            }
+            if txn_to_commit
+                .events()
+                .iter()
+                .any(|event| *event.key() == new_epoch_event_key)
+            {
+                if let Some(participation) = participation.take() {
+                    batch.put::<EpochParticipationSchema>(&epoch, &participation)?;
+                }
+                epoch += 1;
+            }
+        }
+        if let Some(participation) = participation {
+            batch.put::<EpochParticipationSchema>(&epoch, &participation)?;
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -3,7 +3,18 @@
 
 use super::*;
 use crate::{ledger_counters::LedgerCounter, LibraDB};
+use libra_crypto::HashValue;
 use libra_temppath::TempPath;
+use libra_types::{
+    account_address::AccountAddress,
+    block_metadata::BlockMetadata,
+    contract_event::{ContractEvent, TypeTag},
+    epoch_participation::ValidatorParticipation,
+    transaction::ChangeSet,
+    vm_status::StatusCode,
+    write_set::WriteSet,
+};
+use std::collections::HashMap;
 
 fn bump_ledger_counters(
     store: &SystemStore,
@@ -75,3 +86,83 @@ fn test_inc_ledger_counters() {
         assert_eq!(counters.get(LedgerCounter::EventsCreated), 1);
     }
 }
+
+fn block_to_commit(round: u64, proposer: AccountAddress) -> TransactionToCommit {
+    TransactionToCommit::new(
+        Transaction::BlockMetadata(BlockMetadata::new(
+            HashValue::random(),
+            round,
+            round,
+            vec![proposer],
+            proposer,
+        )),
+        HashMap::new(),
+        vec![],
+        0,
+        StatusCode::EXECUTED,
+    )
+}
+
+fn reconfiguration_to_commit() -> TransactionToCommit {
+    let event = ContractEvent::new(new_epoch_event_key(), 0, TypeTag::Bool, vec![]);
+    TransactionToCommit::new(
+        Transaction::WaypointWriteSet(ChangeSet::new(WriteSet::default(), vec![event.clone()])),
+        HashMap::new(),
+        vec![event],
+        0,
+        StatusCode::EXECUTED,
+    )
+}
+
+fn put_epoch_participation(
+    store: &SystemStore,
+    epoch: u64,
+    txns_to_commit: &[TransactionToCommit],
+) {
+    let mut batch = SchemaBatch::new();
+    store
+        .put_epoch_participation(epoch, txns_to_commit, &mut batch)
+        .unwrap();
+    store.db.write_schemas(batch).unwrap();
+}
+
+#[test]
+fn test_put_epoch_participation() {
+    let tmp_dir = TempPath::new();
+    let db = LibraDB::new_for_test(&tmp_dir);
+    let store = &db.system_store;
+    let a = AccountAddress::new([1; AccountAddress::LENGTH]);
+    let b = AccountAddress::new([2; AccountAddress::LENGTH]);
+
+    // The first batch spans a reconfiguration.
+    put_epoch_participation(
+        store,
+        1,
+        &[
+            block_to_commit(1, a),
+            reconfiguration_to_commit(),
+            block_to_commit(1, b),
+        ],
+    );
+    // The second batch adds to the statistics of the epoch it starts in.
+    put_epoch_participation(store, 2, &[block_to_commit(3, a)]);
+
+    let epoch_1 = store.get_epoch_participation(1).unwrap().unwrap();
+    assert_eq!(epoch_1.num_blocks(), 1);
+    assert_eq!(
+        epoch_1.validators().get(&a),
+        Some(&ValidatorParticipation {
+            num_proposals: 1,
+            num_votes: 1,
+        })
+    );
+    assert!(!epoch_1.validators().contains_key(&b));
+
+    let epoch_2 = store.get_epoch_participation(2).unwrap().unwrap();
+    assert_eq!(epoch_2.num_blocks(), 2);
+    assert_eq!(epoch_2.num_skipped_rounds(), 1);
+    assert_eq!(epoch_2.last_round(), 3);
+    assert_eq!(epoch_2.validators().len(), 2);
+
+    assert!(store.get_epoch_participation(3).unwrap().is_none());
+}
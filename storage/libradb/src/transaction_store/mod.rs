@@ -6,9 +6,13 @@
 use crate::{
     change_set::ChangeSet,
     errors::LibraDbError,
-    schema::{transaction::TransactionSchema, transaction_by_account::TransactionByAccountSchema},
+    schema::{
+        transaction::TransactionSchema, transaction_by_account::TransactionByAccountSchema,
+        transaction_by_hash::TransactionByHashSchema,
+    },
 };
 use anyhow::{ensure, format_err, Result};
+use libra_crypto::{hash::CryptoHash, HashValue};
 use libra_types::{
     account_address::AccountAddress,
     block_metadata::BlockMetadata,
@@ -45,6 +49,21 @@ impl TransactionStore {
         Ok(None)
     }
 
+    /// Gets the version of a transaction by its `hash`.
+    pub fn lookup_transaction_by_hash(
+        &self,
+        hash: HashValue,
+        ledger_version: Version,
+    ) -> Result<Option<Version>> {
+        if let Some(version) = self.db.get::<TransactionByHashSchema>(&hash)? {
+            if version <= ledger_version {
+                return Ok(Some(version));
+            }
+        }
+
+        Ok(None)
+    }
+
     /// Get signed transaction given `version`
     pub fn get_transaction(&self, version: Version) -> Result<Transaction> {
         self.db
@@ -107,6 +126,8 @@ impl TransactionStore {
                 &version,
             )?;
         }
+        cs.batch
+            .put::<TransactionByHashSchema>(&transaction.hash(), &version)?;
         cs.batch.put::<TransactionSchema>(&version, &transaction)?;
 
         Ok(())
@@ -44,9 +44,29 @@ proptest! {
                     .unwrap(),
                 Some(ver as Version)
             );
+            prop_assert_eq!(
+                store
+                    .lookup_transaction_by_hash(txn.hash(), ledger_version)
+                    .unwrap(),
+                Some(ver as Version)
+            );
+            if ver > 0 {
+                prop_assert_eq!(
+                    store
+                        .lookup_transaction_by_hash(txn.hash(), ver as Version - 1)
+                        .unwrap(),
+                    None
+                );
+            }
         }
 
         prop_assert!(store.get_transaction(ledger_version + 1).is_err());
+        prop_assert_eq!(
+            store
+                .lookup_transaction_by_hash(HashValue::zero(), ledger_version)
+                .unwrap(),
+            None
+        );
     }
 
     #[test]
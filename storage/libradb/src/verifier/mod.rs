@@ -0,0 +1,222 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module re-hashes the data stored in [`LibraDB`](crate::LibraDB) to detect corruption,
+//! e.g. after a disk incident, before a node trusts its DB again.
+
+use crate::{
+    event_store::EventStore,
+    ledger_store::{Accumulator, LedgerStore},
+    state_store::StateStore,
+    transaction_store::TransactionStore,
+};
+use anyhow::Result;
+use jellyfish_merkle::{iterator::JellyfishMerkleIterator, JellyfishMerkleTree};
+use libra_crypto::{
+    hash::{CryptoHash, EventAccumulatorHasher, TransactionAccumulatorHasher},
+    HashValue,
+};
+use libra_logger::prelude::*;
+use libra_types::{
+    contract_event::ContractEvent,
+    proof::accumulator::InMemoryAccumulator,
+    transaction::{TransactionInfo, Version},
+};
+use std::{collections::BTreeMap, fmt, sync::Arc};
+
+/// Number of versions between two progress reports in the logs.
+const PROGRESS_INTERVAL: u64 = 100_000;
+
+/// The kind of data found corrupted at a version.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CorruptionKind {
+    /// The transaction doesn't hash to the transaction hash of its transaction info.
+    TransactionHash,
+    /// The events of the transaction don't hash to the event root hash of its transaction info.
+    EventRootHash,
+    /// The transaction accumulator nodes don't match the transaction infos up to the version.
+    TransactionAccumulator,
+    /// A ledger info at the version carries a different transaction accumulator root hash.
+    LedgerInfo,
+    /// The state tree at the version doesn't hash to the state root hash of the transaction info.
+    StateRootHash,
+}
+
+/// The first corrupted version found by the [`DbVerifier`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Corruption {
+    pub version: Version,
+    pub kind: CorruptionKind,
+}
+
+impl fmt::Display for Corruption {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} corrupted at version {}", self.kind, self.version)
+    }
+}
+
+/// `DbVerifier` walks the ledger history stored in LibraDB and checks that it hashes to what the
+/// transaction infos, the transaction accumulator and the ledger infos claim.
+#[derive(Clone)]
+pub struct DbVerifier {
+    ledger_store: Arc<LedgerStore>,
+    transaction_store: Arc<TransactionStore>,
+    state_store: Arc<StateStore>,
+    event_store: Arc<EventStore>,
+}
+
+impl DbVerifier {
+    pub(crate) fn new(
+        ledger_store: Arc<LedgerStore>,
+        transaction_store: Arc<TransactionStore>,
+        state_store: Arc<StateStore>,
+        event_store: Arc<EventStore>,
+    ) -> Self {
+        Self {
+            ledger_store,
+            transaction_store,
+            state_store,
+            event_store,
+        }
+    }
+
+    /// Verifies the versions from `start_version` to `end_version` inclusively, returning the
+    /// first corruption found, if any. For each version, the transaction, its events and the root
+    /// node of the state tree are re-hashed against the transaction info, and the transaction
+    /// accumulator, rebuilt from the transaction infos, is checked against the stored one and the
+    /// ledger infos. The accumulator before `start_version` is trusted, so start from 0 to check
+    /// the whole history.
+    pub fn verify_range(
+        &self,
+        start_version: Version,
+        end_version: Version,
+    ) -> Result<Option<Corruption>> {
+        let mut accumulator = InMemoryAccumulator::<TransactionAccumulatorHasher>::new(
+            Accumulator::get_frozen_subtree_hashes(&*self.ledger_store, start_version)?,
+            start_version,
+        )?;
+        let ledger_info_root_hashes =
+            self.get_ledger_info_root_hashes(start_version, end_version)?;
+
+        for version in start_version..=end_version {
+            let txn_info = self.ledger_store.get_transaction_info(version)?;
+            if let Some(kind) = self.verify_version(version, &txn_info)? {
+                return Ok(Some(Corruption { version, kind }));
+            }
+
+            accumulator = accumulator.append(&[txn_info.hash()]);
+            if *accumulator.frozen_subtree_roots()
+                != Accumulator::get_frozen_subtree_hashes(&*self.ledger_store, version + 1)?
+            {
+                return Ok(Some(Corruption {
+                    version,
+                    kind: CorruptionKind::TransactionAccumulator,
+                }));
+            }
+            if let Some(root_hash) = ledger_info_root_hashes.get(&version) {
+                if *root_hash != accumulator.root_hash() {
+                    return Ok(Some(Corruption {
+                        version,
+                        kind: CorruptionKind::LedgerInfo,
+                    }));
+                }
+            }
+
+            if (version - start_version + 1) % PROGRESS_INTERVAL == 0 {
+                info!("Verified versions {} to {}.", start_version, version);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Recomputes the root hash of the whole state tree at `version` from the account state blobs
+    /// it stores, without trusting any of its internal nodes, and checks it against the state root
+    /// hash of the transaction info. This reads every account, so it takes a while on a large
+    /// state.
+    pub fn verify_state(&self, version: Version) -> Result<Option<Corruption>> {
+        let txn_info = self.ledger_store.get_transaction_info(version)?;
+        let blobs = JellyfishMerkleIterator::new(
+            Arc::clone(&self.state_store),
+            version,
+            HashValue::zero(),
+        )?
+        .collect::<Result<Vec<_>>>()?;
+        let (root_hash, _tree_update_batch) = JellyfishMerkleTree::new(&*self.state_store)
+            .batch_build_from_sorted_iter(version, blobs)?;
+
+        Ok(if root_hash == txn_info.state_root_hash() {
+            None
+        } else {
+            Some(Corruption {
+                version,
+                kind: CorruptionKind::StateRootHash,
+            })
+        })
+    }
+
+    fn verify_version(
+        &self,
+        version: Version,
+        txn_info: &TransactionInfo,
+    ) -> Result<Option<CorruptionKind>> {
+        let txn = self.transaction_store.get_transaction(version)?;
+        if txn.hash() != txn_info.transaction_hash() {
+            return Ok(Some(CorruptionKind::TransactionHash));
+        }
+
+        let event_hashes: Vec<_> = self
+            .event_store
+            .get_events_by_version(version)?
+            .iter()
+            .map(ContractEvent::hash)
+            .collect();
+        let event_root_hash =
+            InMemoryAccumulator::<EventAccumulatorHasher>::from_leaves(&event_hashes).root_hash();
+        if event_root_hash != txn_info.event_root_hash() {
+            return Ok(Some(CorruptionKind::EventRootHash));
+        }
+
+        // The root node is gone if the version has been pruned.
+        if let Some(state_root_hash) = self.state_store.get_root_hash_option(version)? {
+            if state_root_hash != txn_info.state_root_hash() {
+                return Ok(Some(CorruptionKind::StateRootHash));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Returns the transaction accumulator root hashes carried by the ledger infos stored for the
+    /// versions between `start_version` and `end_version`, keyed by version.
+    fn get_ledger_info_root_hashes(
+        &self,
+        start_version: Version,
+        end_version: Version,
+    ) -> Result<BTreeMap<Version, HashValue>> {
+        let latest_ledger_info = match self.ledger_store.get_latest_ledger_info_option() {
+            Some(ledger_info_with_sigs) => ledger_info_with_sigs,
+            None => return Ok(BTreeMap::new()),
+        };
+        let latest_epoch = latest_ledger_info.ledger_info().next_block_epoch();
+
+        let mut root_hashes = BTreeMap::new();
+        for ledger_info_with_sigs in self
+            .ledger_store
+            .get_epoch_ending_ledger_info_iter(0, latest_epoch)?
+            .chain(std::iter::once(Ok(latest_ledger_info)))
+        {
+            let ledger_info = ledger_info_with_sigs?.ledger_info().clone();
+            if ledger_info.version() >= start_version && ledger_info.version() <= end_version {
+                root_hashes.insert(
+                    ledger_info.version(),
+                    ledger_info.transaction_accumulator_hash(),
+                );
+            }
+        }
+        Ok(root_hashes)
+    }
+}
+
+#[cfg(test)]
+mod test;
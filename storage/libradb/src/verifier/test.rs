@@ -0,0 +1,99 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    schema::{event::EventSchema, transaction::TransactionSchema},
+    test_helper::arb_blocks_to_commit,
+    LibraDB,
+};
+use libra_temppath::TempPath;
+use libra_types::{
+    account_address::AccountAddress, contract_event::TypeTag, event::EventKey,
+    ledger_info::LedgerInfoWithSignatures, transaction::TransactionToCommit,
+};
+use proptest::prelude::*;
+use storage_interface::DbWriter;
+
+fn save_blocks(
+    db: &LibraDB,
+    blocks: &[(Vec<TransactionToCommit>, LedgerInfoWithSignatures)],
+) -> Version {
+    let mut cur_ver = 0;
+    for (txns_to_commit, ledger_info_with_sigs) in blocks {
+        db.save_transactions(txns_to_commit, cur_ver, Some(ledger_info_with_sigs))
+            .unwrap();
+        cur_ver += txns_to_commit.len() as u64;
+    }
+    cur_ver - 1
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(10))]
+
+    #[test]
+    fn test_verify_intact_db(blocks in arb_blocks_to_commit()) {
+        let tmp_dir = TempPath::new();
+        let db = LibraDB::new_for_test(&tmp_dir);
+        let latest_version = save_blocks(&db, &blocks);
+
+        let verifier = db.get_verifier();
+        prop_assert_eq!(verifier.verify_range(0, latest_version).unwrap(), None);
+        prop_assert_eq!(verifier.verify_range(latest_version, latest_version).unwrap(), None);
+        prop_assert_eq!(verifier.verify_state(latest_version).unwrap(), None);
+    }
+
+    #[test]
+    fn test_verify_corrupted_transaction(
+        blocks in arb_blocks_to_commit(),
+        index in any::<prop::sample::Index>(),
+    ) {
+        let tmp_dir = TempPath::new();
+        let db = LibraDB::new_for_test(&tmp_dir);
+        let latest_version = save_blocks(&db, &blocks);
+
+        // Swap in the transaction of another version.
+        let version = index.index(latest_version as usize + 1) as Version;
+        let other_version = if version == latest_version { 0 } else { version + 1 };
+        let other_txn = db.transaction_store.get_transaction(other_version).unwrap();
+        prop_assume!(other_txn != db.transaction_store.get_transaction(version).unwrap());
+        db.db.put::<TransactionSchema>(&version, &other_txn).unwrap();
+
+        prop_assert_eq!(
+            db.get_verifier().verify_range(0, latest_version).unwrap(),
+            Some(Corruption {
+                version,
+                kind: CorruptionKind::TransactionHash,
+            })
+        );
+    }
+
+    #[test]
+    fn test_verify_corrupted_event(
+        blocks in arb_blocks_to_commit(),
+        index in any::<prop::sample::Index>(),
+    ) {
+        let tmp_dir = TempPath::new();
+        let db = LibraDB::new_for_test(&tmp_dir);
+        let latest_version = save_blocks(&db, &blocks);
+
+        // Append an event nobody emitted.
+        let version = index.index(latest_version as usize + 1) as Version;
+        let num_events = db.event_store.get_events_by_version(version).unwrap().len() as u64;
+        let event = ContractEvent::new(
+            EventKey::new_from_address(&AccountAddress::ZERO, 0),
+            0,
+            TypeTag::Bool,
+            vec![],
+        );
+        db.db.put::<EventSchema>(&(version, num_events), &event).unwrap();
+
+        prop_assert_eq!(
+            db.get_verifier().verify_range(0, latest_version).unwrap(),
+            Some(Corruption {
+                version,
+                kind: CorruptionKind::EventRootHash,
+            })
+        );
+    }
+}
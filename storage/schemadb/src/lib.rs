@@ -39,6 +39,47 @@ pub type ColumnFamilyName = &'static str;
 /// [`LedgerInfo`](../types/ledger_info/struct.LedgerInfo.html).
 pub const DEFAULT_CF_NAME: ColumnFamilyName = "default";
 
+/// Tuning of the RocksDB instance backing a [`DB`], refer to the RocksDB documentation of the
+/// options for details.
+#[derive(Clone, Debug)]
+pub struct DBOptions {
+    pub max_background_jobs: i32,
+    pub max_total_wal_size: u64,
+    /// Options of the column families by name, the column families not listed use
+    /// [`ColumnFamilyOptions::default`].
+    pub column_families: HashMap<String, ColumnFamilyOptions>,
+}
+
+impl Default for DBOptions {
+    fn default() -> Self {
+        Self {
+            max_background_jobs: 2,
+            // For now we set the max total WAL size to be 1G. This config can be useful when
+            // column families are updated at non-uniform frequencies.
+            max_total_wal_size: 1 << 30,
+            column_families: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ColumnFamilyOptions {
+    /// Size of the memtable of the column family in bytes, `None` keeps the RocksDB default.
+    pub write_buffer_size: Option<usize>,
+    /// Whether the writes to the column family are synced to disk before returning. A batch is
+    /// synced as soon as one of the column families it writes to requires it.
+    pub sync_writes: bool,
+}
+
+impl Default for ColumnFamilyOptions {
+    fn default() -> Self {
+        Self {
+            write_buffer_size: None,
+            sync_writes: true,
+        }
+    }
+}
+
 #[derive(Debug)]
 enum WriteOp {
     Value(Vec<u8>),
@@ -184,6 +225,8 @@ pub struct DB {
     name: &'static str, // for logging
     inner: rocksdb::DB,
     column_families: Vec<ColumnFamilyName>,
+    /// Column families whose writes alone don't need to be synced to disk.
+    unsynced_column_families: HashSet<ColumnFamilyName>,
 }
 
 impl DB {
@@ -193,6 +236,16 @@ impl DB {
         path: impl AsRef<Path>,
         name: &'static str,
         column_families: Vec<ColumnFamilyName>,
+    ) -> Result<Self> {
+        Self::open_with_options(path, name, column_families, &DBOptions::default())
+    }
+
+    /// Same as [`DB::open`], tuning RocksDB with `options`.
+    pub fn open_with_options(
+        path: impl AsRef<Path>,
+        name: &'static str,
+        column_families: Vec<ColumnFamilyName>,
+        options: &DBOptions,
     ) -> Result<Self> {
         {
             let cfs_set: HashSet<_> = column_families.iter().collect();
@@ -209,12 +262,10 @@ impl DB {
         let mut db_opts = rocksdb::Options::default();
         db_opts.create_if_missing(true);
         db_opts.create_missing_column_families(true);
+        db_opts.set_max_background_jobs(options.max_background_jobs);
+        db_opts.set_max_total_wal_size(options.max_total_wal_size);
 
-        // For now we set the max total WAL size to be 1G. This config can be useful when column
-        // families are updated at non-uniform frequencies.
-        db_opts.set_max_total_wal_size(1 << 30);
-
-        let db = DB::open_cf(&db_opts, path, name, column_families)?;
+        let db = DB::open_cf(&db_opts, path, name, column_families, options)?;
         Ok(db)
     }
 
@@ -233,20 +284,38 @@ impl DB {
         path: impl AsRef<Path>,
         name: &'static str,
         column_families: Vec<ColumnFamilyName>,
+        options: &DBOptions,
     ) -> Result<DB> {
+        let default_cf_options = ColumnFamilyOptions::default();
+        let cf_options = |cf_name: &str| {
+            options
+                .column_families
+                .get(cf_name)
+                .unwrap_or(&default_cf_options)
+        };
+
         let inner = rocksdb::DB::open_cf_descriptors(
             opts,
             path,
             column_families.iter().map(|cf_name| {
                 let mut cf_opts = rocksdb::Options::default();
                 cf_opts.set_compression_type(rocksdb::DBCompressionType::Lz4);
+                if let Some(write_buffer_size) = cf_options(cf_name).write_buffer_size {
+                    cf_opts.set_write_buffer_size(write_buffer_size);
+                }
                 rocksdb::ColumnFamilyDescriptor::new((*cf_name).to_string(), cf_opts)
             }),
         )?;
+        let unsynced_column_families = column_families
+            .iter()
+            .filter(|cf_name| !cf_options(cf_name).sync_writes)
+            .cloned()
+            .collect();
         Ok(DB {
             name,
             inner,
             column_families,
+            unsynced_column_families,
         })
     }
 
@@ -268,6 +337,7 @@ impl DB {
             name,
             inner,
             column_families,
+            unsynced_column_families: HashSet::new(),
         })
     }
 
@@ -351,7 +421,15 @@ impl DB {
         }
         let serialized_size = db_batch.size_in_bytes();
 
-        self.inner.write_opt(db_batch, &default_write_options())?;
+        let mut write_opts = default_write_options();
+        if batch
+            .rows
+            .keys()
+            .all(|cf_name| self.unsynced_column_families.contains(cf_name))
+        {
+            write_opts.set_sync(false);
+        }
+        self.inner.write_opt(db_batch, &write_opts)?;
 
         // Bump counters only after DB write succeeds.
         for (cf_name, rows) in &batch.rows {
@@ -403,8 +481,8 @@ impl DB {
         Ok(cf_sizes)
     }
 
-    /// Flushes all memtable data. This is only used for testing `get_approximate_sizes_cf` in unit
-    /// tests.
+    /// Flushes all memtable data, e.g., before a planned shutdown so that the next start does not
+    /// replay the write-ahead log.
     pub fn flush_all(&self) -> Result<()> {
         for cf_name in &self.column_families {
             let cf_handle = self.get_cf_handle(cf_name)?;
@@ -414,9 +492,9 @@ impl DB {
     }
 }
 
-/// For now we always use synchronous writes. This makes sure that once the operation returns
-/// `Ok(())` the data is persisted even if the machine crashes. In the future we might consider
-/// selectively turning this off for some non-critical writes to improve performance.
+/// By default we use synchronous writes. This makes sure that once the operation returns `Ok(())`
+/// the data is persisted even if the machine crashes. Column families holding non-critical data can
+/// opt out via [`ColumnFamilyOptions::sync_writes`] to improve performance.
 fn default_write_options() -> rocksdb::WriteOptions {
     let mut opts = rocksdb::WriteOptions::default();
     opts.set_sync(true);
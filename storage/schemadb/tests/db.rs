@@ -7,7 +7,7 @@ use proptest::{collection::vec, prelude::*};
 use schemadb::{
     define_schema,
     schema::{KeyCodec, Schema, ValueCodec},
-    ColumnFamilyName, SchemaBatch, DB, DEFAULT_CF_NAME,
+    ColumnFamilyName, ColumnFamilyOptions, DBOptions, SchemaBatch, DB, DEFAULT_CF_NAME,
 };
 
 // Creating two schemas that share exactly the same structure but are stored in different column
@@ -299,6 +299,38 @@ fn test_reopen() {
     }
 }
 
+#[test]
+fn test_reopen_with_options() {
+    let tmpdir = libra_temppath::TempPath::new();
+    let mut options = DBOptions::default();
+    options.max_background_jobs = 4;
+    options.column_families.insert(
+        TestSchema1::COLUMN_FAMILY_NAME.to_string(),
+        ColumnFamilyOptions {
+            write_buffer_size: Some(1 << 20),
+            sync_writes: false,
+        },
+    );
+    {
+        let db = DB::open_with_options(&tmpdir.path(), "test", get_column_families(), &options)
+            .expect("Failed to open DB.");
+        db.put::<TestSchema1>(&TestField(0), &TestField(0)).unwrap();
+        db.put::<TestSchema2>(&TestField(1), &TestField(1)).unwrap();
+        db.flush_all().unwrap();
+    }
+    {
+        let db = open_db(&tmpdir);
+        assert_eq!(
+            db.get::<TestSchema1>(&TestField(0)).unwrap(),
+            Some(TestField(0)),
+        );
+        assert_eq!(
+            db.get::<TestSchema2>(&TestField(1)).unwrap(),
+            Some(TestField(1)),
+        );
+    }
+}
+
 #[test]
 fn test_open_read_only() {
     let tmpdir = libra_temppath::TempPath::new();
@@ -396,6 +396,13 @@ impl SparseMerkleTree {
         self.root.read_lock().hash()
     }
 
+    /// Returns the number of nodes in memory, across all the trees. Nodes shared by several trees
+    /// are counted once. Every update retains a new generation of the tree, whose nodes are
+    /// released once it is dropped, or pruned after it is committed.
+    pub fn num_retained_nodes() -> usize {
+        node::num_nodes()
+    }
+
     /// Prunes a tree by replacing every node reachable from root with a subtree node that has the
     /// same hash. If a node is empty or a subtree, we don't need to do anything. For example in
     /// the following case, if we drop `S_i`, we will replace o with a subtree node, then `o` no
@@ -25,7 +25,18 @@ use libra_types::{
     account_state_blob::AccountStateBlob,
     proof::{SparseMerkleInternalNode, SparseMerkleLeafNode},
 };
-use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, RwLock, RwLockReadGuard, RwLockWriteGuard,
+};
+
+/// The number of `SparseMerkleNode`s currently in memory, across all trees.
+static NUM_NODES: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns the number of nodes currently in memory, across all trees.
+pub fn num_nodes() -> usize {
+    NUM_NODES.load(Ordering::Relaxed)
+}
 
 /// We wrap the node in `RwLock`. The only case when we will update the node is when we
 /// drop a subtree originated from this node and commit things to storage. In that case we will
@@ -41,29 +52,28 @@ impl SparseMerkleNode {
         left_child: Arc<SparseMerkleNode>,
         right_child: Arc<SparseMerkleNode>,
     ) -> Self {
-        SparseMerkleNode {
-            node: RwLock::new(Node::new_internal(left_child, right_child)),
-        }
+        Self::new(Node::new_internal(left_child, right_child))
     }
 
     /// Constructs a new leaf node using given key and value.
     pub fn new_leaf(key: HashValue, value: LeafValue) -> Self {
-        SparseMerkleNode {
-            node: RwLock::new(Node::new_leaf(key, value)),
-        }
+        Self::new(Node::new_leaf(key, value))
     }
 
     /// Constructs a new subtree node with given root hash.
     pub fn new_subtree(hash: HashValue) -> Self {
-        SparseMerkleNode {
-            node: RwLock::new(Node::new_subtree(hash)),
-        }
+        Self::new(Node::new_subtree(hash))
     }
 
     /// Constructs a new empty node.
     pub fn new_empty() -> Self {
+        Self::new(Node::new_empty())
+    }
+
+    fn new(node: Node) -> Self {
+        NUM_NODES.fetch_add(1, Ordering::Relaxed);
         SparseMerkleNode {
-            node: RwLock::new(Node::new_empty()),
+            node: RwLock::new(node),
         }
     }
 
@@ -78,6 +88,12 @@ impl SparseMerkleNode {
     }
 }
 
+impl Drop for SparseMerkleNode {
+    fn drop(&mut self) {
+        NUM_NODES.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
 /// The underlying node is either `InternalNode`, `LeafNode`, `SubtreeNode` or `EmptyNode`.
 #[derive(Debug)]
 pub enum Node {
@@ -9,8 +9,9 @@ use libra_secure_net::NetworkClient;
 use libra_types::{
     account_address::AccountAddress,
     account_state_blob::{AccountStateBlob, AccountStateWithProof},
-    contract_event::ContractEvent,
+    contract_event::{ContractEvent, EventWithProof},
     epoch_change::EpochChangeProof,
+    epoch_participation::EpochParticipation,
     event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
     proof::{AccumulatorConsistencyProof, SparseMerkleProof},
@@ -106,6 +107,15 @@ impl DbReader for StorageClient {
         unimplemented!()
     }
 
+    fn get_transaction_by_hash(
+        &self,
+        _hash: HashValue,
+        _ledger_version: u64,
+        _fetch_events: bool,
+    ) -> Result<Option<TransactionWithProof>> {
+        unimplemented!()
+    }
+
     fn get_transactions(
         &self,
         _start_version: u64,
@@ -126,6 +136,17 @@ impl DbReader for StorageClient {
         unimplemented!()
     }
 
+    fn get_events_with_proofs(
+        &self,
+        _key: &EventKey,
+        _start: u64,
+        _ascending: bool,
+        _limit: u64,
+        _ledger_version: Version,
+    ) -> Result<Vec<EventWithProof>> {
+        unimplemented!()
+    }
+
     fn get_state_proof(
         &self,
         _known_version: u64,
@@ -145,6 +166,14 @@ impl DbReader for StorageClient {
         unimplemented!()
     }
 
+    fn get_accumulator_consistency_proof(
+        &self,
+        _client_known_version: Version,
+        _target_version: Version,
+    ) -> Result<AccumulatorConsistencyProof> {
+        unimplemented!()
+    }
+
     fn get_account_state_with_proof(
         &self,
         _address: AccountAddress,
@@ -177,6 +206,10 @@ impl DbReader for StorageClient {
     fn get_block_timestamp(&self, _version: u64) -> Result<u64> {
         unimplemented!()
     }
+
+    fn get_epoch_participation(&self, _epoch: u64) -> Result<Option<EpochParticipation>> {
+        unimplemented!()
+    }
 }
 
 impl DbWriter for StorageClient {
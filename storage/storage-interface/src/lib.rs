@@ -9,8 +9,9 @@ use libra_types::{
     account_address::AccountAddress,
     account_state::AccountState,
     account_state_blob::{AccountStateBlob, AccountStateWithProof},
-    contract_event::ContractEvent,
+    contract_event::{ContractEvent, EventWithProof},
     epoch_change::EpochChangeProof,
+    epoch_participation::EpochParticipation,
     epoch_state::EpochState,
     event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
@@ -158,12 +159,27 @@ pub trait DbReader: Send + Sync {
         limit: u64,
     ) -> Result<Vec<(u64, ContractEvent)>>;
 
+    /// Returns events by given event key, with the proofs of their inclusion in the ledger at
+    /// `ledger_version`.
+    fn get_events_with_proofs(
+        &self,
+        event_key: &EventKey,
+        start: u64,
+        ascending: bool,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<EventWithProof>>;
+
     /// See [`LibraDB::get_block_timestamp`].
     ///
     /// [`LibraDB::get_block_timestamp`]:
     /// ../libradb/struct.LibraDB.html#method.get_block_timestamp
     fn get_block_timestamp(&self, version: u64) -> Result<u64>;
 
+    /// Returns the statistics of validator participation in consensus during `epoch`, or `None`
+    /// if no block has been committed in it.
+    fn get_epoch_participation(&self, epoch: u64) -> Result<Option<EpochParticipation>>;
+
     /// See [`LibraDB::get_latest_account_state`].
     ///
     /// [`LibraDB::get_latest_account_state`]:
@@ -201,6 +217,19 @@ pub trait DbReader: Send + Sync {
         fetch_events: bool,
     ) -> Result<Option<TransactionWithProof>>;
 
+    /// Returns the transaction with the given hash, if it is committed at or before
+    /// `ledger_version`, with proof based on `ledger_version`.
+    /// See [`LibraDB::get_transaction_by_hash`].
+    ///
+    /// [`LibraDB::get_transaction_by_hash`]:
+    /// ../libradb/struct.LibraDB.html#method.get_transaction_by_hash
+    fn get_transaction_by_hash(
+        &self,
+        hash: HashValue,
+        ledger_version: Version,
+        fetch_events: bool,
+    ) -> Result<Option<TransactionWithProof>>;
+
     /// Returns proof of new state for a given ledger info with signatures relative to version known
     /// to client
     fn get_state_proof_with_ledger_info(
@@ -219,6 +248,19 @@ pub trait DbReader: Send + Sync {
         AccumulatorConsistencyProof,
     )>;
 
+    /// Returns a proof that the transaction accumulator at `target_version` extends the one at
+    /// `client_known_version`, so a client can move its trusted accumulator forward without
+    /// fetching the transactions in between.
+    /// See [`LibraDB::get_accumulator_consistency_proof`].
+    ///
+    /// [`LibraDB::get_accumulator_consistency_proof`]:
+    /// ../libradb/struct.LibraDB.html#method.get_accumulator_consistency_proof
+    fn get_accumulator_consistency_proof(
+        &self,
+        client_known_version: Version,
+        target_version: Version,
+    ) -> Result<AccumulatorConsistencyProof>;
+
     /// Returns the account state corresponding to the given version and account address with proof
     /// based on `ledger_version`
     fn get_account_state_with_proof(
@@ -11,8 +11,9 @@ use libra_types::{
     account_config::AccountResource,
     account_state::AccountState,
     account_state_blob::{AccountStateBlob, AccountStateWithProof},
-    contract_event::ContractEvent,
+    contract_event::{ContractEvent, EventWithProof},
     epoch_change::EpochChangeProof,
+    epoch_participation::EpochParticipation,
     event::{EventHandle, EventKey},
     ledger_info::LedgerInfoWithSignatures,
     proof::{AccumulatorConsistencyProof, SparseMerkleProof},
@@ -54,10 +55,25 @@ impl DbReader for MockDbReader {
         unimplemented!()
     }
 
+    fn get_events_with_proofs(
+        &self,
+        _event_key: &EventKey,
+        _start: u64,
+        _ascending: bool,
+        _limit: u64,
+        _ledger_version: Version,
+    ) -> Result<Vec<EventWithProof>> {
+        unimplemented!()
+    }
+
     fn get_block_timestamp(&self, _version: u64) -> Result<u64> {
         unimplemented!()
     }
 
+    fn get_epoch_participation(&self, _epoch: u64) -> Result<Option<EpochParticipation>> {
+        unimplemented!()
+    }
+
     fn get_latest_account_state(
         &self,
         _address: AccountAddress,
@@ -84,6 +100,15 @@ impl DbReader for MockDbReader {
         unimplemented!()
     }
 
+    fn get_transaction_by_hash(
+        &self,
+        _hash: HashValue,
+        _ledger_version: Version,
+        _fetch_events: bool,
+    ) -> Result<Option<TransactionWithProof>> {
+        unimplemented!()
+    }
+
     fn get_state_proof_with_ledger_info(
         &self,
         _known_version: u64,
@@ -103,6 +128,14 @@ impl DbReader for MockDbReader {
         unimplemented!()
     }
 
+    fn get_accumulator_consistency_proof(
+        &self,
+        _client_known_version: Version,
+        _target_version: Version,
+    ) -> Result<AccumulatorConsistencyProof> {
+        unimplemented!()
+    }
+
     fn get_account_state_with_proof(
         &self,
         _address: AccountAddress,
@@ -117,11 +150,14 @@ impl DbReader for MockDbReader {
         _address: AccountAddress,
         _version: Version,
     ) -> Result<(Option<AccountStateBlob>, SparseMerkleProof)> {
-        unimplemented!()
+        Ok((
+            Some(get_mock_account_state_blob()),
+            SparseMerkleProof::new(None, vec![]),
+        ))
     }
 
     fn get_latest_state_root(&self) -> Result<(Version, HashValue)> {
-        unimplemented!()
+        Ok((0, HashValue::zero()))
     }
 
     fn get_latest_tree_state(&self) -> Result<TreeState> {
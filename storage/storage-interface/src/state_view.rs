@@ -201,3 +201,54 @@ impl<'a> StateView for VerifiedStateView<'a> {
         self.latest_persistent_version.is_none()
     }
 }
+
+/// `DbStateView` is a snapshot of the global state committed to persistent storage at a given
+/// version. Unlike [`VerifiedStateView`], it does not verify what it reads, so it is meant for
+/// serving reads of local storage, not for execution.
+pub struct DbStateView {
+    reader: Arc<dyn DbReader>,
+    version: Version,
+    account_to_state_cache: RefCell<HashMap<AccountAddress, AccountState>>,
+}
+
+impl DbStateView {
+    pub fn new(reader: Arc<dyn DbReader>, version: Version) -> Self {
+        Self {
+            reader,
+            version,
+            account_to_state_cache: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+impl StateView for DbStateView {
+    fn get(&self, access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
+        let address = access_path.address;
+        let path = &access_path.path;
+        match self.account_to_state_cache.borrow_mut().entry(address) {
+            Entry::Occupied(occupied) => Ok(occupied.get().get(path).cloned()),
+            Entry::Vacant(vacant) => {
+                let (blob, _proof) = self
+                    .reader
+                    .get_account_state_with_proof_by_version(address, self.version)?;
+                Ok(vacant
+                    .insert(
+                        blob.as_ref()
+                            .map(TryInto::try_into)
+                            .transpose()?
+                            .unwrap_or_default(),
+                    )
+                    .get(path)
+                    .cloned())
+            }
+        }
+    }
+
+    fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
+        access_paths.iter().map(|path| self.get(path)).collect()
+    }
+
+    fn is_genesis(&self) -> bool {
+        false
+    }
+}
@@ -13,24 +13,24 @@
 //! derivation.
 //!
 //! Note further that the Key Derivation Function (KDF) chosen in the derivation of Child
-//! Private Keys adheres to [HKDF RFC 5869](https://tools.ietf.org/html/rfc5869).
+//! Private Keys adheres to [HKDF RFC 5869](https://tools.ietf.org/html/rfc5869). Child keys are
+//! derived by `libra_crypto::hd`, where child number `n` is the derivation path `m/<n>`.
 
 use crate::mnemonic::Mnemonic;
 use anyhow::Result;
-use byteorder::{ByteOrder, LittleEndian};
 use hmac::Hmac;
 use libra_crypto::{
     compat::Sha3_256,
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
     hash::CryptoHash,
-    hkdf::Hkdf,
+    hd::HDKeyFactory,
     traits::SigningKey,
 };
 use libra_types::{account_address::AccountAddress, transaction::authenticator::AuthenticationKey};
 use mirai_annotations::*;
 use pbkdf2::pbkdf2;
 use serde::{Deserialize, Serialize};
-use std::{convert::TryFrom, ops::AddAssign};
+use std::ops::AddAssign;
 
 /// Main is a set of raw bytes that are used for child key derivation
 pub struct Main([u8; 32]);
@@ -132,15 +132,13 @@ pub struct KeyFactory {
 
 impl KeyFactory {
     const MNEMONIC_SALT_PREFIX: &'static [u8] = b"LIBRA WALLET: mnemonic salt prefix$";
-    const MAIN_KEY_SALT: &'static [u8] = b"LIBRA WALLET: main key salt$";
-    const INFO_PREFIX: &'static [u8] = b"LIBRA WALLET: derived key$";
     /// Instantiate a new KeyFactor from a Seed, where the [u8; 64] raw bytes of the Seed are used
     /// to derive both the Main and its child keys
     pub fn new(seed: &Seed) -> Result<Self> {
-        let hkdf_extract = Hkdf::<Sha3_256>::extract(Some(KeyFactory::MAIN_KEY_SALT), &seed.0)?;
+        let factory = HDKeyFactory::from_seed(&seed.0)?;
 
         Ok(Self {
-            main: Main::from(&hkdf_extract[..32]),
+            main: Main::from(factory.main()),
         })
     }
 
@@ -154,14 +152,8 @@ impl KeyFactory {
     /// Note that the function below  adheres to [HKDF RFC 5869](https://tools.ietf.org/html/rfc5869).
     pub fn private_child(&self, child: ChildNumber) -> Result<ExtendedPrivKey> {
         // application info in the HKDF context is defined as Libra derived key$child_number.
-        let mut le_n = [0u8; 8];
-        LittleEndian::write_u64(&mut le_n, child.0);
-        let mut info = KeyFactory::INFO_PREFIX.to_vec();
-        info.extend_from_slice(&le_n);
-
-        let hkdf_expand = Hkdf::<Sha3_256>::expand(&self.main(), Some(&info), 32)?;
-        let sk = Ed25519PrivateKey::try_from(hkdf_expand.as_slice())
-            .expect("Unable to convert into private key");
+        let factory = HDKeyFactory::from_main(self.main.0);
+        let sk = factory.private_child(child.0);
 
         Ok(ExtendedPrivKey::new(child, sk))
     }
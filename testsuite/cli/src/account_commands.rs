@@ -3,8 +3,11 @@
 
 use crate::{
     client_proxy::ClientProxy,
-    commands::{blocking_cmd, report_error, subcommand_execute, Command},
+    commands::{
+        blocking_cmd, print_progress, print_result, report_error, subcommand_execute, Command,
+    },
 };
+use serde_json::json;
 
 /// Major command for account related operations.
 pub struct AccountCommand {}
@@ -138,10 +141,10 @@ impl Command for AccountCommandMint {
             println!("Invalid number of arguments for mint");
             return;
         }
-        println!(">> Minting coins");
+        print_progress(">> Minting coins");
         let is_blocking = blocking_cmd(params[0]);
         match client.mint_coins(&params, is_blocking) {
-            Ok(_) => {
+            Ok(_) => print_result(json!({ "executed": is_blocking }), |_| {
                 if is_blocking {
                     println!("Finished minting!");
                 } else {
@@ -149,7 +152,7 @@ impl Command for AccountCommandMint {
                     // setup_scripts/docker/mint/server.py
                     println!("Mint request submitted");
                 }
-            }
+            }),
             Err(e) => report_error("Error minting coins", e),
         }
     }
@@ -173,10 +176,10 @@ impl Command for AccountCommandAddCurrency {
             println!("Invalid number of arguments for adding currency");
             return;
         }
-        println!(">> Adding currency");
+        print_progress(">> Adding currency");
         let is_blocking = blocking_cmd(params[0]);
         match client.add_currency(&params, is_blocking) {
-            Ok(_) => {
+            Ok(_) => print_result(json!({ "executed": is_blocking }), |_| {
                 if is_blocking {
                     println!("Finished adding currency!");
                 } else {
@@ -184,7 +187,7 @@ impl Command for AccountCommandAddCurrency {
                     // setup_scripts/docker/mint/server.py
                     println!("Currency addition request submitted");
                 }
-            }
+            }),
             Err(e) => report_error("Error adding currency", e),
         }
     }
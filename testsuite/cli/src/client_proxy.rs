@@ -2,11 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    commands::{is_address, is_authentication_key},
+    commands::{is_address, is_authentication_key, json_output, print_progress},
     libra_client::LibraClient,
+    profile::{Profile, Profiles},
     AccountData, AccountStatus,
 };
 use anyhow::{bail, ensure, format_err, Error, Result};
+use bytecode_source_map::source_map::SourceMap;
+use bytecode_verifier::{verify_module, DependencyChecker};
 use compiled_stdlib::{transaction_scripts::StdlibScript, StdLibOptions};
 use libra_crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
@@ -14,7 +17,10 @@ use libra_crypto::{
     traits::ValidCryptoMaterial,
     x25519, ValidCryptoMaterialStringExt,
 };
-use libra_json_rpc_client::views::{AccountView, BlockMetadata, EventView, TransactionView};
+use libra_json_rpc_client::views::{
+    AccountView, AmountView, BlockMetadata, BytesView, CurrencyInfoView, EventDataView, EventView,
+    TransactionView,
+};
 use libra_logger::prelude::*;
 use libra_network_address::{
     encrypted::{
@@ -22,6 +28,7 @@ use libra_network_address::{
     },
     NetworkAddress, RawNetworkAddress,
 };
+use libra_secure_storage::OnDiskStorage;
 use libra_temppath::TempPath;
 use libra_types::{
     access_path::AccessPath,
@@ -30,6 +37,7 @@ use libra_types::{
         from_currency_code_string, libra_root_address, testnet_dd_account_address,
         type_tag_for_currency_code, ACCOUNT_RECEIVED_EVENT_PATH, ACCOUNT_SENT_EVENT_PATH, LBR_NAME,
     },
+    account_identifier::{is_account_identifier, AccountIdentifier, AddressNetwork},
     account_state::AccountState,
     chain_id::ChainId,
     ledger_info::LedgerInfoWithSignatures,
@@ -44,6 +52,12 @@ use libra_types::{
     waypoint::Waypoint,
 };
 use libra_wallet::{io_utils, WalletLibrary};
+use move_core_types::{
+    gas_schedule::{AbstractMemorySize, GasAlgebra, GasConstants},
+    language_storage::CORE_CODE_ADDRESS,
+};
+use move_ir_types::location::Loc;
+use move_vm_types::gas_schedule::calculate_intrinsic_gas;
 use num_traits::{
     cast::{FromPrimitive, ToPrimitive},
     identities::Zero,
@@ -62,11 +76,22 @@ use std::{
     thread, time,
 };
 use transaction_builder::encode_set_validator_config_script;
+use vm::{errors::VMError, CompiledModule};
 
 const CLIENT_WALLET_MNEMONIC_FILE: &str = "client.mnemonic";
+/// The stdlib sources, relative to the repository root in which Move programs are compiled.
+const STDLIB_SOURCE_DIR: &str = "language/stdlib/modules";
 const GAS_UNIT_PRICE: u64 = 0;
 const MAX_GAS_AMOUNT: u64 = 1_000_000;
 const TX_EXPIRATION: i64 = 100;
+/// Transactions signed offline need to travel between hosts before being submitted.
+const OFFLINE_TX_EXPIRATION: i64 = 3600;
+/// The number of wallet accounts searched for the sender of a transaction signed offline.
+const MAX_OFFLINE_WALLET_DEPTH: u64 = 1000;
+/// How often new events are polled for when tailing events.
+const EVENT_POLL_INTERVAL_MS: u64 = 1000;
+/// The maximum number of events fetched per poll when tailing events.
+const EVENT_TAIL_BATCH_SIZE: u64 = 100;
 
 /// Enum used for error formatting.
 #[derive(Debug)]
@@ -76,6 +101,22 @@ enum InputType {
     Usize,
 }
 
+/// Error returned when a transaction was committed but failed to execute.
+#[derive(Debug)]
+pub struct ExecutionFailure(pub TransactionView);
+
+impl fmt::Display for ExecutionFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "transaction failed to execute; status: {:?}, reason: {:?}!",
+            self.0.vm_status, self.0.error_reason
+        )
+    }
+}
+
+impl std::error::Error for ExecutionFailure {}
+
 /// Account data is stored in a map and referenced by an index.
 #[derive(Debug)]
 pub struct AddressAndIndex {
@@ -123,6 +164,8 @@ pub struct ClientProxy {
     sync_on_wallet_recovery: bool,
     /// temp files (alive for duration of program)
     temp_files: Vec<PathBuf>,
+    /// Named connection profiles this client can switch between.
+    profiles: Option<Profiles>,
     // invariant self.address_to_ref_id.values().iter().all(|i| i < self.accounts.len())
 }
 
@@ -173,10 +216,7 @@ impl ClientProxy {
 
         let faucet_server = match faucet_server {
             Some(server) => server,
-            None => url
-                .host_str()
-                .ok_or_else(|| format_err!("Missing host in URL"))?
-                .replace("client", "faucet"),
+            None => Self::faucet_server_from_url(&url)?,
         };
 
         let address_to_ref_id = accounts
@@ -196,6 +236,7 @@ impl ClientProxy {
             wallet: Self::get_libra_wallet(mnemonic_file)?,
             sync_on_wallet_recovery,
             temp_files: vec![],
+            profiles: None,
         })
     }
 
@@ -237,11 +278,13 @@ impl ClientProxy {
         if self.accounts.is_empty() {
             println!("No user accounts");
         } else {
+            let network = AddressNetwork::from_chain_id(self.chain_id);
             for (ref index, ref account) in self.accounts.iter().enumerate() {
                 println!(
-                    "User account index: {}, address: {}, sequence number: {}, status: {:?}",
+                    "User account index: {}, address: {} ({}), sequence number: {}, status: {:?}",
                     index,
                     hex::encode(&account.address),
+                    AccountIdentifier::new(network, account.address),
                     account.sequence_number,
                     account.status,
                 );
@@ -726,10 +769,10 @@ impl ClientProxy {
         sequence_number: u64,
     ) -> Result<()> {
         let mut max_iterations = 5000;
-        println!(
+        print_progress(&format!(
             "waiting for {} with sequence number {}",
             account, sequence_number
-        );
+        ));
         loop {
             stdout().flush().unwrap();
 
@@ -739,23 +782,22 @@ impl ClientProxy {
             {
                 Ok(Some(txn_view)) => {
                     if txn_view.vm_status == StatusCode::EXECUTED {
-                        println!("transaction executed!");
+                        print_progress("transaction executed!");
                         if txn_view.events.is_empty() {
-                            println!("no events emitted");
+                            print_progress("no events emitted");
                         }
                         break Ok(());
                     } else {
-                        break Err(format_err!(
-                            "transaction failed to execute; status: {:?}!",
-                            txn_view.vm_status
-                        ));
+                        break Err(ExecutionFailure(txn_view).into());
                     }
                 }
                 Err(e) => {
-                    println!("Response with error: {:?}", e);
+                    print_progress(&format!("Response with error: {:?}", e));
                 }
                 _ => {
-                    print!(".");
+                    if !json_output() {
+                        print!(".");
+                    }
                 }
             }
             max_iterations -= 1;
@@ -931,7 +973,22 @@ impl ClientProxy {
             space_delim_strings[0]
         );
         let (address, _) = self.get_account_address_from_parameter(space_delim_strings[1])?;
-        let file_path = space_delim_strings[2];
+        let dependencies: Vec<_> = space_delim_strings[3..]
+            .iter()
+            .map(|dep| dep.to_string())
+            .collect();
+        self.compile_move(&address, space_delim_strings[2], &dependencies, false)
+    }
+
+    /// Compile the Move source file with the move-build compiler, returning the paths of the
+    /// compiled units.
+    fn compile_move(
+        &mut self,
+        address: &AccountAddress,
+        file_path: &str,
+        dependencies: &[String],
+        emit_source_map: bool,
+    ) -> Result<Vec<String>> {
         let mut tmp_output_dir = TempPath::new();
         tmp_output_dir.persist();
         tmp_output_dir
@@ -946,9 +1003,12 @@ impl ClientProxy {
             address,
             tmp_output_path.display(),
         );
-        for dep in &space_delim_strings[3..] {
+        for dep in dependencies {
             args.push_str(&format!(" -d {}", dep));
         }
+        if emit_source_map {
+            args.push_str(" -m");
+        }
 
         let status = Command::new("cargo")
             .args(args.split(' '))
@@ -996,6 +1056,178 @@ impl ClientProxy {
         self.wait_for_transaction(sender_address, sender_sequence + 1)
     }
 
+    /// Prepare a transfer transaction for offline signing and write the unsigned raw transaction
+    /// to the file specified. The sender does not need to be managed by this client, so its
+    /// sequence number has to be provided.
+    pub fn prepare_transfer_coins_to_file(
+        &mut self,
+        space_delim_strings: &[&str],
+    ) -> Result<RawTransaction> {
+        ensure!(
+            space_delim_strings.len() >= 7 && space_delim_strings.len() <= 10,
+            "Invalid number of arguments for preparing transaction"
+        );
+
+        let (sender_address, _) =
+            self.get_account_address_from_parameter(space_delim_strings[1])?;
+        let (receiver_address, _) =
+            self.get_account_address_from_parameter(space_delim_strings[2])?;
+
+        let transfer_currency = space_delim_strings[4];
+        let num_coins =
+            self.convert_to_on_chain_represenation(space_delim_strings[3], transfer_currency)?;
+        let output_file = space_delim_strings[5];
+
+        let sequence_number = space_delim_strings[6].parse::<u64>().map_err(|error| {
+            format_parse_data_error(
+                "sequence_number",
+                InputType::UnsignedInt,
+                space_delim_strings[6],
+                error,
+            )
+        })?;
+
+        let gas_unit_price = if space_delim_strings.len() > 7 {
+            Some(space_delim_strings[7].parse::<u64>().map_err(|error| {
+                format_parse_data_error(
+                    "gas_unit_price",
+                    InputType::UnsignedInt,
+                    space_delim_strings[7],
+                    error,
+                )
+            })?)
+        } else {
+            None
+        };
+
+        let max_gas_amount = if space_delim_strings.len() > 8 {
+            Some(space_delim_strings[8].parse::<u64>().map_err(|error| {
+                format_parse_data_error(
+                    "max_gas_amount",
+                    InputType::UnsignedInt,
+                    space_delim_strings[8],
+                    error,
+                )
+            })?)
+        } else {
+            None
+        };
+
+        let expiration_secs = if space_delim_strings.len() > 9 {
+            space_delim_strings[9].parse::<i64>().map_err(|error| {
+                format_parse_data_error(
+                    "expiration_secs",
+                    InputType::UnsignedInt,
+                    space_delim_strings[9],
+                    error,
+                )
+            })?
+        } else {
+            OFFLINE_TX_EXPIRATION
+        };
+
+        let currency_code = from_currency_code_string(transfer_currency)
+            .map_err(|_| format_err!("Invalid currency code {} specified", transfer_currency))?;
+        let program = transaction_builder::encode_peer_to_peer_with_metadata_script(
+            type_tag_for_currency_code(currency_code),
+            receiver_address,
+            num_coins,
+            vec![],
+            vec![],
+        );
+        let raw_txn = create_unsigned_txn(
+            TransactionPayload::Script(program),
+            sender_address,
+            sequence_number,
+            max_gas_amount.unwrap_or(MAX_GAS_AMOUNT),
+            gas_unit_price.unwrap_or(GAS_UNIT_PRICE),
+            transfer_currency.to_owned(),
+            expiration_secs,
+            self.chain_id,
+        );
+
+        fs::write(output_file, lcs::to_bytes(&raw_txn)?)?;
+        Ok(raw_txn)
+    }
+
+    /// Sign the unsigned raw transaction read from a file with either a key file or a mnemonic
+    /// file and write the signed transaction to the output file. This does not require any
+    /// connection to a validator, so it can be performed on an offline host.
+    pub fn sign_transaction_from_file(
+        &self,
+        space_delim_strings: &[&str],
+    ) -> Result<SignedTransaction> {
+        ensure!(
+            space_delim_strings.len() == 5,
+            "Invalid number of arguments for signing transaction"
+        );
+        let raw_txn: RawTransaction = lcs::from_bytes(&fs::read(space_delim_strings[1])?)
+            .map_err(|e| format_err!("Unable to parse unsigned transaction: {}", e))?;
+        ensure!(
+            raw_txn.chain_id() == self.chain_id,
+            "Transaction is for chain {}, expected {}",
+            raw_txn.chain_id(),
+            self.chain_id
+        );
+
+        let signer_file = Path::new(space_delim_strings[3]);
+        let signed_txn = match space_delim_strings[2] {
+            "key" => {
+                let private_key: Ed25519PrivateKey = lcs::from_bytes(&fs::read(signer_file)?)
+                    .map_err(|e| format_err!("Unable to parse key file: {}", e))?;
+                KeyPair::from(private_key).sign_txn(raw_txn)?
+            }
+            "mnemonic" => {
+                let wallet = Self::wallet_for_sender(signer_file, raw_txn.sender())?;
+                wallet.sign_txn(raw_txn)?
+            }
+            signer_type => bail!(
+                "Unknown signer type {}, expected 'key' or 'mnemonic'",
+                signer_type
+            ),
+        };
+
+        fs::write(space_delim_strings[4], lcs::to_bytes(&signed_txn)?)?;
+        Ok(signed_txn)
+    }
+
+    /// Submit a signed transaction read from a file. If is_blocking = true, it will keep
+    /// querying validator till the transaction is executed.
+    pub fn submit_signed_transaction_from_file(
+        &mut self,
+        space_delim_strings: &[&str],
+        is_blocking: bool,
+    ) -> Result<IndexAndSequence> {
+        ensure!(
+            space_delim_strings.len() == 2,
+            "Invalid number of arguments for submitting transaction"
+        );
+        let signed_txn: SignedTransaction = lcs::from_bytes(&fs::read(space_delim_strings[1])?)
+            .map_err(|e| format_err!("Unable to parse signed transaction: {}", e))?;
+        let signed_txn = signed_txn
+            .check_signature()
+            .map_err(|e| format_err!("Invalid transaction signature: {}", e))?
+            .into_inner();
+
+        let sender_address = signed_txn.sender();
+        let sender_sequence = signed_txn.sequence_number();
+        let accounts = &mut self.accounts;
+        let sender_account = self
+            .address_to_ref_id
+            .get(&sender_address)
+            .and_then(|ref_id| accounts.get_mut(*ref_id));
+        self.client.submit_transaction(sender_account, signed_txn)?;
+
+        if is_blocking {
+            self.wait_for_transaction(sender_address, sender_sequence + 1)?;
+        }
+
+        Ok(IndexAndSequence {
+            account_index: AccountEntry::Address(sender_address),
+            sequence_number: sender_sequence,
+        })
+    }
+
     fn submit_program(
         &mut self,
         space_delim_strings: &[&str],
@@ -1028,6 +1260,93 @@ impl ClientProxy {
         )
     }
 
+    /// Compile a Move module against the stdlib, verify it locally against the modules published
+    /// on-chain, estimate the gas needed to publish it and publish it, from command
+    /// 'compile_publish <sender> <file_path> [dependency_source_files...]'. Returns the estimated
+    /// gas.
+    pub fn compile_and_publish_module(&mut self, space_delim_strings: &[&str]) -> Result<u64> {
+        ensure!(
+            space_delim_strings.len() >= 3,
+            "Invalid number of arguments to compile and publish module"
+        );
+        let (sender_address, _) =
+            self.get_account_address_from_parameter(space_delim_strings[1])?;
+        let sender_ref_id = self.get_account_ref_id(&sender_address)?;
+        let file_path = space_delim_strings[2];
+
+        print_progress(">> Compiling module");
+        let mut dependencies = vec![STDLIB_SOURCE_DIR.to_string()];
+        dependencies.extend(space_delim_strings[3..].iter().map(|dep| dep.to_string()));
+        let output_files = self.compile_move(&sender_address, file_path, &dependencies, true)?;
+        ensure!(
+            output_files.len() == 1,
+            "Expected a single module in {}, found {} compiled units",
+            file_path,
+            output_files.len()
+        );
+        let module_path = Path::new(&output_files[0]);
+        let module_bytes = fs::read(module_path)?;
+        let module = CompiledModule::deserialize(&module_bytes)
+            .map_err(|e| format_err!("{} does not define a module: {:?}", file_path, e))?;
+
+        print_progress(">> Verifying module against the on-chain modules");
+        let mut on_chain_modules = self.get_on_chain_modules(CORE_CODE_ADDRESS)?;
+        if sender_address != CORE_CODE_ADDRESS {
+            on_chain_modules.extend(self.get_on_chain_modules(sender_address)?);
+        }
+        if let Err(error) = verify_module(&module)
+            .and_then(|_| DependencyChecker::verify_module(&module, &on_chain_modules))
+        {
+            let source_map = read_source_map(&module_path.with_extension("mvsm"));
+            bail!(
+                "Module failed to verify: {}",
+                describe_verification_error(&error, source_map.as_ref())
+            );
+        }
+
+        let sender = self.accounts.get(sender_ref_id).unwrap();
+        let sequence_number = sender.sequence_number;
+        let txn = self.create_txn_to_submit(
+            TransactionPayload::Module(Module::new(module_bytes)),
+            &sender,
+            None,
+            None,
+            None,
+        )?;
+        let estimated_gas = estimate_publishing_gas(txn.raw_txn_bytes_len() as u64);
+        ensure!(
+            estimated_gas <= MAX_GAS_AMOUNT,
+            "Publishing is estimated to use {} gas units, more than the maximum of {}",
+            estimated_gas,
+            MAX_GAS_AMOUNT
+        );
+
+        print_progress(&format!(
+            ">> Publishing module, estimated gas: {}",
+            estimated_gas
+        ));
+        self.client
+            .submit_transaction(self.accounts.get_mut(sender_ref_id), txn)?;
+        self.wait_for_transaction(sender_address, sequence_number + 1)?;
+        Ok(estimated_gas)
+    }
+
+    /// Get all the modules published under an account.
+    fn get_on_chain_modules(&mut self, address: AccountAddress) -> Result<Vec<CompiledModule>> {
+        let account_blob = match self.client.get_account_state_blob(address)?.0 {
+            Some(account_blob) => account_blob,
+            None => return Ok(vec![]),
+        };
+        AccountState::try_from(&account_blob)?
+            .iter()
+            .filter(|(path, _)| path.first() == Some(&AccessPath::CODE_TAG))
+            .map(|(_, code)| {
+                CompiledModule::deserialize(code)
+                    .map_err(|e| format_err!("Unable to deserialize on-chain module: {:?}", e))
+            })
+            .collect()
+    }
+
     /// Execute custom script
     pub fn execute_script(&mut self, space_delim_strings: &[&str]) -> Result<()> {
         ensure!(
@@ -1105,6 +1424,26 @@ impl ClientProxy {
             .get_txn_by_acc_seq(account, sequence_number, fetch_events)
     }
 
+    /// Get the committed transaction, along with its events, sent by a local account or address
+    /// with the given sequence number.
+    pub fn get_committed_txn(
+        &mut self,
+        account: &AccountEntry,
+        sequence_number: u64,
+    ) -> Result<Option<TransactionView>> {
+        let address = match account {
+            AccountEntry::Index(ref_id) => {
+                self.accounts
+                    .get(*ref_id)
+                    .ok_or_else(|| format_err!("Unable to find account by ref id: {}", ref_id))?
+                    .address
+            }
+            AccountEntry::Address(address) => *address,
+        };
+        self.client
+            .get_txn_by_acc_seq(address, sequence_number, true)
+    }
+
     /// Get committed txn by account and sequence number
     pub fn get_committed_txn_by_range(
         &mut self,
@@ -1155,6 +1494,8 @@ impl ClientProxy {
             Ok((auth_key.derived_address(), Some(auth_key)))
         } else if is_address(para) {
             Ok((ClientProxy::address_from_strings(para)?, None))
+        } else if is_account_identifier(para) {
+            Ok((self.address_from_account_identifier(para)?, None))
         } else {
             let account_ref_id = para.parse::<usize>().map_err(|error| {
                 format_parse_data_error(
@@ -1220,6 +1561,91 @@ impl ClientProxy {
             .get_events_by_access_path(access_path, start_seq_number, limit)
     }
 
+    /// Follow the events of an account from command
+    /// 'tail <account> <sent|received> [start_sequence_number] [max_events]' and print each new
+    /// event as it is emitted. Unless a start sequence number is given, only events emitted from
+    /// now on are printed. Keeps polling until `max_events` events were printed, if given.
+    pub fn tail_events(&mut self, space_delim_strings: &[&str]) -> Result<()> {
+        ensure!(
+            space_delim_strings.len() >= 3 && space_delim_strings.len() <= 5,
+            "Invalid number of arguments to tail events"
+        );
+        let (account, _) = self.get_account_address_from_parameter(space_delim_strings[1])?;
+        let account_view = self.get_account_resource_and_update(account)?;
+        let account_resource = match self.client.get_account_state_blob(account)?.0 {
+            Some(blob) => AccountState::try_from(&blob)?.get_account_resource()?,
+            None => None,
+        }
+        .ok_or_else(|| format_err!("No account exists at {:?}", account))?;
+        let (event_key, event_count) = match space_delim_strings[2] {
+            "sent" => (
+                account_view.sent_events_key,
+                account_resource.sent_events().count(),
+            ),
+            "received" => (
+                account_view.received_events_key,
+                account_resource.received_events().count(),
+            ),
+            _ => bail!(
+                "Unknown event type: {:?}, only sent and received are supported",
+                space_delim_strings[2]
+            ),
+        };
+
+        let mut next_seq_number = if space_delim_strings.len() > 3 {
+            space_delim_strings[3].parse::<u64>().map_err(|error| {
+                format_parse_data_error(
+                    "start_seq_number",
+                    InputType::UnsignedInt,
+                    space_delim_strings[3],
+                    error,
+                )
+            })?
+        } else {
+            event_count
+        };
+        let max_events = if space_delim_strings.len() > 4 {
+            Some(space_delim_strings[4].parse::<u64>().map_err(|error| {
+                format_parse_data_error(
+                    "max_events",
+                    InputType::UnsignedInt,
+                    space_delim_strings[4],
+                    error,
+                )
+            })?)
+        } else {
+            None
+        };
+
+        let currency_info: HashMap<_, _> = self
+            .client
+            .get_currency_info()?
+            .into_iter()
+            .map(|view| (view.code.clone(), view))
+            .collect();
+        let mut num_events = 0;
+        println!(
+            "Following {} events of {} from sequence number {}",
+            space_delim_strings[2], account, next_seq_number
+        );
+        loop {
+            let events = self.client.get_events(
+                event_key.0.clone(),
+                next_seq_number,
+                EVENT_TAIL_BATCH_SIZE,
+            )?;
+            for event in events {
+                println!("{}", format_event(&event, &currency_info));
+                next_seq_number = event.sequence_number + 1;
+                num_events += 1;
+                if max_events == Some(num_events) {
+                    return Ok(());
+                }
+            }
+            thread::sleep(time::Duration::from_millis(EVENT_POLL_INTERVAL_MS));
+        }
+    }
+
     /// Write mnemonic recover to the file specified.
     pub fn write_recovery(&self, space_delim_strings: &[&str]) -> Result<()> {
         ensure!(
@@ -1277,6 +1703,132 @@ impl ClientProxy {
         }
     }
 
+    /// Persists the latest verified chain state to the file at `path`, resuming from the state
+    /// stored there if it is ahead of ours.
+    pub fn persist_trusted_state(&mut self, path: &Path) -> Result<()> {
+        self.client
+            .persist_trusted_state(OnDiskStorage::new(path.to_path_buf()))
+    }
+
+    /// Sets the connection profiles this client can switch between.
+    pub fn set_profiles(&mut self, profiles: Profiles) {
+        self.profiles = Some(profiles);
+    }
+
+    /// Returns the connection profiles along with the name of the active one.
+    pub fn list_profiles(&self) -> Result<(Option<String>, Vec<(String, Profile)>)> {
+        let profiles = self.profiles()?;
+        Ok((
+            profiles.active.clone(),
+            profiles
+                .profiles
+                .iter()
+                .map(|(name, profile)| (name.clone(), profile.clone()))
+                .collect(),
+        ))
+    }
+
+    /// Adds a connection profile from command 'add <name> <chain_id> <url> <waypoint>'.
+    pub fn add_profile(&mut self, space_delim_strings: &[&str]) -> Result<()> {
+        ensure!(
+            space_delim_strings.len() == 5,
+            "Invalid number of arguments for adding profile"
+        );
+        let chain_id = ChainId::from_str(space_delim_strings[2])
+            .map_err(|e| format_err!("Invalid chain id {}: {}", space_delim_strings[2], e))?;
+        let url = Url::parse(space_delim_strings[3])?;
+        let waypoint = Waypoint::from_str(space_delim_strings[4])?;
+
+        let profiles = self.profiles_mut()?;
+        profiles.add(
+            space_delim_strings[1],
+            Profile {
+                chain_id,
+                url: url.into_string(),
+                waypoint,
+            },
+        )?;
+        profiles.save()
+    }
+
+    /// Removes a connection profile, along with its trusted state, from command 'remove <name>'.
+    /// The active profile cannot be removed.
+    pub fn remove_profile(&mut self, space_delim_strings: &[&str]) -> Result<()> {
+        ensure!(
+            space_delim_strings.len() == 2,
+            "Invalid number of arguments for removing profile"
+        );
+        let name = space_delim_strings[1];
+        let profiles = self.profiles_mut()?;
+        ensure!(
+            profiles.active.as_deref() != Some(name),
+            "Unable to remove the active profile {}",
+            name
+        );
+        profiles.remove(name)?;
+        profiles.save()
+    }
+
+    /// Connects to the network of the profile from command 'switch <name>'. The trusted state of
+    /// the profile is restored and verified against the network before anything is switched, and
+    /// the cached accounts are then synced with the new network.
+    pub fn switch_profile(&mut self, space_delim_strings: &[&str]) -> Result<BlockMetadata> {
+        ensure!(
+            space_delim_strings.len() == 2,
+            "Invalid number of arguments for switching profile"
+        );
+        let name = space_delim_strings[1];
+        let profiles = self.profiles()?;
+        let profile = profiles.get(name)?.clone();
+        let trusted_state_file = profiles.trusted_state_file(name);
+
+        let url = Url::parse(&profile.url)?;
+        let mut client = LibraClient::new(url.clone(), profile.waypoint)?;
+        client.persist_trusted_state(OnDiskStorage::new(trusted_state_file))?;
+        client.get_state_proof()?;
+        let block_metadata = client.get_metadata()?;
+
+        let profiles = self.profiles_mut()?;
+        profiles.set_active(name)?;
+        profiles.save()?;
+
+        self.client = client;
+        self.chain_id = profile.chain_id;
+        self.faucet_server = Self::faucet_server_from_url(&url)?;
+
+        // Sequence numbers and status are specific to the network they were retrieved from
+        for account in self
+            .accounts
+            .iter_mut()
+            .chain(self.libra_root_account.iter_mut())
+            .chain(self.testnet_designated_dealer_account.iter_mut())
+        {
+            match self.client.get_account_state(account.address, true)?.0 {
+                Some(account_view) => {
+                    account.sequence_number = account_view.sequence_number;
+                    account.status = AccountStatus::Persisted;
+                }
+                None => {
+                    account.sequence_number = 0;
+                    account.status = AccountStatus::Local;
+                }
+            }
+        }
+        Ok(block_metadata)
+    }
+
+    fn profiles(&self) -> Result<&Profiles> {
+        self.profiles
+            .as_ref()
+            .ok_or_else(|| format_err!("No connection profiles configured"))
+    }
+
+    fn profiles_mut(&mut self) -> Result<&mut Profiles> {
+        self.profiles
+            .as_mut()
+            .ok_or_else(|| format_err!("No connection profiles configured"))
+    }
+
     /// Test JSON RPC client connection with validator.
     pub fn test_validator_connection(&mut self) -> Result<BlockMetadata> {
         self.client.get_metadata()
@@ -1390,11 +1942,51 @@ impl ClientProxy {
         Ok(wallet)
     }
 
+    /// Recover the wallet from the mnemonic file and derive its accounts until the one matching
+    /// the sender is found.
+    fn wallet_for_sender(mnemonic_file: &Path, sender: AccountAddress) -> Result<WalletLibrary> {
+        let mut wallet = WalletLibrary::recover(mnemonic_file)?;
+        for _ in 0..MAX_OFFLINE_WALLET_DEPTH {
+            let (authentication_key, _) = wallet.new_address()?;
+            if authentication_key.derived_address() == sender {
+                return Ok(wallet);
+            }
+        }
+        bail!(
+            "Account {} not found in the first {} accounts of the wallet",
+            sender,
+            MAX_OFFLINE_WALLET_DEPTH
+        )
+    }
+
+    fn faucet_server_from_url(url: &Url) -> Result<String> {
+        Ok(url
+            .host_str()
+            .ok_or_else(|| format_err!("Missing host in URL"))?
+            .replace("client", "faucet"))
+    }
+
     /// Set wallet instance used by this client.
     fn set_wallet(&mut self, wallet: WalletLibrary) {
         self.wallet = wallet;
     }
 
+    /// Decodes the address of an account identifier, which must be meant for the network of the
+    /// chain the client is connected to.
+    fn address_from_account_identifier(&self, data: &str) -> Result<AccountAddress> {
+        let identifier = AccountIdentifier::from_str(data)?;
+        let network = AddressNetwork::from_chain_id(self.chain_id);
+        ensure!(
+            identifier.network() == network,
+            "The account identifier {} is for the {:?} network, not the {:?} network of chain {}",
+            data,
+            identifier.network(),
+            network,
+            self.chain_id,
+        );
+        Ok(identifier.address())
+    }
+
     fn address_from_strings(data: &str) -> Result<AccountAddress> {
         let account_vec: Vec<u8> = hex::decode(data.parse::<String>()?)?;
         ensure!(
@@ -1609,8 +2201,12 @@ impl ClientProxy {
     }
 
     fn mut_account_from_parameter(&mut self, para: &str) -> Result<&mut AccountData> {
-        let account_ref_id = if is_address(para) {
-            let account_address = ClientProxy::address_from_strings(para)?;
+        let account_ref_id = if is_address(para) || is_account_identifier(para) {
+            let account_address = if is_address(para) {
+                ClientProxy::address_from_strings(para)?
+            } else {
+                self.address_from_account_identifier(para)?
+            };
             *self
                 .address_to_ref_id
                 .get(&account_address)
@@ -1655,6 +2251,127 @@ fn format_parse_data_error<T: std::fmt::Debug>(
     )
 }
 
+/// Formats an amount in its main unit, e.g., 1.5LBR, if the currency is known.
+fn format_amount(amount: &AmountView, currency_info: &HashMap<String, CurrencyInfoView>) -> String {
+    match currency_info.get(&amount.currency) {
+        Some(info) => format!(
+            "{}.{:0>6}{}",
+            amount.amount / info.scaling_factor,
+            amount.amount % info.scaling_factor,
+            amount.currency
+        ),
+        None => format!("{} {} (on-chain units)", amount.amount, amount.currency),
+    }
+}
+
+/// Formats an event for display, decoding the payment events.
+fn format_event(event: &EventView, currency_info: &HashMap<String, CurrencyInfoView>) -> String {
+    let metadata = |metadata: &BytesView| {
+        if metadata.0.is_empty() {
+            String::new()
+        } else {
+            format!(", metadata {}", metadata.0)
+        }
+    };
+    let data = match &event.data {
+        EventDataView::ReceivedPayment {
+            amount,
+            sender,
+            metadata: payment_metadata,
+            ..
+        } => format!(
+            "Received {} from {}{}",
+            format_amount(amount, currency_info),
+            sender.0,
+            metadata(payment_metadata)
+        ),
+        EventDataView::SentPayment {
+            amount,
+            receiver,
+            metadata: payment_metadata,
+            ..
+        } => format!(
+            "Sent {} to {}{}",
+            format_amount(amount, currency_info),
+            receiver.0,
+            metadata(payment_metadata)
+        ),
+        data => format!("{:?}", data),
+    };
+    format!(
+        "#{} (version {}): {}",
+        event.sequence_number, event.transaction_version, data
+    )
+}
+
+/// Estimates the gas units charged to publish a module in a transaction of the given size: the
+/// intrinsic gas of the transaction and the write to the sender account.
+fn estimate_publishing_gas(transaction_size: u64) -> u64 {
+    let gas_constants = GasConstants::default();
+    let intrinsic_gas =
+        calculate_intrinsic_gas(AbstractMemorySize::new(transaction_size), &gas_constants);
+    let write_gas = gas_constants
+        .global_memory_per_byte_write_cost
+        .mul(gas_constants.default_account_size);
+    let gas = intrinsic_gas.add(write_gas).get();
+    (gas + gas_constants.gas_unit_scaling_factor - 1) / gas_constants.gas_unit_scaling_factor
+}
+
+/// Reads the source map emitted by the compiler alongside a compiled unit, if any.
+fn read_source_map(path: &Path) -> Option<SourceMap<Loc>> {
+    let bytes = fs::read(path).ok()?;
+    // Source locations refer to their file by a static string
+    lcs::from_bytes(Box::leak(bytes.into_boxed_slice())).ok()
+}
+
+/// Describes a verification error, pointing back to the Move source if the source map allows.
+fn describe_verification_error(error: &VMError, source_map: Option<&SourceMap<Loc>>) -> String {
+    let mut description = format!("{:?}", error.major_status());
+    if let Some(message) = error.message() {
+        description.push_str(&format!(": {}", message));
+    }
+    let locations: Vec<_> = source_map
+        .map(|source_map| {
+            error
+                .offsets()
+                .iter()
+                .filter_map(|(fdef_idx, offset)| {
+                    source_map.get_code_location(*fdef_idx, *offset).ok()
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    if locations.is_empty() {
+        for (kind, index) in error.indices() {
+            description.push_str(&format!("\n  at {:?} #{}", kind, index));
+        }
+        for (fdef_idx, offset) in error.offsets() {
+            description.push_str(&format!(
+                "\n  at function #{}, code offset {}",
+                fdef_idx, offset
+            ));
+        }
+    }
+    for location in locations {
+        description.push_str(&format!("\n  at {}", describe_location(location)));
+    }
+    description
+}
+
+/// Describes a source location as file:line:column.
+fn describe_location(location: Loc) -> String {
+    let start = location.span().start().to_usize();
+    match fs::read_to_string(location.file()) {
+        Ok(source) if start <= source.len() => {
+            let prefix = &source[..start];
+            let line = prefix.matches('\n').count() + 1;
+            let column = start - prefix.rfind('\n').map_or(0, |newline| newline + 1) + 1;
+            format!("{}:{}:{}", location.file(), line, column)
+        }
+        _ => format!("{}, byte {}", location.file(), start),
+    }
+}
+
 fn parse_bool(para: &str) -> Result<bool> {
     Ok(para.to_lowercase().parse::<bool>()?)
 }
@@ -1670,14 +2387,30 @@ impl fmt::Display for AccountEntry {
 
 #[cfg(test)]
 mod tests {
-    use crate::client_proxy::{parse_bool, AddressAndIndex, ClientProxy};
+    use crate::client_proxy::{
+        format_event, parse_bool, AddressAndIndex, ClientProxy, GAS_UNIT_PRICE, MAX_GAS_AMOUNT,
+        OFFLINE_TX_EXPIRATION,
+    };
+    use libra_crypto::{PrivateKey, ValidCryptoMaterial};
+    use libra_json_rpc_client::views::{
+        AmountView, BytesView, CurrencyInfoView, EventDataView, EventView,
+    };
     use libra_temppath::TempPath;
     use libra_types::{
-        chain_id::ChainId, ledger_info::LedgerInfo, on_chain_config::ValidatorSet,
+        account_address::AccountAddress,
+        account_config::LBR_NAME,
+        chain_id::ChainId,
+        ledger_info::LedgerInfo,
+        on_chain_config::ValidatorSet,
+        transaction::{
+            authenticator::AuthenticationKey, helpers::create_unsigned_txn, Script,
+            SignedTransaction, TransactionPayload,
+        },
         waypoint::Waypoint,
     };
     use libra_wallet::io_utils;
     use proptest::prelude::*;
+    use std::fs;
 
     fn generate_accounts_from_wallet(count: usize) -> (ClientProxy, Vec<AddressAndIndex>) {
         let mut accounts = Vec::new();
@@ -1797,6 +2530,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_event() {
+        let currency_info = vec![(
+            LBR_NAME.to_owned(),
+            CurrencyInfoView {
+                code: LBR_NAME.to_owned(),
+                scaling_factor: 1_000_000,
+                fractional_part: 1_000,
+                to_lbr_exchange_rate: 1.0,
+                mint_events_key: BytesView::from(&[][..]),
+                burn_events_key: BytesView::from(&[][..]),
+                preburn_events_key: BytesView::from(&[][..]),
+                cancel_burn_events_key: BytesView::from(&[][..]),
+                exchange_rate_update_events_key: BytesView::from(&[][..]),
+            },
+        )]
+        .into_iter()
+        .collect();
+        let sender = AccountAddress::random();
+        let mut event = EventView {
+            key: BytesView::from(&[][..]),
+            sequence_number: 3,
+            transaction_version: 42,
+            data: EventDataView::ReceivedPayment {
+                amount: AmountView {
+                    amount: 1_500_000,
+                    currency: LBR_NAME.to_owned(),
+                },
+                sender: BytesView::from(sender.as_ref()),
+                receiver: BytesView::from(AccountAddress::random().as_ref()),
+                metadata: BytesView::from(&[][..]),
+            },
+        };
+        assert_eq!(
+            format_event(&event, &currency_info),
+            format!(
+                "#3 (version 42): Received 1.500000LBR from {}",
+                hex::encode(sender)
+            )
+        );
+
+        event.data = EventDataView::SentPayment {
+            amount: AmountView {
+                amount: 10,
+                currency: "Coin1".to_owned(),
+            },
+            receiver: BytesView::from(sender.as_ref()),
+            sender: BytesView::from(AccountAddress::random().as_ref()),
+            metadata: BytesView::from(&[1u8, 2][..]),
+        };
+        assert_eq!(
+            format_event(&event, &currency_info),
+            format!(
+                "#3 (version 42): Sent 10 Coin1 (on-chain units) to {}, metadata 0102",
+                hex::encode(sender)
+            )
+        );
+    }
+
     #[test]
     fn test_generate() {
         let num = 1;
@@ -1819,6 +2611,84 @@ mod tests {
         assert_eq!(client.wallet.mnemonic(), wallet.mnemonic());
     }
 
+    fn write_unsigned_txn(sender: AccountAddress) -> TempPath {
+        let raw_txn = create_unsigned_txn(
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            sender,
+            0,
+            MAX_GAS_AMOUNT,
+            GAS_UNIT_PRICE,
+            LBR_NAME.to_owned(),
+            OFFLINE_TX_EXPIRATION,
+            ChainId::test(),
+        );
+        let file = TempPath::new();
+        fs::write(file.path(), lcs::to_bytes(&raw_txn).unwrap()).unwrap();
+        file
+    }
+
+    fn sign_offline(
+        client: &ClientProxy,
+        raw_txn_file: &TempPath,
+        signer_type: &str,
+        signer_file: &TempPath,
+    ) -> SignedTransaction {
+        let signed_txn_file = TempPath::new();
+        client
+            .sign_transaction_from_file(&[
+                "sign",
+                raw_txn_file.path().to_str().unwrap(),
+                signer_type,
+                signer_file.path().to_str().unwrap(),
+                signed_txn_file.path().to_str().unwrap(),
+            ])
+            .unwrap();
+        let signed_txn: SignedTransaction =
+            lcs::from_bytes(&fs::read(signed_txn_file.path()).unwrap()).unwrap();
+        signed_txn.check_signature().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_sign_offline_with_mnemonic() {
+        let (client, accounts) = generate_accounts_from_wallet(3);
+        let mnemonic_file = TempPath::new();
+        io_utils::write_recovery(&client.wallet, &mnemonic_file.path()).unwrap();
+
+        let raw_txn_file = write_unsigned_txn(accounts[2].address);
+        let signed_txn = sign_offline(&client, &raw_txn_file, "mnemonic", &mnemonic_file);
+        assert_eq!(signed_txn.sender(), accounts[2].address);
+
+        // An account the wallet does not hold cannot be signed for
+        let raw_txn_file = write_unsigned_txn(AccountAddress::random());
+        let signed_txn_file = TempPath::new();
+        client
+            .sign_transaction_from_file(&[
+                "sign",
+                raw_txn_file.path().to_str().unwrap(),
+                "mnemonic",
+                mnemonic_file.path().to_str().unwrap(),
+                signed_txn_file.path().to_str().unwrap(),
+            ])
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_sign_offline_with_key_file() {
+        let (client, _) = generate_accounts_from_wallet(1);
+        let key_file = TempPath::new();
+        let private_key = generate_key::generate_and_save_key(key_file.path());
+        let public_key = private_key.public_key();
+        let sender = AuthenticationKey::ed25519(&public_key).derived_address();
+
+        let raw_txn_file = write_unsigned_txn(sender);
+        let signed_txn = sign_offline(&client, &raw_txn_file, "key", &key_file);
+        assert_eq!(signed_txn.sender(), sender);
+        assert_eq!(
+            signed_txn.authenticator().public_key_bytes(),
+            public_key.to_bytes().to_vec()
+        );
+    }
+
     proptest! {
         // Proptest is used to verify that the conversion will not panic with random input.
         #[test]
@@ -2,17 +2,118 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    account_commands::AccountCommand, client_proxy::ClientProxy, dev_commands::DevCommand,
-    query_commands::QueryCommand, transfer_commands::TransferCommand,
+    account_commands::AccountCommand,
+    client_proxy::{ClientProxy, ExecutionFailure, IndexAndSequence},
+    dev_commands::DevCommand,
+    offline_commands::OfflineCommand,
+    profile_commands::ProfileCommand,
+    query_commands::QueryCommand,
+    transfer_commands::TransferCommand,
 };
 use anyhow::Error;
 use libra_metrics::counters::*;
 use libra_types::{account_address::AccountAddress, transaction::authenticator::AuthenticationKey};
-use std::{collections::HashMap, sync::Arc};
+use serde::Serialize;
+use serde_json::json;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// Whether commands emit machine-readable JSON instead of human-readable text.
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// Switch all commands to emitting machine-readable JSON, one object per line.
+pub fn set_json_output(json_output: bool) {
+    JSON_OUTPUT.store(json_output, Ordering::Relaxed);
+}
 
-/// Print the error and bump up error counter.
+/// Check whether commands emit machine-readable JSON.
+pub fn json_output() -> bool {
+    JSON_OUTPUT.load(Ordering::Relaxed)
+}
+
+/// Print a progress message. These are omitted from JSON output so that it stays parseable.
+pub fn print_progress(msg: &str) {
+    if !json_output() {
+        println!("{}", msg);
+    }
+}
+
+/// Print the result of a command, as a single line of JSON if JSON output is enabled, otherwise
+/// as human-readable text with `print_text`.
+pub fn print_result<T: Serialize>(result: T, print_text: impl FnOnce(T)) {
+    if !json_output() {
+        print_text(result);
+        return;
+    }
+    match serde_json::to_string(&result) {
+        Ok(json) => println!("{}", json),
+        Err(e) => report_error("Unable to serialize result", e.into()),
+    }
+}
+
+/// Print the outcome of a submitted transaction. In JSON output, the committed transaction is
+/// included if the submission was blocking.
+pub fn print_submission(
+    client: &mut ClientProxy,
+    index_and_seq: IndexAndSequence,
+    is_blocking: bool,
+) {
+    if !json_output() {
+        if is_blocking {
+            println!("Finished transaction!");
+        } else {
+            println!("Transaction submitted to validator");
+        }
+        println!(
+            "To query for transaction status, run: query txn_acc_seq {} {} \
+             <fetch_events=true|false>",
+            index_and_seq.account_index, index_and_seq.sequence_number
+        );
+        return;
+    }
+
+    let transaction = if is_blocking {
+        match client.get_committed_txn(&index_and_seq.account_index, index_and_seq.sequence_number)
+        {
+            Ok(transaction) => transaction,
+            Err(e) => return report_error("Failed to get committed transaction", e),
+        }
+    } else {
+        None
+    };
+    print_result(
+        json!({
+            "account": index_and_seq.account_index.to_string(),
+            "sequence_number": index_and_seq.sequence_number,
+            "transaction": transaction,
+        }),
+        |_| (),
+    );
+}
+
+/// Print the error and bump up error counter. In JSON output, the transaction is included if it
+/// failed to execute, so that its VM status can be inspected.
 pub fn report_error(msg: &str, e: Error) {
-    println!("[ERROR] {}: {}", msg, e);
+    if json_output() {
+        let transaction = e
+            .downcast_ref::<ExecutionFailure>()
+            .map(|failure| &failure.0);
+        println!(
+            "{}",
+            json!({
+                "error": msg,
+                "details": e.to_string(),
+                "transaction": transaction,
+            })
+        );
+    } else {
+        println!("[ERROR] {}: {}", msg, e);
+    }
     COUNTER_CLIENT_ERRORS.inc();
 }
 
@@ -48,6 +149,8 @@ pub fn get_commands(
         Arc::new(AccountCommand {}),
         Arc::new(QueryCommand {}),
         Arc::new(TransferCommand {}),
+        Arc::new(OfflineCommand {}),
+        Arc::new(ProfileCommand {}),
     ];
     if include_dev {
         commands.push(Arc::new(DevCommand {}));
@@ -3,7 +3,7 @@
 
 use crate::{
     client_proxy::ClientProxy,
-    commands::{subcommand_execute, Command},
+    commands::{report_error, subcommand_execute, Command},
 };
 use chrono::{DateTime, Utc};
 use libra_types::waypoint::Waypoint;
@@ -23,12 +23,14 @@ impl Command for DevCommand {
         let commands: Vec<Box<dyn Command>> = vec![
             Box::new(DevCommandCompile {}),
             Box::new(DevCommandPublish {}),
+            Box::new(DevCommandCompilePublish {}),
             Box::new(DevCommandExecute {}),
             Box::new(DevCommandUpgradeStdlib {}),
             Box::new(DevCommandAddValidator {}),
             Box::new(DevCommandRemoveValidator {}),
             Box::new(DevCommandGenWaypoint {}),
             Box::new(DevCommandRegisterValidator {}),
+            Box::new(DevCommandEvents {}),
         ];
         subcommand_execute(&params[0], commands, client, &params[1..]);
     }
@@ -65,6 +67,51 @@ impl Command for DevCommandCompile {
     }
 }
 
+/// Sub command for following on-chain events
+pub struct DevCommandEvents {}
+
+impl Command for DevCommandEvents {
+    fn get_aliases(&self) -> Vec<&'static str> {
+        vec!["events", "ev"]
+    }
+    fn get_params_help(&self) -> &'static str {
+        "tail <account_ref_id>|<account_address> <sent|received> \
+         [start_sequence_number (default=next event)] [max_events (default=unlimited)]"
+    }
+    fn get_description(&self) -> &'static str {
+        "Follow the events of an account"
+    }
+    fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
+        let commands: Vec<Box<dyn Command>> = vec![Box::new(DevCommandEventsTail {})];
+        subcommand_execute(&params[0], commands, client, &params[1..]);
+    }
+}
+
+/// Sub command to print new events of an account as they are emitted
+pub struct DevCommandEventsTail {}
+
+impl Command for DevCommandEventsTail {
+    fn get_aliases(&self) -> Vec<&'static str> {
+        vec!["tail", "t"]
+    }
+    fn get_params_help(&self) -> &'static str {
+        "<account_ref_id>|<account_address> <sent|received> \
+         [start_sequence_number (default=next event)] [max_events (default=unlimited)]"
+    }
+    fn get_description(&self) -> &'static str {
+        "Print new events of an account as they are emitted, until max_events are printed"
+    }
+    fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
+        if params.len() < 3 || params.len() > 5 {
+            println!("Invalid number of arguments to tail events");
+            return;
+        }
+        if let Err(e) = client.tail_events(params) {
+            println!("{}", e);
+        }
+    }
+}
+
 /// Sub command to publish a Move resource
 pub struct DevCommandPublish {}
 
@@ -93,6 +140,38 @@ impl Command for DevCommandPublish {
     }
 }
 
+/// Sub command to compile, verify and publish a Move module in one go
+pub struct DevCommandCompilePublish {}
+
+impl Command for DevCommandCompilePublish {
+    fn get_aliases(&self) -> Vec<&'static str> {
+        vec!["compile_publish", "cp"]
+    }
+
+    fn get_params_help(&self) -> &'static str {
+        "<sender_account_address>|<sender_account_ref_id> <file_path> [dependency_source_files...]"
+    }
+
+    fn get_description(&self) -> &'static str {
+        "Compile a Move module against the stdlib, verify it against the on-chain modules and \
+         publish it"
+    }
+
+    fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
+        if params.len() < 3 {
+            println!("Invalid number of arguments to compile and publish module");
+            return;
+        }
+        match client.compile_and_publish_module(params) {
+            Ok(estimated_gas) => println!(
+                "Successfully published module, estimated gas: {}",
+                estimated_gas
+            ),
+            Err(e) => report_error("Error compiling and publishing module", e),
+        }
+    }
+}
+
 /// Sub command to execute a custom Move script
 pub struct DevCommandExecute {}
 
@@ -25,6 +25,10 @@ pub mod commands;
 mod dev_commands;
 /// Client wrapper to connect to validator.
 mod libra_client;
+mod offline_commands;
+/// Named connection profiles, each with its own persisted trusted state.
+pub mod profile;
+mod profile_commands;
 mod query_commands;
 mod transfer_commands;
 
@@ -13,12 +13,14 @@ use libra_json_rpc_client::{
     JsonRpcBatch, JsonRpcClient, JsonRpcResponse, ResponseAsView,
 };
 use libra_logger::prelude::*;
+use libra_secure_storage::{OnDiskStorage, TrustedStateStorage};
 use libra_types::{
     access_path::AccessPath,
     account_address::AccountAddress,
     account_config::{ACCOUNT_RECEIVED_EVENT_PATH, ACCOUNT_SENT_EVENT_PATH},
     account_state_blob::AccountStateBlob,
     epoch_change::EpochChangeProof,
+    event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
     transaction::{SignedTransaction, Version},
     trusted_state::{TrustedState, TrustedStateChange},
@@ -26,6 +28,7 @@ use libra_types::{
     waypoint::Waypoint,
 };
 use reqwest::Url;
+use std::convert::TryFrom;
 
 /// A client connection to an AdmissionControl (AC) service. `LibraClient` also
 /// handles verifying the server's responses, retrying on non-fatal failures, and
@@ -49,6 +52,8 @@ pub struct LibraClient {
     /// about our local [`Waypoint`] and have not yet ratcheted to the remote's
     /// latest state.
     latest_epoch_change_li: Option<LedgerInfoWithSignatures>,
+    /// Where the latest verified chain state is persisted, if anywhere.
+    trusted_state_storage: Option<OnDiskStorage>,
 }
 
 impl LibraClient {
@@ -60,9 +65,32 @@ impl LibraClient {
             client,
             trusted_state: initial_trusted_state,
             latest_epoch_change_li: None,
+            trusted_state_storage: None,
         })
     }
 
+    /// Persists the latest verified chain state to `storage` from now on. If `storage` already
+    /// holds a state that is ahead of ours, e.g., from a previous run, we resume from it.
+    pub fn persist_trusted_state(&mut self, mut storage: OnDiskStorage) -> Result<()> {
+        match storage.trusted_state()? {
+            Some(stored_state)
+                if self
+                    .trusted_state
+                    .ensure_not_regressed_by(&stored_state)
+                    .is_ok() =>
+            {
+                info!(
+                    "Resuming from stored trusted state at version {}",
+                    stored_state.latest_version()
+                );
+                self.trusted_state = stored_state;
+            }
+            _ => storage.ratchet_trusted_state(&self.trusted_state)?,
+        }
+        self.trusted_state_storage = Some(storage);
+        Ok(())
+    }
+
     /// Submits a transaction and bumps the sequence number for the sender, pass in `None` for
     /// sender_account if sender's address is not managed by the client.
     pub fn submit_transaction(
@@ -260,20 +288,28 @@ impl LibraClient {
                         .expect("no validator set in epoch change ledger info"),
                 );
                 // Update client state
-                self.trusted_state = new_state;
+                self.update_trusted_state(new_state)?;
                 self.latest_epoch_change_li = Some(latest_epoch_change_li.clone());
             }
             TrustedStateChange::Version { new_state } => {
                 if self.trusted_state.latest_version() < new_state.latest_version() {
                     info!("Verified version change to: {}", new_state.latest_version());
                 }
-                self.trusted_state = new_state;
+                self.update_trusted_state(new_state)?;
             }
             TrustedStateChange::NoChange => (),
         }
         Ok(())
     }
 
+    fn update_trusted_state(&mut self, new_state: TrustedState) -> Result<()> {
+        if let Some(storage) = self.trusted_state_storage.as_mut() {
+            storage.ratchet_trusted_state(&new_state)?;
+        }
+        self.trusted_state = new_state;
+        Ok(())
+    }
+
     /// LedgerInfo corresponding to the latest epoch change.
     pub(crate) fn latest_epoch_change_li(&self) -> Option<&LedgerInfoWithSignatures> {
         self.latest_epoch_change_li.as_ref()
@@ -348,6 +384,13 @@ impl LibraClient {
                 } else {
                     bail!("Unexpected event path found in access path");
                 };
+                ensure!(
+                    EventKey::try_from(&hex::decode(event_key)?[..])?
+                        .is_created_by(&access_path.address),
+                    "Event key {} does not belong to account {}",
+                    event_key,
+                    access_path.address
+                );
 
                 // get_events
                 let events = self.get_events(event_key.to_string(), start_event_seq_num, limit)?;
@@ -9,11 +9,14 @@ use chrono::{
 };
 use cli::{
     client_proxy::ClientProxy,
-    commands::{get_commands, parse_cmd, report_error, Command},
+    commands::{get_commands, parse_cmd, print_progress, report_error, set_json_output, Command},
+    profile::{Profile, Profiles},
 };
+use libra_metrics::counters::COUNTER_CLIENT_ERRORS;
 use libra_types::{chain_id::ChainId, waypoint::Waypoint};
 use rustyline::{config::CompletionType, error::ReadlineError, Config, Editor};
 use std::{
+    path::PathBuf,
     str::FromStr,
     time::{Duration, UNIX_EPOCH},
 };
@@ -36,10 +39,19 @@ struct Args {
             testnet: \"TESTNET\" or 2, devnet: \"DEVNET\" or 3, local swarm: \"TESTING\" or 4
         "
     )]
-    pub chain_id: ChainId,
-    /// Full URL address to connect to - should include port number, if applicable
+    pub chain_id: Option<ChainId>,
+    /// Full URL address to connect to - should include port number, if applicable.
+    /// If not passed, the connection profile is used instead.
     #[structopt(short = "u", long)]
-    pub url: String,
+    pub url: Option<String>,
+    /// Name of the connection profile to use. If `url` is passed as well, a new profile is
+    /// created from `url`, `chain_id` and the waypoint and made the active one. If neither is
+    /// passed, the active profile is used.
+    #[structopt(short = "p", long)]
+    pub profile: Option<String>,
+    /// File in which connection profiles are stored.
+    #[structopt(long, parse(from_os_str), default_value = "client.profiles.yaml")]
+    pub profiles_file: PathBuf,
     /// Path to the generated keypair for the faucet account. The faucet account can be used to
     /// mint coins. If not passed, a new keypair will be generated for
     /// you and placed in a temporary directory.
@@ -72,15 +84,30 @@ struct Args {
         help = "URL for a file with the waypoint to use"
     )]
     pub waypoint_url: Option<String>,
+    /// File in which the latest verified chain state is persisted across runs. If set, the
+    /// client resumes from the state stored there and refuses to move back from it. Defaults to
+    /// the trusted state of the connection profile, if one is used.
+    #[structopt(long, parse(from_os_str))]
+    pub trusted_state_file: Option<PathBuf>,
     /// Verbose output.
     #[structopt(short = "v", long = "verbose")]
     pub verbose: bool,
+    /// If set, commands emit machine-readable JSON, one object per line, instead of
+    /// human-readable text.
+    #[structopt(long)]
+    pub json: bool,
+    /// Command to run non-interactively, can be repeated. If set, the client runs the commands in
+    /// order and exits instead of starting a prompt. The exit code is non-zero if any command
+    /// reported an error.
+    #[structopt(short = "e", long = "exec", number_of_values = 1)]
+    pub exec: Vec<String>,
 }
 
 fn main() {
     ::libra_logger::Logger::new().init();
     crash_handler::setup_panic_handler();
     let args = Args::from_args();
+    set_json_output(args.json);
 
     let (commands, alias_to_cmd) = get_commands(args.faucet_account_file.is_some());
 
@@ -93,28 +120,46 @@ fn main() {
     let mnemonic_file = args.mnemonic_file.clone();
 
     // If waypoint is given explicitly, use its value,
-    // otherwise waypoint_url is required, try to retrieve the waypoint from the URL.
-    let waypoint = args.waypoint.unwrap_or_else(|| {
-        args.waypoint_url
-            .as_ref()
-            .map(|url_str| {
-                retrieve_waypoint(url_str.as_str()).unwrap_or_else(|e| {
-                    panic!("Failure to retrieve a waypoint from {}: {}", url_str, e)
-                })
+    // otherwise if waypoint_url is given, try to retrieve the waypoint from the URL.
+    let waypoint = args.waypoint.or_else(|| {
+        args.waypoint_url.as_ref().map(|url_str| {
+            retrieve_waypoint(url_str.as_str()).unwrap_or_else(|e| {
+                panic!("Failure to retrieve a waypoint from {}: {}", url_str, e)
             })
-            .unwrap()
+        })
+    });
+    let mut profiles = Profiles::load(&args.profiles_file)
+        .unwrap_or_else(|e| panic!("Failed to load connection profiles: {}", e));
+    let (profile_name, profile) = select_profile(&args, waypoint, &mut profiles);
+    let trusted_state_file = args.trusted_state_file.clone().or_else(|| {
+        profile_name
+            .as_ref()
+            .map(|name| profiles.trusted_state_file(name))
     });
+
     let mut client_proxy = ClientProxy::new(
-        args.chain_id,
-        &args.url,
+        profile.chain_id,
+        &profile.url,
         &faucet_account_file,
         &treasury_compliance_account_file,
         args.sync,
         args.faucet_server.clone(),
         mnemonic_file,
-        waypoint,
+        profile.waypoint,
     )
     .expect("Failed to construct client.");
+    client_proxy.set_profiles(profiles);
+
+    if let Some(trusted_state_file) = &trusted_state_file {
+        client_proxy
+            .persist_trusted_state(trusted_state_file)
+            .unwrap_or_else(|e| {
+                panic!(
+                    "Failed to persist the trusted state to {:?}: {}",
+                    trusted_state_file, e
+                )
+            });
+    }
 
     // Test connection to validator
     let block_metadata = client_proxy
@@ -122,7 +167,7 @@ fn main() {
         .unwrap_or_else(|e| {
             panic!(
                 "Not able to connect to validator at {}. Error: {}",
-                args.url, e,
+                profile.url, e,
             )
         });
     let ledger_info_str = format!(
@@ -132,22 +177,45 @@ fn main() {
     );
     let cli_info = format!(
         "Connected to validator at: {}, {}",
-        args.url, ledger_info_str
+        profile.url, ledger_info_str
     );
     if args.mnemonic_file.is_some() {
         match client_proxy.recover_accounts_in_wallet() {
             Ok(account_data) => {
-                println!(
+                print_progress(&format!(
                     "Wallet recovered and the first {} child accounts were derived",
                     account_data.len()
-                );
+                ));
                 for data in account_data {
-                    println!("#{} address {}", data.index, hex::encode(data.address));
+                    print_progress(&format!(
+                        "#{} address {}",
+                        data.index,
+                        hex::encode(data.address)
+                    ));
                 }
             }
             Err(e) => report_error("Error recovering Libra wallet", e),
         }
     }
+
+    if !args.exec.is_empty() {
+        for line in &args.exec {
+            let params = parse_cmd(line);
+            if params.is_empty() {
+                continue;
+            }
+            match alias_to_cmd.get(&params[0]) {
+                Some(cmd) => cmd.execute(&mut client_proxy, &params),
+                None => report_error("Unknown command", anyhow::format_err!("{:?}", params[0])),
+            }
+        }
+        std::process::exit(if COUNTER_CLIENT_ERRORS.get() == 0 {
+            0
+        } else {
+            1
+        });
+    }
+
     print_help(&cli_info, &commands);
     println!("Please, input commands: \n");
 
@@ -214,6 +282,70 @@ fn print_help(client_info: &str, commands: &[std::sync::Arc<dyn Command>]) {
     println!("\n");
 }
 
+/// Selects the connection profile to use, along with its name if it is a stored profile. A new
+/// profile is created if both a URL and a profile name are passed. Connection parameters passed
+/// along with an existing profile must match it, so that networks cannot be mixed up.
+fn select_profile(
+    args: &Args,
+    waypoint: Option<Waypoint>,
+    profiles: &mut Profiles,
+) -> (Option<String>, Profile) {
+    if let Some(url) = &args.url {
+        let profile = Profile {
+            chain_id: args
+                .chain_id
+                .expect("Chain ID is required along with a URL"),
+            url: url.clone(),
+            waypoint: waypoint.expect("Waypoint is required along with a URL"),
+        };
+        let name = match &args.profile {
+            Some(name) => name,
+            None => return (None, profile),
+        };
+        match profiles.profiles.get(name) {
+            Some(existing) if existing != &profile => panic!(
+                "Profile {} exists for a different network: {:?}, remove it first",
+                name, existing
+            ),
+            Some(_) => (),
+            None => {
+                profiles
+                    .add(name, profile.clone())
+                    .and_then(|_| profiles.set_active(name))
+                    .and_then(|_| profiles.save())
+                    .unwrap_or_else(|e| panic!("Failed to save profile {}: {}", name, e));
+                println!("Saved connection profile {}", name);
+            }
+        }
+        return (Some(name.clone()), profile);
+    }
+
+    let name = args
+        .profile
+        .clone()
+        .or_else(|| profiles.active.clone())
+        .expect("Either a URL or a connection profile is required");
+    let profile = profiles
+        .get(&name)
+        .unwrap_or_else(|e| panic!("{}", e))
+        .clone();
+    if let Some(chain_id) = args.chain_id {
+        assert_eq!(
+            chain_id, profile.chain_id,
+            "Chain ID does not match profile {}",
+            name
+        );
+    }
+    if let Some(waypoint) = waypoint {
+        assert_eq!(
+            waypoint, profile.waypoint,
+            "Waypoint does not match profile {}",
+            name
+        );
+    }
+    (Some(name), profile)
+}
+
 /// Retrieve a waypoint given the URL.
 fn retrieve_waypoint(url_str: &str) -> anyhow::Result<Waypoint> {
     let client = reqwest::blocking::ClientBuilder::new().build()?;
@@ -0,0 +1,121 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    client_proxy::ClientProxy,
+    commands::{
+        blocking_cmd, print_progress, print_submission, report_error, subcommand_execute, Command,
+    },
+};
+
+/// Major command for signing transactions on a host that is never connected to the network.
+pub struct OfflineCommand {}
+
+impl Command for OfflineCommand {
+    fn get_aliases(&self) -> Vec<&'static str> {
+        vec!["offline", "o"]
+    }
+    fn get_description(&self) -> &'static str {
+        "Offline transaction signing operations"
+    }
+    fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
+        let commands: Vec<Box<dyn Command>> = vec![
+            Box::new(OfflineCommandPrepare {}),
+            Box::new(OfflineCommandSign {}),
+            Box::new(OfflineCommandSubmit {}),
+        ];
+
+        subcommand_execute(&params[0], commands, client, &params[1..]);
+    }
+}
+
+/// Sub command to write an unsigned transfer transaction to a file.
+pub struct OfflineCommandPrepare {}
+
+impl Command for OfflineCommandPrepare {
+    fn get_aliases(&self) -> Vec<&'static str> {
+        vec!["prepare", "p"]
+    }
+    fn get_params_help(&self) -> &'static str {
+        "\n\t<sender_account_address>|<sender_account_ref_id> \
+         <receiver_account_address>|<receiver_account_ref_id> <number_of_coins> <currency_code> \
+         <output_file_path> <sequence_number> [gas_unit_price_in_micro_libras (default=0)] \
+         [max_gas_amount_in_micro_libras (default 1_000_000)] [expiration_secs (default=3600)]"
+    }
+    fn get_description(&self) -> &'static str {
+        "Write an unsigned transfer transaction to a file, to be signed offline"
+    }
+    fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
+        if params.len() < 7 || params.len() > 10 {
+            println!("Invalid number of arguments for preparing transaction");
+            return;
+        }
+        println!(">> Preparing unsigned transaction");
+        match client.prepare_transfer_coins_to_file(&params) {
+            Ok(raw_txn) => println!(
+                "Wrote unsigned transaction from {} with sequence number {} to {}",
+                raw_txn.sender(),
+                params[6],
+                params[5]
+            ),
+            Err(e) => report_error("Error preparing transaction", e),
+        }
+    }
+}
+
+/// Sub command to sign a transaction read from a file with a key file or a mnemonic file.
+pub struct OfflineCommandSign {}
+
+impl Command for OfflineCommandSign {
+    fn get_aliases(&self) -> Vec<&'static str> {
+        vec!["sign", "s"]
+    }
+    fn get_params_help(&self) -> &'static str {
+        "<unsigned_txn_file_path> key|mnemonic <key_file_path>|<mnemonic_file_path> \
+         <output_file_path>"
+    }
+    fn get_description(&self) -> &'static str {
+        "Sign an unsigned transaction, requires no connection to a validator"
+    }
+    fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
+        if params.len() != 5 {
+            println!("Invalid number of arguments for signing transaction");
+            return;
+        }
+        println!(">> Signing transaction");
+        match client.sign_transaction_from_file(&params) {
+            Ok(signed_txn) => {
+                println!("Signed transaction: {:#?}", signed_txn);
+                println!("Wrote signed transaction to {}", params[4]);
+            }
+            Err(e) => report_error("Error signing transaction", e),
+        }
+    }
+}
+
+/// Sub command to submit a signed transaction read from a file.
+pub struct OfflineCommandSubmit {}
+
+impl Command for OfflineCommandSubmit {
+    fn get_aliases(&self) -> Vec<&'static str> {
+        vec!["submit", "submitb"]
+    }
+    fn get_params_help(&self) -> &'static str {
+        "<signed_txn_file_path>"
+    }
+    fn get_description(&self) -> &'static str {
+        "Submit a transaction signed offline. Suffix 'b' is for blocking"
+    }
+    fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
+        if params.len() != 2 {
+            println!("Invalid number of arguments for submitting transaction");
+            return;
+        }
+        print_progress(">> Submitting signed transaction");
+        let is_blocking = blocking_cmd(params[0]);
+        match client.submit_signed_transaction_from_file(&params, is_blocking) {
+            Ok(index_and_seq) => print_submission(client, index_and_seq, is_blocking),
+            Err(e) => report_error("Failed to submit transaction", e),
+        }
+    }
+}
@@ -0,0 +1,157 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, format_err, Result};
+use libra_types::{chain_id::ChainId, waypoint::Waypoint};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Everything needed to connect to, and verify, a single Libra network.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Profile {
+    /// Chain ID of the network.
+    pub chain_id: ChainId,
+    /// Full URL of the JSON-RPC endpoint.
+    pub url: String,
+    /// Waypoint used for the initial verification of the network.
+    pub waypoint: Waypoint,
+}
+
+/// Named connection profiles stored in a config file. Each profile persists its verified trusted
+/// state in a file of its own next to the config file, so that the chain state of one network is
+/// never used to verify another one.
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Profiles {
+    #[serde(skip)]
+    path: PathBuf,
+    /// Name of the profile used when none is specified.
+    pub active: Option<String>,
+    /// Profiles by name.
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+impl Profiles {
+    /// Loads the profiles stored at `path`, if the file doesn't exist there are no profiles.
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut profiles = if path.exists() {
+            let contents = fs::read_to_string(path)?;
+            serde_yaml::from_str(&contents)
+                .map_err(|e| format_err!("Unable to parse profiles at {:?}: {}", path, e))?
+        } else {
+            Self::default()
+        };
+        profiles.path = path.to_path_buf();
+        Ok(profiles)
+    }
+
+    /// Writes the profiles back to the file they were loaded from.
+    pub fn save(&self) -> Result<()> {
+        fs::write(&self.path, serde_yaml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Returns the profile with the given name.
+    pub fn get(&self, name: &str) -> Result<&Profile> {
+        self.profiles
+            .get(name)
+            .ok_or_else(|| format_err!("Unknown profile: {}", name))
+    }
+
+    /// Returns the active profile along with its name, if any.
+    pub fn active(&self) -> Result<Option<(&str, &Profile)>> {
+        match &self.active {
+            Some(name) => Ok(Some((name.as_str(), self.get(name)?))),
+            None => Ok(None),
+        }
+    }
+
+    /// Adds a new profile. Existing profiles cannot be overwritten, as their trusted state
+    /// belongs to the network they were created for.
+    pub fn add(&mut self, name: &str, profile: Profile) -> Result<()> {
+        ensure!(
+            !self.profiles.contains_key(name),
+            "Profile {} already exists",
+            name
+        );
+        self.profiles.insert(name.to_string(), profile);
+        Ok(())
+    }
+
+    /// Removes a profile along with its trusted state.
+    pub fn remove(&mut self, name: &str) -> Result<Profile> {
+        let profile = self
+            .profiles
+            .remove(name)
+            .ok_or_else(|| format_err!("Unknown profile: {}", name))?;
+        if self.active.as_deref() == Some(name) {
+            self.active = None;
+        }
+        let trusted_state_file = self.trusted_state_file(name);
+        if trusted_state_file.exists() {
+            fs::remove_file(trusted_state_file)?;
+        }
+        Ok(profile)
+    }
+
+    /// Makes the given profile the active one.
+    pub fn set_active(&mut self, name: &str) -> Result<()> {
+        self.get(name)?;
+        self.active = Some(name.to_string());
+        Ok(())
+    }
+
+    /// The file in which the trusted state of the given profile is persisted.
+    pub fn trusted_state_file(&self, name: &str) -> PathBuf {
+        self.path
+            .with_file_name(format!("client.{}.trusted_state", name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libra_temppath::TempPath;
+    use libra_types::{ledger_info::LedgerInfo, on_chain_config::ValidatorSet};
+
+    fn profile(chain_id: ChainId) -> Profile {
+        Profile {
+            chain_id,
+            url: "http://localhost:8080".to_string(),
+            waypoint: Waypoint::new_epoch_boundary(&LedgerInfo::mock_genesis(Some(
+                ValidatorSet::empty(),
+            )))
+            .unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_profiles_save_load() {
+        let path = TempPath::new();
+        let mut profiles = Profiles::load(path.path()).unwrap();
+        assert!(profiles.profiles.is_empty());
+        assert!(profiles.active().unwrap().is_none());
+
+        profiles.add("test", profile(ChainId::test())).unwrap();
+        profiles.add("dev", profile(ChainId::new(3))).unwrap();
+        profiles.add("test", profile(ChainId::new(3))).unwrap_err();
+        profiles.set_active("unknown").unwrap_err();
+        profiles.set_active("test").unwrap();
+        profiles.save().unwrap();
+
+        let mut loaded = Profiles::load(path.path()).unwrap();
+        assert_eq!(loaded, profiles);
+        assert_eq!(
+            loaded.active().unwrap(),
+            Some(("test", &profile(ChainId::test())))
+        );
+
+        loaded.remove("test").unwrap();
+        assert!(loaded.active().unwrap().is_none());
+        loaded.remove("test").unwrap_err();
+        assert_eq!(loaded.get("dev").unwrap(), &profile(ChainId::new(3)));
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    client_proxy::ClientProxy,
+    commands::{report_error, subcommand_execute, Command},
+};
+
+/// Major command for connection profile related operations.
+pub struct ProfileCommand {}
+
+impl Command for ProfileCommand {
+    fn get_aliases(&self) -> Vec<&'static str> {
+        vec!["profile", "pr"]
+    }
+    fn get_description(&self) -> &'static str {
+        "Connection profile operations"
+    }
+    fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
+        let commands: Vec<Box<dyn Command>> = vec![
+            Box::new(ProfileCommandList {}),
+            Box::new(ProfileCommandAdd {}),
+            Box::new(ProfileCommandRemove {}),
+            Box::new(ProfileCommandSwitch {}),
+        ];
+
+        subcommand_execute(&params[0], commands, client, &params[1..]);
+    }
+}
+
+/// Sub command to list all connection profiles.
+pub struct ProfileCommandList {}
+
+impl Command for ProfileCommandList {
+    fn get_aliases(&self) -> Vec<&'static str> {
+        vec!["list", "l"]
+    }
+    fn get_description(&self) -> &'static str {
+        "Print all connection profiles, the active one is marked with '*'"
+    }
+    fn execute(&self, client: &mut ClientProxy, _params: &[&str]) {
+        match client.list_profiles() {
+            Ok((active, profiles)) => {
+                for (name, profile) in profiles {
+                    let marker = if active.as_ref() == Some(&name) {
+                        "*"
+                    } else {
+                        " "
+                    };
+                    println!(
+                        "{} {}: chain id {}, url {}, waypoint {}",
+                        marker, name, profile.chain_id, profile.url, profile.waypoint
+                    );
+                }
+            }
+            Err(e) => report_error("Error listing profiles", e),
+        }
+    }
+}
+
+/// Sub command to add a connection profile.
+pub struct ProfileCommandAdd {}
+
+impl Command for ProfileCommandAdd {
+    fn get_aliases(&self) -> Vec<&'static str> {
+        vec!["add", "a"]
+    }
+    fn get_params_help(&self) -> &'static str {
+        "<name> <chain_id> <url> <waypoint>"
+    }
+    fn get_description(&self) -> &'static str {
+        "Add a connection profile"
+    }
+    fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
+        if params.len() != 5 {
+            println!("Invalid number of arguments for adding profile");
+            return;
+        }
+        match client.add_profile(&params) {
+            Ok(_) => println!("Added profile {}", params[1]),
+            Err(e) => report_error("Error adding profile", e),
+        }
+    }
+}
+
+/// Sub command to remove a connection profile.
+pub struct ProfileCommandRemove {}
+
+impl Command for ProfileCommandRemove {
+    fn get_aliases(&self) -> Vec<&'static str> {
+        vec!["remove", "r"]
+    }
+    fn get_params_help(&self) -> &'static str {
+        "<name>"
+    }
+    fn get_description(&self) -> &'static str {
+        "Remove a connection profile along with its trusted state"
+    }
+    fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
+        if params.len() != 2 {
+            println!("Invalid number of arguments for removing profile");
+            return;
+        }
+        match client.remove_profile(&params) {
+            Ok(_) => println!("Removed profile {}", params[1]),
+            Err(e) => report_error("Error removing profile", e),
+        }
+    }
+}
+
+/// Sub command to connect to the network of another connection profile.
+pub struct ProfileCommandSwitch {}
+
+impl Command for ProfileCommandSwitch {
+    fn get_aliases(&self) -> Vec<&'static str> {
+        vec!["switch", "s"]
+    }
+    fn get_params_help(&self) -> &'static str {
+        "<name>"
+    }
+    fn get_description(&self) -> &'static str {
+        "Connect to the network of a connection profile and make it the active one"
+    }
+    fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
+        if params.len() != 2 {
+            println!("Invalid number of arguments for switching profile");
+            return;
+        }
+        println!(">> Switching to profile {}", params[1]);
+        match client.switch_profile(&params) {
+            Ok(block_metadata) => println!(
+                "Connected to {}, latest version = {}",
+                params[1], block_metadata.version
+            ),
+            Err(e) => report_error("Error switching profile", e),
+        }
+    }
+}
@@ -3,8 +3,9 @@
 
 use crate::{
     client_proxy::ClientProxy,
-    commands::{report_error, subcommand_execute, Command},
+    commands::{print_progress, print_result, report_error, subcommand_execute, Command},
 };
+use serde_json::json;
 
 /// Major command for query operations.
 pub struct QueryCommand {}
@@ -50,9 +51,11 @@ impl Command for QueryCommandGetBalance {
             return;
         }
         match client.get_balances(&params) {
-            Ok(balances) => balances
-                .iter()
-                .for_each(|balance| println!("Balance is: {}", balance)),
+            Ok(balances) => print_result(balances, |balances| {
+                balances
+                    .iter()
+                    .for_each(|balance| println!("Balance is: {}", balance))
+            }),
             Err(e) => report_error("Failed to get balances", e),
         }
     }
@@ -73,9 +76,11 @@ impl Command for QueryCommandGetSeqNum {
          and reset current sequence number in CLI (optional, default is false)"
     }
     fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
-        println!(">> Getting current sequence number");
+        print_progress(">> Getting current sequence number");
         match client.get_sequence_number(&params) {
-            Ok(sn) => println!("Sequence number is: {}", sn),
+            Ok(sn) => print_result(json!({ "sequence_number": sn }), |_| {
+                println!("Sequence number is: {}", sn)
+            }),
             Err(e) => report_error("Error getting sequence number", e),
         }
     }
@@ -95,19 +100,25 @@ impl Command for QueryCommandGetLatestAccountState {
         "Get the latest state for an account"
     }
     fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
-        println!(">> Getting latest account state");
+        print_progress(">> Getting latest account state");
         match client.get_latest_account_state(&params) {
-            Ok((acc, version)) => println!(
-                "Latest account state is: \n \
-                 Account: {:#?}\n \
-                 State: {:#?}\n \
-                 Blockchain Version: {}\n",
-                client
+            Ok((acc, version)) => {
+                let (address, _) = client
                     .get_account_address_from_parameter(params[1])
-                    .expect("Unable to parse account parameter"),
-                acc,
-                version,
-            ),
+                    .expect("Unable to parse account parameter");
+                print_result(
+                    json!({ "account": address, "state": acc, "version": version }),
+                    |_| {
+                        println!(
+                            "Latest account state is: \n \
+                             Account: {:#?}\n \
+                             State: {:#?}\n \
+                             Blockchain Version: {}\n",
+                            address, acc, version,
+                        )
+                    },
+                )
+            }
             Err(e) => report_error("Error getting latest account state", e),
         }
     }
@@ -127,25 +138,27 @@ impl Command for QueryCommandGetLatestAccountResources {
         "Get the latest annotated resources in an account"
     }
     fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
-        println!(">> Getting latest account state");
+        print_progress(">> Getting latest account state");
         match client.get_latest_account_resources(&params) {
-            Ok((Some(acc), version)) => println!(
-                "Latest account state is: \n \
-                 Account: {:#?}\n \
-                 State: {}\n \
-                 Blockchain Version: {}\n",
-                client
+            Ok((acc, version)) => {
+                let (address, _) = client
                     .get_account_address_from_parameter(params[1])
-                    .expect("Unable to parse account parameter"),
-                acc,
-                version,
-            ),
-            Ok((None, _version)) => println!(
-                "No Account found for {:#?}",
-                client
-                    .get_account_address_from_parameter(params[1])
-                    .expect("Unable to parse account parameter")
-            ),
+                    .expect("Unable to parse account parameter");
+                let state = acc.map(|acc| acc.to_string());
+                print_result(
+                    json!({ "account": address, "state": state, "version": version }),
+                    |_| match state {
+                        Some(state) => println!(
+                            "Latest account state is: \n \
+                             Account: {:#?}\n \
+                             State: {}\n \
+                             Blockchain Version: {}\n",
+                            address, state, version,
+                        ),
+                        None => println!("No Account found for {:#?}", address),
+                    },
+                )
+            }
             Err(e) => report_error("Error getting latest account state", e),
         }
     }
@@ -166,16 +179,16 @@ impl Command for QueryCommandGetTxnByAccountSeq {
          Optionally also fetch events emitted by this transaction."
     }
     fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
-        println!(">> Getting committed transaction by account and sequence number");
+        print_progress(">> Getting committed transaction by account and sequence number");
         match client.get_committed_txn_by_acc_seq(&params) {
-            Ok(txn_view) => {
+            Ok(txn_view) => print_result(txn_view, |txn_view| {
                 match txn_view {
                     Some(txn_view) => {
                         println!("Committed transaction: {:#?}", txn_view);
                     }
                     None => println!("Transaction not available"),
                 };
-            }
+            }),
             Err(e) => report_error(
                 "Error getting committed transaction by account and sequence number",
                 e,
@@ -199,16 +212,19 @@ impl Command for QueryCommandGetTxnByRange {
          Optionally also fetch events emitted by these transactions."
     }
     fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
-        println!(">> Getting committed transaction by range");
+        print_progress(">> Getting committed transaction by range");
         match client.get_committed_txn_by_range(&params) {
             Ok(comm_txns_and_events) => {
-                // Note that this should never panic because we shouldn't return items
-                // if the version wasn't able to be parsed in the first place
-                let mut cur_version = params[1].parse::<u64>().expect("Unable to parse version");
-                for txn_view in comm_txns_and_events {
-                    println!("Transaction at version {}: {:#?}", cur_version, txn_view,);
-                    cur_version += 1;
-                }
+                print_result(comm_txns_and_events, |comm_txns_and_events| {
+                    // Note that this should never panic because we shouldn't return items
+                    // if the version wasn't able to be parsed in the first place
+                    let mut cur_version =
+                        params[1].parse::<u64>().expect("Unable to parse version");
+                    for txn_view in comm_txns_and_events {
+                        println!("Transaction at version {}: {:#?}", cur_version, txn_view,);
+                        cur_version += 1;
+                    }
+                })
             }
             Err(e) => report_error("Error getting committed transactions by range", e),
         }
@@ -229,18 +245,21 @@ impl Command for QueryCommandGetEvent {
         "Get events by account and event type (sent|received)."
     }
     fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
-        println!(">> Getting events by account and event type.");
+        print_progress(">> Getting events by account and event type.");
         match client.get_events_by_account_and_type(&params) {
-            Ok((events, last_event_state)) => {
-                if events.is_empty() {
-                    println!("No events returned");
-                } else {
-                    for event in events {
-                        println!("{:?}", event);
+            Ok((events, last_event_state)) => print_result(
+                json!({ "events": events, "last_event_state": last_event_state }),
+                |_| {
+                    if events.is_empty() {
+                        println!("No events returned");
+                    } else {
+                        for event in &events {
+                            println!("{:?}", event);
+                        }
                     }
-                }
-                println!("Last event state: {:#?}", last_event_state);
-            }
+                    println!("Last event state: {:#?}", last_event_state);
+                },
+            ),
             Err(e) => report_error("Error getting events by access path", e),
         }
     }
@@ -3,7 +3,7 @@
 
 use crate::{
     client_proxy::ClientProxy,
-    commands::{blocking_cmd, report_error, Command},
+    commands::{blocking_cmd, print_progress, print_submission, report_error, Command},
 };
 
 /// Command to transfer coins between two accounts.
@@ -33,21 +33,10 @@ impl Command for TransferCommand {
             return;
         }
 
-        println!(">> Transferring");
+        print_progress(">> Transferring");
         let is_blocking = blocking_cmd(&params[0]);
         match client.transfer_coins(&params, is_blocking) {
-            Ok(index_and_seq) => {
-                if is_blocking {
-                    println!("Finished transaction!");
-                } else {
-                    println!("Transaction submitted to validator");
-                }
-                println!(
-                    "To query for transaction status, run: query txn_acc_seq {} {} \
-                     <fetch_events=true|false>",
-                    index_and_seq.account_index, index_and_seq.sequence_number
-                );
-            }
+            Ok(index_and_seq) => print_submission(client, index_and_seq, is_blocking),
             Err(e) => report_error("Failed to perform transaction", e),
         }
     }
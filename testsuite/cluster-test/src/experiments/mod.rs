@@ -10,6 +10,7 @@ mod performance_benchmark;
 mod performance_benchmark_three_region_simulation;
 mod reboot_random_validator;
 mod recovery_time;
+mod rolling_upgrade;
 mod twin_validator;
 mod versioning_test;
 
@@ -29,6 +30,7 @@ pub use performance_benchmark_three_region_simulation::{
 };
 pub use reboot_random_validator::{RebootRandomValidators, RebootRandomValidatorsParams};
 pub use recovery_time::{RecoveryTime, RecoveryTimeParams};
+pub use rolling_upgrade::{RollingUpgrade, RollingUpgradeParams};
 pub use twin_validator::{TwinValidators, TwinValidatorsParams};
 pub use versioning_test::{ValidatorVersioning, ValidatorVersioningParams};
 
@@ -140,6 +142,7 @@ pub fn get_experiment(name: &str, args: &[String], cluster: &Cluster) -> Box<dyn
     known_experiments.insert("generate_cpu_flamegraph", f::<CpuFlamegraphParams>());
     known_experiments.insert("versioning_testing", f::<ValidatorVersioningParams>());
     known_experiments.insert("compatibility_test", f::<CompatiblityTestParams>());
+    known_experiments.insert("rolling_upgrade", f::<RollingUpgradeParams>());
 
     let builder = known_experiments.get(name).expect("Experiment not found");
     builder(args, cluster)
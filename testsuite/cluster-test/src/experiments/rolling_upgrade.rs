@@ -0,0 +1,231 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+use crate::{
+    cluster::Cluster,
+    experiments::{
+        compatibility_test::update_batch_instance, Context, Experiment, ExperimentParam,
+    },
+    instance,
+    instance::Instance,
+    tx_emitter::EmitJobRequest,
+};
+use anyhow::{format_err, Result};
+use async_trait::async_trait;
+use futures::future::try_join_all;
+use libra_json_rpc_client::{
+    views::{BlockMetadata, TransactionView},
+    JsonRpcBatch, ResponseAsView,
+};
+use libra_logger::prelude::*;
+use std::{collections::HashSet, fmt, time::Duration};
+use structopt::StructOpt;
+
+#[derive(StructOpt, Debug)]
+pub struct RollingUpgradeParams {
+    #[structopt(
+        long,
+        help = "Number of validators to run the newer version, half of them if not specified"
+    )]
+    pub count: Option<usize>,
+    #[structopt(long, help = "Image tag of newer validator software")]
+    pub updated_image_tag: String,
+    #[structopt(
+        long,
+        default_value = "120",
+        help = "Duration of the traffic sent to the mixed version cluster"
+    )]
+    pub duration_secs: u64,
+    #[structopt(
+        long,
+        default_value = "200",
+        help = "Number of the latest committed transactions compared across validators"
+    )]
+    pub compared_txns: u64,
+}
+
+/// Runs a cluster where part of the validators run the newer version and the rest the current
+/// one, then checks that the cluster stays live and that all validators commit the same
+/// transactions with the same outputs.
+pub struct RollingUpgrade {
+    updated: Vec<Instance>,
+    validators: Vec<Instance>,
+    updated_image_tag: String,
+    duration: Duration,
+    compared_txns: u64,
+}
+
+impl ExperimentParam for RollingUpgradeParams {
+    type E = RollingUpgrade;
+    fn build(self, cluster: &Cluster) -> Self::E {
+        let num_validators = cluster.validator_instances().len();
+        let count = self.count.unwrap_or(num_validators / 2);
+        if count > num_validators || count == 0 {
+            panic!(
+                "Can not upgrade {} validators in cluster with {} instances",
+                count, num_validators
+            );
+        }
+        let (updated, _) = cluster.split_n_validators_random(count);
+        Self::E {
+            updated: updated.into_validator_instances(),
+            validators: cluster.validator_instances().to_vec(),
+            updated_image_tag: self.updated_image_tag,
+            duration: Duration::from_secs(self.duration_secs),
+            compared_txns: self.compared_txns,
+        }
+    }
+}
+
+#[async_trait]
+impl Experiment for RollingUpgrade {
+    fn affected_validators(&self) -> HashSet<String> {
+        instance::instancelist_to_set(&self.updated)
+    }
+
+    async fn run(&mut self, context: &mut Context<'_>) -> anyhow::Result<()> {
+        info!(
+            "1. Upgrading {} validators from {} to {}",
+            self.updated.len(),
+            context.current_tag,
+            self.updated_image_tag
+        );
+        update_batch_instance(context, &self.updated, self.updated_image_tag.clone()).await?;
+
+        info!("2. Sending traffic to the mixed version cluster");
+        let start_versions = latest_versions(&self.validators).await?;
+        let stats = context
+            .tx_emitter
+            .emit_txn_for(
+                self.duration,
+                EmitJobRequest::for_instances(
+                    self.validators.clone(),
+                    context.global_emit_job_request,
+                ),
+            )
+            .await?;
+        let committed = stats.committed;
+        context
+            .report
+            .report_txn_stats(self.to_string(), stats, self.duration);
+
+        info!("3. Checking liveness and commit divergence");
+        let mut failures = vec![];
+        if committed == 0 {
+            failures.push("no transaction was committed".to_string());
+        }
+        let end_versions = latest_versions(&self.validators).await?;
+        for ((instance, start), end) in self
+            .validators
+            .iter()
+            .zip(start_versions)
+            .zip(&end_versions)
+        {
+            if *end <= start {
+                failures.push(format!(
+                    "{} did not commit since version {}",
+                    instance, start
+                ));
+            }
+        }
+        let common_version = end_versions.iter().min().cloned().unwrap_or(0);
+        if let Some(divergence) = self.find_divergence(common_version).await? {
+            failures.push(divergence);
+        }
+
+        let verdict = if failures.is_empty() {
+            "PASS".to_string()
+        } else {
+            format!("FAIL ({})", failures.join(", "))
+        };
+        let result = format!(
+            "Rolling upgrade from {} to {}: {}",
+            context.current_tag, self.updated_image_tag, verdict
+        );
+        info!("{}", result);
+        context.report.report_text(result.clone());
+        context.report.report_metric(
+            self.to_string(),
+            "passed",
+            failures.is_empty() as u64 as f64,
+        );
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(format_err!("{}", result))
+        }
+    }
+
+    fn deadline(&self) -> Duration {
+        Duration::from_secs(5 * 60) + self.duration
+    }
+}
+
+impl RollingUpgrade {
+    /// Compares the transactions committed up to `version` by every validator against the ones
+    /// of the first validator, and describes the first mismatch, if any.
+    async fn find_divergence(&self, version: u64) -> Result<Option<String>> {
+        let start = (version + 1).saturating_sub(self.compared_txns);
+        let limit = version + 1 - start;
+        let futures: Vec<_> = self
+            .validators
+            .iter()
+            .map(|instance| transactions(instance, start, limit))
+            .collect();
+        let all_txns = try_join_all(futures).await?;
+
+        let (expected, rest) = match all_txns.split_first() {
+            Some(split) => split,
+            None => return Ok(None),
+        };
+        for (instance, txns) in self.validators[1..].iter().zip(rest) {
+            if txns.len() != expected.len() {
+                return Ok(Some(format!(
+                    "{} returned {} transactions up to version {} instead of {}",
+                    instance,
+                    txns.len(),
+                    version,
+                    expected.len()
+                )));
+            }
+            if let Some((_, expected_txn)) = txns.iter().zip(expected).find(|(a, b)| a != b) {
+                return Ok(Some(format!(
+                    "{} diverged from {} at version {}",
+                    instance, self.validators[0], expected_txn.version
+                )));
+            }
+        }
+        Ok(None)
+    }
+}
+
+async fn latest_versions(instances: &[Instance]) -> Result<Vec<u64>> {
+    let futures: Vec<_> = instances.iter().map(latest_version).collect();
+    try_join_all(futures).await
+}
+
+async fn latest_version(instance: &Instance) -> Result<u64> {
+    let mut batch = JsonRpcBatch::new();
+    batch.add_get_metadata_request(None);
+    let response = instance.json_rpc_client().execute(batch).await?.remove(0)?;
+    Ok(BlockMetadata::from_response(response)?.version)
+}
+
+async fn transactions(instance: &Instance, start: u64, limit: u64) -> Result<Vec<TransactionView>> {
+    let mut batch = JsonRpcBatch::new();
+    batch.add_get_transactions_request(start, limit, true);
+    let response = instance.json_rpc_client().execute(batch).await?.remove(0)?;
+    TransactionView::vec_from_response(response)
+}
+
+impl fmt::Display for RollingUpgrade {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Rolling upgrade [")?;
+        for instance in self.updated.iter() {
+            write!(f, "{}, ", instance)?;
+        }
+        write!(f, "] to {}", self.updated_image_tag)
+    }
+}
@@ -17,6 +17,7 @@ pub mod slack;
 pub mod stats;
 pub mod suite;
 pub mod tx_emitter;
+pub mod txn_mix;
 
 pub mod util {
     use std::time::{Duration, SystemTime};
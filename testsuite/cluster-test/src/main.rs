@@ -27,6 +27,7 @@ use cluster_test::{
     slack::SlackClient,
     suite::ExperimentSuite,
     tx_emitter::{AccountData, EmitJobRequest, EmitThreadParams, TxEmitter, TxStats},
+    txn_mix::{TpsRamp, TxnMix},
 };
 use futures::{
     future::{join_all, FutureExt},
@@ -89,6 +90,17 @@ struct Args {
         default_value = "60"
     )]
     duration: u64,
+    #[structopt(
+        long,
+        help = "Weighted transaction classes to emit, e.g. p2p=80,create_account=10,rotate_key=10",
+        default_value = "p2p"
+    )]
+    txn_mix: TxnMix,
+    #[structopt(
+        long,
+        help = "Target TPS steps for --emit-tx as tps:seconds pairs, e.g. 100:60,500:60"
+    )]
+    tps_ramp: Option<TpsRamp>,
 
     #[structopt(
         long,
@@ -288,6 +300,23 @@ fn parse_host_port(s: &str) -> Result<(String, u32, Option<u32>)> {
 }
 
 async fn emit_tx(cluster: &Cluster, args: &Args) -> Result<()> {
+    if let Some(ramp) = &args.tps_ramp {
+        let mut emitter = TxEmitter::new(cluster);
+        let steps = emitter
+            .emit_txn_ramp(
+                cluster.validator_instances().to_vec(),
+                ramp,
+                args.txn_mix.clone(),
+            )
+            .await?;
+        for ((tps, stats), (_, duration)) in steps.iter().zip(&ramp.steps) {
+            println!("Target {} TPS: {}", tps, stats.rate(*duration));
+            for (class, class_stats) in &stats.by_class {
+                println!("  {}: {}", class, class_stats.rate(*duration));
+            }
+        }
+        return Ok(());
+    }
     let accounts_per_client = args.accounts_per_client;
     let workers_per_ac = args.workers_per_ac;
     let thread_params = EmitThreadParams {
@@ -302,6 +331,7 @@ async fn emit_tx(cluster: &Cluster, args: &Args) -> Result<()> {
             accounts_per_client,
             workers_per_ac,
             thread_params,
+            mix: args.txn_mix.clone(),
         })
         .await
         .map_err(|e| format_err!("Failed to start emit job: {}", e))?;
@@ -318,6 +348,9 @@ async fn emit_tx(cluster: &Cluster, args: &Args) -> Result<()> {
     let stats = emitter.stop_job(job).await;
     println!("Total stats: {}", stats);
     println!("Average rate: {}", stats.rate(duration));
+    for (class, class_stats) in &stats.by_class {
+        println!("  {}: {}", class, class_stats.rate(duration));
+    }
     Ok(())
 }
 
@@ -489,6 +522,7 @@ impl ClusterTestRunner {
                 wait_millis: args.wait_millis,
                 wait_committed: !args.burst,
             },
+            mix: args.txn_mix.clone(),
         };
         let emit_to_validator =
             if cluster.fullnode_instances().len() < cluster.validator_instances().len() {
@@ -1,7 +1,7 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::tx_emitter::TxStats;
+use crate::{tx_emitter::TxStats, txn_mix::TxnClass};
 use serde::Serialize;
 use std::{fmt, time::Duration};
 
@@ -67,6 +67,40 @@ impl SuiteReport {
             "{} : {:.0} TPS, {:.1} ms latency, {:.1} ms p99 latency, {}",
             experiment, avg_tps, avg_latency_client, p99_latency, expired_text
         ));
+        for (class, class_stats) in &stats.by_class {
+            self.report_txn_class_stats(&experiment, *class, class_stats, window);
+        }
+    }
+
+    /// Reports the throughput and latency percentiles of a single class of a transaction mix,
+    /// so that regressions affecting only that class stand out.
+    fn report_txn_class_stats(
+        &mut self,
+        experiment: &str,
+        class: TxnClass,
+        stats: &TxStats,
+        window: Duration,
+    ) {
+        let rate = stats.rate(window);
+        let p50_latency = stats.latency_buckets.percentile(50, 100);
+        let p90_latency = stats.latency_buckets.percentile(90, 100);
+        let metric = |name: &str| format!("{}_{}", class, name);
+        self.report_metric(experiment, metric("avg_tps"), rate.committed as f64);
+        self.report_metric(experiment, metric("expired_txn"), stats.expired as f64);
+        self.report_metric(experiment, metric("avg_latency"), rate.latency as f64);
+        self.report_metric(experiment, metric("p50_latency"), p50_latency as f64);
+        self.report_metric(experiment, metric("p90_latency"), p90_latency as f64);
+        self.report_metric(experiment, metric("p99_latency"), rate.p99_latency as f64);
+        self.report_text(format!(
+            "  {} : {} TPS, {} ms latency, {} ms p50, {} ms p90, {} ms p99 latency, {} expired",
+            class,
+            rate.committed,
+            rate.latency,
+            p50_latency,
+            p90_latency,
+            rate.p99_latency,
+            stats.expired
+        ));
     }
 }
 
@@ -3,14 +3,21 @@
 
 #![forbid(unsafe_code)]
 
-use crate::{atomic_histogram::*, cluster::Cluster, instance::Instance};
+use crate::{
+    atomic_histogram::*,
+    cluster::Cluster,
+    instance::Instance,
+    txn_mix::{TpsRamp, TxnClass, TxnMix},
+};
 use std::{
+    collections::{BTreeMap, HashMap},
     fmt, slice,
     sync::Arc,
     time::{Duration, Instant},
 };
 
-use anyhow::{format_err, Result};
+use anyhow::{ensure, format_err, Result};
+use chrono::Utc;
 use itertools::zip;
 use libra_crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
@@ -23,9 +30,11 @@ use libra_types::{
     account_config::{self, testnet_dd_account_address, COIN1_NAME},
     chain_id::ChainId,
     transaction::{
-        authenticator::AuthenticationKey, helpers::create_user_txn, Script, TransactionPayload,
+        authenticator::AuthenticationKey, helpers::create_user_txn, Module, Script,
+        TransactionPayload,
     },
 };
+use move_core_types::identifier::Identifier;
 use rand::{
     prelude::ThreadRng,
     rngs::{OsRng, StdRng},
@@ -35,7 +44,9 @@ use rand::{
 use tokio::runtime::Handle;
 
 use futures::future::{try_join_all, FutureExt};
-use libra_json_rpc_client::JsonRpcAsyncClient;
+use libra_json_rpc_client::{
+    views::BlockMetadata, JsonRpcAsyncClient, JsonRpcBatch, ResponseAsView,
+};
 use libra_types::transaction::SignedTransaction;
 use std::{
     cmp::{max, min},
@@ -43,6 +54,7 @@ use std::{
     sync::atomic::{AtomicBool, AtomicU64, Ordering},
 };
 use tokio::{task::JoinHandle, time};
+use vm::file_format::empty_module;
 
 const MAX_TXN_BATCH_SIZE: usize = 100; // Max transactions per account in mempool
 
@@ -64,6 +76,8 @@ struct StatsAccumulator {
     expired: AtomicU64,
     latency: AtomicU64,
     latencies: Arc<AtomicHistogramAccumulator>,
+    // Stats of each class of the mix, only tracked for mixes of several classes
+    by_class: HashMap<TxnClass, StatsAccumulator>,
 }
 
 #[derive(Debug, Default)]
@@ -73,6 +87,7 @@ pub struct TxStats {
     pub expired: u64,
     pub latency: u64,
     pub latency_buckets: AtomicHistogramSnapshot,
+    pub by_class: BTreeMap<TxnClass, TxStats>,
 }
 
 #[derive(Debug, Default)]
@@ -105,6 +120,7 @@ pub struct EmitJobRequest {
     pub accounts_per_client: usize,
     pub workers_per_ac: Option<usize>,
     pub thread_params: EmitThreadParams,
+    pub mix: TxnMix,
 }
 
 impl EmitJobRequest {
@@ -122,10 +138,27 @@ impl EmitJobRequest {
                 accounts_per_client: 15,
                 workers_per_ac: None,
                 thread_params: EmitThreadParams::default(),
+                mix: TxnMix::default(),
             },
         }
     }
 
+    /// The number of workers sending transactions to each instance, each with its own shard of
+    /// `accounts_per_client` accounts.
+    pub fn workers_per_ac(&self) -> usize {
+        match self.workers_per_ac {
+            Some(x) => x,
+            None => {
+                let target_threads = 300;
+                // Trying to create somewhere between target_threads/2..target_threads threads
+                // We want to have equal numbers of threads for each AC, so that they are equally loaded
+                // Otherwise things like flamegrap/perf going to show different numbers depending on which AC is chosen
+                // Also limiting number of threads as max 10 per AC for use cases with very small number of nodes or use --peers
+                min(10, max(1, target_threads / self.instances.len()))
+            }
+        }
+    }
+
     pub fn fixed_tps_params(instance_count: usize, tps: u64) -> (usize, u64) {
         if tps < 1 {
             panic!("Target tps {} can not less than 1", tps)
@@ -145,6 +178,7 @@ impl EmitJobRequest {
                 wait_millis: wait_time,
                 wait_committed: true,
             },
+            mix: TxnMix::default(),
         }
     }
 }
@@ -190,17 +224,7 @@ impl TxEmitter {
     }
 
     pub async fn start_job(&mut self, req: EmitJobRequest) -> Result<EmitJob> {
-        let workers_per_ac = match req.workers_per_ac {
-            Some(x) => x,
-            None => {
-                let target_threads = 300;
-                // Trying to create somewhere between target_threads/2..target_threads threads
-                // We want to have equal numbers of threads for each AC, so that they are equally loaded
-                // Otherwise things like flamegrap/perf going to show different numbers depending on which AC is chosen
-                // Also limiting number of threads as max 10 per AC for use cases with very small number of nodes or use --peers
-                min(10, max(1, target_threads / req.instances.len()))
-            }
-        };
+        let workers_per_ac = req.workers_per_ac();
         let num_clients = req.instances.len() * workers_per_ac;
         info!(
             "Will use {} workers per AC with total {} AC clients",
@@ -217,8 +241,29 @@ impl TxEmitter {
         let all_addresses: Vec<_> = all_accounts.iter().map(|d| d.address).collect();
         let all_addresses = Arc::new(all_addresses);
         let mut all_accounts = all_accounts.into_iter();
+        // Child VASP accounts can not create accounts, so every worker gets a parent VASP account
+        // to send the account creations of the mix.
+        let creators: Vec<_> = if req.mix.contains(TxnClass::AccountCreation) {
+            let mut libra_root_account = self
+                .load_libra_root_account(self.pick_mint_instance(&req.instances))
+                .await?;
+            create_seed_accounts(
+                &mut libra_root_account,
+                num_clients,
+                100,
+                self.pick_mint_client(&req.instances),
+            )
+            .await
+            .map_err(|e| format_err!("Failed to create account creators: {}", e))?
+            .into_iter()
+            .map(Some)
+            .collect()
+        } else {
+            vec![None; num_clients]
+        };
+        let mut creators = creators.into_iter();
         let stop = Arc::new(AtomicBool::new(false));
-        let stats = Arc::new(StatsAccumulator::default());
+        let stats = Arc::new(StatsAccumulator::for_mix(&req.mix));
         let tokio_handle = Handle::current();
         for instance in &req.instances {
             for _ in 0..workers_per_ac {
@@ -230,10 +275,12 @@ impl TxEmitter {
                 let stats = Arc::clone(&stats);
                 let worker = SubmissionWorker {
                     accounts,
+                    creator: creators.next().unwrap_or(None),
                     client,
                     all_addresses,
                     stop,
                     params,
+                    mix: req.mix.clone(),
                     stats,
                 };
                 let join_handle = tokio_handle.spawn(worker.run().boxed());
@@ -286,6 +333,56 @@ impl TxEmitter {
         })
     }
 
+    pub async fn load_tc_account(&self, instance: &Instance) -> Result<AccountData> {
+        let client = instance.json_rpc_client();
+        let address = account_config::treasury_compliance_account_address();
+        let sequence_number = query_sequence_numbers(&client, &[address])
+            .await
+            .map_err(|e| {
+                format_err!(
+                    "query_sequence_numbers on {:?} for TC account failed: {}",
+                    client,
+                    e
+                )
+            })?[0];
+        Ok(AccountData {
+            address,
+            key_pair: self.mint_key_pair.clone(),
+            sequence_number,
+        })
+    }
+
+    /// Tops up the faucet account up to `amount` coins with a tiered mint from the TC account.
+    /// A tiered mint can't go over the highest tier of the DD, so this fails if the faucet account
+    /// is still short of `amount` after the mint.
+    async fn top_up_faucet_account(&self, instance: &Instance, amount: u64) -> Result<()> {
+        let mut client = instance.json_rpc_client();
+        let address = testnet_dd_account_address();
+        let balance = query_balance(&client, address).await?;
+        if balance >= amount {
+            return Ok(());
+        }
+        let mint_amount = min(amount - balance, DD_TIERS[DD_TIERS.len() - 1]);
+        let tier_index = dd_tier_index(mint_amount).expect("Mint amount is under the highest tier");
+        info!(
+            "Minting {} coins into faucet account at tier {}",
+            mint_amount, tier_index
+        );
+        let mut tc_account = self.load_tc_account(instance).await?;
+        let sliding_nonce = query_next_sliding_nonce(&client, tc_account.address).await?;
+        let mint_txn =
+            gen_tiered_mint_request(&mut tc_account, sliding_nonce, mint_amount, tier_index);
+        execute_and_wait_transactions(&mut client, &mut tc_account, vec![mint_txn]).await?;
+        let balance = query_balance(&client, address).await?;
+        ensure!(
+            balance >= amount,
+            "Faucet account has {} coins after a tiered mint, {} are needed",
+            balance,
+            amount
+        );
+        Ok(())
+    }
+
     pub async fn mint_accounts(
         &mut self,
         req: &EmitJobRequest,
@@ -297,35 +394,32 @@ impl TxEmitter {
         }
         let num_accounts = requested_accounts - self.accounts.len(); // Only minting extra accounts
         info!("Minting additional {} accounts", num_accounts);
+        // Every worker of a job gets its own shard of accounts, which is created by a seed account
+        // of its own, so the shards are created concurrently.
+        let num_seed_accounts = min(num_accounts, req.instances.len() * req.workers_per_ac());
+        let accounts_per_seed = (num_accounts + num_seed_accounts - 1) / num_seed_accounts;
+        let libra_per_seed = LIBRA_PER_NEW_ACCOUNT * accounts_per_seed as u64;
+        self.top_up_faucet_account(
+            self.pick_mint_instance(&req.instances),
+            libra_per_seed * num_seed_accounts as u64,
+        )
+        .await
+        .map_err(|e| format_err!("Failed to mint into faucet account: {}", e))?;
         let mut faucet_account = self
             .load_faucet_account(self.pick_mint_instance(&req.instances))
             .await?;
         let mut libra_root_account = self
             .load_libra_root_account(self.pick_mint_instance(&req.instances))
             .await?;
-        let mint_txn = gen_mint_request(
-            &mut faucet_account,
-            LIBRA_PER_NEW_ACCOUNT * num_accounts as u64,
-        );
-        execute_and_wait_transactions(
-            &mut self.pick_mint_client(&req.instances),
-            &mut faucet_account,
-            vec![mint_txn],
-        )
-        .await
-        .map_err(|e| format_err!("Failed to mint into faucet account: {}", e))?;
         let seed_accounts = create_seed_accounts(
             &mut libra_root_account,
-            req.instances.len(),
+            num_seed_accounts,
             100,
             self.pick_mint_client(&req.instances),
         )
         .await
         .map_err(|e| format_err!("Failed to create seed accounts: {}", e))?;
-        info!("Completed creating seed accounts");
-        let libra_per_seed =
-            (LIBRA_PER_NEW_ACCOUNT * num_accounts as u64) / req.instances.len() as u64;
-        // Create seed accounts with which we can create actual accounts concurrently
+        info!("Completed creating {} seed accounts", num_seed_accounts);
         mint_to_new_accounts(
             &mut faucet_account,
             &seed_accounts,
@@ -336,22 +430,19 @@ impl TxEmitter {
         .await
         .map_err(|e| format_err!("Failed to mint seed_accounts: {}", e))?;
         info!("Completed minting seed accounts");
-        // For each seed account, create a future and transfer libra from that seed account to new accounts
+        // For each seed account, create a future and transfer libra from that seed account to new
+        // accounts, through the instance its shard is going to be sent to
         let account_futures = seed_accounts
             .into_iter()
             .enumerate()
             .map(|(i, seed_account)| {
-                // Spawn new threads
-                let instance = req.instances[i].clone();
-                let num_new_accounts =
-                    (num_accounts + req.instances.len() - 1) / req.instances.len();
-                let client = instance.json_rpc_client();
+                let instance = &req.instances[i * req.instances.len() / num_seed_accounts];
                 create_new_accounts(
                     seed_account,
-                    num_new_accounts,
+                    accounts_per_seed,
                     LIBRA_PER_NEW_ACCOUNT,
                     20,
-                    client,
+                    instance.json_rpc_client(),
                 )
             });
         let mut minted_accounts = try_join_all(account_futures)
@@ -398,6 +489,27 @@ impl TxEmitter {
         Ok(stats)
     }
 
+    /// Sends the `mix` of transactions to `instances` at each target TPS of the `ramp` in turn,
+    /// and returns the stats of every step.
+    pub async fn emit_txn_ramp(
+        &mut self,
+        instances: Vec<Instance>,
+        ramp: &TpsRamp,
+        mix: TxnMix,
+    ) -> Result<Vec<(u64, TxStats)>> {
+        let mut results = vec![];
+        for (tps, duration) in &ramp.steps {
+            info!("Emitting {} TPS for {:?}", tps, duration);
+            let req = EmitJobRequest {
+                mix: mix.clone(),
+                ..EmitJobRequest::fixed_tps(instances.clone(), *tps)
+            };
+            let stats = self.emit_txn_for(*duration, req).await?;
+            results.push((*tps, stats));
+        }
+        Ok(results)
+    }
+
     pub async fn query_sequence_numbers(
         &self,
         instance: &Instance,
@@ -421,15 +533,17 @@ struct Worker {
 
 struct SubmissionWorker {
     accounts: Vec<AccountData>,
+    // Sender of the account creations of the mix
+    creator: Option<AccountData>,
     client: JsonRpcAsyncClient,
     all_addresses: Arc<Vec<AccountAddress>>,
     stop: Arc<AtomicBool>,
     params: EmitThreadParams,
+    mix: TxnMix,
     stats: Arc<StatsAccumulator>,
 }
 
 impl SubmissionWorker {
-    #[allow(clippy::collapsible_if)]
     async fn run(mut self) -> Vec<AccountData> {
         let wait = Duration::from_millis(self.params.wait_millis);
         while !self.stop.load(Ordering::Relaxed) {
@@ -438,56 +552,64 @@ impl SubmissionWorker {
             let start_time = Instant::now();
             let wait_util = start_time + wait;
             let mut tx_offset_time = 0u64;
-            for request in requests {
+            let mut sender_classes = HashMap::new();
+            let mut submitted = HashMap::new();
+            for (class, request) in requests {
                 let cur_time = Instant::now();
                 tx_offset_time += (cur_time - start_time).as_millis() as u64;
                 self.stats.submitted.fetch_add(1, Ordering::Relaxed);
+                if let Some(stats) = self.stats.by_class.get(&class) {
+                    stats.submitted.fetch_add(1, Ordering::Relaxed);
+                }
+                sender_classes.insert(request.sender(), class);
+                *submitted.entry(class).or_insert(0u64) += 1;
                 let resp = self.client.submit_transaction(request).await;
                 if let Err(e) = resp {
                     warn!("[{:?}] Failed to submit request: {:?}", self.client, e);
                 }
             }
             if self.params.wait_committed {
+                let mut expired = HashMap::new();
                 if let Err(uncommitted) =
                     wait_for_accounts_sequence(&self.client, &mut self.accounts).await
                 {
-                    let end_time = (Instant::now() - start_time).as_millis() as u64;
-                    let num_committed = (num_requests - uncommitted.len()) as u64;
-                    let latency = end_time - tx_offset_time / num_requests as u64;
-                    self.stats
-                        .committed
-                        .fetch_add(num_committed, Ordering::Relaxed);
-                    self.stats
-                        .expired
-                        .fetch_add(uncommitted.len() as u64, Ordering::Relaxed);
-                    self.stats.latency.fetch_add(
-                        // To avoid negative result caused by uncommitted tx occur
-                        // Simplified from:
-                        // end_time * num_committed - (tx_offset_time/num_requests) * num_committed
-                        // to
-                        // (end_time - tx_offset_time / num_requests) * num_committed
-                        latency * num_committed as u64,
-                        Ordering::Relaxed,
-                    );
-                    self.stats
-                        .latencies
-                        .record_data_point(latency, num_committed);
                     info!(
                         "[{:?}] Transactions were not committed before expiration: {:?}",
                         self.client, uncommitted
                     );
-                } else {
-                    let end_time = (Instant::now() - start_time).as_millis() as u64;
-                    let latency = end_time - tx_offset_time / num_requests as u64;
-                    self.stats
-                        .committed
-                        .fetch_add(num_requests as u64, Ordering::Relaxed);
-                    self.stats
-                        .latency
-                        .fetch_add(latency * num_requests as u64, Ordering::Relaxed);
-                    self.stats
-                        .latencies
-                        .record_data_point(latency, num_requests as u64);
+                    for (address, _) in uncommitted {
+                        if let Some(class) = sender_classes.get(&address) {
+                            *expired.entry(*class).or_insert(0u64) += 1;
+                        }
+                    }
+                }
+                // The creator sends all the account creations of the batch
+                if let Some(creator) = &mut self.creator {
+                    let expected_sequence_number = creator.sequence_number;
+                    if wait_for_accounts_sequence(&self.client, slice::from_mut(creator))
+                        .await
+                        .is_err()
+                    {
+                        *expired.entry(TxnClass::AccountCreation).or_insert(0u64) +=
+                            expected_sequence_number - creator.sequence_number;
+                    }
+                }
+                let end_time = (Instant::now() - start_time).as_millis() as u64;
+                // To avoid negative result caused by uncommitted tx occur, the latency is
+                // computed as end_time - tx_offset_time / num_requests and not as
+                // (end_time * num_committed - tx_offset_time / num_requests * num_committed)
+                // / num_committed
+                let latency = end_time - tx_offset_time / num_requests as u64;
+                let total_expired = expired.values().sum::<u64>();
+                self.stats.record_batch(
+                    num_requests as u64 - total_expired,
+                    total_expired,
+                    latency,
+                );
+                for (class, stats) in &self.stats.by_class {
+                    let submitted = submitted.get(class).cloned().unwrap_or(0);
+                    let expired = expired.get(class).cloned().unwrap_or(0);
+                    stats.record_batch(submitted - expired, expired, latency);
                 }
             }
             let now = Instant::now();
@@ -498,7 +620,7 @@ impl SubmissionWorker {
         self.accounts
     }
 
-    fn gen_requests(&mut self) -> Vec<SignedTransaction> {
+    fn gen_requests(&mut self) -> Vec<(TxnClass, SignedTransaction)> {
         let mut rng = ThreadRng::default();
         let batch_size = max(MAX_TXN_BATCH_SIZE, self.accounts.len());
         let accounts = self
@@ -507,23 +629,52 @@ impl SubmissionWorker {
             .choose_multiple(&mut rng, batch_size);
         let mut requests = Vec::with_capacity(accounts.len());
         for sender in accounts {
-            let receiver = self
-                .all_addresses
-                .choose(&mut rng)
-                .expect("all_addresses can't be empty");
-            let request = gen_transfer_txn_request(sender, receiver, 1);
-            requests.push(request);
+            let class = self.mix.pick(&mut rng);
+            let request = match class {
+                TxnClass::P2p => {
+                    let receiver = self
+                        .all_addresses
+                        .choose(&mut rng)
+                        .expect("all_addresses can't be empty");
+                    gen_transfer_txn_request(sender, receiver, 1)
+                }
+                TxnClass::AccountCreation => {
+                    let creator = self
+                        .creator
+                        .as_mut()
+                        .expect("Workers sending account creations have a creator");
+                    let new_account = gen_random_accounts(1).remove(0);
+                    gen_create_child_txn_request(
+                        creator,
+                        &new_account.address,
+                        new_account.auth_key_prefix(),
+                        0,
+                    )
+                }
+                TxnClass::ModulePublish => gen_publish_module_request(sender),
+                TxnClass::KeyRotation => gen_rotate_key_request(sender),
+            };
+            requests.push((class, request));
         }
         requests
     }
 }
 
+/// Waits for the transactions sent by `accounts` to be committed. The ones still uncommitted after
+/// `TXN_MAX_WAIT` are given up on once the ledger is past their expiration, so they can't be
+/// committed anymore: the sequence numbers of their senders are then reset to the ones on chain
+/// and the senders are returned along with them.
 async fn wait_for_accounts_sequence(
     client: &JsonRpcAsyncClient,
     accounts: &mut [AccountData],
 ) -> Result<(), Vec<(AccountAddress, u64)>> {
+    // The transactions of `accounts` were all signed before, so they expire before this
+    let expiration_timestamp_usecs =
+        (Utc::now().timestamp() + TXN_EXPIRATION_SECONDS) as u64 * 1_000_000;
     let deadline = Instant::now() + TXN_MAX_WAIT;
     let addresses: Vec<_> = accounts.iter().map(|d| d.address).collect();
+    // Whether the ledger was past the expiration before the last query of the sequence numbers
+    let mut ledger_expired = false;
     loop {
         match query_sequence_numbers(client, &addresses).await {
             Err(e) => {
@@ -537,17 +688,33 @@ async fn wait_for_accounts_sequence(
                 if is_sequence_equal(accounts, &sequence_numbers) {
                     break;
                 }
-                let mut uncommitted = vec![];
-                if Instant::now() > deadline {
+                let now = Instant::now();
+                if ledger_expired || now > deadline + TXN_MAX_WAIT {
+                    let mut uncommitted = vec![];
                     for (account, sequence_number) in zip(accounts, &sequence_numbers) {
                         if account.sequence_number != *sequence_number {
                             warn!("Wait deadline exceeded for account {}, expected sequence {}, got from server: {}", account.address, account.sequence_number, sequence_number);
                             uncommitted.push((account.address, *sequence_number));
-                            account.sequence_number = *sequence_number;
+                            // Without the ledger past the expiration, the transactions may still
+                            // be committed, so their sequence numbers can't be reused yet
+                            if ledger_expired {
+                                account.sequence_number = *sequence_number;
+                            }
                         }
                     }
                     return Err(uncommitted);
                 }
+                if now > deadline {
+                    match query_ledger_timestamp_usecs(client).await {
+                        Ok(timestamp_usecs) => {
+                            ledger_expired = timestamp_usecs > expiration_timestamp_usecs
+                        }
+                        Err(e) => info!(
+                            "Failed to query ledger timestamp for instance {:?} : {:?}",
+                            client, e
+                        ),
+                    }
+                }
             }
         }
         time::delay_for(Duration::from_millis(100)).await;
@@ -585,20 +752,72 @@ async fn query_sequence_numbers(
     Ok(result)
 }
 
+async fn query_balance(client: &JsonRpcAsyncClient, address: AccountAddress) -> Result<u64> {
+    let account = client
+        .get_accounts_state(&[address])
+        .await?
+        .remove(0)
+        .ok_or_else(|| format_err!("account {} does not exist", address))?;
+    Ok(account
+        .balances
+        .iter()
+        .find(|balance| balance.currency == COIN1_NAME)
+        .map_or(0, |balance| balance.amount))
+}
+
+async fn query_next_sliding_nonce(
+    client: &JsonRpcAsyncClient,
+    address: AccountAddress,
+) -> Result<u64> {
+    Ok(client
+        .get_accounts_state(&[address])
+        .await?
+        .remove(0)
+        .ok_or_else(|| format_err!("account {} does not exist", address))?
+        .sliding_nonce
+        .ok_or_else(|| format_err!("account {} has no sliding nonce", address))?
+        .next_nonce)
+}
+
+async fn query_ledger_timestamp_usecs(client: &JsonRpcAsyncClient) -> Result<u64> {
+    let mut batch = JsonRpcBatch::new();
+    batch.add_get_metadata_request(None);
+    let response = client.execute(batch).await?.remove(0)?;
+    Ok(BlockMetadata::from_response(response)?.timestamp)
+}
+
 const MAX_GAS_AMOUNT: u64 = 1_000_000;
 const GAS_UNIT_PRICE: u64 = 0;
 const GAS_CURRENCY_CODE: &str = COIN1_NAME;
 const TXN_EXPIRATION_SECONDS: i64 = 50;
 const TXN_MAX_WAIT: Duration = Duration::from_secs(TXN_EXPIRATION_SECONDS as u64 + 30);
 const LIBRA_PER_NEW_ACCOUNT: u64 = 1_000_000;
+/// The default tiers of a DD, from `DesignatedDealer.move`: the coins minted to a DD at
+/// `tier_index` over a day can't go over `DD_TIERS[tier_index]`.
+const DD_TIERS: [u64; 4] = [500_000, 5_000_000, 50_000_000, 500_000_000];
+
+/// The lowest tier of a DD a mint of `amount` coins fits in.
+fn dd_tier_index(amount: u64) -> Option<u64> {
+    DD_TIERS
+        .iter()
+        .position(|tier| amount <= *tier)
+        .map(|index| index as u64)
+}
 
 fn gen_submit_transaction_request(
     script: Script,
     sender_account: &mut AccountData,
+) -> SignedTransaction {
+    gen_submit_payload_request(TransactionPayload::Script(script), sender_account)
+}
+
+fn gen_submit_payload_request(
+    payload: TransactionPayload,
+    sender_account: &mut AccountData,
 ) -> SignedTransaction {
     let transaction = create_user_txn(
         &sender_account.key_pair,
-        TransactionPayload::Script(script),
+        payload,
         sender_account.address,
         sender_account.sequence_number,
         MAX_GAS_AMOUNT,
@@ -624,6 +843,24 @@ fn gen_mint_request(faucet_account: &mut AccountData, num_coins: u64) -> SignedT
     )
 }
 
+fn gen_tiered_mint_request(
+    tc_account: &mut AccountData,
+    sliding_nonce: u64,
+    num_coins: u64,
+    tier_index: u64,
+) -> SignedTransaction {
+    gen_submit_transaction_request(
+        transaction_builder::encode_tiered_mint_script(
+            account_config::coin1_tag(),
+            sliding_nonce,
+            testnet_dd_account_address(),
+            num_coins,
+            tier_index,
+        ),
+        tc_account,
+    )
+}
+
 fn gen_transfer_txn_request(
     sender: &mut AccountData,
     receiver: &AccountAddress,
@@ -641,6 +878,32 @@ fn gen_transfer_txn_request(
     )
 }
 
+/// Publishes an empty module, named after the sequence number of the transaction so that every
+/// publish of an account is a new module.
+fn gen_publish_module_request(sender: &mut AccountData) -> SignedTransaction {
+    let mut module = empty_module();
+    module.address_identifiers[0] = sender.address;
+    module.identifiers[0] = Identifier::new(format!("M{}", sender.sequence_number))
+        .expect("Module names are valid identifiers");
+    let mut code = vec![];
+    module
+        .freeze()
+        .expect("The empty module is valid")
+        .serialize(&mut code)
+        .expect("Failed to serialize module");
+    gen_submit_payload_request(TransactionPayload::Module(Module::new(code)), sender)
+}
+
+/// Rotates the authentication key of `sender` to the one it already has, which exercises key
+/// rotation while keeping the account usable.
+fn gen_rotate_key_request(sender: &mut AccountData) -> SignedTransaction {
+    let auth_key = AuthenticationKey::ed25519(&sender.key_pair.public_key);
+    gen_submit_transaction_request(
+        transaction_builder::encode_rotate_authentication_key_script(auth_key.to_vec()),
+        sender,
+    )
+}
+
 fn gen_create_child_txn_request(
     sender: &mut AccountData,
     receiver: &AccountAddress,
@@ -880,6 +1143,30 @@ impl AccountData {
 }
 
 impl StatsAccumulator {
+    fn for_mix(mix: &TxnMix) -> Self {
+        let classes: Vec<_> = mix.classes().collect();
+        let by_class = if classes.len() > 1 {
+            classes
+                .into_iter()
+                .map(|class| (class, StatsAccumulator::default()))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+        Self {
+            by_class,
+            ..Self::default()
+        }
+    }
+
+    fn record_batch(&self, committed: u64, expired: u64, latency: u64) {
+        self.committed.fetch_add(committed, Ordering::Relaxed);
+        self.expired.fetch_add(expired, Ordering::Relaxed);
+        self.latency
+            .fetch_add(latency * committed, Ordering::Relaxed);
+        self.latencies.record_data_point(latency, committed);
+    }
+
     pub fn accumulate(&self) -> TxStats {
         TxStats {
             submitted: self.submitted.load(Ordering::Relaxed),
@@ -887,6 +1174,11 @@ impl StatsAccumulator {
             expired: self.expired.load(Ordering::Relaxed),
             latency: self.latency.load(Ordering::Relaxed),
             latency_buckets: self.latencies.snapshot(),
+            by_class: self
+                .by_class
+                .iter()
+                .map(|(class, stats)| (*class, stats.accumulate()))
+                .collect(),
         }
     }
 }
@@ -917,6 +1209,14 @@ impl Sub for &TxStats {
             expired: self.expired - other.expired,
             latency: self.latency - other.latency,
             latency_buckets: &self.latency_buckets - &other.latency_buckets,
+            by_class: self
+                .by_class
+                .iter()
+                .map(|(class, stats)| match other.by_class.get(class) {
+                    Some(other) => (*class, stats - other),
+                    None => (*class, stats - &TxStats::default()),
+                })
+                .collect(),
         }
     }
 }
@@ -943,7 +1243,7 @@ impl fmt::Display for TxStatsRate {
 
 #[cfg(test)]
 mod test {
-    use crate::tx_emitter::EmitJobRequest;
+    use crate::tx_emitter::{dd_tier_index, EmitJobRequest};
 
     #[test]
     pub fn test_fixed_tps_params() {
@@ -957,4 +1257,12 @@ mod test {
         assert_eq!(num_workers, 2usize);
         assert_eq!(wait_time, 2000u64);
     }
+    #[test]
+    pub fn test_dd_tier_index() {
+        assert_eq!(dd_tier_index(1), Some(0));
+        assert_eq!(dd_tier_index(500_000), Some(0));
+        assert_eq!(dd_tier_index(500_001), Some(1));
+        assert_eq!(dd_tier_index(500_000_000), Some(3));
+        assert_eq!(dd_tier_index(500_000_001), None);
+    }
 }
@@ -0,0 +1,199 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+use anyhow::{bail, format_err, Error, Result};
+use rand::Rng;
+use std::{fmt, str::FromStr, time::Duration};
+
+/// The kinds of transactions the tx emitter can send.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum TxnClass {
+    P2p,
+    AccountCreation,
+    ModulePublish,
+    KeyRotation,
+}
+
+impl TxnClass {
+    pub fn name(self) -> &'static str {
+        match self {
+            TxnClass::P2p => "p2p",
+            TxnClass::AccountCreation => "create_account",
+            TxnClass::ModulePublish => "publish_module",
+            TxnClass::KeyRotation => "rotate_key",
+        }
+    }
+}
+
+impl fmt::Display for TxnClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for TxnClass {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "p2p" => TxnClass::P2p,
+            "create_account" => TxnClass::AccountCreation,
+            "publish_module" => TxnClass::ModulePublish,
+            "rotate_key" => TxnClass::KeyRotation,
+            _ => bail!("Unknown transaction class {}", s),
+        })
+    }
+}
+
+/// Weighted transaction classes, parsed from `class=weight` pairs separated by commas, e.g.
+/// `p2p=80,create_account=10,rotate_key=10`. A class without a weight has a weight of 1.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TxnMix {
+    weights: Vec<(TxnClass, u32)>,
+}
+
+impl TxnMix {
+    pub fn new(weights: Vec<(TxnClass, u32)>) -> Result<Self> {
+        if weights.iter().all(|(_, weight)| *weight == 0) {
+            bail!("A transaction mix needs a class with a positive weight");
+        }
+        Ok(Self { weights })
+    }
+
+    pub fn classes(&self) -> impl Iterator<Item = TxnClass> + '_ {
+        self.weights
+            .iter()
+            .filter(|(_, weight)| *weight > 0)
+            .map(|(class, _)| *class)
+    }
+
+    pub fn contains(&self, class: TxnClass) -> bool {
+        self.classes().any(|c| c == class)
+    }
+
+    /// Picks a class with a probability proportional to its weight.
+    pub fn pick<R: Rng>(&self, rng: &mut R) -> TxnClass {
+        let total: u32 = self.weights.iter().map(|(_, weight)| weight).sum();
+        let mut point = rng.gen_range(0, total);
+        for (class, weight) in &self.weights {
+            if point < *weight {
+                return *class;
+            }
+            point -= weight;
+        }
+        unreachable!("The picked point is below the total weight")
+    }
+}
+
+impl Default for TxnMix {
+    fn default() -> Self {
+        Self {
+            weights: vec![(TxnClass::P2p, 1)],
+        }
+    }
+}
+
+impl FromStr for TxnMix {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let weights = s
+            .split(',')
+            .map(|entry| {
+                let mut parts = entry.trim().splitn(2, '=');
+                let class = parts.next().unwrap_or_default().parse()?;
+                let weight = match parts.next() {
+                    Some(weight) => weight
+                        .parse()
+                        .map_err(|e| format_err!("Invalid weight in {}: {}", entry, e))?,
+                    None => 1,
+                };
+                Ok((class, weight))
+            })
+            .collect::<Result<_>>()?;
+        Self::new(weights)
+    }
+}
+
+/// Steps of target TPS, each held for a duration, parsed from `tps:seconds` pairs separated by
+/// commas, e.g. `100:60,500:60,1000:120`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TpsRamp {
+    pub steps: Vec<(u64, Duration)>,
+}
+
+impl FromStr for TpsRamp {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let steps = s
+            .split(',')
+            .map(|step| {
+                let mut parts = step.trim().splitn(2, ':');
+                let (tps, secs) = match (parts.next(), parts.next()) {
+                    (Some(tps), Some(secs)) => (tps, secs),
+                    _ => bail!("Expected tps:seconds, got {}", step),
+                };
+                let tps: u64 = tps
+                    .parse()
+                    .map_err(|e| format_err!("Invalid tps in {}: {}", step, e))?;
+                let secs = secs
+                    .parse()
+                    .map_err(|e| format_err!("Invalid duration in {}: {}", step, e))?;
+                if tps == 0 {
+                    bail!("Target tps can not be 0 in {}", step);
+                }
+                Ok((tps, Duration::from_secs(secs)))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { steps })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_parse_mix() {
+        let mix: TxnMix = "p2p=8, create_account=2,rotate_key".parse().unwrap();
+        assert_eq!(
+            mix,
+            TxnMix::new(vec![
+                (TxnClass::P2p, 8),
+                (TxnClass::AccountCreation, 2),
+                (TxnClass::KeyRotation, 1),
+            ])
+            .unwrap()
+        );
+        assert!("p2p=0".parse::<TxnMix>().is_err());
+        assert!("transfer=1".parse::<TxnMix>().is_err());
+    }
+
+    #[test]
+    fn test_pick_respects_weights() {
+        let mix: TxnMix = "p2p=3,publish_module=0,rotate_key=1".parse().unwrap();
+        let mut rng = StdRng::from_seed([0u8; 32]);
+        let picks: Vec<_> = (0..1000).map(|_| mix.pick(&mut rng)).collect();
+        assert!(!picks.contains(&TxnClass::ModulePublish));
+        let p2p = picks.iter().filter(|c| **c == TxnClass::P2p).count();
+        assert!(p2p > 650 && p2p < 850);
+    }
+
+    #[test]
+    fn test_parse_ramp() {
+        let ramp: TpsRamp = "10:60,100:30".parse().unwrap();
+        assert_eq!(
+            ramp.steps,
+            vec![
+                (10, Duration::from_secs(60)),
+                (100, Duration::from_secs(30))
+            ]
+        );
+        assert!("10".parse::<TpsRamp>().is_err());
+        assert!("0:10".parse::<TpsRamp>().is_err());
+    }
+}
@@ -10,7 +10,7 @@ use structopt::{clap::arg_enum, StructOpt};
 
 /// Consensus messages.
 mod consensus;
-/// Libra transactions.
+/// Libra transactions, along with the ledger infos and account resources clients read.
 mod libra;
 /// Analyze Serde formats to detect certain patterns.
 mod linter;
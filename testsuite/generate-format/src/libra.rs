@@ -8,7 +8,7 @@ use libra_crypto::{
     traits::{SigningKey, Uniform},
 };
 use libra_crypto_derive::{CryptoHasher, LCSCryptoHash};
-use libra_types::{contract_event, event, transaction, write_set};
+use libra_types::{account_config, contract_event, event, ledger_info, transaction, write_set};
 use move_core_types::language_storage;
 use rand::{rngs::StdRng, SeedableRng};
 use serde::{Deserialize, Serialize};
@@ -63,5 +63,10 @@ pub fn get_registry() -> Result<Registry> {
     tracer.trace_type::<transaction::TransactionPayload>(&samples)?;
     tracer.trace_type::<transaction::authenticator::TransactionAuthenticator>(&samples)?;
     tracer.trace_type::<write_set::WriteOp>(&samples)?;
+
+    // 3. Trace the data clients read from the ledger and need to deserialize.
+    tracer.trace_type::<ledger_info::LedgerInfoWithSignatures>(&samples)?;
+    tracer.trace_type::<account_config::AccountResource>(&samples)?;
+    tracer.trace_type::<account_config::BalanceResource>(&samples)?;
     tracer.registry()
 }
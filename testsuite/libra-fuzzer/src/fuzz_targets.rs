@@ -28,6 +28,7 @@ mod network_noise_responder;
 //mod storage_save_blocks;
 mod storage_schema_decode;
 mod vm_value;
+mod x25519_public_key;
 
 static ALL_TARGETS: Lazy<BTreeMap<&'static str, Box<dyn FuzzTargetImpl>>> = Lazy::new(|| {
     let targets: Vec<Box<dyn FuzzTargetImpl>> = vec![
@@ -42,6 +43,7 @@ static ALL_TARGETS: Lazy<BTreeMap<&'static str, Box<dyn FuzzTargetImpl>>> = Lazy
         //        Box::new(storage_save_blocks::StorageSaveBlocks::default()),
         Box::new(storage_schema_decode::StorageSchemaDecode::default()),
         Box::new(vm_value::ValueTarget::default()),
+        Box::new(x25519_public_key::X25519PublicKeyValidation::default()),
     ];
     targets
         .into_iter()
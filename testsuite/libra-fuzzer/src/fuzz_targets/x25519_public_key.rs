@@ -0,0 +1,43 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::FuzzTargetImpl;
+use libra_crypto::{test_utils::TEST_SEED, x25519, Uniform};
+use libra_proptest_helpers::ValueGenerator;
+use proptest::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+use std::convert::TryFrom;
+
+#[derive(Clone, Debug, Default)]
+pub struct X25519PublicKeyValidation;
+
+impl FuzzTargetImpl for X25519PublicKeyValidation {
+    fn name(&self) -> &'static str {
+        module_name!()
+    }
+
+    fn description(&self) -> &'static str {
+        "x25519 network identity public key validation"
+    }
+
+    fn generate(&self, _idx: usize, gen: &mut ValueGenerator) -> Option<Vec<u8>> {
+        let bytes = gen.generate(any::<[u8; x25519::PUBLIC_KEY_SIZE]>());
+        Some(bytes.to_vec())
+    }
+
+    fn fuzz(&self, data: &[u8]) {
+        let public_key = match x25519::PublicKey::try_from(data) {
+            Ok(public_key) => public_key,
+            Err(_) => return,
+        };
+        if public_key.validate().is_ok() {
+            // a valid key never leads to a predictable shared secret
+            let mut rng = StdRng::from_seed(TEST_SEED);
+            let private_key = x25519::PrivateKey::generate(&mut rng);
+            assert_ne!(
+                private_key.diffie_hellman(&public_key),
+                [0u8; x25519::SHARED_SECRET_SIZE]
+            );
+        }
+    }
+}
@@ -5,3 +5,4 @@
 
 pub mod client;
 pub mod swarm;
+pub mod topology;
@@ -10,7 +10,7 @@ use libra_management::config_builder::{FullnodeBuilder, FullnodeType, ValidatorB
 use libra_temppath::TempPath;
 use libra_types::account_address::AccountAddress;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     env,
     fs::File,
     io::{self, Read},
@@ -182,13 +182,13 @@ impl LibraNode {
             }
         }
 
-        match self.debug_client.get_node_metrics() {
-            Ok(_) => {
+        match self.debug_client.check_health() {
+            Ok(()) => {
                 println!("Node '{}' is healthy", self.node_id);
                 HealthStatus::Healthy
             }
             Err(e) => {
-                println!("Error querying metrics for node '{}'", self.node_id);
+                println!("Error checking health of node '{}': {}", self.node_id, e);
                 HealthStatus::RpcFailure(e)
             }
         }
@@ -525,11 +525,27 @@ impl LibraSwarm {
         self.nodes.remove(&node_id);
     }
 
+    /// Launches the node at `idx` again and waits for it to reconnect to all the other nodes.
     pub fn add_node(
         &mut self,
         idx: usize,
         disable_logging: bool,
     ) -> Result<(), SwarmLaunchFailure> {
+        self.launch_node(idx, disable_logging)?;
+        self.wait_for_connectivity(self.nodes.len() as i64 - 1)
+    }
+
+    /// Kills the node at `idx` and launches it again with its (possibly updated) config.
+    pub fn restart_node(
+        &mut self,
+        idx: usize,
+        disable_logging: bool,
+    ) -> Result<(), SwarmLaunchFailure> {
+        self.kill_node(idx);
+        self.add_node(idx, disable_logging)
+    }
+
+    fn launch_node(&mut self, idx: usize, disable_logging: bool) -> Result<(), SwarmLaunchFailure> {
         // First take the configs out to not keep immutable borrow on self when calling
         // `launch_node`.
         let path = self
@@ -556,12 +572,125 @@ impl LibraSwarm {
         for _ in 0..60 {
             if let HealthStatus::Healthy = node.health_check() {
                 self.nodes.insert(node_id, node);
-                return self.wait_for_connectivity(self.nodes.len() as i64 - 1);
+                return Ok(());
             }
             ::std::thread::sleep(::std::time::Duration::from_millis(1000));
         }
         Err(SwarmLaunchFailure::LaunchTimeout)
     }
+
+    /// Splits the validators into the given groups of node indices: each validator is restarted
+    /// with the validators outside of its group denied on the validator network, so that groups
+    /// can no longer reach each other. Nodes missing from all the groups form a group of their own.
+    pub fn partition(
+        &mut self,
+        groups: &[Vec<usize>],
+        disable_logging: bool,
+    ) -> Result<(), SwarmLaunchFailure> {
+        assert_eq!(
+            self.role,
+            RoleType::Validator,
+            "Only validator swarms can be partitioned"
+        );
+        let peer_ids: Vec<_> = self
+            .config
+            .config_files
+            .iter()
+            .map(|path| {
+                let config = NodeConfig::load(path).unwrap();
+                config.validator_network.as_ref().unwrap().peer_id()
+            })
+            .collect();
+        let group_of = |idx: usize| groups.iter().position(|group| group.contains(&idx));
+
+        for idx in 0..peer_ids.len() {
+            let denied_peers = (0..peer_ids.len())
+                .filter(|other| group_of(*other) != group_of(idx))
+                .map(|other| peer_ids[other])
+                .collect();
+            self.update_config(idx, |config| {
+                config.validator_network.as_mut().unwrap().denied_peers = denied_peers;
+            });
+        }
+        self.restart_all(disable_logging)
+    }
+
+    /// Lifts a partition created by `partition` and waits for the validators to reconnect.
+    pub fn heal_partition(&mut self, disable_logging: bool) -> Result<(), SwarmLaunchFailure> {
+        for idx in 0..self.config.config_files.len() {
+            self.update_config(idx, |config| {
+                config.validator_network.as_mut().unwrap().denied_peers = HashSet::new();
+            });
+        }
+        self.restart_all(disable_logging)?;
+        self.wait_for_connectivity(self.nodes.len() as i64 - 1)
+    }
+
+    /// Restarts the validator at `idx` with its consensus clock shifted by `skew_ms`.
+    pub fn skew_clock(
+        &mut self,
+        idx: usize,
+        skew_ms: i64,
+        disable_logging: bool,
+    ) -> Result<(), SwarmLaunchFailure> {
+        self.update_config(idx, |config| config.consensus.clock_skew_ms = skew_ms);
+        self.restart_node(idx, disable_logging)
+    }
+
+    fn update_config<F: FnOnce(&mut NodeConfig)>(&self, idx: usize, update: F) {
+        let path = &self.config.config_files[idx];
+        let mut config = NodeConfig::load(path)
+            .unwrap_or_else(|_| panic!("Failed to load NodeConfig from file: {:?}", path));
+        update(&mut config);
+        config
+            .save(path)
+            .unwrap_or_else(|_| panic!("Failed to save NodeConfig to file: {:?}", path));
+    }
+
+    /// Restarts all the nodes without waiting for connectivity, as they may be partitioned.
+    fn restart_all(&mut self, disable_logging: bool) -> Result<(), SwarmLaunchFailure> {
+        self.nodes.clear();
+        for idx in 0..self.config.config_files.len() {
+            self.launch_node(idx, disable_logging)?;
+        }
+        Ok(())
+    }
+
+    /// Waits for at least `num_nodes` of the running nodes to commit `rounds` rounds past the
+    /// highest round committed at the time of the invocation. This is how callers assert that the
+    /// network makes progress, or recovers, after a fault was injected.
+    pub fn wait_for_progress(&mut self, num_nodes: usize, rounds: i64) -> bool {
+        let num_attempts = 60;
+        let last_committed_round_str = "libra_consensus_last_committed_round{}";
+        let target_round = self
+            .nodes
+            .values_mut()
+            .filter_map(|node| node.get_metric(last_committed_round_str))
+            .max()
+            .unwrap_or(0)
+            + rounds;
+
+        for i in 0..num_attempts {
+            println!(
+                "Wait for progress, target_commit_round = {}, attempt: {} of {}",
+                target_round,
+                i + 1,
+                num_attempts
+            );
+            let num_caught_up = self
+                .nodes
+                .values_mut()
+                .filter_map(|node| node.get_metric(last_committed_round_str))
+                .filter(|round| *round >= target_round)
+                .count();
+            if num_caught_up >= num_nodes {
+                return true;
+            }
+            ::std::thread::sleep(::std::time::Duration::from_millis(1000));
+        }
+
+        false
+    }
 }
 
 impl Drop for LibraSwarm {
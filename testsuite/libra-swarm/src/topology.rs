@@ -0,0 +1,248 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::swarm::{LibraNode, LibraSwarm, SwarmLaunchFailure};
+use anyhow::{ensure, format_err, Result};
+use config_builder::SwarmConfig;
+use libra_config::config::{DiscoveryMethod, NodeConfig, SeedAddresses, HANDSHAKE_VERSION};
+use libra_json_rpc_client::JsonRpcClient;
+use libra_management::config_builder::FullnodeType;
+use reqwest::Url;
+use std::path::{Path, PathBuf};
+
+/// Identifies a node of a topology by its kind and its index among the nodes of that kind.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NodeId {
+    Validator(usize),
+    ValidatorFullNode(usize),
+    PublicFullNode(usize),
+}
+
+/// A public full node, along with the full nodes it syncs from.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PublicFullNodeSpec {
+    /// The full nodes this node connects to. These can be validator full nodes, or public full
+    /// nodes declared before this one. If empty, the node discovers its peers on-chain.
+    pub upstreams: Vec<NodeId>,
+}
+
+/// Declarative description of the nodes of a test network.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TopologySpec {
+    pub num_validators: usize,
+    /// Validator full nodes are attached to the first validators, one each.
+    pub num_validator_full_nodes: usize,
+    pub public_full_nodes: Vec<PublicFullNodeSpec>,
+}
+
+impl TopologySpec {
+    pub fn new(num_validators: usize) -> Self {
+        Self {
+            num_validators,
+            ..Self::default()
+        }
+    }
+
+    pub fn validator_full_nodes(mut self, num_validator_full_nodes: usize) -> Self {
+        self.num_validator_full_nodes = num_validator_full_nodes;
+        self
+    }
+
+    pub fn public_full_node(mut self, upstreams: Vec<NodeId>) -> Self {
+        self.public_full_nodes
+            .push(PublicFullNodeSpec { upstreams });
+        self
+    }
+
+    fn verify(&self) -> Result<()> {
+        ensure!(self.num_validators > 0, "A topology needs validators");
+        ensure!(
+            self.num_validator_full_nodes <= self.num_validators,
+            "Cannot attach {} validator full nodes to {} validators",
+            self.num_validator_full_nodes,
+            self.num_validators
+        );
+        for (index, public_full_node) in self.public_full_nodes.iter().enumerate() {
+            for upstream in &public_full_node.upstreams {
+                let valid = match *upstream {
+                    NodeId::Validator(_) => false,
+                    NodeId::ValidatorFullNode(upstream) => upstream < self.num_validator_full_nodes,
+                    NodeId::PublicFullNode(upstream) => upstream < index,
+                };
+                ensure!(
+                    valid,
+                    "Invalid upstream {:?} for public full node {}",
+                    upstream,
+                    index
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The swarms of a test network built from a `TopologySpec`.
+pub struct LibraTopology {
+    pub validators: LibraSwarm,
+    pub validator_full_nodes: Option<LibraSwarm>,
+    pub public_full_nodes: Option<LibraSwarm>,
+}
+
+impl LibraTopology {
+    /// Generates the configs of all the nodes of the topology. If `config_dir` is specified, the
+    /// full node configs are written to the `vfn` and `pfn` directories within it.
+    pub fn configure(
+        spec: &TopologySpec,
+        config_dir: Option<String>,
+        template: Option<NodeConfig>,
+    ) -> Result<Self> {
+        spec.verify()?;
+        let validators = LibraSwarm::configure_validator_swarm(
+            spec.num_validators,
+            config_dir.clone(),
+            template,
+        )?;
+        let sub_dir = |name: &str| {
+            config_dir
+                .as_ref()
+                .map(|dir| Path::new(dir).join(name).to_string_lossy().into_owned())
+        };
+
+        let validator_full_nodes = if spec.num_validator_full_nodes > 0 {
+            let upstream_config = SwarmConfig {
+                config_files: validators.config.config_files[..spec.num_validator_full_nodes]
+                    .to_vec(),
+                faucet_key_path: validators.config.faucet_key_path.clone(),
+                waypoint: validators.config.waypoint,
+            };
+            Some(LibraSwarm::configure_fn_swarm(
+                sub_dir("vfn"),
+                None,
+                &upstream_config,
+                FullnodeType::ValidatorFullnode,
+            )?)
+        } else {
+            None
+        };
+
+        let public_full_nodes = if !spec.public_full_nodes.is_empty() {
+            let swarm = LibraSwarm::configure_fn_swarm(
+                sub_dir("pfn"),
+                None,
+                &validators.config,
+                FullnodeType::PublicFullnode(spec.public_full_nodes.len()),
+            )?;
+            Some(swarm)
+        } else {
+            None
+        };
+
+        let topology = Self {
+            validators,
+            validator_full_nodes,
+            public_full_nodes,
+        };
+        topology.connect_public_full_nodes(spec)?;
+        Ok(topology)
+    }
+
+    /// Points the public full nodes with upstreams at them. The identities of the public full
+    /// nodes are persisted first, as they would otherwise be generated anew on every load.
+    fn connect_public_full_nodes(&self, spec: &TopologySpec) -> Result<()> {
+        let swarm = match &self.public_full_nodes {
+            Some(swarm) => swarm,
+            None => return Ok(()),
+        };
+        for path in &swarm.config.config_files {
+            NodeConfig::load(path)?.save(path)?;
+        }
+
+        for (path, public_full_node) in swarm
+            .config
+            .config_files
+            .iter()
+            .zip(&spec.public_full_nodes)
+        {
+            if public_full_node.upstreams.is_empty() {
+                continue;
+            }
+            let mut seed_addrs = SeedAddresses::default();
+            for upstream in &public_full_node.upstreams {
+                let mut upstream_config = NodeConfig::load(self.config_path(*upstream)?)?;
+                let public_network = &mut upstream_config.full_node_networks[0];
+                let address = public_network.listen_address.clone().append_prod_protos(
+                    public_network.identity_key().public_key(),
+                    HANDSHAKE_VERSION,
+                );
+                seed_addrs.insert(public_network.peer_id(), vec![address]);
+            }
+
+            let mut config = NodeConfig::load(path)?;
+            let network = &mut config.full_node_networks[0];
+            network.discovery_method = DiscoveryMethod::None;
+            network.seed_addrs = seed_addrs;
+            config.save(path)?;
+        }
+        Ok(())
+    }
+
+    /// Launches the validators, then the validator full nodes and then the public full nodes.
+    pub fn launch(&mut self) {
+        self.validators.launch();
+        if let Some(swarm) = &mut self.validator_full_nodes {
+            swarm.launch();
+        }
+        if let Some(swarm) = &mut self.public_full_nodes {
+            swarm.launch();
+        }
+    }
+
+    /// Single attempt at launching all the nodes, see `LibraSwarm::launch_attempt`.
+    pub fn launch_attempt(&mut self, disable_logging: bool) -> Result<(), SwarmLaunchFailure> {
+        self.validators.launch_attempt(disable_logging)?;
+        if let Some(swarm) = &mut self.validator_full_nodes {
+            swarm.launch_attempt(disable_logging)?;
+        }
+        if let Some(swarm) = &mut self.public_full_nodes {
+            swarm.launch_attempt(disable_logging)?;
+        }
+        Ok(())
+    }
+
+    fn swarm(&self, node: NodeId) -> Result<(&LibraSwarm, usize)> {
+        let (swarm, index) = match node {
+            NodeId::Validator(index) => (Some(&self.validators), index),
+            NodeId::ValidatorFullNode(index) => (self.validator_full_nodes.as_ref(), index),
+            NodeId::PublicFullNode(index) => (self.public_full_nodes.as_ref(), index),
+        };
+        match swarm {
+            Some(swarm) if index < swarm.config.config_files.len() => Ok((swarm, index)),
+            _ => Err(format_err!("No node {:?} in the topology", node)),
+        }
+    }
+
+    /// The config file of a node.
+    pub fn config_path(&self, node: NodeId) -> Result<&PathBuf> {
+        let (swarm, index) = self.swarm(node)?;
+        Ok(&swarm.config.config_files[index])
+    }
+
+    /// A launched node.
+    pub fn node(&self, node: NodeId) -> Result<&LibraNode> {
+        let (swarm, index) = self.swarm(node)?;
+        swarm
+            .get_validator(index)
+            .ok_or_else(|| format_err!("Node {:?} is not running", node))
+    }
+
+    /// The URL of the JSON-RPC endpoint of a node.
+    pub fn json_rpc_url(&self, node: NodeId) -> Result<String> {
+        let config = NodeConfig::load(self.config_path(node)?)?;
+        Ok(format!("http://localhost:{}", config.rpc.address.port()))
+    }
+
+    /// A client to the JSON-RPC endpoint of a node.
+    pub fn json_rpc_client(&self, node: NodeId) -> Result<JsonRpcClient> {
+        JsonRpcClient::new(Url::parse(&self.json_rpc_url(node)?)?)
+    }
+}
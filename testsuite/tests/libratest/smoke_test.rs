@@ -23,7 +23,10 @@ use libra_network_address::{
 };
 use libra_secure_storage::{CryptoStorage, KVStorage, Storage};
 use libra_secure_time::{RealTimeService, TimeService};
-use libra_swarm::swarm::{LibraNode, LibraSwarm};
+use libra_swarm::{
+    swarm::{LibraNode, LibraSwarm},
+    topology::{LibraTopology, NodeId, TopologySpec},
+};
 use libra_temppath::TempPath;
 use libra_trace::trace::trace_node;
 use libra_types::{
@@ -504,6 +507,28 @@ fn test_basic_restartability() {
     ));
 }
 
+#[test]
+fn test_partition_and_clock_skew_recovery() {
+    let mut env = TestEnvironment::new(4);
+    env.validator_swarm.launch();
+    assert!(env.validator_swarm.wait_for_progress(4, 2));
+
+    // The majority side of the partition keeps committing, the isolated node falls behind
+    env.validator_swarm
+        .partition(&[vec![0, 1, 2], vec![3]], false)
+        .unwrap();
+    assert!(env.validator_swarm.wait_for_progress(3, 5));
+
+    // Once healed, all the nodes catch up and commit new rounds
+    env.validator_swarm.heal_partition(false).unwrap();
+    assert!(env.validator_swarm.wait_for_all_nodes_to_catchup());
+    assert!(env.validator_swarm.wait_for_progress(4, 2));
+
+    // A single node with a skewed clock does not prevent progress
+    env.validator_swarm.skew_clock(1, 500, false).unwrap();
+    assert!(env.validator_swarm.wait_for_progress(4, 5));
+}
+
 #[test]
 fn test_startup_sync_state() {
     let (mut env, mut client_proxy_1) = setup_swarm_and_client_proxy(4, 1);
@@ -1024,6 +1049,68 @@ fn test_full_node_basic_flow() {
     ));
 }
 
+#[test]
+fn test_topology_public_full_node_upstreams() {
+    ::libra_logger::Logger::new().init();
+    // The second public full node only syncs through the first one
+    let spec = TopologySpec::new(4)
+        .validator_full_nodes(1)
+        .public_full_node(vec![NodeId::ValidatorFullNode(0)])
+        .public_full_node(vec![NodeId::PublicFullNode(0)]);
+    let mut topology = LibraTopology::configure(&spec, None, None).unwrap();
+    topology.launch();
+
+    let mnemonic_file = TempPath::new();
+    mnemonic_file.create_as_file().unwrap();
+    let mnemonic_file_path = mnemonic_file
+        .path()
+        .canonicalize()
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string();
+    let faucet_key_path = topology
+        .validators
+        .config
+        .faucet_key_path
+        .to_str()
+        .unwrap()
+        .to_string();
+    let client = |node| {
+        ClientProxy::new(
+            ChainId::test(),
+            &topology.json_rpc_url(node).unwrap(),
+            &faucet_key_path,
+            &faucet_key_path,
+            false,
+            /* faucet server */ None,
+            Some(mnemonic_file_path.clone()),
+            topology.validators.config.waypoint,
+        )
+        .unwrap()
+    };
+
+    let mut validator_client = client(NodeId::Validator(0));
+    validator_client.create_next_account(false).unwrap();
+    validator_client
+        .mint_coins(&["mintb", "0", "10", "Coin1"], true)
+        .unwrap();
+
+    let mut public_full_node_client = client(NodeId::PublicFullNode(1));
+    public_full_node_client.create_next_account(false).unwrap();
+    public_full_node_client
+        .wait_for_transaction(testnet_dd_account_address(), 1)
+        .unwrap();
+    assert!(compare_balances(
+        vec![(10.0, "Coin1".to_string())],
+        public_full_node_client.get_balances(&["b", "0"]).unwrap(),
+    ));
+
+    // Specs must only refer to nodes they declare
+    let invalid_spec = TopologySpec::new(1).public_full_node(vec![NodeId::ValidatorFullNode(0)]);
+    assert!(LibraTopology::configure(&invalid_spec, None, None).is_err());
+}
+
 #[test]
 fn test_e2e_reconfiguration() {
     let (env, mut client_proxy_1) = setup_swarm_and_client_proxy(3, 1);
@@ -0,0 +1,60 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification of the proofs of the Merkle accumulators, e.g. the transaction accumulator of a
+//! `LedgerInfo`.
+
+use crate::{error::Error, hash::Hash};
+
+/// Because leaves can only take half the space in the tree, any numbering of the tree leaves must
+/// not take the full width of the total space. Thus, for a 64-bit ordering, our maximum proof
+/// depth is limited to 63.
+pub const MAX_ACCUMULATOR_PROOF_DEPTH: usize = 63;
+
+/// Verifies an element whose hash is `element_hash` and index is `element_index` exists in the
+/// accumulator whose root hash is `expected_root_hash`, given the `siblings` of its path ordered
+/// from the bottom level to the root level. `hash_internal_node` hashes an internal node from its
+/// left and right children, e.g. `HashDomain::new(TRANSACTION_ACCUMULATOR_SALT)`'s.
+pub fn verify_accumulator_element(
+    hash_internal_node: impl Fn(&Hash, &Hash) -> Hash,
+    expected_root_hash: &Hash,
+    element_hash: &Hash,
+    element_index: u64,
+    siblings: &[Hash],
+) -> Result<(), Error> {
+    if siblings.len() > MAX_ACCUMULATOR_PROOF_DEPTH {
+        return Err(Error::TooManySiblings {
+            proof: "Accumulator",
+            max: MAX_ACCUMULATOR_PROOF_DEPTH,
+            actual: siblings.len(),
+        });
+    }
+
+    let actual_root_hash = siblings
+        .iter()
+        .fold(
+            (*element_hash, element_index),
+            // `index` denotes the index of the ancestor of the element at the current level.
+            |(hash, index), sibling_hash| {
+                (
+                    if index % 2 == 0 {
+                        // the current node is a left child.
+                        hash_internal_node(&hash, sibling_hash)
+                    } else {
+                        // the current node is a right child.
+                        hash_internal_node(sibling_hash, &hash)
+                    },
+                    // The index of the parent at its level.
+                    index / 2,
+                )
+            },
+        )
+        .0;
+    if actual_root_hash != *expected_root_hash {
+        return Err(Error::RootHashMismatch {
+            actual: actual_root_hash,
+            expected: *expected_root_hash,
+        });
+    }
+    Ok(())
+}
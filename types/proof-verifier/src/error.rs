@@ -0,0 +1,115 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::hash::Hash;
+use core::fmt;
+
+/// Why a proof or a set of signatures was rejected.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    TooManySiblings {
+        proof: &'static str,
+        max: usize,
+        actual: usize,
+    },
+    RootHashMismatch {
+        actual: Hash,
+        expected: Hash,
+    },
+    KeyMismatch {
+        in_proof: Hash,
+        expected: Hash,
+    },
+    ValueHashMismatch {
+        in_proof: Hash,
+        expected: Hash,
+    },
+    ExpectedInclusionProof,
+    KeyExistsInNonInclusionProof,
+    InvalidNonInclusionProof,
+    UnknownAuthor,
+    DuplicateAuthor,
+    TooManySignatures {
+        num_of_signatures: usize,
+        num_of_authors: usize,
+    },
+    TooLittleVotingPower {
+        voting_power: u64,
+        quorum_voting_power: u64,
+    },
+    InvalidSignature,
+}
+
+struct Hex<'a>(&'a Hash);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for byte in self.0.iter() {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::TooManySiblings { proof, max, actual } => write!(
+                f,
+                "{} proof has more than {} ({}) siblings.",
+                proof, max, actual
+            ),
+            Error::RootHashMismatch { actual, expected } => write!(
+                f,
+                "Root hashes do not match. Actual root hash: {}. Expected root hash: {}.",
+                Hex(actual),
+                Hex(expected)
+            ),
+            Error::KeyMismatch { in_proof, expected } => write!(
+                f,
+                "Keys do not match. Key in proof: {}. Expected key: {}.",
+                Hex(in_proof),
+                Hex(expected)
+            ),
+            Error::ValueHashMismatch { in_proof, expected } => write!(
+                f,
+                "Value hashes do not match. Value hash in proof: {}. Expected value hash: {}",
+                Hex(in_proof),
+                Hex(expected)
+            ),
+            Error::ExpectedInclusionProof => {
+                write!(f, "Expected inclusion proof. Found non-inclusion proof.")
+            }
+            Error::KeyExistsInNonInclusionProof => {
+                write!(f, "Expected non-inclusion proof, but key exists in proof.")
+            }
+            Error::InvalidNonInclusionProof => write!(
+                f,
+                "Key would not have ended up in the subtree where the provided key in proof is \
+                 the only existing key, if it existed. So this is not a valid non-inclusion proof."
+            ),
+            Error::UnknownAuthor => write!(f, "Author is unknown"),
+            Error::DuplicateAuthor => write!(f, "Author signed more than once"),
+            Error::TooManySignatures {
+                num_of_signatures,
+                num_of_authors,
+            } => write!(
+                f,
+                "The number of signatures ({}) is greater than total number of authors ({})",
+                num_of_signatures, num_of_authors
+            ),
+            Error::TooLittleVotingPower {
+                voting_power,
+                quorum_voting_power,
+            } => write!(
+                f,
+                "The voting power ({}) is less than quorum voting power ({})",
+                voting_power, quorum_voting_power
+            ),
+            Error::InvalidSignature => write!(f, "Signature is invalid"),
+        }
+    }
+}
+
+#[cfg(any(test, feature = "std"))]
+impl std::error::Error for Error {}
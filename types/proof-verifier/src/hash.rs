@@ -0,0 +1,72 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The domain separated SHA3-256 hashing of Libra, mirroring `libra_crypto::hash`.
+
+use tiny_keccak::{Hasher, Sha3};
+
+pub const HASH_LENGTH: usize = 32;
+pub type Hash = [u8; HASH_LENGTH];
+
+const LIBRA_HASH_PREFIX: &[u8] = b"LIBRA::";
+
+/// Salts of the hashers of the values involved in the verification of an account state. The
+/// salt of a type deriving `CryptoHasher` is its serde name.
+pub const LEDGER_INFO_SALT: &[u8] = b"LedgerInfo";
+pub const TRANSACTION_INFO_SALT: &[u8] = b"TransactionInfo";
+pub const ACCOUNT_STATE_BLOB_SALT: &[u8] = b"AccountStateBlob";
+pub const TRANSACTION_ACCUMULATOR_SALT: &[u8] = b"TransactionAccumulator";
+pub const EVENT_ACCUMULATOR_SALT: &[u8] = b"EventAccumulator";
+pub const SPARSE_MERKLE_INTERNAL_SALT: &[u8] = b"SparseMerkleInternal";
+pub const SPARSE_MERKLE_LEAF_NODE_SALT: &[u8] = b"SparseMerkleLeafNode";
+
+/// The hash of an empty subtree of a sparse Merkle tree, the name padded with zeros.
+pub const SPARSE_MERKLE_PLACEHOLDER_HASH: Hash = *b"SPARSE_MERKLE_PLACEHOLDER_HASH\0\0";
+
+/// Hashes in the domain of a given salt: the hash of a value is SHA3-256 of the seed of the
+/// domain followed by the bytes of the value, where the seed is SHA3-256 of `LIBRA::` followed by
+/// the salt. An empty salt hashes with no seed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct HashDomain {
+    seed: Option<Hash>,
+}
+
+impl HashDomain {
+    pub fn new(salt: &[u8]) -> Self {
+        if salt.is_empty() {
+            return Self { seed: None };
+        }
+        Self {
+            seed: Some(sha3_256(&[LIBRA_HASH_PREFIX, salt])),
+        }
+    }
+
+    /// The bytes prefixed to a value before hashing it, which is also the prefix of the message
+    /// signed for it, e.g. for a `LedgerInfo`.
+    pub fn seed(&self) -> Option<&Hash> {
+        self.seed.as_ref()
+    }
+
+    /// Hashes the concatenation of `parts`.
+    pub fn hash(&self, parts: &[&[u8]]) -> Hash {
+        let mut sha3 = Sha3::v256();
+        if let Some(seed) = &self.seed {
+            sha3.update(seed);
+        }
+        for part in parts {
+            sha3.update(part);
+        }
+        let mut hash = [0; HASH_LENGTH];
+        sha3.finalize(&mut hash);
+        hash
+    }
+
+    /// Hashes an internal node of a Merkle tree from its children.
+    pub fn hash_internal_node(&self, left_child: &Hash, right_child: &Hash) -> Hash {
+        self.hash(&[left_child, right_child])
+    }
+}
+
+fn sha3_256(parts: &[&[u8]]) -> Hash {
+    HashDomain { seed: None }.hash(parts)
+}
@@ -0,0 +1,35 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+
+//! The core of the Libra proof verification, with no dependency beyond SHA3, so that mobile and
+//! embedded wallets anchored at a waypoint can verify the responses of a full node, e.g. an
+//! `AccountStateWithProof`, without pulling in `libra-types`.
+//!
+//! Everything works on raw 32 byte hashes. The caller hashes the LCS bytes of the values it
+//! received in the right [`HashDomain`](hash/struct.HashDomain.html), e.g. a `TransactionInfo`
+//! in `HashDomain::new(TRANSACTION_INFO_SALT)`, an account blob being hashed as is, and checks:
+//! 1. the signatures of the `LedgerInfo` with [`verify_quorum_signatures`],
+//! 2. that the `TransactionInfo` is in the transaction accumulator of the `LedgerInfo` with
+//!    [`verify_accumulator_element`],
+//! 3. that the account blob is in the state tree of the `TransactionInfo` with
+//!    [`verify_sparse_merkle_element`].
+//!
+//! `libra-types` implements its proofs on top of this crate and re-exports it.
+
+pub mod accumulator;
+mod error;
+pub mod hash;
+pub mod signatures;
+pub mod sparse_merkle;
+
+#[cfg(test)]
+mod unit_tests;
+
+pub use accumulator::{verify_accumulator_element, MAX_ACCUMULATOR_PROOF_DEPTH};
+pub use error::Error;
+pub use hash::{Hash, HashDomain, HASH_LENGTH};
+pub use signatures::{check_voting_power, default_quorum_voting_power, verify_quorum_signatures};
+pub use sparse_merkle::{verify_sparse_merkle_element, SparseMerkleLeaf};
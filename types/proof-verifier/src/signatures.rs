@@ -0,0 +1,69 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Quorum checks of the validator signatures on a `LedgerInfo`. The signature scheme is left to
+//! the caller, so that a wallet can use the Ed25519 implementation of its platform.
+
+use crate::error::Error;
+
+/// The voting power of a `2f + 1` quorum of validators holding `total_voting_power`.
+pub fn default_quorum_voting_power(total_voting_power: u64) -> u64 {
+    total_voting_power * 2 / 3 + 1
+}
+
+/// Ensures the authors whose voting powers are `authors_voting_power`, `None` for an unknown
+/// author, hold at least `quorum_voting_power`.
+pub fn check_voting_power(
+    authors_voting_power: impl IntoIterator<Item = Option<u64>>,
+    quorum_voting_power: u64,
+) -> Result<(), Error> {
+    let mut voting_power = 0;
+    for author_voting_power in authors_voting_power {
+        voting_power += author_voting_power.ok_or(Error::UnknownAuthor)?;
+    }
+    if voting_power < quorum_voting_power {
+        return Err(Error::TooLittleVotingPower {
+            voting_power,
+            quorum_voting_power,
+        });
+    }
+    Ok(())
+}
+
+/// Verifies `signatures` by distinct `validators`, given as authors and voting powers, hold at
+/// least `quorum_voting_power` and are all valid. `verify_signature` checks the signature of an
+/// author over the signing message, which for a `LedgerInfo` is the seed of
+/// `HashDomain::new(LEDGER_INFO_SALT)` followed by its LCS bytes.
+pub fn verify_quorum_signatures<A: PartialEq, S>(
+    validators: &[(A, u64)],
+    quorum_voting_power: u64,
+    signatures: &[(A, S)],
+    mut verify_signature: impl FnMut(&A, &S) -> bool,
+) -> Result<(), Error> {
+    if signatures.len() > validators.len() {
+        return Err(Error::TooManySignatures {
+            num_of_signatures: signatures.len(),
+            num_of_authors: validators.len(),
+        });
+    }
+    for (index, (author, _)) in signatures.iter().enumerate() {
+        if signatures[..index].iter().any(|(other, _)| other == author) {
+            return Err(Error::DuplicateAuthor);
+        }
+    }
+    check_voting_power(
+        signatures.iter().map(|(author, _)| {
+            validators
+                .iter()
+                .find(|(validator, _)| validator == author)
+                .map(|(_, voting_power)| *voting_power)
+        }),
+        quorum_voting_power,
+    )?;
+    for (author, signature) in signatures {
+        if !verify_signature(author, signature) {
+            return Err(Error::InvalidSignature);
+        }
+    }
+    Ok(())
+}
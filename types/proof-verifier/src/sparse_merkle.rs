@@ -0,0 +1,125 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification of the proofs of the sparse Merkle tree of the account states.
+
+use crate::{
+    error::Error,
+    hash::{
+        Hash, HashDomain, HASH_LENGTH, SPARSE_MERKLE_INTERNAL_SALT, SPARSE_MERKLE_LEAF_NODE_SALT,
+        SPARSE_MERKLE_PLACEHOLDER_HASH,
+    },
+};
+
+const HASH_LENGTH_IN_BITS: usize = HASH_LENGTH * 8;
+
+/// The leaf of a sparse Merkle tree proof: the key, i.e. the hash of the account address, and the
+/// hash of the value stored under it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SparseMerkleLeaf {
+    pub key: Hash,
+    pub value_hash: Hash,
+}
+
+impl SparseMerkleLeaf {
+    pub fn hash(&self) -> Hash {
+        HashDomain::new(SPARSE_MERKLE_LEAF_NODE_SALT).hash(&[&self.key, &self.value_hash])
+    }
+}
+
+/// If `element_value_hash` is present, verifies an element whose key is `element_key` and whose
+/// value hashes to `element_value_hash` exists in the sparse Merkle tree whose root hash is
+/// `expected_root_hash`. Otherwise verifies the proof shows `element_key` does not exist in the
+/// tree.
+///
+/// The proof is the `leaf` found on the path of `element_key`, if any, and the `siblings` of the
+/// path including the default ones, ordered from the bottom level to the root level.
+pub fn verify_sparse_merkle_element(
+    expected_root_hash: &Hash,
+    element_key: &Hash,
+    element_value_hash: Option<&Hash>,
+    leaf: Option<&SparseMerkleLeaf>,
+    siblings: &[Hash],
+) -> Result<(), Error> {
+    if siblings.len() > HASH_LENGTH_IN_BITS {
+        return Err(Error::TooManySiblings {
+            proof: "Sparse Merkle Tree",
+            max: HASH_LENGTH_IN_BITS,
+            actual: siblings.len(),
+        });
+    }
+
+    match (element_value_hash, leaf) {
+        (Some(value_hash), Some(leaf)) => {
+            // This is an inclusion proof, so the key and value hash provided in the proof should
+            // match element_key and element_value_hash. `siblings` should prove the route from
+            // the leaf node to the root.
+            if *element_key != leaf.key {
+                return Err(Error::KeyMismatch {
+                    in_proof: leaf.key,
+                    expected: *element_key,
+                });
+            }
+            if *value_hash != leaf.value_hash {
+                return Err(Error::ValueHashMismatch {
+                    in_proof: leaf.value_hash,
+                    expected: *value_hash,
+                });
+            }
+        }
+        (Some(_), None) => return Err(Error::ExpectedInclusionProof),
+        (None, Some(leaf)) => {
+            // This is a non-inclusion proof. The proof intends to show that if a leaf node
+            // representing `element_key` is inserted, it will break a currently existing leaf
+            // node represented by `proof_key` into a branch. `siblings` should prove the route
+            // from that leaf node to the root.
+            if *element_key == leaf.key {
+                return Err(Error::KeyExistsInNonInclusionProof);
+            }
+            if common_prefix_bits_len(element_key, &leaf.key) < siblings.len() {
+                return Err(Error::InvalidNonInclusionProof);
+            }
+        }
+        (None, None) => {
+            // This is a non-inclusion proof. The proof intends to show that if a leaf node
+            // representing `element_key` is inserted, it will show up at a currently empty
+            // position. `sibling` should prove the route from this empty position to the root.
+        }
+    }
+
+    let internal = HashDomain::new(SPARSE_MERKLE_INTERNAL_SALT);
+    let current_hash = leaf.map_or(SPARSE_MERKLE_PLACEHOLDER_HASH, SparseMerkleLeaf::hash);
+    let actual_root_hash = siblings
+        .iter()
+        // The sibling at the bottom level pairs with the deepest bit of the path.
+        .zip(
+            (0..siblings.len())
+                .rev()
+                .map(|depth| bit(element_key, depth)),
+        )
+        .fold(current_hash, |hash, (sibling_hash, bit)| {
+            if bit {
+                internal.hash_internal_node(sibling_hash, &hash)
+            } else {
+                internal.hash_internal_node(&hash, sibling_hash)
+            }
+        });
+    if actual_root_hash != *expected_root_hash {
+        return Err(Error::RootHashMismatch {
+            actual: actual_root_hash,
+            expected: *expected_root_hash,
+        });
+    }
+    Ok(())
+}
+
+/// The bit of `hash` at `index`, from the most significant bit of the first byte.
+fn bit(hash: &Hash, index: usize) -> bool {
+    hash[index / 8] & (0x80 >> (index % 8)) != 0
+}
+
+fn common_prefix_bits_len(a: &Hash, b: &Hash) -> usize {
+    (0..HASH_LENGTH_IN_BITS)
+        .take_while(|index| bit(a, *index) == bit(b, *index))
+        .count()
+}
@@ -0,0 +1,91 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    hash::{
+        HashDomain, EVENT_ACCUMULATOR_SALT, SPARSE_MERKLE_INTERNAL_SALT,
+        SPARSE_MERKLE_PLACEHOLDER_HASH, TRANSACTION_ACCUMULATOR_SALT,
+    },
+    verify_accumulator_element, verify_quorum_signatures, Error, Hash,
+};
+use libra_crypto::hash::{
+    CryptoHasher, EventAccumulatorHasher, SparseMerkleInternalHasher, TransactionAccumulatorHasher,
+    SPARSE_MERKLE_PLACEHOLDER_HASH as CRYPTO_PLACEHOLDER_HASH,
+};
+
+#[test]
+fn test_hash_domains_match_libra_crypto() {
+    for (salt, seed) in &[
+        (
+            TRANSACTION_ACCUMULATOR_SALT,
+            TransactionAccumulatorHasher::seed(),
+        ),
+        (EVENT_ACCUMULATOR_SALT, EventAccumulatorHasher::seed()),
+        (
+            SPARSE_MERKLE_INTERNAL_SALT,
+            SparseMerkleInternalHasher::seed(),
+        ),
+    ] {
+        assert_eq!(HashDomain::new(salt).seed(), Some(*seed));
+    }
+    assert_eq!(
+        &SPARSE_MERKLE_PLACEHOLDER_HASH,
+        CRYPTO_PLACEHOLDER_HASH.as_ref()
+    );
+
+    let mut hasher = TransactionAccumulatorHasher::default();
+    hasher.update(b"left");
+    hasher.update(b"right");
+    assert_eq!(
+        &HashDomain::new(TRANSACTION_ACCUMULATOR_SALT).hash(&[&b"left"[..], &b"right"[..]]),
+        hasher.finish().as_ref()
+    );
+}
+
+#[test]
+fn test_verify_accumulator_element() {
+    let domain = HashDomain::new(TRANSACTION_ACCUMULATOR_SALT);
+    let leaves: Vec<Hash> = (0..4u8).map(|i| [i; 32]).collect();
+    let left = domain.hash_internal_node(&leaves[0], &leaves[1]);
+    let right = domain.hash_internal_node(&leaves[2], &leaves[3]);
+    let root = domain.hash_internal_node(&left, &right);
+    let hash_internal_node = |l: &Hash, r: &Hash| domain.hash_internal_node(l, r);
+
+    verify_accumulator_element(hash_internal_node, &root, &leaves[2], 2, &[leaves[3], left])
+        .unwrap();
+    assert!(matches!(
+        verify_accumulator_element(hash_internal_node, &root, &leaves[2], 3, &[leaves[3], left]),
+        Err(Error::RootHashMismatch { .. })
+    ));
+    assert!(matches!(
+        verify_accumulator_element(hash_internal_node, &root, &leaves[2], 2, &[[0; 32]; 64]),
+        Err(Error::TooManySiblings { .. })
+    ));
+}
+
+#[test]
+fn test_verify_quorum_signatures() {
+    let validators = [(1u8, 1u64), (2, 1), (3, 1), (4, 1)];
+    let valid = |_: &u8, signature: &bool| *signature;
+
+    verify_quorum_signatures(&validators, 3, &[(1, true), (2, true), (4, true)], valid).unwrap();
+    assert_eq!(
+        verify_quorum_signatures(&validators, 3, &[(1, true), (2, true)], valid),
+        Err(Error::TooLittleVotingPower {
+            voting_power: 2,
+            quorum_voting_power: 3
+        })
+    );
+    assert_eq!(
+        verify_quorum_signatures(&validators, 3, &[(1, true), (1, true), (2, true)], valid),
+        Err(Error::DuplicateAuthor)
+    );
+    assert_eq!(
+        verify_quorum_signatures(&validators, 3, &[(1, true), (2, true), (5, true)], valid),
+        Err(Error::UnknownAuthor)
+    );
+    assert_eq!(
+        verify_quorum_signatures(&validators, 3, &[(1, true), (2, false), (3, true)], valid),
+        Err(Error::InvalidSignature)
+    );
+}
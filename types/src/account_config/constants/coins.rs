@@ -21,6 +21,15 @@ pub fn coin1_tag() -> TypeTag {
     })
 }
 
+pub fn coin2_tag() -> TypeTag {
+    TypeTag::Struct(StructTag {
+        address: CORE_CODE_ADDRESS,
+        module: from_currency_code_string(COIN2_NAME).unwrap(),
+        name: from_currency_code_string(COIN2_NAME).unwrap(),
+        type_params: vec![],
+    })
+}
+
 pub static LBR_MODULE: Lazy<ModuleId> =
     Lazy::new(|| ModuleId::new(CORE_CODE_ADDRESS, Identifier::new(LBR_NAME).unwrap()));
 pub static LBR_STRUCT_NAME: Lazy<Identifier> = Lazy::new(|| Identifier::new(LBR_NAME).unwrap());
@@ -4,21 +4,25 @@
 pub mod burn;
 pub mod cancel_burn;
 pub mod exchange_rate_update;
+pub mod freeze_account;
 pub mod mint;
 pub mod new_block;
 pub mod new_epoch;
 pub mod preburn;
 pub mod received_payment;
 pub mod sent_payment;
+pub mod unfreeze_account;
 pub mod upgrade;
 
 pub use burn::*;
 pub use cancel_burn::*;
 pub use exchange_rate_update::*;
+pub use freeze_account::*;
 pub use mint::*;
 pub use new_block::*;
 pub use new_epoch::*;
 pub use preburn::*;
 pub use received_payment::*;
 pub use sent_payment::*;
+pub use unfreeze_account::*;
 pub use upgrade::*;
@@ -0,0 +1,35 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::account_address::AccountAddress;
+use anyhow::Result;
+use move_core_types::move_resource::MoveResource;
+use serde::{Deserialize, Serialize};
+
+/// Struct that represents a UnfreezeAccountEvent.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnfreezeAccountEvent {
+    initiator_address: AccountAddress,
+    unfrozen_address: AccountAddress,
+}
+
+impl UnfreezeAccountEvent {
+    /// Get the address of the account that initiated the unfreeze
+    pub fn initiator_address(&self) -> AccountAddress {
+        self.initiator_address
+    }
+
+    /// Get the address of the unfrozen account
+    pub fn unfrozen_address(&self) -> AccountAddress {
+        self.unfrozen_address
+    }
+
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self> {
+        lcs::from_bytes(bytes).map_err(Into::into)
+    }
+}
+
+impl MoveResource for UnfreezeAccountEvent {
+    const MODULE_NAME: &'static str = "AccountFreezing";
+    const STRUCT_NAME: &'static str = "UnfreezeAccountEvent";
+}
@@ -1,14 +1,72 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use move_core_types::move_resource::MoveResource;
+use crate::{
+    access_path::AccessPath, account_config::constants::CORE_CODE_ADDRESS, event::EventHandle,
+};
+use move_core_types::{
+    language_storage::{StructTag, TypeTag},
+    move_resource::MoveResource,
+};
 use serde::{Deserialize, Serialize};
 
-// TODO: add fields
 #[derive(Debug, Serialize, Deserialize)]
-pub struct DesignatedDealer {}
+pub struct DesignatedDealer {
+    mint_event_handle: EventHandle,
+}
+
+impl DesignatedDealer {
+    pub fn mint_event_handle(&self) -> &EventHandle {
+        &self.mint_event_handle
+    }
+}
 
 impl MoveResource for DesignatedDealer {
     const MODULE_NAME: &'static str = "DesignatedDealer";
     const STRUCT_NAME: &'static str = "Dealer";
 }
+
+/// The tiers of the amounts a designated dealer may be minted in a currency, and how much was
+/// minted in the current time window.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TierInfoResource {
+    window_start: u64,
+    window_inflow: u64,
+    tiers: Vec<u64>,
+}
+
+impl TierInfoResource {
+    pub fn window_start(&self) -> u64 {
+        self.window_start
+    }
+
+    pub fn window_inflow(&self) -> u64 {
+        self.window_inflow
+    }
+
+    pub fn tiers(&self) -> &[u64] {
+        &self.tiers
+    }
+
+    // TODO/XXX: remove this once the MoveResource trait allows type arguments to `struct_tag`.
+    pub fn struct_tag_for_currency(currency_typetag: TypeTag) -> StructTag {
+        StructTag {
+            address: CORE_CODE_ADDRESS,
+            name: TierInfoResource::struct_identifier(),
+            module: TierInfoResource::module_identifier(),
+            type_params: vec![currency_typetag],
+        }
+    }
+
+    // TODO: remove this once the MoveResource trait allows type arguments to `resource_path`.
+    pub fn access_path_for(currency_typetag: TypeTag) -> Vec<u8> {
+        AccessPath::resource_access_vec(&TierInfoResource::struct_tag_for_currency(
+            currency_typetag,
+        ))
+    }
+}
+
+impl MoveResource for TierInfoResource {
+    const MODULE_NAME: &'static str = "DesignatedDealer";
+    const STRUCT_NAME: &'static str = "TierInfo";
+}
@@ -13,6 +13,12 @@ pub struct KeyRotationCapabilityResource {
     account_address: AccountAddress,
 }
 
+impl KeyRotationCapabilityResource {
+    pub fn account_address(&self) -> AccountAddress {
+        self.account_address
+    }
+}
+
 impl MoveResource for KeyRotationCapabilityResource {
     const MODULE_NAME: &'static str = ACCOUNT_MODULE_NAME;
     const STRUCT_NAME: &'static str = "KeyRotationCapability";
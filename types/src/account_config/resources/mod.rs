@@ -8,8 +8,11 @@ pub mod designated_dealer;
 pub mod dual_attestation;
 pub mod freezing_bit;
 pub mod key_rotation_capability;
+pub mod preburn;
+pub mod recovery_address;
 pub mod role;
 pub mod role_id;
+pub mod sliding_nonce;
 pub mod vasp;
 pub mod withdraw_capability;
 
@@ -20,7 +23,10 @@ pub use designated_dealer::*;
 pub use dual_attestation::*;
 pub use freezing_bit::*;
 pub use key_rotation_capability::*;
+pub use preburn::*;
+pub use recovery_address::*;
 pub use role::*;
 pub use role_id::*;
+pub use sliding_nonce::*;
 pub use vasp::*;
 pub use withdraw_capability::*;
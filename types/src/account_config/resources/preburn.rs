@@ -0,0 +1,46 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{access_path::AccessPath, account_config::constants::CORE_CODE_ADDRESS};
+use move_core_types::{
+    language_storage::{StructTag, TypeTag},
+    move_resource::MoveResource,
+};
+use serde::{Deserialize, Serialize};
+
+/// The amount a designated dealer requested to burn in a currency, pending its burn or
+/// cancellation by treasury compliance.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PreburnResource {
+    to_burn: u64,
+}
+
+impl PreburnResource {
+    pub fn new(to_burn: u64) -> Self {
+        Self { to_burn }
+    }
+
+    pub fn to_burn(&self) -> u64 {
+        self.to_burn
+    }
+
+    // TODO/XXX: remove this once the MoveResource trait allows type arguments to `struct_tag`.
+    pub fn struct_tag_for_currency(currency_typetag: TypeTag) -> StructTag {
+        StructTag {
+            address: CORE_CODE_ADDRESS,
+            name: PreburnResource::struct_identifier(),
+            module: PreburnResource::module_identifier(),
+            type_params: vec![currency_typetag],
+        }
+    }
+
+    // TODO: remove this once the MoveResource trait allows type arguments to `resource_path`.
+    pub fn access_path_for(currency_typetag: TypeTag) -> Vec<u8> {
+        AccessPath::resource_access_vec(&PreburnResource::struct_tag_for_currency(currency_typetag))
+    }
+}
+
+impl MoveResource for PreburnResource {
+    const MODULE_NAME: &'static str = "Libra";
+    const STRUCT_NAME: &'static str = "Preburn";
+}
@@ -0,0 +1,32 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{account_address::AccountAddress, account_config::KeyRotationCapabilityResource};
+use move_core_types::move_resource::MoveResource;
+use serde::{Deserialize, Serialize};
+
+/// The key rotation capabilities held by a recovery account on behalf of the accounts of its VASP.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecoveryAddressResource {
+    rotation_caps: Vec<KeyRotationCapabilityResource>,
+}
+
+impl RecoveryAddressResource {
+    pub fn rotation_caps(&self) -> &[KeyRotationCapabilityResource] {
+        &self.rotation_caps
+    }
+
+    /// The addresses of the accounts whose authentication key can be rotated by the recovery
+    /// account, starting with the recovery account itself.
+    pub fn recoverable_addresses(&self) -> Vec<AccountAddress> {
+        self.rotation_caps
+            .iter()
+            .map(|cap| cap.account_address())
+            .collect()
+    }
+}
+
+impl MoveResource for RecoveryAddressResource {
+    const MODULE_NAME: &'static str = "RecoveryAddress";
+    const STRUCT_NAME: &'static str = "RecoveryAddress";
+}
@@ -1,7 +1,7 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::account_config::resources::{ChildVASP, Credential, ParentVASP};
+use crate::account_config::resources::{ChildVASP, Credential, DesignatedDealer, ParentVASP};
 use serde::{Deserialize, Serialize};
 
 /// A enum that captures the collection of role-specific resources stored under each account type
@@ -12,7 +12,10 @@ pub enum AccountRole {
         credential: Credential,
     },
     ChildVASP(ChildVASP),
-    DesignatedDealer(Credential), // TODO: add DesignatedDealer resource as well
+    DesignatedDealer {
+        dd_credential: Credential,
+        designated_dealer: DesignatedDealer,
+    },
     Unknown,
     // TODO: add other roles
 }
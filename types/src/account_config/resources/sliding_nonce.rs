@@ -0,0 +1,90 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::move_resource::MoveResource;
+use serde::{Deserialize, Serialize};
+
+/// Size of `SlidingNonceResource::nonce_mask` in bits.
+pub const NONCE_MASK_SIZE: u64 = 128;
+
+/// The nonces recorded by an account sending admin transactions. Nonces below `min_nonce` are
+/// rejected, and bit `i` of `nonce_mask` is set if nonce `min_nonce + i` was recorded.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SlidingNonceResource {
+    min_nonce: u64,
+    nonce_mask: u128,
+}
+
+impl SlidingNonceResource {
+    pub fn min_nonce(&self) -> u64 {
+        self.min_nonce
+    }
+
+    pub fn nonce_mask(&self) -> u128 {
+        self.nonce_mask
+    }
+
+    /// Whether `nonce` would be rejected because it is too old or was already recorded. The
+    /// nonce 0 is never recorded, and always accepted.
+    pub fn is_used(&self, nonce: u64) -> bool {
+        if nonce == 0 {
+            return false;
+        }
+        if nonce < self.min_nonce {
+            return true;
+        }
+        let bit_pos = nonce - self.min_nonce;
+        bit_pos < NONCE_MASK_SIZE && self.nonce_mask & (1u128 << bit_pos) != 0
+    }
+
+    /// The nonce following the highest recorded one, which is accepted by the next transaction.
+    pub fn next_nonce(&self) -> u64 {
+        if self.nonce_mask == 0 {
+            return std::cmp::max(self.min_nonce, 1);
+        }
+        let highest_bit_pos = NONCE_MASK_SIZE - 1 - u64::from(self.nonce_mask.leading_zeros());
+        self.min_nonce + highest_bit_pos + 1
+    }
+}
+
+impl MoveResource for SlidingNonceResource {
+    const MODULE_NAME: &'static str = "SlidingNonce";
+    const STRUCT_NAME: &'static str = "SlidingNonce";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_nonce() {
+        let nonce = SlidingNonceResource {
+            min_nonce: 0,
+            nonce_mask: 0,
+        };
+        assert_eq!(nonce.next_nonce(), 1);
+        assert!(!nonce.is_used(0));
+        assert!(!nonce.is_used(1));
+
+        // Nonces 1 and 3 are recorded.
+        let nonce = SlidingNonceResource {
+            min_nonce: 0,
+            nonce_mask: 0b1010,
+        };
+        assert_eq!(nonce.next_nonce(), 4);
+        assert!(nonce.is_used(1));
+        assert!(!nonce.is_used(2));
+        assert!(nonce.is_used(3));
+
+        // The window slid past nonce 200, the highest one recorded.
+        let nonce = SlidingNonceResource {
+            min_nonce: 73,
+            nonce_mask: 1 << 127,
+        };
+        assert_eq!(nonce.next_nonce(), 201);
+        assert!(nonce.is_used(72));
+        assert!(!nonce.is_used(74));
+        assert!(nonce.is_used(200));
+        assert!(!nonce.is_used(201));
+    }
+}
@@ -7,6 +7,10 @@ use move_core_types::move_resource::MoveResource;
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
 
+/// The maximum number of child accounts a parent VASP can create. Mirrors
+/// `VASP::MAX_CHILD_ACCOUNTS`.
+pub const MAX_CHILD_ACCOUNTS: u64 = 256;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ParentVASP {
     num_children: u64,
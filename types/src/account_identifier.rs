@@ -0,0 +1,380 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A human-readable encoding of account addresses, for users to read and type them without the
+//! risk of a typo sending funds to the wrong account.
+//!
+//! An `AccountIdentifier` is encoded in bech32 (BIP 173): a prefix naming the network the address
+//! belongs to, `lbr` for the mainnet and `tlb` for the other networks, the separator `1`, then a
+//! version, the address followed by an optional subaddress, and a checksum detecting any error in
+//! up to 4 characters, e.g. `lbr1p7ujcndcl7nudzwt8fglhx6wxnvqqqqqqqqqqqqqflf8ma`. The `Subaddress`
+//! identifies a user of the account, e.g. a customer of a VASP, and is encoded as zeros when
+//! absent.
+
+use crate::{
+    account_address::AccountAddress,
+    chain_id::{ChainId, NamedChain},
+};
+use anyhow::{ensure, format_err, Error, Result};
+use rand::{rngs::OsRng, Rng};
+use std::{
+    convert::TryFrom,
+    fmt,
+    iter::{once, repeat},
+    str::FromStr,
+};
+use thiserror::Error;
+
+/// The prefix of the identifiers of mainnet accounts.
+pub const MAINNET_PREFIX: &str = "lbr";
+/// The prefix of the identifiers of the accounts of test networks.
+pub const TESTNET_PREFIX: &str = "tlb";
+
+/// The version of the encoding, as the first character of the data.
+const VERSION: u8 = 1;
+/// The 32 characters of the data, by value.
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const SEPARATOR: char = '1';
+const CHECKSUM_LENGTH: usize = 6;
+/// The number of characters of the data: the version, then the address and the subaddress in
+/// characters of 5 bits, then the checksum.
+const DATA_LENGTH: usize =
+    1 + ((AccountAddress::LENGTH + Subaddress::LENGTH) * 8 + 4) / 5 + CHECKSUM_LENGTH;
+
+/// Why a string is not a valid account identifier.
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum AccountIdentifierError {
+    #[error("Account identifier mixes lowercase and uppercase characters")]
+    MixedCase,
+    #[error("Account identifier has no separator")]
+    MissingSeparator,
+    #[error("Unknown account identifier prefix {:?}", _0)]
+    UnknownPrefix(String),
+    #[error("Invalid character {:?} in account identifier", _0)]
+    InvalidCharacter(char),
+    #[error(
+        "Account identifier has {} characters of data, expected {}",
+        _0,
+        DATA_LENGTH
+    )]
+    InvalidLength(usize),
+    #[error("Account identifier checksum does not match, it may contain a typo")]
+    InvalidChecksum,
+    #[error("Unsupported account identifier version {}", _0)]
+    UnsupportedVersion(u8),
+    #[error("Account identifier has non-zero padding")]
+    InvalidPadding,
+}
+
+/// The network an account identifier is meant for.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum AddressNetwork {
+    Mainnet,
+    Testnet,
+}
+
+impl AddressNetwork {
+    pub fn from_chain_id(chain_id: ChainId) -> Self {
+        if chain_id.id() == NamedChain::MAINNET as u8 {
+            AddressNetwork::Mainnet
+        } else {
+            AddressNetwork::Testnet
+        }
+    }
+
+    pub fn prefix(self) -> &'static str {
+        match self {
+            AddressNetwork::Mainnet => MAINNET_PREFIX,
+            AddressNetwork::Testnet => TESTNET_PREFIX,
+        }
+    }
+
+    fn from_prefix(prefix: &str) -> Option<Self> {
+        match prefix {
+            MAINNET_PREFIX => Some(AddressNetwork::Mainnet),
+            TESTNET_PREFIX => Some(AddressNetwork::Testnet),
+            _ => None,
+        }
+    }
+}
+
+/// Identifies a user of an account, e.g. a customer of a VASP, for the account to route the
+/// payments it receives. The all-zero subaddress is reserved to mean no subaddress.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Subaddress([u8; Subaddress::LENGTH]);
+
+impl Subaddress {
+    /// The length of a subaddress in bytes.
+    pub const LENGTH: usize = 8;
+
+    /// Returns `None` for the reserved all-zero subaddress.
+    pub fn new(bytes: [u8; Self::LENGTH]) -> Option<Self> {
+        if bytes == [0; Self::LENGTH] {
+            None
+        } else {
+            Some(Self(bytes))
+        }
+    }
+
+    /// A random subaddress, e.g. for a new user or a single payment.
+    pub fn random() -> Self {
+        let mut rng = OsRng;
+        loop {
+            if let Some(subaddress) = Self::new(rng.gen()) {
+                return subaddress;
+            }
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8; Self::LENGTH] {
+        &self.0
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+}
+
+impl TryFrom<&[u8]> for Subaddress {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        ensure!(
+            bytes.len() == Self::LENGTH,
+            "Subaddress has {} bytes, expected {}",
+            bytes.len(),
+            Self::LENGTH
+        );
+        let mut subaddress = [0; Self::LENGTH];
+        subaddress.copy_from_slice(bytes);
+        Self::new(subaddress).ok_or_else(|| format_err!("The all-zero subaddress is reserved"))
+    }
+}
+
+impl fmt::Display for Subaddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", hex::encode(&self.0))
+    }
+}
+
+impl FromStr for Subaddress {
+    type Err = Error;
+
+    /// Parses a hex-encoded subaddress.
+    fn from_str(s: &str) -> Result<Self> {
+        Self::try_from(hex::decode(s)?.as_slice())
+    }
+}
+
+/// An account address along with the network it belongs to and an optional subaddress.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct AccountIdentifier {
+    network: AddressNetwork,
+    address: AccountAddress,
+    subaddress: Option<Subaddress>,
+}
+
+impl AccountIdentifier {
+    pub fn new(network: AddressNetwork, address: AccountAddress) -> Self {
+        Self {
+            network,
+            address,
+            subaddress: None,
+        }
+    }
+
+    pub fn with_subaddress(
+        network: AddressNetwork,
+        address: AccountAddress,
+        subaddress: Subaddress,
+    ) -> Self {
+        Self {
+            network,
+            address,
+            subaddress: Some(subaddress),
+        }
+    }
+
+    pub fn network(&self) -> AddressNetwork {
+        self.network
+    }
+
+    pub fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    pub fn subaddress(&self) -> Option<Subaddress> {
+        self.subaddress
+    }
+
+    pub fn encode(&self) -> String {
+        let mut payload = self.address.to_vec();
+        payload.extend_from_slice(
+            self.subaddress
+                .as_ref()
+                .map_or(&[0; Subaddress::LENGTH], |subaddress| subaddress.as_bytes()),
+        );
+        let mut data = vec![VERSION];
+        data.extend(convert_bits(&payload, 8, 5, true).expect("Padding is allowed"));
+        let checksum = checksum(self.network.prefix(), &data);
+        data.extend_from_slice(&checksum);
+
+        let mut encoded = String::from(self.network.prefix());
+        encoded.push(SEPARATOR);
+        encoded.extend(
+            data.iter()
+                .map(|value| char::from(CHARSET[*value as usize])),
+        );
+        encoded
+    }
+
+    pub fn decode(encoded: &str) -> Result<Self, AccountIdentifierError> {
+        if encoded.chars().any(|c| c.is_ascii_lowercase())
+            && encoded.chars().any(|c| c.is_ascii_uppercase())
+        {
+            return Err(AccountIdentifierError::MixedCase);
+        }
+        let encoded = encoded.to_ascii_lowercase();
+        let separator = encoded
+            .rfind(SEPARATOR)
+            .ok_or(AccountIdentifierError::MissingSeparator)?;
+        let (prefix, data) = (&encoded[..separator], &encoded[separator + 1..]);
+        let network = AddressNetwork::from_prefix(prefix)
+            .ok_or_else(|| AccountIdentifierError::UnknownPrefix(prefix.to_string()))?;
+        let data = data
+            .chars()
+            .map(|c| {
+                CHARSET
+                    .iter()
+                    .position(|value| char::from(*value) == c)
+                    .map(|value| value as u8)
+                    .ok_or(AccountIdentifierError::InvalidCharacter(c))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if data.len() != DATA_LENGTH {
+            return Err(AccountIdentifierError::InvalidLength(data.len()));
+        }
+        if polymod(expand_prefix(prefix).chain(data.iter().copied())) != 1 {
+            return Err(AccountIdentifierError::InvalidChecksum);
+        }
+        if data[0] != VERSION {
+            return Err(AccountIdentifierError::UnsupportedVersion(data[0]));
+        }
+
+        let payload = convert_bits(&data[1..DATA_LENGTH - CHECKSUM_LENGTH], 5, 8, false)
+            .ok_or(AccountIdentifierError::InvalidPadding)?;
+        let (address, subaddress) = payload.split_at(AccountAddress::LENGTH);
+        let mut subaddress_bytes = [0; Subaddress::LENGTH];
+        subaddress_bytes.copy_from_slice(subaddress);
+        Ok(Self {
+            network,
+            address: AccountAddress::try_from(address).expect("Payload holds an address"),
+            subaddress: Subaddress::new(subaddress_bytes),
+        })
+    }
+}
+
+impl fmt::Display for AccountIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.encode())
+    }
+}
+
+impl FromStr for AccountIdentifier {
+    type Err = AccountIdentifierError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::decode(s)
+    }
+}
+
+/// Whether `s` looks like an account identifier rather than a hex-encoded address, which can't
+/// contain the characters of the prefixes.
+pub fn is_account_identifier(s: &str) -> bool {
+    let s = s.to_ascii_lowercase();
+    [MAINNET_PREFIX, TESTNET_PREFIX]
+        .iter()
+        .any(|prefix| s.starts_with(prefix))
+}
+
+/// Parses an account address given either as an account identifier, whose subaddress is ignored,
+/// or hex-encoded.
+pub fn parse_account_address(s: &str) -> Result<AccountAddress> {
+    if is_account_identifier(s) {
+        Ok(AccountIdentifier::decode(s)?.address())
+    } else {
+        let bytes = hex::decode(s)
+            .map_err(|error| format_err!("Invalid account address {:?}: {}", s, error))?;
+        AccountAddress::try_from(bytes.as_slice())
+    }
+}
+
+/// The bech32 checksum generator.
+const GENERATOR: [u32; 5] = [
+    0x3b6a_57b2,
+    0x2650_8e6d,
+    0x1ea1_19fa,
+    0x3d42_33dd,
+    0x2a14_62b3,
+];
+
+fn polymod(values: impl Iterator<Item = u8>) -> u32 {
+    values.fold(1, |checksum, value| {
+        let top = checksum >> 25;
+        GENERATOR
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| (top >> i) & 1 == 1)
+            .fold(
+                (checksum & 0x01ff_ffff) << 5 ^ u32::from(value),
+                |checksum, (_, generator)| checksum ^ generator,
+            )
+    })
+}
+
+fn expand_prefix(prefix: &str) -> impl Iterator<Item = u8> + '_ {
+    prefix
+        .bytes()
+        .map(|byte| byte >> 5)
+        .chain(once(0))
+        .chain(prefix.bytes().map(|byte| byte & 0x1f))
+}
+
+fn checksum(prefix: &str, data: &[u8]) -> [u8; CHECKSUM_LENGTH] {
+    let polymod = polymod(
+        expand_prefix(prefix)
+            .chain(data.iter().copied())
+            .chain(repeat(0).take(CHECKSUM_LENGTH)),
+    ) ^ 1;
+    let mut checksum = [0; CHECKSUM_LENGTH];
+    for (i, value) in checksum.iter_mut().enumerate() {
+        *value = ((polymod >> (5 * (CHECKSUM_LENGTH - 1 - i))) & 0x1f) as u8;
+    }
+    checksum
+}
+
+/// Regroups the bits of `data` from groups of `from` bits into groups of `to` bits. Without
+/// `pad`, the bits left over must be zeros fitting in a group of `from` bits.
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Option<Vec<u8>> {
+    let mut acc = 0u32;
+    let mut bits = 0;
+    let max_value = (1 << to) - 1;
+    let max_acc = (1 << (from + to - 1)) - 1;
+    let mut converted = vec![];
+    for value in data {
+        acc = ((acc << from) | u32::from(*value)) & max_acc;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            converted.push(((acc >> bits) & max_value) as u8);
+        }
+    }
+    if pad {
+        if bits > 0 {
+            converted.push(((acc << (to - bits)) & max_value) as u8);
+        }
+    } else if bits >= from || (acc << (to - bits)) & max_value != 0 {
+        return None;
+    }
+    Some(converted)
+}
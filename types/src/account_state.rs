@@ -5,8 +5,9 @@ use crate::{
     account_address::AccountAddress,
     account_config::{
         type_tag_for_currency_code, AccountResource, AccountRole, BalanceResource, ChildVASP,
-        Credential, DesignatedDealer, FreezingBit, ParentVASP, ACCOUNT_RECEIVED_EVENT_PATH,
-        ACCOUNT_SENT_EVENT_PATH,
+        Credential, DesignatedDealer, FreezingBit, ParentVASP, PreburnResource,
+        RecoveryAddressResource, RoleId, SlidingNonceResource, TierInfoResource,
+        ACCOUNT_RECEIVED_EVENT_PATH, ACCOUNT_SENT_EVENT_PATH,
     },
     block_metadata::{LibraBlockResource, NEW_BLOCK_EVENT_PATH},
     event::EventHandle,
@@ -15,7 +16,9 @@ use crate::{
     validator_config::ValidatorConfigResource,
 };
 use anyhow::{bail, Error, Result};
-use move_core_types::{identifier::Identifier, move_resource::MoveResource};
+use move_core_types::{
+    identifier::Identifier, language_storage::TypeTag, move_resource::MoveResource,
+};
 use serde::{de::DeserializeOwned, export::Formatter, Deserialize, Serialize};
 use std::{collections::btree_map::BTreeMap, convert::TryFrom, fmt};
 
@@ -37,15 +40,40 @@ impl AccountState {
         &self,
         currency_codes: &[Identifier],
     ) -> Result<BTreeMap<Identifier, BalanceResource>> {
+        // TODO: update this to use BalanceResource::resource_path once that takes type parameters
+        self.get_resources_by_currency(currency_codes, BalanceResource::access_path_for)
+    }
+
+    /// Returns the amounts pending burn of a designated dealer, in the currencies it holds a
+    /// preburn resource for among `currency_codes`.
+    pub fn get_preburn_balances(
+        &self,
+        currency_codes: &[Identifier],
+    ) -> Result<BTreeMap<Identifier, PreburnResource>> {
+        self.get_resources_by_currency(currency_codes, PreburnResource::access_path_for)
+    }
+
+    /// Returns the minting tiers of a designated dealer, in the currencies it may be minted among
+    /// `currency_codes`.
+    pub fn get_tier_info_resources(
+        &self,
+        currency_codes: &[Identifier],
+    ) -> Result<BTreeMap<Identifier, TierInfoResource>> {
+        self.get_resources_by_currency(currency_codes, TierInfoResource::access_path_for)
+    }
+
+    fn get_resources_by_currency<T: DeserializeOwned>(
+        &self,
+        currency_codes: &[Identifier],
+        access_path_for: impl Fn(TypeTag) -> Vec<u8>,
+    ) -> Result<BTreeMap<Identifier, T>> {
         currency_codes
             .iter()
             .filter_map(|currency_code| {
                 let currency_type_tag = type_tag_for_currency_code(currency_code.to_owned());
-                // TODO: update this to use BalanceResource::resource_path once that takes type
-                // parameters
-                self.get_resource(&BalanceResource::access_path_for(currency_type_tag))
+                self.get_resource(&access_path_for(currency_type_tag))
                     .transpose()
-                    .map(|balance| balance.map(|b| (currency_code.to_owned(), b)))
+                    .map(|resource| resource.map(|r| (currency_code.to_owned(), r)))
             })
             .collect()
     }
@@ -66,23 +94,74 @@ impl AccountState {
         self.get_resource(&FreezingBit::resource_path())
     }
 
+    pub fn get_role_id(&self) -> Result<Option<RoleId>> {
+        self.get_resource(&RoleId::resource_path())
+    }
+
+    pub fn get_parent_vasp(&self) -> Result<Option<ParentVASP>> {
+        self.get_resource(&ParentVASP::resource_path())
+    }
+
+    pub fn get_child_vasp(&self) -> Result<Option<ChildVASP>> {
+        self.get_resource(&ChildVASP::resource_path())
+    }
+
+    /// Returns the address of the parent VASP of this account: its own address if it is a parent
+    /// VASP, the address of its parent if it is a child VASP, and `None` if it is not a VASP.
+    pub fn get_parent_vasp_address(&self) -> Result<Option<AccountAddress>> {
+        if self.0.contains_key(&ParentVASP::resource_path()) {
+            self.get_account_address()
+        } else {
+            Ok(self
+                .get_child_vasp()?
+                .map(|child_vasp| child_vasp.parent_vasp_addr()))
+        }
+    }
+
+    /// Returns the number of child accounts of this account if it is a parent VASP.
+    pub fn get_num_children(&self) -> Result<Option<u64>> {
+        Ok(self
+            .get_parent_vasp()?
+            .map(|parent_vasp| parent_vasp.num_children()))
+    }
+
+    pub fn get_credential(&self) -> Result<Option<Credential>> {
+        self.get_resource(&Credential::resource_path())
+    }
+
+    pub fn get_designated_dealer(&self) -> Result<Option<DesignatedDealer>> {
+        self.get_resource(&DesignatedDealer::resource_path())
+    }
+
+    pub fn get_recovery_address_resource(&self) -> Result<Option<RecoveryAddressResource>> {
+        self.get_resource(&RecoveryAddressResource::resource_path())
+    }
+
+    pub fn get_sliding_nonce_resource(&self) -> Result<Option<SlidingNonceResource>> {
+        self.get_resource(&SlidingNonceResource::resource_path())
+    }
+
     pub fn get_account_role(&self) -> Result<Option<AccountRole>> {
         if self.0.contains_key(&ParentVASP::resource_path()) {
-            match (
-                self.get_resource(&ParentVASP::resource_path()),
-                self.get_resource(&Credential::resource_path()),
-            ) {
+            match (self.get_parent_vasp(), self.get_credential()) {
                 (Ok(Some(vasp)), Ok(Some(credential))) => {
                     Ok(Some(AccountRole::ParentVASP { vasp, credential }))
                 }
                 _ => Ok(None),
             }
         } else if self.0.contains_key(&ChildVASP::resource_path()) {
-            self.get_resource(&ChildVASP::resource_path())
+            self.get_child_vasp()
                 .map(|r_opt| r_opt.map(AccountRole::ChildVASP))
         } else if self.0.contains_key(&DesignatedDealer::resource_path()) {
-            self.get_resource(&Credential::resource_path())
-                .map(|r_opt| r_opt.map(AccountRole::DesignatedDealer))
+            match (self.get_credential(), self.get_designated_dealer()) {
+                (Ok(Some(dd_credential)), Ok(Some(designated_dealer))) => {
+                    Ok(Some(AccountRole::DesignatedDealer {
+                        dd_credential,
+                        designated_dealer,
+                    }))
+                }
+                _ => Ok(None),
+            }
         } else {
             // TODO: add role_id to Unknown
             Ok(Some(AccountRole::Unknown))
@@ -97,6 +176,37 @@ impl AccountState {
         self.get_resource(&LibraBlockResource::resource_path())
     }
 
+    /// Returns the event handles held by the resources of this account, keyed by the name of
+    /// their event stream: the sent and received payments of every account, along with the
+    /// streams specific to its role (e.g., the mints received by a designated dealer, or the new
+    /// blocks and epochs emitted under the libra root account).
+    pub fn get_event_handles(&self) -> Result<BTreeMap<&'static str, EventHandle>> {
+        let mut event_handles = BTreeMap::new();
+        if let Some(account_resource) = self.get_account_resource()? {
+            event_handles.insert("sent_events", account_resource.sent_events().clone());
+            event_handles.insert(
+                "received_events",
+                account_resource.received_events().clone(),
+            );
+        }
+        if let Some(designated_dealer) = self.get_designated_dealer()? {
+            event_handles.insert(
+                "received_mint_events",
+                designated_dealer.mint_event_handle().clone(),
+            );
+        }
+        if let Some(libra_block_resource) = self.get_libra_block_resource()? {
+            event_handles.insert(
+                "new_block_events",
+                libra_block_resource.new_block_events().clone(),
+            );
+        }
+        if let Some(configuration_resource) = self.get_configuration_resource()? {
+            event_handles.insert("new_epoch_events", configuration_resource.events().clone());
+        }
+        Ok(event_handles)
+    }
+
     pub fn get_event_handle_by_query_path(&self, query_path: &[u8]) -> Result<Option<EventHandle>> {
         let event_handle = if *ACCOUNT_RECEIVED_EVENT_PATH == query_path {
             self.get_account_resource()?
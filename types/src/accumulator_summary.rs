@@ -0,0 +1,139 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! An `AccumulatorSummary` is a compact record of the history of the transaction accumulator over
+//! a range of versions: the accumulator root hashes at a series of checkpoints together with the
+//! epoch-ending `LedgerInfo`s of the range. It carries no transaction, so a third party can
+//! notarize the chain history by keeping the summary and checking it against a waypoint it trusts.
+
+use crate::{
+    epoch_change::{EpochChangeProof, Verifier},
+    ledger_info::LedgerInfoWithSignatures,
+    proof::{accumulator::InMemoryAccumulator, AccumulatorConsistencyProof},
+    transaction::Version,
+    waypoint::Waypoint,
+};
+use anyhow::{ensure, format_err, Result};
+use libra_crypto::{hash::TransactionAccumulatorHasher, HashValue};
+use serde::{Deserialize, Serialize};
+
+/// The root hash of the transaction accumulator after the transaction at `version` was appended.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AccumulatorCheckpoint {
+    pub version: Version,
+    pub root_hash: HashValue,
+    /// The subtrees extending the accumulator at the previous checkpoint, or at `first_version`
+    /// for the first checkpoint, to the accumulator at `version`.
+    pub consistency_proof: AccumulatorConsistencyProof,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AccumulatorSummary {
+    /// The first version covered by the summary.
+    pub first_version: Version,
+    /// The frozen subtree roots of the accumulator holding the `first_version` transactions
+    /// before the range.
+    pub frozen_subtree_roots: Vec<HashValue>,
+    /// Checkpoints in increasing order of versions. They include the version of every
+    /// epoch-ending `LedgerInfo` of the range and end with the version of
+    /// `ledger_info_with_sigs`.
+    pub checkpoints: Vec<AccumulatorCheckpoint>,
+    /// The epoch-ending `LedgerInfo`s from the one preceding `first_version` up to the one
+    /// preceding, or equal to, `ledger_info_with_sigs`.
+    pub epoch_change_proof: EpochChangeProof,
+    /// The `LedgerInfo` the last checkpoint is signed by.
+    pub ledger_info_with_sigs: LedgerInfoWithSignatures,
+}
+
+impl AccumulatorSummary {
+    pub fn new(
+        first_version: Version,
+        frozen_subtree_roots: Vec<HashValue>,
+        checkpoints: Vec<AccumulatorCheckpoint>,
+        epoch_change_proof: EpochChangeProof,
+        ledger_info_with_sigs: LedgerInfoWithSignatures,
+    ) -> Self {
+        Self {
+            first_version,
+            frozen_subtree_roots,
+            checkpoints,
+            epoch_change_proof,
+            ledger_info_with_sigs,
+        }
+    }
+
+    /// Verifies the checkpoints chain up to the transaction accumulator of
+    /// `ledger_info_with_sigs`, without checking any signature.
+    pub fn verify_accumulator(&self) -> Result<()> {
+        let mut accumulator = InMemoryAccumulator::<TransactionAccumulatorHasher>::new(
+            self.frozen_subtree_roots.clone(),
+            self.first_version,
+        )?;
+        for checkpoint in &self.checkpoints {
+            ensure!(
+                checkpoint.version >= accumulator.num_leaves(),
+                "Checkpoint at version {} is not after the accumulator with {} leaves.",
+                checkpoint.version,
+                accumulator.num_leaves(),
+            );
+            accumulator = accumulator.append_subtrees(
+                checkpoint.consistency_proof.subtrees(),
+                checkpoint.version + 1 - accumulator.num_leaves(),
+            )?;
+            ensure!(
+                accumulator.root_hash() == checkpoint.root_hash,
+                "Root hash of checkpoint at version {} does not match. Expected: {:x}. Actual: {:x}.",
+                checkpoint.version,
+                accumulator.root_hash(),
+                checkpoint.root_hash,
+            );
+        }
+
+        let last_checkpoint = self
+            .checkpoints
+            .last()
+            .ok_or_else(|| format_err!("Summary has no checkpoint."))?;
+        let ledger_info = self.ledger_info_with_sigs.ledger_info();
+        ensure!(
+            last_checkpoint.version == ledger_info.version()
+                && last_checkpoint.root_hash == ledger_info.transaction_accumulator_hash(),
+            "Last checkpoint at version {} does not match LedgerInfo at version {}.",
+            last_checkpoint.version,
+            ledger_info.version(),
+        );
+        Ok(())
+    }
+
+    /// Verifies the whole summary, trusting only `waypoint`, which must be that of one of the
+    /// epoch-ending `LedgerInfo`s in `epoch_change_proof`, e.g. the one preceding
+    /// `first_version`.
+    pub fn verify(&self, waypoint: &Waypoint) -> Result<()> {
+        self.verify_accumulator()?;
+
+        let epoch_change_li = self.epoch_change_proof.verify(waypoint)?;
+        if epoch_change_li != &self.ledger_info_with_sigs {
+            epoch_change_li
+                .ledger_info()
+                .next_epoch_state()
+                .ok_or_else(|| format_err!("LedgerInfo doesn't carry a ValidatorSet"))?
+                .verify(&self.ledger_info_with_sigs)?;
+        }
+
+        for ledger_info_with_sigs in &self.epoch_change_proof.ledger_info_with_sigs {
+            let ledger_info = ledger_info_with_sigs.ledger_info();
+            if ledger_info.version() < self.first_version {
+                continue;
+            }
+            ensure!(
+                self.checkpoints.iter().any(|checkpoint| {
+                    checkpoint.version == ledger_info.version()
+                        && checkpoint.root_hash == ledger_info.transaction_accumulator_hash()
+                }),
+                "No checkpoint matches the LedgerInfo ending epoch {} at version {}.",
+                ledger_info.epoch(),
+                ledger_info.version(),
+            );
+        }
+        Ok(())
+    }
+}
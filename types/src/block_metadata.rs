@@ -66,10 +66,18 @@ impl BlockMetadata {
     pub fn proposer(&self) -> AccountAddress {
         self.proposer
     }
+
+    pub fn round(&self) -> u64 {
+        self.round
+    }
+
+    pub fn previous_block_votes(&self) -> &[AccountAddress] {
+        &self.previous_block_votes
+    }
 }
 
 pub fn new_block_event_key() -> EventKey {
-    EventKey::new_from_address(&libra_root_address(), 20)
+    EventKey::new_from_address(&libra_root_address(), 21)
 }
 
 /// The path to the new block event handle under a LibraBlock::BlockMetadata resource.
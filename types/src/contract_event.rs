@@ -3,8 +3,9 @@
 
 use crate::{
     account_config::{
-        BurnEvent, CancelBurnEvent, MintEvent, NewBlockEvent, NewEpochEvent, PreburnEvent,
-        ReceivedPaymentEvent, SentPaymentEvent, ToLBRExchangeRateUpdateEvent, UpgradeEvent,
+        BurnEvent, CancelBurnEvent, FreezeAccountEvent, MintEvent, NewBlockEvent, NewEpochEvent,
+        PreburnEvent, ReceivedPaymentEvent, SentPaymentEvent, ToLBRExchangeRateUpdateEvent,
+        UnfreezeAccountEvent, UpgradeEvent,
     },
     event::EventKey,
     ledger_info::LedgerInfo,
@@ -14,7 +15,8 @@ use crate::{
 use anyhow::{ensure, Error, Result};
 use libra_crypto::hash::CryptoHash;
 use libra_crypto_derive::{CryptoHasher, LCSCryptoHash};
-use move_core_types::{language_storage::TypeTag, move_resource::MoveResource};
+pub use move_core_types::language_storage::TypeTag;
+use move_core_types::move_resource::MoveResource;
 
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest_derive::Arbitrary;
@@ -189,6 +191,28 @@ impl TryFrom<&ContractEvent> for UpgradeEvent {
     }
 }
 
+impl TryFrom<&ContractEvent> for FreezeAccountEvent {
+    type Error = Error;
+
+    fn try_from(event: &ContractEvent) -> Result<Self> {
+        if event.type_tag != TypeTag::Struct(Self::struct_tag()) {
+            anyhow::bail!("Expected FreezeAccountEvent")
+        }
+        Self::try_from_bytes(&event.event_data)
+    }
+}
+
+impl TryFrom<&ContractEvent> for UnfreezeAccountEvent {
+    type Error = Error;
+
+    fn try_from(event: &ContractEvent) -> Result<Self> {
+        if event.type_tag != TypeTag::Struct(Self::struct_tag()) {
+            anyhow::bail!("Expected UnfreezeAccountEvent")
+        }
+        Self::try_from_bytes(&event.event_data)
+    }
+}
+
 impl TryFrom<&ContractEvent> for NewBlockEvent {
     type Error = Error;
 
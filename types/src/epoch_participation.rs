@@ -0,0 +1,83 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{account_address::AccountAddress, block_metadata::BlockMetadata};
+#[cfg(any(test, feature = "fuzzing"))]
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Statistics of the participation of validators in consensus during an epoch, aggregated from
+/// the `BlockMetadata` transactions committed in the epoch.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+pub struct EpochParticipation {
+    /// Number of blocks committed in the epoch, nil blocks included.
+    num_blocks: u64,
+    /// Number of nil blocks, which are committed for rounds that timed out without a proposal.
+    num_nil_blocks: u64,
+    /// Number of rounds for which no block was committed.
+    num_skipped_rounds: u64,
+    /// Round of the last block committed in the epoch.
+    last_round: u64,
+    validators: BTreeMap<AccountAddress, ValidatorParticipation>,
+}
+
+/// Participation of a single validator in consensus during an epoch.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+pub struct ValidatorParticipation {
+    /// Number of committed blocks proposed by the validator.
+    pub num_proposals: u64,
+    /// Number of quorum certificates, carried by committed blocks, the validator voted in.
+    pub num_votes: u64,
+}
+
+impl EpochParticipation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accounts for `block`, which must be committed after all the blocks recorded so far.
+    pub fn record_block(&mut self, block: &BlockMetadata) {
+        self.num_blocks += 1;
+        // Rounds start over at 0 with the genesis block of each epoch, which has no metadata.
+        self.num_skipped_rounds += block.round().saturating_sub(self.last_round + 1);
+        self.last_round = block.round();
+
+        // Nil blocks have no author and use the 0x0 address as their proposer.
+        if block.proposer() == AccountAddress::ZERO {
+            self.num_nil_blocks += 1;
+        } else {
+            self.validators
+                .entry(block.proposer())
+                .or_default()
+                .num_proposals += 1;
+        }
+        for voter in block.previous_block_votes() {
+            self.validators.entry(*voter).or_default().num_votes += 1;
+        }
+    }
+
+    pub fn num_blocks(&self) -> u64 {
+        self.num_blocks
+    }
+
+    pub fn num_nil_blocks(&self) -> u64 {
+        self.num_nil_blocks
+    }
+
+    pub fn num_skipped_rounds(&self) -> u64 {
+        self.num_skipped_rounds
+    }
+
+    pub fn last_round(&self) -> u64 {
+        self.last_round
+    }
+
+    /// Returns the participation of every validator which proposed or voted for a block during
+    /// the epoch.
+    pub fn validators(&self) -> &BTreeMap<AccountAddress, ValidatorParticipation> {
+        &self.validators
+    }
+}
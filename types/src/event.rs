@@ -43,6 +43,19 @@ impl EventKey {
         AccountAddress::new(arr_bytes)
     }
 
+    /// Get the creation number of this event key: the number of event handles its creator had
+    /// created before it.
+    pub fn get_creation_number(&self) -> u64 {
+        let mut salt_bytes = [0u8; 8];
+        salt_bytes.copy_from_slice(&self.0[..8]);
+        u64::from_le_bytes(salt_bytes)
+    }
+
+    /// Whether this event key belongs to an event stream created by the account at `address`.
+    pub fn is_created_by(&self, address: &AccountAddress) -> bool {
+        self.get_creator_address() == *address
+    }
+
     #[cfg(feature = "fuzzing")]
     /// Create a random event key for testing
     pub fn random() -> Self {
@@ -51,7 +64,8 @@ impl EventKey {
         EventKey::new_from_address(&AccountAddress::random(), salt)
     }
 
-    /// Create a unique handle by using an AccountAddress and a counter.
+    /// Create a unique handle by using an AccountAddress and a counter. This derives the key of
+    /// the event handle created by the account at `addr` as its `salt`-th one.
     pub fn new_from_address(addr: &AccountAddress, salt: u64) -> Self {
         let mut output_bytes = [0; Self::LENGTH];
         let (lhs, rhs) = output_bytes.split_at_mut(8);
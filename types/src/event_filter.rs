@@ -0,0 +1,90 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{account_address::AccountAddress, contract_event::ContractEvent, event::EventKey};
+use move_core_types::language_storage::TypeTag;
+use serde::{Deserialize, Serialize};
+
+/// Describes which events a consumer cares about. Filters are built from the basic criteria
+/// below and composed with `and`, `or` and `not`.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum EventFilter {
+    /// Matches every event.
+    All,
+    /// Matches the events emitted to the event stream with this key.
+    Key(EventKey),
+    /// Matches the events emitted to an event stream created by this account.
+    Account(AccountAddress),
+    /// Matches the events whose data is of this type.
+    TypeTag(TypeTag),
+    /// Matches the events emitted to an event stream whose key starts with these bytes.
+    KeyPrefix(#[serde(with = "serde_bytes")] Vec<u8>),
+    /// Matches the events matched by all of the filters. Matches every event if empty.
+    And(Vec<EventFilter>),
+    /// Matches the events matched by any of the filters. Matches no event if empty.
+    Or(Vec<EventFilter>),
+    /// Matches the events not matched by the filter.
+    Not(Box<EventFilter>),
+}
+
+impl EventFilter {
+    pub fn by_key(key: EventKey) -> Self {
+        EventFilter::Key(key)
+    }
+
+    pub fn by_account(address: AccountAddress) -> Self {
+        EventFilter::Account(address)
+    }
+
+    pub fn by_type_tag(type_tag: TypeTag) -> Self {
+        EventFilter::TypeTag(type_tag)
+    }
+
+    pub fn by_key_prefix(prefix: Vec<u8>) -> Self {
+        EventFilter::KeyPrefix(prefix)
+    }
+
+    /// Matches the events emitted to any of the event streams with these keys.
+    pub fn any_key(keys: impl IntoIterator<Item = EventKey>) -> Self {
+        EventFilter::Or(keys.into_iter().map(EventFilter::Key).collect())
+    }
+
+    pub fn and(self, other: EventFilter) -> Self {
+        match self {
+            EventFilter::And(mut filters) => {
+                filters.push(other);
+                EventFilter::And(filters)
+            }
+            filter => EventFilter::And(vec![filter, other]),
+        }
+    }
+
+    pub fn or(self, other: EventFilter) -> Self {
+        match self {
+            EventFilter::Or(mut filters) => {
+                filters.push(other);
+                EventFilter::Or(filters)
+            }
+            filter => EventFilter::Or(vec![filter, other]),
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Self {
+        EventFilter::Not(Box::new(self))
+    }
+
+    /// Returns whether `event` is matched by this filter.
+    pub fn matches(&self, event: &ContractEvent) -> bool {
+        match self {
+            EventFilter::All => true,
+            EventFilter::Key(key) => event.key() == key,
+            EventFilter::Account(address) => event.key().is_created_by(address),
+            EventFilter::TypeTag(type_tag) => event.type_tag() == type_tag,
+            EventFilter::KeyPrefix(prefix) => event.key().as_bytes().starts_with(prefix),
+            EventFilter::And(filters) => filters.iter().all(|filter| filter.matches(event)),
+            EventFilter::Or(filters) => filters.iter().any(|filter| filter.matches(event)),
+            EventFilter::Not(filter) => !filter.matches(event),
+        }
+    }
+}
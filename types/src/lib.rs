@@ -6,15 +6,19 @@
 pub mod access_path;
 pub mod account_address;
 pub mod account_config;
+pub mod account_identifier;
 pub mod account_state;
 pub mod account_state_blob;
+pub mod accumulator_summary;
 pub mod block_info;
 pub mod block_metadata;
 pub mod chain_id;
 pub mod contract_event;
 pub mod epoch_change;
+pub mod epoch_participation;
 pub mod epoch_state;
 pub mod event;
+pub mod event_filter;
 pub mod ledger_info;
 pub mod libra_timestamp;
 pub mod mempool_status;
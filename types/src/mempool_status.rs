@@ -53,6 +53,8 @@ pub enum MempoolStatusCode {
     // transaction didn't pass vm_validation
     VmError = 5,
     UnknownStatus = 6,
+    // Node is draining before a shutdown and does not take new transactions
+    Draining = 7,
 }
 
 impl TryFrom<u64> for MempoolStatusCode {
@@ -67,6 +69,7 @@ impl TryFrom<u64> for MempoolStatusCode {
             4 => Ok(MempoolStatusCode::InvalidUpdate),
             5 => Ok(MempoolStatusCode::VmError),
             6 => Ok(MempoolStatusCode::UnknownStatus),
+            7 => Ok(MempoolStatusCode::Draining),
             _ => Err("invalid StatusCode"),
         }
     }
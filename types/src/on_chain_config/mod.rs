@@ -20,17 +20,20 @@ mod libra_version;
 mod registered_currencies;
 mod validator_set;
 mod vm_config;
+mod vm_publishing_option;
 
 pub use self::{
-    libra_version::LibraVersion,
-    registered_currencies::RegisteredCurrencies,
-    validator_set::ValidatorSet,
-    vm_config::{ModulePublishingOption, ScriptPublishingOption, VMConfig, VMPublishingOption},
+    libra_version::LibraVersion, registered_currencies::RegisteredCurrencies,
+    validator_set::ValidatorSet, vm_config::VMConfig, vm_publishing_option::VMPublishingOption,
 };
 
 /// To register an on-chain config in Rust:
-/// 1. Implement the `OnChainConfig` trait for the Rust representation of the config
-/// 2. Add the config's `ConfigID` to `ON_CHAIN_CONFIG_REGISTRY`
+/// 1. Implement the `OnChainConfig` trait for the Rust representation of the config, which
+///    declares the access path of the config and the LCS type it deserializes into
+/// 2. Add the config type to the `register_on_chain_configs!` invocation below
+///
+/// Registered configs are fetched by state sync on every reconfiguration, and published to the
+/// subscribers of their `ConfigID` as well as to the ones subscribed to the whole registry.
 
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub struct ConfigID(&'static str, &'static str);
@@ -50,13 +53,23 @@ impl ConfigID {
     }
 }
 
-/// State sync will panic if the value of any config in this registry is uninitialized
-pub const ON_CHAIN_CONFIG_REGISTRY: &[ConfigID] = &[
-    VMConfig::CONFIG_ID,
-    LibraVersion::CONFIG_ID,
-    ValidatorSet::CONFIG_ID,
-    RegisteredCurrencies::CONFIG_ID,
-];
+/// Builds `ON_CHAIN_CONFIG_REGISTRY` from the Rust types of the registered configs, rejecting at
+/// compile time any type that does not implement `OnChainConfig`.
+macro_rules! register_on_chain_configs {
+    ($($config:ty),* $(,)?) => {
+        /// State sync will panic if the value of any config in this registry is uninitialized
+        pub const ON_CHAIN_CONFIG_REGISTRY: &[ConfigID] =
+            &[$(<$config as OnChainConfig>::CONFIG_ID),*];
+    };
+}
+
+register_on_chain_configs!(
+    VMConfig,
+    VMPublishingOption,
+    LibraVersion,
+    ValidatorSet,
+    RegisteredCurrencies,
+);
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct OnChainConfigPayload {
@@ -102,7 +115,7 @@ pub trait OnChainConfig: Send + Sync + DeserializeOwned {
     // Single-round LCS deserialization from bytes to `Self`
     // This is the expected deserialization pattern for most Rust representations,
     // but sometimes `deserialize_into_config` may need an extra customized round of deserialization
-    // (e.g. enums like `VMPublishingOption`)
+    // (e.g. configs with nested serialized fields like `VMConfig`)
     // In the override, we can reuse this default logic via this function
     // Note: we cannot directly call the default `deserialize_into_config` implementation
     // in its override - this will just refer to the override implementation itself
@@ -0,0 +1,69 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account_address::AccountAddress, account_config::libra_root_address,
+    on_chain_config::OnChainConfig, transaction::SCRIPT_HASH_LENGTH,
+};
+use libra_crypto::HashValue;
+use serde::{Deserialize, Serialize};
+
+/// Defines and holds the publishing policies for the VM, mirroring the
+/// `LibraTransactionPublishingOption` config. Scripts whose hash is in `script_allow_list` can
+/// always be executed, other scripts only if `is_open_script` is set. Modules can be published by
+/// any account if `is_open_module` is set, otherwise only by the libra root account.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct VMPublishingOption {
+    pub script_allow_list: Vec<HashValue>,
+    pub is_open_script: bool,
+    pub is_open_module: bool,
+}
+
+impl VMPublishingOption {
+    pub fn locked(allow_list: Vec<[u8; SCRIPT_HASH_LENGTH]>) -> Self {
+        Self {
+            script_allow_list: allow_list.into_iter().map(HashValue::new).collect(),
+            is_open_script: false,
+            is_open_module: false,
+        }
+    }
+
+    pub fn custom_scripts() -> Self {
+        Self {
+            script_allow_list: vec![],
+            is_open_script: true,
+            is_open_module: false,
+        }
+    }
+
+    pub fn open() -> Self {
+        Self {
+            script_allow_list: vec![],
+            is_open_script: true,
+            is_open_module: true,
+        }
+    }
+
+    pub fn is_open_module(&self) -> bool {
+        self.is_open_module
+    }
+
+    pub fn is_allowed_module(&self, module_sender: &AccountAddress) -> bool {
+        self.is_open_module || *module_sender == libra_root_address()
+    }
+
+    pub fn is_open_script(&self) -> bool {
+        self.is_open_script
+    }
+
+    pub fn is_allowed_script(&self, program: &[u8]) -> bool {
+        self.is_open_script
+            || self
+                .script_allow_list
+                .contains(&HashValue::sha3_256_of(program))
+    }
+}
+
+impl OnChainConfig for VMPublishingOption {
+    const IDENTIFIER: &'static str = "LibraTransactionPublishingOption";
+}
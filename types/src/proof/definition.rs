@@ -4,15 +4,15 @@
 //! This module has definition of various proofs.
 
 use super::{
-    accumulator::InMemoryAccumulator, position::Position, verify_transaction_info,
-    MerkleTreeInternalNode, SparseMerkleInternalNode, SparseMerkleLeafNode,
+    accumulator::InMemoryAccumulator, position::Position, verifier, verify_transaction_info,
+    MerkleTreeInternalNode, SparseMerkleLeafNode,
 };
 use crate::{
     account_state_blob::AccountStateBlob,
     ledger_info::LedgerInfo,
     transaction::{TransactionInfo, Version},
 };
-use anyhow::{bail, ensure, format_err, Result};
+use anyhow::{ensure, format_err, Result};
 #[cfg(any(test, feature = "fuzzing"))]
 use libra_crypto::hash::TestOnlyHasher;
 use libra_crypto::{
@@ -25,7 +25,7 @@ use libra_crypto::{
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
-use std::marker::PhantomData;
+use std::{convert::TryFrom, marker::PhantomData};
 
 /// A proof that can be used authenticate an element in an accumulator given trusted root hash. For
 /// example, both `LedgerInfoToTransactionInfoProof` and `TransactionInfoToEventProof` can be
@@ -43,7 +43,7 @@ pub struct AccumulatorProof<H> {
 /// not take the full width of the total space.  Thus, for a 64-bit ordering, our maximumm proof
 /// depth is limited to 63.
 pub type LeafCount = u64;
-pub const MAX_ACCUMULATOR_PROOF_DEPTH: usize = 63;
+pub const MAX_ACCUMULATOR_PROOF_DEPTH: usize = verifier::MAX_ACCUMULATOR_PROOF_DEPTH;
 pub const MAX_ACCUMULATOR_LEAVES: LeafCount = 1 << MAX_ACCUMULATOR_PROOF_DEPTH;
 
 impl<H> AccumulatorProof<H>
@@ -71,41 +71,18 @@ where
         element_hash: HashValue,
         element_index: u64,
     ) -> Result<()> {
-        ensure!(
-            self.siblings.len() <= MAX_ACCUMULATOR_PROOF_DEPTH,
-            "Accumulator proof has more than {} ({}) siblings.",
-            MAX_ACCUMULATOR_PROOF_DEPTH,
-            self.siblings.len()
-        );
-
-        let actual_root_hash = self
-            .siblings
-            .iter()
-            .fold(
-                (element_hash, element_index),
-                // `index` denotes the index of the ancestor of the element at the current level.
-                |(hash, index), sibling_hash| {
-                    (
-                        if index % 2 == 0 {
-                            // the current node is a left child.
-                            MerkleTreeInternalNode::<H>::new(hash, *sibling_hash).hash()
-                        } else {
-                            // the current node is a right child.
-                            MerkleTreeInternalNode::<H>::new(*sibling_hash, hash).hash()
-                        },
-                        // The index of the parent at its level.
-                        index / 2,
-                    )
-                },
-            )
-            .0;
-        ensure!(
-            actual_root_hash == expected_root_hash,
-            "Root hashes do not match. Actual root hash: {:x}. Expected root hash: {:x}.",
-            actual_root_hash,
-            expected_root_hash
-        );
-
+        let siblings: Vec<_> = self.siblings.iter().map(|hash| *hash.as_ref()).collect();
+        verifier::verify_accumulator_element(
+            |left, right| {
+                *MerkleTreeInternalNode::<H>::new(HashValue::new(*left), HashValue::new(*right))
+                    .hash()
+                    .as_ref()
+            },
+            expected_root_hash.as_ref(),
+            element_hash.as_ref(),
+            element_index,
+            &siblings,
+        )?;
         Ok(())
     }
 }
@@ -165,6 +142,11 @@ impl SparseMerkleProof {
         &self.siblings
     }
 
+    /// Returns the proof without its default siblings, which is much smaller for sparse trees.
+    pub fn compress(&self) -> CompressedSparseMerkleProof {
+        self.into()
+    }
+
     /// If `element_blob` is present, verifies an element whose key is `element_key` and value is
     /// `element_blob` exists in the Sparse Merkle Tree using the provided proof. Otherwise
     /// verifies the proof is a valid non-inclusion proof that shows this key doesn't exist in the
@@ -175,84 +157,147 @@ impl SparseMerkleProof {
         element_key: HashValue,
         element_blob: Option<&AccountStateBlob>,
     ) -> Result<()> {
+        let siblings: Vec<_> = self.siblings.iter().map(|hash| *hash.as_ref()).collect();
+        let leaf = self.leaf.map(|leaf| verifier::SparseMerkleLeaf {
+            key: *leaf.key().as_ref(),
+            value_hash: *leaf.value_hash().as_ref(),
+        });
+        verifier::verify_sparse_merkle_element(
+            expected_root_hash.as_ref(),
+            element_key.as_ref(),
+            element_blob.map(|blob| *blob.hash().as_ref()).as_ref(),
+            leaf.as_ref(),
+            &siblings,
+        )?;
+        Ok(())
+    }
+}
+
+/// A `SparseMerkleProof` without its default siblings, which are most of the siblings near the
+/// root of a sparse tree. A bitmap tells which siblings are not the default, i.e.
+/// `SPARSE_MERKLE_PLACEHOLDER_HASH`, and only those are kept.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CompressedSparseMerkleProof {
+    /// The leaf node, as in `SparseMerkleProof`.
+    leaf: Option<SparseMerkleLeafNode>,
+    /// The siblings, see `CompressedSiblings`.
+    siblings: CompressedSiblings,
+}
+
+impl CompressedSparseMerkleProof {
+    /// Returns the leaf node in this proof.
+    pub fn leaf(&self) -> Option<SparseMerkleLeafNode> {
+        self.leaf
+    }
+
+    /// Restores the full proof, failing if the bitmap does not match the siblings.
+    pub fn decompress(&self) -> Result<SparseMerkleProof> {
+        Ok(SparseMerkleProof::new(
+            self.leaf,
+            self.siblings.decompress()?,
+        ))
+    }
+
+    /// Same as [`SparseMerkleProof::verify`](struct.SparseMerkleProof.html#method.verify).
+    pub fn verify(
+        &self,
+        expected_root_hash: HashValue,
+        element_key: HashValue,
+        element_blob: Option<&AccountStateBlob>,
+    ) -> Result<()> {
+        self.decompress()?
+            .verify(expected_root_hash, element_key, element_blob)
+    }
+}
+
+impl From<&SparseMerkleProof> for CompressedSparseMerkleProof {
+    fn from(proof: &SparseMerkleProof) -> Self {
+        Self {
+            leaf: proof.leaf,
+            siblings: CompressedSiblings::compress(&proof.siblings),
+        }
+    }
+}
+
+impl TryFrom<&CompressedSparseMerkleProof> for SparseMerkleProof {
+    type Error = anyhow::Error;
+
+    fn try_from(proof: &CompressedSparseMerkleProof) -> Result<Self> {
+        proof.decompress()
+    }
+}
+
+/// A list of sparse Merkle tree siblings where the default ones are left out.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+struct CompressedSiblings {
+    /// The number of siblings, including the default ones.
+    num_siblings: u16,
+    /// One bit per sibling, in the order of the siblings and starting from the most significant bit
+    /// of the first byte, set if the sibling is not the default. The unused bits of the last byte
+    /// are zero.
+    bitmap: Vec<u8>,
+    /// The siblings which are not the default, in order.
+    non_default_siblings: Vec<HashValue>,
+}
+
+impl CompressedSiblings {
+    fn compress(siblings: &[HashValue]) -> Self {
+        let mut bitmap = vec![0u8; (siblings.len() + 7) / 8];
+        let mut non_default_siblings = vec![];
+        for (i, sibling) in siblings.iter().enumerate() {
+            if *sibling != *SPARSE_MERKLE_PLACEHOLDER_HASH {
+                bitmap[i / 8] |= 0x80 >> (i % 8);
+                non_default_siblings.push(*sibling);
+            }
+        }
+        Self {
+            num_siblings: siblings.len() as u16,
+            bitmap,
+            non_default_siblings,
+        }
+    }
+
+    fn decompress(&self) -> Result<Vec<HashValue>> {
+        let num_siblings = self.num_siblings as usize;
         ensure!(
-            self.siblings.len() <= HashValue::LENGTH_IN_BITS,
-            "Sparse Merkle Tree proof has more than {} ({}) siblings.",
+            num_siblings <= HashValue::LENGTH_IN_BITS,
+            "Compressed proof has more than {} ({}) siblings.",
             HashValue::LENGTH_IN_BITS,
-            self.siblings.len(),
+            num_siblings,
+        );
+        ensure!(
+            self.bitmap.len() == (num_siblings + 7) / 8,
+            "Bitmap of {} bytes does not match {} siblings.",
+            self.bitmap.len(),
+            num_siblings,
         );
 
-        match (element_blob, self.leaf) {
-            (Some(blob), Some(leaf)) => {
-                // This is an inclusion proof, so the key and value hash provided in the proof
-                // should match element_key and element_value_hash. `siblings` should prove the
-                // route from the leaf node to the root.
-                ensure!(
-                    element_key == leaf.key,
-                    "Keys do not match. Key in proof: {:x}. Expected key: {:x}.",
-                    leaf.key,
-                    element_key
-                );
-                let hash = blob.hash();
-                ensure!(
-                    hash == leaf.value_hash,
-                    "Value hashes do not match. Value hash in proof: {:x}. \
-                     Expected value hash: {:x}",
-                    leaf.value_hash,
-                    hash,
-                );
-            }
-            (Some(_blob), None) => bail!("Expected inclusion proof. Found non-inclusion proof."),
-            (None, Some(leaf)) => {
-                // This is a non-inclusion proof. The proof intends to show that if a leaf node
-                // representing `element_key` is inserted, it will break a currently existing leaf
-                // node represented by `proof_key` into a branch. `siblings` should prove the
-                // route from that leaf node to the root.
-                ensure!(
-                    element_key != leaf.key,
-                    "Expected non-inclusion proof, but key exists in proof.",
-                );
+        let mut non_default_siblings = self.non_default_siblings.iter();
+        let siblings = (0..num_siblings)
+            .map(|i| -> Result<HashValue> {
+                if self.bitmap[i / 8] & (0x80 >> (i % 8)) == 0 {
+                    return Ok(*SPARSE_MERKLE_PLACEHOLDER_HASH);
+                }
+                let sibling = non_default_siblings
+                    .next()
+                    .ok_or_else(|| format_err!("Bitmap has more bits set than siblings."))?;
                 ensure!(
-                    element_key.common_prefix_bits_len(leaf.key) >= self.siblings.len(),
-                    "Key would not have ended up in the subtree where the provided key in proof \
-                     is the only existing key, if it existed. So this is not a valid \
-                     non-inclusion proof.",
+                    *sibling != *SPARSE_MERKLE_PLACEHOLDER_HASH,
+                    "Default sibling should be left out of compressed proof.",
                 );
-            }
-            (None, None) => {
-                // This is a non-inclusion proof. The proof intends to show that if a leaf node
-                // representing `element_key` is inserted, it will show up at a currently empty
-                // position. `sibling` should prove the route from this empty position to the root.
-            }
-        }
-
-        let current_hash = self
-            .leaf
-            .map_or(*SPARSE_MERKLE_PLACEHOLDER_HASH, |leaf| leaf.hash());
-        let actual_root_hash = self
-            .siblings
-            .iter()
-            .zip(
-                element_key
-                    .iter_bits()
-                    .rev()
-                    .skip(HashValue::LENGTH_IN_BITS - self.siblings.len()),
-            )
-            .fold(current_hash, |hash, (sibling_hash, bit)| {
-                if bit {
-                    SparseMerkleInternalNode::new(*sibling_hash, hash).hash()
-                } else {
-                    SparseMerkleInternalNode::new(hash, *sibling_hash).hash()
-                }
-            });
+                Ok(*sibling)
+            })
+            .collect::<Result<Vec<_>>>()?;
         ensure!(
-            actual_root_hash == expected_root_hash,
-            "Root hashes do not match. Actual root hash: {:x}. Expected root hash: {:x}.",
-            actual_root_hash,
-            expected_root_hash,
+            non_default_siblings.next().is_none(),
+            "Bitmap has fewer bits set than siblings.",
         );
-
-        Ok(())
+        ensure!(
+            num_siblings % 8 == 0
+                || self.bitmap[num_siblings / 8] & (0xff >> (num_siblings % 8)) == 0,
+            "Unused bits of the bitmap are set.",
+        );
+        Ok(siblings)
     }
 }
 
@@ -500,6 +545,42 @@ impl SparseMerkleRangeProof {
     pub fn right_siblings(&self) -> &[HashValue] {
         &self.right_siblings
     }
+
+    /// Returns the proof without its default siblings, see `SparseMerkleProof::compress`.
+    pub fn compress(&self) -> CompressedSparseMerkleRangeProof {
+        self.into()
+    }
+}
+
+/// A `SparseMerkleRangeProof` without its default siblings, see `CompressedSparseMerkleProof`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CompressedSparseMerkleRangeProof {
+    right_siblings: CompressedSiblings,
+}
+
+impl CompressedSparseMerkleRangeProof {
+    /// Restores the full proof, failing if the bitmap does not match the siblings.
+    pub fn decompress(&self) -> Result<SparseMerkleRangeProof> {
+        Ok(SparseMerkleRangeProof::new(
+            self.right_siblings.decompress()?,
+        ))
+    }
+}
+
+impl From<&SparseMerkleRangeProof> for CompressedSparseMerkleRangeProof {
+    fn from(proof: &SparseMerkleRangeProof) -> Self {
+        Self {
+            right_siblings: CompressedSiblings::compress(&proof.right_siblings),
+        }
+    }
+}
+
+impl TryFrom<&CompressedSparseMerkleRangeProof> for SparseMerkleRangeProof {
+    type Error = anyhow::Error;
+
+    fn try_from(proof: &CompressedSparseMerkleRangeProof) -> Result<Self> {
+        proof.decompress()
+    }
 }
 
 /// `TransactionInfo` and a `TransactionAccumulatorProof` connecting it to the ledger root.
@@ -28,11 +28,14 @@ use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
 
+pub use libra_proof_verifier as verifier;
+
 pub use self::definition::{
     AccountStateProof, AccumulatorConsistencyProof, AccumulatorExtensionProof, AccumulatorProof,
-    AccumulatorRangeProof, EventAccumulatorProof, EventProof, SparseMerkleProof,
-    SparseMerkleRangeProof, TransactionAccumulatorProof, TransactionAccumulatorRangeProof,
-    TransactionInfoWithProof, TransactionListProof,
+    AccumulatorRangeProof, CompressedSparseMerkleProof, CompressedSparseMerkleRangeProof,
+    EventAccumulatorProof, EventProof, SparseMerkleProof, SparseMerkleRangeProof,
+    TransactionAccumulatorProof, TransactionAccumulatorRangeProof, TransactionInfoWithProof,
+    TransactionListProof,
 };
 
 #[cfg(any(test, feature = "fuzzing"))]
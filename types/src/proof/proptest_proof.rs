@@ -6,7 +6,8 @@
 
 use crate::proof::{
     definition::MAX_ACCUMULATOR_PROOF_DEPTH, AccumulatorConsistencyProof, AccumulatorProof,
-    AccumulatorRangeProof, SparseMerkleLeafNode, SparseMerkleProof, SparseMerkleRangeProof,
+    AccumulatorRangeProof, CompressedSparseMerkleProof, CompressedSparseMerkleRangeProof,
+    SparseMerkleLeafNode, SparseMerkleProof, SparseMerkleRangeProof,
 };
 use libra_crypto::{
     hash::{CryptoHasher, ACCUMULATOR_PLACEHOLDER_HASH, SPARSE_MERKLE_PLACEHOLDER_HASH},
@@ -97,6 +98,17 @@ impl Arbitrary for SparseMerkleProof {
     }
 }
 
+impl Arbitrary for CompressedSparseMerkleProof {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        any::<SparseMerkleProof>()
+            .prop_map(|proof| proof.compress())
+            .boxed()
+    }
+}
+
 impl Arbitrary for AccumulatorConsistencyProof {
     type Parameters = ();
     type Strategy = BoxedStrategy<Self>;
@@ -143,3 +155,14 @@ impl Arbitrary for SparseMerkleRangeProof {
             .boxed()
     }
 }
+
+impl Arbitrary for CompressedSparseMerkleRangeProof {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        any::<SparseMerkleRangeProof>()
+            .prop_map(|proof| proof.compress())
+            .boxed()
+    }
+}
@@ -2,9 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::proof::{
-    AccountStateProof, AccumulatorConsistencyProof, EventProof, SparseMerkleProof,
-    SparseMerkleRangeProof, TestAccumulatorProof, TestAccumulatorRangeProof,
-    TransactionInfoWithProof, TransactionListProof,
+    AccountStateProof, AccumulatorConsistencyProof, CompressedSparseMerkleProof,
+    CompressedSparseMerkleRangeProof, EventProof, SparseMerkleProof, SparseMerkleRangeProof,
+    TestAccumulatorProof, TestAccumulatorRangeProof, TransactionInfoWithProof,
+    TransactionListProof,
 };
 use lcs::test_helpers::assert_canonical_encode_decode;
 use proptest::prelude::*;
@@ -48,6 +49,32 @@ proptest! {
     }
 
 
+    #[test]
+    fn test_compressed_sparse_merkle_lcs_roundtrip(proof in any::<CompressedSparseMerkleProof>()) {
+        assert_canonical_encode_decode(proof);
+    }
+
+
+    #[test]
+    fn test_compressed_sparse_merkle_range_lcs_roundtrip(
+        proof in any::<CompressedSparseMerkleRangeProof>(),
+    ) {
+        assert_canonical_encode_decode(proof);
+    }
+
+
+    #[test]
+    fn test_sparse_merkle_compression_roundtrip(proof in any::<SparseMerkleProof>()) {
+        prop_assert_eq!(proof.compress().decompress().unwrap(), proof);
+    }
+
+
+    #[test]
+    fn test_sparse_merkle_range_compression_roundtrip(proof in any::<SparseMerkleRangeProof>()) {
+        prop_assert_eq!(proof.compress().decompress().unwrap(), proof);
+    }
+
+
     #[test]
     fn test_transaction_proof_lcs_roundtrip(proof in any::<TransactionInfoWithProof>()) {
         assert_canonical_encode_decode(proof);
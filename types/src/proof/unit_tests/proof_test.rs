@@ -9,9 +9,10 @@ use crate::{
     ledger_info::LedgerInfo,
     proof::{
         definition::MAX_ACCUMULATOR_PROOF_DEPTH, AccountStateProof, AccumulatorExtensionProof,
-        EventAccumulatorInternalNode, EventAccumulatorProof, EventProof, SparseMerkleInternalNode,
-        SparseMerkleLeafNode, SparseMerkleProof, TestAccumulatorInternalNode, TestAccumulatorProof,
-        TransactionAccumulatorInternalNode, TransactionAccumulatorProof, TransactionInfoWithProof,
+        CompressedSparseMerkleProof, EventAccumulatorInternalNode, EventAccumulatorProof,
+        EventProof, SparseMerkleInternalNode, SparseMerkleLeafNode, SparseMerkleProof,
+        TestAccumulatorInternalNode, TestAccumulatorProof, TransactionAccumulatorInternalNode,
+        TransactionAccumulatorProof, TransactionInfoWithProof,
     },
     transaction::{RawTransaction, Script, Transaction, TransactionInfo},
     vm_status::StatusCode,
@@ -24,6 +25,7 @@ use libra_crypto::{
     },
     HashValue, PrivateKey, Uniform,
 };
+use serde::Serialize;
 
 #[test]
 fn test_verify_empty_accumulator() {
@@ -233,6 +235,19 @@ fn test_verify_three_element_sparse_merkle() {
         assert!(proof.verify(root_hash, non_existing_key2, None).is_err());
     }
 
+    {
+        // The compressed proof of key1 leaves out the default sibling.
+        let full_proof = SparseMerkleProof::new(
+            Some(leaf1),
+            vec![internal_b_hash, *SPARSE_MERKLE_PLACEHOLDER_HASH],
+        );
+        let proof = full_proof.compress();
+        assert!(lcs::to_bytes(&proof).unwrap().len() < lcs::to_bytes(&full_proof).unwrap().len());
+        assert!(proof.verify(root_hash, key1, Some(&blob1)).is_ok());
+        assert!(proof.verify(root_hash, key1, Some(&blob2)).is_err());
+        assert!(proof.verify(root_hash, non_existing_key2, None).is_err());
+    }
+
     {
         // Construct a proof of the default node.
         let proof = SparseMerkleProof::new(None, vec![internal_a_hash]);
@@ -502,3 +517,49 @@ fn test_accumulator_extension_proof() {
     let derived_tree_err = two_tree.verify(*ACCUMULATOR_PLACEHOLDER_HASH);
     assert!(derived_tree_err.is_err());
 }
+
+#[test]
+fn test_malformed_compressed_sparse_merkle_proof() {
+    // Same layout as `CompressedSparseMerkleProof`, to build invalid ones.
+    #[derive(Serialize)]
+    struct RawCompressedProof {
+        leaf: Option<SparseMerkleLeafNode>,
+        num_siblings: u16,
+        bitmap: Vec<u8>,
+        non_default_siblings: Vec<HashValue>,
+    }
+    let decompress = |num_siblings, bitmap, non_default_siblings| {
+        let bytes = lcs::to_bytes(&RawCompressedProof {
+            leaf: None,
+            num_siblings,
+            bitmap,
+            non_default_siblings,
+        })
+        .unwrap();
+        lcs::from_bytes::<CompressedSparseMerkleProof>(&bytes)
+            .unwrap()
+            .decompress()
+    };
+    let sibling = HashValue::random();
+
+    let proof = decompress(3, vec![0b0100_0000], vec![sibling]).unwrap();
+    assert_eq!(
+        proof.siblings(),
+        &[
+            *SPARSE_MERKLE_PLACEHOLDER_HASH,
+            sibling,
+            *SPARSE_MERKLE_PLACEHOLDER_HASH
+        ]
+    );
+    // Bitmap of the wrong length.
+    assert!(decompress(9, vec![0b0100_0000], vec![sibling]).is_err());
+    // Too many or too few siblings for the bits set.
+    assert!(decompress(3, vec![0b0100_0000], vec![sibling, sibling]).is_err());
+    assert!(decompress(3, vec![0b1100_0000], vec![sibling]).is_err());
+    // Unused bit set.
+    assert!(decompress(3, vec![0b0101_0000], vec![sibling, sibling]).is_err());
+    // Default sibling not left out.
+    assert!(decompress(3, vec![0b0100_0000], vec![*SPARSE_MERKLE_PLACEHOLDER_HASH]).is_err());
+    // Too many siblings.
+    assert!(decompress(257, vec![0; 33], vec![]).is_err());
+}
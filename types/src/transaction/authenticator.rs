@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::account_address::AccountAddress;
-use anyhow::{Error, Result};
+use anyhow::{bail, ensure, Error, Result};
 use libra_crypto::{
     ed25519::{Ed25519PublicKey, Ed25519Signature},
     hash::CryptoHash,
@@ -60,6 +60,13 @@ pub enum TransactionAuthenticator {
         public_key: MultiEd25519PublicKey,
         signature: MultiEd25519Signature,
     },
+    /// A transaction authorized by its sender and by secondary signers, each of which signs the
+    /// `RawTransactionWithData::MultiAgent` of the transaction
+    MultiAgent {
+        sender: Box<TransactionAuthenticator>,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        secondary_signers: Vec<TransactionAuthenticator>,
+    },
     // ... add more schemes here
 }
 
@@ -69,6 +76,7 @@ impl TransactionAuthenticator {
         match self {
             Self::Ed25519 { .. } => Scheme::Ed25519,
             Self::MultiEd25519 { .. } => Scheme::MultiEd25519,
+            Self::MultiAgent { sender, .. } => sender.scheme(),
         }
     }
 
@@ -91,7 +99,23 @@ impl TransactionAuthenticator {
         }
     }
 
-    /// Return Ok if the authenticator's public key matches its signature, Err otherwise
+    /// Create a multi-agent authenticator from the authenticators of the sender and of the
+    /// secondary signers, in the order of `secondary_signer_addresses`
+    pub fn multi_agent(
+        sender: TransactionAuthenticator,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        secondary_signers: Vec<TransactionAuthenticator>,
+    ) -> Self {
+        Self::MultiAgent {
+            sender: Box::new(sender),
+            secondary_signer_addresses,
+            secondary_signers,
+        }
+    }
+
+    /// Return Ok if the authenticator's public key matches its signature, Err otherwise.
+    /// A multi-agent authenticator must have one signer per secondary signer address, and each of
+    /// its signers must be a single or multi signature authenticator.
     pub fn verify<T: Serialize + CryptoHash>(&self, message: &T) -> Result<()> {
         match self {
             Self::Ed25519 {
@@ -102,22 +126,74 @@ impl TransactionAuthenticator {
                 public_key,
                 signature,
             } => signature.verify(message, public_key),
+            Self::MultiAgent {
+                sender,
+                secondary_signer_addresses,
+                secondary_signers,
+            } => {
+                ensure!(
+                    secondary_signer_addresses.len() == secondary_signers.len(),
+                    "{} secondary signer addresses but {} secondary signers",
+                    secondary_signer_addresses.len(),
+                    secondary_signers.len()
+                );
+                for authenticator in std::iter::once(sender.as_ref()).chain(secondary_signers) {
+                    if let Self::MultiAgent { .. } = authenticator {
+                        bail!("Multi-agent authenticators cannot be nested");
+                    }
+                    authenticator.verify(message)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Return the authenticator of the sender, which is `self` unless it is multi-agent
+    pub fn sender(&self) -> &TransactionAuthenticator {
+        match self {
+            Self::MultiAgent { sender, .. } => sender.as_ref(),
+            _ => self,
+        }
+    }
+
+    /// Return the addresses of the secondary signers, which are empty unless `self` is
+    /// multi-agent
+    pub fn secondary_signer_addresses(&self) -> Vec<AccountAddress> {
+        match self {
+            Self::MultiAgent {
+                secondary_signer_addresses,
+                ..
+            } => secondary_signer_addresses.clone(),
+            _ => vec![],
+        }
+    }
+
+    /// Return the authenticators of the secondary signers, which are empty unless `self` is
+    /// multi-agent
+    pub fn secondary_signers(&self) -> Vec<TransactionAuthenticator> {
+        match self {
+            Self::MultiAgent {
+                secondary_signers, ..
+            } => secondary_signers.clone(),
+            _ => vec![],
         }
     }
 
-    /// Return the raw bytes of `self.public_key`
+    /// Return the raw bytes of `self.public_key`, the sender's one for multi-agent
     pub fn public_key_bytes(&self) -> Vec<u8> {
         match self {
             Self::Ed25519 { public_key, .. } => public_key.to_bytes().to_vec(),
             Self::MultiEd25519 { public_key, .. } => public_key.to_bytes().to_vec(),
+            Self::MultiAgent { sender, .. } => sender.public_key_bytes(),
         }
     }
 
-    /// Return the raw bytes of `self.signature`
+    /// Return the raw bytes of `self.signature`, the sender's one for multi-agent
     pub fn signature_bytes(&self) -> Vec<u8> {
         match self {
             Self::Ed25519 { signature, .. } => signature.to_bytes().to_vec(),
             Self::MultiEd25519 { signature, .. } => signature.to_bytes().to_vec(),
+            Self::MultiAgent { sender, .. } => sender.signature_bytes(),
         }
     }
 
@@ -6,7 +6,10 @@
 //! is using regular subaddressing, is subject to travel rule or corresponds
 //! to an on-chain payment refund.
 
+use crate::account_identifier::Subaddress;
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
 
 /// List of all supported metadata types
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -42,6 +45,40 @@ pub struct GeneralMetadataV0 {
     referenced_event: Option<u64>,
 }
 
+impl GeneralMetadataV0 {
+    pub fn new(
+        to_subaddress: Option<Subaddress>,
+        from_subaddress: Option<Subaddress>,
+        referenced_event: Option<u64>,
+    ) -> Self {
+        Self {
+            to_subaddress: to_subaddress.as_ref().map(Subaddress::to_vec),
+            from_subaddress: from_subaddress.as_ref().map(Subaddress::to_vec),
+            referenced_event,
+        }
+    }
+
+    /// The subaddress the funds are sent to. Fails if the metadata holds an invalid subaddress.
+    pub fn receiver_subaddress(&self) -> Result<Option<Subaddress>> {
+        self.to_subaddress
+            .as_deref()
+            .map(Subaddress::try_from)
+            .transpose()
+    }
+
+    /// The subaddress the funds are sent from. Fails if the metadata holds an invalid subaddress.
+    pub fn sender_subaddress(&self) -> Result<Option<Subaddress>> {
+        self.from_subaddress
+            .as_deref()
+            .map(Subaddress::try_from)
+            .transpose()
+    }
+
+    pub fn referenced_event(&self) -> Option<u64> {
+        self.referenced_event
+    }
+}
+
 /// List of supported transaction metadata format versions for transactions
 /// subject to travel rule
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
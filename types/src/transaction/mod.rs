@@ -17,9 +17,9 @@ use crate::{
 use anyhow::{ensure, format_err, Error, Result};
 use libra_crypto::{
     ed25519::*,
-    hash::{CryptoHash, EventAccumulatorHasher},
+    hash::{CryptoHash, CryptoHasher, EventAccumulatorHasher},
     multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
-    traits::SigningKey,
+    traits::{PrivateKey, SigningKey},
     HashValue,
 };
 use libra_crypto_derive::{CryptoHasher, LCSCryptoHash};
@@ -251,6 +251,43 @@ impl RawTransaction {
         )))
     }
 
+    /// Signs the given `RawTransaction` by its sender and by the secondary signers at
+    /// `secondary_signer_addresses`, whose private keys are `secondary_private_keys` in the same
+    /// order. Each of them signs the `RawTransactionWithData::MultiAgent` of the transaction.
+    pub fn sign_multi_agent(
+        self,
+        sender_private_key: &Ed25519PrivateKey,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        secondary_private_keys: Vec<&Ed25519PrivateKey>,
+    ) -> Result<SignatureCheckedTransaction> {
+        ensure!(
+            secondary_signer_addresses.len() == secondary_private_keys.len(),
+            "{} secondary signer addresses but {} secondary private keys",
+            secondary_signer_addresses.len(),
+            secondary_private_keys.len()
+        );
+        let message = RawTransactionWithData::new_multi_agent(
+            self.clone(),
+            secondary_signer_addresses.clone(),
+        );
+        let authenticator = |private_key: &Ed25519PrivateKey| {
+            TransactionAuthenticator::ed25519(private_key.public_key(), private_key.sign(&message))
+        };
+        let sender = authenticator(sender_private_key);
+        let secondary_signers = secondary_private_keys
+            .into_iter()
+            .map(authenticator)
+            .collect();
+        Ok(SignatureCheckedTransaction(
+            SignedTransaction::new_multi_agent(
+                self,
+                sender,
+                secondary_signer_addresses,
+                secondary_signers,
+            ),
+        ))
+    }
+
     #[cfg(any(test, feature = "fuzzing"))]
     pub fn multi_sign_for_testing(
         self,
@@ -310,6 +347,52 @@ impl RawTransaction {
     pub fn sender(&self) -> AccountAddress {
         self.sender
     }
+
+    /// Return the chain ID of the network this transaction is intended for.
+    pub fn chain_id(&self) -> ChainId {
+        self.chain_id
+    }
+
+    /// Return the exact bytes the sender signs: the domain separation seed of `RawTransaction`
+    /// followed by its LCS serialization. Devices which cannot hash or serialize transactions
+    /// themselves, like hardware wallets, sign these bytes as an arbitrary message.
+    pub fn signing_message(&self) -> Vec<u8> {
+        signing_message(self)
+    }
+}
+
+/// The message signed by every signer of a transaction with secondary signers: the raw
+/// transaction along with the addresses of its secondary signers, so that signatures cannot be
+/// replayed with other signers.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize, CryptoHasher, LCSCryptoHash)]
+pub enum RawTransactionWithData {
+    MultiAgent {
+        raw_txn: RawTransaction,
+        secondary_signer_addresses: Vec<AccountAddress>,
+    },
+}
+
+impl RawTransactionWithData {
+    pub fn new_multi_agent(
+        raw_txn: RawTransaction,
+        secondary_signer_addresses: Vec<AccountAddress>,
+    ) -> Self {
+        Self::MultiAgent {
+            raw_txn,
+            secondary_signer_addresses,
+        }
+    }
+
+    /// Return the exact bytes each signer signs, like `RawTransaction::signing_message`.
+    pub fn signing_message(&self) -> Vec<u8> {
+        signing_message(self)
+    }
+}
+
+fn signing_message<T: CryptoHash + Serialize>(message: &T) -> Vec<u8> {
+    let mut bytes = <T::Hasher as CryptoHasher>::seed().to_vec();
+    lcs::serialize_into(&mut bytes, message).expect("Serialization of transaction should not fail");
+    bytes
 }
 
 #[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
@@ -402,6 +485,25 @@ impl SignedTransaction {
         }
     }
 
+    /// Creates a transaction authorized by its sender and by secondary signers, which must all
+    /// have signed the `RawTransactionWithData::MultiAgent` of `raw_txn`.
+    pub fn new_multi_agent(
+        raw_txn: RawTransaction,
+        sender: TransactionAuthenticator,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        secondary_signers: Vec<TransactionAuthenticator>,
+    ) -> SignedTransaction {
+        let authenticator = TransactionAuthenticator::multi_agent(
+            sender,
+            secondary_signer_addresses,
+            secondary_signers,
+        );
+        SignedTransaction {
+            raw_txn,
+            authenticator,
+        }
+    }
+
     pub fn authenticator(&self) -> TransactionAuthenticator {
         self.authenticator.clone()
     }
@@ -410,6 +512,11 @@ impl SignedTransaction {
         self.raw_txn.sender
     }
 
+    /// Returns the addresses of the accounts which signed the transaction besides its sender.
+    pub fn secondary_signer_addresses(&self) -> Vec<AccountAddress> {
+        self.authenticator.secondary_signer_addresses()
+    }
+
     pub fn into_raw_transaction(self) -> RawTransaction {
         self.raw_txn
     }
@@ -451,10 +558,79 @@ impl SignedTransaction {
     /// Checks that the signature of given transaction. Returns `Ok(SignatureCheckedTransaction)` if
     /// the signature is valid.
     pub fn check_signature(self) -> Result<SignatureCheckedTransaction> {
-        self.authenticator.verify(&self.raw_txn)?;
+        match &self.authenticator {
+            TransactionAuthenticator::MultiAgent {
+                secondary_signer_addresses,
+                ..
+            } => {
+                let message = RawTransactionWithData::new_multi_agent(
+                    self.raw_txn.clone(),
+                    secondary_signer_addresses.clone(),
+                );
+                self.authenticator.verify(&message)?
+            }
+            _ => self.authenticator.verify(&self.raw_txn)?,
+        }
         Ok(SignatureCheckedTransaction(self))
     }
 
+    /// Returns the transaction as a `SignatureCheckedTransaction` without checking its signature.
+    /// Only meant to simulate transactions whose outputs are never committed, e.g. to preflight a
+    /// transaction before it is signed.
+    pub fn into_unchecked_for_simulation(self) -> SignatureCheckedTransaction {
+        SignatureCheckedTransaction(self)
+    }
+
+    /// Checks the signatures of a batch of transactions, returning one result per transaction in
+    /// the same order. The single Ed25519 signatures are verified together as one batch, which is
+    /// much cheaper than checking them one by one. If the batch fails, and for every other kind of
+    /// authenticator, this falls back to `check_signature`.
+    ///
+    /// A valid batch implies that each of its signatures passes `check_signature`, except with
+    /// negligible probability, see `Ed25519Signature::batch_verify_arbitrary_msgs`. The batch may
+    /// however fail on signatures that `check_signature` accepts, which is why every transaction
+    /// is checked on its own in that case. As the batch is not as strict as `check_signature`,
+    /// this is only meant to filter out invalid transactions early: the transactions it accepts
+    /// must be checked again with `check_signature` before they are executed.
+    pub fn check_signatures(
+        txns: Vec<SignedTransaction>,
+    ) -> Vec<Result<SignatureCheckedTransaction>> {
+        let batch_verified = {
+            let ed25519_txns: Vec<_> = txns
+                .iter()
+                .filter_map(|txn| match &txn.authenticator {
+                    TransactionAuthenticator::Ed25519 {
+                        public_key,
+                        signature,
+                    } => Some((txn.raw_txn.signing_message(), public_key, signature)),
+                    _ => None,
+                })
+                .collect();
+            let messages: Vec<&[u8]> = ed25519_txns.iter().map(|(m, _, _)| &m[..]).collect();
+            let public_keys: Vec<_> = ed25519_txns.iter().map(|(_, key, _)| *key).collect();
+            let signatures: Vec<_> = ed25519_txns.iter().map(|(_, _, sig)| *sig).collect();
+            !signatures.is_empty()
+                && Ed25519Signature::batch_verify_arbitrary_msgs(
+                    &messages,
+                    &public_keys,
+                    &signatures,
+                )
+                .is_ok()
+        };
+
+        txns.into_iter()
+            .map(|txn| {
+                if batch_verified
+                    && matches!(txn.authenticator, TransactionAuthenticator::Ed25519 { .. })
+                {
+                    Ok(SignatureCheckedTransaction(txn))
+                } else {
+                    txn.check_signature()
+                }
+            })
+            .collect()
+    }
+
     pub fn format_for_client(&self, get_transaction_name: impl Fn(&[u8]) -> String) -> String {
         format!(
             "SignedTransaction {{ \n \
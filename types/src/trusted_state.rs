@@ -3,16 +3,21 @@
 
 use crate::{
     epoch_change::{EpochChangeProof, Verifier},
+    epoch_state::EpochState,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
     transaction::Version,
     waypoint::Waypoint,
 };
 use anyhow::{ensure, format_err, Result};
-use std::{convert::TryFrom, sync::Arc};
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
 
 /// `TrustedState` keeps track of our latest trusted state, including the latest
 /// verified version and the latest verified validator set.
-#[derive(Clone, Debug)]
+///
+/// A `TrustedState` can be serialized so that clients can persist it and resume
+/// from it after a restart instead of starting over from their waypoint.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct TrustedState {
     /// The latest verified state is from either a waypoint or a ledger info, either
     /// inside an epoch or the epoch change ledger info.
@@ -20,7 +25,45 @@ pub struct TrustedState {
     /// The current verifier. If we're starting up fresh, this is probably a
     /// waypoint from our config. Otherwise, this is generated from the validator
     /// set in the last known epoch change ledger info.
-    verifier: Arc<dyn Verifier>,
+    verifier: TrustedStateVerifier,
+}
+
+/// The verifiers a `TrustedState` can hold.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum TrustedStateVerifier {
+    /// We only know about a waypoint and have not yet seen an epoch change.
+    Waypoint(Waypoint),
+    /// The validator set of the last verified epoch change.
+    EpochState(EpochState),
+}
+
+impl Verifier for TrustedStateVerifier {
+    fn verify(&self, ledger_info: &LedgerInfoWithSignatures) -> Result<()> {
+        match self {
+            TrustedStateVerifier::Waypoint(waypoint) => Verifier::verify(waypoint, ledger_info),
+            TrustedStateVerifier::EpochState(epoch_state) => epoch_state.verify(ledger_info),
+        }
+    }
+
+    fn epoch_change_verification_required(&self, epoch: u64) -> bool {
+        match self {
+            TrustedStateVerifier::Waypoint(waypoint) => {
+                waypoint.epoch_change_verification_required(epoch)
+            }
+            TrustedStateVerifier::EpochState(epoch_state) => {
+                epoch_state.epoch_change_verification_required(epoch)
+            }
+        }
+    }
+
+    fn is_ledger_info_stale(&self, ledger_info: &LedgerInfo) -> bool {
+        match self {
+            TrustedStateVerifier::Waypoint(waypoint) => waypoint.is_ledger_info_stale(ledger_info),
+            TrustedStateVerifier::EpochState(epoch_state) => {
+                epoch_state.is_ledger_info_stale(ledger_info)
+            }
+        }
+    }
 }
 
 /// `TrustedStateChange` is the result of attempting to ratchet to a new trusted
@@ -83,7 +126,7 @@ impl TrustedState {
             .epoch_change_verification_required(latest_li.ledger_info().next_block_epoch())
         {
             // Verify the EpochChangeProof to move us into the latest epoch.
-            let epoch_change_li = epoch_change_proof.verify(self.verifier.as_ref())?;
+            let epoch_change_li = epoch_change_proof.verify(&self.verifier)?;
             let new_epoch_state = epoch_change_li
                 .ledger_info()
                 .next_epoch_state()
@@ -95,7 +138,7 @@ impl TrustedState {
                 })?;
 
             // Verify the latest ledger info inside the latest epoch.
-            let new_verifier = Arc::new(new_epoch_state);
+            let new_verifier = TrustedStateVerifier::EpochState(new_epoch_state);
 
             // If these are the same, then we do not have a LI for the next Epoch and hence there
             // is nothing to verify.
@@ -139,13 +182,43 @@ impl TrustedState {
     pub fn latest_version(&self) -> Version {
         self.verified_state.version()
     }
+
+    /// The epoch of the latest verified validator set, or `None` if we have only
+    /// verified a waypoint so far.
+    pub fn epoch(&self) -> Option<u64> {
+        match &self.verifier {
+            TrustedStateVerifier::Waypoint(_) => None,
+            TrustedStateVerifier::EpochState(epoch_state) => Some(epoch_state.epoch),
+        }
+    }
+
+    /// Returns an error if moving from `self` to `new_state` would go backwards,
+    /// either to an older version or to an older epoch. Epochs are only compared
+    /// when both states have verified a validator set.
+    pub fn ensure_not_regressed_by(&self, new_state: &TrustedState) -> Result<()> {
+        ensure!(
+            new_state.latest_version() >= self.latest_version(),
+            "Trusted state would regress from version {} to {}",
+            self.latest_version(),
+            new_state.latest_version(),
+        );
+        if let (Some(epoch), Some(new_epoch)) = (self.epoch(), new_state.epoch()) {
+            ensure!(
+                new_epoch >= epoch,
+                "Trusted state would regress from epoch {} to {}",
+                epoch,
+                new_epoch,
+            );
+        }
+        Ok(())
+    }
 }
 
 impl From<Waypoint> for TrustedState {
     fn from(waypoint: Waypoint) -> Self {
         Self {
             verified_state: waypoint,
-            verifier: Arc::new(waypoint),
+            verifier: TrustedStateVerifier::Waypoint(waypoint),
         }
     }
 }
@@ -160,7 +233,7 @@ impl TryFrom<&LedgerInfo> for TrustedState {
 
         Ok(Self {
             verified_state: Waypoint::new_epoch_boundary(ledger_info)?,
-            verifier: Arc::new(epoch_state),
+            verifier: TrustedStateVerifier::EpochState(epoch_state),
         })
     }
 }
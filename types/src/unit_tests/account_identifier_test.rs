@@ -0,0 +1,140 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account_address::AccountAddress,
+    account_identifier::{
+        parse_account_address, AccountIdentifier, AccountIdentifierError, AddressNetwork,
+        Subaddress,
+    },
+    chain_id::{ChainId, NamedChain},
+};
+use proptest::prelude::*;
+use std::{convert::TryFrom, str::FromStr};
+
+fn address() -> AccountAddress {
+    AccountAddress::from_str("f72589b71ff4f8d139674a3f7369c69b").unwrap()
+}
+
+#[test]
+fn test_encode_account_identifier() {
+    let mainnet = AccountIdentifier::new(AddressNetwork::Mainnet, address());
+    assert_eq!(
+        mainnet.to_string(),
+        "lbr1p7ujcndcl7nudzwt8fglhx6wxnvqqqqqqqqqqqqqflf8ma"
+    );
+    let testnet = AccountIdentifier::with_subaddress(
+        AddressNetwork::Testnet,
+        address(),
+        Subaddress::from_str("cf64428bdeb62af2").unwrap(),
+    );
+    assert_eq!(
+        testnet.to_string(),
+        "tlb1p7ujcndcl7nudzwt8fglhx6wxn08kgs5tm6mz4usugm707"
+    );
+
+    for identifier in &[mainnet, testnet] {
+        let encoded = identifier.encode();
+        assert_eq!(&AccountIdentifier::decode(&encoded).unwrap(), identifier);
+        assert_eq!(
+            &AccountIdentifier::decode(&encoded.to_uppercase()).unwrap(),
+            identifier
+        );
+    }
+}
+
+#[test]
+fn test_subaddress() {
+    let subaddress = Subaddress::random();
+    assert_eq!(
+        Subaddress::from_str(&subaddress.to_string()).unwrap(),
+        subaddress
+    );
+    assert_eq!(
+        Subaddress::try_from(&subaddress.to_vec()[..]).unwrap(),
+        subaddress
+    );
+    assert_eq!(Subaddress::new([0; Subaddress::LENGTH]), None);
+    assert!(Subaddress::from_str("0000000000000000").is_err());
+    assert!(Subaddress::from_str("cf64428bdeb62a").is_err());
+}
+
+#[test]
+fn test_decode_invalid_account_identifier() {
+    let encoded = "lbr1p7ujcndcl7nudzwt8fglhx6wxnvqqqqqqqqqqqqqflf8ma";
+    for (invalid, error) in vec![
+        (
+            "lbr1p7ujcndcl7nudzwt8fglhx6wxnvqqqqqqqqqqqqqflf8mA".to_string(),
+            AccountIdentifierError::MixedCase,
+        ),
+        (
+            encoded.replace("lbr1", "lbr"),
+            AccountIdentifierError::MissingSeparator,
+        ),
+        (
+            encoded.replace("lbr1", "bc1"),
+            AccountIdentifierError::UnknownPrefix("bc".to_string()),
+        ),
+        (
+            encoded.replace("lbr1", "tlb1"),
+            AccountIdentifierError::InvalidChecksum,
+        ),
+        (
+            encoded.replace("p7uj", "p7uk"),
+            AccountIdentifierError::InvalidChecksum,
+        ),
+        (
+            encoded.replace("p7uj", "p7ub"),
+            AccountIdentifierError::InvalidCharacter('b'),
+        ),
+        (
+            encoded[..encoded.len() - 1].to_string(),
+            AccountIdentifierError::InvalidLength(45),
+        ),
+    ] {
+        assert_eq!(AccountIdentifier::decode(&invalid), Err(error));
+    }
+}
+
+#[test]
+fn test_parse_account_address() {
+    assert_eq!(
+        parse_account_address("f72589b71ff4f8d139674a3f7369c69b").unwrap(),
+        address()
+    );
+    assert_eq!(
+        parse_account_address("tlb1p7ujcndcl7nudzwt8fglhx6wxn08kgs5tm6mz4usugm707").unwrap(),
+        address()
+    );
+    assert!(parse_account_address("tlb1p7ujcndcl7nudzwt8fglhx6wxn08kgs5tm6mz4usugm708").is_err());
+    assert!(parse_account_address("f72589b71ff4f8d139674a3f7369c6").is_err());
+    assert!(parse_account_address("").is_err());
+}
+
+#[test]
+fn test_address_network_from_chain_id() {
+    assert_eq!(
+        AddressNetwork::from_chain_id(ChainId::new(NamedChain::MAINNET as u8)),
+        AddressNetwork::Mainnet
+    );
+    assert_eq!(
+        AddressNetwork::from_chain_id(ChainId::test()),
+        AddressNetwork::Testnet
+    );
+}
+
+proptest! {
+    #[test]
+    fn test_account_identifier_roundtrip(
+        address in any::<AccountAddress>(),
+        subaddress in any::<[u8; 8]>(),
+    ) {
+        for network in &[AddressNetwork::Mainnet, AddressNetwork::Testnet] {
+            let identifier = match Subaddress::new(subaddress) {
+                Some(subaddress) => AccountIdentifier::with_subaddress(*network, address, subaddress),
+                None => AccountIdentifier::new(*network, address),
+            };
+            prop_assert_eq!(AccountIdentifier::decode(&identifier.encode()).unwrap(), identifier);
+        }
+    }
+}
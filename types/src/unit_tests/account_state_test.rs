@@ -0,0 +1,79 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account_address::AccountAddress,
+    account_config::{
+        from_currency_code_string, type_tag_for_currency_code, ChildVASP, DesignatedDealer,
+        ParentVASP, PreburnResource, RoleId, TierInfoResource, COIN1_NAME, COIN2_NAME,
+    },
+    account_state::AccountState,
+    event::{EventHandle, EventKey},
+};
+use move_core_types::move_resource::MoveResource;
+
+#[test]
+fn test_typed_getters() {
+    let coin1 = from_currency_code_string(COIN1_NAME).unwrap();
+    let coin2 = from_currency_code_string(COIN2_NAME).unwrap();
+    let mut account_state = AccountState::default();
+
+    // the resources are read with the layout of their Move counterparts
+    account_state.insert(RoleId::resource_path(), lcs::to_bytes(&2u64).unwrap());
+    account_state.insert(ParentVASP::resource_path(), lcs::to_bytes(&3u64).unwrap());
+    let mint_event_handle =
+        EventHandle::new(EventKey::new_from_address(&AccountAddress::random(), 0), 0);
+    account_state.insert(
+        DesignatedDealer::resource_path(),
+        lcs::to_bytes(&mint_event_handle).unwrap(),
+    );
+    account_state.insert(
+        TierInfoResource::access_path_for(type_tag_for_currency_code(coin1.clone())),
+        lcs::to_bytes(&(1u64, 100u64, vec![500u64, 5_000])).unwrap(),
+    );
+    account_state.insert(
+        PreburnResource::access_path_for(type_tag_for_currency_code(coin2.clone())),
+        lcs::to_bytes(&PreburnResource::new(42)).unwrap(),
+    );
+
+    assert_eq!(account_state.get_role_id().unwrap().unwrap().role_id(), 2);
+    assert_eq!(
+        account_state
+            .get_parent_vasp()
+            .unwrap()
+            .unwrap()
+            .num_children(),
+        3
+    );
+    assert!(account_state.get_child_vasp().unwrap().is_none());
+    assert!(account_state.get_credential().unwrap().is_none());
+    assert_eq!(
+        account_state
+            .get_designated_dealer()
+            .unwrap()
+            .unwrap()
+            .mint_event_handle(),
+        &mint_event_handle
+    );
+    let event_handles = account_state.get_event_handles().unwrap();
+    assert_eq!(event_handles.len(), 1);
+    assert_eq!(event_handles["received_mint_events"], mint_event_handle);
+
+    let currency_codes = vec![coin1.clone(), coin2.clone()];
+    let tier_infos = account_state
+        .get_tier_info_resources(&currency_codes)
+        .unwrap();
+    assert_eq!(tier_infos.len(), 1);
+    let tier_info = &tier_infos[&coin1];
+    assert_eq!(tier_info.window_start(), 1);
+    assert_eq!(tier_info.window_inflow(), 100);
+    assert_eq!(tier_info.tiers(), &[500, 5_000]);
+
+    let preburns = account_state.get_preburn_balances(&currency_codes).unwrap();
+    assert_eq!(preburns.len(), 1);
+    assert_eq!(preburns[&coin2].to_burn(), 42);
+
+    // resources which do not match their layout are reported as errors
+    account_state.insert(ChildVASP::resource_path(), vec![0]);
+    assert!(account_state.get_child_vasp().is_err());
+}
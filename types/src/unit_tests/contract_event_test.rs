@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    account_address::AccountAddress,
     contract_event::{ContractEvent, EventWithProof},
     event::EventKey,
 };
@@ -30,3 +31,14 @@ fn test_event_json_serialize() {
     let contract_event2: ContractEvent = serde_json::from_str(contract_json.as_str()).unwrap();
     assert_eq!(contract_event, contract_event2)
 }
+
+#[test]
+fn test_event_key_derivation() {
+    let address = AccountAddress::random();
+    let event_key = EventKey::new_from_address(&address, 3);
+    assert_eq!(event_key.get_creator_address(), address);
+    assert_eq!(event_key.get_creation_number(), 3);
+    assert!(event_key.is_created_by(&address));
+    assert!(!event_key.is_created_by(&AccountAddress::random()));
+    assert_ne!(event_key, EventKey::new_from_address(&address, 4));
+}
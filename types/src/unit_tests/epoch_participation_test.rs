@@ -0,0 +1,67 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account_address::AccountAddress,
+    block_metadata::BlockMetadata,
+    epoch_participation::{EpochParticipation, ValidatorParticipation},
+};
+use lcs::test_helpers::assert_canonical_encode_decode;
+use libra_crypto::HashValue;
+use proptest::prelude::*;
+
+fn block(round: u64, votes: Vec<AccountAddress>, proposer: AccountAddress) -> BlockMetadata {
+    BlockMetadata::new(HashValue::random(), round, round * 1000, votes, proposer)
+}
+
+#[test]
+fn test_record_blocks() {
+    let a = AccountAddress::new([1; AccountAddress::LENGTH]);
+    let b = AccountAddress::new([2; AccountAddress::LENGTH]);
+    let c = AccountAddress::new([3; AccountAddress::LENGTH]);
+
+    let mut participation = EpochParticipation::new();
+    participation.record_block(&block(1, vec![a, b, c], a));
+    participation.record_block(&block(2, vec![a, b], b));
+    // Round 3 timed out and produced no block, round 4 is a nil block.
+    participation.record_block(&block(4, vec![a, c], AccountAddress::ZERO));
+    participation.record_block(&block(5, vec![b, c], a));
+
+    assert_eq!(participation.num_blocks(), 4);
+    assert_eq!(participation.num_nil_blocks(), 1);
+    assert_eq!(participation.num_skipped_rounds(), 1);
+    assert_eq!(participation.last_round(), 5);
+    assert_eq!(
+        participation.validators().get(&a),
+        Some(&ValidatorParticipation {
+            num_proposals: 2,
+            num_votes: 3,
+        })
+    );
+    assert_eq!(
+        participation.validators().get(&b),
+        Some(&ValidatorParticipation {
+            num_proposals: 1,
+            num_votes: 3,
+        })
+    );
+    assert_eq!(
+        participation.validators().get(&c),
+        Some(&ValidatorParticipation {
+            num_proposals: 0,
+            num_votes: 3,
+        })
+    );
+    assert!(!participation
+        .validators()
+        .contains_key(&AccountAddress::ZERO));
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(20))]
+
+    #[test]
+    fn test_epoch_participation_canonical_serialization(data in any::<EpochParticipation>()) {
+        assert_canonical_encode_decode(data);
+    }
+}
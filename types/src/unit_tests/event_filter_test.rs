@@ -0,0 +1,55 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account_address::AccountAddress, contract_event::ContractEvent, event::EventKey,
+    event_filter::EventFilter,
+};
+use lcs::test_helpers::assert_canonical_encode_decode;
+use move_core_types::language_storage::TypeTag;
+
+#[test]
+fn test_event_filter_matches() {
+    let alice = AccountAddress::random();
+    let bob = AccountAddress::random();
+    let alice_key = EventKey::new_from_address(&alice, 0);
+    let bob_key = EventKey::new_from_address(&bob, 1);
+    let alice_event = ContractEvent::new(alice_key, 0, TypeTag::U64, vec![]);
+    let bob_event = ContractEvent::new(bob_key, 0, TypeTag::Address, vec![]);
+
+    assert!(EventFilter::All.matches(&alice_event));
+    assert!(EventFilter::by_key(alice_key).matches(&alice_event));
+    assert!(!EventFilter::by_key(alice_key).matches(&bob_event));
+    assert!(EventFilter::by_account(bob).matches(&bob_event));
+    assert!(!EventFilter::by_account(bob).matches(&alice_event));
+    assert!(EventFilter::by_type_tag(TypeTag::U64).matches(&alice_event));
+    assert!(!EventFilter::by_type_tag(TypeTag::U64).matches(&bob_event));
+
+    let prefix = bob_key.as_bytes()[..8].to_vec();
+    assert!(EventFilter::by_key_prefix(prefix.clone()).matches(&bob_event));
+    assert!(!EventFilter::by_key_prefix(prefix).matches(&alice_event));
+
+    let filter = EventFilter::by_account(alice).and(EventFilter::by_type_tag(TypeTag::U64));
+    assert!(filter.matches(&alice_event));
+    assert!(!filter.matches(&bob_event));
+
+    let filter = EventFilter::by_key(alice_key).or(EventFilter::by_key(bob_key));
+    assert!(filter.matches(&alice_event));
+    assert!(filter.matches(&bob_event));
+    assert_eq!(filter, EventFilter::any_key(vec![alice_key, bob_key]));
+
+    let filter = EventFilter::by_account(alice).not();
+    assert!(!filter.matches(&alice_event));
+    assert!(filter.matches(&bob_event));
+
+    assert!(EventFilter::And(vec![]).matches(&alice_event));
+    assert!(!EventFilter::any_key(vec![]).matches(&alice_event));
+}
+
+#[test]
+fn test_event_filter_lcs_roundtrip() {
+    let filter = EventFilter::by_account(AccountAddress::random())
+        .and(EventFilter::by_type_tag(TypeTag::Bool))
+        .and(EventFilter::by_key_prefix(vec![1, 2, 3]).not());
+    assert_canonical_encode_decode(filter);
+}
@@ -2,10 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod access_path_test;
+mod account_identifier_test;
+mod account_state_test;
 mod block_metadata_test;
 mod canonical_serialization_examples;
 mod code_debug_fmt_test;
 mod contract_event_test;
+mod epoch_participation_test;
+mod event_filter_test;
 mod transaction_test;
 mod trusted_state_test;
 mod validator_set_test;
@@ -6,14 +6,15 @@ use crate::{
     account_config::LBR_NAME,
     chain_id::ChainId,
     transaction::{
-        RawTransaction, Script, SignedTransaction, Transaction, TransactionInfo,
-        TransactionListWithProof, TransactionPayload, TransactionWithProof,
+        authenticator::TransactionAuthenticator, RawTransaction, RawTransactionWithData, Script,
+        SignedTransaction, Transaction, TransactionInfo, TransactionListWithProof,
+        TransactionPayload, TransactionWithProof,
     },
 };
 use lcs::test_helpers::assert_canonical_encode_decode;
 use libra_crypto::{
     ed25519::{self, Ed25519PrivateKey, Ed25519Signature},
-    PrivateKey, Uniform,
+    PrivateKey, SigningKey, Uniform,
 };
 use proptest::prelude::*;
 use std::convert::TryFrom;
@@ -38,6 +39,164 @@ fn test_invalid_signature() {
         .expect_err("signature checking should fail");
 }
 
+#[test]
+fn test_check_signatures_batch() {
+    let new_txn = |sequence_number| {
+        RawTransaction::new_script(
+            AccountAddress::random(),
+            sequence_number,
+            Script::new(vec![], vec![], vec![]),
+            0,
+            0,
+            LBR_NAME.to_owned(),
+            std::time::Duration::new(0, 0),
+            ChainId::test(),
+        )
+    };
+    let private_key = Ed25519PrivateKey::generate_for_testing();
+    let mut txns: Vec<_> = (0..4)
+        .map(|i| {
+            new_txn(i)
+                .sign(&private_key, private_key.public_key())
+                .unwrap()
+                .into_inner()
+        })
+        .collect();
+    assert!(SignedTransaction::check_signatures(txns.clone())
+        .iter()
+        .all(Result::is_ok));
+
+    // An invalid signature fails the batch, but only its own transaction is rejected.
+    txns.insert(
+        2,
+        SignedTransaction::new(
+            new_txn(4),
+            private_key.public_key(),
+            Ed25519Signature::try_from(&[1u8; 64][..]).unwrap(),
+        ),
+    );
+    let results = SignedTransaction::check_signatures(txns);
+    assert_eq!(results.len(), 5);
+    for (i, result) in results.iter().enumerate() {
+        assert_eq!(result.is_ok(), i != 2);
+    }
+}
+
+#[test]
+fn test_multi_agent_signature() {
+    let raw_txn = RawTransaction::new_script(
+        AccountAddress::random(),
+        0,
+        Script::new(vec![], vec![], vec![]),
+        0,
+        0,
+        LBR_NAME.to_owned(),
+        std::time::Duration::new(0, 0),
+        ChainId::test(),
+    );
+    let sender_key = Ed25519PrivateKey::generate_for_testing();
+    let secondary_key = Ed25519PrivateKey::generate_for_testing();
+    let secondary_address = AccountAddress::random();
+    let txn = raw_txn
+        .clone()
+        .sign_multi_agent(&sender_key, vec![secondary_address], vec![&secondary_key])
+        .unwrap()
+        .into_inner();
+    assert!(matches!(
+        txn.authenticator(),
+        TransactionAuthenticator::MultiAgent { .. }
+    ));
+    assert_eq!(txn.secondary_signer_addresses(), vec![secondary_address]);
+    assert_eq!(
+        txn.authenticator().public_key_bytes(),
+        sender_key.public_key().to_bytes().to_vec()
+    );
+    txn.clone()
+        .check_signature()
+        .expect("signature checking should succeed");
+
+    // the signatures cover the secondary signer addresses
+    let other_address = SignedTransaction::new_multi_agent(
+        raw_txn.clone(),
+        txn.authenticator().sender().clone(),
+        vec![AccountAddress::random()],
+        txn.authenticator().secondary_signers(),
+    );
+    other_address
+        .check_signature()
+        .expect_err("signature checking should fail");
+
+    // each secondary signer address needs a signer
+    let missing_signer = SignedTransaction::new_multi_agent(
+        raw_txn.clone(),
+        txn.authenticator().sender().clone(),
+        vec![secondary_address, AccountAddress::random()],
+        txn.authenticator().secondary_signers(),
+    );
+    missing_signer
+        .check_signature()
+        .expect_err("signature checking should fail");
+
+    // signers cannot be multi-agent themselves
+    let nested = SignedTransaction::new_multi_agent(
+        raw_txn,
+        txn.authenticator(),
+        txn.secondary_signer_addresses(),
+        txn.authenticator().secondary_signers(),
+    );
+    nested
+        .check_signature()
+        .expect_err("signature checking should fail");
+}
+
+#[test]
+fn test_signing_message() {
+    let sender = AccountAddress::random();
+    let raw_txn = |chain_id| {
+        RawTransaction::new_script(
+            sender,
+            0,
+            Script::new(vec![], vec![], vec![]),
+            0,
+            0,
+            LBR_NAME.to_owned(),
+            std::time::Duration::new(0, 0),
+            chain_id,
+        )
+    };
+    let private_key = Ed25519PrivateKey::generate_for_testing();
+
+    // signing the message as arbitrary bytes, like a hardware wallet, yields the same signature
+    let testing_txn = raw_txn(ChainId::test());
+    let signature = private_key.sign_arbitrary_message(&testing_txn.signing_message());
+    assert_eq!(signature, private_key.sign(&testing_txn));
+    SignedTransaction::new(
+        testing_txn.clone(),
+        private_key.public_key(),
+        signature.clone(),
+    )
+    .check_signature()
+    .expect("signature checking should succeed");
+
+    // the chain ID is part of the message, so the signature cannot be replayed on another chain
+    let other_chain_txn = raw_txn(ChainId::new(ChainId::test().id() + 1));
+    assert_ne!(
+        testing_txn.signing_message(),
+        other_chain_txn.signing_message()
+    );
+    SignedTransaction::new(other_chain_txn, private_key.public_key(), signature)
+        .check_signature()
+        .expect_err("signature checking should fail");
+
+    // the signers of a multi-agent transaction sign their message the same way
+    let secondary_addresses = vec![AccountAddress::random()];
+    let message = RawTransactionWithData::new_multi_agent(testing_txn, secondary_addresses);
+    assert_eq!(
+        private_key.sign_arbitrary_message(&message.signing_message()),
+        private_key.sign(&message)
+    );
+}
+
 proptest! {
     #[test]
     fn test_sign_raw_transaction(raw_txn in any::<RawTransaction>(), keypair in ed25519::keypair_strategy()) {
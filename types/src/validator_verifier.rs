@@ -1,7 +1,7 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{account_address::AccountAddress, on_chain_config::ValidatorSet};
+use crate::{account_address::AccountAddress, on_chain_config::ValidatorSet, proof::verifier};
 use anyhow::{ensure, Result};
 use libra_crypto::{
     ed25519::{Ed25519PublicKey, Ed25519Signature},
@@ -89,7 +89,7 @@ impl ValidatorVerifier {
         let quorum_voting_power = if address_to_validator_info.is_empty() {
             0
         } else {
-            total_voting_power * 2 / 3 + 1
+            verifier::default_quorum_voting_power(total_voting_power)
         };
         ValidatorVerifier {
             address_to_validator_info,
@@ -156,6 +156,9 @@ impl ValidatorVerifier {
     /// attached signatures is invalid or it does not correspond to a known author. The latter is to
     /// prevent malicious users from adding arbitrary content to the signature payload that would go
     /// unnoticed.
+    ///
+    /// The signatures are checked with a single batch verification, falling back to verifying
+    /// them one by one only if the batch fails, in order to identify the invalid signature.
     pub fn verify_aggregated_struct_signature<T: CryptoHash + Serialize>(
         &self,
         message: &T,
@@ -163,33 +166,30 @@ impl ValidatorVerifier {
     ) -> std::result::Result<(), VerifyError> {
         self.check_num_of_signatures(aggregated_signature)?;
         self.check_voting_power(aggregated_signature.keys())?;
-        for (author, signature) in aggregated_signature {
-            self.verify(*author, message, &signature.clone())?;
+        let keys_and_signatures = aggregated_signature
+            .iter()
+            .map(|(author, signature)| {
+                self.get_public_key(author)
+                    .map(|public_key| (public_key, signature.clone()))
+                    .ok_or(VerifyError::UnknownAuthor)
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        if Ed25519Signature::batch_verify(message, keys_and_signatures).is_err() {
+            for (author, signature) in aggregated_signature {
+                self.verify(*author, message, signature)?;
+            }
         }
         Ok(())
     }
 
-    /// This function will try batch signature verification and falls back to normal
-    /// iterated verification if batching fails.
+    /// Same as `verify_aggregated_struct_signature`, which already tries batch signature
+    /// verification first and falls back to iterated verification if batching fails.
     pub fn batch_verify_aggregated_signatures<T: CryptoHash + Serialize>(
         &self,
         message: &T,
         aggregated_signature: &BTreeMap<AccountAddress, Ed25519Signature>,
     ) -> std::result::Result<(), VerifyError> {
-        self.check_num_of_signatures(aggregated_signature)?;
-        self.check_voting_power(aggregated_signature.keys())?;
-        let keys_and_signatures: Vec<(Ed25519PublicKey, Ed25519Signature)> = aggregated_signature
-            .iter()
-            .flat_map(|(address, signature)| {
-                let sig = signature.clone();
-                self.get_public_key(&address).map(|pub_key| (pub_key, sig))
-            })
-            .collect();
-        // Fallback is required to identify the source of the problem if batching fails.
-        if Ed25519Signature::batch_verify(message, keys_and_signatures).is_err() {
-            self.verify_aggregated_struct_signature(message, aggregated_signature)?
-        }
-        Ok(())
+        self.verify_aggregated_struct_signature(message, aggregated_signature)
     }
 
     /// Ensure there are not more than the maximum expected signatures (all possible signatures).
@@ -213,22 +213,20 @@ impl ValidatorVerifier {
         &self,
         authors: impl Iterator<Item = &'a AccountAddress>,
     ) -> std::result::Result<(), VerifyError> {
-        // Add voting power for valid accounts, exiting early for unknown authors
-        let mut aggregated_voting_power = 0;
-        for account_address in authors {
-            match self.get_voting_power(&account_address) {
-                Some(voting_power) => aggregated_voting_power += voting_power,
-                None => return Err(VerifyError::UnknownAuthor),
-            }
-        }
-
-        if aggregated_voting_power < self.quorum_voting_power {
-            return Err(VerifyError::TooLittleVotingPower {
-                voting_power: aggregated_voting_power,
-                quorum_voting_power: self.quorum_voting_power,
-            });
-        }
-        Ok(())
+        verifier::check_voting_power(
+            authors.map(|author| self.get_voting_power(author)),
+            self.quorum_voting_power,
+        )
+        .map_err(|e| match e {
+            verifier::Error::TooLittleVotingPower {
+                voting_power,
+                quorum_voting_power,
+            } => VerifyError::TooLittleVotingPower {
+                voting_power,
+                quorum_voting_power,
+            },
+            _ => VerifyError::UnknownAuthor,
+        })
     }
 
     /// Returns the public key for this address.
@@ -485,6 +483,19 @@ mod tests {
             Err(VerifyError::UnknownAuthor)
         );
 
+        // Replace a valid signature with one over a different message; the batch fails and the
+        // fallback reports the invalid signature.
+        author_to_signature_map.remove(&unknown_validator_signer.author());
+        author_to_signature_map.insert(
+            validator_signers[0].author(),
+            validator_signers[0].sign(&TestLibraCrypto("Hello, Libra".to_string())),
+        );
+        assert_eq!(
+            validator_verifier
+                .verify_aggregated_struct_signature(&dummy_struct, &author_to_signature_map),
+            Err(VerifyError::InvalidSignature)
+        );
+
         // Add 4 valid signers only (quorum threshold is NOT met); this will fail.
         author_to_signature_map.clear();
         for validator in validator_signers.iter().take(4) {
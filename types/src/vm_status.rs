@@ -1,5 +1,6 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 pub use move_core_types::vm_status::{
-    convert_prologue_runtime_error, sub_status, AbortLocation, StatusCode, StatusType, VMStatus,
+    convert_prologue_runtime_error, sub_status, AbortFrame, AbortLocation, ErrorCategory,
+    ErrorReason, StatusCode, StatusType, VMStatus,
 };
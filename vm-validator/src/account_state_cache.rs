@@ -0,0 +1,201 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A cache of account states at the latest committed version, shared by mempool and the VM
+//! validator.
+//!
+//! Mempool reads the sequence number of the sender of each incoming transaction and the VM
+//! validator reads its account state again to run the prologue. Senders tend to submit bursts of
+//! transactions, so rather than reading the same account states from storage for each of them,
+//! they are read once per committed version. The cache only holds the states of a single
+//! version: reading at a newer version drops all the entries. When a block is committed, the
+//! cache is primed with the states of the senders of its transactions, which are likely to
+//! submit again.
+
+use anyhow::Result;
+use libra_state_view::{StateView, StateViewId};
+use libra_types::{
+    access_path::AccessPath, account_address::AccountAddress, account_config::AccountResource,
+    account_state::AccountState, account_state_blob::AccountStateBlob, transaction::Version,
+};
+use move_core_types::identifier::Identifier;
+use std::{
+    cell::RefCell,
+    collections::{hash_map::Entry, BTreeMap, HashMap},
+    convert::{TryFrom, TryInto},
+    sync::RwLock,
+};
+use storage_interface::DbReader;
+
+#[cfg(test)]
+#[path = "unit_tests/account_state_cache_test.rs"]
+mod account_state_cache_test;
+
+/// The maximum number of accounts cached for a version. Further accounts are read from storage
+/// until the next version.
+const MAX_CACHED_ACCOUNTS: usize = 100_000;
+
+#[derive(Default)]
+pub struct AccountStateCache {
+    inner: RwLock<CachedVersion>,
+}
+
+#[derive(Default)]
+struct CachedVersion {
+    version: Option<Version>,
+    accounts: HashMap<AccountAddress, Option<AccountStateBlob>>,
+}
+
+impl AccountStateCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the state of `address` at `version`, reading it from `db_reader` unless it is
+    /// cached.
+    pub fn get_account_state(
+        &self,
+        db_reader: &dyn DbReader,
+        address: AccountAddress,
+        version: Version,
+    ) -> Result<Option<AccountStateBlob>> {
+        {
+            let cached = self
+                .inner
+                .read()
+                .expect("account state cache lock poisoned");
+            if cached.version == Some(version) {
+                if let Some(blob) = cached.accounts.get(&address) {
+                    return Ok(blob.clone());
+                }
+            }
+        }
+
+        let (blob, _proof) = db_reader.get_account_state_with_proof_by_version(address, version)?;
+        let mut cached = self
+            .inner
+            .write()
+            .expect("account state cache lock poisoned");
+        if cached
+            .version
+            .map_or(true, |cached_version| cached_version < version)
+        {
+            cached.version = Some(version);
+            cached.accounts.clear();
+        }
+        if cached.version == Some(version) && cached.accounts.len() < MAX_CACHED_ACCOUNTS {
+            cached.accounts.insert(address, blob.clone());
+        }
+        Ok(blob)
+    }
+
+    /// Returns the sequence number of `address` at the latest committed version, 0 if the
+    /// account does not exist.
+    pub fn get_sequence_number(
+        &self,
+        db_reader: &dyn DbReader,
+        address: AccountAddress,
+    ) -> Result<u64> {
+        let (version, _state_root) = db_reader.get_latest_state_root()?;
+        match self.get_account_state(db_reader, address, version)? {
+            Some(blob) => Ok(AccountResource::try_from(&blob)?.sequence_number()),
+            None => Ok(0),
+        }
+    }
+
+    /// Returns the balances of `address` in the currencies it holds among `currency_codes`, at
+    /// the latest committed version.
+    pub fn get_balances(
+        &self,
+        db_reader: &dyn DbReader,
+        address: AccountAddress,
+        currency_codes: &[Identifier],
+    ) -> Result<BTreeMap<Identifier, u64>> {
+        let (version, _state_root) = db_reader.get_latest_state_root()?;
+        match self.get_account_state(db_reader, address, version)? {
+            Some(blob) => Ok(AccountState::try_from(&blob)?
+                .get_balance_resources(currency_codes)?
+                .into_iter()
+                .map(|(currency_code, balance)| (currency_code, balance.coin()))
+                .collect()),
+            None => Ok(BTreeMap::new()),
+        }
+    }
+
+    /// Moves the cache to the latest committed version and reads the states of `addresses` at
+    /// that version.
+    pub fn prime(
+        &self,
+        db_reader: &dyn DbReader,
+        addresses: impl IntoIterator<Item = AccountAddress>,
+    ) -> Result<()> {
+        let (version, _state_root) = db_reader.get_latest_state_root()?;
+        for address in addresses {
+            self.get_account_state(db_reader, address, version)?;
+        }
+        Ok(())
+    }
+}
+
+/// A snapshot of the state committed to storage at a given version, which reads account states
+/// through an [`AccountStateCache`]. Like `DbStateView`, it does not verify what it reads.
+pub struct CachedStateView<'a> {
+    id: StateViewId,
+    db_reader: &'a dyn DbReader,
+    cache: &'a AccountStateCache,
+    version: Version,
+    account_to_state_cache: RefCell<HashMap<AccountAddress, AccountState>>,
+}
+
+impl<'a> CachedStateView<'a> {
+    pub fn new(
+        id: StateViewId,
+        db_reader: &'a dyn DbReader,
+        cache: &'a AccountStateCache,
+        version: Version,
+    ) -> Self {
+        Self {
+            id,
+            db_reader,
+            cache,
+            version,
+            account_to_state_cache: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+impl<'a> StateView for CachedStateView<'a> {
+    fn id(&self) -> StateViewId {
+        self.id
+    }
+
+    fn get(&self, access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
+        let address = access_path.address;
+        let path = &access_path.path;
+        match self.account_to_state_cache.borrow_mut().entry(address) {
+            Entry::Occupied(occupied) => Ok(occupied.get().get(path).cloned()),
+            Entry::Vacant(vacant) => {
+                let blob = self
+                    .cache
+                    .get_account_state(self.db_reader, address, self.version)?;
+                Ok(vacant
+                    .insert(
+                        blob.as_ref()
+                            .map(TryInto::try_into)
+                            .transpose()?
+                            .unwrap_or_default(),
+                    )
+                    .get(path)
+                    .cloned())
+            }
+        }
+    }
+
+    fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
+        access_paths.iter().map(|path| self.get(path)).collect()
+    }
+
+    fn is_genesis(&self) -> bool {
+        false
+    }
+}
@@ -3,5 +3,6 @@
 
 #![forbid(unsafe_code)]
 
+pub mod account_state_cache;
 pub mod mocks;
 pub mod vm_validator;
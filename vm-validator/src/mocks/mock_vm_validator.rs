@@ -78,4 +78,8 @@ impl TransactionValidation for MockVMValidator {
     fn restart(&mut self, _config: OnChainConfigPayload) -> Result<()> {
         unimplemented!();
     }
+
+    fn flush_modules(&mut self) -> Result<()> {
+        Ok(())
+    }
 }
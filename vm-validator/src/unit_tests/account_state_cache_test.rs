@@ -0,0 +1,86 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{account_state_cache::AccountStateCache, vm_validator::get_account_sequence_number};
+use executor::db_bootstrapper::bootstrap_db_if_empty;
+use libra_config::utils::get_genesis_txn;
+use libra_types::{
+    account_address::AccountAddress,
+    account_config::{self, from_currency_code_string, COIN1_NAME, LBR_NAME},
+    account_state::AccountState,
+};
+use libra_vm::LibraVM;
+use libradb::LibraDB;
+use std::convert::TryFrom;
+use storage_interface::{mock::MockDbReader, DbReader, DbReaderWriter};
+
+fn cached_accounts(cache: &AccountStateCache) -> (Option<u64>, usize) {
+    let cached = cache.inner.read().unwrap();
+    (cached.version, cached.accounts.len())
+}
+
+#[test]
+fn test_reads_match_storage() {
+    let (config, _key) = config_builder::test_config();
+    let (db, db_rw) = DbReaderWriter::wrap(LibraDB::new_for_test(&config.storage.dir()));
+    bootstrap_db_if_empty::<LibraVM>(&db_rw, get_genesis_txn(&config).unwrap()).unwrap();
+    let cache = AccountStateCache::new();
+
+    let address = account_config::treasury_compliance_account_address();
+    for _ in 0..2 {
+        assert_eq!(
+            cache.get_sequence_number(db.as_ref(), address).unwrap(),
+            get_account_sequence_number(db.as_ref(), address).unwrap(),
+        );
+    }
+
+    let currency_codes = vec![
+        from_currency_code_string(LBR_NAME).unwrap(),
+        from_currency_code_string(COIN1_NAME).unwrap(),
+    ];
+    let expected_balances = AccountState::try_from(
+        &db.get_latest_account_state(address)
+            .unwrap()
+            .expect("account must exist"),
+    )
+    .unwrap()
+    .get_balance_resources(&currency_codes)
+    .unwrap()
+    .into_iter()
+    .map(|(currency_code, balance)| (currency_code, balance.coin()))
+    .collect();
+    assert_eq!(
+        cache
+            .get_balances(db.as_ref(), address, &currency_codes)
+            .unwrap(),
+        expected_balances,
+    );
+
+    // accounts which do not exist are cached as well
+    let missing = AccountAddress::random();
+    assert_eq!(cache.get_sequence_number(db.as_ref(), missing).unwrap(), 0);
+    assert!(cache
+        .get_balances(db.as_ref(), missing, &currency_codes)
+        .unwrap()
+        .is_empty());
+    assert_eq!(cached_accounts(&cache), (Some(0), 2));
+}
+
+#[test]
+fn test_entries_are_dropped_on_newer_version() {
+    let db = MockDbReader;
+    let cache = AccountStateCache::new();
+    let first = AccountAddress::random();
+    let second = AccountAddress::random();
+
+    cache.prime(&db, vec![first, second, first]).unwrap();
+    assert_eq!(cached_accounts(&cache), (Some(0), 2));
+
+    // reading at a newer version moves the cache to it
+    assert!(cache.get_account_state(&db, first, 1).unwrap().is_some());
+    assert_eq!(cached_accounts(&cache), (Some(1), 1));
+
+    // reading at an older version goes to storage without caching
+    assert!(cache.get_account_state(&db, second, 0).unwrap().is_some());
+    assert_eq!(cached_accounts(&cache), (Some(1), 1));
+}
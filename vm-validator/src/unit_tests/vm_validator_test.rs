@@ -1,7 +1,10 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::vm_validator::{TransactionValidation, VMValidator};
+use crate::{
+    account_state_cache::AccountStateCache,
+    vm_validator::{TransactionValidation, VMValidator},
+};
 use executor::db_bootstrapper::bootstrap_db_if_empty;
 use libra_config::{config::NodeConfig, utils::get_genesis_txn};
 use libra_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
@@ -15,7 +18,7 @@ use libra_types::{
 use libra_vm::LibraVM;
 use libradb::LibraDB;
 use rand::SeedableRng;
-use std::u64;
+use std::{sync::Arc, u64};
 use storage_interface::DbReaderWriter;
 use transaction_builder::encode_peer_to_peer_with_metadata_script;
 
@@ -31,7 +34,7 @@ impl TestValidator {
 
         // Create another client for the vm_validator since the one used for the executor will be
         // run on another runtime which will be dropped before this function returns.
-        let vm_validator = VMValidator::new(db);
+        let vm_validator = VMValidator::new(db, Arc::new(AccountStateCache::new()));
         TestValidator { vm_validator }
     }
 }
@@ -105,6 +108,49 @@ fn test_validate_invalid_signature() {
     );
 }
 
+#[test]
+fn test_validate_transactions() {
+    let (config, key) = config_builder::test_config();
+    let vm_validator = TestValidator::new(&config);
+
+    let mut rng = ::rand::rngs::StdRng::from_seed([1u8; 32]);
+    let other_private_key = Ed25519PrivateKey::generate(&mut rng);
+
+    // Every other transaction is signed with a different private key than its public key.
+    let address = account_config::libra_root_address();
+    let transactions = (0..200)
+        .map(|i| {
+            let program = encode_peer_to_peer_with_metadata_script(
+                lbr_type_tag(),
+                address,
+                100,
+                vec![],
+                vec![],
+            );
+            let private_key = if i % 2 == 0 { &key } else { &other_private_key };
+            transaction_test_helpers::get_test_unchecked_txn(
+                address,
+                1,
+                private_key,
+                key.public_key(),
+                Some(program),
+            )
+        })
+        .collect();
+    let ret = vm_validator.validate_transactions(transactions).unwrap();
+    assert_eq!(ret.len(), 200);
+    for (i, result) in ret.iter().enumerate() {
+        if i % 2 == 0 {
+            assert_eq!(result.status(), None);
+        } else {
+            assert_eq!(
+                result.status().unwrap().status_code(),
+                StatusCode::INVALID_SIGNATURE
+            );
+        }
+    }
+}
+
 #[test]
 fn test_validate_known_script_too_large_args() {
     let (config, key) = config_builder::test_config();
@@ -1,12 +1,13 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::account_state_cache::{AccountStateCache, CachedStateView};
 use anyhow::Result;
 use libra_state_view::StateViewId;
 use libra_types::{
     account_address::AccountAddress,
     account_config::AccountResource,
-    on_chain_config::{LibraVersion, OnChainConfigPayload, VMConfig},
+    on_chain_config::{LibraVersion, OnChainConfigPayload, VMConfig, VMPublishingOption},
     transaction::{SignedTransaction, VMValidatorResult},
 };
 use libra_vm::LibraVMValidator;
@@ -24,30 +25,56 @@ pub trait TransactionValidation: Send + Sync + Clone {
     /// Validate a txn from client
     fn validate_transaction(&self, _txn: SignedTransaction) -> Result<VMValidatorResult>;
 
+    /// Validate a batch of txns, returning one result per txn in the same order
+    fn validate_transactions(
+        &self,
+        txns: Vec<SignedTransaction>,
+    ) -> Result<Vec<VMValidatorResult>> {
+        txns.into_iter()
+            .map(|txn| self.validate_transaction(txn))
+            .collect()
+    }
+
     /// Restart the transaction validation instance
     fn restart(&mut self, config: OnChainConfigPayload) -> Result<()>;
+
+    /// Drop the modules cached by the transaction validation instance, after committed
+    /// transactions published or republished modules
+    fn flush_modules(&mut self) -> Result<()>;
 }
 
 #[derive(Clone)]
 pub struct VMValidator {
     db_reader: Arc<dyn DbReader>,
+    account_cache: Arc<AccountStateCache>,
     vm: LibraVMValidator,
 }
 
 impl VMValidator {
-    pub fn new(db_reader: Arc<dyn DbReader>) -> Self {
-        let (version, state_root) = db_reader.get_latest_state_root().expect("Should not fail.");
+    /// Creates a validator reading account states through `account_cache`, which may be shared
+    /// with mempool.
+    pub fn new(db_reader: Arc<dyn DbReader>, account_cache: Arc<AccountStateCache>) -> Self {
+        let vm = Self::new_vm(&db_reader).expect("Should not fail.");
+        VMValidator {
+            db_reader,
+            account_cache,
+            vm,
+        }
+    }
+
+    /// Creates a VM with the configs at the latest version of `db_reader`.
+    fn new_vm(db_reader: &Arc<dyn DbReader>) -> Result<LibraVMValidator> {
+        let (version, state_root) = db_reader.get_latest_state_root()?;
         let smt = SparseMerkleTree::new(state_root);
         let state_view = VerifiedStateView::new(
             StateViewId::Miscellaneous,
-            Arc::clone(&db_reader),
+            Arc::clone(db_reader),
             Some(version),
             state_root,
             &smt,
         );
 
-        let vm = LibraVMValidator::new(&state_view);
-        VMValidator { db_reader, vm }
+        Ok(LibraVMValidator::new(&state_view))
     }
 }
 
@@ -57,29 +84,52 @@ impl TransactionValidation for VMValidator {
     fn validate_transaction(&self, txn: SignedTransaction) -> Result<VMValidatorResult> {
         use libra_vm::VMValidator;
 
-        let (version, state_root) = self.db_reader.get_latest_state_root()?;
-        let db_reader = Arc::clone(&self.db_reader);
+        let (version, _state_root) = self.db_reader.get_latest_state_root()?;
         let vm = self.vm.clone();
 
-        let smt = SparseMerkleTree::new(state_root);
-        let state_view = VerifiedStateView::new(
+        let state_view = CachedStateView::new(
             StateViewId::TransactionValidation {
                 base_version: version,
             },
-            db_reader,
-            Some(version),
-            state_root,
-            &smt,
+            self.db_reader.as_ref(),
+            &self.account_cache,
+            version,
         );
 
         Ok(vm.validate_transaction(txn, &state_view))
     }
 
+    /// Validates the txns against the same state, verifying their signatures in parallel.
+    fn validate_transactions(
+        &self,
+        txns: Vec<SignedTransaction>,
+    ) -> Result<Vec<VMValidatorResult>> {
+        let (version, _state_root) = self.db_reader.get_latest_state_root()?;
+        let state_view = CachedStateView::new(
+            StateViewId::TransactionValidation {
+                base_version: version,
+            },
+            self.db_reader.as_ref(),
+            &self.account_cache,
+            version,
+        );
+
+        Ok(self.vm.validate_transactions(txns, &state_view))
+    }
+
     fn restart(&mut self, config: OnChainConfigPayload) -> Result<()> {
         let vm_config = config.get::<VMConfig>()?;
+        let publishing_option = config.get::<VMPublishingOption>()?;
         let version = config.get::<LibraVersion>()?;
 
-        self.vm = LibraVMValidator::init_with_config(version, vm_config);
+        self.vm = LibraVMValidator::init_with_config(version, vm_config, publishing_option);
+        Ok(())
+    }
+
+    fn flush_modules(&mut self) -> Result<()> {
+        // The loader of the VM caches the modules it loads for its whole lifetime, so only a new
+        // VM sees the republished ones.
+        self.vm = Self::new_vm(&self.db_reader)?;
         Ok(())
     }
 }